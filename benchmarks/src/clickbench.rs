@@ -19,7 +19,7 @@ use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 
-use crate::util::{BenchmarkRun, CommonOpt, QueryResult, print_memory_stats};
+use crate::util::{BenchmarkRun, CommonOpt, QueryResult, plan_hash, print_memory_stats};
 use clap::Args;
 use datafusion::logical_expr::{ExplainFormat, ExplainOption};
 use datafusion::{
@@ -227,7 +227,8 @@ impl RunOpt {
             benchmark_run.start_new_case(&format!("Query {query_id}"));
             let query_run = self.benchmark_query(&sql, query_id, &ctx).await;
             match query_run {
-                Ok(query_results) => {
+                Ok((query_results, plan_hash)) => {
+                    benchmark_run.set_plan_hash(plan_hash);
                     for iter in query_results {
                         benchmark_run.write_iter(iter.elapsed, iter.row_count);
                     }
@@ -248,9 +249,16 @@ impl RunOpt {
         sql: &str,
         query_id: usize,
         ctx: &SessionContext,
-    ) -> Result<Vec<QueryResult>> {
+    ) -> Result<(Vec<QueryResult>, String)> {
         println!("Q{query_id}: {sql}");
 
+        for _ in 0..self.common.warmup {
+            ctx.sql(sql).await?.collect().await?;
+        }
+
+        let plan_hash =
+            plan_hash(ctx.sql(sql).await?.create_physical_plan().await?.as_ref());
+
         let mut millis = Vec::with_capacity(self.iterations());
         let mut query_results = vec![];
         for i in 0..self.iterations() {
@@ -280,7 +288,7 @@ impl RunOpt {
         // Print memory usage stats using mimalloc (only when compiled with --features mimalloc_extended)
         print_memory_stats();
 
-        Ok(query_results)
+        Ok((query_results, plan_hash))
     }
 
     /// Registers the `hits.parquet` as a table named `hits`
@@ -517,6 +517,7 @@ mod tests {
         let path = get_imdb_data_path()?;
         let common = CommonOpt {
             iterations: 1,
+            warmup: 0,
             partitions: Some(2),
             batch_size: Some(8192),
             mem_pool_type: "fair".to_string(),
@@ -555,6 +556,7 @@ mod tests {
         let path = get_imdb_data_path()?;
         let common = CommonOpt {
             iterations: 1,
+            warmup: 0,
             partitions: Some(2),
             batch_size: Some(8192),
             mem_pool_type: "fair".to_string(),
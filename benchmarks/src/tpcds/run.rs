@@ -19,7 +19,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::util::{BenchmarkRun, CommonOpt, QueryResult, print_memory_stats};
+use crate::util::{BenchmarkRun, CommonOpt, QueryResult, plan_hash, print_memory_stats};
 
 use arrow::record_batch::RecordBatch;
 use arrow::util::pretty::{self, pretty_format_batches};
@@ -177,7 +177,8 @@ impl RunOpt {
             benchmark_run.start_new_case(&format!("Query {query_id}"));
             let query_run = self.benchmark_query(query_id, &ctx).await;
             match query_run {
-                Ok(query_results) => {
+                Ok((query_results, plan_hash)) => {
+                    benchmark_run.set_plan_hash(plan_hash);
                     for iter in query_results {
                         benchmark_run.write_iter(iter.elapsed, iter.row_count);
                     }
@@ -197,7 +198,7 @@ impl RunOpt {
         &self,
         query_id: usize,
         ctx: &SessionContext,
-    ) -> Result<Vec<QueryResult>> {
+    ) -> Result<(Vec<QueryResult>, String)> {
         let mut millis = vec![];
         // run benchmark
         let mut query_results = vec![];
@@ -208,6 +209,13 @@ impl RunOpt {
             println!("=== SQL for query {query_id} ===\n{}\n", sql.join(";\n"));
         }
 
+        for _ in 0..self.common.warmup {
+            for query in sql {
+                self.execute_query(ctx, query).await?;
+            }
+        }
+
+        let mut plan_hash = String::new();
         for i in 0..self.iterations() {
             let start = Instant::now();
 
@@ -216,7 +224,7 @@ impl RunOpt {
             let mut result = vec![];
 
             for query in sql {
-                result = self.execute_query(ctx, query).await?;
+                (result, plan_hash) = self.execute_query(ctx, query).await?;
             }
 
             let elapsed = start.elapsed();
@@ -236,7 +244,7 @@ impl RunOpt {
         // Print memory stats using mimalloc (only when compiled with --features mimalloc_extended)
         print_memory_stats();
 
-        Ok(query_results)
+        Ok((query_results, plan_hash))
     }
 
     async fn register_tables(&self, ctx: &SessionContext) -> Result<()> {
@@ -266,7 +274,7 @@ impl RunOpt {
         &self,
         ctx: &SessionContext,
         sql: &str,
-    ) -> Result<Vec<RecordBatch>> {
+    ) -> Result<(Vec<RecordBatch>, String)> {
         let debug = self.common.debug;
         let plan = ctx.sql(sql).await?;
         let (state, plan) = plan.into_parts();
@@ -299,7 +307,7 @@ impl RunOpt {
                 pretty::print_batches(&result)?;
             }
         }
-        Ok(result)
+        Ok((result, plan_hash(physical_plan.as_ref())))
     }
 
     async fn get_table(
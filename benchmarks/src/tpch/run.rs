@@ -22,7 +22,7 @@ use super::{
     TPCH_QUERY_END_ID, TPCH_QUERY_START_ID, TPCH_TABLES, get_query_sql_for_scale_factor,
     get_tbl_tpch_table_schema, get_tpch_table_schema,
 };
-use crate::util::{BenchmarkRun, CommonOpt, QueryResult, print_memory_stats};
+use crate::util::{BenchmarkRun, CommonOpt, QueryResult, plan_hash, print_memory_stats};
 
 use arrow::record_batch::RecordBatch;
 use arrow::util::pretty::{self, pretty_format_batches};
@@ -145,7 +145,8 @@ impl RunOpt {
             benchmark_run.start_new_case(&format!("Query {query_id}"));
             let query_run = self.benchmark_query(query_id, scale_factor, &ctx).await;
             match query_run {
-                Ok(query_results) => {
+                Ok((query_results, plan_hash)) => {
+                    benchmark_run.set_plan_hash(plan_hash);
                     for iter in query_results {
                         benchmark_run.write_iter(iter.elapsed, iter.row_count);
                     }
@@ -166,32 +167,22 @@ impl RunOpt {
         query_id: usize,
         scale_factor: f64,
         ctx: &SessionContext,
-    ) -> Result<Vec<QueryResult>> {
+    ) -> Result<(Vec<QueryResult>, String)> {
         let mut millis = vec![];
         // run benchmark
         let mut query_results = vec![];
 
         let sql = &get_query_sql_for_scale_factor(query_id, scale_factor)?;
 
+        for _ in 0..self.common.warmup {
+            self.execute_statements(query_id, sql, ctx).await?;
+        }
+
+        let mut plan_hash = String::new();
         for i in 0..self.iterations() {
             let start = Instant::now();
-
-            // query 15 is special, with 3 statements. the second statement is the one from which we
-            // want to capture the results
-            let mut result = vec![];
-            if query_id == 15 {
-                for (n, query) in sql.iter().enumerate() {
-                    if n == 1 {
-                        result = self.execute_query(ctx, query).await?;
-                    } else {
-                        self.execute_query(ctx, query).await?;
-                    }
-                }
-            } else {
-                for query in sql {
-                    result = self.execute_query(ctx, query).await?;
-                }
-            }
+            let result;
+            (result, plan_hash) = self.execute_statements(query_id, sql, ctx).await?;
 
             let elapsed = start.elapsed();
             let ms = elapsed.as_secs_f64() * 1000.0;
@@ -210,7 +201,34 @@ impl RunOpt {
         // Print memory stats using mimalloc (only when compiled with --features mimalloc_extended)
         print_memory_stats();
 
-        Ok(query_results)
+        Ok((query_results, plan_hash))
+    }
+
+    /// Execute all statements of a query, returning the results and the
+    /// [`plan_hash`] of the statement whose output is reported
+    async fn execute_statements(
+        &self,
+        query_id: usize,
+        sql: &[String],
+        ctx: &SessionContext,
+    ) -> Result<(Vec<RecordBatch>, String)> {
+        // query 15 is special, with 3 statements. the second statement is the one from which we
+        // want to capture the results
+        let mut output = (vec![], String::new());
+        if query_id == 15 {
+            for (n, query) in sql.iter().enumerate() {
+                if n == 1 {
+                    output = self.execute_query(ctx, query).await?;
+                } else {
+                    self.execute_query(ctx, query).await?;
+                }
+            }
+        } else {
+            for query in sql {
+                output = self.execute_query(ctx, query).await?;
+            }
+        }
+        Ok(output)
     }
 
     async fn register_tables(&self, ctx: &SessionContext) -> Result<()> {
@@ -240,7 +258,7 @@ impl RunOpt {
         &self,
         ctx: &SessionContext,
         sql: &str,
-    ) -> Result<Vec<RecordBatch>> {
+    ) -> Result<(Vec<RecordBatch>, String)> {
         let debug = self.common.debug;
         let plan = ctx.sql(sql).await?;
         let (state, plan) = plan.into_parts();
@@ -273,7 +291,7 @@ impl RunOpt {
                 pretty::print_batches(&result)?;
             }
         }
-        Ok(result)
+        Ok((result, plan_hash(physical_plan.as_ref())))
     }
 
     async fn get_table(
@@ -464,6 +482,7 @@ mod tests {
         let path = get_tpch_data_path()?;
         let common = CommonOpt {
             iterations: 1,
+            warmup: 0,
             partitions: Some(2),
             batch_size: Some(8192),
             mem_pool_type: "fair".to_string(),
@@ -505,6 +524,7 @@ mod tests {
         let path = get_tpch_data_path()?;
         let common = CommonOpt {
             iterations: 1,
+            warmup: 0,
             partitions: Some(2),
             batch_size: Some(8192),
             mem_pool_type: "fair".to_string(),
@@ -23,4 +23,4 @@ mod run;
 
 pub use memory::print_memory_stats;
 pub use options::CommonOpt;
-pub use run::{BenchQuery, BenchmarkRun, QueryResult};
+pub use run::{BenchQuery, BenchmarkRun, QueryResult, plan_hash};
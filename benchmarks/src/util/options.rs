@@ -40,6 +40,10 @@ pub struct CommonOpt {
     #[arg(short = 'i', long = "iterations", default_value = "3", env)]
     pub iterations: usize,
 
+    /// Number of untimed warmup runs of each query before the timed iterations
+    #[arg(long = "warmup", default_value = "0", env)]
+    pub warmup: usize,
+
     /// Number of partitions to process in parallel. Defaults to number of available cores.
     #[arg(short = 'n', long = "partitions", env)]
     pub partitions: Option<usize>,
@@ -183,6 +187,7 @@ mod tests {
         // when no CLI --memory-limit is provided
         let opt = CommonOpt {
             iterations: 3,
+            warmup: 0,
             partitions: None,
             batch_size: None,
             mem_pool_type: "fair".to_string(),
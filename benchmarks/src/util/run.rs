@@ -15,12 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use datafusion::physical_plan::ExecutionPlan;
 use datafusion::{DATAFUSION_VERSION, error::Result};
 use datafusion_common::utils::get_available_parallelism;
 use serde::{Serialize, Serializer};
 use serde_json::Value;
 use std::{
     collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
     path::Path,
     time::{Duration, SystemTime},
 };
@@ -76,6 +78,25 @@ impl RunContext {
     }
 }
 
+/// Returns a short hex digest of the shape of a physical plan.
+///
+/// Only the operator names and their nesting are hashed, so the digest does
+/// not depend on file paths or statistics and can be compared between runs on
+/// different machines to spot plan changes.
+pub fn plan_hash(plan: &dyn ExecutionPlan) -> String {
+    fn visit(plan: &dyn ExecutionPlan, depth: usize, hasher: &mut DefaultHasher) {
+        depth.hash(hasher);
+        plan.name().hash(hasher);
+        for child in plan.children() {
+            visit(child.as_ref(), depth + 1, hasher);
+        }
+    }
+
+    let mut hasher = DefaultHasher::new();
+    visit(plan, 0, &mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
 /// A single iteration of a benchmark query
 #[derive(Debug, Serialize)]
 struct QueryIter {
@@ -91,6 +112,9 @@ pub struct BenchQuery {
     #[serde(serialize_with = "serialize_start_time")]
     start_time: SystemTime,
     success: bool,
+    /// Hash of the physical plan shape, see [`plan_hash`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plan_hash: Option<String>,
 }
 /// Internal representation of a single benchmark query iteration result.
 pub struct QueryResult {
@@ -126,6 +150,7 @@ impl BenchmarkRun {
             iterations: vec![],
             start_time: SystemTime::now(),
             success: true,
+            plan_hash: None,
         });
         if let Some(c) = self.current_case.as_mut() {
             *c += 1;
@@ -144,6 +169,15 @@ impl BenchmarkRun {
         }
     }
 
+    /// Record the [`plan_hash`] of the current case
+    pub fn set_plan_hash(&mut self, plan_hash: String) {
+        if let Some(idx) = self.current_case {
+            self.queries[idx].plan_hash = Some(plan_hash);
+        } else {
+            panic!("no cases existed yet");
+        }
+    }
+
     /// Print the names of failed queries, if any
     pub fn maybe_print_failures(&self) {
         let failed_queries: Vec<&str> = self
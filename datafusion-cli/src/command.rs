@@ -27,7 +27,7 @@ use datafusion::arrow::array::{ArrayRef, StringArray};
 use datafusion::arrow::datatypes::{DataType, Field, Schema};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::instant::Instant;
-use datafusion::common::{exec_datafusion_err, exec_err};
+use datafusion::common::{ScalarValue, exec_datafusion_err, exec_err};
 use datafusion::error::Result;
 use std::fs::File;
 use std::io::BufReader;
@@ -47,6 +47,7 @@ pub enum Command {
     QuietMode(Option<bool>),
     OutputFormat(Option<String>),
     ObjectStoreProfileMode(Option<String>),
+    Bind(Option<String>),
 }
 
 pub enum OutputFormat {
@@ -146,6 +147,9 @@ impl Command {
 
                 Ok(())
             }
+            Self::Bind(_) => {
+                exec_err!("Unexpected bind, this should be handled outside")
+            }
         }
     }
 
@@ -168,11 +172,15 @@ impl Command {
                 "\\object_store_profiling (disabled|summary|trace)",
                 "print or set object store profile mode",
             ),
+            Self::Bind(_) => (
+                "\\bind [value ...]",
+                "bind values to the parameters ($1, $2, ...) of the next query",
+            ),
         }
     }
 }
 
-const ALL_COMMANDS: [Command; 10] = [
+const ALL_COMMANDS: [Command; 11] = [
     Command::ListTables,
     Command::DescribeTableStmt(String::new()),
     Command::Quit,
@@ -183,6 +191,7 @@ const ALL_COMMANDS: [Command; 10] = [
     Command::QuietMode(None),
     Command::OutputFormat(None),
     Command::ObjectStoreProfileMode(None),
+    Command::Bind(None),
 ];
 
 fn all_commands_info() -> RecordBatch {
@@ -237,11 +246,65 @@ impl FromStr for Command {
                 Self::ObjectStoreProfileMode(Some(mode.to_string()))
             }
             ("object_store_profiling", None) => Self::ObjectStoreProfileMode(None),
+            ("bind", values) => Self::Bind(values.map(str::to_owned)),
             _ => return Err(()),
         })
     }
 }
 
+/// Parses the arguments of `\bind` into the values of the parameters `$1`,
+/// `$2`, ... of the next query.
+///
+/// Arguments are separated by whitespace. Quoted arguments (`'it''s'`) are
+/// strings, and unquoted arguments are integers, floats, booleans, `null`, or
+/// strings otherwise.
+pub fn parse_bind_values(args: &str) -> Result<Vec<ScalarValue>> {
+    let mut values = vec![];
+    let mut chars = args.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            return Ok(values);
+        };
+
+        if first == '\'' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some('\'') if chars.next_if_eq(&'\'').is_some() => value.push('\''),
+                    Some('\'') => break,
+                    Some(c) => value.push(c),
+                    None => return exec_err!("Unterminated quoted value in: {args}"),
+                }
+            }
+            values.push(ScalarValue::Utf8(Some(value)));
+        } else {
+            let mut value = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                value.push(c);
+            }
+            values.push(parse_unquoted_bind_value(value));
+        }
+    }
+}
+
+fn parse_unquoted_bind_value(value: String) -> ScalarValue {
+    if let Ok(v) = value.parse::<i64>() {
+        ScalarValue::Int64(Some(v))
+    } else if let Ok(v) = value.parse::<f64>() {
+        ScalarValue::Float64(Some(v))
+    } else if value.eq_ignore_ascii_case("true") {
+        ScalarValue::Boolean(Some(true))
+    } else if value.eq_ignore_ascii_case("false") {
+        ScalarValue::Boolean(Some(false))
+    } else if value.eq_ignore_ascii_case("null") {
+        ScalarValue::Null
+    } else {
+        ScalarValue::Utf8(Some(value))
+    }
+}
+
 impl FromStr for OutputFormat {
     type Err = ();
 
@@ -335,4 +398,27 @@ mod tests {
             .expect("expected parse to succeed");
         assert!(cmd.execute(&ctx, &mut print_options).await.is_err());
     }
+
+    #[test]
+    fn parse_bind_command_values() -> Result<()> {
+        let Ok(Command::Bind(Some(args))) =
+            "bind 1 2.5 true NULL abc 'it''s a b'".parse()
+        else {
+            panic!("expected a bind command with arguments");
+        };
+        assert_eq!(
+            parse_bind_values(&args)?,
+            vec![
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Float64(Some(2.5)),
+                ScalarValue::Boolean(Some(true)),
+                ScalarValue::Null,
+                ScalarValue::Utf8(Some("abc".to_string())),
+                ScalarValue::Utf8(Some("it's a b".to_string())),
+            ]
+        );
+        assert!(matches!("bind".parse(), Ok(Command::Bind(None))));
+        assert!(parse_bind_values("'abc").is_err());
+        Ok(())
+    }
 }
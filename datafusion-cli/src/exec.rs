@@ -21,19 +21,21 @@ use crate::cli_context::CliSessionContext;
 use crate::helper::split_from_semicolon;
 use crate::print_format::PrintFormat;
 use crate::{
-    command::{Command, OutputFormat},
+    command::{Command, OutputFormat, parse_bind_values},
     helper::CliHelper,
     object_storage::get_object_store,
     print_options::{MaxRows, PrintOptions},
 };
+use datafusion::common::diagnostic::WarningCollector;
 use datafusion::common::instant::Instant;
-use datafusion::common::{plan_datafusion_err, plan_err};
+use datafusion::common::{ParamValues, plan_datafusion_err, plan_err};
 use datafusion::config::ConfigFileType;
 use datafusion::datasource::listing::ListingTableUrl;
 use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::memory_pool::MemoryConsumer;
 use datafusion::logical_expr::{DdlStatement, LogicalPlan};
 use datafusion::physical_plan::execution_plan::EmissionType;
+use datafusion::physical_plan::result_limit::limit_result_stream;
 use datafusion::physical_plan::spill::get_record_batch_memory_size;
 use datafusion::physical_plan::{ExecutionPlanProperties, execute_stream};
 use datafusion::sql::parser::{DFParser, Statement};
@@ -44,7 +46,7 @@ use log::warn;
 use object_store::Error::Generic;
 use rustyline::Editor;
 use rustyline::error::ReadlineError;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs::File;
 use std::io::BufReader;
 use std::io::prelude::*;
@@ -137,6 +139,9 @@ pub async fn exec_from_repl(
     )));
     rl.load_history(".history").ok();
 
+    // values bound with `\bind` to the parameters of the next query
+    let mut param_values: Option<ParamValues> = None;
+
     loop {
         match rl.readline("> ") {
             Ok(line) if line.starts_with('\\') => {
@@ -161,6 +166,24 @@ pub async fn exec_from_repl(
                                 println!("Output format is {:?}.", print_options.format);
                             }
                         }
+                        Command::Bind(_) => {
+                            // parse the raw arguments, as whitespace within
+                            // quoted values is significant
+                            let args = line.trim()[1..]
+                                .split_once(char::is_whitespace)
+                                .map(|(_, args)| args)
+                                .unwrap_or_default();
+                            match parse_bind_values(args) {
+                                Ok(values) => {
+                                    println!(
+                                        "Bound {} parameter(s) to the next query",
+                                        values.len()
+                                    );
+                                    param_values = Some(values.into());
+                                }
+                                Err(e) => eprintln!("{e}"),
+                            }
+                        }
                         _ => {
                             if let Err(e) = cmd.execute(ctx, print_options).await {
                                 eprintln!("{e}")
@@ -178,8 +201,9 @@ pub async fn exec_from_repl(
                 let lines = split_from_semicolon(&line);
                 for line in lines {
                     rl.add_history_entry(line.trim_end())?;
+                    let params = param_values.take();
                     tokio::select! {
-                        res = exec_and_print(ctx, print_options, line) => match res {
+                        res = exec_and_print_with_params(ctx, print_options, line, params) => match res {
                             Ok(_) => {}
                             Err(err) => eprintln!("{err}"),
                         },
@@ -218,6 +242,35 @@ pub(super) async fn exec_and_print(
     print_options: &PrintOptions,
     sql: String,
 ) -> Result<()> {
+    exec_and_print_with_params(ctx, print_options, sql, None).await
+}
+
+/// Like [`exec_and_print`], replacing the parameters (`$1`, `$2`, ...) of the
+/// statements with `param_values`, if any
+pub(super) async fn exec_and_print_with_params(
+    ctx: &dyn CliSessionContext,
+    print_options: &PrintOptions,
+    sql: String,
+    param_values: Option<ParamValues>,
+) -> Result<()> {
+    let statements = parse_statements(ctx, &sql)?;
+    for statement in statements {
+        let result = StatementExecutor::new(statement)
+            .with_param_values(param_values.clone())
+            .execute(ctx, print_options)
+            .await;
+        print_warnings(ctx);
+        result?;
+    }
+
+    Ok(())
+}
+
+/// Parse `sql` into statements, using the SQL dialect of the session
+fn parse_statements(
+    ctx: &dyn CliSessionContext,
+    sql: &str,
+) -> Result<VecDeque<Statement>> {
     let task_ctx = ctx.task_ctx();
     let options = task_ctx.session_config().options();
     let dialect = &options.sql_parser.dialect;
@@ -229,20 +282,42 @@ pub(super) async fn exec_and_print(
         )
     })?;
 
-    let statements = DFParser::parse_sql_with_dialect(&sql, dialect.as_ref())?;
-    for statement in statements {
-        StatementExecutor::new(statement)
-            .execute(ctx, print_options)
-            .await?;
+    DFParser::parse_sql_with_dialect(sql, dialect.as_ref())
+}
+
+/// Create the logical plan of a single SQL statement, registering the object
+/// stores it refers to like statements run from the command line do.
+pub(crate) async fn create_logical_plan(
+    ctx: &impl CliSessionContext,
+    sql: &str,
+) -> Result<LogicalPlan> {
+    let mut statements = parse_statements(ctx, sql)?;
+    match (statements.pop_front(), statements.is_empty()) {
+        (Some(statement), true) => create_plan(ctx, statement, false).await,
+        _ => plan_err!("Expected exactly one SQL statement: {sql}"),
     }
+}
 
-    Ok(())
+/// Print (and clear) any non-fatal warnings collected while running the last
+/// statement to stderr
+fn print_warnings(ctx: &dyn CliSessionContext) {
+    let Some(warnings) = ctx
+        .task_ctx()
+        .session_config()
+        .get_extension::<WarningCollector>()
+    else {
+        return;
+    };
+    for warning in warnings.take() {
+        eprintln!("Warning: {}", warning.message);
+    }
 }
 
 /// Executor for SQL statements, including special handling for S3 region detection retry logic
 struct StatementExecutor {
     statement: Statement,
     statement_for_retry: Option<Statement>,
+    param_values: Option<ParamValues>,
 }
 
 impl StatementExecutor {
@@ -253,9 +328,16 @@ impl StatementExecutor {
         Self {
             statement,
             statement_for_retry,
+            param_values: None,
         }
     }
 
+    /// Replace the parameters of the statement with `param_values`, if any
+    fn with_param_values(mut self, param_values: Option<ParamValues>) -> Self {
+        self.param_values = param_values;
+        self
+    }
+
     async fn execute(
         self,
         ctx: &dyn CliSessionContext,
@@ -283,13 +365,15 @@ impl StatementExecutor {
             // As the input stream comes, we can generate results.
             // However, memory safety is not guaranteed.
             let stream = execute_stream(physical_plan, task_ctx.clone())?;
+            let stream = limit_result_stream(stream, &task_ctx);
             print_options
                 .print_stream(stream, now, &options.format)
                 .await?;
         } else {
             // Bounded stream; collected results size is limited by the maxrows option
             let schema = physical_plan.schema();
-            let mut stream = execute_stream(physical_plan, task_ctx.clone())?;
+            let stream = execute_stream(physical_plan, task_ctx.clone())?;
+            let mut stream = limit_result_stream(stream, &task_ctx);
             let mut results = vec![];
             let mut row_count = 0_usize;
             let max_rows = match print_options.maxrows {
@@ -329,7 +413,9 @@ impl StatementExecutor {
         let adjusted = AdjustedPrintOptions::new(print_options.clone())
             .with_statement(&self.statement);
 
+        let param_values = self.param_values.take();
         let plan = create_plan(ctx, self.statement, false).await?;
+        let plan = bind_params(plan, &param_values)?;
         let adjusted = adjusted.with_plan(&plan);
 
         let df = match ctx.execute_logical_plan(plan).await {
@@ -344,7 +430,8 @@ impl StatementExecutor {
                 let plan =
                     create_plan(ctx, self.statement_for_retry.take().unwrap(), true)
                         .await?;
-                ctx.execute_logical_plan(plan).await
+                ctx.execute_logical_plan(bind_params(plan, &param_values)?)
+                    .await
             }
             Err(e) => Err(e),
         }?;
@@ -353,6 +440,17 @@ impl StatementExecutor {
     }
 }
 
+/// Replace the parameters of `plan` with `param_values`, if any
+fn bind_params(
+    plan: LogicalPlan,
+    param_values: &Option<ParamValues>,
+) -> Result<LogicalPlan> {
+    match param_values {
+        Some(param_values) => plan.with_param_values(param_values.clone()),
+        None => Ok(plan),
+    }
+}
+
 /// Track adjustments to the print options based on the plan / statement being executed
 #[derive(Debug)]
 struct AdjustedPrintOptions {
@@ -409,7 +507,7 @@ fn config_file_type_from_str(ext: &str) -> Option<ConfigFileType> {
 }
 
 async fn create_plan(
-    ctx: &dyn CliSessionContext,
+    ctx: &(impl CliSessionContext + ?Sized),
     statement: Statement,
     resolve_region: bool,
 ) -> Result<LogicalPlan, DataFusionError> {
@@ -474,7 +572,7 @@ async fn create_plan(
 /// alteration fails, or if the object store cannot be retrieved and registered
 /// successfully.
 pub(crate) async fn register_object_store_and_config_extensions(
-    ctx: &dyn CliSessionContext,
+    ctx: &(impl CliSessionContext + ?Sized),
     location: &String,
     options: &HashMap<String, String>,
     format: Option<ConfigFileType>,
@@ -519,7 +617,8 @@ pub(crate) async fn register_object_store_and_config_extensions(
 mod tests {
     use super::*;
 
-    use datafusion::common::plan_err;
+    use datafusion::assert_batches_eq;
+    use datafusion::common::{ScalarValue, plan_err};
 
     use datafusion::prelude::SessionContext;
     use datafusion_common::assert_contains;
@@ -762,4 +861,18 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn bind_params_to_statement() -> Result<()> {
+        let ctx = SessionContext::new();
+        let sql = "SELECT $1 + 1 AS v";
+        let statement = parse_statements(&ctx, sql)?.pop_front().unwrap();
+        let plan = create_plan(&ctx, statement, false).await?;
+
+        let params = Some(vec![ScalarValue::Int64(Some(41))].into());
+        let plan = bind_params(plan, &params)?;
+        let batches = ctx.execute_logical_plan(plan).await?.collect().await?;
+        assert_batches_eq!(["+----+", "| v  |", "+----+", "| 42 |", "+----+"], &batches);
+        Ok(())
+    }
 }
@@ -0,0 +1,716 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Arrow Flight SQL server exposing the CLI session
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+
+use arrow::array::{AsArray, RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, UInt64Type};
+use arrow::ipc::writer::IpcWriteOptions;
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::sql::metadata::{SqlInfoData, SqlInfoDataBuilder};
+use arrow_flight::sql::server::{FlightSqlService, PeekableFlightDataStream};
+use arrow_flight::sql::{
+    ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest,
+    ActionCreatePreparedStatementResult, CommandGetCatalogs, CommandGetDbSchemas,
+    CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables,
+    CommandPreparedStatementQuery, CommandPreparedStatementUpdate, CommandStatementQuery,
+    CommandStatementUpdate, DoPutPreparedStatementResult, ProstMessageExt, SqlInfo,
+    TicketStatementQuery,
+};
+use arrow_flight::{
+    Action, FlightDescriptor, FlightEndpoint, FlightInfo, HandshakeRequest,
+    HandshakeResponse, IpcMessage, SchemaAsIpc, Ticket,
+};
+use datafusion::common::{ParamValues, ScalarValue, exec_datafusion_err};
+use datafusion::datasource::TableType;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::LogicalPlan;
+use datafusion::prelude::SessionContext;
+use futures::{Stream, TryStreamExt, stream};
+use log::info;
+use parking_lot::Mutex;
+use prost::Message;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::DATAFUSION_CLI_VERSION;
+use crate::exec::create_logical_plan;
+
+/// Information about the server returned by `CommandGetSqlInfo` requests
+static SQL_INFO: LazyLock<SqlInfoData> = LazyLock::new(|| {
+    let mut builder = SqlInfoDataBuilder::new();
+    builder.append(SqlInfo::FlightSqlServerName, "DataFusion CLI");
+    builder.append(SqlInfo::FlightSqlServerVersion, DATAFUSION_CLI_VERSION);
+    builder.append(SqlInfo::FlightSqlServerArrowVersion, "1.3");
+    builder.append(SqlInfo::FlightSqlServerReadOnly, false);
+    builder.append(SqlInfo::FlightSqlServerTransaction, 0_i32);
+    builder.build().expect("valid SQL info")
+});
+
+/// Serve the tables of `ctx` over Arrow Flight SQL on `addr`, until the
+/// process receives Ctrl-C
+pub async fn serve(ctx: SessionContext, addr: SocketAddr) -> Result<()> {
+    let service = FlightServiceServer::new(CliFlightSqlService::new(ctx));
+    info!("Serving Flight SQL on {addr}");
+    Server::builder()
+        .add_service(service)
+        .serve_with_shutdown(addr, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+/// A prepared statement, with the values bound to its parameters, if any
+struct PreparedStatement {
+    plan: LogicalPlan,
+    params: Option<ParamValues>,
+}
+
+impl PreparedStatement {
+    /// The plan of the statement, with its parameters replaced by their values
+    fn bound_plan(&self) -> Result<LogicalPlan> {
+        match &self.params {
+            Some(params) => self.plan.clone().with_param_values(params.clone()),
+            None => Ok(self.plan.clone()),
+        }
+    }
+}
+
+/// [`FlightSqlService`] executing statements against a [`SessionContext`].
+///
+/// All the clients share the same session, so the tables and settings created
+/// by one client are visible to the others.
+///
+/// Besides ad-hoc statements and prepared statements, this service answers
+/// the catalog, schema, table and table type metadata requests JDBC and ADBC
+/// drivers issue. Transactions and authentication are not supported.
+pub struct CliFlightSqlService {
+    ctx: SessionContext,
+    /// Plans of the ad-hoc queries, from `get_flight_info_statement` until
+    /// their results are fetched
+    queries: Mutex<HashMap<String, LogicalPlan>>,
+    /// The open prepared statements
+    prepared_statements: Mutex<HashMap<String, PreparedStatement>>,
+    next_handle: AtomicU64,
+}
+
+impl CliFlightSqlService {
+    /// Create a new service executing statements against `ctx`
+    pub fn new(ctx: SessionContext) -> Self {
+        Self {
+            ctx,
+            queries: Default::default(),
+            prepared_statements: Default::default(),
+            next_handle: AtomicU64::new(0),
+        }
+    }
+
+    fn new_handle(&self) -> String {
+        self.next_handle.fetch_add(1, Ordering::Relaxed).to_string()
+    }
+
+    async fn plan_sql(&self, sql: &str) -> Result<LogicalPlan, Status> {
+        create_logical_plan(&self.ctx, sql).await.map_err(to_status)
+    }
+
+    fn prepared_plan(&self, handle: &[u8]) -> Result<LogicalPlan, Status> {
+        let handle = parse_handle(handle)?;
+        self.prepared_statements
+            .lock()
+            .get(handle)
+            .ok_or_else(|| {
+                Status::not_found(format!("Prepared statement not found: {handle}"))
+            })?
+            .bound_plan()
+            .map_err(to_status)
+    }
+
+    /// Bind the values of the first row of `batches` to the parameters of the
+    /// prepared statement `handle`
+    fn bind_parameters(&self, handle: &str, batches: &[RecordBatch]) -> Result<()> {
+        let params = match batches.iter().find(|batch| batch.num_rows() > 0) {
+            Some(batch) => Some(ParamValues::from(
+                batch
+                    .columns()
+                    .iter()
+                    .map(|array| ScalarValue::try_from_array(array, 0))
+                    .collect::<Result<Vec<_>>>()?,
+            )),
+            None => None,
+        };
+
+        let mut prepared_statements = self.prepared_statements.lock();
+        let statement = prepared_statements.get_mut(handle).ok_or_else(|| {
+            exec_datafusion_err!("Prepared statement not found: {handle}")
+        })?;
+        statement.params = params;
+        Ok(())
+    }
+
+    /// Execute `plan` and stream its results
+    async fn execute_plan(
+        &self,
+        plan: LogicalPlan,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let df = self
+            .ctx
+            .execute_logical_plan(plan)
+            .await
+            .map_err(to_status)?;
+        let schema = Arc::new(df.schema().as_arrow().clone());
+        let stream = df
+            .execute_stream()
+            .await
+            .map_err(to_status)?
+            .map_err(|e| FlightError::ExternalError(Box::new(e)));
+        Ok(encode_stream(schema, stream))
+    }
+
+    /// Execute `plan` and return the number of rows it modified, or -1 if
+    /// unknown
+    async fn execute_update(&self, plan: LogicalPlan) -> Result<i64, Status> {
+        let batches = self
+            .ctx
+            .execute_logical_plan(plan)
+            .await
+            .map_err(to_status)?
+            .collect()
+            .await
+            .map_err(to_status)?;
+
+        // DML statements return the number of rows they modified in a single
+        // `count` column, other statements don't report any count
+        let count = batches
+            .iter()
+            .map(|batch| match batch.schema().fields().as_ref() {
+                [field] if field.name() == "count" => batch
+                    .column(0)
+                    .as_primitive_opt::<UInt64Type>()
+                    .map(|counts| counts.iter().flatten().sum::<u64>() as i64),
+                _ => None,
+            })
+            .reduce(|a, b| Some(a? + b?))
+            .flatten();
+        Ok(count.unwrap_or(-1))
+    }
+}
+
+#[tonic::async_trait]
+impl FlightSqlService for CliFlightSqlService {
+    type FlightService = CliFlightSqlService;
+
+    async fn do_handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<
+        Response<Pin<Box<dyn Stream<Item = Result<HandshakeResponse, Status>> + Send>>>,
+        Status,
+    > {
+        // no authentication: every client is accepted and shares the session
+        let response = HandshakeResponse {
+            protocol_version: 0,
+            payload: Default::default(),
+        };
+        Ok(Response::new(Box::pin(stream::iter([Ok(response)]))))
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        info!("get_flight_info_statement: {}", query.query);
+        let plan = self.plan_sql(&query.query).await?;
+        let schema = plan.schema().as_arrow().clone();
+
+        let handle = self.new_handle();
+        self.queries.lock().insert(handle.clone(), plan);
+
+        let ticket = TicketStatementQuery {
+            statement_handle: handle.into(),
+        };
+        flight_info(&schema, &ticket.as_any(), request.into_inner())
+    }
+
+    async fn get_flight_info_prepared_statement(
+        &self,
+        query: CommandPreparedStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let plan = self.prepared_plan(&query.prepared_statement_handle)?;
+        let schema = plan.schema().as_arrow().clone();
+        flight_info(&schema, &query.as_any(), request.into_inner())
+    }
+
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.into_builder().schema();
+        flight_info(&schema, &query.as_any(), request.into_inner())
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+        flight_info(&schema, &query.as_any(), request.into_inner())
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+        flight_info(&schema, &query.as_any(), request.into_inner())
+    }
+
+    async fn get_flight_info_table_types(
+        &self,
+        query: CommandGetTableTypes,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        flight_info(&table_types_schema(), &query.as_any(), request.into_inner())
+    }
+
+    async fn get_flight_info_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder(&SQL_INFO).schema();
+        flight_info(&schema, &query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: TicketStatementQuery,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let handle = parse_handle(&ticket.statement_handle)?;
+        let plan =
+            self.queries.lock().remove(handle).ok_or_else(|| {
+                Status::not_found(format!("Statement not found: {handle}"))
+            })?;
+        self.execute_plan(plan).await
+    }
+
+    async fn do_get_prepared_statement(
+        &self,
+        query: CommandPreparedStatementQuery,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let plan = self.prepared_plan(&query.prepared_statement_handle)?;
+        self.execute_plan(plan).await
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let mut builder = query.into_builder();
+        let mut catalog_names = self.ctx.catalog_names();
+        catalog_names.sort();
+        for catalog_name in catalog_names {
+            builder.append(catalog_name);
+        }
+        encode_batch(builder.schema(), builder.build())
+    }
+
+    async fn do_get_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let mut builder = query.into_builder();
+        for catalog_name in self.ctx.catalog_names() {
+            let Some(catalog) = self.ctx.catalog(&catalog_name) else {
+                continue;
+            };
+            for schema_name in catalog.schema_names() {
+                builder.append(&catalog_name, schema_name);
+            }
+        }
+        encode_batch(builder.schema(), builder.build())
+    }
+
+    async fn do_get_tables(
+        &self,
+        query: CommandGetTables,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let mut builder = query.into_builder();
+        for catalog_name in self.ctx.catalog_names() {
+            let Some(catalog) = self.ctx.catalog(&catalog_name) else {
+                continue;
+            };
+            for schema_name in catalog.schema_names() {
+                let Some(schema) = catalog.schema(&schema_name) else {
+                    continue;
+                };
+                for table_name in schema.table_names() {
+                    let Some(table) =
+                        schema.table(&table_name).await.map_err(to_status)?
+                    else {
+                        continue;
+                    };
+                    builder
+                        .append(
+                            &catalog_name,
+                            &schema_name,
+                            table_name,
+                            table_type_name(table.table_type()),
+                            &table.schema(),
+                        )
+                        .map_err(|e| Status::internal(e.to_string()))?;
+                }
+            }
+        }
+        encode_batch(builder.schema(), builder.build())
+    }
+
+    async fn do_get_table_types(
+        &self,
+        _query: CommandGetTableTypes,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let schema = table_types_schema();
+        let table_types =
+            [TableType::Base, TableType::Temporary, TableType::View].map(table_type_name);
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(StringArray::from(table_types.to_vec()))],
+        );
+        encode_batch(schema, batch)
+    }
+
+    async fn do_get_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let builder = query.into_builder(&SQL_INFO);
+        encode_batch(builder.schema(), builder.build())
+    }
+
+    async fn do_put_statement_update(
+        &self,
+        ticket: CommandStatementUpdate,
+        _request: Request<PeekableFlightDataStream>,
+    ) -> Result<i64, Status> {
+        info!("do_put_statement_update: {}", ticket.query);
+        let plan = self.plan_sql(&ticket.query).await?;
+        self.execute_update(plan).await
+    }
+
+    async fn do_put_prepared_statement_query(
+        &self,
+        query: CommandPreparedStatementQuery,
+        request: Request<PeekableFlightDataStream>,
+    ) -> Result<DoPutPreparedStatementResult, Status> {
+        let handle = parse_handle(&query.prepared_statement_handle)?;
+        let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(
+            request.into_inner().map_err(FlightError::from),
+        )
+        .try_collect()
+        .await
+        .map_err(Status::from)?;
+        self.bind_parameters(handle, &batches).map_err(to_status)?;
+
+        Ok(DoPutPreparedStatementResult {
+            prepared_statement_handle: Some(query.prepared_statement_handle),
+        })
+    }
+
+    async fn do_put_prepared_statement_update(
+        &self,
+        query: CommandPreparedStatementUpdate,
+        _request: Request<PeekableFlightDataStream>,
+    ) -> Result<i64, Status> {
+        let plan = self.prepared_plan(&query.prepared_statement_handle)?;
+        self.execute_update(plan).await
+    }
+
+    async fn do_action_create_prepared_statement(
+        &self,
+        query: ActionCreatePreparedStatementRequest,
+        _request: Request<Action>,
+    ) -> Result<ActionCreatePreparedStatementResult, Status> {
+        info!("do_action_create_prepared_statement: {}", query.query);
+        let plan = self.plan_sql(&query.query).await?;
+        let dataset_schema = encode_schema(plan.schema().as_arrow())?;
+        let parameter_schema = encode_schema(&parameter_schema(&plan)?)?;
+
+        let handle = self.new_handle();
+        self.prepared_statements
+            .lock()
+            .insert(handle.clone(), PreparedStatement { plan, params: None });
+
+        Ok(ActionCreatePreparedStatementResult {
+            prepared_statement_handle: handle.into(),
+            dataset_schema,
+            parameter_schema,
+        })
+    }
+
+    async fn do_action_close_prepared_statement(
+        &self,
+        query: ActionClosePreparedStatementRequest,
+        _request: Request<Action>,
+    ) -> Result<(), Status> {
+        let handle = parse_handle(&query.prepared_statement_handle)?;
+        self.prepared_statements.lock().remove(handle);
+        Ok(())
+    }
+
+    async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
+}
+
+fn to_status(e: DataFusionError) -> Status {
+    match e {
+        DataFusionError::Plan(_) | DataFusionError::SQL(_, _) => {
+            Status::invalid_argument(e.to_string())
+        }
+        e => Status::internal(e.to_string()),
+    }
+}
+
+fn parse_handle(handle: &[u8]) -> Result<&str, Status> {
+    std::str::from_utf8(handle)
+        .map_err(|e| Status::invalid_argument(format!("Invalid handle: {e}")))
+}
+
+/// A [`FlightInfo`] for a result with `schema` fetched with `ticket`
+fn flight_info(
+    schema: &Schema,
+    ticket: &arrow_flight::sql::Any,
+    descriptor: FlightDescriptor,
+) -> Result<Response<FlightInfo>, Status> {
+    let ticket = Ticket::new(ticket.encode_to_vec());
+    let info = FlightInfo::new()
+        .try_with_schema(schema)
+        .map_err(|e| Status::internal(format!("Unable to encode schema: {e}")))?
+        .with_endpoint(FlightEndpoint::new().with_ticket(ticket))
+        .with_descriptor(descriptor);
+    Ok(Response::new(info))
+}
+
+fn encode_schema(schema: &Schema) -> Result<prost::bytes::Bytes, Status> {
+    let IpcMessage(schema) = SchemaAsIpc::new(schema, &IpcWriteOptions::default())
+        .try_into()
+        .map_err(|e| Status::internal(format!("Unable to encode schema: {e}")))?;
+    Ok(schema)
+}
+
+fn encode_stream(
+    schema: SchemaRef,
+    batches: impl Stream<Item = Result<RecordBatch, FlightError>> + Send + 'static,
+) -> Response<<CliFlightSqlService as FlightService>::DoGetStream> {
+    let stream = FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .build(batches)
+        .map_err(Status::from);
+    Response::new(Box::pin(stream))
+}
+
+fn encode_batch(
+    schema: SchemaRef,
+    batch: Result<RecordBatch, impl std::fmt::Display>,
+) -> Result<Response<<CliFlightSqlService as FlightService>::DoGetStream>, Status> {
+    let batch = batch.map_err(|e| Status::internal(e.to_string()))?;
+    Ok(encode_stream(schema, stream::iter([Ok(batch)])))
+}
+
+/// The name of `table_type`, as in `information_schema.tables`
+fn table_type_name(table_type: TableType) -> &'static str {
+    match table_type {
+        TableType::Base => "BASE TABLE",
+        TableType::View => "VIEW",
+        TableType::Temporary => "LOCAL TEMPORARY",
+    }
+}
+
+/// The schema of the results of `CommandGetTableTypes` requests
+fn table_types_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![Field::new(
+        "table_type",
+        DataType::Utf8,
+        false,
+    )]))
+}
+
+/// The schema of the parameters of `plan`, with a field for each positional
+/// parameter (`$1`, `$2`, ...) in order
+fn parameter_schema(plan: &LogicalPlan) -> Result<Schema, Status> {
+    let mut parameters: Vec<_> = plan
+        .get_parameter_types()
+        .map_err(to_status)?
+        .into_iter()
+        .collect();
+    parameters.sort_by_key(|(name, _)| {
+        name.strip_prefix('$')
+            .and_then(|position| position.parse::<usize>().ok())
+            .unwrap_or(usize::MAX)
+    });
+
+    Ok(Schema::new(
+        parameters
+            .into_iter()
+            .map(|(name, data_type)| {
+                Field::new(name, data_type.unwrap_or(DataType::Null), true)
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_flight::sql::Any;
+    use datafusion::assert_batches_eq;
+
+    async fn fetch(
+        service: &CliFlightSqlService,
+        info: FlightInfo,
+    ) -> Result<Vec<RecordBatch>, Status> {
+        let ticket = info.endpoint[0].ticket.clone().unwrap();
+        let message = Any::decode(ticket.ticket.clone()).unwrap();
+        let response =
+            if let Some(ticket) = message.unpack::<TicketStatementQuery>().unwrap() {
+                service
+                    .do_get_statement(ticket, Request::new(Ticket::default()))
+                    .await?
+            } else {
+                let query = message
+                    .unpack::<CommandPreparedStatementQuery>()
+                    .unwrap()
+                    .unwrap();
+                service
+                    .do_get_prepared_statement(query, Request::new(Ticket::default()))
+                    .await?
+            };
+        FlightRecordBatchStream::new_from_flight_data(
+            response.into_inner().map_err(FlightError::from),
+        )
+        .try_collect()
+        .await
+        .map_err(Status::from)
+    }
+
+    #[tokio::test]
+    async fn flight_sql_statements() -> Result<(), Status> {
+        let service = CliFlightSqlService::new(SessionContext::new());
+        let descriptor = || Request::new(FlightDescriptor::new_cmd(vec![]));
+
+        let count = service
+            .execute_update(
+                service
+                    .plan_sql("CREATE TABLE t AS VALUES (1, 'a'), (2, 'b')")
+                    .await?,
+            )
+            .await?;
+        assert_eq!(count, -1);
+
+        let query = CommandStatementQuery {
+            query: "SELECT column1 + 1 AS a, column2 AS b FROM t".to_string(),
+            transaction_id: None,
+        };
+        let info = service
+            .get_flight_info_statement(query, descriptor())
+            .await?
+            .into_inner();
+        assert_batches_eq!(
+            [
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| 2 | a |",
+                "| 3 | b |",
+                "+---+---+",
+            ],
+            &fetch(&service, info).await?
+        );
+
+        // prepared statement with a parameter
+        let prepared = service
+            .do_action_create_prepared_statement(
+                ActionCreatePreparedStatementRequest {
+                    query: "SELECT column2 FROM t WHERE column1 = $1".to_string(),
+                    transaction_id: None,
+                },
+                Request::new(Action::default()),
+            )
+            .await?;
+        let handle = parse_handle(&prepared.prepared_statement_handle)?.to_string();
+        let params = RecordBatch::try_from_iter([(
+            "$1",
+            Arc::new(arrow::array::Int64Array::from(vec![2])) as _,
+        )])
+        .unwrap();
+        service.bind_parameters(&handle, &[params]).unwrap();
+
+        let query = CommandPreparedStatementQuery {
+            prepared_statement_handle: prepared.prepared_statement_handle.clone(),
+        };
+        let info = service
+            .get_flight_info_prepared_statement(query, descriptor())
+            .await?
+            .into_inner();
+        assert_batches_eq!(
+            [
+                "+---------+",
+                "| column2 |",
+                "+---------+",
+                "| b       |",
+                "+---------+",
+            ],
+            &fetch(&service, info).await?
+        );
+
+        service
+            .do_action_close_prepared_statement(
+                ActionClosePreparedStatementRequest {
+                    prepared_statement_handle: prepared.prepared_statement_handle,
+                },
+                Request::new(Action::default()),
+            )
+            .await?;
+        assert!(service.prepared_statements.lock().is_empty());
+
+        let count = service
+            .execute_update(service.plan_sql("INSERT INTO t VALUES (3, 'c')").await?)
+            .await?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+}
@@ -44,6 +44,7 @@ use datafusion::scalar::ScalarValue;
 use async_trait::async_trait;
 use parquet::basic::ConvertedType;
 use parquet::data_type::{ByteArray, FixedLenByteArray};
+use parquet::file::metadata::FileMetaData;
 use parquet::file::reader::FileReader;
 use parquet::file::serialized_reader::SerializedFileReader;
 use parquet::file::statistics::Statistics;
@@ -318,6 +319,42 @@ fn fixed_len_byte_array_to_string(val: Option<&FixedLenByteArray>) -> Option<Str
     })
 }
 
+/// Returns the key-value metadata of a parquet file, one row per key
+fn parquet_key_value_metadata(
+    filename: &str,
+    metadata: &FileMetaData,
+) -> Result<Arc<dyn TableProvider>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("filename", DataType::Utf8, true),
+        Field::new("key", DataType::Utf8, true),
+        Field::new("value", DataType::Utf8, true),
+    ]));
+
+    let key_value_metadata = metadata
+        .key_value_metadata()
+        .map(|key_value| key_value.as_slice())
+        .unwrap_or_default();
+    let filename_arr = vec![filename; key_value_metadata.len()];
+    let key_arr = key_value_metadata
+        .iter()
+        .map(|kv| kv.key.as_str())
+        .collect::<Vec<_>>();
+    let value_arr = key_value_metadata
+        .iter()
+        .map(|kv| kv.value.as_deref())
+        .collect::<Vec<_>>();
+
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(StringArray::from(filename_arr)),
+            Arc::new(StringArray::from(key_arr)),
+            Arc::new(StringArray::from(value_arr)),
+        ],
+    )?;
+    Ok(Arc::new(ParquetMetadataTable { schema, batch }))
+}
+
 #[derive(Debug)]
 pub struct ParquetMetadataFunc {}
 
@@ -334,10 +371,28 @@ impl TableFunctionImpl for ParquetMetadataFunc {
             }
         };
 
+        let key_value = match exprs.get(1) {
+            None => false,
+            Some(Expr::Literal(ScalarValue::Utf8(Some(kind)), _))
+                if kind.eq_ignore_ascii_case("key_value") =>
+            {
+                true
+            }
+            Some(kind) => {
+                return plan_err!(
+                    "parquet_metadata expects 'key_value' as its second argument, got {kind}"
+                );
+            }
+        };
+
         let file = File::open(filename.clone())?;
         let reader = SerializedFileReader::new(file)?;
         let metadata = reader.metadata();
 
+        if key_value {
+            return parquet_key_value_metadata(filename, metadata.file_metadata());
+        }
+
         let schema = Arc::new(Schema::new(vec![
             Field::new("filename", DataType::Utf8, true),
             Field::new("row_group_id", DataType::Int64, true),
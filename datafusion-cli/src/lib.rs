@@ -27,6 +27,7 @@ pub mod catalog;
 pub mod cli_context;
 pub mod command;
 pub mod exec;
+pub mod flight_sql;
 pub mod functions;
 pub mod helper;
 pub mod highlighter;
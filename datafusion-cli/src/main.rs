@@ -17,6 +17,7 @@
 
 use std::collections::HashMap;
 use std::env;
+use std::net::SocketAddr;
 use std::num::NonZeroUsize;
 use std::path::Path;
 use std::process::ExitCode;
@@ -38,7 +39,7 @@ use datafusion_cli::object_storage::instrumented::{
     InstrumentedObjectStoreMode, InstrumentedObjectStoreRegistry,
 };
 use datafusion_cli::{
-    DATAFUSION_CLI_VERSION, exec,
+    DATAFUSION_CLI_VERSION, exec, flight_sql,
     pool_type::PoolType,
     print_format::PrintFormat,
     print_options::{MaxRows, PrintOptions},
@@ -47,7 +48,9 @@ use datafusion_cli::{
 use clap::Parser;
 use datafusion::common::config_err;
 use datafusion::config::ConfigOptions;
+use datafusion::execution::TaskContextProvider;
 use datafusion::execution::disk_manager::{DiskManagerBuilder, DiskManagerMode};
+use datafusion_ffi::plugin::load_plugins;
 use mimalloc::MiMalloc;
 
 #[global_allocator]
@@ -156,6 +159,13 @@ struct Args {
         default_value_t = InstrumentedObjectStoreMode::Disabled
     )]
     object_store_profiling: InstrumentedObjectStoreMode,
+
+    #[clap(
+        long,
+        value_name = "ADDR",
+        help = "Serve the session over Arrow Flight SQL on the given address (e.g. '127.0.0.1:50051') instead of starting the REPL. Commands and files are executed first to set up the session"
+    )]
+    serve_flight_sql: Option<SocketAddr>,
 }
 
 #[tokio::main]
@@ -260,6 +270,9 @@ async fn main_inner() -> Result<()> {
         )),
     );
 
+    // the plugins only hold a weak reference to the session, keep it alive
+    let _plugin_session = register_plugins(&ctx)?;
+
     let mut print_options = PrintOptions {
         format: args.format,
         quiet: args.quiet,
@@ -285,6 +298,22 @@ async fn main_inner() -> Result<()> {
         }
     };
 
+    if let Some(addr) = args.serve_flight_sql {
+        if commands.is_empty() && files.is_empty() && !rc.is_empty() {
+            exec::exec_from_files(&ctx, rc, &print_options).await?;
+        }
+        if !files.is_empty() {
+            exec::exec_from_files(&ctx, files, &print_options).await?;
+        }
+        if !commands.is_empty() {
+            exec::exec_from_commands(&ctx, commands, &print_options).await?;
+        }
+        if !args.quiet {
+            println!("Serving Flight SQL on {addr}, press Ctrl-C to stop");
+        }
+        return flight_sql::serve(ctx, addr).await;
+    }
+
     if commands.is_empty() && files.is_empty() {
         if !rc.is_empty() {
             exec::exec_from_files(&ctx, rc, &print_options).await?;
@@ -335,6 +364,50 @@ fn get_session_config(args: &Args) -> Result<SessionConfig> {
     Ok(session_config)
 }
 
+/// Loads the plugins of the directories listed in
+/// `datafusion.execution.plugin_dirs` and registers their extensions with
+/// `ctx`. Returns the session the plugins use to decode plans and
+/// expressions, which must outlive them.
+fn register_plugins(
+    ctx: &SessionContext,
+) -> Result<Option<Arc<dyn TaskContextProvider>>> {
+    let Some(plugin_dirs) = ctx.copied_config().options().execution.plugin_dirs.clone()
+    else {
+        return Ok(None);
+    };
+    let dirs = plugin_dirs
+        .split(',')
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty());
+
+    let session: Arc<dyn TaskContextProvider> = Arc::new(ctx.clone());
+    for plugin in load_plugins(dirs, &session)? {
+        for udf in plugin.scalar_udfs {
+            ctx.register_udf(udf);
+        }
+        for udaf in plugin.aggregate_udfs {
+            ctx.register_udaf(udaf);
+        }
+        for udwf in plugin.window_udfs {
+            ctx.register_udwf(udwf);
+        }
+        for (name, udtf) in plugin.table_functions {
+            ctx.register_udtf(&name, udtf);
+        }
+        for (name, catalog) in plugin.catalogs {
+            ctx.register_catalog(name, catalog);
+        }
+        let state = ctx.state_ref();
+        let mut state = state.write();
+        for (file_type, factory) in plugin.table_factories {
+            state
+                .table_factories_mut()
+                .insert(file_type.to_uppercase(), factory);
+        }
+    }
+    Ok(Some(session))
+}
+
 fn parse_valid_file(dir: &str) -> Result<String, String> {
     if Path::new(dir).is_file() {
         Ok(dir.to_string())
@@ -442,7 +515,7 @@ mod tests {
 
     use super::*;
     use datafusion::{
-        common::test_util::batches_to_string,
+        common::{assert_contains, test_util::batches_to_string},
         execution::cache::{
             DefaultListFilesCache, cache_manager::CacheManagerConfig,
             cache_unit::DefaultFileStatisticsCache,
@@ -563,6 +636,33 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_parquet_metadata_key_value() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        ctx.register_udtf("parquet_metadata", Arc::new(ParquetMetadataFunc {}));
+
+        let sql = "SELECT key, value IS NOT NULL AS has_value FROM parquet_metadata('../datafusion/core/tests/data/fixed_size_list_array.parquet', 'key_value')";
+        let df = ctx.sql(sql).await?;
+        let rbs = df.collect().await?;
+
+        assert_snapshot!(batches_to_string(&rbs), @r"
+        +--------------+-----------+
+        | key          | has_value |
+        +--------------+-----------+
+        | ARROW:schema | true      |
+        +--------------+-----------+
+        ");
+
+        let sql = "SELECT * FROM parquet_metadata('../datafusion/core/tests/data/fixed_size_list_array.parquet', 'footer')";
+        let err = ctx.sql(sql).await.unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "parquet_metadata expects 'key_value' as its second argument"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_metadata_cache() -> Result<(), DataFusionError> {
         let ctx = SessionContext::new();
@@ -0,0 +1,373 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ChangeTrackingTable`]: incremental scans of the files added to a
+//! [`ListingTable`] since a snapshot
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use arrow::datatypes::{DataType, SchemaRef};
+use async_trait::async_trait;
+use datafusion_catalog::{
+    ScanArgs, ScanResult, Session, TableFunctionArgs, TableFunctionImpl, TableProvider,
+};
+use datafusion_common::{
+    Constraints, Result, ScalarValue, Statistics, TableReference, plan_err,
+};
+use datafusion_expr::dml::InsertOp;
+use datafusion_expr::{Expr, TableProviderFilterPushDown, TableType};
+use datafusion_physical_plan::ExecutionPlan;
+use object_store::path::Path;
+
+use crate::{FileManifest, ListingTable};
+
+/// A log of the files of a table at successive snapshots, used by a
+/// [`ChangeTrackingTable`] to find the files added since a snapshot.
+///
+/// Snapshots are identified by increasing ids starting at 1, while the id 0
+/// refers to the table before any snapshot, without files.
+/// [`MemorySnapshotLog`] keeps the log in memory, implement this trait to
+/// persist it, for example next to the table, so that incremental scans can
+/// resume after a restart.
+pub trait SnapshotLog: Debug + Send + Sync {
+    /// Record a new snapshot of the table containing `files`, returning its id
+    fn record(&self, files: Vec<Path>) -> Result<u64>;
+
+    /// The files of the table at `snapshot`, or an error if no such
+    /// snapshot was recorded
+    fn files(&self, snapshot: u64) -> Result<Arc<HashSet<Path>>>;
+
+    /// The id of the latest snapshot, or 0 if no snapshot was recorded
+    fn latest(&self) -> u64;
+}
+
+/// Reference counted [`SnapshotLog`]
+pub type SnapshotLogRef = Arc<dyn SnapshotLog>;
+
+/// A [`SnapshotLog`] keeping the snapshots in memory
+#[derive(Debug, Default)]
+pub struct MemorySnapshotLog {
+    /// The files of each snapshot, the snapshot with id `n` at index `n - 1`
+    snapshots: RwLock<Vec<Arc<HashSet<Path>>>>,
+}
+
+impl MemorySnapshotLog {
+    /// Create an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SnapshotLog for MemorySnapshotLog {
+    fn record(&self, files: Vec<Path>) -> Result<u64> {
+        let mut snapshots = self.snapshots.write().expect("Can't lock snapshots");
+        snapshots.push(Arc::new(files.into_iter().collect()));
+        Ok(snapshots.len() as u64)
+    }
+
+    fn files(&self, snapshot: u64) -> Result<Arc<HashSet<Path>>> {
+        if snapshot == 0 {
+            return Ok(Arc::default());
+        }
+        let snapshots = self.snapshots.read().expect("Can't lock snapshots");
+        match snapshots.get(snapshot as usize - 1) {
+            Some(files) => Ok(Arc::clone(files)),
+            None => plan_err!("Snapshot {snapshot} does not exist"),
+        }
+    }
+
+    fn latest(&self) -> u64 {
+        self.snapshots.read().expect("Can't lock snapshots").len() as u64
+    }
+}
+
+/// A [`TableProvider`] wrapping a [`ListingTable`] that tracks the files
+/// added to it, so that incremental pipelines only read the rows of the new
+/// files.
+///
+/// [`Self::snapshot`] records the files currently in the table in a
+/// [`SnapshotLog`], and [`Self::changes`] returns a table scanning the files
+/// that were not in the table at a snapshot. Files are identified by their
+/// location, so rewriting an existing file is not a change. Scans of the
+/// table itself read all of its files.
+///
+/// See also [`ChangesFunc`] and `SessionContext::enable_change_tracking`,
+/// which makes the changes available as `changes('table', snapshot)` in
+/// SQL.
+#[derive(Debug, Clone)]
+pub struct ChangeTrackingTable {
+    table: Arc<ListingTable>,
+    log: SnapshotLogRef,
+}
+
+impl ChangeTrackingTable {
+    /// Track the files added to `table` in a [`MemorySnapshotLog`]
+    pub fn new(table: Arc<ListingTable>) -> Self {
+        Self {
+            table,
+            log: Arc::new(MemorySnapshotLog::new()),
+        }
+    }
+
+    /// Record the snapshots in `log` instead of in memory
+    pub fn with_snapshot_log(mut self, log: SnapshotLogRef) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Return the wrapped table
+    pub fn table(&self) -> &Arc<ListingTable> {
+        &self.table
+    }
+
+    /// Return the log of the snapshots of the table
+    pub fn snapshot_log(&self) -> &SnapshotLogRef {
+        &self.log
+    }
+
+    /// List the files of the table and record them as a new snapshot,
+    /// returning its id
+    pub async fn snapshot(&self, state: &dyn Session) -> Result<u64> {
+        let files = self.table.list_files(state).await?;
+        self.log.record(
+            files
+                .into_iter()
+                .map(|file| file.object_meta.location)
+                .collect(),
+        )
+    }
+
+    /// Return a table with the schema of this table scanning only the files
+    /// added since `snapshot`, which are listed when the table is scanned
+    ///
+    /// `snapshot` 0 returns all the files of the table.
+    pub fn changes(&self, snapshot: u64) -> Result<Arc<dyn TableProvider>> {
+        Ok(Arc::new(ChangesTable {
+            table: Arc::clone(&self.table),
+            since: self.log.files(snapshot)?,
+        }))
+    }
+}
+
+#[async_trait]
+impl TableProvider for ChangeTrackingTable {
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.table.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.table.table_type()
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.table.get_table_definition()
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.table.get_column_default(column)
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.table.scan(state, projection, filters, limit).await
+    }
+
+    async fn scan_with_args<'a>(
+        &self,
+        state: &dyn Session,
+        args: ScanArgs<'a>,
+    ) -> Result<ScanResult> {
+        self.table.scan_with_args(state, args).await
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.table.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.table.statistics()
+    }
+
+    async fn insert_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.table.insert_into(state, input, insert_op).await
+    }
+}
+
+/// The files of a [`ListingTable`] that are not in the `since` snapshot,
+/// returned by [`ChangeTrackingTable::changes`]
+#[derive(Debug)]
+struct ChangesTable {
+    table: Arc<ListingTable>,
+    since: Arc<HashSet<Path>>,
+}
+
+impl ChangesTable {
+    /// The table scanning the files added since the snapshot
+    async fn added_files_table(&self, state: &dyn Session) -> Result<ListingTable> {
+        let files = self
+            .table
+            .list_files(state)
+            .await?
+            .into_iter()
+            .filter(|file| !self.since.contains(&file.object_meta.location))
+            .collect();
+        self.table
+            .as_ref()
+            .clone()
+            .with_manifest(Arc::new(FileManifest::new(files)))
+    }
+}
+
+#[async_trait]
+impl TableProvider for ChangesTable {
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.table.table_type()
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let args = ScanArgs::default()
+            .with_projection(projection.map(|p| p.as_slice()))
+            .with_filters(Some(filters))
+            .with_limit(limit);
+        Ok(self.scan_with_args(state, args).await?.into_inner())
+    }
+
+    async fn scan_with_args<'a>(
+        &self,
+        state: &dyn Session,
+        args: ScanArgs<'a>,
+    ) -> Result<ScanResult> {
+        self.added_files_table(state)
+            .await?
+            .scan_with_args(state, args)
+            .await
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.table.supports_filters_pushdown(filters)
+    }
+}
+
+/// The `changes(table [, snapshot])` table function, returning the rows of
+/// the files added to a [`ChangeTrackingTable`] since `snapshot`, or since
+/// its latest snapshot if `snapshot` is omitted.
+///
+/// The tables are looked up by the name they were added with in
+/// [`Self::register`].
+#[derive(Debug, Default)]
+pub struct ChangesFunc {
+    tables: RwLock<HashMap<TableReference, ChangeTrackingTable>>,
+}
+
+impl ChangesFunc {
+    /// Make the changes of `table` available as `changes('<table_ref>')`,
+    /// replacing any table registered with the same name
+    pub fn register(
+        &self,
+        table_ref: impl Into<TableReference>,
+        table: ChangeTrackingTable,
+    ) {
+        self.tables
+            .write()
+            .expect("Can't lock tables")
+            .insert(table_ref.into(), table);
+    }
+
+    /// Stop tracking the changes of the table `table_ref`, returning it if
+    /// it was registered
+    pub fn deregister(
+        &self,
+        table_ref: impl Into<TableReference>,
+    ) -> Option<ChangeTrackingTable> {
+        self.tables
+            .write()
+            .expect("Can't lock tables")
+            .remove(&table_ref.into())
+    }
+}
+
+impl TableFunctionImpl for ChangesFunc {
+    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
+        let (name, snapshot) = match args.exprs() {
+            [Expr::Literal(name, _)] => (name, None),
+            [Expr::Literal(name, _), Expr::Literal(snapshot, _)] => {
+                (name, Some(snapshot))
+            }
+            _ => {
+                return plan_err!(
+                    "changes function requires a table name and an optional snapshot id literal"
+                );
+            }
+        };
+        let Some(Some(name)) = name.try_as_str() else {
+            return plan_err!(
+                "The table name of the changes function must be a string, got {name}"
+            );
+        };
+        let table = self
+            .tables
+            .read()
+            .expect("Can't lock tables")
+            .get(&TableReference::from(name))
+            .cloned();
+        let Some(table) = table else {
+            return plan_err!("Changes of table {name} are not tracked");
+        };
+        let snapshot = match snapshot.map(|s| s.cast_to(&DataType::UInt64)) {
+            None => table.snapshot_log().latest(),
+            Some(Ok(ScalarValue::UInt64(Some(snapshot)))) => snapshot,
+            Some(_) => {
+                return plan_err!(
+                    "The snapshot of the changes function must be a non-negative integer"
+                );
+            }
+        };
+        table.changes(snapshot)
+    }
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::manifest::FileManifest;
 use crate::options::ListingOptions;
 use arrow::datatypes::{DataType, Schema, SchemaRef};
 use datafusion_catalog::Session;
@@ -68,6 +69,8 @@ pub struct ListingTableConfig {
     pub(crate) schema_source: SchemaSource,
     /// Optional [`PhysicalExprAdapterFactory`] for creating physical expression adapters
     pub(crate) expr_adapter_factory: Option<Arc<dyn PhysicalExprAdapterFactory>>,
+    /// Optional [`FileManifest`] listing the files of the table
+    pub(crate) manifest: Option<Arc<FileManifest>>,
 }
 
 impl ListingTableConfig {
@@ -139,6 +142,22 @@ impl ListingTableConfig {
         }
     }
 
+    /// Set the schema of the table, recording that it was inferred from the
+    /// table's files rather than specified.
+    ///
+    /// This is equivalent to [`Self::infer_schema`] for a schema that was
+    /// already inferred, and allows [`ListingTable::with_refreshed_schema`]
+    /// to infer it again later.
+    ///
+    /// [`ListingTable::with_refreshed_schema`]: crate::ListingTable::with_refreshed_schema
+    pub fn with_inferred_schema(self, schema: SchemaRef) -> Self {
+        Self {
+            file_schema: Some(schema),
+            schema_source: SchemaSource::Inferred,
+            ..self
+        }
+    }
+
     /// Add `listing_options` to [`ListingTableConfig`]
     ///
     /// If not provided, format and other options are inferred via
@@ -215,6 +234,7 @@ impl ListingTableConfig {
                     options: _,
                     schema_source,
                     expr_adapter_factory,
+                    manifest,
                 } = self;
 
                 let (schema, new_schema_source) = match file_schema {
@@ -237,6 +257,7 @@ impl ListingTableConfig {
                     options: Some(options),
                     schema_source: new_schema_source,
                     expr_adapter_factory,
+                    manifest,
                 })
             }
             None => internal_err!("No `ListingOptions` set for inferring schema"),
@@ -277,6 +298,7 @@ impl ListingTableConfig {
                     options: Some(options),
                     schema_source: self.schema_source,
                     expr_adapter_factory: self.expr_adapter_factory,
+                    manifest: self.manifest,
                 })
             }
             None => config_err!("No `ListingOptions` set for inferring schema"),
@@ -298,6 +320,21 @@ impl ListingTableConfig {
         }
     }
 
+    /// Set the [`FileManifest`] listing the files of the [`crate::ListingTable`]
+    ///
+    /// The table scans the files of the manifest, located in the object store
+    /// of the first table path, instead of listing its table paths. As the
+    /// schema is otherwise inferred from the files of the first table path,
+    /// it should be specified with [`Self::with_schema`].
+    ///
+    /// Inserting into a table with a manifest is not supported.
+    pub fn with_manifest(self, manifest: Arc<FileManifest>) -> Self {
+        Self {
+            manifest: Some(manifest),
+            ..self
+        }
+    }
+
     /// Deprecated: Set the [`SchemaAdapterFactory`] for the [`crate::ListingTable`]
     ///
     /// `SchemaAdapterFactory` has been removed. Use [`Self::with_expr_adapter_factory`]
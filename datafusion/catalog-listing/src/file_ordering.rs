@@ -0,0 +1,288 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::Ordering;
+
+use arrow::datatypes::DataType;
+use datafusion_common::{Column, ScalarValue};
+use datafusion_datasource::PartitionedFile;
+use datafusion_expr::utils::split_conjunction;
+use datafusion_expr::{BinaryExpr, Expr, Operator, SortExpr};
+
+/// Declares that the files of a [`ListingTable`] are sorted by a column and
+/// do not overlap: taken in the order of their paths, the values of the
+/// column in a file all come before, or are equal to, the values in the next
+/// file. For example, files of events named after the time they were
+/// written are usually ordered by the event time.
+///
+/// Each file is then also sorted by the column, unless
+/// [`ListingOptions::file_sort_order`] declares an ordering of the files
+/// starting with it. The scans of the table read the files of each partition
+/// in the order of their paths, so that the partitions are sorted without
+/// the statistics of the files, and a query sorting the table by the column
+/// only needs to merge them, for example with a `SortPreservingMergeExec`.
+///
+/// If [`Self::with_first_value_in_file_name`] is set, scans also prune the
+/// files that can not match the range filters on the column, such as
+/// `ts >= '2024-01-01'`, without the statistics of the files.
+///
+/// The declaration is trusted: queries return wrong results if the files
+/// are not ordered.
+///
+/// [`ListingTable`]: crate::ListingTable
+/// [`ListingOptions::file_sort_order`]: crate::ListingOptions::file_sort_order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOrdering {
+    column: String,
+    descending: bool,
+    first_value_in_file_name: bool,
+}
+
+impl FileOrdering {
+    /// Declare that the files are sorted in ascending order of `column`
+    pub fn new(column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            descending: false,
+            first_value_in_file_name: false,
+        }
+    }
+
+    /// Set whether the files are sorted in descending order of the column,
+    /// with nulls first
+    pub fn with_descending(mut self, descending: bool) -> Self {
+        self.descending = descending;
+        self
+    }
+
+    /// Set whether the name of each file, without the file extension of the
+    /// table, is the first value of the column in the file, for example
+    /// `2024-01-01T00:00:00.parquet`. The names are cast to the type of the
+    /// column, and the files whose names can not be cast are not pruned.
+    pub fn with_first_value_in_file_name(
+        mut self,
+        first_value_in_file_name: bool,
+    ) -> Self {
+        self.first_value_in_file_name = first_value_in_file_name;
+        self
+    }
+
+    /// The column the files are sorted by
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// Whether the files are sorted in descending order of the column
+    pub fn descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Whether the name of each file is the first value of the column in
+    /// the file
+    pub fn first_value_in_file_name(&self) -> bool {
+        self.first_value_in_file_name
+    }
+
+    /// The ordering of the files
+    pub fn sort_expr(&self) -> SortExpr {
+        Expr::Column(Column::from_name(&self.column))
+            .sort(!self.descending, self.descending)
+    }
+
+    /// Returns the files that may contain rows matching the range `filters`
+    /// on the column, whose type is `data_type`, sorted by path
+    pub(crate) fn prune_files(
+        &self,
+        mut files: Vec<PartitionedFile>,
+        filters: &[Expr],
+        data_type: &DataType,
+        file_extension: &str,
+    ) -> Vec<PartitionedFile> {
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        if !self.first_value_in_file_name {
+            return files;
+        }
+        let ranges = self.range_filters(filters, data_type);
+        if ranges.is_empty() {
+            return files;
+        }
+
+        let first_values = files
+            .iter()
+            .map(|file| {
+                let name = file.object_meta.location.filename().unwrap_or_default();
+                let name = name.strip_suffix(file_extension).unwrap_or(name);
+                // the file extensions of formats do not start with a dot
+                let name = name.strip_suffix('.').unwrap_or(name);
+                ScalarValue::Utf8(Some(name.to_string()))
+                    .cast_to(data_type)
+                    .ok()
+                    .filter(|value| !value.is_null())
+            })
+            .collect::<Vec<_>>();
+        files
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                // the values of a file are between its first value and the
+                // first value of the next file
+                let first = first_values[*i].as_ref();
+                let next = first_values.get(i + 1).and_then(Option::as_ref);
+                let (min, max) = if self.descending {
+                    (next, first)
+                } else {
+                    (first, next)
+                };
+                ranges
+                    .iter()
+                    .all(|(op, value)| may_match(*op, value, min, max))
+            })
+            .map(|(_, file)| file)
+            .collect()
+    }
+
+    /// The filters of the form `column <op> literal`, with the literal cast
+    /// to `data_type`
+    fn range_filters(
+        &self,
+        filters: &[Expr],
+        data_type: &DataType,
+    ) -> Vec<(Operator, ScalarValue)> {
+        filters
+            .iter()
+            .flat_map(split_conjunction)
+            .filter_map(|filter| {
+                let Expr::BinaryExpr(BinaryExpr { left, op, right }) = filter else {
+                    return None;
+                };
+                let (op, value) = match (left.as_ref(), right.as_ref()) {
+                    (Expr::Column(column), Expr::Literal(value, _))
+                        if column.name == self.column =>
+                    {
+                        (*op, value)
+                    }
+                    (Expr::Literal(value, _), Expr::Column(column))
+                        if column.name == self.column =>
+                    {
+                        (op.swap()?, value)
+                    }
+                    _ => return None,
+                };
+                let value = value.cast_to(data_type).ok()?;
+                (!value.is_null()).then_some((op, value))
+            })
+            .collect()
+    }
+}
+
+/// Returns true if values between `min` and `max`, inclusive, may match
+/// `column <op> value`, where a missing bound is unbounded
+fn may_match(
+    op: Operator,
+    value: &ScalarValue,
+    min: Option<&ScalarValue>,
+    max: Option<&ScalarValue>,
+) -> bool {
+    let compare = |bound: Option<&ScalarValue>, matches: fn(Ordering) -> bool| {
+        bound
+            .and_then(|bound| bound.partial_cmp(value))
+            .is_none_or(matches)
+    };
+    match op {
+        Operator::Eq => compare(min, Ordering::is_le) && compare(max, Ordering::is_ge),
+        Operator::Lt => compare(min, Ordering::is_lt),
+        Operator::LtEq => compare(min, Ordering::is_le),
+        Operator::Gt => compare(max, Ordering::is_gt),
+        Operator::GtEq => compare(max, Ordering::is_ge),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use datafusion_expr::{col, lit};
+
+    fn file_names(files: &[PartitionedFile]) -> Vec<&str> {
+        files
+            .iter()
+            .map(|file| file.object_meta.location.filename().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn prune_files_by_first_value_in_file_name() {
+        let files = ["30.csv", "10.csv", "20.csv", "x.csv"]
+            .into_iter()
+            .map(|name| PartitionedFile::new(format!("t/{name}"), 10))
+            .collect::<Vec<_>>();
+        let ordering = FileOrdering::new("a").with_first_value_in_file_name(true);
+        let prune = |filters: &[Expr]| {
+            let files =
+                ordering.prune_files(files.clone(), filters, &DataType::Int64, ".csv");
+            file_names(&files)
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+
+        // the files are sorted by path
+        assert_eq!(prune(&[]), ["10.csv", "20.csv", "30.csv", "x.csv"]);
+        // `10.csv` has values of at most 20, `30.csv` has values of at least 30,
+        // and the values of `x.csv` are unknown
+        assert_eq!(
+            prune(&[col("a").gt(lit(20)).and(col("a").lt(lit(30)))]),
+            ["20.csv", "x.csv"]
+        );
+        assert_eq!(prune(&[lit(20).gt(col("a"))]), ["10.csv", "x.csv"]);
+        // the values of `10.csv` are at most 20
+        assert_eq!(prune(&[col("a").eq(lit(25))]), ["20.csv", "x.csv"]);
+        assert_eq!(
+            prune(&[col("a").eq(lit(20))]),
+            ["10.csv", "20.csv", "x.csv"]
+        );
+        // other filters do not prune files
+        assert_eq!(
+            prune(&[col("b").gt(lit(20)), col("a").not_eq(lit(10))]),
+            ["10.csv", "20.csv", "30.csv", "x.csv"]
+        );
+
+        // the file extension may not start with a dot
+        let files = ordering.prune_files(
+            files.clone(),
+            &[col("a").gt(lit(20))],
+            &DataType::Int64,
+            "csv",
+        );
+        assert_eq!(file_names(&files), ["20.csv", "30.csv", "x.csv"]);
+
+        // in descending order, the values of `-5.csv` are between -9 and -5
+        let ordering = ordering.with_descending(true);
+        let files = ["-1.csv", "-5.csv", "-9.csv"]
+            .into_iter()
+            .map(|name| PartitionedFile::new(format!("t/{name}"), 10))
+            .collect();
+        let files = ordering.prune_files(
+            files,
+            &[col("a").gt(lit(-5))],
+            &DataType::Int64,
+            ".csv",
+        );
+        assert_eq!(file_names(&files), ["-1.csv"]);
+    }
+}
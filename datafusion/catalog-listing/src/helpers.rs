@@ -24,13 +24,15 @@ use datafusion_catalog::Session;
 use datafusion_common::{HashMap, Result, ScalarValue, assert_or_internal_err};
 use datafusion_datasource::ListingTableUrl;
 use datafusion_datasource::PartitionedFile;
+use datafusion_expr::expr::{Cast, ScalarFunction};
 use datafusion_expr::{BinaryExpr, Operator, lit, utils};
 
 use arrow::{
     array::AsArray,
-    datatypes::{DataType, Field},
+    datatypes::{DataType, Date32Type, Field},
     record_batch::RecordBatch,
 };
+use chrono::Datelike;
 use datafusion_expr::execution_props::ExecutionProps;
 use futures::stream::FuturesUnordered;
 use futures::{StreamExt, TryStreamExt, stream::BoxStream};
@@ -261,38 +263,233 @@ enum PartitionValue {
 
 fn populate_partition_values<'a>(
     partition_values: &mut HashMap<&'a str, PartitionValue>,
+    partition_cols: &[(String, DataType)],
     filter: &'a Expr,
 ) {
     if let Expr::BinaryExpr(BinaryExpr { left, op, right }) = filter {
         match op {
-            Operator::Eq => match (left.as_ref(), right.as_ref()) {
-                (Expr::Column(Column { name, .. }), Expr::Literal(val, _))
-                | (Expr::Literal(val, _), Expr::Column(Column { name, .. })) => {
+            Operator::Eq => {
+                let values = match (left.as_ref(), right.as_ref()) {
+                    (expr, Expr::Literal(val, _)) | (Expr::Literal(val, _), expr) => {
+                        invert_partition_expr(expr, val, partition_cols)
+                    }
+                    _ => None,
+                };
+                for (name, val) in values.into_iter().flatten() {
                     if partition_values
-                        .insert(name, PartitionValue::Single(val.to_string()))
+                        .insert(name, PartitionValue::Single(val))
                         .is_some()
                     {
                         partition_values.insert(name, PartitionValue::Multi);
                     }
                 }
-                _ => {}
-            },
+            }
             Operator::And => {
-                populate_partition_values(partition_values, left);
-                populate_partition_values(partition_values, right);
+                populate_partition_values(partition_values, partition_cols, left);
+                populate_partition_values(partition_values, partition_cols, right);
             }
             _ => {}
         }
     }
 }
 
+/// Inverts `expr = value` into the values of the partition columns that
+/// `expr` is built from, e.g. `concat(year, '-', month) = '2024-03'` into
+/// `year = '2024'` and `month = '03'`.
+///
+/// Returns `None` if the values of the columns are not uniquely determined
+/// by `value`.
+fn invert_partition_expr<'a>(
+    expr: &'a Expr,
+    value: &ScalarValue,
+    partition_cols: &[(String, DataType)],
+) -> Option<Vec<(&'a str, String)>> {
+    match expr {
+        Expr::Column(Column { name, .. }) => {
+            Some(vec![(name.as_str(), value.to_string())])
+        }
+        Expr::ScalarFunction(ScalarFunction { func, args }) => match func.name() {
+            "concat" => invert_concat(args, value, partition_cols),
+            "make_date" => invert_make_date(args, value, partition_cols),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A part of a `concat` over partition columns
+#[derive(Debug, Clone, Copy)]
+enum ConcatSegment<'a> {
+    Column(&'a str),
+    Literal(&'a str),
+}
+
+/// Inverts `concat(args) = value`, where the arguments are string partition
+/// columns and string literals
+fn invert_concat<'a>(
+    args: &'a [Expr],
+    value: &ScalarValue,
+    partition_cols: &[(String, DataType)],
+) -> Option<Vec<(&'a str, String)>> {
+    let (ScalarValue::Utf8(Some(value))
+    | ScalarValue::LargeUtf8(Some(value))
+    | ScalarValue::Utf8View(Some(value))) = value
+    else {
+        return None;
+    };
+
+    let segments = args
+        .iter()
+        .filter_map(|arg| match arg {
+            Expr::Literal(
+                ScalarValue::Utf8(Some(s))
+                | ScalarValue::LargeUtf8(Some(s))
+                | ScalarValue::Utf8View(Some(s)),
+                _,
+            ) => (!s.is_empty()).then_some(Some(ConcatSegment::Literal(s))),
+            _ => Some(
+                string_partition_column(arg, partition_cols).map(ConcatSegment::Column),
+            ),
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    // the boundary between two adjacent columns is not known
+    if segments
+        .windows(2)
+        .any(|w| matches!(w, [ConcatSegment::Column(_), ConcatSegment::Column(_)]))
+    {
+        return None;
+    }
+
+    let mut splits = vec![];
+    split_concat(&segments, value, &mut vec![], &mut splits);
+    // the values are only determined if there is a single way to split `value`
+    if splits.len() == 1 {
+        splits.pop()
+    } else {
+        None
+    }
+}
+
+/// Collects the ways to split `value` into the values of the column
+/// segments, stopping once two splits are found
+fn split_concat<'a>(
+    segments: &[ConcatSegment<'a>],
+    value: &str,
+    current: &mut Vec<(&'a str, String)>,
+    splits: &mut Vec<Vec<(&'a str, String)>>,
+) {
+    if splits.len() > 1 {
+        return;
+    }
+    match segments {
+        [] => {
+            if value.is_empty() {
+                splits.push(current.clone());
+            }
+        }
+        [ConcatSegment::Literal(s), rest @ ..] => {
+            if let Some(value) = value.strip_prefix(s) {
+                split_concat(rest, value, current, splits);
+            }
+        }
+        [ConcatSegment::Column(name), rest @ ..] => {
+            // the column ends at an occurrence of the next literal, or at the
+            // end of `value` if it is the last segment
+            let ends: Vec<usize> = match rest.first() {
+                Some(ConcatSegment::Literal(next)) => (0..=value.len())
+                    .filter(|i| {
+                        value.is_char_boundary(*i) && value[*i..].starts_with(next)
+                    })
+                    .collect(),
+                _ => vec![value.len()],
+            };
+            for end in ends {
+                current.push((*name, value[..end].to_string()));
+                split_concat(rest, &value[end..], current, splits);
+                current.pop();
+            }
+        }
+    }
+}
+
+/// Inverts `make_date(year, month, day) = value`, where the arguments are
+/// integer partition columns and integer literals
+fn invert_make_date<'a>(
+    args: &'a [Expr],
+    value: &ScalarValue,
+    partition_cols: &[(String, DataType)],
+) -> Option<Vec<(&'a str, String)>> {
+    let ScalarValue::Date32(Some(days)) = value else {
+        return None;
+    };
+    let date = Date32Type::to_naive_date_opt(*days)?;
+    let parts = [date.year(), date.month() as i32, date.day() as i32];
+
+    let mut values = vec![];
+    for (arg, part) in args.iter().zip(parts) {
+        if let Expr::Literal(val, _) = arg {
+            // a literal that does not match the date makes the filter always
+            // false, which is left to the evaluation of the filter
+            if val.cast_to(&DataType::Int32).ok()? != ScalarValue::Int32(Some(part)) {
+                return None;
+            }
+        } else {
+            let name = integer_partition_column(arg, partition_cols)?;
+            values.push((name, part.to_string()));
+        }
+    }
+    Some(values)
+}
+
+/// Returns the name of the string partition column `expr` refers to, if any,
+/// looking through casts to strings
+fn string_partition_column<'a>(
+    expr: &'a Expr,
+    partition_cols: &[(String, DataType)],
+) -> Option<&'a str> {
+    partition_column(expr, partition_cols, |data_type| {
+        let data_type = match data_type {
+            DataType::Dictionary(_, value_type) => value_type.as_ref(),
+            data_type => data_type,
+        };
+        data_type.is_string()
+    })
+}
+
+/// Returns the name of the integer partition column `expr` refers to, if
+/// any, looking through casts to integers
+fn integer_partition_column<'a>(
+    expr: &'a Expr,
+    partition_cols: &[(String, DataType)],
+) -> Option<&'a str> {
+    partition_column(expr, partition_cols, DataType::is_integer)
+}
+
+fn partition_column<'a>(
+    expr: &'a Expr,
+    partition_cols: &[(String, DataType)],
+    has_type: impl Fn(&DataType) -> bool,
+) -> Option<&'a str> {
+    match expr {
+        Expr::Cast(Cast { expr, field }) if has_type(field.data_type()) => {
+            partition_column(expr, partition_cols, has_type)
+        }
+        Expr::Column(Column { name, .. }) => partition_cols
+            .iter()
+            .any(|(col, data_type)| col == name && has_type(data_type))
+            .then_some(name.as_str()),
+        _ => None,
+    }
+}
+
 pub fn evaluate_partition_prefix<'a>(
     partition_cols: &'a [(String, DataType)],
     filters: &'a [Expr],
 ) -> Option<Path> {
     let mut partition_values = HashMap::new();
     for filter in filters {
-        populate_partition_values(&mut partition_values, filter);
+        populate_partition_values(&mut partition_values, partition_cols, filter);
     }
 
     if partition_values.is_empty() {
@@ -322,7 +519,21 @@ pub fn evaluate_partition_prefix<'a>(
     }
 }
 
-fn filter_partitions(
+/// The schema of the partition columns, used to evaluate filters on the
+/// partition values of a file
+pub(crate) fn partition_cols_schema(
+    partition_cols: &[(String, DataType)],
+) -> Result<DFSchema> {
+    DFSchema::from_unqualified_fields(
+        partition_cols
+            .iter()
+            .map(|(n, d)| Field::new(n, d.clone(), true))
+            .collect(),
+        Default::default(),
+    )
+}
+
+pub(crate) fn filter_partitions(
     pf: PartitionedFile,
     filters: &[Expr],
     df_schema: &DFSchema,
@@ -419,13 +630,7 @@ pub async fn pruned_partition_list<'a>(
         // if no partition col => simply list all the files
         Ok(objects.map_ok(|object_meta| object_meta.into()).boxed())
     } else {
-        let df_schema = DFSchema::from_unqualified_fields(
-            partition_cols
-                .iter()
-                .map(|(n, d)| Field::new(n, d.clone(), true))
-                .collect(),
-            Default::default(),
-        )?;
+        let df_schema = partition_cols_schema(partition_cols)?;
 
         Ok(objects
             .try_filter_map(|object_meta| {
@@ -487,7 +692,9 @@ mod tests {
     use std::ops::Not;
 
     use super::*;
-    use datafusion_expr::{case, col};
+    use datafusion_expr::{case, cast, col};
+    use datafusion_functions::datetime::expr_fn::make_date;
+    use datafusion_functions::string::expr_fn::concat;
 
     #[test]
     fn test_split_files() {
@@ -868,4 +1075,134 @@ mod tests {
             Some(Path::from("a=1970-01-05")),
         );
     }
+
+    #[test]
+    fn test_evaluate_concat_partition_prefix() {
+        let partitions = &[
+            ("year".to_string(), DataType::Utf8),
+            (
+                "month".to_string(),
+                DataType::Dictionary(
+                    Box::new(DataType::UInt16),
+                    Box::new(DataType::Utf8),
+                ),
+            ),
+            ("day".to_string(), DataType::Int32),
+        ];
+        let month = || cast(col("month"), DataType::Utf8);
+
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[concat(vec![col("year"), lit("-"), month()]).eq(lit("2024-03"))],
+            ),
+            Some(Path::from("year=2024/month=03")),
+        );
+
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[lit("2024-03-01").eq(concat(vec![
+                    col("year"),
+                    lit("-"),
+                    month(),
+                    lit("-01"),
+                ]))],
+            ),
+            Some(Path::from("year=2024/month=03")),
+        );
+
+        // the literal does not match
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[concat(vec![col("year"), lit("-"), month(), lit("-01")])
+                    .eq(lit("2024-03-02"))],
+            ),
+            None,
+        );
+
+        // the separator occurs in the value in more than one place
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[concat(vec![col("year"), lit("-"), month()]).eq(lit("2024-03-01"))],
+            ),
+            None,
+        );
+
+        // adjacent columns can be split anywhere
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[concat(vec![col("year"), month()]).eq(lit("202403"))],
+            ),
+            None,
+        );
+
+        // the string representation of an integer column is not unique
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[concat(vec![
+                    col("year"),
+                    lit("-"),
+                    month(),
+                    lit("-"),
+                    cast(col("day"), DataType::Utf8),
+                ])
+                .eq(lit("2024-03-01"))],
+            ),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_evaluate_make_date_partition_prefix() {
+        let partitions = &[
+            ("year".to_string(), DataType::Int32),
+            ("month".to_string(), DataType::Int64),
+            ("day".to_string(), DataType::Utf8),
+        ];
+        let date = |days| Expr::Literal(ScalarValue::Date32(Some(days)), None);
+        // 2024-03-01
+        let days = 19783;
+
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[
+                    make_date(col("year"), cast(col("month"), DataType::Int32), lit(1))
+                        .eq(date(days))
+                ],
+            ),
+            Some(Path::from("year=2024/month=3")),
+        );
+
+        // the day does not match
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[
+                    make_date(col("year"), cast(col("month"), DataType::Int32), lit(2))
+                        .eq(date(days))
+                ],
+            ),
+            None,
+        );
+
+        // string columns may be zero padded
+        assert_eq!(
+            evaluate_partition_prefix(
+                partitions,
+                &[make_date(
+                    col("year"),
+                    cast(col("month"), DataType::Int32),
+                    cast(col("day"), DataType::Int32)
+                )
+                .eq(date(days))],
+            ),
+            None,
+        );
+    }
 }
@@ -0,0 +1,410 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FileManifest`]: an explicit list of the files of a [`ListingTable`]
+//!
+//! [`ListingTable`]: crate::ListingTable
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Schema, TimeUnit, TimestampNanosecondType, UInt64Type};
+use chrono::{TimeZone, Utc};
+use datafusion_common::stats::Precision;
+use datafusion_common::{
+    ColumnStatistics, Result, ScalarValue, Statistics, assert_or_internal_err,
+    plan_datafusion_err, plan_err,
+};
+use datafusion_datasource::PartitionedFile;
+use datafusion_expr::Expr;
+
+use crate::helpers::{filter_partitions, partition_cols_schema};
+
+/// Name of the manifest column holding the location of each file
+const PATH_COLUMN: &str = "path";
+/// Name of the manifest column holding the size in bytes of each file
+const SIZE_COLUMN: &str = "size";
+/// Name of the manifest column holding the last modification time of each file
+const LAST_MODIFIED_COLUMN: &str = "last_modified";
+/// Name of the manifest column holding the number of rows of each file
+const NUM_ROWS_COLUMN: &str = "num_rows";
+
+/// An explicit list of the files of a [`ListingTable`], with their sizes,
+/// partition values and optional statistics.
+///
+/// A [`ListingTable`] configured with a manifest (see
+/// [`ListingTableConfig::with_manifest`]) scans the files of the manifest
+/// instead of listing its table paths, so no LIST request is sent to the
+/// object store. This is useful for tables with many files whose file list
+/// is already known, for example from a metastore.
+///
+/// Files are still pruned with the filters on the partition columns. The
+/// statistics of the manifest are used even if [`ListingOptions::collect_stat`]
+/// is not set, while files without statistics have them collected from the
+/// files as for a listed file if it is set.
+///
+/// [`ListingTable`]: crate::ListingTable
+/// [`ListingTableConfig::with_manifest`]: crate::ListingTableConfig::with_manifest
+/// [`ListingOptions::collect_stat`]: crate::ListingOptions::collect_stat
+#[derive(Debug, Clone, Default)]
+pub struct FileManifest {
+    files: Vec<PartitionedFile>,
+}
+
+impl FileManifest {
+    /// Create a manifest from a list of files.
+    ///
+    /// The files are located in the object store of the first table path of
+    /// the table. Their `partition_values` must match the table partition
+    /// columns, and their `statistics`, if any, must cover the table schema
+    /// including the partition columns, as set by
+    /// [`PartitionedFile::with_statistics`].
+    pub fn new(files: Vec<PartitionedFile>) -> Self {
+        Self { files }
+    }
+
+    /// Create a manifest from record batches, for example read from a JSON
+    /// or Parquet manifest file.
+    ///
+    /// Each row describes one file with the following columns:
+    /// * `path` (required): the location of the file in the object store
+    /// * `size` (required): the size of the file in bytes
+    /// * `last_modified` (optional): the last modification time of the file
+    /// * one column named after each of the `partition_cols` (required)
+    /// * `num_rows` (optional): the number of rows of the file
+    /// * `<column>.min`, `<column>.max` and `<column>.null_count` (optional):
+    ///   the statistics of each column of `file_schema`
+    ///
+    /// Values are cast to the expected types, e.g. partition values and
+    /// statistics may be strings. Files have statistics only if the manifest
+    /// has a `num_rows` or a column statistics column, and null statistics
+    /// are unknown.
+    pub fn try_from_batches(
+        batches: &[RecordBatch],
+        file_schema: &Schema,
+        partition_cols: &[(String, DataType)],
+    ) -> Result<Self> {
+        let mut files = vec![];
+        for batch in batches {
+            read_batch(batch, file_schema, partition_cols, &mut files)?;
+        }
+        Ok(Self::new(files))
+    }
+
+    /// The files of the manifest
+    pub fn files(&self) -> &[PartitionedFile] {
+        &self.files
+    }
+
+    /// Number of files in the manifest
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns true if the manifest has no file
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns an error if the partition values of a file do not match
+    /// `partition_cols`
+    pub(crate) fn validate(&self, partition_cols: &[(String, DataType)]) -> Result<()> {
+        for file in &self.files {
+            if file.partition_values.len() != partition_cols.len() {
+                return plan_err!(
+                    "File {} of the manifest has {} partition values, expected {}",
+                    file.object_meta.location,
+                    file.partition_values.len(),
+                    partition_cols.len()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the files whose partition values match `filters`, which
+    /// should only reference the partition columns
+    pub(crate) fn pruned_files(
+        &self,
+        filters: &[Expr],
+        partition_cols: &[(String, DataType)],
+    ) -> Result<Vec<PartitionedFile>> {
+        if partition_cols.is_empty() {
+            assert_or_internal_err!(
+                filters.is_empty(),
+                "Got partition filters for unpartitioned table"
+            );
+            return Ok(self.files.clone());
+        }
+
+        let df_schema = partition_cols_schema(partition_cols)?;
+        self.files
+            .iter()
+            .cloned()
+            .filter_map(|file| filter_partitions(file, filters, &df_schema).transpose())
+            .collect()
+    }
+}
+
+/// The statistics columns of the manifest for one column of the file schema
+struct ManifestColumnStatistics {
+    min: Option<ArrayRef>,
+    max: Option<ArrayRef>,
+    null_count: Option<ArrayRef>,
+}
+
+impl ManifestColumnStatistics {
+    fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none() && self.null_count.is_none()
+    }
+
+    fn statistics(&self, row: usize) -> Result<ColumnStatistics> {
+        Ok(ColumnStatistics {
+            null_count: exact_count(self.null_count.as_ref(), row),
+            max_value: exact_value(self.max.as_ref(), row)?,
+            min_value: exact_value(self.min.as_ref(), row)?,
+            ..ColumnStatistics::new_unknown()
+        })
+    }
+}
+
+fn read_batch(
+    batch: &RecordBatch,
+    file_schema: &Schema,
+    partition_cols: &[(String, DataType)],
+    files: &mut Vec<PartitionedFile>,
+) -> Result<()> {
+    let paths = required_column(batch, PATH_COLUMN, &DataType::Utf8)?;
+    let paths = paths.as_string::<i32>();
+    let sizes = required_column(batch, SIZE_COLUMN, &DataType::UInt64)?;
+    let sizes = sizes.as_primitive::<UInt64Type>();
+    let last_modified = column(
+        batch,
+        LAST_MODIFIED_COLUMN,
+        &DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+    )?;
+    let partition_values = partition_cols
+        .iter()
+        .map(|(name, data_type)| required_column(batch, name, data_type))
+        .collect::<Result<Vec<_>>>()?;
+    let num_rows = column(batch, NUM_ROWS_COLUMN, &DataType::UInt64)?;
+    let column_statistics = file_schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let name = field.name();
+            Ok(ManifestColumnStatistics {
+                min: column(batch, &format!("{name}.min"), field.data_type())?,
+                max: column(batch, &format!("{name}.max"), field.data_type())?,
+                null_count: column(
+                    batch,
+                    &format!("{name}.null_count"),
+                    &DataType::UInt64,
+                )?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let has_statistics =
+        num_rows.is_some() || column_statistics.iter().any(|c| !c.is_empty());
+
+    for row in 0..batch.num_rows() {
+        if paths.is_null(row) || sizes.is_null(row) {
+            return plan_err!("File {row} of the manifest has no path or size");
+        }
+        let path = paths.value(row);
+        let mut file = PartitionedFile::new(path, sizes.value(row));
+        if let Some(last_modified) = &last_modified
+            && last_modified.is_valid(row)
+        {
+            let nanos = last_modified
+                .as_primitive::<TimestampNanosecondType>()
+                .value(row);
+            file.object_meta.last_modified = Utc.timestamp_nanos(nanos);
+        }
+        file.partition_values = partition_values
+            .iter()
+            .zip(partition_cols)
+            .map(|(values, (name, _))| {
+                if values.is_null(row) {
+                    return plan_err!(
+                        "Partition column {name} of file {path} of the manifest is null"
+                    );
+                }
+                ScalarValue::try_from_array(values, row)
+            })
+            .collect::<Result<_>>()?;
+
+        if has_statistics {
+            let statistics = Statistics {
+                num_rows: exact_count(num_rows.as_ref(), row),
+                total_byte_size: Precision::Absent,
+                column_statistics: column_statistics
+                    .iter()
+                    .map(|c| c.statistics(row))
+                    .collect::<Result<_>>()?,
+            };
+            file = file.with_statistics(Arc::new(statistics));
+        }
+        files.push(file);
+    }
+    Ok(())
+}
+
+/// Returns the column `name` of `batch` cast to `data_type`, if any
+fn column(
+    batch: &RecordBatch,
+    name: &str,
+    data_type: &DataType,
+) -> Result<Option<ArrayRef>> {
+    batch
+        .column_by_name(name)
+        .map(|values| Ok(cast(values, data_type)?))
+        .transpose()
+}
+
+fn required_column(
+    batch: &RecordBatch,
+    name: &str,
+    data_type: &DataType,
+) -> Result<ArrayRef> {
+    column(batch, name, data_type)?
+        .ok_or_else(|| plan_datafusion_err!("The manifest has no '{name}' column"))
+}
+
+/// `values` must be a `UInt64` array
+fn exact_count(values: Option<&ArrayRef>, row: usize) -> Precision<usize> {
+    match values {
+        Some(values) if values.is_valid(row) => {
+            Precision::Exact(values.as_primitive::<UInt64Type>().value(row) as usize)
+        }
+        _ => Precision::Absent,
+    }
+}
+
+fn exact_value(values: Option<&ArrayRef>, row: usize) -> Result<Precision<ScalarValue>> {
+    match values {
+        Some(values) if values.is_valid(row) => {
+            Ok(Precision::Exact(ScalarValue::try_from_array(values, row)?))
+        }
+        _ => Ok(Precision::Absent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::Field;
+    use datafusion_expr::{col, lit};
+
+    fn manifest_batch() -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            (
+                "path",
+                Arc::new(StringArray::from(vec!["t/a.parquet", "t/b.parquet"]))
+                    as ArrayRef,
+            ),
+            ("size", Arc::new(Int64Array::from(vec![100, 200]))),
+            (
+                "last_modified",
+                Arc::new(StringArray::from(vec![Some("2024-01-01T00:00:00Z"), None])),
+            ),
+            ("year", Arc::new(StringArray::from(vec!["2023", "2024"]))),
+            ("num_rows", Arc::new(Int64Array::from(vec![Some(10), None]))),
+            ("id.min", Arc::new(StringArray::from(vec!["1", "11"]))),
+            ("id.max", Arc::new(Int64Array::from(vec![Some(10), None]))),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn manifest_from_batches() -> Result<()> {
+        let file_schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        let partition_cols = vec![("year".to_string(), DataType::Int32)];
+        let manifest = FileManifest::try_from_batches(
+            &[manifest_batch()],
+            &file_schema,
+            &partition_cols,
+        )?;
+        manifest.validate(&partition_cols)?;
+        assert_eq!(manifest.len(), 2);
+
+        let [a, b] = manifest.files() else {
+            unreachable!()
+        };
+        assert_eq!(a.object_meta.location.as_ref(), "t/a.parquet");
+        assert_eq!(a.object_meta.size, 100);
+        assert_eq!(a.object_meta.last_modified.timestamp(), 1704067200);
+        assert_eq!(a.partition_values, vec![ScalarValue::Int32(Some(2023))]);
+        assert_eq!(b.object_meta.last_modified.timestamp(), 0);
+
+        let a_stats = a.statistics.as_ref().unwrap();
+        assert_eq!(a_stats.num_rows, Precision::Exact(10));
+        // The file column, then the partition column
+        assert_eq!(a_stats.column_statistics.len(), 2);
+        assert_eq!(
+            a_stats.column_statistics[0].min_value,
+            Precision::Exact(ScalarValue::Int32(Some(1)))
+        );
+        assert_eq!(
+            a_stats.column_statistics[0].max_value,
+            Precision::Exact(ScalarValue::Int32(Some(10)))
+        );
+        assert_eq!(
+            a_stats.column_statistics[1].min_value,
+            Precision::Exact(ScalarValue::Int32(Some(2023)))
+        );
+
+        let b_stats = b.statistics.as_ref().unwrap();
+        assert_eq!(b_stats.num_rows, Precision::Absent);
+        assert_eq!(b_stats.column_statistics[0].max_value, Precision::Absent);
+
+        // Files are pruned with filters on the partition columns
+        let pruned =
+            manifest.pruned_files(&[col("year").gt(lit(2023))], &partition_cols)?;
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(pruned[0].object_meta.location.as_ref(), "t/b.parquet");
+
+        // The partition columns must match those of the table
+        let err = manifest.validate(&[]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("has 1 partition values, expected 0")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn manifest_missing_columns() {
+        let file_schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        let batch = manifest_batch().project(&[0, 2]).unwrap();
+        let err =
+            FileManifest::try_from_batches(&[batch], &file_schema, &[]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("The manifest has no 'size' column")
+        );
+
+        // Without statistics columns files have no statistics
+        let batch = manifest_batch().project(&[0, 1, 3]).unwrap();
+        let manifest =
+            FileManifest::try_from_batches(&[batch], &file_schema, &[]).unwrap();
+        assert!(manifest.files().iter().all(|f| f.statistics.is_none()));
+    }
+}
@@ -25,11 +25,18 @@
 // https://github.com/apache/datafusion/issues/11143
 #![cfg_attr(not(test), deny(clippy::clone_on_ref_ptr))]
 
+pub mod changes;
 mod config;
+mod file_ordering;
 pub mod helpers;
+mod manifest;
 mod options;
+mod schema_report;
 mod table;
 
 pub use config::{ListingTableConfig, SchemaSource};
-pub use options::ListingOptions;
-pub use table::{ListFilesResult, ListingTable};
+pub use file_ordering::FileOrdering;
+pub use manifest::FileManifest;
+pub use options::{ListingOptions, merge_evolved_schemas};
+pub use schema_report::{SchemaDifference, SchemaDifferenceKind};
+pub use table::{ListFilesResult, ListingTable, MetadataFetchProgress};
@@ -15,16 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use arrow::datatypes::{DataType, SchemaRef};
+use crate::FileOrdering;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion_catalog::Session;
 use datafusion_common::plan_err;
 use datafusion_datasource::ListingTableUrl;
 use datafusion_datasource::file_format::FileFormat;
+use datafusion_datasource::metadata_column::FileMetadataColumn;
 use datafusion_execution::config::SessionConfig;
 use datafusion_expr::SortExpr;
+use datafusion_expr::type_coercion::binary::type_union_resolution;
 use futures::StreamExt;
 use futures::TryStreamExt;
 use itertools::Itertools;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Options for creating a [`crate::ListingTable`]
@@ -61,6 +65,15 @@ pub struct ListingOptions {
     ///       multiple equivalent orderings, the outer `Vec` will have a
     ///       single element.
     pub file_sort_order: Vec<Vec<SortExpr>>,
+    /// Optional declaration that the files are sorted by a column and do not
+    /// overlap. See [Self::with_file_ordering] for details
+    pub file_ordering: Option<FileOrdering>,
+    /// The hidden metadata columns of the table.
+    /// See [Self::with_metadata_columns] for details
+    pub metadata_columns: Vec<FileMetadataColumn>,
+    /// Set true to merge the schemas of files that have evolved over time.
+    /// See [Self::with_schema_evolution] for details
+    pub schema_evolution: bool,
 }
 
 impl ListingOptions {
@@ -78,6 +91,9 @@ impl ListingOptions {
             collect_stat: false,
             target_partitions: 1,
             file_sort_order: vec![],
+            file_ordering: None,
+            metadata_columns: vec![],
+            schema_evolution: false,
         }
     }
 
@@ -205,6 +221,36 @@ impl ListingOptions {
         self
     }
 
+    /// Set the hidden metadata columns on [`ListingOptions`] and returns self.
+    ///
+    /// Metadata columns, such as the path of the file each row was read
+    /// from, are not part of `SELECT *` but can be referenced by name. They
+    /// are only read when referenced, after the partition columns.
+    ///
+    /// The positions of the rows in their files, [`FileMetadataColumn::RowNumber`],
+    /// can only be read from formats decoding all the rows of the files,
+    /// such as CSV or JSON, and not from Parquet.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion_catalog_listing::ListingOptions;
+    /// # use datafusion_datasource::metadata_column::FileMetadataColumn;
+    /// # use datafusion_datasource_parquet::file_format::ParquetFormat;
+    ///
+    /// // `SELECT _file, count(*) FROM t GROUP BY _file` counts the rows of each file
+    /// let listing_options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+    ///     .with_metadata_columns(vec![FileMetadataColumn::Location]);
+    ///
+    /// assert_eq!(listing_options.metadata_columns, vec![FileMetadataColumn::Location]);
+    /// ```
+    pub fn with_metadata_columns(
+        mut self,
+        metadata_columns: Vec<FileMetadataColumn>,
+    ) -> Self {
+        self.metadata_columns = metadata_columns;
+        self
+    }
+
     /// Set stat collection on [`ListingOptions`] and returns self.
     ///
     /// ```
@@ -260,10 +306,71 @@ impl ListingOptions {
         self
     }
 
+    /// Declare that the files are sorted by a column and do not overlap when
+    /// taken in the order of their paths, and returns self.
+    ///
+    /// Scans keep the files of each partition in the order of their paths,
+    /// so that they produce sorted output without the statistics of the
+    /// files, and may prune files by their names. See [`FileOrdering`].
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion_catalog_listing::{FileOrdering, ListingOptions};
+    /// # use datafusion_datasource_parquet::file_format::ParquetFormat;
+    ///
+    /// // Tell datafusion that the files, named after their first event time,
+    /// // hold increasing event times
+    /// let file_ordering =
+    ///     FileOrdering::new("event_time").with_first_value_in_file_name(true);
+    ///
+    /// let listing_options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+    ///     .with_file_ordering(file_ordering.clone());
+    ///
+    /// assert_eq!(listing_options.file_ordering, Some(file_ordering));
+    /// ```
+    pub fn with_file_ordering(mut self, file_ordering: FileOrdering) -> Self {
+        self.file_ordering = Some(file_ordering);
+        self
+    }
+
+    /// Set schema evolution on [`ListingOptions`] and returns self.
+    ///
+    /// By default, the schemas of the files are merged by the
+    /// [`FileFormat::infer_schema`] of the format, which fails if a column
+    /// has different types in different files. With schema evolution, the
+    /// schema of each file is inferred separately, and the table schema is
+    /// the union of their columns:
+    ///
+    /// - a column with different types in different files has the widest
+    ///   type, e.g. `Int64` for a column widened from `Int32`
+    /// - a column missing from some files, e.g. because it was added, dropped
+    ///   or renamed, is nullable and read as nulls from those files
+    ///
+    /// Each file is adapted to the table schema when it is scanned, see
+    /// [`PhysicalExprAdapterFactory`].
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion_catalog_listing::ListingOptions;
+    /// # use datafusion_datasource_parquet::file_format::ParquetFormat;
+    ///
+    /// let listing_options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+    ///     .with_schema_evolution(true);
+    ///
+    /// assert!(listing_options.schema_evolution);
+    /// ```
+    ///
+    /// [`PhysicalExprAdapterFactory`]: datafusion_physical_expr_adapter::PhysicalExprAdapterFactory
+    pub fn with_schema_evolution(mut self, schema_evolution: bool) -> Self {
+        self.schema_evolution = schema_evolution;
+        self
+    }
+
     /// Infer the schema of the files at the given path on the provided object store.
     ///
     /// If the table_path contains one or more files (i.e. it is a directory /
-    /// prefix of files) their schema is merged by calling [`FileFormat::infer_schema`].
+    /// prefix of files) their schema is merged by calling [`FileFormat::infer_schema`],
+    /// or with [`merge_evolved_schemas`] if [`Self::schema_evolution`] is set.
     ///
     /// Returns a `Plan` error if `table_path` contains no files at all (e.g. an
     /// empty or non-existent directory), since an inferred schema with zero
@@ -304,9 +411,27 @@ impl ListingOptions {
             .filter(|object_meta| object_meta.size > 0)
             .collect();
 
-        let schema = self.format.infer_schema(state, &store, &files).await?;
+        if !self.schema_evolution {
+            return self.format.infer_schema(state, &store, &files).await;
+        }
+
+        // Infer the schema of each file separately, in a deterministic order,
+        // as the order of the fields depends on the order of the files
+        let mut files = files;
+        files.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+        let inferences = files
+            .iter()
+            .map(|file| {
+                self.format
+                    .infer_schema(state, &store, std::slice::from_ref(file))
+            })
+            .collect::<Vec<_>>();
+        let schemas: Vec<_> = futures::stream::iter(inferences)
+            .buffered(state.config_options().execution.meta_fetch_concurrency)
+            .try_collect()
+            .await?;
 
-        Ok(schema)
+        Ok(Arc::new(merge_evolved_schemas(&schemas)?))
     }
 
     /// Infers the partition columns stored in `LOCATION` and compares
@@ -418,3 +543,111 @@ impl ListingOptions {
         }
     }
 }
+
+/// Merges the schemas of files whose schema evolved over time, see
+/// [`ListingOptions::with_schema_evolution`].
+///
+/// The merged schema has the fields of all the schemas, in the order they are
+/// first seen. A field with different types in different schemas has the
+/// widest type, and a field that is nullable or missing in any schema is
+/// nullable. Returns an error if the types of a field cannot be coerced to a
+/// common type.
+pub fn merge_evolved_schemas(schemas: &[SchemaRef]) -> datafusion_common::Result<Schema> {
+    let mut fields: Vec<Field> = vec![];
+    let mut metadata = HashMap::new();
+
+    for schema in schemas {
+        for field in schema.fields() {
+            match fields.iter_mut().find(|f| f.name() == field.name()) {
+                Some(merged) => {
+                    if merged.data_type() != field.data_type() {
+                        let Some(data_type) = type_union_resolution(&[
+                            merged.data_type().clone(),
+                            field.data_type().clone(),
+                        ]) else {
+                            return plan_err!(
+                                "Cannot merge the types {} and {} of column '{}' of the evolved schema",
+                                merged.data_type(),
+                                field.data_type(),
+                                field.name()
+                            );
+                        };
+                        merged.set_data_type(data_type);
+                    }
+                    if field.is_nullable() {
+                        merged.set_nullable(true);
+                    }
+                    let mut field_metadata = merged.metadata().clone();
+                    for (key, value) in field.metadata() {
+                        field_metadata
+                            .entry(key.clone())
+                            .or_insert_with(|| value.clone());
+                    }
+                    merged.set_metadata(field_metadata);
+                }
+                None => fields.push(field.as_ref().clone()),
+            }
+        }
+        for (key, value) in schema.metadata() {
+            metadata.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+
+    // A field missing from any schema is read as nulls from those files
+    for field in &mut fields {
+        if schemas
+            .iter()
+            .any(|schema| schema.field_with_name(field.name()).is_err())
+        {
+            field.set_nullable(true);
+        }
+    }
+
+    Ok(Schema::new_with_metadata(fields, metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_evolved_schemas_widens_and_unions() {
+        let v1 = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let v2 = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("full_name", DataType::Utf8, true),
+            Field::new("score", DataType::Float32, false),
+        ]));
+        let v3 = Arc::new(Schema::new(vec![Field::new(
+            "score",
+            DataType::Float64,
+            false,
+        )]));
+
+        let merged = merge_evolved_schemas(&[v1, v2, v3]).unwrap();
+        assert_eq!(
+            merged,
+            Schema::new(vec![
+                Field::new("id", DataType::Int64, true),
+                Field::new("name", DataType::Utf8, true),
+                Field::new("full_name", DataType::Utf8, true),
+                Field::new("score", DataType::Float64, true),
+            ])
+        );
+
+        let incompatible = Arc::new(Schema::new(vec![Field::new(
+            "id",
+            DataType::new_list(DataType::Int32, true),
+            false,
+        )]));
+        let v1 = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let err = merge_evolved_schemas(&[v1, incompatible]).unwrap_err();
+        assert!(
+            err.to_string().contains("Cannot merge the types"),
+            "unexpected error: {err}"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Differences between the schemas of the files of a [`ListingTable`] and
+//! the schema of the table
+
+use std::fmt::{self, Display};
+
+use arrow::datatypes::{DataType, SchemaRef};
+use datafusion_catalog::{Session, TableProvider};
+use datafusion_common::Result;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+
+use crate::ListingTable;
+
+/// How the schema of a file differs from the schema of its table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaDifferenceKind {
+    /// A column of the table is missing from the file
+    Missing,
+    /// The file has a column that the table does not have
+    Extra,
+    /// A column of the table has a different type in the file
+    TypeChange,
+}
+
+impl Display for SchemaDifferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "missing"),
+            Self::Extra => write!(f, "extra"),
+            Self::TypeChange => write!(f, "type_change"),
+        }
+    }
+}
+
+/// A difference between the schema of a file of a [`ListingTable`] and the
+/// schema of the table, see [`ListingTable::schema_differences`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDifference {
+    /// The location of the file
+    pub file: Path,
+    /// The name of the column
+    pub column: String,
+    /// How the file differs from the table
+    pub kind: SchemaDifferenceKind,
+    /// The type of the column in the table, if the table has it
+    pub table_type: Option<DataType>,
+    /// The type of the column in the file, if the file has it
+    pub file_type: Option<DataType>,
+}
+
+impl ListingTable {
+    /// Infer the schema of each file of this table and return how it
+    /// differs from the schema of the table, ordered by file location.
+    ///
+    /// Files whose columns and types match the table are not reported.
+    /// Partition columns, which are not stored in the files, and empty
+    /// files are ignored. This helps to find the files responsible for
+    /// schema mismatch errors when reading a table whose files were
+    /// written with different schemas.
+    pub async fn schema_differences(
+        &self,
+        state: &dyn Session,
+    ) -> Result<Vec<SchemaDifference>> {
+        let Some(url) = self.table_paths().first() else {
+            return Ok(vec![]);
+        };
+        let store = state.runtime_env().object_store(url)?;
+        let mut files = self
+            .list_files(state)
+            .await?
+            .into_iter()
+            .map(|file| file.object_meta)
+            .filter(|object_meta| object_meta.size > 0)
+            .collect::<Vec<_>>();
+        files.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+
+        let format = &self.options().format;
+        let inferences = files
+            .iter()
+            .map(|file| format.infer_schema(state, &store, std::slice::from_ref(file)))
+            .collect::<Vec<_>>();
+        let file_schemas: Vec<SchemaRef> = futures::stream::iter(inferences)
+            .buffered(state.config_options().execution.meta_fetch_concurrency)
+            .try_collect()
+            .await?;
+
+        let table_schema = self.schema();
+        let partition_cols = &self.options().table_partition_cols;
+        let table_fields = table_schema
+            .fields()
+            .iter()
+            .filter(|field| !partition_cols.iter().any(|(name, _)| name == field.name()))
+            .collect::<Vec<_>>();
+
+        let mut differences = vec![];
+        for (file, file_schema) in files.into_iter().zip(file_schemas) {
+            for table_field in &table_fields {
+                let (kind, file_type) =
+                    match file_schema.field_with_name(table_field.name()) {
+                        Err(_) => (SchemaDifferenceKind::Missing, None),
+                        Ok(file_field)
+                            if file_field.data_type() != table_field.data_type() =>
+                        {
+                            (
+                                SchemaDifferenceKind::TypeChange,
+                                Some(file_field.data_type().clone()),
+                            )
+                        }
+                        Ok(_) => continue,
+                    };
+                differences.push(SchemaDifference {
+                    file: file.location.clone(),
+                    column: table_field.name().clone(),
+                    kind,
+                    table_type: Some(table_field.data_type().clone()),
+                    file_type,
+                });
+            }
+            for file_field in file_schema.fields() {
+                if table_fields
+                    .iter()
+                    .all(|table_field| table_field.name() != file_field.name())
+                {
+                    differences.push(SchemaDifference {
+                        file: file.location.clone(),
+                        column: file_field.name().clone(),
+                        kind: SchemaDifferenceKind::Extra,
+                        table_type: None,
+                        file_type: Some(file_field.data_type().clone()),
+                    });
+                }
+            }
+        }
+        Ok(differences)
+    }
+}
@@ -17,18 +17,20 @@
 
 use crate::config::SchemaSource;
 use crate::helpers::{expr_applicable_for_cols, pruned_partition_list};
-use crate::{ListingOptions, ListingTableConfig};
+use crate::{FileManifest, FileOrdering, ListingOptions, ListingTableConfig};
 use arrow::datatypes::{Field, Schema, SchemaBuilder, SchemaRef};
 use async_trait::async_trait;
 use datafusion_catalog::{ScanArgs, ScanResult, Session, TableProvider};
 use datafusion_common::stats::Precision;
 use datafusion_common::{
-    Constraints, SchemaExt, Statistics, internal_datafusion_err, plan_err, project_schema,
+    ColumnStatistics, Constraints, SchemaExt, Statistics, internal_datafusion_err,
+    not_impl_err, plan_err, project_schema,
 };
 use datafusion_datasource::file::FileSource;
 use datafusion_datasource::file_groups::FileGroup;
 use datafusion_datasource::file_scan_config::{FileScanConfig, FileScanConfigBuilder};
 use datafusion_datasource::file_sink_config::{FileOutputMode, FileSinkConfig};
+use datafusion_datasource::metadata_column::{FileMetadataColumn, FileRowNumberColumn};
 #[expect(deprecated)]
 use datafusion_datasource::schema_adapter::SchemaAdapterFactory;
 use datafusion_datasource::{
@@ -41,14 +43,19 @@ use datafusion_expr::dml::InsertOp;
 use datafusion_expr::execution_props::ExecutionProps;
 use datafusion_expr::{Expr, TableProviderFilterPushDown, TableType};
 use datafusion_physical_expr::create_lex_ordering;
-use datafusion_physical_expr_adapter::PhysicalExprAdapterFactory;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr_adapter::{
+    DefaultPhysicalExprAdapterFactory, PhysicalExprAdapterFactory,
+};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
 use datafusion_physical_plan::ExecutionPlan;
 use datafusion_physical_plan::empty::EmptyExec;
 use futures::{Stream, StreamExt, TryStreamExt, future, stream};
-use object_store::ObjectStore;
-use std::collections::HashMap;
+use object_store::{ObjectMeta, ObjectStore};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Result of a file listing operation from [`ListingTable::list_files_for_scan`].
 #[derive(Debug)]
@@ -61,6 +68,24 @@ pub struct ListFilesResult {
     pub grouped_by_partition: bool,
 }
 
+/// Observes the progress of a [`ListingTable`] fetching the metadata of its
+/// files, such as Parquet footers, to collect their statistics when planning a
+/// scan.
+///
+/// Planning a scan over many files of a remote object store can take a long
+/// time when their statistics are not cached, even though the metadata of up to
+/// `datafusion.execution.meta_fetch_concurrency` files is fetched concurrently.
+/// Set with [`ListingTable::with_metadata_fetch_progress`], for example to
+/// report the progress of planning to users.
+pub trait MetadataFetchProgress: Debug + Send + Sync {
+    /// Called after the metadata of `file` was fetched, where `fetched` is the
+    /// number of files whose metadata was fetched so far for the scan.
+    ///
+    /// This is not called for files whose statistics are cached, or provided
+    /// by a [`FileManifest`].
+    fn file_fetched(&self, file: &ObjectMeta, fetched: usize);
+}
+
 /// Built in [`TableProvider`] that reads data from one or more files as a single table.
 ///
 /// The files are read using an  [`ObjectStore`] instance, for example from
@@ -194,6 +219,10 @@ pub struct ListingTable {
     column_defaults: HashMap<String, Expr>,
     /// Optional [`PhysicalExprAdapterFactory`] for creating physical expression adapters
     expr_adapter_factory: Option<Arc<dyn PhysicalExprAdapterFactory>>,
+    /// Optional [`FileManifest`] scanned instead of listing `table_paths`
+    manifest: Option<Arc<FileManifest>>,
+    /// Optional observer of the progress of fetching the metadata of files
+    metadata_fetch_progress: Option<Arc<dyn MetadataFetchProgress>>,
 }
 
 impl ListingTable {
@@ -212,17 +241,31 @@ impl ListingTable {
             .options
             .ok_or_else(|| internal_datafusion_err!("No ListingOptions provided"))?;
 
-        // Add the partition columns to the file schema
-        let mut builder = SchemaBuilder::from(file_schema.as_ref().to_owned());
-        for (part_col_name, part_col_type) in &options.table_partition_cols {
-            builder.push(Field::new(part_col_name, part_col_type.clone(), false));
+        let table_schema = Self::table_schema_with_partitions(&file_schema, &options);
+
+        for metadata_column in &options.metadata_columns {
+            if table_schema.field_with_name(metadata_column.name()).is_ok() {
+                return plan_err!(
+                    "Metadata column {} conflicts with a column of the table",
+                    metadata_column.name()
+                );
+            }
         }
 
-        let table_schema = Arc::new(
-            builder
-                .finish()
-                .with_metadata(file_schema.metadata().clone()),
-        );
+        if let Some(manifest) = &config.manifest {
+            manifest.validate(&options.table_partition_cols)?;
+        }
+
+        if let Some(file_ordering) = &options.file_ordering
+            && table_schema
+                .field_with_name(file_ordering.column())
+                .is_err()
+        {
+            return plan_err!(
+                "The files are declared to be ordered by {}, which is not a column of the table",
+                file_ordering.column()
+            );
+        }
 
         let table = Self {
             table_paths: config.table_paths,
@@ -235,11 +278,110 @@ impl ListingTable {
             constraints: Constraints::default(),
             column_defaults: HashMap::new(),
             expr_adapter_factory: config.expr_adapter_factory,
+            manifest: config.manifest,
+            metadata_fetch_progress: None,
         };
 
         Ok(table)
     }
 
+    /// Add the partition columns of `options` to `file_schema`
+    fn table_schema_with_partitions(
+        file_schema: &SchemaRef,
+        options: &ListingOptions,
+    ) -> SchemaRef {
+        let mut builder = SchemaBuilder::from(file_schema.as_ref().to_owned());
+        for (part_col_name, part_col_type) in &options.table_partition_cols {
+            builder.push(Field::new(part_col_name, part_col_type.clone(), false));
+        }
+
+        Arc::new(
+            builder
+                .finish()
+                .with_metadata(file_schema.metadata().clone()),
+        )
+    }
+
+    /// The table schema followed by the hidden metadata columns, which the
+    /// projection of a scan refers to
+    fn scan_schema(&self) -> SchemaRef {
+        if self.options.metadata_columns.is_empty() {
+            return Arc::clone(&self.table_schema);
+        }
+        let mut builder = SchemaBuilder::from(self.table_schema.as_ref().to_owned());
+        for metadata_column in &self.options.metadata_columns {
+            builder.push(metadata_column.field());
+        }
+        Arc::new(
+            builder
+                .finish()
+                .with_metadata(self.table_schema.metadata().clone()),
+        )
+    }
+
+    /// Append the values of the metadata columns for `file` to its partition
+    /// values, so that the scan materializes them like partition columns
+    fn with_metadata_column_values(&self, mut file: PartitionedFile) -> PartitionedFile {
+        if self.options.metadata_columns.is_empty() {
+            return file;
+        }
+        let values = self
+            .options
+            .metadata_columns
+            .iter()
+            .map(|metadata_column| metadata_column.value(&file))
+            .collect::<Vec<_>>();
+        file.partition_values.extend(values);
+        if let Some(index) = self.row_number_column() {
+            file = file.with_extension(FileRowNumberColumn {
+                partition_value_index: self.options.table_partition_cols.len() + index,
+            });
+        }
+        // statistics already set, such as the ones of a manifest, do not
+        // cover the metadata columns
+        if let Some(statistics) = file.statistics.take() {
+            let mut statistics = Arc::unwrap_or_clone(statistics);
+            statistics.column_statistics.extend(
+                self.options
+                    .metadata_columns
+                    .iter()
+                    .map(|_| ColumnStatistics::new_unknown()),
+            );
+            file.statistics = Some(Arc::new(statistics));
+        }
+        file
+    }
+
+    /// The index of the [`FileMetadataColumn::RowNumber`] column among the
+    /// metadata columns, if the table has one
+    fn row_number_column(&self) -> Option<usize> {
+        self.options
+            .metadata_columns
+            .iter()
+            .position(|column| *column == FileMetadataColumn::RowNumber)
+    }
+
+    /// Replace the statistics of the row numbers of `file`, which
+    /// [`PartitionedFile::with_statistics`] derives from their placeholder
+    /// value, by unknown statistics
+    fn with_row_number_statistics(&self, mut file: PartitionedFile) -> PartitionedFile {
+        let Some(index) = self.row_number_column() else {
+            return file;
+        };
+        let Some(statistics) = file.statistics.take() else {
+            return file;
+        };
+        let mut statistics = Arc::unwrap_or_clone(statistics);
+        let index = self.file_schema.fields().len()
+            + self.options.table_partition_cols.len()
+            + index;
+        if let Some(column_statistics) = statistics.column_statistics.get_mut(index) {
+            *column_statistics = ColumnStatistics::new_unknown();
+        }
+        file.statistics = Some(Arc::new(statistics));
+        file
+    }
+
     /// Assign constraints
     pub fn with_constraints(mut self, constraints: Constraints) -> Self {
         self.constraints = constraints;
@@ -267,12 +409,38 @@ impl ListingTable {
         self
     }
 
+    /// Set the [`MetadataFetchProgress`] notified as the metadata of files is
+    /// fetched to collect their statistics when planning a scan
+    pub fn with_metadata_fetch_progress(
+        mut self,
+        progress: Arc<dyn MetadataFetchProgress>,
+    ) -> Self {
+        self.metadata_fetch_progress = Some(progress);
+        self
+    }
+
     /// Specify the SQL definition for this table, if any
     pub fn with_definition(mut self, definition: Option<String>) -> Self {
         self.definition = definition;
         self
     }
 
+    /// The [`FileManifest`] scanned instead of listing the table paths, if any
+    pub fn manifest(&self) -> Option<&Arc<FileManifest>> {
+        self.manifest.as_ref()
+    }
+
+    /// Scan the files of `manifest` instead of listing the table paths, see
+    /// [`ListingTableConfig::with_manifest`]
+    pub fn with_manifest(
+        mut self,
+        manifest: Arc<FileManifest>,
+    ) -> datafusion_common::Result<Self> {
+        manifest.validate(&self.options.table_partition_cols)?;
+        self.manifest = Some(manifest);
+        Ok(self)
+    }
+
     /// Get paths ref
     pub fn table_paths(&self) -> &Vec<ListingTableUrl> {
         &self.table_paths
@@ -288,6 +456,107 @@ impl ListingTable {
         self.schema_source
     }
 
+    /// Infer the schema of the files currently in this table's first path,
+    /// without the partition columns
+    ///
+    /// The cached file listing of the path is discarded first, so that files
+    /// added since the table was last scanned are taken into account.
+    pub async fn infer_file_schema(
+        &self,
+        state: &dyn Session,
+    ) -> datafusion_common::Result<SchemaRef> {
+        let Some(url) = self.table_paths.first() else {
+            return Ok(Arc::new(Schema::empty()));
+        };
+        if let Some(cache) = state.runtime_env().cache_manager.get_list_files_cache() {
+            cache.drop_table_entries(url.get_table_ref())?;
+        }
+        self.options.infer_schema(state, url).await
+    }
+
+    /// Returns true if the schema of this table was inferred when the table
+    /// was created and the files currently in its paths have a different
+    /// schema.
+    ///
+    /// The schema of a table is not updated when files are added, so columns
+    /// only present in new files are not visible. Use
+    /// [`Self::with_refreshed_schema`] to update it.
+    pub async fn is_schema_stale(
+        &self,
+        state: &dyn Session,
+    ) -> datafusion_common::Result<bool> {
+        if self.schema_source != SchemaSource::Inferred {
+            return Ok(false);
+        }
+        Ok(self.infer_file_schema(state).await? != self.file_schema)
+    }
+
+    /// Return a copy of this table with its schema inferred again from the
+    /// files currently in its paths.
+    ///
+    /// Returns an error if the schema of this table was specified rather
+    /// than inferred. If the schema changed, the statistics cache of the
+    /// returned table is reset, as the cached statistics refer to the columns
+    /// of the previous schema.
+    pub async fn with_refreshed_schema(
+        &self,
+        state: &dyn Session,
+    ) -> datafusion_common::Result<Self> {
+        if self.schema_source != SchemaSource::Inferred {
+            return plan_err!(
+                "Can not refresh the schema of a table whose schema was not inferred"
+            );
+        }
+        let file_schema = self.infer_file_schema(state).await?;
+        if file_schema == self.file_schema {
+            return Ok(self.clone());
+        }
+
+        let table_schema =
+            Self::table_schema_with_partitions(&file_schema, &self.options);
+        Ok(Self {
+            file_schema,
+            table_schema,
+            collected_statistics: Arc::new(DefaultFileStatisticsCache::default()),
+            ..self.clone()
+        })
+    }
+
+    /// Return a copy of this table with a different file schema, for example
+    /// to add or remove columns. The partition columns are not changed.
+    ///
+    /// The schema is validated against the files currently in the table's
+    /// first path with the table's [`PhysicalExprAdapterFactory`]: with the
+    /// default adapter, columns present in the files must have a type that
+    /// can be cast to the new type, and columns missing from the files must
+    /// be nullable, as they are read as nulls.
+    pub async fn with_altered_file_schema(
+        &self,
+        state: &dyn Session,
+        file_schema: SchemaRef,
+    ) -> datafusion_common::Result<Self> {
+        let physical_file_schema = self.infer_file_schema(state).await?;
+        let adapter_factory = self
+            .expr_adapter_factory
+            .clone()
+            .unwrap_or_else(|| Arc::new(DefaultPhysicalExprAdapterFactory));
+        let adapter =
+            adapter_factory.create(Arc::clone(&file_schema), physical_file_schema)?;
+        for (index, field) in file_schema.fields().iter().enumerate() {
+            adapter.rewrite(Arc::new(Column::new(field.name(), index)))?;
+        }
+
+        let table_schema =
+            Self::table_schema_with_partitions(&file_schema, &self.options);
+        Ok(Self {
+            file_schema,
+            table_schema,
+            schema_source: SchemaSource::Specified,
+            collected_statistics: Arc::new(DefaultFileStatisticsCache::default()),
+            ..self.clone()
+        })
+    }
+
     /// Deprecated: Set the [`SchemaAdapterFactory`] for this [`ListingTable`]
     ///
     /// `SchemaAdapterFactory` has been removed. Use [`ListingTableConfig::with_expr_adapter_factory`]
@@ -330,6 +599,12 @@ impl ListingTable {
                 .table_partition_cols
                 .iter()
                 .map(|(col, field)| Arc::new(Field::new(col, field.clone(), false)))
+                .chain(
+                    self.options
+                        .metadata_columns
+                        .iter()
+                        .map(|metadata_column| Arc::new(metadata_column.field())),
+                )
                 .collect(),
         );
 
@@ -355,6 +630,13 @@ impl ListingTable {
                 execution_props,
             );
         }
+        if let Some(file_ordering) = &self.options.file_ordering {
+            return create_lex_ordering(
+                &self.table_schema,
+                &[vec![file_ordering.sort_expr()]],
+                execution_props,
+            );
+        }
         if let Some(ordering) = derive_common_ordering_from_files(file_groups) {
             return Ok(vec![ordering]);
         }
@@ -456,6 +738,19 @@ impl TableProvider for ListingTable {
         Some(&self.constraints)
     }
 
+    fn metadata_columns(&self) -> Option<SchemaRef> {
+        if self.options.metadata_columns.is_empty() {
+            return None;
+        }
+        Some(Arc::new(Schema::new(
+            self.options
+                .metadata_columns
+                .iter()
+                .map(|metadata_column| metadata_column.field())
+                .collect::<Vec<_>>(),
+        )))
+    }
+
     fn table_type(&self) -> TableType {
         TableType::Base
     }
@@ -509,15 +804,29 @@ impl TableProvider for ListingTable {
 
         let ListFilesResult {
             file_groups: mut partitioned_file_lists,
-            statistics,
+            mut statistics,
             grouped_by_partition: partitioned_by_file_group,
         } = self
             .list_files_for_scan(state, &partition_filters, statistic_file_limit)
             .await?;
 
+        if let Some(file_ordering) = &self.options.file_ordering {
+            let all_filters = [partition_filters.as_slice(), filters.as_slice()].concat();
+            let pruned;
+            (partitioned_file_lists, pruned) = self.order_file_groups(
+                file_ordering,
+                partitioned_file_lists,
+                &all_filters,
+            )?;
+            if pruned {
+                statistics = statistics.to_inexact();
+            }
+        }
+
         // if no files need to be read, return an `EmptyExec`
         if partitioned_file_lists.is_empty() {
-            let projected_schema = project_schema(&self.schema(), projection.as_ref())?;
+            let projected_schema =
+                project_schema(&self.scan_schema(), projection.as_ref())?;
             return Ok(ScanResult::new(Arc::new(EmptyExec::new(projected_schema))));
         }
 
@@ -579,6 +888,10 @@ impl TableProvider for ListingTable {
                     .with_output_ordering(output_ordering)
                     .with_expr_adapter(self.expr_adapter_factory.clone())
                     .with_partitioned_by_file_group(partitioned_by_file_group)
+                    .with_ordered_file_groups(self.options.file_ordering.is_some())
+                    .with_unify_dictionaries(
+                        state.config_options().execution.unify_scan_dictionaries,
+                    )
                     .build(),
             )
             .await?;
@@ -599,6 +912,16 @@ impl TableProvider for ListingTable {
         filters
             .iter()
             .map(|filter| {
+                // metadata columns are not part of the table schema the
+                // pushed down filters are evaluated against
+                if filter.column_refs().iter().any(|column| {
+                    self.options
+                        .metadata_columns
+                        .iter()
+                        .any(|metadata_column| metadata_column.name() == column.name)
+                }) {
+                    return Ok(TableProviderFilterPushDown::Unsupported);
+                }
                 if can_be_evaluated_for_partition_pruning(&partition_column_names, filter)
                 {
                     // if filter can be handled by partition pruning, it is exact
@@ -624,6 +947,12 @@ impl TableProvider for ListingTable {
         self.schema()
             .logically_equivalent_names_and_types(&input.schema())?;
 
+        if self.manifest.is_some() {
+            return not_impl_err!(
+                "Inserting into a ListingTable with a file manifest is not supported"
+            );
+        }
+
         let table_path = &self.table_paths()[0];
         if !table_path.is_collection() {
             return plan_err!(
@@ -670,6 +999,7 @@ impl TableProvider for ListingTable {
             keep_partition_by_columns,
             file_extension: self.options().format.get_ext(),
             file_output_mode: FileOutputMode::Automatic,
+            statistics_cache: Some(Arc::clone(&self.collected_statistics)),
         };
 
         // For writes, we only use user-specified ordering (no file groups to derive from)
@@ -689,6 +1019,42 @@ impl TableProvider for ListingTable {
 }
 
 impl ListingTable {
+    /// List all the files of this table, or the files of its manifest if it
+    /// has one, without their statistics
+    ///
+    /// The cached file listings of the table paths are discarded first, so
+    /// that files added since the table was last scanned are listed.
+    pub async fn list_files(
+        &self,
+        state: &dyn Session,
+    ) -> datafusion_common::Result<Vec<PartitionedFile>> {
+        if let Some(manifest) = &self.manifest {
+            return Ok(manifest.files().to_vec());
+        }
+        let Some(url) = self.table_paths.first() else {
+            return Ok(vec![]);
+        };
+        let store = state.runtime_env().object_store(url)?;
+        let mut files = vec![];
+        for table_path in &self.table_paths {
+            if let Some(cache) = state.runtime_env().cache_manager.get_list_files_cache()
+            {
+                cache.drop_table_entries(table_path.get_table_ref())?;
+            }
+            let listed = pruned_partition_list(
+                state,
+                store.as_ref(),
+                table_path,
+                &[],
+                &self.options.file_extension,
+                &self.options.table_partition_cols,
+            )
+            .await?;
+            files.extend(listed.try_collect::<Vec<_>>().await?);
+        }
+        Ok(files)
+    }
+
     /// Get the list of files for a scan as well as the file level statistics.
     /// The list is grouped to let the execution plan know how the files should
     /// be distributed to different threads / executors.
@@ -707,40 +1073,61 @@ impl ListingTable {
                 grouped_by_partition: false,
             });
         };
-        // list files (with partitions)
-        let file_list = future::try_join_all(self.table_paths.iter().map(|table_path| {
-            pruned_partition_list(
-                ctx,
-                store.as_ref(),
-                table_path,
-                filters,
-                &self.options.file_extension,
-                &self.options.table_partition_cols,
-            )
-        }))
-        .await?;
-        let meta_fetch_concurrency =
-            ctx.config_options().execution.meta_fetch_concurrency;
-        let file_list = stream::iter(file_list).flatten_unordered(meta_fetch_concurrency);
-        // collect the statistics and ordering if required by the config
+        let file_list = if let Some(manifest) = &self.manifest {
+            // the manifest replaces listing the table paths
+            let files =
+                manifest.pruned_files(filters, &self.options.table_partition_cols)?;
+            stream::iter(files.into_iter().map(Ok)).boxed()
+        } else {
+            // list files (with partitions)
+            let file_list =
+                future::try_join_all(self.table_paths.iter().map(|table_path| {
+                    pruned_partition_list(
+                        ctx,
+                        store.as_ref(),
+                        table_path,
+                        filters,
+                        &self.options.file_extension,
+                        &self.options.table_partition_cols,
+                    )
+                }))
+                .await?;
+            let meta_fetch_concurrency =
+                ctx.config_options().execution.meta_fetch_concurrency;
+            stream::iter(file_list)
+                .flatten_unordered(meta_fetch_concurrency)
+                .boxed()
+        };
+        // collect the statistics and ordering if required by the config,
+        // fetching the metadata of several files concurrently
+        let (store, fetched) = (&store, &AtomicUsize::new(0));
         let files = file_list
-            .map(|part_file| async {
-                let part_file = part_file?;
+            .map(|part_file| async move {
+                let part_file = self.with_metadata_column_values(part_file?);
+                // statistics from a manifest are used as is
+                if part_file.statistics.is_some() {
+                    return Ok(part_file);
+                }
                 let (statistics, ordering) = if self.options.collect_stat {
-                    self.do_collect_statistics_and_ordering(ctx, &store, &part_file)
-                        .await?
+                    self.do_collect_statistics_and_ordering(
+                        ctx, store, &part_file, fetched,
+                    )
+                    .await?
                 } else {
                     (Arc::new(Statistics::new_unknown(&self.file_schema)), None)
                 };
-                Ok(part_file
+                let part_file = part_file
                     .with_statistics(statistics)
-                    .with_ordering(ordering))
+                    .with_ordering(ordering);
+                Ok(self.with_row_number_statistics(part_file))
             })
             .boxed()
             .buffer_unordered(ctx.config_options().execution.meta_fetch_concurrency);
 
+        // statistics of a manifest are used even if they are not collected
+        let use_stats = self.options.collect_stat || self.manifest.is_some();
         let (file_group, inexact_stats) =
-            get_files_with_limit(files, limit, self.options.collect_stat).await?;
+            get_files_with_limit(files, limit, use_stats).await?;
 
         // Threshold: 0 = disabled, N > 0 = enabled when distinct_keys >= N
         //
@@ -773,8 +1160,8 @@ impl ListingTable {
 
         let (file_groups, stats) = compute_all_files_statistics(
             file_groups,
-            self.schema(),
-            self.options.collect_stat,
+            self.scan_schema(),
+            use_stats,
             inexact_stats,
         )?;
 
@@ -789,16 +1176,65 @@ impl ListingTable {
         })
     }
 
+    /// Sorts the files of each group by path, as the files are declared to be
+    /// ordered by `file_ordering`, and prunes the files that can not match
+    /// `filters`. Returns the non-empty groups, and whether files were pruned.
+    fn order_file_groups(
+        &self,
+        file_ordering: &FileOrdering,
+        file_groups: Vec<FileGroup>,
+        filters: &[Expr],
+    ) -> datafusion_common::Result<(Vec<FileGroup>, bool)> {
+        let data_type = self
+            .table_schema
+            .field_with_name(file_ordering.column())?
+            .data_type();
+        let files = file_groups
+            .iter()
+            .flat_map(FileGroup::iter)
+            .cloned()
+            .collect::<Vec<_>>();
+        let num_files = files.len();
+        let kept = file_ordering
+            .prune_files(files, filters, data_type, &self.options.file_extension)
+            .into_iter()
+            .map(|file| file.object_meta.location)
+            .collect::<HashSet<_>>();
+
+        let file_groups = file_groups
+            .into_iter()
+            .filter_map(|group| {
+                let statistics = group.file_statistics(None).cloned();
+                let len = group.len();
+                let mut files = group.into_inner();
+                files.retain(|file| kept.contains(&file.object_meta.location));
+                files.sort_by(|a, b| a.path().cmp(b.path()));
+                let group = FileGroup::new(files);
+                match statistics {
+                    _ if group.is_empty() => None,
+                    // the statistics of the group are kept if no file was pruned
+                    Some(statistics) if group.len() == len => {
+                        Some(group.with_statistics(Arc::new(statistics)))
+                    }
+                    _ => Some(group),
+                }
+            })
+            .collect();
+        Ok((file_groups, kept.len() < num_files))
+    }
+
     /// Collects statistics and ordering for a given partitioned file.
     ///
     /// This method checks if statistics are cached. If cached, it returns the
     /// cached statistics and infers ordering separately. If not cached, it infers
-    /// both statistics and ordering in a single metadata read for efficiency.
+    /// both statistics and ordering in a single metadata read for efficiency,
+    /// and counts the read in `fetched`.
     async fn do_collect_statistics_and_ordering(
         &self,
         ctx: &dyn Session,
         store: &Arc<dyn ObjectStore>,
         part_file: &PartitionedFile,
+        fetched: &AtomicUsize,
     ) -> datafusion_common::Result<(Arc<Statistics>, Option<LexOrdering>)> {
         use datafusion_execution::cache::cache_manager::CachedFileMetadata;
 
@@ -819,6 +1255,10 @@ impl ListingTable {
             .format
             .infer_stats_and_ordering(ctx, store, Arc::clone(&self.file_schema), meta)
             .await?;
+        let fetched = fetched.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(progress) = &self.metadata_fetch_progress {
+            progress.file_fetched(meta, fetched);
+        }
 
         let statistics = Arc::new(file_meta.statistics);
 
@@ -0,0 +1,174 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AnalyzeSink`] storing the statistics collected by `ANALYZE TABLE`
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::TableProvider;
+
+use arrow::array::RecordBatch;
+use arrow::datatypes::{DataType, SchemaRef};
+use datafusion_common::stats::Precision;
+use datafusion_common::{
+    ColumnStatistics, Result, ScalarValue, Statistics, internal_datafusion_err,
+    internal_err,
+};
+use datafusion_datasource::sink::DataSink;
+use datafusion_execution::{SendableRecordBatchStream, TaskContext};
+use datafusion_expr::dml::{ANALYZE_ROW_COUNT_COLUMN, analyze_statistic_column};
+use datafusion_physical_plan::{DisplayAs, DisplayFormatType};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+
+/// A [`DataSink`] passing the statistics collected by an `ANALYZE TABLE`
+/// statement to [`TableProvider::update_statistics`].
+///
+/// The input must be a single row, see [`WriteOp::Analyze`] for its schema.
+/// Returns the number of rows of the analyzed table.
+///
+/// [`WriteOp::Analyze`]: datafusion_expr::dml::WriteOp::Analyze
+#[derive(Debug)]
+pub struct AnalyzeSink {
+    table: Arc<dyn TableProvider>,
+    /// Schema of the collected statistics
+    schema: SchemaRef,
+}
+
+impl AnalyzeSink {
+    /// Create a sink storing the statistics with `schema` in `table`
+    pub fn new(table: Arc<dyn TableProvider>, schema: SchemaRef) -> Self {
+        Self { table, schema }
+    }
+}
+
+impl DisplayAs for AnalyzeSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "AnalyzeSink")
+            }
+            DisplayFormatType::TreeRender => write!(f, ""),
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for AnalyzeSink {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        mut data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let mut statistics = None;
+        while let Some(batch) = data.next().await.transpose()? {
+            for row in 0..batch.num_rows() {
+                if statistics.is_some() {
+                    return internal_err!("ANALYZE TABLE expects a single row");
+                }
+                statistics = Some(self.statistics_from_row(&batch, row)?);
+            }
+        }
+        let Some(statistics) = statistics else {
+            return internal_err!("ANALYZE TABLE expects a single row");
+        };
+        let row_count = statistics.num_rows.get_value().copied().unwrap_or_default();
+        self.table.update_statistics(statistics).await?;
+        Ok(row_count as u64)
+    }
+}
+
+impl AnalyzeSink {
+    fn statistics_from_row(&self, batch: &RecordBatch, row: usize) -> Result<Statistics> {
+        let num_rows = count_statistic(batch, row, ANALYZE_ROW_COUNT_COLUMN)?;
+        if num_rows == Precision::Absent {
+            return internal_err!(
+                "ANALYZE TABLE input has no {ANALYZE_ROW_COUNT_COLUMN} column"
+            );
+        }
+        let column_statistics = self
+            .table
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| {
+                let name = field.name();
+                let statistic =
+                    |statistic: &str| analyze_statistic_column(statistic, name);
+                Ok(ColumnStatistics {
+                    null_count: count_statistic(batch, row, &statistic("null_count"))?,
+                    max_value: value_statistic(batch, row, &statistic("max"))?,
+                    min_value: value_statistic(batch, row, &statistic("min"))?,
+                    // distinct counts are estimated with a sketch
+                    distinct_count: count_statistic(
+                        batch,
+                        row,
+                        &statistic("distinct_count"),
+                    )?
+                    .to_inexact(),
+                    ..ColumnStatistics::new_unknown()
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Statistics {
+            num_rows,
+            total_byte_size: Precision::Absent,
+            column_statistics,
+        })
+    }
+}
+
+/// Return the value of column `name` of `row`, or `Absent` if there is no
+/// such column or the value is null
+fn value_statistic(
+    batch: &RecordBatch,
+    row: usize,
+    name: &str,
+) -> Result<Precision<ScalarValue>> {
+    let Some(array) = batch.column_by_name(name) else {
+        return Ok(Precision::Absent);
+    };
+    let value = ScalarValue::try_from_array(array, row)?;
+    Ok(if value.is_null() {
+        Precision::Absent
+    } else {
+        Precision::Exact(value)
+    })
+}
+
+fn count_statistic(
+    batch: &RecordBatch,
+    row: usize,
+    name: &str,
+) -> Result<Precision<usize>> {
+    let value = match value_statistic(batch, row, name)? {
+        Precision::Exact(value) => value.cast_to(&DataType::UInt64)?,
+        _ => return Ok(Precision::Absent),
+    };
+    let ScalarValue::UInt64(Some(count)) = value else {
+        return Ok(Precision::Absent);
+    };
+    let count = usize::try_from(count)
+        .map_err(|_| internal_datafusion_err!("{name} out of range: {count}"))?;
+    Ok(Precision::Exact(count))
+}
@@ -15,12 +15,17 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use datafusion_common::{HashMap, TableReference, error::Result, not_impl_err};
+use datafusion_common::{
+    HashMap, ResolvedTableReference, TableReference, error::Result, not_impl_err,
+};
 use datafusion_execution::config::SessionConfig;
 
+use parking_lot::RwLock;
+
 use crate::{CatalogProvider, CatalogProviderList, SchemaProvider, TableProvider};
 
 /// A schema provider that looks up tables in a cache
@@ -188,6 +193,13 @@ impl CatalogProviderList for ResolvedCatalogProviderList {
 pub trait AsyncSchemaProvider: Send + Sync {
     /// Lookup a table in the schema provider
     async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>>;
+
+    /// Lists the tables of the schema. Providers that can not list their
+    /// tables return an empty list, the default
+    async fn table_names(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
     /// Creates a cached provider that can be used to execute a query containing given references
     ///
     /// This method will walk through the references and look them up once, creating a cache of table
@@ -254,6 +266,12 @@ pub trait AsyncCatalogProvider: Send + Sync {
     /// Lookup a schema in the provider
     async fn schema(&self, name: &str) -> Result<Option<Arc<dyn AsyncSchemaProvider>>>;
 
+    /// Lists the schemas of the catalog. Providers that can not list their
+    /// schemas return an empty list, the default
+    async fn schema_names(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
     /// Creates a cached provider that can be used to execute a query containing given references
     ///
     /// This method will walk through the references and look them up once, creating a cache of schema
@@ -327,6 +345,12 @@ pub trait AsyncCatalogProviderList: Send + Sync {
     /// Lookup a catalog in the provider
     async fn catalog(&self, name: &str) -> Result<Option<Arc<dyn AsyncCatalogProvider>>>;
 
+    /// Lists the catalogs of the provider. Providers that can not list their
+    /// catalogs return an empty list, the default
+    async fn catalog_names(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
     /// Creates a cached provider that can be used to execute a query containing given references
     ///
     /// This method will walk through the references and look them up once, creating a cache of catalog
@@ -410,6 +434,142 @@ pub trait AsyncCatalogProviderList: Send + Sync {
     }
 }
 
+/// Resolves tables from an [`AsyncCatalogProviderList`] on demand, caching
+/// the catalogs, schemas and tables it finds across queries.
+///
+/// Unlike [`AsyncCatalogProviderList::resolve`], whose cache is meant for a
+/// single query, the entries of this cache are kept until they are
+/// invalidated, for example when the remote metastore reports that a table
+/// changed. Lookups that find nothing are not cached, so tables created
+/// remotely are found by the next query that references them.
+///
+/// A cache can be registered with `SessionState::set_async_catalog_list`, so
+/// that the tables of remote catalogs are resolved while planning.
+pub struct AsyncCatalogCache {
+    catalog_list: Arc<dyn AsyncCatalogProviderList>,
+    catalogs: RwLock<HashMap<String, Arc<dyn AsyncCatalogProvider>>>,
+    schemas: RwLock<HashMap<(String, String), Arc<dyn AsyncSchemaProvider>>>,
+    tables: RwLock<HashMap<ResolvedTableReference, Arc<dyn TableProvider>>>,
+}
+
+impl Debug for AsyncCatalogCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncCatalogCache")
+            .field("catalogs", &self.catalogs.read().keys())
+            .field("schemas", &self.schemas.read().keys())
+            .field("tables", &self.tables.read().keys())
+            .finish()
+    }
+}
+
+impl AsyncCatalogCache {
+    /// Creates an empty cache of the tables of `catalog_list`
+    pub fn new(catalog_list: Arc<dyn AsyncCatalogProviderList>) -> Self {
+        Self {
+            catalog_list,
+            catalogs: RwLock::default(),
+            schemas: RwLock::default(),
+            tables: RwLock::default(),
+        }
+    }
+
+    /// Returns the catalogs the cache resolves tables from
+    pub fn catalog_list(&self) -> &Arc<dyn AsyncCatalogProviderList> {
+        &self.catalog_list
+    }
+
+    /// Returns the table `table_ref`, looking up its catalog, schema and
+    /// itself unless they are cached, or `None` if any of them does not exist
+    pub async fn table(
+        &self,
+        table_ref: &ResolvedTableReference,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        if let Some(table) = self.tables.read().get(table_ref) {
+            return Ok(Some(Arc::clone(table)));
+        }
+        let Some(schema) = self.schema(&table_ref.catalog, &table_ref.schema).await?
+        else {
+            return Ok(None);
+        };
+        let table = schema.table(&table_ref.table).await?;
+        if let Some(table) = &table {
+            self.tables
+                .write()
+                .insert(table_ref.clone(), Arc::clone(table));
+        }
+        Ok(table)
+    }
+
+    async fn schema(
+        &self,
+        catalog_name: &str,
+        schema_name: &str,
+    ) -> Result<Option<Arc<dyn AsyncSchemaProvider>>> {
+        let key = (catalog_name.to_string(), schema_name.to_string());
+        if let Some(schema) = self.schemas.read().get(&key) {
+            return Ok(Some(Arc::clone(schema)));
+        }
+        let Some(catalog) = self.catalog(catalog_name).await? else {
+            return Ok(None);
+        };
+        let schema = catalog.schema(schema_name).await?;
+        if let Some(schema) = &schema {
+            self.schemas.write().insert(key, Arc::clone(schema));
+        }
+        Ok(schema)
+    }
+
+    async fn catalog(
+        &self,
+        catalog_name: &str,
+    ) -> Result<Option<Arc<dyn AsyncCatalogProvider>>> {
+        if let Some(catalog) = self.catalogs.read().get(catalog_name) {
+            return Ok(Some(Arc::clone(catalog)));
+        }
+        let catalog = self.catalog_list.catalog(catalog_name).await?;
+        if let Some(catalog) = &catalog {
+            self.catalogs
+                .write()
+                .insert(catalog_name.to_string(), Arc::clone(catalog));
+        }
+        Ok(catalog)
+    }
+
+    /// Evicts the table `table_ref`, so that it is looked up again by the
+    /// next query that references it
+    pub fn invalidate_table(&self, table_ref: &ResolvedTableReference) {
+        self.tables.write().remove(table_ref);
+    }
+
+    /// Evicts the schema `schema_name` of `catalog_name` and its tables
+    pub fn invalidate_schema(&self, catalog_name: &str, schema_name: &str) {
+        self.schemas
+            .write()
+            .remove(&(catalog_name.to_string(), schema_name.to_string()));
+        self.tables.write().retain(|table_ref, _| {
+            *table_ref.catalog != *catalog_name || *table_ref.schema != *schema_name
+        });
+    }
+
+    /// Evicts the catalog `catalog_name`, its schemas and their tables
+    pub fn invalidate_catalog(&self, catalog_name: &str) {
+        self.catalogs.write().remove(catalog_name);
+        self.schemas
+            .write()
+            .retain(|(catalog, _), _| catalog != catalog_name);
+        self.tables
+            .write()
+            .retain(|table_ref, _| *table_ref.catalog != *catalog_name);
+    }
+
+    /// Evicts all the cached catalogs, schemas and tables
+    pub fn invalidate_all(&self) {
+        self.catalogs.write().clear();
+        self.schemas.write().clear();
+        self.tables.write().clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{
@@ -419,14 +579,19 @@ mod tests {
 
     use arrow::datatypes::SchemaRef;
     use async_trait::async_trait;
-    use datafusion_common::{Statistics, TableReference, error::Result};
+    use datafusion_common::{
+        ResolvedTableReference, Statistics, TableReference, error::Result,
+    };
     use datafusion_execution::config::SessionConfig;
     use datafusion_expr::{Expr, TableType};
     use datafusion_physical_plan::ExecutionPlan;
 
     use crate::{Session, TableProvider};
 
-    use super::{AsyncCatalogProvider, AsyncCatalogProviderList, AsyncSchemaProvider};
+    use super::{
+        AsyncCatalogCache, AsyncCatalogProvider, AsyncCatalogProviderList,
+        AsyncSchemaProvider,
+    };
 
     #[derive(Debug)]
     struct MockTableProvider {}
@@ -733,4 +898,47 @@ mod tests {
             assert!(schema.table(table_ref.table()).await.unwrap().is_some());
         }
     }
+
+    #[tokio::test]
+    async fn test_async_catalog_cache() {
+        let table_ref = |catalog: &str, table: &str| ResolvedTableReference {
+            catalog: catalog.into(),
+            schema: MOCK_SCHEMA.into(),
+            table: table.into(),
+        };
+        let catalog_list = Arc::new(MockAsyncCatalogProviderList::default());
+        let cache = AsyncCatalogCache::new(Arc::clone(&catalog_list) as _);
+        let lookup_count = || catalog_list.lookup_count.load(Ordering::Acquire);
+
+        let mock_table = table_ref(MOCK_CATALOG, MOCK_TABLE);
+        let table = cache.table(&mock_table).await.unwrap().unwrap();
+        assert_eq!(lookup_count(), 1);
+
+        // tables and the catalogs and schemas they belong to are cached
+        let cached = cache.table(&mock_table).await.unwrap().unwrap();
+        assert!(Arc::ptr_eq(&table, &cached));
+        let missing_table = table_ref(MOCK_CATALOG, "not_exists");
+        assert!(cache.table(&missing_table).await.unwrap().is_none());
+        assert_eq!(lookup_count(), 1);
+
+        // missing catalogs are not cached
+        let missing_catalog = table_ref("not_exists", MOCK_TABLE);
+        assert!(cache.table(&missing_catalog).await.unwrap().is_none());
+        assert!(cache.table(&missing_catalog).await.unwrap().is_none());
+        assert_eq!(lookup_count(), 3);
+
+        cache.invalidate_table(&mock_table);
+        let resolved = cache.table(&mock_table).await.unwrap().unwrap();
+        assert!(!Arc::ptr_eq(&table, &resolved));
+        assert_eq!(lookup_count(), 3);
+
+        cache.invalidate_catalog(MOCK_CATALOG);
+        let resolved_again = cache.table(&mock_table).await.unwrap().unwrap();
+        assert!(!Arc::ptr_eq(&resolved, &resolved_again));
+        assert_eq!(lookup_count(), 4);
+
+        cache.invalidate_all();
+        cache.table(&mock_table).await.unwrap().unwrap();
+        assert_eq!(lookup_count(), 5);
+    }
 }
@@ -20,11 +20,18 @@
 use std::borrow::Cow;
 use std::sync::Arc;
 
-use crate::TableProvider;
+use crate::{Session, TableProvider};
 
 use arrow::datatypes::SchemaRef;
-use datafusion_common::{Constraints, internal_err};
-use datafusion_expr::{Expr, TableProviderFilterPushDown, TableSource, TableType};
+use async_trait::async_trait;
+use datafusion_common::{Constraints, Statistics, internal_err};
+use datafusion_expr::{
+    Expr, TableAggregate, TableProviderFilterPushDown, TableSource, TableType,
+};
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_plan::ExecutionPlan;
+use datafusion_physical_plan::projection::ProjectionExec;
 
 /// Implements [`TableSource`] for a [`TableProvider`]
 ///
@@ -51,6 +58,11 @@ impl TableSource for DefaultTableSource {
         self.table_provider.schema()
     }
 
+    /// Get the hidden metadata columns of this table, if any.
+    fn metadata_columns(&self) -> Option<SchemaRef> {
+        self.table_provider.metadata_columns()
+    }
+
     /// Get a reference to applicable constraints, if any exists.
     fn constraints(&self) -> Option<&Constraints> {
         self.table_provider.constraints()
@@ -70,6 +82,22 @@ impl TableSource for DefaultTableSource {
         self.table_provider.supports_filters_pushdown(filter)
     }
 
+    /// Returns a source scanning the result of `aggregate`, computed by the
+    /// table provider, if the provider supports it.
+    fn push_down_aggregate(
+        &self,
+        aggregate: &TableAggregate,
+    ) -> datafusion_common::Result<Option<Arc<dyn TableSource>>> {
+        if !self.table_provider.supports_aggregate_pushdown(aggregate)? {
+            return Ok(None);
+        }
+        let table = AggregatedTable {
+            table_provider: Arc::clone(&self.table_provider),
+            aggregate: aggregate.clone(),
+        };
+        Ok(Some(provider_as_source(Arc::new(table))))
+    }
+
     fn get_logical_plan(&'_ self) -> Option<Cow<'_, datafusion_expr::LogicalPlan>> {
         self.table_provider.get_logical_plan()
     }
@@ -77,6 +105,60 @@ impl TableSource for DefaultTableSource {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.table_provider.get_column_default(column)
     }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.table_provider.statistics()
+    }
+}
+
+/// The result of a [`TableAggregate`] computed by a [`TableProvider`] in
+/// [`TableProvider::scan_with_aggregates`]
+#[derive(Debug)]
+struct AggregatedTable {
+    table_provider: Arc<dyn TableProvider>,
+    aggregate: TableAggregate,
+}
+
+#[async_trait]
+impl TableProvider for AggregatedTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.aggregate.schema)
+    }
+
+    fn table_type(&self) -> TableType {
+        self.table_provider.table_type()
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> datafusion_common::Result<Arc<dyn ExecutionPlan>> {
+        let plan = self
+            .table_provider
+            .scan_with_aggregates(state, &self.aggregate)
+            .await?;
+        let schema = plan.schema();
+        if schema.fields().len() != self.aggregate.schema.fields().len() {
+            return internal_err!(
+                "Pushed down aggregate produced {} columns, expected {}",
+                schema.fields().len(),
+                self.aggregate.schema.fields().len()
+            );
+        }
+
+        let Some(projection) = projection else {
+            return Ok(plan);
+        };
+        let exprs = projection.iter().map(|&index| {
+            let name = schema.field(index).name();
+            let column: Arc<dyn PhysicalExpr> = Arc::new(Column::new(name, index));
+            (column, name.clone())
+        });
+        Ok(Arc::new(ProjectionExec::try_new(exprs, plan)?))
+    }
 }
 
 /// Wrap TableProvider in TableSource
@@ -117,12 +199,11 @@ fn preserves_table_type() {
 
         async fn scan(
             &self,
-            _: &dyn crate::Session,
+            _: &dyn Session,
             _: Option<&Vec<usize>>,
             _: &[Expr],
             _: Option<usize>,
-        ) -> Result<Arc<dyn datafusion_physical_plan::ExecutionPlan>, DataFusionError>
-        {
+        ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
             unimplemented!()
         }
     }
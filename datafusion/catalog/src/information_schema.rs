@@ -54,6 +54,7 @@ pub(crate) const DF_SETTINGS: &str = "df_settings";
 pub(crate) const SCHEMATA: &str = "schemata";
 pub(crate) const ROUTINES: &str = "routines";
 pub(crate) const PARAMETERS: &str = "parameters";
+pub(crate) const TABLE_PROPERTIES: &str = "table_properties";
 
 /// All information schema tables
 pub const INFORMATION_SCHEMA_TABLES: &[&str] = &[
@@ -64,6 +65,7 @@ pub const INFORMATION_SCHEMA_TABLES: &[&str] = &[
     SCHEMATA,
     ROUTINES,
     PARAMETERS,
+    TABLE_PROPERTIES,
 ];
 
 /// Implements the `information_schema` virtual schema and tables
@@ -180,6 +182,46 @@ impl InformationSchemaConfig {
         Ok(())
     }
 
+    /// Construct the `information_schema.table_properties` virtual table from
+    /// the metadata of the table schemas, such as the key-value metadata of
+    /// Parquet files
+    async fn make_table_properties(
+        &self,
+        builder: &mut InformationSchemaTablePropertiesBuilder,
+    ) -> Result<(), DataFusionError> {
+        for catalog_name in self.catalog_list.catalog_names() {
+            let catalog = self.catalog_list.catalog(&catalog_name).unwrap();
+
+            for schema_name in catalog.schema_names() {
+                if schema_name != INFORMATION_SCHEMA {
+                    // schema name may not exist in the catalog, so we need to check
+                    if let Some(schema) = catalog.schema(&schema_name) {
+                        for table_name in schema.table_names() {
+                            if let Some(table) = schema.table(&table_name).await? {
+                                let table_schema = table.schema();
+                                let properties = table_schema
+                                    .metadata()
+                                    .iter()
+                                    .collect::<BTreeSet<_>>();
+                                for (key, value) in properties {
+                                    builder.add_property(
+                                        &catalog_name,
+                                        &schema_name,
+                                        &table_name,
+                                        key,
+                                        value,
+                                    )
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Construct the `information_schema.columns` virtual table
     async fn make_columns(
         &self,
@@ -197,12 +239,17 @@ impl InformationSchemaConfig {
                                 for (field_position, field) in
                                     table.schema().fields().iter().enumerate()
                                 {
+                                    let column_default =
+                                        table.get_column_default(field.name()).map(
+                                            |default| default.human_display().to_string(),
+                                        );
                                     builder.add_column(
                                         &catalog_name,
                                         &schema_name,
                                         &table_name,
                                         field_position,
                                         field,
+                                        column_default,
                                     )
                                 }
                             }
@@ -552,6 +599,7 @@ impl SchemaProvider for InformationSchemaProvider {
             SCHEMATA => Arc::new(InformationSchemata::new(config)),
             ROUTINES => Arc::new(InformationSchemaRoutines::new(config)),
             PARAMETERS => Arc::new(InformationSchemaParameters::new(config)),
+            TABLE_PROPERTIES => Arc::new(InformationSchemaTableProperties::new(config)),
             _ => return Ok(None),
         };
 
@@ -745,6 +793,98 @@ impl InformationSchemaViewBuilder {
     }
 }
 
+#[derive(Debug)]
+struct InformationSchemaTableProperties {
+    schema: SchemaRef,
+    config: InformationSchemaConfig,
+}
+
+impl InformationSchemaTableProperties {
+    fn new(config: InformationSchemaConfig) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("table_catalog", DataType::Utf8, false),
+            Field::new("table_schema", DataType::Utf8, false),
+            Field::new("table_name", DataType::Utf8, false),
+            Field::new("property_key", DataType::Utf8, false),
+            Field::new("property_value", DataType::Utf8, false),
+        ]));
+
+        Self { schema, config }
+    }
+
+    fn builder(&self) -> InformationSchemaTablePropertiesBuilder {
+        InformationSchemaTablePropertiesBuilder {
+            catalog_names: StringBuilder::new(),
+            schema_names: StringBuilder::new(),
+            table_names: StringBuilder::new(),
+            property_keys: StringBuilder::new(),
+            property_values: StringBuilder::new(),
+            schema: Arc::clone(&self.schema),
+        }
+    }
+}
+
+impl PartitionStream for InformationSchemaTableProperties {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = self.builder();
+        let config = self.config.clone();
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            // TODO: Stream this
+            futures::stream::once(async move {
+                config.make_table_properties(&mut builder).await?;
+                Ok(builder.finish())
+            }),
+        ))
+    }
+}
+
+/// Builds the `information_schema.TABLE_PROPERTIES` table row by row
+struct InformationSchemaTablePropertiesBuilder {
+    schema: SchemaRef,
+    catalog_names: StringBuilder,
+    schema_names: StringBuilder,
+    table_names: StringBuilder,
+    property_keys: StringBuilder,
+    property_values: StringBuilder,
+}
+
+impl InformationSchemaTablePropertiesBuilder {
+    fn add_property(
+        &mut self,
+        catalog_name: impl AsRef<str>,
+        schema_name: impl AsRef<str>,
+        table_name: impl AsRef<str>,
+        property_key: impl AsRef<str>,
+        property_value: impl AsRef<str>,
+    ) {
+        // Note: append_value is actually infallible.
+        self.catalog_names.append_value(catalog_name.as_ref());
+        self.schema_names.append_value(schema_name.as_ref());
+        self.table_names.append_value(table_name.as_ref());
+        self.property_keys.append_value(property_key.as_ref());
+        self.property_values.append_value(property_value.as_ref());
+    }
+
+    fn finish(&mut self) -> RecordBatch {
+        RecordBatch::try_new(
+            Arc::clone(&self.schema),
+            vec![
+                Arc::new(self.catalog_names.finish()),
+                Arc::new(self.schema_names.finish()),
+                Arc::new(self.table_names.finish()),
+                Arc::new(self.property_keys.finish()),
+                Arc::new(self.property_values.finish()),
+            ],
+        )
+        .unwrap()
+    }
+}
+
 #[derive(Debug)]
 struct InformationSchemaColumns {
     schema: SchemaRef,
@@ -850,6 +990,7 @@ impl InformationSchemaColumnsBuilder {
         table_name: &str,
         field_position: usize,
         field: &Field,
+        column_default: Option<String>,
     ) {
         use DataType::*;
 
@@ -862,8 +1003,8 @@ impl InformationSchemaColumnsBuilder {
 
         self.ordinal_positions.append_value(field_position as u64);
 
-        // DataFusion does not support column default values, so null
-        self.column_defaults.append_null();
+        // The default value of the column, if the table declares one
+        self.column_defaults.append_option(column_default);
 
         // "YES if the column is possibly nullable, NO if it is known not nullable. "
         let nullable_str = if field.is_nullable() { "YES" } else { "NO" };
@@ -0,0 +1,343 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`LateralTableFunctionExec`] calls a table function for every input row
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::TableFunctionImpl;
+
+use arrow::array::{ArrayRef, RecordBatch, UInt32Array, new_null_array};
+use arrow::compute::{concat, take_arrays};
+use arrow::datatypes::SchemaRef;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::{
+    Constraints, JoinType, Result, ScalarValue, exec_err, internal_err, plan_err,
+};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr::equivalence::ProjectionMapping;
+use datafusion_physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet, RecordOutput,
+};
+use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion_physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    SendableRecordBatchStream, check_if_same_properties,
+};
+use futures::StreamExt;
+use itertools::Itertools;
+
+/// Calls a table function for every row of its input, with the values of the
+/// argument expressions in that row, and joins the row with the rows the call
+/// returns. This implements lateral table function calls such as
+/// `SELECT * FROM t, LATERAL f(t.x)`, see [`TableFunctionImpl::call_lateral`].
+///
+/// The output contains the columns of the input followed by the columns of
+/// the function. With [`JoinType::Left`], input rows the function returns no
+/// rows for are kept, with nulls for the columns of the function.
+#[derive(Debug, Clone)]
+pub struct LateralTableFunctionExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Name of the table function
+    function_name: String,
+    /// The table function
+    function: Arc<dyn TableFunctionImpl>,
+    /// Arguments of the call, evaluated against the input
+    args: Vec<Arc<dyn PhysicalExpr>>,
+    /// Either [`JoinType::Inner`] or [`JoinType::Left`]
+    join_type: JoinType,
+    /// The output schema
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: Arc<PlanProperties>,
+}
+
+impl LateralTableFunctionExec {
+    /// Create a new [`LateralTableFunctionExec`] with the output `schema`,
+    /// which starts with the fields of the input
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        function_name: impl Into<String>,
+        function: Arc<dyn TableFunctionImpl>,
+        args: Vec<Arc<dyn PhysicalExpr>>,
+        join_type: JoinType,
+        schema: SchemaRef,
+    ) -> Result<Self> {
+        if !matches!(join_type, JoinType::Inner | JoinType::Left) {
+            return plan_err!(
+                "Lateral table functions only support inner and left joins, got {join_type}"
+            );
+        }
+        let input_schema = input.schema();
+        if schema.fields().len() < input_schema.fields().len()
+            || schema
+                .fields()
+                .iter()
+                .zip(input_schema.fields())
+                .any(|(field, input_field)| field.data_type() != input_field.data_type())
+        {
+            return internal_err!(
+                "The schema of LateralTableFunctionExec must start with the fields of its input"
+            );
+        }
+        let cache = Self::compute_properties(&input, &schema)?;
+        Ok(Self {
+            input,
+            function_name: function_name.into(),
+            function,
+            args,
+            join_type,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: Arc::new(cache),
+        })
+    }
+
+    /// This function creates the cache object that stores the plan properties
+    /// such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        input: &Arc<dyn ExecutionPlan>,
+        schema: &SchemaRef,
+    ) -> Result<PlanProperties> {
+        // The columns of the input keep their position, and the rows for an
+        // input row are adjacent, so the ordering of the input is retained
+        let projection_mapping = ProjectionMapping::from_indices(
+            &(0..input.schema().fields().len()).collect_vec(),
+            &input.schema(),
+        )?;
+        let eq_properties = input
+            .equivalence_properties()
+            .project(&projection_mapping, Arc::clone(schema))
+            .with_constraints(Constraints::default());
+        let output_partitioning = input
+            .output_partitioning()
+            .project(&projection_mapping, &eq_properties);
+
+        Ok(PlanProperties::new(
+            eq_properties,
+            output_partitioning,
+            input.pipeline_behavior(),
+            input.boundedness(),
+        ))
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Name of the table function
+    pub fn function_name(&self) -> &str {
+        &self.function_name
+    }
+
+    /// The table function
+    pub fn function(&self) -> &Arc<dyn TableFunctionImpl> {
+        &self.function
+    }
+
+    /// Arguments of the call, evaluated against the input
+    pub fn args(&self) -> &[Arc<dyn PhysicalExpr>] {
+        &self.args
+    }
+
+    /// Either [`JoinType::Inner`] or [`JoinType::Left`]
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    fn with_new_children_and_same_properties(
+        &self,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Self {
+        Self {
+            input: children.swap_remove(0),
+            metrics: ExecutionPlanMetricsSet::new(),
+            ..Self::clone(self)
+        }
+    }
+}
+
+impl DisplayAs for LateralTableFunctionExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        let args = self.args.iter().map(|arg| arg.to_string()).join(", ");
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "LateralTableFunctionExec: {}({args})",
+                    self.function_name
+                )?;
+                if self.join_type != JoinType::Inner {
+                    write!(f, ", join_type={}", self.join_type)?;
+                }
+                Ok(())
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "function={}({args})", self.function_name)?;
+                write!(f, "join_type={}", self.join_type)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for LateralTableFunctionExec {
+    fn name(&self) -> &'static str {
+        "LateralTableFunctionExec"
+    }
+
+    fn properties(&self) -> &Arc<PlanProperties> {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn apply_expressions(
+        &self,
+        f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        let mut tnr = TreeNodeRecursion::Continue;
+        for arg in &self.args {
+            tnr = tnr.visit_sibling(|| f(arg.as_ref()))?;
+        }
+        Ok(tnr)
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        check_if_same_properties!(self, children);
+        Ok(Arc::new(Self::try_new(
+            children.swap_remove(0),
+            self.function_name.clone(),
+            Arc::clone(&self.function),
+            self.args.clone(),
+            self.join_type,
+            Arc::clone(&self.schema),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, Arc::clone(&context))?;
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let config = Arc::clone(context.session_config().options());
+        let call = LateralCall {
+            function: Arc::clone(&self.function),
+            args: self.args.clone(),
+            join_type: self.join_type,
+            schema: Arc::clone(&self.schema),
+        };
+
+        let stream = input.map(move |batch| {
+            let _timer = baseline_metrics.elapsed_compute().timer();
+            call.call(&batch?, &config)
+                .map(|batch| batch.record_output(&baseline_metrics))
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+/// Calls the table function for the rows of a batch
+struct LateralCall {
+    function: Arc<dyn TableFunctionImpl>,
+    args: Vec<Arc<dyn PhysicalExpr>>,
+    join_type: JoinType,
+    schema: SchemaRef,
+}
+
+impl LateralCall {
+    fn call(&self, batch: &RecordBatch, config: &ConfigOptions) -> Result<RecordBatch> {
+        let num_rows = batch.num_rows();
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.evaluate(batch)?.into_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+        let function_fields = &self.schema.fields()[batch.num_columns()..];
+
+        // The index of the input row of every output row, and the columns
+        // of the function for the output rows
+        let mut indices = vec![];
+        let mut function_columns: Vec<Vec<ArrayRef>> =
+            vec![vec![]; function_fields.len()];
+        for row in 0..num_rows {
+            let row_args = args
+                .iter()
+                .map(|arg| ScalarValue::try_from_array(arg, row))
+                .collect::<Result<Vec<_>>>()?;
+            let batches = self.function.call_lateral(&row_args, config)?;
+            let row_count = batches.iter().map(RecordBatch::num_rows).sum::<usize>();
+            if row_count == 0 && self.join_type == JoinType::Left {
+                indices.push(row as u32);
+                for (columns, field) in function_columns.iter_mut().zip(function_fields) {
+                    columns.push(new_null_array(field.data_type(), 1));
+                }
+                continue;
+            }
+            indices.extend(std::iter::repeat_n(row as u32, row_count));
+            for batch in batches {
+                if batch.num_columns() != function_fields.len() {
+                    return exec_err!(
+                        "Table function returned {} columns, expected {}",
+                        batch.num_columns(),
+                        function_fields.len()
+                    );
+                }
+                for (columns, column) in function_columns.iter_mut().zip(batch.columns())
+                {
+                    columns.push(Arc::clone(column));
+                }
+            }
+        }
+
+        let indices = UInt32Array::from(indices);
+        let mut columns = take_arrays(batch.columns(), &indices, None)?;
+        for (arrays, field) in function_columns.iter().zip(function_fields) {
+            if arrays.is_empty() {
+                columns.push(new_null_array(field.data_type(), 0));
+            } else {
+                let arrays = arrays.iter().map(|array| array.as_ref()).collect_vec();
+                columns.push(concat(&arrays)?);
+            }
+        }
+        Ok(RecordBatch::try_new(Arc::clone(&self.schema), columns)?)
+    }
+}
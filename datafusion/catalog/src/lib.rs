@@ -32,10 +32,12 @@
 //! * Simple memory based catalog: [`MemoryCatalogProviderList`], [`MemoryCatalogProvider`], [`MemorySchemaProvider`]
 //! * Listing schema: [`listing_schema`]
 
+pub mod analyze;
 pub mod cte_worktable;
 pub mod default_table_source;
 pub mod empty;
 pub mod information_schema;
+pub mod lateral;
 pub mod listing_schema;
 pub mod memory;
 pub mod stream;
@@ -45,6 +47,7 @@ pub mod view;
 mod r#async;
 mod catalog;
 mod dynamic_file;
+mod overlay;
 mod schema;
 mod table;
 
@@ -55,6 +58,7 @@ pub use dynamic_file::catalog::*;
 pub use memory::{
     MemTable, MemoryCatalogProvider, MemoryCatalogProviderList, MemorySchemaProvider,
 };
+pub use overlay::*;
 pub use schema::*;
 pub use table::*;
 
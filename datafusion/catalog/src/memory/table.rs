@@ -306,63 +306,9 @@ impl TableProvider for MemTable {
         state: &dyn Session,
         filters: Vec<Expr>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        // Early exit if table has no partitions
-        if self.batches.is_empty() {
-            return Ok(Arc::new(DmlResultExec::new(0)));
-        }
-
-        *self.sort_order.lock() = vec![];
-
-        let mut total_deleted: u64 = 0;
         let df_schema = DFSchema::try_from(Arc::clone(&self.schema))?;
-
-        for partition_data in &self.batches {
-            let mut partition = partition_data.write().await;
-            let mut new_batches = Vec::with_capacity(partition.len());
-
-            for batch in partition.iter() {
-                if batch.num_rows() == 0 {
-                    continue;
-                }
-
-                // Evaluate filters - None means "match all rows"
-                let filter_mask = evaluate_filters_to_mask(
-                    &filters,
-                    batch,
-                    &df_schema,
-                    state.execution_props(),
-                )?;
-
-                let (delete_count, keep_mask) = match filter_mask {
-                    Some(mask) => {
-                        // Count rows where mask is true (will be deleted)
-                        let count = mask.iter().filter(|v| v == &Some(true)).count();
-                        // Keep rows where predicate is false or NULL (SQL three-valued logic)
-                        let keep: BooleanArray =
-                            mask.iter().map(|v| Some(v != Some(true))).collect();
-                        (count, keep)
-                    }
-                    None => {
-                        // No filters = delete all rows
-                        (
-                            batch.num_rows(),
-                            BooleanArray::from(vec![false; batch.num_rows()]),
-                        )
-                    }
-                };
-
-                total_deleted += delete_count as u64;
-
-                let filtered_batch = filter_record_batch(batch, &keep_mask)?;
-                if filtered_batch.num_rows() > 0 {
-                    new_batches.push(filtered_batch);
-                }
-            }
-
-            *partition = new_batches;
-        }
-
-        Ok(Arc::new(DmlResultExec::new(total_deleted)))
+        let filters = create_physical_filters(&filters, &df_schema, state)?;
+        Ok(Arc::new(MemDmlExec::new(self, MemDmlOp::Delete, filters)))
     }
 
     async fn update(
@@ -371,11 +317,6 @@ impl TableProvider for MemTable {
         assignments: Vec<(String, Expr)>,
         filters: Vec<Expr>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        // Early exit if table has no partitions
-        if self.batches.is_empty() {
-            return Ok(Arc::new(DmlResultExec::new(0)));
-        }
-
         // Validate column names upfront with clear error messages
         let available_columns: Vec<&str> = self
             .schema
@@ -395,7 +336,8 @@ impl TableProvider for MemTable {
 
         let df_schema = DFSchema::try_from(Arc::clone(&self.schema))?;
 
-        // Create physical expressions for assignments upfront (outside batch loop)
+        // Create physical expressions for assignments upfront, so that invalid
+        // statements fail when they are planned
         let physical_assignments: HashMap<String, Arc<dyn PhysicalExpr>> = assignments
             .iter()
             .map(|(name, expr)| {
@@ -404,107 +346,34 @@ impl TableProvider for MemTable {
                 Ok((name.clone(), physical_expr))
             })
             .collect::<Result<_>>()?;
+        let filters = create_physical_filters(&filters, &df_schema, state)?;
 
-        *self.sort_order.lock() = vec![];
-
-        let mut total_updated: u64 = 0;
-
-        for partition_data in &self.batches {
-            let mut partition = partition_data.write().await;
-            let mut new_batches = Vec::with_capacity(partition.len());
-
-            for batch in partition.iter() {
-                if batch.num_rows() == 0 {
-                    continue;
-                }
-
-                // Evaluate filters - None means "match all rows"
-                let filter_mask = evaluate_filters_to_mask(
-                    &filters,
-                    batch,
-                    &df_schema,
-                    state.execution_props(),
-                )?;
-
-                let (update_count, update_mask) = match filter_mask {
-                    Some(mask) => {
-                        // Count rows where mask is true (will be updated)
-                        let count = mask.iter().filter(|v| v == &Some(true)).count();
-                        // Normalize mask: only true (not NULL) triggers update
-                        let normalized: BooleanArray =
-                            mask.iter().map(|v| Some(v == Some(true))).collect();
-                        (count, normalized)
-                    }
-                    None => {
-                        // No filters = update all rows
-                        (
-                            batch.num_rows(),
-                            BooleanArray::from(vec![true; batch.num_rows()]),
-                        )
-                    }
-                };
-
-                total_updated += update_count as u64;
-
-                if update_count == 0 {
-                    new_batches.push(batch.clone());
-                    continue;
-                }
-
-                let mut new_columns: Vec<ArrayRef> =
-                    Vec::with_capacity(batch.num_columns());
-
-                for field in self.schema.fields() {
-                    let column_name = field.name();
-                    let original_column =
-                        batch.column_by_name(column_name).ok_or_else(|| {
-                            datafusion_common::DataFusionError::Internal(format!(
-                                "Column '{column_name}' not found in batch"
-                            ))
-                        })?;
-
-                    let new_column = if let Some(physical_expr) =
-                        physical_assignments.get(column_name.as_str())
-                    {
-                        // Use evaluate_selection to only evaluate on matching rows.
-                        // This avoids errors (e.g., divide-by-zero) on rows that won't
-                        // be updated. The result is scattered back with nulls for
-                        // non-matching rows, which zip() will replace with originals.
-                        let new_values =
-                            physical_expr.evaluate_selection(batch, &update_mask)?;
-                        let new_array = new_values.into_array(batch.num_rows())?;
-
-                        // Convert to &dyn Array which implements Datum
-                        let new_arr: &dyn Array = new_array.as_ref();
-                        let orig_arr: &dyn Array = original_column.as_ref();
-                        zip(&update_mask, &new_arr, &orig_arr)?
-                    } else {
-                        Arc::clone(original_column)
-                    };
-
-                    new_columns.push(new_column);
-                }
-
-                let updated_batch =
-                    ArrowRecordBatch::try_new(Arc::clone(&self.schema), new_columns)?;
-                new_batches.push(updated_batch);
-            }
-
-            *partition = new_batches;
-        }
-
-        Ok(Arc::new(DmlResultExec::new(total_updated)))
+        Ok(Arc::new(MemDmlExec::new(
+            self,
+            MemDmlOp::Update(physical_assignments),
+            filters,
+        )))
     }
 }
 
+/// Create the physical expressions of the filters of a DELETE or UPDATE
+fn create_physical_filters(
+    filters: &[Expr],
+    df_schema: &DFSchema,
+    state: &dyn Session,
+) -> Result<Vec<Arc<dyn PhysicalExpr>>> {
+    filters
+        .iter()
+        .map(|filter| create_physical_expr(filter, df_schema, state.execution_props()))
+        .collect()
+}
+
 /// Evaluate filter expressions against a batch and return a combined boolean mask.
 /// Returns None if filters is empty (meaning "match all rows").
 /// The returned mask has true for rows that match the filter predicates.
 fn evaluate_filters_to_mask(
-    filters: &[Expr],
+    filters: &[Arc<dyn PhysicalExpr>],
     batch: &RecordBatch,
-    df_schema: &DFSchema,
-    execution_props: &datafusion_expr::execution_props::ExecutionProps,
 ) -> Result<Option<BooleanArray>> {
     if filters.is_empty() {
         return Ok(None);
@@ -512,10 +381,7 @@ fn evaluate_filters_to_mask(
 
     let mut combined_mask: Option<BooleanArray> = None;
 
-    for filter_expr in filters {
-        let physical_expr =
-            create_physical_expr(filter_expr, df_schema, execution_props)?;
-
+    for physical_expr in filters {
         let result = physical_expr.evaluate(batch)?;
         let array = result.into_array(batch.num_rows())?;
         let bool_array = array
@@ -537,16 +403,42 @@ fn evaluate_filters_to_mask(
     Ok(combined_mask)
 }
 
+/// The change a [`MemDmlExec`] applies to the matching rows of a [`MemTable`]
+#[derive(Debug, Clone)]
+enum MemDmlOp {
+    /// Delete the rows
+    Delete,
+    /// Set the columns to the values of the expressions
+    Update(HashMap<String, Arc<dyn PhysicalExpr>>),
+}
+
+impl MemDmlOp {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Delete => "Delete",
+            Self::Update(_) => "Update",
+        }
+    }
+}
+
+/// Deletes or updates the rows of a [`MemTable`] matching the filters.
+///
+/// The table is only changed when the plan is executed, not when it is
+/// created, so that for example `EXPLAIN DELETE` leaves the table untouched.
 /// Returns a single row with the count of affected rows.
 #[derive(Debug)]
-struct DmlResultExec {
-    rows_affected: u64,
+struct MemDmlExec {
+    op: MemDmlOp,
+    filters: Vec<Arc<dyn PhysicalExpr>>,
+    batches: Vec<PartitionData>,
+    table_schema: SchemaRef,
+    sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
     schema: SchemaRef,
     properties: Arc<PlanProperties>,
 }
 
-impl DmlResultExec {
-    fn new(rows_affected: u64) -> Self {
+impl MemDmlExec {
+    fn new(table: &MemTable, op: MemDmlOp, filters: Vec<Arc<dyn PhysicalExpr>>) -> Self {
         let schema = Arc::new(Schema::new(vec![Field::new(
             "count",
             DataType::UInt64,
@@ -561,14 +453,18 @@ impl DmlResultExec {
         );
 
         Self {
-            rows_affected,
+            op,
+            filters,
+            batches: table.batches.clone(),
+            table_schema: Arc::clone(&table.schema),
+            sort_order: Arc::clone(&table.sort_order),
             schema,
             properties: Arc::new(properties),
         }
     }
 }
 
-impl DisplayAs for DmlResultExec {
+impl DisplayAs for MemDmlExec {
     fn fmt_as(
         &self,
         t: DisplayFormatType,
@@ -578,15 +474,15 @@ impl DisplayAs for DmlResultExec {
             DisplayFormatType::Default
             | DisplayFormatType::Verbose
             | DisplayFormatType::TreeRender => {
-                write!(f, "DmlResultExec: rows_affected={}", self.rows_affected)
+                write!(f, "MemDmlExec: op={}", self.op.name())
             }
         }
     }
 }
 
-impl ExecutionPlan for DmlResultExec {
+impl ExecutionPlan for MemDmlExec {
     fn name(&self) -> &str {
-        "DmlResultExec"
+        "MemDmlExec"
     }
 
     fn schema(&self) -> SchemaRef {
@@ -613,15 +509,33 @@ impl ExecutionPlan for DmlResultExec {
         _partition: usize,
         _context: Arc<datafusion_execution::TaskContext>,
     ) -> Result<datafusion_execution::SendableRecordBatchStream> {
-        // Create a single batch with the count
-        let count_array = UInt64Array::from(vec![self.rows_affected]);
-        let batch = ArrowRecordBatch::try_new(
-            Arc::clone(&self.schema),
-            vec![Arc::new(count_array) as ArrayRef],
-        )?;
-
-        // Create a stream that yields just this one batch
-        let stream = futures::stream::iter(vec![Ok(batch)]);
+        let op = self.op.clone();
+        let filters = self.filters.clone();
+        let batches = self.batches.clone();
+        let table_schema = Arc::clone(&self.table_schema);
+        let sort_order = Arc::clone(&self.sort_order);
+        let schema = Arc::clone(&self.schema);
+
+        let stream = futures::stream::once(async move {
+            let rows_affected = if batches.is_empty() {
+                0
+            } else {
+                *sort_order.lock() = vec![];
+                match op {
+                    MemDmlOp::Delete => delete_rows(&batches, &filters).await?,
+                    MemDmlOp::Update(assignments) => {
+                        update_rows(&batches, &table_schema, &assignments, &filters)
+                            .await?
+                    }
+                }
+            };
+            // Create a single batch with the count
+            let count_array = UInt64Array::from(vec![rows_affected]);
+            Ok::<_, datafusion_common::DataFusionError>(ArrowRecordBatch::try_new(
+                schema,
+                vec![Arc::new(count_array) as ArrayRef],
+            )?)
+        });
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             Arc::clone(&self.schema),
             stream,
@@ -630,8 +544,158 @@ impl ExecutionPlan for DmlResultExec {
 
     fn apply_expressions(
         &self,
-        _f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+        f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
     ) -> Result<TreeNodeRecursion> {
-        Ok(TreeNodeRecursion::Continue)
+        let mut tnr = TreeNodeRecursion::Continue;
+        for filter in &self.filters {
+            tnr = tnr.visit_sibling(|| f(filter.as_ref()))?;
+        }
+        if let MemDmlOp::Update(assignments) = &self.op {
+            for expr in assignments.values() {
+                tnr = tnr.visit_sibling(|| f(expr.as_ref()))?;
+            }
+        }
+        Ok(tnr)
+    }
+}
+
+/// Delete the rows matching `filters`, returning the number of deleted rows
+async fn delete_rows(
+    batches: &[PartitionData],
+    filters: &[Arc<dyn PhysicalExpr>],
+) -> Result<u64> {
+    let mut total_deleted: u64 = 0;
+
+    for partition_data in batches {
+        let mut partition = partition_data.write().await;
+        let mut new_batches = Vec::with_capacity(partition.len());
+
+        for batch in partition.iter() {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+
+            // Evaluate filters - None means "match all rows"
+            let filter_mask = evaluate_filters_to_mask(filters, batch)?;
+
+            let (delete_count, keep_mask) = match filter_mask {
+                Some(mask) => {
+                    // Count rows where mask is true (will be deleted)
+                    let count = mask.iter().filter(|v| v == &Some(true)).count();
+                    // Keep rows where predicate is false or NULL (SQL three-valued logic)
+                    let keep: BooleanArray =
+                        mask.iter().map(|v| Some(v != Some(true))).collect();
+                    (count, keep)
+                }
+                None => {
+                    // No filters = delete all rows
+                    (
+                        batch.num_rows(),
+                        BooleanArray::from(vec![false; batch.num_rows()]),
+                    )
+                }
+            };
+
+            total_deleted += delete_count as u64;
+
+            let filtered_batch = filter_record_batch(batch, &keep_mask)?;
+            if filtered_batch.num_rows() > 0 {
+                new_batches.push(filtered_batch);
+            }
+        }
+
+        *partition = new_batches;
+    }
+
+    Ok(total_deleted)
+}
+
+/// Apply `assignments` to the rows matching `filters`, returning the number
+/// of updated rows
+async fn update_rows(
+    batches: &[PartitionData],
+    table_schema: &SchemaRef,
+    assignments: &HashMap<String, Arc<dyn PhysicalExpr>>,
+    filters: &[Arc<dyn PhysicalExpr>],
+) -> Result<u64> {
+    let mut total_updated: u64 = 0;
+
+    for partition_data in batches {
+        let mut partition = partition_data.write().await;
+        let mut new_batches = Vec::with_capacity(partition.len());
+
+        for batch in partition.iter() {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+
+            // Evaluate filters - None means "match all rows"
+            let filter_mask = evaluate_filters_to_mask(filters, batch)?;
+
+            let (update_count, update_mask) = match filter_mask {
+                Some(mask) => {
+                    // Count rows where mask is true (will be updated)
+                    let count = mask.iter().filter(|v| v == &Some(true)).count();
+                    // Normalize mask: only true (not NULL) triggers update
+                    let normalized: BooleanArray =
+                        mask.iter().map(|v| Some(v == Some(true))).collect();
+                    (count, normalized)
+                }
+                None => {
+                    // No filters = update all rows
+                    (
+                        batch.num_rows(),
+                        BooleanArray::from(vec![true; batch.num_rows()]),
+                    )
+                }
+            };
+
+            total_updated += update_count as u64;
+
+            if update_count == 0 {
+                new_batches.push(batch.clone());
+                continue;
+            }
+
+            let mut new_columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns());
+
+            for field in table_schema.fields() {
+                let column_name = field.name();
+                let original_column =
+                    batch.column_by_name(column_name).ok_or_else(|| {
+                        datafusion_common::DataFusionError::Internal(format!(
+                            "Column '{column_name}' not found in batch"
+                        ))
+                    })?;
+
+                let new_column =
+                    if let Some(physical_expr) = assignments.get(column_name.as_str()) {
+                        // Use evaluate_selection to only evaluate on matching rows.
+                        // This avoids errors (e.g., divide-by-zero) on rows that won't
+                        // be updated. The result is scattered back with nulls for
+                        // non-matching rows, which zip() will replace with originals.
+                        let new_values =
+                            physical_expr.evaluate_selection(batch, &update_mask)?;
+                        let new_array = new_values.into_array(batch.num_rows())?;
+
+                        // Convert to &dyn Array which implements Datum
+                        let new_arr: &dyn Array = new_array.as_ref();
+                        let orig_arr: &dyn Array = original_column.as_ref();
+                        zip(&update_mask, &new_arr, &orig_arr)?
+                    } else {
+                        Arc::clone(original_column)
+                    };
+
+                new_columns.push(new_column);
+            }
+
+            let updated_batch =
+                ArrowRecordBatch::try_new(Arc::clone(table_schema), new_columns)?;
+            new_batches.push(updated_batch);
+        }
+
+        *partition = new_batches;
     }
+
+    Ok(total_updated)
 }
@@ -0,0 +1,299 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`OverlayCatalogProviderList`] that layers local catalogs, schemas and
+//! tables over a shared parent catalog list
+
+use crate::{
+    CatalogProvider, CatalogProviderList, MemorySchemaProvider, SchemaProvider,
+    TableProvider,
+};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use datafusion_common::{Result, exec_err, plan_err};
+use std::sync::Arc;
+
+/// A [`CatalogProviderList`] that reads through to a parent catalog list but
+/// keeps all changes local.
+///
+/// Catalogs, schemas and tables registered through the overlay are only
+/// visible through the overlay, while everything registered in the parent,
+/// before or after the overlay was created, is visible through both. Tables
+/// and schemas of the parent can not be dropped through the overlay.
+///
+/// This is used by child sessions (see `SessionContext::new_child`) so that
+/// many short lived sessions can share the catalogs of one parent session
+/// without seeing each other's temporary tables.
+#[derive(Debug)]
+pub struct OverlayCatalogProviderList {
+    /// The shared catalog list
+    parent: Arc<dyn CatalogProviderList>,
+    /// Catalogs registered in the overlay and wrappers of parent catalogs
+    catalogs: DashMap<String, Arc<dyn CatalogProvider>>,
+}
+
+impl OverlayCatalogProviderList {
+    /// Creates an overlay over `parent` that has no local changes
+    pub fn new(parent: Arc<dyn CatalogProviderList>) -> Self {
+        Self {
+            parent,
+            catalogs: DashMap::new(),
+        }
+    }
+
+    /// Returns the catalog list this overlay reads through to
+    pub fn parent(&self) -> &Arc<dyn CatalogProviderList> {
+        &self.parent
+    }
+}
+
+impl CatalogProviderList for OverlayCatalogProviderList {
+    fn register_catalog(
+        &self,
+        name: String,
+        catalog: Arc<dyn CatalogProvider>,
+    ) -> Option<Arc<dyn CatalogProvider>> {
+        self.catalogs.insert(name, catalog)
+    }
+
+    fn catalog_names(&self) -> Vec<String> {
+        union(
+            self.parent.catalog_names(),
+            self.catalogs.iter().map(|c| c.key().clone()),
+        )
+    }
+
+    fn catalog(&self, name: &str) -> Option<Arc<dyn CatalogProvider>> {
+        if let Some(catalog) = self.catalogs.get(name) {
+            return Some(Arc::clone(catalog.value()));
+        }
+        let parent = self.parent.catalog(name)?;
+        let catalog = self
+            .catalogs
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(OverlayCatalogProvider::new(parent)));
+        Some(Arc::clone(catalog.value()))
+    }
+}
+
+/// Overlay of a single catalog of the parent catalog list
+#[derive(Debug)]
+struct OverlayCatalogProvider {
+    /// The shared catalog
+    parent: Arc<dyn CatalogProvider>,
+    /// Schemas registered in the overlay
+    registered: DashMap<String, Arc<dyn SchemaProvider>>,
+    /// Wrappers of parent schemas, holding the tables registered in them
+    overlays: DashMap<String, Arc<dyn SchemaProvider>>,
+}
+
+impl OverlayCatalogProvider {
+    fn new(parent: Arc<dyn CatalogProvider>) -> Self {
+        Self {
+            parent,
+            registered: DashMap::new(),
+            overlays: DashMap::new(),
+        }
+    }
+}
+
+impl CatalogProvider for OverlayCatalogProvider {
+    fn schema_names(&self) -> Vec<String> {
+        union(
+            self.parent.schema_names(),
+            self.registered.iter().map(|s| s.key().clone()),
+        )
+    }
+
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        if let Some(schema) = self.registered.get(name) {
+            return Some(Arc::clone(schema.value()));
+        }
+        if let Some(schema) = self.overlays.get(name) {
+            return Some(Arc::clone(schema.value()));
+        }
+        let parent = self.parent.schema(name)?;
+        let schema = self
+            .overlays
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(OverlaySchemaProvider::new(parent)));
+        Some(Arc::clone(schema.value()))
+    }
+
+    fn register_schema(
+        &self,
+        name: &str,
+        schema: Arc<dyn SchemaProvider>,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        Ok(self.registered.insert(name.to_string(), schema))
+    }
+
+    fn deregister_schema(
+        &self,
+        name: &str,
+        cascade: bool,
+    ) -> Result<Option<Arc<dyn SchemaProvider>>> {
+        let Some(schema) = self.registered.get(name).map(|s| Arc::clone(s.value()))
+        else {
+            if self.parent.schema(name).is_some() {
+                return plan_err!(
+                    "Schema '{name}' belongs to the parent session and cannot be dropped"
+                );
+            }
+            return Ok(None);
+        };
+        let table_names = schema.table_names();
+        if !cascade && !table_names.is_empty() {
+            return exec_err!(
+                "Cannot drop schema {} because other tables depend on it: {}",
+                name,
+                itertools::join(table_names.iter(), ", ")
+            );
+        }
+        Ok(self.registered.remove(name).map(|(_, schema)| schema))
+    }
+}
+
+/// Overlay of a single schema of a parent catalog
+#[derive(Debug)]
+struct OverlaySchemaProvider {
+    /// The shared schema
+    parent: Arc<dyn SchemaProvider>,
+    /// Tables registered in the overlay
+    local: MemorySchemaProvider,
+}
+
+impl OverlaySchemaProvider {
+    fn new(parent: Arc<dyn SchemaProvider>) -> Self {
+        Self {
+            parent,
+            local: MemorySchemaProvider::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl SchemaProvider for OverlaySchemaProvider {
+    fn owner_name(&self) -> Option<&str> {
+        self.parent.owner_name()
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        union(self.parent.table_names(), self.local.table_names())
+    }
+
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        match self.local.table(name).await? {
+            Some(table) => Ok(Some(table)),
+            None => self.parent.table(name).await,
+        }
+    }
+
+    fn register_table(
+        &self,
+        name: String,
+        table: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        if self.parent.table_exist(&name) {
+            return exec_err!("The table {name} already exists");
+        }
+        self.local.register_table(name, table)
+    }
+
+    fn deregister_table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        if !self.local.table_exist(name) && self.parent.table_exist(name) {
+            return plan_err!(
+                "Table '{name}' belongs to the parent session and cannot be dropped"
+            );
+        }
+        self.local.deregister_table(name)
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.local.table_exist(name) || self.parent.table_exist(name)
+    }
+}
+
+/// Appends the names in `local` that are not already in `parent`
+fn union(
+    mut parent: Vec<String>,
+    local: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    for name in local {
+        if !parent.contains(&name) {
+            parent.push(name);
+        }
+    }
+    parent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemTable, MemoryCatalogProvider, MemoryCatalogProviderList};
+    use arrow::datatypes::Schema;
+
+    fn table() -> Arc<dyn TableProvider> {
+        Arc::new(MemTable::try_new(Arc::new(Schema::empty()), vec![vec![]]).unwrap())
+    }
+
+    #[test]
+    fn overlay_keeps_changes_local() {
+        let parent_schema = Arc::new(MemorySchemaProvider::new());
+        parent_schema
+            .register_table("shared".into(), table())
+            .unwrap();
+        let parent_catalog = Arc::new(MemoryCatalogProvider::new());
+        parent_catalog
+            .register_schema("public", Arc::clone(&parent_schema) as _)
+            .unwrap();
+        let parent = Arc::new(MemoryCatalogProviderList::new());
+        parent.register_catalog("datafusion".into(), parent_catalog);
+
+        let overlay = OverlayCatalogProviderList::new(Arc::clone(&parent) as _);
+        let schema = overlay
+            .catalog("datafusion")
+            .unwrap()
+            .schema("public")
+            .unwrap();
+        assert!(schema.table_exist("shared"));
+
+        schema.register_table("local".into(), table()).unwrap();
+        assert!(schema.register_table("shared".into(), table()).is_err());
+        assert!(schema.deregister_table("shared").is_err());
+
+        // the wrapper is kept, so local tables stay visible through the overlay
+        let schema = overlay
+            .catalog("datafusion")
+            .unwrap()
+            .schema("public")
+            .unwrap();
+        let mut names = schema.table_names();
+        names.sort();
+        assert_eq!(names, vec!["local", "shared"]);
+        assert!(!parent_schema.table_exist("local"));
+
+        // tables registered in the parent later are visible too
+        parent_schema
+            .register_table("later".into(), table())
+            .unwrap();
+        assert!(schema.table_exist("later"));
+
+        assert!(schema.deregister_table("local").unwrap().is_some());
+        assert!(!schema.table_exist("local"));
+    }
+}
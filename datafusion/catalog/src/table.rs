@@ -21,15 +21,18 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::session::Session;
-use arrow::datatypes::SchemaRef;
+use arrow::array::RecordBatch;
+use arrow::datatypes::{DataType, SchemaRef};
 use async_trait::async_trait;
-use datafusion_common::{Constraints, Statistics, not_impl_err};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{Constraints, ScalarValue, Statistics, not_impl_err};
 use datafusion_common::{Result, internal_err};
 use datafusion_expr::Expr;
 
 use datafusion_expr::dml::InsertOp;
 use datafusion_expr::{
-    CreateExternalTable, LogicalPlan, TableProviderFilterPushDown, TableType,
+    CreateExternalTable, LogicalPlan, TableAggregate, TableProviderFilterPushDown,
+    TableType,
 };
 use datafusion_physical_plan::ExecutionPlan;
 
@@ -62,6 +65,16 @@ pub trait TableProvider: Any + Debug + Sync + Send {
         None
     }
 
+    /// Get the hidden metadata columns of this table, if any.
+    ///
+    /// Metadata columns, such as the file each row was read from, are not
+    /// part of [`Self::schema`] and `SELECT *`, but can be referenced by name.
+    /// In the projection passed to [`Self::scan`], index
+    /// `schema().fields().len() + i` refers to the `i`-th metadata column.
+    fn metadata_columns(&self) -> Option<SchemaRef> {
+        None
+    }
+
     /// Get the type of this table for metadata/catalog purposes.
     fn table_type(&self) -> TableType;
 
@@ -309,10 +322,38 @@ pub trait TableProvider: Any + Debug + Sync + Send {
         ])
     }
 
+    /// Tests whether the table can compute `aggregate` itself, in
+    /// [`Self::scan_with_aggregates`].
+    ///
+    /// For example, a table that maintains pre-aggregated data or a remote
+    /// SQL engine can compute `COUNT`, `MIN`, `MAX` or `SUM`, possibly
+    /// grouped by partition columns, much faster than DataFusion can from
+    /// the rows of the table.
+    ///
+    /// The aggregate expressions are the logical expressions of the query,
+    /// such as `count(t.a)`, and are only offered when the filters of the
+    /// query were all pushed down as [`TableProviderFilterPushDown::Exact`].
+    fn supports_aggregate_pushdown(&self, _aggregate: &TableAggregate) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Create an [`ExecutionPlan`] producing the result of `aggregate`, which
+    /// [`Self::supports_aggregate_pushdown`] accepted.
+    ///
+    /// The schema of the plan must be `aggregate.schema`: one column per
+    /// grouping expression followed by one column per aggregate expression.
+    async fn scan_with_aggregates(
+        &self,
+        _state: &dyn Session,
+        _aggregate: &TableAggregate,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Aggregate pushdown not implemented for this table")
+    }
+
     /// Get statistics for this table, if available
-    /// Although not presently used in mainline DataFusion, this allows implementation specific
-    /// behavior for downstream repositories, in conjunction with specialized optimizer rules to
-    /// perform operations such as re-ordering of joins.
+    ///
+    /// The logical optimizer uses the row counts and distinct value counts to
+    /// reorder inner joins, see the `ReorderJoins` optimizer rule.
     fn statistics(&self) -> Option<Statistics> {
         None
     }
@@ -378,6 +419,37 @@ pub trait TableProvider: Any + Debug + Sync + Send {
     async fn truncate(&self, _state: &dyn Session) -> Result<Arc<dyn ExecutionPlan>> {
         not_impl_err!("TRUNCATE not supported for {} table", self.table_type())
     }
+
+    /// Apply the changes of a `MERGE INTO` statement to the table.
+    ///
+    /// `input` produces one row for each inserted, updated or deleted row,
+    /// see [`WriteOp::Merge`] for its schema.
+    ///
+    /// Returns an [`ExecutionPlan`] producing a single row with `count`
+    /// (UInt64), the number of rows changed.
+    ///
+    /// [`WriteOp::Merge`]: datafusion_expr::dml::WriteOp::Merge
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        _input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("MERGE not supported for {} table", self.table_type())
+    }
+
+    /// Store the statistics collected by an `ANALYZE TABLE` statement.
+    ///
+    /// Columns that were not analyzed have [`ColumnStatistics`] with all
+    /// values `Absent`. Implementations that keep the statistics should
+    /// return them from [`Self::statistics`], so later queries can use them.
+    ///
+    /// [`ColumnStatistics`]: datafusion_common::ColumnStatistics
+    async fn update_statistics(&self, _statistics: Statistics) -> Result<()> {
+        not_impl_err!(
+            "ANALYZE TABLE not supported for {} table",
+            self.table_type()
+        )
+    }
 }
 
 impl dyn TableProvider {
@@ -565,6 +637,28 @@ pub trait TableFunctionImpl: Debug + Sync + Send + Any {
         #[expect(deprecated)]
         self.call(args.exprs)
     }
+
+    /// Return the schema of the rows the function returns when called
+    /// laterally with arguments of the given types, or `None` if the function
+    /// can not be called laterally, which is the default.
+    ///
+    /// The arguments of a lateral call, such as `SELECT * FROM t, LATERAL f(t.x)`,
+    /// reference the columns of the preceding `FROM` items, and the function is
+    /// called with [`Self::call_lateral`] for every row of them.
+    fn lateral_schema(&self, _arg_types: &[DataType]) -> Result<Option<SchemaRef>> {
+        Ok(None)
+    }
+
+    /// Call the function laterally with the argument values of one row,
+    /// returning the rows for that row, which must have the schema returned by
+    /// [`Self::lateral_schema`].
+    fn call_lateral(
+        &self,
+        _args: &[ScalarValue],
+        _config: &ConfigOptions,
+    ) -> Result<Vec<RecordBatch>> {
+        not_impl_err!("Table function does not support lateral calls")
+    }
 }
 
 /// A table that uses a function to generate data
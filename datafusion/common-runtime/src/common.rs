@@ -21,6 +21,7 @@ use std::{
     task::{Context, Poll},
 };
 
+use tokio::runtime::Handle;
 use tokio::task::{JoinError, JoinHandle};
 
 use crate::trace_utils::{trace_block, trace_future};
@@ -49,6 +50,18 @@ impl<R: 'static> SpawnedTask<R> {
         Self { inner }
     }
 
+    /// Spawns `task` on the runtime of `handle`, rather than on the current
+    /// runtime as [`Self::spawn`] does
+    pub fn spawn_on<T>(task: T, handle: &Handle) -> Self
+    where
+        T: Future<Output = R>,
+        T: Send + 'static,
+        R: Send,
+    {
+        let inner = handle.spawn(trace_future(task));
+        Self { inner }
+    }
+
     pub fn spawn_blocking<T>(task: T) -> Self
     where
         T: FnOnce() -> R,
@@ -24,7 +24,7 @@ use crate::encryption::{FileDecryptionProperties, FileEncryptionProperties};
 use crate::error::_config_err;
 use crate::format::{ExplainAnalyzeCategories, ExplainFormat, MetricType};
 use crate::parquet_config::DFParquetWriterVersion;
-use crate::parsers::{CompressionTypeVariant, CsvQuoteStyle};
+use crate::parsers::{CompressionTypeVariant, CsvLineTerminator, CsvQuoteStyle};
 use crate::utils::get_available_parallelism;
 use crate::{DataFusionError, Result};
 #[cfg(feature = "parquet_encryption")]
@@ -257,6 +257,13 @@ config_namespace! {
         /// parallel file scanning. Setting this to `true` ensures that newlines in values are
         /// parsed successfully, which may reduce performance.
         pub newlines_in_values: bool, default = false
+
+        /// Comma separated list of namespaces, such as `my_ext.geo`, searched
+        /// in order for the functions that are called without a namespace and
+        /// are not registered under their own name. A function is registered
+        /// in a namespace by naming it after the namespace, such as
+        /// `my_ext.geo.st_area`, and can always be called by this qualified name
+        pub function_search_path: Option<String>, default = None
     }
 }
 
@@ -466,6 +473,152 @@ impl From<SpillCompression> for Option<CompressionType> {
     }
 }
 
+/// When a partial hash aggregation emits groups before the end of its input,
+/// see `datafusion.execution.aggregate_emission_policy`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateEmissionPolicy {
+    /// Emit groups early only if the input is known to be ordered by the
+    /// group keys, or under memory pressure
+    #[default]
+    Hash,
+    /// Also emit groups early while the input turns out to be sorted by the
+    /// first group key at runtime
+    Adaptive,
+}
+
+impl FromStr for AggregateEmissionPolicy {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "hash" | "" => Ok(Self::Hash),
+            "adaptive" => Ok(Self::Adaptive),
+            other => Err(DataFusionError::Configuration(format!(
+                "Invalid aggregate emission policy: {other}. Expected one of: hash, adaptive"
+            ))),
+        }
+    }
+}
+
+impl ConfigField for AggregateEmissionPolicy {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = AggregateEmissionPolicy::from_str(value)?;
+        Ok(())
+    }
+}
+
+impl Display for AggregateEmissionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Self::Hash => "hash",
+            Self::Adaptive => "adaptive",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// How `sum`, `avg`, `var` and `stddev` add up floating point values, see
+/// `datafusion.execution.float_summation`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatSummation {
+    /// Add the values in the order in which they arrive
+    #[default]
+    Standard,
+    /// Track the rounding error of the running sum (Neumaier summation)
+    Compensated,
+    /// Compute the correctly rounded result of the exact sum, which does not
+    /// depend on the order of the values or the number of partitions
+    Deterministic,
+}
+
+impl FromStr for FloatSummation {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "standard" | "" => Ok(Self::Standard),
+            "compensated" => Ok(Self::Compensated),
+            "deterministic" => Ok(Self::Deterministic),
+            other => Err(DataFusionError::Configuration(format!(
+                "Invalid float summation: {other}. Expected one of: standard, compensated, deterministic"
+            ))),
+        }
+    }
+}
+
+impl ConfigField for FloatSummation {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = FloatSummation::from_str(value)?;
+        Ok(())
+    }
+}
+
+impl Display for FloatSummation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Self::Standard => "standard",
+            Self::Compensated => "compensated",
+            Self::Deterministic => "deterministic",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// What happens when the results of a query exceed
+/// `datafusion.execution.max_result_rows` or
+/// `datafusion.execution.max_result_bytes`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResultLimitAction {
+    /// Fail the query
+    #[default]
+    Error,
+    /// Return the results up to the limit and report a warning
+    Truncate,
+}
+
+impl FromStr for ResultLimitAction {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" | "" => Ok(Self::Error),
+            "truncate" => Ok(Self::Truncate),
+            other => Err(DataFusionError::Configuration(format!(
+                "Invalid result limit action: {other}. Expected one of: error, truncate"
+            ))),
+        }
+    }
+}
+
+impl ConfigField for ResultLimitAction {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = ResultLimitAction::from_str(value)?;
+        Ok(())
+    }
+}
+
+impl Display for ResultLimitAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Self::Error => "error",
+            Self::Truncate => "truncate",
+        };
+        write!(f, "{str}")
+    }
+}
+
 config_namespace! {
     /// Options related to query execution
     ///
@@ -497,6 +650,21 @@ config_namespace! {
         /// Support for build_side.num_rows() >= u32::MAX will be added in the future.
         pub perfect_hash_join_min_key_density: f64, default = 0.15
 
+        /// When set to true, each partition of a `HashJoinExec` in `Partitioned`
+        /// mode checks the actual number of rows on its build side before
+        /// probing. If it exceeds the estimated number of rows by more than
+        /// `hash_join_adaptive_build_side_ratio`, and the probe side of the
+        /// partition turns out to be smaller, the hash table is built on the
+        /// probe side instead. Joins whose output ordering is required are
+        /// not changed.
+        pub enable_hash_join_adaptive_build_side: bool, default = false
+
+        /// Factor by which the build side of a hash join must exceed its
+        /// estimated number of rows before switching the build side is
+        /// considered, see `enable_hash_join_adaptive_build_side`. Build sides
+        /// without an estimate are always considered.
+        pub hash_join_adaptive_build_side_ratio: f64, default = 4.0
+
         /// When set to true, record batches will be examined between each operator and
         /// small batches will be coalesced into larger batches. This is helpful when there
         /// are highly selective filters or joins that could produce tiny output batches. The
@@ -508,6 +676,15 @@ config_namespace! {
         /// `ListingTableProvider` in DataFusion. Defaults to true.
         pub collect_statistics: bool, default = true
 
+        /// When set to true, the dictionary encoded columns of the batches
+        /// read from the files of each partition of a `ListingTable` scan
+        /// share a unified dictionary, instead of a different dictionary per
+        /// file. This lets operators such as aggregations and joins combine
+        /// batches without merging their dictionaries. The unified dictionary
+        /// grows with the number of distinct values, so this is best suited
+        /// to low cardinality columns.
+        pub unify_scan_dictionaries: bool, default = false
+
         /// Number of partitions for query execution. Increasing partitions can increase
         /// concurrency.
         ///
@@ -590,12 +767,58 @@ config_namespace! {
         /// A smaller value creates more files but allows finer-grained space reclamation
         /// as files can be deleted once fully consumed.
         ///
-        /// Now only `RepartitionExec` supports this spill file rotation feature, other spilling operators
-        /// may create spill files larger than the limit.
+        /// Now only `RepartitionExec` and spooled results (see `spool_results`) support this
+        /// spill file rotation feature, other spilling operators may create spill files larger
+        /// than the limit.
         ///
         /// Default: 128 MB
         pub max_spill_file_size_bytes: usize, default = 128 * 1024 * 1024
 
+        /// Should `DataFrame::execute_stream` spool the results of a query to temporary
+        /// Arrow IPC files, and stream them back from disk. The query then completes, and
+        /// releases its memory, without waiting for the results to be consumed, which
+        /// suits servers whose clients fetch large results slowly. Requires the disk
+        /// manager to allow temporary files.
+        pub spool_results: bool, default = false
+
+        /// Maximum number of rows a query returns through `DataFrame::collect`,
+        /// `DataFrame::execute_stream` and their partitioned variants. This protects
+        /// services running interactive queries from accidentally returning huge
+        /// results, such as a `SELECT *` over billions of rows. What happens when a
+        /// query exceeds the limit is set by `result_limit_action`. If NULL, the
+        /// number of rows is not limited
+        pub max_result_rows: Option<usize>, default = None
+
+        /// Maximum size in bytes of the results a query returns, see `max_result_rows`.
+        /// The size of the results is the memory size of their Arrow arrays. If NULL,
+        /// the size is not limited
+        pub max_result_bytes: Option<usize>, default = None
+
+        /// What happens when the results of a query exceed `max_result_rows` or
+        /// `max_result_bytes`: `error` fails the query, while `truncate` returns the
+        /// results up to the limit and reports a warning
+        pub result_limit_action: ResultLimitAction, default = ResultLimitAction::Error
+
+        /// Maximum time, in milliseconds, an operator evaluating expressions (`FilterExec`
+        /// and `ProjectionExec`) spends evaluating them before it yields to the Tokio
+        /// runtime. Expensive expressions, such as regular expressions over huge strings
+        /// or large `IN` lists, can otherwise block a worker thread of a shared runtime
+        /// for a long time. The operator yields between batches, so a single batch may
+        /// still take longer. The number of yields is reported as the `poll_time_yields`
+        /// metric. Set to 0 to disable
+        pub max_poll_duration_ms: usize, default = 0
+
+        /// Maximum number of batches an input of an order-preserving `RepartitionExec`
+        /// buffers in memory for one output partition.
+        ///
+        /// The merge of an output partition needs a batch from every input, so an
+        /// output partition waiting for a slow input, for example on skewed data,
+        /// can not consume the batches the other inputs send it. Once this many batches
+        /// are buffered, further batches are spilled to disk until the output partition
+        /// catches up. Batches are only spilled if the disk manager allows temporary
+        /// files. Set to 0 to buffer without limit.
+        pub preserve_order_repartition_max_buffered_batches: usize, default = 16
+
         /// Number of files to read in parallel when inferring schema and statistics
         pub meta_fetch_concurrency: usize, default = 32
 
@@ -617,6 +840,14 @@ config_namespace! {
         /// memory consumption
         pub max_buffered_batches_per_output_file: usize, default = 2
 
+        /// Maximum number of files a write with partition columns (`COPY TO ...
+        /// PARTITIONED BY` or `INSERT INTO` a partitioned table) keeps open at the
+        /// same time, one per partition. When a batch belongs to a new partition
+        /// while this many files are open, the file of the least recently used
+        /// partition is finished, and later rows of that partition are written
+        /// to a new file. Set to 0 for no limit
+        pub max_open_partition_files: usize, default = 1024
+
         /// Should sub directories be ignored when scanning directories for data
         /// files. Defaults to true (ignores subdirectories), consistent with
         /// Hive. Note that this setting does not affect reading partitioned
@@ -639,6 +870,13 @@ config_namespace! {
         /// Should DataFusion keep the columns used for partition_by in the output RecordBatches
         pub keep_partition_by_columns: bool, default = false
 
+        /// Format of timestamp values in the directory names of writes with
+        /// partition columns (`COPY TO ... PARTITIONED BY` or `INSERT INTO` a
+        /// partitioned table), as a chrono format string. For example,
+        /// `%Y-%m-%d` names the directories of timestamps truncated to days by
+        /// their dates
+        pub partition_timestamp_format: String, default = "%Y-%m-%dT%H:%M:%S%.f".to_string()
+
         /// Aggregation ratio (number of distinct groups / number of input rows)
         /// threshold for skipping partial aggregation. If the value is greater
         /// then partial aggregation will skip aggregation for further input
@@ -648,6 +886,36 @@ config_namespace! {
         /// aggregation ratio check and trying to switch to skipping aggregation mode
         pub skip_partial_aggregation_probe_rows_threshold: usize, default = 100_000
 
+        /// Number of consecutive times a partial aggregation partition may emit
+        /// its groups early, because of memory pressure, with an aggregation ratio
+        /// (number of groups / number of input rows since the previous early
+        /// emission) greater than `skip_partial_aggregation_probe_ratio_threshold`,
+        /// before it skips aggregation for further input, regardless of
+        /// `skip_partial_aggregation_probe_rows_threshold`. This keeps high
+        /// cardinality group bys from repeatedly filling the memory pool. 0
+        /// disables the check
+        pub skip_partial_aggregation_early_emit_threshold: usize, default = 0
+
+        /// When partial hash aggregation emits groups before the end of its input.
+        /// `hash` emits early only if the input is known to be ordered by the
+        /// group keys, or under memory pressure. `adaptive` additionally detects
+        /// at runtime whether the input is sorted by the first GROUP BY column and,
+        /// as long as it is, emits and evicts the groups whose key can no longer
+        /// appear, bounding the memory used by the partial aggregation. Groups
+        /// emitted more than once are merged by the final aggregation.
+        pub aggregate_emission_policy: AggregateEmissionPolicy, default = AggregateEmissionPolicy::Hash
+
+        /// How `sum`, `avg`, `var` and `stddev` add up `Float64` values.
+        /// `standard` adds them in the order in which they arrive, so results
+        /// may differ in the last digits between runs and partition counts.
+        /// `compensated` tracks the rounding error of the running sums (Neumaier
+        /// summation), which makes results more accurate. `deterministic`
+        /// computes the correctly rounded result of the exact sums, which is the
+        /// same for every order of the input and number of partitions. Both
+        /// alternatives are slower than `standard`. `var` and `stddev` use exact
+        /// sums in either of them
+        pub float_summation: FloatSummation, default = FloatSummation::Standard
+
         /// Should DataFusion use row number estimates at the input to decide
         /// whether increasing parallelism is beneficial or not. By default,
         /// only exact row numbers (not estimates) are used for this decision.
@@ -669,24 +937,23 @@ config_namespace! {
         /// the remote end point.
         pub objectstore_writer_buffer_size: usize, default = 10 * 1024 * 1024
 
+        /// Maximum number of bytes buffered by the encoders of the parallel
+        /// Parquet writer (see `datafusion.execution.parquet.allow_single_file_parallelism`)
+        /// for each output file. Once the limit is reached, the row group being
+        /// encoded is closed early and no more data is encoded until the buffered
+        /// row groups are flushed to the object store. If NULL, the buffered data
+        /// is only limited by the memory pool.
+        pub parquet_writer_memory_limit: Option<usize>, default = None
+
         /// Whether to enable ANSI SQL mode.
         ///
-        /// The flag is experimental and relevant only for DataFusion Spark built-in functions
+        /// The flag is experimental. When set to `true`, integer `+`, `-` and `*` raise
+        /// an error on overflow instead of wrapping around, consistently at runtime and
+        /// during constant folding, and the DataFusion Spark built-in functions follow
+        /// the ANSI semantics of Spark, e.g. `abs()` on the minimum representable integer
+        /// value raises an overflow error instead of returning the input value.
         ///
-        /// When `enable_ansi_mode` is set to `true`, the query engine follows ANSI SQL
-        /// semantics for expressions, casting, and error handling. This means:
-        /// - **Strict type coercion rules:** implicit casts between incompatible types are disallowed.
-        /// - **Standard SQL arithmetic behavior:** operations such as division by zero,
-        ///   numeric overflow, or invalid casts raise runtime errors rather than returning
-        ///   `NULL` or adjusted values.
-        /// - **Consistent ANSI behavior** for string concatenation, comparisons, and `NULL` handling.
-        ///
-        /// When `enable_ansi_mode` is `false` (the default), the engine uses a more permissive,
-        /// non-ANSI mode designed for user convenience and backward compatibility. In this mode:
-        /// - Implicit casts between types are allowed (e.g., string to integer when possible).
-        /// - Arithmetic operations are more lenient — for example, `abs()` on the minimum
-        ///   representable integer value returns the input value instead of raising overflow.
-        /// - Division by zero or invalid casts may return `NULL` instead of failing.
+        /// Division by zero and invalid casts raise an error regardless of this flag.
         ///
         /// # Default
         /// `false` — ANSI SQL mode is disabled by default.
@@ -706,6 +973,16 @@ config_namespace! {
         ///
         /// Disabled by default, set to a number greater than 0 for enabling it.
         pub hash_join_buffering_capacity: usize, default = 0
+
+        /// Comma separated list of directories to load plugins from when the
+        /// session is created. Every shared library (for example `.so` files
+        /// on Linux) directly in these directories is loaded as a plugin, and
+        /// the functions, catalogs and table factories it exports are
+        /// registered with the session. Plugins are built with
+        /// `datafusion_ffi::export_plugin!`, and must use the same major
+        /// version of DataFusion as the host. This is only supported by hosts
+        /// that load plugins, such as `datafusion-cli`
+        pub plugin_dirs: Option<String>, default = None
     }
 }
 
@@ -1105,6 +1382,12 @@ config_namespace! {
         /// cause regressions in both memory usage and runtime.
         pub enable_window_topn: bool, default = false
 
+        /// When set to true, the optimizer will fuse a ProjectionExec on top
+        /// of a FilterExec into a single FilterProjectExec, which evaluates the
+        /// predicate and the projection in one pass over each input batch and
+        /// computes sub-expressions shared by both only once.
+        pub enable_filter_projection_fusion: bool, default = false
+
         /// When set to true, the optimizer will push TopK (Sort with fetch)
         /// below hash repartition when the partition key is a prefix of the
         /// sort key, reducing data volume before the shuffle.
@@ -1244,6 +1527,17 @@ config_namespace! {
         /// `RepartitionExec` even if this requires subsequently resorting data using a `SortExec`.
         pub prefer_existing_sort: bool, default = false
 
+        /// When set to true, the physical plan optimizer chooses between preserving the
+        /// ordering of a bounded input through an order-preserving `RepartitionExec` and
+        /// sorting the repartitioned data again based on the estimated number of rows
+        /// of the input, instead of `prefer_existing_sort`.
+        ///
+        /// The ordering is preserved when the input is estimated to have more rows than
+        /// an order-preserving repartition buffers (`target_partitions` squared batches
+        /// of `batch_size` rows), as sorting it again then costs more. When the number
+        /// of rows is unknown, `prefer_existing_sort` decides.
+        pub order_preserving_repartition_cost_model: bool, default = false
+
         /// When set to true, the logical plan optimizer will produce warning
         /// messages if any optimization rules produce errors and then proceed to the next
         /// rule. When set to false, any rules that produce errors will cause the query to fail
@@ -1257,11 +1551,21 @@ config_namespace! {
         pub top_down_join_key_reordering: bool, default = true
 
         /// When set to true, the physical plan optimizer may swap join inputs
-        /// based on statistics. When set to false, statistics-driven join
-        /// input reordering is disabled and the original join order in the
-        /// query is used.
+        /// based on statistics, and the logical plan optimizer may reorder
+        /// trees of inner joins as configured by `join_reordering_max_relations`.
+        /// When set to false, statistics-driven join reordering is disabled and
+        /// the original join order in the query is used.
         pub join_reordering: bool, default = true
 
+        /// The maximum number of relations in a tree of inner joins that the
+        /// logical plan optimizer reorders based on the row counts and distinct
+        /// value counts reported by `TableProvider::statistics`. The number of
+        /// join orders considered grows exponentially with the number of
+        /// relations; larger trees keep the join order of the query. Values
+        /// above 16 are treated as 16. Cost-based join reordering is disabled
+        /// when this is less than 3 or when `join_reordering` is false.
+        pub join_reordering_max_relations: usize, transform = OptimizerOptions::limited_join_reordering_max_relations, default = 10
+
         /// When set to true, the physical plan optimizer uses the pluggable
         /// `StatisticsRegistry` for statistics propagation across operators.
         /// This enables more accurate cardinality estimates compared to each
@@ -1277,6 +1581,18 @@ config_namespace! {
         /// one range filter.
         pub enable_piecewise_merge_join: bool, default = false
 
+        /// When set to true, hash joins on expressions, such as `lower(a.k) = lower(b.k)`,
+        /// evaluate the key expressions themselves instead of computing them in projections
+        /// added below the join. This keeps the join inputs unchanged, which preserves column
+        /// provenance and lets more filters and projections be pushed down through the join.
+        pub hash_join_evaluate_key_expressions: bool, default = false
+
+        /// When set to true, the physical plan optimizer replaces chains of inner hash joins
+        /// of a fact table with several small dimension tables by a single StarJoinExec,
+        /// which looks up each fact row in all dimensions in one pass. StarJoinExec is
+        /// currently experimental.
+        pub enable_star_join: bool, default = false
+
         /// The maximum estimated size in bytes for one input side of a HashJoin
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold: usize, default = 1024 * 1024
@@ -1315,6 +1631,15 @@ config_namespace! {
         /// See: <https://trino.io/docs/current/admin/dynamic-filtering.html#dynamic-filter-collection-thresholds>
         pub hash_join_inlist_pushdown_max_distinct_values: usize, default = 150
 
+        /// When set, build sides of a hash join that are too large to be pushed down as an InList
+        /// expression push down a bloom filter over their join keys for dynamic filtering, with this
+        /// false positive probability, instead of a lookup into the hash table.
+        ///
+        /// A bloom filter is much smaller than the hash table for large build sides and is cheaper
+        /// to probe, at the cost of letting some probe rows without a match through the filter.
+        /// Building it requires hashing the build side join keys once more.
+        pub hash_join_bloom_filter_pushdown_fpp: Option<f64>, default = None
+
         /// The default filter selectivity used by Filter Statistics
         /// when an exact selectivity cannot be determined. Valid values are
         /// between 0 (no selectivity) and 100 (all rows are selected).
@@ -1348,6 +1673,24 @@ config_namespace! {
         /// closer to the leaf table scans, and push those projections down
         /// towards the leaf nodes.
         pub enable_leaf_expression_pushdown: bool, default = true
+
+        /// When set to a value greater than 0, filter predicates of the form
+        /// `expr [NOT] IN (<literals>)` with at least this many literals are
+        /// rewritten to a semi (or anti) join against a `VALUES` table, which
+        /// avoids comparing every row against a very large list. 0 disables
+        /// the rewrite.
+        pub in_list_to_join_threshold: usize, default = 0
+
+        /// When set to true, the analyzed logical plan is checked for likely
+        /// mistakes, such as columns of subqueries that are never used,
+        /// predicates that are always true or false, and casts of literals
+        /// that always fail. Any findings are reported as warnings of the session.
+        pub report_plan_warnings: bool, default = true
+
+        /// When set to true, hash joins in `CollectLeft` mode whose build sides
+        /// are the same subplan, such as a dimension table joined twice, build
+        /// a single hash table that is shared by all of them.
+        pub share_hash_join_build_side: bool, default = true
     }
 }
 
@@ -1395,6 +1738,23 @@ config_namespace! {
     }
 }
 
+impl OptimizerOptions {
+    /// The largest supported value of `join_reordering_max_relations`. The
+    /// cost-based join reordering keeps the cheapest join of every subset of
+    /// the relations, so its memory grows with `2^n` for `n` relations.
+    pub const MAX_JOIN_REORDERING_RELATIONS: usize = 16;
+
+    /// Returns `value`, limited to [`Self::MAX_JOIN_REORDERING_RELATIONS`]
+    fn limited_join_reordering_max_relations(value: &str) -> String {
+        match value.parse::<usize>() {
+            Ok(relations) if relations > Self::MAX_JOIN_REORDERING_RELATIONS => {
+                Self::MAX_JOIN_REORDERING_RELATIONS.to_string()
+            }
+            _ => value.to_owned(),
+        }
+    }
+}
+
 impl ExecutionOptions {
     /// Returns the correct parallelism based on the provided `value`.
     /// If `value` is `"0"`, returns the default available parallelism, computed with
@@ -1492,6 +1852,13 @@ pub struct ConfigOptions {
     pub extensions: Extensions,
     /// Formatting options when printing batches
     pub format: FormatOptions,
+    /// Opaque values attached to the session, keyed by their Rust type.
+    ///
+    /// Unlike [`Self::extensions`], these are not configuration options and
+    /// can not be set from strings: they carry typed per-query context, such
+    /// as the user running the query, to the functions evaluated while
+    /// executing it. See `SessionConfig::with_extension`.
+    pub session_extensions: crate::extensions::Extensions,
 }
 
 impl ConfigField for ConfigOptions {
@@ -2053,6 +2420,17 @@ impl ConfigField for CsvQuoteStyle {
     }
 }
 
+impl ConfigField for CsvLineTerminator {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = CsvLineTerminator::from_str(value)?;
+        Ok(())
+    }
+}
+
 /// An implementation trait used to recursively walk configuration
 pub trait Visit {
     fn some<V: Display>(&mut self, key: &str, value: V, description: &'static str);
@@ -3175,6 +3553,9 @@ config_namespace! {
         /// Quote style for CSV writing.
         /// One of: "Always", "Necessary", "NonNumeric", "Never"
         pub quote_style: CsvQuoteStyle, default = CsvQuoteStyle::Necessary
+        /// Line terminator for CSV writing.
+        /// One of: "LF", "CRLF"
+        pub line_terminator: CsvLineTerminator, default = CsvLineTerminator::Lf
         /// Whether to ignore leading whitespace in string values when writing CSV.
         /// Defaults to `false` when `None`.
         pub ignore_leading_whitespace: Option<bool>, default = None
@@ -3295,6 +3676,12 @@ impl CsvOptions {
         self
     }
 
+    /// Set the line terminator for CSV writing.
+    pub fn with_line_terminator(mut self, line_terminator: CsvLineTerminator) -> Self {
+        self.line_terminator = line_terminator;
+        self
+    }
+
     /// Set whether to ignore leading whitespace in string values when writing CSV.
     pub fn with_ignore_leading_whitespace(
         mut self,
@@ -3402,6 +3789,9 @@ config_namespace! {
        /// ]
        /// ```
        pub newline_delimited: bool, default = true
+        /// Whether to write null values as explicit `null`s when writing JSON.
+        /// Null values are omitted from the written objects when `None` or `false`.
+        pub explicit_nulls: Option<bool>, default = None
     }
 }
 
@@ -3437,8 +3827,8 @@ mod tests {
     #[cfg(feature = "parquet")]
     use crate::config::TableParquetOptions;
     use crate::config::{
-        ConfigEntry, ConfigExtension, ConfigField, ConfigFileType, ExtensionOptions,
-        Extensions, TableOptions,
+        ConfigEntry, ConfigExtension, ConfigField, ConfigFileType, ConfigOptions,
+        ExtensionOptions, Extensions, OptimizerOptions, TableOptions,
     };
     use std::any::Any;
     use std::collections::HashMap;
@@ -3566,6 +3956,22 @@ mod tests {
         assert_eq!(COUNT.load(std::sync::atomic::Ordering::Relaxed), 1);
     }
 
+    #[test]
+    fn join_reordering_max_relations_is_limited() {
+        let mut options = ConfigOptions::new();
+        options
+            .set("datafusion.optimizer.join_reordering_max_relations", "16")
+            .unwrap();
+        assert_eq!(options.optimizer.join_reordering_max_relations, 16);
+        options
+            .set("datafusion.optimizer.join_reordering_max_relations", "64")
+            .unwrap();
+        assert_eq!(
+            options.optimizer.join_reordering_max_relations,
+            OptimizerOptions::MAX_JOIN_REORDERING_RELATIONS
+        );
+    }
+
     #[test]
     fn reset_nested_scalar_reports_helpful_error() {
         let mut value = true;
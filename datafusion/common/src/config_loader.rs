@@ -0,0 +1,418 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ConfigLoader`]: build [`ConfigOptions`] from named profiles, files and
+//! environment variables
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::Result;
+use crate::config::ConfigOptions;
+use crate::error::_config_err;
+
+/// Settings tuned for memory constrained deployments: smaller batches and
+/// sort-merge joins, which can spill, instead of hash joins
+const LOW_MEMORY_PROFILE: &[(&str, &str)] = &[
+    ("datafusion.execution.batch_size", "4096"),
+    ("datafusion.optimizer.prefer_hash_join", "false"),
+    ("datafusion.execution.max_spill_file_size_bytes", "33554432"),
+];
+
+/// Settings tuned for low latency ad-hoc queries: files are not scanned
+/// up front to collect statistics
+const INTERACTIVE_PROFILE: &[(&str, &str)] =
+    &[("datafusion.execution.collect_statistics", "false")];
+
+/// Settings tuned for throughput of large batch jobs
+const BATCH_ETL_PROFILE: &[(&str, &str)] = &[
+    ("datafusion.execution.batch_size", "16384"),
+    ("datafusion.execution.collect_statistics", "true"),
+];
+
+/// One layer of settings, and where they came from for error messages
+#[derive(Debug, Clone)]
+struct Layer {
+    source: String,
+    settings: Vec<(String, String)>,
+}
+
+/// Builds [`ConfigOptions`] by applying layers of settings on top of the
+/// defaults, so deployments can describe their configuration declaratively
+/// instead of calling setters for each option.
+///
+/// Layers are applied in the order they are added, so later layers override
+/// earlier ones. A typical setup applies a named profile, then a
+/// configuration file, then environment variables:
+///
+/// ```
+/// # use datafusion_common::config_loader::ConfigLoader;
+/// # use datafusion_common::Result;
+/// # fn main() -> Result<()> {
+/// let options = ConfigLoader::new()
+///     .with_profile("low_memory")?
+///     .with_settings([("datafusion.execution.target_partitions", "4")])
+///     .with_env()
+///     .load()?;
+/// assert_eq!(options.execution.batch_size, 4096);
+/// assert_eq!(options.execution.target_partitions, 4);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The built-in profiles are `low_memory`, `interactive` and `batch_etl`.
+/// Additional profiles can be defined with [`Self::with_profile_definition`]
+/// or in a TOML file (requires the `toml` feature), see
+/// [`Self::with_toml_str`].
+///
+/// Unlike [`ConfigOptions::set`], loading does not stop at the first
+/// unknown `datafusion.*` key: all of them are listed in the returned error.
+#[derive(Debug, Clone)]
+pub struct ConfigLoader {
+    profiles: BTreeMap<String, Vec<(String, String)>>,
+    layers: Vec<Layer>,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        let profiles = [
+            ("low_memory", LOW_MEMORY_PROFILE),
+            ("interactive", INTERACTIVE_PROFILE),
+            ("batch_etl", BATCH_ETL_PROFILE),
+        ]
+        .into_iter()
+        .map(|(name, settings)| {
+            let settings = settings
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            (name.to_string(), settings)
+        })
+        .collect();
+        Self {
+            profiles,
+            layers: vec![],
+        }
+    }
+}
+
+impl ConfigLoader {
+    /// Create a loader with the built-in profiles and no layers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define (or replace) the profile `name` with the given settings
+    pub fn with_profile_definition<K, V>(
+        mut self,
+        name: impl Into<String>,
+        settings: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let settings = settings
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.profiles.insert(name.into(), settings);
+        self
+    }
+
+    /// Return the names of the known profiles
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Add a layer with the settings of the profile `name`
+    pub fn with_profile(mut self, name: &str) -> Result<Self> {
+        let Some(settings) = self.profiles.get(name) else {
+            return _config_err!(
+                "Unknown configuration profile '{name}', expected one of: {}",
+                self.profile_names().collect::<Vec<_>>().join(", ")
+            );
+        };
+        self.layers.push(Layer {
+            source: format!("profile '{name}'"),
+            settings: settings.clone(),
+        });
+        Ok(self)
+    }
+
+    /// Add a layer with the given `key`, `value` settings
+    pub fn with_settings<K, V>(
+        mut self,
+        settings: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.layers.push(Layer {
+            source: "settings".to_string(),
+            settings: settings
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        });
+        self
+    }
+
+    /// Add a layer with the options set in environment variables, named as
+    /// described in [`ConfigOptions::from_env`]
+    pub fn with_env(mut self) -> Self {
+        let settings = ConfigOptions::default()
+            .entries()
+            .into_iter()
+            .filter_map(|entry| {
+                let env = entry.key.to_uppercase().replace('.', "_");
+                let value = std::env::var_os(env)?;
+                Some((entry.key, value.to_string_lossy().into_owned()))
+            })
+            .collect();
+        self.layers.push(Layer {
+            source: "environment".to_string(),
+            settings,
+        });
+        self
+    }
+
+    /// Parse `toml` and add its settings as a layer.
+    ///
+    /// Options are set using nested tables or dotted keys. The optional
+    /// top-level `profile` key adds a layer with that profile's settings
+    /// before the file's own settings, and `[profiles.<name>]` tables define
+    /// additional profiles:
+    ///
+    /// ```toml
+    /// profile = "nightly"
+    ///
+    /// [datafusion.execution]
+    /// target_partitions = 16
+    ///
+    /// [profiles.nightly]
+    /// datafusion.execution.batch_size = 32768
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn with_toml_str(mut self, toml: &str) -> Result<Self> {
+        let mut table = toml.parse::<toml::Table>().map_err(|e| {
+            crate::DataFusionError::Configuration(format!(
+                "Failed to parse TOML configuration: {e}"
+            ))
+        })?;
+
+        if let Some(profiles) = table.remove("profiles") {
+            let toml::Value::Table(profiles) = profiles else {
+                return _config_err!("Expected 'profiles' to be a table");
+            };
+            for (name, profile) in profiles {
+                let toml::Value::Table(profile) = profile else {
+                    return _config_err!("Expected profile '{name}' to be a table");
+                };
+                let mut settings = vec![];
+                flatten_toml("", profile, &mut settings)?;
+                self.profiles.insert(name, settings);
+            }
+        }
+
+        if let Some(profile) = table.remove("profile") {
+            let toml::Value::String(profile) = profile else {
+                return _config_err!("Expected 'profile' to be a string");
+            };
+            self = self.with_profile(&profile)?;
+        }
+
+        let mut settings = vec![];
+        flatten_toml("", table, &mut settings)?;
+        self.layers.push(Layer {
+            source: "TOML configuration".to_string(),
+            settings,
+        });
+        Ok(self)
+    }
+
+    /// Read the TOML file at `path` and add its settings as a layer, see
+    /// [`Self::with_toml_str`]
+    #[cfg(feature = "toml")]
+    pub fn with_toml_file(self, path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let toml = std::fs::read_to_string(path)?;
+        self.with_toml_str(&toml)
+    }
+
+    /// Create [`ConfigOptions`] with the default values overridden by all
+    /// layers
+    pub fn load(&self) -> Result<ConfigOptions> {
+        let mut options = ConfigOptions::default();
+        self.apply(&mut options)?;
+        Ok(options)
+    }
+
+    /// Apply all layers to `options`.
+    ///
+    /// Use this instead of [`Self::load`] when settings for
+    /// [`ConfigExtension`]s should be applied, after registering the
+    /// extensions on `options`.
+    ///
+    /// [`ConfigExtension`]: crate::config::ConfigExtension
+    pub fn apply(&self, options: &mut ConfigOptions) -> Result<()> {
+        let known_keys = options
+            .entries()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect::<HashSet<_>>();
+
+        let unknown = self
+            .layers
+            .iter()
+            .flat_map(|layer| {
+                layer
+                    .settings
+                    .iter()
+                    .filter(|(key, _)| {
+                        key.starts_with("datafusion.") && !known_keys.contains(key)
+                    })
+                    .map(move |(key, _)| format!("{key} (from {})", layer.source))
+            })
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            return _config_err!("Unknown configuration keys: {}", unknown.join(", "));
+        }
+
+        for layer in &self.layers {
+            for (key, value) in &layer.settings {
+                options.set(key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Convert a (possibly nested) TOML table into dotted `key`, `value` pairs
+#[cfg(feature = "toml")]
+fn flatten_toml(
+    prefix: &str,
+    table: toml::Table,
+    settings: &mut Vec<(String, String)>,
+) -> Result<()> {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let value = match value {
+            toml::Value::Table(table) => {
+                flatten_toml(&key, table, settings)?;
+                continue;
+            }
+            toml::Value::String(s) => s,
+            toml::Value::Integer(i) => i.to_string(),
+            toml::Value::Float(f) => f.to_string(),
+            toml::Value::Boolean(b) => b.to_string(),
+            toml::Value::Datetime(_) | toml::Value::Array(_) => {
+                return _config_err!(
+                    "Unsupported value for configuration key '{key}', expected a string, number or boolean"
+                );
+            }
+        };
+        settings.push((key, value));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_override_in_order() -> Result<()> {
+        let options = ConfigLoader::new()
+            .with_profile("low_memory")?
+            .with_settings([("datafusion.execution.batch_size", "100")])
+            .load()?;
+        assert_eq!(options.execution.batch_size, 100);
+        assert!(!options.optimizer.prefer_hash_join);
+
+        let options = ConfigLoader::new()
+            .with_profile_definition(
+                "custom",
+                [("datafusion.execution.coalesce_batches", "false")],
+            )
+            .with_profile("custom")?
+            .load()?;
+        assert!(!options.execution.coalesce_batches);
+
+        let err = ConfigLoader::new().with_profile("missing").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("batch_etl, interactive, low_memory")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_keys_are_listed() {
+        let err = ConfigLoader::new()
+            .with_settings([
+                ("datafusion.execution.batch_sise", "1"),
+                ("datafusion.execution.batch_size", "1"),
+                ("datafusion.optimizer.nope", "1"),
+            ])
+            .load()
+            .unwrap_err()
+            .strip_backtrace();
+        assert_eq!(
+            err,
+            "Invalid or Unsupported Configuration: Unknown configuration keys: \
+             datafusion.execution.batch_sise (from settings), \
+             datafusion.optimizer.nope (from settings)"
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_profiles() -> Result<()> {
+        let loader = ConfigLoader::new().with_toml_str(
+            r#"
+            profile = "nightly"
+            datafusion.execution.target_partitions = 3
+
+            [datafusion.optimizer]
+            prefer_hash_join = false
+
+            [profiles.nightly]
+            datafusion.execution.batch_size = 32768
+            datafusion.execution.target_partitions = 1
+            "#,
+        )?;
+        let options = loader.load()?;
+        assert_eq!(options.execution.batch_size, 32768);
+        assert_eq!(options.execution.target_partitions, 3);
+        assert!(!options.optimizer.prefer_hash_join);
+        assert!(loader.profile_names().any(|name| name == "nightly"));
+
+        let err = ConfigLoader::new()
+            .with_toml_str("[datafusion.execution]\nbatch = 1")?
+            .load()
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("datafusion.execution.batch (from TOML configuration)")
+        );
+        Ok(())
+    }
+}
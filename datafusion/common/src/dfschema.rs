@@ -24,6 +24,7 @@ use std::hash::Hash;
 use std::sync::{Arc, LazyLock};
 
 use crate::error::{_plan_err, _schema_err, DataFusionError, Result};
+use crate::metadata_column::is_metadata_column;
 use crate::{
     Column, FunctionalDependencies, SchemaError, TableReference, field_not_found,
     unqualified_field_not_found,
@@ -889,6 +890,15 @@ impl DFSchema {
             .zip(self.inner.fields().iter())
             .map(|(qualifier, field)| (qualifier.as_ref(), field))
     }
+
+    /// Returns true if the field at index `i` is a hidden metadata column of a
+    /// table, see [`METADATA_COLUMN_KEY`]
+    ///
+    /// [`METADATA_COLUMN_KEY`]: crate::metadata_column::METADATA_COLUMN_KEY
+    pub fn is_metadata_column(&self, i: usize) -> bool {
+        is_metadata_column(self.field(i))
+    }
+
     /// Returns a tree-like string representation of the schema.
     ///
     /// This method formats the schema
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::{Arc, Mutex, MutexGuard};
+
 use crate::Span;
 
 /// Additional contextual information intended for end users, to help them
@@ -147,3 +149,73 @@ impl Diagnostic {
         self
     }
 }
+
+/// A thread-safe collector of non-fatal warnings raised while planning and
+/// executing queries, such as ignored table hints or deprecated options.
+///
+/// Rather than silently proceeding, components that detect a potential problem
+/// which does not prevent the query from running can report it here, as a
+/// [`Diagnostic`] with [`DiagnosticKind::Warning`]. The warnings can then be
+/// retrieved (e.g. by a CLI or a service embedding DataFusion) after the query
+/// has run.
+///
+/// Cloning a [`WarningCollector`] is cheap and all clones share the same
+/// underlying list of warnings.
+///
+/// Example:
+///
+/// ```rust
+/// # use datafusion_common::diagnostic::WarningCollector;
+/// # use datafusion_common::Diagnostic;
+/// let warnings = WarningCollector::new();
+/// warnings.report(Diagnostic::new_warning("table hints are ignored", None));
+/// assert_eq!(warnings.len(), 1);
+///
+/// let taken = warnings.take();
+/// assert_eq!(taken[0].message, "table hints are ignored");
+/// assert!(warnings.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WarningCollector {
+    inner: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl WarningCollector {
+    /// Creates a new, empty [`WarningCollector`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a warning. Diagnostics reported through this method are always
+    /// stored with [`DiagnosticKind::Warning`].
+    pub fn report(&self, mut warning: Diagnostic) {
+        warning.kind = DiagnosticKind::Warning;
+        self.lock().push(warning);
+    }
+
+    /// Returns a copy of all warnings collected so far
+    pub fn warnings(&self) -> Vec<Diagnostic> {
+        self.lock().clone()
+    }
+
+    /// Removes and returns all warnings collected so far
+    pub fn take(&self) -> Vec<Diagnostic> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// Returns the number of collected warnings
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if no warnings have been collected
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Diagnostic>> {
+        // a panic while holding the lock can not leave the list in an
+        // inconsistent state, so recover from poisoning
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -43,6 +43,7 @@ use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::result;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::utils::datafusion_strsim::normalized_levenshtein;
@@ -53,7 +54,6 @@ use arrow::error::ArrowError;
 use parquet::errors::ParquetError;
 #[cfg(feature = "sql")]
 use sqlparser::parser::ParserError;
-use tokio::task::JoinError;
 
 /// Result type for operations that could result in an [DataFusionError]
 pub type Result<T, E = DataFusionError> = result::Result<T, E>;
@@ -130,10 +130,11 @@ pub enum DataFusionError {
     /// SQL method, opened a CSV file that is broken, or tried to divide an
     /// integer by zero.
     Execution(String),
-    /// [`JoinError`] during execution of the query.
+    /// Failure to join a task spawned during execution of the query, such as
+    /// a tokio `JoinError` of a task that panicked or was cancelled.
     ///
     /// This error can't occur for unjoined tasks, such as execution shutdown.
-    ExecutionJoin(Box<JoinError>),
+    ExecutionJoin(Box<dyn Error + Send + Sync>),
     /// Error when resources (such as memory of scratch disk space) are exhausted.
     ///
     /// This error is thrown when a consumer cannot acquire additional memory
@@ -144,16 +145,9 @@ pub enum DataFusionError {
     /// For example, a custom S3Error from the crate datafusion-objectstore-s3
     External(GenericError),
     /// Error during type coercion.
-    TypeCoercion {
-        /// The general error message
-        message: String,
-        /// The optional index of the offending argument
-        arg_index: Option<usize>,
-        /// The expected types (from the signature)
-        expected_types: String,
-        /// The actual type of the offending argument
-        actual_type: String,
-    },
+    ///
+    /// Boxing the details to keep the size of [`DataFusionError`] small
+    TypeCoercion(Box<TypeCoercionError>),
     /// Error with additional context
     Context(String, Box<DataFusionError>),
     /// Errors from either mapping LogicalPlans to/from Substrait plans
@@ -190,6 +184,19 @@ macro_rules! context {
     };
 }
 
+/// Details of a [`DataFusionError::TypeCoercion`] error
+#[derive(Debug)]
+pub struct TypeCoercionError {
+    /// The general error message
+    pub message: String,
+    /// The optional index of the offending argument
+    pub arg_index: Option<usize>,
+    /// The expected types (from the signature)
+    pub expected_types: String,
+    /// The actual type of the offending argument
+    pub actual_type: String,
+}
+
 /// Schema-related errors
 #[derive(Debug)]
 pub enum SchemaError {
@@ -414,7 +421,7 @@ impl Error for DataFusionError {
             // can't be executed.
             DataFusionError::Collection(errs) => errs.first().map(|e| e as &dyn Error),
             DataFusionError::Shared(e) => Some(e.as_ref()),
-            DataFusionError::TypeCoercion { .. } => None,
+            DataFusionError::TypeCoercion(_) => None,
             DataFusionError::Ffi(_) => None,
         }
     }
@@ -538,7 +545,7 @@ impl DataFusionError {
                 "Resources exhausted: "
             }
             DataFusionError::External(_) => "External error: ",
-            DataFusionError::TypeCoercion { .. } => "Type coercion error: ",
+            DataFusionError::TypeCoercion(_) => "Type coercion error: ",
             DataFusionError::Context(_, _) => "",
             DataFusionError::Substrait(_) => "Substrait error: ",
             DataFusionError::Diagnostic(_, _) => "",
@@ -582,9 +589,7 @@ impl DataFusionError {
             DataFusionError::ExecutionJoin(ref desc) => Cow::Owned(desc.to_string()),
             DataFusionError::ResourcesExhausted(ref desc) => Cow::Owned(desc.to_string()),
             DataFusionError::External(ref desc) => Cow::Owned(desc.to_string()),
-            DataFusionError::TypeCoercion { ref message, .. } => {
-                Cow::Owned(message.to_string())
-            }
+            DataFusionError::TypeCoercion(ref err) => Cow::Owned(err.message.to_string()),
             #[cfg(feature = "object_store")]
             DataFusionError::ObjectStore(ref desc) => Cow::Owned(desc.to_string()),
             DataFusionError::Context(ref desc, ref err) => {
@@ -653,6 +658,80 @@ impl DataFusionError {
         DiagnosticsIterator { head: self }.next()
     }
 
+    /// Return the stable [`ErrorCode`] classifying this error
+    ///
+    /// Wrapping variants such as [`Self::Context`], [`Self::Diagnostic`] and
+    /// [`Self::Shared`] are looked through, so the code reflects the root
+    /// cause (see [`Self::find_root`]). For a [`Self::Collection`] the code of
+    /// the first error is returned.
+    pub fn code(&self) -> ErrorCode {
+        match self.find_root() {
+            DataFusionError::ArrowError(_, _) => ErrorCode::Arrow,
+            #[cfg(feature = "parquet")]
+            DataFusionError::ParquetError(_) => ErrorCode::Parquet,
+            #[cfg(feature = "object_store")]
+            DataFusionError::ObjectStore(_) => ErrorCode::ObjectStore,
+            DataFusionError::IoError(_) => ErrorCode::Io,
+            #[cfg(feature = "sql")]
+            DataFusionError::SQL(_, _) => ErrorCode::SqlParse,
+            DataFusionError::NotImplemented(_) => ErrorCode::NotImplemented,
+            DataFusionError::Internal(_) => ErrorCode::Internal,
+            DataFusionError::Plan(_) => ErrorCode::Plan,
+            DataFusionError::Configuration(_) => ErrorCode::Configuration,
+            DataFusionError::SchemaError(e, _) => e.code(),
+            DataFusionError::Execution(_) => ErrorCode::Execution,
+            DataFusionError::ExecutionJoin(_) => ErrorCode::ExecutionJoin,
+            DataFusionError::ResourcesExhausted(_) => ErrorCode::ResourcesExhausted,
+            DataFusionError::External(_) => ErrorCode::External,
+            DataFusionError::TypeCoercion(_) => ErrorCode::TypeCoercion,
+            DataFusionError::Substrait(_) => ErrorCode::Substrait,
+            DataFusionError::Ffi(_) => ErrorCode::Ffi,
+            // `find_root` looks through these wrappers, so they are only
+            // reached if the wrapped error is not a `DataFusionError`
+            DataFusionError::Context(_, e) | DataFusionError::Diagnostic(_, e) => {
+                e.code()
+            }
+            DataFusionError::Shared(e) => e.code(),
+            DataFusionError::Collection(errs) => errs
+                .first()
+                .map(|e| e.code())
+                .unwrap_or(ErrorCode::Internal),
+        }
+    }
+
+    /// Return a [`StructuredError`] describing this error, suitable for
+    /// mapping to user-facing handling without matching on message text
+    pub fn to_structured(&self) -> StructuredError {
+        let mut context = vec![];
+        let mut current = self;
+        loop {
+            match current {
+                DataFusionError::Context(desc, e) => {
+                    context.push(desc.clone());
+                    current = e;
+                }
+                DataFusionError::Diagnostic(_, e) => current = e,
+                DataFusionError::Shared(e) => current = e,
+                DataFusionError::Collection(errs) if !errs.is_empty() => {
+                    current = &errs[0]
+                }
+                _ => break,
+            }
+        }
+        let root = current.find_root();
+        let message = root.message();
+        let message = message
+            .split(Self::BACK_TRACE_SEP)
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        StructuredError {
+            code: self.code(),
+            message,
+            context,
+        }
+    }
+
     /// Return an iterator over this [`DataFusionError`] and any other
     /// [`DataFusionError`]s in a [`DataFusionError::Collection`].
     ///
@@ -688,6 +767,236 @@ impl DataFusionError {
     }
 }
 
+/// A stable, machine-readable code classifying a [`DataFusionError`]
+///
+/// Error messages are intended for humans and may change between releases.
+/// Services embedding DataFusion that need to map errors to user-facing
+/// handling should match on the [`ErrorCode`] (or its string forms returned
+/// by [`ErrorCode::code`] and [`ErrorCode::name`]) instead of the message.
+///
+/// The numeric codes are stable: once assigned, a code is never reused for a
+/// different condition.
+///
+/// # Example
+/// ```
+/// # use datafusion_common::{Column, DataFusionError, SchemaError};
+/// # use datafusion_common::error::ErrorCode;
+/// let err = DataFusionError::SchemaError(
+///     Box::new(SchemaError::AmbiguousReference {
+///         field: Box::new(Column::new_unqualified("a")),
+///     }),
+///     Box::new(None),
+/// )
+/// .context("while planning");
+///
+/// assert_eq!(err.code(), ErrorCode::AmbiguousColumn);
+/// assert_eq!(err.code().to_string(), "DF-1042 AMBIGUOUS_COLUMN");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// Internal invariant violated, see [`DataFusionError::Internal`]
+    Internal,
+    /// Feature not implemented, see [`DataFusionError::NotImplemented`]
+    NotImplemented,
+    /// Invalid or unsupported configuration, see [`DataFusionError::Configuration`]
+    Configuration,
+    /// Errors originating outside of DataFusion, see [`DataFusionError::External`]
+    External,
+    /// Errors crossing a foreign function interface, see [`DataFusionError::Ffi`]
+    Ffi,
+    /// Malformed SQL text, see [`DataFusionError::SQL`]
+    SqlParse,
+    /// Generic planning error, see [`DataFusionError::Plan`]
+    Plan,
+    /// Type coercion failure, see [`DataFusionError::TypeCoercion`]
+    TypeCoercion,
+    /// Substrait conversion failure, see [`DataFusionError::Substrait`]
+    Substrait,
+    /// Referenced column does not exist, see [`SchemaError::FieldNotFound`]
+    ColumnNotFound,
+    /// Schema contains a duplicate qualified field, see
+    /// [`SchemaError::DuplicateQualifiedField`]
+    DuplicateQualifiedField,
+    /// Reference to a column is ambiguous, see [`SchemaError::AmbiguousReference`]
+    AmbiguousColumn,
+    /// Schema contains a duplicate unqualified field, see
+    /// [`SchemaError::DuplicateUnqualifiedField`]
+    DuplicateUnqualifiedField,
+    /// Generic execution error, see [`DataFusionError::Execution`]
+    Execution,
+    /// A spawned task failed, see [`DataFusionError::ExecutionJoin`]
+    ExecutionJoin,
+    /// Memory or other resources exhausted, see [`DataFusionError::ResourcesExhausted`]
+    ResourcesExhausted,
+    /// Error from the arrow crate, see [`DataFusionError::ArrowError`]
+    Arrow,
+    /// Error from the parquet crate, see [`DataFusionError::ParquetError`]
+    Parquet,
+    /// Error from an object store, see [`DataFusionError::ObjectStore`]
+    ObjectStore,
+    /// I/O failure, see [`DataFusionError::IoError`]
+    Io,
+}
+
+impl ErrorCode {
+    /// All known error codes
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::Internal,
+        ErrorCode::NotImplemented,
+        ErrorCode::Configuration,
+        ErrorCode::External,
+        ErrorCode::Ffi,
+        ErrorCode::SqlParse,
+        ErrorCode::Plan,
+        ErrorCode::TypeCoercion,
+        ErrorCode::Substrait,
+        ErrorCode::ColumnNotFound,
+        ErrorCode::DuplicateQualifiedField,
+        ErrorCode::AmbiguousColumn,
+        ErrorCode::DuplicateUnqualifiedField,
+        ErrorCode::Execution,
+        ErrorCode::ExecutionJoin,
+        ErrorCode::ResourcesExhausted,
+        ErrorCode::Arrow,
+        ErrorCode::Parquet,
+        ErrorCode::ObjectStore,
+        ErrorCode::Io,
+    ];
+
+    /// The numeric part of the code, e.g. `1042` for `DF-1042`
+    ///
+    /// Codes are grouped by the phase in which the error is raised:
+    /// `1000`-`1009` general, `1010`-`1029` planning, `1040`-`1049` schema,
+    /// `1060`-`1079` execution and `1080`-`1099` storage / I/O.
+    pub const fn number(&self) -> u16 {
+        match self {
+            ErrorCode::Internal => 1000,
+            ErrorCode::NotImplemented => 1001,
+            ErrorCode::Configuration => 1002,
+            ErrorCode::External => 1003,
+            ErrorCode::Ffi => 1004,
+            ErrorCode::SqlParse => 1010,
+            ErrorCode::Plan => 1011,
+            ErrorCode::TypeCoercion => 1012,
+            ErrorCode::Substrait => 1013,
+            ErrorCode::ColumnNotFound => 1040,
+            ErrorCode::DuplicateQualifiedField => 1041,
+            ErrorCode::AmbiguousColumn => 1042,
+            ErrorCode::DuplicateUnqualifiedField => 1043,
+            ErrorCode::Execution => 1060,
+            ErrorCode::ExecutionJoin => 1061,
+            ErrorCode::ResourcesExhausted => 1062,
+            ErrorCode::Arrow => 1080,
+            ErrorCode::Parquet => 1081,
+            ErrorCode::ObjectStore => 1082,
+            ErrorCode::Io => 1083,
+        }
+    }
+
+    /// The stable code, e.g. `"DF-1042"`
+    pub fn code(&self) -> String {
+        format!("DF-{}", self.number())
+    }
+
+    /// The stable symbolic name, e.g. `"AMBIGUOUS_COLUMN"`
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ErrorCode::Internal => "INTERNAL",
+            ErrorCode::NotImplemented => "NOT_IMPLEMENTED",
+            ErrorCode::Configuration => "CONFIGURATION",
+            ErrorCode::External => "EXTERNAL",
+            ErrorCode::Ffi => "FFI",
+            ErrorCode::SqlParse => "SQL_PARSE",
+            ErrorCode::Plan => "PLAN",
+            ErrorCode::TypeCoercion => "TYPE_COERCION",
+            ErrorCode::Substrait => "SUBSTRAIT",
+            ErrorCode::ColumnNotFound => "COLUMN_NOT_FOUND",
+            ErrorCode::DuplicateQualifiedField => "DUPLICATE_QUALIFIED_FIELD",
+            ErrorCode::AmbiguousColumn => "AMBIGUOUS_COLUMN",
+            ErrorCode::DuplicateUnqualifiedField => "DUPLICATE_UNQUALIFIED_FIELD",
+            ErrorCode::Execution => "EXECUTION",
+            ErrorCode::ExecutionJoin => "EXECUTION_JOIN",
+            ErrorCode::ResourcesExhausted => "RESOURCES_EXHAUSTED",
+            ErrorCode::Arrow => "ARROW",
+            ErrorCode::Parquet => "PARQUET",
+            ErrorCode::ObjectStore => "OBJECT_STORE",
+            ErrorCode::Io => "IO",
+        }
+    }
+
+    /// Returns true if the error was caused by the user's input (e.g. a bad
+    /// query or configuration) rather than by a bug or an external system
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Configuration
+                | ErrorCode::SqlParse
+                | ErrorCode::Plan
+                | ErrorCode::TypeCoercion
+                | ErrorCode::ColumnNotFound
+                | ErrorCode::DuplicateQualifiedField
+                | ErrorCode::AmbiguousColumn
+                | ErrorCode::DuplicateUnqualifiedField
+        )
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DF-{} {}", self.number(), self.name())
+    }
+}
+
+/// Parses an [`ErrorCode`] from either its code (`"DF-1042"`) or its name
+/// (`"AMBIGUOUS_COLUMN"`)
+impl FromStr for ErrorCode {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .find(|c| c.name() == s || c.code() == s)
+            .copied()
+            .ok_or_else(|| DataFusionError::Plan(format!("Unknown error code '{s}'")))
+    }
+}
+
+impl SchemaError {
+    /// Return the [`ErrorCode`] for this schema error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SchemaError::AmbiguousReference { .. } => ErrorCode::AmbiguousColumn,
+            SchemaError::DuplicateQualifiedField { .. } => {
+                ErrorCode::DuplicateQualifiedField
+            }
+            SchemaError::DuplicateUnqualifiedField { .. } => {
+                ErrorCode::DuplicateUnqualifiedField
+            }
+            SchemaError::FieldNotFound { .. } => ErrorCode::ColumnNotFound,
+        }
+    }
+}
+
+/// A structured, machine-readable representation of a [`DataFusionError`]
+///
+/// Created with [`DataFusionError::to_structured`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuredError {
+    /// The stable error code
+    pub code: ErrorCode,
+    /// The human readable message of the root error, without prefix or backtrace
+    pub message: String,
+    /// The messages of any context wrapping the root error, outermost first
+    pub context: Vec<String>,
+}
+
+impl Display for StructuredError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
 /// A builder for [`DataFusionError`]
 ///
 /// This builder can be used to collect multiple errors and return them as a
@@ -1150,6 +1459,7 @@ pub fn add_possible_columns_to_diag(
 mod test {
     use super::*;
 
+    use std::collections::HashSet;
     use std::mem::size_of;
     use std::sync::Arc;
 
@@ -1264,6 +1574,65 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_error_code() {
+        let err = DataFusionError::SchemaError(
+            Box::new(SchemaError::AmbiguousReference {
+                field: Box::new(Column::new_unqualified("a")),
+            }),
+            Box::new(None),
+        );
+        assert_eq!(err.code(), ErrorCode::AmbiguousColumn);
+        assert_eq!(err.code().code(), "DF-1042");
+        assert_eq!(err.code().name(), "AMBIGUOUS_COLUMN");
+
+        // wrappers are looked through
+        let err = DataFusionError::Shared(Arc::new(
+            err.context("foo")
+                .with_diagnostic(Diagnostic::new_error("bar", None)),
+        ));
+        assert_eq!(err.code(), ErrorCode::AmbiguousColumn);
+
+        let err = DataFusionError::ArrowError(
+            Box::new(ArrowError::ExternalError(Box::new(
+                DataFusionError::ResourcesExhausted("oom".to_string()),
+            ))),
+            None,
+        );
+        assert_eq!(err.code(), ErrorCode::ResourcesExhausted);
+
+        let err = DataFusionError::Collection(vec![
+            DataFusionError::Plan("a".to_string()),
+            DataFusionError::Internal("b".to_string()),
+        ]);
+        assert_eq!(err.code(), ErrorCode::Plan);
+    }
+
+    #[test]
+    fn test_error_code_unique() {
+        let mut numbers = HashSet::new();
+        let mut names = HashSet::new();
+        for code in ErrorCode::ALL {
+            assert!(numbers.insert(code.number()), "duplicate number {code}");
+            assert!(names.insert(code.name()), "duplicate name {code}");
+            assert_eq!(code.code().parse::<ErrorCode>().unwrap(), *code);
+            assert_eq!(code.name().parse::<ErrorCode>().unwrap(), *code);
+        }
+        assert!("DF-9999".parse::<ErrorCode>().is_err());
+    }
+
+    #[test]
+    fn test_to_structured() {
+        let err = plan_datafusion_err!("bad plan")
+            .context("outer")
+            .context("outermost");
+        let structured = err.to_structured();
+        assert_eq!(structured.code, ErrorCode::Plan);
+        assert_eq!(structured.message, "bad plan");
+        assert_eq!(structured.context, vec!["outermost", "outer"]);
+        assert_eq!(structured.to_string(), "[DF-1011 PLAN] bad plan");
+    }
+
     #[test]
     fn test_error_size() {
         // Since Errors influence the size of Result which influence the size of the stack
@@ -130,6 +130,18 @@ impl Extensions {
         self.inner.contains_key(&TypeId::of::<T>())
     }
 
+    /// Returns true if `self` and `other` hold the same values, that is the
+    /// same [`Arc`] for each type.
+    pub fn ptr_eq(&self, other: &Extensions) -> bool {
+        self.inner.len() == other.inner.len()
+            && self.inner.iter().all(|(id, ext)| {
+                other
+                    .inner
+                    .get(id)
+                    .is_some_and(|other_ext| Arc::ptr_eq(ext, other_ext))
+            })
+    }
+
     /// Merge entries from `other` into `self`. Entries in `other` take
     /// precedence over existing entries with the same type.
     pub fn merge(&mut self, other: &Extensions) {
@@ -208,4 +220,21 @@ mod tests {
         assert_eq!(a.get::<A>(), Some(&A(2)));
         assert_eq!(a.get::<B>(), Some(&B("hi")));
     }
+
+    #[test]
+    fn ptr_eq_compares_values_by_pointer() {
+        let mut a = Extensions::new();
+        a.insert(A(1));
+        let b = a.clone();
+        assert!(a.ptr_eq(&b));
+        assert!(Extensions::new().ptr_eq(&Extensions::new()));
+
+        // equal, but not the same value
+        let mut c = Extensions::new();
+        c.insert(A(1));
+        assert!(!a.ptr_eq(&c));
+
+        c.insert(B("x"));
+        assert!(!c.ptr_eq(&a));
+    }
 }
@@ -18,8 +18,8 @@
 //! Options related to how csv files should be written
 
 use crate::config::CsvOptions;
-use crate::error::{DataFusionError, Result};
-use crate::parsers::CompressionTypeVariant;
+use crate::error::{_config_err, DataFusionError, Result};
+use crate::parsers::{CompressionTypeVariant, CsvLineTerminator, CsvQuoteStyle};
 
 use arrow::csv::WriterBuilder;
 
@@ -33,6 +33,9 @@ pub struct CsvWriterOptions {
     pub compression: CompressionTypeVariant,
     /// Compression level for the output file.
     pub compression_level: Option<u32>,
+    /// The bytes that terminate each record. Applied by DataFusion, as the
+    /// ArrowWriter always terminates records with `\n`.
+    pub line_terminator: CsvLineTerminator,
 }
 
 impl CsvWriterOptions {
@@ -44,6 +47,7 @@ impl CsvWriterOptions {
             writer_options,
             compression,
             compression_level: None,
+            line_terminator: CsvLineTerminator::Lf,
         }
     }
 
@@ -57,14 +61,22 @@ impl CsvWriterOptions {
             writer_options,
             compression,
             compression_level: Some(compression_level),
+            line_terminator: CsvLineTerminator::Lf,
         }
     }
+
+    /// Set the line terminator of the written records.
+    pub fn with_line_terminator(mut self, line_terminator: CsvLineTerminator) -> Self {
+        self.line_terminator = line_terminator;
+        self
+    }
 }
 
 impl TryFrom<&CsvOptions> for CsvWriterOptions {
     type Error = DataFusionError;
 
     fn try_from(value: &CsvOptions) -> Result<Self> {
+        validate_csv_writer_options(value)?;
         let mut builder = WriterBuilder::default()
             .with_header(value.has_header.unwrap_or(true))
             .with_quote(value.quote)
@@ -105,6 +117,99 @@ impl TryFrom<&CsvOptions> for CsvWriterOptions {
             writer_options: builder,
             compression: value.compression,
             compression_level: value.compression_level,
+            line_terminator: value.line_terminator,
         })
     }
 }
+
+/// Returns an error if files written with `options` could not be read back
+/// unambiguously, or would not be valid UTF-8
+fn validate_csv_writer_options(options: &CsvOptions) -> Result<()> {
+    let special = [
+        ("delimiter", Some(options.delimiter)),
+        ("quote", Some(options.quote)),
+        ("escape", options.escape),
+    ];
+    for (i, (name, byte)) in special.iter().enumerate() {
+        let Some(byte) = byte else {
+            continue;
+        };
+        if !byte.is_ascii() {
+            return _config_err!("CSV {name} must be an ASCII character");
+        }
+        if matches!(byte, b'\r' | b'\n') {
+            return _config_err!("CSV {name} must not be a line terminator");
+        }
+        // an escape equal to the quote escapes quotes by doubling them
+        for (other, other_byte) in &special[..i] {
+            if Some(*byte) == *other_byte && !(*name == "escape" && *other == "quote") {
+                return _config_err!("CSV {name} must differ from the {other}");
+            }
+        }
+    }
+
+    if options.quote_style == CsvQuoteStyle::Never
+        && let Some(null_value) = &options.null_value
+        && null_value.bytes().any(|byte| {
+            matches!(byte, b'\r' | b'\n')
+                || byte == options.delimiter
+                || byte == options.quote
+        })
+    {
+        return _config_err!(
+            "CSV null value {null_value:?} must not contain the delimiter, quote or line \
+            terminators when values are never quoted"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_csv_writer_options() {
+        let options = CsvOptions::default()
+            .with_delimiter(b';')
+            .with_escape(Some(b'"'))
+            .with_line_terminator(CsvLineTerminator::Crlf);
+        let writer_options = CsvWriterOptions::try_from(&options).unwrap();
+        assert_eq!(writer_options.line_terminator, CsvLineTerminator::Crlf);
+
+        let err = CsvWriterOptions::try_from(&options.clone().with_quote(b';'))
+            .unwrap_err()
+            .strip_backtrace();
+        assert_eq!(
+            err,
+            "Invalid or Unsupported Configuration: CSV quote must differ from the delimiter"
+        );
+
+        let err = CsvWriterOptions::try_from(&options.clone().with_delimiter(b'\n'))
+            .unwrap_err()
+            .strip_backtrace();
+        assert_eq!(
+            err,
+            "Invalid or Unsupported Configuration: CSV delimiter must not be a line terminator"
+        );
+
+        let err = CsvWriterOptions::try_from(&options.clone().with_escape(Some(0xe9)))
+            .unwrap_err()
+            .strip_backtrace();
+        assert_eq!(
+            err,
+            "Invalid or Unsupported Configuration: CSV escape must be an ASCII character"
+        );
+
+        let mut never_quoted = options.with_quote_style(CsvQuoteStyle::Never);
+        never_quoted.null_value = Some("N;A".to_string());
+        let err = CsvWriterOptions::try_from(&never_quoted)
+            .unwrap_err()
+            .strip_backtrace();
+        assert_eq!(
+            err,
+            "Invalid or Unsupported Configuration: CSV null value \"N;A\" must not contain \
+            the delimiter, quote or line terminators when values are never quoted"
+        );
+    }
+}
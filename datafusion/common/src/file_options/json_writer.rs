@@ -28,6 +28,9 @@ use crate::{
 pub struct JsonWriterOptions {
     pub compression: CompressionTypeVariant,
     pub compression_level: Option<u32>,
+    /// Whether null values are written as explicit `null`s rather than
+    /// omitted from the objects
+    pub explicit_nulls: bool,
 }
 
 impl JsonWriterOptions {
@@ -35,6 +38,7 @@ impl JsonWriterOptions {
         Self {
             compression,
             compression_level: None,
+            explicit_nulls: false,
         }
     }
 
@@ -46,8 +50,15 @@ impl JsonWriterOptions {
         Self {
             compression,
             compression_level: Some(compression_level),
+            explicit_nulls: false,
         }
     }
+
+    /// Set whether null values are written as explicit `null`s.
+    pub fn with_explicit_nulls(mut self, explicit_nulls: bool) -> Self {
+        self.explicit_nulls = explicit_nulls;
+        self
+    }
 }
 
 impl TryFrom<&JsonOptions> for JsonWriterOptions {
@@ -57,6 +68,7 @@ impl TryFrom<&JsonOptions> for JsonWriterOptions {
         Ok(JsonWriterOptions {
             compression: value.compression,
             compression_level: value.compression_level,
+            explicit_nulls: value.explicit_nulls.unwrap_or(false),
         })
     }
 }
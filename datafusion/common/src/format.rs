@@ -430,3 +430,40 @@ impl ConfigField for ExplainAnalyzeCategories {
         Ok(())
     }
 }
+
+/// Output format of `EXPLAIN ANALYZE`
+///
+/// Set via `EXPLAIN (ANALYZE, FORMAT JSON) ...`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Default)]
+pub enum ExplainAnalyzeFormat {
+    /// The plan annotated with its metrics, as an indented text tree (the
+    /// default)
+    #[default]
+    Indent,
+    /// A JSON document of the plan tree, including the metrics of each
+    /// operator, aggregated and per partition
+    Json,
+}
+
+impl FromStr for ExplainAnalyzeFormat {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "indent" => Ok(Self::Indent),
+            "json" => Ok(Self::Json),
+            other => Err(DataFusionError::Configuration(format!(
+                "Invalid explain analyze format '{other}'. Expected 'indent' or 'json'."
+            ))),
+        }
+    }
+}
+
+impl Display for ExplainAnalyzeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Indent => write!(f, "indent"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
@@ -36,7 +36,9 @@ mod unnest;
 
 pub mod alias;
 pub mod cast;
+pub mod clock;
 pub mod config;
+pub mod config_loader;
 pub mod cse;
 pub mod datatype;
 pub mod diagnostic;
@@ -49,6 +51,7 @@ pub mod format;
 pub mod hash_utils;
 pub mod instant;
 pub mod metadata;
+pub mod metadata_column;
 pub mod nested_struct;
 mod null_equality;
 pub mod parquet_config;
@@ -59,9 +62,11 @@ pub mod scalar;
 pub mod spans;
 pub mod stats;
 pub mod test_util;
+pub mod timezone;
 pub mod tree_node;
 pub mod types;
 pub mod utils;
+pub mod watermark;
 /// Reexport arrow crate
 pub use arrow;
 pub use column::Column;
@@ -73,8 +78,8 @@ pub use display::human_readable::{
     human_readable_count, human_readable_duration, human_readable_size, units,
 };
 pub use error::{
-    DataFusionError, Result, SchemaError, SharedResult, field_not_found,
-    unqualified_field_not_found,
+    DataFusionError, ErrorCode, Result, SchemaError, SharedResult, StructuredError,
+    field_not_found, unqualified_field_not_found,
 };
 pub use file_options::file_type::{
     DEFAULT_ARROW_EXTENSION, DEFAULT_AVRO_EXTENSION, DEFAULT_CSV_EXTENSION,
@@ -95,7 +100,7 @@ pub use schema_reference::SchemaReference;
 pub use spans::{Location, Span, Spans};
 pub use stats::{ColumnStatistics, Statistics};
 pub use table_reference::{ResolvedTableReference, TableReference};
-pub use unnest::{RecursionUnnestOption, UnnestOptions};
+pub use unnest::{RecursionUnnestOption, UNNEST_ORDINALITY_COLUMN, UnnestOptions};
 pub use utils::project_schema;
 
 // These are hidden from docs purely to avoid polluting the public view of what this crate exports.
@@ -0,0 +1,75 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hidden metadata columns of tables
+
+use std::sync::Arc;
+
+use arrow::datatypes::{Field, FieldRef};
+
+/// Field metadata key marking a hidden metadata column of a table.
+///
+/// Metadata columns describe where the rows of a table come from, for example
+/// the file each row was read from. They can be referenced explicitly, but are
+/// not part of `SELECT *` and are not matched by a `NATURAL JOIN`.
+///
+/// The marker is only set on the fields of logical plans that scan or pass
+/// through a metadata column. Once the column is referenced by an expression,
+/// such as in `SELECT _file FROM t`, the resulting field is a regular column.
+pub const METADATA_COLUMN_KEY: &str = "datafusion.metadata_column";
+
+/// Returns `field` marked as a hidden metadata column
+pub fn with_metadata_column_marker(field: Field) -> Field {
+    let mut metadata = field.metadata().clone();
+    metadata.insert(METADATA_COLUMN_KEY.to_string(), "true".to_string());
+    field.with_metadata(metadata)
+}
+
+/// Returns `field` without the hidden metadata column marker
+pub fn without_metadata_column_marker(field: FieldRef) -> FieldRef {
+    if !is_metadata_column(&field) {
+        return field;
+    }
+    let mut field = Arc::unwrap_or_clone(field);
+    field.metadata_mut().remove(METADATA_COLUMN_KEY);
+    Arc::new(field)
+}
+
+/// Returns true if `field` is marked as a hidden metadata column
+pub fn is_metadata_column(field: &Field) -> bool {
+    field.metadata().contains_key(METADATA_COLUMN_KEY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::datatypes::DataType;
+
+    #[test]
+    fn metadata_column_marker() {
+        let field = Field::new("_file", DataType::Utf8, false);
+        assert!(!is_metadata_column(&field));
+
+        let marked = with_metadata_column_marker(field.clone());
+        assert!(is_metadata_column(&marked));
+
+        let unmarked = without_metadata_column_marker(Arc::new(marked));
+        assert!(!is_metadata_column(&unmarked));
+        assert_eq!(unmarked.as_ref(), &field);
+    }
+}
@@ -19,10 +19,14 @@ use crate::error::{_plan_err, Result};
 use arrow::{
     array::{
         Array, ArrayRef, DictionaryArray, GenericListArray, GenericListViewArray,
-        StructArray, downcast_integer, new_null_array,
+        StructArray, UnionArray, downcast_integer, make_array, new_null_array,
     },
-    compute::{CastOptions, can_cast_types, cast_with_options},
-    datatypes::{DataType, DataType::Struct, Field, FieldRef},
+    buffer::{NullBuffer, ScalarBuffer},
+    compute::{
+        CastOptions, can_cast_types, cast_with_options,
+        kernels::union_extract::union_extract,
+    },
+    datatypes::{DataType, DataType::Struct, Field, FieldRef, UnionFields, UnionMode},
 };
 use std::{collections::HashSet, sync::Arc};
 
@@ -174,6 +178,12 @@ pub fn cast_column(
     cast_options: &CastOptions,
 ) -> Result<ArrayRef> {
     match (source_col.data_type(), target_type) {
+        (DataType::Union(_, _), Struct(target_fields)) => {
+            cast_union_to_struct_column(source_col, target_fields, cast_options)
+        }
+        (Struct(_), DataType::Union(union_fields, mode)) => {
+            cast_struct_to_union_column(source_col, union_fields, *mode, cast_options)
+        }
         (_, Struct(target_fields)) => {
             cast_struct_column(source_col, target_fields, cast_options)
         }
@@ -203,6 +213,124 @@ pub fn cast_column(
     }
 }
 
+/// Cast a union column to a struct with a field per union field, matched by
+/// name, like a "variant" with one field per possible type.
+///
+/// Each row has the value of its selected union field in the struct field of
+/// the same name, and NULL in the other struct fields. Struct fields without
+/// a matching union field are NULL, and union fields without a matching struct
+/// field are ignored.
+fn cast_union_to_struct_column(
+    source_col: &ArrayRef,
+    target_fields: &[FieldRef],
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let (DataType::Union(union_fields, _), Some(source_union)) = (
+        source_col.data_type(),
+        source_col.as_any().downcast_ref::<UnionArray>(),
+    ) else {
+        return _plan_err!("Expected union array but got {}", source_col.data_type());
+    };
+    validate_union_to_struct_compatibility(union_fields, target_fields)?;
+
+    let arrays = target_fields
+        .iter()
+        .map(|target_field| {
+            if !union_fields
+                .iter()
+                .any(|(_, field)| field.name() == target_field.name())
+            {
+                return Ok(new_null_array(target_field.data_type(), source_col.len()));
+            }
+            let values = union_extract(source_union, target_field.name())?;
+            cast_column(&values, target_field.data_type(), cast_options).map_err(|e| {
+                e.context(format!(
+                    "While casting union field '{}'",
+                    target_field.name()
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Arc::new(StructArray::try_new(
+        target_fields.to_vec().into(),
+        arrays,
+        None,
+    )?))
+}
+
+/// Cast a struct column to a union whose fields are matched by name with the
+/// fields of the struct, the inverse of casting a union to a struct.
+///
+/// Each row selects the first union field whose struct field is not NULL.
+/// Rows whose matching struct fields are all NULL select the first union field
+/// with a matching struct field, with a NULL value.
+fn cast_struct_to_union_column(
+    source_col: &ArrayRef,
+    union_fields: &UnionFields,
+    mode: UnionMode,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let Some(source_struct) = source_col.as_any().downcast_ref::<StructArray>() else {
+        return _plan_err!(
+            "Cannot cast column of type {} to union type. Source must be a struct to cast to union.",
+            source_col.data_type()
+        );
+    };
+    validate_struct_to_union_compatibility(source_struct.fields(), union_fields)?;
+
+    let num_rows = source_col.len();
+    let mut selected: Vec<Option<i8>> = vec![None; num_rows];
+    let mut default_type_id = None;
+    let mut children = Vec::with_capacity(union_fields.len());
+    for (type_id, union_field) in union_fields.iter() {
+        let Some(source_child) = source_struct.column_by_name(union_field.name()) else {
+            children.push(new_null_array(union_field.data_type(), num_rows));
+            continue;
+        };
+        // the values of NULL struct rows are NULL
+        let source_child = match source_struct.nulls() {
+            Some(nulls) if source_child.data_type() != &DataType::Null => {
+                let nulls =
+                    NullBuffer::union(Some(nulls), source_child.logical_nulls().as_ref());
+                make_array(source_child.to_data().into_builder().nulls(nulls).build()?)
+            }
+            _ => Arc::clone(source_child),
+        };
+        let child = cast_column(&source_child, union_field.data_type(), cast_options)
+            .map_err(|e| {
+                e.context(format!(
+                    "While casting struct field '{}'",
+                    union_field.name()
+                ))
+            })?;
+        for (row, selected) in selected.iter_mut().enumerate() {
+            if selected.is_none() && child.is_valid(row) {
+                *selected = Some(type_id);
+            }
+        }
+        default_type_id.get_or_insert(type_id);
+        children.push(child);
+    }
+
+    let default_type_id = default_type_id.unwrap_or_default();
+    let type_ids: ScalarBuffer<i8> = selected
+        .into_iter()
+        .map(|type_id| type_id.unwrap_or(default_type_id))
+        .collect();
+    // the children have a value for every row, like the children of a sparse
+    // union, so a dense union points each row at its own index
+    let offsets = match mode {
+        UnionMode::Sparse => None,
+        UnionMode::Dense => Some((0..num_rows as i32).collect()),
+    };
+    Ok(Arc::new(UnionArray::try_new(
+        union_fields.clone(),
+        type_ids,
+        offsets,
+        children,
+    )?))
+}
+
 fn cast_list_column<O: arrow::array::OffsetSizeTrait>(
     source_col: &ArrayRef,
     target_inner_field: &FieldRef,
@@ -425,6 +553,12 @@ pub fn validate_data_type_compatibility(
         (Struct(source_nested), Struct(target_nested)) => {
             validate_struct_compatibility(source_nested, target_nested)?;
         }
+        (DataType::Union(union_fields, _), Struct(target_nested)) => {
+            validate_union_to_struct_compatibility(union_fields, target_nested)?;
+        }
+        (Struct(source_nested), DataType::Union(union_fields, _)) => {
+            validate_struct_to_union_compatibility(source_nested, union_fields)?;
+        }
         (DataType::List(s), DataType::List(t))
         | (DataType::LargeList(s), DataType::LargeList(t))
         | (DataType::ListView(s), DataType::ListView(t))
@@ -456,11 +590,90 @@ pub fn validate_data_type_compatibility(
     Ok(())
 }
 
+/// Validates that a union with `union_fields` can be cast to a struct with
+/// `target_fields`, see [`cast_column`].
+///
+/// The union and the struct must have at least one field name in common, the
+/// union fields must be castable to the struct fields of the same name, and
+/// the struct fields must be nullable, as they are NULL for the rows that
+/// select other union fields.
+pub fn validate_union_to_struct_compatibility(
+    union_fields: &UnionFields,
+    target_fields: &[FieldRef],
+) -> Result<()> {
+    let source_fields = union_fields
+        .iter()
+        .map(|(_, field)| Arc::clone(field))
+        .collect::<Vec<_>>();
+    if !has_one_of_more_common_fields(&source_fields, target_fields) {
+        return _plan_err!(
+            "Cannot cast union with {} fields to struct with {} fields because there is no field name overlap",
+            source_fields.len(),
+            target_fields.len()
+        );
+    }
+    for target_field in target_fields {
+        if !target_field.is_nullable() {
+            return _plan_err!(
+                "Cannot cast union to struct: target field '{}' is non-nullable, but is NULL \
+                 for the rows selecting other union fields",
+                target_field.name()
+            );
+        }
+        if let Some(source_field) = source_fields
+            .iter()
+            .find(|field| field.name() == target_field.name())
+        {
+            validate_data_type_compatibility(
+                target_field.name(),
+                source_field.data_type(),
+                target_field.data_type(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Validates that a struct with `source_fields` can be cast to a union with
+/// `union_fields`, see [`cast_column`].
+///
+/// The struct and the union must have at least one field name in common, and
+/// the struct fields must be castable to the union fields of the same name.
+pub fn validate_struct_to_union_compatibility(
+    source_fields: &[FieldRef],
+    union_fields: &UnionFields,
+) -> Result<()> {
+    let target_fields = union_fields
+        .iter()
+        .map(|(_, field)| Arc::clone(field))
+        .collect::<Vec<_>>();
+    if !has_one_of_more_common_fields(source_fields, &target_fields) {
+        return _plan_err!(
+            "Cannot cast struct with {} fields to union with {} fields because there is no field name overlap",
+            source_fields.len(),
+            target_fields.len()
+        );
+    }
+    for target_field in &target_fields {
+        if let Some(source_field) = source_fields
+            .iter()
+            .find(|field| field.name() == target_field.name())
+        {
+            validate_data_type_compatibility(
+                target_field.name(),
+                source_field.data_type(),
+                target_field.data_type(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
 /// Returns true if casting from `source_type` to `target_type` requires
 /// name-based nested struct casting logic, rather than Arrow's standard cast.
 ///
-/// This is the case when both types are struct types, or both are the same
-/// container type (List, LargeList, ListView, LargeListView, Dictionary) wrapping
+/// This is the case when both types are struct types, one is a struct type and
+/// the other a union type, or both are the same container type (List, LargeList, ListView, LargeListView, Dictionary) wrapping
 /// types that recursively contain structs.
 ///
 /// Use this predicate at both planning time (to decide whether to apply struct
@@ -471,7 +684,9 @@ pub fn requires_nested_struct_cast(
     target_type: &DataType,
 ) -> bool {
     match (source_type, target_type) {
-        (Struct(_), Struct(_)) => true,
+        (Struct(_), Struct(_))
+        | (DataType::Union(_, _), Struct(_))
+        | (Struct(_), DataType::Union(_, _)) => true,
         (DataType::List(s), DataType::List(t))
         | (DataType::LargeList(s), DataType::LargeList(t))
         | (DataType::ListView(s), DataType::ListView(t))
@@ -1336,4 +1551,146 @@ mod tests {
             &DataType::List(arc_field("item", DataType::Int64)),
         ));
     }
+
+    fn int_or_string_union(mode: UnionMode) -> UnionArray {
+        let fields = UnionFields::try_new(
+            vec![3, 1],
+            vec![
+                non_null_field("int", DataType::Int32),
+                non_null_field("string", DataType::Utf8),
+            ],
+        )
+        .unwrap();
+        let (offsets, children): (_, Vec<ArrayRef>) = match mode {
+            UnionMode::Sparse => (
+                None,
+                vec![
+                    Arc::new(Int32Array::from(vec![1, 0, 3])),
+                    Arc::new(StringArray::from(vec!["", "bar", ""])),
+                ],
+            ),
+            UnionMode::Dense => (
+                Some(ScalarBuffer::from(vec![0, 0, 1])),
+                vec![
+                    Arc::new(Int32Array::from(vec![1, 3])),
+                    Arc::new(StringArray::from(vec!["bar"])),
+                ],
+            ),
+        };
+        UnionArray::try_new(fields, ScalarBuffer::from(vec![3, 1, 3]), offsets, children)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cast_union_to_struct() {
+        for mode in [UnionMode::Sparse, UnionMode::Dense] {
+            let source: ArrayRef = Arc::new(int_or_string_union(mode));
+            let target = struct_type(vec![
+                field("int", DataType::Int64),
+                field("string", DataType::Utf8),
+                field("missing", DataType::Boolean),
+            ]);
+
+            let result = cast_column(&source, &target, &DEFAULT_CAST_OPTIONS).unwrap();
+            let struct_array = result.as_any().downcast_ref::<StructArray>().unwrap();
+            let ints = get_column_as!(&struct_array, "int", Int64Array);
+            assert_eq!(ints.iter().collect::<Vec<_>>(), [Some(1), None, Some(3)]);
+            let strings = get_column_as!(&struct_array, "string", StringArray);
+            assert_eq!(
+                strings.iter().collect::<Vec<_>>(),
+                [None, Some("bar"), None]
+            );
+            assert_eq!(
+                struct_array.column_by_name("missing").unwrap().null_count(),
+                3
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_struct_to_union() {
+        let source: ArrayRef = Arc::new(StructArray::new(
+            vec![
+                arc_field("int", DataType::Int32),
+                arc_field("string", DataType::Utf8),
+            ]
+            .into(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3), None])),
+                Arc::new(StringArray::from(vec![None, Some("bar"), None, None])),
+            ],
+            Some(NullBuffer::from(vec![true, true, true, false])),
+        ));
+        for mode in [UnionMode::Sparse, UnionMode::Dense] {
+            let expected = int_or_string_union(mode);
+            let DataType::Union(union_fields, _) = expected.data_type() else {
+                unreachable!()
+            };
+            let target = DataType::Union(union_fields.clone(), mode);
+
+            let result = cast_column(&source, &target, &DEFAULT_CAST_OPTIONS).unwrap();
+            let union = result.as_any().downcast_ref::<UnionArray>().unwrap();
+            // rows without value select the first union field
+            assert_eq!(union.type_ids().to_vec(), vec![3, 1, 3, 3]);
+            let ints = union_extract(union, "int").unwrap();
+            let ints = ints.as_any().downcast_ref::<Int32Array>().unwrap();
+            assert_eq!(
+                ints.iter().collect::<Vec<_>>(),
+                [Some(1), None, Some(3), None]
+            );
+            let strings = union_extract(union, "string").unwrap();
+            let strings = strings.as_any().downcast_ref::<StringArray>().unwrap();
+            assert_eq!(strings.value(1), "bar");
+
+            // casting back returns the struct
+            let struct_type = source.data_type().clone();
+            let round_trip = cast_column(&result, &struct_type, &DEFAULT_CAST_OPTIONS);
+            assert_eq!(round_trip.unwrap().len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_validate_union_struct_compatibility() {
+        let union_fields = UnionFields::try_new(
+            vec![0, 1],
+            vec![
+                field("int", DataType::Int32),
+                field("bin", DataType::Binary),
+            ],
+        )
+        .unwrap();
+
+        let err = validate_union_to_struct_compatibility(
+            &union_fields,
+            &[arc_field("other", DataType::Int32)],
+        )
+        .unwrap_err();
+        assert_contains!(err.to_string(), "there is no field name overlap");
+
+        let err = validate_union_to_struct_compatibility(
+            &union_fields,
+            &[Arc::new(non_null_field("int", DataType::Int32))],
+        )
+        .unwrap_err();
+        assert_contains!(err.to_string(), "target field 'int' is non-nullable");
+
+        let err = validate_struct_to_union_compatibility(
+            &[
+                arc_field("bin", DataType::Boolean),
+                arc_field("int", DataType::Int64),
+            ],
+            &union_fields,
+        )
+        .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Cannot cast struct field 'bin' from type Boolean to type Binary"
+        );
+
+        let union_type = DataType::Union(union_fields, UnionMode::Sparse);
+        let struct_type = struct_type(vec![field("int", DataType::Int64)]);
+        assert!(requires_nested_struct_cast(&union_type, &struct_type));
+        assert!(requires_nested_struct_cast(&struct_type, &union_type));
+        assert!(validate_data_type_compatibility("", &union_type, &struct_type).is_ok());
+    }
 }
@@ -129,3 +129,49 @@ impl Display for CsvQuoteStyle {
         write!(f, "{str}")
     }
 }
+
+/// CSV line terminator
+///
+/// Controls how records are terminated when writing CSV files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CsvLineTerminator {
+    /// Terminate records with `\n` (default)
+    #[default]
+    Lf,
+    /// Terminate records with `\r\n`, as specified by RFC 4180
+    Crlf,
+}
+
+impl CsvLineTerminator {
+    /// The bytes that terminate a record
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Self::Lf => b"\n",
+            Self::Crlf => b"\r\n",
+        }
+    }
+}
+
+impl FromStr for CsvLineTerminator {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lf" | "\n" => Ok(Self::Lf),
+            "crlf" | "\r\n" => Ok(Self::Crlf),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "Unsupported CSV line terminator {s}"
+            ))),
+        }
+    }
+}
+
+impl Display for CsvLineTerminator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Lf => "LF",
+            Self::Crlf => "CRLF",
+        };
+        write!(f, "{str}")
+    }
+}
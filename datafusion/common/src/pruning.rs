@@ -334,6 +334,10 @@ impl PrunableStatistics {
         get_stat: impl Fn(&ColumnStatistics) -> &Precision<ScalarValue>,
     ) -> Option<ArrayRef> {
         let index = self.schema.index_of(column.name()).ok()?;
+        // containers without a statistic are represented by a null of the
+        // column's type, so the values of the other containers can be kept
+        let null = ScalarValue::try_from(self.schema.field(index).data_type())
+            .unwrap_or(ScalarValue::Null);
         let mut has_value = false;
         match ScalarValue::iter_to_array(self.statistics.iter().map(|s| {
             s.column_statistics
@@ -346,7 +350,7 @@ impl PrunableStatistics {
                         None
                     }
                 })
-                .unwrap_or(ScalarValue::Null)
+                .unwrap_or_else(|| null.clone())
         })) {
             // If there is any non-null value and no errors, return the array
             Ok(array) => has_value.then_some(array),
@@ -0,0 +1,227 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Databases of the time zones that can be used in timestamps
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::array::timezone::Tz;
+
+use crate::Result;
+use crate::error::_config_err;
+use crate::utils::datafusion_strsim::levenshtein;
+
+/// A reference counted [`TimeZoneDatabase`]
+pub type TimeZoneDatabaseRef = Arc<dyn TimeZoneDatabase>;
+
+/// A database of time zones, which resolves the time zones named by users,
+/// for example with `SET TIME ZONE`, to the time zones of Arrow timestamps.
+///
+/// Arrow time zones are either fixed offsets, such as `+05:00`, or names of
+/// the IANA time zone database, such as `America/New_York`. DataFusion
+/// bundles the IANA database, see [`BundledTimeZoneDatabase`], so that they
+/// do not depend on the `/usr/share/zoneinfo` files of the host, which
+/// containers and WASM environments often lack.
+///
+/// A different database can be injected to restrict the time zones that can
+/// be used, or to accept other names, for example Windows time zone names or
+/// the zones of a newer release of the IANA database, by resolving them to
+/// Arrow time zones.
+pub trait TimeZoneDatabase: Debug + Send + Sync {
+    /// Returns the name of this database, for example its source and version
+    fn name(&self) -> &str;
+
+    /// Resolves the time zone `tz` to an Arrow time zone, or returns `None`
+    /// if this database does not know `tz`
+    fn resolve(&self, tz: &str) -> Option<String>;
+
+    /// Returns the names of the time zones of this database, which are used
+    /// to suggest a time zone when one can not be resolved
+    fn zone_names(&self) -> Vec<String>;
+}
+
+/// The IANA time zone database bundled with DataFusion, in addition to fixed
+/// offsets such as `+05:00`.
+///
+/// Names are resolved case insensitively, so `america/new_york` resolves to
+/// `America/New_York`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BundledTimeZoneDatabase;
+
+impl TimeZoneDatabase for BundledTimeZoneDatabase {
+    fn name(&self) -> &str {
+        "bundled"
+    }
+
+    fn resolve(&self, tz: &str) -> Option<String> {
+        if Tz::from_str(tz).is_ok() {
+            return Some(tz.to_string());
+        }
+        chrono_tz::Tz::from_str_insensitive(tz)
+            .ok()
+            .map(|tz| tz.name().to_string())
+    }
+
+    fn zone_names(&self) -> Vec<String> {
+        chrono_tz::TZ_VARIANTS
+            .iter()
+            .map(|tz| tz.name().to_string())
+            .collect()
+    }
+}
+
+/// A [`TimeZoneDatabase`] of the time zones it was created with, each
+/// resolving to an Arrow time zone.
+///
+/// # Example
+/// ```
+/// # use datafusion_common::timezone::{MemoryTimeZoneDatabase, TimeZoneDatabase};
+/// let database = MemoryTimeZoneDatabase::new("windows")
+///     .with_zone("Eastern Standard Time", "America/New_York")
+///     .with_zone("UTC", "+00:00");
+/// assert_eq!(
+///     database.resolve("Eastern Standard Time").as_deref(),
+///     Some("America/New_York")
+/// );
+/// assert_eq!(database.resolve("Europe/Paris"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryTimeZoneDatabase {
+    name: String,
+    zones: HashMap<String, String>,
+}
+
+impl MemoryTimeZoneDatabase {
+    /// Creates an empty database named `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            zones: HashMap::new(),
+        }
+    }
+
+    /// Adds the time zone `name`, which resolves to the Arrow time zone `tz`
+    pub fn with_zone(mut self, name: impl Into<String>, tz: impl Into<String>) -> Self {
+        self.zones.insert(name.into(), tz.into());
+        self
+    }
+}
+
+impl TimeZoneDatabase for MemoryTimeZoneDatabase {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resolve(&self, tz: &str) -> Option<String> {
+        self.zones.get(tz).cloned()
+    }
+
+    fn zone_names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self.zones.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// Resolves the time zone `tz` with `database`, and checks that Arrow
+/// supports the resolved time zone.
+///
+/// The error of an unknown time zone suggests the closest time zone of the
+/// database, if any is close enough.
+pub fn resolve_time_zone(database: &dyn TimeZoneDatabase, tz: &str) -> Result<String> {
+    let Some(resolved) = database.resolve(tz) else {
+        return match closest_zone_name(database, tz) {
+            Some(suggestion) => {
+                _config_err!("Invalid time zone '{tz}'. Did you mean '{suggestion}'?")
+            }
+            None => _config_err!(
+                "Invalid time zone '{tz}': not found in the '{}' time zone database. \
+                Time zones are IANA names, such as 'America/New_York', or offsets, \
+                such as '+05:00'",
+                database.name()
+            ),
+        };
+    };
+    if let Err(e) = Tz::from_str(&resolved) {
+        return _config_err!(
+            "Invalid time zone '{tz}': the '{}' time zone database resolved it to \
+            '{resolved}', which is not supported: {e}",
+            database.name()
+        );
+    }
+    Ok(resolved)
+}
+
+/// Returns the name of the time zone of `database` closest to `tz`, if it
+/// differs by at most a quarter of its characters
+fn closest_zone_name(database: &dyn TimeZoneDatabase, tz: &str) -> Option<String> {
+    let tz = tz.to_lowercase();
+    let max_distance = tz.len() / 4;
+    database
+        .zone_names()
+        .into_iter()
+        .map(|name| (levenshtein(&name.to_lowercase(), &tz), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_time_zones() {
+        let database = BundledTimeZoneDatabase;
+        for (tz, expected) in [
+            ("America/New_York", "America/New_York"),
+            ("america/new_york", "America/New_York"),
+            ("UTC", "UTC"),
+            ("+05:30", "+05:30"),
+        ] {
+            assert_eq!(resolve_time_zone(&database, tz).unwrap(), expected);
+        }
+
+        let err = resolve_time_zone(&database, "America/New_Yrok").unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: Invalid time zone 'America/New_Yrok'. Did you mean 'America/New_York'?"
+        );
+        let err = resolve_time_zone(&database, "Mars/Olympus_Mons").unwrap_err();
+        assert!(err.to_string().contains("not found in the 'bundled'"));
+    }
+
+    #[test]
+    fn injected_time_zones() {
+        let database = MemoryTimeZoneDatabase::new("test")
+            .with_zone("Eastern Standard Time", "America/New_York")
+            .with_zone("Broken", "Nowhere");
+
+        assert_eq!(
+            resolve_time_zone(&database, "Eastern Standard Time").unwrap(),
+            "America/New_York"
+        );
+        // only the zones of the database can be used
+        let err = resolve_time_zone(&database, "Europe/Paris").unwrap_err();
+        assert!(err.to_string().contains("not found in the 'test'"));
+        let err = resolve_time_zone(&database, "Broken").unwrap_err();
+        assert!(err.to_string().contains("resolved it to 'Nowhere'"));
+    }
+}
@@ -0,0 +1,394 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::error::{_exec_err, _plan_err};
+use crate::types::extension::DFExtensionType;
+use crate::{Result, ScalarValue};
+use arrow::array::{Array, AsArray, make_array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field};
+use arrow_schema::extension::{EXTENSION_TYPE_METADATA_KEY, EXTENSION_TYPE_NAME_KEY};
+
+/// Defines the `datafusion.categorical` extension type, an ENUM-like string type that only
+/// accepts a fixed set of values declared in the schema.
+///
+/// Categorical values are stored as a dictionary of strings (e.g.,
+/// `Dictionary(Int32, Utf8)`). The allowed values are serialized as a JSON array of strings in the
+/// extension type metadata, for example `["small","medium","large"]`.
+///
+/// The allowed values are validated when inserting into a table and used by the optimizer, for
+/// example to remove `IN` list items and `CASE` branches that can never match.
+///
+/// See [`DFExtensionType`] for information on DataFusion's extension type mechanism.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DFCategorical {
+    values: Vec<String>,
+    storage_type: DataType,
+}
+
+impl DFCategorical {
+    /// The name of the extension type.
+    pub const NAME: &'static str = "datafusion.categorical";
+
+    /// Creates a new [`DFCategorical`] with the allowed `values`, validating that the storage
+    /// type is a dictionary of strings and that the values are unique.
+    pub fn try_new(data_type: &DataType, values: Vec<String>) -> Result<Self> {
+        let DataType::Dictionary(key_type, value_type) = data_type else {
+            return _plan_err!(
+                "Categorical type expects a dictionary storage type, got {data_type}"
+            );
+        };
+        if !key_type.is_dictionary_key_type()
+            || !matches!(
+                value_type.as_ref(),
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            )
+        {
+            return _plan_err!(
+                "Categorical type expects a dictionary of strings, got {data_type}"
+            );
+        }
+
+        if values.is_empty() {
+            return _plan_err!("Categorical type requires at least one allowed value");
+        }
+        let mut seen = HashSet::with_capacity(values.len());
+        if let Some(duplicate) = values.iter().find(|value| !seen.insert(value.as_str()))
+        {
+            return _plan_err!("Duplicate value '{duplicate}' in categorical type");
+        }
+
+        Ok(Self {
+            values,
+            storage_type: data_type.clone(),
+        })
+    }
+
+    /// Creates a new [`DFCategorical`] from the serialized extension type `metadata`.
+    pub fn try_new_from_metadata(
+        data_type: &DataType,
+        metadata: Option<&str>,
+    ) -> Result<Self> {
+        let Some(metadata) = metadata else {
+            return _plan_err!("Categorical type requires metadata with allowed values");
+        };
+        Self::try_new(data_type, deserialize_values(metadata)?)
+    }
+
+    /// Returns the [`DFCategorical`] declared by `field`, or `None` if the field is not annotated
+    /// with the categorical extension type.
+    pub fn try_from_field(field: &Field) -> Result<Option<Self>> {
+        if field.extension_type_name() != Some(Self::NAME) {
+            return Ok(None);
+        }
+        Self::try_new_from_metadata(field.data_type(), field.extension_type_metadata())
+            .map(Some)
+    }
+
+    /// Returns a nullable [`Field`] named `name` that is annotated with this type.
+    pub fn to_field(&self, name: impl Into<String>) -> Field {
+        Field::new(name, self.storage_type.clone(), true).with_metadata(HashMap::from([
+            (EXTENSION_TYPE_NAME_KEY.to_string(), Self::NAME.to_string()),
+            (
+                EXTENSION_TYPE_METADATA_KEY.to_string(),
+                serialize_values(&self.values),
+            ),
+        ]))
+    }
+
+    /// Returns the allowed values, in declaration order.
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    /// Returns true if `value` is one of the allowed values.
+    pub fn contains(&self, value: &str) -> bool {
+        self.values.iter().any(|allowed| allowed == value)
+    }
+
+    /// Returns `Some(true)` if the string `scalar` is an allowed value, `Some(false)` if it is
+    /// not, and `None` if the scalar is null or not a string.
+    pub fn contains_scalar(&self, scalar: &ScalarValue) -> Option<bool> {
+        let scalar = match scalar {
+            ScalarValue::Dictionary(_, inner) => inner.as_ref(),
+            scalar => scalar,
+        };
+        scalar
+            .try_as_str()
+            .flatten()
+            .map(|value| self.contains(value))
+    }
+
+    /// Checks that every non-null value of `array` is an allowed value.
+    pub fn validate(&self, array: &dyn Array) -> Result<()> {
+        let (values, keys) = match array.as_any_dictionary_opt() {
+            Some(dictionary) => (
+                Arc::clone(dictionary.values()),
+                Some(dictionary.normalized_keys()),
+            ),
+            None => (make_array(array.to_data()), None),
+        };
+        let values = cast(&values, &DataType::Utf8)?;
+        let values = values.as_string::<i32>();
+
+        let mut invalid = (0..array.len())
+            .filter(|&row| array.is_valid(row))
+            .map(|row| keys.as_ref().map_or(row, |keys| keys[row]))
+            .filter(|&index| values.is_valid(index))
+            .map(|index| values.value(index))
+            .filter(|value| !self.contains(value));
+        match invalid.next() {
+            Some(value) => _exec_err!(
+                "Value '{value}' is not allowed for categorical type, expected one of: {}",
+                self.values.join(", ")
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+impl DFExtensionType for DFCategorical {
+    fn storage_type(&self) -> DataType {
+        self.storage_type.clone()
+    }
+
+    fn serialize_metadata(&self) -> Option<String> {
+        Some(serialize_values(&self.values))
+    }
+}
+
+/// Serializes `values` as a JSON array of strings
+fn serialize_values(values: &[String]) -> String {
+    let mut serialized = String::from("[");
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            serialized.push(',');
+        }
+        serialized.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => serialized.push_str("\\\""),
+                '\\' => serialized.push_str("\\\\"),
+                c if c.is_control() => write!(serialized, "\\u{:04x}", c as u32).unwrap(),
+                c => serialized.push(c),
+            }
+        }
+        serialized.push('"');
+    }
+    serialized.push(']');
+    serialized
+}
+
+/// Parses a JSON array of strings, as written by [`serialize_values`]
+fn deserialize_values(metadata: &str) -> Result<Vec<String>> {
+    let invalid = || {
+        _plan_err!(
+            "Invalid categorical type metadata, expected a JSON array of strings: {metadata}"
+        )
+    };
+
+    let Some(inner) = metadata
+        .trim()
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+    else {
+        return invalid();
+    };
+
+    let mut values = vec![];
+    let mut chars = inner.trim().chars().peekable();
+    while chars.peek().is_some() {
+        if chars.next() != Some('"') {
+            return invalid();
+        }
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('u') => {
+                        let code: String = chars.by_ref().take(4).collect();
+                        match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32)
+                        {
+                            Some(c) => value.push(c),
+                            None => return invalid(),
+                        }
+                    }
+                    _ => return invalid(),
+                },
+                Some(c) => value.push(c),
+                None => return invalid(),
+            }
+        }
+        values.push(value);
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            Some(',') => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if chars.peek().is_none() {
+                    return invalid();
+                }
+            }
+            Some(_) => return invalid(),
+            None => {}
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{DictionaryArray, StringArray};
+    use arrow::datatypes::Int32Type;
+
+    fn dictionary_type() -> DataType {
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    }
+
+    fn size() -> DFCategorical {
+        DFCategorical::try_new(
+            &dictionary_type(),
+            vec![
+                "small".to_string(),
+                "medium".to_string(),
+                "large".to_string(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_categorical_metadata_roundtrip() -> Result<()> {
+        let values = vec![
+            "a".to_string(),
+            "with \"quotes\", commas and \\".to_string(),
+            "new\nline".to_string(),
+        ];
+        let categorical = DFCategorical::try_new(&dictionary_type(), values.clone())?;
+
+        let field = categorical.to_field("c");
+        assert_eq!(field.extension_type_name(), Some(DFCategorical::NAME));
+        let roundtrip = DFCategorical::try_from_field(&field)?.unwrap();
+        assert_eq!(roundtrip.values(), values.as_slice());
+
+        let parsed = DFCategorical::try_new_from_metadata(
+            &dictionary_type(),
+            Some(r#"[ "x" , "yé" ]"#),
+        )?;
+        assert_eq!(parsed.values(), &["x".to_string(), "yé".to_string()]);
+
+        assert!(
+            DFCategorical::try_from_field(&Field::new("c", DataType::Utf8, true))?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_categorical_invalid() {
+        let values = || vec!["a".to_string()];
+        let err = DFCategorical::try_new(&DataType::Utf8, values()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expects a dictionary storage type")
+        );
+
+        let storage =
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Int64));
+        let err = DFCategorical::try_new(&storage, values()).unwrap_err();
+        assert!(err.to_string().contains("expects a dictionary of strings"));
+
+        let err = DFCategorical::try_new(&dictionary_type(), vec![]).unwrap_err();
+        assert!(err.to_string().contains("at least one allowed value"));
+
+        let err = DFCategorical::try_new(
+            &dictionary_type(),
+            vec!["a".to_string(), "a".to_string()],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Duplicate value 'a'"));
+
+        for metadata in ["", "[", "[\"a\",]", "[\"a\" \"b\"]", "[a]", "[\"a]"] {
+            assert!(
+                DFCategorical::try_new_from_metadata(&dictionary_type(), Some(metadata))
+                    .is_err(),
+                "{metadata}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_categorical_validate() -> Result<()> {
+        let categorical = size();
+
+        let valid: DictionaryArray<Int32Type> =
+            vec![Some("small"), None, Some("large"), Some("small")]
+                .into_iter()
+                .collect();
+        categorical.validate(&valid)?;
+
+        // unreferenced dictionary values are not checked
+        let unreferenced = DictionaryArray::<Int32Type>::try_new(
+            vec![Some(0), None].into(),
+            Arc::new(StringArray::from(vec!["medium", "huge"])),
+        )?;
+        categorical.validate(&unreferenced)?;
+
+        let invalid: DictionaryArray<Int32Type> =
+            vec![Some("small"), Some("huge")].into_iter().collect();
+        let err = categorical.validate(&invalid).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Value 'huge' is not allowed for categorical type")
+        );
+
+        let plain = StringArray::from(vec![Some("medium"), None, Some("tiny")]);
+        let err = categorical.validate(&plain).unwrap_err();
+        assert!(err.to_string().contains("Value 'tiny'"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_categorical_contains_scalar() {
+        let categorical = size();
+        assert_eq!(
+            categorical.contains_scalar(&ScalarValue::from("small")),
+            Some(true)
+        );
+        assert_eq!(
+            categorical.contains_scalar(&ScalarValue::Dictionary(
+                Box::new(DataType::Int32),
+                Box::new(ScalarValue::from("huge"))
+            )),
+            Some(false)
+        );
+        assert_eq!(categorical.contains_scalar(&ScalarValue::Utf8(None)), None);
+        assert_eq!(categorical.contains_scalar(&ScalarValue::from(1)), None);
+    }
+}
@@ -17,6 +17,7 @@
 
 mod builtin;
 mod canonical_extensions;
+mod categorical;
 mod extension;
 mod field;
 mod logical;
@@ -24,6 +25,7 @@ mod native;
 
 pub use builtin::*;
 pub use canonical_extensions::*;
+pub use categorical::*;
 pub use extension::*;
 pub use field::*;
 pub use logical::*;
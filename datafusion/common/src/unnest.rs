@@ -66,6 +66,15 @@ use crate::Column;
 /// `recursions` instruct how a column should be unnested (e.g unnesting a column multiple
 /// time, with depth = 1 and depth = 2). Any unnested column not being mentioned inside this
 /// options is inferred to be unnested with depth = 1
+///
+/// If `ordinality` is true, an [`UNNEST_ORDINALITY_COLUMN`] column of type
+/// `Int64` is appended to the output, holding the 1-based position of each
+/// row among the rows unnested from the same input row, like SQL
+/// `UNNEST(...) WITH ORDINALITY`.
+///
+/// If `outer` is true, every input row produces at least one output row:
+/// rows whose lists are all empty or null produce a single row with nulls in
+/// the unnested columns, like SQL `LEFT JOIN UNNEST(...) ON true`.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq)]
 pub struct UnnestOptions {
     /// Should nulls in the input be preserved? Defaults to true
@@ -74,8 +83,17 @@ pub struct UnnestOptions {
     /// declare them here. Any unnested columns not being mentioned inside this option
     /// will be unnested with depth = 1
     pub recursions: Vec<RecursionUnnestOption>,
+    /// Should a column with the position of each unnested row be appended?
+    /// Defaults to false
+    pub ordinality: bool,
+    /// Should rows with only empty or null lists produce a row of nulls?
+    /// Defaults to false
+    pub outer: bool,
 }
 
+/// Name of the column appended by [`UnnestOptions::ordinality`]
+pub const UNNEST_ORDINALITY_COLUMN: &str = "ordinality";
+
 /// Instruction on how to unnest a column (mostly with a list type)
 /// such as how to name the output, and how many level it should be unnested
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]
@@ -91,6 +109,8 @@ impl Default for UnnestOptions {
             // default to true to maintain backwards compatible behavior
             preserve_nulls: true,
             recursions: vec![],
+            ordinality: false,
+            outer: false,
         }
     }
 }
@@ -113,4 +133,18 @@ impl UnnestOptions {
         self.recursions.push(recursion);
         self
     }
+
+    /// Set whether a column with the position of each unnested row is
+    /// appended, as described on [`Self`]
+    pub fn with_ordinality(mut self, ordinality: bool) -> Self {
+        self.ordinality = ordinality;
+        self
+    }
+
+    /// Set whether rows with only empty or null lists are preserved, as
+    /// described on [`Self`]
+    pub fn with_outer(mut self, outer: bool) -> Self {
+        self.outer = outer;
+        self
+    }
 }
@@ -0,0 +1,183 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of numeric [`RecordBatch`]es to dense matrices for use with
+//! numerical libraries
+
+use arrow::array::AsArray;
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Float64Type};
+use arrow::record_batch::RecordBatch;
+
+use crate::Result;
+use crate::error::_plan_err;
+
+/// Order in which the values of a [`Float64Matrix`] are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixLayout {
+    /// The values of each row are contiguous (C order)
+    RowMajor,
+    /// The values of each column are contiguous (Fortran order)
+    ColumnMajor,
+}
+
+/// A dense two dimensional matrix of `f64` values.
+///
+/// The `data` and shape can be handed to numerical libraries without further
+/// conversion, for example with `ndarray`:
+///
+/// ```text
+/// let array = Array2::from_shape_vec((m.num_rows, m.num_columns), m.data)?; // RowMajor
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Float64Matrix {
+    /// The values, in the order given by `layout`
+    pub data: Vec<f64>,
+    /// Number of rows
+    pub num_rows: usize,
+    /// Number of columns
+    pub num_columns: usize,
+    /// Memory layout of `data`
+    pub layout: MatrixLayout,
+}
+
+impl Float64Matrix {
+    /// Return the value at `row` and `column`
+    pub fn get(&self, row: usize, column: usize) -> Option<f64> {
+        if row >= self.num_rows || column >= self.num_columns {
+            return None;
+        }
+        let idx = match self.layout {
+            MatrixLayout::RowMajor => row * self.num_columns + column,
+            MatrixLayout::ColumnMajor => column * self.num_rows + row,
+        };
+        self.data.get(idx).copied()
+    }
+}
+
+/// Convert the numeric columns of `batches` into a [`Float64Matrix`] with
+/// one row per input row and one column per field.
+///
+/// All columns must have a numeric (integer, floating point or decimal) type
+/// and are cast to `f64`. Null values become `NaN`.
+pub fn record_batches_to_matrix(
+    batches: &[RecordBatch],
+    layout: MatrixLayout,
+) -> Result<Float64Matrix> {
+    let Some(first) = batches.first() else {
+        return Ok(Float64Matrix {
+            data: vec![],
+            num_rows: 0,
+            num_columns: 0,
+            layout,
+        });
+    };
+    let schema = first.schema();
+    for field in schema.fields() {
+        if !field.data_type().is_numeric() {
+            return _plan_err!(
+                "Cannot convert column '{}' of type {} to a matrix, only numeric columns are supported",
+                field.name(),
+                field.data_type()
+            );
+        }
+    }
+
+    let num_rows = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+    let num_columns = schema.fields().len();
+    let mut data = vec![0.0; num_rows * num_columns];
+
+    let mut row_offset = 0;
+    for batch in batches {
+        if batch.num_columns() != num_columns {
+            return _plan_err!("All batches must have the same number of columns");
+        }
+        for (column_idx, column) in batch.columns().iter().enumerate() {
+            let column = cast(column, &DataType::Float64)?;
+            let values = column.as_primitive::<Float64Type>();
+            for (i, value) in values.iter().enumerate() {
+                let row = row_offset + i;
+                let idx = match layout {
+                    MatrixLayout::RowMajor => row * num_columns + column_idx,
+                    MatrixLayout::ColumnMajor => column_idx * num_rows + row,
+                };
+                data[idx] = value.unwrap_or(f64::NAN);
+            }
+        }
+        row_offset += batch.num_rows();
+    }
+
+    Ok(Float64Matrix {
+        data,
+        num_rows,
+        num_columns,
+        layout,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Float32Array, Int32Array, StringArray};
+    use std::sync::Arc;
+
+    fn batch(a: Vec<Option<i32>>, b: Vec<f32>) -> RecordBatch {
+        RecordBatch::try_from_iter([
+            ("a", Arc::new(Int32Array::from(a)) as ArrayRef),
+            ("b", Arc::new(Float32Array::from(b)) as ArrayRef),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_record_batches_to_matrix() -> Result<()> {
+        let batches = [
+            batch(vec![Some(1), Some(2)], vec![0.5, 1.5]),
+            batch(vec![None], vec![2.5]),
+        ];
+
+        let m = record_batches_to_matrix(&batches, MatrixLayout::RowMajor)?;
+        assert_eq!((m.num_rows, m.num_columns), (3, 2));
+        assert_eq!(&m.data[..4], &[1.0, 0.5, 2.0, 1.5]);
+        assert!(m.data[4].is_nan());
+        assert_eq!(m.data[5], 2.5);
+
+        let m = record_batches_to_matrix(&batches, MatrixLayout::ColumnMajor)?;
+        assert_eq!(&m.data[..2], &[1.0, 2.0]);
+        assert!(m.data[2].is_nan());
+        assert_eq!(&m.data[3..], &[0.5, 1.5, 2.5]);
+        assert_eq!(m.get(1, 1), Some(1.5));
+        assert_eq!(m.get(3, 0), None);
+
+        let empty = record_batches_to_matrix(&[], MatrixLayout::RowMajor)?;
+        assert_eq!((empty.num_rows, empty.num_columns), (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_batches_to_matrix_non_numeric() {
+        let batch = RecordBatch::try_from_iter([(
+            "s",
+            Arc::new(StringArray::from(vec!["x"])) as ArrayRef,
+        )])
+        .unwrap();
+        let err = record_batches_to_matrix(&[batch], MatrixLayout::RowMajor)
+            .unwrap_err()
+            .strip_backtrace();
+        assert!(err.contains("only numeric columns are supported"), "{err}");
+    }
+}
@@ -19,6 +19,7 @@
 
 pub(crate) mod aggregate;
 pub mod expr;
+pub mod matrix;
 pub mod memory;
 pub mod proxy;
 pub mod string_utils;
@@ -0,0 +1,160 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Event time watermarks for unbounded streams
+
+use std::collections::HashMap;
+
+use crate::Result;
+use crate::error::_plan_err;
+use arrow::datatypes::{DataType, Field, TimeUnit};
+
+/// Field metadata key declaring a timestamp column as the event time of an
+/// unbounded stream. The value is the allowed lateness in nanoseconds.
+pub const WATERMARK_DELAY_KEY: &str = "datafusion.watermark.delay";
+
+/// Declares that a timestamp column is the event time of an unbounded
+/// stream, whose rows arrive at most `delay` late.
+///
+/// The watermark of such a column is the largest value seen so far minus the
+/// delay: rows arriving later are assumed to have a value at or above the
+/// watermark. Aggregations grouped by the column can therefore emit the
+/// groups below the watermark before the end of the input, and drop any row
+/// that arrives after its group was emitted.
+///
+/// Watermarks are declared with the [`WATERMARK_DELAY_KEY`] field metadata,
+/// either by the source itself or with the `watermark.<column>` option of
+/// `CREATE UNBOUNDED EXTERNAL TABLE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Watermark {
+    /// Allowed lateness, in nanoseconds
+    delay_nanos: i64,
+}
+
+impl Watermark {
+    /// Create a new `Watermark` allowing rows to be up to `delay_nanos`
+    /// nanoseconds late
+    pub fn try_new(delay_nanos: i64) -> Result<Self> {
+        if delay_nanos < 0 {
+            return _plan_err!("Watermark delay must not be negative, got {delay_nanos}");
+        }
+        Ok(Self { delay_nanos })
+    }
+
+    /// Returns the allowed lateness, in nanoseconds
+    pub fn delay_nanos(&self) -> i64 {
+        self.delay_nanos
+    }
+
+    /// Returns the allowed lateness in `unit`, rounded up
+    pub fn delay(&self, unit: TimeUnit) -> i64 {
+        let nanos_per_unit = match unit {
+            TimeUnit::Second => 1_000_000_000_u64,
+            TimeUnit::Millisecond => 1_000_000,
+            TimeUnit::Microsecond => 1_000,
+            TimeUnit::Nanosecond => 1,
+        };
+        // the delay is never negative
+        (self.delay_nanos as u64).div_ceil(nanos_per_unit) as i64
+    }
+
+    /// Returns a watermark that allows `nanos` more lateness, for example for
+    /// a column derived by rounding down this event time
+    pub fn with_additional_delay(&self, nanos: i64) -> Result<Self> {
+        match self.delay_nanos.checked_add(nanos) {
+            Some(delay_nanos) => Self::try_new(delay_nanos),
+            None => _plan_err!("Watermark delay overflows: {} + {nanos}", self),
+        }
+    }
+
+    /// Returns the watermark declared by `field`, if any
+    pub fn try_from_field(field: &Field) -> Result<Option<Self>> {
+        let Some(delay) = field.metadata().get(WATERMARK_DELAY_KEY) else {
+            return Ok(None);
+        };
+        if !matches!(field.data_type(), DataType::Timestamp(_, _)) {
+            return _plan_err!(
+                "Watermark can only be declared on a timestamp column, got {} for column {}",
+                field.data_type(),
+                field.name()
+            );
+        }
+        match delay.parse::<i64>() {
+            Ok(delay_nanos) => Self::try_new(delay_nanos).map(Some),
+            Err(_) => _plan_err!(
+                "Invalid watermark delay for column {}: '{delay}'",
+                field.name()
+            ),
+        }
+    }
+
+    /// Adds this watermark to the metadata of `field`
+    pub fn add_to_field(&self, field: Field) -> Field {
+        let mut metadata = field.metadata().clone();
+        self.add_to_metadata(&mut metadata);
+        field.with_metadata(metadata)
+    }
+
+    /// Adds this watermark to field `metadata`
+    pub fn add_to_metadata(&self, metadata: &mut HashMap<String, String>) {
+        metadata.insert(
+            WATERMARK_DELAY_KEY.to_string(),
+            self.delay_nanos.to_string(),
+        );
+    }
+}
+
+impl std::fmt::Display for Watermark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}ns", self.delay_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watermark_field_metadata() -> Result<()> {
+        let field = Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        );
+        assert_eq!(Watermark::try_from_field(&field)?, None);
+
+        let watermark = Watermark::try_new(1_500_000)?;
+        let field = watermark.add_to_field(field);
+        assert_eq!(Watermark::try_from_field(&field)?, Some(watermark));
+        assert_eq!(watermark.delay(TimeUnit::Millisecond), 2);
+        assert_eq!(watermark.delay(TimeUnit::Nanosecond), 1_500_000);
+
+        let watermark = watermark.with_additional_delay(500_000)?;
+        assert_eq!(watermark.delay(TimeUnit::Millisecond), 2);
+
+        let field =
+            Watermark::try_new(1)?.add_to_field(Field::new("id", DataType::Int64, false));
+        assert_eq!(
+            Watermark::try_from_field(&field)
+                .unwrap_err()
+                .strip_backtrace(),
+            "Error during planning: Watermark can only be declared on a timestamp column, got Int64 for column id"
+        );
+        assert!(Watermark::try_new(-1).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks the cost of building a default [`SessionContext`], which
+//! registers every built-in function and the default optimizer rules.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use datafusion::execution::SessionStateBuilder;
+use datafusion::execution::context::SessionContext;
+use std::hint::black_box;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("SessionContext::new", |b| {
+        b.iter(|| black_box(SessionContext::new()))
+    });
+
+    c.bench_function("SessionStateBuilder::with_default_features", |b| {
+        b.iter(|| black_box(SessionStateBuilder::new().with_default_features().build()))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,219 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use super::{DataFrame, LogicalPlanBuilder, Result};
+
+use arrow::array::UInt64Array;
+use arrow::datatypes::DataType;
+use datafusion_common::hash_utils::{RandomState, create_hashes};
+use datafusion_common::{Column, NullEquality, plan_err};
+use datafusion_expr::{
+    ColumnarValue, Expr, JoinType, LogicalPlan, Operator, ScalarFunctionArgs, ScalarUDF,
+    ScalarUDFImpl, Signature, Volatility, binary_expr, lit, when,
+};
+use datafusion_functions::core::expr_fn::coalesce;
+
+impl DataFrame {
+    /// Compare the rows of this `DataFrame` with the rows of `other`, matching
+    /// rows on the `keys` columns, and return the keys of the rows that differ.
+    ///
+    /// The result has the `keys` columns, in that order, and a `diff` column
+    /// that is:
+    /// * `added` if the key is only present in `other`
+    /// * `removed` if the key is only present in `self`
+    /// * `changed` if any of the other columns differ
+    ///
+    /// Both inputs must have the same column names and types, though not
+    /// necessarily in the same order, and `keys` should be unique in each of
+    /// them. `NULL` keys match each other.
+    ///
+    /// Rather than joining all the columns, only the keys and a 64 bit hash
+    /// of the remaining columns of each row are joined, so a change is missed
+    /// if, very unlikely, the old and new values of a row have the same hash.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let old = ctx.sql("SELECT * FROM (VALUES (1, 'a'), (2, 'b')) t(id, v)").await?;
+    /// let new = ctx.sql("SELECT * FROM (VALUES (2, 'c'), (3, 'd')) t(id, v)").await?;
+    /// let diff = old.diff(new, &["id"])?;
+    /// let expected = vec![
+    ///     "+----+---------+",
+    ///     "| id | diff    |",
+    ///     "+----+---------+",
+    ///     "| 1  | removed |",
+    ///     "| 2  | changed |",
+    ///     "| 3  | added   |",
+    ///     "+----+---------+",
+    /// ];
+    /// # assert_batches_sorted_eq!(expected, &diff.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff(self, other: DataFrame, keys: &[&str]) -> Result<DataFrame> {
+        if keys.is_empty() {
+            return plan_err!("DataFrame::diff requires at least one key column");
+        }
+
+        let schema = self.schema();
+        let other_schema = other.schema();
+        if schema.fields().len() != other_schema.fields().len() {
+            return plan_err!(
+                "Cannot diff inputs with {} and {} columns",
+                schema.fields().len(),
+                other_schema.fields().len()
+            );
+        }
+
+        // Pairs of matching columns of both inputs
+        let mut key_columns = Vec::with_capacity(keys.len());
+        let mut value_columns = vec![];
+        for (qualifier, field) in schema.iter() {
+            let name = field.name();
+            let (other_qualifier, other_field) =
+                other_schema.qualified_field_with_unqualified_name(name)?;
+            if field.data_type() != other_field.data_type() {
+                return plan_err!(
+                    "Cannot diff column {name} of type {} with a column of type {}",
+                    field.data_type(),
+                    other_field.data_type()
+                );
+            }
+            let pair = (
+                Column::new(qualifier.cloned(), name),
+                Column::new(other_qualifier.cloned(), name),
+            );
+            if keys.contains(&name.as_str()) {
+                key_columns.push(pair);
+            } else {
+                value_columns.push(pair);
+            }
+        }
+        if key_columns.len() != keys.len() {
+            return plan_err!("Key columns {keys:?} are not all columns of the input");
+        }
+        key_columns.sort_by_key(|(key, _)| keys.iter().position(|k| *k == key.name));
+
+        let (left_keys, right_keys): (Vec<_>, Vec<_>) = key_columns.into_iter().unzip();
+        let (left_values, right_values) = value_columns.into_iter().unzip();
+        let left = hash_values(self.plan, left_keys, left_values, "left")?;
+        let right = hash_values(other.plan, right_keys.clone(), right_values, "right")?;
+
+        let key_names = |side| {
+            (0..keys.len())
+                .map(|i| Column::from_name(format!("__{side}_key_{i}")))
+                .collect::<Vec<_>>()
+        };
+        let left_hash = Expr::Column(Column::from_name("__left_hash"));
+        let right_hash = Expr::Column(Column::from_name("__right_hash"));
+
+        let diff = when(left_hash.clone().is_null(), lit("added"))
+            .when(right_hash.clone().is_null(), lit("removed"))
+            .otherwise(lit("changed"))?
+            .alias("diff");
+        let projection = key_names("left")
+            .into_iter()
+            .zip(key_names("right"))
+            .zip(right_keys)
+            .map(|((left, right), column)| {
+                coalesce(vec![Expr::Column(left), Expr::Column(right)]).alias(column.name)
+            })
+            .chain([diff]);
+
+        let plan = LogicalPlanBuilder::from(left)
+            .join_detailed(
+                right,
+                JoinType::Full,
+                (key_names("left"), key_names("right")),
+                None,
+                NullEquality::NullEqualsNull,
+            )?
+            .filter(binary_expr(left_hash, Operator::IsDistinctFrom, right_hash))?
+            .project(projection)?
+            .build()?;
+
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+            projection_requires_validation: true,
+        })
+    }
+}
+
+/// Project `plan` to the `keys` columns, renamed to `__{side}_key_{i}`, and
+/// a `__{side}_hash` of the `values` columns
+fn hash_values(
+    plan: LogicalPlan,
+    keys: Vec<Column>,
+    values: Vec<Column>,
+    side: &str,
+) -> Result<LogicalPlan> {
+    let hash = if values.is_empty() {
+        lit(0u64)
+    } else {
+        let args = values.into_iter().map(Expr::Column).collect();
+        ScalarUDF::new_from_impl(RowHash::new()).call(args)
+    };
+    let projection = keys
+        .into_iter()
+        .enumerate()
+        .map(|(i, key)| Expr::Column(key).alias(format!("__{side}_key_{i}")))
+        .chain([hash.alias(format!("__{side}_hash"))]);
+    LogicalPlanBuilder::from(plan).project(projection)?.build()
+}
+
+/// Hashes the values of its arguments in each row to a `UInt64`
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct RowHash {
+    signature: Signature,
+}
+
+impl RowHash {
+    fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RowHash {
+    fn name(&self) -> &str {
+        "row_hash"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(&args.args)?;
+        let mut hashes = vec![0; args.number_rows];
+        create_hashes(&arrays, &RandomState::default(), &mut hashes)?;
+        Ok(ColumnarValue::Array(Arc::new(UInt64Array::from(hashes))))
+    }
+}
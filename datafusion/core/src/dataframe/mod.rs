@@ -17,13 +17,17 @@
 
 //! [`DataFrame`] API for building and executing query plans.
 
+mod diff;
 #[cfg(feature = "parquet")]
 mod parquet;
+#[cfg(feature = "serde")]
+mod serde_rows;
 
 use crate::arrow::record_batch::RecordBatch;
 use crate::arrow::util::pretty;
 use crate::datasource::file_format::csv::CsvFormatFactory;
 use crate::datasource::file_format::format_as_file_type;
+#[cfg(feature = "json")]
 use crate::datasource::file_format::json::JsonFormatFactory;
 use crate::datasource::{
     DefaultTableSource, MemTable, TableProvider, provider_as_source,
@@ -36,9 +40,12 @@ use crate::logical_expr::{
     Expr, JoinType, LogicalPlan, LogicalPlanBuilder, LogicalPlanBuilderOptions,
     Partitioning, TableType, col, ident,
 };
+use crate::physical_plan::common::collect as collect_stream;
+use crate::physical_plan::result_limit::{ResultLimiter, limit_result_stream};
+use crate::physical_plan::spool::spool_stream;
 use crate::physical_plan::{
-    ExecutionPlan, SendableRecordBatchStream, collect, collect_partitioned,
-    execute_stream, execute_stream_partitioned,
+    ExecutionPlan, SendableRecordBatchStream, collect_partitioned, execute_stream,
+    execute_stream_partitioned,
 };
 use crate::prelude::SessionContext;
 use std::borrow::Cow;
@@ -49,7 +56,9 @@ use arrow::array::{Array, ArrayRef, Int64Array, StringArray};
 use arrow::compute::{cast, concat};
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow_schema::FieldRef;
-use datafusion_common::config::{CsvOptions, JsonOptions};
+use datafusion_common::config::CsvOptions;
+#[cfg(feature = "json")]
+use datafusion_common::config::JsonOptions;
 use datafusion_common::{
     Column, DFSchema, DataFusionError, ParamValues, ScalarValue, SchemaError,
     TableReference, UnnestOptions, exec_err, internal_datafusion_err, not_impl_err,
@@ -57,7 +66,7 @@ use datafusion_common::{
 };
 use datafusion_expr::select_expr::SelectExpr;
 use datafusion_expr::{
-    ExplainOption, SortExpr, TableProviderFilterPushDown, UNNAMED_TABLE, case,
+    DedupKeep, ExplainOption, SortExpr, TableProviderFilterPushDown, UNNAMED_TABLE, case,
     dml::InsertOp,
     expr::{Alias, ScalarFunction},
     is_null, lit,
@@ -977,6 +986,53 @@ impl DataFrame {
         })
     }
 
+    /// Return a new `DataFrame` with only one row for each distinct value of
+    /// `keys`: the first or the last row of each group according to
+    /// `order_by`, as determined by `keep`.
+    ///
+    /// All columns are kept. See [`LogicalPlanBuilder::deduplicate`] for
+    /// details.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx
+    ///     .read_csv("tests/data/example.csv", CsvReadOptions::new())
+    ///     .await?
+    ///     // Keep the row with the highest b for each distinct value of a
+    ///     .dedup(vec![col("a")], vec![col("b").sort(true, true)], DedupKeep::Last)?;
+    /// let expected = vec![
+    ///     "+---+---+---+",
+    ///     "| a | b | c |",
+    ///     "+---+---+---+",
+    ///     "| 1 | 2 | 3 |",
+    ///     "+---+---+---+",
+    /// ];
+    /// # assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dedup(
+        self,
+        keys: Vec<Expr>,
+        order_by: Vec<SortExpr>,
+        keep: DedupKeep,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .deduplicate(keys, order_by, keep)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+            projection_requires_validation: true,
+        })
+    }
+
     /// Return a new `DataFrame` that has statistics for a DataFrame.
     ///
     /// Only summarizes numeric datatypes at the moment and returns nulls for
@@ -1463,7 +1519,8 @@ impl DataFrame {
     pub async fn collect(self) -> Result<Vec<RecordBatch>> {
         let task_ctx = Arc::new(self.task_ctx());
         let plan = self.create_physical_plan().await?;
-        collect(plan, task_ctx).await
+        let stream = execute_stream(plan, Arc::clone(&task_ctx))?;
+        collect_stream(limit_result_stream(stream, &task_ctx)).await
     }
 
     /// Execute the `DataFrame` and print the results to the console.
@@ -1581,10 +1638,31 @@ impl DataFrame {
     ///
     /// Dropping the stream will abort the execution of the query, and free up
     /// any allocated resources
+    ///
+    /// # Spooling Results
+    ///
+    /// If `datafusion.execution.spool_results` is set, the results are written
+    /// to temporary files as the query produces them, and the stream reads them
+    /// back, so the query does not wait for the stream to be consumed. See
+    /// [`spool_stream`].
+    ///
+    /// # Limiting Results
+    ///
+    /// If `datafusion.execution.max_result_rows` or
+    /// `datafusion.execution.max_result_bytes` is set, the stream returns an
+    /// error, or ends, once the results exceed the limit, see
+    /// [`ResultLimiter`]. This also applies to [`Self::collect`] and the
+    /// partitioned variants of these methods.
     pub async fn execute_stream(self) -> Result<SendableRecordBatchStream> {
         let task_ctx = Arc::new(self.task_ctx());
         let plan = self.create_physical_plan().await?;
-        execute_stream(plan, task_ctx)
+        let stream = execute_stream(plan, Arc::clone(&task_ctx))?;
+        let stream = limit_result_stream(stream, &task_ctx);
+        if task_ctx.session_config().options().execution.spool_results {
+            Ok(spool_stream(stream, &task_ctx))
+        } else {
+            Ok(stream)
+        }
     }
 
     /// Executes this DataFrame and collects all results into a vector of vector of RecordBatch
@@ -1607,7 +1685,16 @@ impl DataFrame {
     pub async fn collect_partitioned(self) -> Result<Vec<Vec<RecordBatch>>> {
         let task_ctx = Arc::new(self.task_ctx());
         let plan = self.create_physical_plan().await?;
-        collect_partitioned(plan, task_ctx).await
+        let Some(limiter) = ResultLimiter::try_new(&task_ctx) else {
+            return collect_partitioned(plan, task_ctx).await;
+        };
+        let streams = execute_stream_partitioned(plan, task_ctx)?;
+        futures::future::try_join_all(
+            streams
+                .into_iter()
+                .map(|stream| collect_stream(limiter.limit_stream(stream))),
+        )
+        .await
     }
 
     /// Executes this DataFrame and returns one stream per partition.
@@ -1635,7 +1722,14 @@ impl DataFrame {
     ) -> Result<Vec<SendableRecordBatchStream>> {
         let task_ctx = Arc::new(self.task_ctx());
         let plan = self.create_physical_plan().await?;
-        execute_stream_partitioned(plan, task_ctx)
+        let streams = execute_stream_partitioned(plan, Arc::clone(&task_ctx))?;
+        Ok(match ResultLimiter::try_new(&task_ctx) {
+            Some(limiter) => streams
+                .into_iter()
+                .map(|stream| limiter.limit_stream(stream))
+                .collect(),
+            None => streams,
+        })
     }
 
     /// Returns the `DFSchema` describing the output of this DataFrame.
@@ -2112,6 +2206,7 @@ impl DataFrame {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "json")]
     pub async fn write_json(
         self,
         path: &str,
@@ -2373,6 +2468,51 @@ impl DataFrame {
         })
     }
 
+    /// Attach a typed, per-query value, such as the user running the query,
+    /// to the session used to execute this DataFrame.
+    ///
+    /// The value is only visible to this DataFrame (and the DataFrames derived
+    /// from it), not to the [`SessionContext`] it was created from. While the
+    /// query executes, it is available through [`SessionConfig::get_extension`],
+    /// e.g. in [`TableProvider::scan`], and in scalar functions through
+    /// [`ScalarFunctionArgs::config_options`].
+    ///
+    /// See [`SessionConfig::with_extension`] for details.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use std::sync::Arc;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// struct UserId(String);
+    ///
+    /// let ctx = SessionContext::new();
+    /// let df = ctx
+    ///     .sql("SELECT 1")
+    ///     .await?
+    ///     .with_extension(Arc::new(UserId("alice".to_string())));
+    /// let user = df.task_ctx().session_config().get_extension::<UserId>();
+    /// assert_eq!(user.unwrap().0, "alice");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`SessionConfig::get_extension`]: crate::prelude::SessionConfig::get_extension
+    /// [`SessionConfig::with_extension`]: crate::prelude::SessionConfig::with_extension
+    /// [`TableProvider::scan`]: crate::datasource::TableProvider::scan
+    /// [`ScalarFunctionArgs::config_options`]: datafusion_expr::ScalarFunctionArgs::config_options
+    pub fn with_extension<T>(mut self, ext: Arc<T>) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.session_state.config_mut().set_extension(ext);
+        // scalar functions read the options captured when the query started
+        let config_options = Arc::clone(self.session_state.config_options());
+        self.session_state.execution_props_mut().config_options = Some(config_options);
+        self
+    }
+
     /// Cache DataFrame as a memory table.
     ///
     /// Default behavior could be changed using
@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::{DataFrame, Result};
+use crate::execution::context::serde_rows_from_record_batches;
+
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+
+impl DataFrame {
+    /// Execute this `DataFrame` and convert each resulting row to a value of
+    /// type `T`, matching columns to fields of `T` by name.
+    ///
+    /// See [`Self::execute_stream_typed`] to convert rows without buffering
+    /// all of them in memory.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// #[derive(Debug, PartialEq, serde::Deserialize)]
+    /// struct Row {
+    ///     a: i64,
+    ///     b: String,
+    /// }
+    ///
+    /// let ctx = SessionContext::new();
+    /// let rows: Vec<Row> = ctx
+    ///     .sql("SELECT 1 AS a, 'x' AS b")
+    ///     .await?
+    ///     .collect_typed()
+    ///     .await?;
+    /// assert_eq!(rows, vec![Row { a: 1, b: "x".to_string() }]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collect_typed<T: DeserializeOwned>(self) -> Result<Vec<T>> {
+        serde_rows_from_record_batches(&self.collect().await?)
+    }
+
+    /// Execute this `DataFrame` and return a stream of its rows converted to
+    /// values of type `T`, see [`Self::collect_typed`].
+    ///
+    /// Rows are converted one `RecordBatch` at a time as they are produced.
+    pub async fn execute_stream_typed<T: DeserializeOwned + Send + 'static>(
+        self,
+    ) -> Result<BoxStream<'static, Result<T>>> {
+        let stream = self.execute_stream().await?;
+        Ok(stream
+            .map(|batch| serde_rows_from_record_batches::<T>(&[batch?]))
+            .map_ok(|rows| stream::iter(rows.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::context::SessionContext;
+    use datafusion_execution::config::SessionConfig;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Row {
+        id: i64,
+        name: Option<String>,
+    }
+
+    #[tokio::test]
+    async fn collect_typed_rows() -> Result<()> {
+        let ctx = SessionContext::new();
+        let rows: Vec<Row> = ctx
+            .sql("SELECT * FROM (VALUES (1, 'a'), (2, NULL)) AS t(id, name) ORDER BY id")
+            .await?
+            .collect_typed()
+            .await?;
+        assert_eq!(
+            rows,
+            vec![
+                Row {
+                    id: 1,
+                    name: Some("a".to_string())
+                },
+                Row { id: 2, name: None },
+            ]
+        );
+
+        // column types that do not match the fields are reported as errors
+        let err = ctx
+            .sql("SELECT 'x' AS id, 'a' AS name")
+            .await?
+            .collect_typed::<Row>()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid type"), "{err}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn execute_stream_typed_rows() -> Result<()> {
+        let ctx =
+            SessionContext::new_with_config(SessionConfig::new().with_batch_size(3));
+        let stream = ctx
+            .sql("SELECT value AS id, NULL AS name FROM generate_series(1, 10)")
+            .await?
+            .execute_stream_typed::<Row>()
+            .await?;
+        let mut ids = stream.map_ok(|row| row.id).try_collect::<Vec<_>>().await?;
+        ids.sort();
+        assert_eq!(ids, (1..=10).collect::<Vec<_>>());
+        Ok(())
+    }
+}
@@ -0,0 +1,727 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`BlobTable`]: tables whose large binary or string values are stored out
+//! of line in an object store, and only fetched when queries read them
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::catalog::{Session, TableProvider};
+use crate::datasource::provider_as_source;
+use crate::error::Result;
+use crate::logical_expr::async_udf::{AsyncScalarUDF, AsyncScalarUDFImpl};
+use crate::logical_expr::expr::{Alias, ScalarFunction};
+use crate::logical_expr::expr_rewriter::NamePreserver;
+use crate::logical_expr::ptr_eq::PtrEq;
+use crate::logical_expr::{
+    ColumnarValue, Expr, LogicalPlan, LogicalPlanBuilder, Projection, ScalarFunctionArgs,
+    ScalarUDF, ScalarUDFImpl, Signature, TableProviderFilterPushDown, TableType,
+    TypeSignature, Volatility, lit,
+};
+use crate::optimizer::{ApplyOrder, OptimizerConfig, OptimizerRule};
+use crate::physical_plan::ExecutionPlan;
+
+use arrow::array::{
+    ArrayRef, AsArray, BinaryArray, Int32Array, Int64Array, LargeBinaryArray,
+    LargeStringArray, StringArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, SchemaRef};
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{
+    Column, ScalarValue, TableReference, exec_datafusion_err, exec_err, plan_err,
+};
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{ObjectStore, ObjectStoreExt};
+
+/// The number of values of a batch fetched concurrently
+const BLOB_FETCH_CONCURRENCY: usize = 16;
+
+/// The maximum length in bytes of a UTF-8 encoded character
+const MAX_UTF8_CHAR_LEN: u64 = 4;
+
+/// A table whose blob columns contain the locations, in an object store, of
+/// large binary or string values, such as embedded documents, which are only
+/// fetched when a query reads them.
+///
+/// The table is planned as a projection of the wrapped table, replacing each
+/// blob column by a `blob_read(location)` call, so that the values are
+/// fetched after the other columns are filtered and limited, and not at all
+/// if the column is not read. With the [`BlobSlicePushdown`] optimizer rule,
+/// queries reading a prefix of a string blob, such as `substr(doc, 1, 100)`
+/// or `left(doc, 100)`, only fetch the bytes of the prefix, and
+/// `octet_length(doc)` only fetches the size of the values.
+///
+/// See also [`SessionContext::enable_blob_columns`], which installs the
+/// table and the rule.
+///
+/// [`SessionContext::enable_blob_columns`]: crate::execution::context::SessionContext::enable_blob_columns
+#[derive(Debug)]
+pub struct BlobTable {
+    inner: Arc<dyn TableProvider>,
+    plan: LogicalPlan,
+}
+
+impl BlobTable {
+    /// Wrap `inner`, the table `table_ref`, whose `blob_columns` contain
+    /// the locations of values in `store`, each read as values of the given
+    /// type: `Utf8`, `LargeUtf8`, `Binary` or `LargeBinary`.
+    ///
+    /// Returns an error if a blob column does not exist or does not contain
+    /// strings, or if a type is not supported.
+    pub fn try_new(
+        table_ref: impl Into<TableReference>,
+        inner: Arc<dyn TableProvider>,
+        store: Arc<dyn ObjectStore>,
+        blob_columns: Vec<(String, DataType)>,
+    ) -> Result<Self> {
+        let schema = inner.schema();
+        for (name, data_type) in &blob_columns {
+            let field = schema.field_with_name(name)?;
+            if !matches!(
+                field.data_type(),
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            ) {
+                return plan_err!(
+                    "Blob column {name} must contain the locations of the values, got type {}",
+                    field.data_type()
+                );
+            }
+            if !matches!(
+                data_type,
+                DataType::Utf8
+                    | DataType::LargeUtf8
+                    | DataType::Binary
+                    | DataType::LargeBinary
+            ) {
+                return plan_err!("Unsupported type {data_type} of blob column {name}");
+            }
+        }
+
+        let exprs = schema.fields().iter().map(move |field| {
+            let column = Expr::Column(Column::new_unqualified(field.name()));
+            match blob_columns.iter().find(|(name, _)| name == field.name()) {
+                Some((name, data_type)) => {
+                    let read = BlobReadFunc::new(Arc::clone(&store), data_type.clone());
+                    read.udf().call(vec![column]).alias(name)
+                }
+                None => column,
+            }
+        });
+        let plan = LogicalPlanBuilder::scan(
+            table_ref,
+            provider_as_source(Arc::clone(&inner)),
+            None,
+        )?
+        .project(exprs)?
+        .build()?;
+        Ok(Self { inner, plan })
+    }
+
+    /// Return the wrapped table
+    pub fn inner(&self) -> &Arc<dyn TableProvider> {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl TableProvider for BlobTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(self.plan.schema().inner())
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    fn get_logical_plan(&'_ self) -> Option<Cow<'_, LogicalPlan>> {
+        Some(Cow::Borrowed(&self.plan))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        // the filters are applied on the plan of the table
+        Ok(vec![TableProviderFilterPushDown::Exact; filters.len()])
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut plan = LogicalPlanBuilder::from(self.plan.clone());
+        if let Some(filter) = filters.iter().cloned().reduce(|acc, new| acc.and(new)) {
+            plan = plan.filter(filter)?;
+        }
+        if let Some(projection) = projection {
+            let schema = self.plan.schema();
+            plan = plan.project(
+                projection
+                    .iter()
+                    .map(|i| Expr::Column(Column::from(schema.qualified_field(*i)))),
+            )?;
+        }
+        if let Some(limit) = limit {
+            plan = plan.limit(0, Some(limit))?;
+        }
+        state.create_physical_plan(&plan.build()?).await
+    }
+}
+
+/// `blob_read(location [, max_bytes])`: fetches the value at `location` in
+/// the store, or only its first `max_bytes` bytes. A string prefix ending
+/// in the middle of a character is truncated to the previous character.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct BlobReadFunc {
+    store: PtrEq<Arc<dyn ObjectStore>>,
+    data_type: DataType,
+    signature: Signature,
+}
+
+impl BlobReadFunc {
+    fn new(store: Arc<dyn ObjectStore>, data_type: DataType) -> Self {
+        Self {
+            store: store.into(),
+            data_type,
+            signature: Signature::one_of(
+                vec![TypeSignature::Any(1), TypeSignature::Any(2)],
+                Volatility::Volatile,
+            ),
+        }
+    }
+
+    fn udf(self) -> ScalarUDF {
+        AsyncScalarUDF::new(Arc::new(self)).into_scalar_udf()
+    }
+
+    /// Returns the function if `func` is a `blob_read` function
+    fn downcast(func: &ScalarUDF) -> Option<&Self> {
+        let func =
+            (func.inner().as_ref() as &dyn Any).downcast_ref::<AsyncScalarUDF>()?;
+        (func.inner().as_ref() as &dyn Any).downcast_ref()
+    }
+
+    fn is_string(&self) -> bool {
+        matches!(self.data_type, DataType::Utf8 | DataType::LargeUtf8)
+    }
+
+    async fn fetch(&self, location: &str, max_bytes: Option<u64>) -> Result<Bytes> {
+        let location = Path::from(location);
+        let Some(max_bytes) = max_bytes else {
+            return Ok(self.store.get(&location).await?.bytes().await?);
+        };
+        let end = self.store.head(&location).await?.size.min(max_bytes);
+        if end == 0 {
+            return Ok(Bytes::new());
+        }
+        Ok(self.store.get_range(&location, 0..end).await?)
+    }
+}
+
+impl ScalarUDFImpl for BlobReadFunc {
+    fn name(&self) -> &str {
+        "blob_read"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.data_type.clone())
+    }
+
+    fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        exec_err!("blob_read can only be called asynchronously")
+    }
+}
+
+#[async_trait]
+impl AsyncScalarUDFImpl for BlobReadFunc {
+    async fn invoke_async_with_args(
+        &self,
+        args: ScalarFunctionArgs,
+    ) -> Result<ColumnarValue> {
+        let max_bytes = match args.args.get(1) {
+            None => None,
+            Some(ColumnarValue::Scalar(ScalarValue::Int64(Some(max_bytes)))) => {
+                Some(u64::try_from(*max_bytes).unwrap_or_default())
+            }
+            Some(_) => {
+                return exec_err!(
+                    "The maximum number of bytes of blob_read must be an Int64 literal"
+                );
+            }
+        };
+        let locations = locations(&args)?;
+        let values: Vec<Option<Bytes>> = futures::stream::iter(locations)
+            .map(|location| async move {
+                match location {
+                    Some(location) => self.fetch(&location, max_bytes).await.map(Some),
+                    None => Ok(None),
+                }
+            })
+            .buffered(BLOB_FETCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let strings = || {
+            values
+                .iter()
+                .map(|value| {
+                    value
+                        .as_ref()
+                        .map(|value| blob_to_str(value, max_bytes.is_some()))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let binaries = || values.iter().map(Option::as_deref);
+        let array: ArrayRef = match self.data_type {
+            DataType::Utf8 => Arc::new(StringArray::from(strings()?)),
+            DataType::LargeUtf8 => Arc::new(LargeStringArray::from(strings()?)),
+            DataType::Binary => Arc::new(binaries().collect::<BinaryArray>()),
+            DataType::LargeBinary => Arc::new(binaries().collect::<LargeBinaryArray>()),
+            _ => return exec_err!("Unsupported blob type {}", self.data_type),
+        };
+        Ok(ColumnarValue::Array(array))
+    }
+}
+
+/// Decodes a blob as UTF-8, where a `prefix` may end in the middle of a
+/// character
+fn blob_to_str(value: &[u8], prefix: bool) -> Result<&str> {
+    match std::str::from_utf8(value) {
+        Ok(value) => Ok(value),
+        Err(e) if prefix && e.error_len().is_none() => {
+            Ok(std::str::from_utf8(&value[..e.valid_up_to()]).unwrap_or_default())
+        }
+        Err(e) => exec_err!("Blob is not valid UTF-8: {e}"),
+    }
+}
+
+/// `blob_octet_length(location)`: the size of the value at `location` in
+/// the store, returned with the type of `octet_length` of the blobs.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct BlobOctetLengthFunc {
+    store: PtrEq<Arc<dyn ObjectStore>>,
+    data_type: DataType,
+    signature: Signature,
+}
+
+impl BlobOctetLengthFunc {
+    fn new(read: &BlobReadFunc) -> Self {
+        let data_type = match read.data_type {
+            DataType::LargeUtf8 | DataType::LargeBinary => DataType::Int64,
+            _ => DataType::Int32,
+        };
+        Self {
+            store: read.store.clone(),
+            data_type,
+            signature: Signature::any(1, Volatility::Volatile),
+        }
+    }
+}
+
+impl ScalarUDFImpl for BlobOctetLengthFunc {
+    fn name(&self) -> &str {
+        "blob_octet_length"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.data_type.clone())
+    }
+
+    fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        exec_err!("blob_octet_length can only be called asynchronously")
+    }
+}
+
+#[async_trait]
+impl AsyncScalarUDFImpl for BlobOctetLengthFunc {
+    async fn invoke_async_with_args(
+        &self,
+        args: ScalarFunctionArgs,
+    ) -> Result<ColumnarValue> {
+        let locations = locations(&args)?;
+        let sizes: Vec<Option<u64>> = futures::stream::iter(locations)
+            .map(|location| async move {
+                match location {
+                    Some(location) => {
+                        let meta = self.store.head(&Path::from(location)).await?;
+                        Ok::<_, crate::error::DataFusionError>(Some(meta.size))
+                    }
+                    None => Ok(None),
+                }
+            })
+            .buffered(BLOB_FETCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let array: ArrayRef = if self.data_type == DataType::Int64 {
+            Arc::new(
+                sizes
+                    .into_iter()
+                    .map(|size| size.map(i64::try_from).transpose())
+                    .collect::<Result<Int64Array, _>>()
+                    .map_err(|e| exec_datafusion_err!("Blob is too large: {e}"))?,
+            )
+        } else {
+            Arc::new(
+                sizes
+                    .into_iter()
+                    .map(|size| size.map(i32::try_from).transpose())
+                    .collect::<Result<Int32Array, _>>()
+                    .map_err(|e| exec_datafusion_err!("Blob is too large: {e}"))?,
+            )
+        };
+        Ok(ColumnarValue::Array(array))
+    }
+}
+
+/// The locations passed as the first argument of a blob function
+///
+/// The locations are owned so that the futures fetching them do not borrow
+/// the arguments.
+fn locations(args: &ScalarFunctionArgs) -> Result<Vec<Option<String>>> {
+    let locations = args.args[0].to_array(args.number_rows)?;
+    let locations = cast(&locations, &DataType::Utf8)?;
+    Ok(locations
+        .as_string::<i32>()
+        .iter()
+        .map(|location| location.map(str::to_string))
+        .collect())
+}
+
+/// Optimizer rule fetching only the bytes of the blobs of a [`BlobTable`]
+/// that projections read:
+///
+/// * `substr(doc, start, count)` and `left(doc, count)` fetch the bytes of
+///   the prefix of the string they return
+/// * `octet_length(doc)` only fetches the size of the value
+///
+/// The prefixes and sizes are computed in the projection of the
+/// [`BlobTable`] as additional columns, which replace the blob columns in
+/// the projections above it, so that the values are no longer fully fetched
+/// if they are not otherwise used.
+#[derive(Debug, Default)]
+pub struct BlobSlicePushdown {}
+
+impl BlobSlicePushdown {
+    /// Create a new [`BlobSlicePushdown`]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for BlobSlicePushdown {
+    fn name(&self) -> &str {
+        "blob_slice_pushdown"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Projection(projection) = &plan else {
+            return Ok(Transformed::no(plan));
+        };
+        let Some(blob_projection) = find_blob_projection(&projection.input) else {
+            return Ok(Transformed::no(plan));
+        };
+
+        let input_schema = projection.input.schema();
+        let mut slices = vec![];
+        let mut rewrite = |expr: Expr| -> Result<Transformed<Expr>> {
+            let Expr::ScalarFunction(ScalarFunction { func, args }) = &expr else {
+                return Ok(Transformed::no(expr));
+            };
+            let Some(Expr::Column(column)) = args.first() else {
+                return Ok(Transformed::no(expr));
+            };
+            // the columns passed through to the projection have the same
+            // index in the blob projection
+            let Ok(index) = input_schema.index_of_column(column) else {
+                return Ok(Transformed::no(expr));
+            };
+            let Some((read_func, read, location)) =
+                blob_read_call(&blob_projection.expr[index])
+            else {
+                return Ok(Transformed::no(expr));
+            };
+            let name = input_schema.field(index).name();
+            let qualifier = input_schema.qualified_field(index).0.cloned();
+
+            if is_octet_length(func) {
+                let octet_length =
+                    AsyncScalarUDF::new(Arc::new(BlobOctetLengthFunc::new(read)))
+                        .into_scalar_udf();
+                let slice_name = format!("{BLOB_SLICE_PREFIX}octet_length_{name}");
+                slices.push(octet_length.call(vec![location.clone()]).alias(&slice_name));
+                return Ok(Transformed::yes(Expr::Column(Column::new(
+                    qualifier, slice_name,
+                ))));
+            }
+            let Some(chars) = prefix_chars(func, &args[1..]).filter(|_| read.is_string())
+            else {
+                return Ok(Transformed::no(expr));
+            };
+            let max_bytes = chars.saturating_mul(MAX_UTF8_CHAR_LEN).min(i64::MAX as u64);
+            let slice_name = format!("{BLOB_SLICE_PREFIX}prefix_{max_bytes}_{name}");
+            slices.push(
+                Expr::ScalarFunction(ScalarFunction::new_udf(
+                    Arc::clone(read_func),
+                    vec![location.clone(), lit(max_bytes as i64)],
+                ))
+                .alias(&slice_name),
+            );
+            let mut args = args.clone();
+            args[0] = Expr::Column(Column::new(qualifier, slice_name));
+            Ok(Transformed::yes(Expr::ScalarFunction(
+                ScalarFunction::new_udf(Arc::clone(func), args),
+            )))
+        };
+
+        // Preserve expression names to avoid changing the schema of the plan.
+        let name_preserver = NamePreserver::new(&plan);
+        let exprs = projection
+            .expr
+            .iter()
+            .map(|expr| {
+                let name = name_preserver.save(expr);
+                Ok(expr
+                    .clone()
+                    .transform_up(&mut rewrite)?
+                    .update_data(|expr| name.restore(expr)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if slices.is_empty() {
+            return Ok(Transformed::no(plan));
+        }
+
+        let exprs = exprs.into_iter().map(|expr| expr.data).collect();
+        let input = add_blob_slices(&projection.input, slices)?;
+        Ok(Transformed::yes(LogicalPlan::Projection(
+            Projection::try_new(exprs, Arc::new(input))?,
+        )))
+    }
+}
+
+/// The prefix of the names of the columns added by [`BlobSlicePushdown`]
+const BLOB_SLICE_PREFIX: &str = "__blob_";
+
+/// Returns the projection of a [`BlobTable`] below `plan`, looking through
+/// the nodes passing all the columns of their input, in the same order
+fn find_blob_projection(plan: &LogicalPlan) -> Option<&Projection> {
+    match plan {
+        LogicalPlan::Projection(projection)
+            if projection
+                .expr
+                .iter()
+                .any(|expr| blob_read_call(expr).is_some()) =>
+        {
+            Some(projection)
+        }
+        LogicalPlan::Filter(_)
+        | LogicalPlan::Sort(_)
+        | LogicalPlan::Limit(_)
+        | LogicalPlan::SubqueryAlias(_) => find_blob_projection(plan.inputs()[0]),
+        _ => None,
+    }
+}
+
+/// Adds the `slices` to the projection returned by [`find_blob_projection`],
+/// and the columns to the nodes above it
+fn add_blob_slices(plan: &LogicalPlan, slices: Vec<Expr>) -> Result<LogicalPlan> {
+    match plan {
+        LogicalPlan::Projection(projection) => {
+            let mut exprs = projection.expr.clone();
+            for slice in slices {
+                if !exprs.contains(&slice) {
+                    exprs.push(slice);
+                }
+            }
+            Ok(LogicalPlan::Projection(Projection::try_new(
+                exprs,
+                Arc::clone(&projection.input),
+            )?))
+        }
+        _ => {
+            let input = add_blob_slices(plan.inputs()[0], slices)?;
+            plan.with_new_exprs(plan.expressions(), vec![input])
+        }
+    }
+}
+
+/// Returns the function, its implementation and the location argument if
+/// `expr` reads a whole blob, as in the projection of a [`BlobTable`]
+fn blob_read_call(expr: &Expr) -> Option<(&Arc<ScalarUDF>, &BlobReadFunc, &Expr)> {
+    let Expr::Alias(Alias { expr, .. }) = expr else {
+        return None;
+    };
+    let Expr::ScalarFunction(ScalarFunction { func, args }) = expr.as_ref() else {
+        return None;
+    };
+    let [location] = args.as_slice() else {
+        return None;
+    };
+    Some((func, BlobReadFunc::downcast(func)?, location))
+}
+
+/// Returns true if `func` is the `octet_length` function
+fn is_octet_length(func: &ScalarUDF) -> bool {
+    #[cfg(feature = "string_expressions")]
+    {
+        use crate::functions::string::octet_length::OctetLengthFunc;
+        (func.inner().as_ref() as &dyn Any).is::<OctetLengthFunc>()
+    }
+    #[cfg(not(feature = "string_expressions"))]
+    {
+        let _ = func;
+        false
+    }
+}
+
+/// The number of leading characters of the string read by `func` when
+/// called with the string and `args`, if `func` returns a prefix of its
+/// first argument
+fn prefix_chars(func: &ScalarUDF, args: &[Expr]) -> Option<u64> {
+    #[cfg(feature = "unicode_expressions")]
+    {
+        use crate::functions::unicode::{left::LeftFunc, substr::SubstrFunc};
+
+        let int_literal = |expr: &Expr| match expr {
+            Expr::Literal(ScalarValue::Int64(Some(value)), _) => Some(*value),
+            _ => None,
+        };
+        let func = func.inner().as_ref() as &dyn Any;
+        let chars = match args {
+            // `substr(s, start, count)` returns characters before `start + count`
+            [start, count] if func.is::<SubstrFunc>() => int_literal(start)?
+                .saturating_add(int_literal(count)?)
+                .saturating_sub(1),
+            [count] if func.is::<LeftFunc>() => int_literal(count).filter(|c| *c >= 0)?,
+            _ => return None,
+        };
+        Some(u64::try_from(chars).unwrap_or_default())
+    }
+    #[cfg(not(feature = "unicode_expressions"))]
+    {
+        let _ = (func, args);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::MemTable;
+    use crate::prelude::SessionContext;
+    use arrow::array::RecordBatch;
+    use arrow::datatypes::{Field, Schema};
+    use arrow::util::pretty::pretty_format_batches;
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn read_blobs_lazily() -> Result<()> {
+        let store = Arc::new(InMemory::new());
+        store
+            .put(&Path::from("docs/a.txt"), "été à la plage".into())
+            .await?;
+        store.put(&Path::from("docs/b.txt"), "hello".into()).await?;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("doc", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![
+                    Some("docs/a.txt"),
+                    Some("docs/b.txt"),
+                    None,
+                ])),
+            ],
+        )?;
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))?;
+        ctx.enable_blob_columns("t", store, vec![("doc".to_string(), DataType::Utf8)])
+            .await?;
+
+        let results = ctx
+            .sql("SELECT doc FROM t WHERE id = 1")
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(
+            [
+                "+----------------+",
+                "| doc            |",
+                "+----------------+",
+                "| été à la plage |",
+                "+----------------+",
+            ],
+            &results
+        );
+
+        let sql = "SELECT id, substr(doc, 1, 3) AS prefix, left(doc, 1) AS first, \
+                   octet_length(doc) AS size FROM t ORDER BY id";
+        let results = ctx.sql(sql).await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+----+--------+-------+------+",
+                "| id | prefix | first | size |",
+                "+----+--------+-------+------+",
+                "| 1  | été    | é     | 17   |",
+                "| 2  | hel    | h     | 5    |",
+                "| 3  |        |       |      |",
+                "+----+--------+-------+------+",
+            ],
+            &results
+        );
+
+        // only the prefixes and the sizes of the blobs are fetched
+        let explain = ctx.sql(&format!("EXPLAIN {sql}")).await?.collect().await?;
+        let plan = pretty_format_batches(&explain)?.to_string();
+        assert!(plan.contains("blob_read(t.doc, Int64(12))"), "{plan}");
+        assert!(plan.contains("blob_read(t.doc, Int64(4))"), "{plan}");
+        assert!(plan.contains("blob_octet_length(t.doc)"), "{plan}");
+        assert!(!plan.contains("blob_read(t.doc) AS doc"), "{plan}");
+        Ok(())
+    }
+}
@@ -33,22 +33,58 @@ use datafusion_session::SessionStore;
 use async_trait::async_trait;
 
 /// [DynamicListTableFactory] is a factory that can create a [ListingTable] from the given url.
+///
+/// The file format, partitioning and schema of the table are inferred from
+/// the files at the url, so a directory laid out as
+/// `path/to/dir/year=2024/part-0.parquet` can be queried directly with
+/// `SELECT * FROM 'path/to/dir'`.
 #[derive(Default, Debug)]
 pub struct DynamicListTableFactory {
     /// The session store that contains the current session.
     session_store: SessionStore,
+    /// If not empty, only urls under one of these roots are accessible
+    allowed_roots: Vec<ListingTableUrl>,
 }
 
 impl DynamicListTableFactory {
     /// Create a new [DynamicListTableFactory] with the given state store.
     pub fn new(session_store: SessionStore) -> Self {
-        Self { session_store }
+        Self {
+            session_store,
+            allowed_roots: vec![],
+        }
+    }
+
+    /// Only create tables for urls under one of `roots`, such as
+    /// `/data/warehouse` or `s3://bucket/prefix`.
+    ///
+    /// Urls outside of the roots are treated as if they did not exist. Roots
+    /// are compared with the url after it is parsed, so relative paths and
+    /// `..` segments can not be used to escape them. Symbolic links are not
+    /// resolved. If no roots are set, all urls are accessible.
+    pub fn with_allowed_roots(mut self, roots: Vec<ListingTableUrl>) -> Self {
+        self.allowed_roots = roots;
+        self
     }
 
     /// Get the session store.
     pub fn session_store(&self) -> &SessionStore {
         &self.session_store
     }
+
+    /// Return the roots set by [`Self::with_allowed_roots`]
+    pub fn allowed_roots(&self) -> &[ListingTableUrl] {
+        &self.allowed_roots
+    }
+
+    /// Return true if tables can be created for `url`
+    fn is_allowed(&self, url: &ListingTableUrl) -> bool {
+        self.allowed_roots.is_empty()
+            || self.allowed_roots.iter().any(|root| {
+                root.object_store() == url.object_store()
+                    && url.prefix().prefix_match(root.prefix()).is_some()
+            })
+    }
 }
 
 #[async_trait]
@@ -57,6 +93,10 @@ impl UrlTableFactory for DynamicListTableFactory {
         let Ok(table_url) = ListingTableUrl::parse(url) else {
             return Ok(None);
         };
+        if !self.is_allowed(&table_url) {
+            log::debug!("Not creating a table for '{url}' outside of the allowed roots");
+            return Ok(None);
+        }
 
         let state = &self
             .session_store()
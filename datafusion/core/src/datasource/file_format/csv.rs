@@ -56,7 +56,7 @@ mod tests {
     use async_trait::async_trait;
     use bytes::Bytes;
     use chrono::DateTime;
-    use datafusion_common::parsers::CompressionTypeVariant;
+    use datafusion_common::parsers::{CompressionTypeVariant, CsvLineTerminator};
     use futures::StreamExt;
     use futures::stream::BoxStream;
     use insta::assert_snapshot;
@@ -689,6 +689,27 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_write_csv_crlf_line_terminator() -> Result<()> {
+        let ctx = SessionContext::new();
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("crlf.csv");
+        let path = path.to_string_lossy().to_string();
+        let df = ctx
+            .sql("SELECT CAST(1 AS BIGINT) AS a, 'x' || chr(10) || 'y' AS b")
+            .await?;
+        let options = CsvOptions::default().with_line_terminator(CsvLineTerminator::Crlf);
+        df.write_csv(
+            &path,
+            crate::dataframe::DataFrameWriteOptions::new(),
+            Some(options),
+        )
+        .await?;
+        // the line break within the quoted value is kept
+        assert_eq!(std::fs::read_to_string(&path)?, "a,b\r\n1,\"x\ny\"\r\n");
+        Ok(())
+    }
+
     /// Explain the `sql` query under `ctx` to make sure the underlying csv scan is parallelized
     /// e.g. "DataSourceExec: file_groups={2 groups:" in plan means 2 DataSourceExec runs concurrently
     async fn count_query_csv_partitions(
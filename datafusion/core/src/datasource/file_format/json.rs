@@ -48,6 +48,7 @@ mod tests {
 
     use crate::execution::options::JsonReadOptions;
     use datafusion_common::Result;
+    use datafusion_common::config::JsonOptions;
     use datafusion_datasource::file_compression_type::FileCompressionType;
     use futures::StreamExt;
     use insta::assert_snapshot;
@@ -433,6 +434,36 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_write_json_explicit_nulls() -> Result<()> {
+        let ctx = SessionContext::new();
+        let tmp_dir = tempfile::TempDir::new()?;
+        let df = ctx
+            .sql("SELECT CAST(1 AS BIGINT) AS id, CAST(NULL AS VARCHAR) AS name")
+            .await?;
+
+        for (explicit_nulls, expected) in [
+            (None, "{\"id\":1}\n"),
+            (Some(true), "{\"id\":1,\"name\":null}\n"),
+        ] {
+            let path = tmp_dir.path().join(format!("{explicit_nulls:?}.json"));
+            let path = path.to_string_lossy().to_string();
+            let options = JsonOptions {
+                explicit_nulls,
+                ..Default::default()
+            };
+            df.clone()
+                .write_json(
+                    &path,
+                    crate::dataframe::DataFrameWriteOptions::new(),
+                    Some(options),
+                )
+                .await?;
+            assert_eq!(std::fs::read_to_string(&path)?, expected);
+        }
+        Ok(())
+    }
+
     // ==================== JSON Array Format Tests ====================
 
     #[tokio::test]
@@ -20,6 +20,7 @@
 
 pub mod arrow;
 pub mod csv;
+#[cfg(feature = "json")]
 pub mod json;
 
 #[cfg(feature = "avro")]
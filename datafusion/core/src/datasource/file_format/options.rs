@@ -41,6 +41,7 @@ use datafusion_common::{
 };
 
 use async_trait::async_trait;
+#[cfg(feature = "json")]
 use datafusion_datasource_json::file_format::JsonFormat;
 use datafusion_expr::SortExpr;
 
@@ -699,6 +700,7 @@ impl ReadOptions<'_> for ParquetReadOptions<'_> {
     }
 }
 
+#[cfg(feature = "json")]
 #[async_trait]
 impl ReadOptions<'_> for JsonReadOptions<'_> {
     fn to_listing_options(
@@ -169,6 +169,7 @@ mod tests {
         ParquetOffsetIndex,
     };
     use parquet::file::page_index::column_index::ColumnIndexMetaData;
+    use parquet::file::statistics::Statistics;
     use tokio::fs::File;
 
     enum ForceViews {
@@ -1546,6 +1547,7 @@ mod tests {
             keep_partition_by_columns: false,
             file_extension: "parquet".into(),
             file_output_mode: FileOutputMode::Automatic,
+            statistics_cache: None,
         };
         let parquet_sink = Arc::new(ParquetSink::new(
             file_sink_config,
@@ -1638,6 +1640,7 @@ mod tests {
             keep_partition_by_columns: false,
             file_extension: "parquet".into(),
             file_output_mode: FileOutputMode::Automatic,
+            statistics_cache: None,
         };
         let parquet_sink = Arc::new(ParquetSink::new(
             file_sink_config,
@@ -1707,6 +1710,184 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn parquet_sink_write_with_memory_limit() -> Result<()> {
+        let field_a = Field::new("a", DataType::Utf8, false);
+        let field_b = Field::new("b", DataType::Utf8, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+        let object_store_url = ObjectStoreUrl::local_filesystem();
+
+        let file_sink_config = FileSinkConfig {
+            original_url: String::default(),
+            object_store_url: object_store_url.clone(),
+            file_group: FileGroup::new(vec![PartitionedFile::new("/tmp".to_string(), 1)]),
+            table_paths: vec![ListingTableUrl::parse("file:///")?],
+            output_schema: schema.clone(),
+            table_partition_cols: vec![],
+            insert_op: InsertOp::Overwrite,
+            keep_partition_by_columns: false,
+            file_extension: "parquet".into(),
+            file_output_mode: FileOutputMode::Automatic,
+            statistics_cache: None,
+        };
+        let parquet_sink = Arc::new(ParquetSink::new(
+            file_sink_config,
+            TableParquetOptions::default(),
+        ));
+
+        let col_a: ArrayRef = Arc::new(StringArray::from(vec!["foo", "bar"]));
+        let col_b: ArrayRef = Arc::new(StringArray::from(vec!["baz", "baz"]));
+        let batch = RecordBatch::try_from_iter(vec![("a", col_a), ("b", col_b)])?;
+
+        // a limit smaller than a single batch closes the row groups early
+        let task_context = build_ctx(object_store_url.as_ref());
+        let mut session = task_context.session_config().clone();
+        session.options_mut().execution.parquet_writer_memory_limit = Some(1);
+        // write all batches to a single file
+        session
+            .options_mut()
+            .execution
+            .minimum_parallel_output_files = 1;
+        let task_context = Arc::new(
+            TaskContext::default()
+                .with_session_config(session)
+                .with_runtime(task_context.runtime_env()),
+        );
+
+        FileSink::write_all(
+            parquet_sink.as_ref(),
+            Box::pin(RecordBatchStreamAdapter::new(
+                schema,
+                bounded_stream(batch, 10),
+            )),
+            &task_context,
+        )
+        .await?;
+
+        let (_, parquet_meta_data) = get_written(parquet_sink)?;
+        assert_eq!(parquet_meta_data.file_metadata().num_rows(), 20);
+        assert!(
+            parquet_meta_data.num_row_groups() > 1,
+            "should close row groups early once the limit is exceeded"
+        );
+        assert_eq!(
+            task_context.memory_pool().reserved(),
+            0,
+            "no leaking byte reservation"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parquet_sink_parallel_write_multiple_row_groups() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Utf8, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+        let object_store_url = ObjectStoreUrl::local_filesystem();
+
+        let file_sink_config = FileSinkConfig {
+            original_url: String::default(),
+            object_store_url: object_store_url.clone(),
+            file_group: FileGroup::new(vec![PartitionedFile::new("/tmp".to_string(), 1)]),
+            table_paths: vec![ListingTableUrl::parse("file:///")?],
+            output_schema: schema.clone(),
+            table_partition_cols: vec![],
+            insert_op: InsertOp::Overwrite,
+            keep_partition_by_columns: false,
+            file_extension: "parquet".into(),
+            file_output_mode: FileOutputMode::Automatic,
+            statistics_cache: None,
+        };
+        let parquet_sink = Arc::new(ParquetSink::new(
+            file_sink_config,
+            TableParquetOptions {
+                global: ParquetOptions {
+                    allow_single_file_parallelism: true,
+                    max_row_group_size: 5,
+                    maximum_parallel_row_group_writers: 2,
+                    maximum_buffered_record_batches_per_stream: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+
+        // 4 batches of 6 rows, `a` counts up from 0 across the batches
+        let batches = (0..4)
+            .map(|i| {
+                let col_a: ArrayRef =
+                    Arc::new(Int64Array::from_iter_values(i * 6..(i + 1) * 6));
+                let col_b: ArrayRef = Arc::new(StringArray::from_iter_values(
+                    (i * 6..(i + 1) * 6).map(|v| format!("row {v}")),
+                ));
+                RecordBatch::try_from_iter(vec![("a", col_a), ("b", col_b)])
+                    .map_err(Into::into)
+            })
+            .collect::<Vec<Result<_>>>();
+
+        let task_context = build_ctx(object_store_url.as_ref());
+        let mut session = task_context.session_config().clone();
+        // write all batches to a single file
+        session
+            .options_mut()
+            .execution
+            .minimum_parallel_output_files = 1;
+        let task_context = Arc::new(
+            TaskContext::default()
+                .with_session_config(session)
+                .with_runtime(task_context.runtime_env()),
+        );
+
+        FileSink::write_all(
+            parquet_sink.as_ref(),
+            Box::pin(RecordBatchStreamAdapter::new(
+                schema,
+                futures::stream::iter(batches),
+            )),
+            &task_context,
+        )
+        .await?;
+
+        let (_, parquet_meta_data) = get_written(parquet_sink)?;
+        assert_eq!(parquet_meta_data.file_metadata().num_rows(), 24);
+
+        // the row groups are split at `max_row_group_size`, in input order
+        let row_groups = parquet_meta_data.row_groups();
+        assert_eq!(
+            row_groups.iter().map(|rg| rg.num_rows()).collect::<Vec<_>>(),
+            vec![5, 5, 5, 5, 4]
+        );
+        for (i, rg) in row_groups.iter().enumerate() {
+            assert_eq!(rg.num_columns(), 2);
+            let paths = rg
+                .columns()
+                .iter()
+                .map(|c| c.column_path().string())
+                .collect::<Vec<_>>();
+            assert_eq!(paths, vec!["a", "b"]);
+            for column in rg.columns() {
+                assert_eq!(column.num_values(), rg.num_rows());
+                assert!(column.compressed_size() > 0);
+            }
+
+            let Some(Statistics::Int64(stats)) = rg.column(0).statistics() else {
+                panic!("expected Int64 statistics for column a");
+            };
+            let min = i as i64 * 5;
+            assert_eq!(stats.min_opt(), Some(&min));
+            assert_eq!(stats.max_opt(), Some(&(min + rg.num_rows() - 1)));
+        }
+
+        assert_eq!(
+            task_context.memory_pool().reserved(),
+            0,
+            "no leaking byte reservation"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_sink_write_memory_reservation() -> Result<()> {
         async fn test_memory_reservation(global: ParquetOptions) -> Result<()> {
@@ -1729,6 +1910,7 @@ mod tests {
                 keep_partition_by_columns: false,
                 file_extension: "parquet".into(),
                 file_output_mode: FileOutputMode::Automatic,
+                statistics_cache: None,
             };
             let parquet_sink = Arc::new(ParquetSink::new(
                 file_sink_config,
@@ -18,11 +18,17 @@
 //! A table that uses the `ObjectStore` listing capability
 //! to get the list of files to process.
 
+mod schema_report;
 mod table;
+pub use datafusion_catalog_listing::changes;
 pub use datafusion_catalog_listing::helpers;
-pub use datafusion_catalog_listing::{ListingOptions, ListingTable, ListingTableConfig};
+pub use datafusion_catalog_listing::{
+    FileManifest, FileOrdering, ListingOptions, ListingTable, ListingTableConfig,
+    MetadataFetchProgress, SchemaDifference, SchemaDifferenceKind, merge_evolved_schemas,
+};
 // Keep for backwards compatibility until removed
 #[expect(deprecated)]
 pub use datafusion_datasource::PartitionedFileStream;
 pub use datafusion_datasource::{FileRange, ListingTableUrl, PartitionedFile};
+pub use schema_report::SchemaReportFunc;
 pub use table::ListingTableConfigExt;
@@ -0,0 +1,157 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The `schema_report` table function
+
+use std::sync::{Arc, LazyLock};
+
+use crate::execution::SessionState;
+use arrow::array::{RecordBatch, StringBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion_catalog::{TableFunctionArgs, TableFunctionImpl, TableProvider};
+use datafusion_catalog_listing::ListingTable;
+use datafusion_catalog_listing::changes::ChangeTrackingTable;
+use datafusion_common::{
+    Result, ScalarValue, TableReference, internal_datafusion_err, plan_err,
+};
+use datafusion_datasource::memory::MemorySourceConfig;
+use datafusion_expr::{Expr, TableType};
+use datafusion_physical_plan::ExecutionPlan;
+use datafusion_session::Session;
+
+/// The schema of the rows returned by `schema_report`
+static SCHEMA_REPORT_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("file", DataType::Utf8, false),
+        Field::new("column_name", DataType::Utf8, false),
+        Field::new("difference", DataType::Utf8, false),
+        Field::new("table_type", DataType::Utf8, true),
+        Field::new("file_type", DataType::Utf8, true),
+    ]))
+});
+
+/// The `schema_report('table')` table function, which lists how the schema
+/// of each file of a [`ListingTable`] differs from the schema of the table,
+/// see [`ListingTable::schema_differences`].
+///
+/// Each row is a column that is `missing` from a file, an `extra` column of
+/// a file, or a column with a different type in a file (`type_change`),
+/// together with the type of the column in the table and in the file.
+#[derive(Debug, Default)]
+pub struct SchemaReportFunc {}
+
+impl TableFunctionImpl for SchemaReportFunc {
+    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
+        let table_ref = match args.exprs() {
+            [Expr::Literal(ScalarValue::Utf8(Some(name)), _)] => {
+                TableReference::from(name.as_str())
+            }
+            _ => return plan_err!("schema_report requires a table name as its input"),
+        };
+        Ok(Arc::new(SchemaReportTable { table_ref }))
+    }
+}
+
+/// The rows returned by `schema_report`, computed when the table is scanned
+#[derive(Debug)]
+struct SchemaReportTable {
+    table_ref: TableReference,
+}
+
+impl SchemaReportTable {
+    /// Find the listing table the report is about
+    async fn listing_table(&self, state: &SessionState) -> Result<ListingTable> {
+        let schema = state.schema_for_ref(self.table_ref.clone())?;
+        let Some(table) = schema.table(self.table_ref.table()).await? else {
+            return plan_err!("No table named '{}'", self.table_ref);
+        };
+        if let Some(table) = table.downcast_ref::<ListingTable>() {
+            Ok(table.clone())
+        } else if let Some(table) = table.downcast_ref::<ChangeTrackingTable>() {
+            Ok(table.table().as_ref().clone())
+        } else {
+            plan_err!(
+                "schema_report requires a listing table, but '{}' is not",
+                self.table_ref
+            )
+        }
+    }
+
+    async fn report(&self, state: &SessionState) -> Result<RecordBatch> {
+        let differences = self
+            .listing_table(state)
+            .await?
+            .schema_differences(state)
+            .await?;
+
+        let mut files = StringBuilder::new();
+        let mut columns = StringBuilder::new();
+        let mut kinds = StringBuilder::new();
+        let mut table_types = StringBuilder::new();
+        let mut file_types = StringBuilder::new();
+        for difference in differences {
+            files.append_value(difference.file.as_ref());
+            columns.append_value(&difference.column);
+            kinds.append_value(difference.kind.to_string());
+            table_types.append_option(difference.table_type.map(|t| t.to_string()));
+            file_types.append_option(difference.file_type.map(|t| t.to_string()));
+        }
+        Ok(RecordBatch::try_new(
+            Arc::clone(&SCHEMA_REPORT_SCHEMA),
+            vec![
+                Arc::new(files.finish()),
+                Arc::new(columns.finish()),
+                Arc::new(kinds.finish()),
+                Arc::new(table_types.finish()),
+                Arc::new(file_types.finish()),
+            ],
+        )?)
+    }
+}
+
+#[async_trait]
+impl TableProvider for SchemaReportTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&SCHEMA_REPORT_SCHEMA)
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let state = state
+            .as_any()
+            .downcast_ref::<SessionState>()
+            .ok_or_else(|| {
+                internal_datafusion_err!("schema_report requires a SessionState")
+            })?;
+        let batch = self.report(state).await?;
+        Ok(MemorySourceConfig::try_new_exec(
+            &[vec![batch]],
+            self.schema(),
+            projection.cloned(),
+        )?)
+    }
+}
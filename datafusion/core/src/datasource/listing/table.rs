@@ -128,26 +128,30 @@ mod tests {
     use arrow_schema::{DataType, Field, Schema, SchemaRef};
     use datafusion_catalog::TableProvider;
     use datafusion_catalog_listing::{
-        ListingOptions, ListingTable, ListingTableConfig, SchemaSource,
+        FileManifest, FileOrdering, ListingOptions, ListingTable, ListingTableConfig,
+        MetadataFetchProgress, SchemaSource,
     };
     use datafusion_common::{
-        DataFusionError, Result, ScalarValue, assert_contains,
+        DataFusionError, Result, ScalarValue, Statistics, assert_contains,
+        assert_not_contains,
         stats::Precision,
         test_util::{batches_to_string, datafusion_test_data},
     };
-    use datafusion_datasource::ListingTableUrl;
     use datafusion_datasource::file_compression_type::FileCompressionType;
     use datafusion_datasource::file_format::FileFormat;
+    use datafusion_datasource::metadata_column::FileMetadataColumn;
+    use datafusion_datasource::{ListingTableUrl, PartitionedFile};
     use datafusion_expr::dml::InsertOp;
     use datafusion_expr::{BinaryExpr, LogicalPlanBuilder, Operator};
     use datafusion_physical_expr::PhysicalSortExpr;
     use datafusion_physical_expr::expressions::binary;
     use datafusion_physical_expr_common::sort_expr::LexOrdering;
     use datafusion_physical_plan::empty::EmptyExec;
-    use datafusion_physical_plan::{ExecutionPlanProperties, collect};
+    use datafusion_physical_plan::{ExecutionPlanProperties, collect, displayable};
+    use object_store::ObjectMeta;
     use std::collections::HashMap;
     use std::io::Write;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use tempfile::TempDir;
     use url::Url;
 
@@ -258,6 +262,345 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn report_metadata_fetch_progress() -> Result<()> {
+        #[derive(Debug, Default)]
+        struct RecordingProgress(Mutex<Vec<(String, usize)>>);
+
+        impl MetadataFetchProgress for RecordingProgress {
+            fn file_fetched(&self, file: &ObjectMeta, fetched: usize) {
+                let name = file.location.filename().unwrap().to_string();
+                self.0.lock().unwrap().push((name, fetched));
+            }
+        }
+
+        let ctx = SessionContext::new_with_config(
+            SessionConfig::new().with_collect_statistics(true),
+        );
+        let testdata = crate::test_util::parquet_test_data();
+        let table_path =
+            ListingTableUrl::parse(format!("{testdata}/alltypes_plain.parquet"))?;
+        let config = ListingTableConfig::new(table_path)
+            .infer(&ctx.state())
+            .await?;
+        let progress = Arc::new(RecordingProgress::default());
+        let table = ListingTable::try_new(config)?
+            .with_metadata_fetch_progress(Arc::clone(&progress) as _);
+
+        table.scan(&ctx.state(), None, &[], None).await?;
+        assert_eq!(
+            *progress.0.lock().unwrap(),
+            vec![("alltypes_plain.parquet".to_string(), 1)]
+        );
+
+        // the statistics are cached, so the metadata is not fetched again
+        table.scan(&ctx.state(), None, &[], None).await?;
+        assert_eq!(progress.0.lock().unwrap().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_metadata_columns() -> Result<()> {
+        let ctx = SessionContext::new();
+        let testdata = datafusion_test_data();
+        let filename = format!("{testdata}/aggregate_simple.csv");
+        let file_size = std::fs::metadata(&filename)?.len();
+        let table_path = ListingTableUrl::parse(filename)?;
+
+        let options =
+            ListingOptions::new(Arc::new(CsvFormat::default())).with_metadata_columns(
+                vec![FileMetadataColumn::Location, FileMetadataColumn::Size],
+            );
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .infer_schema(&ctx.state())
+            .await?;
+        let table = ListingTable::try_new(config)?;
+        assert_eq!(table.metadata_columns().unwrap().fields().len(), 2);
+        ctx.register_table("t", Arc::new(table))?;
+
+        // metadata columns are not part of `SELECT *`
+        let df = ctx.sql("SELECT * FROM t").await?;
+        assert_eq!(df.schema().fields().len(), 3);
+
+        // but can be referenced by name
+        let batches = ctx
+            .sql(&format!(
+                "SELECT count(*) AS n FROM t \
+                 WHERE _file LIKE '%aggregate_simple.csv' AND _size = {file_size}"
+            ))
+            .await?
+            .collect()
+            .await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +----+
+        | n  |
+        +----+
+        | 15 |
+        +----+
+        ");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_file_row_numbers() -> Result<()> {
+        // the rows of the file are read in several batches
+        let ctx =
+            SessionContext::new_with_config(SessionConfig::new().with_batch_size(4));
+        let testdata = datafusion_test_data();
+        let table_path =
+            ListingTableUrl::parse(format!("{testdata}/aggregate_simple.csv"))?;
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_metadata_columns(vec![FileMetadataColumn::RowNumber]);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .infer_schema(&ctx.state())
+            .await?;
+        ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        let batches = ctx
+            .sql(
+                "SELECT _file_row_number, c3 FROM t \
+                 WHERE _file_row_number IN (0, 3, 6, 14) ORDER BY _file_row_number",
+            )
+            .await?
+            .collect()
+            .await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +------------------+-------+
+        | _file_row_number | c3    |
+        +------------------+-------+
+        | 0                | true  |
+        | 3                | true  |
+        | 6                | false |
+        | 14               | true  |
+        +------------------+-------+
+        ");
+
+        // the statistics of the row numbers are unknown, so they are computed
+        let batches = ctx
+            .sql("SELECT min(_file_row_number), max(_file_row_number) FROM t")
+            .await?
+            .collect()
+            .await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +-------------------------+-------------------------+
+        | min(t._file_row_number) | max(t._file_row_number) |
+        +-------------------------+-------------------------+
+        | 0                       | 14                      |
+        +-------------------------+-------------------------+
+        ");
+        Ok(())
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn read_file_row_numbers_of_parquet_files() -> Result<()> {
+        use crate::datasource::file_format::parquet::ParquetFormat;
+
+        let ctx = SessionContext::new();
+        let testdata = crate::test_util::parquet_test_data();
+        let table_path =
+            ListingTableUrl::parse(format!("{testdata}/alltypes_plain.parquet"))?;
+        let options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+            .with_metadata_columns(vec![FileMetadataColumn::RowNumber]);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .infer_schema(&ctx.state())
+            .await?;
+        ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        // scans not reading the row numbers are not affected
+        let batches = ctx.sql("SELECT id FROM t").await?.collect().await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 8);
+
+        let err = ctx
+            .sql("SELECT id, _file_row_number FROM t")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Reading the row numbers of Parquet files is not supported"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scan_files_declared_ordered() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        for (name, rows) in [
+            ("2024-01-01.csv", "2024-01-01,1\n2024-01-02,2\n"),
+            ("2024-01-03.csv", "2024-01-03,3\n2024-01-04,4\n"),
+            ("2024-01-05.csv", "2024-01-05,5\n"),
+        ] {
+            std::fs::write(tmp_dir.path().join(name), format!("d,v\n{rows}"))?;
+        }
+
+        let config = SessionConfig::new()
+            .with_target_partitions(2)
+            .with_repartition_file_scans(false);
+        let ctx = SessionContext::new_with_config(config);
+        let table_path =
+            ListingTableUrl::parse(format!("{}/", tmp_dir.path().to_str().unwrap()))?;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("d", DataType::Date32, false),
+            Field::new("v", DataType::Int32, false),
+        ]));
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_target_partitions(2)
+            .with_file_ordering(
+                FileOrdering::new("d").with_first_value_in_file_name(true),
+            );
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(schema);
+        ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        // the two files of the first partition are read in order, so the
+        // partitions are merged instead of sorted
+        let df = ctx.sql("SELECT d, v FROM t ORDER BY d").await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        assert_contains!(&plan, "SortPreservingMergeExec");
+        assert_not_contains!(&plan, "SortExec");
+        insta::assert_snapshot!(batches_to_string(&df.collect().await?), @r"
+        +------------+---+
+        | d          | v |
+        +------------+---+
+        | 2024-01-01 | 1 |
+        | 2024-01-02 | 2 |
+        | 2024-01-03 | 3 |
+        | 2024-01-04 | 4 |
+        | 2024-01-05 | 5 |
+        +------------+---+
+        ");
+
+        // the values of the first file are at most 2024-01-03, the first value
+        // of the next file, so it is kept for an inclusive lower bound
+        let df = ctx
+            .sql("SELECT d, v FROM t WHERE d >= '2024-01-03' ORDER BY d")
+            .await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        assert_contains!(&plan, "2024-01-01.csv");
+        assert_contains!(&plan, "2024-01-03.csv");
+        insta::assert_snapshot!(batches_to_string(&df.collect().await?), @r"
+        +------------+---+
+        | d          | v |
+        +------------+---+
+        | 2024-01-03 | 3 |
+        | 2024-01-04 | 4 |
+        | 2024-01-05 | 5 |
+        +------------+---+
+        ");
+
+        // and pruned for an exclusive one
+        let df = ctx
+            .sql("SELECT d, v FROM t WHERE d > '2024-01-03' ORDER BY d")
+            .await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        assert_not_contains!(&plan, "2024-01-01.csv");
+        assert_contains!(&plan, "2024-01-03.csv");
+        insta::assert_snapshot!(batches_to_string(&df.collect().await?), @r"
+        +------------+---+
+        | d          | v |
+        +------------+---+
+        | 2024-01-04 | 4 |
+        | 2024-01-05 | 5 |
+        +------------+---+
+        ");
+
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_ordering(FileOrdering::new("x"));
+        let config = ListingTableConfig::new(ListingTableUrl::parse("file:///t/")?)
+            .with_listing_options(options)
+            .with_schema(Arc::new(Schema::new(vec![Field::new(
+                "d",
+                DataType::Date32,
+                false,
+            )])));
+        let err = ListingTable::try_new(config).unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "The files are declared to be ordered by x, which is not a column of the table"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_changes_since_snapshot() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let write_file = |name: &str, rows: &str| {
+            std::fs::write(tmp_dir.path().join(name), format!("a,b\n{rows}"))
+        };
+        write_file("1.csv", "1,x\n2,y\n")?;
+
+        let ctx = SessionContext::new();
+        let table_path =
+            ListingTableUrl::parse(format!("{}/", tmp_dir.path().to_str().unwrap()))?;
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()));
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .infer_schema(&ctx.state())
+            .await?;
+        ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+        let table = ctx.enable_change_tracking("t").await?;
+        let snapshot = table.snapshot(&ctx.state()).await?;
+        assert_eq!(snapshot, 1);
+
+        write_file("2.csv", "3,z\n")?;
+        let sql = "SELECT a, b FROM changes('t', 1) ORDER BY a";
+        let batches = ctx.sql(sql).await?.collect().await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +---+---+
+        | a | b |
+        +---+---+
+        | 3 | z |
+        +---+---+
+        ");
+        // the table itself reads all files
+        let batches = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +----------+
+        | count(*) |
+        +----------+
+        | 3        |
+        +----------+
+        ");
+        // snapshot 0 has no files
+        let sql = "SELECT count(*) FROM changes('t', 0)";
+        let batches = ctx.sql(sql).await?.collect().await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +----------+
+        | count(*) |
+        +----------+
+        | 3        |
+        +----------+
+        ");
+
+        // without a snapshot id, changes are read since the latest snapshot
+        assert_eq!(table.snapshot(&ctx.state()).await?, 2);
+        let sql = "SELECT count(*) FROM changes('t')";
+        let batches = ctx.sql(sql).await?.collect().await?;
+        insta::assert_snapshot!(batches_to_string(&batches), @r"
+        +----------+
+        | count(*) |
+        +----------+
+        | 0        |
+        +----------+
+        ");
+
+        let err = ctx.sql("SELECT * FROM changes('t', 3)").await.unwrap_err();
+        assert_contains!(err.to_string(), "Snapshot 3 does not exist");
+        let err = ctx.sql("SELECT * FROM changes('u')").await.unwrap_err();
+        assert_contains!(err.to_string(), "Changes of table u are not tracked");
+        Ok(())
+    }
+
     #[cfg(feature = "parquet")]
     #[tokio::test]
     async fn test_try_create_output_ordering() {
@@ -414,6 +757,67 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn list_files_from_manifest() -> Result<()> {
+        let ctx = SessionContext::new();
+        // a file that is not in the manifest is never listed
+        register_test_store(&ctx, &[("table/p1=v1/listed.json", 100)]);
+
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Boolean, false)]));
+        let manifest_file = |path: &str, partition: &str, num_rows: usize| {
+            let mut file = PartitionedFile::new(path, 100);
+            file.partition_values = vec![ScalarValue::from(partition)];
+            let statistics = Statistics::new_unknown(&file_schema)
+                .with_num_rows(Precision::Exact(num_rows));
+            file.with_statistics(Arc::new(statistics))
+        };
+        let manifest = FileManifest::new(vec![
+            manifest_file("table/p1=v1/a.json", "v1", 5),
+            manifest_file("table/p1=v2/b.json", "v2", 7),
+        ]);
+
+        let opt = ListingOptions::new(Arc::new(JsonFormat::default()))
+            .with_table_partition_cols(vec![(String::from("p1"), DataType::Utf8)])
+            .with_target_partitions(4);
+        let config = ListingTableConfig::new(ListingTableUrl::parse("test:///table/")?)
+            .with_listing_options(opt)
+            .with_schema(Arc::clone(&file_schema))
+            .with_manifest(Arc::new(manifest));
+        let table = ListingTable::try_new(config)?;
+
+        let result = table.list_files_for_scan(&ctx.state(), &[], None).await?;
+        let mut paths = result
+            .file_groups
+            .iter()
+            .flat_map(|group| group.iter())
+            .map(|file| file.object_meta.location.to_string())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, vec!["table/p1=v1/a.json", "table/p1=v2/b.json"]);
+        assert_eq!(result.statistics.num_rows, Precision::Exact(12));
+
+        // files are pruned with the partition filters
+        let filter = Expr::eq(col("p1"), lit("v2"));
+        let result = table
+            .list_files_for_scan(&ctx.state(), &[filter], None)
+            .await?;
+        let files = result.file_groups.iter().flat_map(|g| g.iter());
+        let files = files.collect::<Vec<_>>();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].object_meta.location.as_ref(), "table/p1=v2/b.json");
+        assert_eq!(result.statistics.num_rows, Precision::Exact(7));
+
+        let input = table.scan(&ctx.state(), None, &[], None).await?;
+        let err = table
+            .insert_into(&ctx.state(), input, InsertOp::Append)
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "with a file manifest is not supported");
+
+        Ok(())
+    }
+
     async fn load_table(
         ctx: &SessionContext,
         name: &str,
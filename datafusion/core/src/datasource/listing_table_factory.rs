@@ -136,8 +136,16 @@ impl TableProviderFactory for ListingTableFactory {
                     project_idx.push(i);
                 }
             }
-            let schema = Arc::new(schema.project(&project_idx)?);
-            (Some(schema), table_partition_cols)
+            // when only the partition columns are declared, e.g.
+            // `create external table a stored as parquet partitioned by (year int)...`,
+            // the schema of the files is inferred and the declared types are only
+            // used to cast the partition values parsed from the paths
+            let file_schema = if project_idx.is_empty() {
+                None
+            } else {
+                Some(Arc::new(schema.project(&project_idx)?))
+            };
+            (file_schema, table_partition_cols)
         };
 
         options = options.with_table_partition_cols(table_partition_cols);
@@ -146,6 +154,7 @@ impl TableProviderFactory for ListingTableFactory {
             .validate_partitions(session_state, &table_path)
             .await?;
 
+        let schema_inferred = provided_schema.is_none();
         let resolved_schema = match provided_schema {
             // We will need to check the table columns against the schema
             // this is done so that we can do an ORDER BY for external table creation
@@ -191,8 +200,12 @@ impl TableProviderFactory for ListingTableFactory {
             Some(s) => s,
         };
         let config = ListingTableConfig::new(table_path)
-            .with_listing_options(options.with_file_sort_order(cmd.order_exprs.clone()))
-            .with_schema(resolved_schema);
+            .with_listing_options(options.with_file_sort_order(cmd.order_exprs.clone()));
+        let config = if schema_inferred {
+            config.with_inferred_schema(resolved_schema)
+        } else {
+            config.with_schema(resolved_schema)
+        };
         let provider = ListingTable::try_new(config)?
             .with_cache(state.runtime_env().cache_manager.get_file_statistic_cache());
         let table = provider
@@ -240,8 +253,9 @@ mod tests {
     use std::fs::File;
     use std::path::PathBuf;
 
+    use datafusion_catalog_listing::SchemaSource;
     use datafusion_common::parsers::CompressionTypeVariant;
-    use datafusion_common::{DFSchema, TableReference};
+    use datafusion_common::{DFSchema, TableReference, assert_contains};
     use datafusion_expr::registry::ExtensionTypeRegistryRef;
 
     #[tokio::test]
@@ -649,4 +663,52 @@ mod tests {
                 .contains("Internal error: ListingTableFactory requires SessionState")
         );
     }
+
+    #[tokio::test]
+    async fn test_refresh_inferred_schema() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("1.csv"), "a\n1\n")?;
+
+        let ctx = SessionContext::new();
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t STORED AS CSV LOCATION '{}/' \
+             OPTIONS ('format.has_header' 'true')",
+            dir.path().display()
+        ))
+        .await?;
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t_fixed (a BIGINT) STORED AS CSV LOCATION '{}/' \
+             OPTIONS ('format.has_header' 'true')",
+            dir.path().display()
+        ))
+        .await?;
+
+        let listing_table = |provider: &Arc<dyn TableProvider>| {
+            provider.downcast_ref::<ListingTable>().unwrap().clone()
+        };
+        let table = listing_table(&ctx.table_provider("t").await?);
+        assert_eq!(table.schema_source(), SchemaSource::Inferred);
+        assert!(!table.is_schema_stale(&ctx.state()).await?);
+
+        // a new file adds a column, which is only visible after a refresh
+        fs::write(dir.path().join("2.csv"), "a,b\n2,x\n")?;
+        assert!(table.is_schema_stale(&ctx.state()).await?);
+        assert_eq!(ctx.table("t").await?.schema().fields().len(), 1);
+
+        ctx.sql("ALTER TABLE t REFRESH SCHEMA").await?;
+        let table = listing_table(&ctx.table_provider("t").await?);
+        assert!(!table.is_schema_stale(&ctx.state()).await?);
+        assert_eq!(ctx.table("t").await?.schema().field_names(), ["t.a", "t.b"]);
+
+        // tables with a declared schema keep it
+        let table = listing_table(&ctx.table_provider("t_fixed").await?);
+        assert_eq!(table.schema_source(), SchemaSource::Specified);
+        assert!(!table.is_schema_stale(&ctx.state()).await?);
+        let err = ctx
+            .sql("ALTER TABLE t_fixed REFRESH SCHEMA")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "whose schema was not inferred");
+        Ok(())
+    }
 }
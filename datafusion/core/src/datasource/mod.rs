@@ -19,6 +19,7 @@
 //!
 //! [`ListingTable`]: crate::datasource::listing::ListingTable
 
+pub mod blob;
 pub mod dynamic_file;
 pub mod file_format;
 pub mod listing;
@@ -26,6 +27,9 @@ pub mod listing_table_factory;
 mod memory_test;
 pub mod physical_plan;
 pub mod provider;
+pub mod scan_options;
+pub mod statistics_collecting;
+pub mod synthetic_statistics;
 mod view_test;
 
 // backwards compatibility
@@ -33,6 +37,9 @@ pub use self::default_table_source::{
     DefaultTableSource, provider_as_source, source_as_provider,
 };
 pub use self::memory::MemTable;
+pub use self::scan_options::ScanOptionsTable;
+pub use self::statistics_collecting::StatisticsCollectingTable;
+pub use self::synthetic_statistics::SyntheticStatisticsTable;
 pub use self::view::ViewTable;
 pub use crate::catalog::TableProvider;
 pub use crate::logical_expr::TableType;
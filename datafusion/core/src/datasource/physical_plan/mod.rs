@@ -19,6 +19,7 @@
 
 pub mod arrow;
 pub mod csv;
+#[cfg(feature = "json")]
 pub mod json;
 
 #[cfg(feature = "parquet")]
@@ -35,6 +36,7 @@ pub use datafusion_datasource_parquet::source::ParquetSource;
 #[cfg(feature = "parquet")]
 pub use datafusion_datasource_parquet::{ParquetFileMetrics, ParquetFileReaderFactory};
 
+#[cfg(feature = "json")]
 pub use json::{JsonOpener, JsonSource};
 
 pub use arrow::{ArrowOpener, ArrowSource};
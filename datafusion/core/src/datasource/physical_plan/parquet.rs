@@ -57,7 +57,8 @@ mod tests {
     use datafusion_datasource::{PartitionedFile, TableSchema};
     use datafusion_datasource_parquet::source::ParquetSource;
     use datafusion_datasource_parquet::{
-        DefaultParquetFileReaderFactory, ParquetFileReaderFactory, ParquetFormat,
+        ColumnScanMetrics, DefaultParquetFileReaderFactory, ParquetFileReaderFactory,
+        ParquetFormat,
     };
     use datafusion_execution::object_store::ObjectStoreUrl;
     use datafusion_expr::{Expr, col, lit, when};
@@ -1929,6 +1930,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn parquet_exec_column_scan_metrics() {
+        let c1: ArrayRef =
+            Arc::new(StringArray::from(vec![Some("Foo"), None, Some("bar")]));
+        let c2: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), Some(2), None]));
+        let batch = create_batch(vec![("c1", c1), ("c2", c2)]);
+
+        // only read c2
+        let rt = RoundTrip::new()
+            .with_projection(vec![1])
+            .round_trip(vec![batch])
+            .await;
+        assert_eq!(rt.batches.unwrap()[0].num_rows(), 3);
+
+        let metrics = rt.parquet_exec.metrics().unwrap().aggregate_by_name();
+        let column_scan_stats = metrics
+            .iter()
+            .find_map(|metric| match metric.value() {
+                MetricValue::Custom { name, value } if name == "column_scan_stats" => {
+                    value
+                        .as_any()
+                        .downcast_ref::<ColumnScanMetrics>()
+                        .map(|v| v.columns())
+                }
+                _ => None,
+            })
+            .expect("no column scan stats in metrics");
+
+        // the bytes of c1 are never read
+        assert_eq!(column_scan_stats.keys().collect::<Vec<_>>(), vec!["c2"]);
+        let c2_bytes = column_scan_stats["c2"].bytes_scanned;
+        assert!(c2_bytes > 0);
+        assert!(c2_bytes <= get_value(&metrics, "bytes_scanned"));
+        assert!(
+            get_value(&metrics, "decode_time") > 0,
+            "no decode time in metrics: {metrics:#?}"
+        );
+    }
+
     #[tokio::test]
     async fn parquet_exec_display() {
         // batch1: c1(string)
@@ -0,0 +1,355 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScanOptionsTable`]: override configuration options for the scans of a
+//! single table
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::catalog::{ScanArgs, ScanResult, Session, TableProvider};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::logical_expr::{
+    Expr, LogicalPlan, TableProviderFilterPushDown, TableType, dml::InsertOp,
+};
+use crate::physical_plan::ExecutionPlan;
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{Constraints, Statistics, not_impl_datafusion_err};
+use datafusion_datasource::file_scan_config::{FileScanConfig, FileScanConfigBuilder};
+use datafusion_datasource::source::DataSourceExec;
+
+/// Prefix of the options applied to the [`ParquetSource`] of a scan
+///
+/// [`ParquetSource`]: datafusion_datasource_parquet::source::ParquetSource
+#[cfg(feature = "parquet")]
+const PARQUET_PREFIX: &str = "datafusion.execution.parquet.";
+
+/// A [`TableProvider`] that scans another table with some configuration
+/// options overridden, so that tables with different characteristics can be
+/// tuned independently of the session wide settings.
+///
+/// The options are applied to the [`SessionState`] passed to the wrapped
+/// table's scan, which affects all options read while planning the scan,
+/// such as `datafusion.execution.meta_fetch_concurrency`. In addition, for
+/// file based tables:
+///
+/// * `datafusion.execution.batch_size` sets the batch size of the scan
+/// * `datafusion.execution.parquet.*` options are applied to Parquet scans.
+///   Note that filter pushdown is enabled for a scan if it is enabled either
+///   for the table or for the session, so `pushdown_filters` can only be used
+///   to enable it for a single table.
+///
+/// The same overrides can be set for a single query using table hints, which
+/// wrap the referenced table in a `ScanOptionsTable`:
+///
+/// ```sql
+/// SELECT * FROM t WITH (datafusion.execution.batch_size = 1024)
+/// ```
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion::datasource::{MemTable, ScanOptionsTable};
+/// # use datafusion::error::Result;
+/// # use datafusion::prelude::*;
+/// # use arrow::datatypes::{DataType, Field, Schema};
+/// # fn main() -> Result<()> {
+/// # let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+/// # let provider = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+/// let ctx = SessionContext::new();
+/// let table = ScanOptionsTable::try_new(
+///     provider,
+///     [("datafusion.execution.parquet.pushdown_filters", "true")],
+/// )?;
+/// ctx.register_table("t", Arc::new(table))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScanOptionsTable {
+    inner: Arc<dyn TableProvider>,
+    options: Vec<(String, String)>,
+}
+
+impl ScanOptionsTable {
+    /// Wrap `inner`, overriding the given configuration `options` for its
+    /// scans.
+    ///
+    /// Returns an error if any of the options is unknown or has an invalid
+    /// value.
+    pub fn try_new<K, V>(
+        inner: Arc<dyn TableProvider>,
+        options: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let options = options
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect::<Vec<_>>();
+
+        let mut config = ConfigOptions::default();
+        for (key, value) in &options {
+            config.set(key, value)?;
+        }
+        Ok(Self { inner, options })
+    }
+
+    /// Return the wrapped table
+    pub fn inner(&self) -> &Arc<dyn TableProvider> {
+        &self.inner
+    }
+
+    /// Return the overridden options as `key`, `value` pairs
+    pub fn options(&self) -> &[(String, String)] {
+        &self.options
+    }
+
+    fn overrides(&self, key: &str) -> bool {
+        self.options.iter().any(|(k, _)| k == key)
+    }
+
+    /// Return true if any option must be applied to the file scan itself
+    fn overrides_file_scan(&self) -> bool {
+        #[cfg(feature = "parquet")]
+        if self
+            .options
+            .iter()
+            .any(|(k, _)| k.starts_with(PARQUET_PREFIX))
+        {
+            return true;
+        }
+        self.overrides("datafusion.execution.batch_size")
+    }
+
+    /// Apply the options that are fixed when a file scan is created
+    fn apply_to_file_scan(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let Some(file_scan) = plan
+            .downcast_ref::<DataSourceExec>()
+            .and_then(|exec| exec.data_source().downcast_ref::<FileScanConfig>())
+        else {
+            return Ok(plan);
+        };
+
+        let mut builder = FileScanConfigBuilder::from(file_scan.clone());
+        if self.overrides("datafusion.execution.batch_size") {
+            builder = builder.with_batch_size(Some(config.execution.batch_size));
+        }
+
+        #[cfg(feature = "parquet")]
+        if let Some(source) = file_scan
+            .file_source()
+            .downcast_ref::<datafusion_datasource_parquet::source::ParquetSource>(
+        ) {
+            use datafusion_common::config::ConfigField;
+
+            let mut parquet_options = source.table_parquet_options().clone();
+            for (key, value) in &self.options {
+                if let Some(key) = key.strip_prefix(PARQUET_PREFIX) {
+                    parquet_options.global.set(key, value)?;
+                }
+            }
+            builder = builder.with_source(Arc::new(
+                source.clone().with_table_parquet_options(parquet_options),
+            ));
+        }
+
+        Ok(DataSourceExec::from_data_source(builder.build()))
+    }
+}
+
+#[async_trait]
+impl TableProvider for ScanOptionsTable {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.inner.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.inner.table_type()
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.inner.get_table_definition()
+    }
+
+    fn get_logical_plan(&'_ self) -> Option<Cow<'_, LogicalPlan>> {
+        self.inner.get_logical_plan()
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.inner.get_column_default(column)
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let args = ScanArgs::default()
+            .with_projection(projection.map(|p| p.as_slice()))
+            .with_filters(Some(filters))
+            .with_limit(limit);
+        Ok(self.scan_with_args(state, args).await?.into_inner())
+    }
+
+    async fn scan_with_args<'a>(
+        &self,
+        state: &dyn Session,
+        args: ScanArgs<'a>,
+    ) -> Result<ScanResult> {
+        let state = state
+            .as_any()
+            .downcast_ref::<SessionState>()
+            .ok_or_else(|| {
+                not_impl_datafusion_err!("ScanOptionsTable requires a SessionState")
+            })?;
+        let mut state = state.clone();
+        let config = state.config_mut().options_mut();
+        for (key, value) in &self.options {
+            config.set(key, value)?;
+        }
+
+        let plan = self.inner.scan_with_args(&state, args).await?.into_inner();
+        if !self.overrides_file_scan() {
+            return Ok(plan.into());
+        }
+        let plan = self.apply_to_file_scan(plan, state.config_options())?;
+        Ok(plan.into())
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.inner.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.inner.statistics()
+    }
+
+    async fn insert_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.insert_into(state, input, insert_op).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::MemTable;
+    use crate::prelude::{CsvReadOptions, SessionContext};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    /// Return the batch size of the first file scan in `plan`
+    fn scan_batch_size(plan: &Arc<dyn ExecutionPlan>) -> Option<usize> {
+        if let Some(file_scan) = plan
+            .downcast_ref::<DataSourceExec>()
+            .and_then(|exec| exec.data_source().downcast_ref::<FileScanConfig>())
+        {
+            return file_scan.batch_size;
+        }
+        plan.children().into_iter().find_map(scan_batch_size)
+    }
+
+    #[tokio::test]
+    async fn scan_options_from_table_and_hints() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("t.csv");
+        std::fs::write(&path, "a\n1\n2\n3\n")?;
+
+        let ctx = SessionContext::new();
+        ctx.register_csv("t", path.to_str().unwrap(), CsvReadOptions::new())
+            .await?;
+        let provider = ctx.table_provider("t").await?;
+        ctx.register_table(
+            "t_small",
+            Arc::new(ScanOptionsTable::try_new(
+                provider,
+                [("datafusion.execution.batch_size", "2")],
+            )?),
+        )?;
+
+        let plan = ctx
+            .sql("SELECT a FROM t")
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert_eq!(scan_batch_size(&plan), None);
+        let plan = ctx
+            .sql("SELECT a FROM t_small")
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert_eq!(scan_batch_size(&plan), Some(2));
+        let plan = ctx
+            .sql("SELECT a FROM t WITH (datafusion.execution.batch_size = 5)")
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert_eq!(scan_batch_size(&plan), Some(5));
+
+        let batches = ctx
+            .sql(
+                "SELECT count(*) FROM t_small WITH (datafusion.execution.batch_size = 1)",
+            )
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(batches[0].num_rows(), 1);
+
+        let err = ctx
+            .sql("SELECT a FROM t WITH (datafusion.execution.batch_sise = 5)")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("batch_sise"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_options() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let provider = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        let err = ScanOptionsTable::try_new(
+            provider,
+            [("datafusion.execution.batch_size", "many")],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("many"), "{err}");
+        Ok(())
+    }
+}
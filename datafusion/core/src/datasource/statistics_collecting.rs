@@ -0,0 +1,313 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`StatisticsCollectingTable`]: collect the statistics of a table while
+//! queries read it, for planning later queries
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::catalog::{ScanArgs, ScanResult, Session, TableProvider};
+use crate::error::Result;
+use crate::logical_expr::{
+    Expr, LogicalPlan, TableProviderFilterPushDown, TableType, dml::InsertOp,
+};
+use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::statistics_collector::{
+    CollectedStatistics, CollectedStatisticsSink, DEFAULT_TOP_K, HeavyHitter,
+    StatisticsCollectorExec,
+};
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_common::stats::Precision;
+use datafusion_common::{ColumnStatistics, Constraints, Statistics};
+use parking_lot::RwLock;
+
+/// A [`TableProvider`] that collects the statistics of another table while
+/// queries read all of its rows, and reports them from
+/// [`TableProvider::statistics`] so that later queries are planned with them,
+/// similar to an automatic `ANALYZE TABLE`.
+///
+/// Scans without filters or limit are wrapped in a
+/// [`StatisticsCollectorExec`], which collects the number of rows and, for the
+/// columns read, their null counts, approximate numbers of distinct values and
+/// most frequent values, see [`Self::heavy_hitters`]. The statistics of each
+/// column are kept until a later scan reading that column replaces them.
+///
+/// The statistics are reported as [`Precision::Inexact`], as the table may
+/// have changed since they were collected. Statistics stored by
+/// `ANALYZE TABLE` replace the collected ones.
+///
+/// See also [`SessionContext::enable_table_statistics_collection`].
+///
+/// [`SessionContext::enable_table_statistics_collection`]: crate::execution::context::SessionContext::enable_table_statistics_collection
+#[derive(Debug)]
+pub struct StatisticsCollectingTable {
+    inner: Arc<dyn TableProvider>,
+    top_k: usize,
+    store: Arc<RwLock<CollectedStatistics>>,
+}
+
+impl StatisticsCollectingTable {
+    /// Wrap `inner`, collecting its statistics
+    pub fn new(inner: Arc<dyn TableProvider>) -> Self {
+        let statistics = Statistics::new_unknown(&inner.schema());
+        let heavy_hitters = vec![vec![]; statistics.column_statistics.len()];
+        Self {
+            inner,
+            top_k: DEFAULT_TOP_K,
+            store: Arc::new(RwLock::new(CollectedStatistics {
+                statistics,
+                heavy_hitters,
+            })),
+        }
+    }
+
+    /// Set the number of most frequent values collected per column, which
+    /// defaults to [`DEFAULT_TOP_K`]
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Return the wrapped table
+    pub fn inner(&self) -> &Arc<dyn TableProvider> {
+        &self.inner
+    }
+
+    /// Return the approximately most frequent values of each column of the
+    /// table, most frequent first, which are empty for the columns whose
+    /// statistics were not collected yet
+    pub fn heavy_hitters(&self) -> Vec<Vec<HeavyHitter>> {
+        self.store.read().heavy_hitters.clone()
+    }
+}
+
+#[async_trait]
+impl TableProvider for StatisticsCollectingTable {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.inner.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.inner.table_type()
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.inner.get_table_definition()
+    }
+
+    fn get_logical_plan(&'_ self) -> Option<Cow<'_, LogicalPlan>> {
+        self.inner.get_logical_plan()
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.inner.get_column_default(column)
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let args = ScanArgs::default()
+            .with_projection(projection.map(|p| p.as_slice()))
+            .with_filters(Some(filters))
+            .with_limit(limit);
+        Ok(self.scan_with_args(state, args).await?.into_inner())
+    }
+
+    async fn scan_with_args<'a>(
+        &self,
+        state: &dyn Session,
+        args: ScanArgs<'a>,
+    ) -> Result<ScanResult> {
+        let plan = self
+            .inner
+            .scan_with_args(state, args.clone())
+            .await?
+            .into_inner();
+        // only scans of all rows produce the statistics of the table
+        if args.filters().is_some_and(|filters| !filters.is_empty())
+            || args.limit().is_some()
+        {
+            return Ok(plan.into());
+        }
+        let columns = match args.projection() {
+            Some(projection) => projection.to_vec(),
+            None => (0..self.schema().fields().len()).collect(),
+        };
+        let sink = Arc::new(ScanStatisticsSink {
+            store: Arc::clone(&self.store),
+            columns,
+        });
+        let exec = StatisticsCollectorExec::new(plan, sink).with_top_k(self.top_k);
+        Ok(ScanResult::new(Arc::new(exec)))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.inner.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        let statistics = &self.store.read().statistics;
+        if statistics.num_rows == Precision::Absent {
+            return self.inner.statistics();
+        }
+        Some(statistics.clone().to_inexact())
+    }
+
+    async fn insert_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.insert_into(state, input, insert_op).await
+    }
+
+    async fn update_statistics(&self, statistics: Statistics) -> Result<()> {
+        let mut store = self.store.write();
+        store.heavy_hitters.iter_mut().for_each(Vec::clear);
+        store.statistics = statistics;
+        Ok(())
+    }
+}
+
+/// Stores the statistics collected by a scan of a
+/// [`StatisticsCollectingTable`] reading `columns` of the table
+#[derive(Debug)]
+struct ScanStatisticsSink {
+    store: Arc<RwLock<CollectedStatistics>>,
+    columns: Vec<usize>,
+}
+
+#[async_trait]
+impl CollectedStatisticsSink for ScanStatisticsSink {
+    async fn collected(&self, collected: CollectedStatistics) -> Result<()> {
+        let mut store = self.store.write();
+        let CollectedStatistics {
+            statistics,
+            heavy_hitters,
+        } = collected;
+        store.statistics.num_rows = statistics.num_rows;
+        // the scan may produce additional columns, such as metadata columns
+        let num_columns = store.statistics.column_statistics.len();
+        for ((&column, column_statistics), heavy_hitters) in self
+            .columns
+            .iter()
+            .zip(statistics.column_statistics)
+            .zip(heavy_hitters)
+            .filter(|((column, _), _)| **column < num_columns)
+        {
+            let ColumnStatistics {
+                null_count,
+                distinct_count,
+                ..
+            } = column_statistics;
+            let stored = &mut store.statistics.column_statistics[column];
+            stored.null_count = null_count;
+            stored.distinct_count = distinct_count;
+            store.heavy_hitters[column] = heavy_hitters;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::MemTable;
+    use crate::prelude::SessionContext;
+    use arrow::array::{Int32Array, RecordBatch, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::ScalarValue;
+
+    #[tokio::test]
+    async fn collect_statistics_while_querying() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 2, 3, 2])),
+                Arc::new(StringArray::from(vec![
+                    Some("x"),
+                    None,
+                    Some("y"),
+                    None,
+                    None,
+                ])),
+            ],
+        )?;
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))?;
+        ctx.enable_table_statistics_collection("t", 1).await?;
+        let table = ctx.table_provider("t").await?;
+        let table = table.downcast_ref::<StatisticsCollectingTable>().unwrap();
+        assert_eq!(table.statistics(), table.inner().statistics());
+
+        // scans with a limit do not read all rows
+        ctx.sql("SELECT a FROM t LIMIT 1").await?.collect().await?;
+        assert_eq!(table.statistics(), table.inner().statistics());
+
+        ctx.sql("SELECT a FROM t").await?.collect().await?;
+        let statistics = table.statistics().unwrap();
+        assert_eq!(statistics.num_rows, Precision::Inexact(5));
+        let a = &statistics.column_statistics[0];
+        assert_eq!(a.null_count, Precision::Inexact(0));
+        assert_eq!(a.distinct_count, Precision::Inexact(3));
+        // `b` was not read
+        assert_eq!(
+            statistics.column_statistics[1],
+            ColumnStatistics::new_unknown()
+        );
+        assert_eq!(
+            table.heavy_hitters(),
+            vec![
+                vec![HeavyHitter {
+                    value: ScalarValue::Int32(Some(2)),
+                    count: 3
+                }],
+                vec![]
+            ]
+        );
+
+        ctx.sql("SELECT b FROM t").await?.collect().await?;
+        let statistics = table.statistics().unwrap();
+        assert_eq!(
+            statistics.column_statistics[0].distinct_count,
+            Precision::Inexact(3)
+        );
+        let b = &statistics.column_statistics[1];
+        assert_eq!(b.null_count, Precision::Inexact(3));
+        assert_eq!(b.distinct_count, Precision::Inexact(2));
+        Ok(())
+    }
+}
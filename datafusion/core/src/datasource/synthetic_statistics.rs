@@ -0,0 +1,383 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SyntheticStatisticsTable`]: attach synthetic statistics to a table, to
+//! test optimizer behavior without generating data at scale
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::catalog::{ScanArgs, ScanResult, Session, TableProvider};
+use crate::error::Result;
+use crate::execution::TaskContext;
+use crate::logical_expr::{
+    Expr, LogicalPlan, TableProviderFilterPushDown, TableType, dml::InsertOp,
+};
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties,
+    SendableRecordBatchStream,
+};
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_common::stats::Precision;
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::{
+    ColumnStatistics, Constraints, Statistics, assert_eq_or_internal_err, plan_err,
+};
+use datafusion_physical_expr::PhysicalExpr;
+
+/// A [`TableProvider`] that reports synthetic [`Statistics`] for another
+/// table, such as the number of rows and the number of distinct values,
+/// minimum and maximum of each column.
+///
+/// This allows testing how the optimizer plans queries over large tables,
+/// for example the join order or the build side of hash joins, without
+/// generating the data. The statistics are reported both to the logical
+/// optimizer, by [`TableProvider::statistics`], and to the physical optimizer,
+/// by wrapping the scans of the table in a [`SyntheticStatisticsExec`].
+/// The rows themselves are read from the wrapped table as usual.
+///
+/// Note that [`Precision::Exact`] statistics may be used to answer queries
+/// without reading the table at all, e.g. `SELECT count(*) FROM t` returns
+/// an exact `num_rows`. Use [`Precision::Inexact`] to only affect planning.
+///
+/// See also [`SessionContext::set_table_statistics`].
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion::datasource::{MemTable, SyntheticStatisticsTable};
+/// # use datafusion::error::Result;
+/// # use datafusion::prelude::*;
+/// # use datafusion::common::stats::Precision;
+/// # use datafusion::common::{ColumnStatistics, Statistics};
+/// # use arrow::datatypes::{DataType, Field, Schema};
+/// # fn main() -> Result<()> {
+/// # let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+/// # let provider = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+/// // Pretend the (empty) table has a billion rows with 1000 distinct values of `a`
+/// let statistics = Statistics::default()
+///     .with_num_rows(Precision::Inexact(1_000_000_000))
+///     .add_column_statistics(
+///         ColumnStatistics::new_unknown().with_distinct_count(Precision::Inexact(1000)),
+///     );
+/// let table = SyntheticStatisticsTable::try_new(provider, statistics)?;
+/// let ctx = SessionContext::new();
+/// ctx.register_table("t", Arc::new(table))?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SessionContext::set_table_statistics`]: crate::execution::context::SessionContext::set_table_statistics
+#[derive(Debug)]
+pub struct SyntheticStatisticsTable {
+    inner: Arc<dyn TableProvider>,
+    statistics: Statistics,
+}
+
+impl SyntheticStatisticsTable {
+    /// Wrap `inner`, reporting `statistics` for it.
+    ///
+    /// Returns an error if `statistics` does not have exactly one
+    /// [`ColumnStatistics`] per column of the table.
+    pub fn try_new(
+        inner: Arc<dyn TableProvider>,
+        statistics: Statistics,
+    ) -> Result<Self> {
+        let num_columns = inner.schema().fields().len();
+        if statistics.column_statistics.len() != num_columns {
+            return plan_err!(
+                "Expected statistics for {num_columns} columns, got {}",
+                statistics.column_statistics.len()
+            );
+        }
+        Ok(Self { inner, statistics })
+    }
+
+    /// Return the wrapped table
+    pub fn inner(&self) -> &Arc<dyn TableProvider> {
+        &self.inner
+    }
+
+    /// Return the synthetic statistics of the table
+    pub fn synthetic_statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Return the statistics of a scan of the table with the given arguments
+    fn scan_statistics(&self, args: &ScanArgs<'_>, schema: &SchemaRef) -> Statistics {
+        let projection = args.projection().map(|p| p.to_vec());
+        let mut statistics = self.statistics.clone().project(projection.as_ref());
+        // The scan may produce additional columns, such as metadata columns
+        statistics
+            .column_statistics
+            .resize(schema.fields().len(), ColumnStatistics::new_unknown());
+        // Filters pushed into the scan make the statistics an upper bound
+        if args.filters().is_some_and(|filters| !filters.is_empty()) {
+            statistics = statistics.to_inexact();
+        }
+        if let Some(limit) = args.limit() {
+            statistics.num_rows = statistics.num_rows.min(&Precision::Exact(limit));
+        }
+        statistics
+    }
+}
+
+#[async_trait]
+impl TableProvider for SyntheticStatisticsTable {
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.inner.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.inner.table_type()
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.inner.get_table_definition()
+    }
+
+    fn get_logical_plan(&'_ self) -> Option<Cow<'_, LogicalPlan>> {
+        self.inner.get_logical_plan()
+    }
+
+    fn get_column_default(&self, column: &str) -> Option<&Expr> {
+        self.inner.get_column_default(column)
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let args = ScanArgs::default()
+            .with_projection(projection.map(|p| p.as_slice()))
+            .with_filters(Some(filters))
+            .with_limit(limit);
+        Ok(self.scan_with_args(state, args).await?.into_inner())
+    }
+
+    async fn scan_with_args<'a>(
+        &self,
+        state: &dyn Session,
+        args: ScanArgs<'a>,
+    ) -> Result<ScanResult> {
+        let plan = self
+            .inner
+            .scan_with_args(state, args.clone())
+            .await?
+            .into_inner();
+        let statistics = self.scan_statistics(&args, &plan.schema());
+        Ok(ScanResult::new(Arc::new(SyntheticStatisticsExec::new(
+            plan, statistics,
+        ))))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.inner.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        Some(self.statistics.clone())
+    }
+
+    async fn insert_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+        insert_op: InsertOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.inner.insert_into(state, input, insert_op).await
+    }
+}
+
+/// An [`ExecutionPlan`] that passes through the rows of its input, but
+/// reports synthetic statistics, see [`SyntheticStatisticsTable`].
+///
+/// The statistics are reported for the whole output; the statistics of each
+/// partition are unknown.
+#[derive(Debug, Clone)]
+pub struct SyntheticStatisticsExec {
+    input: Arc<dyn ExecutionPlan>,
+    statistics: Arc<Statistics>,
+}
+
+impl SyntheticStatisticsExec {
+    /// Create a new `SyntheticStatisticsExec` reporting `statistics` for `input`
+    pub fn new(input: Arc<dyn ExecutionPlan>, statistics: Statistics) -> Self {
+        Self {
+            input,
+            statistics: Arc::new(statistics),
+        }
+    }
+
+    /// Returns the wrapped input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+}
+
+impl DisplayAs for SyntheticStatisticsExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "SyntheticStatisticsExec: rows={}",
+                    self.statistics.num_rows
+                )
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "rows={}", self.statistics.num_rows)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SyntheticStatisticsExec {
+    fn name(&self) -> &str {
+        "SyntheticStatisticsExec"
+    }
+
+    fn properties(&self) -> &Arc<PlanProperties> {
+        self.input.properties()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn apply_expressions(
+        &self,
+        _f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        Ok(TreeNodeRecursion::Continue)
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert_eq_or_internal_err!(
+            children.len(),
+            1,
+            "SyntheticStatisticsExec requires exactly one child"
+        );
+        Ok(Arc::new(Self {
+            input: children.swap_remove(0),
+            statistics: Arc::clone(&self.statistics),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        self.input.execute(partition, context)
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Arc<Statistics>> {
+        match partition {
+            None => Ok(Arc::clone(&self.statistics)),
+            Some(_) => Ok(Arc::new(Statistics::new_unknown(&self.schema()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::MemTable;
+    use crate::prelude::SessionContext;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::ScalarValue;
+
+    #[tokio::test]
+    async fn synthetic_statistics() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let provider = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        let column = |min: i32, max: i32| {
+            ColumnStatistics::new_unknown()
+                .with_min_value(Precision::Exact(ScalarValue::from(min)))
+                .with_max_value(Precision::Exact(ScalarValue::from(max)))
+        };
+        let statistics = Statistics::default()
+            .with_num_rows(Precision::Exact(1_000_000))
+            .add_column_statistics(column(0, 10))
+            .add_column_statistics(column(100, 200));
+
+        let err =
+            SyntheticStatisticsTable::try_new(provider.clone(), Statistics::default())
+                .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Expected statistics for 2 columns, got 0")
+        );
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", provider)?;
+        ctx.set_table_statistics("t", statistics.clone()).await?;
+        let table = ctx.table_provider("t").await?;
+        assert_eq!(table.statistics(), Some(statistics.clone()));
+
+        // The statistics of a projection are projected
+        let plan = ctx
+            .sql("SELECT b FROM t")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let actual = plan.partition_statistics(None)?;
+        assert_eq!(actual.num_rows, Precision::Exact(1_000_000));
+        assert_eq!(actual.column_statistics, vec![column(100, 200)]);
+
+        // Setting the statistics again replaces them
+        let statistics = statistics.with_num_rows(Precision::Inexact(5));
+        ctx.set_table_statistics("t", statistics.clone()).await?;
+        let table = ctx.table_provider("t").await?;
+        assert_eq!(table.statistics(), Some(statistics));
+
+        // With inexact statistics, the rows are read from the wrapped table
+        let batches = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_eq!(
+            batches[0].column(0).as_ref(),
+            &arrow::array::Int64Array::from(vec![0])
+        );
+        Ok(())
+    }
+}
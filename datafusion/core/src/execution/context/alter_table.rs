@@ -0,0 +1,377 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Changing the columns of tables with `ALTER TABLE`
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{DataFrame, Result, SessionContext};
+use crate::catalog::TableProvider;
+use crate::datasource::MemTable;
+use crate::datasource::file_format::csv::CsvFormat;
+use crate::datasource::listing::ListingTable;
+use arrow::array::{RecordBatch, new_null_array};
+use arrow::datatypes::{FieldRef, Schema, SchemaRef};
+use datafusion_common::{not_impl_err, plan_err};
+use datafusion_expr::{AlterTable, AlterTableOperation};
+
+/// A column of an altered table, and the index of the column of the
+/// original table it is read from, if any
+type AlteredColumn = (FieldRef, Option<usize>);
+
+impl SessionContext {
+    /// Apply an `ALTER TABLE` statement by registering a copy of the table
+    /// with the altered columns.
+    ///
+    /// The data of in-memory tables is rewritten. Listing tables are checked
+    /// to be compatible with their files, which are not modified.
+    pub(super) async fn alter_table(&self, cmd: AlterTable) -> Result<DataFrame> {
+        let AlterTable {
+            name,
+            if_exists,
+            operations,
+            ..
+        } = cmd;
+        if !self.table_exist(name.clone())? {
+            if if_exists {
+                return self.return_empty_dataframe();
+            }
+            return plan_err!("Table '{name}' doesn't exist.");
+        }
+
+        let provider = self.table_provider(name.clone()).await?;
+        let altered: Arc<dyn TableProvider> =
+            if let Some(table) = provider.downcast_ref::<MemTable>() {
+                Arc::new(alter_mem_table(table, &operations).await?)
+            } else if let Some(table) = provider.downcast_ref::<ListingTable>() {
+                Arc::new(self.alter_listing_table(table, &operations).await?)
+            } else {
+                return not_impl_err!(
+                    "ALTER TABLE is only supported for in-memory and listing tables"
+                );
+            };
+
+        self.deregister_table(name.clone())?;
+        self.register_table(name, altered)?;
+        self.return_empty_dataframe()
+    }
+
+    async fn alter_listing_table(
+        &self,
+        table: &ListingTable,
+        operations: &[AlterTableOperation],
+    ) -> Result<ListingTable> {
+        if table.options().format.downcast_ref::<CsvFormat>().is_some() {
+            return not_impl_err!(
+                "ALTER TABLE is not supported for CSV tables, as their columns are read by position"
+            );
+        }
+        let is_partition_column = |name: &str| {
+            table
+                .options()
+                .table_partition_cols
+                .iter()
+                .any(|(col, _)| col == name)
+        };
+        for operation in operations {
+            let names = match operation {
+                AlterTableOperation::AddColumn { .. } => vec![],
+                AlterTableOperation::DropColumn { names, .. } => names.iter().collect(),
+                AlterTableOperation::RenameColumn { old_name, new_name } => {
+                    vec![old_name, new_name]
+                }
+            };
+            if let Some(name) = names.into_iter().find(|name| is_partition_column(name)) {
+                return plan_err!("Can not alter partition column '{name}'");
+            }
+        }
+
+        let table_schema = table.schema();
+        let file_schema = Schema::new_with_metadata(
+            table_schema
+                .fields()
+                .iter()
+                .filter(|field| !is_partition_column(field.name()))
+                .cloned()
+                .collect::<Vec<_>>(),
+            table_schema.metadata().clone(),
+        );
+        let columns = alter_columns(&file_schema, operations)?;
+        if let Some((field, _)) = columns
+            .iter()
+            .find(|(field, _)| is_partition_column(field.name()))
+        {
+            return plan_err!("Column '{}' already exists", field.name());
+        }
+
+        // The files are not rewritten, so the data of a renamed column would
+        // no longer be found
+        let state = self.state();
+        let physical_file_schema = table.infer_file_schema(&state).await?;
+        for (field, index) in &columns {
+            if let Some(index) = index {
+                let old_name = file_schema.field(*index).name();
+                if old_name != field.name()
+                    && physical_file_schema.field_with_name(old_name).is_ok()
+                {
+                    return plan_err!(
+                        "Can not rename column '{old_name}' of a listing table, as it is stored in the table's files"
+                    );
+                }
+            }
+        }
+
+        table
+            .with_altered_file_schema(&state, altered_schema(&file_schema, &columns))
+            .await
+    }
+}
+
+/// Apply `operations` to the columns of `schema`. Added columns are appended
+/// after the existing ones.
+fn alter_columns(
+    schema: &Schema,
+    operations: &[AlterTableOperation],
+) -> Result<Vec<AlteredColumn>> {
+    let mut columns = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(index, field)| (Arc::clone(field), Some(index)))
+        .collect::<Vec<AlteredColumn>>();
+    let position = |columns: &[AlteredColumn], name: &str| {
+        columns.iter().position(|(field, _)| field.name() == name)
+    };
+
+    for operation in operations {
+        match operation {
+            AlterTableOperation::AddColumn {
+                field,
+                if_not_exists,
+            } => match position(&columns, field.name()) {
+                Some(_) if *if_not_exists => {}
+                Some(_) => return plan_err!("Column '{}' already exists", field.name()),
+                None => columns.push((Arc::clone(field), None)),
+            },
+            AlterTableOperation::DropColumn { names, if_exists } => {
+                for name in names {
+                    match position(&columns, name) {
+                        Some(index) => {
+                            columns.remove(index);
+                        }
+                        None if *if_exists => {}
+                        None => return plan_err!("Column '{name}' does not exist"),
+                    }
+                }
+            }
+            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                let Some(index) = position(&columns, old_name) else {
+                    return plan_err!("Column '{old_name}' does not exist");
+                };
+                if position(&columns, new_name).is_some() {
+                    return plan_err!("Column '{new_name}' already exists");
+                }
+                let field = columns[index].0.as_ref().clone().with_name(new_name);
+                columns[index].0 = Arc::new(field);
+            }
+        }
+    }
+
+    if columns.is_empty() {
+        return plan_err!("Can not drop all columns of a table");
+    }
+    Ok(columns)
+}
+
+fn altered_schema(schema: &Schema, columns: &[AlteredColumn]) -> SchemaRef {
+    Arc::new(Schema::new_with_metadata(
+        columns
+            .iter()
+            .map(|(field, _)| Arc::clone(field))
+            .collect::<Vec<_>>(),
+        schema.metadata().clone(),
+    ))
+}
+
+/// Return a copy of `table` with the altered columns and rewritten data.
+///
+/// Constraints and column defaults of the remaining columns are kept, the
+/// sort order of the table is not.
+async fn alter_mem_table(
+    table: &MemTable,
+    operations: &[AlterTableOperation],
+) -> Result<MemTable> {
+    let schema = table.schema();
+    let columns = alter_columns(&schema, operations)?;
+    let new_schema = altered_schema(&schema, &columns);
+
+    let mut partitions = Vec::with_capacity(table.batches.len());
+    for partition in &table.batches {
+        let batches = partition.read().await;
+        if let Some((field, _)) = columns.iter().find(|(field, index)| {
+            index.is_none()
+                && !field.is_nullable()
+                && batches.iter().any(|b| b.num_rows() > 0)
+        }) {
+            return plan_err!(
+                "Can not add NOT NULL column '{}' to a table with rows",
+                field.name()
+            );
+        }
+        let batches = batches
+            .iter()
+            .map(|batch| {
+                let arrays = columns
+                    .iter()
+                    .map(|(field, index)| match index {
+                        Some(index) => Arc::clone(batch.column(*index)),
+                        None => new_null_array(field.data_type(), batch.num_rows()),
+                    })
+                    .collect();
+                RecordBatch::try_new(Arc::clone(&new_schema), arrays)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        partitions.push(batches);
+    }
+
+    let mut altered = MemTable::try_new(new_schema, partitions)?;
+
+    // The original columns that remain are first, in their original order
+    let indices = columns
+        .iter()
+        .filter_map(|(_, index)| *index)
+        .collect::<Vec<_>>();
+    if let Some(constraints) = table
+        .constraints()
+        .and_then(|constraints| constraints.project(&indices))
+    {
+        altered = altered.with_constraints(constraints);
+    }
+    let column_defaults = columns
+        .iter()
+        .filter_map(|(field, index)| {
+            let old_name = schema.field((*index)?).name();
+            let default = table.get_column_default(old_name)?;
+            Some((field.name().clone(), default.clone()))
+        })
+        .collect::<HashMap<_, _>>();
+    Ok(altered.with_column_defaults(column_defaults))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use datafusion_common::assert_contains;
+
+    #[tokio::test]
+    async fn alter_memory_table_columns() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t (a INT, b VARCHAR DEFAULT 'x', c INT)")
+            .await?;
+        ctx.sql("INSERT INTO t (a, c) VALUES (1, 10), (2, 20)")
+            .await?
+            .collect()
+            .await?;
+
+        ctx.sql("ALTER TABLE t DROP COLUMN c").await?;
+        ctx.sql("ALTER TABLE t ADD COLUMN d DOUBLE").await?;
+        ctx.sql("ALTER TABLE t RENAME COLUMN b TO e").await?;
+        ctx.sql("INSERT INTO t (a) VALUES (3)")
+            .await?
+            .collect()
+            .await?;
+
+        let batches = ctx
+            .sql("SELECT * FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(
+            [
+                "+---+---+---+",
+                "| a | e | d |",
+                "+---+---+---+",
+                "| 1 | x |   |",
+                "| 2 | x |   |",
+                "| 3 | x |   |",
+                "+---+---+---+",
+            ],
+            &batches
+        );
+
+        let err = ctx
+            .sql("ALTER TABLE t ADD COLUMN f INT NOT NULL")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Can not add NOT NULL column 'f'");
+        let err = ctx
+            .sql("ALTER TABLE t RENAME COLUMN a TO d")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Column 'd' already exists");
+        let err = ctx.sql("ALTER TABLE t DROP COLUMN x").await.unwrap_err();
+        assert_contains!(err.to_string(), "Column 'x' does not exist");
+        ctx.sql("ALTER TABLE IF EXISTS missing DROP COLUMN x")
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn alter_listing_table_columns() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("1.json"), r#"{"a": 1, "b": "x"}"#)?;
+
+        let ctx = SessionContext::new();
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t STORED AS JSON LOCATION '{}/'",
+            dir.path().display()
+        ))
+        .await?;
+
+        ctx.sql("ALTER TABLE t ADD COLUMN c INT").await?;
+        ctx.sql("ALTER TABLE t DROP COLUMN b").await?;
+        let batches = ctx.sql("SELECT * FROM t").await?.collect().await?;
+        assert_batches_eq!(
+            [
+                "+---+---+",
+                "| a | c |",
+                "+---+---+",
+                "| 1 |   |",
+                "+---+---+",
+            ],
+            &batches
+        );
+
+        // columns missing from the files must be nullable
+        let err = ctx
+            .sql("ALTER TABLE t ADD COLUMN d INT NOT NULL")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Non-nullable column 'd' is missing");
+        // columns stored in the files can not be renamed
+        let err = ctx
+            .sql("ALTER TABLE t RENAME COLUMN a TO e")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Can not rename column 'a'");
+        ctx.sql("ALTER TABLE t RENAME COLUMN c TO e").await?;
+        assert_eq!(ctx.table("t").await?.schema().field_names(), ["t.a", "t.e"]);
+        Ok(())
+    }
+}
@@ -17,6 +17,7 @@
 
 //! [`SessionContext`] API for registering data sources and executing queries
 
+use std::any::Any;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::sync::{Arc, Weak};
@@ -24,17 +25,24 @@ use std::time::Duration;
 
 use super::options::ReadOptions;
 use crate::datasource::dynamic_file::DynamicListTableFactory;
+use crate::execution::query_middleware::{QueryMiddleware, QueryMiddlewareMetrics};
 use crate::execution::session_state::SessionStateBuilder;
 use crate::{
     catalog::listing_schema::ListingSchemaProvider,
     catalog::{
-        CatalogProvider, CatalogProviderList, TableProvider, TableProviderFactory,
+        AsyncCatalogProviderList, CatalogProvider, CatalogProviderList, TableProvider,
+        TableProviderFactory,
     },
     dataframe::DataFrame,
     datasource::listing::{
         ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+        changes::{ChangeTrackingTable, ChangesFunc},
+    },
+    datasource::{
+        MemTable, StatisticsCollectingTable, SyntheticStatisticsTable, ViewTable,
+        blob::{BlobSlicePushdown, BlobTable},
+        provider_as_source,
     },
-    datasource::{MemTable, ViewTable, provider_as_source},
     error::Result,
     execution::{
         FunctionRegistry,
@@ -46,8 +54,9 @@ use crate::{
     logical_expr::{
         CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
         CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, Execute, LogicalPlan, LogicalPlanBuilder, Prepare, ResetVariable,
-        SetVariable, TableType, UNNAMED_TABLE,
+        DropView, Estimate, Execute, ExecuteImmediate, LogicalPlan, LogicalPlanBuilder,
+        Prepare, RefreshTableSchema, ResetVariable, SetVariable, TableType,
+        UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
     physical_plan::ExecutionPlan,
@@ -57,7 +66,8 @@ use crate::{
 // backwards compatibility
 pub use crate::execution::session_state::SessionState;
 
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::array::{BooleanArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_catalog::MemoryCatalogProvider;
 use datafusion_catalog::memory::MemorySchemaProvider;
@@ -66,8 +76,10 @@ use datafusion_catalog::{
 };
 use datafusion_common::config::{ConfigField, ConfigOptions};
 use datafusion_common::metadata::ScalarAndMetadata;
+use datafusion_common::timezone::resolve_time_zone;
 use datafusion_common::{
-    DFSchema, DataFusionError, ParamValues, SchemaReference, TableReference,
+    DFSchema, DataFusionError, Diagnostic, ParamValues, ScalarValue, SchemaReference,
+    Statistics, TableReference,
     config::{ConfigExtension, TableOptions},
     exec_datafusion_err, exec_err, internal_datafusion_err, not_impl_err,
     plan_datafusion_err, plan_err,
@@ -87,10 +99,11 @@ use datafusion_expr::HigherOrderUDF;
 pub use datafusion_expr::execution_props::ExecutionProps;
 #[cfg(feature = "sql")]
 use datafusion_expr::planner::RelationPlanner;
+use datafusion_expr::registry::{CastRegistryRef, UserDefinedCast};
 use datafusion_expr::simplify::SimplifyContext;
 use datafusion_expr::{
-    Expr, UserDefinedLogicalNode, WindowUDF,
-    expr_rewriter::FunctionRewrite,
+    Expr, PlanRewrite, PolicyProvider, UserDefinedLogicalNode, WindowUDF,
+    expr_rewriter::{FunctionRewrite, OperatorOverload},
     logical_plan::{DdlStatement, Statement},
     planner::ExprPlanner,
 };
@@ -98,6 +111,9 @@ use datafusion_optimizer::analyzer::type_coercion::TypeCoercion;
 use datafusion_optimizer::simplify_expressions::ExprSimplifier;
 use datafusion_optimizer::{Analyzer, OptimizerContext};
 use datafusion_optimizer::{AnalyzerRule, OptimizerRule};
+use datafusion_physical_optimizer::{
+    PhysicalOptimizerRule, PhysicalOptimizerRulePosition,
+};
 use datafusion_session::SessionStore;
 
 use async_trait::async_trait;
@@ -107,6 +123,7 @@ use parking_lot::RwLock;
 use url::Url;
 
 mod csv;
+#[cfg(feature = "json")]
 mod json;
 #[cfg(feature = "parquet")]
 mod parquet;
@@ -114,6 +131,23 @@ mod parquet;
 #[cfg(feature = "avro")]
 mod avro;
 
+mod alter_table;
+mod reconfigure;
+pub use reconfigure::{ConfigChangeHook, RECONFIGURABLE_OPTIONS};
+#[cfg(feature = "sql")]
+mod script;
+#[cfg(feature = "sql")]
+pub use script::{
+    ScriptOptions, ScriptResult, ScriptStatementOutput, ScriptStatementResult,
+};
+mod sql_function;
+pub use sql_function::SqlFunctionFactory;
+
+#[cfg(feature = "serde")]
+mod serde_rows;
+#[cfg(feature = "serde")]
+pub use serde_rows::{record_batch_from_serde_rows, serde_rows_from_record_batches};
+
 /// DataFilePaths adds a method to convert strings and vector of strings to vector of [`ListingTableUrl`] URLs.
 /// This allows methods such [`SessionContext::read_csv`] and [`SessionContext::read_avro`]
 /// to take either a single file or multiple files.
@@ -296,6 +330,9 @@ pub struct SessionContext {
     session_start_time: DateTime<Utc>,
     /// Shared session state for the session
     state: Arc<RwLock<SessionState>>,
+    /// Functions called when an option is changed, see
+    /// [`Self::register_config_change_hook`]
+    config_change_hooks: Arc<RwLock<Vec<ConfigChangeHook>>>,
 }
 
 impl Default for SessionContext {
@@ -366,11 +403,52 @@ impl SessionContext {
     pub fn new_with_state(state: SessionState) -> Self {
         Self {
             session_id: state.session_id().to_string(),
-            session_start_time: Utc::now(),
+            session_start_time: state
+                .execution_props()
+                .clock()
+                .map_or_else(Utc::now, |clock| clock.now()),
             state: Arc::new(RwLock::new(state)),
+            config_change_hooks: Default::default(),
         }
     }
 
+    /// Creates a lightweight child of this `SessionContext`, for example to
+    /// run a single query of a service that handles many concurrent requests.
+    ///
+    /// The child shares the catalogs, functions and [`RuntimeEnv`] (memory
+    /// pool, object stores and caches) of this context, but has its own
+    /// configuration and warnings. Tables and views created in the child are
+    /// only visible to the child and disappear with it, and tables of this
+    /// context can not be dropped from the child. See
+    /// [`SessionState::new_child`] for details.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// ctx.sql("CREATE TABLE shared AS VALUES (1)").await?;
+    ///
+    /// let child = ctx.new_child();
+    /// child.sql("SET datafusion.execution.batch_size = 1024").await?;
+    /// child.sql("CREATE TABLE scratch AS SELECT * FROM shared").await?;
+    /// assert!(child.table_exist("scratch")?);
+    /// assert!(!ctx.table_exist("scratch")?);
+    /// assert_eq!(ctx.state().config().batch_size(), 8192);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_child(&self) -> SessionContext {
+        let child = Self::new_with_state(self.state.read().new_child());
+        child
+            .config_change_hooks
+            .write()
+            .clone_from(&self.config_change_hooks.read());
+        child
+    }
+
     /// Enable querying local files as tables.
     ///
     /// This feature is security sensitive and should only be enabled for
@@ -411,8 +489,46 @@ impl SessionContext {
     /// # }
     /// ```
     pub fn enable_url_table(self) -> Self {
+        self.enable_url_table_with_factory(DynamicListTableFactory::new(
+            SessionStore::new(),
+        ))
+    }
+
+    /// Enable querying files as tables, like [`Self::enable_url_table`], but
+    /// only for files and directories under one of `roots`.
+    ///
+    /// Each root is a path or url such as `/data/warehouse` or
+    /// `s3://bucket/prefix`. References to other locations are treated as
+    /// unknown tables. See [`DynamicListTableFactory::with_allowed_roots`]
+    /// for details.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new().enable_url_table_with_allowed_roots(["tests/data"])?;
+    /// ctx.sql("SELECT * FROM 'tests/data/example.csv'").await?;
+    /// assert!(ctx.sql("SELECT * FROM 'tests/tpch-csv/customer.csv'").await.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enable_url_table_with_allowed_roots(
+        self,
+        roots: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Self> {
+        let roots = roots
+            .into_iter()
+            .map(ListingTableUrl::parse)
+            .collect::<Result<Vec<_>>>()?;
+        let factory =
+            DynamicListTableFactory::new(SessionStore::new()).with_allowed_roots(roots);
+        Ok(self.enable_url_table_with_factory(factory))
+    }
+
+    fn enable_url_table_with_factory(self, factory: DynamicListTableFactory) -> Self {
         let current_catalog_list = Arc::clone(self.state.read().catalog_list());
-        let factory = Arc::new(DynamicListTableFactory::new(SessionStore::new()));
+        let factory = Arc::new(factory);
         let catalog_list = Arc::new(DynamicFileCatalog::new(
             current_catalog_list,
             Arc::clone(&factory) as Arc<dyn UrlTableFactory>,
@@ -457,6 +573,7 @@ impl SessionContext {
             session_id: _,
             session_start_time: _,
             state,
+            config_change_hooks: _,
         } = self;
         let state = match Arc::try_unwrap(state) {
             Ok(rwlock) => rwlock.into_inner(),
@@ -494,6 +611,18 @@ impl SessionContext {
         self.state.write().remove_optimizer_rule(name)
     }
 
+    /// Inserts a physical optimizer rule at `position` relative to the
+    /// existing rules, see [`SessionState::add_physical_optimizer_rule`].
+    pub fn add_physical_optimizer_rule(
+        &self,
+        physical_optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+        position: PhysicalOptimizerRulePosition,
+    ) -> Result<()> {
+        self.state
+            .write()
+            .add_physical_optimizer_rule(physical_optimizer_rule, position)
+    }
+
     /// Adds an analyzer rule to the end of the existing rules.
     ///
     /// See [`SessionState`] for more control of when the rule is applied.
@@ -501,6 +630,48 @@ impl SessionContext {
         self.state.write().add_analyzer_rule(analyzer_rule);
     }
 
+    /// Registers a [`PlanRewrite`] that is applied to the analyzed plan of
+    /// queries, before they are optimized.
+    ///
+    /// See [`PlanRewrite`] for more details.
+    pub fn register_plan_rewrite(&self, rewrite: Arc<dyn PlanRewrite + Send + Sync>) {
+        self.state.write().register_plan_rewrite(rewrite);
+    }
+
+    /// Registers a [`PolicyProvider`] that enforces row-level security and
+    /// column masking on the tables read by queries.
+    ///
+    /// See [`PolicyProvider`] for more details.
+    pub fn register_policy_provider(&self, provider: Arc<dyn PolicyProvider>) {
+        self.state.write().register_policy_provider(provider);
+    }
+
+    /// Registers a [`QueryMiddleware`] at the end of the chain of middlewares
+    /// rewriting the SQL text and the logical plans of queries, before they
+    /// are optimized.
+    ///
+    /// See [`QueryMiddleware`] for more details.
+    pub fn register_query_middleware(&self, middleware: Arc<dyn QueryMiddleware>) {
+        self.state.write().register_query_middleware(middleware);
+    }
+
+    /// Returns the metrics of the [`QueryMiddleware`] named `name`, if any
+    pub fn query_middleware_metrics(
+        &self,
+        name: &str,
+    ) -> Option<Arc<QueryMiddlewareMetrics>> {
+        self.state.read().query_middleware_metrics(name)
+    }
+
+    /// Registers an [`OperatorOverload`] that rewrites a binary operator on
+    /// operands of specific types, such as `geometry @> geometry`, to a call
+    /// of a scalar function.
+    ///
+    /// See [`OperatorOverload`] for more details.
+    pub fn register_operator_overload(&self, overload: Arc<OperatorOverload>) {
+        self.state.write().register_operator_overload(overload);
+    }
+
     /// Registers an [`ObjectStore`] to be used with a specific URL prefix.
     ///
     /// See [`RuntimeEnv::register_object_store`] for more details.
@@ -551,6 +722,21 @@ impl SessionContext {
         self.session_id.clone()
     }
 
+    /// Return the non-fatal warnings (such as ignored table hints) collected
+    /// while planning and executing queries in this session.
+    ///
+    /// Warnings accumulate until they are removed with
+    /// [`Self::take_warnings`].
+    pub fn warnings(&self) -> Vec<Diagnostic> {
+        self.state.read().warnings().warnings()
+    }
+
+    /// Remove and return the non-fatal warnings collected so far, see
+    /// [`Self::warnings`]
+    pub fn take_warnings(&self) -> Vec<Diagnostic> {
+        self.state.read().warnings().take()
+    }
+
     /// Return the [`TableProviderFactory`] that is registered for the
     /// specified file type, if any.
     pub fn table_factory(
@@ -675,6 +861,40 @@ impl SessionContext {
         self.state.read().create_logical_expr(sql, df_schema)
     }
 
+    /// Creates an [`Expr`] from SQL text created by `to_sql_string`, which
+    /// plans back to the expression that was converted to SQL text.
+    ///
+    /// Expressions can be stored as text this way, for example the
+    /// definitions of computed columns, and planned again later. See
+    /// [`SessionState::parse_expr`] for how this differs from
+    /// [`Self::parse_sql_expr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use arrow::datatypes::{DataType, Field, Schema};
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::sql::unparser::ExprSqlExt;
+    /// # use datafusion::sql::unparser::dialect::DefaultDialect;
+    /// # use datafusion_common::{DFSchema, Result, ScalarValue};
+    /// # fn main() -> Result<()> {
+    /// let schema = Schema::new(vec![Field::new("a", DataType::Int8, true)]);
+    /// let df_schema = DFSchema::try_from(schema)?;
+    /// let expr = col("a").eq(lit(ScalarValue::Int8(Some(1))));
+    ///
+    /// let sql = expr.to_sql_string(&DefaultDialect {})?;
+    /// assert_eq!(sql, "(a = CAST(1 AS TINYINT))");
+    /// let parsed = SessionContext::new().parse_expr(&sql, &df_schema)?;
+    /// assert_eq!(parsed, expr);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sql")]
+    pub fn parse_expr(&self, sql: &str, df_schema: &DFSchema) -> Result<Expr> {
+        self.state.read().parse_expr(sql, df_schema)
+    }
+
     /// Execute the [`LogicalPlan`], return a [`DataFrame`]. This API
     /// is not featured limited (so all SQL such as `CREATE TABLE` and
     /// `COPY` will be run).
@@ -717,6 +937,12 @@ impl SessionContext {
                     DdlStatement::DropFunction(cmd) => {
                         Box::pin(self.drop_function(cmd)).await
                     }
+                    DdlStatement::RefreshTableSchema(cmd) => {
+                        Box::pin(self.refresh_table_schema(cmd)).await
+                    }
+                    DdlStatement::AlterTable(cmd) => {
+                        Box::pin(self.alter_table(cmd)).await
+                    }
                     ddl => Ok(DataFrame::new(self.state(), LogicalPlan::Ddl(ddl))),
                 }
             }
@@ -763,6 +989,13 @@ impl SessionContext {
             LogicalPlan::Statement(Statement::Execute(execute)) => {
                 self.execute_prepared(execute)
             }
+            LogicalPlan::Statement(Statement::ExecuteImmediate(execute)) => {
+                let plan = Box::pin(self.plan_execute_immediate(execute)).await?;
+                Box::pin(self.execute_logical_plan(plan)).await
+            }
+            LogicalPlan::Statement(Statement::Estimate(estimate)) => {
+                Box::pin(self.estimate(estimate)).await
+            }
             LogicalPlan::Statement(Statement::Deallocate(deallocate)) => {
                 self.state
                     .write()
@@ -1033,13 +1266,12 @@ impl SessionContext {
         let DropTable {
             name, if_exists, ..
         } = cmd;
-        let result = self
+        let dropped = self
             .find_and_deregister(name.clone(), TableType::Base)
-            .await;
-        match (result, if_exists) {
-            (Ok(true), _) => self.return_empty_dataframe(),
-            (_, true) => self.return_empty_dataframe(),
-            (_, _) => exec_err!("Table '{name}' doesn't exist."),
+            .await?;
+        match (dropped, if_exists) {
+            (true, _) | (_, true) => self.return_empty_dataframe(),
+            (false, false) => exec_err!("Table '{name}' doesn't exist."),
         }
     }
 
@@ -1047,13 +1279,12 @@ impl SessionContext {
         let DropView {
             name, if_exists, ..
         } = cmd;
-        let result = self
+        let dropped = self
             .find_and_deregister(name.clone(), TableType::View)
-            .await;
-        match (result, if_exists) {
-            (Ok(true), _) => self.return_empty_dataframe(),
-            (_, true) => self.return_empty_dataframe(),
-            (_, _) => exec_err!("View '{name}' doesn't exist."),
+            .await?;
+        match (dropped, if_exists) {
+            (true, _) | (_, true) => self.return_empty_dataframe(),
+            (false, false) => exec_err!("View '{name}' doesn't exist."),
         }
     }
 
@@ -1097,31 +1328,47 @@ impl SessionContext {
             variable, value, ..
         } = stmt;
 
+        // Resolve the time zone, so that unknown time zones are rejected when
+        // they are set rather than when they are used
+        let value = if variable == "datafusion.execution.time_zone" {
+            let database = Arc::clone(self.state.read().time_zone_database());
+            resolve_time_zone(database.as_ref(), &value)?
+        } else {
+            value
+        };
+
         // Check if this is a runtime configuration
         if variable.starts_with("datafusion.runtime.") {
             self.set_runtime_variable(&variable, &value)?;
         } else {
-            let mut state = self.state.write();
-            state.config_mut().options_mut().set(&variable, &value)?;
-
-            // Re-initialize any UDFs that depend on configuration
-            // This allows both built-in and custom functions to respond to configuration changes
-            let config_options = state.config().options();
-
-            // Collect updated UDFs in a separate vector
-            let udfs_to_update: Vec<_> = state
-                .scalar_functions()
-                .values()
-                .filter_map(|udf| {
-                    udf.inner()
-                        .with_updated_config(config_options)
-                        .map(Arc::new)
-                })
-                .collect();
+            self.set_session_variable(&variable, &value)?;
+        }
+        self.notify_config_change(&variable, &value);
 
-            for udf in udfs_to_update {
-                state.register_udf(udf)?;
-            }
+        Ok(())
+    }
+
+    fn set_session_variable(&self, variable: &str, value: &str) -> Result<()> {
+        let mut state = self.state.write();
+        state.config_mut().options_mut().set(variable, value)?;
+
+        // Re-initialize any UDFs that depend on configuration
+        // This allows both built-in and custom functions to respond to configuration changes
+        let config_options = state.config().options();
+
+        // Collect updated UDFs in a separate vector
+        let udfs_to_update: Vec<_> = state
+            .scalar_functions()
+            .values()
+            .filter_map(|udf| {
+                udf.inner()
+                    .with_updated_config(config_options)
+                    .map(Arc::new)
+            })
+            .collect();
+
+        for udf in udfs_to_update {
+            state.register_udf(udf)?;
         }
 
         Ok(())
@@ -1183,6 +1430,10 @@ impl SessionContext {
                 let duration = Self::parse_duration(variable, value)?;
                 builder.with_object_list_cache_ttl(Some(duration))
             }
+            "file_range_cache_limit" => {
+                let limit = Self::parse_capacity_limit(variable, value)?;
+                builder.with_file_range_cache_limit(limit)
+            }
             _ => return plan_err!("Unknown runtime configuration: {variable}"),
             // Remember to update `reset_runtime_variable()` when adding new options
         };
@@ -1222,6 +1473,9 @@ impl SessionContext {
                 builder =
                     builder.with_object_list_cache_ttl(DEFAULT_LIST_FILES_CACHE_TTL);
             }
+            "file_range_cache_limit" => {
+                builder = builder.with_file_range_cache_limit(0);
+            }
             _ => return plan_err!("Unknown runtime configuration: {variable}"),
         };
 
@@ -1483,6 +1737,22 @@ impl SessionContext {
         }
     }
 
+    async fn refresh_table_schema(&self, cmd: RefreshTableSchema) -> Result<DataFrame> {
+        let provider = self.table_provider(cmd.name.clone()).await?;
+        let Some(table) = provider.downcast_ref::<ListingTable>() else {
+            return plan_err!(
+                "REFRESH SCHEMA is only supported for listing tables, {} is not one",
+                cmd.name
+            );
+        };
+        let refreshed = table.with_refreshed_schema(&self.state()).await?;
+        if refreshed.schema() != table.schema() {
+            self.deregister_table(cmd.name.clone())?;
+            self.register_table(cmd.name, Arc::new(refreshed))?;
+        }
+        self.return_empty_dataframe()
+    }
+
     fn execute_prepared(&self, execute: Execute) -> Result<DataFrame> {
         let Execute {
             name, parameters, ..
@@ -1498,6 +1768,7 @@ impl SessionContext {
             .with_query_execution_start_time(
                 state.execution_props().query_execution_start_time,
             )
+            .with_cast_registry(state.execution_props().cast_registry().cloned())
             .build();
         let simplifier = ExprSimplifier::new(context);
 
@@ -1538,6 +1809,117 @@ impl SessionContext {
         Ok(DataFrame::new(self.state(), plan))
     }
 
+    /// Creates the physical plan of the input of an `EXPLAIN ESTIMATE`
+    /// statement without executing it, and returns a single row with the
+    /// number of rows and bytes the plan is estimated to return, as well as
+    /// its estimated cost: the sum of the estimated number of rows produced
+    /// by each operator of the plan, ignoring operators without estimate.
+    ///
+    /// `exact` is true when the number of rows is known exactly, e.g. from
+    /// the metadata of the scanned files.
+    async fn estimate(&self, estimate: Estimate) -> Result<DataFrame> {
+        let plan = self.state().create_physical_plan(&estimate.input).await?;
+        let statistics = plan.partition_statistics(None)?;
+
+        let mut cost = None;
+        let mut stack = vec![&plan];
+        while let Some(node) = stack.pop() {
+            let node_statistics = node.partition_statistics(None)?;
+            if let Some(rows) = node_statistics.num_rows.get_value() {
+                cost = Some(cost.unwrap_or(0_u64).saturating_add(*rows as u64));
+            }
+            stack.extend(node.children());
+        }
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("estimated_rows", DataType::UInt64, true),
+            Field::new("estimated_bytes", DataType::UInt64, true),
+            Field::new("estimated_cost", DataType::UInt64, true),
+            Field::new("exact", DataType::Boolean, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(UInt64Array::from(vec![
+                    statistics.num_rows.get_value().map(|rows| *rows as u64),
+                ])),
+                Arc::new(UInt64Array::from(vec![
+                    statistics
+                        .total_byte_size
+                        .get_value()
+                        .map(|bytes| *bytes as u64),
+                ])),
+                Arc::new(UInt64Array::from(vec![cost])),
+                Arc::new(BooleanArray::from(vec![
+                    statistics.num_rows.is_exact().unwrap_or(false),
+                ])),
+            ],
+        )?;
+        self.read_batch(batch)
+    }
+
+    /// Evaluates the SQL string and the parameters of an `EXECUTE IMMEDIATE`
+    /// statement, and returns the plan of the SQL string with the parameters
+    /// bound to its placeholders
+    async fn plan_execute_immediate(
+        &self,
+        execute: ExecuteImmediate,
+    ) -> Result<LogicalPlan> {
+        let ExecuteImmediate { sql, parameters } = execute;
+
+        // Evaluate all expressions with a query, so that they may reference
+        // variables or subqueries
+        let exprs = std::iter::once(sql)
+            .chain(parameters)
+            .enumerate()
+            .map(|(i, expr)| expr.alias(format!("${i}")));
+        let plan = LogicalPlanBuilder::empty(true).project(exprs)?.build()?;
+        let batches = DataFrame::new(self.state(), plan).collect().await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| {
+                internal_datafusion_err!("EXECUTE IMMEDIATE returned no row")
+            })?;
+        let mut values = batch
+            .columns()
+            .iter()
+            .map(|array| ScalarValue::try_from_array(array, 0));
+
+        let sql = values.next().transpose()?.unwrap_or(ScalarValue::Null);
+        let Some(Some(sql)) = sql.try_as_str() else {
+            return plan_err!(
+                "EXECUTE IMMEDIATE requires a non null SQL string, got {sql:?}"
+            );
+        };
+        let plan = self.plan_sql(sql).await?;
+
+        // Cast the params to the types inferred for their placeholders
+        let fields = plan.get_parameter_fields()?;
+        let params = values
+            .enumerate()
+            .map(|(i, value)| {
+                let value = ScalarAndMetadata::from(value?);
+                match fields.get(&format!("${}", i + 1)) {
+                    Some(Some(field)) => value.cast_storage_to(field.data_type()),
+                    _ => Ok(value),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        plan.replace_params_with_values(&ParamValues::List(params))
+    }
+
+    #[cfg(feature = "sql")]
+    async fn plan_sql(&self, sql: &str) -> Result<LogicalPlan> {
+        self.state().create_logical_plan(sql).await
+    }
+
+    #[cfg(not(feature = "sql"))]
+    async fn plan_sql(&self, _sql: &str) -> Result<LogicalPlan> {
+        not_impl_err!("EXECUTE IMMEDIATE requires the sql feature")
+    }
+
     /// Registers a variable provider within this context.
     pub fn register_variable(
         &self,
@@ -1591,6 +1973,14 @@ impl SessionContext {
         self.state.write().register_udwf(Arc::new(f)).ok();
     }
 
+    /// Registers a [`UserDefinedCast`] within this context, which is used by
+    /// `CAST` expressions and type coercion to convert between types Arrow
+    /// can not cast between, such as extension types.
+    pub fn register_cast(&self, cast: Arc<dyn UserDefinedCast>) -> Result<()> {
+        self.state.write().register_cast(cast)?;
+        Ok(())
+    }
+
     #[cfg(feature = "sql")]
     /// Registers a [`RelationPlanner`] to customize SQL table-factor planning.
     ///
@@ -1623,6 +2013,11 @@ impl SessionContext {
         self.state.write().deregister_udtf(name).ok();
     }
 
+    /// Deregisters a [`UserDefinedCast`] within this context.
+    pub fn deregister_cast(&self, name: &str) {
+        self.state.write().deregister_cast(name).ok();
+    }
+
     /// Creates a [`DataFrame`] for reading a data source.
     ///
     /// For more control such as reading multiple files, you can use
@@ -1897,6 +2292,158 @@ impl SessionContext {
         Ok(DataFrame::new(self.state(), plan))
     }
 
+    /// Returns a [`DataFrame`] with the keys of the rows that were added,
+    /// removed or changed between the tables `old` and `new`, matching rows on
+    /// the `keys` columns.
+    ///
+    /// See [`DataFrame::diff`] for details.
+    pub async fn table_diff(
+        &self,
+        old: impl Into<TableReference>,
+        new: impl Into<TableReference>,
+        keys: &[&str],
+    ) -> Result<DataFrame> {
+        let old = self.table(old).await?;
+        let new = self.table(new).await?;
+        old.diff(new, keys)
+    }
+
+    /// Attaches synthetic [`Statistics`] to a registered table, replacing
+    /// any statistics attached before, so that queries are planned as if the
+    /// table had the given number of rows, distinct values, etc.
+    ///
+    /// This is useful to test optimizer behavior and plan shapes without
+    /// generating data at scale. See [`SyntheticStatisticsTable`] for details.
+    ///
+    /// Returns an error if the table does not exist, or if `statistics` does
+    /// not have one [`ColumnStatistics`] per column of the table.
+    ///
+    /// [`ColumnStatistics`]: datafusion_common::ColumnStatistics
+    pub async fn set_table_statistics(
+        &self,
+        table_ref: impl Into<TableReference>,
+        statistics: Statistics,
+    ) -> Result<()> {
+        let table_ref: TableReference = table_ref.into();
+        let mut provider = self.table_provider(table_ref.clone()).await?;
+        if let Some(table) = provider.downcast_ref::<SyntheticStatisticsTable>() {
+            provider = Arc::clone(table.inner());
+        }
+        let table = SyntheticStatisticsTable::try_new(provider, statistics)?;
+        self.deregister_table(table_ref.clone())?;
+        self.register_table(table_ref, Arc::new(table))?;
+        Ok(())
+    }
+
+    /// Collects the statistics of a registered table while queries read all
+    /// of its rows, so that later queries are planned with them, replacing
+    /// any collection enabled before.
+    ///
+    /// `top_k` is the number of most frequent values collected per column.
+    /// See [`StatisticsCollectingTable`] for details.
+    ///
+    /// Returns an error if the table does not exist.
+    pub async fn enable_table_statistics_collection(
+        &self,
+        table_ref: impl Into<TableReference>,
+        top_k: usize,
+    ) -> Result<()> {
+        let table_ref: TableReference = table_ref.into();
+        let mut provider = self.table_provider(table_ref.clone()).await?;
+        if let Some(table) = provider.downcast_ref::<StatisticsCollectingTable>() {
+            provider = Arc::clone(table.inner());
+        }
+        let table = StatisticsCollectingTable::new(provider).with_top_k(top_k);
+        self.deregister_table(table_ref.clone())?;
+        self.register_table(table_ref, Arc::new(table))?;
+        Ok(())
+    }
+
+    /// Tracks the files added to a registered [`ListingTable`], so that the
+    /// rows of the files added since a snapshot can be read with the
+    /// `changes('table', snapshot)` table function, or since the latest
+    /// snapshot with `changes('table')`.
+    ///
+    /// The table is replaced by the returned [`ChangeTrackingTable`], whose
+    /// [`ChangeTrackingTable::snapshot`] records the snapshots. If the changes
+    /// of the table are already tracked, its tracking table is returned.
+    ///
+    /// Returns an error if the table does not exist or is not a
+    /// [`ListingTable`], or if another function is registered as `changes`.
+    pub async fn enable_change_tracking(
+        &self,
+        table_ref: impl Into<TableReference>,
+    ) -> Result<ChangeTrackingTable> {
+        let table_ref: TableReference = table_ref.into();
+        let provider = self.table_provider(table_ref.clone()).await?;
+
+        let function = match self.table_function("changes") {
+            Ok(function) => Arc::clone(function.function()),
+            Err(_) => {
+                let function: Arc<dyn TableFunctionImpl> =
+                    Arc::new(ChangesFunc::default());
+                self.register_udtf("changes", Arc::clone(&function));
+                function
+            }
+        };
+        let Some(changes) = (function.as_ref() as &dyn Any).downcast_ref::<ChangesFunc>()
+        else {
+            return plan_err!(
+                "Can not track the changes of {table_ref}: the table function changes is already registered"
+            );
+        };
+
+        let table = if let Some(table) = provider.downcast_ref::<ChangeTrackingTable>() {
+            table.clone()
+        } else if let Some(table) = provider.downcast_ref::<ListingTable>() {
+            let table = ChangeTrackingTable::new(Arc::new(table.clone()));
+            self.deregister_table(table_ref.clone())?;
+            self.register_table(table_ref.clone(), Arc::new(table.clone()))?;
+            table
+        } else {
+            return plan_err!(
+                "Can not track the changes of {table_ref}: only the changes of listing tables can be tracked"
+            );
+        };
+        changes.register(table_ref, table.clone());
+        Ok(table)
+    }
+
+    /// Stores the values of the `blob_columns` of a registered table out of
+    /// line: the columns contain the locations of the values in `store`, each
+    /// read as values of the given type, which are only fetched when queries
+    /// read them.
+    ///
+    /// The table is replaced by a [`BlobTable`], and the
+    /// [`BlobSlicePushdown`] optimizer rule is added if it is missing, so that
+    /// queries reading a prefix or the length of the values only fetch what
+    /// they read. Calling this again replaces the blob columns of the table.
+    ///
+    /// Returns an error if the table does not exist, or if a blob column does
+    /// not exist or does not contain strings.
+    pub async fn enable_blob_columns(
+        &self,
+        table_ref: impl Into<TableReference>,
+        store: Arc<dyn ObjectStore>,
+        blob_columns: Vec<(String, DataType)>,
+    ) -> Result<()> {
+        let table_ref: TableReference = table_ref.into();
+        let mut provider = self.table_provider(table_ref.clone()).await?;
+        if let Some(table) = provider.downcast_ref::<BlobTable>() {
+            provider = Arc::clone(table.inner());
+        }
+        let table = BlobTable::try_new(table_ref.clone(), provider, store, blob_columns)?;
+        self.deregister_table(table_ref.clone())?;
+        self.register_table(table_ref, Arc::new(table))?;
+
+        let rule = BlobSlicePushdown::new();
+        let mut state = self.state.write();
+        if !state.optimizers().iter().any(|r| r.name() == rule.name()) {
+            state.append_optimizer_rule(Arc::new(rule));
+        }
+        Ok(())
+    }
+
     /// Retrieves a [`TableFunction`] reference by name.
     ///
     /// Returns an error if no table function has been registered with the provided name.
@@ -1918,11 +2465,32 @@ impl SessionContext {
     ) -> Result<Arc<dyn TableProvider>> {
         let table_ref = table_ref.into();
         let table = table_ref.table().to_string();
-        let schema = self.state.read().schema_for_ref(table_ref)?;
-        match schema.table(&table).await? {
-            Some(ref provider) => Ok(Arc::clone(provider)),
-            _ => plan_err!("No table named '{table}'"),
+        if self.state.read().table_resolver().is_some() {
+            let state = self.state();
+            let resolved = state.resolve_table_ref(table_ref.clone());
+            if let Some(table_resolver) = state.table_resolver()
+                && let Some(provider) = table_resolver
+                    .resolve_table(&resolved, state.config())
+                    .await?
+            {
+                return Ok(provider);
+            }
         }
+        let schema = self.state.read().schema_for_ref(table_ref.clone());
+        if let Ok(schema) = &schema
+            && let Some(provider) = schema.table(&table).await?
+        {
+            return Ok(provider);
+        }
+        let async_catalog = self.state.read().async_catalog().cloned();
+        if let Some(async_catalog) = async_catalog {
+            let resolved = self.state.read().resolve_table_ref(table_ref);
+            if let Some(provider) = async_catalog.table(&resolved).await? {
+                return Ok(provider);
+            }
+        }
+        schema?;
+        plan_err!("No table named '{table}'")
     }
 
     /// Get a new TaskContext to run in this session
@@ -1963,6 +2531,16 @@ impl SessionContext {
         self.state.write().register_catalog_list(catalog_list)
     }
 
+    /// Registers an [`AsyncCatalogProviderList`], such as a remote metastore,
+    /// to resolve the tables that are not found in the catalogs from. See
+    /// [`SessionState::set_async_catalog_list`]
+    pub fn register_async_catalog_list(
+        &self,
+        catalog_list: Arc<dyn AsyncCatalogProviderList>,
+    ) {
+        self.state.write().set_async_catalog_list(catalog_list)
+    }
+
     /// Registers a [`ConfigExtension`] as a table option extension that can be
     /// referenced from SQL statements executed against this context.
     pub fn register_table_options_extension<T: ConfigExtension>(&self, extension: T) {
@@ -2033,6 +2611,10 @@ impl FunctionRegistry for SessionContext {
         self.state.write().register_expr_planner(expr_planner)
     }
 
+    fn cast_registry(&self) -> Option<CastRegistryRef> {
+        self.state.read().execution_props().cast_registry().cloned()
+    }
+
     fn higher_order_function_names(&self) -> HashSet<String> {
         self.state.read().higher_order_function_names()
     }
@@ -2091,9 +2673,9 @@ pub trait QueryPlanner: Debug {
 /// DataFusion will parse `CREATE FUNCTION` statements into [`CreateFunction`]
 /// structs and pass them to the [`create`](Self::create) method.
 ///
-/// Note there is no default implementation of this trait provided in DataFusion,
-/// because the implementation and requirements vary widely. Please see
-/// [function_factory example] for a reference implementation.
+/// DataFusion provides [`SqlFunctionFactory`] for functions whose body is a
+/// SQL expression. Other kinds of functions require a custom implementation,
+/// see the [function_factory example] for a reference implementation.
 ///
 /// [function_factory example]: https://github.com/apache/datafusion/blob/main/datafusion-examples/examples/builtin_functions/function_factory.rs
 ///
@@ -2247,9 +2829,20 @@ impl<'n> TreeNodeVisitor<'n> for BadPlanVisitor<'_> {
             LogicalPlan::Copy(_) if !self.options.allow_dml => {
                 plan_err!("DML not supported: COPY")
             }
+            // EXPLAIN ESTIMATE only plans its input, which is checked below
+            LogicalPlan::Statement(Statement::Estimate(_)) => {
+                Ok(TreeNodeRecursion::Continue)
+            }
             LogicalPlan::Statement(stmt) if !self.options.allow_statements => {
                 plan_err!("Statement not supported: {}", stmt.name())
             }
+            // the SQL string may contain any statement, which is only known
+            // once it is executed
+            LogicalPlan::Statement(Statement::ExecuteImmediate(_))
+                if !self.options.allow_ddl || !self.options.allow_dml =>
+            {
+                plan_err!("EXECUTE IMMEDIATE requires DDL and DML to be allowed")
+            }
             _ => Ok(TreeNodeRecursion::Continue),
         }
     }
@@ -2263,8 +2856,8 @@ mod tests {
     use crate::test_util::{plan_and_collect, populate_csv_partitions};
     use arrow::datatypes::{DataType, TimeUnit};
     use arrow_schema::FieldRef;
-    use datafusion_common::DataFusionError;
     use datafusion_common::datatype::DataTypeExt;
+    use datafusion_common::{DataFusionError, assert_contains};
     use std::error::Error;
     use std::path::PathBuf;
 
@@ -2280,6 +2873,143 @@ mod tests {
     use sqlparser::ast;
     use tempfile::TempDir;
 
+    #[tokio::test]
+    async fn ignored_table_hints_are_reported_as_warnings() -> Result<()> {
+        let ctx = SessionContext::new_with_config(
+            SessionConfig::new().set_str("datafusion.sql_parser.dialect", "mssql"),
+        );
+        ctx.sql("CREATE TABLE t AS VALUES (1)")
+            .await?
+            .collect()
+            .await?;
+        assert!(ctx.warnings().is_empty());
+
+        ctx.sql("SELECT * FROM t WITH (NOLOCK)")
+            .await?
+            .collect()
+            .await?;
+        let warnings = ctx.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "table hints for 't' are not supported and were ignored"
+        );
+        assert!(ctx.warnings().is_empty());
+
+        // the collector is shared with the task context
+        let task_ctx = ctx.task_ctx();
+        let collector = task_ctx
+            .session_config()
+            .get_extension::<datafusion_common::diagnostic::WarningCollector>()
+            .unwrap();
+        collector.report(Diagnostic::new_warning("from execution", None));
+        assert_eq!(ctx.take_warnings()[0].message, "from execution");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn plan_mistakes_are_reported_as_warnings() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t (a INT, b VARCHAR) AS VALUES (1, 'x'), (2, 'y')")
+            .await?
+            .collect()
+            .await?;
+        ctx.sql("CREATE VIEW v AS SELECT b, a FROM t")
+            .await?
+            .collect()
+            .await?;
+        assert!(ctx.take_warnings().is_empty());
+
+        let sql = "WITH c AS (SELECT a, b, a + 1 AS a1 FROM t) \
+            SELECT c.a FROM c WHERE c.b = NULL \
+            UNION ALL SELECT s.a FROM (SELECT b, a FROM t) s WHERE s.a IS NULL OR true";
+        ctx.sql(sql).await?.collect().await?;
+        let messages = ctx
+            .take_warnings()
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 4, "{messages:?}");
+        assert_contains!(&messages[0], "is never true, so no rows pass it");
+        assert_contains!(&messages[1], "is always true and has no effect");
+        assert_eq!(messages[2], "column 'c.a1' is selected but never used");
+        assert_eq!(messages[3], "column 's.b' is selected but never used");
+
+        // the columns of views and `SELECT *` are not reported, nor are
+        // predicates that are written as literals
+        ctx.sql("SELECT a FROM v").await?.collect().await?;
+        ctx.sql("SELECT s.a FROM (SELECT * FROM t) s WHERE false")
+            .await?
+            .collect()
+            .await?;
+        assert!(ctx.take_warnings().is_empty());
+
+        let result = ctx
+            .sql("SELECT CAST('x' AS INT) FROM t")
+            .await?
+            .collect()
+            .await;
+        assert!(result.is_err());
+        assert_contains!(
+            &ctx.take_warnings()[0].message,
+            "cast of Utf8(\"x\") to Int32 always fails"
+        );
+
+        ctx.sql("SET datafusion.optimizer.report_plan_warnings = false")
+            .await?
+            .collect()
+            .await?;
+        ctx.sql("SELECT a FROM t WHERE a = NULL")
+            .await?
+            .collect()
+            .await?;
+        assert!(ctx.take_warnings().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn child_contexts_are_isolated() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t AS VALUES (1), (2)").await?;
+        let child = ctx.new_child();
+        let other = ctx.new_child();
+        assert_ne!(child.session_id(), ctx.session_id());
+        assert!(Arc::ptr_eq(&child.runtime_env(), &ctx.runtime_env()));
+
+        child.sql("CREATE TABLE c AS SELECT * FROM t").await?;
+        child.sql("CREATE SCHEMA s").await?;
+        child.sql("SET datafusion.execution.batch_size = 1").await?;
+        assert!(child.table_exist("c")?);
+        assert!(!ctx.table_exist("c")?);
+        assert!(!other.table_exist("c")?);
+        assert!(ctx.catalog("datafusion").unwrap().schema("s").is_none());
+        assert_eq!(child.state().config().batch_size(), 1);
+        assert_eq!(ctx.state().config().batch_size(), 8192);
+
+        // tables of the parent are visible, including ones created later,
+        // but can not be dropped
+        ctx.sql("CREATE TABLE later AS VALUES (3)").await?;
+        let results = child
+            .sql("SELECT count(*) FROM c JOIN later ON true")
+            .await?
+            .collect()
+            .await?;
+        assert_snapshot!(batches_to_string(&results), @r"
+        +----------+
+        | count(*) |
+        +----------+
+        | 2        |
+        +----------+
+        ");
+        let err = child.sql("DROP TABLE t").await.unwrap_err();
+        assert_contains!(err.to_string(), "belongs to the parent session");
+        assert!(ctx.table_exist("t")?);
+
+        child.sql("DROP TABLE c").await?;
+        assert!(!child.table_exist("c")?);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn shared_memory_and_disk_manager() {
         // Demonstrate the ability to share DiskManager and
@@ -2466,6 +3196,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_dynamic_file_query_allowed_roots() -> Result<()> {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+        let ctx = SessionContext::new().enable_url_table_with_allowed_roots([root
+            .join("tpch-csv")
+            .display()
+            .to_string()])?;
+
+        let allowed = root.join("tpch-csv/customer.csv");
+        let result = plan_and_collect(
+            &ctx,
+            &format!("select count(*) from '{}'", allowed.display()),
+        )
+        .await?;
+        assert_eq!(result[0].num_rows(), 1);
+
+        for denied in [
+            root.join("data/example.csv"),
+            // `..` segments are resolved before checking the roots
+            root.join("tpch-csv/../data/example.csv"),
+            // a root does not allow siblings sharing its name as prefix
+            root.join("tpch-csv-other/customer.csv"),
+        ] {
+            let err = ctx
+                .sql(&format!("select * from '{}'", denied.display()))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("not found"), "{err}");
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn custom_query_planner() -> Result<()> {
         let runtime = Arc::new(RuntimeEnv::default());
@@ -2684,6 +3446,56 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn explain_estimate() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t AS VALUES (1), (2), (3)")
+            .await?
+            .collect()
+            .await?;
+
+        let estimate = |sql: &'static str| {
+            let ctx = ctx.clone();
+            async move {
+                let batches = ctx.sql(sql).await?.collect().await?;
+                assert_eq!(batches.len(), 1);
+                assert_eq!(batches[0].num_rows(), 1);
+                batches[0]
+                    .columns()
+                    .iter()
+                    .map(|array| ScalarValue::try_from_array(array, 0))
+                    .collect::<Result<Vec<_>>>()
+            }
+        };
+
+        // the rows of a memory table are known exactly
+        let values = estimate("EXPLAIN ESTIMATE SELECT * FROM t").await?;
+        assert_eq!(values[0], ScalarValue::UInt64(Some(3)));
+        assert!(!values[1].is_null());
+        assert!(matches!(values[2], ScalarValue::UInt64(Some(cost)) if cost >= 3));
+        assert_eq!(values[3], ScalarValue::Boolean(Some(true)));
+
+        // filters make the estimate inexact
+        let values =
+            estimate("EXPLAIN ESTIMATE SELECT * FROM t WHERE column1 > 1").await?;
+        assert_eq!(values[3], ScalarValue::Boolean(Some(false)));
+
+        // EXPLAIN ESTIMATE is allowed when statements are not, but not the
+        // statements it estimates
+        let options = SQLOptions::new().with_allow_statements(false);
+        ctx.sql_with_options("EXPLAIN ESTIMATE SELECT * FROM t", options)
+            .await?;
+        let err = ctx
+            .sql_with_options(
+                "EXPLAIN ESTIMATE DROP TABLE t",
+                options.with_allow_ddl(false),
+            )
+            .await
+            .unwrap_err();
+        assert_contains!(err.strip_backtrace(), "DDL not supported: DropTable");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn custom_type_planner() -> Result<()> {
         let state = SessionStateBuilder::new()
@@ -2845,6 +3657,106 @@ mod tests {
         Ok(())
     }
 
+    #[derive(Debug)]
+    struct PositionedRule {
+        name: &'static str,
+        preserves_ordering: bool,
+    }
+
+    impl PhysicalOptimizerRule for PositionedRule {
+        fn optimize(
+            &self,
+            plan: Arc<dyn ExecutionPlan>,
+            _config: &ConfigOptions,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(plan)
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn schema_check(&self) -> bool {
+            true
+        }
+
+        fn preserves_ordering(&self) -> bool {
+            self.preserves_ordering
+        }
+    }
+
+    #[test]
+    fn add_physical_optimizer_rule_at_position() -> Result<()> {
+        let ctx = SessionContext::new();
+        let rule_names = |ctx: &SessionContext| {
+            ctx.state()
+                .physical_optimizers()
+                .iter()
+                .map(|rule| rule.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        let index_of =
+            |names: &[String], name: &str| names.iter().position(|n| n == name).unwrap();
+
+        ctx.add_physical_optimizer_rule(
+            Arc::new(PositionedRule {
+                name: "before_sorting",
+                preserves_ordering: false,
+            }),
+            PhysicalOptimizerRulePosition::Before("EnforceSorting".to_string()),
+        )?;
+        ctx.add_physical_optimizer_rule(
+            Arc::new(PositionedRule {
+                name: "after_sorting",
+                preserves_ordering: true,
+            }),
+            PhysicalOptimizerRulePosition::After("EnforceSorting".to_string()),
+        )?;
+        ctx.add_physical_optimizer_rule(
+            Arc::new(PositionedRule {
+                name: "first",
+                preserves_ordering: false,
+            }),
+            PhysicalOptimizerRulePosition::First,
+        )?;
+
+        let names = rule_names(&ctx);
+        let sorting = index_of(&names, "EnforceSorting");
+        assert_eq!(index_of(&names, "before_sorting"), sorting - 1);
+        assert_eq!(index_of(&names, "after_sorting"), sorting + 1);
+        assert_eq!(index_of(&names, "first"), 0);
+
+        // unknown reference rule
+        let err = ctx
+            .add_physical_optimizer_rule(
+                Arc::new(PositionedRule {
+                    name: "unknown",
+                    preserves_ordering: true,
+                }),
+                PhysicalOptimizerRulePosition::After("NoSuchRule".to_string()),
+            )
+            .unwrap_err();
+        assert_contains!(err.to_string(), "NoSuchRule");
+
+        // rules that may break orderings can not run after EnforceSorting
+        let err = ctx
+            .add_physical_optimizer_rule(
+                Arc::new(PositionedRule {
+                    name: "last",
+                    preserves_ordering: false,
+                }),
+                PhysicalOptimizerRulePosition::Last,
+            )
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Physical optimizer rule last does not preserve ordering and must run before EnforceSorting"
+        );
+        assert!(!rule_names(&ctx).contains(&"last".to_string()));
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_duration() {
         const LIST_FILES_CACHE_TTL: &str = "datafusion.runtime.list_files_cache_ttl";
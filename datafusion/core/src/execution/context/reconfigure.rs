@@ -0,0 +1,178 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Changing options of a live [`SessionContext`]
+
+use std::sync::Arc;
+
+use super::{Result, SessionContext};
+use datafusion_common::plan_err;
+
+/// Options that can be changed with [`SessionContext::reconfigure`]
+pub const RECONFIGURABLE_OPTIONS: &[&str] = &[
+    "datafusion.execution.batch_size",
+    "datafusion.execution.target_partitions",
+    "datafusion.execution.planning_concurrency",
+    "datafusion.execution.sort_spill_reservation_bytes",
+    "datafusion.runtime.memory_limit",
+    "datafusion.runtime.max_temp_directory_size",
+];
+
+/// Function called with the key and new value of each option changed on a
+/// [`SessionContext`], see [`SessionContext::register_config_change_hook`]
+pub type ConfigChangeHook = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+impl SessionContext {
+    /// Change options of this context while it is in use, for example to
+    /// tune a long running server under load.
+    ///
+    /// Only the options listed in [`RECONFIGURABLE_OPTIONS`] can be changed.
+    /// All settings are validated before any is applied, so either all or
+    /// none of them take effect. The new values are used by queries planned
+    /// after this call returns, queries that are already running are not
+    /// affected. In particular a new `datafusion.runtime.memory_limit`
+    /// creates a new memory pool for subsequent queries.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// ctx.reconfigure([
+    ///     ("datafusion.execution.target_partitions", "2"),
+    ///     ("datafusion.runtime.memory_limit", "512M"),
+    /// ])?;
+    /// assert_eq!(ctx.copied_config().target_partitions(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reconfigure<K, V>(
+        &self,
+        settings: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<()>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let settings = settings
+            .into_iter()
+            .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
+            .collect::<Vec<_>>();
+
+        let mut options = self.state.read().config_options().as_ref().clone();
+        for (key, value) in &settings {
+            if !RECONFIGURABLE_OPTIONS.contains(&key.as_str()) {
+                return plan_err!(
+                    "Option '{key}' can not be changed at runtime, expected one of: {}",
+                    RECONFIGURABLE_OPTIONS.join(", ")
+                );
+            }
+            if key.starts_with("datafusion.runtime.") {
+                Self::parse_capacity_limit(key, value)?;
+            } else {
+                options.set(key, value)?;
+            }
+        }
+
+        for (key, value) in &settings {
+            if key.starts_with("datafusion.runtime.") {
+                self.set_runtime_variable(key, value)?;
+            } else {
+                self.set_session_variable(key, value)?;
+            }
+            self.notify_config_change(key, value);
+        }
+        Ok(())
+    }
+
+    /// Register a function that is called after an option of this context is
+    /// changed with [`Self::reconfigure`] or a `SET` statement.
+    ///
+    /// The hook receives the option's key and new value. It is shared by all
+    /// clones of this context.
+    pub fn register_config_change_hook(
+        &self,
+        hook: impl Fn(&str, &str) + Send + Sync + 'static,
+    ) {
+        self.config_change_hooks.write().push(Arc::new(hook));
+    }
+
+    pub(super) fn notify_config_change(&self, key: &str, value: &str) {
+        let hooks = self.config_change_hooks.read().clone();
+        for hook in hooks {
+            hook(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_execution::memory_pool::MemoryLimit;
+    use parking_lot::Mutex;
+
+    #[tokio::test]
+    async fn reconfigure_live_context() -> Result<()> {
+        let ctx = SessionContext::new();
+        let changes = Arc::new(Mutex::new(vec![]));
+        let hook_changes = Arc::clone(&changes);
+        ctx.register_config_change_hook(move |key, value| {
+            hook_changes.lock().push(format!("{key}={value}"));
+        });
+
+        ctx.clone().reconfigure([
+            ("datafusion.execution.batch_size", "100"),
+            ("datafusion.runtime.memory_limit", "1M"),
+        ])?;
+        assert_eq!(ctx.copied_config().batch_size(), 100);
+        assert!(matches!(
+            ctx.state().runtime_env().memory_pool.memory_limit(),
+            MemoryLimit::Finite(limit) if limit == 1024 * 1024
+        ));
+
+        ctx.sql("SET datafusion.execution.target_partitions = 3")
+            .await?;
+        assert_eq!(
+            *changes.lock(),
+            [
+                "datafusion.execution.batch_size=100",
+                "datafusion.runtime.memory_limit=1M",
+                "datafusion.execution.target_partitions=3",
+            ]
+        );
+
+        // nothing is applied if any of the settings is rejected
+        let err = ctx
+            .reconfigure([
+                ("datafusion.execution.batch_size", "200"),
+                ("datafusion.optimizer.max_passes", "1"),
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("can not be changed at runtime"));
+        let err = ctx
+            .reconfigure([
+                ("datafusion.execution.batch_size", "200"),
+                ("datafusion.execution.target_partitions", "many"),
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("many"), "{err}");
+        assert_eq!(ctx.copied_config().batch_size(), 100);
+        assert_eq!(changes.lock().len(), 3);
+        Ok(())
+    }
+}
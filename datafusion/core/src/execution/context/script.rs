@@ -0,0 +1,448 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SessionContext::execute_script`]: running scripts of multiple SQL
+//! statements
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{Result, SQLOptions, SessionContext};
+use arrow::array::AsArray;
+use arrow::datatypes::{DataType, UInt64Type};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{ScalarValue, exec_datafusion_err, plan_err};
+use datafusion_expr::LogicalPlan;
+use datafusion_expr::var_provider::{VarProvider, VarType};
+use datafusion_sql::parser::Statement as DFStatement;
+use parking_lot::RwLock;
+use sqlparser::ast::{Expr as SQLExpr, Set, Statement as SQLStatement};
+
+/// Options for running a script with
+/// [`SessionContext::execute_script_with_options`]
+#[derive(Clone, Debug, Default)]
+pub struct ScriptOptions {
+    /// See [`Self::with_sql_options`]
+    sql_options: SQLOptions,
+    /// See [`Self::with_continue_on_error`]
+    continue_on_error: bool,
+    /// See [`Self::with_variable`]
+    variables: HashMap<String, ScalarValue>,
+}
+
+impl ScriptOptions {
+    /// Create a new `ScriptOptions` with default values
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The statements that can be run by the script. Defaults to all
+    /// statements, see [`SQLOptions`].
+    pub fn with_sql_options(mut self, sql_options: SQLOptions) -> Self {
+        self.sql_options = sql_options;
+        self
+    }
+
+    /// Should the remaining statements be run after a statement fails?
+    /// Defaults to `false`, stopping the script at the first error.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Set the initial value of the variable `@name`
+    pub fn with_variable(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<ScalarValue>,
+    ) -> Self {
+        let name = name.into();
+        let name = name.strip_prefix('@').map(str::to_string).unwrap_or(name);
+        self.variables.insert(name, value.into());
+        self
+    }
+}
+
+/// The result of running a script, see [`SessionContext::execute_script`]
+#[derive(Debug)]
+pub struct ScriptResult {
+    /// The results of the statements that were run, in order
+    pub statements: Vec<ScriptStatementResult>,
+    /// The values of the script variables once the script finished, without
+    /// the leading `@`
+    pub variables: HashMap<String, ScalarValue>,
+}
+
+impl ScriptResult {
+    /// Returns true if all the statements of the script succeeded
+    pub fn is_success(&self) -> bool {
+        self.statements
+            .iter()
+            .all(|statement| statement.output.is_ok())
+    }
+}
+
+/// The result of one statement of a script
+#[derive(Debug)]
+pub struct ScriptStatementResult {
+    /// The SQL text of the statement
+    pub sql: String,
+    /// The output of the statement, or the error it failed with
+    pub output: Result<ScriptStatementOutput>,
+}
+
+/// The output of a statement of a script that succeeded
+#[derive(Debug, Clone)]
+pub struct ScriptStatementOutput {
+    /// The batches returned by the statement
+    pub batches: Vec<RecordBatch>,
+    /// The number of rows inserted, updated or deleted by a DML statement, or
+    /// returned by any other statement
+    pub row_count: u64,
+}
+
+/// The `@variable`s of a script, shared by its statements.
+///
+/// Variables not set by the script are looked up in the provider of user
+/// defined variables that was registered before the script.
+#[derive(Debug)]
+struct ScriptVariables {
+    values: RwLock<HashMap<String, ScalarValue>>,
+    fallback: Option<Arc<dyn VarProvider + Send + Sync>>,
+}
+
+impl ScriptVariables {
+    fn get(&self, var_names: &[String]) -> Option<ScalarValue> {
+        let [name] = var_names else {
+            return None;
+        };
+        let name = name.strip_prefix('@')?;
+        self.values.read().get(name).cloned()
+    }
+}
+
+impl VarProvider for ScriptVariables {
+    fn get_value(&self, var_names: Vec<String>) -> Result<ScalarValue> {
+        if let Some(value) = self.get(&var_names) {
+            return Ok(value);
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.get_value(var_names),
+            None => Err(exec_datafusion_err!(
+                "Variable {} is not defined",
+                var_names.join(".")
+            )),
+        }
+    }
+
+    fn get_type(&self, var_names: &[String]) -> Option<DataType> {
+        match self.get(var_names) {
+            Some(value) => Some(value.data_type()),
+            None => self.fallback.as_ref()?.get_type(var_names),
+        }
+    }
+}
+
+/// Returns the name, without the leading `@`, and value of a
+/// `SET @name = value` statement
+fn variable_assignment(statement: &DFStatement) -> Option<(String, &SQLExpr)> {
+    let DFStatement::Statement(statement) = statement else {
+        return None;
+    };
+    let SQLStatement::Set(Set::SingleAssignment {
+        scope: None,
+        hivevar: false,
+        variable,
+        values,
+    }) = statement.as_ref()
+    else {
+        return None;
+    };
+    let name = variable.to_string();
+    let [value] = values.as_slice() else {
+        return None;
+    };
+    if name.starts_with("@@") {
+        return None;
+    }
+    Some((name.strip_prefix('@')?.to_string(), value))
+}
+
+impl SessionContext {
+    /// Runs a script of SQL statements separated by `;`, such as a migration
+    /// script, stopping at the first statement that fails.
+    ///
+    /// See [`Self::execute_script_with_options`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let result = ctx
+    ///     .execute_script(
+    ///         "CREATE TABLE t (x INT);
+    ///          SET @start = 10;
+    ///          INSERT INTO t VALUES (@start), (@start + 1);
+    ///          SELECT * FROM t WHERE x > @start;",
+    ///     )
+    ///     .await?;
+    /// assert!(result.is_success());
+    /// assert_eq!(result.statements[2].output.as_ref().unwrap().row_count, 2);
+    /// assert_eq!(result.statements[3].output.as_ref().unwrap().row_count, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_script(&self, sql: &str) -> Result<ScriptResult> {
+        self.execute_script_with_options(sql, ScriptOptions::new())
+            .await
+    }
+
+    /// Runs a script of SQL statements separated by `;`, validating each
+    /// statement with the [`SQLOptions`] of `options`.
+    ///
+    /// The statements run one after the other and are fully executed, so
+    /// each statement sees the tables, views and settings created by the
+    /// previous ones. Returns an error only if the script can not be parsed;
+    /// the errors of the statements are part of the [`ScriptResult`].
+    ///
+    /// # Variables
+    ///
+    /// `SET @name = expr` evaluates `expr` and assigns the result to the
+    /// variable `@name`, which the following statements can reference. Initial
+    /// values can be passed with [`ScriptOptions::with_variable`] and the
+    /// final values are returned in [`ScriptResult::variables`].
+    ///
+    /// The variables are registered with this context as the provider of
+    /// [`VarType::UserDefined`] variables, so they are also visible to later
+    /// queries of the session.
+    ///
+    /// # Dynamic SQL
+    ///
+    /// `EXECUTE IMMEDIATE sql [USING param, ...]` runs a SQL string built
+    /// from variables, such as `'DROP TABLE ' || @table`. As the statement is
+    /// unknown until it runs, it requires [`SQLOptions`] that allow DDL and DML.
+    pub async fn execute_script_with_options(
+        &self,
+        sql: &str,
+        options: ScriptOptions,
+    ) -> Result<ScriptResult> {
+        let statements = {
+            let state = self.state.read();
+            let dialect = state.config().options().sql_parser.dialect;
+            state.sql_to_statements(sql, &dialect)?
+        };
+
+        let fallback = self
+            .state
+            .read()
+            .execution_props()
+            .get_var_provider(VarType::UserDefined);
+        let variables = Arc::new(ScriptVariables {
+            values: RwLock::new(options.variables),
+            fallback,
+        });
+        self.register_variable(
+            VarType::UserDefined,
+            Arc::clone(&variables) as Arc<dyn VarProvider + Send + Sync>,
+        );
+
+        let mut results = Vec::with_capacity(statements.len());
+        for statement in statements {
+            let sql = statement.to_string();
+            let output = match variable_assignment(&statement) {
+                Some((name, value)) => {
+                    self.assign_variable(&variables, name, value, options.sql_options)
+                        .await
+                }
+                None => self.execute_statement(statement, options.sql_options).await,
+            };
+            let failed = output.is_err();
+            results.push(ScriptStatementResult { sql, output });
+            if failed && !options.continue_on_error {
+                break;
+            }
+        }
+
+        let variables = variables.values.read().clone();
+        Ok(ScriptResult {
+            statements: results,
+            variables,
+        })
+    }
+
+    /// Plans and runs one statement of a script
+    async fn execute_statement(
+        &self,
+        statement: DFStatement,
+        sql_options: SQLOptions,
+    ) -> Result<ScriptStatementOutput> {
+        let plan = self.state().statement_to_plan(statement).await?;
+        sql_options.verify_plan(&plan)?;
+
+        let is_dml = matches!(plan, LogicalPlan::Dml(_) | LogicalPlan::Copy(_));
+        let batches = self.execute_logical_plan(plan).await?.collect().await?;
+        let row_count = if is_dml {
+            // DML statements return the number of rows they modified
+            batches
+                .iter()
+                .filter_map(|batch| batch.column(0).as_primitive_opt::<UInt64Type>())
+                .flat_map(|counts| counts.iter().flatten())
+                .sum()
+        } else {
+            batches.iter().map(|batch| batch.num_rows() as u64).sum()
+        };
+        Ok(ScriptStatementOutput { batches, row_count })
+    }
+
+    /// Runs a `SET @name = value` statement of a script
+    async fn assign_variable(
+        &self,
+        variables: &ScriptVariables,
+        name: String,
+        value: &SQLExpr,
+        sql_options: SQLOptions,
+    ) -> Result<ScriptStatementOutput> {
+        if !sql_options.allow_statements {
+            return plan_err!("Statement not supported: SetVariable");
+        }
+        let batches = self
+            .sql_with_options(&format!("SELECT {value}"), sql_options)
+            .await?
+            .collect()
+            .await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| exec_datafusion_err!("No value for variable @{name}"))?;
+        let value = ScalarValue::try_from_array(batch.column(0), 0)?;
+        variables.values.write().insert(name, value);
+        Ok(ScriptStatementOutput {
+            batches: vec![],
+            row_count: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_common::test_util::batches_to_sort_string;
+
+    #[tokio::test]
+    async fn script_variables() -> Result<()> {
+        let ctx = SessionContext::new();
+        let options = ScriptOptions::new().with_variable("@limit", 2_i64);
+        let result = ctx
+            .execute_script_with_options(
+                "CREATE TABLE t AS VALUES (1), (2), (3);
+                 SET @total = (SELECT sum(column1) FROM t);
+                 SELECT column1, @total AS total FROM t WHERE column1 <= @limit;",
+                options,
+            )
+            .await?;
+        assert!(result.is_success());
+        assert_eq!(result.statements.len(), 3);
+        assert_eq!(result.variables["total"], ScalarValue::Int64(Some(6)));
+
+        let output = result.statements[2].output.as_ref().unwrap();
+        assert_eq!(output.row_count, 2);
+        insta::assert_snapshot!(batches_to_sort_string(&output.batches), @r"
+        +---------+-------+
+        | column1 | total |
+        +---------+-------+
+        | 1       | 6     |
+        | 2       | 6     |
+        +---------+-------+
+        ");
+
+        // the variables are visible to later queries of the session
+        let batches = ctx.sql("SELECT @total + 1 AS x").await?.collect().await?;
+        insta::assert_snapshot!(batches_to_sort_string(&batches), @r"
+        +---+
+        | x |
+        +---+
+        | 7 |
+        +---+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn script_errors() -> Result<()> {
+        let ctx = SessionContext::new();
+        let sql = "CREATE TABLE t (x INT);
+                   INSERT INTO missing VALUES (1);
+                   INSERT INTO t VALUES (1), (2);";
+
+        // stops at the first error by default
+        let result = ctx.execute_script(sql).await?;
+        assert!(!result.is_success());
+        assert_eq!(result.statements.len(), 2);
+        assert!(result.statements[1].output.is_err());
+
+        let ctx = SessionContext::new();
+        let options = ScriptOptions::new().with_continue_on_error(true);
+        let result = ctx.execute_script_with_options(sql, options).await?;
+        assert_eq!(result.statements.len(), 3);
+        assert!(result.statements[1].output.is_err());
+        assert_eq!(result.statements[2].output.as_ref().unwrap().row_count, 2);
+
+        // statements are validated with the SQL options
+        let options = ScriptOptions::new()
+            .with_sql_options(SQLOptions::new().with_allow_ddl(false))
+            .with_continue_on_error(true);
+        let result = ctx
+            .execute_script_with_options("CREATE TABLE u (x INT); SELECT 1;", options)
+            .await?;
+        assert!(result.statements[0].output.is_err());
+        assert!(result.statements[1].output.is_ok());
+        assert!(!ctx.table_exist("u")?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn script_execute_immediate() -> Result<()> {
+        let ctx = SessionContext::new();
+        let options = ScriptOptions::new().with_variable("@table", "items");
+        let result = ctx
+            .execute_script_with_options(
+                "EXECUTE IMMEDIATE 'CREATE TABLE ' || @table || ' (x INT)';
+                 SET @start = 10;
+                 EXECUTE IMMEDIATE 'INSERT INTO ' || @table || ' VALUES ($1), ($2)'
+                     USING @start, @start + 1;
+                 SELECT * FROM items WHERE x >= @start;",
+                options,
+            )
+            .await?;
+        assert!(result.is_success());
+        assert_eq!(result.statements[3].output.as_ref().unwrap().row_count, 2);
+
+        // the statement to run is unknown until it is executed, so it is
+        // rejected if the SQL options restrict the statements
+        let options = ScriptOptions::new()
+            .with_sql_options(SQLOptions::new().with_allow_ddl(false));
+        let result = ctx
+            .execute_script_with_options("EXECUTE IMMEDIATE 'DROP TABLE items'", options)
+            .await?;
+        assert!(!result.is_success());
+        assert!(ctx.table_exist("items")?);
+        Ok(())
+    }
+}
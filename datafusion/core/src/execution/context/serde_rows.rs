@@ -0,0 +1,295 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion between Rust values implementing [`serde`] traits and
+//! [`RecordBatch`]es
+
+use std::sync::Arc;
+
+use super::{DataFrame, Result, SessionContext};
+use crate::datasource::TableProvider;
+use arrow::datatypes::SchemaRef;
+use arrow::json::reader::{ReaderBuilder, infer_json_schema_from_iterator};
+use arrow::json::writer::{JsonArray, WriterBuilder};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DataFusionError, TableReference};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Convert `rows` to a [`RecordBatch`], with one column per field of `T`.
+///
+/// If `schema` is `None` it is inferred from the serialized rows: integers
+/// become `Int64`, floating point numbers `Float64`, strings `Utf8`, nested
+/// structs `Struct` and sequences `List` columns. Pass a schema to control
+/// the column types, for example to read timestamps or decimals.
+///
+/// # Example
+/// ```
+/// # use datafusion::error::Result;
+/// # use datafusion::execution::context::record_batch_from_serde_rows;
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     x: i64,
+///     y: f64,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let rows = vec![Point { x: 1, y: 2.5 }, Point { x: 2, y: 0.5 }];
+/// let batch = record_batch_from_serde_rows(&rows, None)?;
+/// assert_eq!(batch.num_rows(), 2);
+/// assert_eq!(batch.schema().field(0).name(), "x");
+/// # Ok(())
+/// # }
+/// ```
+pub fn record_batch_from_serde_rows<T: Serialize>(
+    rows: &[T],
+    schema: Option<SchemaRef>,
+) -> Result<RecordBatch> {
+    let schema = match schema {
+        Some(schema) => schema,
+        None => {
+            let values = rows
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            Arc::new(infer_json_schema_from_iterator(values.into_iter().map(Ok))?)
+        }
+    };
+
+    let mut decoder = ReaderBuilder::new(Arc::clone(&schema))
+        .with_batch_size(rows.len().max(1))
+        .build_decoder()?;
+    decoder.serialize(rows)?;
+    Ok(decoder
+        .flush()?
+        .unwrap_or_else(|| RecordBatch::new_empty(schema)))
+}
+
+/// Convert the rows of `batches` to values of type `T`, matching columns to
+/// fields of `T` by name.
+///
+/// This is the inverse of [`record_batch_from_serde_rows`]. The rows are
+/// converted through JSON values, which limits the values that round trip:
+///
+/// * `NaN` and infinite floating point values are read as `null`, so they can
+///   only be read into `Option` fields, as `None`
+/// * decimals are read as numbers, so they can be read into floating point
+///   fields, which may round them
+/// * dates and timestamps are read as strings, so their fields must
+///   deserialize from their string representation, e.g.
+///   `"2024-01-01T00:00:00"`
+pub fn serde_rows_from_record_batches<T: DeserializeOwned>(
+    batches: &[RecordBatch],
+) -> Result<Vec<T>> {
+    if batches.iter().all(|batch| batch.num_rows() == 0) {
+        return Ok(vec![]);
+    }
+
+    let mut writer = WriterBuilder::new()
+        .with_explicit_nulls(true)
+        .build::<_, JsonArray>(Vec::new());
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    writer.finish()?;
+    serde_json::from_slice(&writer.into_inner())
+        .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+impl SessionContext {
+    /// Create a [`DataFrame`] for reading `rows` of a type implementing
+    /// [`Serialize`], see [`record_batch_from_serde_rows`] for how the
+    /// schema is determined
+    pub fn read_serde_rows<T: Serialize>(
+        &self,
+        rows: &[T],
+        schema: Option<SchemaRef>,
+    ) -> Result<DataFrame> {
+        self.read_batch(record_batch_from_serde_rows(rows, schema)?)
+    }
+
+    /// Registers `rows` of a type implementing [`Serialize`] as a table that
+    /// can be referenced from SQL statements executed against this context.
+    ///
+    /// See [`record_batch_from_serde_rows`] for how the schema is determined
+    pub fn register_serde_rows<T: Serialize>(
+        &self,
+        table_ref: impl Into<TableReference>,
+        rows: &[T],
+        schema: Option<SchemaRef>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        self.register_batch(table_ref, record_batch_from_serde_rows(rows, schema)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{AsArray, Decimal128Array, TimestampSecondArray};
+    use arrow::datatypes::{DataType, Field, Float64Type, Schema, TimeUnit};
+    use datafusion_common::assert_contains;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Person {
+        name: String,
+        age: u32,
+        score: Option<f64>,
+        tags: Vec<String>,
+        address: Address,
+    }
+
+    fn people() -> Vec<Person> {
+        vec![
+            Person {
+                name: "alice".to_string(),
+                age: 34,
+                score: Some(1.5),
+                tags: vec!["a".to_string(), "b".to_string()],
+                address: Address {
+                    city: "Paris".to_string(),
+                },
+            },
+            Person {
+                name: "bob".to_string(),
+                age: 27,
+                score: None,
+                tags: vec![],
+                address: Address {
+                    city: "Oslo".to_string(),
+                },
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn register_serde_rows_roundtrip() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_serde_rows("people", &people(), None)?;
+
+        let batches = ctx
+            .sql("SELECT * FROM people ORDER BY name")
+            .await?
+            .collect()
+            .await?;
+        let schema = batches[0].schema();
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, ["name", "age", "score", "tags", "address"]);
+
+        let rows: Vec<Person> = serde_rows_from_record_batches(&batches)?;
+        assert_eq!(rows, people());
+
+        let df = ctx
+            .sql("SELECT age FROM people WHERE name = 'carol'")
+            .await?;
+        let rows: Vec<Person> = serde_rows_from_record_batches(&df.collect().await?)?;
+        assert!(rows.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_serde_rows_with_schema() -> Result<()> {
+        #[derive(Serialize)]
+        struct Row {
+            id: i32,
+        }
+
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let ctx = SessionContext::new();
+        let df = ctx.read_serde_rows(&[Row { id: 1 }, Row { id: 2 }], Some(schema))?;
+        assert_eq!(df.schema().field(0).data_type(), &DataType::Int32);
+        assert_eq!(df.count().await?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_floats_are_read_as_null() -> Result<()> {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Row {
+            x: f64,
+        }
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct NullableRow {
+            x: Option<f64>,
+        }
+
+        // writing keeps non-finite values
+        let rows = [
+            Row { x: f64::NAN },
+            Row { x: f64::INFINITY },
+            Row { x: 1.5 },
+        ];
+        let batch = record_batch_from_serde_rows(&rows, None)?;
+        let x = batch.column(0).as_primitive::<Float64Type>();
+        assert!(x.value(0).is_nan());
+        assert_eq!(x.value(1), f64::INFINITY);
+
+        // but reading goes through JSON, which has no representation for them
+        let rows: Vec<NullableRow> =
+            serde_rows_from_record_batches(std::slice::from_ref(&batch))?;
+        assert_eq!(
+            rows,
+            [
+                NullableRow { x: None },
+                NullableRow { x: None },
+                NullableRow { x: Some(1.5) }
+            ]
+        );
+        let err = serde_rows_from_record_batches::<Row>(&[batch]).unwrap_err();
+        assert_contains!(err.to_string(), "invalid type: null, expected f64");
+        Ok(())
+    }
+
+    #[test]
+    fn decimals_are_read_as_numbers_and_timestamps_as_strings() -> Result<()> {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Row {
+            price: f64,
+            ts: String,
+        }
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("price", DataType::Decimal128(5, 2), false),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(
+                    Decimal128Array::from(vec![150]).with_precision_and_scale(5, 2)?,
+                ),
+                Arc::new(TimestampSecondArray::from(vec![1_704_067_200])),
+            ],
+        )?;
+        let rows: Vec<Row> = serde_rows_from_record_batches(&[batch])?;
+        assert_eq!(
+            rows,
+            [Row {
+                price: 1.5,
+                ts: "2024-01-01T00:00:00".to_string()
+            }]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SqlFunctionFactory`]: `CREATE FUNCTION` support for functions whose
+//! body is a SQL expression
+
+use std::sync::Arc;
+
+use super::{FunctionFactory, RegisterFunction, Result, SessionState};
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{exec_datafusion_err, internal_err, not_impl_err, plan_err};
+use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyContext};
+use datafusion_expr::{
+    ColumnarValue, CreateFunction, Expr, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl,
+    Signature, TypeSignature, Volatility, cast,
+};
+use datafusion_optimizer::analyzer::type_coercion::TypeCoercionRewriter;
+
+/// A [`FunctionFactory`] for scalar functions whose body is a SQL
+/// expression over the function's parameters.
+///
+/// The function body is inlined into the calling query when it is
+/// optimized, so it is planned once per query and evaluated with the same
+/// vectorized kernels as any other expression. This makes it a lightweight
+/// way to define simple row-level transformations at runtime, for example
+/// from configuration files.
+///
+/// Parameters are referenced positionally (`$1`) or by name (`$a`) and may
+/// have default values. Only `LANGUAGE SQL` (the default) is supported.
+/// Scripting languages such as Lua or JavaScript are not embedded; use a SQL
+/// body or a native [`ScalarUDF`] instead.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion::error::Result;
+/// # use datafusion::execution::context::SqlFunctionFactory;
+/// # use datafusion::prelude::*;
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let ctx = SessionContext::new().with_function_factory(Arc::new(SqlFunctionFactory));
+/// ctx.sql("CREATE FUNCTION to_miles(km DOUBLE) RETURNS DOUBLE RETURN $km * 0.62137")
+///     .await?;
+/// let df = ctx.sql("SELECT to_miles(10.0)").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct SqlFunctionFactory;
+
+#[async_trait]
+impl FunctionFactory for SqlFunctionFactory {
+    async fn create(
+        &self,
+        _state: &SessionState,
+        statement: CreateFunction,
+    ) -> Result<RegisterFunction> {
+        let udf = SqlScalarFunction::try_new(statement)?;
+        Ok(RegisterFunction::Scalar(Arc::new(ScalarUDF::from(udf))))
+    }
+}
+
+/// A scalar function defined by a SQL expression, see [`SqlFunctionFactory`]
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct SqlScalarFunction {
+    name: String,
+    body: Expr,
+    return_type: DataType,
+    defaults: Vec<Option<Expr>>,
+    signature: Signature,
+}
+
+impl SqlScalarFunction {
+    fn try_new(statement: CreateFunction) -> Result<Self> {
+        let CreateFunction {
+            name,
+            args,
+            return_type,
+            params,
+            ..
+        } = statement;
+        if let Some(language) = &params.language
+            && !language.value.eq_ignore_ascii_case("sql")
+        {
+            return not_impl_err!(
+                "LANGUAGE {language} is not supported, only SQL expressions can be used as function bodies"
+            );
+        }
+        let Some(body) = params.function_body else {
+            return plan_err!("Function '{name}' must have a body");
+        };
+        let Some(return_type) = return_type else {
+            return plan_err!("Function '{name}' must declare a return type");
+        };
+
+        let args = args.unwrap_or_default();
+        let arg_types = args.iter().map(|a| a.data_type.clone()).collect::<Vec<_>>();
+        let defaults = args.into_iter().map(|a| a.default_expr).collect::<Vec<_>>();
+        let volatility = params.behavior.unwrap_or(Volatility::Volatile);
+        let signature = match defaults.iter().position(Option::is_some) {
+            // one signature for each number of trailing arguments left out
+            Some(first_default) => Signature::one_of(
+                (first_default..=arg_types.len())
+                    .map(|n| match n {
+                        0 => TypeSignature::Nullary,
+                        n => TypeSignature::Exact(arg_types[..n].to_vec()),
+                    })
+                    .collect(),
+                volatility,
+            ),
+            None => Signature::exact(arg_types, volatility),
+        };
+
+        Ok(Self {
+            name,
+            body,
+            return_type,
+            defaults,
+            signature,
+        })
+    }
+
+    /// Replace the parameter placeholders in the body with `args`, coercing
+    /// the result as the body was not type checked against the arguments
+    fn inline(&self, args: &[Expr], info: &SimplifyContext) -> Result<Expr> {
+        let body = self.body.clone().transform(|e| {
+            let Expr::Placeholder(placeholder) = &e else {
+                return Ok(Transformed::no(e));
+            };
+            let idx = placeholder
+                .id
+                .strip_prefix('$')
+                .and_then(|id| id.parse::<usize>().ok())
+                .and_then(|position| position.checked_sub(1))
+                .ok_or_else(|| {
+                    exec_datafusion_err!("Invalid parameter {}", placeholder.id)
+                })?;
+            match (args.get(idx), self.defaults.get(idx)) {
+                (Some(arg), _) => Ok(Transformed::yes(arg.clone())),
+                (None, Some(Some(default))) => Ok(Transformed::yes(default.clone())),
+                _ => plan_err!(
+                    "No value for parameter {} of function '{}'",
+                    placeholder.id,
+                    self.name
+                ),
+            }
+        })?;
+        let body = body
+            .data
+            .rewrite(&mut TypeCoercionRewriter::new(info.schema()))?
+            .data;
+        Ok(cast(body, self.return_type.clone()))
+    }
+}
+
+impl ScalarUDFImpl for SqlScalarFunction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        internal_err!(
+            "Function '{}' should have been inlined during simplification",
+            self.name
+        )
+    }
+
+    fn simplify(
+        &self,
+        args: Vec<Expr>,
+        info: &SimplifyContext,
+    ) -> Result<ExprSimplifyResult> {
+        Ok(ExprSimplifyResult::Simplified(self.inline(&args, info)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::SessionContext;
+    use datafusion_common::assert_batches_eq;
+
+    #[tokio::test]
+    async fn sql_function_factory() -> Result<()> {
+        let ctx =
+            SessionContext::new().with_function_factory(Arc::new(SqlFunctionFactory));
+        ctx.sql("CREATE FUNCTION add_n(a BIGINT, n BIGINT = 10) RETURNS BIGINT RETURN $a + $n")
+            .await?;
+        ctx.sql("CREATE FUNCTION greet(VARCHAR) RETURNS VARCHAR RETURN 'hello ' || $1")
+            .await?;
+
+        let batches = ctx
+            .sql("SELECT add_n(1) AS a, add_n(1, 2) AS b, greet('bob') AS c")
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(
+            [
+                "+----+---+-----------+",
+                "| a  | b | c         |",
+                "+----+---+-----------+",
+                "| 11 | 3 | hello bob |",
+                "+----+---+-----------+",
+            ],
+            &batches
+        );
+
+        // the body is inlined into the plan
+        let plan = ctx
+            .sql("SELECT add_n(1, 2) AS b")
+            .await?
+            .into_optimized_plan()?;
+        assert!(!plan.display_indent().to_string().contains("add_n"));
+
+        let err = ctx
+            .sql("CREATE FUNCTION f(BIGINT) RETURNS BIGINT LANGUAGE PYTHON AS 'return 1'")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("LANGUAGE PYTHON is not supported"));
+        Ok(())
+    }
+}
@@ -18,9 +18,12 @@
 //! Shared state for query planning and execution.
 
 pub mod context;
+pub mod query_middleware;
 pub mod session_state;
 pub use session_state::{SessionState, SessionStateBuilder};
 
+mod plan_warnings;
+
 mod session_state_defaults;
 
 pub use session_state_defaults::SessionStateDefaults;
@@ -0,0 +1,188 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Warnings about likely mistakes in analyzed [`LogicalPlan`]s
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::execution::session_state::SessionState;
+use datafusion_common::diagnostic::WarningCollector;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, DFSchemaRef, Diagnostic, Result, ScalarValue};
+use datafusion_expr::simplify::SimplifyContext;
+use datafusion_expr::{Cast, Expr, LogicalPlan, Projection, SubqueryAlias};
+use datafusion_optimizer::simplify_expressions::ExprSimplifier;
+
+/// Reports likely mistakes in the analyzed `plan` to the warnings of `state`:
+///
+/// * columns of subqueries and common table expressions that are never used
+/// * filter and join predicates that are always true or never true
+/// * casts of literals that always fail
+///
+/// Predicates that are written as a literal, such as `WHERE false` or
+/// `ON true`, are assumed to be intentional and are not reported.
+pub(crate) fn report_plan_warnings(
+    state: &SessionState,
+    plan: &LogicalPlan,
+) -> Result<()> {
+    let warnings = state.warnings();
+    // the output columns of the plan are used by the caller
+    let mut used: HashSet<Column> = plan.schema().columns().into_iter().collect();
+    let mut aliases = vec![];
+
+    plan.apply_with_subqueries(|node| {
+        node.apply_expressions(|expr| {
+            expr.apply(|expr| {
+                match expr {
+                    Expr::Column(column) | Expr::OuterReferenceColumn(_, column) => {
+                        used.insert(column.clone());
+                    }
+                    Expr::Cast(Cast { expr, field }) => {
+                        if let Expr::Literal(value, _) = expr.as_ref()
+                            && let Err(e) = value.cast_to(field.data_type())
+                        {
+                            warnings.report(Diagnostic::new_warning(
+                                format!(
+                                    "cast of {expr} to {} always fails: {}",
+                                    field.data_type(),
+                                    e.strip_backtrace()
+                                ),
+                                None,
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+                Ok(TreeNodeRecursion::Continue)
+            })
+        })?;
+
+        match node {
+            LogicalPlan::Filter(filter) => check_predicate(
+                state,
+                &warnings,
+                &filter.predicate,
+                filter.input.schema(),
+            ),
+            LogicalPlan::Join(join) => {
+                if let Some(filter) = &join.filter {
+                    // the join schema of semi and anti joins lacks one side
+                    let schema = join.left.schema().join(join.right.schema())?;
+                    check_predicate(state, &warnings, filter, &Arc::new(schema));
+                }
+            }
+            // these nodes either pass their input columns through to their
+            // parent or refer to the ones they need by expression
+            LogicalPlan::Projection(_)
+            | LogicalPlan::Aggregate(_)
+            | LogicalPlan::Window(_)
+            | LogicalPlan::Sort(_)
+            | LogicalPlan::Limit(_)
+            | LogicalPlan::Repartition(_) => {}
+            _ => {
+                if let LogicalPlan::SubqueryAlias(alias) = node {
+                    aliases.push(alias.clone());
+                }
+                // other nodes consume all columns of their inputs by position
+                for input in node.inputs() {
+                    used.extend(input.schema().columns());
+                }
+            }
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+
+    // a common table expression referenced several times appears once per reference
+    let mut reported = HashSet::new();
+    for alias in aliases {
+        let LogicalPlan::Projection(projection) = alias.input.as_ref() else {
+            continue;
+        };
+        if selects_all_columns(projection) || is_table(state, &alias) {
+            continue;
+        }
+        for (expr, column) in projection.expr.iter().zip(alias.schema.columns()) {
+            if used.contains(&column) || !reported.insert(column.clone()) {
+                continue;
+            }
+            let span = expr.spans().and_then(|spans| spans.first());
+            warnings.report(Diagnostic::new_warning(
+                format!("column '{column}' is selected but never used"),
+                span,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reports `predicate` if it simplifies to a constant
+fn check_predicate(
+    state: &SessionState,
+    warnings: &WarningCollector,
+    predicate: &Expr,
+    schema: &DFSchemaRef,
+) {
+    if matches!(predicate, Expr::Literal(..)) {
+        return;
+    }
+    let context = SimplifyContext::builder()
+        .with_schema(Arc::clone(schema))
+        .with_config_options(Arc::clone(state.config_options()))
+        .with_query_execution_start_time(
+            state.execution_props().query_execution_start_time,
+        )
+        .with_cast_registry(state.execution_props().cast_registry().cloned())
+        .build();
+    let Ok(Expr::Literal(value, _)) =
+        ExprSimplifier::new(context).simplify(predicate.clone())
+    else {
+        return;
+    };
+    let message = match value {
+        ScalarValue::Boolean(Some(true)) => {
+            format!("predicate {predicate} is always true and has no effect")
+        }
+        _ => format!("predicate {predicate} is never true, so no rows pass it"),
+    };
+    let span = predicate
+        .column_refs()
+        .into_iter()
+        .find_map(|column| column.spans().first());
+    warnings.report(Diagnostic::new_warning(message, span));
+}
+
+/// Returns true if `projection` selects all columns of its input unchanged
+/// and in order, which is how `SELECT *` is planned
+fn selects_all_columns(projection: &Projection) -> bool {
+    let columns = projection.input.schema().columns();
+    projection.expr.len() == columns.len()
+        && projection
+            .expr
+            .iter()
+            .zip(&columns)
+            .all(|(expr, column)| matches!(expr, Expr::Column(c) if c == column))
+}
+
+/// Returns true if `alias` names a registered table or view, whose plan was
+/// inlined rather than written as part of the query
+fn is_table(state: &SessionState, alias: &SubqueryAlias) -> bool {
+    state
+        .schema_for_ref(alias.alias.clone())
+        .map(|schema| schema.table_exist(alias.alias.table()))
+        .unwrap_or(false)
+}
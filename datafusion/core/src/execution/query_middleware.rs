@@ -0,0 +1,206 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`QueryMiddleware`]s rewriting the SQL text and the logical plans of
+//! queries before they are planned and optimized
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use datafusion_common::Result;
+use datafusion_common::instant::Instant;
+use datafusion_common::tree_node::Transformed;
+use datafusion_expr::LogicalPlan;
+
+use crate::execution::SessionState;
+
+/// A [`QueryMiddleware`] can be registered via [`SessionState`] or
+/// [`SessionContext`] to rewrite the queries of the session, e.g. to rename
+/// tables, to support legacy syntax, or to reject some queries (a query
+/// firewall).
+///
+/// The registered middlewares form a chain, and are applied in the order
+/// they were registered:
+///
+/// 1. [`Self::rewrite_sql`] is applied to the SQL text of queries, before
+///    it is parsed
+/// 2. [`Self::rewrite_plan`] is applied to the [`LogicalPlan`]s created
+///    from SQL statements, before they are verified against the
+///    [`SQLOptions`], analyzed and optimized
+///
+/// Errors returned by a middleware stop the query, and are reported with the
+/// name of the middleware as context. The [`QueryMiddlewareMetrics`] of each
+/// middleware count its calls, rewrites and errors, and its elapsed time.
+///
+/// [`SessionContext`]: crate::execution::context::SessionContext
+/// [`SQLOptions`]: crate::execution::context::SQLOptions
+pub trait QueryMiddleware: Debug + Send + Sync {
+    /// Returns the name of this middleware, used in the context of its errors
+    fn name(&self) -> &str;
+
+    /// Potentially rewrite the SQL text of a query, which may contain
+    /// several statements
+    fn rewrite_sql(
+        &self,
+        sql: String,
+        _state: &SessionState,
+    ) -> Result<Transformed<String>> {
+        Ok(Transformed::no(sql))
+    }
+
+    /// Potentially rewrite the [`LogicalPlan`] of a SQL statement
+    fn rewrite_plan(
+        &self,
+        plan: LogicalPlan,
+        _state: &SessionState,
+    ) -> Result<Transformed<LogicalPlan>> {
+        Ok(Transformed::no(plan))
+    }
+}
+
+/// Metrics of a [`QueryMiddleware`], shared by the sessions created from the
+/// session the middleware is registered on
+#[derive(Debug, Default)]
+pub struct QueryMiddlewareMetrics {
+    calls: AtomicUsize,
+    rewrites: AtomicUsize,
+    errors: AtomicUsize,
+    elapsed_nanos: AtomicU64,
+}
+
+impl QueryMiddlewareMetrics {
+    /// Returns the number of SQL texts and plans passed to the middleware
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of SQL texts and plans rewritten by the middleware
+    pub fn rewrites(&self) -> usize {
+        self.rewrites.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of errors returned by the middleware
+    pub fn errors(&self) -> usize {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total time spent in the middleware
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Records a call of the middleware
+    fn record<T>(&self, start: Instant, result: &Result<Transformed<T>>) {
+        let elapsed = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.elapsed_nanos.fetch_add(elapsed, Ordering::Relaxed);
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        match result {
+            Ok(transformed) if transformed.transformed => {
+                self.rewrites.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(_) => {}
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// A [`QueryMiddleware`] registered on a session, with its metrics
+#[derive(Debug, Clone)]
+struct RegisteredQueryMiddleware {
+    middleware: Arc<dyn QueryMiddleware>,
+    metrics: Arc<QueryMiddlewareMetrics>,
+}
+
+/// The chain of [`QueryMiddleware`]s of a session, in registration order
+#[derive(Debug, Clone, Default)]
+pub struct QueryMiddlewares {
+    middlewares: Vec<RegisteredQueryMiddleware>,
+}
+
+impl QueryMiddlewares {
+    /// Adds `middleware` to the end of the chain
+    pub fn push(&mut self, middleware: Arc<dyn QueryMiddleware>) {
+        self.middlewares.push(RegisteredQueryMiddleware {
+            middleware,
+            metrics: Arc::default(),
+        });
+    }
+
+    /// Returns true if the chain has no middlewares
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
+    /// Returns the middlewares of the chain, in order
+    pub fn middlewares(&self) -> impl Iterator<Item = &Arc<dyn QueryMiddleware>> {
+        self.middlewares
+            .iter()
+            .map(|registered| &registered.middleware)
+    }
+
+    /// Returns the metrics of the middleware named `name`, if any
+    pub fn metrics(&self, name: &str) -> Option<Arc<QueryMiddlewareMetrics>> {
+        self.middlewares
+            .iter()
+            .find(|registered| registered.middleware.name() == name)
+            .map(|registered| Arc::clone(&registered.metrics))
+    }
+
+    /// Applies [`QueryMiddleware::rewrite_sql`] of each middleware to `sql`
+    pub fn rewrite_sql(&self, sql: &str, state: &SessionState) -> Result<String> {
+        self.apply(sql.to_string(), |middleware, sql| {
+            middleware.rewrite_sql(sql, state)
+        })
+    }
+
+    /// Applies [`QueryMiddleware::rewrite_plan`] of each middleware to `plan`
+    pub fn rewrite_plan(
+        &self,
+        plan: LogicalPlan,
+        state: &SessionState,
+    ) -> Result<LogicalPlan> {
+        self.apply(plan, |middleware, plan| {
+            middleware.rewrite_plan(plan, state)
+        })
+    }
+
+    fn apply<T>(
+        &self,
+        mut value: T,
+        rewrite: impl Fn(&dyn QueryMiddleware, T) -> Result<Transformed<T>>,
+    ) -> Result<T> {
+        for RegisteredQueryMiddleware {
+            middleware,
+            metrics,
+        } in &self.middlewares
+        {
+            let start = Instant::now();
+            let result = rewrite(middleware.as_ref(), value);
+            metrics.record(start, &result);
+            value = result
+                .map_err(|e| {
+                    e.context(format!("query middleware {}", middleware.name()))
+                })?
+                .data;
+        }
+        Ok(value)
+    }
+}
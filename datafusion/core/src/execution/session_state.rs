@@ -23,24 +23,34 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use crate::catalog::{CatalogProviderList, SchemaProvider, TableProviderFactory};
+use crate::catalog::{
+    AsyncCatalogCache, AsyncCatalogProviderList, CatalogProviderList, SchemaProvider,
+    TableProvider, TableProviderFactory,
+};
 use crate::datasource::file_format::FileFormatFactory;
 #[cfg(feature = "sql")]
-use crate::datasource::provider_as_source;
+use crate::datasource::{ScanOptionsTable, provider_as_source, source_as_provider};
 use crate::execution::SessionStateDefaults;
 use crate::execution::context::{EmptySerializerRegistry, FunctionFactory, QueryPlanner};
+use crate::execution::plan_warnings::report_plan_warnings;
+use crate::execution::query_middleware::{
+    QueryMiddleware, QueryMiddlewareMetrics, QueryMiddlewares,
+};
 use crate::physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner};
 use arrow_schema::{DataType, FieldRef};
-use datafusion_catalog::MemoryCatalogProviderList;
 use datafusion_catalog::information_schema::{
     INFORMATION_SCHEMA, InformationSchemaProvider,
 };
+use datafusion_catalog::{MemoryCatalogProviderList, OverlayCatalogProviderList};
 use datafusion_catalog::{TableFunction, TableFunctionImpl};
 use datafusion_common::alias::AliasGenerator;
+use datafusion_common::clock::ClockRef;
 #[cfg(feature = "sql")]
 use datafusion_common::config::Dialect;
 use datafusion_common::config::{ConfigExtension, ConfigOptions, TableOptions};
+use datafusion_common::diagnostic::WarningCollector;
 use datafusion_common::display::{PlanType, StringifiedPlan, ToStringifiedPlan};
+use datafusion_common::timezone::{BundledTimeZoneDatabase, TimeZoneDatabaseRef};
 use datafusion_common::tree_node::TreeNode;
 use datafusion_common::{
     DFSchema, DataFusionError, ResolvedTableReference, TableReference, config_err,
@@ -52,17 +62,18 @@ use datafusion_execution::runtime_env::RuntimeEnv;
 #[cfg(feature = "sql")]
 use datafusion_expr::TableSource;
 use datafusion_expr::execution_props::ExecutionProps;
-use datafusion_expr::expr_rewriter::FunctionRewrite;
+use datafusion_expr::expr_rewriter::{FunctionRewrite, OperatorOverload};
 use datafusion_expr::planner::ExprPlanner;
 #[cfg(feature = "sql")]
 use datafusion_expr::planner::{RelationPlanner, TypePlanner};
 use datafusion_expr::registry::{
-    ExtensionTypeRegistryRef, FunctionRegistry, MemoryExtensionTypeRegistry,
-    SerializerRegistry,
+    CastRegistryRef, ExtensionTypeRegistryRef, FunctionRegistry, MemoryCastRegistry,
+    MemoryExtensionTypeRegistry, SerializerRegistry, UserDefinedCastRef,
 };
 use datafusion_expr::simplify::SimplifyContext;
 use datafusion_expr::{
-    AggregateUDF, Explain, Expr, HigherOrderUDF, LogicalPlan, ScalarUDF, WindowUDF,
+    AggregateUDF, Explain, Expr, HigherOrderUDF, LogicalPlan, PlanRewrite,
+    PolicyProvider, ScalarUDF, WindowUDF,
 };
 use datafusion_optimizer::simplify_expressions::ExprSimplifier;
 use datafusion_optimizer::{
@@ -72,6 +83,7 @@ use datafusion_physical_expr::create_physical_expr;
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 use datafusion_physical_optimizer::PhysicalOptimizerContext;
 use datafusion_physical_optimizer::PhysicalOptimizerRule;
+use datafusion_physical_optimizer::PhysicalOptimizerRulePosition;
 use datafusion_physical_optimizer::optimizer::PhysicalOptimizer;
 use datafusion_physical_plan::ExecutionPlan;
 use datafusion_physical_plan::operator_statistics::StatisticsRegistry;
@@ -159,14 +171,15 @@ pub struct SessionState {
     catalog_list: Arc<dyn CatalogProviderList>,
     /// Table Functions
     table_functions: HashMap<String, Arc<TableFunction>>,
-    /// Scalar functions that are registered with the context
-    scalar_functions: HashMap<String, Arc<ScalarUDF>>,
+    /// Scalar functions that are registered with the context, copied on write
+    /// as they may be shared with other sessions
+    scalar_functions: Arc<HashMap<String, Arc<ScalarUDF>>>,
     /// Higher order functions that are registered with the context
     higher_order_functions: HashMap<String, Arc<dyn HigherOrderUDF>>,
-    /// Aggregate functions registered in the context
-    aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
-    /// Window functions registered in the context
-    window_functions: HashMap<String, Arc<WindowUDF>>,
+    /// Aggregate functions registered in the context, copied on write
+    aggregate_functions: Arc<HashMap<String, Arc<AggregateUDF>>>,
+    /// Window functions registered in the context, copied on write
+    window_functions: Arc<HashMap<String, Arc<WindowUDF>>>,
     /// Extension types registry for extensions.
     extension_types: ExtensionTypeRegistryRef,
     /// Deserializer registry for extensions.
@@ -197,6 +210,17 @@ pub struct SessionState {
     /// thus, changing dialect o PostgreSql is required
     function_factory: Option<Arc<dyn FunctionFactory>>,
     cache_factory: Option<Arc<dyn CacheFactory>>,
+    /// [TableResolver] consulted before the catalogs to resolve the tables
+    /// referenced by queries
+    table_resolver: Option<Arc<dyn TableResolver>>,
+    /// [QueryMiddleware]s rewriting the SQL text and the logical plans of
+    /// queries, in order
+    query_middlewares: QueryMiddlewares,
+    /// Database of the time zones that can be set with `SET TIME ZONE`
+    time_zone_database: TimeZoneDatabaseRef,
+    /// Cache of the tables of remote catalogs, consulted after the catalogs
+    /// to resolve the tables referenced by queries
+    async_catalog: Option<Arc<AsyncCatalogCache>>,
     /// Optional statistics registry for pluggable statistics providers.
     ///
     /// When set, physical optimizer rules can use this registry to obtain
@@ -235,6 +259,10 @@ impl Debug for SessionState {
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
             .field("cache_factory", &self.cache_factory)
+            .field("table_resolver", &self.table_resolver)
+            .field("query_middlewares", &self.query_middlewares)
+            .field("time_zone_database", &self.time_zone_database)
+            .field("async_catalog", &self.async_catalog)
             .field("expr_planners", &self.expr_planners);
 
         #[cfg(feature = "sql")]
@@ -376,6 +404,30 @@ impl SessionState {
         self
     }
 
+    /// Register a [`PlanRewrite`] that is applied to the analyzed plan of
+    /// queries, before they are optimized.
+    pub fn register_plan_rewrite(&mut self, rewrite: Arc<dyn PlanRewrite + Send + Sync>) {
+        self.analyzer.add_plan_rewrite(rewrite);
+    }
+
+    /// Register a [`PolicyProvider`] whose row filters and column masks are
+    /// applied to the table scans of queries, before they are analyzed.
+    pub fn register_policy_provider(&mut self, provider: Arc<dyn PolicyProvider>) {
+        self.analyzer.add_policy_provider(provider);
+    }
+
+    /// Register an [`OperatorOverload`], that resolves its operator to a
+    /// function call when the types of its operands match.
+    ///
+    /// The overload is registered both as an [`ExprPlanner`], ahead of the
+    /// other planners, to resolve the operators of SQL queries, and as a
+    /// [`FunctionRewrite`], to resolve the operators of other plans.
+    pub fn register_operator_overload(&mut self, overload: Arc<OperatorOverload>) {
+        self.expr_planners
+            .insert(0, Arc::clone(&overload) as Arc<dyn ExprPlanner>);
+        self.analyzer.add_function_rewrite(overload);
+    }
+
     // the add_optimizer_rule takes an owned reference
     // it should probably be renamed to `with_optimizer_rule` to follow builder style
     // and `add_optimizer_rule` that takes &mut self added instead of this
@@ -393,6 +445,20 @@ impl SessionState {
         self.optimizer.rules.len() < original_len
     }
 
+    /// Inserts `physical_optimizer_rule` at `position` relative to the
+    /// existing [`PhysicalOptimizerRule`]s used to rewrite queries.
+    ///
+    /// See [`PhysicalOptimizer::insert_rule`] for the conditions under which
+    /// this returns an error.
+    pub fn add_physical_optimizer_rule(
+        &mut self,
+        physical_optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+        position: PhysicalOptimizerRulePosition,
+    ) -> datafusion_common::Result<()> {
+        self.physical_optimizers
+            .insert_rule(physical_optimizer_rule, position)
+    }
+
     /// Registers a [`FunctionFactory`] to handle `CREATE FUNCTION` statements
     pub fn set_function_factory(&mut self, function_factory: Arc<dyn FunctionFactory>) {
         self.function_factory = Some(function_factory);
@@ -413,6 +479,112 @@ impl SessionState {
         self.cache_factory.as_ref()
     }
 
+    /// Register a [`UserDefinedCast`], used by `CAST` expressions and type
+    /// coercion, returning any previously registered cast with the same name.
+    ///
+    /// If no [`CastRegistry`] is set, a [`MemoryCastRegistry`] is created.
+    ///
+    /// [`UserDefinedCast`]: datafusion_expr::registry::UserDefinedCast
+    /// [`CastRegistry`]: datafusion_expr::registry::CastRegistry
+    pub fn register_cast(
+        &mut self,
+        cast: UserDefinedCastRef,
+    ) -> datafusion_common::Result<Option<UserDefinedCastRef>> {
+        self.execution_props
+            .cast_registry
+            .get_or_insert_with(|| Arc::new(MemoryCastRegistry::new()))
+            .register_cast(cast)
+    }
+
+    /// Deregister the [`UserDefinedCast`] named `name`, returning the cast
+    /// that was deregistered, if any.
+    ///
+    /// [`UserDefinedCast`]: datafusion_expr::registry::UserDefinedCast
+    pub fn deregister_cast(
+        &mut self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<UserDefinedCastRef>> {
+        match &self.execution_props.cast_registry {
+            Some(cast_registry) => cast_registry.deregister_cast(name),
+            None => Ok(None),
+        }
+    }
+
+    /// Register a [`TableResolver`] for per query table resolution
+    pub fn set_table_resolver(&mut self, table_resolver: Arc<dyn TableResolver>) {
+        self.table_resolver = Some(table_resolver);
+    }
+
+    /// Get the table resolver
+    pub fn table_resolver(&self) -> Option<&Arc<dyn TableResolver>> {
+        self.table_resolver.as_ref()
+    }
+
+    /// Add a [`QueryMiddleware`] to the end of the chain of middlewares
+    /// rewriting the SQL text and the logical plans of queries
+    pub fn register_query_middleware(&mut self, middleware: Arc<dyn QueryMiddleware>) {
+        self.query_middlewares.push(middleware);
+    }
+
+    /// Get the chain of [`QueryMiddleware`]s
+    pub fn query_middlewares(&self) -> &QueryMiddlewares {
+        &self.query_middlewares
+    }
+
+    /// Get the metrics of the [`QueryMiddleware`] named `name`, if any
+    pub fn query_middleware_metrics(
+        &self,
+        name: &str,
+    ) -> Option<Arc<QueryMiddlewareMetrics>> {
+        self.query_middlewares.metrics(name)
+    }
+
+    /// Set the [`TimeZoneDatabase`] that resolves the time zones set with
+    /// `SET TIME ZONE`
+    ///
+    /// [`TimeZoneDatabase`]: datafusion_common::timezone::TimeZoneDatabase
+    pub fn set_time_zone_database(&mut self, time_zone_database: TimeZoneDatabaseRef) {
+        self.time_zone_database = time_zone_database;
+    }
+
+    /// Get the time zone database
+    pub fn time_zone_database(&self) -> &TimeZoneDatabaseRef {
+        &self.time_zone_database
+    }
+
+    /// Register an [`AsyncCatalogProviderList`] to resolve the tables that
+    /// are not found in the catalogs from, such as the tables of a remote
+    /// metastore. The tables are looked up while planning the queries that
+    /// reference them, and cached until they are invalidated, see
+    /// [`Self::invalidate_async_table`].
+    pub fn set_async_catalog_list(
+        &mut self,
+        catalog_list: Arc<dyn AsyncCatalogProviderList>,
+    ) {
+        self.async_catalog = Some(Arc::new(AsyncCatalogCache::new(catalog_list)));
+    }
+
+    /// Get the cache of the tables of the [`AsyncCatalogProviderList`], if any
+    pub fn async_catalog(&self) -> Option<&Arc<AsyncCatalogCache>> {
+        self.async_catalog.as_ref()
+    }
+
+    /// Evicts `table_ref` from the cache of the [`AsyncCatalogProviderList`],
+    /// so that the next query referencing it looks it up again
+    pub fn invalidate_async_table(&self, table_ref: impl Into<TableReference>) {
+        if let Some(async_catalog) = &self.async_catalog {
+            async_catalog.invalidate_table(&self.resolve_table_ref(table_ref));
+        }
+    }
+
+    /// Evicts all the catalogs, schemas and tables cached from the
+    /// [`AsyncCatalogProviderList`]
+    pub fn invalidate_async_catalog(&self) {
+        if let Some(async_catalog) = &self.async_catalog {
+            async_catalog.invalidate_all();
+        }
+    }
+
     /// Get the table factories
     pub fn table_factories(&self) -> &HashMap<String, Arc<dyn TableProviderFactory>> {
         &self.table_factories
@@ -435,6 +607,31 @@ impl SessionState {
         sql: &str,
         dialect: &Dialect,
     ) -> datafusion_common::Result<Statement> {
+        let mut statements = self.sql_to_statements(sql, dialect)?;
+
+        if statements.len() > 1 {
+            return datafusion_common::not_impl_err!(
+                "The context currently only supports a single SQL statement"
+            );
+        }
+
+        let statement = statements.pop_front().ok_or_else(|| {
+            plan_datafusion_err!("No SQL statements were provided in the query string")
+        })?;
+        Ok(statement)
+    }
+
+    /// Parse an SQL string with any number of statements, separated by `;`,
+    /// into DataFusion specific AST [`Statement`]s. See
+    /// [`SessionContext::execute_script`] for running them.
+    ///
+    /// [`SessionContext::execute_script`]: crate::execution::context::SessionContext::execute_script
+    #[cfg(feature = "sql")]
+    pub fn sql_to_statements(
+        &self,
+        sql: &str,
+        dialect: &Dialect,
+    ) -> datafusion_common::Result<std::collections::VecDeque<Statement>> {
         let dialect = dialect_from_str(dialect).ok_or_else(|| {
             plan_datafusion_err!(
                 "Unsupported SQL dialect: {dialect}. Available dialects: \
@@ -445,22 +642,17 @@ impl SessionState {
 
         let recursion_limit = self.config.options().sql_parser.recursion_limit;
 
-        let mut statements = DFParserBuilder::new(sql)
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("datafusion.parse_sql", session_id = self.session_id())
+                .entered();
+
+        let sql = self.query_middlewares.rewrite_sql(sql, self)?;
+        DFParserBuilder::new(sql.as_str())
             .with_dialect(dialect.as_ref())
             .with_recursion_limit(recursion_limit)
             .build()?
-            .parse_statements()?;
-
-        if statements.len() > 1 {
-            return datafusion_common::not_impl_err!(
-                "The context currently only supports a single SQL statement"
-            );
-        }
-
-        let statement = statements.pop_front().ok_or_else(|| {
-            plan_datafusion_err!("No SQL statements were provided in the query string")
-        })?;
-        Ok(statement)
+            .parse_statements()
     }
 
     /// parse a sql string into a sqlparser-rs AST [`SQLExpr`].
@@ -521,33 +713,72 @@ impl SessionState {
         Ok(table_refs)
     }
 
-    /// Convert an AST Statement into a LogicalPlan
+    /// Convert an AST Statement into a LogicalPlan, rewritten by the
+    /// registered [`QueryMiddleware`]s
     #[cfg(feature = "sql")]
     pub async fn statement_to_plan(
         &self,
         statement: Statement,
     ) -> datafusion_common::Result<LogicalPlan> {
-        let references = self.resolve_table_references(&statement)?;
+        let planning = async {
+            let references = self.resolve_table_references(&statement)?;
 
-        let mut provider = SessionContextProvider {
-            state: self,
-            tables: HashMap::with_capacity(references.len()),
-        };
+            let mut provider = SessionContextProvider {
+                state: self,
+                tables: HashMap::with_capacity(references.len()),
+            };
 
-        for reference in references {
-            let resolved = self.resolve_table_ref(reference);
-            if let Entry::Vacant(v) = provider.tables.entry(resolved) {
-                let resolved = v.key();
-                if let Ok(schema) = self.schema_for_ref(resolved.clone())
-                    && let Some(table) = schema.table(&resolved.table).await?
+            for reference in references {
+                let resolved = self.resolve_table_ref(reference);
+                if let Entry::Vacant(v) = provider.tables.entry(resolved)
+                    && let Some(table) = self.resolve_table(v.key()).await?
                 {
                     v.insert(provider_as_source(table));
                 }
             }
-        }
 
-        let query = SqlToRel::new_with_options(&provider, self.get_parser_options());
-        query.statement_to_plan(statement)
+            let query = SqlToRel::new_with_options(&provider, self.get_parser_options());
+            query.statement_to_plan(statement)
+        };
+
+        #[cfg(feature = "tracing")]
+        let planning = tracing::Instrument::instrument(
+            planning,
+            tracing::info_span!(
+                "datafusion.create_logical_plan",
+                session_id = self.session_id()
+            ),
+        );
+
+        let plan = planning.await?;
+        self.query_middlewares.rewrite_plan(plan, self)
+    }
+
+    /// Resolves `table_ref` to a [`TableProvider`], asking the
+    /// [`TableResolver`] first, if any, then the catalogs and finally the
+    /// [`AsyncCatalogProviderList`], if any.
+    ///
+    /// Returns `None` if the table does not exist.
+    pub async fn resolve_table(
+        &self,
+        table_ref: &ResolvedTableReference,
+    ) -> datafusion_common::Result<Option<Arc<dyn TableProvider>>> {
+        if let Some(table_resolver) = &self.table_resolver
+            && let Some(table) = table_resolver
+                .resolve_table(table_ref, &self.config)
+                .await?
+        {
+            return Ok(Some(table));
+        }
+        if let Ok(schema) = self.schema_for_ref(table_ref.clone())
+            && let Some(table) = schema.table(&table_ref.table).await?
+        {
+            return Ok(Some(table));
+        }
+        match &self.async_catalog {
+            Some(async_catalog) => async_catalog.table(table_ref).await,
+            None => Ok(None),
+        }
     }
 
     #[cfg(feature = "sql")]
@@ -608,6 +839,38 @@ impl SessionState {
         self.create_logical_expr_from_sql_expr(sql_expr, df_schema)
     }
 
+    /// Creates an [`Expr`] from SQL text created by `to_sql_string` (see
+    /// [`ExprSqlExt`]), which plans back to the expression that was converted.
+    ///
+    /// Unlike [`Self::create_logical_expr`], casts of literals, with which
+    /// `to_sql_string` keeps the types of literals, are planned as literals of
+    /// the type they are cast to.
+    ///
+    /// See example on [SessionContext::parse_expr](crate::execution::context::SessionContext::parse_expr)
+    ///
+    /// [`ExprSqlExt`]: datafusion_sql::unparser::ExprSqlExt
+    #[cfg(feature = "sql")]
+    pub fn parse_expr(
+        &self,
+        sql: &str,
+        df_schema: &DFSchema,
+    ) -> datafusion_common::Result<Expr> {
+        use datafusion_common::tree_node::{Transformed, TransformedResult};
+        use datafusion_expr::Cast;
+
+        self.create_logical_expr(sql, df_schema)?
+            .transform_up(|expr| {
+                if let Expr::Cast(Cast { expr: inner, field }) = &expr
+                    && let Expr::Literal(value, None) = inner.as_ref()
+                    && let Ok(value) = value.cast_to(field.data_type())
+                {
+                    return Ok(Transformed::yes(Expr::Literal(value, None)));
+                }
+                Ok(Transformed::no(expr))
+            })
+            .data()
+    }
+
     /// Creates a datafusion style AST [`Expr`] from a SQL expression.
     #[cfg(feature = "sql")]
     pub fn create_logical_expr_from_sql_expr(
@@ -664,13 +927,19 @@ impl SessionState {
 
     /// Optimizes the logical plan by applying optimizer rules.
     pub fn optimize(&self, plan: &LogicalPlan) -> datafusion_common::Result<LogicalPlan> {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("datafusion.optimize", session_id = self.session_id())
+                .entered();
+
         if let LogicalPlan::Explain(e) = plan {
             let mut stringified_plans = e.stringified_plans.clone();
 
             // analyze & capture output of each rule
-            let analyzer_result = self.analyzer.execute_and_check(
+            let analyzer_result = self.analyzer.execute_and_check_with_registry(
                 e.plan.as_ref().clone(),
                 &self.options(),
+                self,
                 |analyzed_plan, analyzer| {
                     let analyzer_name = analyzer.name().to_string();
                     let plan_type = PlanType::AnalyzedLogicalPlan { analyzer_name };
@@ -730,11 +999,15 @@ impl SessionState {
                 logical_optimization_succeeded,
             }))
         } else {
-            let analyzed_plan = self.analyzer.execute_and_check(
+            let analyzed_plan = self.analyzer.execute_and_check_with_registry(
                 plan.clone(),
                 &self.options(),
+                self,
                 |_, _| {},
             )?;
+            if self.config_options().optimizer.report_plan_warnings {
+                report_plan_warnings(self, &analyzed_plan)?;
+            }
             self.optimizer.optimize(analyzed_plan, self, |_, _| {})
         }
     }
@@ -754,9 +1027,18 @@ impl SessionState {
         logical_plan: &LogicalPlan,
     ) -> datafusion_common::Result<Arc<dyn ExecutionPlan>> {
         let logical_plan = self.optimize(logical_plan)?;
-        self.query_planner
-            .create_physical_plan(&logical_plan, self)
-            .await
+        let planning = self.query_planner.create_physical_plan(&logical_plan, self);
+
+        #[cfg(feature = "tracing")]
+        let planning = tracing::Instrument::instrument(
+            planning,
+            tracing::info_span!(
+                "datafusion.create_physical_plan",
+                session_id = self.session_id()
+            ),
+        );
+
+        planning.await
     }
 
     /// Create a [`PhysicalExpr`] from an [`Expr`] after applying type
@@ -793,7 +1075,9 @@ impl SessionState {
         // rewrite Exprs to functions if necessary
         for rewrite in self.analyzer.function_rewrites() {
             expr = expr
-                .transform_up(|expr| rewrite.rewrite(expr, df_schema, config_options))?
+                .transform_up(|expr| {
+                    rewrite.rewrite_with_registry(expr, df_schema, config_options, self)
+                })?
                 .data;
         }
         create_physical_expr(&expr, df_schema, self.execution_props())
@@ -804,6 +1088,32 @@ impl SessionState {
         &self.session_id
     }
 
+    /// Returns a new session that shares the catalogs, functions and
+    /// [`RuntimeEnv`] (and with it the memory pool and caches) of this one.
+    ///
+    /// The child gets its own session ID, warnings and prepared statements and
+    /// a copy of the configuration that can be changed without affecting this
+    /// session. Catalogs, schemas and tables registered in the child, for
+    /// example with `CREATE TABLE`, are only visible to the child, while those
+    /// registered in this session remain visible to it; see
+    /// [`OverlayCatalogProviderList`].
+    ///
+    /// Creating a child is cheap, as function registries and rules are shared
+    /// rather than copied, so a child can be created for every query.
+    pub fn new_child(&self) -> SessionState {
+        let mut config = self.config.clone();
+        config.set_extension(Arc::new(WarningCollector::new()));
+        SessionState {
+            session_id: Uuid::new_v4().to_string(),
+            catalog_list: Arc::new(OverlayCatalogProviderList::new(Arc::clone(
+                &self.catalog_list,
+            ))),
+            config,
+            prepared_plans: HashMap::new(),
+            ..self.clone()
+        }
+    }
+
     /// Return the runtime env
     pub fn runtime_env(&self) -> &Arc<RuntimeEnv> {
         &self.runtime_env
@@ -829,6 +1139,19 @@ impl SessionState {
         &mut self.config
     }
 
+    /// Return the [`WarningCollector`] gathering non-fatal warnings raised
+    /// while planning and executing queries in this session.
+    ///
+    /// The collector is stored as an extension of the [`SessionConfig`], so
+    /// execution time components can report warnings via
+    /// [`TaskContext::session_config`].
+    pub fn warnings(&self) -> WarningCollector {
+        self.config
+            .get_extension::<WarningCollector>()
+            .map(|warnings| warnings.as_ref().clone())
+            .unwrap_or_default()
+    }
+
     /// Return the logical optimizers
     pub fn optimizers(&self) -> &[Arc<dyn OptimizerRule + Send + Sync>] {
         &self.optimizer.rules
@@ -1037,6 +1360,7 @@ pub struct SessionStateBuilder {
     higher_order_functions: Option<Vec<Arc<dyn HigherOrderUDF>>>,
     aggregate_functions: Option<Vec<Arc<AggregateUDF>>>,
     window_functions: Option<Vec<Arc<WindowUDF>>>,
+    static_default_functions: bool,
     extension_types: Option<ExtensionTypeRegistryRef>,
     serializer_registry: Option<Arc<dyn SerializerRegistry>>,
     file_formats: Option<Vec<Arc<dyn FileFormatFactory>>>,
@@ -1047,9 +1371,14 @@ pub struct SessionStateBuilder {
     runtime_env: Option<Arc<RuntimeEnv>>,
     function_factory: Option<Arc<dyn FunctionFactory>>,
     cache_factory: Option<Arc<dyn CacheFactory>>,
+    table_resolver: Option<Arc<dyn TableResolver>>,
+    query_middlewares: Option<QueryMiddlewares>,
+    time_zone_database: Option<TimeZoneDatabaseRef>,
+    async_catalog: Option<Arc<AsyncCatalogCache>>,
     statistics_registry: Option<StatisticsRegistry>,
     // fields to support convenience functions
     analyzer_rules: Option<Vec<Arc<dyn AnalyzerRule + Send + Sync>>>,
+    function_rewrites: Option<Vec<Arc<dyn FunctionRewrite + Send + Sync>>>,
     optimizer_rules: Option<Vec<Arc<dyn OptimizerRule + Send + Sync>>>,
     physical_optimizer_rules: Option<Vec<Arc<dyn PhysicalOptimizerRule + Send + Sync>>>,
 }
@@ -1080,6 +1409,7 @@ impl SessionStateBuilder {
             higher_order_functions: None,
             aggregate_functions: None,
             window_functions: None,
+            static_default_functions: false,
             extension_types: None,
             serializer_registry: None,
             file_formats: None,
@@ -1090,9 +1420,14 @@ impl SessionStateBuilder {
             runtime_env: None,
             function_factory: None,
             cache_factory: None,
+            table_resolver: None,
+            query_middlewares: None,
+            time_zone_database: None,
+            async_catalog: None,
             statistics_registry: None,
             // fields to support convenience functions
             analyzer_rules: None,
+            function_rewrites: None,
             optimizer_rules: None,
             physical_optimizer_rules: None,
         }
@@ -1132,14 +1467,25 @@ impl SessionStateBuilder {
             query_planner: Some(existing.query_planner),
             catalog_list: Some(existing.catalog_list),
             table_functions: Some(existing.table_functions),
-            scalar_functions: Some(existing.scalar_functions.into_values().collect_vec()),
+            scalar_functions: Some(
+                Arc::unwrap_or_clone(existing.scalar_functions)
+                    .into_values()
+                    .collect_vec(),
+            ),
             higher_order_functions: Some(
                 existing.higher_order_functions.into_values().collect_vec(),
             ),
             aggregate_functions: Some(
-                existing.aggregate_functions.into_values().collect_vec(),
+                Arc::unwrap_or_clone(existing.aggregate_functions)
+                    .into_values()
+                    .collect_vec(),
             ),
-            window_functions: Some(existing.window_functions.into_values().collect_vec()),
+            window_functions: Some(
+                Arc::unwrap_or_clone(existing.window_functions)
+                    .into_values()
+                    .collect_vec(),
+            ),
+            static_default_functions: false,
             extension_types: Some(existing.extension_types),
             serializer_registry: Some(existing.serializer_registry),
             file_formats: Some(existing.file_formats.into_values().collect_vec()),
@@ -1150,16 +1496,21 @@ impl SessionStateBuilder {
             runtime_env: Some(existing.runtime_env),
             function_factory: existing.function_factory,
             cache_factory: existing.cache_factory,
+            table_resolver: existing.table_resolver,
+            query_middlewares: Some(existing.query_middlewares),
+            time_zone_database: Some(existing.time_zone_database),
+            async_catalog: existing.async_catalog,
             statistics_registry: existing.statistics_registry,
             // fields to support convenience functions
             analyzer_rules: None,
+            function_rewrites: None,
             optimizer_rules: None,
             physical_optimizer_rules: None,
         }
     }
 
-    /// Adds defaults for table_factories, file formats, expr_planners and builtin
-    /// scalar, aggregate and windows functions.
+    /// Adds defaults for table_factories, file formats, expr_planners, function
+    /// rewrites and builtin scalar, aggregate and windows functions.
     ///
     /// Note overwrites any previously registered items with the same name.
     pub fn with_default_features(mut self) -> Self {
@@ -1175,6 +1526,10 @@ impl SessionStateBuilder {
             .get_or_insert_with(Vec::new)
             .extend(SessionStateDefaults::default_expr_planners());
 
+        self.function_rewrites
+            .get_or_insert_with(Vec::new)
+            .extend(SessionStateDefaults::default_function_rewrites());
+
         self.scalar_functions
             .get_or_insert_with(Vec::new)
             .extend(SessionStateDefaults::default_scalar_functions());
@@ -1250,6 +1605,18 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Add `function_rewrite` to the end of the list of [`FunctionRewrite`]s
+    /// applied before the [`AnalyzerRule`]s.
+    pub fn with_function_rewrite(
+        mut self,
+        function_rewrite: Arc<dyn FunctionRewrite + Send + Sync>,
+    ) -> Self {
+        let mut rewrites = self.function_rewrites.unwrap_or_default();
+        rewrites.push(function_rewrite);
+        self.function_rewrites = Some(rewrites);
+        self
+    }
+
     /// Set the [`OptimizerRule`]s used to optimize plans.
     pub fn with_optimizer_rules(
         mut self,
@@ -1395,6 +1762,34 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Start from the built-in scalar, aggregate and window functions of a
+    /// registry that is built once per process and shared by all sessions,
+    /// rather than registering each function with every new session.
+    ///
+    /// This reduces the time and allocations needed to build a
+    /// [`SessionState`], which matters for servers that create a session per
+    /// request. A session only copies the shared registry when it is modified,
+    /// for example by registering or deregistering a function, or by a function
+    /// such as `now` that depends on a non default configuration.
+    ///
+    /// Functions set with [`Self::with_scalar_functions`] and similar methods,
+    /// including by [`Self::with_default_features`], are registered on top of
+    /// the shared functions.
+    ///
+    /// ```
+    /// use datafusion::execution::session_state::SessionStateBuilder;
+    ///
+    /// let state = SessionStateBuilder::new()
+    ///     .with_default_features()
+    ///     .with_static_default_functions()
+    ///     .build();
+    /// assert!(state.scalar_functions().contains_key("abs"));
+    /// ```
+    pub fn with_static_default_functions(mut self) -> Self {
+        self.static_default_functions = true;
+        self
+    }
+
     /// Sets the [`ExtensionTypeRegistry`](datafusion_expr::registry::ExtensionTypeRegistry).
     pub fn with_extension_type_registry(
         mut self,
@@ -1404,6 +1799,22 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Sets the [`CastRegistry`](datafusion_expr::registry::CastRegistry) with
+    /// the user defined casts.
+    pub fn with_cast_registry(mut self, registry: CastRegistryRef) -> Self {
+        self.execution_props.get_or_insert_default().cast_registry = Some(registry);
+        self
+    }
+
+    /// Sets the [`Clock`] that provides the start time of queries, returned by
+    /// functions such as `now()`. Defaults to the system time
+    ///
+    /// [`Clock`]: datafusion_common::clock::Clock
+    pub fn with_clock(mut self, clock: ClockRef) -> Self {
+        self.execution_props.get_or_insert_default().clock = Some(clock);
+        self
+    }
+
     /// Set the [`SerializerRegistry`]
     pub fn with_serializer_registry(
         mut self,
@@ -1485,6 +1896,47 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Set a [`TableResolver`] for per query table resolution
+    pub fn with_table_resolver(
+        mut self,
+        table_resolver: Option<Arc<dyn TableResolver>>,
+    ) -> Self {
+        self.table_resolver = table_resolver;
+        self
+    }
+
+    /// Add a [`QueryMiddleware`] to the end of the chain of middlewares
+    /// rewriting the SQL text and the logical plans of queries
+    pub fn with_query_middleware(mut self, middleware: Arc<dyn QueryMiddleware>) -> Self {
+        self.query_middlewares
+            .get_or_insert_default()
+            .push(middleware);
+        self
+    }
+
+    /// Set the [`TimeZoneDatabase`] that resolves the time zones set with
+    /// `SET TIME ZONE`. Defaults to the [`BundledTimeZoneDatabase`]
+    ///
+    /// [`TimeZoneDatabase`]: datafusion_common::timezone::TimeZoneDatabase
+    pub fn with_time_zone_database(
+        mut self,
+        time_zone_database: Option<TimeZoneDatabaseRef>,
+    ) -> Self {
+        self.time_zone_database = time_zone_database;
+        self
+    }
+
+    /// Set an [`AsyncCatalogProviderList`] to resolve the tables that are not
+    /// found in the catalogs from, see [`SessionState::set_async_catalog_list`]
+    pub fn with_async_catalog_list(
+        mut self,
+        catalog_list: Option<Arc<dyn AsyncCatalogProviderList>>,
+    ) -> Self {
+        self.async_catalog = catalog_list
+            .map(|catalog_list| Arc::new(AsyncCatalogCache::new(catalog_list)));
+        self
+    }
+
     /// Set a [`StatisticsRegistry`] for pluggable statistics providers.
     ///
     /// The registry allows physical optimizer rules to access enhanced statistics
@@ -1553,6 +2005,7 @@ impl SessionStateBuilder {
             higher_order_functions,
             aggregate_functions,
             window_functions,
+            static_default_functions,
             extension_types,
             serializer_registry,
             file_formats,
@@ -1563,14 +2016,24 @@ impl SessionStateBuilder {
             runtime_env,
             function_factory,
             cache_factory,
+            table_resolver,
+            query_middlewares,
+            time_zone_database,
+            async_catalog,
             statistics_registry,
             analyzer_rules,
+            function_rewrites,
             optimizer_rules,
             physical_optimizer_rules,
         } = self;
 
-        let config = config.unwrap_or_default();
+        let mut config = config.unwrap_or_default();
+        if config.get_extension::<WarningCollector>().is_none() {
+            config.set_extension(Arc::new(WarningCollector::new()));
+        }
         let runtime_env = runtime_env.unwrap_or_else(|| Arc::new(RuntimeEnv::default()));
+        let static_functions =
+            static_default_functions.then(SessionStateDefaults::static_default_functions);
 
         let mut state = SessionState {
             session_id: session_id.unwrap_or_else(|| Uuid::new_v4().to_string()),
@@ -1588,10 +2051,33 @@ impl SessionStateBuilder {
                 Arc::new(MemoryCatalogProviderList::new()) as Arc<dyn CatalogProviderList>
             }),
             table_functions: table_functions.unwrap_or_default(),
-            scalar_functions: HashMap::new(),
-            higher_order_functions: HashMap::new(),
-            aggregate_functions: HashMap::new(),
-            window_functions: HashMap::new(),
+            // Size the function registries up front, every function is
+            // registered under its name and all of its aliases
+            scalar_functions: match static_functions {
+                Some(defaults) => Arc::clone(&defaults.scalar_functions),
+                None => Arc::new(HashMap::with_capacity(registry_capacity(
+                    scalar_functions.as_deref(),
+                    |udf| udf.aliases().len(),
+                ))),
+            },
+            higher_order_functions: HashMap::with_capacity(registry_capacity(
+                higher_order_functions.as_deref(),
+                |function| function.aliases().len(),
+            )),
+            aggregate_functions: match static_functions {
+                Some(defaults) => Arc::clone(&defaults.aggregate_functions),
+                None => Arc::new(HashMap::with_capacity(registry_capacity(
+                    aggregate_functions.as_deref(),
+                    |udaf| udaf.aliases().len(),
+                ))),
+            },
+            window_functions: match static_functions {
+                Some(defaults) => Arc::clone(&defaults.window_functions),
+                None => Arc::new(HashMap::with_capacity(registry_capacity(
+                    window_functions.as_deref(),
+                    |udwf| udwf.aliases().len(),
+                ))),
+            },
             extension_types: Arc::new(MemoryExtensionTypeRegistry::default()),
             serializer_registry: serializer_registry
                 .unwrap_or_else(|| Arc::new(EmptySerializerRegistry)),
@@ -1605,6 +2091,11 @@ impl SessionStateBuilder {
             runtime_env,
             function_factory,
             cache_factory,
+            table_resolver,
+            query_middlewares: query_middlewares.unwrap_or_default(),
+            time_zone_database: time_zone_database
+                .unwrap_or_else(|| Arc::new(BundledTimeZoneDatabase)),
+            async_catalog,
             statistics_registry,
             prepared_plans: HashMap::new(),
         };
@@ -1617,10 +2108,32 @@ impl SessionStateBuilder {
             }
         }
 
+        // the shared functions that depend on the configuration were created
+        // with the default one, so update them for this session if needed
+        let config_dependent_functions = static_functions
+            .map(|defaults| defaults.config_dependent_functions.as_slice())
+            .unwrap_or_default();
+        for udf in config_dependent_functions {
+            if let Some(new_udf) = udf.inner().with_updated_config(state.config.options())
+                && new_udf != **udf
+                && let Err(err) = state.register_udf(Arc::new(new_udf))
+            {
+                debug!("Failed to update shared UDF '{}': {}", udf.name(), err);
+            }
+        }
+
         if let Some(scalar_functions) = scalar_functions {
             for udf in scalar_functions {
                 let config_options = state.config().options();
                 match udf.inner().with_updated_config(config_options) {
+                    // skip the functions that are already shared with the session
+                    Some(new_udf)
+                        if is_shared(&state.scalar_functions, udf.name(), &new_udf) => {}
+                    None if is_shared(
+                        &state.scalar_functions,
+                        udf.name(),
+                        udf.as_ref(),
+                    ) => {}
                     Some(new_udf) => {
                         if let Err(err) = state.register_udf(Arc::new(new_udf)) {
                             debug!(
@@ -1670,6 +2183,9 @@ impl SessionStateBuilder {
 
         if let Some(aggregate_functions) = aggregate_functions {
             aggregate_functions.into_iter().for_each(|udaf| {
+                if is_shared(&state.aggregate_functions, udaf.name(), udaf.as_ref()) {
+                    return;
+                }
                 let existing_udf = state.register_udaf(udaf);
                 if let Ok(Some(existing_udf)) = existing_udf {
                     debug!("Overwrote an existing UDF: {}", existing_udf.name());
@@ -1679,6 +2195,9 @@ impl SessionStateBuilder {
 
         if let Some(window_functions) = window_functions {
             window_functions.into_iter().for_each(|udwf| {
+                if is_shared(&state.window_functions, udwf.name(), udwf.as_ref()) {
+                    return;
+                }
                 let existing_udf = state.register_udwf(udwf);
                 if let Ok(Some(existing_udf)) = existing_udf {
                     debug!("Overwrote an existing UDF: {}", existing_udf.name());
@@ -1713,6 +2232,12 @@ impl SessionStateBuilder {
             }
         }
 
+        if let Some(function_rewrites) = function_rewrites {
+            for function_rewrite in function_rewrites {
+                state.analyzer.add_function_rewrite(function_rewrite);
+            }
+        }
+
         if let Some(optimizer_rules) = optimizer_rules {
             for optimizer_rule in optimizer_rules {
                 state.optimizer.rules.push(optimizer_rule);
@@ -1854,6 +2379,26 @@ impl SessionStateBuilder {
         &mut self.cache_factory
     }
 
+    /// Returns the table resolver
+    pub fn table_resolver(&mut self) -> &mut Option<Arc<dyn TableResolver>> {
+        &mut self.table_resolver
+    }
+
+    /// Returns the chain of query middlewares
+    pub fn query_middlewares(&mut self) -> &mut Option<QueryMiddlewares> {
+        &mut self.query_middlewares
+    }
+
+    /// Returns the time zone database
+    pub fn time_zone_database(&mut self) -> &mut Option<TimeZoneDatabaseRef> {
+        &mut self.time_zone_database
+    }
+
+    /// Returns the cache of the tables of the [`AsyncCatalogProviderList`]
+    pub fn async_catalog(&mut self) -> &mut Option<Arc<AsyncCatalogCache>> {
+        &mut self.async_catalog
+    }
+
     /// Returns the current analyzer_rules value
     pub fn analyzer_rules(
         &mut self,
@@ -1861,6 +2406,13 @@ impl SessionStateBuilder {
         &mut self.analyzer_rules
     }
 
+    /// Returns the current function_rewrites value
+    pub fn function_rewrites(
+        &mut self,
+    ) -> &mut Option<Vec<Arc<dyn FunctionRewrite + Send + Sync>>> {
+        &mut self.function_rewrites
+    }
+
     /// Returns the current optimizer_rules value
     pub fn optimizer_rules(
         &mut self,
@@ -1893,11 +2445,16 @@ impl Debug for SessionStateBuilder {
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
             .field("cache_factory", &self.cache_factory)
+            .field("table_resolver", &self.table_resolver)
+            .field("query_middlewares", &self.query_middlewares)
+            .field("time_zone_database", &self.time_zone_database)
+            .field("async_catalog", &self.async_catalog)
             .field("expr_planners", &self.expr_planners);
         #[cfg(feature = "sql")]
         let ret = ret.field("type_planner", &self.type_planner);
         ret.field("query_planners", &self.query_planner)
             .field("analyzer_rules", &self.analyzer_rules)
+            .field("function_rewrites", &self.function_rewrites)
             .field("analyzer", &self.analyzer)
             .field("optimizer_rules", &self.optimizer_rules)
             .field("optimizer", &self.optimizer)
@@ -1918,6 +2475,28 @@ impl Default for SessionStateBuilder {
     }
 }
 
+/// Returns true if `functions` is still shared with other sessions and
+/// already contains `function` under `name`, so registering it would only
+/// copy the registry
+fn is_shared<T: PartialEq>(
+    functions: &Arc<HashMap<String, Arc<T>>>,
+    name: &str,
+    function: &T,
+) -> bool {
+    Arc::strong_count(functions) > 1
+        && functions.get(name).is_some_and(|existing| {
+            std::ptr::eq(existing.as_ref(), function) || **existing == *function
+        })
+}
+
+/// Number of registry entries needed for `functions`, registered under their
+/// names and `aliases`
+fn registry_capacity<T>(functions: Option<&[T]>, aliases: impl Fn(&T) -> usize) -> usize {
+    functions.map_or(0, |functions| {
+        functions.iter().map(|function| 1 + aliases(function)).sum()
+    })
+}
+
 impl From<SessionState> for SessionStateBuilder {
     fn from(state: SessionState) -> Self {
         SessionStateBuilder::new_from_existing(state)
@@ -1963,6 +2542,37 @@ impl ContextProvider for SessionContextProvider<'_> {
             .ok_or_else(|| plan_datafusion_err!("table '{name}' not found"))
     }
 
+    fn get_table_source_with_options(
+        &self,
+        name: TableReference,
+        options: Vec<(String, String)>,
+    ) -> datafusion_common::Result<Arc<dyn TableSource>> {
+        let source = self.get_table_source(name)?;
+        let provider = source_as_provider(&source)?;
+        Ok(provider_as_source(Arc::new(ScanOptionsTable::try_new(
+            provider, options,
+        )?)))
+    }
+
+    fn get_lateral_table_function_schema(
+        &self,
+        name: &str,
+        arg_types: &[DataType],
+    ) -> datafusion_common::Result<arrow::datatypes::SchemaRef> {
+        let tbl_func =
+            self.state.table_functions.get(name).ok_or_else(|| {
+                plan_datafusion_err!("table function '{name}' not found")
+            })?;
+        tbl_func
+            .function()
+            .lateral_schema(arg_types)?
+            .ok_or_else(|| {
+                plan_datafusion_err!(
+                    "table function '{name}' can not be called laterally"
+                )
+            })
+    }
+
     fn get_table_function_source(
         &self,
         name: &str,
@@ -1981,6 +2591,7 @@ impl ContextProvider for SessionContextProvider<'_> {
             .with_query_execution_start_time(
                 self.state.execution_props().query_execution_start_time,
             )
+            .with_cast_registry(self.state.execution_props().cast_registry().cloned())
             .build();
         let simplifier = ExprSimplifier::new(simplify_context);
         let schema = DFSchema::empty();
@@ -2072,6 +2683,10 @@ impl ContextProvider for SessionContextProvider<'_> {
         self.state.window_functions().keys().cloned().collect()
     }
 
+    fn report_warning(&self, warning: datafusion_common::Diagnostic) {
+        self.state.warnings().report(warning)
+    }
+
     fn get_file_type(
         &self,
         ext: &str,
@@ -2133,11 +2748,11 @@ impl FunctionRegistry for SessionState {
         &mut self,
         udf: Arc<ScalarUDF>,
     ) -> datafusion_common::Result<Option<Arc<ScalarUDF>>> {
+        let scalar_functions = Arc::make_mut(&mut self.scalar_functions);
         udf.aliases().iter().for_each(|alias| {
-            self.scalar_functions
-                .insert(alias.clone(), Arc::clone(&udf));
+            scalar_functions.insert(alias.clone(), Arc::clone(&udf));
         });
-        Ok(self.scalar_functions.insert(udf.name().into(), udf))
+        Ok(scalar_functions.insert(udf.name().into(), udf))
     }
 
     fn register_higher_order_function(
@@ -2157,32 +2772,36 @@ impl FunctionRegistry for SessionState {
         &mut self,
         udaf: Arc<AggregateUDF>,
     ) -> datafusion_common::Result<Option<Arc<AggregateUDF>>> {
+        let aggregate_functions = Arc::make_mut(&mut self.aggregate_functions);
         udaf.aliases().iter().for_each(|alias| {
-            self.aggregate_functions
-                .insert(alias.clone(), Arc::clone(&udaf));
+            aggregate_functions.insert(alias.clone(), Arc::clone(&udaf));
         });
-        Ok(self.aggregate_functions.insert(udaf.name().into(), udaf))
+        Ok(aggregate_functions.insert(udaf.name().into(), udaf))
     }
 
     fn register_udwf(
         &mut self,
         udwf: Arc<WindowUDF>,
     ) -> datafusion_common::Result<Option<Arc<WindowUDF>>> {
+        let window_functions = Arc::make_mut(&mut self.window_functions);
         udwf.aliases().iter().for_each(|alias| {
-            self.window_functions
-                .insert(alias.clone(), Arc::clone(&udwf));
+            window_functions.insert(alias.clone(), Arc::clone(&udwf));
         });
-        Ok(self.window_functions.insert(udwf.name().into(), udwf))
+        Ok(window_functions.insert(udwf.name().into(), udwf))
     }
 
     fn deregister_udf(
         &mut self,
         name: &str,
     ) -> datafusion_common::Result<Option<Arc<ScalarUDF>>> {
-        let udf = self.scalar_functions.remove(name);
+        if !self.scalar_functions.contains_key(name) {
+            return Ok(None);
+        }
+        let scalar_functions = Arc::make_mut(&mut self.scalar_functions);
+        let udf = scalar_functions.remove(name);
         if let Some(udf) = &udf {
             for alias in udf.aliases() {
-                self.scalar_functions.remove(alias);
+                scalar_functions.remove(alias);
             }
         }
         Ok(udf)
@@ -2205,10 +2824,14 @@ impl FunctionRegistry for SessionState {
         &mut self,
         name: &str,
     ) -> datafusion_common::Result<Option<Arc<AggregateUDF>>> {
-        let udaf = self.aggregate_functions.remove(name);
+        if !self.aggregate_functions.contains_key(name) {
+            return Ok(None);
+        }
+        let aggregate_functions = Arc::make_mut(&mut self.aggregate_functions);
+        let udaf = aggregate_functions.remove(name);
         if let Some(udaf) = &udaf {
             for alias in udaf.aliases() {
-                self.aggregate_functions.remove(alias);
+                aggregate_functions.remove(alias);
             }
         }
         Ok(udaf)
@@ -2218,10 +2841,14 @@ impl FunctionRegistry for SessionState {
         &mut self,
         name: &str,
     ) -> datafusion_common::Result<Option<Arc<WindowUDF>>> {
-        let udwf = self.window_functions.remove(name);
+        if !self.window_functions.contains_key(name) {
+            return Ok(None);
+        }
+        let window_functions = Arc::make_mut(&mut self.window_functions);
+        let udwf = window_functions.remove(name);
         if let Some(udwf) = &udwf {
             for alias in udwf.aliases() {
-                self.window_functions.remove(alias);
+                window_functions.remove(alias);
             }
         }
         Ok(udwf)
@@ -2239,6 +2866,10 @@ impl FunctionRegistry for SessionState {
         self.expr_planners.clone()
     }
 
+    fn cast_registry(&self) -> Option<CastRegistryRef> {
+        self.execution_props.cast_registry.clone()
+    }
+
     fn register_expr_planner(
         &mut self,
         expr_planner: Arc<dyn ExprPlanner>,
@@ -2292,10 +2923,10 @@ impl From<&SessionState> for TaskContext {
             task_id,
             state.session_id.clone(),
             state.config.clone(),
-            state.scalar_functions.clone(),
+            state.scalar_functions.as_ref().clone(),
             state.higher_order_functions.clone(),
-            state.aggregate_functions.clone(),
-            state.window_functions.clone(),
+            state.aggregate_functions.as_ref().clone(),
+            state.window_functions.as_ref().clone(),
             Arc::clone(&state.runtime_env),
         )
     }
@@ -2341,35 +2972,70 @@ pub trait CacheFactory: Debug + Send + Sync {
     ) -> datafusion_common::Result<LogicalPlan>;
 }
 
+/// A [`TableResolver`] can be registered via [`SessionState`] to resolve the
+/// tables referenced by each query before the catalogs are consulted.
+///
+/// This allows routing the same table name to different [`TableProvider`]s
+/// per query, e.g. to the tables of the tenant identified by an extension of
+/// the [`SessionConfig`], without registering a copy of each table for every
+/// tenant.
+#[async_trait]
+pub trait TableResolver: Debug + Send + Sync {
+    /// Returns the table for `table_ref`, or `None` to resolve it from the
+    /// catalogs
+    async fn resolve_table(
+        &self,
+        table_ref: &ResolvedTableReference,
+        config: &SessionConfig,
+    ) -> datafusion_common::Result<Option<Arc<dyn TableProvider>>>;
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{SessionContextProvider, SessionStateBuilder};
+    use super::{SessionContextProvider, SessionStateBuilder, TableResolver};
+    use crate::catalog::{
+        AsyncCatalogProvider, AsyncCatalogProviderList, AsyncSchemaProvider,
+        TableProvider,
+    };
     use crate::common::assert_contains;
     use crate::config::ConfigOptions;
     use crate::datasource::MemTable;
     use crate::datasource::empty::EmptyTable;
     use crate::datasource::provider_as_source;
-    use crate::execution::context::SessionState;
+    use crate::execution::context::{SessionContext, SessionState};
+    use crate::execution::query_middleware::QueryMiddleware;
     use crate::logical_expr::planner::ExprPlanner;
     use crate::logical_expr::{AggregateUDF, ScalarUDF, TableSource, WindowUDF};
     use crate::physical_plan::ExecutionPlan;
     use crate::sql::planner::ContextProvider;
     use crate::sql::{ResolvedTableReference, TableReference};
+    use arrow::array::AsArray;
     use arrow::array::{ArrayRef, Int32Array, RecordBatch, StringArray};
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::datatypes::{DataType, Field, Int32Type, Schema};
+    use async_trait::async_trait;
+    use chrono::DateTime;
     use datafusion_catalog::MemoryCatalogProviderList;
     use datafusion_common::DFSchema;
-    use datafusion_common::Result;
+    use datafusion_common::clock::FixedClock;
     use datafusion_common::config::Dialect;
+    use datafusion_common::timezone::MemoryTimeZoneDatabase;
+    use datafusion_common::tree_node::Transformed;
+    use datafusion_common::{Result, plan_err};
     use datafusion_execution::config::SessionConfig;
-    use datafusion_expr::Expr;
     use datafusion_expr::HigherOrderUDF;
+    use datafusion_expr::expr_rewriter::FunctionRewrite;
+    use datafusion_expr::registry::FunctionRegistry;
+    use datafusion_expr::{
+        BinaryExpr, EmptyRelation, Expr, LogicalPlan, Operator, PlanRewrite,
+        PolicyProvider, col, lit,
+    };
     use datafusion_optimizer::Optimizer;
     use datafusion_optimizer::optimizer::OptimizerRule;
     use datafusion_physical_plan::display::DisplayableExecutionPlan;
     use datafusion_sql::planner::{PlannerContext, SqlToRel};
     use std::collections::HashMap;
     use std::sync::Arc;
+    use std::sync::atomic::{AtomicI32, Ordering};
 
     #[test]
     #[cfg(feature = "sql")]
@@ -2602,6 +3268,574 @@ mod tests {
         Ok(())
     }
 
+    /// Rewrites `a || b` to a call of the registered `concat` function
+    #[derive(Debug)]
+    struct ConcatToFunction;
+
+    impl FunctionRewrite for ConcatToFunction {
+        fn name(&self) -> &str {
+            "concat_to_function"
+        }
+
+        fn rewrite_with_registry(
+            &self,
+            expr: Expr,
+            _schema: &DFSchema,
+            _config: &ConfigOptions,
+            registry: &dyn FunctionRegistry,
+        ) -> Result<Transformed<Expr>> {
+            match expr {
+                Expr::BinaryExpr(BinaryExpr {
+                    left,
+                    op: Operator::StringConcat,
+                    right,
+                }) => Ok(Transformed::yes(
+                    registry.udf("concat")?.call(vec![*left, *right]),
+                )),
+                _ => Ok(Transformed::no(expr)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_function_rewrite_with_registry() -> Result<()> {
+        let mut state = SessionStateBuilder::new().with_default_features().build();
+        state.register_function_rewrite(Arc::new(ConcatToFunction))?;
+        let ctx = SessionContext::new_with_state(state);
+        let batch = RecordBatch::try_from_iter([
+            ("a", Arc::new(StringArray::from(vec!["x"])) as ArrayRef),
+            ("b", Arc::new(StringArray::from(vec!["y"])) as ArrayRef),
+        ])?;
+        ctx.register_batch("t", batch)?;
+
+        let df = ctx.sql("SELECT a || b AS c FROM t").await?;
+        let plan = df.clone().into_optimized_plan()?;
+        assert_contains!(plan.display_indent().to_string(), "concat(t.a, t.b) AS c");
+        let batches = df.collect().await?;
+        assert_eq!(batches[0].column(0).as_string::<i32>().value(0), "xy");
+        Ok(())
+    }
+
+    /// Replaces filters on scans of table `t` with an empty relation
+    #[derive(Debug)]
+    struct EmptyFilteredScans;
+
+    impl PlanRewrite for EmptyFilteredScans {
+        fn name(&self) -> &str {
+            "empty_filtered_scans"
+        }
+
+        fn rewrite(
+            &self,
+            plan: LogicalPlan,
+            _config: &ConfigOptions,
+        ) -> Result<Transformed<LogicalPlan>> {
+            match &plan {
+                LogicalPlan::Filter(filter)
+                    if matches!(
+                        filter.input.as_ref(),
+                        LogicalPlan::TableScan(scan) if scan.table_name.table() == "t"
+                    ) =>
+                {
+                    Ok(Transformed::yes(LogicalPlan::EmptyRelation(
+                        EmptyRelation {
+                            produce_one_row: false,
+                            schema: Arc::clone(plan.schema()),
+                        },
+                    )))
+                }
+                _ => Ok(Transformed::no(plan)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_plan_rewrite() -> Result<()> {
+        let ctx = SessionContext::new();
+        let batch = RecordBatch::try_from_iter([(
+            "a",
+            Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+        )])?;
+        ctx.register_batch("t", batch.clone())?;
+        ctx.register_batch("u", batch)?;
+        ctx.register_plan_rewrite(Arc::new(EmptyFilteredScans));
+
+        let plan = ctx
+            .sql("SELECT a FROM t WHERE a > 1")
+            .await?
+            .into_optimized_plan()?;
+        assert_eq!(plan.display_indent().to_string(), "EmptyRelation: rows=0");
+        let batches = ctx
+            .sql("SELECT a FROM u WHERE a > 1")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+        Ok(())
+    }
+
+    /// Shows the rows of tenant 1 of table `t`, and masks their secrets
+    #[derive(Debug)]
+    struct TenantPolicy;
+
+    impl PolicyProvider for TenantPolicy {
+        fn name(&self) -> &str {
+            "tenant_policy"
+        }
+
+        fn row_filter(
+            &self,
+            table: &TableReference,
+            _schema: &DFSchema,
+            _config: &ConfigOptions,
+        ) -> Result<Option<Expr>> {
+            Ok((table.table() == "t").then(|| col("tenant").eq(lit(1))))
+        }
+
+        fn column_masks(
+            &self,
+            table: &TableReference,
+            _schema: &DFSchema,
+            _config: &ConfigOptions,
+        ) -> Result<datafusion_common::HashMap<String, Expr>> {
+            Ok((table.table() == "t")
+                .then(|| ("secret".to_string(), lit("***")))
+                .into_iter()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_policy_provider() -> Result<()> {
+        let ctx = SessionContext::new();
+        let batch = RecordBatch::try_from_iter([
+            (
+                "tenant",
+                Arc::new(Int32Array::from(vec![1, 2, 1])) as ArrayRef,
+            ),
+            (
+                "secret",
+                Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef,
+            ),
+        ])?;
+        ctx.register_batch("t", batch.clone())?;
+        ctx.register_batch("u", batch)?;
+        ctx.register_policy_provider(Arc::new(TenantPolicy));
+
+        // the rows are filtered even if the query does not read `tenant`
+        let batches = ctx
+            .sql("SELECT secret, t.secret = 'a' AS revealed FROM t")
+            .await?
+            .collect()
+            .await?;
+        datafusion_common::assert_batches_eq!(
+            [
+                "+--------+----------+",
+                "| secret | revealed |",
+                "+--------+----------+",
+                "| ***    | false    |",
+                "| ***    | false    |",
+                "+--------+----------+",
+            ],
+            &batches
+        );
+
+        // other tables are not affected
+        let batches = ctx
+            .sql("SELECT secret FROM u WHERE tenant = 2")
+            .await?
+            .collect()
+            .await?;
+        datafusion_common::assert_batches_eq!(
+            [
+                "+--------+",
+                "| secret |",
+                "+--------+",
+                "| b      |",
+                "+--------+",
+            ],
+            &batches
+        );
+        Ok(())
+    }
+
+    /// The tenant of a session, stored as an extension of its config
+    #[derive(Debug)]
+    struct Tenant(i32);
+
+    /// Resolves `orders` to the orders of the tenant of the session
+    #[derive(Debug)]
+    struct TenantTableResolver;
+
+    #[async_trait]
+    impl TableResolver for TenantTableResolver {
+        async fn resolve_table(
+            &self,
+            table_ref: &ResolvedTableReference,
+            config: &SessionConfig,
+        ) -> Result<Option<Arc<dyn TableProvider>>> {
+            let Some(tenant) = config.get_extension::<Tenant>() else {
+                return Ok(None);
+            };
+            if table_ref.table.as_ref() != "orders" {
+                return Ok(None);
+            }
+            let batch = RecordBatch::try_from_iter([(
+                "tenant",
+                Arc::new(Int32Array::from(vec![tenant.0])) as ArrayRef,
+            )])?;
+            let table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+            Ok(Some(Arc::new(table)))
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_table_resolver() -> Result<()> {
+        let tenant_ctx = |tenant| {
+            let config = SessionConfig::new().with_extension(Arc::new(Tenant(tenant)));
+            let state = SessionStateBuilder::new()
+                .with_config(config)
+                .with_default_features()
+                .with_table_resolver(Some(Arc::new(TenantTableResolver)))
+                .build();
+            SessionContext::new_with_state(state)
+        };
+
+        for tenant in [1, 2] {
+            let ctx = tenant_ctx(tenant);
+            let batches = ctx
+                .sql("SELECT tenant FROM orders")
+                .await?
+                .collect()
+                .await?;
+            assert_eq!(
+                batches[0].column(0).as_primitive::<Int32Type>().value(0),
+                tenant
+            );
+
+            let batches = ctx.table("orders").await?.collect().await?;
+            assert_eq!(
+                batches[0].column(0).as_primitive::<Int32Type>().value(0),
+                tenant
+            );
+        }
+
+        // other tables are resolved from the catalogs
+        let ctx = tenant_ctx(1);
+        ctx.register_batch(
+            "t",
+            RecordBatch::try_from_iter([(
+                "a",
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            )])?,
+        )?;
+        let batches = ctx.sql("SELECT a FROM t").await?.collect().await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+        let err = ctx.sql("SELECT * FROM missing").await.unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "table 'datafusion.public.missing' not found"
+        );
+        Ok(())
+    }
+
+    /// Renames the tables of a legacy schema in the SQL text of queries
+    #[derive(Debug)]
+    struct LegacyTableNames;
+
+    impl QueryMiddleware for LegacyTableNames {
+        fn name(&self) -> &str {
+            "legacy_table_names"
+        }
+
+        fn rewrite_sql(
+            &self,
+            sql: String,
+            _state: &SessionState,
+        ) -> Result<Transformed<String>> {
+            if sql.contains("legacy_t") {
+                Ok(Transformed::yes(sql.replace("legacy_t", "t")))
+            } else {
+                Ok(Transformed::no(sql))
+            }
+        }
+    }
+
+    /// Rejects the queries modifying the catalogs or the tables
+    #[derive(Debug)]
+    struct ReadOnlyFirewall;
+
+    impl QueryMiddleware for ReadOnlyFirewall {
+        fn name(&self) -> &str {
+            "read_only_firewall"
+        }
+
+        fn rewrite_plan(
+            &self,
+            plan: LogicalPlan,
+            _state: &SessionState,
+        ) -> Result<Transformed<LogicalPlan>> {
+            match plan {
+                LogicalPlan::Ddl(_) | LogicalPlan::Dml(_) => {
+                    plan_err!("Only read-only queries are allowed")
+                }
+                plan => Ok(Transformed::no(plan)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_query_middlewares() -> Result<()> {
+        let state = SessionStateBuilder::new()
+            .with_default_features()
+            .with_query_middleware(Arc::new(LegacyTableNames))
+            .build();
+        let ctx = SessionContext::new_with_state(state);
+        ctx.register_query_middleware(Arc::new(ReadOnlyFirewall));
+        ctx.register_batch(
+            "t",
+            RecordBatch::try_from_iter([(
+                "a",
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            )])?,
+        )?;
+
+        let batches = ctx.sql("SELECT a FROM legacy_t").await?.collect().await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+        let err = ctx
+            .sql("CREATE TABLE u AS SELECT a FROM t")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "query middleware read_only_firewall\ncaused by\n\
+             Error during planning: Only read-only queries are allowed"
+        );
+        assert!(!ctx.table_exist("u")?);
+
+        // both the SQL text and the plan of each query are passed to every
+        // middleware
+        let metrics = ctx.query_middleware_metrics("legacy_table_names").unwrap();
+        assert_eq!(metrics.calls(), 4);
+        assert_eq!(metrics.rewrites(), 1);
+        assert_eq!(metrics.errors(), 0);
+
+        let metrics = ctx.query_middleware_metrics("read_only_firewall").unwrap();
+        assert_eq!(metrics.calls(), 4);
+        assert_eq!(metrics.rewrites(), 0);
+        assert_eq!(metrics.errors(), 1);
+
+        // the metrics are shared with the sessions created from this one
+        let state = SessionStateBuilder::new_from_existing(ctx.state()).build();
+        let ctx = SessionContext::new_with_state(state);
+        ctx.sql("SELECT a FROM legacy_t").await?;
+        assert_eq!(metrics.calls(), 6);
+        assert!(ctx.query_middleware_metrics("missing").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_clock() -> Result<()> {
+        let clock = FixedClock(DateTime::from_timestamp(86_400, 0).unwrap());
+        let state = SessionStateBuilder::new()
+            .with_default_features()
+            .with_clock(Arc::new(clock))
+            .build();
+        let ctx = SessionContext::new_with_state(state);
+        assert_eq!(ctx.session_start_time(), clock.0);
+
+        let batches = ctx
+            .sql("SELECT current_date() AS today")
+            .await?
+            .collect()
+            .await?;
+        datafusion_common::assert_batches_eq!(
+            [
+                "+------------+",
+                "| today      |",
+                "+------------+",
+                "| 1970-01-02 |",
+                "+------------+",
+            ],
+            &batches
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_time_zone_database() -> Result<()> {
+        let database = MemoryTimeZoneDatabase::new("windows")
+            .with_zone("Eastern Standard Time", "America/New_York");
+        let state = SessionStateBuilder::new()
+            .with_default_features()
+            .with_time_zone_database(Some(Arc::new(database)))
+            .build();
+        let ctx = SessionContext::new_with_state(state);
+
+        ctx.sql("SET TIME ZONE = 'Eastern Standard Time'").await?;
+        assert_eq!(
+            ctx.state()
+                .config()
+                .options()
+                .execution
+                .time_zone
+                .as_deref(),
+            Some("America/New_York")
+        );
+
+        // the zones of the bundled database are not known
+        let err = ctx.sql("SET TIME ZONE = 'Europe/Paris'").await.unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Invalid time zone 'Europe/Paris': not found in the 'windows' time zone database"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_static_default_functions() -> Result<()> {
+        let build = |config: SessionConfig| {
+            SessionStateBuilder::new()
+                .with_config(config)
+                .with_default_features()
+                .with_static_default_functions()
+                .build()
+        };
+        let mut state1 = build(SessionConfig::new());
+        let state2 = build(SessionConfig::new());
+        assert!(Arc::ptr_eq(
+            &state1.scalar_functions,
+            &state2.scalar_functions
+        ));
+        assert!(Arc::ptr_eq(
+            &state1.aggregate_functions,
+            &state2.aggregate_functions
+        ));
+        assert!(Arc::ptr_eq(
+            &state1.window_functions,
+            &state2.window_functions
+        ));
+
+        // the same functions are registered as without the shared registry
+        let expected = SessionStateBuilder::new().with_default_features().build();
+        assert_eq!(state1.scalar_functions(), expected.scalar_functions());
+        assert_eq!(state1.aggregate_functions(), expected.aggregate_functions());
+        assert_eq!(state1.window_functions(), expected.window_functions());
+
+        // modifying a session copies its registry
+        state1.deregister_udf("abs")?;
+        assert!(!state1.scalar_functions().contains_key("abs"));
+        assert!(state2.scalar_functions().contains_key("abs"));
+
+        // functions that depend on the configuration are updated
+        let state3 = build(
+            SessionConfig::new().set_str("datafusion.execution.time_zone", "+05:00"),
+        );
+        assert!(!Arc::ptr_eq(
+            &state2.scalar_functions,
+            &state3.scalar_functions
+        ));
+        assert_ne!(state2.udf("now")?, state3.udf("now")?);
+        assert!(Arc::ptr_eq(
+            &state2.aggregate_functions,
+            &state3.aggregate_functions
+        ));
+        Ok(())
+    }
+
+    /// The tables of a remote catalog `remote.db`, which return the number of
+    /// times they were looked up
+    #[derive(Debug, Clone, Default)]
+    struct RemoteCatalog {
+        lookups: Arc<AtomicI32>,
+    }
+
+    #[async_trait]
+    impl AsyncCatalogProviderList for RemoteCatalog {
+        async fn catalog(
+            &self,
+            name: &str,
+        ) -> Result<Option<Arc<dyn AsyncCatalogProvider>>> {
+            Ok((name == "remote").then(|| Arc::new(self.clone()) as _))
+        }
+    }
+
+    #[async_trait]
+    impl AsyncCatalogProvider for RemoteCatalog {
+        async fn schema(
+            &self,
+            name: &str,
+        ) -> Result<Option<Arc<dyn AsyncSchemaProvider>>> {
+            Ok((name == "db").then(|| Arc::new(self.clone()) as _))
+        }
+    }
+
+    #[async_trait]
+    impl AsyncSchemaProvider for RemoteCatalog {
+        async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+            if name != "t" {
+                return Ok(None);
+            }
+            let lookups = self.lookups.fetch_add(1, Ordering::Relaxed) + 1;
+            let batch = RecordBatch::try_from_iter([(
+                "lookups",
+                Arc::new(Int32Array::from(vec![lookups])) as ArrayRef,
+            )])?;
+            let table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+            Ok(Some(Arc::new(table)))
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "sql")]
+    async fn test_async_catalog_list() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_async_catalog_list(Arc::new(RemoteCatalog::default()));
+        async fn lookups(ctx: &SessionContext) -> Result<i32> {
+            let batches = ctx
+                .sql("SELECT lookups FROM remote.db.t")
+                .await?
+                .collect()
+                .await?;
+            Ok(batches[0].column(0).as_primitive::<Int32Type>().value(0))
+        }
+
+        // the table is looked up once, and cached until it is invalidated
+        assert_eq!(lookups(&ctx).await?, 1);
+        assert_eq!(lookups(&ctx).await?, 1);
+        ctx.table("remote.db.t").await?;
+        ctx.state().invalidate_async_table("remote.db.t");
+        assert_eq!(lookups(&ctx).await?, 2);
+        ctx.state().invalidate_async_catalog();
+        assert_eq!(lookups(&ctx).await?, 3);
+
+        // the tables of the catalogs are still resolved
+        ctx.register_batch(
+            "t",
+            RecordBatch::try_from_iter([(
+                "a",
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            )])?,
+        )?;
+        let batches = ctx.sql("SELECT a FROM t").await?.collect().await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+        let err = ctx
+            .sql("SELECT * FROM remote.db.missing")
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "table 'remote.db.missing' not found");
+        Ok(())
+    }
+
     /// A `ContextProvider` based on `SessionState`.
     ///
     /// Almost all planning context are retrieved from the `SessionState`.
@@ -22,24 +22,33 @@ use crate::datasource::file_format::arrow::ArrowFormatFactory;
 #[cfg(feature = "avro")]
 use crate::datasource::file_format::avro::AvroFormatFactory;
 use crate::datasource::file_format::csv::CsvFormatFactory;
+#[cfg(feature = "json")]
 use crate::datasource::file_format::json::JsonFormatFactory;
 #[cfg(feature = "parquet")]
 use crate::datasource::file_format::parquet::ParquetFormatFactory;
+use crate::datasource::listing::SchemaReportFunc;
 use crate::datasource::provider::DefaultTableFactory;
 use crate::execution::context::SessionState;
+#[cfg(feature = "json_expressions")]
+use crate::functions_json;
 #[cfg(feature = "nested_expressions")]
 use crate::functions_nested;
 use crate::{functions, functions_aggregate, functions_table, functions_window};
 use datafusion_catalog::TableFunction;
 use datafusion_catalog::{MemoryCatalogProvider, MemorySchemaProvider};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::types::DFCategorical;
 use datafusion_execution::config::SessionConfig;
 use datafusion_execution::object_store::ObjectStoreUrl;
 use datafusion_execution::runtime_env::RuntimeEnv;
+use datafusion_expr::expr_rewriter::FunctionRewrite;
 use datafusion_expr::planner::ExprPlanner;
-use datafusion_expr::registry::ExtensionTypeRegistrationRef;
+use datafusion_expr::registry::{
+    ExtensionTypeRegistration, ExtensionTypeRegistrationRef,
+};
 use datafusion_expr::{AggregateUDF, HigherOrderUDF, ScalarUDF, WindowUDF};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use url::Url;
 
 /// Defaults that are used as part of creating a SessionState such as table providers,
@@ -104,12 +113,18 @@ impl SessionStateDefaults {
 
     /// returns the list of default [`ScalarUDF`]s
     pub fn default_scalar_functions() -> Vec<Arc<ScalarUDF>> {
-        #[cfg_attr(not(feature = "nested_expressions"), expect(unused_mut))]
+        #[cfg_attr(
+            not(any(feature = "nested_expressions", feature = "json_expressions")),
+            expect(unused_mut)
+        )]
         let mut functions: Vec<Arc<ScalarUDF>> = functions::all_default_functions();
 
         #[cfg(feature = "nested_expressions")]
         functions.append(&mut functions_nested::all_default_nested_functions());
 
+        #[cfg(feature = "json_expressions")]
+        functions.append(&mut functions_json::all_default_json_functions());
+
         functions
     }
 
@@ -132,16 +147,87 @@ impl SessionStateDefaults {
         functions_window::all_default_window_functions()
     }
 
+    /// Returns the default scalar, aggregate and window functions indexed by
+    /// name and alias, built on first use and shared by all sessions that are
+    /// built with [`SessionStateBuilder::with_static_default_functions`]
+    ///
+    /// [`SessionStateBuilder::with_static_default_functions`]: crate::execution::SessionStateBuilder::with_static_default_functions
+    pub(crate) fn static_default_functions() -> &'static StaticDefaultFunctions {
+        static FUNCTIONS: LazyLock<StaticDefaultFunctions> = LazyLock::new(|| {
+            let config = ConfigOptions::default();
+            let mut scalar_functions = HashMap::new();
+            let mut config_dependent_functions = vec![];
+            for udf in SessionStateDefaults::default_scalar_functions() {
+                let udf = match udf.inner().with_updated_config(&config) {
+                    Some(new_udf) => {
+                        let new_udf = Arc::new(new_udf);
+                        config_dependent_functions.push(Arc::clone(&new_udf));
+                        new_udf
+                    }
+                    None => udf,
+                };
+                index_function(&mut scalar_functions, udf.name(), udf.aliases(), &udf);
+            }
+            let mut aggregate_functions = HashMap::new();
+            for udaf in SessionStateDefaults::default_aggregate_functions() {
+                index_function(
+                    &mut aggregate_functions,
+                    udaf.name(),
+                    udaf.aliases(),
+                    &udaf,
+                );
+            }
+            let mut window_functions = HashMap::new();
+            for udwf in SessionStateDefaults::default_window_functions() {
+                index_function(&mut window_functions, udwf.name(), udwf.aliases(), &udwf);
+            }
+            StaticDefaultFunctions {
+                scalar_functions: Arc::new(scalar_functions),
+                aggregate_functions: Arc::new(aggregate_functions),
+                window_functions: Arc::new(window_functions),
+                config_dependent_functions,
+            }
+        });
+        &FUNCTIONS
+    }
+
     /// Returns the list of default extension types.
     ///
-    /// For now, we do not register any extension types by default.
+    /// For now, only the [`DFCategorical`] type, which can be declared in SQL
+    /// using `ENUM(...)`, is registered by default.
     pub fn default_extension_types() -> Vec<ExtensionTypeRegistrationRef> {
-        vec![]
+        vec![ExtensionTypeRegistration::new_arc(
+            DFCategorical::NAME,
+            |storage_type, metadata| {
+                Ok(Arc::new(DFCategorical::try_new_from_metadata(
+                    storage_type,
+                    metadata,
+                )?))
+            },
+        )]
     }
 
     /// returns the list of default [`TableFunction`]s
     pub fn default_table_functions() -> Vec<Arc<TableFunction>> {
-        functions_table::all_default_table_functions()
+        let mut functions = functions_table::all_default_table_functions();
+        functions.push(Arc::new(TableFunction::new(
+            "schema_report".to_string(),
+            Arc::new(SchemaReportFunc::default()),
+        )));
+
+        #[cfg(feature = "json_expressions")]
+        functions.append(&mut functions_json::all_default_json_table_functions());
+
+        functions
+    }
+
+    /// returns the list of default [`FunctionRewrite`]s
+    pub fn default_function_rewrites() -> Vec<Arc<dyn FunctionRewrite + Send + Sync>> {
+        #[cfg(feature = "json_expressions")]
+        return functions_json::all_default_json_function_rewrites();
+
+        #[cfg(not(feature = "json_expressions"))]
+        return Vec::new();
     }
 
     /// returns the list of default [`FileFormatFactory`]s
@@ -149,6 +235,7 @@ impl SessionStateDefaults {
         let file_formats: Vec<Arc<dyn FileFormatFactory>> = vec![
             #[cfg(feature = "parquet")]
             Arc::new(ParquetFormatFactory::new()),
+            #[cfg(feature = "json")]
             Arc::new(JsonFormatFactory::new()),
             Arc::new(CsvFormatFactory::new()),
             Arc::new(ArrowFormatFactory::new()),
@@ -241,3 +328,27 @@ impl SessionStateDefaults {
         }
     }
 }
+
+/// The default functions shared by sessions, see
+/// [`SessionStateDefaults::static_default_functions`]
+pub(crate) struct StaticDefaultFunctions {
+    pub(crate) scalar_functions: Arc<HashMap<String, Arc<ScalarUDF>>>,
+    pub(crate) aggregate_functions: Arc<HashMap<String, Arc<AggregateUDF>>>,
+    pub(crate) window_functions: Arc<HashMap<String, Arc<WindowUDF>>>,
+    /// Scalar functions that were updated for the default [`ConfigOptions`],
+    /// which sessions with another configuration update again
+    pub(crate) config_dependent_functions: Vec<Arc<ScalarUDF>>,
+}
+
+/// Registers `function` under its `name` and all of its `aliases`
+fn index_function<T>(
+    functions: &mut HashMap<String, Arc<T>>,
+    name: &str,
+    aliases: &[String],
+    function: &Arc<T>,
+) {
+    for alias in aliases {
+        functions.insert(alias.clone(), Arc::clone(function));
+    }
+    functions.insert(name.to_string(), Arc::clone(function));
+}
@@ -641,6 +641,10 @@
 //!                                                                                           time
 //! ```
 //!
+//! For the requests of the [`ObjectStore`]s registered in the [`RuntimeEnv`],
+//! [`RuntimeEnvBuilder::with_io_runtime`] configures such a separate IO
+//! [`Runtime`], while the plans keep running on the current [`Runtime`].
+//!
 //! Note that DataFusion does not use [`tokio::task::spawn_blocking`] for
 //! CPU-bounded work, because `spawn_blocking` is designed for blocking **IO**,
 //! not designed CPU bound tasks. Among other challenges, spawned blocking
@@ -655,6 +659,8 @@
 //! [Using Rustlang’s Async Tokio Runtime for CPU-Bound Tasks]: https://thenewstack.io/using-rustlangs-async-tokio-runtime-for-cpu-bound-tasks/
 //! [`RepartitionExec`]: physical_plan::repartition::RepartitionExec
 //! [`CoalescePartitionsExec`]: physical_plan::coalesce_partitions::CoalescePartitionsExec
+//! [`ObjectStore`]: object_store::ObjectStore
+//! [`RuntimeEnvBuilder::with_io_runtime`]: crate::execution::runtime_env::RuntimeEnvBuilder::with_io_runtime
 //!
 //! ## State Management and Configuration
 //!
@@ -718,6 +724,7 @@
 //! * [datafusion_expr]: [`LogicalPlan`], [`Expr`] and related logical planning structure
 //! * [datafusion_functions]: Scalar function packages
 //! * [datafusion_functions_aggregate]: Aggregate functions such as `MIN`, `MAX`, `SUM`, etc
+//! * [datafusion_functions_json]: Functions, operators and casts for JSON documents
 //! * [datafusion_functions_nested]: Scalar function packages for `ARRAY`s, `MAP`s and `STRUCT`s
 //! * [datafusion_functions_table]: Table Functions such as `GENERATE_SERIES`
 //! * [datafusion_functions_window]: Window functions such as `ROW_NUMBER`, `RANK`, etc
@@ -869,6 +876,12 @@ pub mod functions {
     pub use datafusion_functions::*;
 }
 
+/// re-export of [`datafusion_functions_json`] crate, if "json_expressions" feature is enabled
+pub mod functions_json {
+    #[cfg(feature = "json_expressions")]
+    pub use datafusion_functions_json::*;
+}
+
 /// re-export of [`datafusion_functions_nested`] crate, if "nested_expressions" feature is enabled
 pub mod functions_nested {
     #[cfg(feature = "nested_expressions")]
@@ -42,7 +42,8 @@ use crate::physical_plan::explain::ExplainExec;
 use crate::physical_plan::filter::FilterExecBuilder;
 use crate::physical_plan::joins::utils as join_utils;
 use crate::physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
+    AsOfJoinExec, CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
+    SortMergeJoinExec,
 };
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::projection::{ProjectionExec, ProjectionExpr};
@@ -62,6 +63,8 @@ use arrow::compute::SortOptions;
 use arrow::datatypes::Schema;
 use arrow_schema::Field;
 use datafusion_catalog::ScanArgs;
+use datafusion_catalog::analyze::AnalyzeSink;
+use datafusion_catalog::lateral::LateralTableFunctionExec;
 use datafusion_common::Column;
 use datafusion_common::HashMap as DFHashMap;
 use datafusion_common::display::ToStringifiedPlan;
@@ -71,13 +74,14 @@ use datafusion_common::tree_node::{
 };
 use datafusion_common::{
     DFSchema, DFSchemaRef, ScalarValue, exec_err, internal_datafusion_err, internal_err,
-    not_impl_err, plan_err,
+    not_impl_err, plan_datafusion_err, plan_err,
 };
 use datafusion_common::{
     TableReference, assert_eq_or_internal_err, assert_or_internal_err,
 };
 use datafusion_datasource::file_groups::FileGroup;
 use datafusion_datasource::memory::MemorySourceConfig;
+use datafusion_datasource::sink::DataSinkExec;
 use datafusion_expr::dml::{CopyTo, InsertOp};
 use datafusion_expr::execution_props::{ScalarSubqueryResults, SubqueryIndex};
 use datafusion_expr::expr::{
@@ -90,8 +94,9 @@ use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessar
 use datafusion_expr::utils::{expr_to_columns, split_conjunction};
 use datafusion_expr::{
     Analyze, BinaryExpr, DescribeTable, DmlStatement, Explain, ExplainFormat, Extension,
-    FetchType, Filter, JoinType, Operator, RecursiveQuery, SkipType, StringifiedPlan,
-    WindowFrame, WindowFrameBound, WriteOp,
+    FetchType, Filter, JoinType, LateralTableFunction, Operator, RecursiveQuery,
+    SkipType, StringifiedPlan, WindowFrame, WindowFrameBound, WindowFunctionDefinition,
+    WriteOp,
 };
 use datafusion_physical_expr::aggregate::{AggregateExprBuilder, AggregateFunctionExpr};
 use datafusion_physical_expr::expressions::Literal;
@@ -106,6 +111,7 @@ use datafusion_physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion_physical_plan::recursive_query::RecursiveQueryExec;
 use datafusion_physical_plan::scalar_subquery::{ScalarSubqueryExec, ScalarSubqueryLink};
 use datafusion_physical_plan::unnest::ListUnnest;
+use datafusion_physical_plan::values::ValuesBatchGenerator;
 
 use async_trait::async_trait;
 use datafusion_physical_plan::async_func::{AsyncFuncExec, AsyncMapper};
@@ -697,8 +703,22 @@ impl DefaultPhysicalPlanner {
                             .collect::<Result<Vec<Arc<dyn PhysicalExpr>>>>()
                     })
                     .collect::<Result<Vec<_>>>()?;
-                MemorySourceConfig::try_new_as_values(Arc::clone(schema.inner()), exprs)?
-                    as _
+                // Evaluate large lists one batch at a time instead of
+                // materializing them as a single batch up front
+                let batch_size = session_state.config().batch_size();
+                if exprs.len() > batch_size {
+                    let generator = ValuesBatchGenerator::try_new(
+                        Arc::clone(schema.inner()),
+                        exprs,
+                        batch_size,
+                    )?;
+                    Arc::new(generator.into_exec()?)
+                } else {
+                    MemorySourceConfig::try_new_as_values(
+                        Arc::clone(schema.inner()),
+                        exprs,
+                    )? as _
+                }
             }
             LogicalPlan::EmptyRelation(EmptyRelation {
                 produce_one_row: false,
@@ -805,6 +825,7 @@ impl DefaultPhysicalPlanner {
                     keep_partition_by_columns,
                     file_extension,
                     file_output_mode,
+                    statistics_cache: None,
                 };
 
                 let ordering = input_exec.properties().output_ordering().cloned();
@@ -905,6 +926,45 @@ impl DefaultPhysicalPlanner {
                     );
                 }
             }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                target,
+                op: WriteOp::Merge,
+                ..
+            }) => {
+                if let Some(provider) = target.downcast_ref::<DefaultTableSource>() {
+                    let input_exec = children.one()?;
+                    provider
+                        .table_provider
+                        .merge_into(session_state, input_exec)
+                        .await
+                        .map_err(|e| {
+                            e.context(format!("MERGE operation on table '{table_name}'"))
+                        })?
+                } else {
+                    return exec_err!(
+                        "Table source can't be downcasted to DefaultTableSource"
+                    );
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                target,
+                op: WriteOp::Analyze,
+                ..
+            }) => {
+                if let Some(provider) = target.downcast_ref::<DefaultTableSource>() {
+                    let input_exec = children.one()?;
+                    let sink = AnalyzeSink::new(
+                        Arc::clone(&provider.table_provider),
+                        input_exec.schema(),
+                    );
+                    Arc::new(DataSinkExec::new(input_exec, Arc::new(sink), None))
+                } else {
+                    return exec_err!(
+                        "Table source can't be downcasted to DefaultTableSource"
+                    );
+                }
+            }
             LogicalPlan::Window(Window { window_expr, .. }) => {
                 assert_or_internal_err!(
                     !window_expr.is_empty(),
@@ -1332,8 +1392,92 @@ impl DefaultPhysicalPlanner {
                     options.clone(),
                 )?)
             }
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                input,
+                function_name,
+                args,
+                join_type,
+                schema,
+                ..
+            }) => {
+                let physical_input = children.one()?;
+                let function = session_state
+                    .table_functions()
+                    .get(function_name)
+                    .ok_or_else(|| {
+                        plan_datafusion_err!("table function '{function_name}' not found")
+                    })?;
+                let args = args
+                    .iter()
+                    .map(|arg| create_physical_expr(arg, input.schema(), execution_props))
+                    .collect::<Result<Vec<_>>>()?;
+                Arc::new(LateralTableFunctionExec::try_new(
+                    physical_input,
+                    function_name.clone(),
+                    Arc::clone(function.function()),
+                    args,
+                    *join_type,
+                    Arc::clone(schema.inner()),
+                )?)
+            }
 
             // 2 Children
+            LogicalPlan::Join(Join {
+                left,
+                right,
+                on,
+                join_type,
+                asof_match: Some(asof_match),
+                ..
+            }) => {
+                let [physical_left, physical_right] = children.two()?;
+                let left_df_schema = left.schema();
+                let right_df_schema = right.schema();
+                let join_on = on
+                    .iter()
+                    .map(|(l, r)| {
+                        let l = create_physical_expr(l, left_df_schema, execution_props)?;
+                        let r =
+                            create_physical_expr(r, right_df_schema, execution_props)?;
+                        Ok((l, r))
+                    })
+                    .collect::<Result<join_utils::JoinOn>>()?;
+                let match_on = (
+                    create_physical_expr(
+                        &asof_match.left,
+                        left_df_schema,
+                        execution_props,
+                    )?,
+                    create_physical_expr(
+                        &asof_match.right,
+                        right_df_schema,
+                        execution_props,
+                    )?,
+                );
+                let tolerance = asof_match
+                    .tolerance
+                    .as_ref()
+                    .map(|expr| {
+                        create_join_filter(
+                            expr,
+                            left_df_schema,
+                            right_df_schema,
+                            &physical_left,
+                            &physical_right,
+                            execution_props,
+                        )
+                    })
+                    .transpose()?;
+                Arc::new(AsOfJoinExec::try_new(
+                    physical_left,
+                    physical_right,
+                    join_on,
+                    match_on,
+                    asof_match.op,
+                    tolerance,
+                    *join_type,
+                )?)
+            }
             LogicalPlan::Join(Join {
                 left: original_left,
                 right: original_right,
@@ -1347,11 +1491,25 @@ impl DefaultPhysicalPlanner {
             }) => {
                 let [physical_left, physical_right] = children.two()?;
 
-                // If join has expression equijoin keys, add physical projection.
+                let config = session_state.config();
+                let prefer_hash_join = config.options().optimizer.prefer_hash_join;
+                let use_sort_merge_join = config.target_partitions() > 1
+                    && config.repartition_joins()
+                    && !prefer_hash_join;
+
+                // If join has expression equijoin keys, add physical projection,
+                // unless hash joins are allowed to evaluate them
                 let has_expr_join_key = keys.iter().any(|(l, r)| {
                     !(matches!(l, Expr::Column(_)) && matches!(r, Expr::Column(_)))
                 });
-                let (new_logical, physical_left, physical_right) = if has_expr_join_key {
+                let evaluate_key_expressions = config
+                    .options()
+                    .optimizer
+                    .hash_join_evaluate_key_expressions
+                    && !use_sort_merge_join;
+                let (new_logical, physical_left, physical_right) = if has_expr_join_key
+                    && !evaluate_key_expressions
+                {
                     // TODO: Can we extract this transformation to somewhere before physical plan
                     //       creation?
                     let (left_keys, right_keys): (Vec<_>, Vec<_>) =
@@ -1463,7 +1621,8 @@ impl DefaultPhysicalPlanner {
                     _ => unreachable!(),
                 };
 
-                // All equi-join keys are columns now, create physical join plan
+                // All equi-join keys are columns now, unless the hash join evaluates
+                // them, create physical join plan
                 let left_df_schema = left.schema();
                 let right_df_schema = right.schema();
                 let join_on = keys
@@ -1512,84 +1671,18 @@ impl DefaultPhysicalPlanner {
                             }
                         }
 
-                        // Extract columns from filter expression and saved in a HashSet
-                        let cols = expr.column_refs();
-
-                        // Collect left & right field indices, the field indices are sorted in ascending order
-                        let left_field_indices = cols
-                            .iter()
-                            .filter_map(|c| left_df_schema.index_of_column(c).ok())
-                            .sorted()
-                            .collect::<Vec<_>>();
-                        let right_field_indices = cols
-                            .iter()
-                            .filter_map(|c| right_df_schema.index_of_column(c).ok())
-                            .sorted()
-                            .collect::<Vec<_>>();
-
-                        // Collect DFFields and Fields required for intermediate schemas
-                        let (filter_df_fields, filter_fields): (Vec<_>, Vec<_>) =
-                            left_field_indices
-                                .clone()
-                                .into_iter()
-                                .map(|i| {
-                                    (
-                                        left_df_schema.qualified_field(i),
-                                        physical_left.schema().field(i).clone(),
-                                    )
-                                })
-                                .chain(right_field_indices.clone().into_iter().map(|i| {
-                                    (
-                                        right_df_schema.qualified_field(i),
-                                        physical_right.schema().field(i).clone(),
-                                    )
-                                }))
-                                .unzip();
-                        let filter_df_fields = filter_df_fields
-                            .into_iter()
-                            .map(|(qualifier, field)| {
-                                (qualifier.cloned(), Arc::clone(field))
-                            })
-                            .collect();
-
-                        let metadata: HashMap<_, _> = left_df_schema
-                            .metadata()
-                            .clone()
-                            .into_iter()
-                            .chain(right_df_schema.metadata().clone())
-                            .collect();
-
-                        // Construct intermediate schemas used for filtering data and
-                        // convert logical expression to physical according to filter schema
-                        let filter_df_schema = DFSchema::new_with_metadata(
-                            filter_df_fields,
-                            metadata.clone(),
-                        )?;
-                        let filter_schema =
-                            Schema::new_with_metadata(filter_fields, metadata);
-
-                        let filter_expr = create_physical_expr(
+                        Some(create_join_filter(
                             expr,
-                            &filter_df_schema,
+                            left_df_schema,
+                            right_df_schema,
+                            &physical_left,
+                            &physical_right,
                             execution_props,
-                        )?;
-                        let column_indices = join_utils::JoinFilter::build_column_indices(
-                            left_field_indices,
-                            right_field_indices,
-                        );
-
-                        Some(join_utils::JoinFilter::new(
-                            filter_expr,
-                            column_indices,
-                            Arc::new(filter_schema),
-                        ))
+                        )?)
                     }
                     _ => None,
                 };
 
-                let prefer_hash_join =
-                    session_state.config_options().optimizer.prefer_hash_join;
-
                 // TODO: Allow PWMJ to deal with residual equijoin conditions
                 let join: Arc<dyn ExecutionPlan> = if join_on.is_empty() {
                     if join_filter.is_none() && *join_type == JoinType::Inner {
@@ -1717,10 +1810,7 @@ impl DefaultPhysicalPlanner {
                             None,
                         )?)
                     }
-                } else if session_state.config().target_partitions() > 1
-                    && session_state.config().repartition_joins()
-                    && !prefer_hash_join
-                {
+                } else if use_sort_merge_join {
                     // Use SortMergeJoin if hash join is not preferred
                     let join_on_len = join_on.len();
                     Arc::new(SortMergeJoinExec::try_new(
@@ -2143,6 +2233,80 @@ fn qualify_join_schema_sides(
     join_schema.with_field_specific_qualified_schema(qualifiers)
 }
 
+/// Creates the [`join_utils::JoinFilter`] evaluating `expr`, which references
+/// columns of both join inputs, on an intermediate batch holding only the
+/// referenced columns.
+fn create_join_filter(
+    expr: &Expr,
+    left_df_schema: &DFSchema,
+    right_df_schema: &DFSchema,
+    physical_left: &Arc<dyn ExecutionPlan>,
+    physical_right: &Arc<dyn ExecutionPlan>,
+    execution_props: &ExecutionProps,
+) -> Result<join_utils::JoinFilter> {
+    // Extract columns from filter expression and saved in a HashSet
+    let cols = expr.column_refs();
+
+    // Collect left & right field indices, the field indices are sorted in ascending order
+    let left_field_indices = cols
+        .iter()
+        .filter_map(|c| left_df_schema.index_of_column(c).ok())
+        .sorted()
+        .collect::<Vec<_>>();
+    let right_field_indices = cols
+        .iter()
+        .filter_map(|c| right_df_schema.index_of_column(c).ok())
+        .sorted()
+        .collect::<Vec<_>>();
+
+    // Collect DFFields and Fields required for intermediate schemas
+    let (filter_df_fields, filter_fields): (Vec<_>, Vec<_>) = left_field_indices
+        .clone()
+        .into_iter()
+        .map(|i| {
+            (
+                left_df_schema.qualified_field(i),
+                physical_left.schema().field(i).clone(),
+            )
+        })
+        .chain(right_field_indices.clone().into_iter().map(|i| {
+            (
+                right_df_schema.qualified_field(i),
+                physical_right.schema().field(i).clone(),
+            )
+        }))
+        .unzip();
+    let filter_df_fields = filter_df_fields
+        .into_iter()
+        .map(|(qualifier, field)| (qualifier.cloned(), Arc::clone(field)))
+        .collect();
+
+    let metadata: HashMap<_, _> = left_df_schema
+        .metadata()
+        .clone()
+        .into_iter()
+        .chain(right_df_schema.metadata().clone())
+        .collect();
+
+    // Construct intermediate schemas used for filtering data and
+    // convert logical expression to physical according to filter schema
+    let filter_df_schema =
+        DFSchema::new_with_metadata(filter_df_fields, metadata.clone())?;
+    let filter_schema = Schema::new_with_metadata(filter_fields, metadata);
+
+    let filter_expr = create_physical_expr(expr, &filter_df_schema, execution_props)?;
+    let column_indices = join_utils::JoinFilter::build_column_indices(
+        left_field_indices,
+        right_field_indices,
+    );
+
+    Ok(join_utils::JoinFilter::new(
+        filter_expr,
+        column_indices,
+        Arc::new(filter_schema),
+    ))
+}
+
 fn get_physical_expr_pair(
     expr: &Expr,
     input_dfschema: &DFSchema,
@@ -2231,6 +2395,7 @@ fn extract_dml_filters(
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
             | LogicalPlan::Unnest(_)
+            | LogicalPlan::LateralTableFunction(_)
             | LogicalPlan::RecursiveQuery(_) => {
                 // No filters to extract from leaf/meta plans
             }
@@ -2447,8 +2612,16 @@ pub fn create_window_expr_with_name(
                 .map(|f| create_physical_expr(f, logical_schema, execution_props))
                 .transpose()?;
 
+            // aggregate functions may pick their accumulators based on the
+            // configuration, see `AggregateUDFImpl::with_updated_config`
+            let updated_fun = match (fun, execution_props.config_options()) {
+                (WindowFunctionDefinition::AggregateUDF(udaf), Some(config)) => udaf
+                    .with_updated_config(config)
+                    .map(|udaf| WindowFunctionDefinition::AggregateUDF(Arc::new(udaf))),
+                _ => None,
+            };
             windows::create_window_expr(
-                fun,
+                updated_fun.as_ref().unwrap_or(fun),
                 name,
                 &physical_args,
                 &partition_by,
@@ -2513,6 +2686,13 @@ pub fn create_aggregate_expr_with_name_and_maybe_filter(
                 physical_name(e)?
             };
 
+            // aggregate functions may pick their accumulators based on the
+            // configuration, see `AggregateUDFImpl::with_updated_config`
+            let func = execution_props
+                .config_options()
+                .and_then(|config| func.with_updated_config(config))
+                .map_or_else(|| Arc::clone(func), Arc::new);
+
             let physical_args =
                 create_physical_exprs(args, logical_input_schema, execution_props)?;
             let filter = match filter {
@@ -2534,16 +2714,15 @@ pub fn create_aggregate_expr_with_name_and_maybe_filter(
                     execution_props,
                 )?;
 
-                let agg_expr =
-                    AggregateExprBuilder::new(func.to_owned(), physical_args.to_vec())
-                        .order_by(order_bys.clone())
-                        .schema(Arc::new(physical_input_schema.to_owned()))
-                        .alias(name)
-                        .human_display(human_displan)
-                        .with_ignore_nulls(ignore_nulls)
-                        .with_distinct(*distinct)
-                        .build()
-                        .map(Arc::new)?;
+                let agg_expr = AggregateExprBuilder::new(func, physical_args.to_vec())
+                    .order_by(order_bys.clone())
+                    .schema(Arc::new(physical_input_schema.to_owned()))
+                    .alias(name)
+                    .human_display(human_displan)
+                    .with_ignore_nulls(ignore_nulls)
+                    .with_distinct(*distinct)
+                    .build()
+                    .map(Arc::new)?;
 
                 (agg_expr, filter, order_bys)
             };
@@ -2814,14 +2993,17 @@ impl DefaultPhysicalPlanner {
             ExplainAnalyzeCategories::All => None,
             ExplainAnalyzeCategories::Only(cats) => Some(cats),
         };
-        Ok(Arc::new(AnalyzeExec::new(
-            a.verbose,
-            show_statistics,
-            metric_types,
-            metric_categories,
-            input,
-            schema,
-        )))
+        Ok(Arc::new(
+            AnalyzeExec::new(
+                a.verbose,
+                show_statistics,
+                metric_types,
+                metric_categories,
+                input,
+                schema,
+            )
+            .with_format(a.format),
+        ))
     }
 
     /// Optimize a physical plan by applying each physical optimizer,
@@ -2850,8 +3032,24 @@ impl DefaultPhysicalPlanner {
         // to verify that the plan fulfills the base requirements.
         InvariantChecker(InvariantLevel::Always).check(&plan)?;
 
+        let collect_statistics =
+            session_state.config_options().execution.collect_statistics;
         let mut new_plan = Arc::clone(&plan);
         for optimizer in optimizers {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!(
+                "datafusion.physical_optimizer_rule",
+                rule = optimizer.name()
+            )
+            .entered();
+
+            if optimizer.requires_statistics() && !collect_statistics {
+                debug!(
+                    "Skipping physical optimizer rule {} as statistics are not collected",
+                    optimizer.name()
+                );
+                continue;
+            }
             let before_schema = new_plan.schema();
             new_plan = optimizer
                 .optimize_with_context(new_plan, session_state)
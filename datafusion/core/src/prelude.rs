@@ -37,7 +37,7 @@ pub use datafusion_expr::{
     Expr,
     expr_fn::*,
     lit, lit_timestamp_nano,
-    logical_plan::{JoinType, Partitioning},
+    logical_plan::{DedupKeep, JoinType, Partitioning},
 };
 pub use datafusion_functions::expr_fn::*;
 #[cfg(feature = "nested_expressions")]
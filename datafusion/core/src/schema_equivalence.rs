@@ -15,7 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+
 use arrow::datatypes::{DataType, Field, Fields, Schema};
+use datafusion_common::metadata_column::{
+    is_metadata_column, without_metadata_column_marker,
+};
 
 /// Verifies whether the original planned schema can be satisfied with data
 /// adhering to the candidate schema. In practice, this is equality check on the
@@ -39,10 +44,23 @@ fn fields_satisfied_by(original: &Fields, candidate: &Fields) -> bool {
 fn field_satisfied_by(original: &Field, candidate: &Field) -> bool {
     original.name() == candidate.name()
         && (original.is_nullable() || !candidate.is_nullable())
-        && original.metadata() == candidate.metadata()
+        && metadata_satisfied_by(original, candidate)
         && data_type_satisfied_by(original.data_type(), candidate.data_type())
 }
 
+/// See [`schema_satisfied_by`] for the contract.
+///
+/// The marker of a hidden metadata column only exists in logical plans, so it
+/// is ignored.
+fn metadata_satisfied_by(original: &Field, candidate: &Field) -> bool {
+    if is_metadata_column(original) {
+        without_metadata_column_marker(Arc::new(original.clone())).metadata()
+            == candidate.metadata()
+    } else {
+        original.metadata() == candidate.metadata()
+    }
+}
+
 /// See [`schema_satisfied_by`] for the contract.
 fn data_type_satisfied_by(original: &DataType, candidate: &DataType) -> bool {
     match (original, candidate) {
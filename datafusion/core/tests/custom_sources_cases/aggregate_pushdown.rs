@@ -0,0 +1,158 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tests for aggregations computed by [`TableProvider::scan_with_aggregates`]
+
+use std::sync::Arc;
+
+use arrow::array::{Int64Array, RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::util::pretty::pretty_format_batches;
+use datafusion::catalog::TableProvider;
+use datafusion::datasource::MemTable;
+use datafusion::datasource::memory::MemorySourceConfig;
+use datafusion::error::Result;
+use datafusion::logical_expr::{TableAggregate, TableType};
+use datafusion::physical_plan::{ExecutionPlan, displayable};
+use datafusion::prelude::*;
+use datafusion_catalog::Session;
+
+use async_trait::async_trait;
+
+/// A table of sales that maintains the total amount of each region
+#[derive(Debug)]
+struct SalesTable {
+    sales: MemTable,
+    totals: RecordBatch,
+}
+
+impl SalesTable {
+    fn try_new() -> Result<Self> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("region", DataType::Utf8, false),
+            Field::new("amount", DataType::Int64, false),
+        ]));
+        let sales = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(StringArray::from(vec!["east", "west", "east"])),
+                Arc::new(Int64Array::from(vec![10, 20, 30])),
+            ],
+        )?;
+        let totals = RecordBatch::try_from_iter(vec![
+            (
+                "region",
+                Arc::new(StringArray::from(vec!["east", "west"])) as _,
+            ),
+            ("total", Arc::new(Int64Array::from(vec![40, 20])) as _),
+        ])?;
+        Ok(Self {
+            sales: MemTable::try_new(schema, vec![vec![sales]])?,
+            totals,
+        })
+    }
+}
+
+#[async_trait]
+impl TableProvider for SalesTable {
+    fn schema(&self) -> SchemaRef {
+        self.sales.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.sales.scan(state, projection, filters, limit).await
+    }
+
+    fn supports_aggregate_pushdown(&self, aggregate: &TableAggregate) -> Result<bool> {
+        let group_expr = aggregate.group_expr.iter().map(|e| e.to_string());
+        let aggr_expr = aggregate.aggr_expr.iter().map(|e| e.to_string());
+        Ok(aggregate.filters.is_empty()
+            && group_expr.eq(["sales.region"])
+            && aggr_expr.eq(["sum(sales.amount)"]))
+    }
+
+    async fn scan_with_aggregates(
+        &self,
+        _state: &dyn Session,
+        aggregate: &TableAggregate,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let totals = RecordBatch::try_new(
+            Arc::clone(&aggregate.schema),
+            self.totals.columns().to_vec(),
+        )?;
+        MemorySourceConfig::try_new_exec(
+            &[vec![totals]],
+            Arc::clone(&aggregate.schema),
+            None,
+        )
+        .map(|exec| exec as _)
+    }
+}
+
+async fn run(sql: &str) -> Result<(String, String)> {
+    let ctx = SessionContext::new();
+    ctx.register_table("sales", Arc::new(SalesTable::try_new()?))?;
+    let df = ctx.sql(sql).await?;
+    let plan = df.clone().create_physical_plan().await?;
+    let plan = displayable(plan.as_ref()).indent(false).to_string();
+    let results = pretty_format_batches(&df.collect().await?)?.to_string();
+    Ok((plan, results))
+}
+
+#[tokio::test]
+async fn aggregate_computed_by_provider() -> Result<()> {
+    let (plan, results) =
+        run("SELECT region, sum(amount) FROM sales GROUP BY region ORDER BY region")
+            .await?;
+    assert!(!plan.contains("AggregateExec"), "{plan}");
+    insta::assert_snapshot!(results, @r"
+    +--------+-------------------+
+    | region | sum(sales.amount) |
+    +--------+-------------------+
+    | east   | 40                |
+    | west   | 20                |
+    +--------+-------------------+
+    ");
+    Ok(())
+}
+
+#[tokio::test]
+async fn aggregate_not_supported_by_provider() -> Result<()> {
+    let (plan, results) =
+        run("SELECT region, max(amount) FROM sales GROUP BY region ORDER BY region")
+            .await?;
+    assert!(plan.contains("AggregateExec"), "{plan}");
+    insta::assert_snapshot!(results, @r"
+    +--------+-------------------+
+    | region | max(sales.amount) |
+    +--------+-------------------+
+    | east   | 30                |
+    | west   | 20                |
+    +--------+-------------------+
+    ");
+    Ok(())
+}
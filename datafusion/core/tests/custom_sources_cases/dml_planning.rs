@@ -15,23 +15,25 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Tests for DELETE, UPDATE, and TRUNCATE planning to verify filter and assignment extraction.
+//! Tests for DELETE, UPDATE, TRUNCATE, and MERGE planning to verify filter and assignment extraction.
 
 use std::sync::{Arc, Mutex};
 
+use arrow::array::{Int32Array, RecordBatch, StringArray};
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use async_trait::async_trait;
-use datafusion::datasource::{TableProvider, TableType};
+use datafusion::datasource::{MemTable, TableProvider, TableType};
 use datafusion::error::Result;
 use datafusion::execution::context::{SessionConfig, SessionContext};
 use datafusion::logical_expr::{
     Expr, LogicalPlan, TableProviderFilterPushDown, TableScan,
 };
 use datafusion_catalog::Session;
-use datafusion_common::ScalarValue;
+use datafusion_common::stats::Precision;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
-use datafusion_physical_plan::ExecutionPlan;
+use datafusion_common::{ColumnStatistics, ScalarValue, Statistics};
 use datafusion_physical_plan::empty::EmptyExec;
+use datafusion_physical_plan::{ExecutionPlan, collect};
 
 /// A TableProvider that captures the filters passed to delete_from().
 struct CaptureDeleteProvider {
@@ -287,6 +289,122 @@ impl TableProvider for CaptureTruncateProvider {
     }
 }
 
+/// A TableProvider that scans a fixed batch and captures the change stream
+/// passed to merge_into().
+struct CaptureMergeProvider {
+    table: MemTable,
+    changes: Arc<Mutex<Option<Vec<RecordBatch>>>>,
+}
+
+impl CaptureMergeProvider {
+    fn try_new(batch: RecordBatch) -> Result<Self> {
+        Ok(Self {
+            table: MemTable::try_new(batch.schema(), vec![vec![batch]])?,
+            changes: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    fn captured_changes(&self) -> Option<Vec<RecordBatch>> {
+        self.changes.lock().unwrap().clone()
+    }
+}
+
+impl std::fmt::Debug for CaptureMergeProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CaptureMergeProvider")
+            .field("schema", &self.table.schema())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl TableProvider for CaptureMergeProvider {
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.table.scan(state, projection, filters, limit).await
+    }
+
+    async fn merge_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let changes = collect(input, state.task_ctx()).await?;
+        *self.changes.lock().unwrap() = Some(changes);
+
+        Ok(Arc::new(EmptyExec::new(Arc::new(Schema::new(vec![
+            Field::new("count", DataType::UInt64, false),
+        ])))))
+    }
+}
+
+/// A TableProvider that scans a fixed batch and keeps the statistics passed
+/// to update_statistics().
+struct CaptureStatisticsProvider {
+    table: MemTable,
+    statistics: Mutex<Option<Statistics>>,
+}
+
+impl CaptureStatisticsProvider {
+    fn try_new(batch: RecordBatch) -> Result<Self> {
+        Ok(Self {
+            table: MemTable::try_new(batch.schema(), vec![vec![batch]])?,
+            statistics: Mutex::new(None),
+        })
+    }
+}
+
+impl std::fmt::Debug for CaptureStatisticsProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CaptureStatisticsProvider")
+            .field("schema", &self.table.schema())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl TableProvider for CaptureStatisticsProvider {
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.table.scan(state, projection, filters, limit).await
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.statistics.lock().unwrap().clone()
+    }
+
+    async fn update_statistics(&self, statistics: Statistics) -> Result<()> {
+        *self.statistics.lock().unwrap() = Some(statistics);
+        Ok(())
+    }
+}
+
 fn test_schema() -> SchemaRef {
     Arc::new(Schema::new(vec![
         Field::new("id", DataType::Int32, false),
@@ -649,6 +767,122 @@ async fn test_truncate_calls_provider() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_merge_passes_changes_to_provider() -> Result<()> {
+    let ctx = SessionContext::new();
+    let target = RecordBatch::try_new(
+        test_schema(),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            Arc::new(Int32Array::from(vec![10, 20, 30])),
+        ],
+    )?;
+    let provider = Arc::new(CaptureMergeProvider::try_new(target)?);
+    ctx.register_table("t", Arc::clone(&provider) as Arc<dyn TableProvider>)?;
+
+    ctx.sql(
+        "MERGE INTO t USING (VALUES (1, 11), (2, -1), (4, 40)) AS s(id, value) \
+         ON t.id = s.id \
+         WHEN MATCHED AND s.value < 0 THEN DELETE \
+         WHEN MATCHED THEN UPDATE SET value = s.value \
+         WHEN NOT MATCHED THEN INSERT (id, value) VALUES (s.id, s.value)",
+    )
+    .await?
+    .collect()
+    .await?;
+
+    let changes = provider
+        .captured_changes()
+        .expect("merge_into() should be called on the TableProvider");
+    let sorted = ctx
+        .read_batches(changes)?
+        .sort_by(vec![datafusion::prelude::col("id")])?
+        .collect()
+        .await?;
+    insta::assert_snapshot!(arrow::util::pretty::pretty_format_batches(&sorted)?, @r"
+    +----------------+----+--------+-------+----------------+--------------------+-------------------+
+    | __merge_action | id | status | value | __merge_old_id | __merge_old_status | __merge_old_value |
+    +----------------+----+--------+-------+----------------+--------------------+-------------------+
+    | update         | 1  | a      | 11    | 1              | a                  | 10                |
+    | delete         | 2  | b      | 20    | 2              | b                  | 20                |
+    | insert         | 4  |        | 40    |                |                    |                   |
+    +----------------+----+--------+-------+----------------+--------------------+-------------------+
+    ");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_analyze_passes_statistics_to_provider() -> Result<()> {
+    let ctx = SessionContext::new();
+    let batch = RecordBatch::try_new(
+        test_schema(),
+        vec![
+            Arc::new(Int32Array::from(vec![3, 1, 2, 1])),
+            Arc::new(StringArray::from(vec![Some("a"), None, Some("b"), None])),
+            Arc::new(Int32Array::from(vec![10, 20, 30, 40])),
+        ],
+    )?;
+    let provider = Arc::new(CaptureStatisticsProvider::try_new(batch)?);
+    ctx.register_table("t", Arc::clone(&provider) as Arc<dyn TableProvider>)?;
+
+    let result = ctx
+        .sql("ANALYZE TABLE t COMPUTE STATISTICS FOR COLUMNS id, status")
+        .await?
+        .collect()
+        .await?;
+    insta::assert_snapshot!(arrow::util::pretty::pretty_format_batches(&result)?, @r"
+    +-------+
+    | count |
+    +-------+
+    | 4     |
+    +-------+
+    ");
+
+    let statistics = provider
+        .statistics()
+        .expect("update_statistics() should be called on the TableProvider");
+    assert_eq!(statistics.num_rows, Precision::Exact(4));
+    let id = &statistics.column_statistics[0];
+    assert_eq!(id.min_value, Precision::Exact(ScalarValue::Int32(Some(1))));
+    assert_eq!(id.max_value, Precision::Exact(ScalarValue::Int32(Some(3))));
+    assert_eq!(id.null_count, Precision::Exact(0));
+    assert_eq!(id.distinct_count, Precision::Inexact(3));
+    let status = &statistics.column_statistics[1];
+    assert_eq!(
+        status.min_value,
+        Precision::Exact(ScalarValue::Utf8(Some("a".to_string())))
+    );
+    assert_eq!(status.null_count, Precision::Exact(2));
+    assert_eq!(status.distinct_count, Precision::Inexact(2));
+    // `value` was not analyzed
+    assert_eq!(
+        statistics.column_statistics[2],
+        ColumnStatistics::new_unknown()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unsupported_table_analyze() -> Result<()> {
+    let ctx = SessionContext::new();
+    let empty_table = datafusion::datasource::empty::EmptyTable::new(test_schema());
+    ctx.register_table("empty_t", Arc::new(empty_table))?;
+
+    let err = ctx
+        .sql("ANALYZE TABLE empty_t")
+        .await?
+        .collect()
+        .await
+        .expect_err("ANALYZE TABLE should fail");
+    assert!(
+        err.to_string()
+            .contains("ANALYZE TABLE not supported for Base table"),
+        "{err}"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_unsupported_table_delete() -> Result<()> {
     let schema = test_schema();
@@ -804,3 +1038,22 @@ async fn test_unsupported_table_truncate() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_unsupported_table_merge() -> Result<()> {
+    let schema = test_schema();
+    let ctx = SessionContext::new();
+
+    let empty_table = datafusion::datasource::empty::EmptyTable::new(schema);
+    ctx.register_table("empty_t", Arc::new(empty_table))?;
+
+    let result = ctx
+        .sql(
+            "MERGE INTO empty_t USING (VALUES (1)) AS s(id) ON empty_t.id = s.id \
+             WHEN MATCHED THEN DELETE",
+        )
+        .await;
+
+    assert!(result.is_err() || result.unwrap().collect().await.is_err());
+    Ok(())
+}
@@ -57,7 +57,7 @@ use datafusion::error::Result;
 use datafusion::execution::context::SessionContext;
 use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::logical_expr::{ColumnarValue, Volatility};
-use datafusion::prelude::{CsvReadOptions, JoinType, ParquetReadOptions};
+use datafusion::prelude::{CsvReadOptions, DedupKeep, JoinType, ParquetReadOptions};
 use datafusion::test_util::{
     parquet_test_data, populate_csv_partitions, register_aggregate_csv, test_table,
     test_table_with_cache_factory, test_table_with_name,
@@ -1477,6 +1477,118 @@ async fn test_distinct_on_sort_by_unprojected() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_dedup() -> Result<()> {
+    let ctx = SessionContext::new();
+    let df = ctx.read_batch(record_batch!(
+        ("id", Int32, [1, 2, 1, 2, 1]),
+        ("version", Int32, [1, 1, 2, 2, 3]),
+        ("value", Utf8, ["a", "b", "c", "d", "e"])
+    )?)?;
+
+    let first = df.clone().dedup(
+        vec![col("id")],
+        vec![col("version").sort(true, false)],
+        DedupKeep::First,
+    )?;
+    assert_snapshot!(
+        batches_to_sort_string(&first.collect().await?),
+        @r"
+    +----+---------+-------+
+    | id | version | value |
+    +----+---------+-------+
+    | 1  | 1       | a     |
+    | 2  | 1       | b     |
+    +----+---------+-------+
+    "
+    );
+
+    let last = df.dedup(
+        vec![col("id")],
+        vec![col("version").sort(true, false)],
+        DedupKeep::Last,
+    )?;
+    assert_snapshot!(
+        batches_to_sort_string(&last.collect().await?),
+        @r"
+    +----+---------+-------+
+    | id | version | value |
+    +----+---------+-------+
+    | 1  | 3       | e     |
+    | 2  | 2       | d     |
+    +----+---------+-------+
+    "
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_diff() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_batch(
+        "old",
+        record_batch!(
+            ("id", Int32, [Some(1), Some(2), Some(3), None]),
+            ("a", Utf8, [Some("x"), Some("y"), None, Some("z")]),
+            ("b", Int64, [Some(10), Some(20), Some(30), Some(40)])
+        )?,
+    )?;
+    ctx.register_batch(
+        "new",
+        record_batch!(
+            (
+                "b",
+                Int64,
+                [Some(10), Some(21), Some(30), Some(40), Some(50)]
+            ),
+            (
+                "a",
+                Utf8,
+                [Some("x"), Some("y"), Some("w"), Some("z"), Some("v")]
+            ),
+            ("id", Int32, [Some(1), Some(2), Some(3), None, Some(5)])
+        )?,
+    )?;
+
+    let diff = ctx.table_diff("old", "new", &["id"]).await?;
+    assert_snapshot!(
+        batches_to_sort_string(&diff.collect().await?),
+        @r"
+    +----+---------+
+    | id | diff    |
+    +----+---------+
+    | 2  | changed |
+    | 3  | changed |
+    | 5  | added   |
+    +----+---------+
+    "
+    );
+
+    let diff = ctx.table_diff("new", "old", &["id", "a"]).await?;
+    assert_snapshot!(
+        batches_to_sort_string(&diff.collect().await?),
+        @r"
+    +----+---+---------+
+    | id | a | diff    |
+    +----+---+---------+
+    | 2  | y | changed |
+    | 3  |   | added   |
+    | 3  | w | removed |
+    | 5  | v | removed |
+    +----+---+---------+
+    "
+    );
+
+    let diff = ctx.table_diff("old", "old", &["id"]).await?;
+    assert!(diff.collect().await?.iter().all(|b| b.num_rows() == 0));
+
+    let err = ctx.table_diff("old", "new", &["c"]).await.unwrap_err();
+    assert_snapshot!(err.strip_backtrace(), @r#"Error during planning: Key columns ["c"] are not all columns of the input"#);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn join() -> Result<()> {
     let left = test_table().await?.select_columns(&["c1", "c2"])?;
@@ -6898,3 +7010,73 @@ async fn test_grouping_with_alias() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn execute_stream_spools_results() -> Result<()> {
+    let config =
+        SessionConfig::new().set_bool("datafusion.execution.spool_results", true);
+    let ctx = SessionContext::new_with_config(config);
+    register_aggregate_csv(&ctx, "aggregate_test_100").await?;
+
+    let sql = "SELECT c1, count(*) FROM aggregate_test_100 GROUP BY c1";
+    let expected = ctx.sql(sql).await?.collect().await?;
+    let stream = ctx.sql(sql).await?.execute_stream().await?;
+    let results = datafusion_physical_plan::common::collect(stream).await?;
+    assert_eq!(
+        batches_to_sort_string(&results),
+        batches_to_sort_string(&expected)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn results_exceeding_max_result_rows() -> Result<()> {
+    let ctx = SessionContext::new_with_config(
+        SessionConfig::new().set_str("datafusion.execution.max_result_rows", "10"),
+    );
+    register_aggregate_csv(&ctx, "aggregate_test_100").await?;
+
+    // results within the limit are returned
+    let results = ctx
+        .sql("SELECT c1 FROM aggregate_test_100 LIMIT 10")
+        .await?
+        .collect()
+        .await?;
+    assert_eq!(results.iter().map(|b| b.num_rows()).sum::<usize>(), 10);
+
+    let err = ctx
+        .sql("SELECT c1 FROM aggregate_test_100")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert_contains!(
+        err.strip_backtrace(),
+        "Resources exhausted: The results of the query exceed the limit of 10 rows (max_result_rows)"
+    );
+
+    // the limit applies to all the partitions together
+    ctx.sql("SET datafusion.execution.result_limit_action = 'truncate'")
+        .await?
+        .collect()
+        .await?;
+    let partitions = ctx
+        .sql("SELECT c1 FROM aggregate_test_100")
+        .await?
+        .repartition(datafusion_expr::Partitioning::RoundRobinBatch(4))?
+        .collect_partitioned()
+        .await?;
+    let num_rows = partitions
+        .iter()
+        .flatten()
+        .map(|b| b.num_rows())
+        .sum::<usize>();
+    assert_eq!(num_rows, 10);
+    assert_eq!(
+        ctx.take_warnings()[0].message,
+        "The results of the query were truncated to the limit of 10 rows (max_result_rows)"
+    );
+
+    Ok(())
+}
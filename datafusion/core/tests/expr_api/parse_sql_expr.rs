@@ -18,11 +18,14 @@
 use arrow::datatypes::{DataType, Field, Schema};
 use datafusion::prelude::{CsvReadOptions, SessionContext};
 use datafusion_common::DFSchema;
-use datafusion_common::{DFSchemaRef, Result, ToDFSchema};
+use datafusion_common::{DFSchemaRef, Result, ScalarValue, ToDFSchema};
 use datafusion_expr::Expr;
 use datafusion_expr::col;
-use datafusion_expr::lit;
-use datafusion_sql::unparser::Unparser;
+use datafusion_expr::{cast, lit};
+use datafusion_functions::math::expr_fn::abs;
+use datafusion_sql::unparser::dialect::DefaultDialect;
+use datafusion_sql::unparser::{ExprSqlExt, Unparser};
+
 /// A schema like:
 ///
 /// a: Int32 (possibly with nulls)
@@ -99,6 +102,33 @@ async fn roundtrip_qualified_schema() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn round_trip_to_sql_string() -> Result<()> {
+    let tests = vec![
+        (cast(col("a"), DataType::Int64), "CAST(a AS BIGINT)"),
+        (
+            col("a").eq(lit(ScalarValue::Int32(Some(1)))),
+            "(a = CAST(1 AS INTEGER))",
+        ),
+        (lit(ScalarValue::Int32(None)), "CAST(NULL AS INTEGER)"),
+        (lit(ScalarValue::Int8(Some(-3))), "CAST(-3 AS TINYINT)"),
+        (col("c").gt(lit(1.5_f32)), "(c > CAST(1.5 AS FLOAT))"),
+        (col("c").gt(lit(1.5_f64)), "(c > 1.5)"),
+        (abs(col("a")).lt(lit(10_i64)), "(abs(a) < 10)"),
+        (lit("x"), "'x'"),
+        (col("a").is_null().or(lit(true)), "(a IS NULL OR true)"),
+    ];
+
+    let ctx = SessionContext::new();
+    let df_schema = schema();
+    for (expr, expected) in tests {
+        let sql = expr.to_sql_string(&DefaultDialect {})?;
+        assert_eq!(sql, expected);
+        assert_eq!(ctx.parse_expr(&sql, &df_schema)?, expr, "{sql}");
+    }
+    Ok(())
+}
+
 fn unparse_sql_expr(expr: &Expr) -> Result<String> {
     let unparser = Unparser::default();
 
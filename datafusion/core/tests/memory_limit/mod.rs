@@ -579,6 +579,8 @@ async fn setup_context(
         parquet_encryption_factory_registry: runtime
             .parquet_encryption_factory_registry
             .clone(),
+        plan_span_exporter: None,
+        io_runtime: None,
     });
 
     let config = SessionConfig::new()
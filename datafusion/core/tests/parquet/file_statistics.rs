@@ -27,11 +27,12 @@ use datafusion::datasource::source::DataSourceExec;
 use datafusion::execution::context::SessionState;
 use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::prelude::SessionContext;
-use datafusion_common::DFSchema;
 use datafusion_common::stats::Precision;
-use datafusion_execution::cache::DefaultListFilesCache;
+use datafusion_common::{DFSchema, ScalarValue};
 use datafusion_execution::cache::cache_manager::CacheManagerConfig;
+use datafusion_execution::cache::cache_manager::FileStatisticsCache;
 use datafusion_execution::cache::cache_unit::DefaultFileStatisticsCache;
+use datafusion_execution::cache::{CacheAccessor, DefaultListFilesCache};
 use datafusion_execution::config::SessionConfig;
 use datafusion_execution::runtime_env::RuntimeEnvBuilder;
 use datafusion_expr::{Expr, col, lit};
@@ -234,6 +235,66 @@ async fn list_files_with_session_level_cache() {
     assert_eq!(get_list_file_cache_size(&state1), 1);
 }
 
+#[tokio::test]
+async fn insert_into_updates_statistics_cache() {
+    let temp_dir = tempdir().unwrap();
+    let table_path = temp_dir.path().to_str().unwrap();
+
+    let (cache, _, state) = get_cache_runtime_state();
+    let ctx = SessionContext::new_with_state(state);
+    ctx.sql(&format!(
+        "CREATE EXTERNAL TABLE t (a INT) STORED AS PARQUET LOCATION '{table_path}/'"
+    ))
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+
+    // The statistics of the written files are cached without scanning them
+    for values in ["(1), (5), (3)", "(10), (7)"] {
+        ctx.sql(&format!("INSERT INTO t VALUES {values}"))
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+    }
+    let mut entries = cache.list_entries().into_iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(_, entry)| entry.num_rows.get_value().copied());
+    let num_rows = entries
+        .iter()
+        .map(|(_, entry)| entry.num_rows)
+        .collect::<Vec<_>>();
+    assert_eq!(num_rows, vec![Precision::Exact(2), Precision::Exact(3)]);
+
+    let (path, _) = &entries[1];
+    let statistics = cache.get(path).unwrap().statistics;
+    let column_statistics = &statistics.column_statistics[0];
+    assert_eq!(
+        column_statistics.min_value,
+        Precision::Exact(ScalarValue::Int32(Some(1)))
+    );
+    assert_eq!(
+        column_statistics.max_value,
+        Precision::Exact(ScalarValue::Int32(Some(5)))
+    );
+
+    // Planning a scan of the table uses the cached statistics
+    let plan = ctx
+        .table("t")
+        .await
+        .unwrap()
+        .create_physical_plan()
+        .await
+        .unwrap();
+    assert_eq!(
+        plan.partition_statistics(None).unwrap().num_rows,
+        Precision::Exact(5)
+    );
+    assert_eq!(cache.len(), 2);
+}
+
 async fn get_listing_table(
     table_path: &ListingTableUrl,
     static_cache: Option<Arc<DefaultFileStatisticsCache>>,
@@ -55,6 +55,7 @@ mod filter_pushdown;
 mod ordering;
 mod page_pruning;
 mod row_group_pruning;
+mod row_group_repartition;
 mod schema;
 mod schema_coercion;
 mod utils;
@@ -0,0 +1,127 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tests for splitting a single Parquet file across partitions at row group
+//! boundaries
+
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow::array::{Int64Array, RecordBatch};
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion::datasource::physical_plan::FileScanConfig;
+use datafusion::datasource::source::DataSourceExec;
+use datafusion::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
+use datafusion_common::Result;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_physical_plan::{ExecutionPlan, collect};
+use parquet::arrow::ArrowWriter;
+use parquet::file::metadata::ParquetMetaDataReader;
+use parquet::file::properties::WriterProperties;
+use tempfile::TempDir;
+
+/// Returns the `(start, end)` byte ranges of the files in each file group of
+/// the scan in `plan`
+fn file_group_ranges(plan: &Arc<dyn ExecutionPlan>) -> Vec<Vec<(i64, i64)>> {
+    let mut ranges = vec![];
+    plan.apply(|plan| {
+        if let Some(config) = plan
+            .downcast_ref::<DataSourceExec>()
+            .and_then(|exec| exec.data_source().downcast_ref::<FileScanConfig>())
+        {
+            ranges = config
+                .file_groups
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|file| {
+                            let range = file.range.as_ref().unwrap();
+                            (range.start, range.end)
+                        })
+                        .collect()
+                })
+                .collect();
+            return Ok(TreeNodeRecursion::Stop);
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })
+    .unwrap();
+    ranges
+}
+
+#[tokio::test]
+async fn repartition_single_file_by_row_groups() -> Result<()> {
+    // A single file with 4 row groups of 1000 rows
+    let tmp_dir = TempDir::new()?;
+    let path = tmp_dir.path().join("data.parquet");
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+    let props = WriterProperties::builder()
+        .set_max_row_group_row_count(Some(1000))
+        .build();
+    let mut writer =
+        ArrowWriter::try_new(File::create(&path)?, Arc::clone(&schema), Some(props))?;
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![Arc::new(Int64Array::from_iter_values(0..4000))],
+    )?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    let file_size = std::fs::metadata(&path)?.len() as i64;
+    let metadata = ParquetMetaDataReader::new().parse_and_finish(&File::open(&path)?)?;
+    let offsets = metadata
+        .row_groups()
+        .iter()
+        .map(|row_group| {
+            let column = row_group.column(0);
+            column
+                .dictionary_page_offset()
+                .unwrap_or_else(|| column.data_page_offset())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(offsets.len(), 4);
+
+    let config = SessionConfig::new()
+        .with_target_partitions(4)
+        .with_repartition_file_min_size(1);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_parquet("t", path.to_str().unwrap(), ParquetReadOptions::default())
+        .await?;
+
+    // Each partition reads exactly one row group
+    let plan = ctx
+        .sql("SELECT a FROM t WHERE a <> 42")
+        .await?
+        .create_physical_plan()
+        .await?;
+    assert_eq!(
+        file_group_ranges(&plan),
+        vec![
+            vec![(0, offsets[1])],
+            vec![(offsets[1], offsets[2])],
+            vec![(offsets[2], offsets[3])],
+            vec![(offsets[3], file_size)],
+        ]
+    );
+
+    let batches = collect(plan, ctx.task_ctx()).await?;
+    let num_rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+    assert_eq!(num_rows, 3999);
+
+    Ok(())
+}
@@ -21,6 +21,10 @@ use std::{collections::HashMap, fs, path::Path};
 use tempfile::TempDir;
 
 use super::*;
+use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
 use datafusion_common::test_util::batches_to_sort_string;
 use insta::assert_snapshot;
 
@@ -185,6 +189,110 @@ async fn schema_merge_can_preserve_metadata() {
     assert_metadata(&actual, &expected_metadata);
 }
 
+#[tokio::test]
+async fn schema_evolution_merges_evolved_files() {
+    let tmp_dir = TempDir::new().unwrap();
+    let table_dir = tmp_dir.path().join("parquet_test");
+    fs::create_dir(&table_dir).unwrap();
+
+    // v1: `id` is an Int32 and `name` a string
+    let v1 = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "name",
+            Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    // v2: `id` is widened to Int64, `name` is renamed to `full_name` and
+    // `score` is added
+    let v2 = RecordBatch::try_from_iter(vec![
+        (
+            "id",
+            Arc::new(Int64Array::from(vec![3_000_000_000])) as ArrayRef,
+        ),
+        (
+            "full_name",
+            Arc::new(StringArray::from(vec!["c"])) as ArrayRef,
+        ),
+        ("score", Arc::new(Float64Array::from(vec![0.5])) as ArrayRef),
+    ])
+    .unwrap();
+    for (i, batch) in [v1, v2].into_iter().enumerate() {
+        let file = fs::File::create(table_dir.join(format!("part-{i}.parquet"))).unwrap();
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    let ctx = SessionContext::new();
+    let table_path = ListingTableUrl::parse(table_dir.to_str().unwrap()).unwrap();
+    let options = ListingOptions::new(Arc::new(ParquetFormat::default()));
+
+    // Without schema evolution, the types of `id` cannot be merged
+    let err = options
+        .infer_schema(&ctx.state(), &table_path)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("id"), "unexpected error: {err}");
+
+    let options = options.with_schema_evolution(true);
+    let schema = options
+        .infer_schema(&ctx.state(), &table_path)
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.as_ref(),
+        &Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("full_name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ])
+    );
+
+    let config = ListingTableConfig::new(table_path)
+        .with_listing_options(options)
+        .with_schema(schema);
+    let table = ListingTable::try_new(config).unwrap();
+    ctx.register_table("t", Arc::new(table)).unwrap();
+
+    // Each file is adapted to the merged schema when it is scanned
+    let actual = ctx
+        .sql("SELECT * FROM t ORDER BY id")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_snapshot!(batches_to_sort_string(&actual), @r"
+    +------------+------+-----------+-------+
+    | id         | name | full_name | score |
+    +------------+------+-----------+-------+
+    | 1          | a    |           |       |
+    | 2          | b    |           |       |
+    | 3000000000 |      | c         | 0.5   |
+    +------------+------+-----------+-------+
+    ");
+
+    // Predicates on widened and missing columns are adapted as well
+    let actual = ctx
+        .sql("SELECT id FROM t WHERE id > 2147483647 OR score IS NULL AND id = 2")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_snapshot!(batches_to_sort_string(&actual), @r"
+    +------------+
+    | id         |
+    +------------+
+    | 2          |
+    | 3000000000 |
+    +------------+
+    ");
+}
+
 fn make_meta(k: impl Into<String>, v: impl Into<String>) -> HashMap<String, String> {
     let mut meta = HashMap::new();
     meta.insert(k.into(), v.into());
@@ -0,0 +1,103 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tests for the FuseStarJoins physical optimizer rule.
+
+use datafusion::prelude::{SessionConfig, SessionContext};
+use datafusion_common::Result;
+use datafusion_common::test_util::batches_to_sort_string;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_physical_plan::collect;
+use datafusion_physical_plan::joins::{HashJoinExec, StarJoinExec};
+
+// with the products first, their join with the sales is estimated to be larger
+// than the stores, which keeps the sales on the probe side of both joins
+const QUERY: &str = "SELECT s.id, st.name, p.name, s.amount \
+    FROM products p JOIN (stores st JOIN sales s ON s.store = st.id) ON s.product = p.id";
+
+async fn context(star_join: bool) -> Result<SessionContext> {
+    let mut config = SessionConfig::new().with_target_partitions(4);
+    config.options_mut().optimizer.enable_star_join = star_join;
+    let ctx = SessionContext::new_with_config(config);
+    ctx.sql(
+        "CREATE TABLE stores (id INT, name VARCHAR) AS VALUES (1, 'north'), (2, 'south')",
+    )
+    .await?;
+    ctx.sql(
+        "CREATE TABLE products (id INT, name VARCHAR) AS VALUES \
+        (10, 'apple'), (20, 'pear'), (20, 'plum')",
+    )
+    .await?;
+    ctx.sql(
+        "CREATE TABLE sales (id INT, store INT, product INT, amount INT) AS VALUES \
+        (1, 1, 10, 5), (2, 2, 20, 7), (3, 1, 30, 1), (4, 3, 10, 2), \
+        (5, 2, 10, 4), (6, NULL, 20, 3)",
+    )
+    .await?;
+    Ok(ctx)
+}
+
+/// Returns the number of star joins and hash joins in the plan of `QUERY`,
+/// and the results of the query
+async fn run(star_join: bool) -> Result<(usize, usize, String)> {
+    let ctx = context(star_join).await?;
+    let plan = ctx.sql(QUERY).await?.create_physical_plan().await?;
+    let (mut star_joins, mut hash_joins) = (0, 0);
+    plan.apply(|node| {
+        if node.is::<StarJoinExec>() {
+            star_joins += 1;
+        } else if node.is::<HashJoinExec>() {
+            hash_joins += 1;
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    let batches = collect(plan, ctx.task_ctx()).await?;
+    Ok((star_joins, hash_joins, batches_to_sort_string(&batches)))
+}
+
+#[tokio::test]
+async fn hash_joins_with_dimensions_are_fused() -> Result<()> {
+    let (star_joins, hash_joins, results) = run(true).await?;
+    assert_eq!((star_joins, hash_joins), (1, 0));
+
+    let (star_joins, hash_joins, expected) = run(false).await?;
+    assert_eq!((star_joins, hash_joins), (0, 2));
+    assert_eq!(results, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn joins_on_dimension_columns_are_not_fused() -> Result<()> {
+    let ctx = context(true).await?;
+    // the second join key references the first dimension, not the fact table
+    let df = ctx
+        .sql(
+            "SELECT s.id, st.name, p.name \
+            FROM sales s JOIN stores st ON s.store = st.id JOIN products p ON st.id = p.id",
+        )
+        .await?;
+    let plan = df.create_physical_plan().await?;
+    let mut star_joins = 0;
+    plan.apply(|node| {
+        if node.is::<StarJoinExec>() {
+            star_joins += 1;
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    assert_eq!(star_joins, 0);
+    Ok(())
+}
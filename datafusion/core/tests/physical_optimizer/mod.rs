@@ -25,6 +25,7 @@ mod enforce_distribution;
 mod enforce_sorting;
 mod enforce_sorting_monotonicity;
 mod filter_pushdown;
+mod fuse_star_joins;
 mod join_selection;
 #[expect(clippy::needless_pass_by_value)]
 mod limit_pushdown;
@@ -34,6 +35,7 @@ mod projection_pushdown;
 mod pushdown_sort;
 mod replace_with_order_preserving_variants;
 mod sanity_checker;
+mod share_hash_join_build_side;
 #[expect(clippy::needless_pass_by_value)]
 mod test_utils;
 mod window_optimize;
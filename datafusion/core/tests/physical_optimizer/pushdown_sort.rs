@@ -1084,3 +1084,64 @@ fn test_sort_pushdown_exact_preserves_fetch_with_source_support() {
     "
     );
 }
+
+#[test]
+fn test_sort_pushdown_exact_merges_sorted_partitions() {
+    // When every partition of the input is sorted (Exact), a SortExec that
+    // combines them into a single partition is replaced by a merge
+    let schema = schema();
+    let a = sort_expr("a", &schema);
+    let source =
+        Arc::new(TestScan::new(schema.clone(), vec![]).with_exact_pushdown(true));
+    let repartition = repartition_exec(source);
+
+    let ordering = LexOrdering::new(vec![a]).unwrap();
+    let plan = sort_exec(ordering, repartition);
+
+    insta::assert_snapshot!(
+        OptimizationTest::new(plan, PushdownSort::new(), true),
+        @r"
+    OptimizationTest:
+      input:
+        - SortExec: expr=[a@0 ASC], preserve_partitioning=[false]
+        -   RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1
+        -     TestScan
+      output:
+        Ok:
+          - SortPreservingMergeExec: [a@0 ASC]
+          -   RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1, maintains_sort_order=true
+          -     TestScan: requested_ordering=[a@0 ASC]
+    "
+    );
+}
+
+#[test]
+fn test_sort_pushdown_exact_through_coalesce_partitions() {
+    // CoalescePartitionsExec over sorted partitions becomes a merge, which
+    // keeps the ordering, so the SortExec (and its fetch) is replaced by it
+    let schema = schema();
+    let a = sort_expr("a", &schema);
+    let source =
+        Arc::new(TestScan::new(schema.clone(), vec![]).with_exact_pushdown(true));
+    let coalesce_parts = coalesce_partitions_exec(repartition_exec(source));
+
+    let ordering = LexOrdering::new(vec![a]).unwrap();
+    let plan = sort_exec_with_fetch(ordering, Some(10), coalesce_parts);
+
+    insta::assert_snapshot!(
+        OptimizationTest::new(plan, PushdownSort::new(), true),
+        @r"
+    OptimizationTest:
+      input:
+        - SortExec: TopK(fetch=10), expr=[a@0 ASC], preserve_partitioning=[false]
+        -   CoalescePartitionsExec
+        -     RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1
+        -       TestScan
+      output:
+        Ok:
+          - SortPreservingMergeExec: [a@0 ASC], fetch=10
+          -   RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=1, maintains_sort_order=true
+          -     TestScan: requested_ordering=[a@0 ASC]
+    "
+    );
+}
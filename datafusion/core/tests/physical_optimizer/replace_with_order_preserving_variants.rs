@@ -1262,3 +1262,48 @@ fn test_plan_with_order_breaking_variants_preserves_fetch() -> Result<()> {
     assert_eq!(res.plan.fetch(), Some(10));
     Ok(())
 }
+
+#[tokio::test]
+async fn test_order_preserving_repartition_cost_model() -> Result<()> {
+    let schema = create_test_schema()?;
+    let sort_exprs: LexOrdering = [sort_expr("a", &schema)].into();
+    let source = memory_exec_sorted(&schema, sort_exprs.clone());
+    let repartition = repartition_exec_hash(repartition_exec_round_robin(source));
+    let sort = sort_exec_with_preserve_partitioning(sort_exprs.clone(), repartition);
+    let physical_plan = sort_preserving_merge_exec(sort_exprs, sort);
+
+    let optimize = |target_partitions: usize, batch_size: usize| {
+        let mut config = ConfigOptions::new();
+        config.optimizer.order_preserving_repartition_cost_model = true;
+        config.execution.target_partitions = target_partitions;
+        config.execution.batch_size = batch_size;
+        let plan = OrderPreservationContext::new_default(physical_plan.clone())
+            .transform_up(|plan| {
+                replace_with_order_preserving_variants(plan, false, false, &config)
+            })
+            .data()
+            .and_then(check_integrity)
+            .unwrap()
+            .plan;
+        displayable(plan.as_ref()).indent(true).to_string()
+    };
+
+    // The 5 input rows are fewer than the repartition buffers, sort again
+    assert_snapshot!(optimize(8, 8192), @r"
+    SortPreservingMergeExec: [a@0 ASC NULLS LAST]
+      SortExec: expr=[a@0 ASC NULLS LAST], preserve_partitioning=[true]
+        RepartitionExec: partitioning=Hash([c@1], 8), input_partitions=8
+          RepartitionExec: partitioning=RoundRobinBatch(8), input_partitions=1, maintains_sort_order=true
+            DataSourceExec: partitions=1, partition_sizes=[1], output_ordering=a@0 ASC NULLS LAST
+    ");
+
+    // The input rows exceed what the repartition buffers, preserve the ordering
+    assert_snapshot!(optimize(2, 1), @r"
+    SortPreservingMergeExec: [a@0 ASC NULLS LAST]
+      RepartitionExec: partitioning=Hash([c@1], 8), input_partitions=8, preserve_order=true, sort_exprs=a@0 ASC NULLS LAST
+        RepartitionExec: partitioning=RoundRobinBatch(8), input_partitions=1, maintains_sort_order=true
+          DataSourceExec: partitions=1, partition_sizes=[1], output_ordering=a@0 ASC NULLS LAST
+    ");
+
+    Ok(())
+}
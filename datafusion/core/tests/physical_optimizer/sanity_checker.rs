@@ -34,7 +34,9 @@ use datafusion_physical_expr::Partitioning;
 use datafusion_physical_expr::expressions::{Literal, col};
 use datafusion_physical_expr_common::sort_expr::LexOrdering;
 use datafusion_physical_optimizer::PhysicalOptimizerRule;
-use datafusion_physical_optimizer::sanity_checker::SanityCheckPlan;
+use datafusion_physical_optimizer::sanity_checker::{
+    SanityCheckPlan, validate_physical_plan,
+};
 use datafusion_physical_plan::repartition::RepartitionExec;
 use datafusion_physical_plan::{ExecutionPlan, displayable};
 
@@ -427,6 +429,7 @@ async fn test_bounded_window_agg_sort_requirement() -> Result<()> {
     "#
     );
     assert_sanity_check(&bw, true);
+    validate_physical_plan(&bw, &ConfigOptions::default())?;
     Ok(())
 }
 
@@ -455,6 +458,14 @@ async fn test_bounded_window_agg_no_sort_requirement() -> Result<()> {
     );
     // Order requirement of the `BoundedWindowAggExec` is not satisfied. We expect to receive error during sanity check.
     assert_sanity_check(&bw, false);
+
+    let err = validate_physical_plan(&bw, &ConfigOptions::default()).unwrap_err();
+    assert!(
+        err.strip_backtrace().starts_with(
+            "Invariant for ExecutionPlan node 'BoundedWindowAggExec' failed"
+        ),
+        "{err}"
+    );
     Ok(())
 }
 
@@ -0,0 +1,211 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tests for the ShareHashJoinBuildSide physical optimizer rule.
+
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion::datasource::listing::PartitionedFile;
+use datafusion::datasource::physical_plan::ParquetSource;
+use datafusion::datasource::source::DataSourceExec;
+use datafusion::prelude::{SessionConfig, SessionContext};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::test_util::batches_to_sort_string;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::{JoinType, NullEquality, Result};
+use datafusion_datasource::file_groups::FileGroup;
+use datafusion_datasource::file_scan_config::FileScanConfigBuilder;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_physical_expr::expressions::col;
+use datafusion_physical_optimizer::PhysicalOptimizerRule;
+use datafusion_physical_optimizer::share_hash_join_build_side::ShareHashJoinBuildSide;
+use datafusion_physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion_physical_plan::{ExecutionPlan, collect};
+
+// the derived table keeps the joins in the written order, with `d` on the
+// build side of both
+const QUERY: &str = "SELECT j.id, j.name, d2.name \
+    FROM d d2 JOIN (SELECT * FROM d d1 JOIN f ON f.a = d1.k) j ON j.b = d2.k";
+
+async fn context(share: bool) -> Result<SessionContext> {
+    let mut config = SessionConfig::new().with_target_partitions(4);
+    config.options_mut().optimizer.share_hash_join_build_side = share;
+    let ctx = SessionContext::new_with_config(config);
+    ctx.sql("CREATE TABLE d (k INT, name VARCHAR) AS VALUES (1, 'one'), (2, 'two')")
+        .await?;
+    ctx.sql(
+        "CREATE TABLE f (id INT, a INT, b INT) AS VALUES \
+        (1, 1, 2), (2, 2, 2), (3, 1, 3), (4, 2, 1), (5, 3, 1)",
+    )
+    .await?;
+    Ok(ctx)
+}
+
+/// Returns whether each `CollectLeft` hash join in the plan of `QUERY`
+/// shares its build side, and the results of the query
+async fn run(share: bool) -> Result<(Vec<bool>, String)> {
+    let ctx = context(share).await?;
+    let plan = ctx.sql(QUERY).await?.create_physical_plan().await?;
+    let mut shared = vec![];
+    plan.apply(|node| {
+        if let Some(join) = node.downcast_ref::<HashJoinExec>()
+            && join.mode == PartitionMode::CollectLeft
+        {
+            shared.push(join.shares_build_side());
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    let batches = collect(plan, ctx.task_ctx()).await?;
+    Ok((shared, batches_to_sort_string(&batches)))
+}
+
+#[tokio::test]
+async fn joins_over_the_same_table_share_the_build_side() -> Result<()> {
+    let (shared, results) = run(true).await?;
+    assert_eq!(shared, vec![true, true]);
+
+    let (not_shared, expected) = run(false).await?;
+    assert_eq!(not_shared, vec![false, false]);
+    assert_eq!(results, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn different_tables_do_not_share_the_build_side() -> Result<()> {
+    let ctx = context(true).await?;
+    // same display, but different data
+    ctx.sql("CREATE TABLE e (k INT, name VARCHAR) AS VALUES (1, 'uno'), (2, 'dos')")
+        .await?;
+    let df = ctx
+        .sql(
+            "SELECT f.id, d.name, e.name \
+            FROM f JOIN d ON f.a = d.k JOIN e ON f.b = e.k",
+        )
+        .await?;
+    let plan = df.create_physical_plan().await?;
+    let mut joins = 0;
+    plan.apply(|node| {
+        if let Some(join) = node.downcast_ref::<HashJoinExec>() {
+            joins += 1;
+            assert!(!join.shares_build_side());
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    assert_eq!(joins, 2);
+    Ok(())
+}
+
+/// A parquet scan of `files`, each 100 bytes long, in a single group
+fn parquet_scan(store: &str, files: Vec<PartitionedFile>) -> Arc<dyn ExecutionPlan> {
+    let schema = Arc::new(Schema::new(vec![Field::new("k", DataType::Int32, true)]));
+    let config = FileScanConfigBuilder::new(
+        ObjectStoreUrl::parse(store).unwrap(),
+        Arc::new(ParquetSource::new(schema)),
+    )
+    .with_file_group(FileGroup::new(files))
+    .build();
+    DataSourceExec::from_data_source(config)
+}
+
+fn files(names: &[&str]) -> Vec<PartitionedFile> {
+    names
+        .iter()
+        .map(|name| PartitionedFile::new(*name, 100))
+        .collect()
+}
+
+/// Returns whether the `CollectLeft` hash joins of `first` and `second`, as
+/// build sides of two joins over the same probe side, share their hash table
+fn shared_build_sides(
+    first: Arc<dyn ExecutionPlan>,
+    second: Arc<dyn ExecutionPlan>,
+) -> Result<Vec<bool>> {
+    let join = |build: Arc<dyn ExecutionPlan>,
+                probe: Arc<dyn ExecutionPlan>|
+     -> Result<Arc<dyn ExecutionPlan>> {
+        let on = vec![(col("k", &build.schema())?, col("k", &probe.schema())?)];
+        Ok(Arc::new(HashJoinExec::try_new(
+            build,
+            probe,
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::CollectLeft,
+            NullEquality::NullEqualsNothing,
+            false,
+        )?))
+    };
+    let probe = parquet_scan("test:///", files(&["probe"]));
+    let plan = join(second, join(first, probe)?)?;
+    let plan = ShareHashJoinBuildSide::new().optimize(plan, &ConfigOptions::new())?;
+    let mut shared = vec![];
+    plan.apply(|node| {
+        if let Some(join) = node.downcast_ref::<HashJoinExec>() {
+            shared.push(join.shares_build_side());
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    Ok(shared)
+}
+
+#[test]
+fn scans_of_the_same_files_share_the_build_side() -> Result<()> {
+    let names = ["1", "2", "3", "4", "5", "6", "7"];
+    let shared = shared_build_sides(
+        parquet_scan("test:///", files(&names)),
+        parquet_scan("test:///", files(&names)),
+    )?;
+    assert_eq!(shared, vec![true, true]);
+    Ok(())
+}
+
+#[test]
+fn scans_of_different_files_do_not_share_the_build_side() -> Result<()> {
+    // the display of the scans only shows the first 5 files
+    let shared = shared_build_sides(
+        parquet_scan("test:///", files(&["1", "2", "3", "4", "5", "6"])),
+        parquet_scan("test:///", files(&["1", "2", "3", "4", "5", "7"])),
+    )?;
+    assert_eq!(shared, vec![false, false]);
+
+    // the display of the scans does not show the object store
+    let shared = shared_build_sides(
+        parquet_scan("test:///", files(&["1"])),
+        parquet_scan("other:///", files(&["1"])),
+    )?;
+    assert_eq!(shared, vec![false, false]);
+
+    let shared = shared_build_sides(
+        parquet_scan(
+            "test:///",
+            vec![PartitionedFile::new_with_range("1".to_string(), 100, 0, 50)],
+        ),
+        parquet_scan(
+            "test:///",
+            vec![PartitionedFile::new_with_range(
+                "1".to_string(),
+                100,
+                50,
+                100,
+            )],
+        ),
+    )?;
+    assert_eq!(shared, vec![false, false]);
+    Ok(())
+}
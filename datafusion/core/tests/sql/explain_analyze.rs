@@ -848,6 +848,42 @@ async fn csv_explain_analyze() {
     assert_not_contains!(formatted, verbose_needle);
 }
 
+#[tokio::test]
+#[cfg_attr(tarpaulin, ignore)]
+async fn csv_explain_analyze_json() {
+    let ctx = SessionContext::new();
+    register_aggregate_csv_by_sql(&ctx).await;
+    let sql =
+        "EXPLAIN (ANALYZE, FORMAT JSON) SELECT c1 FROM aggregate_test_100 order by c1";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let plan = actual[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap()
+        .value(0);
+    let output: serde_json::Value = serde_json::from_str(plan).unwrap();
+    assert_eq!(output["output_rows"], 100);
+
+    let sort = &output["plan"];
+    assert_eq!(sort["operator"], "SortExec");
+    assert_eq!(sort["metrics"]["output_rows"], 100);
+    assert!(sort["metrics"]["elapsed_compute"].as_u64().is_some());
+    assert_eq!(sort["metrics"]["spill_count"], 0);
+    assert_eq!(sort["partitions"][0]["partition"], 0);
+    assert_eq!(sort["partitions"][0]["metrics"]["output_rows"], 100);
+
+    let scan = &sort["children"][0];
+    assert_eq!(scan["operator"], "DataSourceExec");
+    assert_eq!(scan["metrics"]["output_rows"], 100);
+
+    let err = ctx
+        .sql("EXPLAIN (ANALYZE, FORMAT TREE) SELECT 1")
+        .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "Invalid explain analyze format 'tree'");
+}
+
 #[tokio::test]
 #[cfg_attr(tarpaulin, ignore)]
 async fn csv_explain_analyze_order_by() {
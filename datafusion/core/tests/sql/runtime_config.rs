@@ -300,6 +300,42 @@ async fn test_list_files_cache_limit() {
     assert_eq!(get_limit(&ctx), 123 * 1024);
 }
 
+#[tokio::test]
+async fn test_file_range_cache_limit() {
+    let ctx = SessionContext::new();
+
+    let update_limit = async |ctx: &SessionContext, limit: &str| {
+        ctx.sql(
+            format!("SET datafusion.runtime.file_range_cache_limit = '{limit}'").as_str(),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    };
+
+    let get_limit = |ctx: &SessionContext| -> Option<usize> {
+        ctx.task_ctx()
+            .runtime_env()
+            .cache_manager
+            .get_file_range_cache()
+            .map(|cache| cache.cache_limit())
+    };
+
+    // disabled by default
+    assert_eq!(get_limit(&ctx), None);
+
+    update_limit(&ctx, "100M").await;
+    assert_eq!(get_limit(&ctx), Some(100 * 1024 * 1024));
+
+    update_limit(&ctx, "123K").await;
+    assert_eq!(get_limit(&ctx), Some(123 * 1024));
+
+    update_limit(&ctx, "0").await;
+    assert_eq!(get_limit(&ctx), None);
+}
+
 #[tokio::test]
 async fn test_list_files_cache_ttl() {
     let list_files_cache = Arc::new(DefaultListFilesCache::default());
@@ -493,3 +493,38 @@ async fn test_recursive_cte_batch_schema_stable_with_order_by_limit() -> Result<
     }
     Ok(())
 }
+
+// The node returned by the graph functions is planned as nullable, like the
+// edge destinations of the recursive term, so aggregating it in
+// `graph_shortest_paths` passes the physical schema check.
+#[tokio::test]
+async fn test_graph_shortest_paths_execute() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE edges (src INT, dst INT) AS VALUES \
+            (1, 2), (2, 3), (1, 4), (4, 1), (3, NULL)",
+    )
+    .await?
+    .collect()
+    .await?;
+
+    let df = ctx
+        .sql("SELECT * FROM graph_shortest_paths(edges, 1, 10) ORDER BY node")
+        .await?;
+    assert!(df.schema().field(0).is_nullable());
+    let plan = df.create_physical_plan().await?;
+    assert!(plan.schema().field(0).is_nullable());
+
+    let results = collect(plan, ctx.task_ctx()).await?;
+    assert_snapshot!(batches_to_sort_string(&results), @r"
+    +------+-------+
+    | node | depth |
+    +------+-------+
+    | 1    | 0     |
+    | 2    | 1     |
+    | 3    | 2     |
+    | 4    | 1     |
+    +------+-------+
+    ");
+    Ok(())
+}
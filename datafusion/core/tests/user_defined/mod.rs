@@ -30,6 +30,12 @@ mod user_defined_plan;
 /// Tests for User Defined Window Functions
 mod user_defined_window_functions;
 
+/// Tests for user defined casts
+mod user_defined_casts;
+
+/// Tests for user defined binary operators
+mod operator_overload;
+
 /// Tests for User Defined Table Functions
 mod user_defined_table_functions;
 
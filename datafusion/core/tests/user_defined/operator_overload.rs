@@ -0,0 +1,155 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BooleanArray, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::common::test_util::batches_to_string;
+use datafusion::datasource::MemTable;
+use datafusion::error::Result;
+use datafusion::prelude::{SessionContext, col};
+use datafusion_common::cast::as_string_array;
+use datafusion_common::{assert_contains, exec_datafusion_err};
+use datafusion_expr::expr_rewriter::{OperandType, OperatorOverload};
+use datafusion_expr::{ColumnarValue, Operator, Volatility, binary_expr, create_udf};
+
+/// Extension type of ranges of integers, stored as `lo..hi` strings
+const RANGE: &str = "example.range";
+
+fn parse_range(s: &str) -> Result<(i64, i64)> {
+    let err = || exec_datafusion_err!("Invalid range '{s}'");
+    let (lo, hi) = s.split_once("..").ok_or_else(err)?;
+    Ok((
+        lo.parse().map_err(|_| err())?,
+        hi.parse().map_err(|_| err())?,
+    ))
+}
+
+/// `range_contains(a, b)`: true if range `a` contains range `b`
+fn range_contains(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let arrays = ColumnarValue::values_to_arrays(args)?;
+    let (a, b) = (as_string_array(&arrays[0])?, as_string_array(&arrays[1])?);
+    let contains = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| match (a, b) {
+            (Some(a), Some(b)) => {
+                let ((a_lo, a_hi), (b_lo, b_hi)) = (parse_range(a)?, parse_range(b)?);
+                Ok(Some(a_lo <= b_lo && b_hi <= a_hi))
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<BooleanArray>>()?;
+    Ok(ColumnarValue::Array(Arc::new(contains)))
+}
+
+fn range_context() -> Result<SessionContext> {
+    let range_field = |name: &str| {
+        Field::new(name, DataType::Utf8, true).with_metadata(HashMap::from([(
+            "ARROW:extension:name".to_string(),
+            RANGE.to_string(),
+        )]))
+    };
+    let schema = Arc::new(Schema::new(vec![
+        range_field("a"),
+        range_field("b"),
+        Field::new("s", DataType::Utf8, true),
+    ]));
+    let column =
+        |values: [&str; 3]| Arc::new(StringArray::from(values.to_vec())) as ArrayRef;
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            column(["0..10", "0..10", "5..6"]),
+            column(["2..3", "5..15", "0..10"]),
+            column(["x", "y", "z"]),
+        ],
+    )?;
+
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))?;
+
+    let udf = create_udf(
+        "range_contains",
+        vec![DataType::Utf8, DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(range_contains),
+    );
+    let range = OperandType::Extension(RANGE.to_string());
+    ctx.register_operator_overload(Arc::new(OperatorOverload::new(
+        Operator::AtArrow,
+        range.clone(),
+        range,
+        Arc::new(udf),
+    )));
+    Ok(ctx)
+}
+
+#[tokio::test]
+async fn sql_operator_overload() -> Result<()> {
+    let ctx = range_context()?;
+    let batches = ctx
+        .sql("SELECT a, b, a @> b AS contains FROM t")
+        .await?
+        .collect()
+        .await?;
+
+    insta::assert_snapshot!(batches_to_string(&batches), @r"
+    +-------+-------+----------+
+    | a     | b     | contains |
+    +-------+-------+----------+
+    | 0..10 | 2..3  | true     |
+    | 0..10 | 5..15 | false    |
+    | 5..6  | 0..10 | false    |
+    +-------+-------+----------+
+    ");
+
+    // operands of other types are not overloaded
+    let err = ctx
+        .sql("SELECT s @> s FROM t")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "AtArrow' is not supported");
+    Ok(())
+}
+
+#[tokio::test]
+async fn dataframe_operator_overload() -> Result<()> {
+    let ctx = range_context()?;
+    let batches = ctx
+        .table("t")
+        .await?
+        .filter(binary_expr(col("b"), Operator::AtArrow, col("a")))?
+        .select_columns(&["s"])?
+        .collect()
+        .await?;
+
+    insta::assert_snapshot!(batches_to_string(&batches), @r"
+    +---+
+    | s |
+    +---+
+    | z |
+    +---+
+    ");
+    Ok(())
+}
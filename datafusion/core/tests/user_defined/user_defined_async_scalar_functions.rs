@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use arrow::array::{Int32Array, RecordBatch, StringArray};
 use arrow::datatypes::{DataType, Field, Schema};
@@ -113,6 +115,48 @@ async fn test_async_udf_metrics() -> Result<()> {
     Ok(())
 }
 
+// This test checks that chunks of a batch are evaluated concurrently, up to
+// `max_concurrency`, and that results are returned in input order for both
+// projections and filters
+#[tokio::test]
+async fn test_async_udf_bounded_concurrency() -> Result<()> {
+    let num_rows = 8;
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![Arc::new(Int32Array::from(
+            (0..num_rows).collect::<Vec<_>>(),
+        ))],
+    )?;
+
+    let ctx = SessionContext::new();
+    ctx.register_batch("test_table", batch)?;
+    let udf = ConcurrentAsyncUDFImpl::new(3);
+    ctx.register_udf(AsyncScalarUDF::new(Arc::new(udf.clone())).into_scalar_udf());
+
+    let result = ctx
+        .sql("SELECT id, slow_double(id) AS doubled FROM test_table WHERE slow_double(id) > 9")
+        .await?
+        .collect()
+        .await?;
+
+    assert_batches_eq!(
+        &[
+            "+----+---------+",
+            "| id | doubled |",
+            "+----+---------+",
+            "| 5  | 10      |",
+            "| 6  | 12      |",
+            "| 7  | 14      |",
+            "+----+---------+"
+        ],
+        &result
+    );
+    assert_eq!(udf.max_in_flight.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 struct TestAsyncUDFImpl {
     batch_size: usize,
@@ -165,3 +209,87 @@ impl AsyncScalarUDFImpl for TestAsyncUDFImpl {
 async fn call_external_service(arg1: ColumnarValue) -> Result<ColumnarValue> {
     Ok(arg1)
 }
+
+/// Doubles its Int32 input, one row per invocation, finishing earlier rows
+/// last and recording the maximum number of concurrent invocations
+#[derive(Debug, Clone)]
+struct ConcurrentAsyncUDFImpl {
+    max_concurrency: usize,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+    signature: Signature,
+}
+
+impl ConcurrentAsyncUDFImpl {
+    fn new(max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: Arc::new(AtomicUsize::new(0)),
+            signature: Signature::exact(vec![DataType::Int32], Volatility::Volatile),
+        }
+    }
+}
+
+impl PartialEq for ConcurrentAsyncUDFImpl {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_concurrency == other.max_concurrency
+            && Arc::ptr_eq(&self.in_flight, &other.in_flight)
+    }
+}
+
+impl Eq for ConcurrentAsyncUDFImpl {}
+
+impl Hash for ConcurrentAsyncUDFImpl {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.max_concurrency.hash(state);
+    }
+}
+
+impl ScalarUDFImpl for ConcurrentAsyncUDFImpl {
+    fn name(&self) -> &str {
+        "slow_double"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int32)
+    }
+
+    fn invoke_with_args(&self, _args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        panic!("Call invoke_async_with_args instead")
+    }
+}
+
+#[async_trait]
+impl AsyncScalarUDFImpl for ConcurrentAsyncUDFImpl {
+    fn ideal_batch_size(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    async fn invoke_async_with_args(
+        &self,
+        args: ScalarFunctionArgs,
+    ) -> Result<ColumnarValue> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+        let input = args.args[0].to_array(args.number_rows)?;
+        let input = input.as_any().downcast_ref::<Int32Array>().unwrap();
+        // lower values take longer so that invocations complete out of order
+        for _ in 0..(10 - input.value(0)) {
+            tokio::task::yield_now().await;
+        }
+        let result: Int32Array = input.iter().map(|v| v.map(|v| v * 2)).collect();
+
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    }
+}
@@ -0,0 +1,172 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, FixedSizeBinaryArray, StringArray};
+use arrow::compute::{CastOptions, cast};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::common::test_util::batches_to_string;
+use datafusion::datasource::MemTable;
+use datafusion::error::Result;
+use datafusion::prelude::SessionContext;
+use datafusion_common::cast::{as_fixed_size_binary_array, as_string_array};
+use datafusion_common::{assert_contains, exec_datafusion_err};
+use datafusion_expr::registry::UserDefinedCast;
+
+const UUID: &str = "arrow.uuid";
+
+/// Converts between `arrow.uuid` values and their string form
+#[derive(Debug)]
+struct UuidCast;
+
+fn is_uuid(field: &Field) -> bool {
+    field.extension_type_name() == Some(UUID)
+}
+
+fn is_string(field: &Field) -> bool {
+    matches!(
+        field.data_type(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    )
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn parse_uuid(s: &str) -> Result<Vec<u8>> {
+    let hex = s.replace('-', "");
+    if hex.len() != 32 {
+        return Err(exec_datafusion_err!("Invalid UUID '{s}'"));
+    }
+    (0..32)
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| exec_datafusion_err!("Invalid UUID '{s}'"))
+        })
+        .collect()
+}
+
+impl UserDefinedCast for UuidCast {
+    fn name(&self) -> &str {
+        "uuid"
+    }
+
+    fn can_cast(&self, from: &Field, to: &Field) -> bool {
+        (is_uuid(from) && is_string(to)) || (is_string(from) && is_uuid(to))
+    }
+
+    fn cast(
+        &self,
+        array: &ArrayRef,
+        from: &Field,
+        to: &Field,
+        _options: &CastOptions,
+    ) -> Result<ArrayRef> {
+        if is_uuid(from) {
+            let strings: StringArray = as_fixed_size_binary_array(array)?
+                .iter()
+                .map(|v| v.map(format_uuid))
+                .collect();
+            Ok(cast(&strings, to.data_type())?)
+        } else {
+            let strings = cast(array, &DataType::Utf8)?;
+            let uuids = as_string_array(&strings)?
+                .iter()
+                .map(|v| v.map(parse_uuid).transpose())
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    uuids.into_iter(),
+                    16,
+                )?,
+            ))
+        }
+    }
+}
+
+fn uuid_context() -> Result<SessionContext> {
+    let field = Field::new("id", DataType::FixedSizeBinary(16), true).with_metadata(
+        HashMap::from([("ARROW:extension:name".to_string(), UUID.to_string())]),
+    );
+    let schema = Arc::new(Schema::new(vec![field]));
+    let ids = FixedSizeBinaryArray::try_from_iter(
+        [(0..16).collect::<Vec<u8>>(), vec![0xff; 16]].into_iter(),
+    )?;
+    let batch = RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(ids)])?;
+
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))?;
+    ctx.register_cast(Arc::new(UuidCast))?;
+    Ok(ctx)
+}
+
+#[tokio::test]
+async fn explicit_user_defined_cast() -> Result<()> {
+    let ctx = uuid_context()?;
+    let batches = ctx
+        .sql("SELECT CAST(id AS VARCHAR) AS id FROM t")
+        .await?
+        .collect()
+        .await?;
+
+    insta::assert_snapshot!(batches_to_string(&batches), @r"
+    +--------------------------------------+
+    | id                                   |
+    +--------------------------------------+
+    | 00010203-0405-0607-0809-0a0b0c0d0e0f |
+    | ffffffff-ffff-ffff-ffff-ffffffffffff |
+    +--------------------------------------+
+    ");
+    Ok(())
+}
+
+#[tokio::test]
+async fn coercion_with_user_defined_cast() -> Result<()> {
+    let ctx = uuid_context()?;
+    let sql = "SELECT count(*) AS n FROM t \
+        WHERE id = '00010203-0405-0607-0809-0a0b0c0d0e0f'";
+    let batches = ctx.sql(sql).await?.collect().await?;
+
+    insta::assert_snapshot!(batches_to_string(&batches), @r"
+    +---+
+    | n |
+    +---+
+    | 1 |
+    +---+
+    ");
+
+    // without the cast the operands can not be compared
+    ctx.deregister_cast("uuid");
+    let err = async { ctx.sql(sql).await?.collect().await }
+        .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "Cannot infer common argument type");
+    Ok(())
+}
@@ -441,6 +441,61 @@ async fn case_sensitive_identifiers_user_defined_functions() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn namespaced_user_defined_functions() -> Result<()> {
+    let ctx = SessionContext::new();
+    let arr = Int32Array::from(vec![-5]);
+    let batch = RecordBatch::try_from_iter(vec![("a", Arc::new(arr) as _)])?;
+    ctx.register_batch("t", batch)?;
+
+    // functions of two extensions with the same name, and one with the name
+    // of a built-in function
+    for (name, value) in [
+        ("ext_a.geo.st_area", 1),
+        ("ext_b.st_area", 2),
+        ("ext_b.abs", 3),
+    ] {
+        ctx.register_udf(create_udf(
+            name,
+            vec![DataType::Int32],
+            DataType::Int32,
+            Volatility::Immutable,
+            Arc::new(move |_| Ok(ColumnarValue::Scalar(ScalarValue::Int32(Some(value))))),
+        ));
+    }
+
+    let sql = "SELECT ext_a.geo.st_area(a) AS x, EXT_B.ST_AREA(a) AS y FROM t";
+    let result = plan_and_collect(&ctx, sql).await?;
+    insta::assert_snapshot!(batches_to_string(&result), @r"
+    +---+---+
+    | x | y |
+    +---+---+
+    | 1 | 2 |
+    +---+---+
+    ");
+
+    let err = plan_and_collect(&ctx, "SELECT st_area(a) FROM t")
+        .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "Invalid function 'st_area'");
+
+    // unqualified names are resolved in the namespaces of the search path,
+    // after the functions registered under their own name
+    ctx.sql("SET datafusion.catalog.function_search_path = 'ext_b, ext_a.geo'")
+        .await?;
+    let sql = "SELECT st_area(a) AS x, abs(a) AS y, ext_a.geo.st_area(a) AS z FROM t";
+    let result = plan_and_collect(&ctx, sql).await?;
+    insta::assert_snapshot!(batches_to_string(&result), @r"
+    +---+---+---+
+    | x | y | z |
+    +---+---+---+
+    | 2 | 5 | 1 |
+    +---+---+---+
+    ");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_user_defined_functions_with_alias() -> Result<()> {
     let ctx = SessionContext::new();
@@ -2001,6 +2056,82 @@ async fn test_config_options_work_for_scalar_func() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_session_extensions_work_for_scalar_func() -> Result<()> {
+    /// Per-query context, attached to the session of the query
+    struct UserId(String);
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct CurrentUser {
+        signature: Signature,
+    }
+
+    impl ScalarUDFImpl for CurrentUser {
+        fn name(&self) -> &str {
+            "current_user_id"
+        }
+
+        fn signature(&self) -> &Signature {
+            &self.signature
+        }
+
+        fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+            Ok(DataType::Utf8)
+        }
+
+        fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+            let user = args
+                .config_options
+                .session_extensions
+                .get::<UserId>()
+                .map(|user| user.0.clone());
+            Ok(ColumnarValue::Scalar(ScalarValue::Utf8(user)))
+        }
+    }
+
+    let ctx = SessionContext::new();
+    ctx.register_udf(ScalarUDF::from(CurrentUser {
+        signature: Signature::nullary(Volatility::Volatile),
+    }));
+
+    let sql = "SELECT current_user_id() AS user_id";
+    let df = ctx.sql(sql).await?;
+    let alice = df
+        .clone()
+        .with_extension(Arc::new(UserId("alice".to_string())))
+        .collect()
+        .await?;
+    let bob = df
+        .with_extension(Arc::new(UserId("bob".to_string())))
+        .collect()
+        .await?;
+    let anonymous = ctx.sql(sql).await?.collect().await?;
+
+    insta::assert_snapshot!(batches_to_string(&alice), @r"
+    +---------+
+    | user_id |
+    +---------+
+    | alice   |
+    +---------+
+    ");
+    insta::assert_snapshot!(batches_to_string(&bob), @r"
+    +---------+
+    | user_id |
+    +---------+
+    | bob     |
+    +---------+
+    ");
+    insta::assert_snapshot!(batches_to_string(&anonymous), @r"
+    +---------+
+    | user_id |
+    +---------+
+    |         |
+    +---------+
+    ");
+
+    Ok(())
+}
+
 /// https://github.com/apache/datafusion/issues/17425
 #[tokio::test]
 async fn test_extension_metadata_preserve_in_sql_values() -> Result<()> {
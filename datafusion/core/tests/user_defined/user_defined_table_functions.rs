@@ -20,7 +20,7 @@ use std::io::Seek;
 use std::path::Path;
 use std::sync::Arc;
 
-use arrow::array::Int64Array;
+use arrow::array::{Int64Array, StringArray};
 use arrow::csv::ReaderBuilder;
 use arrow::csv::reader::Format;
 
@@ -35,7 +35,8 @@ use datafusion::physical_plan::{ExecutionPlan, collect};
 use datafusion::prelude::SessionContext;
 use datafusion_catalog::TableFunctionImpl;
 use datafusion_catalog::{Session, TableFunctionArgs};
-use datafusion_common::{DFSchema, ScalarValue};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{DFSchema, ScalarValue, assert_contains, not_impl_err, plan_err};
 use datafusion_expr::{EmptyRelation, Expr, LogicalPlan, Projection, TableType};
 
 use async_trait::async_trait;
@@ -243,6 +244,109 @@ async fn test_udtf_type_coercion() -> Result<()> {
     Ok(())
 }
 
+/// Test a udtf called laterally with arguments that reference columns
+#[tokio::test]
+async fn test_lateral_udtf() -> Result<()> {
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    /// Splits a string into one row per word
+    #[derive(Debug)]
+    struct SplitWordsFunc;
+
+    impl SplitWordsFunc {
+        fn schema() -> SchemaRef {
+            Arc::new(Schema::new(vec![Field::new("word", DataType::Utf8, false)]))
+        }
+    }
+
+    impl TableFunctionImpl for SplitWordsFunc {
+        fn call_with_args(&self, _: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
+            not_impl_err!("split_words can only be called laterally")
+        }
+
+        fn lateral_schema(&self, arg_types: &[DataType]) -> Result<Option<SchemaRef>> {
+            match arg_types {
+                [DataType::Utf8 | DataType::Utf8View | DataType::LargeUtf8] => {
+                    Ok(Some(Self::schema()))
+                }
+                _ => plan_err!("split_words expects a single string argument"),
+            }
+        }
+
+        fn call_lateral(
+            &self,
+            args: &[ScalarValue],
+            _config: &ConfigOptions,
+        ) -> Result<Vec<RecordBatch>> {
+            let Some(Some(text)) = args[0].try_as_str() else {
+                return Ok(vec![]);
+            };
+            let words = StringArray::from(text.split_whitespace().collect::<Vec<_>>());
+            Ok(vec![RecordBatch::try_new(
+                Self::schema(),
+                vec![Arc::new(words)],
+            )?])
+        }
+    }
+
+    let ctx = SessionContext::new();
+    ctx.register_udtf("split_words", Arc::new(SplitWordsFunc));
+    ctx.sql(
+        "CREATE TABLE docs(id INT, body VARCHAR) AS VALUES \
+         (1, 'hello lateral world'), (2, NULL), (3, 'bye')",
+    )
+    .await?
+    .collect()
+    .await?;
+
+    let rbs = ctx
+        .sql("SELECT id, w.word FROM docs, LATERAL split_words(body) AS w ORDER BY id, word")
+        .await?
+        .collect()
+        .await?;
+    insta::assert_snapshot!(batches_to_string(&rbs), @r"
+    +----+---------+
+    | id | word    |
+    +----+---------+
+    | 1  | hello   |
+    | 1  | lateral |
+    | 1  | world   |
+    | 3  | bye     |
+    +----+---------+
+    ");
+
+    let rbs = ctx
+        .sql(
+            "SELECT id, word FROM docs LEFT JOIN LATERAL split_words(body) ON true \
+             ORDER BY id, word",
+        )
+        .await?
+        .collect()
+        .await?;
+    insta::assert_snapshot!(batches_to_string(&rbs), @r"
+    +----+---------+
+    | id | word    |
+    +----+---------+
+    | 1  | hello   |
+    | 1  | lateral |
+    | 1  | world   |
+    | 2  |         |
+    | 3  | bye     |
+    +----+---------+
+    ");
+
+    let err = ctx
+        .sql("SELECT * FROM docs, LATERAL split_words(id)")
+        .await
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "split_words expects a single string argument"
+    );
+
+    Ok(())
+}
+
 fn read_csv_batches(csv_path: impl AsRef<Path>) -> Result<(SchemaRef, Vec<RecordBatch>)> {
     let mut file = File::open(csv_path)?;
     let (schema, _) = Format::default()
@@ -353,7 +353,10 @@ impl FileSource for ArrowSource {
                 // Use the default trait implementation logic for file format
                 use datafusion_datasource::file_groups::FileGroupPartitioner;
 
-                if config.file_compression_type.is_compressed() {
+                if config.file_compression_type.is_compressed()
+                    || config.has_deletion_vectors()
+                    || config.reads_file_row_numbers()
+                {
                     return Ok(None);
                 }
 
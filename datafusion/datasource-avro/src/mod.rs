@@ -33,6 +33,7 @@ pub mod source;
 use arrow::datatypes::{DataType, Field, Fields, Schema, UnionFields};
 pub use arrow_avro;
 use arrow_avro::reader::ReaderBuilder;
+use arrow_avro::schema::{AvroSchema, SchemaStore};
 pub use file_format::*;
 use std::io::{BufReader, Read};
 use std::sync::Arc;
@@ -49,6 +50,18 @@ pub fn read_avro_schema_from_reader<R: Read>(
     Ok(strip_metadata_from_schema(avro_reader.schema().as_ref()))
 }
 
+/// Convert an Avro schema in its JSON representation, as stored in a schema
+/// registry, to an Arrow [`Schema`]
+pub fn avro_schema_to_arrow(avro_schema: &str) -> datafusion_common::Result<Schema> {
+    let mut store = SchemaStore::new();
+    let fingerprint = store.register(AvroSchema::new(avro_schema.to_string()))?;
+    let decoder = ReaderBuilder::new()
+        .with_writer_schema_store(store)
+        .with_active_fingerprint(fingerprint)
+        .build_decoder()?;
+    Ok(strip_metadata_from_schema(decoder.schema().as_ref()))
+}
+
 fn strip_metadata_from_schema(schema: &Schema) -> Schema {
     let fields = schema
         .fields
@@ -150,4 +163,28 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_avro_schema_to_arrow() -> DFResult<()> {
+        let schema = avro_schema_to_arrow(
+            r#"{
+                "type": "record",
+                "name": "user",
+                "fields": [
+                    {"name": "id", "type": "long"},
+                    {"name": "name", "type": ["null", "string"]},
+                    {"name": "tags", "type": {"type": "array", "items": "string"}}
+                ]
+            }"#,
+        )?;
+
+        assert_eq!(schema.fields().len(), 3);
+        assert_eq!(schema.field(0), &Field::new("id", DataType::Int64, false));
+        assert_eq!(schema.field(1), &Field::new("name", DataType::Utf8, true));
+        assert_eq!(schema.field(2).name(), "tags");
+        assert!(matches!(schema.field(2).data_type(), DataType::List(_)));
+
+        assert!(avro_schema_to_arrow("not json").is_err());
+        Ok(())
+    }
 }
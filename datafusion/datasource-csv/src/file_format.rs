@@ -29,6 +29,7 @@ use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
 use arrow::error::ArrowError;
 use datafusion_common::config::{ConfigField, ConfigFileType, CsvOptions};
 use datafusion_common::file_options::csv_writer::CsvWriterOptions;
+use datafusion_common::parsers::CsvLineTerminator;
 use datafusion_common::{
     DEFAULT_CSV_EXTENSION, DataFusionError, GetExt, Result, Statistics, exec_err,
     not_impl_err,
@@ -351,6 +352,7 @@ impl Debug for CsvSerializer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("CsvSerializer")
             .field("header", &self.header)
+            .field("line_terminator", &self.line_terminator)
             .finish()
     }
 }
@@ -700,6 +702,8 @@ pub struct CsvSerializer {
     builder: WriterBuilder,
     // Flag to indicate whether there will be a header
     header: bool,
+    // Bytes that terminate each record
+    line_terminator: CsvLineTerminator,
 }
 
 impl CsvSerializer {
@@ -708,6 +712,7 @@ impl CsvSerializer {
         Self {
             builder: WriterBuilder::new(),
             header: true,
+            line_terminator: CsvLineTerminator::Lf,
         }
     }
 
@@ -722,6 +727,12 @@ impl CsvSerializer {
         self.header = header;
         self
     }
+
+    /// Method for setting the bytes that terminate each record
+    pub fn with_line_terminator(mut self, line_terminator: CsvLineTerminator) -> Self {
+        self.line_terminator = line_terminator;
+        self
+    }
 }
 
 impl BatchSerializer for CsvSerializer {
@@ -732,10 +743,48 @@ impl BatchSerializer for CsvSerializer {
         let mut writer = builder.with_header(header).build(&mut buffer);
         writer.write(&batch)?;
         drop(writer);
+        if self.line_terminator != CsvLineTerminator::Lf {
+            let escape = (!self.builder.double_quote()).then(|| self.builder.escape());
+            buffer = replace_line_terminators(
+                &buffer,
+                self.line_terminator.as_bytes(),
+                self.builder.quote(),
+                escape,
+            );
+        }
         Ok(Bytes::from(buffer))
     }
 }
 
+/// Replaces the `\n`s that terminate the records in `buffer`, which is CSV
+/// written with `quote` and `escape`, by `terminator`. Line breaks within
+/// quoted values are kept as they are.
+fn replace_line_terminators(
+    buffer: &[u8],
+    terminator: &[u8],
+    quote: u8,
+    escape: Option<u8>,
+) -> Vec<u8> {
+    let records = buffer.iter().filter(|byte| **byte == b'\n').count();
+    let mut output = Vec::with_capacity(buffer.len() + records * terminator.len());
+    let mut quoted = false;
+    let mut escaped = false;
+    for &byte in buffer {
+        if escaped {
+            escaped = false;
+        } else if quoted && Some(byte) == escape {
+            escaped = true;
+        } else if byte == quote {
+            quoted = !quoted;
+        } else if byte == b'\n' && !quoted {
+            output.extend_from_slice(terminator);
+            continue;
+        }
+        output.push(byte);
+    }
+    output
+}
+
 /// Implements [`DataSink`] for writing to a CSV file.
 pub struct CsvSink {
     /// Config options for writing data
@@ -798,7 +847,8 @@ impl FileSink for CsvSink {
         let serializer = Arc::new(
             CsvSerializer::new()
                 .with_builder(builder)
-                .with_header(header),
+                .with_header(header)
+                .with_line_terminator(self.writer_options.line_terminator),
         ) as _;
         spawn_writer_tasks_and_join(
             context,
@@ -830,10 +880,26 @@ impl DataSink for CsvSink {
 
 #[cfg(test)]
 mod tests {
-    use super::build_schema_helper;
+    use super::{build_schema_helper, replace_line_terminators};
     use arrow::datatypes::DataType;
     use std::collections::HashSet;
 
+    #[test]
+    fn test_replace_line_terminators() {
+        let csv = b"a,b\n\"x\ny\",\"\"\"\n\"\n";
+        assert_eq!(
+            replace_line_terminators(csv, b"\r\n", b'"', None),
+            b"a,b\r\n\"x\ny\",\"\"\"\n\"\r\n"
+        );
+
+        // an escaped quote does not end the quoted value
+        let csv = b"\"x\\\"\ny\",1\n";
+        assert_eq!(
+            replace_line_terminators(csv, b"\r\n", b'"', Some(b'\\')),
+            b"\"x\\\"\ny\",1\r\n"
+        );
+    }
+
     #[test]
     fn test_build_schema_helper_different_column_counts() {
         // Test the core schema building logic with different column counts
@@ -387,19 +387,33 @@ impl Default for JsonSerializer {
 }
 
 /// Define a struct for serializing Json records to a stream
-pub struct JsonSerializer {}
+pub struct JsonSerializer {
+    // Flag to indicate whether null values are written as explicit nulls
+    explicit_nulls: bool,
+}
 
 impl JsonSerializer {
     /// Constructor for the JsonSerializer object
     pub fn new() -> Self {
-        Self {}
+        Self {
+            explicit_nulls: false,
+        }
+    }
+
+    /// Method for setting whether null values are written as explicit
+    /// `null`s rather than omitted
+    pub fn with_explicit_nulls(mut self, explicit_nulls: bool) -> Self {
+        self.explicit_nulls = explicit_nulls;
+        self
     }
 }
 
 impl BatchSerializer for JsonSerializer {
     fn serialize(&self, batch: RecordBatch, _initial: bool) -> Result<Bytes> {
         let mut buffer = Vec::with_capacity(4096);
-        let mut writer = json::LineDelimitedWriter::new(&mut buffer);
+        let mut writer = json::WriterBuilder::new()
+            .with_explicit_nulls(self.explicit_nulls)
+            .build::<_, json::writer::LineDelimited>(&mut buffer);
         writer.write(&batch)?;
         Ok(Bytes::from(buffer))
     }
@@ -463,7 +477,9 @@ impl FileSink for JsonSink {
         file_stream_rx: DemuxedStreamReceiver,
         object_store: Arc<dyn ObjectStore>,
     ) -> Result<u64> {
-        let serializer = Arc::new(JsonSerializer::new()) as _;
+        let serializer = Arc::new(
+            JsonSerializer::new().with_explicit_nulls(self.writer_options.explicit_nulls),
+        ) as _;
         spawn_writer_tasks_and_join(
             context,
             serializer,
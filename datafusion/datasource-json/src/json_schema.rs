@@ -0,0 +1,226 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of [JSON Schema](https://json-schema.org/) documents to Arrow
+//! schemas, so that JSON data governed by a schema registry can be read
+//! without schema inference.
+
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use datafusion_common::{Result, not_impl_err, plan_datafusion_err, plan_err};
+use serde_json::{Map, Value};
+
+/// Parse a JSON Schema document and convert it to an Arrow [`Schema`], see
+/// [`json_schema_to_arrow`]
+pub fn parse_json_schema(json_schema: &str) -> Result<Schema> {
+    let value: Value = serde_json::from_str(json_schema)
+        .map_err(|e| plan_datafusion_err!("Invalid JSON Schema: {e}"))?;
+    json_schema_to_arrow(&value)
+}
+
+/// Convert a JSON Schema document describing an object to an Arrow
+/// [`Schema`] with one field per property.
+///
+/// Properties listed in `required` are not nullable. The types are mapped as
+/// follows:
+///
+/// | JSON Schema                           | Arrow                         |
+/// |---------------------------------------|-------------------------------|
+/// | `boolean`                             | `Boolean`                     |
+/// | `integer`                             | `Int64`                       |
+/// | `number`                              | `Float64`                     |
+/// | `string`                              | `Utf8`                        |
+/// | `string` with `"format": "date"`      | `Date32`                      |
+/// | `string` with `"format": "date-time"` | `Timestamp(Microsecond, UTC)` |
+/// | `array`                               | `List` of the `items` type    |
+/// | `object` with `properties`            | `Struct`                      |
+///
+/// A type given as an array that includes `"null"` (e.g.
+/// `["string", "null"]`) makes the field nullable. References (`$ref`) and
+/// combinators such as `anyOf` are not supported.
+pub fn json_schema_to_arrow(json_schema: &Value) -> Result<Schema> {
+    match to_data_type(json_schema)? {
+        (DataType::Struct(fields), _) => Ok(Schema::new(fields)),
+        (other, _) => {
+            plan_err!("JSON Schema must describe an object, found type {other}")
+        }
+    }
+}
+
+/// Return the Arrow type of a JSON Schema and whether it allows `null`
+fn to_data_type(json_schema: &Value) -> Result<(DataType, bool)> {
+    let Value::Object(json_schema) = json_schema else {
+        return plan_err!("Expected a JSON Schema object, found {json_schema}");
+    };
+    for keyword in ["$ref", "anyOf", "oneOf", "allOf"] {
+        if json_schema.contains_key(keyword) {
+            return not_impl_err!("JSON Schema keyword '{keyword}' is not supported");
+        }
+    }
+
+    let (type_name, nullable) = match json_schema.get("type") {
+        Some(Value::String(t)) => (t.as_str(), false),
+        Some(Value::Array(types)) => {
+            let mut nullable = false;
+            let mut non_null = vec![];
+            for t in types {
+                match t.as_str() {
+                    Some("null") => nullable = true,
+                    Some(t) => non_null.push(t),
+                    None => return plan_err!("Invalid JSON Schema type {t}"),
+                }
+            }
+            match non_null.as_slice() {
+                [] => ("null", true),
+                [t] => (*t, nullable),
+                _ => {
+                    return not_impl_err!(
+                        "JSON Schema types with multiple non-null alternatives are not supported: {}",
+                        Value::Array(types.clone())
+                    );
+                }
+            }
+        }
+        Some(other) => return plan_err!("Invalid JSON Schema type {other}"),
+        // A schema with properties but no explicit type is an object
+        None if json_schema.contains_key("properties") => ("object", false),
+        None => return plan_err!("JSON Schema is missing a 'type'"),
+    };
+
+    let data_type = match type_name {
+        "null" => DataType::Null,
+        "boolean" => DataType::Boolean,
+        "integer" => DataType::Int64,
+        "number" => DataType::Float64,
+        "string" => match json_schema.get("format").and_then(Value::as_str) {
+            Some("date") => DataType::Date32,
+            Some("date-time") => {
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+            }
+            _ => DataType::Utf8,
+        },
+        "array" => {
+            let Some(items) = json_schema.get("items") else {
+                return plan_err!("JSON Schema array is missing 'items'");
+            };
+            let (item_type, _) = to_data_type(items)?;
+            DataType::new_list(item_type, true)
+        }
+        "object" => DataType::Struct(object_fields(json_schema)?),
+        other => return plan_err!("Unknown JSON Schema type '{other}'"),
+    };
+    Ok((data_type, nullable))
+}
+
+fn object_fields(json_schema: &Map<String, Value>) -> Result<Fields> {
+    let properties = match json_schema.get("properties") {
+        Some(Value::Object(properties)) => properties,
+        Some(other) => return plan_err!("Invalid JSON Schema properties {other}"),
+        None => {
+            return not_impl_err!(
+                "JSON Schema objects without 'properties' are not supported"
+            );
+        }
+    };
+    let required = match json_schema.get("required") {
+        Some(Value::Array(required)) => required
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let (data_type, allows_null) = to_data_type(property)?;
+            let nullable = allows_null || !required.contains(&name.as_str());
+            Ok(Arc::new(Field::new(name, data_type, nullable)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_json_schema() -> Result<()> {
+        let schema = parse_json_schema(
+            r#"{
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "name": {"type": ["string", "null"]},
+                    "score": {"type": "number"},
+                    "active": {"type": "boolean"},
+                    "created": {"type": "string", "format": "date-time"},
+                    "birthday": {"type": "string", "format": "date"},
+                    "tags": {"type": "array", "items": {"type": "string"}},
+                    "address": {
+                        "type": "object",
+                        "properties": {"city": {"type": "string"}},
+                        "required": ["city"]
+                    }
+                },
+                "required": ["id", "name"]
+            }"#,
+        )?;
+
+        let expected = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+            Field::new("active", DataType::Boolean, true),
+            Field::new(
+                "created",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+            Field::new("birthday", DataType::Date32, true),
+            Field::new("tags", DataType::new_list(DataType::Utf8, true), true),
+            Field::new(
+                "address",
+                DataType::Struct(vec![Field::new("city", DataType::Utf8, false)].into()),
+                true,
+            ),
+        ]);
+        assert_eq!(schema, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn convert_json_schema_errors() {
+        let err = parse_json_schema(r#"{"type": "string"}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("JSON Schema must describe an object"),
+            "{err}"
+        );
+
+        let err = parse_json_schema(
+            r##"{"type": "object", "properties": {"a": {"$ref": "#/$defs/a"}}}"##,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("'$ref' is not supported"), "{err}");
+
+        let err = parse_json_schema("not json").unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON Schema"), "{err}");
+    }
+}
@@ -22,6 +22,7 @@
 
 pub mod boundary_stream;
 pub mod file_format;
+pub mod json_schema;
 pub mod source;
 pub mod utils;
 
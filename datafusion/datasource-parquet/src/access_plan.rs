@@ -17,6 +17,7 @@
 
 use crate::sort::reverse_row_selection;
 use datafusion_common::{Result, assert_eq_or_internal_err};
+use datafusion_datasource::deletion_vector::DeletionVector;
 use parquet::arrow::arrow_reader::{RowSelection, RowSelector};
 use parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
 
@@ -171,6 +172,49 @@ impl ParquetAccessPlan {
         }
     }
 
+    /// Skip the rows deleted by `deletion_vector`
+    ///
+    /// Row groups in which all rows are deleted are skipped entirely, and a
+    /// [`RowSelection`] excluding the deleted rows is applied to the row groups
+    /// that have some deleted rows, as described in [`Self::scan_selection`].
+    ///
+    /// The positions of the deleted rows are relative to the start of the file,
+    /// whose row groups are described by `row_group_meta_data`.
+    pub fn apply_deletion_vector(
+        &mut self,
+        deletion_vector: &DeletionVector,
+        row_group_meta_data: &[RowGroupMetaData],
+    ) {
+        let mut first_row = 0;
+        for (idx, rg_meta) in row_group_meta_data.iter().enumerate() {
+            let rows = first_row..first_row + rg_meta.num_rows() as u64;
+            first_row = rows.end;
+
+            let deleted = deletion_vector.deleted_in(rows.clone());
+            if deleted.is_empty() {
+                continue;
+            }
+            if deleted.len() as u64 == rows.end - rows.start {
+                self.skip(idx);
+                continue;
+            }
+
+            let mut selectors = Vec::with_capacity(2 * deleted.len() + 1);
+            let mut next = rows.start;
+            for &position in deleted {
+                if position > next {
+                    selectors.push(RowSelector::select((position - next) as usize));
+                }
+                selectors.push(RowSelector::skip(1));
+                next = position + 1;
+            }
+            if next < rows.end {
+                selectors.push(RowSelector::select((rows.end - next) as usize));
+            }
+            self.scan_selection(idx, selectors.into());
+        }
+    }
+
     /// Return an overall `RowSelection`, if needed
     ///
     /// This is used to compute the row selection for the parquet reader. See
@@ -577,6 +621,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_apply_deletion_vector() {
+        let mut access_plan = ParquetAccessPlan::new(vec![
+            RowGroupAccess::Scan,
+            RowGroupAccess::Scan,
+            RowGroupAccess::Scan,
+            RowGroupAccess::Selection(
+                vec![RowSelector::skip(20), RowSelector::select(20)].into(),
+            ),
+        ]);
+
+        // all rows of row group 0 (rows 0-9), rows 12 and 15 of row group 1
+        // (rows 10-29), none of row group 2 (rows 30-59) and the last row of
+        // row group 3 (rows 60-99)
+        let deletion_vector = DeletionVector::new((0..10).chain([12, 15, 99]));
+        access_plan.apply_deletion_vector(&deletion_vector, &ROW_GROUP_METADATA);
+
+        assert_eq!(
+            access_plan.inner(),
+            &[
+                RowGroupAccess::Skip,
+                RowGroupAccess::Selection(
+                    vec![
+                        RowSelector::select(2),
+                        RowSelector::skip(1),
+                        RowSelector::select(2),
+                        RowSelector::skip(1),
+                        RowSelector::select(14),
+                    ]
+                    .into()
+                ),
+                RowGroupAccess::Scan,
+                RowGroupAccess::Selection(
+                    vec![
+                        RowSelector::skip(20),
+                        RowSelector::select(19),
+                        RowSelector::skip(1),
+                    ]
+                    .into()
+                ),
+            ]
+        );
+    }
+
     /// [`RowGroupMetaData`] that returns 4 row groups with 10, 20, 30, 40 rows
     /// respectively
     static ROW_GROUP_METADATA: LazyLock<Vec<RowGroupMetaData>> = LazyLock::new(|| {
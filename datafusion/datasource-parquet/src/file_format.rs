@@ -22,6 +22,7 @@ use std::fmt::Debug;
 use std::ops::Range;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{fmt, vec};
 
 use arrow::array::RecordBatch;
@@ -67,7 +68,7 @@ use crate::source::{ParquetSource, parse_coerce_int96_string};
 use async_trait::async_trait;
 use bytes::Bytes;
 use datafusion_datasource::source::DataSourceExec;
-use datafusion_execution::cache::cache_manager::FileMetadataCache;
+use datafusion_execution::cache::cache_manager::{CachedFileMetadata, FileMetadataCache};
 use datafusion_execution::runtime_env::RuntimeEnv;
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt, TryStreamExt};
@@ -91,6 +92,7 @@ use parquet::file::properties::{
 use parquet::file::writer::SerializedFileWriter;
 use parquet::schema::types::SchemaDescriptor;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::Notify;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 
 /// Initial writing buffer size. Note this is just a size hint for efficiency. It
@@ -515,12 +517,17 @@ impl FileFormat for ParquetFormat {
 
         // Use the CachedParquetFileReaderFactory
         let metadata_cache = state.runtime_env().cache_manager.get_file_metadata_cache();
+        let file_range_cache = state.runtime_env().cache_manager.get_file_range_cache();
         let store = state
             .runtime_env()
             .object_store(conf.object_store_url.clone())?;
-        let cached_parquet_read_factory =
-            Arc::new(CachedParquetFileReaderFactory::new(store, metadata_cache));
-        source = source.with_parquet_file_reader_factory(cached_parquet_read_factory);
+        let cached_parquet_read_factory = Arc::new(
+            CachedParquetFileReaderFactory::new(store, Arc::clone(&metadata_cache))
+                .with_file_range_cache(file_range_cache),
+        );
+        source = source
+            .with_parquet_file_reader_factory(cached_parquet_read_factory)
+            .with_file_metadata_cache(metadata_cache);
 
         if let Some(metadata_size_hint) = metadata_size_hint {
             source = source.with_metadata_size_hint(metadata_size_hint)
@@ -1271,6 +1278,56 @@ impl ParquetSink {
     pub fn parquet_options(&self) -> &TableParquetOptions {
         &self.parquet_options
     }
+
+    /// Adds the statistics and ordering of the file written to `path`, taken
+    /// from its footer, to the statistics cache of the table, if any.
+    ///
+    /// This keeps the cache up to date as files are appended to the table,
+    /// without reading back the metadata of the new files.
+    async fn cache_statistics(
+        &self,
+        object_store: &Arc<dyn ObjectStore>,
+        path: &Path,
+        parquet_meta_data: &ParquetMetaData,
+    ) -> Result<()> {
+        let Some(cache) = &self.config.statistics_cache else {
+            return Ok(());
+        };
+
+        // The statistics are cached for the columns stored in the files of
+        // the table, which never include the partition columns
+        let output_schema = self.config.output_schema();
+        let file_schema: SchemaRef = Arc::new(Schema::new_with_metadata(
+            output_schema
+                .fields()
+                .iter()
+                .filter(|f| {
+                    !self
+                        .config
+                        .table_partition_cols
+                        .iter()
+                        .any(|(name, _)| name == f.name())
+                })
+                .cloned()
+                .collect::<Fields>(),
+            output_schema.metadata().clone(),
+        ));
+
+        let statistics = DFParquetMetadata::statistics_from_parquet_metadata(
+            parquet_meta_data,
+            &file_schema,
+        )?;
+        let ordering = crate::metadata::ordering_from_parquet_metadata(
+            parquet_meta_data,
+            &file_schema,
+        )?;
+        let meta = object_store.head(path).await?;
+        cache.put(
+            path,
+            CachedFileMetadata::new(meta, Arc::new(statistics), ordering),
+        );
+        Ok(())
+    }
 }
 
 #[cfg(feature = "parquet_encryption")]
@@ -1404,12 +1461,18 @@ impl FileSink for ParquetSink {
                         .objectstore_writer_buffer_size,
                 ))
                 .build()?;
+                let memory_limit = context
+                    .session_config()
+                    .options()
+                    .execution
+                    .parquet_writer_memory_limit;
                 let ctx = ParquetFileWriteContext {
                     schema: get_writer_schema(&self.config),
                     props: Arc::new(parquet_props),
                     skip_arrow_metadata: self.parquet_options.global.skip_arrow_metadata,
                     parallel_options: Arc::new(parallel_options.clone()),
                     pool: Arc::clone(context.memory_pool()),
+                    budget: Arc::new(EncoderMemoryBudget::new(memory_limit)),
                 };
                 let encoding_time = elapsed_compute.clone();
                 file_write_tasks.spawn(async move {
@@ -1437,6 +1500,8 @@ impl FileSink for ParquetSink {
                         .sum();
                     rows_written_counter.add(file_rows);
                     bytes_written_counter.add(file_bytes);
+                    self.cache_statistics(&object_store, &path, &parquet_meta_data)
+                        .await?;
                     let mut written_files = self.written.lock();
                     written_files
                         .try_insert(path.clone(), parquet_meta_data)
@@ -1482,22 +1547,36 @@ impl DataSink for ParquetSink {
 }
 
 /// Consumes a stream of [ArrowLeafColumn] via a channel and serializes them using an [ArrowColumnWriter]
-/// Once the channel is exhausted, returns the ArrowColumnWriter.
+/// Once the channel is exhausted, closes the ArrowColumnWriter and returns the encoded
+/// column chunk, so that the last pages of the columns of a row group are also
+/// encoded and compressed in parallel.
 async fn column_serializer_task(
     mut rx: Receiver<ArrowLeafColumn>,
     mut writer: ArrowColumnWriter,
     reservation: MemoryReservation,
+    budget: Arc<EncoderMemoryBudget>,
     encoding_time: Time,
-) -> Result<(ArrowColumnWriter, MemoryReservation)> {
+) -> Result<(ArrowColumnChunk, MemoryReservation)> {
     while let Some(col) = rx.recv().await {
         let _timer = encoding_time.timer();
         writer.write(&col)?;
+        let previous_size = reservation.size();
         reservation.try_resize(writer.memory_size())?;
+        budget.resize(previous_size, reservation.size());
     }
-    Ok((writer, reservation))
+
+    let encoded_size = writer.get_estimated_total_bytes();
+    let chunk = {
+        let _timer = encoding_time.timer();
+        writer.close()?
+    };
+    let previous_size = reservation.size();
+    reservation.try_resize(encoded_size)?;
+    budget.resize(previous_size, reservation.size());
+    Ok((chunk, reservation))
 }
 
-type ColumnWriterTask = SpawnedTask<Result<(ArrowColumnWriter, MemoryReservation)>>;
+type ColumnWriterTask = SpawnedTask<Result<(ArrowColumnChunk, MemoryReservation)>>;
 type ColSender = Sender<ArrowLeafColumn>;
 
 /// Spawns a parallel serialization task for each column
@@ -1507,6 +1586,7 @@ fn spawn_column_parallel_row_group_writer(
     col_writers: Vec<ArrowColumnWriter>,
     max_buffer_size: usize,
     pool: &Arc<dyn MemoryPool>,
+    budget: &Arc<EncoderMemoryBudget>,
     encoding_time: &Time,
 ) -> Result<(Vec<ColumnWriterTask>, Vec<ColSender>)> {
     let num_columns = col_writers.len();
@@ -1525,6 +1605,7 @@ fn spawn_column_parallel_row_group_writer(
             receive_array,
             writer,
             reservation,
+            Arc::clone(budget),
             encoding_time.clone(),
         ));
         col_writer_tasks.push(task);
@@ -1540,6 +1621,63 @@ struct ParallelParquetWriterOptions {
     max_buffered_record_batches_per_stream: usize,
 }
 
+/// Tracks the memory buffered by the parallel encoders of a single Parquet
+/// file, from the column writers until the encoded row groups are flushed, to
+/// apply back-pressure once it exceeds
+/// `datafusion.execution.parquet_writer_memory_limit`.
+#[derive(Debug)]
+struct EncoderMemoryBudget {
+    limit: Option<usize>,
+    used: AtomicUsize,
+    released: Notify,
+}
+
+impl EncoderMemoryBudget {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            used: AtomicUsize::new(0),
+            released: Notify::new(),
+        }
+    }
+
+    fn grow(&self, bytes: usize) {
+        self.used.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::Relaxed);
+        self.released.notify_waiters();
+    }
+
+    fn resize(&self, previous_size: usize, new_size: usize) {
+        if new_size >= previous_size {
+            self.grow(new_size - previous_size);
+        } else {
+            self.shrink(previous_size - new_size);
+        }
+    }
+
+    /// Returns true if the buffered memory exceeds the limit
+    fn is_exceeded(&self) -> bool {
+        self.limit
+            .is_some_and(|limit| self.used.load(Ordering::Relaxed) > limit)
+    }
+
+    /// Waits until the buffered memory no longer exceeds the limit
+    async fn wait_for_capacity(&self) {
+        loop {
+            // register for notifications before checking, so that a release
+            // between the check and the wait is not missed
+            let released = self.released.notified();
+            if !self.is_exceeded() {
+                return;
+            }
+            released.await;
+        }
+    }
+}
+
 /// Write configuration inputs shared across all parallel tasks that encode a
 /// single Parquet file. These values are invariant for the duration of one file
 /// write and do not change per row-group or per column.
@@ -1554,6 +1692,7 @@ struct ParquetFileWriteContext {
     skip_arrow_metadata: bool,
     parallel_options: Arc<ParallelParquetWriterOptions>,
     pool: Arc<dyn MemoryPool>,
+    budget: Arc<EncoderMemoryBudget>,
 }
 
 /// This is the return type of calling [ArrowColumnWriter].close() on each column
@@ -1590,7 +1729,6 @@ fn spawn_rg_join_and_finalize_task(
     column_writer_tasks: Vec<ColumnWriterTask>,
     rg_rows: usize,
     pool: &Arc<dyn MemoryPool>,
-    encoding_time: Time,
 ) -> SpawnedTask<RBStreamSerializeResult> {
     let rg_reservation =
         MemoryConsumer::new("ParquetSink(SerializedRowGroupWriter)").register(pool);
@@ -1599,14 +1737,14 @@ fn spawn_rg_join_and_finalize_task(
         let num_cols = column_writer_tasks.len();
         let mut finalized_rg = Vec::with_capacity(num_cols);
         for task in column_writer_tasks.into_iter() {
-            let (writer, _col_reservation) = task
+            let (chunk, col_reservation) = task
                 .join_unwind()
                 .await
                 .map_err(|e| DataFusionError::ExecutionJoin(Box::new(e)))??;
-            let encoded_size = writer.get_estimated_total_bytes();
-            rg_reservation.grow(encoded_size);
-            let _timer = encoding_time.timer();
-            finalized_rg.push(writer.close()?);
+            // the encoded chunk stays buffered, and counted in the budget,
+            // until the row group is flushed
+            rg_reservation.grow(col_reservation.free());
+            finalized_rg.push(chunk);
         }
 
         Ok((finalized_rg, rg_reservation, rg_rows))
@@ -1642,11 +1780,43 @@ fn spawn_parquet_parallel_serialization_task(
                 col_writers,
                 max_buffer_rb,
                 &ctx.pool,
+                &ctx.budget,
                 &encoding_time,
             )?;
         let mut current_rg_rows = 0;
 
         while let Some(mut rb) = data.recv().await {
+            if ctx.budget.is_exceeded() {
+                // The encoders buffer too much memory: close the current row
+                // group early, and wait for the buffered row groups to be
+                // flushed before encoding more data.
+                if current_rg_rows > 0 {
+                    drop(col_array_channels);
+                    let finalize_rg_task = spawn_rg_join_and_finalize_task(
+                        column_writer_handles,
+                        current_rg_rows,
+                        &ctx.pool,
+                    );
+                    if serialize_tx.send(finalize_rg_task).await.is_err() {
+                        return Ok(());
+                    }
+
+                    current_rg_rows = 0;
+                    row_group_index += 1;
+                    let col_writers = row_group_writer_factory
+                        .create_column_writers(row_group_index)?;
+                    (column_writer_handles, col_array_channels) =
+                        spawn_column_parallel_row_group_writer(
+                            col_writers,
+                            max_buffer_rb,
+                            &ctx.pool,
+                            &ctx.budget,
+                            &encoding_time,
+                        )?;
+                }
+                ctx.budget.wait_for_capacity().await;
+            }
+
             // This loop allows the "else" block to repeatedly split the RecordBatch to handle the case
             // when max_row_group_rows < execution.batch_size as an alternative to a recursive async
             // function.
@@ -1678,7 +1848,6 @@ fn spawn_parquet_parallel_serialization_task(
                         column_writer_handles,
                         max_row_group_rows,
                         &ctx.pool,
-                        encoding_time.clone(),
                     );
 
                     // Do not surface error from closed channel (means something
@@ -1698,6 +1867,7 @@ fn spawn_parquet_parallel_serialization_task(
                             col_writers,
                             max_buffer_rb,
                             &ctx.pool,
+                            &ctx.budget,
                             &encoding_time,
                         )?;
                 }
@@ -1711,7 +1881,6 @@ fn spawn_parquet_parallel_serialization_task(
                 column_writer_handles,
                 current_rg_rows,
                 &ctx.pool,
-                encoding_time.clone(),
             );
 
             // Do not surface error from closed channel (means something
@@ -1733,6 +1902,7 @@ async fn concatenate_parallel_row_groups(
     mut serialize_rx: Receiver<SpawnedTask<RBStreamSerializeResult>>,
     mut object_store_writer: Box<dyn AsyncWrite + Send + Unpin>,
     pool: Arc<dyn MemoryPool>,
+    budget: Arc<EncoderMemoryBudget>,
 ) -> Result<ParquetMetaData> {
     let file_reservation =
         MemoryConsumer::new("ParquetSink(SerializedFileWriter)").register(&pool);
@@ -1745,7 +1915,7 @@ async fn concatenate_parallel_row_groups(
         let mut rg_out = parquet_writer.next_row_group()?;
         for chunk in serialized_columns {
             chunk.append_to_row_group(&mut rg_out)?;
-            rg_reservation.free();
+            budget.shrink(rg_reservation.free());
 
             let mut buff_to_flush = merged_buff.buffer.try_lock().unwrap();
             file_reservation.try_resize(buff_to_flush.len())?;
@@ -1798,6 +1968,7 @@ async fn output_single_parquet_file_parallelized(
     let (writer, row_group_writer_factory) = writer.into_serialized_writer()?;
 
     let pool = Arc::clone(&ctx.pool);
+    let budget = Arc::clone(&ctx.budget);
     let launch_serialization_task = spawn_parquet_parallel_serialization_task(
         row_group_writer_factory,
         data,
@@ -1811,6 +1982,7 @@ async fn output_single_parquet_file_parallelized(
         serialize_rx,
         object_store_writer,
         pool,
+        budget,
     )
     .await?;
 
@@ -15,10 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::sync::Arc;
+
+use datafusion_common::human_readable_size;
 use datafusion_physical_plan::metrics::{
-    Count, ExecutionPlanMetricsSet, Gauge, MetricBuilder, MetricCategory, MetricType,
-    PruningMetrics, RatioMergeStrategy, RatioMetrics, Time,
+    Count, CustomMetricValue, ExecutionPlanMetricsSet, Gauge, MetricBuilder,
+    MetricCategory, MetricType, MetricValue, PruningMetrics, RatioMergeStrategy,
+    RatioMetrics, Time,
 };
+use parking_lot::Mutex;
+use parquet::file::metadata::ParquetMetaData;
 
 /// Stores metrics about the parquet execution for a particular parquet file.
 ///
@@ -53,6 +63,12 @@ pub struct ParquetFileMetrics {
     pub row_groups_pruned_statistics: PruningMetrics,
     /// Total number of bytes scanned
     pub bytes_scanned: Count,
+    /// Number of bytes and pages scanned from each column, see
+    /// [`ColumnScanMetrics`]
+    pub column_scan_stats: Arc<ColumnScanMetrics>,
+    /// Total time spent decoding the scanned pages into arrow arrays,
+    /// including the time to evaluate row-level pushdown filters
+    pub decode_time: Time,
     /// Total rows filtered out by predicates pushed into parquet scan
     pub pushdown_rows_pruned: Count,
     /// Total rows passed predicates pushed into parquet scan
@@ -174,6 +190,20 @@ impl ParquetFileMetrics {
             .with_new_label("filename", filename.to_string())
             .subset_time("bloom_filter_eval_time", partition);
 
+        let column_scan_stats = Arc::new(ColumnScanMetrics::default());
+        MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .with_category(MetricCategory::Bytes)
+            .with_partition(partition)
+            .build(MetricValue::Custom {
+                name: "column_scan_stats".into(),
+                value: Arc::clone(&column_scan_stats) as _,
+            });
+
+        let decode_time = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .subset_time("decode_time", partition);
+
         let page_index_eval_time = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
             .subset_time("page_index_eval_time", partition);
@@ -199,6 +229,8 @@ impl ParquetFileMetrics {
             row_groups_pruned_statistics,
             limit_pruned_row_groups,
             bytes_scanned,
+            column_scan_stats,
+            decode_time,
             pushdown_rows_pruned,
             pushdown_rows_matched,
             row_pushdown_eval_time,
@@ -214,3 +246,141 @@ impl ParquetFileMetrics {
         }
     }
 }
+
+/// Number of bytes and pages scanned from each column of Parquet files.
+///
+/// Each byte range fetched from a file is attributed to the column chunks it
+/// overlaps, so that `EXPLAIN ANALYZE` shows which columns are responsible
+/// for the cost of a scan, for example:
+///
+/// ```text
+/// column_scan_stats=[l_comment: 2.1 MB/32 pages, l_orderkey: 512.0 KB/16 pages]
+/// ```
+///
+/// Columns are listed by decreasing number of bytes scanned. Nested columns
+/// are listed by the dotted path of their leaves. Pages are only counted for
+/// files with a page index, which records where each page starts.
+#[derive(Debug, Default)]
+pub struct ColumnScanMetrics {
+    columns: Mutex<HashMap<String, ColumnScanStats>>,
+}
+
+/// Bytes and pages scanned from a single column, see [`ColumnScanMetrics`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnScanStats {
+    /// Number of bytes scanned
+    pub bytes_scanned: usize,
+    /// Number of pages that start in the scanned bytes
+    pub pages_scanned: usize,
+}
+
+impl ColumnScanMetrics {
+    /// Attributes the bytes of `ranges`, which were read from the file
+    /// described by `metadata`, to the columns whose chunks they overlap
+    pub fn record_ranges(&self, metadata: &ParquetMetaData, ranges: &[Range<u64>]) {
+        let offset_index = metadata.offset_index();
+        let mut columns = self.columns.lock();
+        for (row_group_idx, row_group) in metadata.row_groups().iter().enumerate() {
+            for (column_idx, column) in row_group.columns().iter().enumerate() {
+                let (start, length) = column.byte_range();
+                let chunk = start..start + length;
+                let bytes_scanned: u64 = ranges
+                    .iter()
+                    .map(|range| {
+                        range
+                            .end
+                            .min(chunk.end)
+                            .saturating_sub(range.start.max(chunk.start))
+                    })
+                    .sum();
+                if bytes_scanned == 0 {
+                    continue;
+                }
+                let pages_scanned = offset_index
+                    .and_then(|index| index.get(row_group_idx)?.get(column_idx))
+                    .map_or(0, |index| {
+                        index
+                            .page_locations()
+                            .iter()
+                            .filter(|page| {
+                                let offset = page.offset as u64;
+                                ranges.iter().any(|range| range.contains(&offset))
+                            })
+                            .count()
+                    });
+                let stats = columns
+                    .entry(column.column_descr().path().string())
+                    .or_default();
+                stats.bytes_scanned += bytes_scanned as usize;
+                stats.pages_scanned += pages_scanned;
+            }
+        }
+    }
+
+    /// Returns the bytes and pages scanned from each column
+    pub fn columns(&self) -> HashMap<String, ColumnScanStats> {
+        self.columns.lock().clone()
+    }
+}
+
+impl Display for ColumnScanMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut columns = self.columns().into_iter().collect::<Vec<_>>();
+        columns.sort_unstable_by(|(a_name, a), (b_name, b)| {
+            b.bytes_scanned
+                .cmp(&a.bytes_scanned)
+                .then_with(|| a_name.cmp(b_name))
+        });
+        write!(f, "[")?;
+        for (i, (name, stats)) in columns.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{name}: {}/{} pages",
+                human_readable_size(stats.bytes_scanned),
+                stats.pages_scanned
+            )?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl CustomMetricValue for ColumnScanMetrics {
+    fn new_empty(&self) -> Arc<dyn CustomMetricValue> {
+        Arc::new(Self::default())
+    }
+
+    fn aggregate(&self, other: Arc<dyn CustomMetricValue + 'static>) {
+        let Some(other) = other.as_any().downcast_ref::<Self>() else {
+            return;
+        };
+        let other = other.columns();
+        let mut columns = self.columns.lock();
+        for (name, stats) in other {
+            let total = columns.entry(name).or_default();
+            total.bytes_scanned += stats.bytes_scanned;
+            total.pages_scanned += stats.pages_scanned;
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_usize(&self) -> usize {
+        self.columns
+            .lock()
+            .values()
+            .map(|stats| stats.bytes_scanned)
+            .sum()
+    }
+
+    fn is_eq(&self, other: &Arc<dyn CustomMetricValue>) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self.columns() == other.columns())
+    }
+}
@@ -21,14 +21,16 @@ use crate::page_filter::PagePruningAccessPlanFilter;
 use crate::row_filter::build_projection_read_plan;
 use crate::row_group_filter::{BloomFilterStatistics, RowGroupAccessPlanFilter};
 use crate::{
-    ParquetAccessPlan, ParquetFileMetrics, ParquetFileReaderFactory,
+    ColumnScanMetrics, ParquetAccessPlan, ParquetFileMetrics, ParquetFileReaderFactory,
     apply_file_schema_type_coercions, coerce_int96_to_resolution, row_filter,
 };
 use arrow::array::{RecordBatch, RecordBatchOptions};
 use arrow::datatypes::DataType;
+use datafusion_datasource::deletion_vector::DeletionVector;
+use datafusion_datasource::metadata_column::FileRowNumberColumn;
 use datafusion_datasource::morsel::{Morsel, MorselPlan, MorselPlanner, Morselizer};
 use datafusion_physical_expr::projection::{ProjectionExprs, Projector};
-use datafusion_physical_expr::utils::reassign_expr_columns;
+use datafusion_physical_expr::utils::{collect_columns, reassign_expr_columns};
 use datafusion_physical_expr_adapter::replace_columns_with_literals;
 use std::collections::HashMap;
 use std::fmt;
@@ -43,6 +45,7 @@ use datafusion_common::encryption::FileDecryptionProperties;
 use datafusion_common::stats::Precision;
 use datafusion_common::{
     ColumnStatistics, DataFusionError, Result, ScalarValue, Statistics, exec_err,
+    not_impl_err,
 };
 use datafusion_datasource::{PartitionedFile, TableSchema};
 use datafusion_physical_expr::simplifier::PhysicalExprSimplifier;
@@ -52,9 +55,11 @@ use datafusion_physical_expr_common::physical_expr::{
 };
 use datafusion_physical_plan::metrics::{
     BaselineMetrics, Count, ExecutionPlanMetricsSet, Gauge, MetricBuilder,
-    MetricCategory, PruningMetrics,
+    MetricCategory, PruningMetrics, Time,
+};
+use datafusion_pruning::{
+    FilePruner, PruningPredicate, PruningPredicateCache, build_pruning_predicate,
 };
-use datafusion_pruning::{FilePruner, PruningPredicate, build_pruning_predicate};
 
 #[cfg(feature = "parquet_encryption")]
 use datafusion_common::config::EncryptionFactoryOptions;
@@ -75,7 +80,7 @@ use parquet::arrow::parquet_column;
 use parquet::arrow::push_decoder::{ParquetPushDecoder, ParquetPushDecoderBuilder};
 use parquet::basic::Type;
 use parquet::bloom_filter::Sbbf;
-use parquet::file::metadata::{PageIndexPolicy, ParquetMetaDataReader};
+use parquet::file::metadata::{PageIndexPolicy, ParquetMetaData, ParquetMetaDataReader};
 
 /// Stateless Parquet morselizer implementation.
 ///
@@ -136,6 +141,8 @@ pub(super) struct ParquetMorselizer {
     pub max_predicate_cache_size: Option<usize>,
     /// Whether to read row groups in reverse order
     pub reverse_row_groups: bool,
+    /// Pruning predicates shared by the files of the scan
+    pub(crate) pruning_predicate_cache: Arc<PruningPredicateCache>,
 }
 
 impl fmt::Debug for ParquetMorselizer {
@@ -287,6 +294,7 @@ struct PreparedParquetOpen {
     max_predicate_cache_size: Option<usize>,
     reverse_row_groups: bool,
     preserve_order: bool,
+    pruning_predicate_cache: Arc<PruningPredicateCache>,
     #[cfg(feature = "parquet_encryption")]
     file_decryption_properties: Option<Arc<FileDecryptionProperties>>,
 }
@@ -554,6 +562,23 @@ impl ParquetMorselizer {
                 &self.metrics,
             )?;
 
+        // Parquet skips rows while decoding, so the positions of the rows read
+        // are not known
+        if let Some(row_numbers) = partitioned_file.extension::<FileRowNumberColumn>() {
+            let index = self.table_schema.file_schema().fields().len()
+                + row_numbers.partition_value_index;
+            let reads_row_numbers = self
+                .projection
+                .expr_iter()
+                .chain(self.predicate.clone())
+                .any(|expr| collect_columns(&expr).iter().any(|c| c.index() == index));
+            if reads_row_numbers {
+                return not_impl_err!(
+                    "Reading the row numbers of Parquet files is not supported: {file_name}"
+                );
+            }
+        }
+
         // Calculate the output schema from the original projection (before literal replacement)
         // so we get correct field names from column references
         let logical_file_schema = Arc::clone(self.table_schema.file_schema());
@@ -656,6 +681,7 @@ impl ParquetMorselizer {
             max_predicate_cache_size: self.max_predicate_cache_size,
             reverse_row_groups: self.reverse_row_groups,
             preserve_order: self.preserve_order,
+            pruning_predicate_cache: Arc::clone(&self.pruning_predicate_cache),
             #[cfg(feature = "parquet_encryption")]
             file_decryption_properties: None,
         })
@@ -822,12 +848,15 @@ impl MetadataLoadedParquetOpen {
         }
         prepared.physical_file_schema = Arc::clone(&physical_file_schema);
 
-        // Build predicates for this specific file
-        let pruning_predicate = build_pruning_predicates(
-            prepared.predicate.as_ref(),
-            &physical_file_schema,
-            &prepared.predicate_creation_errors,
-        );
+        // Build predicates for this specific file, reusing the pruning
+        // predicate of any previous file with the same predicate and schema
+        let pruning_predicate = prepared.predicate.as_ref().and_then(|predicate| {
+            prepared.pruning_predicate_cache.get_or_build(
+                Arc::clone(predicate),
+                &physical_file_schema,
+                &prepared.predicate_creation_errors,
+            )
+        });
 
         // Only build page pruning predicate if page index is enabled
         let page_pruning_predicate = if prepared.enable_page_index {
@@ -1101,15 +1130,25 @@ impl RowGroupsPrunedParquetOpen {
             None
         };
 
-        // Prune by limit if limit is set and limit order is not sensitive
-        if let (Some(limit), false) = (prepared.limit, prepared.preserve_order) {
+        // Prune by limit if limit is set and limit order is not sensitive. The
+        // row counts of the row groups do not account for deleted rows, so
+        // row groups can not be pruned by limit when rows are deleted.
+        let deletion_vector = prepared.extensions.get::<DeletionVector>();
+        if let (Some(limit), false, None) =
+            (prepared.limit, prepared.preserve_order, deletion_vector)
+        {
             row_groups.prune_by_limit(limit, rg_metadata, &prepared.file_metrics);
         }
 
+        // Skip the deleted rows while decoding
+        let mut access_plan = row_groups.build();
+        if let Some(deletion_vector) = deletion_vector {
+            access_plan.apply_deletion_vector(deletion_vector, rg_metadata);
+        }
+
         // Page index pruning: if all data on individual pages can
         // be ruled using page metadata, rows from other columns
         // with that range can be skipped as well.
-        let mut access_plan = row_groups.build();
         if prepared.enable_page_index
             && !access_plan.is_empty()
             && let Some(page_pruning_predicate) = page_pruning_predicate
@@ -1191,6 +1230,9 @@ impl RowGroupsPrunedParquetOpen {
             PushDecoderStreamState {
                 decoder,
                 reader: prepared.async_file_reader,
+                file_metadata,
+                column_scan_stats: Arc::clone(&prepared.file_metrics.column_scan_stats),
+                decode_time: prepared.file_metrics.decode_time.clone(),
                 projector,
                 output_schema,
                 replace_schema,
@@ -1227,6 +1269,9 @@ impl RowGroupsPrunedParquetOpen {
 struct PushDecoderStreamState {
     decoder: ParquetPushDecoder,
     reader: Box<dyn AsyncFileReader>,
+    file_metadata: Arc<ParquetMetaData>,
+    column_scan_stats: Arc<ColumnScanMetrics>,
+    decode_time: Time,
     projector: Projector,
     output_schema: Arc<Schema>,
     replace_schema: bool,
@@ -1252,8 +1297,14 @@ impl PushDecoderStreamState {
     /// with `unfold`'s ownership across yield points.
     async fn transition(mut self) -> Option<(Result<RecordBatch>, Self)> {
         loop {
-            match self.decoder.try_decode() {
+            let decoded = {
+                let _timer = self.decode_time.timer();
+                self.decoder.try_decode()
+            };
+            match decoded {
                 Ok(DecodeResult::NeedsData(ranges)) => {
+                    self.column_scan_stats
+                        .record_ranges(&self.file_metadata, &ranges);
                     let data = self
                         .reader
                         .get_byte_ranges(ranges.clone())
@@ -1759,6 +1810,12 @@ mod test {
             self
         }
 
+        /// Set the limit.
+        fn with_limit(mut self, limit: Option<usize>) -> Self {
+            self.limit = limit;
+            self
+        }
+
         /// Build the ParquetMorselizer instance.
         ///
         /// # Panics
@@ -1810,6 +1867,7 @@ mod test {
                 encryption_factory: None,
                 max_predicate_cache_size: self.max_predicate_cache_size,
                 reverse_row_groups: self.reverse_row_groups,
+                pruning_predicate_cache: Default::default(),
             }
         }
     }
@@ -2645,6 +2703,64 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_deletion_vector() {
+        use datafusion_datasource::deletion_vector::DeletionVector;
+        use parquet::file::properties::WriterProperties;
+
+        let store = Arc::new(InMemory::new()) as Arc<dyn ObjectStore>;
+
+        // Create 3 row groups with 4 rows each
+        let batch0 =
+            record_batch!(("a", Int32, vec![Some(1), Some(2), Some(3), Some(4)]))
+                .unwrap();
+        let batch1 =
+            record_batch!(("a", Int32, vec![Some(5), Some(6), Some(7), Some(8)]))
+                .unwrap();
+        let batch2 =
+            record_batch!(("a", Int32, vec![Some(9), Some(10), Some(11), Some(12)]))
+                .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(4))
+            .build();
+
+        let data_len = write_parquet_batches(
+            Arc::clone(&store),
+            "test.parquet",
+            vec![batch0.clone(), batch1, batch2],
+            Some(props),
+        )
+        .await;
+
+        let schema = batch0.schema();
+
+        // Delete all rows of row group 0, and some rows of row groups 1 and 2
+        let file = PartitionedFile::new(
+            "test.parquet".to_string(),
+            u64::try_from(data_len).unwrap(),
+        )
+        .with_deletion_vector(Arc::new(DeletionVector::new([0, 1, 2, 3, 5, 11])));
+
+        let make_opener = |limit: Option<usize>| {
+            ParquetMorselizerBuilder::new()
+                .with_store(Arc::clone(&store))
+                .with_schema(Arc::clone(&schema))
+                .with_projection_indices(&[0])
+                .with_limit(limit)
+                .build()
+        };
+
+        let opener = make_opener(None);
+        let stream = open_file(&opener, file.clone()).await.unwrap();
+        assert_eq!(collect_int32_values(stream).await, vec![5, 7, 8, 9, 10, 11]);
+
+        // The limit applies to the rows remaining after the deletions
+        let opener = make_opener(Some(4));
+        let stream = open_file(&opener, file).await.unwrap();
+        assert_eq!(collect_int32_values(stream).await, vec![5, 7, 8, 9]);
+    }
+
     /// Test that page pruning predicates are only built and applied when `enable_page_index` is true.
     ///
     /// The file has a single row group with 10 pages (10 rows each, values 1..100).
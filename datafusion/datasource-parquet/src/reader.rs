@@ -24,6 +24,9 @@ use bytes::Bytes;
 use datafusion_datasource::PartitionedFile;
 use datafusion_execution::cache::cache_manager::FileMetadata;
 use datafusion_execution::cache::cache_manager::FileMetadataCache;
+use datafusion_execution::cache::cache_manager::{
+    CachedFileRange, FileRangeCache, FileRangeKey,
+};
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use futures::FutureExt;
 use futures::future::BoxFuture;
@@ -179,10 +182,15 @@ impl ParquetFileReaderFactory for DefaultParquetFileReaderFactory {
 /// This reader always loads the entire metadata (including page index, unless the file is
 /// encrypted), even if not required by the current query, to ensure it is always available for
 /// those that need it.
+///
+/// If a [`FileRangeCache`] is set with [`Self::with_file_range_cache`], the byte ranges read
+/// from the files (i.e., the column chunks and pages) are also cached, so that repeated scans of
+/// the same files do not fetch the same data from the object store again.
 #[derive(Debug)]
 pub struct CachedParquetFileReaderFactory {
     store: Arc<dyn ObjectStore>,
     metadata_cache: Arc<dyn FileMetadataCache>,
+    file_range_cache: Option<Arc<dyn FileRangeCache>>,
 }
 
 impl CachedParquetFileReaderFactory {
@@ -193,8 +201,18 @@ impl CachedParquetFileReaderFactory {
         Self {
             store,
             metadata_cache,
+            file_range_cache: None,
         }
     }
+
+    /// Sets the cache used for the byte ranges read from the files.
+    pub fn with_file_range_cache(
+        mut self,
+        file_range_cache: Option<Arc<dyn FileRangeCache>>,
+    ) -> Self {
+        self.file_range_cache = file_range_cache;
+        self
+    }
 }
 
 impl ParquetFileReaderFactory for CachedParquetFileReaderFactory {
@@ -222,20 +240,27 @@ impl ParquetFileReaderFactory for CachedParquetFileReaderFactory {
             inner = inner.with_footer_size_hint(hint)
         };
 
-        Ok(Box::new(CachedParquetFileReader::new(
-            file_metrics,
-            Arc::clone(&self.store),
-            inner,
-            partitioned_file,
-            Arc::clone(&self.metadata_cache),
-            metadata_size_hint,
-        )))
+        Ok(Box::new(
+            CachedParquetFileReader::new(
+                file_metrics,
+                Arc::clone(&self.store),
+                inner,
+                partitioned_file,
+                Arc::clone(&self.metadata_cache),
+                metadata_size_hint,
+            )
+            .with_file_range_cache(self.file_range_cache.clone()),
+        ))
     }
 }
 
 /// Implements [`AsyncFileReader`] for a Parquet file in object storage. Reads the file metadata
 /// from the [`FileMetadataCache`], if available, otherwise reads it directly from the file and then
 /// updates the cache.
+///
+/// If a [`FileRangeCache`] is set, the requested byte ranges are also served from the cache when
+/// they were read before from the same version of the file. Only the bytes fetched from the
+/// object store are counted as scanned.
 pub struct CachedParquetFileReader {
     pub file_metrics: ParquetFileMetrics,
     store: Arc<dyn ObjectStore>,
@@ -243,6 +268,7 @@ pub struct CachedParquetFileReader {
     partitioned_file: PartitionedFile,
     metadata_cache: Arc<dyn FileMetadataCache>,
     metadata_size_hint: Option<usize>,
+    file_range_cache: Option<Arc<dyn FileRangeCache>>,
 }
 
 impl CachedParquetFileReader {
@@ -261,8 +287,44 @@ impl CachedParquetFileReader {
             partitioned_file,
             metadata_cache,
             metadata_size_hint,
+            file_range_cache: None,
         }
     }
+
+    /// Sets the cache used for the byte ranges read from the file.
+    pub fn with_file_range_cache(
+        mut self,
+        file_range_cache: Option<Arc<dyn FileRangeCache>>,
+    ) -> Self {
+        self.file_range_cache = file_range_cache;
+        self
+    }
+
+    /// Returns the cached bytes of `range`, if cached for the current version of the file.
+    fn get_cached_range(
+        &self,
+        cache: &dyn FileRangeCache,
+        range: &Range<u64>,
+    ) -> Option<Bytes> {
+        let object_meta = &self.partitioned_file.object_meta;
+        let key = FileRangeKey::new(object_meta.location.clone(), range.clone());
+        cache
+            .get(&key)
+            .filter(|cached| cached.is_valid_for(object_meta))
+            .map(|cached| cached.bytes)
+    }
+
+    /// Adds the bytes of `range`, read from the current version of the file, to the cache.
+    fn put_cached_range(
+        &self,
+        cache: &dyn FileRangeCache,
+        range: Range<u64>,
+        bytes: Bytes,
+    ) {
+        let object_meta = &self.partitioned_file.object_meta;
+        let key = FileRangeKey::new(object_meta.location.clone(), range);
+        cache.put(&key, CachedFileRange::new(object_meta.clone(), bytes));
+    }
 }
 
 impl AsyncFileReader for CachedParquetFileReader {
@@ -270,9 +332,24 @@ impl AsyncFileReader for CachedParquetFileReader {
         &mut self,
         range: Range<u64>,
     ) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        let Some(cache) = self.file_range_cache.clone() else {
+            let bytes_scanned = range.end - range.start;
+            self.file_metrics.bytes_scanned.add(bytes_scanned as usize);
+            return self.inner.get_bytes(range);
+        };
+
+        if let Some(bytes) = self.get_cached_range(cache.as_ref(), &range) {
+            return futures::future::ready(Ok(bytes)).boxed();
+        }
+
         let bytes_scanned = range.end - range.start;
         self.file_metrics.bytes_scanned.add(bytes_scanned as usize);
-        self.inner.get_bytes(range)
+        async move {
+            let bytes = self.inner.get_bytes(range.clone()).await?;
+            self.put_cached_range(cache.as_ref(), range, bytes.clone());
+            Ok(bytes)
+        }
+        .boxed()
     }
 
     fn get_byte_ranges(
@@ -282,9 +359,40 @@ impl AsyncFileReader for CachedParquetFileReader {
     where
         Self: Send,
     {
-        let total: u64 = ranges.iter().map(|r| r.end - r.start).sum();
+        let Some(cache) = self.file_range_cache.clone() else {
+            let total: u64 = ranges.iter().map(|r| r.end - r.start).sum();
+            self.file_metrics.bytes_scanned.add(total as usize);
+            return self.inner.get_byte_ranges(ranges);
+        };
+
+        let mut results = Vec::with_capacity(ranges.len());
+        let mut missing = vec![];
+        for (idx, range) in ranges.iter().enumerate() {
+            let cached = self.get_cached_range(cache.as_ref(), range);
+            if cached.is_none() {
+                missing.push(idx);
+            }
+            results.push(cached);
+        }
+
+        if missing.is_empty() {
+            let results = results.into_iter().flatten().collect();
+            return futures::future::ready(Ok(results)).boxed();
+        }
+
+        let missing_ranges: Vec<_> =
+            missing.iter().map(|&idx| ranges[idx].clone()).collect();
+        let total: u64 = missing_ranges.iter().map(|r| r.end - r.start).sum();
         self.file_metrics.bytes_scanned.add(total as usize);
-        self.inner.get_byte_ranges(ranges)
+        async move {
+            let fetched = self.inner.get_byte_ranges(missing_ranges).await?;
+            for (idx, bytes) in missing.into_iter().zip(fetched) {
+                self.put_cached_range(cache.as_ref(), ranges[idx].clone(), bytes.clone());
+                results[idx] = Some(bytes);
+            }
+            Ok(results.into_iter().flatten().collect())
+        }
+        .boxed()
     }
 
     fn get_metadata<'a>(
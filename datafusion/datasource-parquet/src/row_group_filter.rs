@@ -31,7 +31,7 @@ use datafusion_physical_expr::{PhysicalExpr, PhysicalExprSimplifier};
 use datafusion_pruning::PruningPredicate;
 use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
 use parquet::basic::Type;
-use parquet::data_type::Decimal;
+use parquet::data_type::{AsBytes, Decimal};
 use parquet::schema::types::SchemaDescriptor;
 use parquet::{bloom_filter::Sbbf, file::metadata::RowGroupMetaData};
 
@@ -410,6 +410,10 @@ impl RowGroupAccessPlanFilter {
     /// `row_group_bloom_filters[idx]` contains the bloom filters for the
     /// parquet row group at index `idx`.
     ///
+    /// The predicate is evaluated for all remaining row groups at once, so
+    /// the literals of large `IN` lists are converted to bloom filter keys
+    /// only once per file rather than once per row group.
+    ///
     /// # Panics
     /// if `row_group_bloom_filters` does not have the same number of row groups as this set
     pub(crate) fn prune_by_bloom_filters(
@@ -422,37 +426,41 @@ impl RowGroupAccessPlanFilter {
         let _timer_guard = metrics.bloom_filter_eval_time.timer();
 
         assert_eq!(row_group_bloom_filters.len(), self.access_plan.len());
-        for (idx, stats) in row_group_bloom_filters.iter().enumerate() {
-            if !self.access_plan.should_scan(idx) {
-                continue;
-            }
+        let row_group_indexes = self.row_group_indexes().collect::<Vec<_>>();
+        if row_group_indexes.is_empty() {
+            return;
+        }
+        let statistics = RowGroupBloomFilters {
+            row_groups: row_group_indexes
+                .iter()
+                .map(|idx| &row_group_bloom_filters[*idx])
+                .collect(),
+        };
 
-            // Can this group be pruned?
-            let prune_group = match predicate.prune(stats) {
-                Ok(values) => !values[0],
-                Err(e) => {
-                    log::debug!(
-                        "Error evaluating row group predicate on bloom filter: {e}"
-                    );
-                    metrics.predicate_evaluation_errors.add(1);
-                    false
-                }
-            };
+        // Can these groups be pruned?
+        let keep_groups = match predicate.prune(&statistics) {
+            Ok(values) => values,
+            Err(e) => {
+                log::debug!("Error evaluating row group predicate on bloom filter: {e}");
+                metrics.predicate_evaluation_errors.add(1);
+                vec![true; row_group_indexes.len()]
+            }
+        };
 
-            if prune_group {
+        for (idx, keep_group) in row_group_indexes.into_iter().zip(keep_groups) {
+            if keep_group {
+                metrics.row_groups_pruned_bloom_filter.add_matched(1);
+            } else {
                 metrics.row_groups_pruned_bloom_filter.add_pruned(1);
                 self.access_plan.skip(idx)
-            } else {
-                metrics.row_groups_pruned_bloom_filter.add_matched(1);
             }
         }
     }
 }
 
-/// In memory Parquet Split Block Bloom Filters (SBBF).
+/// In memory Parquet Split Block Bloom Filters (SBBF) of a row group.
 ///
-/// This structure implements [`PruningStatistics`] and is used to prune
-/// Parquet row groups and data pages based on the query predicate.
+/// See [`RowGroupBloomFilters`] for pruning row groups with them.
 #[derive(Debug, Clone, Default)]
 pub(crate) struct BloomFilterStatistics {
     /// Per-column Bloom filters
@@ -481,26 +489,27 @@ impl BloomFilterStatistics {
         self.column_sbbf.insert(column.into(), (sbbf, ty));
     }
 
-    /// Helper function for checking if [`Sbbf`] filter contains [`ScalarValue`].
+    /// Return the bytes [`Sbbf`] hashes for [`ScalarValue`], as
+    /// [`AsBytes::as_bytes`] of the corresponding Parquet value.
     ///
-    /// In case the type of scalar is not supported, returns `true`, assuming that the
-    /// value may be present.
-    fn check_scalar(sbbf: &Sbbf, value: &ScalarValue, parquet_type: &Type) -> bool {
-        match value {
+    /// In case the type of scalar is not supported, returns `None`, and the
+    /// value must be assumed to be present.
+    fn bloom_filter_key(value: &ScalarValue, parquet_type: &Type) -> Option<Vec<u8>> {
+        let key = match value {
             ScalarValue::Utf8(Some(v))
             | ScalarValue::Utf8View(Some(v))
-            | ScalarValue::LargeUtf8(Some(v)) => sbbf.check(&v.as_str()),
+            | ScalarValue::LargeUtf8(Some(v)) => v.as_bytes().to_vec(),
             ScalarValue::Binary(Some(v))
             | ScalarValue::BinaryView(Some(v))
-            | ScalarValue::LargeBinary(Some(v)) => sbbf.check(v),
-            ScalarValue::FixedSizeBinary(_size, Some(v)) => sbbf.check(v),
-            ScalarValue::Boolean(Some(v)) => sbbf.check(v),
-            ScalarValue::Float64(Some(v)) => sbbf.check(v),
-            ScalarValue::Float32(Some(v)) => sbbf.check(v),
-            ScalarValue::Int64(Some(v)) => sbbf.check(v),
-            ScalarValue::Int32(Some(v)) => sbbf.check(v),
-            ScalarValue::UInt64(Some(v)) => sbbf.check(v),
-            ScalarValue::UInt32(Some(v)) => sbbf.check(v),
+            | ScalarValue::LargeBinary(Some(v)) => v.clone(),
+            ScalarValue::FixedSizeBinary(_size, Some(v)) => v.clone(),
+            ScalarValue::Boolean(Some(v)) => v.as_bytes().to_vec(),
+            ScalarValue::Float64(Some(v)) => v.as_bytes().to_vec(),
+            ScalarValue::Float32(Some(v)) => v.as_bytes().to_vec(),
+            ScalarValue::Int64(Some(v)) => v.as_bytes().to_vec(),
+            ScalarValue::Int32(Some(v)) => v.as_bytes().to_vec(),
+            ScalarValue::UInt64(Some(v)) => v.as_bytes().to_vec(),
+            ScalarValue::UInt32(Some(v)) => v.as_bytes().to_vec(),
             ScalarValue::Decimal128(Some(v), p, s) => match parquet_type {
                 Type::INT32 => {
                     //https://github.com/apache/parquet-format/blob/eb4b31c1d64a01088d02a2f9aefc6c17c54cc6fc/Encodings.md?plain=1#L35-L42
@@ -510,7 +519,7 @@ impl BloomFilterStatistics {
                         //
                         // int32: for 1 <= precision <= 9
                         // int64: for 1 <= precision <= 18
-                        return true;
+                        return None;
                     }
                     let b = (*v as i32).to_le_bytes();
                     // Use Decimal constructor after https://github.com/apache/arrow-rs/issues/5325
@@ -519,11 +528,11 @@ impl BloomFilterStatistics {
                         precision: *p as i32,
                         scale: *s as i32,
                     };
-                    sbbf.check(&decimal)
+                    decimal.as_bytes().to_vec()
                 }
                 Type::INT64 => {
                     if *p > 18 {
-                        return true;
+                        return None;
                     }
                     let b = (*v as i64).to_le_bytes();
                     let decimal = Decimal::Int64 {
@@ -531,7 +540,7 @@ impl BloomFilterStatistics {
                         precision: *p as i32,
                         scale: *s as i32,
                     };
-                    sbbf.check(&decimal)
+                    decimal.as_bytes().to_vec()
                 }
                 Type::FIXED_LEN_BYTE_ARRAY => {
                     // keep with from_bytes_to_i128
@@ -542,19 +551,28 @@ impl BloomFilterStatistics {
                         precision: *p as i32,
                         scale: *s as i32,
                     };
-                    sbbf.check(&decimal)
+                    decimal.as_bytes().to_vec()
                 }
-                _ => true,
+                _ => return None,
             },
             ScalarValue::Dictionary(_, inner) => {
-                BloomFilterStatistics::check_scalar(sbbf, inner, parquet_type)
+                return BloomFilterStatistics::bloom_filter_key(inner, parquet_type);
             }
-            _ => true,
-        }
+            _ => return None,
+        };
+        Some(key)
     }
 }
 
-impl PruningStatistics for BloomFilterStatistics {
+/// The bloom filters of a set of row groups, implementing
+/// [`PruningStatistics`] with one container per row group.
+///
+/// This is used to prune Parquet row groups based on the query predicate.
+pub(crate) struct RowGroupBloomFilters<'a> {
+    row_groups: Vec<&'a BloomFilterStatistics>,
+}
+
+impl PruningStatistics for RowGroupBloomFilters<'_> {
     fn min_values(&self, _column: &Column) -> Option<ArrayRef> {
         None
     }
@@ -564,7 +582,7 @@ impl PruningStatistics for BloomFilterStatistics {
     }
 
     fn num_containers(&self) -> usize {
-        1
+        self.row_groups.len()
     }
 
     fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
@@ -585,30 +603,42 @@ impl PruningStatistics for BloomFilterStatistics {
         column: &Column,
         values: &HashSet<ScalarValue>,
     ) -> Option<BooleanArray> {
-        let (sbbf, parquet_type) = self.column_sbbf.get(column.name.as_str())?;
-
-        // Bloom filters are probabilistic data structures that can return false
-        // positives (i.e. it might return true even if the value is not
-        // present) however, the bloom filter will return `false` if the value is
-        // definitely not present.
-
-        let known_not_present = values
+        // The physical type of a column is the same in all row groups, so
+        // the keys are computed once for all of them
+        let parquet_type = self
+            .row_groups
             .iter()
-            .map(|value| BloomFilterStatistics::check_scalar(sbbf, value, parquet_type))
-            // The row group doesn't contain any of the values if
-            // all the checks are false
-            .all(|v| !v);
-
-        let contains = if known_not_present {
-            Some(false)
-        } else {
-            // Given the bloom filter is probabilistic, we can't be sure that
-            // the row group actually contains the values. Return `None` to
-            // indicate this uncertainty
-            None
-        };
+            .find_map(|filters| filters.column_sbbf.get(column.name.as_str()))
+            .map(|(_, parquet_type)| parquet_type)?;
+        let keys = values
+            .iter()
+            .map(|value| BloomFilterStatistics::bloom_filter_key(value, parquet_type))
+            .collect::<Option<Vec<_>>>();
 
-        Some(BooleanArray::from(vec![contains]))
+        let contains = self
+            .row_groups
+            .iter()
+            .map(|filters| {
+                let keys = keys.as_ref()?;
+                let (sbbf, _) = filters.column_sbbf.get(column.name.as_str())?;
+
+                // Bloom filters are probabilistic data structures that can
+                // return false positives (i.e. it might return true even if
+                // the value is not present) however, the bloom filter will
+                // return `false` if the value is definitely not present.
+                //
+                // The row group doesn't contain any of the values if all the
+                // checks are false, stop at the first value that may be present
+                let known_not_present =
+                    keys.iter().all(|key| !sbbf.check(key.as_slice()));
+
+                // Given the bloom filter is probabilistic, we can't be sure that
+                // the row group actually contains the values. Return `None` to
+                // indicate this uncertainty
+                if known_not_present { Some(false) } else { None }
+            })
+            .collect::<BooleanArray>();
+        Some(contains)
     }
 }
 
@@ -1612,6 +1642,51 @@ mod tests {
         assert!(pruned_row_groups.access_plan.row_group_indexes().is_empty());
     }
 
+    #[test]
+    fn test_row_group_bloom_filter_pruning_large_in_list() {
+        // row group `i` contains the ids `i * 1000 .. i * 1000 + 100`
+        let row_group_bloom_filters = (0..4i64)
+            .map(|i| {
+                // low false positive rate, as every id in the list is probed
+                let mut sbbf = Sbbf::new_with_ndv_fpp(100, 0.00001).unwrap();
+                for id in i * 1000..i * 1000 + 100 {
+                    sbbf.insert(&id);
+                }
+                let mut filters = BloomFilterStatistics::new();
+                filters.insert("id", sbbf, Type::INT64);
+                filters
+            })
+            .chain(std::iter::once(BloomFilterStatistics::new()))
+            .collect::<Vec<_>>();
+
+        // `id IN (...)` with hundreds of ids, only present in row group 2
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let expr = col("id").in_list(
+            (0..500i64)
+                .map(|i| lit(if i == 250 { 2050 } else { 100_000 + i }))
+                .collect(),
+            false,
+        );
+        let expr = logical2physical(&expr, &schema);
+        let pruning_predicate = PruningPredicate::try_new(expr, schema).unwrap();
+        assert_eq!(pruning_predicate.literal_columns(), vec!["id"]);
+
+        let metrics = parquet_file_metrics();
+        let mut row_groups = RowGroupAccessPlanFilter::new(ParquetAccessPlan::new_all(5));
+        // row group 0 was already pruned and is not evaluated
+        row_groups.access_plan.skip(0);
+        row_groups.prune_by_bloom_filters(
+            &pruning_predicate,
+            &metrics,
+            &row_group_bloom_filters,
+        );
+        // row group 4 has no bloom filter, so it can not be pruned
+        assert_eq!(row_groups.access_plan.row_group_indexes(), vec![2, 4]);
+        assert_eq!(metrics.row_groups_pruned_bloom_filter.pruned(), 2);
+        assert_eq!(metrics.row_groups_pruned_bloom_filter.matched(), 2);
+    }
+
     #[tokio::test]
     async fn test_row_group_bloom_filter_pruning_predicate_with_exists_value() {
         BloomFilterTest::new_data_index_bloom_encoding_stats()
@@ -18,10 +18,12 @@
 //! ParquetSource implementation for reading parquet files
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::DefaultParquetFileReaderFactory;
 use crate::ParquetFileReaderFactory;
+use crate::metadata::CachedParquetMetaData;
 use crate::opener::ParquetMorselizer;
 use crate::opener::build_pruning_predicates;
 use crate::row_filter::can_expr_be_pushed_down_with_schemas;
@@ -36,8 +38,10 @@ use arrow::datatypes::TimeUnit;
 use datafusion_common::DataFusionError;
 use datafusion_common::config::TableParquetOptions;
 use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_datasource::PartitionedFile;
 use datafusion_datasource::TableSchema;
 use datafusion_datasource::file::FileSource;
+use datafusion_datasource::file_groups::{FileGroup, FileGroupPartitioner};
 use datafusion_datasource::file_scan_config::FileScanConfig;
 use datafusion_physical_expr::projection::ProjectionExprs;
 use datafusion_physical_expr::{EquivalenceProperties, conjunction};
@@ -52,10 +56,12 @@ use datafusion_physical_plan::filter_pushdown::{
 };
 use datafusion_physical_plan::metrics::Count;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
+use datafusion_pruning::PruningPredicateCache;
 
+use datafusion_execution::cache::cache_manager::FileMetadataCache;
 #[cfg(feature = "parquet_encryption")]
 use datafusion_execution::parquet_encryption::EncryptionFactory;
-use datafusion_physical_expr_common::sort_expr::PhysicalSortExpr;
+use datafusion_physical_expr_common::sort_expr::{LexOrdering, PhysicalSortExpr};
 use itertools::Itertools;
 use object_store::ObjectStore;
 #[cfg(feature = "parquet_encryption")]
@@ -294,6 +300,12 @@ pub struct ParquetSource {
     /// so we still need to sort them after reading, so the reverse scan is inexact.
     /// Used to optimize ORDER BY ... DESC on sorted data.
     reverse_row_groups: bool,
+    /// Optional cache of file metadata, used to split files at row group
+    /// boundaries when repartitioning
+    pub(crate) file_metadata_cache: Option<Arc<dyn FileMetadataCache>>,
+    /// Pruning predicates built while opening files, shared by all
+    /// partitions so each distinct file schema is only handled once
+    pruning_predicate_cache: Arc<PruningPredicateCache>,
 }
 
 impl ParquetSource {
@@ -319,6 +331,8 @@ impl ParquetSource {
             #[cfg(feature = "parquet_encryption")]
             encryption_factory: None,
             reverse_row_groups: false,
+            file_metadata_cache: None,
+            pruning_predicate_cache: Default::default(),
         }
     }
 
@@ -387,6 +401,19 @@ impl ParquetSource {
         self
     }
 
+    /// Set the cache of file metadata.
+    ///
+    /// If the metadata of the files to scan is cached (e.g. after inferring
+    /// their schema or statistics), large files are split across partitions
+    /// at row group boundaries rather than at arbitrary byte offsets.
+    pub fn with_file_metadata_cache(
+        mut self,
+        file_metadata_cache: Arc<dyn FileMetadataCache>,
+    ) -> Self {
+        self.file_metadata_cache = Some(file_metadata_cache);
+        self
+    }
+
     /// If true, the predicate will be used during the parquet scan.
     /// Defaults to false.
     pub fn with_pushdown_filters(mut self, pushdown_filters: bool) -> Self {
@@ -581,6 +608,7 @@ impl FileSource for ParquetSource {
             encryption_factory: self.get_encryption_factory_with_config(),
             max_predicate_cache_size: self.max_predicate_cache_size(),
             reverse_row_groups: self.reverse_row_groups,
+            pruning_predicate_cache: Arc::clone(&self.pruning_predicate_cache),
         }))
     }
 
@@ -619,6 +647,45 @@ impl FileSource for ParquetSource {
         "parquet"
     }
 
+    /// Redistribute files across partitions according to their size.
+    ///
+    /// If the metadata of all files is cached, and the order of the files
+    /// does not need to be preserved, files are split at row group
+    /// boundaries, balancing the compressed size of the row groups. Otherwise
+    /// files are split by byte ranges, see [`FileGroupPartitioner`].
+    fn repartitioned(
+        &self,
+        target_partitions: usize,
+        repartition_file_min_size: usize,
+        output_ordering: Option<LexOrdering>,
+        config: &FileScanConfig,
+    ) -> datafusion_common::Result<Option<FileScanConfig>> {
+        let repartitioned_file_groups = match &self.file_metadata_cache {
+            Some(file_metadata_cache) if output_ordering.is_none() => {
+                repartition_by_row_groups(
+                    &config.file_groups,
+                    target_partitions,
+                    repartition_file_min_size,
+                    file_metadata_cache.as_ref(),
+                )
+            }
+            _ => None,
+        }
+        .or_else(|| {
+            FileGroupPartitioner::new()
+                .with_target_partitions(target_partitions)
+                .with_repartition_file_min_size(repartition_file_min_size)
+                .with_preserve_order_within_groups(output_ordering.is_some())
+                .repartition_file_groups(&config.file_groups)
+        });
+
+        Ok(repartitioned_file_groups.map(|file_groups| {
+            let mut config = config.clone();
+            config.file_groups = file_groups;
+            config
+        }))
+    }
+
     fn fmt_extra(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
@@ -849,11 +916,277 @@ impl FileSource for ParquetSource {
     }
 }
 
+/// Evenly repartition the files of `file_groups` across `target_partitions`
+/// groups by the compressed size of their row groups, with each group reading
+/// consecutive row groups.
+///
+/// Each file is split into byte ranges starting at the first page of a row
+/// group, so that the range of a file assigned to a group contains exactly the
+/// row groups read by that group (see [`RowGroupAccessPlanFilter::prune_by_range`]).
+///
+/// Files read entirely by a single group are not split into ranges.
+///
+/// Returns `None` if the metadata of a file is not in `file_metadata_cache`,
+/// a file is already split into ranges, or the files are too small to be
+/// repartitioned.
+///
+/// [`RowGroupAccessPlanFilter::prune_by_range`]: crate::RowGroupAccessPlanFilter::prune_by_range
+fn repartition_by_row_groups(
+    file_groups: &[FileGroup],
+    target_partitions: usize,
+    repartition_file_min_size: usize,
+    file_metadata_cache: &dyn FileMetadataCache,
+) -> Option<Vec<FileGroup>> {
+    if target_partitions == 0 {
+        return None;
+    }
+
+    // The byte range and compressed size of each row group of each file
+    let mut row_groups: Vec<(&PartitionedFile, Range<u64>, u64)> = vec![];
+    for file in file_groups.iter().flat_map(FileGroup::iter) {
+        if file.range.is_some() {
+            return None;
+        }
+        let cached = file_metadata_cache.get(&file.object_meta.location)?;
+        if !cached.is_valid_for(&file.object_meta) {
+            return None;
+        }
+        let metadata = cached
+            .file_metadata
+            .as_any()
+            .downcast_ref::<CachedParquetMetaData>()?
+            .parquet_metadata();
+
+        let offsets = metadata
+            .row_groups()
+            .iter()
+            .map(|row_group| {
+                let column = row_group.columns().first()?;
+                let offset = column
+                    .dictionary_page_offset()
+                    .unwrap_or_else(|| column.data_page_offset());
+                u64::try_from(offset).ok()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        // Row groups can only be split into ranges if they are stored in order
+        if !offsets.is_sorted_by(|a, b| a < b) {
+            return None;
+        }
+        if offsets.is_empty() {
+            row_groups.push((file, 0..file.object_meta.size, 0));
+            continue;
+        }
+        for (idx, row_group) in metadata.row_groups().iter().enumerate() {
+            // The first range includes the file header, the last the footer
+            let start = if idx == 0 { 0 } else { offsets[idx] };
+            let end = offsets
+                .get(idx + 1)
+                .copied()
+                .unwrap_or(file.object_meta.size);
+            let size = u64::try_from(row_group.compressed_size()).unwrap_or_default();
+            row_groups.push((file, start..end, size));
+        }
+    }
+
+    let total_size = row_groups.iter().map(|(_, _, size)| size).sum::<u64>();
+    if total_size < repartition_file_min_size as u64 || total_size == 0 {
+        return None;
+    }
+
+    // Assign each row group to the partition its midpoint falls into
+    let mut partitions: Vec<Vec<PartitionedFile>> = vec![vec![]; target_partitions];
+    let mut offset = 0;
+    for (file, range, size) in row_groups {
+        let midpoint = offset + size / 2;
+        offset += size;
+        let partition = ((midpoint as u128 * target_partitions as u128
+            / total_size as u128) as usize)
+            .min(target_partitions - 1);
+
+        // Consecutive row groups of a file in a partition are read as one range
+        let files = &mut partitions[partition];
+        if let Some(last) = files.last_mut()
+            && last.object_meta.location == file.object_meta.location
+            && let Some(last_range) = last.range.as_mut()
+            && last_range.end as u64 == range.start
+        {
+            last_range.end = range.end as i64;
+        } else {
+            files.push(
+                file.clone()
+                    .with_range(range.start as i64, range.end as i64),
+            );
+        }
+    }
+
+    Some(
+        partitions
+            .into_iter()
+            .filter(|files| !files.is_empty())
+            .map(|mut files| {
+                // Files read entirely by one partition need no range
+                for file in &mut files {
+                    if file.range() == (0, file.object_meta.size) {
+                        file.range = None;
+                    }
+                }
+                FileGroup::new(files)
+            })
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::datatypes::Schema;
+    use arrow::array::{Int64Array, RecordBatch};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_execution::cache::CacheAccessor;
+    use datafusion_execution::cache::DefaultFilesMetadataCache;
+    use datafusion_execution::cache::cache_manager::CachedFileMetadataEntry;
     use datafusion_physical_expr::expressions::lit;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::metadata::ParquetMetaDataReader;
+    use parquet::file::properties::WriterProperties;
+
+    /// Write a parquet file with `num_row_groups` row groups of 100 rows each
+    /// and add its metadata to `cache`
+    fn cached_parquet_file(
+        name: &str,
+        num_row_groups: usize,
+        cache: &DefaultFilesMetadataCache,
+    ) -> (PartitionedFile, Vec<u64>) {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(100))
+            .build();
+        let mut buffer = vec![];
+        let mut writer =
+            ArrowWriter::try_new(&mut buffer, Arc::clone(&schema), Some(props)).unwrap();
+        let values = Int64Array::from_iter_values(0..(num_row_groups as i64 * 100));
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(values)]).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(&bytes::Bytes::from(buffer.clone()))
+            .unwrap();
+        let offsets = metadata
+            .row_groups()
+            .iter()
+            .map(|row_group| {
+                let column = row_group.column(0);
+                let offset = column
+                    .dictionary_page_offset()
+                    .unwrap_or_else(|| column.data_page_offset());
+                offset as u64
+            })
+            .collect();
+
+        let file = PartitionedFile::new(name, buffer.len() as u64);
+        cache.put(
+            &file.object_meta.location,
+            CachedFileMetadataEntry::new(
+                file.object_meta.clone(),
+                Arc::new(CachedParquetMetaData::new(Arc::new(metadata))),
+            ),
+        );
+        (file, offsets)
+    }
+
+    fn file_ranges(file_groups: &[FileGroup]) -> Vec<Vec<(String, i64, i64)>> {
+        file_groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|file| {
+                        let (start, end) = file.range();
+                        let location = file.object_meta.location.to_string();
+                        (location, start as i64, end as i64)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_repartition_by_row_groups() {
+        let cache = DefaultFilesMetadataCache::new(usize::MAX);
+        let (file, offsets) = cached_parquet_file("a.parquet", 4, &cache);
+        let size = file.object_meta.size as i64;
+        let offsets = offsets.iter().map(|o| *o as i64).collect::<Vec<_>>();
+        let file_groups = vec![FileGroup::new(vec![file])];
+
+        // two row groups per partition
+        let repartitioned =
+            repartition_by_row_groups(&file_groups, 2, 0, &cache).unwrap();
+        assert_eq!(
+            file_ranges(&repartitioned),
+            vec![
+                vec![("a.parquet".to_string(), 0, offsets[2])],
+                vec![("a.parquet".to_string(), offsets[2], size)],
+            ]
+        );
+
+        // no more partitions than row groups
+        let repartitioned =
+            repartition_by_row_groups(&file_groups, 8, 0, &cache).unwrap();
+        assert_eq!(
+            file_ranges(&repartitioned),
+            vec![
+                vec![("a.parquet".to_string(), 0, offsets[1])],
+                vec![("a.parquet".to_string(), offsets[1], offsets[2])],
+                vec![("a.parquet".to_string(), offsets[2], offsets[3])],
+                vec![("a.parquet".to_string(), offsets[3], size)],
+            ]
+        );
+
+        // a single partition reads the whole file
+        let repartitioned =
+            repartition_by_row_groups(&file_groups, 1, 0, &cache).unwrap();
+        assert_eq!(repartitioned.len(), 1);
+        assert!(repartitioned[0].files()[0].range.is_none());
+
+        // files smaller than the minimum size are not repartitioned
+        assert!(
+            repartition_by_row_groups(&file_groups, 2, size as usize, &cache).is_none()
+        );
+    }
+
+    #[test]
+    fn test_repartition_by_row_groups_multiple_files() {
+        let cache = DefaultFilesMetadataCache::new(usize::MAX);
+        let (a, a_offsets) = cached_parquet_file("a.parquet", 1, &cache);
+        let (b, b_offsets) = cached_parquet_file("b.parquet", 3, &cache);
+        let (a_size, b_size) = (a.object_meta.size as i64, b.object_meta.size as i64);
+        let file_groups = vec![FileGroup::new(vec![a]), FileGroup::new(vec![b])];
+
+        let repartitioned =
+            repartition_by_row_groups(&file_groups, 2, 0, &cache).unwrap();
+        assert_eq!(a_offsets.len(), 1);
+        assert_eq!(
+            file_ranges(&repartitioned),
+            vec![
+                vec![
+                    ("a.parquet".to_string(), 0, a_size),
+                    ("b.parquet".to_string(), 0, b_offsets[1] as i64),
+                ],
+                vec![("b.parquet".to_string(), b_offsets[1] as i64, b_size)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repartition_by_row_groups_uncached_file() {
+        let cache = DefaultFilesMetadataCache::new(usize::MAX);
+        let (a, _) = cached_parquet_file("a.parquet", 4, &cache);
+        let file_groups = vec![FileGroup::new(vec![
+            a,
+            PartitionedFile::new("uncached.parquet", 1000),
+        ])];
+        assert!(repartition_by_row_groups(&file_groups, 2, 0, &cache).is_none());
+    }
 
     #[test]
     #[expect(deprecated)]
@@ -0,0 +1,180 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DeletionVector`]: rows of a file that must not be read
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::array::{BooleanArray, RecordBatch};
+use arrow::compute::filter_record_batch;
+use datafusion_common::Result;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+
+/// The positions of the rows of a file that have been deleted, and must be
+/// suppressed when the file is scanned.
+///
+/// Table formats such as Iceberg (positional delete files) and Delta Lake
+/// (deletion vectors) delete rows without rewriting the data files that
+/// contain them, by recording the positions of the deleted rows instead.
+/// Attach the deleted rows of a file to its [`PartitionedFile`] with
+/// [`PartitionedFile::with_deletion_vector`], and the scan of the
+/// [`FileScanConfig`] skips them:
+///
+/// * Parquet skips the deleted rows while decoding, using a row selection,
+///   and does not read row groups in which all rows are deleted
+/// * Other formats filter the deleted rows out of the decoded batches. As the
+///   position of a row is only known when the file is read from its start,
+///   files with deletion vectors are not split into byte ranges.
+///
+/// Positions are zero based, and count all the rows of the file.
+///
+/// [`PartitionedFile`]: crate::PartitionedFile
+/// [`PartitionedFile::with_deletion_vector`]: crate::PartitionedFile::with_deletion_vector
+/// [`FileScanConfig`]: crate::file_scan_config::FileScanConfig
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletionVector {
+    /// The positions of the deleted rows, sorted and without duplicates
+    deleted: Vec<u64>,
+}
+
+impl DeletionVector {
+    /// Create a new [`DeletionVector`] deleting the rows at `positions`
+    pub fn new(positions: impl IntoIterator<Item = u64>) -> Self {
+        let mut deleted: Vec<u64> = positions.into_iter().collect();
+        deleted.sort_unstable();
+        deleted.dedup();
+        Self { deleted }
+    }
+
+    /// The number of deleted rows
+    pub fn len(&self) -> usize {
+        self.deleted.len()
+    }
+
+    /// Returns true if no row is deleted
+    pub fn is_empty(&self) -> bool {
+        self.deleted.is_empty()
+    }
+
+    /// Returns true if the row at `position` is deleted
+    pub fn is_deleted(&self, position: u64) -> bool {
+        self.deleted.binary_search(&position).is_ok()
+    }
+
+    /// The sorted positions of the deleted rows within `rows`
+    pub fn deleted_in(&self, rows: Range<u64>) -> &[u64] {
+        let start = self.deleted.partition_point(|&p| p < rows.start);
+        let end = self.deleted.partition_point(|&p| p < rows.end);
+        &self.deleted[start..end]
+    }
+
+    /// Removes the deleted rows from `batch`, whose first row is the row at
+    /// position `first_row` of the file
+    pub fn filter_batch(
+        &self,
+        batch: RecordBatch,
+        first_row: u64,
+    ) -> Result<RecordBatch> {
+        let num_rows = batch.num_rows();
+        let deleted = self.deleted_in(first_row..first_row + num_rows as u64);
+        if deleted.is_empty() {
+            return Ok(batch);
+        }
+
+        let mut keep = vec![true; num_rows];
+        for &position in deleted {
+            keep[(position - first_row) as usize] = false;
+        }
+        Ok(filter_record_batch(&batch, &BooleanArray::from(keep))?)
+    }
+}
+
+impl FromIterator<u64> for DeletionVector {
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Removes the rows deleted by `deletion_vector` from `stream`, the batches
+/// of a file read from its start
+pub(crate) fn filter_deleted_rows(
+    stream: BoxStream<'static, Result<RecordBatch>>,
+    deletion_vector: Arc<DeletionVector>,
+) -> BoxStream<'static, Result<RecordBatch>> {
+    let mut first_row = 0;
+    stream
+        .map(move |batch| {
+            let batch = batch?;
+            let num_rows = batch.num_rows() as u64;
+            let batch = deletion_vector.filter_batch(batch, first_row);
+            first_row += num_rows;
+            batch
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int32Array;
+    use datafusion_common::record_batch;
+    use futures::stream;
+
+    #[test]
+    fn deletion_vector_positions() {
+        let deletion_vector = DeletionVector::new([7, 2, 4, 2]);
+        assert_eq!(deletion_vector.len(), 3);
+        assert!(deletion_vector.is_deleted(4));
+        assert!(!deletion_vector.is_deleted(5));
+        assert_eq!(deletion_vector.deleted_in(0..4), &[2]);
+        assert_eq!(deletion_vector.deleted_in(4..8), &[4, 7]);
+        assert!(deletion_vector.deleted_in(8..100).is_empty());
+    }
+
+    #[tokio::test]
+    async fn filter_deleted_rows_across_batches() -> Result<()> {
+        let batches = vec![
+            Ok(record_batch!(("a", Int32, vec![0, 1, 2]))?),
+            Ok(record_batch!(("a", Int32, vec![3, 4]))?),
+            Ok(record_batch!(("a", Int32, vec![5, 6, 7]))?),
+        ];
+        let deletion_vector = Arc::new(DeletionVector::new([1, 3, 4, 7]));
+
+        let batches: Vec<RecordBatch> =
+            filter_deleted_rows(stream::iter(batches).boxed(), deletion_vector)
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .collect::<Result<_>>()?;
+        let values: Vec<i32> = batches
+            .iter()
+            .flat_map(|batch| {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                array.values().to_vec()
+            })
+            .collect();
+        assert_eq!(values, vec![0, 2, 5, 6]);
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DictionaryUnifier`]: shares the dictionaries of the batches read from
+//! different files
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, RecordBatch, RecordBatchOptions, UInt64Array, make_array,
+    new_empty_array,
+};
+use arrow::compute::{CastOptions, cast_with_options, concat, take};
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::row::{RowConverter, SortField};
+use datafusion_common::Result;
+
+/// Rewrites the dictionary encoded columns of a stream of batches so that
+/// they share a single, unified dictionary per column.
+///
+/// Each file (and often each row group) of a scan is encoded with its own
+/// dictionary, so batches read from different files have different
+/// dictionaries. Operators that combine batches, such as `concat` or the
+/// group by and join hash tables, then have to merge the dictionaries or fall
+/// back to comparing the values. The [`DictionaryUnifier`] remaps the keys of
+/// each batch to a dictionary that accumulates the distinct values seen so
+/// far: as long as no new value is seen, consecutive batches share the very
+/// same dictionary.
+///
+/// The unified dictionary of a column grows with the number of its distinct
+/// values, so unification suits low cardinality columns. A column is no
+/// longer unified once its distinct values can not be addressed by its key
+/// type.
+#[derive(Debug)]
+pub struct DictionaryUnifier {
+    /// The unified dictionary of each column, `None` for columns that are not
+    /// dictionary encoded
+    columns: Vec<Option<UnifiedDictionary>>,
+}
+
+impl DictionaryUnifier {
+    /// Create a new [`DictionaryUnifier`] for batches of `schema`
+    pub fn new(schema: &SchemaRef) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| match field.data_type() {
+                DataType::Dictionary(key_type, value_type) => {
+                    UnifiedDictionary::try_new(key_type, value_type)
+                }
+                _ => None,
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Returns true if `schema` has a column whose dictionary can be unified
+    pub fn is_needed(&self) -> bool {
+        self.columns.iter().any(Option::is_some)
+    }
+
+    /// Remaps the dictionary encoded columns of `batch` to the unified
+    /// dictionaries
+    pub fn unify(&mut self, batch: RecordBatch) -> Result<RecordBatch> {
+        if !self.is_needed() {
+            return Ok(batch);
+        }
+
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(self.columns.iter_mut())
+            .map(|(column, unified)| match unified {
+                Some(unified) => unified.unify(column),
+                None => Ok(Arc::clone(column)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+        Ok(RecordBatch::try_new_with_options(
+            batch.schema(),
+            columns,
+            &options,
+        )?)
+    }
+}
+
+/// The unified dictionary of a column
+struct UnifiedDictionary {
+    key_type: DataType,
+    /// The number of values addressable by `key_type`
+    capacity: usize,
+    converter: RowConverter,
+    /// The distinct values seen so far
+    values: ArrayRef,
+    /// The position in `values` of each value, in the row format
+    positions: HashMap<Box<[u8]>, u64>,
+    /// The dictionary of the last batch, and the position in `values` of
+    /// each of its values. Batches read from the same row group usually share
+    /// their dictionary.
+    last: Option<(ArrayRef, ArrayRef)>,
+    /// Set once the distinct values can not be addressed by `key_type`
+    exhausted: bool,
+}
+
+impl std::fmt::Debug for UnifiedDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnifiedDictionary")
+            .field("key_type", &self.key_type)
+            .field("values", &self.values.len())
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl UnifiedDictionary {
+    fn try_new(key_type: &DataType, value_type: &DataType) -> Option<Self> {
+        let converter =
+            RowConverter::new(vec![SortField::new(value_type.clone())]).ok()?;
+        Some(Self {
+            key_type: key_type.clone(),
+            capacity: key_capacity(key_type),
+            converter,
+            values: new_empty_array(value_type),
+            positions: HashMap::new(),
+            last: None,
+            exhausted: false,
+        })
+    }
+
+    fn unify(&mut self, column: &ArrayRef) -> Result<ArrayRef> {
+        let Some(dictionary) = column.as_any_dictionary_opt() else {
+            return Ok(Arc::clone(column));
+        };
+        if self.exhausted {
+            return Ok(Arc::clone(column));
+        }
+
+        let mapping = match &self.last {
+            Some((values, mapping))
+                if values.to_data().ptr_eq(&dictionary.values().to_data()) =>
+            {
+                Arc::clone(mapping)
+            }
+            _ => {
+                let Some(mapping) = self.map_values(dictionary.values())? else {
+                    self.exhausted = true;
+                    self.last = None;
+                    return Ok(Arc::clone(column));
+                };
+                self.last = Some((Arc::clone(dictionary.values()), Arc::clone(&mapping)));
+                mapping
+            }
+        };
+
+        let keys = take(mapping.as_ref(), dictionary.keys(), None)?;
+        let data = keys
+            .to_data()
+            .into_builder()
+            .data_type(column.data_type().clone())
+            .child_data(vec![self.values.to_data()])
+            .build()?;
+        Ok(make_array(data))
+    }
+
+    /// Adds the values of a dictionary to the unified values, and returns
+    /// the position of each of them in the unified values, as keys, or `None`
+    /// if they do not fit in the key type
+    fn map_values(&mut self, values: &ArrayRef) -> Result<Option<ArrayRef>> {
+        let rows = self.converter.convert_columns(&[Arc::clone(values)])?;
+
+        let mut new_positions: HashMap<Box<[u8]>, u64> = HashMap::new();
+        let mut new_values = vec![];
+        let mut mapping = Vec::with_capacity(rows.num_rows());
+        for (i, row) in rows.iter().enumerate() {
+            let row: &[u8] = row.as_ref();
+            let position = match self.positions.get(row) {
+                Some(&position) => position,
+                None => *new_positions.entry(row.into()).or_insert_with(|| {
+                    new_values.push(i as u64);
+                    (self.values.len() + new_values.len() - 1) as u64
+                }),
+            };
+            mapping.push(position);
+        }
+
+        if self.values.len() + new_values.len() > self.capacity {
+            return Ok(None);
+        }
+        if !new_values.is_empty() {
+            let new_values = take(values.as_ref(), &UInt64Array::from(new_values), None)?;
+            self.values = concat(&[self.values.as_ref(), new_values.as_ref()])?;
+            self.positions.extend(new_positions);
+        }
+
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let mapping =
+            cast_with_options(&UInt64Array::from(mapping), &self.key_type, &options)?;
+        Ok(Some(mapping))
+    }
+}
+
+/// The number of values that keys of `key_type` can address
+fn key_capacity(key_type: &DataType) -> usize {
+    let max = match key_type {
+        DataType::Int8 => i8::MAX as usize,
+        DataType::Int16 => i16::MAX as usize,
+        DataType::Int32 => i32::MAX as usize,
+        DataType::UInt8 => u8::MAX as usize,
+        DataType::UInt16 => u16::MAX as usize,
+        DataType::UInt32 => u32::MAX as usize,
+        _ => usize::MAX,
+    };
+    max.saturating_add(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{DictionaryArray, StringArray};
+    use arrow::datatypes::{Field, Int8Type, Int32Type, Schema};
+
+    fn batch(schema: &SchemaRef, keys: Vec<Option<i32>>, values: &[&str]) -> RecordBatch {
+        let dictionary = DictionaryArray::<Int32Type>::try_new(
+            keys.into(),
+            Arc::new(StringArray::from(values.to_vec())),
+        )
+        .unwrap();
+        RecordBatch::try_new(Arc::clone(schema), vec![Arc::new(dictionary)]).unwrap()
+    }
+
+    fn decoded(batch: &RecordBatch) -> Vec<Option<String>> {
+        let dictionary = batch.column(0).as_dictionary::<Int32Type>();
+        let values = dictionary.values().as_string::<i32>();
+        dictionary
+            .keys()
+            .iter()
+            .map(|key| key.map(|key| values.value(key as usize).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn unify_dictionaries_across_batches() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new_dictionary(
+            "a",
+            DataType::Int32,
+            DataType::Utf8,
+            true,
+        )]));
+        let mut unifier = DictionaryUnifier::new(&schema);
+        assert!(unifier.is_needed());
+
+        let batches = [
+            batch(&schema, vec![Some(0), Some(1), None], &["x", "y"]),
+            batch(&schema, vec![Some(1), Some(0), Some(1)], &["y", "x"]),
+            batch(&schema, vec![Some(2), Some(0)], &["x", "y", "z"]),
+        ];
+        let unified = batches
+            .iter()
+            .map(|batch| unifier.unify(batch.clone()))
+            .collect::<Result<Vec<_>>>()?;
+
+        for (batch, unified) in batches.iter().zip(&unified) {
+            assert_eq!(decoded(batch), decoded(unified));
+        }
+        let dictionary =
+            |i: usize| unified[i].column(0).as_any_dictionary().values().to_data();
+        // no new value: the dictionary is shared
+        assert!(dictionary(0).ptr_eq(&dictionary(1)));
+        // a new value: the dictionary grows
+        assert!(!dictionary(1).ptr_eq(&dictionary(2)));
+        assert_eq!(
+            unified[2]
+                .column(0)
+                .as_dictionary::<Int32Type>()
+                .values()
+                .as_ref(),
+            &StringArray::from(vec!["x", "y", "z"]) as &dyn Array
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unify_dictionaries_stops_when_keys_overflow() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new_dictionary(
+            "a",
+            DataType::Int8,
+            DataType::Utf8,
+            false,
+        )]));
+        let mut unifier = DictionaryUnifier::new(&schema);
+
+        for n in 0..3 {
+            let values: Vec<String> = (0..100).map(|i| format!("{n}_{i}")).collect();
+            let dictionary = DictionaryArray::<Int8Type>::try_new(
+                (0..100).collect::<Vec<i8>>().into(),
+                Arc::new(StringArray::from(values)),
+            )?;
+            let batch =
+                RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(dictionary)])?;
+
+            let unified = unifier.unify(batch.clone())?;
+            let values = unified.column(0).as_any_dictionary().values();
+            if n == 0 {
+                assert_eq!(values.len(), 100);
+            } else {
+                // 200 values do not fit in Int8 keys: the batches are unchanged
+                assert_eq!(unified, batch);
+            }
+        }
+        Ok(())
+    }
+}
@@ -154,7 +154,12 @@ pub trait FileSource: Any + Send + Sync {
     /// own repartitioning logic.
     ///
     /// The default implementation uses [`FileGroupPartitioner`]. See that
-    /// struct for more details.
+    /// struct for more details. Files with a [`DeletionVector`], or whose
+    /// row numbers are read (see [`FileRowNumberColumn`]), must be read from
+    /// their start, so scans containing them are not repartitioned.
+    ///
+    /// [`DeletionVector`]: crate::deletion_vector::DeletionVector
+    /// [`FileRowNumberColumn`]: crate::metadata_column::FileRowNumberColumn
     fn repartitioned(
         &self,
         target_partitions: usize,
@@ -162,7 +167,10 @@ pub trait FileSource: Any + Send + Sync {
         output_ordering: Option<LexOrdering>,
         config: &FileScanConfig,
     ) -> Result<Option<FileScanConfig>> {
-        if config.file_compression_type.is_compressed() || !self.supports_repartitioning()
+        if config.file_compression_type.is_compressed()
+            || !self.supports_repartitioning()
+            || config.has_deletion_vectors()
+            || config.reads_file_row_numbers()
         {
             return Ok(None);
         }
@@ -24,8 +24,8 @@ use crate::file_groups::FileGroup;
 use crate::{
     PartitionedFile, display::FileGroupsDisplay, file::FileSource,
     file_compression_type::FileCompressionType, file_stream::FileStreamBuilder,
-    file_stream::work_source::SharedWorkSource, source::DataSource,
-    statistics::MinMaxStatistics,
+    file_stream::work_source::SharedWorkSource, metadata_column::FileRowNumberColumn,
+    source::DataSource, statistics::MinMaxStatistics,
 };
 use arrow::datatypes::FieldRef;
 use arrow::datatypes::{DataType, Schema, SchemaRef};
@@ -210,6 +210,17 @@ pub struct FileScanConfig {
     /// If the number of file partitions > target_partitions, the file partitions will be grouped
     /// in a round-robin fashion such that number of file partitions = target_partitions.
     pub partitioned_by_file_group: bool,
+    /// When true, the dictionary encoded columns of the batches read from
+    /// the files of each partition share a unified dictionary. See
+    /// [`DictionaryUnifier`] for more details.
+    ///
+    /// [`DictionaryUnifier`]: crate::dictionary_unifier::DictionaryUnifier
+    pub unify_dictionaries: bool,
+    /// When true, the files of each file group are known to be sorted by the
+    /// output orderings and to not overlap, so the output orderings hold for
+    /// file groups of several files even if the statistics of the files can
+    /// not prove it. See [`FileScanConfigBuilder::with_ordered_file_groups`].
+    pub ordered_file_groups: bool,
 }
 
 /// A builder for [`FileScanConfig`]'s.
@@ -280,6 +291,8 @@ pub struct FileScanConfigBuilder {
     batch_size: Option<usize>,
     expr_adapter_factory: Option<Arc<dyn PhysicalExprAdapterFactory>>,
     partitioned_by_file_group: bool,
+    unify_dictionaries: bool,
+    ordered_file_groups: bool,
 }
 
 impl FileScanConfigBuilder {
@@ -306,6 +319,8 @@ impl FileScanConfigBuilder {
             batch_size: None,
             expr_adapter_factory: None,
             partitioned_by_file_group: false,
+            unify_dictionaries: false,
+            ordered_file_groups: false,
         }
     }
 
@@ -506,6 +521,27 @@ impl FileScanConfigBuilder {
         self
     }
 
+    /// Set whether the dictionary encoded columns of the batches read from
+    /// different files share a unified dictionary.
+    ///
+    /// See [`FileScanConfig::unify_dictionaries`] for more details.
+    pub fn with_unify_dictionaries(mut self, unify_dictionaries: bool) -> Self {
+        self.unify_dictionaries = unify_dictionaries;
+        self
+    }
+
+    /// Declare that the files of each file group, in the order they are
+    /// read, are sorted by the output orderings and do not overlap, for
+    /// example because the files are known to be written in that order.
+    ///
+    /// The output orderings of file groups with several files are otherwise
+    /// only kept if the statistics of the files prove them, see
+    /// [`FileScanConfig::ordered_file_groups`].
+    pub fn with_ordered_file_groups(mut self, ordered_file_groups: bool) -> Self {
+        self.ordered_file_groups = ordered_file_groups;
+        self
+    }
+
     /// Build the final [`FileScanConfig`] with all the configured settings.
     ///
     /// This method takes ownership of the builder and returns the constructed `FileScanConfig`.
@@ -527,6 +563,8 @@ impl FileScanConfigBuilder {
             batch_size,
             expr_adapter_factory: expr_adapter,
             partitioned_by_file_group,
+            unify_dictionaries,
+            ordered_file_groups,
         } = self;
 
         let constraints = constraints.unwrap_or_default();
@@ -552,6 +590,8 @@ impl FileScanConfigBuilder {
             expr_adapter_factory: expr_adapter,
             statistics,
             partitioned_by_file_group,
+            unify_dictionaries,
+            ordered_file_groups,
         }
     }
 }
@@ -571,6 +611,8 @@ impl From<FileScanConfig> for FileScanConfigBuilder {
             batch_size: config.batch_size,
             expr_adapter_factory: config.expr_adapter_factory,
             partitioned_by_file_group: config.partitioned_by_file_group,
+            unify_dictionaries: config.unify_dictionaries,
+            ordered_file_groups: config.ordered_file_groups,
         }
     }
 }
@@ -666,6 +708,10 @@ impl DataSource for FileScanConfig {
                     write!(f, ", {}", self.constraints)?;
                 }
 
+                if self.unify_dictionaries {
+                    write!(f, ", unify_dictionaries=true")?;
+                }
+
                 self.fmt_file_source(t, f)
             }
             DisplayFormatType::TreeRender => {
@@ -809,14 +855,23 @@ impl DataSource for FileScanConfig {
             if let Some(file_group) = self.file_groups.get(partition)
                 && let Some(stat) = file_group.file_statistics(None)
             {
+                // The statistics of the files do not account for deleted rows
+                let stat = if file_group
+                    .iter()
+                    .any(|file| file.deletion_vector().is_some())
+                {
+                    stat.clone().to_inexact()
+                } else {
+                    stat.clone()
+                };
                 // Project the statistics based on the projection
                 let output_schema = self.projected_schema()?;
                 return if let Some(projection) = self.file_source.projection() {
                     Ok(Arc::new(
-                        projection.project_statistics(stat.clone(), &output_schema)?,
+                        projection.project_statistics(stat, &output_schema)?,
                     ))
                 } else {
-                    Ok(Arc::new(stat.clone()))
+                    Ok(Arc::new(stat))
                 };
             }
             // If no statistics available for this partition, return unknown
@@ -1053,6 +1108,9 @@ impl FileScanConfig {
     /// Merging of the multiple partition streams into a single ordered stream is handled
     /// upstream e.g. by `SortPreservingMergeExec`.
     fn validated_output_ordering(&self) -> Vec<LexOrdering> {
+        if self.ordered_file_groups {
+            return self.output_ordering.clone();
+        }
         let schema = self.file_source.table_schema().table_schema();
         sort_pushdown::validate_orderings(
             &self.output_ordering,
@@ -1062,6 +1120,36 @@ impl FileScanConfig {
         )
     }
 
+    /// Returns true if any file of this scan has a [`DeletionVector`]
+    ///
+    /// [`DeletionVector`]: crate::deletion_vector::DeletionVector
+    pub fn has_deletion_vectors(&self) -> bool {
+        self.file_groups
+            .iter()
+            .flat_map(|group| group.iter())
+            .any(|file| file.deletion_vector().is_some())
+    }
+
+    /// Returns true if the scan reads the row numbers of its files, see
+    /// [`FileRowNumberColumn`]
+    pub fn reads_file_row_numbers(&self) -> bool {
+        let num_file_columns = self.file_schema().fields().len();
+        let projected = self
+            .file_source
+            .projection()
+            .map(|projection| projection.column_indices());
+        self.file_groups
+            .iter()
+            .flat_map(|group| group.iter())
+            .filter_map(|file| file.extension::<FileRowNumberColumn>())
+            .any(|row_numbers| {
+                let index = num_file_columns + row_numbers.partition_value_index;
+                projected
+                    .as_ref()
+                    .is_none_or(|projected| projected.contains(&index))
+            })
+    }
+
     /// Get the file schema (schema of the files without partition columns)
     pub fn file_schema(&self) -> &SchemaRef {
         self.file_source.table_schema().file_schema()
@@ -1072,13 +1160,15 @@ impl FileScanConfig {
         self.file_source.table_schema().table_partition_cols()
     }
 
-    /// Returns the unprojected table statistics, marking them as inexact if filters are present.
+    /// Returns the unprojected table statistics, marking them as inexact if filters
+    /// or deletion vectors are present.
     ///
     /// When filters are pushed down (including pruning predicates and bloom filters),
     /// we can't guarantee the statistics are exact because we don't know how many
-    /// rows will be filtered out.
+    /// rows will be filtered out. The statistics of a file do not account for the
+    /// rows deleted by its deletion vector either.
     pub fn statistics(&self) -> Statistics {
-        if self.file_source.filter().is_some() {
+        if self.file_source.filter().is_some() || self.has_deletion_vectors() {
             self.statistics.clone().to_inexact()
         } else {
             self.statistics.clone()
@@ -532,6 +532,10 @@ pub(crate) fn get_projected_output_ordering(
 ) -> Vec<LexOrdering> {
     let projected_orderings =
         project_orderings(&base_config.output_ordering, projected_schema);
+    if base_config.ordered_file_groups {
+        // the files of each group are declared to be in order
+        return projected_orderings;
+    }
 
     let indices = base_config
         .file_source
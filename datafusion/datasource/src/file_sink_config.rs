@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 
 use crate::ListingTableUrl;
@@ -25,6 +26,7 @@ use crate::write::demux::{DemuxedStreamReceiver, start_demuxer_task};
 use arrow::datatypes::{DataType, SchemaRef};
 use datafusion_common::Result;
 use datafusion_common_runtime::SpawnedTask;
+use datafusion_execution::cache::cache_manager::FileStatisticsCache;
 use datafusion_execution::object_store::ObjectStoreUrl;
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use datafusion_expr::dml::InsertOp;
@@ -135,7 +137,7 @@ pub trait FileSink: DataSink {
 
 /// The base configurations to provide when creating a physical plan for
 /// writing to any given file format.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct FileSinkConfig {
     /// The unresolved URL specified by the user
     pub original_url: String,
@@ -160,6 +162,34 @@ pub struct FileSinkConfig {
     pub file_extension: String,
     /// Determines how the output path is interpreted.
     pub file_output_mode: FileOutputMode,
+    /// The statistics cache of the table written to, if any.
+    ///
+    /// Sinks that know the statistics of the files they write (e.g. the row
+    /// counts and min/max values in the footer of Parquet files) add them to
+    /// this cache, so that planning queries on the table does not need to read
+    /// them back from the new files.
+    pub statistics_cache: Option<Arc<dyn FileStatisticsCache>>,
+}
+
+impl Debug for FileSinkConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileSinkConfig")
+            .field("original_url", &self.original_url)
+            .field("object_store_url", &self.object_store_url)
+            .field("file_group", &self.file_group)
+            .field("table_paths", &self.table_paths)
+            .field("output_schema", &self.output_schema)
+            .field("table_partition_cols", &self.table_partition_cols)
+            .field("insert_op", &self.insert_op)
+            .field("keep_partition_by_columns", &self.keep_partition_by_columns)
+            .field("file_extension", &self.file_extension)
+            .field("file_output_mode", &self.file_output_mode)
+            .field(
+                "statistics_cache",
+                &self.statistics_cache.as_ref().map(|cache| cache.name()),
+            )
+            .finish()
+    }
 }
 
 impl FileSinkConfig {
@@ -17,6 +17,7 @@
 
 use std::sync::Arc;
 
+use crate::dictionary_unifier::DictionaryUnifier;
 use crate::file_scan_config::FileScanConfig;
 use crate::file_stream::scan_state::ScanState;
 use crate::file_stream::work_source::{SharedWorkSource, WorkSource};
@@ -133,8 +134,14 @@ impl<'a> FileStreamBuilder<'a> {
             file_stream_metrics,
         ));
 
+        let dictionary_unifier = config
+            .unify_dictionaries
+            .then(|| DictionaryUnifier::new(&projected_schema))
+            .filter(DictionaryUnifier::is_needed);
+
         Ok(FileStream {
             projected_schema,
+            dictionary_unifier,
             state: FileStreamState::Scan { scan_state },
             baseline_metrics: BaselineMetrics::new(metrics, partition),
         })
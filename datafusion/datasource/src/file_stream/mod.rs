@@ -31,6 +31,7 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use crate::PartitionedFile;
+use crate::dictionary_unifier::DictionaryUnifier;
 use crate::file_scan_config::FileScanConfig;
 use arrow::datatypes::SchemaRef;
 use datafusion_common::Result;
@@ -53,6 +54,9 @@ pub struct FileStream {
     /// The stream schema (file schema including partition columns and after
     /// projection).
     projected_schema: SchemaRef,
+    /// Unifies the dictionaries of the batches read from different files,
+    /// if enabled
+    dictionary_unifier: Option<DictionaryUnifier>,
     /// The stream state
     state: FileStreamState,
     /// runtime baseline metrics
@@ -122,7 +126,13 @@ impl Stream for FileStream {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let result = self.poll_inner(cx);
+        let result = match self.poll_inner(cx) {
+            Poll::Ready(Some(Ok(batch))) => match &mut self.dictionary_unifier {
+                Some(unifier) => Poll::Ready(Some(unifier.unify(batch))),
+                None => Poll::Ready(Some(Ok(batch))),
+            },
+            result => result,
+        };
         self.baseline_metrics.record_poll(result)
     }
 }
@@ -26,12 +26,10 @@ use std::sync::Arc;
 use crate::sink::DataSink;
 use crate::source::{DataSource, DataSourceExec};
 
-use arrow::array::{RecordBatch, RecordBatchOptions};
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::array::RecordBatch;
+use arrow::datatypes::SchemaRef;
 use datafusion_common::tree_node::TreeNodeRecursion;
-use datafusion_common::{
-    Result, ScalarValue, assert_or_internal_err, plan_err, project_schema,
-};
+use datafusion_common::{Result, assert_or_internal_err, plan_err, project_schema};
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::equivalence::project_orderings;
 use datafusion_physical_expr::projection::ProjectionExprs;
@@ -41,9 +39,10 @@ use datafusion_physical_plan::memory::MemoryStream;
 use datafusion_physical_plan::projection::{
     all_alias_free_columns, new_projections_for_columns,
 };
+use datafusion_physical_plan::values::evaluate_values;
 use datafusion_physical_plan::{
-    ColumnarValue, DisplayAs, DisplayFormatType, Partitioning, PhysicalExpr,
-    SendableRecordBatchStream, Statistics, common,
+    DisplayAs, DisplayFormatType, Partitioning, PhysicalExpr, SendableRecordBatchStream,
+    Statistics, common,
 };
 
 use async_trait::async_trait;
@@ -314,47 +313,7 @@ impl MemorySourceConfig {
             return plan_err!("Values list cannot be empty");
         }
 
-        let n_row = data.len();
-        let n_col = schema.fields().len();
-
-        // We have this single row batch as a placeholder to satisfy evaluation argument
-        // and generate a single output row
-        let placeholder_schema = Arc::new(Schema::empty());
-        let placeholder_batch = RecordBatch::try_new_with_options(
-            Arc::clone(&placeholder_schema),
-            vec![],
-            &RecordBatchOptions::new().with_row_count(Some(1)),
-        )?;
-
-        // Evaluate each column
-        let arrays = (0..n_col)
-            .map(|j| {
-                (0..n_row)
-                    .map(|i| {
-                        let expr = &data[i][j];
-                        let result = expr.evaluate(&placeholder_batch)?;
-
-                        match result {
-                            ColumnarValue::Scalar(scalar) => Ok(scalar),
-                            ColumnarValue::Array(array) if array.len() == 1 => {
-                                ScalarValue::try_from_array(&array, 0)
-                            }
-                            ColumnarValue::Array(_) => {
-                                plan_err!("Cannot have array values in a values list")
-                            }
-                        }
-                    })
-                    .collect::<Result<Vec<_>>>()
-                    .and_then(ScalarValue::iter_to_array)
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        let batch = RecordBatch::try_new_with_options(
-            Arc::clone(&schema),
-            arrays,
-            &RecordBatchOptions::new().with_row_count(Some(n_row)),
-        )?;
-
+        let batch = evaluate_values(&schema, &data)?;
         let partitions = vec![batch];
         Self::try_new_from_batches(Arc::clone(&schema), partitions)
     }
@@ -863,9 +822,9 @@ mod tests {
     use crate::tests::{aggr_test_schema, make_partition};
 
     use arrow::array::{ArrayRef, Int32Array, Int64Array, StringArray};
-    use arrow::datatypes::{DataType, Field};
-    use datafusion_common::assert_batches_eq;
+    use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::stats::{ColumnStatistics, Precision};
+    use datafusion_common::{ScalarValue, assert_batches_eq};
     use datafusion_physical_expr::PhysicalSortExpr;
     use datafusion_physical_plan::expressions::lit;
 
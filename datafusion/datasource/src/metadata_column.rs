@@ -0,0 +1,133 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hidden metadata columns of file based tables
+
+use arrow::datatypes::{DataType, Field, TimeUnit};
+use datafusion_common::ScalarValue;
+
+use crate::PartitionedFile;
+
+/// A hidden metadata column of a file based table, whose value is derived
+/// from the file each row is read from.
+///
+/// Like partition columns, the value of a metadata column is appended to the
+/// [`PartitionedFile::partition_values`] after the values of the partition
+/// columns, and the scan materializes it only when the column is part of the
+/// projection. The value of [`Self::RowNumber`], which differs for each row,
+/// is a placeholder replaced by the positions of the rows when the file is
+/// read, see [`FileRowNumberColumn`].
+///
+/// See [`datafusion_common::metadata_column`] for how metadata columns are
+/// handled in logical plans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileMetadataColumn {
+    /// `_file`: the path of the file
+    Location,
+    /// `_size`: the size of the file in bytes
+    Size,
+    /// `_last_modified`: the time the file was last modified
+    LastModified,
+    /// `_file_row_number`: the position of the row in the file, starting at 0
+    RowNumber,
+}
+
+impl FileMetadataColumn {
+    /// All the metadata columns, in their default order
+    pub const ALL: [FileMetadataColumn; 4] = [
+        Self::Location,
+        Self::Size,
+        Self::LastModified,
+        Self::RowNumber,
+    ];
+
+    /// The name of the column
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Location => "_file",
+            Self::Size => "_size",
+            Self::LastModified => "_last_modified",
+            Self::RowNumber => "_file_row_number",
+        }
+    }
+
+    /// The field of the column
+    pub fn field(&self) -> Field {
+        let data_type = match self {
+            Self::Location => DataType::Utf8,
+            Self::Size | Self::RowNumber => DataType::UInt64,
+            Self::LastModified => {
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+            }
+        };
+        Field::new(self.name(), data_type, false)
+    }
+
+    /// The value of the column for the rows of `file`, which is null for
+    /// [`Self::RowNumber`]
+    pub fn value(&self, file: &PartitionedFile) -> ScalarValue {
+        let meta = &file.object_meta;
+        match self {
+            Self::Location => ScalarValue::Utf8(Some(meta.location.to_string())),
+            Self::Size => ScalarValue::UInt64(Some(meta.size)),
+            Self::LastModified => ScalarValue::TimestampMillisecond(
+                Some(meta.last_modified.timestamp_millis()),
+                Some("UTC".into()),
+            ),
+            Self::RowNumber => ScalarValue::UInt64(None),
+        }
+    }
+}
+
+/// Marks the value of the [`FileMetadataColumn::RowNumber`] column among the
+/// [`PartitionedFile::partition_values`] of a file, as an extension of the
+/// file (see [`PartitionedFile::with_extension`]).
+///
+/// The scan replaces the placeholder value by the positions of the rows in
+/// the file. The positions are only known when a file is read from its start
+/// and all its rows are decoded, so:
+///
+/// * Scans reading the row numbers are not split into byte ranges
+/// * Formats skipping rows while decoding, such as Parquet, do not support
+///   reading the row numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileRowNumberColumn {
+    /// The index of the column in the partition values
+    pub partition_value_index: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_metadata_column_values() {
+        let file = PartitionedFile::new("bucket/year=2024/data.parquet", 1024);
+        for column in FileMetadataColumn::ALL {
+            assert_eq!(&column.value(&file).data_type(), column.field().data_type());
+        }
+        assert!(FileMetadataColumn::RowNumber.value(&file).is_null());
+        assert_eq!(
+            FileMetadataColumn::Location.value(&file),
+            ScalarValue::from("bucket/year=2024/data.parquet")
+        );
+        assert_eq!(
+            FileMetadataColumn::Size.value(&file),
+            ScalarValue::UInt64(Some(1024))
+        );
+    }
+}
@@ -29,6 +29,8 @@
 //! to get the list of files to process.
 
 pub mod decoder;
+pub mod deletion_vector;
+pub mod dictionary_unifier;
 pub mod display;
 pub mod file;
 pub mod file_compression_type;
@@ -38,13 +40,16 @@ pub mod file_scan_config;
 pub mod file_sink_config;
 pub mod file_stream;
 pub mod memory;
+pub mod metadata_column;
 pub mod morsel;
 pub mod projection;
+pub mod retry;
 pub mod schema_adapter;
 pub mod sink;
 pub mod source;
 mod statistics;
 pub mod table_schema;
+pub mod transform;
 
 #[cfg(test)]
 pub mod test_util;
@@ -53,6 +58,7 @@ pub mod url;
 pub mod write;
 pub use self::file::as_file_source;
 pub use self::url::ListingTableUrl;
+use crate::deletion_vector::DeletionVector;
 use crate::file_groups::FileGroup;
 use chrono::TimeZone;
 use datafusion_common::stats::Precision;
@@ -284,6 +290,18 @@ impl PartitionedFile {
         self.extensions.get::<T>()
     }
 
+    /// Attach the [`DeletionVector`] of this file, whose rows are then
+    /// skipped when the file is scanned.
+    pub fn with_deletion_vector(mut self, deletion_vector: Arc<DeletionVector>) -> Self {
+        self.extensions.insert_arc(deletion_vector);
+        self
+    }
+
+    /// The [`DeletionVector`] of this file, if one is attached.
+    pub fn deletion_vector(&self) -> Option<Arc<DeletionVector>> {
+        self.extensions.get_arc::<DeletionVector>()
+    }
+
     /// Attach a type-erased extension to this file.
     ///
     /// Kept as a backwards-compatible shim; prefer [`Self::with_extension`]
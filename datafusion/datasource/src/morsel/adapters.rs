@@ -16,10 +16,11 @@
 // under the License.
 
 use crate::PartitionedFile;
+use crate::deletion_vector::filter_deleted_rows;
 use crate::file_stream::FileOpener;
 use crate::morsel::{Morsel, MorselPlan, MorselPlanner, Morselizer};
 use arrow::array::RecordBatch;
-use datafusion_common::Result;
+use datafusion_common::{Result, not_impl_err};
 use futures::FutureExt;
 use futures::stream::BoxStream;
 use std::fmt::Debug;
@@ -29,6 +30,11 @@ use std::sync::Arc;
 ///
 /// This preserves backwards compatibility for file formats that have not yet
 /// implemented a native [`Morselizer`].
+///
+/// Rows deleted by the [`DeletionVector`] of a file are filtered out of the
+/// batches produced by the [`FileOpener`].
+///
+/// [`DeletionVector`]: crate::deletion_vector::DeletionVector
 pub struct FileOpenerMorselizer {
     file_opener: Arc<dyn FileOpener>,
 }
@@ -90,8 +96,18 @@ impl MorselPlanner for FileOpenFutureMorselPlanner {
     fn plan(self: Box<Self>) -> Result<Option<MorselPlan>> {
         match *self {
             Self::Unopened { file_opener, file } => {
+                let deletion_vector = file.deletion_vector();
+                if deletion_vector.is_some() && file.range.is_some() {
+                    return not_impl_err!(
+                        "Deletion vectors are not supported when reading a byte range of {}",
+                        file.object_meta.location
+                    );
+                }
                 let io_future = async move {
-                    let stream = file_opener.open(*file)?.await?;
+                    let mut stream = file_opener.open(*file)?.await?;
+                    if let Some(deletion_vector) = deletion_vector {
+                        stream = filter_deleted_rows(stream, deletion_vector);
+                    }
                     Ok(Box::new(Self::ReadyStream(stream)) as Box<dyn MorselPlanner>)
                 }
                 .boxed();
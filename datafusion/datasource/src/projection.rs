@@ -17,9 +17,10 @@
 
 use std::sync::Arc;
 
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::array::{RecordBatch, UInt64Array};
+use arrow::datatypes::{Schema, SchemaBuilder, SchemaRef};
 use datafusion_common::{
-    Result, ScalarValue,
+    Result, ScalarValue, not_impl_err,
     tree_node::{Transformed, TransformedResult, TreeNode},
 };
 use datafusion_physical_expr::{
@@ -32,11 +33,13 @@ use itertools::Itertools;
 use crate::{
     PartitionedFile, TableSchema,
     file_stream::{FileOpenFuture, FileOpener},
+    metadata_column::{FileMetadataColumn, FileRowNumberColumn},
 };
 
 /// A file opener that handles applying a projection on top of an inner opener.
 ///
-/// This includes handling partition columns.
+/// This includes handling partition columns, and the row numbers of the file
+/// (see [`FileRowNumberColumn`]), which are counted from the batches read.
 ///
 /// Any projection pushed down will be split up into:
 /// - Simple column indices / column selection
@@ -69,25 +72,72 @@ impl ProjectionOpener {
 impl FileOpener for ProjectionOpener {
     fn open(&self, partitioned_file: PartitionedFile) -> Result<FileOpenFuture> {
         let partition_values = partitioned_file.partition_values.clone();
+        // The row numbers of the file, if they are projected, are appended to
+        // the batches read from the file instead of being substituted
+        let row_numbers = partitioned_file
+            .extension::<FileRowNumberColumn>()
+            .and_then(|row_numbers| {
+                self.partition_columns.iter().find(|pci| {
+                    pci.in_partition_values == row_numbers.partition_value_index
+                })
+            })
+            .copied();
+        if row_numbers.is_some() && partitioned_file.range.is_some() {
+            return not_impl_err!(
+                "Row numbers are not supported when reading a byte range of {}",
+                partitioned_file.object_meta.location
+            );
+        }
+        let partition_columns = self
+            .partition_columns
+            .iter()
+            .filter(|pci| {
+                row_numbers.is_none_or(|row_numbers| {
+                    pci.in_remainder_projection != row_numbers.in_remainder_projection
+                })
+            })
+            .copied()
+            .collect_vec();
         // Modify any references to partition columns in the projection expressions
         // and substitute them with literal values from PartitionedFile.partition_values
-        let projection = if self.partition_columns.is_empty() {
+        let mut projection = if partition_columns.is_empty() {
             self.projection.clone()
         } else {
             inject_partition_columns_into_projection(
                 &self.projection,
-                &self.partition_columns,
+                &partition_columns,
                 partition_values,
             )
         };
-        let projector = projection.make_projector(&self.input_schema)?;
+        let mut input_schema = Arc::clone(&self.input_schema);
+        if let Some(row_numbers) = row_numbers {
+            projection = inject_row_numbers_into_projection(
+                &projection,
+                row_numbers.in_remainder_projection,
+                input_schema.fields().len(),
+            );
+            let mut builder = SchemaBuilder::from(input_schema.fields());
+            builder.push(FileMetadataColumn::RowNumber.field());
+            input_schema = Arc::new(builder.finish());
+        }
+        let projector = projection.make_projector(&input_schema)?;
 
         let inner = self.inner.open(partitioned_file)?;
 
         Ok(async move {
             let stream = inner.await?;
+            let mut first_row = 0;
             let stream = stream.map(move |batch| {
-                let batch = batch?;
+                let mut batch = batch?;
+                if row_numbers.is_some() {
+                    let num_rows = batch.num_rows() as u64;
+                    let mut columns = batch.columns().to_vec();
+                    columns.push(Arc::new(UInt64Array::from_iter_values(
+                        first_row..first_row + num_rows,
+                    )));
+                    batch = RecordBatch::try_new(Arc::clone(&input_schema), columns)?;
+                    first_row += num_rows;
+                }
                 let batch = projector.project_batch(&batch)?;
                 Ok(batch)
             });
@@ -143,6 +193,37 @@ fn inject_partition_columns_into_projection(
     ProjectionExprs::new(projections)
 }
 
+/// Replaces the references to the row number column at `in_remainder_projection`
+/// by references to the row numbers appended to the file columns at `index`
+fn inject_row_numbers_into_projection(
+    projection: &ProjectionExprs,
+    in_remainder_projection: usize,
+    index: usize,
+) -> ProjectionExprs {
+    let projections = projection
+        .iter()
+        .map(|projection| {
+            let expr = Arc::clone(&projection.expr)
+                .transform(|expr| {
+                    if let Some(column) = expr.downcast_ref::<Column>()
+                        && column.index() == in_remainder_projection
+                    {
+                        return Ok(Transformed::yes(Arc::new(Column::new(
+                            column.name(),
+                            index,
+                        ))
+                            as Arc<dyn datafusion_physical_plan::PhysicalExpr>));
+                    }
+                    Ok(Transformed::no(expr))
+                })
+                .data()
+                .expect("infallible transform");
+            ProjectionExpr::new(expr, projection.alias.clone())
+        })
+        .collect_vec();
+    ProjectionExprs::new(projections)
+}
+
 /// At a high level the goal of SplitProjection is to take a ProjectionExprs meant to be applied to the table schema
 /// and split that into:
 /// - The projection indices into the file schema (file_indices)
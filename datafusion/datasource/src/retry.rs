@@ -0,0 +1,587 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`RetryingObjectStore`] retries transient errors while reading objects
+
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use datafusion_physical_plan::metrics::Count;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use object_store::path::Path;
+use object_store::{
+    CopyOptions, GetOptions, GetRange, GetResult, GetResultPayload, ListResult,
+    MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOptions, PutOptions,
+    PutPayload, PutResult,
+};
+
+/// How [`RetryingObjectStore`] retries transient errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of consecutive retries of a failed request before
+    /// the error is returned. The count is reset whenever a resumed stream
+    /// makes progress.
+    pub max_retries: usize,
+    /// The delay before the first retry, doubled for every following retry
+    pub initial_backoff: Duration,
+    /// The upper bound of the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Set the maximum number of consecutive retries
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the upper bound of the delay between retries
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// The delay before the `attempt`-th retry, starting at 0
+    fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Returns true if `err` should be retried after `attempt` retries
+    fn should_retry(&self, err: &object_store::Error, attempt: usize) -> bool {
+        attempt < self.max_retries && is_transient(err)
+    }
+}
+
+/// Returns true if `err` may succeed when retried.
+///
+/// Network and server errors are reported by the object store
+/// implementations as [`object_store::Error::Generic`]; all other variants
+/// describe a permanent condition such as a missing object.
+fn is_transient(err: &object_store::Error) -> bool {
+    matches!(err, object_store::Error::Generic { .. })
+}
+
+/// Counters of the retries performed by a [`RetryingObjectStore`]
+///
+/// Cloning the metrics shares the underlying counters.
+#[derive(Debug, Clone, Default)]
+pub struct RetryMetrics {
+    /// Number of requests retried after a transient error
+    pub retries: Count,
+    /// Number of object streams resumed after failing part way through
+    pub resumed_streams: Count,
+}
+
+/// An [`ObjectStore`] that retries reads failing with a transient error.
+///
+/// Failed `get` requests are retried according to the [`RetryPolicy`]. If
+/// the byte stream of an object fails part way through, only the remaining
+/// byte range is requested again, pinned to the version of the object that
+/// was being read, and the stream continues where it stopped. Writes, lists
+/// and deletes are passed through unchanged.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion_datasource::retry::{RetryPolicy, RetryingObjectStore};
+/// # use object_store::memory::InMemory;
+/// let store = RetryingObjectStore::new(Arc::new(InMemory::new()))
+///     .with_policy(RetryPolicy::default().with_max_retries(5));
+/// let metrics = store.metrics().clone();
+/// assert_eq!(metrics.retries.value(), 0);
+/// ```
+pub struct RetryingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    policy: RetryPolicy,
+    metrics: RetryMetrics,
+}
+
+impl RetryingObjectStore {
+    /// Create a store retrying reads from `inner` with the default policy
+    pub fn new(inner: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::default(),
+            metrics: RetryMetrics::default(),
+        }
+    }
+
+    /// Set the [`RetryPolicy`]
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Report retries to `metrics` instead of counters owned by this store
+    pub fn with_metrics(mut self, metrics: RetryMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// The retry policy
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// The retry counters
+    pub fn metrics(&self) -> &RetryMetrics {
+        &self.metrics
+    }
+
+    /// The wrapped store
+    pub fn inner(&self) -> &Arc<dyn ObjectStore> {
+        &self.inner
+    }
+}
+
+impl Debug for RetryingObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryingObjectStore")
+            .field("inner", &self.inner)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl Display for RetryingObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RetryingObjectStore({})", self.inner)
+    }
+}
+
+/// Issue `get_opts` against `store`, retrying transient errors
+async fn get_with_retry(
+    store: &Arc<dyn ObjectStore>,
+    policy: &RetryPolicy,
+    metrics: &RetryMetrics,
+    location: &Path,
+    options: GetOptions,
+) -> object_store::Result<GetResult> {
+    let mut attempt = 0;
+    loop {
+        match store.get_opts(location, options.clone()).await {
+            Err(e) if policy.should_retry(&e, attempt) => {
+                tokio::time::sleep(policy.backoff(attempt)).await;
+                metrics.retries.add(1);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// State of a byte stream that is resumed after transient errors
+struct ResumableStream {
+    store: Arc<dyn ObjectStore>,
+    policy: RetryPolicy,
+    metrics: RetryMetrics,
+    location: Path,
+    /// Options to request the remainder of the object, pinned to its version
+    options: GetOptions,
+    stream: BoxStream<'static, object_store::Result<Bytes>>,
+    /// The remaining byte range of the object
+    remaining: Range<u64>,
+    /// Retries since the stream last made progress
+    attempt: usize,
+    done: bool,
+}
+
+impl ResumableStream {
+    fn into_stream(self) -> BoxStream<'static, object_store::Result<Bytes>> {
+        futures::stream::unfold(self, |mut state| async move {
+            if state.done {
+                return None;
+            }
+            loop {
+                match state.stream.next().await {
+                    Some(Ok(bytes)) => {
+                        state.remaining.start += bytes.len() as u64;
+                        state.attempt = 0;
+                        return Some((Ok(bytes), state));
+                    }
+                    None => return None,
+                    Some(Err(e))
+                        if !state.remaining.is_empty()
+                            && state.policy.should_retry(&e, state.attempt) =>
+                    {
+                        if let Err(e) = state.resume().await {
+                            state.done = true;
+                            return Some((Err(e), state));
+                        }
+                    }
+                    Some(Err(e)) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Request the remaining byte range and continue streaming from it
+    async fn resume(&mut self) -> object_store::Result<()> {
+        tokio::time::sleep(self.policy.backoff(self.attempt)).await;
+        self.metrics.retries.add(1);
+        self.attempt += 1;
+
+        let mut options = self.options.clone();
+        options.range = Some(GetRange::Bounded(self.remaining.clone()));
+        let result = get_with_retry(
+            &self.store,
+            &self.policy,
+            &self.metrics,
+            &self.location,
+            options,
+        )
+        .await?;
+        self.metrics.resumed_streams.add(1);
+        self.stream = result.into_stream();
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ObjectStore for RetryingObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let result = get_with_retry(
+            &self.inner,
+            &self.policy,
+            &self.metrics,
+            location,
+            options.clone(),
+        )
+        .await?;
+        let GetResult {
+            payload,
+            meta,
+            range,
+            attributes,
+        } = result;
+        let payload = match payload {
+            GetResultPayload::Stream(stream) => {
+                // Pin the remainder to the version being read, so a resumed
+                // stream never mixes bytes of different versions
+                let options = GetOptions {
+                    if_match: meta.e_tag.clone().or(options.if_match),
+                    version: meta.version.clone().or(options.version),
+                    ..options
+                };
+                let stream = ResumableStream {
+                    store: Arc::clone(&self.inner),
+                    policy: self.policy,
+                    metrics: self.metrics.clone(),
+                    location: location.clone(),
+                    options,
+                    stream,
+                    remaining: range.clone(),
+                    attempt: 0,
+                    done: false,
+                };
+                GetResultPayload::Stream(stream.into_stream())
+            }
+            payload @ GetResultPayload::File(..) => payload,
+        };
+        Ok(GetResult {
+            payload,
+            meta,
+            range,
+            attributes,
+        })
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<u64>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let mut attempt = 0;
+        loop {
+            match self.inner.get_ranges(location, ranges).await {
+                Err(e) if self.policy.should_retry(&e, attempt) => {
+                    tokio::time::sleep(self.policy.backoff(attempt)).await;
+                    self.metrics.retries.add(1);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn delete_stream(
+        &self,
+        locations: BoxStream<'static, object_store::Result<Path>>,
+    ) -> BoxStream<'static, object_store::Result<Path>> {
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy_opts(
+        &self,
+        from: &Path,
+        to: &Path,
+        options: CopyOptions,
+    ) -> object_store::Result<()> {
+        self.inner.copy_opts(from, to, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::TryStreamExt;
+    use object_store::memory::InMemory;
+    use object_store::{ObjectStoreExt, PutPayload};
+
+    /// Serves objects from memory in chunks of 4 bytes, failing the first
+    /// `failures` streams after `fail_after_chunks` chunks
+    #[derive(Debug)]
+    struct FlakyStore {
+        inner: InMemory,
+        fail_after_chunks: usize,
+        failures: AtomicUsize,
+    }
+
+    impl Display for FlakyStore {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "FlakyStore")
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ObjectStore for FlakyStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOptions,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            let result = self.inner.get_opts(location, options).await?;
+            let meta = result.meta.clone();
+            let range = result.range.clone();
+            let attributes = result.attributes.clone();
+            let bytes = result.bytes().await?;
+            let fail = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            let mut chunks = bytes
+                .chunks(4)
+                .map(|chunk| bytes.slice_ref(chunk))
+                .map(Ok)
+                .collect::<Vec<_>>();
+            if fail {
+                chunks.truncate(self.fail_after_chunks);
+                chunks.push(Err(object_store::Error::Generic {
+                    store: "FlakyStore",
+                    source: "connection reset".into(),
+                }));
+            }
+            let stream = futures::stream::iter(chunks);
+            Ok(GetResult {
+                payload: GetResultPayload::Stream(stream.boxed()),
+                meta,
+                range,
+                attributes,
+            })
+        }
+
+        fn delete_stream(
+            &self,
+            locations: BoxStream<'static, object_store::Result<Path>>,
+        ) -> BoxStream<'static, object_store::Result<Path>> {
+            self.inner.delete_stream(locations)
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy_opts(
+            &self,
+            from: &Path,
+            to: &Path,
+            options: CopyOptions,
+        ) -> object_store::Result<()> {
+            self.inner.copy_opts(from, to, options).await
+        }
+    }
+
+    async fn flaky_store(
+        fail_after_chunks: usize,
+        failures: usize,
+    ) -> (Arc<dyn ObjectStore>, Path) {
+        let store = FlakyStore {
+            inner: InMemory::new(),
+            fail_after_chunks,
+            failures: AtomicUsize::new(failures),
+        };
+        let location = Path::from("data.bin");
+        store
+            .put(&location, PutPayload::from_static(b"0123456789abcdef"))
+            .await
+            .unwrap();
+        (Arc::new(store), location)
+    }
+
+    fn no_backoff() -> RetryPolicy {
+        RetryPolicy::default().with_initial_backoff(Duration::ZERO)
+    }
+
+    #[tokio::test]
+    async fn resumes_failed_stream() {
+        let (inner, location) = flaky_store(1, 2).await;
+        let store = RetryingObjectStore::new(inner).with_policy(no_backoff());
+
+        let bytes = store.get(&location).await.unwrap().bytes().await.unwrap();
+        assert_eq!(bytes.as_ref(), b"0123456789abcdef");
+        assert_eq!(store.metrics().retries.value(), 2);
+        assert_eq!(store.metrics().resumed_streams.value(), 2);
+    }
+
+    #[tokio::test]
+    async fn fails_after_max_retries() {
+        let (inner, location) = flaky_store(0, usize::MAX).await;
+        let store =
+            RetryingObjectStore::new(inner).with_policy(no_backoff().with_max_retries(2));
+
+        let err = store
+            .get(&location)
+            .await
+            .unwrap()
+            .into_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("connection reset"), "{err}");
+        assert_eq!(store.metrics().retries.value(), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_errors() {
+        let (inner, _) = flaky_store(0, 0).await;
+        let store = RetryingObjectStore::new(inner).with_policy(no_backoff());
+
+        let err = store.get(&Path::from("missing")).await.unwrap_err();
+        assert!(matches!(err, object_store::Error::NotFound { .. }), "{err}");
+        assert_eq!(store.metrics().retries.value(), 0);
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy::default()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(250));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(250));
+        assert_eq!(policy.backoff(40), Duration::from_millis(250));
+    }
+}
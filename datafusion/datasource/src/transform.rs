@@ -0,0 +1,534 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`TransformingObjectStore`] transforms the bytes of files as they are read,
+//! for example to decrypt them, before they are decoded by a [`FileFormat`]
+//!
+//! [`FileFormat`]: crate::file_format::FileFormat
+
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use datafusion_common::Result;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{
+    CopyOptions, GetOptions, GetRange, GetResult, GetResultPayload, ListResult,
+    MultipartUpload, ObjectMeta, ObjectStore, PutMultipartOptions, PutOptions,
+    PutPayload, PutResult,
+};
+use parking_lot::Mutex;
+
+/// Transforms the stored bytes of a file into the bytes decoded by a
+/// [`FileFormat`], such as decrypting an envelope, decompressing a custom
+/// compression or stripping a proprietary header.
+///
+/// Files are transformed as a whole by a [`TransformingObjectStore`], so the
+/// existing file formats, including those reading byte ranges such as
+/// Parquet, read the transformed files without changes.
+///
+/// [`FileFormat`]: crate::file_format::FileFormat
+pub trait FileTransform: Debug + Send + Sync {
+    /// Returns true if the file at `location` is transformed. Other files are
+    /// read unchanged. Defaults to all files.
+    fn applies_to(&self, _location: &Path) -> bool {
+        true
+    }
+
+    /// Returns the size of the transformed file described by `meta`, if it
+    /// is known without reading the file, for example when a header of a
+    /// fixed size is stripped. Otherwise files are transformed when they are
+    /// listed to report their sizes.
+    fn transformed_size(&self, _meta: &ObjectMeta) -> Option<u64> {
+        None
+    }
+
+    /// Transform the stored `bytes` of the file at `location`
+    fn transform(&self, location: &Path, bytes: Bytes) -> Result<Bytes>;
+}
+
+/// A shared reference to a [`FileTransform`]
+pub type FileTransformRef = Arc<dyn FileTransform>;
+
+/// The default number of transformed files kept in memory by a
+/// [`TransformingObjectStore`]
+pub const DEFAULT_CACHED_FILES: usize = 4;
+
+/// An [`ObjectStore`] whose files are transformed by a [`FileTransform`] when
+/// they are read.
+///
+/// Reads of a transformed file, including reads of byte ranges and `head`
+/// requests, see the transformed bytes, and listings report the sizes of the
+/// transformed files. The most recently read transformed files are kept in
+/// memory, see [`Self::with_cached_files`], so that formats reading several
+/// ranges of a file transform it once. Writes and deletes are passed through
+/// unchanged.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use bytes::Bytes;
+/// # use datafusion_common::Result;
+/// # use datafusion_datasource::transform::{FileTransform, TransformingObjectStore};
+/// # use object_store::memory::InMemory;
+/// # use object_store::path::Path;
+/// /// Strips a 4 byte header from every file
+/// #[derive(Debug)]
+/// struct StripHeader;
+///
+/// impl FileTransform for StripHeader {
+///     fn transform(&self, _location: &Path, bytes: Bytes) -> Result<Bytes> {
+///         Ok(bytes.slice(4.min(bytes.len())..))
+///     }
+/// }
+///
+/// let store = TransformingObjectStore::new(Arc::new(InMemory::new()), Arc::new(StripHeader));
+/// ```
+pub struct TransformingObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    transform: FileTransformRef,
+    cache: Arc<TransformedFiles>,
+}
+
+impl TransformingObjectStore {
+    /// Create a store transforming the files of `inner` with `transform`
+    pub fn new(inner: Arc<dyn ObjectStore>, transform: FileTransformRef) -> Self {
+        Self {
+            inner,
+            transform,
+            cache: Arc::new(TransformedFiles::new(DEFAULT_CACHED_FILES)),
+        }
+    }
+
+    /// Set the number of transformed files kept in memory, which defaults to
+    /// [`DEFAULT_CACHED_FILES`]. 0 transforms a file for every read.
+    pub fn with_cached_files(mut self, cached_files: usize) -> Self {
+        self.cache = Arc::new(TransformedFiles::new(cached_files));
+        self
+    }
+
+    /// The transform of the files
+    pub fn transform(&self) -> &FileTransformRef {
+        &self.transform
+    }
+
+    /// The wrapped store
+    pub fn inner(&self) -> &Arc<dyn ObjectStore> {
+        &self.inner
+    }
+
+    /// A stream reporting the sizes of the transformed files in `metas`
+    fn transformed_metas(
+        &self,
+        metas: BoxStream<'static, object_store::Result<ObjectMeta>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let inner = Arc::clone(&self.inner);
+        let transform = Arc::clone(&self.transform);
+        let cache = Arc::clone(&self.cache);
+        metas
+            .and_then(move |meta| {
+                let inner = Arc::clone(&inner);
+                let transform = Arc::clone(&transform);
+                let cache = Arc::clone(&cache);
+                async move { transformed_meta(&inner, &transform, &cache, meta).await }
+            })
+            .boxed()
+    }
+}
+
+impl Debug for TransformingObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransformingObjectStore")
+            .field("inner", &self.inner)
+            .field("transform", &self.transform)
+            .finish()
+    }
+}
+
+impl Display for TransformingObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TransformingObjectStore({})", self.inner)
+    }
+}
+
+/// The most recently read transformed files, most recent last
+#[derive(Debug)]
+struct TransformedFiles {
+    capacity: usize,
+    files: Mutex<VecDeque<(ObjectMeta, Bytes)>>,
+}
+
+impl TransformedFiles {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            files: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// The transformed bytes of the stored file described by `meta`, if the
+    /// same version of the file was transformed recently
+    fn get(&self, meta: &ObjectMeta) -> Option<Bytes> {
+        let mut files = self.files.lock();
+        let index = files.iter().position(|(cached, _)| cached == meta)?;
+        let file = files.remove(index)?;
+        let bytes = file.1.clone();
+        files.push_back(file);
+        Some(bytes)
+    }
+
+    fn insert(&self, meta: ObjectMeta, bytes: Bytes) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut files = self.files.lock();
+        files.retain(|(cached, _)| cached.location != meta.location);
+        if files.len() == self.capacity {
+            files.pop_front();
+        }
+        files.push_back((meta, bytes));
+    }
+}
+
+/// Wraps an error of a [`FileTransform`]
+fn transform_error(err: datafusion_common::DataFusionError) -> object_store::Error {
+    object_store::Error::Generic {
+        store: "TransformingObjectStore",
+        source: Box::new(err),
+    }
+}
+
+/// Returns the transformed bytes of the stored file described by `meta`,
+/// pinned to its version
+async fn transformed_bytes(
+    inner: &Arc<dyn ObjectStore>,
+    transform: &FileTransformRef,
+    cache: &TransformedFiles,
+    meta: &ObjectMeta,
+) -> object_store::Result<Bytes> {
+    if let Some(bytes) = cache.get(meta) {
+        return Ok(bytes);
+    }
+    let options = GetOptions {
+        if_match: meta.e_tag.clone(),
+        version: meta.version.clone(),
+        ..Default::default()
+    };
+    let stored = inner
+        .get_opts(&meta.location, options)
+        .await?
+        .bytes()
+        .await?;
+    let bytes = transform
+        .transform(&meta.location, stored)
+        .map_err(transform_error)?;
+    cache.insert(meta.clone(), bytes.clone());
+    Ok(bytes)
+}
+
+/// Replaces the size of the stored file described by `meta` by the size of
+/// the transformed file
+async fn transformed_meta(
+    inner: &Arc<dyn ObjectStore>,
+    transform: &FileTransformRef,
+    cache: &TransformedFiles,
+    meta: ObjectMeta,
+) -> object_store::Result<ObjectMeta> {
+    if !transform.applies_to(&meta.location) {
+        return Ok(meta);
+    }
+    let size = match transform.transformed_size(&meta) {
+        Some(size) => size,
+        None => transformed_bytes(inner, transform, cache, &meta)
+            .await?
+            .len() as u64,
+    };
+    Ok(ObjectMeta { size, ..meta })
+}
+
+/// The byte range of a file of `len` bytes requested by `range`
+#[expect(clippy::result_large_err)] // returns the error of the ObjectStore API
+fn resolve_range(range: Option<&GetRange>, len: u64) -> object_store::Result<Range<u64>> {
+    let resolved = match range {
+        None => 0..len,
+        Some(GetRange::Bounded(range)) if range.start <= range.end => {
+            range.start.min(len)..range.end.min(len)
+        }
+        Some(GetRange::Offset(offset)) => (*offset).min(len)..len,
+        Some(GetRange::Suffix(suffix)) => len.saturating_sub(*suffix)..len,
+        Some(range) => {
+            return Err(object_store::Error::Generic {
+                store: "TransformingObjectStore",
+                source: format!("invalid range {range:?} of a file of {len} bytes")
+                    .into(),
+            });
+        }
+    };
+    if range.is_some() && len > 0 && resolved.start >= len {
+        return Err(object_store::Error::Generic {
+            store: "TransformingObjectStore",
+            source: format!(
+                "range starting at {} is beyond the end of a file of {len} bytes",
+                resolved.start
+            )
+            .into(),
+        });
+    }
+    Ok(resolved)
+}
+
+#[async_trait::async_trait]
+impl ObjectStore for TransformingObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        if !self.transform.applies_to(location) {
+            return self.inner.get_opts(location, options).await;
+        }
+
+        // Check the conditions of the request against the stored file, whose
+        // ranges are then read from the transformed file
+        let range = options.range.clone();
+        let head = options.head;
+        let head_options = GetOptions {
+            range: None,
+            head: true,
+            ..options
+        };
+        let stored = self.inner.get_opts(location, head_options).await?;
+        let stored_meta = stored.meta;
+        let bytes =
+            transformed_bytes(&self.inner, &self.transform, &self.cache, &stored_meta)
+                .await?;
+        let len = bytes.len() as u64;
+        let range = resolve_range(range.as_ref(), len)?;
+        let payload = if head {
+            futures::stream::empty().boxed()
+        } else {
+            let bytes = bytes.slice(range.start as usize..range.end as usize);
+            futures::stream::once(async move { Ok(bytes) }).boxed()
+        };
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(payload),
+            meta: ObjectMeta {
+                size: len,
+                ..stored_meta
+            },
+            range,
+            attributes: stored.attributes,
+        })
+    }
+
+    fn delete_stream(
+        &self,
+        locations: BoxStream<'static, object_store::Result<Path>>,
+    ) -> BoxStream<'static, object_store::Result<Path>> {
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.transformed_metas(self.inner.list(prefix))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.transformed_metas(self.inner.list_with_offset(prefix, offset))
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        let ListResult {
+            common_prefixes,
+            objects,
+        } = self.inner.list_with_delimiter(prefix).await?;
+        let objects = self
+            .transformed_metas(futures::stream::iter(objects.into_iter().map(Ok)).boxed())
+            .try_collect()
+            .await?;
+        Ok(ListResult {
+            common_prefixes,
+            objects,
+        })
+    }
+
+    async fn copy_opts(
+        &self,
+        from: &Path,
+        to: &Path,
+        options: CopyOptions,
+    ) -> object_store::Result<()> {
+        self.inner.copy_opts(from, to, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use datafusion_common::exec_err;
+    use object_store::memory::InMemory;
+    use object_store::{ObjectStoreExt, PutPayload};
+
+    /// Strips the `HDR!` header of the files ending with `.wrapped`, counting
+    /// the transformed files
+    #[derive(Debug, Default)]
+    struct StripHeader {
+        transformed: AtomicUsize,
+    }
+
+    impl FileTransform for StripHeader {
+        fn applies_to(&self, location: &Path) -> bool {
+            location.as_ref().ends_with(".wrapped")
+        }
+
+        fn transform(&self, location: &Path, bytes: Bytes) -> Result<Bytes> {
+            self.transformed.fetch_add(1, Ordering::SeqCst);
+            match bytes.strip_prefix(b"HDR!") {
+                Some(data) => Ok(bytes.slice_ref(data)),
+                None => exec_err!("{location} is missing its header"),
+            }
+        }
+    }
+
+    async fn store(
+        transform: Arc<StripHeader>,
+        cached_files: usize,
+    ) -> TransformingObjectStore {
+        let inner = InMemory::new();
+        for (location, data) in
+            [("a.wrapped", &b"HDR!0123456789"[..]), ("b.csv", b"plain")]
+        {
+            inner
+                .put(&Path::from(location), PutPayload::from_static(data))
+                .await
+                .unwrap();
+        }
+        TransformingObjectStore::new(Arc::new(inner), transform)
+            .with_cached_files(cached_files)
+    }
+
+    #[tokio::test]
+    async fn reads_transformed_files() {
+        let transform = Arc::new(StripHeader::default());
+        let store = store(Arc::clone(&transform), DEFAULT_CACHED_FILES).await;
+        let location = Path::from("a.wrapped");
+
+        let result = store.get(&location).await.unwrap();
+        assert_eq!(result.meta.size, 10);
+        assert_eq!(result.bytes().await.unwrap().as_ref(), b"0123456789");
+        let range = store.get_range(&location, 2..5).await.unwrap();
+        assert_eq!(range.as_ref(), b"234");
+        let suffix = store
+            .get_opts(
+                &location,
+                GetOptions {
+                    range: Some(GetRange::Suffix(3)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(suffix.range, 7..10);
+        assert_eq!(suffix.bytes().await.unwrap().as_ref(), b"789");
+        assert_eq!(store.head(&location).await.unwrap().size, 10);
+        // the file is transformed once
+        assert_eq!(transform.transformed.load(Ordering::SeqCst), 1);
+
+        // other files are read unchanged
+        let plain = store.get(&Path::from("b.csv")).await.unwrap();
+        assert_eq!(plain.bytes().await.unwrap().as_ref(), b"plain");
+    }
+
+    #[tokio::test]
+    async fn lists_sizes_of_transformed_files() {
+        let transform = Arc::new(StripHeader::default());
+        let store = store(transform, 0).await;
+        let mut metas = store.list(None).try_collect::<Vec<_>>().await.unwrap();
+        metas.sort_by(|a, b| a.location.cmp(&b.location));
+        let sizes = metas
+            .iter()
+            .map(|meta| (meta.location.as_ref(), meta.size))
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![("a.wrapped", 10), ("b.csv", 5)]);
+    }
+
+    #[tokio::test]
+    async fn reports_transform_errors() {
+        let transform = Arc::new(StripHeader::default());
+        let store = store(transform, DEFAULT_CACHED_FILES).await;
+        let location = Path::from("c.wrapped");
+        store
+            .put(&location, PutPayload::from_static(b"corrupt"))
+            .await
+            .unwrap();
+        let err = store.get(&location).await.unwrap_err();
+        assert!(err.to_string().contains("is missing its header"), "{err}");
+        // the sizes of the files can not be listed either
+        let err = store.list(None).try_collect::<Vec<_>>().await.unwrap_err();
+        assert!(err.to_string().contains("is missing its header"), "{err}");
+    }
+
+    #[test]
+    fn resolves_ranges() {
+        assert_eq!(resolve_range(None, 10).unwrap(), 0..10);
+        assert_eq!(
+            resolve_range(Some(&GetRange::Bounded(2..20)), 10).unwrap(),
+            2..10
+        );
+        assert_eq!(
+            resolve_range(Some(&GetRange::Offset(4)), 10).unwrap(),
+            4..10
+        );
+        assert_eq!(
+            resolve_range(Some(&GetRange::Suffix(20)), 10).unwrap(),
+            0..10
+        );
+        resolve_range(Some(&GetRange::Offset(10)), 10).unwrap_err();
+    }
+}
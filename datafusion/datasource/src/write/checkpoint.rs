@@ -0,0 +1,438 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CheckpointedFileSink`] for exactly-once writes of unbounded streams
+
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use super::{BatchSerializer, ObjectWriterBuilder};
+use crate::file_compression_type::FileCompressionType;
+use crate::sink::DataSink;
+
+use arrow::array::{Array, AsArray, RecordBatch};
+use arrow::datatypes::{DataType, SchemaRef, UInt64Type};
+use async_trait::async_trait;
+use datafusion_common::{DataFusionError, Result, exec_err, plan_err};
+use datafusion_execution::TaskContext;
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::{ObjectStore, ObjectStoreExt, PutMode, PutPayload};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Directory, relative to the sink path, of the manifests
+const MANIFEST_DIR: &str = "_manifest";
+
+/// Extension of the manifest files
+const MANIFEST_EXTENSION: &str = "manifest";
+
+/// A [`DataSink`] that writes a, possibly unbounded, stream to files and
+/// commits the files of each epoch atomically with a manifest.
+///
+/// The input is divided into epochs by a non-nullable `UInt64` epoch column
+/// whose values must be non-decreasing, for example the watermark window or
+/// the batch of source offsets a row belongs to. The rows of each epoch are
+/// written to a file named after the epoch and, once a row of a later epoch or
+/// the end of the input is reached, the epoch is committed by creating a
+/// manifest listing this file in the `_manifest` directory.
+///
+/// Creating the manifest is atomic, so a file is either committed or not part
+/// of the output at all, see [`Self::committed_files`]. The file of an epoch
+/// that was not committed is overwritten when the epoch is written again.
+///
+/// Rows of epochs that were already committed are skipped, so when the
+/// source replays its input from the offsets of the
+/// [last committed epoch](Self::last_committed_epoch) after a failure, each
+/// row is written exactly once.
+pub struct CheckpointedFileSink {
+    /// Object store the files are written to
+    object_store_url: ObjectStoreUrl,
+    /// Directory the files are written to
+    path: Path,
+    /// Schema of the input
+    schema: SchemaRef,
+    /// Index of the epoch column in `schema`
+    epoch_column: usize,
+    /// Serializer of the data files
+    serializer: Arc<dyn BatchSerializer>,
+    /// Extension of the data files
+    file_extension: String,
+}
+
+impl CheckpointedFileSink {
+    /// Create a sink writing files with `file_extension`, serialized by
+    /// `serializer`, to `path` in the object store at `object_store_url`.
+    ///
+    /// Returns an error if `epoch_column` is not a `UInt64` column of `schema`.
+    pub fn try_new(
+        object_store_url: ObjectStoreUrl,
+        path: Path,
+        schema: SchemaRef,
+        epoch_column: &str,
+        serializer: Arc<dyn BatchSerializer>,
+        file_extension: impl Into<String>,
+    ) -> Result<Self> {
+        let epoch_column_index = schema.index_of(epoch_column)?;
+        let data_type = schema.field(epoch_column_index).data_type();
+        if data_type != &DataType::UInt64 {
+            return plan_err!(
+                "Epoch column {epoch_column} must be of type UInt64, got {data_type}"
+            );
+        }
+        Ok(Self {
+            object_store_url,
+            path,
+            schema,
+            epoch_column: epoch_column_index,
+            serializer,
+            file_extension: file_extension.into(),
+        })
+    }
+
+    /// Returns the last epoch committed to `store`, if any
+    pub async fn last_committed_epoch(
+        &self,
+        store: &dyn ObjectStore,
+    ) -> Result<Option<u64>> {
+        Ok(self.committed_epochs(store).await?.into_iter().max())
+    }
+
+    /// Returns the files committed to `store`, in the order of their epochs
+    pub async fn committed_files(&self, store: &dyn ObjectStore) -> Result<Vec<Path>> {
+        let mut epochs = self.committed_epochs(store).await?;
+        epochs.sort_unstable();
+
+        let mut files = vec![];
+        for epoch in epochs {
+            let manifest = store.get(&self.manifest_path(epoch)).await?.bytes().await?;
+            let Ok(manifest) = std::str::from_utf8(&manifest) else {
+                return exec_err!("Manifest of epoch {epoch} is not valid UTF-8");
+            };
+            files.extend(manifest.lines().map(Path::from));
+        }
+        Ok(files)
+    }
+
+    async fn committed_epochs(&self, store: &dyn ObjectStore) -> Result<Vec<u64>> {
+        let manifests: Vec<_> = store
+            .list(Some(&self.path.clone().join(MANIFEST_DIR)))
+            .try_collect()
+            .await?;
+        Ok(manifests
+            .iter()
+            .filter_map(|meta| {
+                let name = meta.location.filename()?;
+                let epoch = name.strip_suffix(MANIFEST_EXTENSION)?.strip_suffix('.')?;
+                epoch.parse().ok()
+            })
+            .collect())
+    }
+
+    fn data_path(&self, epoch: u64) -> Path {
+        self.path
+            .clone()
+            .join(format!("epoch-{epoch:020}.{}", self.file_extension))
+    }
+
+    fn manifest_path(&self, epoch: u64) -> Path {
+        self.path
+            .clone()
+            .join(MANIFEST_DIR)
+            .join(format!("{epoch:020}.{MANIFEST_EXTENSION}"))
+    }
+
+    fn start_epoch(
+        &self,
+        epoch: u64,
+        store: &Arc<dyn ObjectStore>,
+    ) -> Result<EpochWriter> {
+        let location = self.data_path(epoch);
+        let writer = ObjectWriterBuilder::new(
+            FileCompressionType::UNCOMPRESSED,
+            &location,
+            Arc::clone(store),
+        )
+        .build()?;
+        Ok(EpochWriter {
+            epoch,
+            location,
+            writer,
+            initial: true,
+        })
+    }
+
+    async fn commit_epoch(
+        &self,
+        mut epoch_writer: EpochWriter,
+        store: &dyn ObjectStore,
+    ) -> Result<()> {
+        epoch_writer.writer.shutdown().await?;
+
+        let epoch = epoch_writer.epoch;
+        let manifest = PutPayload::from(format!("{}\n", epoch_writer.location));
+        match store
+            .put_opts(&self.manifest_path(epoch), manifest, PutMode::Create.into())
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(object_store::Error::AlreadyExists { .. }) => {
+                exec_err!("Epoch {epoch} was already committed by another writer")
+            }
+            Err(e) => Err(DataFusionError::ObjectStore(Box::new(e))),
+        }
+    }
+}
+
+impl Debug for CheckpointedFileSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckpointedFileSink")
+            .field("object_store_url", &self.object_store_url)
+            .field("path", &self.path)
+            .field("epoch_column", &self.epoch_column)
+            .field("file_extension", &self.file_extension)
+            .finish()
+    }
+}
+
+impl DisplayAs for CheckpointedFileSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let epoch_column = self.schema.field(self.epoch_column).name();
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "CheckpointedFileSink(path={}{}, epoch_column={epoch_column})",
+                    self.object_store_url, self.path
+                )
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "format: {}", self.file_extension)?;
+                writeln!(f, "path={}{}", self.object_store_url, self.path)?;
+                write!(f, "epoch_column={epoch_column}")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for CheckpointedFileSink {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        mut data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let store = context.runtime_env().object_store(&self.object_store_url)?;
+        let last_committed = self.last_committed_epoch(store.as_ref()).await?;
+
+        let mut current: Option<EpochWriter> = None;
+        let mut row_count = 0;
+        while let Some(batch) = data.next().await.transpose()? {
+            for (epoch, batch) in split_epochs(&batch, self.epoch_column)? {
+                if last_committed.is_some_and(|last| epoch <= last) {
+                    // Replayed rows of an epoch that was already committed
+                    continue;
+                }
+
+                let epoch_writer = match current.take() {
+                    Some(epoch_writer) if epoch_writer.epoch == epoch => epoch_writer,
+                    Some(epoch_writer) if epoch_writer.epoch > epoch => {
+                        return exec_err!(
+                            "Epochs must be non-decreasing, got epoch {epoch} after epoch {}",
+                            epoch_writer.epoch
+                        );
+                    }
+                    previous => {
+                        if let Some(previous) = previous {
+                            self.commit_epoch(previous, store.as_ref()).await?;
+                        }
+                        self.start_epoch(epoch, &store)?
+                    }
+                };
+                row_count += batch.num_rows() as u64;
+                current =
+                    Some(epoch_writer.write(batch, self.serializer.as_ref()).await?);
+            }
+        }
+
+        if let Some(epoch_writer) = current {
+            self.commit_epoch(epoch_writer, store.as_ref()).await?;
+        }
+        Ok(row_count)
+    }
+}
+
+/// Writer of the data file of an epoch
+struct EpochWriter {
+    epoch: u64,
+    location: Path,
+    writer: Box<dyn AsyncWrite + Send + Unpin>,
+    /// Whether no batch was written yet
+    initial: bool,
+}
+
+impl EpochWriter {
+    async fn write(
+        mut self,
+        batch: RecordBatch,
+        serializer: &dyn BatchSerializer,
+    ) -> Result<Self> {
+        let bytes = serializer.serialize(batch, self.initial)?;
+        self.initial = false;
+        self.writer.write_all(&bytes).await?;
+        Ok(self)
+    }
+}
+
+/// Split `batch` into slices of rows with the same epoch
+fn split_epochs(
+    batch: &RecordBatch,
+    epoch_column: usize,
+) -> Result<Vec<(u64, RecordBatch)>> {
+    let epochs = batch.column(epoch_column).as_primitive::<UInt64Type>();
+    if epochs.null_count() > 0 {
+        return exec_err!("Epoch column must not contain nulls");
+    }
+
+    let epochs = epochs.values();
+    let mut slices = vec![];
+    let mut start = 0;
+    for end in 1..=epochs.len() {
+        if end == epochs.len() || epochs[end] != epochs[start] {
+            slices.push((epochs[start], batch.slice(start, end - start)));
+            start = end;
+        }
+    }
+    Ok(slices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{StringArray, UInt64Array};
+    use arrow::datatypes::{Field, Schema};
+    use bytes::Bytes;
+    use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+    use object_store::memory::InMemory;
+
+    /// Writes the values of the second column, one per line
+    struct LineSerializer;
+
+    impl BatchSerializer for LineSerializer {
+        fn serialize(&self, batch: RecordBatch, _initial: bool) -> Result<Bytes> {
+            let values = batch.column(1).as_string::<i32>();
+            let lines: String =
+                values.iter().flatten().map(|v| format!("{v}\n")).collect();
+            Ok(Bytes::from(lines))
+        }
+    }
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("epoch", DataType::UInt64, false),
+            Field::new("value", DataType::Utf8, false),
+        ]))
+    }
+
+    fn input(batches: Vec<(Vec<u64>, Vec<&str>)>) -> SendableRecordBatchStream {
+        let batches = batches
+            .into_iter()
+            .map(|(epochs, values)| {
+                RecordBatch::try_new(
+                    schema(),
+                    vec![
+                        Arc::new(UInt64Array::from(epochs)),
+                        Arc::new(StringArray::from(values)),
+                    ],
+                )
+                .map_err(Into::into)
+            })
+            .collect::<Vec<_>>();
+        Box::pin(RecordBatchStreamAdapter::new(
+            schema(),
+            futures::stream::iter(batches),
+        ))
+    }
+
+    async fn read_files(store: &dyn ObjectStore, files: &[Path]) -> Result<String> {
+        let mut contents = String::new();
+        for file in files {
+            let bytes = store.get(file).await?.bytes().await?;
+            contents.push_str(std::str::from_utf8(&bytes).unwrap());
+        }
+        Ok(contents)
+    }
+
+    #[tokio::test]
+    async fn write_epochs_exactly_once() -> Result<()> {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let url = ObjectStoreUrl::parse("memory://")?;
+        let context = Arc::new(TaskContext::default());
+        context
+            .runtime_env()
+            .register_object_store(url.as_ref(), Arc::clone(&store));
+        let sink = CheckpointedFileSink::try_new(
+            url,
+            Path::from("out"),
+            schema(),
+            "epoch",
+            Arc::new(LineSerializer),
+            "txt",
+        )?;
+
+        let data = input(vec![
+            (vec![1, 1, 2], vec!["a", "b", "c"]),
+            (vec![2, 3], vec!["d", "e"]),
+        ]);
+        assert_eq!(sink.write_all(data, &context).await?, 5);
+        assert_eq!(sink.last_committed_epoch(store.as_ref()).await?, Some(3));
+
+        // Replay from epoch 2: only the rows of epoch 4 are written
+        let data = input(vec![(vec![2, 3, 4], vec!["c", "e", "f"])]);
+        assert_eq!(sink.write_all(data, &context).await?, 1);
+
+        let files = sink.committed_files(store.as_ref()).await?;
+        assert_eq!(
+            files,
+            vec![
+                Path::from("out/epoch-00000000000000000001.txt"),
+                Path::from("out/epoch-00000000000000000002.txt"),
+                Path::from("out/epoch-00000000000000000003.txt"),
+                Path::from("out/epoch-00000000000000000004.txt"),
+            ]
+        );
+        assert_eq!(
+            read_files(store.as_ref(), &files).await?,
+            "a\nb\nc\nd\ne\nf\n"
+        );
+
+        let data = input(vec![(vec![6, 5], vec!["g", "h"])]);
+        let err = sink.write_all(data, &context).await.unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: Epochs must be non-decreasing, got epoch 5 after epoch 6"
+        );
+
+        Ok(())
+    }
+}
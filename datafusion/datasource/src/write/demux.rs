@@ -32,6 +32,7 @@ use arrow::array::{
     cast::AsArray, downcast_dictionary_array,
 };
 use arrow::datatypes::{DataType, Schema};
+use arrow::util::display::{ArrayFormatter, FormatOptions};
 use datafusion_common::cast::{
     as_boolean_array, as_date32_array, as_date64_array, as_float16_array,
     as_float32_array, as_float64_array, as_int8_array, as_int16_array, as_int32_array,
@@ -306,13 +307,21 @@ async fn hive_style_partitions_demuxer(
 
     let exec_options = &context.session_config().options().execution;
     let max_buffered_recordbatches = exec_options.max_buffered_batches_per_output_file;
+    let max_open_files = exec_options.max_open_partition_files;
+    let timestamp_format = exec_options.partition_timestamp_format.as_str();
 
     // To support non string partition col types, cast the type to &str first
-    let mut value_map: HashMap<Vec<String>, Sender<RecordBatch>> = HashMap::new();
+    let mut value_map: HashMap<Vec<String>, OpenPartitionFile> = HashMap::new();
+    // Number of files written so far for each partition, to name the next one
+    let mut file_counts: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut batch_index = 0;
 
     while let Some(rb) = input.next().await.transpose()? {
+        batch_index += 1;
+
         // First compute partition key for each row of batch, e.g. (col1=val1, col2=val2, ...)
-        let all_partition_values = compute_partition_keys_by_row(&rb, &partition_by)?;
+        let all_partition_values =
+            compute_partition_keys_by_row(&rb, &partition_by, timestamp_format)?;
 
         // Next compute how the batch should be split up to take each distinct key to its own batch
         let take_map = compute_take_arrays(&rb, &all_partition_values);
@@ -327,17 +336,38 @@ async fn hive_style_partitions_demuxer(
                 arrow::compute::take(&struct_array, &take_indices, None)?.as_struct(),
             );
 
+            if !value_map.contains_key(&part_key)
+                && max_open_files > 0
+                && value_map.len() >= max_open_files
+            {
+                // Finish the file of the least recently used partition, by
+                // dropping its channel, to bound the number of open files
+                let lru_key = value_map
+                    .iter()
+                    .min_by_key(|(_, file)| file.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(lru_key) = lru_key {
+                    value_map.remove(&lru_key);
+                }
+            }
+
             // Get or create channel for this batch
-            let part_tx = match value_map.get_mut(&part_key) {
-                Some(part_tx) => part_tx,
+            let part_file = match value_map.get_mut(&part_key) {
+                Some(part_file) => part_file,
                 None => {
                     // Create channel for previously unseen distinct partition key and notify consumer of new file
                     let (part_tx, part_rx) =
                         mpsc::channel::<RecordBatch>(max_buffered_recordbatches);
+                    let file_count = file_counts.entry(part_key.clone()).or_default();
+                    let file_id = match *file_count {
+                        0 => Cow::Borrowed(write_id.as_str()),
+                        n => Cow::Owned(format!("{write_id}_{n}")),
+                    };
+                    *file_count += 1;
                     let file_path = compute_hive_style_file_path(
                         &part_key,
                         &partition_by,
-                        &write_id,
+                        &file_id,
                         &file_extension,
                         &base_output_path,
                     );
@@ -346,12 +376,19 @@ async fn hive_style_partitions_demuxer(
                         exec_datafusion_err!("Error sending new file stream!")
                     })?;
 
-                    value_map.insert(part_key.clone(), part_tx);
+                    value_map.insert(
+                        part_key.clone(),
+                        OpenPartitionFile {
+                            tx: part_tx,
+                            last_used: batch_index,
+                        },
+                    );
                     value_map.get_mut(&part_key).ok_or_else(|| {
                         exec_datafusion_err!("Key must exist since it was just inserted!")
                     })?
                 }
             };
+            part_file.last_used = batch_index;
 
             let final_batch_to_send = if keep_partition_by_columns {
                 parted_batch
@@ -360,7 +397,7 @@ async fn hive_style_partitions_demuxer(
             };
 
             // Finally send the partial batch partitioned by distinct value!
-            part_tx.send(final_batch_to_send).await.map_err(|_| {
+            part_file.tx.send(final_batch_to_send).await.map_err(|_| {
                 internal_datafusion_err!("Unexpected error sending parted batch!")
             })?;
         }
@@ -369,9 +406,19 @@ async fn hive_style_partitions_demuxer(
     Ok(())
 }
 
+/// The file a partition is currently written to by
+/// [`hive_style_partitions_demuxer`]
+struct OpenPartitionFile {
+    /// Sends the batches of the partition to the file
+    tx: Sender<RecordBatch>,
+    /// Index of the input batch which last had rows of the partition
+    last_used: usize,
+}
+
 fn compute_partition_keys_by_row<'a>(
     rb: &'a RecordBatch,
     partition_by: &'a [(String, DataType)],
+    timestamp_format: &str,
 ) -> Result<Vec<Vec<Cow<'a, str>>>> {
     let mut all_partition_values = vec![];
 
@@ -445,6 +492,15 @@ fn compute_partition_keys_by_row<'a>(
                     partition_values.push(Cow::from(date));
                 }
             }
+            DataType::Timestamp(_, _) => {
+                let options = FormatOptions::new()
+                    .with_timestamp_format(Some(timestamp_format))
+                    .with_timestamp_tz_format(Some(timestamp_format));
+                let formatter = ArrayFormatter::try_new(col_array, &options)?;
+                for i in 0..rb.num_rows() {
+                    partition_values.push(Cow::from(formatter.value(i).to_string()));
+                }
+            }
             DataType::Int8 => {
                 let array = as_int8_array(col_array)?;
                 for i in 0..rb.num_rows() {
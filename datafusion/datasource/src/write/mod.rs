@@ -33,6 +33,7 @@ use object_store::buffered::BufWriter;
 use object_store::path::Path;
 use tokio::io::AsyncWrite;
 
+pub mod checkpoint;
 pub mod demux;
 pub mod orchestration;
 
@@ -30,6 +30,7 @@ pub mod scalar_doc_sections {
             DOC_SECTION_ARRAY,
             DOC_SECTION_STRUCT,
             DOC_SECTION_MAP,
+            DOC_SECTION_JSON,
             DOC_SECTION_HASHING,
             DOC_SECTION_UNION,
             DOC_SECTION_OTHER,
@@ -47,6 +48,7 @@ pub mod scalar_doc_sections {
             DOC_SECTION_ARRAY,
             DOC_SECTION_STRUCT,
             DOC_SECTION_MAP,
+            DOC_SECTION_JSON,
             DOC_SECTION_HASHING,
             DOC_SECTION_UNION,
             DOC_SECTION_OTHER,
@@ -112,6 +114,21 @@ The following regular expression functions are supported:"#,
         description: None,
     };
 
+    pub const DOC_SECTION_JSON: DocSection = DocSection {
+        include: true,
+        label: "JSON Functions",
+        description: Some(
+            r#"Functions to work with JSON documents stored as strings. Values within a document are selected with JSON path expressions:
+`$` is the whole document, `.key` or `["key"]` selects the member `key` of an object, and `[n]` selects the `n`th
+element of an array, starting from `0` (negative indexes count from the end of the array). For example
+`$.store.books[0].title`.
+
+The `->` and `->>` operators select the member or element of a JSON document with the given key or index, and are
+equivalent to `json_extract` and `json_value` respectively. Casting a JSON string to a `STRUCT` or array type parses
+it with `from_json`, and casting a `STRUCT` or array to a string formats it as JSON with `to_json`."#,
+        ),
+    };
+
     pub const DOC_SECTION_HASHING: DocSection = DocSection {
         include: true,
         label: "Hashing Functions",
@@ -16,10 +16,12 @@
 // under the License.
 
 use crate::cache::CacheAccessor;
+use crate::cache::DefaultFileRangeCache;
 use crate::cache::DefaultListFilesCache;
 use crate::cache::cache_unit::DefaultFilesMetadataCache;
 use crate::cache::list_files_cache::ListFilesEntry;
 use crate::cache::list_files_cache::TableScopedPath;
+use bytes::Bytes;
 use datafusion_common::TableReference;
 use datafusion_common::stats::Precision;
 use datafusion_common::{Result, Statistics};
@@ -29,7 +31,7 @@ use object_store::path::Path;
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -237,12 +239,26 @@ impl CachedFileMetadataEntry {
     }
 
     /// Check if this cached entry is still valid for the given metadata.
+    ///
+    /// Returns true if the file size and last modified time match, as well
+    /// as the e-tag, if known for both.
     pub fn is_valid_for(&self, current_meta: &ObjectMeta) -> bool {
-        self.meta.size == current_meta.size
-            && self.meta.last_modified == current_meta.last_modified
+        is_same_object_version(&self.meta, current_meta)
     }
 }
 
+/// Returns true if `cached` and `current` describe the same version of an
+/// object: the size and last modified time match, as well as the e-tag, if
+/// known for both.
+fn is_same_object_version(cached: &ObjectMeta, current: &ObjectMeta) -> bool {
+    cached.size == current.size
+        && cached.last_modified == current.last_modified
+        && match (&cached.e_tag, &current.e_tag) {
+            (Some(cached), Some(current)) => cached == current,
+            _ => true,
+        }
+}
+
 impl Debug for CachedFileMetadataEntry {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CachedFileMetadataEntry")
@@ -296,6 +312,72 @@ pub struct FileMetadataCacheEntry {
     pub extra: HashMap<String, String>,
 }
 
+/// A byte range of a file, used as the key of a [`FileRangeCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileRangeKey {
+    /// The path of the file
+    pub path: Path,
+    /// The range of bytes of the file
+    pub range: Range<u64>,
+}
+
+impl FileRangeKey {
+    /// Create a new key for `range` of the file at `path`.
+    pub fn new(path: Path, range: Range<u64>) -> Self {
+        Self { path, range }
+    }
+}
+
+/// Cached byte range of a file, with validation information.
+#[derive(Debug, Clone)]
+pub struct CachedFileRange {
+    /// File metadata used for cache validation (size, last_modified, e_tag).
+    pub meta: ObjectMeta,
+    /// The cached bytes.
+    pub bytes: Bytes,
+}
+
+impl CachedFileRange {
+    /// Create a new cached file range.
+    pub fn new(meta: ObjectMeta, bytes: Bytes) -> Self {
+        Self { meta, bytes }
+    }
+
+    /// Check if this cached entry is still valid for the given metadata.
+    ///
+    /// Returns true if the file size and last modified time match, as well
+    /// as the e-tag, if known for both.
+    pub fn is_valid_for(&self, current_meta: &ObjectMeta) -> bool {
+        is_same_object_version(&self.meta, current_meta)
+    }
+}
+
+/// Cache for byte ranges of data files, such as the column chunks of Parquet
+/// files, so that repeated queries over the same remote files do not fetch
+/// the same bytes from object storage again.
+///
+/// The ranges are cached exactly as requested by the readers: a request for
+/// a range that overlaps, but is not equal to, a cached range is a miss.
+///
+/// DataFusion provides a default implementation, [`DefaultFileRangeCache`],
+/// which is enabled by setting a limit with
+/// [`CacheManagerConfig::with_file_range_cache_limit`]. Users can also
+/// provide their own implementations to implement custom caching policies.
+///
+/// The typical usage pattern is:
+/// 1. Call `get(key)` to check for cached value
+/// 2. If `Some(cached)`, validate with `cached.is_valid_for(&current_meta)`
+/// 3. If invalid or missing, read the range and call `put(key, new_value)`
+///
+/// [`DefaultFileRangeCache`]: crate::cache::DefaultFileRangeCache
+pub trait FileRangeCache: CacheAccessor<FileRangeKey, CachedFileRange> {
+    /// Returns the cache's memory limit in bytes.
+    fn cache_limit(&self) -> usize;
+
+    /// Updates the cache with a new memory limit in bytes.
+    fn update_cache_limit(&self, limit: usize);
+}
+
 impl Debug for dyn FileStatisticsCache {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Cache name: {} with length: {}", self.name(), self.len())
@@ -314,6 +396,12 @@ impl Debug for dyn FileMetadataCache {
     }
 }
 
+impl Debug for dyn FileRangeCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cache name: {} with length: {}", self.name(), self.len())
+    }
+}
+
 /// Manages various caches used in DataFusion.
 ///
 /// Following DataFusion design principles, DataFusion provides default cache
@@ -326,6 +414,7 @@ pub struct CacheManager {
     file_statistic_cache: Option<Arc<dyn FileStatisticsCache>>,
     list_files_cache: Option<Arc<dyn ListFilesCache>>,
     file_metadata_cache: Arc<dyn FileMetadataCache>,
+    file_range_cache: Option<Arc<dyn FileRangeCache>>,
 }
 
 impl CacheManager {
@@ -364,10 +453,24 @@ impl CacheManager {
         // the cache memory limit might have changed, ensure the limit is updated
         file_metadata_cache.update_cache_limit(config.metadata_cache_limit);
 
+        let file_range_cache = match &config.file_range_cache {
+            Some(frc) if config.file_range_cache_limit > 0 => {
+                frc.update_cache_limit(config.file_range_cache_limit);
+                Some(Arc::clone(frc))
+            }
+            None if config.file_range_cache_limit > 0 => {
+                let frc: Arc<dyn FileRangeCache> =
+                    Arc::new(DefaultFileRangeCache::new(config.file_range_cache_limit));
+                Some(frc)
+            }
+            _ => None,
+        };
+
         Ok(Arc::new(CacheManager {
             file_statistic_cache,
             list_files_cache,
             file_metadata_cache,
+            file_range_cache,
         }))
     }
 
@@ -402,6 +505,18 @@ impl CacheManager {
     pub fn get_metadata_cache_limit(&self) -> usize {
         self.file_metadata_cache.cache_limit()
     }
+
+    /// Get the cache of byte ranges of data files, if enabled.
+    pub fn get_file_range_cache(&self) -> Option<Arc<dyn FileRangeCache>> {
+        self.file_range_cache.clone()
+    }
+
+    /// Get the memory limit of the file range cache, 0 if disabled.
+    pub fn get_file_range_cache_limit(&self) -> usize {
+        self.file_range_cache
+            .as_ref()
+            .map_or(0, |c| c.cache_limit())
+    }
 }
 
 pub const DEFAULT_METADATA_CACHE_LIMIT: usize = 50 * 1024 * 1024; // 50M
@@ -432,6 +547,13 @@ pub struct CacheManagerConfig {
     pub file_metadata_cache: Option<Arc<dyn FileMetadataCache>>,
     /// Limit of the file-embedded metadata cache, in bytes.
     pub metadata_cache_limit: usize,
+    /// Cache of byte ranges of data files, used to avoid fetching the same
+    /// data (e.g., Parquet column chunks) from object storage repeatedly.
+    /// If not provided, the [`CacheManager`] will create a
+    /// [`DefaultFileRangeCache`] when `file_range_cache_limit` is not 0.
+    pub file_range_cache: Option<Arc<dyn FileRangeCache>>,
+    /// Limit of the file range cache, in bytes. Default: 0 (disabled).
+    pub file_range_cache_limit: usize,
 }
 
 impl Default for CacheManagerConfig {
@@ -443,6 +565,8 @@ impl Default for CacheManagerConfig {
             list_files_cache_ttl: DEFAULT_LIST_FILES_CACHE_TTL,
             file_metadata_cache: Default::default(),
             metadata_cache_limit: DEFAULT_METADATA_CACHE_LIMIT,
+            file_range_cache: Default::default(),
+            file_range_cache_limit: 0,
         }
     }
 }
@@ -502,6 +626,26 @@ impl CacheManagerConfig {
         self.metadata_cache_limit = limit;
         self
     }
+
+    /// Sets the cache for byte ranges of data files.
+    ///
+    /// The cache is only used if the limit set by
+    /// [`Self::with_file_range_cache_limit`] is not 0.
+    pub fn with_file_range_cache(
+        mut self,
+        cache: Option<Arc<dyn FileRangeCache>>,
+    ) -> Self {
+        self.file_range_cache = cache;
+        self
+    }
+
+    /// Sets the limit of the file range cache, in bytes.
+    ///
+    /// Default: 0, which disables the cache.
+    pub fn with_file_range_cache_limit(mut self, limit: usize) -> Self {
+        self.file_range_cache_limit = limit;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,290 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Mutex;
+
+use crate::cache::{
+    CacheAccessor,
+    cache_manager::{CachedFileRange, FileRangeCache, FileRangeKey},
+    lru_queue::LruQueue,
+};
+
+/// Handles the inner state of the [`DefaultFileRangeCache`] struct.
+struct DefaultFileRangeCacheState {
+    lru_queue: LruQueue<FileRangeKey, CachedFileRange>,
+    memory_limit: usize,
+    memory_used: usize,
+}
+
+impl DefaultFileRangeCacheState {
+    fn new(memory_limit: usize) -> Self {
+        Self {
+            lru_queue: LruQueue::new(),
+            memory_limit,
+            memory_used: 0,
+        }
+    }
+
+    /// Returns the respective entry from the cache, if it exists.
+    /// If the entry exists, it becomes the most recently used.
+    fn get(&mut self, k: &FileRangeKey) -> Option<CachedFileRange> {
+        self.lru_queue.get(k).cloned()
+    }
+
+    /// Checks if the range is currently cached.
+    /// The LRU queue is not updated.
+    fn contains_key(&self, k: &FileRangeKey) -> bool {
+        self.lru_queue.peek(k).is_some()
+    }
+
+    /// Adds a new key-value pair to cache, meaning LRU entries might be evicted if required.
+    /// If the key is already in the cache, the previous value is returned.
+    /// If the size of the range is greater than the `memory_limit`, the value is not inserted.
+    fn put(
+        &mut self,
+        key: FileRangeKey,
+        value: CachedFileRange,
+    ) -> Option<CachedFileRange> {
+        let value_size = value.bytes.len();
+
+        // no point in trying to add this value to the cache if it cannot fit entirely
+        if value_size > self.memory_limit {
+            return None;
+        }
+
+        // if the key is already in the cache, the old value is removed
+        let old_value = self.lru_queue.put(key, value);
+        self.memory_used += value_size;
+        if let Some(ref old_entry) = old_value {
+            self.memory_used -= old_entry.bytes.len();
+        }
+
+        self.evict_entries();
+
+        old_value
+    }
+
+    /// Evicts entries from the LRU cache until `memory_used` is lower than `memory_limit`.
+    fn evict_entries(&mut self) {
+        while self.memory_used > self.memory_limit {
+            if let Some(removed) = self.lru_queue.pop() {
+                self.memory_used -= removed.1.bytes.len();
+            } else {
+                // cache is empty while memory_used > memory_limit, cannot happen
+                debug_assert!(
+                    false,
+                    "cache is empty while memory_used > memory_limit, cannot happen"
+                );
+                return;
+            }
+        }
+    }
+
+    /// Removes an entry from the cache and returns it, if it exists.
+    fn remove(&mut self, k: &FileRangeKey) -> Option<CachedFileRange> {
+        let old_entry = self.lru_queue.remove(k)?;
+        self.memory_used -= old_entry.bytes.len();
+        Some(old_entry)
+    }
+
+    /// Returns the number of entries currently cached.
+    fn len(&self) -> usize {
+        self.lru_queue.len()
+    }
+
+    /// Removes all entries from the cache.
+    fn clear(&mut self) {
+        self.lru_queue.clear();
+        self.memory_used = 0;
+    }
+}
+
+/// Default implementation of [`FileRangeCache`]
+///
+/// Caches byte ranges of data files, keyed by path and range.
+///
+/// Each cached range is validated by comparing the cached [`ObjectMeta`]
+/// (size, last_modified and e_tag) against the current file state using
+/// `cached.is_valid_for(&current_meta)`.
+///
+/// # Internal details
+///
+/// The `memory_limit` controls the maximum size of the cache, which uses a
+/// Least Recently Used eviction algorithm. When adding a new entry, if the total
+/// size of the cached entries exceeds `memory_limit`, the least recently used entries
+/// are evicted until the total size is lower than `memory_limit`.
+///
+/// [`ObjectMeta`]: object_store::ObjectMeta
+pub struct DefaultFileRangeCache {
+    // the state is wrapped in a Mutex to ensure the operations are atomic
+    state: Mutex<DefaultFileRangeCacheState>,
+}
+
+impl DefaultFileRangeCache {
+    /// Create a new instance of [`DefaultFileRangeCache`].
+    ///
+    /// # Arguments
+    /// `memory_limit`:  the maximum size of the cache, in bytes
+    pub fn new(memory_limit: usize) -> Self {
+        Self {
+            state: Mutex::new(DefaultFileRangeCacheState::new(memory_limit)),
+        }
+    }
+
+    /// Returns the size of the cached memory, in bytes.
+    pub fn memory_used(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.memory_used
+    }
+}
+
+impl CacheAccessor<FileRangeKey, CachedFileRange> for DefaultFileRangeCache {
+    fn get(&self, key: &FileRangeKey) -> Option<CachedFileRange> {
+        let mut state = self.state.lock().unwrap();
+        state.get(key)
+    }
+
+    fn put(&self, key: &FileRangeKey, value: CachedFileRange) -> Option<CachedFileRange> {
+        let mut state = self.state.lock().unwrap();
+        state.put(key.clone(), value)
+    }
+
+    fn remove(&self, k: &FileRangeKey) -> Option<CachedFileRange> {
+        let mut state = self.state.lock().unwrap();
+        state.remove(k)
+    }
+
+    fn contains_key(&self, k: &FileRangeKey) -> bool {
+        let state = self.state.lock().unwrap();
+        state.contains_key(k)
+    }
+
+    fn len(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.len()
+    }
+
+    fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.clear();
+    }
+
+    fn name(&self) -> String {
+        "DefaultFileRangeCache".to_string()
+    }
+}
+
+impl FileRangeCache for DefaultFileRangeCache {
+    fn cache_limit(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.memory_limit
+    }
+
+    fn update_cache_limit(&self, limit: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.memory_limit = limit;
+        state.evict_entries();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::DateTime;
+    use object_store::ObjectMeta;
+    use object_store::path::Path;
+
+    use super::*;
+
+    fn create_test_object_meta(path: &str, e_tag: Option<&str>) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from(path),
+            last_modified: DateTime::parse_from_rfc3339("2025-07-29T12:12:12+00:00")
+                .unwrap()
+                .into(),
+            size: 1024,
+            e_tag: e_tag.map(String::from),
+            version: None,
+        }
+    }
+
+    fn cached_range(meta: &ObjectMeta, len: usize) -> CachedFileRange {
+        CachedFileRange::new(meta.clone(), Bytes::from(vec![0u8; len]))
+    }
+
+    #[test]
+    fn test_default_file_range_cache() {
+        let meta = create_test_object_meta("a.parquet", Some("v1"));
+        let key = FileRangeKey::new(meta.location.clone(), 0..100);
+        let cache = DefaultFileRangeCache::new(1000);
+
+        assert!(cache.get(&key).is_none());
+        cache.put(&key, cached_range(&meta, 100));
+        assert!(cache.contains_key(&key));
+        assert_eq!(cache.memory_used(), 100);
+
+        // a different range of the same file is a miss
+        let other = FileRangeKey::new(meta.location.clone(), 0..50);
+        assert!(cache.get(&other).is_none());
+
+        // the cached range is only valid for the same version of the file
+        let cached = cache.get(&key).unwrap();
+        assert!(cached.is_valid_for(&meta));
+        assert!(cached.is_valid_for(&create_test_object_meta("a.parquet", None)));
+        assert!(!cached.is_valid_for(&create_test_object_meta("a.parquet", Some("v2"))));
+
+        // replacing the value updates the used memory
+        cache.put(&key, cached_range(&meta, 200));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.memory_used(), 200);
+
+        assert!(cache.remove(&key).is_some());
+        assert!(cache.is_empty());
+        assert_eq!(cache.memory_used(), 0);
+    }
+
+    #[test]
+    fn test_default_file_range_cache_with_limit() {
+        let meta = create_test_object_meta("a.parquet", None);
+        let key = |start| FileRangeKey::new(meta.location.clone(), start..start + 100);
+        let cache = DefaultFileRangeCache::new(250);
+
+        // ranges larger than the limit are not cached
+        cache.put(&key(0), cached_range(&meta, 300));
+        assert!(cache.is_empty());
+
+        cache.put(&key(0), cached_range(&meta, 100));
+        cache.put(&key(100), cached_range(&meta, 100));
+        // accessing the first range makes the second one the least recently used
+        assert!(cache.get(&key(0)).is_some());
+        cache.put(&key(200), cached_range(&meta, 100));
+        assert!(cache.contains_key(&key(0)));
+        assert!(!cache.contains_key(&key(100)));
+        assert!(cache.contains_key(&key(200)));
+        assert_eq!(cache.memory_used(), 200);
+
+        // lowering the limit evicts the least recently used entries
+        cache.update_cache_limit(100);
+        assert_eq!(cache.cache_limit(), 100);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains_key(&key(200)));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.memory_used(), 0);
+    }
+}
@@ -20,9 +20,11 @@ pub mod cache_unit;
 pub mod lru_queue;
 
 mod file_metadata_cache;
+mod file_range_cache;
 mod list_files_cache;
 
 pub use file_metadata_cache::DefaultFilesMetadataCache;
+pub use file_range_cache::DefaultFileRangeCache;
 pub use list_files_cache::DefaultListFilesCache;
 pub use list_files_cache::ListFilesEntry;
 pub use list_files_cache::TableScopedPath;
@@ -31,7 +33,8 @@ pub use list_files_cache::TableScopedPath;
 ///
 /// This trait provides the fundamental cache operations (`get`, `put`, `remove`, etc.)
 /// that all cache types share. Specific cache traits like [`cache_manager::FileStatisticsCache`],
-/// [`cache_manager::ListFilesCache`], and [`cache_manager::FileMetadataCache`] extend this
+/// [`cache_manager::ListFilesCache`], [`cache_manager::FileMetadataCache`], and
+/// [`cache_manager::FileRangeCache`] extend this
 /// trait with their specialized methods.
 ///
 /// ## Thread Safety
@@ -20,7 +20,7 @@ use std::{collections::HashMap, sync::Arc};
 use datafusion_common::{
     Result, ScalarValue,
     config::{ConfigExtension, ConfigOptions, SpillCompression},
-    extensions::Extensions,
+    config_loader::ConfigLoader,
 };
 
 /// Configuration options for [`SessionContext`].
@@ -94,18 +94,18 @@ pub struct SessionConfig {
     ///
     /// A new copy is created on write, if there are other outstanding
     /// references to the same options.
-    options: Arc<ConfigOptions>,
-    /// Opaque extensions, keyed by concrete Rust type. See
+    ///
+    /// Opaque extensions, keyed by concrete Rust type, are stored in the
+    /// [`ConfigOptions::session_extensions`]. See
     /// [`with_extension`](Self::with_extension) and
     /// [`get_extension`](Self::get_extension).
-    extensions: Extensions,
+    options: Arc<ConfigOptions>,
 }
 
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
             options: Arc::new(ConfigOptions::new()),
-            extensions: Extensions::new(),
         }
     }
 }
@@ -129,6 +129,12 @@ impl SessionConfig {
         Ok(ConfigOptions::from_string_hash_map(settings)?.into())
     }
 
+    /// Create an execution config with the options built by `loader` from
+    /// profiles, files and environment variables
+    pub fn from_loader(loader: &ConfigLoader) -> Result<Self> {
+        Ok(loader.load()?.into())
+    }
+
     /// Return a handle to the configuration options.
     ///
     /// Can be used to read the current configuration.
@@ -517,7 +523,11 @@ impl SessionConfig {
     /// Extensions are indexed by their type `T`. If multiple values of the same type are provided, only the last one
     /// will be kept.
     ///
-    /// You may use [`get_extension`](Self::get_extension) to retrieve extensions.
+    /// You may use [`get_extension`](Self::get_extension) to retrieve extensions. As they are stored in the
+    /// [`ConfigOptions::session_extensions`], extensions are also available while executing a query, e.g. in
+    /// `TableProvider::scan` through the session's config, and in scalar functions through
+    /// `ScalarFunctionArgs::config_options`. This makes them suitable for passing per-query context, such as the
+    /// user running the query, see `DataFrame::with_extension`.
     ///
     /// # Example
     /// ```
@@ -596,7 +606,7 @@ impl SessionConfig {
     where
         T: Send + Sync + 'static,
     {
-        self.extensions.insert_arc(ext);
+        self.options_mut().session_extensions.insert_arc(ext);
     }
 
     /// Get extension, if any for the specified type `T` exists.
@@ -606,16 +616,14 @@ impl SessionConfig {
     where
         T: Send + Sync + 'static,
     {
-        self.extensions.get_arc::<T>()
+        self.options.session_extensions.get_arc::<T>()
     }
 }
 
 impl From<ConfigOptions> for SessionConfig {
     fn from(options: ConfigOptions) -> Self {
-        let options = Arc::new(options);
         Self {
-            options,
-            ..Default::default()
+            options: Arc::new(options),
         }
     }
 }
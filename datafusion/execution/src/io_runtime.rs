@@ -0,0 +1,477 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`IoRuntimeObjectStore`] runs the requests of an [`ObjectStore`] on a
+//! dedicated IO runtime
+//!
+//! DataFusion runs CPU-bound operators (for example decoding Parquet or
+//! evaluating expressions) as tokio tasks. When the same runtime also drives
+//! the network requests of the object stores, long CPU-bound polls delay
+//! the IO (which may then time out), and many concurrent requests delay the
+//! operators. Running the object store requests on a separate runtime,
+//! configured with [`RuntimeEnvBuilder::with_io_runtime`], keeps both
+//! responsive.
+//!
+//! [`RuntimeEnvBuilder::with_io_runtime`]: crate::runtime_env::RuntimeEnvBuilder::with_io_runtime
+
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::ops::Range;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion_common_runtime::SpawnedTask;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use object_store::path::Path;
+use object_store::{
+    CopyOptions, GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload,
+    ObjectMeta, ObjectStore, PutMultipartOptions, PutOptions, PutPayload, PutResult,
+    UploadPart,
+};
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tokio::task::JoinError;
+
+/// The number of items of a stream buffered between the IO runtime and the
+/// consumer of the stream
+const STREAM_BUFFER: usize = 2;
+
+/// An [`ObjectStore`] running the requests of another [`ObjectStore`] on the
+/// runtime of a [`Handle`], typically a runtime dedicated to IO.
+///
+/// Every request, and the polling of every returned stream (such as the
+/// bytes of an object, or a listing), is spawned on the IO runtime, while
+/// the results are consumed on the caller's runtime. Dropping a request or
+/// a stream cancels the corresponding task.
+///
+/// [`RuntimeEnv::object_store`] wraps the registered object stores in an
+/// [`IoRuntimeObjectStore`] when an IO runtime is configured.
+///
+/// [`RuntimeEnv::object_store`]: crate::runtime_env::RuntimeEnv::object_store
+#[derive(Debug)]
+pub struct IoRuntimeObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    handle: Handle,
+}
+
+impl IoRuntimeObjectStore {
+    /// Runs the requests of `inner` on the runtime of `handle`
+    pub fn new(inner: Arc<dyn ObjectStore>, handle: Handle) -> Self {
+        Self { inner, handle }
+    }
+
+    /// The wrapped object store
+    pub fn inner(&self) -> &Arc<dyn ObjectStore> {
+        &self.inner
+    }
+
+    /// Runs `f` with the wrapped object store on the IO runtime
+    async fn spawn<T, F, Fut>(&self, f: F) -> object_store::Result<T>
+    where
+        F: FnOnce(Arc<dyn ObjectStore>) -> Fut,
+        Fut: Future<Output = object_store::Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        spawn_io(&self.handle, f(Arc::clone(&self.inner))).await
+    }
+}
+
+impl Display for IoRuntimeObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IoRuntime({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for IoRuntimeObjectStore {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        let location = location.clone();
+        self.spawn(|store| async move { store.put_opts(&location, payload, opts).await })
+            .await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOptions,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        let location = location.clone();
+        let upload = self
+            .spawn(|store| async move { store.put_multipart_opts(&location, opts).await })
+            .await?;
+        Ok(Box::new(IoRuntimeUpload {
+            inner: upload,
+            handle: self.handle.clone(),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &Path,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let location = location.clone();
+        let result = self
+            .spawn(|store| async move { store.get_opts(&location, options).await })
+            .await?;
+        let payload = match result.payload {
+            GetResultPayload::Stream(stream) => {
+                GetResultPayload::Stream(spawn_stream(&self.handle, stream))
+            }
+            payload @ GetResultPayload::File(..) => payload,
+        };
+        Ok(GetResult { payload, ..result })
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<u64>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let location = location.clone();
+        let ranges = ranges.to_vec();
+        self.spawn(|store| async move { store.get_ranges(&location, &ranges).await })
+            .await
+    }
+
+    fn delete_stream(
+        &self,
+        locations: BoxStream<'static, object_store::Result<Path>>,
+    ) -> BoxStream<'static, object_store::Result<Path>> {
+        spawn_stream(&self.handle, self.inner.delete_stream(locations))
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        spawn_stream(&self.handle, self.inner.list(prefix))
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        spawn_stream(&self.handle, self.inner.list_with_offset(prefix, offset))
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        let prefix = prefix.cloned();
+        self.spawn(
+            |store| async move { store.list_with_delimiter(prefix.as_ref()).await },
+        )
+        .await
+    }
+
+    async fn copy_opts(
+        &self,
+        from: &Path,
+        to: &Path,
+        options: CopyOptions,
+    ) -> object_store::Result<()> {
+        let from = from.clone();
+        let to = to.clone();
+        self.spawn(|store| async move { store.copy_opts(&from, &to, options).await })
+            .await
+    }
+}
+
+/// A [`MultipartUpload`] uploading its parts on the IO runtime
+///
+/// Completing or aborting the upload is a single small request, and runs on
+/// the caller's runtime.
+#[derive(Debug)]
+struct IoRuntimeUpload {
+    inner: Box<dyn MultipartUpload>,
+    handle: Handle,
+}
+
+#[async_trait]
+impl MultipartUpload for IoRuntimeUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let part = self.inner.put_part(data);
+        let handle = self.handle.clone();
+        async move { spawn_io(&handle, part).await }.boxed()
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        self.inner.complete().await
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.inner.abort().await
+    }
+}
+
+/// Runs `future` on the runtime of `handle`
+async fn spawn_io<T, F>(handle: &Handle, future: F) -> object_store::Result<T>
+where
+    F: Future<Output = object_store::Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    SpawnedTask::spawn_on(future, handle)
+        .join_unwind()
+        .await
+        .map_err(join_error)?
+}
+
+/// Polls `stream` on the runtime of `handle`, returning a stream of its
+/// items to be consumed on any runtime
+fn spawn_stream<T: Send + 'static>(
+    handle: &Handle,
+    mut stream: BoxStream<'static, object_store::Result<T>>,
+) -> BoxStream<'static, object_store::Result<T>> {
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+    let task = SpawnedTask::spawn_on(
+        async move {
+            while let Some(item) = stream.next().await {
+                if sender.send(item).await.is_err() {
+                    // the returned stream was dropped
+                    break;
+                }
+            }
+        },
+        handle,
+    );
+
+    stream::unfold(Some((receiver, task)), |state| async move {
+        let (mut receiver, task) = state?;
+        match receiver.recv().await {
+            Some(item) => Some((item, Some((receiver, task)))),
+            // the stream is exhausted, or the task was cancelled
+            None => match task.join_unwind().await {
+                Ok(()) => None,
+                Err(e) => Some((Err(join_error(e)), None)),
+            },
+        }
+    })
+    .boxed()
+}
+
+fn join_error(e: JoinError) -> object_store::Error {
+    object_store::Error::Generic {
+        store: "IoRuntime",
+        source: Box::new(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::task::Poll;
+    use std::thread;
+
+    use futures::TryStreamExt;
+    use object_store::ObjectStoreExt;
+    use object_store::memory::InMemory;
+    use parking_lot::Mutex;
+    use tokio::runtime::{Builder, Runtime};
+
+    use crate::object_store::ObjectStoreUrl;
+    use crate::runtime_env::RuntimeEnvBuilder;
+
+    /// An [`InMemory`] store recording the names of the threads serving its
+    /// requests and streams
+    #[derive(Debug, Default)]
+    struct ThreadRecordingStore {
+        inner: InMemory,
+        threads: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ThreadRecordingStore {
+        fn record(threads: &Mutex<Vec<String>>) {
+            let name = thread::current().name().unwrap_or_default().to_string();
+            threads.lock().push(name);
+        }
+
+        fn recorded(&self) -> Vec<String> {
+            self.threads.lock().clone()
+        }
+    }
+
+    impl Display for ThreadRecordingStore {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "ThreadRecordingStore")
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for ThreadRecordingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            Self::record(&self.threads);
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOptions,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            Self::record(&self.threads);
+            let GetResult {
+                payload,
+                meta,
+                range,
+                attributes,
+            } = self.inner.get_opts(location, options).await?;
+            let GetResultPayload::Stream(stream) = payload else {
+                unreachable!("InMemory returns streams")
+            };
+            let threads = Arc::clone(&self.threads);
+            let stream = stream.inspect(move |_| Self::record(&threads)).boxed();
+            Ok(GetResult {
+                payload: GetResultPayload::Stream(stream),
+                meta,
+                range,
+                attributes,
+            })
+        }
+
+        fn delete_stream(
+            &self,
+            locations: BoxStream<'static, object_store::Result<Path>>,
+        ) -> BoxStream<'static, object_store::Result<Path>> {
+            self.inner.delete_stream(locations)
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+            let threads = Arc::clone(&self.threads);
+            self.inner
+                .list(prefix)
+                .inspect(move |_| Self::record(&threads))
+                .boxed()
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy_opts(
+            &self,
+            from: &Path,
+            to: &Path,
+            options: CopyOptions,
+        ) -> object_store::Result<()> {
+            self.inner.copy_opts(from, to, options).await
+        }
+    }
+
+    fn io_runtime() -> Runtime {
+        Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("io")
+            .enable_all()
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn requests_run_on_io_runtime() {
+        let io_runtime = io_runtime();
+        let recording = Arc::new(ThreadRecordingStore::default());
+        let runtime_env = RuntimeEnvBuilder::new()
+            .with_io_runtime(io_runtime.handle().clone())
+            .build()
+            .unwrap();
+        let url = ObjectStoreUrl::parse("memory://").unwrap();
+        runtime_env.register_object_store(url.as_ref(), Arc::clone(&recording) as _);
+        let store = runtime_env.object_store(&url).unwrap();
+        assert_eq!(store.to_string(), "IoRuntime(ThreadRecordingStore)");
+
+        let location = Path::from("data.bin");
+        store
+            .put(&location, PutPayload::from_static(b"0123456789"))
+            .await
+            .unwrap();
+        let bytes = store.get(&location).await.unwrap().bytes().await.unwrap();
+        assert_eq!(bytes.as_ref(), b"0123456789");
+        let listed = store.list(None).try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(listed.len(), 1);
+
+        // at least the put, the get, the bytes of the get, and the listing
+        let threads = recording.recorded();
+        assert!(threads.len() >= 4, "{threads:?}");
+        assert!(threads.iter().all(|name| name == "io"), "{threads:?}");
+
+        io_runtime.shutdown_background();
+    }
+
+    #[tokio::test]
+    async fn requests_run_on_caller_runtime_by_default() {
+        let recording = Arc::new(ThreadRecordingStore::default());
+        let runtime_env = RuntimeEnvBuilder::new().build().unwrap();
+        let url = ObjectStoreUrl::parse("memory://").unwrap();
+        runtime_env.register_object_store(url.as_ref(), Arc::clone(&recording) as _);
+        let store = runtime_env.object_store(&url).unwrap();
+
+        let location = Path::from("data.bin");
+        store
+            .put(&location, PutPayload::from_static(b"0123456789"))
+            .await
+            .unwrap();
+        assert!(recording.recorded().iter().all(|name| name != "io"));
+    }
+
+    #[tokio::test]
+    async fn dropping_stream_cancels_task() {
+        let io_runtime = io_runtime();
+        let (sender, mut receiver) = mpsc::channel::<()>(1);
+        let pending = stream::poll_fn(move |_| {
+            // keeps the sender alive as long as the stream
+            let _ = &sender;
+            Poll::<Option<object_store::Result<()>>>::Pending
+        });
+
+        let stream = spawn_stream(io_runtime.handle(), pending.boxed());
+        drop(stream);
+
+        // the task, and so the sender, are dropped
+        assert!(receiver.recv().await.is_none());
+        io_runtime.shutdown_background();
+    }
+}
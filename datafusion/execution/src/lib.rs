@@ -30,10 +30,13 @@
 pub mod cache;
 pub mod config;
 pub mod disk_manager;
+pub mod io_runtime;
 pub mod memory_pool;
 pub mod object_store;
 #[cfg(feature = "parquet_encryption")]
 pub mod parquet_encryption;
+pub mod plan_span;
+pub mod resource_limits;
 pub mod runtime_env;
 mod stream;
 mod task;
@@ -182,6 +182,10 @@ pub use pool::*;
 ///
 /// * [`TrackConsumersPool`]: Wraps another [`MemoryPool`] and tracks consumers,
 ///   providing better error messages on the largest memory users.
+///
+/// * [`ProfilingMemoryPool`]: Wraps another [`MemoryPool`] and records the peak
+///   memory used by each consumer. `EXPLAIN ANALYZE` reports the peak memory
+///   used by each consumer of the analyzed query when the pool records it.
 pub trait MemoryPool: Send + Sync + std::fmt::Debug + Display {
     /// Return pool name
     fn name(&self) -> &str;
@@ -222,6 +226,54 @@ pub trait MemoryPool: Send + Sync + std::fmt::Debug + Display {
     fn memory_limit(&self) -> MemoryLimit {
         MemoryLimit::Unknown
     }
+
+    /// Return the peak memory usage recorded per [`MemoryConsumer`], if this
+    /// pool records it (see [`ProfilingMemoryPool`]).
+    ///
+    /// The default implementation returns `None`.
+    fn memory_profile(&self) -> Option<Vec<MemoryProfileEntry>> {
+        None
+    }
+}
+
+/// A shared [`MemoryPool`], which can be wrapped by pools such as
+/// [`ProfilingMemoryPool`] without taking ownership of it
+impl MemoryPool for Arc<dyn MemoryPool> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.as_ref().register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.as_ref().unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.as_ref().grow(reservation, additional)
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.as_ref().shrink(reservation, shrink)
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.as_ref().try_grow(reservation, additional)
+    }
+
+    fn reserved(&self) -> usize {
+        self.as_ref().reserved()
+    }
+
+    fn memory_limit(&self) -> MemoryLimit {
+        self.as_ref().memory_limit()
+    }
+
+    fn memory_profile(&self) -> Option<Vec<MemoryProfileEntry>> {
+        self.as_ref().memory_profile()
+    }
 }
 
 /// Memory limit of `MemoryPool`
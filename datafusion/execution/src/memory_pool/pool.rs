@@ -600,6 +600,235 @@ impl<I: MemoryPool> MemoryPool for TrackConsumersPool<I> {
     fn memory_limit(&self) -> MemoryLimit {
         self.inner.memory_limit()
     }
+
+    fn memory_profile(&self) -> Option<Vec<MemoryProfileEntry>> {
+        self.inner.memory_profile()
+    }
+}
+
+/// Peak memory usage recorded by a [`ProfilingMemoryPool`] for all
+/// [`MemoryConsumer`]s sharing the same name.
+///
+/// Operators name their consumers after themselves and, typically, the
+/// partition they run on (e.g. `ExternalSorter[2]`), so each entry attributes
+/// memory to an operator instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryProfileEntry {
+    /// The name of the memory consumer(s)
+    pub name: String,
+    /// The largest number of bytes reserved at any one time by a single
+    /// consumer with this name
+    pub peak: usize,
+    /// The sum of all the reservations made by consumers with this name,
+    /// not accounting for memory that was released. This is not the number
+    /// of bytes allocated: growing a reservation does not allocate memory.
+    pub cumulative_reserved: usize,
+    /// The number of consumers registered with this name
+    pub num_consumers: usize,
+}
+
+/// Live state of a consumer tracked by [`ProfilingMemoryPool`]
+#[derive(Debug)]
+struct ProfiledConsumer {
+    name: String,
+    reserved: usize,
+}
+
+#[derive(Debug, Default)]
+struct ProfilingState {
+    /// Maps consumer_id --> ProfiledConsumer, for registered consumers
+    live: HashMap<usize, ProfiledConsumer>,
+    /// Maps consumer name --> profile, retained after consumers unregister
+    profile: HashMap<String, MemoryProfileEntry>,
+    /// Peak of the total reservation of the pool
+    peak_reserved: usize,
+}
+
+impl ProfilingState {
+    fn entry(&mut self, name: &str) -> &mut MemoryProfileEntry {
+        self.profile
+            .entry(name.to_string())
+            .or_insert_with(|| MemoryProfileEntry {
+                name: name.to_string(),
+                peak: 0,
+                cumulative_reserved: 0,
+                num_consumers: 0,
+            })
+    }
+
+    fn grow(&mut self, consumer_id: usize, additional: usize, pool_reserved: usize) {
+        self.peak_reserved = self.peak_reserved.max(pool_reserved);
+        let Some(consumer) = self.live.get_mut(&consumer_id) else {
+            return;
+        };
+        consumer.reserved += additional;
+        let reserved = consumer.reserved;
+        let name = consumer.name.clone();
+        let entry = self.entry(&name);
+        entry.peak = entry.peak.max(reserved);
+        entry.cumulative_reserved += additional;
+    }
+
+    fn shrink(&mut self, consumer_id: usize, shrink: usize) {
+        if let Some(consumer) = self.live.get_mut(&consumer_id) {
+            consumer.reserved = consumer.reserved.saturating_sub(shrink);
+        }
+    }
+}
+
+/// A [`MemoryPool`] that records the peak memory reserved by each
+/// [`MemoryConsumer`] of the inner memory pool, for investigating memory
+/// usage without an external profiler.
+///
+/// Unlike [`TrackConsumersPool`], which only knows about consumers that are
+/// currently registered, the profile is retained after consumers (and the
+/// queries that created them) finish, until [`Self::reset`] is called.
+///
+/// When a query is run with `EXPLAIN ANALYZE` using a pool that records a
+/// profile (see [`MemoryPool::memory_profile`]), the output includes the
+/// profile of the consumers of that query only, which are recorded by a
+/// [`ProfilingMemoryPool`] of the query wrapping the pool (see
+/// [`TaskContext::with_memory_profile`]).
+///
+/// [`TaskContext::with_memory_profile`]: crate::TaskContext::with_memory_profile
+///
+/// # Example
+///
+/// ```rust
+/// use datafusion_execution::memory_pool::{
+///     GreedyMemoryPool, MemoryConsumer, MemoryPool, ProfilingMemoryPool,
+/// };
+/// use std::sync::Arc;
+///
+/// let pool: Arc<dyn MemoryPool> =
+///     Arc::new(ProfilingMemoryPool::new(GreedyMemoryPool::new(1024)));
+///
+/// let reservation = MemoryConsumer::new("ExternalSorter[0]").register(&pool);
+/// reservation.grow(100);
+/// reservation.shrink(50);
+/// reservation.grow(10);
+/// drop(reservation);
+///
+/// let profile = pool.memory_profile().unwrap();
+/// assert_eq!(profile[0].name, "ExternalSorter[0]");
+/// assert_eq!(profile[0].peak, 100);
+/// assert_eq!(profile[0].cumulative_reserved, 110);
+/// ```
+#[derive(Debug)]
+pub struct ProfilingMemoryPool<I> {
+    /// The wrapped memory pool that actually handles reservation logic
+    inner: I,
+    state: Mutex<ProfilingState>,
+}
+
+impl<I: MemoryPool> Display for ProfilingMemoryPool<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(inner_pool: {})", self.name(), &self.inner)
+    }
+}
+
+impl<I: MemoryPool> ProfilingMemoryPool<I> {
+    /// Creates a new [`ProfilingMemoryPool`] wrapping `inner`
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            state: Default::default(),
+        }
+    }
+
+    /// Returns a reference to the wrapped inner [`MemoryPool`].
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns the largest total reservation of the pool observed so far
+    pub fn peak_reserved(&self) -> usize {
+        self.state.lock().peak_reserved
+    }
+
+    /// Returns the recorded profile, ordered by decreasing peak usage
+    pub fn profile(&self) -> Vec<MemoryProfileEntry> {
+        let mut profile = self
+            .state
+            .lock()
+            .profile
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        profile.sort_by(|a, b| b.peak.cmp(&a.peak).then_with(|| a.name.cmp(&b.name)));
+        profile
+    }
+
+    /// Clears the recorded profile. Consumers that are currently registered
+    /// continue to be tracked.
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        state.profile.clear();
+        state.peak_reserved = self.inner.reserved();
+    }
+}
+
+impl<I: MemoryPool> MemoryPool for ProfilingMemoryPool<I> {
+    fn name(&self) -> &str {
+        "profiling"
+    }
+
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.inner.register(consumer);
+
+        let mut state = self.state.lock();
+        state.entry(consumer.name()).num_consumers += 1;
+        state.live.insert(
+            consumer.id(),
+            ProfiledConsumer {
+                name: consumer.name().to_string(),
+                reserved: 0,
+            },
+        );
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.inner.unregister(consumer);
+        self.state.lock().live.remove(&consumer.id());
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.inner.grow(reservation, additional);
+        self.state.lock().grow(
+            reservation.consumer().id(),
+            additional,
+            self.inner.reserved(),
+        );
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.inner.shrink(reservation, shrink);
+        self.state
+            .lock()
+            .shrink(reservation.consumer().id(), shrink);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.inner.try_grow(reservation, additional)?;
+        self.state.lock().grow(
+            reservation.consumer().id(),
+            additional,
+            self.inner.reserved(),
+        );
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.inner.reserved()
+    }
+
+    fn memory_limit(&self) -> MemoryLimit {
+        self.inner.memory_limit()
+    }
+
+    fn memory_profile(&self) -> Option<Vec<MemoryProfileEntry>> {
+        Some(self.profile())
+    }
 }
 
 fn provide_top_memory_consumers_to_error_msg(
@@ -1046,4 +1275,59 @@ mod tests {
             "TrackConsumersPool<UnboundedMemoryPool> Display"
         );
     }
+
+    #[test]
+    fn test_profiling_pool() {
+        let profiling = Arc::new(ProfilingMemoryPool::new(GreedyMemoryPool::new(100)));
+        let pool: Arc<dyn MemoryPool> = Arc::clone(&profiling) as _;
+
+        let r1 = MemoryConsumer::new("r1").register(&pool);
+        let r2 = MemoryConsumer::new("r2").register(&pool);
+        r1.grow(50);
+        r2.try_grow(30).unwrap();
+        r1.shrink(40);
+        r1.grow(20);
+        // failed allocations are not recorded
+        r2.try_grow(100).unwrap_err();
+        assert_eq!(profiling.peak_reserved(), 80);
+
+        // a second consumer with the same name is reported together
+        drop(r1);
+        let r1 = MemoryConsumer::new("r1").register(&pool);
+        r1.grow(10);
+        drop(r1);
+        drop(r2);
+
+        assert_eq!(pool.reserved(), 0);
+        assert_eq!(
+            pool.memory_profile().unwrap(),
+            vec![
+                MemoryProfileEntry {
+                    name: "r1".to_string(),
+                    peak: 50,
+                    cumulative_reserved: 80,
+                    num_consumers: 2,
+                },
+                MemoryProfileEntry {
+                    name: "r2".to_string(),
+                    peak: 30,
+                    cumulative_reserved: 30,
+                    num_consumers: 1,
+                },
+            ]
+        );
+
+        profiling.reset();
+        assert!(profiling.profile().is_empty());
+        assert_eq!(profiling.peak_reserved(), 0);
+
+        // pools that do not profile return None
+        assert!(GreedyMemoryPool::new(100).memory_profile().is_none());
+        // TrackConsumersPool delegates to the inner pool
+        let tracked = TrackConsumersPool::new(
+            ProfilingMemoryPool::new(UnboundedMemoryPool::default()),
+            NonZeroUsize::new(3).unwrap(),
+        );
+        assert_eq!(tracked.memory_profile(), Some(vec![]));
+    }
 }
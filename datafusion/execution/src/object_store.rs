@@ -26,6 +26,8 @@ use datafusion_common::{
 use object_store::ObjectStore;
 #[cfg(not(target_arch = "wasm32"))]
 use object_store::local::LocalFileSystem;
+#[cfg(target_arch = "wasm32")]
+use object_store::memory::InMemory;
 use std::sync::Arc;
 use url::Url;
 
@@ -213,18 +215,22 @@ impl DefaultObjectStoreRegistry {
         Self { object_stores }
     }
 
-    /// Default without any backend registered.
+    /// This will register an [`InMemory`] store to handle `file://` paths, as
+    /// `wasm32` targets such as browsers have no local file system. Files can
+    /// be written to it with [`ObjectStoreRegistry::get_store`] before they are
+    /// queried.
     #[cfg(target_arch = "wasm32")]
     pub fn new() -> Self {
         let object_stores: DashMap<String, Arc<dyn ObjectStore>> = DashMap::new();
+        object_stores.insert("file://".to_string(), Arc::new(InMemory::new()));
         Self { object_stores }
     }
 }
 
 ///
 /// Stores are registered based on the scheme, host and port of the provided URL
-/// with a [`LocalFileSystem::new`] automatically registered for `file://` (if the
-/// target arch is not `wasm32`).
+/// with a [`LocalFileSystem::new`] automatically registered for `file://`, or an
+/// in-memory store if the target arch is `wasm32`.
 ///
 /// For example:
 ///
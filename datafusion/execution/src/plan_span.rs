@@ -0,0 +1,88 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PlanSpan`] and [`PlanSpanExporter`] for exporting the profile of executed
+//! plans to tracing systems such as OpenTelemetry
+
+use std::fmt::Debug;
+use std::time::SystemTime;
+
+/// The profile of an executed operator of a physical plan, and of its inputs.
+///
+/// A [`PlanSpan`] tree mirrors the tree of the executed plan: each span
+/// describes one operator, with the time it started and finished executing
+/// and its metrics (e.g., `output_rows` or `elapsed_compute`) as attributes.
+/// Its structure is modeled after tracing spans, so that [`PlanSpanExporter`]s
+/// can map it directly to the spans of tracing systems like OpenTelemetry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanSpan {
+    /// The name of the operator, e.g. `ProjectionExec`
+    pub name: String,
+    /// When the operator started executing, if known
+    pub start_time: Option<SystemTime>,
+    /// When the operator finished executing, if known
+    pub end_time: Option<SystemTime>,
+    /// Attributes of the span, such as the description of the operator and
+    /// its metrics, aggregated over all its partitions
+    pub attributes: Vec<(String, String)>,
+    /// The spans of the inputs of the operator
+    pub children: Vec<PlanSpan>,
+}
+
+impl PlanSpan {
+    /// Create a new span named `name`, without timings, attributes or children
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            start_time: None,
+            end_time: None,
+            attributes: vec![],
+            children: vec![],
+        }
+    }
+
+    /// Returns the value of the attribute `key`, if any
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Exports the [`PlanSpan`]s of executed plans, for example as OpenTelemetry
+/// spans, so that query profiles show up in observability stacks like Jaeger
+/// or Tempo.
+///
+/// Exporting is opt-in: register an exporter with
+/// [`RuntimeEnvBuilder::with_plan_span_exporter`] and DataFusion calls
+/// [`Self::export`] with the profile of every plan executed to a single
+/// stream (e.g. with `DataFrame::collect`, or `EXPLAIN ANALYZE`) once the
+/// execution completes.
+///
+/// DataFusion does not depend on any tracing library: an OpenTelemetry
+/// exporter creates a span for each [`PlanSpan`], as a child of the span of
+/// its parent, with the span's start and end times and attributes.
+///
+/// [`RuntimeEnvBuilder::with_plan_span_exporter`]: crate::runtime_env::RuntimeEnvBuilder::with_plan_span_exporter
+pub trait PlanSpanExporter: Debug + Send + Sync {
+    /// Export the profile of an executed plan, whose root is `span`.
+    ///
+    /// This is called on the thread polling the plan's results, so
+    /// implementations should not block.
+    fn export(&self, span: &PlanSpan);
+}
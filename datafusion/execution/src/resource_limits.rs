@@ -0,0 +1,419 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`QueryResourceLimits`] and [`QueryResourceTracker`] for limiting the
+//! resources used by a single query
+
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use datafusion_common::instant::Instant;
+use datafusion_common::{DataFusionError, Result, resources_datafusion_err};
+
+use crate::memory_pool::{
+    MemoryConsumer, MemoryLimit, MemoryPool, MemoryProfileEntry, MemoryReservation,
+    human_readable_size,
+};
+
+/// Limits on the resources a single query may use.
+///
+/// Limits are attached to the [`TaskContext`] of a query with
+/// [`TaskContext::with_resource_limits`], and are enforced cooperatively:
+///
+/// * `max_memory` is checked whenever an operator reserves memory, so that
+///   operators that can spill do so before failing the query
+/// * `max_spilled_bytes` is checked whenever an operator writes to a spill file
+/// * `max_execution_time` is checked whenever the output streams of the query
+///   are polled, as well as when memory is reserved or data is spilled
+/// * `max_output_rows` is checked whenever the query produces a batch
+///
+/// When a limit is exceeded, the query fails with a
+/// [`DataFusionError::ResourcesExhausted`] naming the limit and describing
+/// the resources used by the query so far, which allows services embedding
+/// DataFusion to stop a runaway query without affecting other queries.
+///
+/// [`TaskContext`]: crate::TaskContext
+/// [`TaskContext::with_resource_limits`]: crate::TaskContext::with_resource_limits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryResourceLimits {
+    /// The maximum number of bytes of memory the query may reserve, in
+    /// addition to the limit of the memory pool of the runtime
+    pub max_memory: Option<usize>,
+    /// The maximum number of bytes the query may write to spill files
+    pub max_spilled_bytes: Option<usize>,
+    /// The maximum wall clock time the query may execute for
+    pub max_execution_time: Option<Duration>,
+    /// The maximum number of rows the query may produce
+    pub max_output_rows: Option<usize>,
+}
+
+impl QueryResourceLimits {
+    /// Create limits that do not limit anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of bytes of memory the query may reserve
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Set the maximum number of bytes the query may write to spill files
+    pub fn with_max_spilled_bytes(mut self, max_spilled_bytes: usize) -> Self {
+        self.max_spilled_bytes = Some(max_spilled_bytes);
+        self
+    }
+
+    /// Set the maximum wall clock time the query may execute for
+    pub fn with_max_execution_time(mut self, max_execution_time: Duration) -> Self {
+        self.max_execution_time = Some(max_execution_time);
+        self
+    }
+
+    /// Set the maximum number of rows the query may produce
+    pub fn with_max_output_rows(mut self, max_output_rows: usize) -> Self {
+        self.max_output_rows = Some(max_output_rows);
+        self
+    }
+}
+
+/// A limit of [`QueryResourceLimits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryResourceLimit {
+    /// [`QueryResourceLimits::max_memory`]
+    Memory,
+    /// [`QueryResourceLimits::max_spilled_bytes`]
+    SpilledBytes,
+    /// [`QueryResourceLimits::max_execution_time`]
+    ExecutionTime,
+    /// [`QueryResourceLimits::max_output_rows`]
+    OutputRows,
+}
+
+impl Display for QueryResourceLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Memory => write!(f, "max_memory"),
+            Self::SpilledBytes => write!(f, "max_spilled_bytes"),
+            Self::ExecutionTime => write!(f, "max_execution_time"),
+            Self::OutputRows => write!(f, "max_output_rows"),
+        }
+    }
+}
+
+/// The resources used by a query, as tracked by a [`QueryResourceTracker`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryResourceUsage {
+    /// The number of bytes of memory currently reserved by the query
+    pub memory: usize,
+    /// The number of bytes written to spill files by the query
+    pub spilled_bytes: usize,
+    /// The wall clock time since the query started executing
+    pub execution_time: Duration,
+    /// The number of rows produced by the query
+    pub output_rows: usize,
+}
+
+impl Display for QueryResourceUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "memory: {}, spilled_bytes: {}, execution_time: {:?}, output_rows: {}",
+            human_readable_size(self.memory),
+            human_readable_size(self.spilled_bytes),
+            self.execution_time,
+            self.output_rows
+        )
+    }
+}
+
+/// Tracks the resources used by a query and enforces its
+/// [`QueryResourceLimits`].
+///
+/// A tracker is created by [`TaskContext::with_resource_limits`] and shared by
+/// all the operators and partitions of the query, which report the resources
+/// they use to it.
+///
+/// [`TaskContext::with_resource_limits`]: crate::TaskContext::with_resource_limits
+#[derive(Debug)]
+pub struct QueryResourceTracker {
+    limits: QueryResourceLimits,
+    /// When the query started executing, see [`Self::start`]
+    started_at: OnceLock<Instant>,
+    memory: AtomicUsize,
+    spilled_bytes: AtomicUsize,
+    output_rows: AtomicUsize,
+}
+
+impl QueryResourceTracker {
+    /// Create a new tracker enforcing `limits`
+    pub fn new(limits: QueryResourceLimits) -> Self {
+        Self {
+            limits,
+            started_at: OnceLock::new(),
+            memory: AtomicUsize::new(0),
+            spilled_bytes: AtomicUsize::new(0),
+            output_rows: AtomicUsize::new(0),
+        }
+    }
+
+    /// The limits enforced by this tracker
+    pub fn limits(&self) -> &QueryResourceLimits {
+        &self.limits
+    }
+
+    /// Record that the query started executing, if not done already. The
+    /// execution time of the query is measured from the first call.
+    pub fn start(&self) {
+        self.started_at.get_or_init(Instant::now);
+    }
+
+    /// The resources used by the query so far
+    pub fn usage(&self) -> QueryResourceUsage {
+        QueryResourceUsage {
+            memory: self.memory.load(Ordering::Relaxed),
+            spilled_bytes: self.spilled_bytes.load(Ordering::Relaxed),
+            execution_time: self
+                .started_at
+                .get()
+                .map(Instant::elapsed)
+                .unwrap_or_default(),
+            output_rows: self.output_rows.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns an error if the query has executed for longer than
+    /// [`QueryResourceLimits::max_execution_time`]
+    pub fn check_execution_time(&self) -> Result<()> {
+        match (self.limits.max_execution_time, self.started_at.get()) {
+            (Some(max), Some(started_at)) if started_at.elapsed() > max => {
+                Err(self.limit_exceeded(QueryResourceLimit::ExecutionTime))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record that the query wrote `bytes` to spill files, returning an
+    /// error if it has now spilled more than
+    /// [`QueryResourceLimits::max_spilled_bytes`]
+    pub fn add_spilled_bytes(&self, bytes: usize) -> Result<()> {
+        let spilled_bytes =
+            self.spilled_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if let Some(max) = self.limits.max_spilled_bytes
+            && spilled_bytes > max
+        {
+            return Err(self.limit_exceeded(QueryResourceLimit::SpilledBytes));
+        }
+        self.check_execution_time()
+    }
+
+    /// Record that the query produced `rows` rows, returning an error if it
+    /// has now produced more than [`QueryResourceLimits::max_output_rows`]
+    pub fn add_output_rows(&self, rows: usize) -> Result<()> {
+        let output_rows = self.output_rows.fetch_add(rows, Ordering::Relaxed) + rows;
+        if let Some(max) = self.limits.max_output_rows
+            && output_rows > max
+        {
+            return Err(self.limit_exceeded(QueryResourceLimit::OutputRows));
+        }
+        self.check_execution_time()
+    }
+
+    /// Returns a [`MemoryPool`] that reserves memory from `inner`, and
+    /// records the memory reserved through it as used by the query.
+    /// Reservations fail if the query would use more than
+    /// [`QueryResourceLimits::max_memory`].
+    pub fn memory_pool(
+        self: &Arc<Self>,
+        inner: Arc<dyn MemoryPool>,
+    ) -> Arc<dyn MemoryPool> {
+        Arc::new(QueryMemoryPool {
+            inner,
+            tracker: Arc::clone(self),
+        })
+    }
+
+    fn try_grow_memory(&self, additional: usize) -> Result<()> {
+        self.check_execution_time()?;
+        let Some(max) = self.limits.max_memory else {
+            self.memory.fetch_add(additional, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.memory
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |memory| {
+                let new_memory = memory.checked_add(additional)?;
+                (new_memory <= max).then_some(new_memory)
+            })
+            .map_err(|_| self.limit_exceeded(QueryResourceLimit::Memory))?;
+        Ok(())
+    }
+
+    fn limit_exceeded(&self, limit: QueryResourceLimit) -> DataFusionError {
+        let max = match limit {
+            QueryResourceLimit::Memory => self.limits.max_memory.map(human_readable_size),
+            QueryResourceLimit::SpilledBytes => {
+                self.limits.max_spilled_bytes.map(human_readable_size)
+            }
+            QueryResourceLimit::ExecutionTime => {
+                self.limits.max_execution_time.map(|max| format!("{max:?}"))
+            }
+            QueryResourceLimit::OutputRows => {
+                self.limits.max_output_rows.map(|max| max.to_string())
+            }
+        }
+        .unwrap_or_default();
+        resources_datafusion_err!(
+            "Query exceeded its {limit} limit of {max}. Resources used by the query: {}",
+            self.usage()
+        )
+    }
+}
+
+/// The [`MemoryPool`] of a query with [`QueryResourceLimits`], see
+/// [`QueryResourceTracker::memory_pool`]
+#[derive(Debug)]
+struct QueryMemoryPool {
+    inner: Arc<dyn MemoryPool>,
+    tracker: Arc<QueryResourceTracker>,
+}
+
+impl Display for QueryMemoryPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(inner_pool: {})", self.name(), self.inner)
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn name(&self) -> &str {
+        "query"
+    }
+
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.inner.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.inner.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.inner.grow(reservation, additional);
+        self.tracker.memory.fetch_add(additional, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.inner.shrink(reservation, shrink);
+        self.tracker.memory.fetch_sub(shrink, Ordering::Relaxed);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.tracker.try_grow_memory(additional)?;
+        self.inner
+            .try_grow(reservation, additional)
+            .inspect_err(|_| {
+                self.tracker.memory.fetch_sub(additional, Ordering::Relaxed);
+            })
+    }
+
+    fn reserved(&self) -> usize {
+        self.inner.reserved()
+    }
+
+    fn memory_limit(&self) -> MemoryLimit {
+        match (self.inner.memory_limit(), self.tracker.limits.max_memory) {
+            (MemoryLimit::Finite(limit), Some(max)) => {
+                MemoryLimit::Finite(limit.min(max))
+            }
+            (_, Some(max)) => MemoryLimit::Finite(max),
+            (limit, None) => limit,
+        }
+    }
+
+    fn memory_profile(&self) -> Option<Vec<MemoryProfileEntry>> {
+        self.inner.memory_profile()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_pool::UnboundedMemoryPool;
+
+    #[test]
+    fn query_memory_limit() {
+        let tracker = Arc::new(QueryResourceTracker::new(
+            QueryResourceLimits::new().with_max_memory(100),
+        ));
+        let pool = tracker.memory_pool(Arc::new(UnboundedMemoryPool::default()));
+
+        let r1 = MemoryConsumer::new("r1").register(&pool);
+        r1.try_grow(60).unwrap();
+        let r2 = MemoryConsumer::new("r2").register(&pool);
+        let err = r2.try_grow(50).unwrap_err().strip_backtrace();
+        assert_eq!(
+            err,
+            "Resources exhausted: Query exceeded its max_memory limit of 100.0 B. Resources used by the query: memory: 60.0 B, spilled_bytes: 0.0 B, execution_time: 0ns, output_rows: 0"
+        );
+        assert_eq!(tracker.usage().memory, 60);
+
+        drop(r1);
+        r2.try_grow(50).unwrap();
+        assert_eq!(tracker.usage().memory, 50);
+        assert_eq!(pool.reserved(), 50);
+    }
+
+    #[test]
+    fn query_spill_and_output_limits() {
+        let tracker = QueryResourceTracker::new(
+            QueryResourceLimits::new()
+                .with_max_spilled_bytes(1024)
+                .with_max_output_rows(10),
+        );
+
+        tracker.add_spilled_bytes(1000).unwrap();
+        let err = tracker
+            .add_spilled_bytes(100)
+            .unwrap_err()
+            .strip_backtrace();
+        assert!(err.contains("Query exceeded its max_spilled_bytes limit of 1024.0 B"));
+
+        tracker.add_output_rows(10).unwrap();
+        let err = tracker.add_output_rows(1).unwrap_err().strip_backtrace();
+        assert!(err.contains("Query exceeded its max_output_rows limit of 10"));
+        assert!(err.contains("output_rows: 11"));
+    }
+
+    #[test]
+    fn query_execution_time_limit() {
+        let tracker = QueryResourceTracker::new(
+            QueryResourceLimits::new().with_max_execution_time(Duration::ZERO),
+        );
+        // the execution time is only measured once the query started
+        tracker.check_execution_time().unwrap();
+
+        tracker.start();
+        std::thread::sleep(Duration::from_millis(1));
+        let err = tracker
+            .check_execution_time()
+            .unwrap_err()
+            .strip_backtrace();
+        assert!(err.contains("Query exceeded its max_execution_time limit of 0ns"));
+    }
+}
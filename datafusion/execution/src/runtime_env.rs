@@ -29,8 +29,10 @@ use crate::{
 };
 
 use crate::cache::cache_manager::{CacheManager, CacheManagerConfig};
+use crate::io_runtime::IoRuntimeObjectStore;
 #[cfg(feature = "parquet_encryption")]
 use crate::parquet_encryption::{EncryptionFactory, EncryptionFactoryRegistry};
+use crate::plan_span::PlanSpanExporter;
 use datafusion_common::{Result, config::ConfigEntry};
 use object_store::ObjectStore;
 use std::sync::Arc;
@@ -39,6 +41,7 @@ use std::{
     num::NonZeroUsize,
 };
 use std::{path::PathBuf, time::Duration};
+use tokio::runtime::Handle;
 use url::Url;
 
 #[derive(Clone)]
@@ -52,6 +55,8 @@ use url::Url;
 /// * [`DiskManager`]: Manage temporary files on local disk
 /// * [`CacheManager`]: Manage temporary cache data during the session lifetime
 /// * [`ObjectStoreRegistry`]: Manage mapping URLs to object store instances
+/// * [`PlanSpanExporter`]: Export the profiles of executed plans
+/// * IO runtime: Run object store requests apart from the CPU-bound operators
 ///
 /// # Example: Create default `RuntimeEnv`
 /// ```
@@ -83,6 +88,11 @@ pub struct RuntimeEnv {
     /// Parquet encryption factory registry
     #[cfg(feature = "parquet_encryption")]
     pub parquet_encryption_factory_registry: Arc<EncryptionFactoryRegistry>,
+    /// Exporter of the profiles of executed plans, if any
+    pub plan_span_exporter: Option<Arc<dyn PlanSpanExporter>>,
+    /// Runtime on which the requests of the object stores run, if any. See
+    /// [`RuntimeEnvBuilder::with_io_runtime`]
+    pub io_runtime: Option<Handle>,
 }
 
 impl Debug for RuntimeEnv {
@@ -103,6 +113,7 @@ fn create_runtime_config_entries(
     metadata_cache_limit: Option<String>,
     list_files_cache_limit: Option<String>,
     list_files_cache_ttl: Option<String>,
+    file_range_cache_limit: Option<String>,
 ) -> Vec<ConfigEntry> {
     vec![
         ConfigEntry {
@@ -135,6 +146,11 @@ fn create_runtime_config_entries(
             value: list_files_cache_ttl,
             description: "TTL (time-to-live) of the entries in the list file cache. Supports units m (minutes), and s (seconds). Example: '2m' for 2 minutes.",
         },
+        ConfigEntry {
+            key: "datafusion.runtime.file_range_cache_limit".to_string(),
+            value: file_range_cache_limit,
+            description: "Maximum memory to use for caching byte ranges of data files such as Parquet column chunks. Supports suffixes K (kilobytes), M (megabytes), and G (gigabytes) or '0' to disable it.",
+        },
     ]
 }
 
@@ -195,8 +211,15 @@ impl RuntimeEnv {
     /// Retrieves a `ObjectStore` instance for a url by consulting the
     /// registry. See [`ObjectStoreRegistry::get_store`] for more
     /// details.
+    ///
+    /// If an IO runtime is configured, the returned store runs its requests
+    /// on it, see [`IoRuntimeObjectStore`].
     pub fn object_store(&self, url: impl AsRef<Url>) -> Result<Arc<dyn ObjectStore>> {
-        self.object_store_registry.get_store(url.as_ref())
+        let store = self.object_store_registry.get_store(url.as_ref())?;
+        Ok(match &self.io_runtime {
+            Some(handle) => Arc::new(IoRuntimeObjectStore::new(store, handle.clone())),
+            None => store,
+        })
     }
 
     /// Returns the current spilling progress
@@ -296,6 +319,13 @@ impl RuntimeEnv {
             .get_list_files_cache_ttl()
             .map(format_duration);
 
+        let file_range_cache_limit = self.cache_manager.get_file_range_cache_limit();
+        let file_range_cache_value = format_byte_size(
+            file_range_cache_limit
+                .try_into()
+                .expect("File range cache size conversion failed"),
+        );
+
         create_runtime_config_entries(
             memory_limit_value,
             Some(max_temp_dir_value),
@@ -303,6 +333,7 @@ impl RuntimeEnv {
             Some(metadata_cache_value),
             Some(list_files_cache_value),
             list_files_cache_ttl,
+            Some(file_range_cache_value),
         )
     }
 }
@@ -334,6 +365,11 @@ pub struct RuntimeEnvBuilder {
     /// Parquet encryption factory registry
     #[cfg(feature = "parquet_encryption")]
     pub parquet_encryption_factory_registry: Arc<EncryptionFactoryRegistry>,
+    /// Exporter of the profiles of executed plans, see [`PlanSpanExporter`]
+    pub plan_span_exporter: Option<Arc<dyn PlanSpanExporter>>,
+    /// Runtime on which the requests of the object stores run, see
+    /// [`Self::with_io_runtime`]
+    pub io_runtime: Option<Handle>,
 }
 
 impl Default for RuntimeEnvBuilder {
@@ -353,6 +389,8 @@ impl RuntimeEnvBuilder {
             object_store_registry: Arc::new(DefaultObjectStoreRegistry::default()),
             #[cfg(feature = "parquet_encryption")]
             parquet_encryption_factory_registry: Default::default(),
+            plan_span_exporter: None,
+            io_runtime: None,
         }
     }
 
@@ -438,6 +476,50 @@ impl RuntimeEnvBuilder {
         self
     }
 
+    /// Specifies the memory limit for the cache of byte ranges of data files,
+    /// in bytes. A limit of 0 disables the cache.
+    pub fn with_file_range_cache_limit(mut self, limit: usize) -> Self {
+        self.cache_manager = self.cache_manager.with_file_range_cache_limit(limit);
+        self
+    }
+
+    /// Export the profile of every executed plan with `exporter`, for example
+    /// to OpenTelemetry. See [`PlanSpanExporter`] for details.
+    pub fn with_plan_span_exporter(
+        mut self,
+        exporter: Arc<dyn PlanSpanExporter>,
+    ) -> Self {
+        self.plan_span_exporter = Some(exporter);
+        self
+    }
+
+    /// Run the requests of the object stores on the runtime of `handle`,
+    /// typically a runtime dedicated to IO, rather than on the runtime
+    /// executing the plans.
+    ///
+    /// This keeps CPU-bound operators, such as decoding Parquet, from
+    /// delaying the network requests (which may then time out), and many
+    /// concurrent requests from delaying the operators. See
+    /// [`IoRuntimeObjectStore`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    /// let io_runtime = tokio::runtime::Builder::new_multi_thread()
+    ///     .thread_name("io")
+    ///     .enable_all()
+    ///     .build()
+    ///     .unwrap();
+    /// let runtime_env = RuntimeEnvBuilder::new()
+    ///     .with_io_runtime(io_runtime.handle().clone())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn with_io_runtime(mut self, handle: Handle) -> Self {
+        self.io_runtime = Some(handle);
+        self
+    }
+
     /// Build a RuntimeEnv
     pub fn build(self) -> Result<RuntimeEnv> {
         let Self {
@@ -448,6 +530,8 @@ impl RuntimeEnvBuilder {
             object_store_registry,
             #[cfg(feature = "parquet_encryption")]
             parquet_encryption_factory_registry,
+            plan_span_exporter,
+            io_runtime,
         } = self;
         let memory_pool =
             memory_pool.unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
@@ -464,6 +548,8 @@ impl RuntimeEnvBuilder {
             object_store_registry,
             #[cfg(feature = "parquet_encryption")]
             parquet_encryption_factory_registry,
+            plan_span_exporter,
+            io_runtime,
         })
     }
 
@@ -487,6 +573,10 @@ impl RuntimeEnvBuilder {
                 runtime_env.cache_manager.get_file_metadata_cache(),
             ),
             metadata_cache_limit: runtime_env.cache_manager.get_metadata_cache_limit(),
+            file_range_cache: runtime_env.cache_manager.get_file_range_cache(),
+            file_range_cache_limit: runtime_env
+                .cache_manager
+                .get_file_range_cache_limit(),
         };
 
         Self {
@@ -502,6 +592,8 @@ impl RuntimeEnvBuilder {
             parquet_encryption_factory_registry: Arc::clone(
                 &runtime_env.parquet_encryption_factory_registry,
             ),
+            plan_span_exporter: runtime_env.plan_span_exporter.clone(),
+            io_runtime: runtime_env.io_runtime.clone(),
         }
     }
 
@@ -514,6 +606,7 @@ impl RuntimeEnvBuilder {
             Some("50M".to_owned()),
             Some("1M".to_owned()),
             None,
+            Some("0".to_owned()),
         )
     }
 
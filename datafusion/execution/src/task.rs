@@ -16,7 +16,10 @@
 // under the License.
 
 use crate::{
-    config::SessionConfig, memory_pool::MemoryPool, registry::FunctionRegistry,
+    config::SessionConfig,
+    memory_pool::{MemoryPool, ProfilingMemoryPool},
+    registry::FunctionRegistry,
+    resource_limits::{QueryResourceLimits, QueryResourceTracker},
     runtime_env::RuntimeEnv,
 };
 use datafusion_common::{Result, internal_datafusion_err, plan_datafusion_err};
@@ -48,7 +51,7 @@ use std::{collections::HashMap, sync::Arc};
 ///
 /// [`SessionContext`]: https://docs.rs/datafusion/latest/datafusion/execution/context/struct.SessionContext.html
 /// [`ExecutionProps`]: datafusion_expr::execution_props::ExecutionProps
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TaskContext {
     /// Session Id
     session_id: String,
@@ -66,6 +69,12 @@ pub struct TaskContext {
     window_functions: HashMap<String, Arc<WindowUDF>>,
     /// Runtime environment associated with this task context
     runtime: Arc<RuntimeEnv>,
+    /// Memory pool used by the operators of the task, see [`Self::memory_pool`]
+    memory_pool: Arc<dyn MemoryPool>,
+    /// Tracks the resources used by the task, if it has [`QueryResourceLimits`]
+    resource_tracker: Option<Arc<QueryResourceTracker>>,
+    /// Records the memory used by the task, see [`Self::with_memory_profile`]
+    memory_profiler: Option<Arc<ProfilingMemoryPool<Arc<dyn MemoryPool>>>>,
 }
 
 impl Default for TaskContext {
@@ -81,7 +90,10 @@ impl Default for TaskContext {
             higher_order_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
             window_functions: HashMap::new(),
+            memory_pool: Arc::clone(&runtime.memory_pool),
             runtime,
+            resource_tracker: None,
+            memory_profiler: None,
         }
     }
 }
@@ -111,7 +123,10 @@ impl TaskContext {
             higher_order_functions,
             aggregate_functions,
             window_functions,
+            memory_pool: Arc::clone(&runtime.memory_pool),
             runtime,
+            resource_tracker: None,
+            memory_profiler: None,
         }
     }
 
@@ -131,8 +146,19 @@ impl TaskContext {
     }
 
     /// Return the [`MemoryPool`] associated with this [TaskContext]
+    ///
+    /// This is the memory pool of the [`RuntimeEnv`], unless the task has
+    /// [`QueryResourceLimits`], in which case reservations are also tracked
+    /// and limited by the [`QueryResourceTracker`] of the task, or records a
+    /// memory profile (see [`Self::with_memory_profile`]).
     pub fn memory_pool(&self) -> &Arc<dyn MemoryPool> {
-        &self.runtime.memory_pool
+        &self.memory_pool
+    }
+
+    /// Return the [`QueryResourceTracker`] of this [TaskContext], if it has
+    /// [`QueryResourceLimits`]
+    pub fn resource_tracker(&self) -> Option<&Arc<QueryResourceTracker>> {
+        self.resource_tracker.as_ref()
     }
 
     /// Return the [RuntimeEnv] associated with this [TaskContext]
@@ -165,8 +191,58 @@ impl TaskContext {
     /// Update the [`RuntimeEnv`]
     pub fn with_runtime(mut self, runtime: Arc<RuntimeEnv>) -> Self {
         self.runtime = runtime;
+        self.update_memory_pool();
         self
     }
+
+    /// Limit the resources used by the task, see [`QueryResourceLimits`].
+    ///
+    /// The limits apply to all the partitions executed with this
+    /// [`TaskContext`], replacing any limits set previously.
+    pub fn with_resource_limits(mut self, limits: QueryResourceLimits) -> Self {
+        self.resource_tracker = Some(Arc::new(QueryResourceTracker::new(limits)));
+        self.update_memory_pool();
+        self
+    }
+
+    /// Record the peak memory reserved by each consumer of the task, in a
+    /// [`ProfilingMemoryPool`] of its own that wraps the memory pool of the
+    /// task. Unlike the profile of a [`ProfilingMemoryPool`] shared by a
+    /// session, the profile only covers the consumers of this task, which is
+    /// how `EXPLAIN ANALYZE` reports the memory used by a query.
+    ///
+    /// Replaces the profile recorded previously, if any.
+    pub fn with_memory_profile(mut self) -> Self {
+        self.memory_profiler = None;
+        self.update_memory_pool();
+        let profiler = Arc::new(ProfilingMemoryPool::new(Arc::clone(&self.memory_pool)));
+        self.memory_pool = Arc::clone(&profiler) as _;
+        self.memory_profiler = Some(profiler);
+        self
+    }
+
+    /// Return the [`ProfilingMemoryPool`] recording the memory used by the
+    /// task, if enabled with [`Self::with_memory_profile`]
+    pub fn memory_profiler(
+        &self,
+    ) -> Option<&Arc<ProfilingMemoryPool<Arc<dyn MemoryPool>>>> {
+        self.memory_profiler.as_ref()
+    }
+
+    /// Wrap the memory pool of the runtime in the pools tracking the
+    /// resources and recording the memory profile of the task
+    fn update_memory_pool(&mut self) {
+        let mut memory_pool = Arc::clone(&self.runtime.memory_pool);
+        if let Some(tracker) = &self.resource_tracker {
+            memory_pool = tracker.memory_pool(memory_pool);
+        }
+        if self.memory_profiler.is_some() {
+            let profiler = Arc::new(ProfilingMemoryPool::new(memory_pool));
+            memory_pool = Arc::clone(&profiler) as _;
+            self.memory_profiler = Some(profiler);
+        }
+        self.memory_pool = memory_pool;
+    }
 }
 
 impl FunctionRegistry for TaskContext {
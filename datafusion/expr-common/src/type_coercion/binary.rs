@@ -267,6 +267,20 @@ impl<'a> BinaryTypeCoercer<'a> {
                 ret: Int64,
             });
         }
+        Multiply | Divide if is_interval_scaling(*self.op, lhs, rhs) => {
+            // Scaling an interval by a number, e.g. `INTERVAL '1 day' * 1.5`,
+            // carries fractional months and days into the smaller units
+            let (lhs, rhs) = if lhs.is_numeric() {
+                (Float64, Interval(MonthDayNano))
+            } else {
+                (Interval(MonthDayNano), Float64)
+            };
+            return Ok(Signature {
+                lhs,
+                rhs,
+                ret: Interval(MonthDayNano),
+            });
+        }
         Plus | Minus | Multiply | Divide | Modulo  =>  {
             if let Ok(ret) = self.get_result(lhs, rhs) {
 
@@ -326,6 +340,11 @@ impl<'a> BinaryTypeCoercer<'a> {
         Colon => {
             Ok(Signature { lhs: lhs.clone(), rhs: rhs.clone(), ret: lhs.clone() })
         },
+        Arrow | LongArrow if json_access_coercion(lhs, rhs) => {
+            // JSON member or element access, which is rewritten to a
+            // function call, see `datafusion-functions-json`
+            Ok(Signature { lhs: lhs.clone(), rhs: rhs.clone(), ret: Utf8 })
+        }
         IntegerDivide | Arrow | LongArrow | HashArrow | HashLongArrow
         | HashMinus | AtQuestion | Question | QuestionAnd | QuestionPipe => {
             not_impl_err!("Operator {} is not yet supported", self.op)
@@ -362,6 +381,18 @@ fn is_date_minus_date(lhs: &DataType, rhs: &DataType) -> bool {
     )
 }
 
+/// Returns true if `op` scales an interval by a number, i.e. `interval * number`,
+/// `number * interval` or `interval / number`
+pub fn is_interval_scaling(op: Operator, lhs: &DataType, rhs: &DataType) -> bool {
+    match (op, lhs, rhs) {
+        (Operator::Multiply | Operator::Divide, DataType::Interval(_), rhs) => {
+            rhs.is_numeric()
+        }
+        (Operator::Multiply, lhs, DataType::Interval(_)) => lhs.is_numeric(),
+        _ => false,
+    }
+}
+
 /// Coercion rules for mathematics operators between decimal and non-decimal types.
 fn math_decimal_coercion(
     lhs_type: &DataType,
@@ -1607,6 +1638,16 @@ fn ree_coercion(
     }
 }
 
+/// Returns true if `lhs -> rhs` and `lhs ->> rhs` can select a member or an
+/// element of the JSON document `lhs`: `lhs` must be a string, and `rhs` a
+/// string key or an integer index.
+fn json_access_coercion(lhs_type: &DataType, rhs_type: &DataType) -> bool {
+    use arrow::datatypes::DataType::*;
+    matches!(lhs_type, Utf8 | LargeUtf8 | Utf8View | Null)
+        && (matches!(rhs_type, Utf8 | LargeUtf8 | Utf8View | Null)
+            || rhs_type.is_integer())
+}
+
 /// Coercion rules for string concat.
 /// This is a union of string coercion rules and specified rules:
 /// 1. At least one side of lhs and rhs should be string type (Utf8 / LargeUtf8)
@@ -1869,6 +1910,9 @@ fn is_time_with_valid_unit(datatype: &DataType) -> bool {
 /// - If only one has a timezone, coerce the other to match
 /// - If both have a timezone, coerce to the left type
 /// - "UTC" and "+00:00" are considered equivalent
+///
+/// Timestamps of different units are coerced to the finer unit, see
+/// [`timeunit_coercion`].
 fn temporal_coercion_nonstrict_timezone(
     lhs_type: &DataType,
     rhs_type: &DataType,
@@ -1906,6 +1950,9 @@ fn temporal_coercion_nonstrict_timezone(
 /// - If only one has a timezone, coerce the other to match
 /// - If both have a timezone, throw an error
 /// - "UTC" and "+00:00" are considered equivalent
+///
+/// Timestamps of different units are coerced to the finer unit, see
+/// [`timeunit_coercion`].
 fn temporal_coercion_strict_timezone(
     lhs_type: &DataType,
     rhs_type: &DataType,
@@ -1973,13 +2020,16 @@ fn temporal_math_coercion(
         (Date64, Time64(_)) => Some((Timestamp(Nanosecond, None), Duration(Nanosecond))),
         (Time64(_), Date64) => Some((Duration(Nanosecond), Timestamp(Nanosecond, None))),
 
-        // Coerce Duration to match Timestamp's unit,
+        // Coerce Timestamp and Duration to the finer of their units,
         // e.g. Timestamp(ms) + Duration(s) → Timestamp(ms) + Duration(ms)
-        (Timestamp(ts_unit, tz), Duration(_)) => {
-            Some((Timestamp(*ts_unit, tz.clone()), Duration(*ts_unit)))
+        // and Timestamp(s) + Duration(ms) → Timestamp(ms) + Duration(ms)
+        (Timestamp(ts_unit, tz), Duration(duration_unit)) => {
+            let unit = timeunit_coercion(ts_unit, duration_unit);
+            Some((Timestamp(unit, tz.clone()), Duration(unit)))
         }
-        (Duration(_), Timestamp(ts_unit, tz)) => {
-            Some((Duration(*ts_unit), Timestamp(*ts_unit, tz.clone())))
+        (Duration(duration_unit), Timestamp(ts_unit, tz)) => {
+            let unit = timeunit_coercion(ts_unit, duration_unit);
+            Some((Duration(unit), Timestamp(unit, tz.clone())))
         }
         // time - time -> Interval
         (Time32(_) | Time64(_), Time32(_) | Time64(_)) => {
@@ -1992,7 +2042,7 @@ fn temporal_math_coercion(
         (Interval(_), Time32(_) | Time64(_)) => {
             Some((Interval(MonthDayNano), Interval(MonthDayNano)))
         }
-        // Interval * number => Interval
+        // Interval + number => Interval, `*` and `/` are handled by `is_interval_scaling`
         (
             Interval(_),
             Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Float16
@@ -2029,41 +2079,31 @@ fn temporal_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataTyp
         (Date32, Time64(_)) | (Time64(_), Date32) => Some(Timestamp(Nanosecond, None)),
         (Date64, Time32(_)) | (Time32(_), Date64) => Some(Timestamp(Nanosecond, None)),
         (Date64, Time64(_)) | (Time64(_), Date64) => Some(Timestamp(Nanosecond, None)),
-        (Timestamp(_, None), Date64) | (Date64, Timestamp(_, None)) => {
-            Some(Timestamp(Nanosecond, None))
+        // Keep the unit of the timestamp (widened to milliseconds for Date64)
+        // so that timestamps outside the range of nanoseconds can be compared
+        // with dates. Timestamps with a timezone are compared in UTC.
+        (Timestamp(unit, _), Date64) | (Date64, Timestamp(unit, _)) => {
+            Some(Timestamp(timeunit_coercion(unit, &Millisecond), None))
         }
-        (Timestamp(_, _tz), Date64) | (Date64, Timestamp(_, _tz)) => {
-            Some(Timestamp(Nanosecond, None))
-        }
-        (Timestamp(_, None), Date32) | (Date32, Timestamp(_, None)) => {
-            Some(Timestamp(Nanosecond, None))
-        }
-        (Timestamp(_, _tz), Date32) | (Date32, Timestamp(_, _tz)) => {
-            Some(Timestamp(Nanosecond, None))
+        (Timestamp(unit, _), Date32) | (Date32, Timestamp(unit, _)) => {
+            Some(Timestamp(*unit, None))
         }
         _ => None,
     }
 }
 
+/// Returns the finer of two time units, to which timestamps, durations and
+/// times of both units can be cast without losing precision.
+///
+/// Casting to a finer unit can overflow for values far from the epoch (e.g.
+/// `Timestamp(Nanosecond)` covers the years 1677 to 2262), in which case the
+/// cast returns an error rather than silently truncating the other side.
 fn timeunit_coercion(lhs_unit: &TimeUnit, rhs_unit: &TimeUnit) -> TimeUnit {
-    use arrow::datatypes::TimeUnit::*;
     match (lhs_unit, rhs_unit) {
-        (Second, Millisecond) => Second,
-        (Second, Microsecond) => Second,
-        (Second, Nanosecond) => Second,
-        (Millisecond, Second) => Second,
-        (Millisecond, Microsecond) => Millisecond,
-        (Millisecond, Nanosecond) => Millisecond,
-        (Microsecond, Second) => Second,
-        (Microsecond, Millisecond) => Millisecond,
-        (Microsecond, Nanosecond) => Microsecond,
-        (Nanosecond, Second) => Second,
-        (Nanosecond, Millisecond) => Millisecond,
-        (Nanosecond, Microsecond) => Microsecond,
-        (l, r) => {
-            assert_eq!(l, r);
-            *l
-        }
+        (Nanosecond, _) | (_, Nanosecond) => Nanosecond,
+        (Microsecond, _) | (_, Microsecond) => Microsecond,
+        (Millisecond, _) | (_, Millisecond) => Millisecond,
+        (Second, Second) => Second,
     }
 }
 
@@ -872,6 +872,77 @@ fn test_type_union_coercion_prefers_string() {
     );
 }
 
+/// Tests that timestamps of different units are coerced to the finer unit,
+/// keeping the timezone, for comparisons, joins and UNION.
+#[test]
+fn test_timestamp_precision_coercion() {
+    let units = [Second, Millisecond, Microsecond, Nanosecond];
+    let utc: Option<Arc<str>> = Some("UTC".into());
+    for (i, lhs_unit) in units.iter().enumerate() {
+        for (j, rhs_unit) in units.iter().enumerate() {
+            let unit = units[i.max(j)];
+            for (lhs_tz, rhs_tz, tz) in [
+                (None, None, None),
+                (utc.clone(), None, utc.clone()),
+                (None, utc.clone(), utc.clone()),
+                (utc.clone(), utc.clone(), utc.clone()),
+            ] {
+                let lhs = DataType::Timestamp(*lhs_unit, lhs_tz);
+                let rhs = DataType::Timestamp(*rhs_unit, rhs_tz);
+                let expected = Some(DataType::Timestamp(unit, tz));
+                assert_eq!(comparison_coercion(&lhs, &rhs), expected, "{lhs} = {rhs}");
+                assert_eq!(type_union_coercion(&lhs, &rhs), expected, "{lhs}, {rhs}");
+                assert_eq!(
+                    type_union_resolution(&[lhs.clone(), rhs.clone()]),
+                    expected,
+                    "{lhs}, {rhs}"
+                );
+            }
+        }
+    }
+
+    // dates keep the unit of the timestamp, widened to the unit of the date
+    for unit in units {
+        assert_eq!(
+            comparison_coercion(&DataType::Timestamp(unit, None), &DataType::Date32),
+            Some(DataType::Timestamp(unit, None))
+        );
+        assert_eq!(
+            comparison_coercion(
+                &DataType::Date64,
+                &DataType::Timestamp(unit, utc.clone())
+            ),
+            Some(DataType::Timestamp(
+                if unit == Second { Millisecond } else { unit },
+                None
+            ))
+        );
+    }
+}
+
+/// Tests that timestamp and duration arithmetic is done in the finer unit
+#[test]
+fn test_timestamp_duration_precision_coercion() -> Result<()> {
+    let (lhs, rhs) = BinaryTypeCoercer::new(
+        &DataType::Timestamp(Second, None),
+        &Operator::Plus,
+        &DataType::Duration(Millisecond),
+    )
+    .get_input_types()?;
+    assert_eq!(lhs, DataType::Timestamp(Millisecond, None));
+    assert_eq!(rhs, DataType::Duration(Millisecond));
+
+    let (lhs, rhs) = BinaryTypeCoercer::new(
+        &DataType::Duration(Second),
+        &Operator::Plus,
+        &DataType::Timestamp(Microsecond, Some("UTC".into())),
+    )
+    .get_input_types()?;
+    assert_eq!(lhs, DataType::Duration(Microsecond));
+    assert_eq!(rhs, DataType::Timestamp(Microsecond, Some("UTC".into())));
+    Ok(())
+}
+
 /// Tests that comparison operators coerce to numeric when comparing
 /// numeric and string types.
 #[test]
@@ -43,6 +43,17 @@ pub trait AsyncScalarUDFImpl: ScalarUDFImpl {
         None
     }
 
+    /// The maximum number of invocations of this function that may be in
+    /// flight at once while evaluating a single batch.
+    ///
+    /// When [`Self::ideal_batch_size`] splits a batch into several chunks,
+    /// up to this many chunks are evaluated concurrently, which is useful
+    /// for functions that call out to external services. Results are
+    /// always returned in input order. Defaults to 1 (one chunk at a time).
+    fn max_concurrency(&self) -> usize {
+        1
+    }
+
     /// Invoke the function asynchronously with the async arguments
     async fn invoke_async_with_args(
         &self,
@@ -81,11 +92,21 @@ impl AsyncScalarUDF {
         Self { inner }
     }
 
+    /// The wrapped function
+    pub fn inner(&self) -> &Arc<dyn AsyncScalarUDFImpl> {
+        &self.inner
+    }
+
     /// The ideal batch size for this function
     pub fn ideal_batch_size(&self) -> Option<usize> {
         self.inner.ideal_batch_size()
     }
 
+    /// The maximum number of concurrent invocations for a single batch
+    pub fn max_concurrency(&self) -> usize {
+        self.inner.max_concurrency()
+    }
+
     /// Turn this AsyncUDF into a ScalarUDF, suitable for
     /// registering in the context
     pub fn into_scalar_udf(self) -> ScalarUDF {
@@ -15,12 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::registry::CastRegistryRef;
 use crate::var_provider::{VarProvider, VarType};
 use chrono::{DateTime, Utc};
 use datafusion_common::HashMap;
 use datafusion_common::ScalarValue;
 use datafusion_common::TableReference;
 use datafusion_common::alias::AliasGenerator;
+use datafusion_common::clock::ClockRef;
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::{Result, internal_err};
 use std::fmt;
@@ -74,6 +76,11 @@ pub struct ExecutionProps {
     /// during physical planning. Populated by the physical planner for
     /// each lambda before calling `create_physical_expr`.
     pub lambda_variable_qualifier: HashMap<String, TableReference>,
+    /// User defined casts consulted when planning `CAST` expressions
+    pub cast_registry: Option<CastRegistryRef>,
+    /// The clock that provides the query execution start time. If `None`,
+    /// the system time is used
+    pub clock: Option<ClockRef>,
 }
 
 impl Default for ExecutionProps {
@@ -93,6 +100,8 @@ impl ExecutionProps {
             subquery_indexes: HashMap::new(),
             subquery_results: ScalarSubqueryResults::default(),
             lambda_variable_qualifier: HashMap::new(),
+            cast_registry: None,
+            clock: None,
         }
     }
 
@@ -114,7 +123,11 @@ impl ExecutionProps {
     /// Marks the execution of query started timestamp.
     /// This also instantiates a new alias generator.
     pub fn mark_start_execution(&mut self, config_options: Arc<ConfigOptions>) -> &Self {
-        self.query_execution_start_time = Some(Utc::now());
+        let now = self
+            .clock
+            .as_ref()
+            .map_or_else(Utc::now, |clock| clock.now());
+        self.query_execution_start_time = Some(now);
         self.alias_generator = Arc::new(AliasGenerator::new());
         self.config_options = Some(config_options);
         &*self
@@ -146,6 +159,28 @@ impl ExecutionProps {
             .and_then(|var_providers| var_providers.get(&var_type).cloned())
     }
 
+    /// Set the registry of user defined casts to use
+    pub fn with_cast_registry(mut self, cast_registry: CastRegistryRef) -> Self {
+        self.cast_registry = Some(cast_registry);
+        self
+    }
+
+    /// Returns the registry of user defined casts, if any
+    pub fn cast_registry(&self) -> Option<&CastRegistryRef> {
+        self.cast_registry.as_ref()
+    }
+
+    /// Set the clock that provides the query execution start time
+    pub fn with_clock(mut self, clock: ClockRef) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Returns the clock that provides the query execution start time, if any
+    pub fn clock(&self) -> Option<&ClockRef> {
+        self.clock.as_ref()
+    }
+
     /// Returns the configuration properties for this execution
     /// if the execution has started
     pub fn config_options(&self) -> Option<&Arc<ConfigOptions>> {
@@ -274,7 +309,7 @@ mod test {
     fn debug() {
         let props = ExecutionProps::new();
         assert_eq!(
-            "ExecutionProps { query_execution_start_time: None, alias_generator: AliasGenerator { next_id: 1 }, config_options: None, var_providers: None, subquery_indexes: {}, subquery_results: [], lambda_variable_qualifier: {} }",
+            "ExecutionProps { query_execution_start_time: None, alias_generator: AliasGenerator { next_id: 1 }, config_options: None, var_providers: None, subquery_indexes: {}, subquery_results: [], lambda_variable_qualifier: {}, cast_registry: None, clock: None }",
             format!("{props:?}")
         );
     }
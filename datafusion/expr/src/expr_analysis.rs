@@ -0,0 +1,247 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Analysis of the dependencies and properties of [`Expr`]s
+
+use crate::expr::{AggregateFunction, ScalarFunction};
+use crate::interval_arithmetic::Interval;
+use crate::sort_properties::{ExprProperties, SortProperties};
+use crate::{BinaryExpr, Expr, ExprSchemable, Operator, Volatility};
+
+use arrow::compute::SortOptions;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, DFSchema, Result};
+use indexmap::IndexSet;
+
+/// A summary of the columns an [`Expr`] depends on and of its properties,
+/// computed by [`ExprAnalysis::analyze`].
+///
+/// # Example
+/// ```
+/// # use arrow::datatypes::{DataType, Field, Schema};
+/// # use datafusion_common::{Column, DFSchema, Result};
+/// # use datafusion_expr::expr_analysis::ExprAnalysis;
+/// # use datafusion_expr::sort_properties::SortProperties;
+/// # use datafusion_expr::{Volatility, col, lit};
+/// # fn main() -> Result<()> {
+/// let schema = DFSchema::try_from(Schema::new(vec![
+///     Field::new("a", DataType::Int64, false),
+///     Field::new("b", DataType::Int64, true),
+/// ]))?;
+/// let analysis = ExprAnalysis::analyze(&(col("a") + lit(1)), &schema)?;
+/// assert_eq!(analysis.columns, vec![Column::from_name("a")]);
+/// assert_eq!(analysis.volatility, Volatility::Immutable);
+/// assert!(!analysis.nullable);
+/// // `a + 1` is sorted when `a` is sorted
+/// assert!(matches!(analysis.sort_properties, SortProperties::Ordered(_)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExprAnalysis {
+    /// The columns referenced by the expression, in the order they are first
+    /// referenced
+    pub columns: Vec<Column>,
+    /// The volatility of the expression: the most volatile [`Volatility`] of
+    /// the functions it calls, or [`Volatility::Immutable`] if it calls none
+    pub volatility: Volatility,
+    /// Whether the expression can evaluate to null
+    pub nullable: bool,
+    /// The ordering of the expression when the columns it references are
+    /// sorted in ascending order. For example `-a` is sorted in descending
+    /// order when `a` is sorted, while `a % 10` is not sorted.
+    pub sort_properties: SortProperties,
+    /// Whether the expression preserves the lexicographical ordering of the
+    /// columns it references, see [`ExprProperties::preserves_lex_ordering`]
+    pub preserves_lex_ordering: bool,
+}
+
+impl ExprAnalysis {
+    /// Analyzes `expr`, whose columns are resolved with `schema`, in one walk
+    /// of the expression
+    pub fn analyze(expr: &Expr, schema: &DFSchema) -> Result<Self> {
+        let mut analyzer = Analyzer {
+            schema,
+            columns: IndexSet::new(),
+            volatility: Volatility::Immutable,
+        };
+        let properties = analyzer.visit(expr)?;
+        Ok(Self {
+            columns: analyzer.columns.into_iter().collect(),
+            volatility: analyzer.volatility,
+            nullable: expr.nullable(schema)?,
+            sort_properties: properties.sort_properties,
+            preserves_lex_ordering: properties.preserves_lex_ordering,
+        })
+    }
+
+    /// Returns true if the expression returns the same results when evaluated
+    /// more than once with the same input, i.e. it is not volatile
+    pub fn is_deterministic(&self) -> bool {
+        self.volatility != Volatility::Volatile
+    }
+}
+
+struct Analyzer<'a> {
+    schema: &'a DFSchema,
+    columns: IndexSet<Column>,
+    volatility: Volatility,
+}
+
+impl Analyzer<'_> {
+    /// Returns the ordering properties of `expr`, and records the columns it
+    /// references and the volatility of the functions it calls
+    fn visit(&mut self, expr: &Expr) -> Result<ExprProperties> {
+        let mut children = vec![];
+        expr.apply_children(|child| {
+            children.push(self.visit(child)?);
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        let unknown = ExprProperties::new_unknown().with_range(self.unbounded(expr)?);
+        Ok(match expr {
+            Expr::Column(column) => {
+                self.columns.insert(column.clone());
+                unknown
+                    .with_order(SortProperties::Ordered(SortOptions::default()))
+                    .with_preserves_lex_ordering(true)
+            }
+            Expr::Literal(value, _) => {
+                let range = Interval::try_new(value.clone(), value.clone())
+                    .unwrap_or(unknown.range);
+                ExprProperties::new_unknown()
+                    .with_order(SortProperties::Singleton)
+                    .with_range(range)
+                    .with_preserves_lex_ordering(true)
+            }
+            Expr::Alias(_) => children.swap_remove(0),
+            Expr::Negative(_) => unknown.with_order(-children[0].sort_properties),
+            Expr::Cast(_) | Expr::TryCast(_) => {
+                // casts between numeric types keep the order of their input
+                let child = children.swap_remove(0);
+                if child.range.data_type().is_numeric()
+                    && unknown.range.data_type().is_numeric()
+                {
+                    child.with_range(unknown.range)
+                } else {
+                    unknown
+                }
+            }
+            Expr::BinaryExpr(BinaryExpr { op, .. }) => {
+                let (left, right) =
+                    (&children[0].sort_properties, &children[1].sort_properties);
+                let order = match op {
+                    Operator::Plus => left.add(right),
+                    Operator::Minus => left.sub(right),
+                    Operator::Gt | Operator::GtEq => left.gt_or_gteq(right),
+                    Operator::Lt | Operator::LtEq => right.gt_or_gteq(left),
+                    Operator::And | Operator::Or => right.and_or(left),
+                    _ => SortProperties::Unordered,
+                };
+                unknown.with_order(order)
+            }
+            Expr::ScalarFunction(ScalarFunction { func, .. }) => {
+                self.volatility = self.volatility.max(func.signature().volatility);
+                unknown
+                    .with_order(func.output_ordering(&children)?)
+                    .with_preserves_lex_ordering(func.preserves_lex_ordering(&children)?)
+            }
+            Expr::AggregateFunction(AggregateFunction { func, .. }) => {
+                self.volatility = self.volatility.max(func.signature().volatility);
+                unknown
+            }
+            _ => unknown,
+        })
+    }
+
+    /// Returns the unbounded range of the type of `expr`
+    fn unbounded(&self, expr: &Expr) -> Result<Interval> {
+        let data_type = expr.get_type(self.schema)?;
+        Ok(Interval::make_unbounded(&data_type)
+            .unwrap_or_else(|_| ExprProperties::new_unknown().range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{cast, col, lit, out_ref_col};
+
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn schema() -> DFSchema {
+        DFSchema::try_from(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+            Field::new("s", DataType::Utf8, true),
+        ]))
+        .unwrap()
+    }
+
+    fn analyze(expr: Expr) -> ExprAnalysis {
+        ExprAnalysis::analyze(&expr, &schema()).unwrap()
+    }
+
+    #[test]
+    fn referenced_columns_and_nullability() {
+        let analysis = analyze(col("b") + col("a") * col("b"));
+        assert_eq!(
+            analysis.columns,
+            vec![Column::from_name("b"), Column::from_name("a")]
+        );
+        assert!(analysis.nullable);
+
+        let analysis = analyze(col("a") * lit(2));
+        assert_eq!(analysis.columns, vec![Column::from_name("a")]);
+        assert!(!analysis.nullable);
+
+        let analysis = analyze(lit(1));
+        assert!(analysis.columns.is_empty());
+        assert_eq!(analysis.sort_properties, SortProperties::Singleton);
+
+        // outer references are not columns of `schema`
+        let analysis = analyze(out_ref_col(DataType::Int32, "t.x").eq(col("a")));
+        assert_eq!(analysis.columns, vec![Column::from_name("a")]);
+    }
+
+    #[test]
+    fn ordering() {
+        let ascending = SortProperties::Ordered(SortOptions::default());
+        let descending = -ascending;
+        for (expr, expected) in [
+            (col("a"), ascending),
+            (col("a") + lit(1), ascending),
+            (lit(1) - col("a"), descending),
+            (-col("a"), descending),
+            (cast(col("a"), DataType::Int64), ascending),
+            (cast(col("a"), DataType::Utf8), SortProperties::Unordered),
+            (col("a") % lit(10), SortProperties::Unordered),
+        ] {
+            assert_eq!(analyze(expr.clone()).sort_properties, expected, "{expr}");
+        }
+        assert!(analyze(col("s").alias("t")).preserves_lex_ordering);
+        assert!(!analyze(col("a") + lit(1)).preserves_lex_ordering);
+    }
+
+    #[test]
+    fn immutable_expressions_are_deterministic() {
+        let analysis = analyze(col("a").eq(lit(1)).and(col("s").is_null()));
+        assert_eq!(analysis.volatility, Volatility::Immutable);
+        assert!(analysis.is_deterministic());
+    }
+}
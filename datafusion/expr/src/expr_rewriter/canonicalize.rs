@@ -0,0 +1,296 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Canonical form of [`Expr`]s
+
+use std::cmp::Ordering;
+
+use crate::expr::InList;
+use crate::utils::split_binary_owned;
+use crate::{BinaryExpr, Expr, ExprSchemable, Operator, binary_expr};
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{DFSchema, Result};
+
+/// Rewrites `expr` to a canonical form, so that equivalent expressions
+/// written differently compare equal.
+///
+/// The rewrite is applied bottom-up, and:
+///
+/// * orders the operands of commutative operators: comparisons such as `=`,
+///   `<` (swapping the operator, so `5 < a` becomes `a > 5`), and `+`, `*`
+///   and the bitwise operators on numeric operands (per `schema`)
+/// * flattens nested `AND` / `OR` chains, and orders and deduplicates their
+///   operands, unless any of them is volatile
+/// * rewrites the negation of a comparison, such as `NOT (a = b)`, to the
+///   negated comparison, `a != b`
+/// * orders and deduplicates the literals of `IN` lists
+///
+/// Operands are ordered by the [`PartialOrd`] implementation of [`Expr`],
+/// which places columns before literals. The canonical form is meant to be
+/// compared, for example by optimizer rules, common subexpression
+/// elimination or materialized view matching, and is not necessarily the
+/// cheapest form to evaluate.
+///
+/// # Example
+/// ```
+/// # use datafusion_common::{DFSchema, Result};
+/// # use datafusion_expr::{col, lit, not};
+/// # use datafusion_expr::expr_rewriter::canonicalize_expr;
+/// # fn main() -> Result<()> {
+/// # let schema = DFSchema::empty();
+/// // (5 < a AND NOT (b = 1)) AND 5 < a
+/// let expr = lit(5)
+///     .lt(col("a"))
+///     .and(not(col("b").eq(lit(1))))
+///     .and(lit(5).lt(col("a")));
+/// // a > 5 AND b != 1
+/// assert_eq!(
+///     canonicalize_expr(expr, &schema)?,
+///     col("a").gt(lit(5)).and(col("b").not_eq(lit(1)))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn canonicalize_expr(expr: Expr, schema: &DFSchema) -> Result<Expr> {
+    expr.transform_up(|expr| canonicalize(expr, schema)).data()
+}
+
+fn canonicalize(expr: Expr, schema: &DFSchema) -> Result<Transformed<Expr>> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: op @ (Operator::And | Operator::Or),
+            right,
+        }) => {
+            let left_deep = !matches!(
+                right.as_ref(),
+                Expr::BinaryExpr(BinaryExpr { op: right_op, .. }) if *right_op == op
+            );
+            let mut operands = split_binary_owned(binary_expr(*left, op, *right), op);
+            let ordered = operands
+                .windows(2)
+                .all(|pair| cmp_exprs(&pair[0], &pair[1]) == Ordering::Less);
+            if !ordered && !operands.iter().any(Expr::is_volatile) {
+                operands.sort_by(cmp_exprs);
+                operands.dedup();
+            }
+            let expr = operands
+                .into_iter()
+                .reduce(|acc, operand| binary_expr(acc, op, operand))
+                .expect("split_binary_owned returns at least one operand");
+            Ok(Transformed::new_transformed(expr, !(left_deep && ordered)))
+        }
+        Expr::BinaryExpr(BinaryExpr { left, op, right })
+            if cmp_exprs(&left, &right) == Ordering::Greater =>
+        {
+            let swapped_op = match op.swap() {
+                Some(swapped_op) => Some(swapped_op),
+                None if is_commutative(op)
+                    && left.get_type(schema)?.is_numeric()
+                    && right.get_type(schema)?.is_numeric() =>
+                {
+                    Some(op)
+                }
+                None => None,
+            };
+            Ok(match swapped_op {
+                Some(swapped_op) => {
+                    Transformed::yes(binary_expr(*right, swapped_op, *left))
+                }
+                None => Transformed::no(binary_expr(*left, op, *right)),
+            })
+        }
+        Expr::Not(inner) => match *inner {
+            Expr::BinaryExpr(BinaryExpr { left, op, right })
+                if op.supports_propagation() && op.negate().is_some() =>
+            {
+                let negated_op = op.negate().unwrap();
+                Ok(Transformed::yes(binary_expr(*left, negated_op, *right)))
+            }
+            inner => Ok(Transformed::no(Expr::Not(Box::new(inner)))),
+        },
+        Expr::InList(InList {
+            expr,
+            mut list,
+            negated,
+        }) if list.iter().all(|item| matches!(item, Expr::Literal(..))) => {
+            let ordered = list
+                .windows(2)
+                .all(|pair| cmp_exprs(&pair[0], &pair[1]) == Ordering::Less);
+            if !ordered {
+                list.sort_by(cmp_exprs);
+                list.dedup();
+            }
+            Ok(Transformed::new_transformed(
+                Expr::InList(InList {
+                    expr,
+                    list,
+                    negated,
+                }),
+                !ordered,
+            ))
+        }
+        expr => Ok(Transformed::no(expr)),
+    }
+}
+
+/// Returns true if `a op b` equals `b op a` for numeric operands
+fn is_commutative(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::Plus
+            | Operator::Multiply
+            | Operator::BitwiseAnd
+            | Operator::BitwiseOr
+            | Operator::BitwiseXor
+    )
+}
+
+/// A total order of expressions, falling back to their display form if
+/// [`PartialOrd`] can not compare them (for example literals of different
+/// types)
+fn cmp_exprs(a: &Expr, b: &Expr) -> Ordering {
+    a.partial_cmp(b)
+        .unwrap_or_else(|| a.to_string().cmp(&b.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::expr_fn::{create_udf, in_list, not};
+    use crate::{ColumnarValue, Volatility, col, lit};
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::not_impl_err;
+
+    fn schema() -> DFSchema {
+        DFSchema::try_from(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+            Field::new("s", DataType::Utf8, true),
+        ]))
+        .unwrap()
+    }
+
+    fn random() -> Expr {
+        create_udf(
+            "random",
+            vec![],
+            DataType::Float64,
+            Volatility::Volatile,
+            Arc::new(|_: &[ColumnarValue]| -> Result<ColumnarValue> {
+                not_impl_err!("random")
+            }),
+        )
+        .call(vec![])
+    }
+
+    fn canonicalize(expr: Expr) -> Expr {
+        canonicalize_expr(expr, &schema()).unwrap()
+    }
+
+    #[test]
+    fn orders_commutative_operands() {
+        assert_eq!(canonicalize(lit(1).eq(col("a"))), col("a").eq(lit(1)));
+        assert_eq!(canonicalize(col("b").eq(col("a"))), col("a").eq(col("b")));
+        assert_eq!(canonicalize(lit(5).lt(col("a"))), col("a").gt(lit(5)));
+        assert_eq!(canonicalize(lit(1) + col("a")), col("a") + lit(1));
+        assert_eq!(canonicalize(col("b") * col("a")), col("a") * col("b"));
+
+        // not commutative
+        assert_eq!(canonicalize(lit(1) - col("a")), lit(1) - col("a"));
+        let concat = binary_expr(lit("x"), Operator::StringConcat, col("s"));
+        assert_eq!(canonicalize(concat.clone()), concat);
+    }
+
+    #[test]
+    fn flattens_and_orders_conjunctions() {
+        // b = 1 AND (a = 1 AND b = 1)
+        let expr = col("b")
+            .eq(lit(1))
+            .and(col("a").eq(lit(1)).and(col("b").eq(lit(1))));
+        assert_eq!(
+            canonicalize(expr),
+            col("a").eq(lit(1)).and(col("b").eq(lit(1)))
+        );
+
+        // (1 = b OR a = 2) OR a = 1
+        let expr = lit(1)
+            .eq(col("b"))
+            .or(col("a").eq(lit(2)))
+            .or(col("a").eq(lit(1)));
+        assert_eq!(
+            canonicalize(expr),
+            col("a")
+                .eq(lit(1))
+                .or(col("a").eq(lit(2)))
+                .or(col("b").eq(lit(1)))
+        );
+
+        // volatile operands keep their order
+        let expr = col("b").gt(random()).and(col("a").gt(lit(1)));
+        assert_eq!(canonicalize(expr.clone()), expr);
+    }
+
+    #[test]
+    fn negates_comparisons() {
+        assert_eq!(
+            canonicalize(not(col("a").eq(col("b")))),
+            col("a").not_eq(col("b"))
+        );
+        assert_eq!(
+            canonicalize(not(lit(1).lt(col("a")))),
+            col("a").lt_eq(lit(1))
+        );
+        assert_eq!(
+            canonicalize(not(col("a").is_null())),
+            not(col("a").is_null())
+        );
+    }
+
+    #[test]
+    fn orders_in_list_literals() {
+        let expr = in_list(col("a"), vec![lit(3), lit(1), lit(2), lit(1)], false);
+        assert_eq!(
+            canonicalize(expr),
+            in_list(col("a"), vec![lit(1), lit(2), lit(3)], false)
+        );
+
+        // lists of non literals are left as is
+        let expr = in_list(col("a"), vec![col("b"), lit(1)], true);
+        assert_eq!(canonicalize(expr.clone()), expr);
+    }
+
+    #[test]
+    fn equivalent_expressions_are_equal() {
+        let expr1 = lit(1)
+            .lt(col("a"))
+            .and(not(col("b").eq(lit(2))).and(in_list(
+                col("s"),
+                vec![lit("y"), lit("x")],
+                false,
+            )));
+        let expr2 = in_list(col("s"), vec![lit("x"), lit("y")], false)
+            .and(col("b").not_eq(lit(2)))
+            .and(col("a").gt(lit(1)));
+        assert_eq!(canonicalize(expr1), canonicalize(expr2));
+    }
+}
@@ -0,0 +1,327 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Common subexpression elimination for [`Expr`]s
+
+use crate::expr::{HigherOrderFunction, ScalarFunction};
+use crate::{BinaryExpr, Case, Expr, ExpressionPlacement, Operator, col};
+
+use datafusion_common::Result;
+use datafusion_common::alias::AliasGenerator;
+use datafusion_common::cse::{CSE, CSEController, FoundCommonNodes};
+use datafusion_common::tree_node::{Transformed, TreeNode};
+
+/// Prefix of the names of extracted common subexpressions
+pub const CSE_PREFIX: &str = "__common_expr";
+
+/// The result of [`rewrite_exprs_with_cse`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CseRewrite {
+    /// The rewritten expressions, referring to the `common_exprs` by their
+    /// names
+    pub exprs: Vec<Expr>,
+    /// The rewritten common subexpressions, aliased with their names. These
+    /// must be evaluated by an intermediate projection, that also passes
+    /// through the input columns, below the one evaluating `exprs`.
+    pub common_exprs: Vec<Expr>,
+}
+
+/// Rewrite `exprs` with `rewriter`, applied bottom-up, only rewriting each
+/// subexpression that appears multiple times in `exprs` once.
+///
+/// The common subexpressions are first extracted from `exprs`, as done by the
+/// `CommonSubexprEliminate` optimizer rule, and named with `alias_generator`.
+/// `rewriter` is then applied to each of the common subexpressions, and to
+/// `exprs` in which these are replaced by columns, so the rewritten common
+/// subexpressions are also only evaluated once.
+///
+/// If there are no common subexpressions `exprs` are simply rewritten, and
+/// the returned `common_exprs` are empty.
+pub fn rewrite_exprs_with_cse<F>(
+    exprs: Vec<Expr>,
+    alias_generator: &AliasGenerator,
+    mut rewriter: F,
+) -> Result<Transformed<CseRewrite>>
+where
+    F: FnMut(Expr) -> Result<Transformed<Expr>>,
+{
+    let mut cse = CSE::new(ExprCSEController::new(alias_generator, ExprMask::Normal));
+    let (exprs, common_exprs) = match cse.extract_common_nodes(vec![exprs])? {
+        FoundCommonNodes::Yes {
+            common_nodes,
+            mut new_nodes_list,
+            ..
+        } => (new_nodes_list.pop().unwrap_or_default(), common_nodes),
+        FoundCommonNodes::No {
+            mut original_nodes_list,
+        } => (original_nodes_list.pop().unwrap_or_default(), vec![]),
+    };
+    let mut transformed = !common_exprs.is_empty();
+
+    let mut rewrite = |expr: Expr| {
+        let result = expr.transform_up(&mut rewriter)?;
+        transformed |= result.transformed;
+        Ok(result.data)
+    };
+    let common_exprs = common_exprs
+        .into_iter()
+        .map(|(expr, name)| Ok(rewrite(expr)?.alias(name)))
+        .collect::<Result<_>>()?;
+    let exprs = exprs.into_iter().map(&mut rewrite).collect::<Result<_>>()?;
+
+    Ok(Transformed::new_transformed(
+        CseRewrite {
+            exprs,
+            common_exprs,
+        },
+        transformed,
+    ))
+}
+
+/// Which type of [expressions](Expr) should be considered for rewriting?
+#[derive(Debug, Clone, Copy)]
+pub enum ExprMask {
+    /// Ignores:
+    ///
+    /// - [`Literal`](Expr::Literal)
+    /// - [`Columns`](Expr::Column)
+    /// - [`ScalarVariable`](Expr::ScalarVariable)
+    /// - [`Alias`](Expr::Alias)
+    /// - [`Wildcard`](Expr::Wildcard)
+    /// - [`AggregateFunction`](Expr::AggregateFunction)
+    Normal,
+
+    /// Like [`Normal`](Self::Normal), but includes [`AggregateFunction`](Expr::AggregateFunction).
+    NormalAndAggregates,
+}
+
+/// [`CSEController`] for [`Expr`]s, used to extract common subexpressions with
+/// [`CSE`]
+pub struct ExprCSEController<'a> {
+    alias_generator: &'a AliasGenerator,
+    mask: ExprMask,
+
+    // how many aliases have we seen so far
+    alias_counter: usize,
+}
+
+impl<'a> ExprCSEController<'a> {
+    /// Create a new controller, naming the extracted subexpressions with
+    /// `alias_generator`
+    pub fn new(alias_generator: &'a AliasGenerator, mask: ExprMask) -> Self {
+        Self {
+            alias_generator,
+            mask,
+            alias_counter: 0,
+        }
+    }
+}
+
+impl CSEController for ExprCSEController<'_> {
+    type Node = Expr;
+
+    fn conditional_children(node: &Expr) -> Option<(Vec<&Expr>, Vec<&Expr>)> {
+        match node {
+            // In case of `ScalarFunction`s and `HigherOrderFunction`s we don't know which children are surely
+            // executed so start visiting all children conditionally and stop the
+            // recursion with `TreeNodeRecursion::Jump`.
+            Expr::ScalarFunction(ScalarFunction { func, args }) => {
+                func.conditional_arguments(args)
+            }
+            Expr::HigherOrderFunction(HigherOrderFunction { func, args }) => {
+                func.conditional_arguments(args)
+            }
+
+            // In case of `And` and `Or` the first child is surely executed, but we
+            // account subexpressions as conditional in the second.
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::And | Operator::Or,
+                right,
+            }) => Some((vec![left.as_ref()], vec![right.as_ref()])),
+
+            // In case of `Case` the optional base expression and the first when
+            // expressions are surely executed, but we account subexpressions as
+            // conditional in the others.
+            Expr::Case(Case {
+                expr,
+                when_then_expr,
+                else_expr,
+            }) => Some((
+                expr.iter()
+                    .map(|e| e.as_ref())
+                    .chain(when_then_expr.iter().take(1).map(|(when, _)| when.as_ref()))
+                    .collect(),
+                when_then_expr
+                    .iter()
+                    .take(1)
+                    .map(|(_, then)| then.as_ref())
+                    .chain(
+                        when_then_expr
+                            .iter()
+                            .skip(1)
+                            .flat_map(|(when, then)| [when.as_ref(), then.as_ref()]),
+                    )
+                    .chain(else_expr.iter().map(|e| e.as_ref()))
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn is_valid(node: &Expr) -> bool {
+        !node.is_volatile_node()
+            && !matches!(node, Expr::Lambda(_) | Expr::LambdaVariable(_))
+    }
+
+    fn is_ignored(&self, node: &Expr) -> bool {
+        // MoveTowardsLeafNodes expressions (e.g. get_field) are cheap struct
+        // field accesses that the ExtractLeafExpressions / PushDownLeafProjections
+        // rules deliberately duplicate when needed (one copy for a filter
+        // predicate, another for an output column). CSE deduplicating them
+        // creates intermediate projections that fight with those rules,
+        // causing optimizer instability — ExtractLeafExpressions will undo
+        // the dedup, creating an infinite loop that runs until the iteration
+        // limit is hit. Skip them.
+        if node.placement() == ExpressionPlacement::MoveTowardsLeafNodes {
+            return true;
+        }
+
+        // TODO: remove the next line after `Expr::Wildcard` is removed
+        #[expect(deprecated)]
+        let is_normal_minus_aggregates = matches!(
+            node,
+            // TODO: there's an argument for removing `Literal` from here,
+            // maybe using `Expr::placemement().should_push_to_leaves()` instead
+            // so that we extract common literals and don't broadcast them to num_batch_rows multiple times.
+            // However that currently breaks things like `percentile_cont()` which expect literal arguments
+            // (and would instead be getting `col(__common_expr_n)`).
+            Expr::Literal(..)
+                | Expr::Column(..)
+                | Expr::ScalarVariable(..)
+                | Expr::Alias(..)
+                | Expr::Wildcard { .. }
+                | Expr::Lambda(_)
+                | Expr::LambdaVariable(_)
+        );
+
+        let is_aggr = matches!(node, Expr::AggregateFunction(..));
+
+        match self.mask {
+            ExprMask::Normal => is_normal_minus_aggregates || is_aggr,
+            ExprMask::NormalAndAggregates => is_normal_minus_aggregates,
+        }
+    }
+
+    fn generate_alias(&self) -> String {
+        self.alias_generator.next(CSE_PREFIX)
+    }
+
+    fn rewrite(&mut self, node: &Self::Node, alias: &str) -> Self::Node {
+        // alias the expressions without an `Alias` ancestor node
+        if self.alias_counter > 0 {
+            col(alias)
+        } else {
+            self.alias_counter += 1;
+            col(alias).alias(node.schema_name().to_string())
+        }
+    }
+
+    fn rewrite_f_down(&mut self, node: &Expr) {
+        if matches!(node, Expr::Alias(_)) {
+            self.alias_counter += 1;
+        }
+    }
+    fn rewrite_f_up(&mut self, node: &Expr) {
+        if matches!(node, Expr::Alias(_)) {
+            self.alias_counter -= 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{binary_expr, lit};
+
+    #[test]
+    fn rewrite_common_subexpressions_once() -> Result<()> {
+        let common = col("a") + col("b");
+        let exprs = vec![common.clone() * lit(2), common * lit(3), col("c") + lit(1)];
+
+        // Rewrite `+` to `-`, counting the rewritten expressions
+        let mut rewrites = 0;
+        let result =
+            rewrite_exprs_with_cse(exprs, &AliasGenerator::new(), |expr| match expr {
+                Expr::BinaryExpr(BinaryExpr {
+                    left,
+                    op: Operator::Plus,
+                    right,
+                }) => {
+                    rewrites += 1;
+                    Ok(Transformed::yes(binary_expr(
+                        *left,
+                        Operator::Minus,
+                        *right,
+                    )))
+                }
+                _ => Ok(Transformed::no(expr)),
+            })?;
+
+        assert!(result.transformed);
+        assert_eq!(rewrites, 2);
+        let CseRewrite {
+            exprs,
+            common_exprs,
+        } = result.data;
+        let display = |exprs: Vec<Expr>| {
+            exprs
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        insta::assert_snapshot!(display(common_exprs), @"a - b AS __common_expr_1");
+        insta::assert_snapshot!(display(exprs), @r"
+        __common_expr_1 AS a + b * Int32(2)
+        __common_expr_1 AS a + b * Int32(3)
+        c - Int32(1)
+        ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_without_common_subexpressions() -> Result<()> {
+        let exprs = vec![col("a") + col("b"), col("a") * col("b")];
+        let result =
+            rewrite_exprs_with_cse(exprs.clone(), &AliasGenerator::new(), |expr| {
+                Ok(Transformed::no(expr))
+            })?;
+
+        assert!(!result.transformed);
+        assert_eq!(
+            result.data,
+            CseRewrite {
+                exprs,
+                common_exprs: vec![]
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -24,17 +24,27 @@ use std::sync::Arc;
 
 use crate::expr::{Alias, Sort, Unnest};
 use crate::logical_plan::Projection;
+use crate::registry::FunctionRegistry;
 use crate::{Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder};
 
 use datafusion_common::TableReference;
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::metadata::FieldMetadata;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
-use datafusion_common::{Column, DFSchema, Result};
-
+use datafusion_common::{Column, DFSchema, DFSchemaRef, Result};
+
+mod canonicalize;
+pub use canonicalize::canonicalize_expr;
+mod cse;
+pub use cse::{
+    CSE_PREFIX, CseRewrite, ExprCSEController, ExprMask, rewrite_exprs_with_cse,
+};
 mod guarantees;
 pub use guarantees::GuaranteeRewriter;
 pub use guarantees::rewrite_with_guarantees;
 pub use guarantees::rewrite_with_guarantees_map;
+mod operator_overload;
+pub use operator_overload::{OperandType, OperatorOverload};
 mod order_by;
 
 pub use order_by::rewrite_sort_cols_by_aggs;
@@ -56,12 +66,33 @@ pub trait FunctionRewrite: Debug {
     ///
     /// Note that recursion is handled by the caller -- this method should only
     /// handle `expr`, not recurse to its children.
+    ///
+    /// Rewrites that need to look up registered functions should implement
+    /// [`Self::rewrite_with_registry`] instead. The default implementation
+    /// returns `expr` unchanged.
     fn rewrite(
+        &self,
+        expr: Expr,
+        _schema: &DFSchema,
+        _config: &ConfigOptions,
+    ) -> Result<Transformed<Expr>> {
+        Ok(Transformed::no(expr))
+    }
+
+    /// Potentially rewrite `expr` to some other expression, using `registry`
+    /// to look up the functions to call, for example to rewrite `a || b` to
+    /// `array_concat(a, b)` with whichever `array_concat` is registered.
+    ///
+    /// The default implementation calls [`Self::rewrite`].
+    fn rewrite_with_registry(
         &self,
         expr: Expr,
         schema: &DFSchema,
         config: &ConfigOptions,
-    ) -> Result<Transformed<Expr>>;
+        _registry: &dyn FunctionRegistry,
+    ) -> Result<Transformed<Expr>> {
+        self.rewrite(expr, schema, config)
+    }
 }
 
 /// Recursively call `LogicalPlanBuilder::normalize` on all [`Column`] expressions
@@ -255,9 +286,16 @@ fn coerce_exprs_for_schema(
             let new_type = dst_schema.field(idx).data_type();
             if new_type != &expr.get_type(src_schema)? {
                 match expr {
-                    Expr::Alias(Alias { expr, name, .. }) => {
-                        Ok(expr.cast_to(new_type, src_schema)?.alias(name))
-                    }
+                    // keep the metadata of the alias, which the cast of the
+                    // aliased expression does not carry
+                    Expr::Alias(Alias {
+                        expr,
+                        name,
+                        metadata,
+                        ..
+                    }) => Ok(expr
+                        .cast_to(new_type, src_schema)?
+                        .alias_with_metadata(name, metadata)),
                     #[expect(deprecated)]
                     Expr::Wildcard { .. } => Ok(expr),
                     _ => {
@@ -297,8 +335,14 @@ pub fn unalias(expr: Expr) -> Expr {
 /// expression should be preserved: `3 as "1 + 2"`
 ///
 /// See <https://github.com/apache/datafusion/issues/3555> for details
+///
+/// The metadata of the output field of the expression, such as the metadata of
+/// extension types, is preserved as well when the `NamePreserver` is created
+/// with [`NamePreserver::new`].
 pub struct NamePreserver {
     use_alias: bool,
+    /// The output schema of the plan, if any of its fields has metadata
+    schema_with_metadata: Option<DFSchemaRef>,
 }
 
 /// If the qualified name of an expression is remembered, it will be preserved
@@ -309,6 +353,8 @@ pub enum SavedName {
     Saved {
         relation: Option<TableReference>,
         name: String,
+        /// Metadata of the output field of the expression, if any
+        metadata: Option<FieldMetadata>,
     },
     /// Name is not preserved
     None,
@@ -317,17 +363,36 @@ pub enum SavedName {
 impl NamePreserver {
     /// Create a new NamePreserver for rewriting the `expr` that is part of the specified plan
     pub fn new(plan: &LogicalPlan) -> Self {
+        // The expressions of these plans do not contribute to their output schema,
+        // so there is no need to preserve expression names to prevent a schema change.
+        let use_alias = !matches!(
+            plan,
+            LogicalPlan::Filter(_)
+                | LogicalPlan::Join(_)
+                | LogicalPlan::TableScan(_)
+                | LogicalPlan::Limit(_)
+                | LogicalPlan::Statement(_)
+        );
+        if use_alias {
+            Self::new_for_schema(plan.schema())
+        } else {
+            Self {
+                use_alias,
+                schema_with_metadata: None,
+            }
+        }
+    }
+
+    /// Create a new NamePreserver that preserves the qualified names, and the
+    /// field metadata, of the fields of `schema`
+    pub fn new_for_schema(schema: &DFSchemaRef) -> Self {
+        let has_metadata = schema
+            .fields()
+            .iter()
+            .any(|field| !field.metadata().is_empty());
         Self {
-            // The expressions of these plans do not contribute to their output schema,
-            // so there is no need to preserve expression names to prevent a schema change.
-            use_alias: !matches!(
-                plan,
-                LogicalPlan::Filter(_)
-                    | LogicalPlan::Join(_)
-                    | LogicalPlan::TableScan(_)
-                    | LogicalPlan::Limit(_)
-                    | LogicalPlan::Statement(_)
-            ),
+            use_alias: true,
+            schema_with_metadata: has_metadata.then(|| Arc::clone(schema)),
         }
     }
 
@@ -335,13 +400,26 @@ impl NamePreserver {
     ///
     /// This will use aliases
     pub fn new_for_projection() -> Self {
-        Self { use_alias: true }
+        Self {
+            use_alias: true,
+            schema_with_metadata: None,
+        }
     }
 
     pub fn save(&self, expr: &Expr) -> SavedName {
         if self.use_alias {
             let (relation, name) = expr.qualified_name();
-            SavedName::Saved { relation, name }
+            // The output field of an expression is named after the expression
+            let metadata = self.schema_with_metadata.as_ref().and_then(|schema| {
+                let field = schema.field_with_name(relation.as_ref(), &name).ok()?;
+                (!field.metadata().is_empty())
+                    .then(|| FieldMetadata::from(field.metadata()))
+            });
+            SavedName::Saved {
+                relation,
+                name,
+                metadata,
+            }
         } else {
             SavedName::None
         }
@@ -349,13 +427,18 @@ impl NamePreserver {
 }
 
 impl SavedName {
-    /// Ensures the qualified name of the rewritten expression is preserved
+    /// Ensures the qualified name, and the saved field metadata, of the
+    /// rewritten expression are preserved
     pub fn restore(self, expr: Expr) -> Expr {
         match self {
-            SavedName::Saved { relation, name } => {
+            SavedName::Saved {
+                relation,
+                name,
+                metadata,
+            } => {
                 let (new_relation, new_name) = expr.qualified_name();
                 if new_relation != relation || new_name != name {
-                    expr.alias_qualified(relation, name)
+                    expr.alias_qualified_with_metadata(relation, name, metadata)
                 } else {
                     expr
                 }
@@ -480,6 +563,42 @@ mod test {
         assert_eq!(error, expected);
     }
 
+    #[test]
+    fn normalize_col_lateral_table_function() -> Result<()> {
+        let input = crate::logical_plan::table_scan(
+            Some("t"),
+            &Schema::new(vec![Field::new("x", DataType::Int64, false)]),
+            None,
+        )?
+        .build()?;
+        let function_schema = DFSchema::try_from_qualified_schema(
+            "f",
+            &Schema::new(vec![Field::new("value", DataType::Int64, false)]),
+        )?;
+        let plan = LogicalPlanBuilder::from(input)
+            .lateral_table_function(
+                "f",
+                vec![col("x")],
+                Arc::new(function_schema),
+                datafusion_common::JoinType::Inner,
+            )?
+            .build()?;
+
+        // The arguments are resolved against the input of the call
+        let LogicalPlan::LateralTableFunction(lateral) = &plan else {
+            unreachable!()
+        };
+        assert_eq!(lateral.args, vec![col("t.x")]);
+
+        // Both the columns of the input and of the function are in scope
+        // above the call
+        assert_eq!(
+            normalize_col(col("x") + col("value"), &plan)?,
+            col("t.x") + col("f.value")
+        );
+        Ok(())
+    }
+
     #[test]
     fn unnormalize_cols() {
         let expr = col("tableA.a") + col("tableB.b");
@@ -543,6 +662,81 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_rewrite_preserving_metadata() -> Result<()> {
+        let metadata = HashMap::from([(
+            "ARROW:extension:name".to_string(),
+            "arrow.uuid".to_string(),
+        )]);
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false).with_metadata(metadata.clone()),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let plan = crate::table_scan(Some("t"), &schema, None)?
+            .project(vec![col("a"), col("b")])?
+            .build()?;
+        let name_preserver = NamePreserver::new(&plan);
+
+        // the metadata of the output field is restored with the name
+        let saved_name = name_preserver.save(&col("t.a"));
+        let restored = saved_name.restore(col("t.a").add(lit(0)));
+        let (_, field) = restored.to_field(plan.inputs()[0].schema())?;
+        assert_eq!(field.name(), "a");
+        assert_eq!(field.metadata(), &metadata);
+
+        // expressions without metadata are aliased as before
+        let restored = name_preserver
+            .save(&col("t.b"))
+            .restore(col("t.b").add(lit(0)));
+        assert_eq!(
+            restored,
+            col("t.b").add(lit(0)).alias_qualified(Some("t"), "b")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn coerce_plan_expr_for_nested_struct_schema() -> Result<()> {
+        let struct_type = |fields: Vec<Field>| {
+            DataType::new_list(DataType::Struct(fields.into()), true)
+        };
+        let a = Field::new("a", DataType::Int32, true);
+        let b = Field::new_list("b", Field::new_list_field(DataType::Int32, true), true);
+        let metadata = HashMap::from([("k".to_string(), "v".to_string())]);
+        let schema = Schema::new(vec![Field::new(
+            "s",
+            struct_type(vec![a.clone(), b.clone()]),
+            true,
+        )]);
+        let plan = crate::table_scan(Some("t"), &schema, None)?
+            .project(vec![
+                col("s").alias_with_metadata("s", Some(metadata.clone().into())),
+            ])?
+            .build()?;
+
+        // the fields of the structs in the lists are matched by name, although
+        // `a` can not be cast to `b` by position
+        let target_type = struct_type(vec![b, a]);
+        let target = DFSchema::try_from(Schema::new(vec![Field::new(
+            "s",
+            target_type.clone(),
+            true,
+        )]))?;
+        let plan = coerce_plan_expr_for_schema(plan, &target)?;
+        let field = plan.schema().field(0);
+        assert_eq!(field.data_type(), &target_type);
+        assert_eq!(field.metadata(), &metadata);
+
+        // structs without a common field can not be cast
+        let target = DFSchema::try_from(Schema::new(vec![Field::new(
+            "s",
+            struct_type(vec![Field::new("c", DataType::Int32, true)]),
+            true,
+        )]))?;
+        assert!(coerce_plan_expr_for_schema(plan, &target).is_err());
+        Ok(())
+    }
+
     /// rewrites `expr_from` to `rewrite_to` while preserving the original qualified name
     /// by using the `NamePreserver`
     fn test_rewrite(expr_from: Expr, rewrite_to: Expr) {
@@ -561,7 +755,7 @@ mod test {
         let mut rewriter = TestRewriter {
             rewrite_to: rewrite_to.clone(),
         };
-        let saved_name = NamePreserver { use_alias: true }.save(&expr_from);
+        let saved_name = NamePreserver::new_for_projection().save(&expr_from);
         let new_expr = expr_from.clone().rewrite(&mut rewriter).unwrap().data;
         let new_expr = saved_name.restore(new_expr);
 
@@ -0,0 +1,282 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Overloading of binary operators for user defined and extension types
+
+use std::sync::Arc;
+
+use crate::expr_rewriter::FunctionRewrite;
+use crate::planner::{ExprPlanner, PlannerResult, RawBinaryExpr};
+use crate::{BinaryExpr, Expr, ExprSchemable, Operator, ScalarUDF};
+
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{DFSchema, Result};
+
+/// The type of an operand of an [`OperatorOverload`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OperandType {
+    /// Operands of this Arrow type that are not of an extension type
+    DataType(DataType),
+    /// Operands of the extension type with this name, for example
+    /// `geoarrow.wkb`, whatever their Arrow type
+    Extension(String),
+}
+
+impl OperandType {
+    /// Returns true if an operand with `field` is of this type
+    pub fn matches(&self, field: &Field) -> bool {
+        match self {
+            Self::DataType(data_type) => {
+                field.extension_type_name().is_none() && field.data_type() == data_type
+            }
+            Self::Extension(name) => field.extension_type_name() == Some(name.as_str()),
+        }
+    }
+}
+
+/// Overloads a binary operator, such as `+`, `||` or `@>`, for a combination
+/// of operand types, by calling a [`ScalarUDF`] with the two operands.
+///
+/// For example, an overload of `@>` for two `geoarrow.wkb` operands with a
+/// `st_contains` function rewrites `a @> b` to `st_contains(a, b)` when both
+/// `a` and `b` are of that extension type. Operators on other types are not
+/// affected.
+///
+/// An overload is both an [`ExprPlanner`], to resolve the operators of SQL
+/// queries while they are planned, and a [`FunctionRewrite`], to resolve the
+/// operators of plans built otherwise, for example with the `DataFrame` API.
+/// `SessionContext::register_operator_overload` registers it as both.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::datatypes::{DataType, Field, Schema};
+/// # use datafusion_common::config::ConfigOptions;
+/// # use datafusion_common::{DFSchema, Result};
+/// # use datafusion_expr::expr_rewriter::{FunctionRewrite, OperandType, OperatorOverload};
+/// # use datafusion_expr::{ColumnarValue, Operator, Volatility, binary_expr, col, create_udf};
+/// # fn main() -> Result<()> {
+/// # let schema = DFSchema::try_from(Schema::new(vec![
+/// #     Field::new("a", DataType::Binary, true),
+/// #     Field::new("b", DataType::Binary, true),
+/// # ]))?;
+/// # let binary_concat = Arc::new(create_udf(
+/// #     "binary_concat",
+/// #     vec![DataType::Binary, DataType::Binary],
+/// #     DataType::Binary,
+/// #     Volatility::Immutable,
+/// #     Arc::new(|args: &[ColumnarValue]| -> Result<ColumnarValue> {
+/// #         Ok(args[0].clone())
+/// #     }),
+/// # ));
+/// let overload = OperatorOverload::new(
+///     Operator::StringConcat,
+///     OperandType::DataType(DataType::Binary),
+///     OperandType::DataType(DataType::Binary),
+///     binary_concat,
+/// );
+/// // a || b
+/// let expr = binary_expr(col("a"), Operator::StringConcat, col("b"));
+/// let rewritten = overload.rewrite(expr, &schema, &ConfigOptions::default())?;
+/// assert_eq!(rewritten.data.to_string(), "binary_concat(a, b)");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperatorOverload {
+    op: Operator,
+    left: OperandType,
+    right: OperandType,
+    func: Arc<ScalarUDF>,
+}
+
+impl OperatorOverload {
+    /// Overloads `op` for `left` and `right` operands, with `func`
+    pub fn new(
+        op: Operator,
+        left: OperandType,
+        right: OperandType,
+        func: Arc<ScalarUDF>,
+    ) -> Self {
+        Self {
+            op,
+            left,
+            right,
+            func,
+        }
+    }
+
+    /// Returns the overloaded operator
+    pub fn op(&self) -> Operator {
+        self.op
+    }
+
+    /// Returns the type of the left operand
+    pub fn left(&self) -> &OperandType {
+        &self.left
+    }
+
+    /// Returns the type of the right operand
+    pub fn right(&self) -> &OperandType {
+        &self.right
+    }
+
+    /// Returns the function called instead of the operator
+    pub fn func(&self) -> &Arc<ScalarUDF> {
+        &self.func
+    }
+
+    /// Returns true if this overload applies to `left op right`
+    fn applies_to(
+        &self,
+        op: Operator,
+        left: &Expr,
+        right: &Expr,
+        schema: &DFSchema,
+    ) -> Result<bool> {
+        if op != self.op {
+            return Ok(false);
+        }
+        let (_, left_field) = left.to_field(schema)?;
+        let (_, right_field) = right.to_field(schema)?;
+        Ok(self.left.matches(&left_field) && self.right.matches(&right_field))
+    }
+}
+
+impl FunctionRewrite for OperatorOverload {
+    fn name(&self) -> &str {
+        self.func.name()
+    }
+
+    fn rewrite(
+        &self,
+        expr: Expr,
+        schema: &DFSchema,
+        _config: &ConfigOptions,
+    ) -> Result<Transformed<Expr>> {
+        match expr {
+            Expr::BinaryExpr(BinaryExpr { left, op, right })
+                if self.applies_to(op, &left, &right, schema)? =>
+            {
+                Ok(Transformed::yes(self.func.call(vec![*left, *right])))
+            }
+            expr => Ok(Transformed::no(expr)),
+        }
+    }
+}
+
+impl ExprPlanner for OperatorOverload {
+    fn plan_binary_op(
+        &self,
+        expr: RawBinaryExpr,
+        schema: &DFSchema,
+    ) -> Result<PlannerResult<RawBinaryExpr>> {
+        // operators without a DataFusion equivalent can not be overloaded
+        let Ok(op) = expr.operator() else {
+            return Ok(PlannerResult::Original(expr));
+        };
+        if self.applies_to(op, &expr.left, &expr.right, schema)? {
+            let RawBinaryExpr { left, right, .. } = expr;
+            return Ok(PlannerResult::Planned(self.func.call(vec![left, right])));
+        }
+        Ok(PlannerResult::Original(expr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use crate::{ColumnarValue, Volatility, binary_expr, col, create_udf};
+
+    use arrow::datatypes::Schema;
+    use datafusion_common::ScalarValue;
+
+    fn schema() -> DFSchema {
+        let metadata = HashMap::from([(
+            "ARROW:extension:name".to_string(),
+            "geoarrow.wkb".to_string(),
+        )]);
+        DFSchema::try_from(Schema::new(vec![
+            Field::new("g1", DataType::Binary, true).with_metadata(metadata.clone()),
+            Field::new("g2", DataType::Binary, true).with_metadata(metadata),
+            Field::new("b", DataType::Binary, true),
+        ]))
+        .unwrap()
+    }
+
+    fn st_contains() -> Arc<ScalarUDF> {
+        Arc::new(create_udf(
+            "st_contains",
+            vec![DataType::Binary, DataType::Binary],
+            DataType::Boolean,
+            Volatility::Immutable,
+            Arc::new(|_: &[ColumnarValue]| -> Result<ColumnarValue> {
+                Ok(ColumnarValue::Scalar(ScalarValue::Boolean(Some(true))))
+            }),
+        ))
+    }
+
+    fn contains_overload() -> OperatorOverload {
+        let geometry = OperandType::Extension("geoarrow.wkb".to_string());
+        OperatorOverload::new(
+            Operator::AtArrow,
+            geometry.clone(),
+            geometry,
+            st_contains(),
+        )
+    }
+
+    fn rewrite(expr: Expr) -> Transformed<Expr> {
+        contains_overload()
+            .rewrite(expr, &schema(), &ConfigOptions::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn rewrites_matching_operands() {
+        let expr = binary_expr(col("g1"), Operator::AtArrow, col("g2"));
+        let rewritten = rewrite(expr);
+        assert!(rewritten.transformed);
+        // the functions of different `create_udf` calls are never equal
+        let Expr::ScalarFunction(function) = rewritten.data else {
+            panic!("expected a function call, got {}", rewritten.data);
+        };
+        assert_eq!(function.name(), "st_contains");
+        assert_eq!(function.args, vec![col("g1"), col("g2")]);
+    }
+
+    #[test]
+    fn ignores_other_operands_and_operators() {
+        // not a geometry
+        let expr = binary_expr(col("g1"), Operator::AtArrow, col("b"));
+        assert!(!rewrite(expr).transformed);
+
+        // another operator
+        let expr = binary_expr(col("g1"), Operator::ArrowAt, col("g2"));
+        assert!(!rewrite(expr).transformed);
+
+        // the Arrow type of an extension type does not match
+        let binary = OperandType::DataType(DataType::Binary);
+        let field = Arc::clone(schema().field_with_unqualified_name("g1").unwrap());
+        assert!(!binary.matches(&field));
+    }
+}
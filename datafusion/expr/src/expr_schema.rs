@@ -32,6 +32,10 @@ use arrow::compute::can_cast_types;
 use arrow::datatypes::FieldRef;
 use arrow::datatypes::{DataType, Field};
 use datafusion_common::datatype::FieldExt;
+use datafusion_common::metadata_column::without_metadata_column_marker;
+use datafusion_common::nested_struct::{
+    requires_nested_struct_cast, validate_data_type_compatibility,
+};
 use datafusion_common::{
     Column, DataFusionError, ExprSchema, Result, ScalarValue, Spans, TableReference,
     not_impl_err, plan_datafusion_err, plan_err,
@@ -167,12 +171,14 @@ impl ExprSchemable for Expr {
                     | DataType::ListView(field)
                     | DataType::LargeListView(field) => Ok(field.data_type().clone()),
                     DataType::Struct(_) => Ok(arg_data_type),
+                    // A map is unnested into its key/value entries
+                    DataType::Map(entries, _) => Ok(entries.data_type().clone()),
                     DataType::Null => {
                         not_impl_err!("unnest() does not support null yet")
                     }
                     _ => {
                         plan_err!(
-                            "unnest() can only be applied to array, struct and null"
+                            "unnest() can only be applied to array, map, struct and null"
                         )
                     }
                 }
@@ -495,7 +501,10 @@ impl ExprSchemable for Expr {
                     .with_field_metadata(&combined_metadata))
             }
             Expr::Negative(expr) => expr.to_field(schema).map(|(_, f)| f),
-            Expr::Column(c) => schema.field_from_column(c).map(Arc::clone),
+            // A metadata column referenced by an expression is a regular column
+            Expr::Column(c) => schema
+                .field_from_column(c)
+                .map(|field| without_metadata_column_marker(Arc::clone(field))),
             Expr::OuterReferenceColumn(field, _) => {
                 Ok(Arc::clone(field).renamed(&schema_name))
             }
@@ -699,6 +708,11 @@ impl ExprSchemable for Expr {
                 // Always allow struct-to-struct casts; field matching happens at runtime
                 true
             }
+            // Structs nested in lists and dictionaries are also cast by name at
+            // runtime, so validate their fields by name rather than by position
+            _ if requires_nested_struct_cast(&this_type, cast_to_type) => {
+                validate_data_type_compatibility("", &this_type, cast_to_type).is_ok()
+            }
             _ => can_cast_types(&this_type, cast_to_type),
         };
 
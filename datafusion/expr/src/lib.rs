@@ -34,6 +34,16 @@
 //! working with these types.
 //!
 //! The [expr_fn] module contains functions for creating expressions.
+//!
+//! # Dependencies
+//!
+//! This crate does not depend on the DataFusion runtime, such as `tokio`,
+//! `object_store` or the physical operators, so services that only build,
+//! rewrite or serialize plans can use [Expr], [LogicalPlan], [DFSchema] and
+//! the [expr_rewriter] utilities without them. Disabling the default `sql`
+//! feature also removes the `sqlparser` dependency.
+//!
+//! [DFSchema]: datafusion_common::DFSchema
 
 extern crate core;
 
@@ -50,6 +60,7 @@ pub mod arguments;
 pub mod conditional_expressions;
 pub mod execution_props;
 pub mod expr;
+pub mod expr_analysis;
 pub mod expr_fn;
 pub mod expr_rewriter;
 pub mod expr_schema;
@@ -127,12 +138,15 @@ pub use logical_plan::*;
 pub use partition_evaluator::PartitionEvaluator;
 #[cfg(feature = "sql")]
 pub use sqlparser;
-pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
+pub use table_source::{
+    TableAggregate, TableProviderFilterPushDown, TableSource, TableType,
+};
 pub use udaf::{
-    AggregateUDF, AggregateUDFImpl, ReversedUDAF, SetMonotonicity, StatisticsArgs,
+    AGGREGATE_STATE_FORMAT_VERSION, AggregateUDF, AggregateUDFImpl, ReversedUDAF,
+    SetMonotonicity, StatisticsArgs, udaf_default_deserialize_state,
     udaf_default_display_name, udaf_default_human_display, udaf_default_return_field,
-    udaf_default_schema_name, udaf_default_window_function_display_name,
-    udaf_default_window_function_schema_name,
+    udaf_default_schema_name, udaf_default_serialize_state,
+    udaf_default_window_function_display_name, udaf_default_window_function_schema_name,
 };
 pub use udf::{
     ReturnFieldArgs, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, StructFieldMapping,
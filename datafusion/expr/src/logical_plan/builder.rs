@@ -23,18 +23,18 @@ use std::collections::{HashMap, HashSet};
 use std::iter::once;
 use std::sync::Arc;
 
-use crate::dml::CopyTo;
-use crate::expr::{Alias, PlannedReplaceSelectItem, Sort as SortExpr};
+use crate::dml::{CopyTo, MERGE_ACTION_COLUMN, MERGE_OLD_VALUE_PREFIX};
+use crate::expr::{Alias, Placeholder, PlannedReplaceSelectItem, Sort as SortExpr};
 use crate::expr_rewriter::{
-    coerce_plan_expr_for_schema, normalize_col,
+    NamePreserver, coerce_plan_expr_for_schema, normalize_col,
     normalize_col_with_schemas_and_ambiguity_check, normalize_cols, normalize_sorts,
     rewrite_sort_cols_by_aggs,
 };
 use crate::logical_plan::{
-    Aggregate, Analyze, Distinct, DistinctOn, EmptyRelation, Explain, Filter, Join,
-    JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Aggregate, Analyze, AsOfMatch, DedupKeep, Distinct, DistinctOn, EmptyRelation,
+    Explain, Filter, Join, JoinConstraint, JoinType, LateralTableFunction, Limit,
+    LogicalPlan, Partitioning, PlanType, Prepare, Projection, Repartition, Sort,
+    SubqueryAlias, TableScan, Union, Unnest, Values, Window, projection_schema,
 };
 use crate::select_expr::SelectExpr;
 use crate::utils::{
@@ -44,7 +44,8 @@ use crate::utils::{
 };
 use crate::{
     DmlStatement, ExplainOption, Expr, ExprSchemable, Operator, RecursiveQuery,
-    Statement, TableProviderFilterPushDown, TableSource, WriteOp, and, binary_expr, lit,
+    Scd2MergeOptions, Statement, TableProviderFilterPushDown, TableSource, WriteOp, and,
+    binary_expr, col, lit, when,
 };
 
 use super::dml::InsertOp;
@@ -56,8 +57,8 @@ use datafusion_common::metadata::FieldMetadata;
 use datafusion_common::{
     Column, Constraints, DFSchema, DFSchemaRef, NullEquality, Result, ScalarValue,
     TableReference, ToDFSchema, UnnestOptions, exec_err,
-    get_target_functional_dependencies, internal_datafusion_err, plan_datafusion_err,
-    plan_err,
+    get_target_functional_dependencies, internal_datafusion_err, internal_err,
+    plan_datafusion_err, plan_err,
 };
 use datafusion_expr_common::type_coercion::binary::type_union_resolution;
 
@@ -206,7 +207,10 @@ impl LogicalPlanBuilder {
     ///
     /// so it's usually better to override the default names with a table alias list.
     ///
-    /// If the values include params/binders such as $1, $2, $3, etc, then the `param_data_types` should be provided.
+    /// The type of each column is the common type of its values, see
+    /// [`type_union_resolution`]. `NULL`s and placeholders without a type such
+    /// as `$1` take the type of the other values in their column, and literals
+    /// are cast to the column type when the plan is built.
     pub fn values(values: Vec<Vec<Expr>>) -> Result<Self> {
         if values.is_empty() {
             return plan_err!("Values list cannot be empty");
@@ -316,6 +320,10 @@ impl LogicalPlanBuilder {
                 } else {
                     common_metadata = Some(metadata.clone());
                 }
+                // NULLs and untyped placeholders take the type of the other values
+                if matches!(value, Expr::Placeholder(Placeholder { field: None, .. })) {
+                    continue;
+                }
                 let data_type = value.get_type(&schema)?;
                 if data_type == DataType::Null {
                     continue;
@@ -352,17 +360,30 @@ impl LogicalPlanBuilder {
         schema: &DFSchema,
     ) -> Result<Self> {
         let fields = fields.into_fields();
-        // wrap cast if data type is not same as common type.
+        // cast literals to the common type directly and wrap other expressions
+        // in a cast, so that large lists do not need to be simplified later
         for row in &mut values {
-            for (j, field_type) in fields.iter().map(|f| f.data_type()).enumerate() {
-                if let Expr::Literal(ScalarValue::Null, metadata) = &row[j] {
-                    row[j] = Expr::Literal(
-                        ScalarValue::try_from(field_type)?,
-                        metadata.clone(),
-                    );
-                } else {
-                    row[j] = std::mem::take(&mut row[j]).cast_to(field_type, schema)?;
-                }
+            for (j, field) in fields.iter().enumerate() {
+                let field_type = field.data_type();
+                row[j] = match std::mem::take(&mut row[j]) {
+                    Expr::Literal(value, metadata) => {
+                        let value = if value.is_null() {
+                            ScalarValue::try_from(field_type)?
+                        } else {
+                            value.cast_to(field_type)?
+                        };
+                        Expr::Literal(value, metadata)
+                    }
+                    Expr::Placeholder(Placeholder { id, field: None })
+                        if !field_type.is_null() =>
+                    {
+                        Expr::Placeholder(Placeholder::new_with_field(
+                            id,
+                            Some(field.as_ref().clone().with_nullable(true).into()),
+                        ))
+                    }
+                    expr => expr.cast_to(field_type, schema)?,
+                };
             }
         }
 
@@ -412,6 +433,25 @@ impl LogicalPlanBuilder {
         Self::scan_with_filters(table_name, table_source, projection, vec![])
     }
 
+    /// Convert a table provider into a builder with a TableScan of all its
+    /// columns, including its hidden metadata columns.
+    ///
+    /// The metadata columns can be referenced by name, but are not part of
+    /// `SELECT *`. Unused columns are removed from the scan by the
+    /// `optimize_projections` rule. See [`TableSource::metadata_columns`].
+    pub fn scan_with_metadata_columns(
+        table_name: impl Into<TableReference>,
+        table_source: Arc<dyn TableSource>,
+    ) -> Result<Self> {
+        let num_metadata_columns = table_source
+            .metadata_columns()
+            .map_or(0, |metadata_columns| metadata_columns.fields().len());
+        let projection = (num_metadata_columns > 0).then(|| {
+            (0..table_source.schema().fields().len() + num_metadata_columns).collect()
+        });
+        Self::scan(table_name, table_source, projection)
+    }
+
     /// Create a [CopyTo] for copying the contents of this builder to the specified file(s)
     pub fn copy_to(
         input: LogicalPlan,
@@ -912,6 +952,189 @@ impl LogicalPlanBuilder {
         ))))
     }
 
+    /// Remove duplicate rows that have the same values for `keys`, keeping
+    /// only the first or the last row of each group according to `order_by`.
+    ///
+    /// All columns of the input are kept. This is planned as a
+    /// [`DistinctOn`], and therefore executed as a per-group top-1 aggregate
+    /// rather than a full sort of the input.
+    ///
+    /// If `order_by` is empty an arbitrary row of each group is kept, which is
+    /// only allowed for [`DedupKeep::First`].
+    pub fn deduplicate(
+        self,
+        keys: Vec<Expr>,
+        order_by: Vec<SortExpr>,
+        keep: DedupKeep,
+    ) -> Result<Self> {
+        if order_by.is_empty() {
+            if keep == DedupKeep::Last {
+                return plan_err!("Deduplicating with KEEP LAST requires an ORDER BY");
+            }
+            let select_expr = self.plan.schema().columns().into_iter().map(Expr::Column);
+            return self.distinct_on(keys, select_expr.collect(), None);
+        }
+
+        let order_by = match keep {
+            DedupKeep::First => order_by,
+            DedupKeep::Last => order_by.iter().map(SortExpr::reverse).collect(),
+        };
+        // `DISTINCT ON` requires the `ON` expressions to be the leading sort keys
+        let sort_expr = keys
+            .iter()
+            .map(|key| key.clone().sort(true, false))
+            .chain(order_by)
+            .collect();
+        let select_expr = self.plan.schema().columns().into_iter().map(Expr::Column);
+        self.distinct_on(keys, select_expr.collect(), Some(sort_expr))
+    }
+
+    /// Upsert the rows of this plan into the table `table_name`: rows whose
+    /// `keys` match a row of the table replace it, the other rows are
+    /// inserted.
+    ///
+    /// This plan must have a column for each column of the table, matched by
+    /// name, and at most one row for each key, see [`Self::deduplicate`]. The
+    /// result is a [`WriteOp::Merge`] [`DmlStatement`], like the one of
+    /// `MERGE INTO t USING s ON <keys> WHEN MATCHED THEN UPDATE ... WHEN NOT
+    /// MATCHED THEN INSERT ...`.
+    pub fn upsert_into(
+        self,
+        table_name: impl Into<TableReference>,
+        target: Arc<dyn TableSource>,
+        keys: &[String],
+    ) -> Result<Self> {
+        let table_name = table_name.into();
+        let merge = MergeJoin::try_new(self.plan, &table_name, &target, keys, None)?;
+        let table_schema = target.schema();
+
+        let action =
+            when(merge.target_row().is_null(), lit("insert")).otherwise(lit("update"))?;
+        let new_values = table_schema
+            .fields()
+            .iter()
+            .map(|field| merge.source_value(field))
+            .collect::<Result<Vec<_>>>()?;
+        let changes = merge.changes(action, new_values, merge.target_values())?;
+
+        Ok(Self::new(LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            target,
+            WriteOp::Merge,
+            Arc::new(changes),
+        ))))
+    }
+
+    /// Merge the rows of this plan into the table `table_name`, keeping the
+    /// history of the changes as a type 2 slowly changing dimension.
+    ///
+    /// Each row of the table is a version of the row with its `keys`, valid
+    /// from [`Scd2MergeOptions::valid_from`] until
+    /// [`Scd2MergeOptions::valid_to`], which is `NULL` for the current
+    /// version. For each row of this plan:
+    ///
+    /// * if there is no current version with its keys, the row is inserted as
+    ///   the current version
+    /// * if any other column differs from the current version, the current
+    ///   version is closed by setting its `valid_to`, and the row is inserted
+    ///   as the new current version
+    /// * otherwise the table is left unchanged
+    ///
+    /// This plan must have a column for each column of the table except the
+    /// validity columns, matched by name, and at most one row for each key.
+    /// The result is a [`WriteOp::Merge`] [`DmlStatement`].
+    pub fn scd2_merge(
+        self,
+        table_name: impl Into<TableReference>,
+        target: Arc<dyn TableSource>,
+        keys: &[String],
+        options: Scd2MergeOptions,
+    ) -> Result<Self> {
+        let Scd2MergeOptions {
+            valid_from,
+            valid_to,
+            effective_time,
+        } = options;
+        let table_name = table_name.into();
+        let table_schema = target.schema();
+        let valid_from_field = table_schema.field_with_name(&valid_from)?;
+        let valid_to_field = table_schema.field_with_name(&valid_to)?;
+
+        // only the current versions are merged
+        let is_current =
+            Expr::Column(Column::new(Some(table_name.clone()), &valid_to)).is_null();
+        let merge =
+            MergeJoin::try_new(self.plan, &table_name, &target, keys, Some(is_current))?;
+
+        let mut changed = lit(false);
+        for field in table_schema.fields() {
+            let name = field.name();
+            if keys.contains(name) || *name == valid_from || *name == valid_to {
+                continue;
+            }
+            changed = changed.or(binary_expr(
+                merge.source_value(field)?,
+                Operator::IsDistinctFrom,
+                merge.target_value(field),
+            ));
+        }
+        let effective_time_of = |field: &Field| {
+            effective_time
+                .clone()
+                .cast_to(field.data_type(), merge.plan.schema())
+        };
+
+        // close the current versions of the changed rows
+        let closed_values = table_schema
+            .fields()
+            .iter()
+            .map(|field| {
+                if field.name() == valid_to_field.name() {
+                    effective_time_of(field)
+                } else {
+                    Ok(merge.target_value(field))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let closed = merge
+            .filter(merge.target_row().is_not_null().and(changed.clone()))?
+            .changes(lit("update"), closed_values, merge.target_values())?;
+
+        // insert the new versions of the changed and the new rows
+        let inserted_values = table_schema
+            .fields()
+            .iter()
+            .map(|field| {
+                if field.name() == valid_from_field.name() {
+                    effective_time_of(field)
+                } else if field.name() == valid_to_field.name() {
+                    Expr::Literal(ScalarValue::Null, None)
+                        .cast_to(field.data_type(), merge.plan.schema())
+                } else {
+                    merge.source_value(field)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let no_old_values = table_schema
+            .fields()
+            .iter()
+            .map(|field| {
+                Expr::Literal(ScalarValue::Null, None)
+                    .cast_to(field.data_type(), merge.plan.schema())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let inserted = merge
+            .filter(merge.target_row().is_null().or(changed))?
+            .changes(lit("insert"), inserted_values, no_old_values)?;
+
+        Ok(Self::new(LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            target,
+            WriteOp::Merge,
+            Arc::new(LogicalPlanBuilder::from(closed).union(inserted)?.build()?),
+        ))))
+    }
+
     /// Apply a join to `right` using explicitly specified columns and an
     /// optional filter expression.
     ///
@@ -1163,9 +1386,101 @@ impl LogicalPlanBuilder {
             schema: DFSchemaRef::new(join_schema),
             null_equality,
             null_aware,
+            asof_match: None,
         })))
     }
 
+    /// Apply an ASOF join.
+    ///
+    /// Each row of `self` is matched with at most one row of `right`: among the
+    /// right rows with equal `join_keys`, the nearest one according to
+    /// `asof_match`. `join_type` must be [`JoinType::Inner`], which drops left
+    /// rows without a match, or [`JoinType::Left`], which keeps them.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion_expr::{col, lit, AsOfMatch, JoinType, LogicalPlanBuilder, Operator};
+    /// # use datafusion_common::Result;
+    /// # fn example(trades: LogicalPlanBuilder, quotes: datafusion_expr::LogicalPlan) -> Result<()> {
+    /// // For each trade, the most recent quote of the same symbol, if it is
+    /// // at most 10 time units old
+    /// let asof_match = AsOfMatch::try_new(
+    ///     col("trades.ts"),
+    ///     Operator::GtEq,
+    ///     col("quotes.ts"),
+    ///     Some((col("trades.ts") - col("quotes.ts")).lt_eq(lit(10))),
+    /// )?;
+    /// let plan = trades
+    ///     .asof_join(
+    ///         quotes,
+    ///         JoinType::Left,
+    ///         (vec!["trades.symbol"], vec!["quotes.symbol"]),
+    ///         asof_match,
+    ///     )?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn asof_join(
+        self,
+        right: LogicalPlan,
+        join_type: JoinType,
+        join_keys: (Vec<impl Into<Column>>, Vec<impl Into<Column>>),
+        asof_match: AsOfMatch,
+    ) -> Result<Self> {
+        let left_schema = Arc::clone(self.plan.schema());
+        let right_schema = Arc::clone(right.schema());
+        let asof_match = AsOfMatch {
+            left: normalize_col_with_schemas_and_ambiguity_check(
+                asof_match.left,
+                &[&[&left_schema]],
+                &[],
+            )?,
+            op: asof_match.op,
+            right: normalize_col_with_schemas_and_ambiguity_check(
+                asof_match.right,
+                &[&[&right_schema]],
+                &[],
+            )?,
+            tolerance: asof_match
+                .tolerance
+                .map(|expr| {
+                    normalize_col_with_schemas_and_ambiguity_check(
+                        expr,
+                        &[&[&left_schema, &right_schema]],
+                        &[],
+                    )
+                })
+                .transpose()?,
+        };
+
+        // Plan the equijoin part as an inner join, which allows empty keys
+        let LogicalPlan::Join(join) = Arc::unwrap_or_clone(
+            self.join_detailed(
+                right,
+                JoinType::Inner,
+                join_keys,
+                None,
+                NullEquality::NullEqualsNothing,
+            )?
+            .plan,
+        ) else {
+            return internal_err!("Expected a join plan");
+        };
+        let join = Join::try_new(
+            join.left,
+            join.right,
+            join.on,
+            None,
+            join_type,
+            JoinConstraint::On,
+            NullEquality::NullEqualsNothing,
+            false,
+        )?
+        .with_asof_match(asof_match)?;
+        Ok(Self::new(LogicalPlan::Join(join)))
+    }
+
     /// Apply a join with using constraint, which duplicates all join columns in output schema.
     pub fn join_using(
         self,
@@ -1327,6 +1642,7 @@ impl LogicalPlanBuilder {
         if explain_option.analyze {
             Ok(Self::new(LogicalPlan::Analyze(Analyze {
                 verbose: explain_option.verbose,
+                format: explain_option.analyze_format,
                 input: self.plan,
                 schema,
             })))
@@ -1542,6 +1858,28 @@ impl LogicalPlanBuilder {
         unnest_with_options(Arc::unwrap_or_clone(self.plan), columns, options)
             .map(Self::new)
     }
+
+    /// Call the table function `function_name` with `args`, which may reference
+    /// the columns of this plan, for every row of this plan, and join the row
+    /// with the rows the call returns. See [`LateralTableFunction`].
+    pub fn lateral_table_function(
+        self,
+        function_name: impl Into<String>,
+        args: Vec<Expr>,
+        function_schema: DFSchemaRef,
+        join_type: JoinType,
+    ) -> Result<Self> {
+        let args = normalize_cols(args, &self.plan)?;
+        LateralTableFunction::try_new(
+            self.plan,
+            function_name,
+            args,
+            function_schema,
+            join_type,
+        )
+        .map(LogicalPlan::LateralTableFunction)
+        .map(Self::new)
+    }
 }
 
 impl From<LogicalPlan> for LogicalPlanBuilder {
@@ -1934,6 +2272,77 @@ pub fn project(
     project_with_validation(plan, expr.into_iter().map(|e| (e, true)), None)
 }
 
+/// Replaces the output of `plan` with `exprs`, one for each of its output
+/// fields, by adding a [`Projection`] that preserves these fields.
+///
+/// The projection keeps the qualifier, name, metadata and nullability of each
+/// output field of `plan`, so that the plans above it, and the columns they
+/// reference, for example to declare an ordering in a `Sort`, are not
+/// affected. Only the data types of the fields may change, for example when
+/// `exprs` cast the output of `plan`. Rules that rewrite the output of a plan
+/// should use this rather than adding their own projection.
+///
+/// No projection is added if `exprs` are the output columns of `plan`.
+///
+/// # Errors
+/// This function errors under any of the following conditions:
+/// * The number of `exprs` differs from the number of output fields of `plan`
+/// * An expression is nullable, but the field it replaces is not
+pub fn project_plan_preserving(
+    plan: LogicalPlan,
+    exprs: Vec<Expr>,
+) -> Result<LogicalPlan> {
+    let schema = Arc::clone(plan.schema());
+    if exprs.len() != schema.fields().len() {
+        return plan_err!(
+            "Expected {} expressions to replace the output of the plan, got {}",
+            schema.fields().len(),
+            exprs.len()
+        );
+    }
+    let columns = schema.columns();
+    if exprs
+        .iter()
+        .zip(&columns)
+        .all(|(expr, column)| expr.try_as_col() == Some(column))
+    {
+        return Ok(plan);
+    }
+
+    let name_preserver = NamePreserver::new_for_schema(&schema);
+    let exprs = exprs
+        .into_iter()
+        .zip(columns)
+        .map(|(expr, column)| name_preserver.save(&Expr::Column(column)).restore(expr))
+        .collect::<Vec<_>>();
+
+    let projected = projection_schema(&plan, &exprs)?;
+    let fields = projected
+        .iter()
+        .zip(schema.fields())
+        .map(|((qualifier, field), original)| {
+            if field.is_nullable() && !original.is_nullable() {
+                return plan_err!(
+                    "Can not replace the non-nullable field '{}' with a nullable expression",
+                    original.name()
+                );
+            }
+            let field = field
+                .as_ref()
+                .clone()
+                .with_nullable(original.is_nullable())
+                .with_metadata(original.metadata().clone());
+            Ok((qualifier.cloned(), Arc::new(field)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let projected_schema =
+        DFSchema::new_with_metadata(fields, projected.metadata().clone())?
+            .with_functional_dependencies(projected.functional_dependencies().clone())?;
+
+    Projection::try_new_with_schema(exprs, Arc::new(plan), Arc::new(projected_schema))
+        .map(LogicalPlan::Projection)
+}
+
 /// Create Projection. Similar to project except that the expressions
 /// passed in have a flag to indicate if that expression requires
 /// validation (normalize & columnize) (true) or not (false)
@@ -2030,21 +2439,38 @@ fn project_with_validation(
 /// "REPLACE (some_column_within_an_expr AS some_column)", this function replaces
 /// that column with the given replace expression. Column name remains the same.
 /// Multiple REPLACEs are also possible with comma separations.
+///
+/// Returns an error if a replaced column is not one of the columns the
+/// wildcard expanded to, for example because it was excluded.
 fn replace_columns(
     mut exprs: Vec<Expr>,
     replace: &PlannedReplaceSelectItem,
 ) -> Result<Vec<Expr>> {
+    let mut replaced = vec![false; replace.items().len()];
     for expr in exprs.iter_mut() {
         if let Expr::Column(Column { name, .. }) = expr
-            && let Some((_, new_expr)) = replace
+            && let Some((index, (_, new_expr))) = replace
                 .items()
                 .iter()
                 .zip(replace.expressions().iter())
-                .find(|(item, _)| item.column_name.value == *name)
+                .enumerate()
+                .find(|(_, (item, _))| item.column_name.value == *name)
         {
+            replaced[index] = true;
             *expr = new_expr.clone().alias(name.clone())
         }
     }
+    if let Some((item, _)) = replace
+        .items()
+        .iter()
+        .zip(replaced)
+        .find(|(_, replaced)| !replaced)
+    {
+        return plan_err!(
+            "Column {} in REPLACE list not found in the columns of the wildcard",
+            item.column_name
+        );
+    }
     Ok(exprs)
 }
 
@@ -2108,10 +2534,7 @@ pub fn table_scan_with_filter_and_fetch(
 pub fn table_source(table_schema: &Schema) -> Arc<dyn TableSource> {
     // TODO should we take SchemaRef and avoid cloning?
     let table_schema = Arc::new(table_schema.clone());
-    Arc::new(LogicalTableSource {
-        table_schema,
-        constraints: Default::default(),
-    })
+    Arc::new(LogicalTableSource::new(table_schema))
 }
 
 pub fn table_source_with_constraints(
@@ -2120,10 +2543,134 @@ pub fn table_source_with_constraints(
 ) -> Arc<dyn TableSource> {
     // TODO should we take SchemaRef and avoid cloning?
     let table_schema = Arc::new(table_schema.clone());
-    Arc::new(LogicalTableSource {
-        table_schema,
-        constraints,
-    })
+    Arc::new(LogicalTableSource::new(table_schema).with_constraints(constraints))
+}
+
+/// Qualifier of the source rows in the plans of
+/// [`LogicalPlanBuilder::upsert_into`] and [`LogicalPlanBuilder::scd2_merge`]
+const MERGE_SOURCE: &str = "__merge_source";
+
+/// Marker column of the target rows in the plans of
+/// [`LogicalPlanBuilder::upsert_into`] and [`LogicalPlanBuilder::scd2_merge`]
+const MERGE_TARGET_ROW: &str = "__merge_target_row";
+
+/// The rows of a source plan left joined to the rows of a target table with
+/// the same keys, from which the changes of a [`WriteOp::Merge`] are built
+struct MergeJoin {
+    plan: LogicalPlan,
+    table_name: TableReference,
+    table_schema: SchemaRef,
+}
+
+impl MergeJoin {
+    /// Join `source` to the rows of `target` matching `filter` on `keys`
+    fn try_new(
+        source: Arc<LogicalPlan>,
+        table_name: &TableReference,
+        target: &Arc<dyn TableSource>,
+        keys: &[String],
+        filter: Option<Expr>,
+    ) -> Result<Self> {
+        if keys.is_empty() {
+            return plan_err!("Merging into {table_name} requires at least one key");
+        }
+        let table_schema = target.schema();
+        for key in keys {
+            table_schema.field_with_name(key)?;
+        }
+
+        let mut target_rows =
+            LogicalPlanBuilder::scan(table_name.clone(), Arc::clone(target), None)?;
+        if let Some(filter) = filter {
+            target_rows = target_rows.filter(filter)?;
+        }
+        let target_columns = target_rows.schema().columns().into_iter().map(Expr::Column);
+        let target_rows = target_rows
+            .project(target_columns.chain([lit(true).alias(MERGE_TARGET_ROW)]))?
+            .build()?;
+
+        let plan = LogicalPlanBuilder::new_from_arc(source)
+            .alias(MERGE_SOURCE)?
+            .join(
+                target_rows,
+                JoinType::Left,
+                (
+                    keys.iter()
+                        .map(|key| Column::new(Some(MERGE_SOURCE), key))
+                        .collect(),
+                    keys.iter()
+                        .map(|key| Column::new(Some(table_name.clone()), key))
+                        .collect(),
+                ),
+                None,
+            )?
+            .build()?;
+        Ok(Self {
+            plan,
+            table_name: table_name.clone(),
+            table_schema,
+        })
+    }
+
+    /// Keep only the joined rows matching `predicate`
+    fn filter(&self, predicate: Expr) -> Result<Self> {
+        Ok(Self {
+            plan: LogicalPlanBuilder::from(self.plan.clone())
+                .filter(predicate)?
+                .build()?,
+            table_name: self.table_name.clone(),
+            table_schema: Arc::clone(&self.table_schema),
+        })
+    }
+
+    /// `true` if the source row matched a target row, `NULL` otherwise
+    fn target_row(&self) -> Expr {
+        col(MERGE_TARGET_ROW)
+    }
+
+    /// The value of the column of the source row for `field` of the table
+    fn source_value(&self, field: &Field) -> Result<Expr> {
+        Expr::Column(Column::new(Some(MERGE_SOURCE), field.name()))
+            .cast_to(field.data_type(), self.plan.schema())
+    }
+
+    /// The value of the column of the matching target row for `field`
+    fn target_value(&self, field: &Field) -> Expr {
+        Expr::Column(Column::new(Some(self.table_name.clone()), field.name()))
+    }
+
+    /// The values of all the columns of the matching target row
+    fn target_values(&self) -> Vec<Expr> {
+        self.table_schema
+            .fields()
+            .iter()
+            .map(|field| self.target_value(field))
+            .collect()
+    }
+
+    /// The changes of a [`WriteOp::Merge`] with `action`, the `new_values`
+    /// and the `old_values` of the columns of the table
+    fn changes(
+        &self,
+        action: Expr,
+        new_values: Vec<Expr>,
+        old_values: Vec<Expr>,
+    ) -> Result<LogicalPlan> {
+        let fields = self.table_schema.fields();
+        let exprs = once(action.alias(MERGE_ACTION_COLUMN))
+            .chain(
+                fields
+                    .iter()
+                    .zip(new_values)
+                    .map(|(field, value)| value.alias(field.name())),
+            )
+            .chain(fields.iter().zip(old_values).map(|(field, value)| {
+                value.alias(format!("{MERGE_OLD_VALUE_PREFIX}{}", field.name()))
+            }));
+        LogicalPlanBuilder::from(self.plan.clone())
+            .project(exprs)?
+            .build()
+    }
 }
 
 /// Wrap projection for a plan, if the join keys contains normal expression.
@@ -2196,6 +2743,7 @@ pub fn wrap_projection_for_join_if_necessary(
 /// DefaultTableSource.
 pub struct LogicalTableSource {
     table_schema: SchemaRef,
+    metadata_columns: Option<SchemaRef>,
     constraints: Constraints,
 }
 
@@ -2204,6 +2752,7 @@ impl LogicalTableSource {
     pub fn new(table_schema: SchemaRef) -> Self {
         Self {
             table_schema,
+            metadata_columns: None,
             constraints: Constraints::default(),
         }
     }
@@ -2212,6 +2761,12 @@ impl LogicalTableSource {
         self.constraints = constraints;
         self
     }
+
+    /// Set the hidden metadata columns of the table
+    pub fn with_metadata_columns(mut self, metadata_columns: SchemaRef) -> Self {
+        self.metadata_columns = Some(metadata_columns);
+        self
+    }
 }
 
 impl TableSource for LogicalTableSource {
@@ -2219,6 +2774,10 @@ impl TableSource for LogicalTableSource {
         Arc::clone(&self.table_schema)
     }
 
+    fn metadata_columns(&self) -> Option<SchemaRef> {
+        self.metadata_columns.clone()
+    }
+
     fn constraints(&self) -> Option<&Constraints> {
         Some(&self.constraints)
     }
@@ -2294,11 +2853,11 @@ mod tests {
     use super::*;
     use crate::lit_with_metadata;
     use crate::logical_plan::StringifiedPlan;
-    use crate::{col, expr, expr_fn::exists, in_subquery, scalar_subquery};
+    use crate::{cast, col, expr, expr_fn::exists, in_subquery, scalar_subquery};
 
     use crate::test::function_stub::sum;
     use datafusion_common::{
-        Constraint, DataFusionError, RecursionUnnestOption, SchemaError,
+        Constraint, DataFusionError, RecursionUnnestOption, SchemaError, assert_contains,
     };
     use insta::assert_snapshot;
 
@@ -2846,6 +3405,93 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_deduplicate() -> Result<()> {
+        let plan = table_scan(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .deduplicate(
+            vec![col("state")],
+            vec![col("salary").sort(true, false)],
+            DedupKeep::Last,
+        )?
+        .build()?;
+
+        assert_snapshot!(plan, @r"
+        DistinctOn: on_expr=[[employee_csv.state]], select_expr=[[employee_csv.id, employee_csv.state, employee_csv.salary]], sort_expr=[[employee_csv.state ASC NULLS LAST, employee_csv.salary DESC NULLS FIRST]]
+          TableScan: employee_csv projection=[id, state, salary]
+        ");
+
+        let err = table_scan(Some("employee_csv"), &employee_schema(), None)?
+            .deduplicate(vec![col("state")], vec![], DedupKeep::Last)
+            .unwrap_err();
+        assert_snapshot!(err.strip_backtrace(), @"Error during planning: Deduplicating with KEEP LAST requires an ORDER BY");
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_upsert_and_scd2_merge() -> Result<()> {
+        let table_schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("valid_from", DataType::Int64, true),
+            Field::new("valid_to", DataType::Int64, true),
+        ]);
+        let source_schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let target = table_source(&table_schema);
+        let change_columns = |plan: &LogicalPlan| {
+            let LogicalPlan::Dml(dml) = plan else {
+                panic!("expected a DML statement, got {plan}");
+            };
+            assert_eq!(dml.op, WriteOp::Merge);
+            dml.input
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect::<Vec<_>>()
+        };
+        let expected = [
+            MERGE_ACTION_COLUMN,
+            "id",
+            "name",
+            "valid_from",
+            "valid_to",
+            "__merge_old_id",
+            "__merge_old_name",
+            "__merge_old_valid_from",
+            "__merge_old_valid_to",
+        ];
+
+        let plan = table_scan(Some("updates"), &table_schema, None)?
+            .upsert_into("t", Arc::clone(&target), &["id".to_string()])?
+            .build()?;
+        assert_eq!(change_columns(&plan), expected);
+
+        let plan = table_scan(Some("updates"), &source_schema, None)?
+            .scd2_merge(
+                "t",
+                Arc::clone(&target),
+                &["id".to_string()],
+                Scd2MergeOptions::new("valid_from", "valid_to", lit(42_i64)),
+            )?
+            .build()?;
+        assert_eq!(change_columns(&plan), expected);
+
+        let err = table_scan(Some("updates"), &table_schema, None)?
+            .upsert_into("t", target, &[])
+            .unwrap_err();
+        assert_snapshot!(err.strip_backtrace(), @"Error during planning: Merging into t requires at least one key");
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_aggregate_without_implicit_group_by_exprs() -> Result<()> {
         let constraints =
@@ -2914,6 +3560,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_project_plan_preserving() -> Result<()> {
+        let metadata =
+            HashMap::from([("ARROW:extension:name".to_string(), "example".to_string())]);
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false).with_metadata(metadata.clone()),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let plan = table_scan(Some("t"), &schema, None)?.build()?;
+
+        // the columns of the plan do not need a projection
+        let unchanged =
+            project_plan_preserving(plan.clone(), vec![col("t.a"), col("t.b")])?;
+        assert_eq!(unchanged, plan);
+
+        let projected = project_plan_preserving(
+            plan.clone(),
+            vec![cast(col("t.a"), DataType::Int64), col("t.b")],
+        )?;
+        assert!(matches!(projected, LogicalPlan::Projection(_)));
+        let (qualifier, field) = projected.schema().qualified_field(0);
+        assert_eq!(qualifier, Some(&TableReference::bare("t")));
+        assert_eq!(field.name(), "a");
+        assert_eq!(field.data_type(), &DataType::Int64);
+        assert!(!field.is_nullable());
+        assert_eq!(field.metadata(), &metadata);
+        assert_eq!(
+            projected.schema().qualified_field(1),
+            plan.schema().qualified_field(1)
+        );
+
+        let err = project_plan_preserving(
+            plan.clone(),
+            vec![lit(ScalarValue::Int32(None)), col("t.b")],
+        )
+        .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Can not replace the non-nullable field 'a' with a nullable expression"
+        );
+        let err = project_plan_preserving(plan, vec![col("t.a")]).unwrap_err();
+        assert_contains!(err.to_string(), "Expected 2 expressions");
+
+        Ok(())
+    }
+
     #[test]
     fn test_values_metadata() -> Result<()> {
         let metadata: HashMap<String, String> =
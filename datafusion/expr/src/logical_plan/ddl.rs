@@ -27,7 +27,7 @@ use std::{
 use crate::expr::Sort;
 #[cfg(not(feature = "sql"))]
 use crate::sql::Ident;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, FieldRef};
 use datafusion_common::tree_node::{Transformed, TreeNodeContainer, TreeNodeRecursion};
 use datafusion_common::{
     Constraints, DFSchemaRef, Result, SchemaReference, TableReference,
@@ -60,6 +60,10 @@ pub enum DdlStatement {
     CreateFunction(CreateFunction),
     /// Drop function statement
     DropFunction(DropFunction),
+    /// Infers the schema of a table again from its files.
+    RefreshTableSchema(RefreshTableSchema),
+    /// Adds, drops or renames columns of a table.
+    AlterTable(AlterTable),
 }
 
 impl DdlStatement {
@@ -81,6 +85,8 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(DropCatalogSchema { schema, .. }) => schema,
             DdlStatement::CreateFunction(CreateFunction { schema, .. }) => schema,
             DdlStatement::DropFunction(DropFunction { schema, .. }) => schema,
+            DdlStatement::RefreshTableSchema(RefreshTableSchema { schema, .. }) => schema,
+            DdlStatement::AlterTable(AlterTable { schema, .. }) => schema,
         }
     }
 
@@ -99,6 +105,8 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(_) => "DropCatalogSchema",
             DdlStatement::CreateFunction(_) => "CreateFunction",
             DdlStatement::DropFunction(_) => "DropFunction",
+            DdlStatement::RefreshTableSchema(_) => "RefreshTableSchema",
+            DdlStatement::AlterTable(_) => "AlterTable",
         }
     }
 
@@ -118,6 +126,8 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(_) => vec![],
             DdlStatement::CreateFunction(_) => vec![],
             DdlStatement::DropFunction(_) => vec![],
+            DdlStatement::RefreshTableSchema(_) => vec![],
+            DdlStatement::AlterTable(_) => vec![],
         }
     }
 
@@ -197,6 +207,21 @@ impl DdlStatement {
                     DdlStatement::DropFunction(DropFunction { name, .. }) => {
                         write!(f, "DropFunction: name {name:?}")
                     }
+                    DdlStatement::RefreshTableSchema(RefreshTableSchema {
+                        name, ..
+                    }) => {
+                        write!(f, "RefreshTableSchema: {name:?}")
+                    }
+                    DdlStatement::AlterTable(AlterTable {
+                        name, operations, ..
+                    }) => {
+                        let operations = operations
+                            .iter()
+                            .map(|op| op.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        write!(f, "AlterTable: {name:?} {operations}")
+                    }
                 }
             }
         }
@@ -562,6 +587,104 @@ impl PartialOrd for DropTable {
     }
 }
 
+/// Infers the schema of a table again from its files, see
+/// `ListingTable::with_refreshed_schema`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefreshTableSchema {
+    /// The table name
+    pub name: TableReference,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for RefreshTableSchema {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.name.partial_cmp(&other.name)
+    }
+}
+
+/// Adds, drops or renames columns of a table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTable {
+    /// The table name
+    pub name: TableReference,
+    /// Do nothing if the table does not exist
+    pub if_exists: bool,
+    /// The changes to apply, in order
+    pub operations: Vec<AlterTableOperation>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for AlterTable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.name.partial_cmp(&other.name) {
+            Some(Ordering::Equal) => match self.if_exists.partial_cmp(&other.if_exists) {
+                Some(Ordering::Equal) => self.operations.partial_cmp(&other.operations),
+                cmp => cmp,
+            },
+            cmp => cmp,
+        }
+        // TODO (https://github.com/apache/datafusion/issues/17477) avoid recomparing all fields
+        .filter(|cmp| *cmp != Ordering::Equal || self == other)
+    }
+}
+
+/// A change to the columns of a table, see [`AlterTable`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum AlterTableOperation {
+    /// Add a column, which is null for the existing rows
+    AddColumn {
+        /// The new column
+        field: FieldRef,
+        /// Do nothing if a column with the same name exists
+        if_not_exists: bool,
+    },
+    /// Drop columns
+    DropColumn {
+        /// The names of the columns to drop
+        names: Vec<String>,
+        /// Do nothing for columns that do not exist
+        if_exists: bool,
+    },
+    /// Rename a column
+    RenameColumn {
+        /// The current name of the column
+        old_name: String,
+        /// The new name of the column
+        new_name: String,
+    },
+}
+
+impl Display for AlterTableOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterTableOperation::AddColumn {
+                field,
+                if_not_exists,
+            } => {
+                let if_not_exists = if *if_not_exists { "IF NOT EXISTS " } else { "" };
+                let not_null = if field.is_nullable() { "" } else { " NOT NULL" };
+                write!(
+                    f,
+                    "ADD COLUMN {if_not_exists}{} {}{not_null}",
+                    field.name(),
+                    field.data_type()
+                )
+            }
+            AlterTableOperation::DropColumn { names, if_exists } => {
+                let if_exists = if *if_exists { "IF EXISTS " } else { "" };
+                write!(f, "DROP COLUMN {if_exists}{}", names.join(", "))
+            }
+            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                write!(f, "RENAME COLUMN {old_name} TO {new_name}")
+            }
+        }
+    }
+}
+
 /// Drops a view.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DropView {
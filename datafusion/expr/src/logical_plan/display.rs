@@ -22,9 +22,9 @@ use std::fmt;
 
 use crate::{
     Aggregate, DescribeTable, Distinct, DistinctOn, DmlStatement, Expr, Filter, Join,
-    Limit, LogicalPlan, Partitioning, Projection, RecursiveQuery, Repartition, Sort,
-    Subquery, SubqueryAlias, TableProviderFilterPushDown, TableScan, Unnest, Values,
-    Window, expr_vec_fmt,
+    LateralTableFunction, Limit, LogicalPlan, Partitioning, Projection, RecursiveQuery,
+    Repartition, Sort, Subquery, SubqueryAlias, TableProviderFilterPushDown, TableScan,
+    Unnest, Values, Window, expr_vec_fmt,
 };
 
 use crate::dml::CopyTo;
@@ -634,6 +634,19 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                     "StructColumn": expr_vec_fmt!(struct_type_columns),
                 })
             }
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                function_name,
+                args,
+                join_type,
+                ..
+            }) => {
+                json!({
+                    "Node Type": "LateralTableFunction",
+                    "Function": function_name,
+                    "Arguments": expr_vec_fmt!(args),
+                    "Join Type": join_type.to_string(),
+                })
+            }
         }
     }
 }
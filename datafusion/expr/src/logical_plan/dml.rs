@@ -25,7 +25,7 @@ use arrow::datatypes::{DataType, Field, Schema};
 use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::{DFSchemaRef, TableReference};
 
-use crate::{LogicalPlan, TableSource};
+use crate::{Expr, LogicalPlan, TableSource};
 
 /// Operator that copies the contents of a database to file(s)
 #[derive(Clone)]
@@ -239,6 +239,45 @@ pub enum WriteOp {
     Ctas,
     /// `TRUNCATE` operation
     Truncate,
+    /// `MERGE INTO` operation
+    ///
+    /// The input of the [`DmlStatement`] is the stream of changes to the
+    /// target table, with one row for each inserted, updated or deleted row:
+    /// * [`MERGE_ACTION_COLUMN`]: the action, `insert`, `update` or `delete`
+    /// * one column for each column of the target table with its new value,
+    ///   or, for `delete`, the current value
+    /// * one column for each column of the target table, prefixed with
+    ///   [`MERGE_OLD_VALUE_PREFIX`], with its current value, which is `NULL`
+    ///   for `insert`
+    Merge,
+    /// `ANALYZE TABLE` operation
+    ///
+    /// The input of the [`DmlStatement`] produces a single row with the
+    /// statistics collected from the table:
+    /// * [`ANALYZE_ROW_COUNT_COLUMN`]: the number of rows
+    /// * for each analyzed column, the columns named by
+    ///   [`analyze_statistic_column`] for `min`, `max`, `null_count` and
+    ///   `distinct_count`. Statistics that could not be collected for a
+    ///   column, such as the minimum of a nested column, are omitted
+    Analyze,
+}
+
+/// Name of the column with the action of each row of the input of a
+/// [`WriteOp::Merge`]
+pub const MERGE_ACTION_COLUMN: &str = "__merge_action";
+
+/// Prefix of the columns with the current values of the target rows of the
+/// input of a [`WriteOp::Merge`]
+pub const MERGE_OLD_VALUE_PREFIX: &str = "__merge_old_";
+
+/// Name of the column with the number of rows of the input of a
+/// [`WriteOp::Analyze`]
+pub const ANALYZE_ROW_COUNT_COLUMN: &str = "row_count";
+
+/// Return the name of the column with `statistic` of `column` in the input
+/// of a [`WriteOp::Analyze`], for example `min(a)`
+pub fn analyze_statistic_column(statistic: &str, column: &str) -> String {
+    format!("{statistic}({column})")
 }
 
 impl WriteOp {
@@ -250,6 +289,8 @@ impl WriteOp {
             WriteOp::Update => "Update",
             WriteOp::Ctas => "Ctas",
             WriteOp::Truncate => "Truncate",
+            WriteOp::Merge => "Merge",
+            WriteOp::Analyze => "Analyze",
         }
     }
 }
@@ -260,6 +301,36 @@ impl Display for WriteOp {
     }
 }
 
+/// Options of
+/// [`LogicalPlanBuilder::scd2_merge`](crate::LogicalPlanBuilder::scd2_merge)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Scd2MergeOptions {
+    /// The column of the table with the time a version of a row became
+    /// current
+    pub valid_from: String,
+    /// The column of the table with the time a version of a row was replaced
+    /// by a newer version, `NULL` for the current version
+    pub valid_to: String,
+    /// The time of the changes, for example `now()`
+    pub effective_time: Expr,
+}
+
+impl Scd2MergeOptions {
+    /// Create options for the validity columns `valid_from` and `valid_to`,
+    /// with changes effective at `effective_time`
+    pub fn new(
+        valid_from: impl Into<String>,
+        valid_to: impl Into<String>,
+        effective_time: Expr,
+    ) -> Self {
+        Self {
+            valid_from: valid_from.into(),
+            valid_to: valid_to.into(),
+            effective_time,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub enum InsertOp {
     /// Appends new rows to the existing table without modifying any
@@ -115,6 +115,26 @@ pub trait UserDefinedLogicalNode: fmt::Debug + Send + Sync {
         None
     }
 
+    /// Returns a copy of this node that only produces the output columns at
+    /// `output_columns`, the indices (in increasing order) of the columns of
+    /// [`Self::schema`] that are needed by its parents.
+    ///
+    /// This is used for projection push-down, so that this node neither
+    /// computes the columns that are not needed, nor requires the input
+    /// columns they are computed from (see [`Self::necessary_children_exprs`]
+    /// of the returned node). The output schema of the returned node must
+    /// consist of the columns of [`Self::schema`] at `output_columns`, in the
+    /// same order.
+    ///
+    /// Return `Ok(None)`, the default, if this node always produces all of
+    /// its output columns.
+    fn prune_output_columns(
+        &self,
+        _output_columns: &[usize],
+    ) -> Result<Option<Arc<dyn UserDefinedLogicalNode>>> {
+        Ok(None)
+    }
+
     /// Update the hash `state` with this node requirements from
     /// [`Hash`].
     ///
@@ -303,6 +323,14 @@ pub trait UserDefinedLogicalNodeCore:
         None
     }
 
+    /// Returns a copy of this node that only produces the output columns at
+    /// `output_columns`.
+    ///
+    /// See [`UserDefinedLogicalNode::prune_output_columns`] for details.
+    fn prune_output_columns(&self, _output_columns: &[usize]) -> Result<Option<Self>> {
+        Ok(None)
+    }
+
     /// Returns `true` if a limit can be safely pushed down through this
     /// `UserDefinedLogicalNode` node.
     ///
@@ -364,6 +392,15 @@ impl<T: UserDefinedLogicalNodeCore> UserDefinedLogicalNode for T {
         self.necessary_children_exprs(output_columns)
     }
 
+    fn prune_output_columns(
+        &self,
+        output_columns: &[usize],
+    ) -> Result<Option<Arc<dyn UserDefinedLogicalNode>>> {
+        Ok(self
+            .prune_output_columns(output_columns)?
+            .map(|node| Arc::new(node) as _))
+    }
+
     fn dyn_hash(&self, state: &mut dyn Hasher) {
         let mut s = state;
         self.hash(&mut s);
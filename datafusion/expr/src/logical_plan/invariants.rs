@@ -66,6 +66,80 @@ pub fn assert_executable_invariants(plan: &LogicalPlan) -> Result<()> {
     Ok(())
 }
 
+/// Validates `plan`, and all of its subqueries, returning an error describing
+/// the first broken invariant found.
+///
+/// This is intended to be called after building or rewriting a plan by hand
+/// (for example in a custom optimizer rule), to catch bugs before the plan is
+/// executed. In addition to the [`InvariantLevel::Executable`] checks, it
+/// confirms that for every node:
+///
+/// * its schema matches the schema derived from its inputs and expressions
+/// * every column referenced by its expressions is produced by one of its
+///   inputs
+pub fn validate_logical_plan(plan: &LogicalPlan) -> Result<()> {
+    assert_executable_invariants(plan)?;
+    plan.apply_with_subqueries(|plan: &LogicalPlan| {
+        assert_always_invariants_at_current_node(plan)?;
+        assert_no_dangling_columns(plan)?;
+        assert_coherent_schema(plan)?;
+        Ok(TreeNodeRecursion::Continue)
+    })
+    .map(|_| ())
+}
+
+/// Returns an error if the schema of the plan node does not match the schema
+/// derived from its inputs and expressions. Ignores metadata and nullability.
+fn assert_coherent_schema(plan: &LogicalPlan) -> Result<()> {
+    let recomputed = plan.clone().recompute_schema().map_err(|e| {
+        e.context(format!("Failed to derive the schema of {}", plan.display()))
+    })?;
+    if !plan
+        .schema()
+        .logically_equivalent_names_and_types(recomputed.schema())
+    {
+        return plan_err!(
+            "The schema of {} does not match the schema derived from its inputs and expressions: expected {}, got {}",
+            plan.display(),
+            recomputed.schema(),
+            plan.schema()
+        );
+    }
+    Ok(())
+}
+
+/// Returns an error if an expression of the plan node references a column
+/// that none of its inputs produces.
+///
+/// The expressions of leaf nodes, such as the filters of a [`TableScan`],
+/// reference their source rather than an input, and are not checked.
+///
+/// [`TableScan`]: crate::TableScan
+fn assert_no_dangling_columns(plan: &LogicalPlan) -> Result<()> {
+    let inputs = plan.inputs();
+    if inputs.is_empty() {
+        return Ok(());
+    }
+    plan.apply_expressions(|expr| {
+        for column in expr.column_refs() {
+            if !inputs.iter().any(|input| input.schema().has_column(column)) {
+                let available = inputs
+                    .iter()
+                    .flat_map(|input| input.schema().columns())
+                    .map(|column| column.flat_name())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return plan_err!(
+                    "Column {column} referenced by {} is not produced by its inputs. Available columns are: [{available}]",
+                    plan.display()
+                );
+            }
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })
+    .map(|_| ())
+}
+
 /// Asserts that the query plan, and subplan, extension nodes have valid invariants.
 ///
 /// Refer to [`UserDefinedLogicalNode::check_invariants`](super::UserDefinedLogicalNode)
@@ -437,7 +511,9 @@ mod test {
     use std::cmp::Ordering;
     use std::sync::Arc;
 
-    use crate::{Extension, UserDefinedLogicalNodeCore};
+    use crate::logical_plan::builder::{LogicalPlanBuilder, table_scan};
+    use crate::{Extension, Projection, UserDefinedLogicalNodeCore, col};
+    use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::{DFSchema, DFSchemaRef};
 
     use super::*;
@@ -489,6 +565,53 @@ mod test {
         }
     }
 
+    #[test]
+    fn validate_logical_plan_reports_broken_invariants() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let scan = table_scan(Some("t"), &schema, None)?.build()?;
+        let plan = LogicalPlanBuilder::from(scan.clone())
+            .filter(col("a").gt(col("b")))?
+            .project(vec![col("a")])?
+            .build()?;
+        validate_logical_plan(&plan)?;
+
+        let only_a = LogicalPlanBuilder::from(scan.clone())
+            .project(vec![col("a")])?
+            .build()?;
+
+        // a projection whose schema was not derived from its expressions
+        let plan = LogicalPlan::Projection(Projection::try_new_with_schema(
+            vec![col("t.b")],
+            Arc::new(scan),
+            Arc::clone(only_a.schema()),
+        )?);
+        let err = validate_logical_plan(&plan).unwrap_err();
+        assert!(
+            err.strip_backtrace()
+                .contains("The schema of Projection: t.b does not match the schema derived from its inputs and expressions"),
+            "{err}"
+        );
+
+        // a projection referencing a column its input does not produce
+        let schema = Arc::clone(only_a.schema());
+        let plan = LogicalPlan::Projection(Projection::try_new_with_schema(
+            vec![col("t.b")],
+            Arc::new(only_a),
+            schema,
+        )?);
+        let err = validate_logical_plan(&plan).unwrap_err();
+        assert!(
+            err.strip_backtrace()
+                .contains("Column t.b referenced by Projection: t.b is not produced by its inputs. Available columns are: [t.a]"),
+            "{err}"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn wont_fail_extension_plan() {
         let plan = LogicalPlan::Extension(Extension {
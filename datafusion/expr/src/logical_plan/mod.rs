@@ -21,33 +21,38 @@ pub mod display;
 pub mod dml;
 mod extension;
 pub(crate) mod invariants;
-pub use invariants::{InvariantLevel, assert_expected_schema, check_subquery_expr};
+pub use invariants::{
+    InvariantLevel, assert_expected_schema, check_subquery_expr, validate_logical_plan,
+};
 mod plan;
+mod plan_rewrite;
+mod policy;
 mod statement;
 pub mod tree_node;
 
 pub use builder::{
     LogicalPlanBuilder, LogicalPlanBuilderOptions, LogicalTableSource, UNNAMED_TABLE,
-    build_join_schema, requalify_sides_if_needed, table_scan, union,
-    wrap_projection_for_join_if_necessary,
+    build_join_schema, project_plan_preserving, requalify_sides_if_needed, table_scan,
+    union, wrap_projection_for_join_if_necessary,
 };
 pub use ddl::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-    CreateFunctionBody, CreateIndex, CreateMemoryTable, CreateView, DdlStatement,
-    DropCatalogSchema, DropFunction, DropTable, DropView, OperateFunctionArg,
+    AlterTable, AlterTableOperation, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable, CreateFunction, CreateFunctionBody, CreateIndex,
+    CreateMemoryTable, CreateView, DdlStatement, DropCatalogSchema, DropFunction,
+    DropTable, DropView, OperateFunctionArg, RefreshTableSchema,
 };
-pub use dml::{DmlStatement, WriteOp};
+pub use dml::{DmlStatement, Scd2MergeOptions, WriteOp};
 pub use plan::{
-    Aggregate, Analyze, ColumnUnnestList, DescribeTable, Distinct, DistinctOn,
-    EmptyRelation, Explain, ExplainOption, Extension, FetchType, Filter, Join,
-    JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Projection,
-    RecursiveQuery, Repartition, SkipType, Sort, StringifiedPlan, Subquery,
-    SubqueryAlias, TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
-    projection_schema,
+    Aggregate, Analyze, AsOfMatch, ColumnUnnestList, DedupKeep, DescribeTable, Distinct,
+    DistinctOn, EmptyRelation, Explain, ExplainOption, Extension, FetchType, Filter,
+    Join, JoinConstraint, JoinType, LateralTableFunction, Limit, LogicalPlan,
+    Partitioning, PlanType, Projection, RecursiveQuery, Repartition, SkipType, Sort,
+    StringifiedPlan, Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Union,
+    Unnest, Values, Window, projection_schema,
 };
 pub use statement::{
-    Deallocate, Execute, Prepare, ResetVariable, SetVariable, Statement,
-    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    Deallocate, Estimate, Execute, ExecuteImmediate, Prepare, ResetVariable, SetVariable,
+    Statement, TransactionAccessMode, TransactionConclusion, TransactionEnd,
     TransactionIsolationLevel, TransactionStart,
 };
 
@@ -56,3 +61,6 @@ pub use datafusion_common::format::ExplainFormat;
 pub use display::display_schema;
 
 pub use extension::{UserDefinedLogicalNode, UserDefinedLogicalNodeCore};
+
+pub use plan_rewrite::PlanRewrite;
+pub use policy::PolicyProvider;
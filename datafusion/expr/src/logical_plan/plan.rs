@@ -45,23 +45,26 @@ use crate::utils::{
     grouping_set_expr_count, grouping_set_to_exprlist, split_conjunction,
 };
 use crate::{
-    BinaryExpr, CreateMemoryTable, CreateView, Execute, Expr, ExprSchemable, GroupingSet,
-    LogicalPlanBuilder, Operator, Prepare, TableProviderFilterPushDown, TableSource,
-    WindowFunctionDefinition, build_join_schema, expr_vec_fmt, requalify_sides_if_needed,
+    BinaryExpr, CreateMemoryTable, CreateView, Estimate, Execute, ExecuteImmediate, Expr,
+    ExprSchemable, GroupingSet, LogicalPlanBuilder, Operator, Prepare,
+    TableProviderFilterPushDown, TableSource, WindowFunctionDefinition,
+    build_join_schema, expr_vec_fmt, requalify_sides_if_needed,
 };
 
 use arrow::datatypes::{DataType, Field, FieldRef, Schema, SchemaRef};
 use datafusion_common::cse::{NormalizeEq, Normalizeable};
-use datafusion_common::format::ExplainFormat;
+use datafusion_common::format::{ExplainAnalyzeFormat, ExplainFormat};
 use datafusion_common::metadata::check_metadata_with_storage_equal;
+use datafusion_common::metadata_column::with_metadata_column_marker;
 use datafusion_common::tree_node::{
-    Transformed, TreeNode, TreeNodeContainer, TreeNodeRecursion,
+    Transformed, TreeNode, TreeNodeContainer, TreeNodeRecursion, TreeNodeRefContainer,
 };
 use datafusion_common::{
     Column, Constraints, DFSchema, DFSchemaRef, DataFusionError, Dependency,
     FunctionalDependence, FunctionalDependencies, NullEquality, ParamValues, Result,
-    ScalarValue, Spans, TableReference, UnnestOptions, aggregate_functional_dependencies,
-    assert_eq_or_internal_err, assert_or_internal_err, internal_err, plan_err,
+    ScalarValue, Spans, TableReference, UNNEST_ORDINALITY_COLUMN, UnnestOptions,
+    aggregate_functional_dependencies, assert_eq_or_internal_err, assert_or_internal_err,
+    internal_err, plan_datafusion_err, plan_err,
 };
 use indexmap::IndexSet;
 
@@ -290,6 +293,9 @@ pub enum LogicalPlan {
     Unnest(Unnest),
     /// A variadic query (e.g. "Recursive CTEs")
     RecursiveQuery(RecursiveQuery),
+    /// Call a table function for every row of the input. This is used to
+    /// implement SQL `LATERAL` table function calls
+    LateralTableFunction(LateralTableFunction),
 }
 
 impl Default for LogicalPlan {
@@ -353,6 +359,9 @@ impl LogicalPlan {
             LogicalPlan::Copy(CopyTo { output_schema, .. }) => output_schema,
             LogicalPlan::Ddl(ddl) => ddl.schema(),
             LogicalPlan::Unnest(Unnest { schema, .. }) => schema,
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                schema, ..
+            }) => schema,
             LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
                 // we take the schema of the static term as the schema of the entire recursive query
                 static_term.schema()
@@ -368,6 +377,7 @@ impl LogicalPlan {
             | LogicalPlan::Projection(_)
             | LogicalPlan::Aggregate(_)
             | LogicalPlan::Unnest(_)
+            | LogicalPlan::LateralTableFunction(_)
             | LogicalPlan::Join(_) => self
                 .inputs()
                 .iter()
@@ -474,6 +484,9 @@ impl LogicalPlan {
             LogicalPlan::Copy(copy) => vec![&copy.input],
             LogicalPlan::Ddl(ddl) => ddl.inputs(),
             LogicalPlan::Unnest(Unnest { input, .. }) => vec![input],
+            LogicalPlan::LateralTableFunction(LateralTableFunction { input, .. }) => {
+                vec![input]
+            }
             LogicalPlan::RecursiveQuery(RecursiveQuery {
                 static_term,
                 recursive_term,
@@ -566,6 +579,17 @@ impl LogicalPlan {
                     right.head_output_expr()
                 }
             },
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                input,
+                schema,
+                ..
+            }) => {
+                if input.schema().fields().is_empty() {
+                    Ok(Some(Expr::Column(Column::from(schema.qualified_field(0)))))
+                } else {
+                    input.head_output_expr()
+                }
+            }
             LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
                 static_term.head_output_expr()
             }
@@ -665,6 +689,7 @@ impl LogicalPlan {
                 schema: _,
                 null_equality,
                 null_aware,
+                asof_match,
             }) => {
                 let schema =
                     build_join_schema(left.schema(), right.schema(), &join_type)?;
@@ -687,6 +712,7 @@ impl LogicalPlan {
                     schema: DFSchemaRef::new(schema),
                     null_equality,
                     null_aware,
+                    asof_match,
                 }))
             }
             LogicalPlan::Subquery(_) => Ok(self),
@@ -756,6 +782,21 @@ impl LogicalPlan {
                 // Update schema with unnested column type.
                 unnest_with_options(Arc::unwrap_or_clone(input), exec_columns, options)
             }
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                input,
+                function_name,
+                args,
+                function_schema,
+                join_type,
+                schema: _,
+            }) => LateralTableFunction::try_new(
+                input,
+                function_name,
+                args,
+                function_schema,
+                join_type,
+            )
+            .map(LogicalPlan::LateralTableFunction),
         }
     }
 
@@ -907,22 +948,46 @@ impl LogicalPlan {
                 on,
                 null_equality,
                 null_aware,
+                asof_match,
                 ..
             }) => {
                 let (left, right) = self.only_two_inputs(inputs)?;
                 let schema = build_join_schema(left.schema(), right.schema(), join_type)?;
 
                 let equi_expr_count = on.len() * 2;
-                assert!(expr.len() >= equi_expr_count);
+                let asof_expr_count =
+                    asof_match.as_deref().map_or(0, AsOfMatch::expression_count);
+                assert!(expr.len() >= equi_expr_count + asof_expr_count);
 
                 // Assume that the last expr, if any,
                 // is the filter_expr (non equality predicate from ON clause)
-                let filter_expr = if expr.len() > equi_expr_count {
+                let filter_expr = if expr.len() > equi_expr_count + asof_expr_count {
                     expr.pop()
                 } else {
                     None
                 };
 
+                // The ASOF match condition, if any, follows the equi-exprs
+                let new_asof_match = match asof_match {
+                    Some(asof_match) => {
+                        let mut asof_exprs = expr.split_off(equi_expr_count).into_iter();
+                        let (Some(asof_left), Some(asof_right)) =
+                            (asof_exprs.next(), asof_exprs.next())
+                        else {
+                            return internal_err!(
+                                "Expected the ASOF join match expressions"
+                            );
+                        };
+                        Some(Box::new(AsOfMatch {
+                            left: asof_left,
+                            op: asof_match.op,
+                            right: asof_right,
+                            tolerance: asof_exprs.next(),
+                        }))
+                    }
+                    None => None,
+                };
+
                 // The first part of expr is equi-exprs,
                 // and the struct of each equi-expr is like `left-expr = right-expr`.
                 assert_eq!(expr.len(), equi_expr_count);
@@ -949,6 +1014,7 @@ impl LogicalPlan {
                     schema: DFSchemaRef::new(schema),
                     null_equality: *null_equality,
                     null_aware: *null_aware,
+                    asof_match: new_asof_match,
                 }))
             }
             LogicalPlan::Subquery(Subquery {
@@ -1092,6 +1158,7 @@ impl LogicalPlan {
                 let input = self.only_input(inputs)?;
                 Ok(LogicalPlan::Analyze(Analyze {
                     verbose: a.verbose,
+                    format: a.format,
                     schema: Arc::clone(&a.schema),
                     input: Arc::new(input),
                 }))
@@ -1119,6 +1186,13 @@ impl LogicalPlan {
                     input: Arc::new(input),
                 })))
             }
+            LogicalPlan::Statement(Statement::Estimate(_)) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Ok(LogicalPlan::Statement(Statement::Estimate(Estimate {
+                    input: Arc::new(input),
+                })))
+            }
             LogicalPlan::Statement(Statement::Execute(Execute { name, .. })) => {
                 self.assert_no_inputs(inputs)?;
                 Ok(LogicalPlan::Statement(Statement::Execute(Execute {
@@ -1126,6 +1200,19 @@ impl LogicalPlan {
                     parameters: expr,
                 })))
             }
+            LogicalPlan::Statement(Statement::ExecuteImmediate(_)) => {
+                self.assert_no_inputs(inputs)?;
+                let mut expr = expr.into_iter();
+                let Some(sql) = expr.next() else {
+                    return internal_err!("ExecuteImmediate requires a SQL expression");
+                };
+                Ok(LogicalPlan::Statement(Statement::ExecuteImmediate(
+                    ExecuteImmediate {
+                        sql,
+                        parameters: expr.collect(),
+                    },
+                )))
+            }
             LogicalPlan::TableScan(ts) => {
                 self.assert_no_inputs(inputs)?;
                 Ok(LogicalPlan::TableScan(TableScan {
@@ -1154,6 +1241,23 @@ impl LogicalPlan {
                     unnest_with_options(input, columns.clone(), options.clone())?;
                 Ok(new_plan)
             }
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                function_name,
+                function_schema,
+                join_type,
+                ..
+            }) => {
+                let input = self.only_input(inputs)?;
+                Ok(LogicalPlan::LateralTableFunction(
+                    LateralTableFunction::try_new(
+                        Arc::new(input),
+                        function_name.clone(),
+                        expr,
+                        Arc::clone(function_schema),
+                        *join_type,
+                    )?,
+                ))
+            }
         }
     }
 
@@ -1384,6 +1488,7 @@ impl LogicalPlan {
             ) => input.max_rows(),
             LogicalPlan::Values(v) => Some(v.values.len()),
             LogicalPlan::Unnest(_) => None,
+            LogicalPlan::LateralTableFunction(_) => None,
             LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
@@ -1429,6 +1534,7 @@ impl LogicalPlan {
             LogicalPlan::Copy(_) => Ok(None),
             LogicalPlan::DescribeTable(_) => Ok(None),
             LogicalPlan::Unnest(_) => Ok(None),
+            LogicalPlan::LateralTableFunction(_) => Ok(None),
             LogicalPlan::RecursiveQuery(_) => Ok(None),
         }
     }
@@ -1467,6 +1573,7 @@ impl LogicalPlan {
             LogicalPlan::Copy(_) => Ok(None),
             LogicalPlan::DescribeTable(_) => Ok(None),
             LogicalPlan::Unnest(_) => Ok(None),
+            LogicalPlan::LateralTableFunction(_) => Ok(None),
             LogicalPlan::RecursiveQuery(_) => Ok(None),
         }
     }
@@ -1866,6 +1973,18 @@ impl LogicalPlan {
                     }) => {
                         write!(f, "RecursiveQuery: is_distinct={is_distinct}")
                     }
+                    LogicalPlan::LateralTableFunction(LateralTableFunction {
+                        function_name,
+                        args,
+                        join_type,
+                        ..
+                    }) => {
+                        write!(
+                            f,
+                            "LateralTableFunction: {function_name}({}), join_type={join_type}",
+                            expr_vec_fmt!(args)
+                        )
+                    }
                     LogicalPlan::Values(Values { values, .. }) => {
                         let str_values: Vec<_> = values
                             .iter()
@@ -1896,9 +2015,17 @@ impl LogicalPlan {
                         let projected_fields = match projection {
                             Some(indices) => {
                                 let schema = source.schema();
+                                let metadata_columns = source.metadata_columns();
                                 let names: Vec<&str> = indices
                                     .iter()
-                                    .map(|i| schema.field(*i).name().as_str())
+                                    .filter_map(|i| match schema.fields().get(*i) {
+                                        Some(field) => Some(field),
+                                        None => metadata_columns
+                                            .as_ref()?
+                                            .fields()
+                                            .get(i - schema.fields().len()),
+                                    })
+                                    .map(|field| field.name().as_str())
                                     .collect();
                                 format!(" projection=[{}]", names.join(", "))
                             }
@@ -2029,6 +2156,25 @@ impl LogicalPlan {
 
                         Ok(())
                     }
+                    LogicalPlan::Join(Join {
+                        on: keys,
+                        filter,
+                        join_type,
+                        asof_match: Some(asof_match),
+                        ..
+                    }) => {
+                        let join_expr: Vec<String> =
+                            keys.iter().map(|(l, r)| format!("{l} = {r}")).collect();
+                        write!(f, "{join_type} AsOf Join:")?;
+                        if !join_expr.is_empty() {
+                            write!(f, " {}", join_expr.join(", "))?;
+                        }
+                        write!(f, " Match: {asof_match}")?;
+                        if let Some(filter) = filter {
+                            write!(f, " Filter: {filter}")?;
+                        }
+                        Ok(())
+                    }
                     LogicalPlan::Join(Join {
                         on: keys,
                         filter,
@@ -2159,6 +2305,7 @@ impl LogicalPlan {
                         input: plan,
                         list_type_columns: list_col_indices,
                         struct_type_columns: struct_col_indices,
+                        options,
                         ..
                     }) => {
                         let input_columns = plan.schema().columns();
@@ -2182,7 +2329,14 @@ impl LogicalPlan {
                             "Unnest: lists[{}] structs[{}]",
                             expr_vec_fmt!(list_type_columns),
                             expr_vec_fmt!(struct_type_columns)
-                        )
+                        )?;
+                        if options.ordinality {
+                            write!(f, " with_ordinality")?;
+                        }
+                        if options.outer {
+                            write!(f, " outer")?;
+                        }
+                        Ok(())
                     }
                 }
             }
@@ -2761,7 +2915,9 @@ pub struct TableScan {
     pub table_name: TableReference,
     /// The source of the table
     pub source: Arc<dyn TableSource>,
-    /// Optional column indices to use as a projection
+    /// Optional column indices to use as a projection. Indices past the end
+    /// of the source schema refer to its hidden metadata columns, see
+    /// [`TableSource::metadata_columns`]
     pub projection: Option<Vec<usize>>,
     /// The schema description of the output
     pub projected_schema: DFSchemaRef,
@@ -2866,14 +3022,36 @@ impl TableScan {
                 let projected_func_dependencies =
                     func_dependencies.project_functional_dependencies(p, p.len());
 
-                let df_schema = DFSchema::new_with_metadata(
-                    p.iter()
-                        .map(|i| {
-                            (Some(table_name.clone()), Arc::clone(&schema.fields()[*i]))
-                        })
-                        .collect(),
-                    schema.metadata.clone(),
-                )?;
+                let metadata_columns = table_source.metadata_columns();
+                let fields = p
+                    .iter()
+                    .map(|&i| {
+                        let field = match (schema.fields().get(i), &metadata_columns) {
+                            (Some(field), _) => Arc::clone(field),
+                            (None, Some(metadata_columns)) => metadata_columns
+                                .fields()
+                                .get(i - schema.fields().len())
+                                .map(|field| {
+                                    Arc::new(with_metadata_column_marker(
+                                        field.as_ref().clone(),
+                                    ))
+                                })
+                                .ok_or_else(|| {
+                                    plan_datafusion_err!(
+                                        "Projection index {i} is out of bounds for table {table_name}"
+                                    )
+                                })?,
+                            (None, None) => {
+                                return plan_err!(
+                                    "Projection index {i} is out of bounds for table {table_name}"
+                                );
+                            }
+                        };
+                        Ok((Some(table_name.clone()), field))
+                    })
+                    .collect::<Result<_>>()?;
+                let df_schema =
+                    DFSchema::new_with_metadata(fields, schema.metadata.clone())?;
                 df_schema.with_functional_dependencies(projected_func_dependencies)
             })
             .unwrap_or_else(|| {
@@ -3205,6 +3383,8 @@ pub struct ExplainOption {
     pub analyze: bool,
     /// Output syntax/format
     pub format: ExplainFormat,
+    /// Output format of `EXPLAIN ANALYZE`
+    pub analyze_format: ExplainAnalyzeFormat,
 }
 
 impl Default for ExplainOption {
@@ -3213,6 +3393,7 @@ impl Default for ExplainOption {
             verbose: false,
             analyze: false,
             format: ExplainFormat::Indent,
+            analyze_format: ExplainAnalyzeFormat::Indent,
         }
     }
 }
@@ -3235,6 +3416,12 @@ impl ExplainOption {
         self.format = format;
         self
     }
+
+    /// Builder‐style setter for `analyze_format`
+    pub fn with_analyze_format(mut self, analyze_format: ExplainAnalyzeFormat) -> Self {
+        self.analyze_format = analyze_format;
+        self
+    }
 }
 
 /// Produces a relation with string representations of
@@ -3299,6 +3486,8 @@ impl PartialOrd for Explain {
 pub struct Analyze {
     /// Should extra detail be included?
     pub verbose: bool,
+    /// Output format of the plan with its metrics
+    pub format: ExplainAnalyzeFormat,
     /// The logical plan that is being EXPLAIN ANALYZE'd
     pub input: Arc<LogicalPlan>,
     /// The output schema of the explain (2 columns of text)
@@ -3309,7 +3498,10 @@ pub struct Analyze {
 impl PartialOrd for Analyze {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.verbose.partial_cmp(&other.verbose) {
-            Some(Ordering::Equal) => self.input.partial_cmp(&other.input),
+            Some(Ordering::Equal) => match self.format.partial_cmp(&other.format) {
+                Some(Ordering::Equal) => self.input.partial_cmp(&other.input),
+                cmp => cmp,
+            },
             cmp => cmp,
         }
         // TODO (https://github.com/apache/datafusion/issues/17477) avoid recomparing all fields
@@ -3514,6 +3706,26 @@ impl DistinctOn {
     }
 }
 
+/// Which row of each group of duplicates is kept by
+/// [`LogicalPlanBuilder::deduplicate`](crate::LogicalPlanBuilder::deduplicate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Default)]
+pub enum DedupKeep {
+    /// Keep the first row of each group according to the `ORDER BY` clause
+    #[default]
+    First,
+    /// Keep the last row of each group according to the `ORDER BY` clause
+    Last,
+}
+
+impl Display for DedupKeep {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DedupKeep::First => write!(f, "FIRST"),
+            DedupKeep::Last => write!(f, "LAST"),
+        }
+    }
+}
+
 // Manual implementation needed because of `schema` field. Comparison excludes this field.
 impl PartialOrd for DistinctOn {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -3920,6 +4132,109 @@ pub struct Join {
     ///
     /// This is required for correct NOT IN subquery behavior with three-valued logic.
     pub null_aware: bool,
+    /// The match condition of an ASOF join, if this is one.
+    ///
+    /// An ASOF join matches each left row with at most one right row: the
+    /// nearest row, according to [`AsOfMatch::op`], among the right rows that
+    /// satisfy the equijoin clause in `on`. Only `Inner` and `Left` joins may
+    /// carry a match condition.
+    pub asof_match: Option<Box<AsOfMatch>>,
+}
+
+/// The match condition of an ASOF [`Join`].
+///
+/// For example `l.ts >= r.ts` matches each left row with the right row that has
+/// the greatest `r.ts` not after `l.ts`, i.e. the most recent preceding right
+/// row. `<` and `<=` match the nearest following right row instead.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct AsOfMatch {
+    /// Ordering expression of the left input
+    pub left: Expr,
+    /// Comparison between `left` and `right`: one of `>=`, `>`, `<=` or `<`
+    pub op: Operator,
+    /// Ordering expression of the right input
+    pub right: Expr,
+    /// Optional predicate on the joined row that the nearest match must
+    /// satisfy, such as `l.ts - r.ts <= INTERVAL '5' SECOND`. Left rows whose
+    /// nearest match does not satisfy it are treated as unmatched.
+    pub tolerance: Option<Expr>,
+}
+
+impl AsOfMatch {
+    /// Creates a new match condition, checking that `op` is an inequality
+    pub fn try_new(
+        left: Expr,
+        op: Operator,
+        right: Expr,
+        tolerance: Option<Expr>,
+    ) -> Result<Self> {
+        if !matches!(
+            op,
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq
+        ) {
+            return plan_err!(
+                "ASOF join match condition must be one of >, >=, < or <=, got {op}"
+            );
+        }
+        Ok(Self {
+            left,
+            op,
+            right,
+            tolerance,
+        })
+    }
+
+    /// Number of expressions in this match condition, including the tolerance
+    pub(crate) fn expression_count(&self) -> usize {
+        2 + usize::from(self.tolerance.is_some())
+    }
+}
+
+/// Placeholder used while a boxed [`AsOfMatch`] is rewritten in place
+impl Default for AsOfMatch {
+    fn default() -> Self {
+        Self {
+            left: Expr::default(),
+            op: Operator::GtEq,
+            right: Expr::default(),
+            tolerance: None,
+        }
+    }
+}
+
+impl<'a> TreeNodeContainer<'a, Expr> for AsOfMatch {
+    fn apply_elements<F: FnMut(&'a Expr) -> Result<TreeNodeRecursion>>(
+        &'a self,
+        f: F,
+    ) -> Result<TreeNodeRecursion> {
+        (&self.left, &self.right, &self.tolerance).apply_ref_elements(f)
+    }
+
+    fn map_elements<F: FnMut(Expr) -> Result<Transformed<Expr>>>(
+        self,
+        f: F,
+    ) -> Result<Transformed<Self>> {
+        (self.left, self.right, self.tolerance)
+            .map_elements(f)?
+            .map_data(|(left, right, tolerance)| {
+                Ok(Self {
+                    left,
+                    op: self.op,
+                    right,
+                    tolerance,
+                })
+            })
+    }
+}
+
+impl Display for AsOfMatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.op, self.right)?;
+        if let Some(tolerance) = &self.tolerance {
+            write!(f, " AND {tolerance}")?;
+        }
+        Ok(())
+    }
 }
 
 impl Join {
@@ -3965,9 +4280,24 @@ impl Join {
             schema: Arc::new(join_schema),
             null_equality,
             null_aware,
+            asof_match: None,
         })
     }
 
+    /// Turns this join into an ASOF join with the given match condition.
+    ///
+    /// Returns an error if the join type is neither `Inner` nor `Left`.
+    pub fn with_asof_match(mut self, asof_match: AsOfMatch) -> Result<Self> {
+        if !matches!(self.join_type, JoinType::Inner | JoinType::Left) {
+            return plan_err!(
+                "ASOF join only supports Inner and Left joins, got {}",
+                self.join_type
+            );
+        }
+        self.asof_match = Some(Box::new(asof_match));
+        Ok(self)
+    }
+
     /// Create Join with input which wrapped with projection, this method is used in physical planning only to help
     /// create the physical join.
     pub fn try_new_with_project_input(
@@ -4021,6 +4351,7 @@ impl Join {
                 schema: Arc::new(join_schema),
                 null_equality: original_join.null_equality,
                 null_aware: original_join.null_aware,
+                asof_match: original_join.asof_match.clone(),
             },
             requalified,
         ))
@@ -4046,6 +4377,8 @@ impl PartialOrd for Join {
             pub join_constraint: &'a JoinConstraint,
             /// The null handling behavior for equalities
             pub null_equality: &'a NullEquality,
+            /// ASOF match condition
+            pub asof_match: &'a Option<Box<AsOfMatch>>,
         }
         let comparable_self = ComparableJoin {
             left: &self.left,
@@ -4055,6 +4388,7 @@ impl PartialOrd for Join {
             join_type: &self.join_type,
             join_constraint: &self.join_constraint,
             null_equality: &self.null_equality,
+            asof_match: &self.asof_match,
         };
         let comparable_other = ComparableJoin {
             left: &other.left,
@@ -4064,6 +4398,7 @@ impl PartialOrd for Join {
             join_type: &other.join_type,
             join_constraint: &other.join_constraint,
             null_equality: &other.null_equality,
+            asof_match: &other.asof_match,
         };
         comparable_self
             .partial_cmp(&comparable_other)
@@ -4275,7 +4610,7 @@ impl Unnest {
         // 4.unnest_col2_depth_1: int
         // Meaning the placeholder column will be replaced by its unnested variation(s), note
         // the plural.
-        let fields = input_schema
+        let mut fields = input_schema
             .iter()
             .enumerate()
             .map(|(index, (original_qualifier, original_field))| {
@@ -4286,6 +4621,13 @@ impl Unnest {
                             .iter()
                             .filter(|p| -> bool { &p.input_column == *column_to_unnest })
                             .collect::<Vec<_>>();
+                        if !recursions_on_column.is_empty()
+                            && matches!(original_field.data_type(), DataType::Map(_, _))
+                        {
+                            return plan_err!(
+                                "Map column {column_to_unnest} can only be unnested with depth 1"
+                            );
+                        }
                         let mut transformed_columns = recursions_on_column
                             .iter()
                             .map(|r| {
@@ -4316,6 +4658,20 @@ impl Unnest {
                                 DataType::Struct(_) => {
                                     struct_columns.push(index);
                                 }
+                                // A map is unnested as a list of its entries,
+                                // which are then flattened like a struct
+                                DataType::Map(_, _) => {
+                                    list_columns.push((
+                                        index,
+                                        ColumnUnnestList {
+                                            output_column: Column::from_name(
+                                                &column_to_unnest.name,
+                                            ),
+                                            depth: 1,
+                                        },
+                                    ));
+                                    struct_columns.push(index);
+                                }
                                 DataType::List(_)
                                 | DataType::FixedSizeList(_, _)
                                 | DataType::LargeList(_)
@@ -4361,6 +4717,15 @@ impl Unnest {
             .flatten()
             .collect::<Vec<_>>();
 
+        if options.ordinality {
+            // The position of a row depends on the unnested columns
+            dependency_indices.push(input_schema.index_of_column(&exec_columns[0])?);
+            fields.push((
+                None,
+                Arc::new(Field::new(UNNEST_ORDINALITY_COLUMN, DataType::Int64, false)),
+            ));
+        }
+
         let metadata = input_schema.metadata().clone();
         let df_schema = DFSchema::new_with_metadata(fields, metadata)?;
         // We can use the existing functional dependencies:
@@ -4379,6 +4744,75 @@ impl Unnest {
     }
 }
 
+/// Calls a table function for every row of its input, with arguments that
+/// reference columns of the input, and joins the row with the rows the call
+/// returns. This is used to implement SQL `FROM t, LATERAL f(t.x)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LateralTableFunction {
+    /// The incoming logical plan
+    pub input: Arc<LogicalPlan>,
+    /// Name of the table function
+    pub function_name: String,
+    /// Arguments of the call, evaluated for every row of the input
+    pub args: Vec<Expr>,
+    /// The schema of the rows the function returns
+    pub function_schema: DFSchemaRef,
+    /// [`JoinType::Inner`] drops the input rows the function returns no rows
+    /// for, [`JoinType::Left`] keeps them, with nulls for the function's columns
+    pub join_type: JoinType,
+    /// The output schema, the input's columns followed by the function's
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for LateralTableFunction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.input.partial_cmp(&other.input) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        match self.function_name.partial_cmp(&other.function_name) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        match self.args.partial_cmp(&other.args) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        self.join_type
+            .partial_cmp(&other.join_type)
+            // TODO (https://github.com/apache/datafusion/issues/17477) avoid recomparing all fields
+            .filter(|cmp| *cmp != Ordering::Equal || self == other)
+    }
+}
+
+impl LateralTableFunction {
+    /// Create a new [`LateralTableFunction`], calling the table function
+    /// `function_name` with `args` for every row of `input`
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        function_name: impl Into<String>,
+        args: Vec<Expr>,
+        function_schema: DFSchemaRef,
+        join_type: JoinType,
+    ) -> Result<Self> {
+        if !matches!(join_type, JoinType::Inner | JoinType::Left) {
+            return plan_err!(
+                "Lateral table functions only support inner and left joins, got {join_type}"
+            );
+        }
+        let schema = build_join_schema(input.schema(), &function_schema, &join_type)?;
+        Ok(Self {
+            input,
+            function_name: function_name.into(),
+            args,
+            function_schema,
+            join_type,
+            schema: Arc::new(schema),
+        })
+    }
+}
+
 // Based on data type, either struct or a variant of list
 // return a set of columns as the result of unnesting
 // the input columns.
@@ -4420,6 +4854,19 @@ fn get_unnested_columns(
                 (column, Arc::new(new_field))
             }))
         }
+        DataType::Map(entries, _) => {
+            let DataType::Struct(fields) = entries.data_type() else {
+                return internal_err!("map entries must be a struct, got {data_type}");
+            };
+            // Unnesting may produce NULLs for the keys and values of a null map
+            qualified_columns.extend(fields.iter().map(|f| {
+                let new_name = format!("{}.{}", col_name, f.name());
+                let column = Column::from_name(&new_name);
+                let new_field =
+                    f.as_ref().clone().with_name(new_name).with_nullable(true);
+                (column, Arc::new(new_field))
+            }))
+        }
         _ => {
             return internal_err!("trying to unnest on invalid data type {data_type}");
         }
@@ -5490,6 +5937,7 @@ mod tests {
                 schema: Arc::new(left_schema.join(&right_schema)?),
                 null_equality: NullEquality::NullEqualsNothing,
                 null_aware: false,
+                asof_match: None,
             }))
         }
 
@@ -0,0 +1,52 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PlanRewrite`] to replace fragments of a [`LogicalPlan`]
+
+use std::fmt::Debug;
+
+use crate::LogicalPlan;
+use datafusion_common::Result;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::Transformed;
+
+/// Trait for rewriting fragments of a [`LogicalPlan`], for example to replace
+/// a `Filter` on a `TableScan` of a particular table with a custom
+/// [`Extension`](crate::Extension) node.
+///
+/// `PlanRewrite`s are registered on the `SessionState` with
+/// `SessionState::register_plan_rewrite`, and are applied to every node of
+/// the plan, bottom-up, after the plan has been analyzed and before it is
+/// optimized. This is simpler than writing an `OptimizerRule` for rewrites
+/// that only need to be done once per query.
+pub trait PlanRewrite: Debug {
+    /// Return a human readable name for this rewrite
+    fn name(&self) -> &str;
+
+    /// Potentially rewrite `plan` to some other plan
+    ///
+    /// Note that recursion is handled by the caller -- this method should only
+    /// handle `plan`, not recurse to its inputs. As the plan is rewritten
+    /// bottom-up, the inputs of `plan` have already been rewritten.
+    ///
+    /// The rewritten plan must have the same schema as `plan`.
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &ConfigOptions,
+    ) -> Result<Transformed<LogicalPlan>>;
+}
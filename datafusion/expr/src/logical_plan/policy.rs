@@ -0,0 +1,71 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PolicyProvider`] for row-level security and column masking
+
+use std::fmt::Debug;
+
+use crate::Expr;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{DFSchema, HashMap, Result, TableReference};
+
+/// Provides the row-level security and column masking policies of tables.
+///
+/// `PolicyProvider`s are registered on the `SessionState` with
+/// `SessionState::register_policy_provider`, and are consulted for every
+/// [`TableScan`](crate::TableScan) of a query while it is analyzed, before it
+/// is type coerced and optimized. This gives services that share a session
+/// between tenants or users a single place to enforce which rows and values
+/// they may see, regardless of how the query is written.
+///
+/// For a scan of a table with policies, the rows of the table are first
+/// filtered with the row filters of all providers, and the masked columns
+/// are then replaced with their masking expressions, in the order the
+/// providers were registered. The output of the scan keeps its names and
+/// types, so the rest of the plan is unchanged.
+pub trait PolicyProvider: Debug + Send + Sync {
+    /// Return a human readable name for this provider
+    fn name(&self) -> &str;
+
+    /// Returns a predicate the rows of `table` must satisfy to be visible, if
+    /// any.
+    ///
+    /// `schema` is the schema of the table, and the predicate may refer to
+    /// any of its columns, even those the query does not read.
+    fn row_filter(
+        &self,
+        _table: &TableReference,
+        _schema: &DFSchema,
+        _config: &ConfigOptions,
+    ) -> Result<Option<Expr>> {
+        Ok(None)
+    }
+
+    /// Returns the expressions replacing the values of the columns of
+    /// `table`, keyed by column name.
+    ///
+    /// A masking expression may refer to any of the columns of `schema`, and
+    /// is cast to the type of the column it replaces.
+    fn column_masks(
+        &self,
+        _table: &TableReference,
+        _schema: &DFSchema,
+        _config: &ConfigOptions,
+    ) -> Result<HashMap<String, Expr>> {
+        Ok(HashMap::new())
+    }
+}
@@ -46,9 +46,15 @@ pub enum Statement {
     Prepare(Prepare),
     /// Execute a prepared statement. This is used to implement SQL 'EXECUTE'.
     Execute(Execute),
+    /// Plan and execute a dynamically constructed SQL string.
+    /// This is used to implement SQL 'EXECUTE IMMEDIATE'.
+    ExecuteImmediate(ExecuteImmediate),
     /// Deallocate a prepared statement.
     /// This is used to implement SQL 'DEALLOCATE'.
     Deallocate(Deallocate),
+    /// Estimate the size and cost of a query without executing it.
+    /// This is used to implement SQL 'EXPLAIN ESTIMATE'.
+    Estimate(Estimate),
 }
 
 impl Statement {
@@ -68,14 +74,17 @@ impl Statement {
             Statement::ResetVariable(_) => "ResetVariable",
             Statement::Prepare(_) => "Prepare",
             Statement::Execute(_) => "Execute",
+            Statement::ExecuteImmediate(_) => "ExecuteImmediate",
             Statement::Deallocate(_) => "Deallocate",
+            Statement::Estimate(_) => "Estimate",
         }
     }
 
     /// Returns input LogicalPlans in the current `Statement`.
     pub(super) fn inputs(&self) -> Vec<&LogicalPlan> {
         match self {
-            Statement::Prepare(Prepare { input, .. }) => vec![input.as_ref()],
+            Statement::Prepare(Prepare { input, .. })
+            | Statement::Estimate(Estimate { input }) => vec![input.as_ref()],
             _ => vec![],
         }
     }
@@ -135,9 +144,17 @@ impl Statement {
                             expr_vec_fmt!(parameters)
                         )
                     }
+                    Statement::ExecuteImmediate(ExecuteImmediate { sql, parameters }) => {
+                        write!(
+                            f,
+                            "ExecuteImmediate: {sql} params=[{}]",
+                            expr_vec_fmt!(parameters)
+                        )
+                    }
                     Statement::Deallocate(Deallocate { name }) => {
                         write!(f, "Deallocate: {name}")
                     }
+                    Statement::Estimate(_) => write!(f, "Estimate"),
                 }
             }
         }
@@ -224,9 +241,26 @@ pub struct Execute {
     pub parameters: Vec<Expr>,
 }
 
+/// Plan and execute a dynamically constructed SQL string.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+pub struct ExecuteImmediate {
+    /// Expression that evaluates to the SQL string
+    pub sql: Expr,
+    /// Values of the placeholders (e.g. `$1`) of the SQL string
+    pub parameters: Vec<Expr>,
+}
+
 /// Deallocate a prepared statement.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
 pub struct Deallocate {
     /// The name of the prepared statement to deallocate
     pub name: String,
 }
+
+/// Estimate the number of rows, the size and the cost of a query from the
+/// statistics of its physical plan, without executing it.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+pub struct Estimate {
+    /// The logical plan of the query to estimate
+    pub input: Arc<LogicalPlan>,
+}
@@ -39,10 +39,11 @@
 
 use crate::{
     Aggregate, Analyze, CreateMemoryTable, CreateView, DdlStatement, Distinct,
-    DistinctOn, DmlStatement, Execute, Explain, Expr, Extension, Filter, Join, Limit,
-    LogicalPlan, Partitioning, Prepare, Projection, RecursiveQuery, Repartition, Sort,
-    Statement, Subquery, SubqueryAlias, TableScan, Union, Unnest, UserDefinedLogicalNode,
-    Values, Window, dml::CopyTo,
+    DistinctOn, DmlStatement, Estimate, Execute, ExecuteImmediate, Explain, Expr,
+    Extension, Filter, Join, LateralTableFunction, Limit, LogicalPlan, Partitioning,
+    Prepare, Projection, RecursiveQuery, Repartition, Sort, Statement, Subquery,
+    SubqueryAlias, TableScan, Union, Unnest, UserDefinedLogicalNode, Values, Window,
+    dml::CopyTo,
 };
 use datafusion_common::tree_node::TreeNodeRefContainer;
 
@@ -134,6 +135,7 @@ impl TreeNode for LogicalPlan {
                 schema,
                 null_equality,
                 null_aware,
+                asof_match,
             }) => (left, right).map_elements(f)?.update_data(|(left, right)| {
                 LogicalPlan::Join(Join {
                     left,
@@ -145,6 +147,7 @@ impl TreeNode for LogicalPlan {
                     schema,
                     null_equality,
                     null_aware,
+                    asof_match,
                 })
             }),
             LogicalPlan::Limit(Limit { skip, fetch, input }) => input
@@ -215,11 +218,13 @@ impl TreeNode for LogicalPlan {
             }),
             LogicalPlan::Analyze(Analyze {
                 verbose,
+                format,
                 input,
                 schema,
             }) => input.map_elements(f)?.update_data(|input| {
                 LogicalPlan::Analyze(Analyze {
                     verbose,
+                    format,
                     input,
                     schema,
                 })
@@ -301,7 +306,9 @@ impl TreeNode for LogicalPlan {
                     | DdlStatement::DropView(_)
                     | DdlStatement::DropCatalogSchema(_)
                     | DdlStatement::CreateFunction(_)
-                    | DdlStatement::DropFunction(_) => Transformed::no(ddl),
+                    | DdlStatement::DropFunction(_)
+                    | DdlStatement::RefreshTableSchema(_)
+                    | DdlStatement::AlterTable(_) => Transformed::no(ddl),
                 }
                 .update_data(LogicalPlan::Ddl)
             }
@@ -324,6 +331,23 @@ impl TreeNode for LogicalPlan {
                     options,
                 })
             }),
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                input,
+                function_name,
+                args,
+                function_schema,
+                join_type,
+                schema,
+            }) => input.map_elements(f)?.update_data(|input| {
+                LogicalPlan::LateralTableFunction(LateralTableFunction {
+                    input,
+                    function_name,
+                    args,
+                    function_schema,
+                    join_type,
+                    schema,
+                })
+            }),
             LogicalPlan::RecursiveQuery(RecursiveQuery {
                 name,
                 static_term,
@@ -344,6 +368,9 @@ impl TreeNode for LogicalPlan {
                     .input
                     .map_elements(f)?
                     .update_data(|input| Statement::Prepare(Prepare { input, ..p })),
+                Statement::Estimate(Estimate { input }) => input
+                    .map_elements(f)?
+                    .update_data(|input| Statement::Estimate(Estimate { input })),
                 _ => Transformed::no(stmt),
             }
             .update_data(LogicalPlan::Statement),
@@ -424,12 +451,16 @@ impl LogicalPlan {
                 aggr_expr,
                 ..
             }) => (group_expr, aggr_expr).apply_ref_elements(f),
-            // There are two part of expression for join, equijoin(on) and non-equijoin(filter).
+            // There are three parts of expression for join, equijoin(on), the ASOF match condition and non-equijoin(filter).
             // 1. the first part is `on.len()` equijoin expressions, and the struct of each expr is `left-on = right-on`.
-            // 2. the second part is non-equijoin(filter).
-            LogicalPlan::Join(Join { on, filter, .. }) => {
-                (on, filter).apply_ref_elements(f)
-            }
+            // 2. the second part is the ASOF match condition, if any.
+            // 3. the third part is non-equijoin(filter).
+            LogicalPlan::Join(Join {
+                on,
+                filter,
+                asof_match,
+                ..
+            }) => (on, asof_match, filter).apply_ref_elements(f),
             LogicalPlan::Sort(Sort { expr, .. }) => expr.apply_elements(f),
             LogicalPlan::Extension(extension) => {
                 // would be nice to avoid this copy -- maybe can
@@ -448,6 +479,9 @@ impl LogicalPlan {
                     .collect::<Vec<_>>();
                 exprs.apply_elements(f)
             }
+            LogicalPlan::LateralTableFunction(LateralTableFunction { args, .. }) => {
+                args.apply_elements(f)
+            }
             LogicalPlan::Distinct(Distinct::On(DistinctOn {
                 on_expr,
                 select_expr,
@@ -461,6 +495,9 @@ impl LogicalPlan {
                 Statement::Execute(Execute { parameters, .. }) => {
                     parameters.apply_elements(f)
                 }
+                Statement::ExecuteImmediate(ExecuteImmediate { sql, parameters }) => {
+                    (sql, parameters).apply_ref_elements(f)
+                }
                 _ => Ok(TreeNodeRecursion::Continue),
             },
             // plans without expressions
@@ -556,7 +593,8 @@ impl LogicalPlan {
 
             // There are two part of expression for join, equijoin(on) and non-equijoin(filter).
             // 1. the first part is `on.len()` equijoin expressions, and the struct of each expr is `left-on = right-on`.
-            // 2. the second part is non-equijoin(filter).
+            // 2. the second part is the ASOF match condition, if any.
+            // 3. the third part is non-equijoin(filter).
             LogicalPlan::Join(Join {
                 left,
                 right,
@@ -567,19 +605,23 @@ impl LogicalPlan {
                 schema,
                 null_equality,
                 null_aware,
-            }) => (on, filter).map_elements(f)?.update_data(|(on, filter)| {
-                LogicalPlan::Join(Join {
-                    left,
-                    right,
-                    on,
-                    filter,
-                    join_type,
-                    join_constraint,
-                    schema,
-                    null_equality,
-                    null_aware,
-                })
-            }),
+                asof_match,
+            }) => (on, asof_match, filter).map_elements(f)?.update_data(
+                |(on, asof_match, filter)| {
+                    LogicalPlan::Join(Join {
+                        left,
+                        right,
+                        on,
+                        filter,
+                        join_type,
+                        join_constraint,
+                        schema,
+                        null_equality,
+                        null_aware,
+                        asof_match,
+                    })
+                },
+            ),
             LogicalPlan::Sort(Sort { expr, input, fetch }) => expr
                 .map_elements(f)?
                 .update_data(|expr| LogicalPlan::Sort(Sort { expr, input, fetch })),
@@ -642,6 +684,23 @@ impl LogicalPlan {
                         schema,
                     }))
                 }),
+            LogicalPlan::LateralTableFunction(LateralTableFunction {
+                input,
+                function_name,
+                args,
+                function_schema,
+                join_type,
+                schema,
+            }) => args.map_elements(f)?.update_data(|args| {
+                LogicalPlan::LateralTableFunction(LateralTableFunction {
+                    input,
+                    function_name,
+                    args,
+                    function_schema,
+                    join_type,
+                    schema,
+                })
+            }),
             LogicalPlan::Limit(Limit { skip, fetch, input }) => {
                 (skip, fetch).map_elements(f)?.update_data(|(skip, fetch)| {
                     LogicalPlan::Limit(Limit { skip, fetch, input })
@@ -653,6 +712,16 @@ impl LogicalPlan {
                         Statement::Execute(Execute { parameters, ..e })
                     })
                 }
+                Statement::ExecuteImmediate(ExecuteImmediate { sql, parameters }) => {
+                    (sql, parameters)
+                        .map_elements(f)?
+                        .update_data(|(sql, parameters)| {
+                            Statement::ExecuteImmediate(ExecuteImmediate {
+                                sql,
+                                parameters,
+                            })
+                        })
+                }
                 _ => Transformed::no(stmt),
             }
             .update_data(LogicalPlan::Statement),
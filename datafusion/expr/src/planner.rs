@@ -24,17 +24,19 @@ use crate::expr::NullTreatment;
 #[cfg(feature = "sql")]
 use crate::logical_plan::LogicalPlan;
 use crate::{
-    AggregateUDF, Expr, GetFieldAccess, HigherOrderUDF, ScalarUDF, SortExpr, TableSource,
-    WindowFrame, WindowFunctionDefinition, WindowUDF,
+    AggregateUDF, Expr, GetFieldAccess, HigherOrderUDF, Operator, ScalarUDF, SortExpr,
+    TableSource, WindowFrame, WindowFunctionDefinition, WindowUDF,
 };
 use arrow::datatypes::{DataType, Field, FieldRef, SchemaRef};
 use datafusion_common::datatype::DataTypeExt;
 use datafusion_common::{
-    DFSchema, Result, TableReference, config::ConfigOptions,
+    DFSchema, Diagnostic, Result, TableReference, config::ConfigOptions,
     file_options::file_type::FileType, not_impl_err,
 };
 #[cfg(feature = "sql")]
-use sqlparser::ast::{Expr as SQLExpr, Ident, ObjectName, TableAlias, TableFactor};
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, Ident, ObjectName, TableAlias, TableFactor,
+};
 
 /// Provides the `SQL` query planner meta-data about tables and
 /// functions referenced in SQL statements, without a direct dependency on the
@@ -45,6 +47,19 @@ pub trait ContextProvider {
     /// Returns a table by reference, if it exists
     fn get_table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>>;
 
+    /// Returns a table by reference, with configuration options overridden
+    /// for its scans.
+    ///
+    /// This is used to plan table hints such as
+    /// `SELECT * FROM t WITH (datafusion.execution.batch_size = 1024)`
+    fn get_table_source_with_options(
+        &self,
+        name: TableReference,
+        _options: Vec<(String, String)>,
+    ) -> Result<Arc<dyn TableSource>> {
+        not_impl_err!("Table options are not supported for table '{name}'")
+    }
+
     /// Return the type of a file based on its extension (e.g. `.parquet`)
     ///
     /// This is used to plan `COPY` statements
@@ -61,6 +76,19 @@ pub trait ContextProvider {
         not_impl_err!("Table Functions are not supported")
     }
 
+    /// Return the schema of the rows the table function `name` returns when
+    /// called laterally with arguments of the given types, such as in
+    /// `SELECT * FROM t, LATERAL f(t.x)`.
+    ///
+    /// See [`LogicalPlan::LateralTableFunction`](crate::LogicalPlan::LateralTableFunction)
+    fn get_lateral_table_function_schema(
+        &self,
+        name: &str,
+        _arg_types: &[DataType],
+    ) -> Result<SchemaRef> {
+        not_impl_err!("Lateral calls of table function '{name}' are not supported")
+    }
+
     /// Provides an intermediate table that is used to store the results of a CTE during execution
     ///
     /// CTE stands for "Common Table Expression"
@@ -142,6 +170,12 @@ pub trait ContextProvider {
 
     /// Return all window function names
     fn udwf_names(&self) -> Vec<String>;
+
+    /// Report a non-fatal warning found while planning, such as a SQL
+    /// feature that was accepted by the parser but ignored by the planner.
+    ///
+    /// The default implementation discards the warning.
+    fn report_warning(&self, _warning: Diagnostic) {}
 }
 
 /// Customize planning of SQL AST expressions to [`Expr`]s
@@ -287,13 +321,82 @@ pub trait ExprPlanner: Debug + Send + Sync {
 #[derive(Debug, Clone)]
 pub struct RawBinaryExpr {
     #[cfg(not(feature = "sql"))]
-    pub op: datafusion_expr_common::operator::Operator,
+    pub op: Operator,
     #[cfg(feature = "sql")]
-    pub op: sqlparser::ast::BinaryOperator,
+    pub op: BinaryOperator,
     pub left: Expr,
     pub right: Expr,
 }
 
+impl RawBinaryExpr {
+    /// Returns the DataFusion [`Operator`] of this expression, or an error if
+    /// the SQL operator has no DataFusion equivalent
+    pub fn operator(&self) -> Result<Operator> {
+        #[cfg(feature = "sql")]
+        {
+            sql_binary_operator(&self.op)
+        }
+        #[cfg(not(feature = "sql"))]
+        {
+            Ok(self.op)
+        }
+    }
+}
+
+/// Returns the DataFusion [`Operator`] of the SQL binary operator `op`
+#[cfg(feature = "sql")]
+pub fn sql_binary_operator(op: &BinaryOperator) -> Result<Operator> {
+    match op {
+        BinaryOperator::Gt => Ok(Operator::Gt),
+        BinaryOperator::GtEq => Ok(Operator::GtEq),
+        BinaryOperator::Lt => Ok(Operator::Lt),
+        BinaryOperator::LtEq => Ok(Operator::LtEq),
+        BinaryOperator::Eq => Ok(Operator::Eq),
+        BinaryOperator::NotEq => Ok(Operator::NotEq),
+        BinaryOperator::Plus => Ok(Operator::Plus),
+        BinaryOperator::Minus => Ok(Operator::Minus),
+        BinaryOperator::Multiply => Ok(Operator::Multiply),
+        BinaryOperator::Divide => Ok(Operator::Divide),
+        BinaryOperator::Modulo => Ok(Operator::Modulo),
+        BinaryOperator::And => Ok(Operator::And),
+        BinaryOperator::Or => Ok(Operator::Or),
+        BinaryOperator::PGRegexMatch => Ok(Operator::RegexMatch),
+        BinaryOperator::PGRegexIMatch => Ok(Operator::RegexIMatch),
+        BinaryOperator::PGRegexNotMatch => Ok(Operator::RegexNotMatch),
+        BinaryOperator::PGRegexNotIMatch => Ok(Operator::RegexNotIMatch),
+        BinaryOperator::PGLikeMatch => Ok(Operator::LikeMatch),
+        BinaryOperator::PGILikeMatch => Ok(Operator::ILikeMatch),
+        BinaryOperator::PGNotLikeMatch => Ok(Operator::NotLikeMatch),
+        BinaryOperator::PGNotILikeMatch => Ok(Operator::NotILikeMatch),
+        BinaryOperator::BitwiseAnd => Ok(Operator::BitwiseAnd),
+        BinaryOperator::BitwiseOr => Ok(Operator::BitwiseOr),
+        BinaryOperator::Xor => Ok(Operator::BitwiseXor),
+        BinaryOperator::BitwiseXor => Ok(Operator::BitwiseXor),
+        BinaryOperator::PGBitwiseXor => Ok(Operator::BitwiseXor),
+        BinaryOperator::PGBitwiseShiftRight => Ok(Operator::BitwiseShiftRight),
+        BinaryOperator::PGBitwiseShiftLeft => Ok(Operator::BitwiseShiftLeft),
+        BinaryOperator::StringConcat => Ok(Operator::StringConcat),
+        BinaryOperator::ArrowAt => Ok(Operator::ArrowAt),
+        BinaryOperator::AtArrow => Ok(Operator::AtArrow),
+        BinaryOperator::Arrow => Ok(Operator::Arrow),
+        BinaryOperator::LongArrow => Ok(Operator::LongArrow),
+        BinaryOperator::HashArrow => Ok(Operator::HashArrow),
+        BinaryOperator::HashLongArrow => Ok(Operator::HashLongArrow),
+        BinaryOperator::AtAt => Ok(Operator::AtAt),
+        BinaryOperator::Spaceship => Ok(Operator::IsNotDistinctFrom),
+        BinaryOperator::DuckIntegerDivide | BinaryOperator::MyIntegerDivide => {
+            Ok(Operator::IntegerDivide)
+        }
+        BinaryOperator::HashMinus => Ok(Operator::HashMinus),
+        BinaryOperator::AtQuestion => Ok(Operator::AtQuestion),
+        BinaryOperator::Question => Ok(Operator::Question),
+        BinaryOperator::QuestionAnd => Ok(Operator::QuestionAnd),
+        BinaryOperator::QuestionPipe => Ok(Operator::QuestionPipe),
+        BinaryOperator::Custom(s) if s == ":" => Ok(Operator::Colon),
+        _ => not_impl_err!("Unsupported binary operator: {:?}", op),
+    }
+}
+
 /// An expression with GetFieldAccess to plan
 ///
 /// This structure is used by [`ExprPlanner`] to plan operators with
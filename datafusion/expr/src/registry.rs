@@ -21,6 +21,8 @@ use crate::expr_rewriter::FunctionRewrite;
 use crate::higher_order_function::HigherOrderUDF;
 use crate::planner::ExprPlanner;
 use crate::{AggregateUDF, ScalarUDF, UserDefinedLogicalNode, WindowUDF};
+use arrow::array::ArrayRef;
+use arrow::compute::CastOptions;
 use arrow::datatypes::Field;
 use arrow_schema::DataType;
 use arrow_schema::extension::{
@@ -168,6 +170,11 @@ pub trait FunctionRegistry {
     ) -> Result<()> {
         not_impl_err!("Registering ExprPlanner")
     }
+
+    /// Returns the [`CastRegistry`] with the user defined casts, if any
+    fn cast_registry(&self) -> Option<CastRegistryRef> {
+        None
+    }
 }
 
 /// Serializer and deserializer registry for extensions like [UserDefinedLogicalNode].
@@ -594,3 +601,121 @@ impl From<HashMap<String, ExtensionTypeRegistrationRef>> for MemoryExtensionType
         }
     }
 }
+
+/// A cheaply cloneable pointer to a [UserDefinedCast].
+pub type UserDefinedCastRef = Arc<dyn UserDefinedCast>;
+
+/// A conversion between two types provided by the user, for example from or
+/// to an extension type, or between types Arrow can not cast between.
+///
+/// User defined casts are registered in a [`CastRegistry`], which is consulted
+/// by `CAST` expressions and by type coercion.
+pub trait UserDefinedCast: Debug + Send + Sync {
+    /// The name of the cast, used to replace or deregister it.
+    fn name(&self) -> &str;
+
+    /// Returns true if this cast converts values of `from` to `to`.
+    ///
+    /// The fields carry the extension type metadata, if any.
+    fn can_cast(&self, from: &Field, to: &Field) -> bool;
+
+    /// Converts `array`, whose values are of `from`, to `to`.
+    fn cast(
+        &self,
+        array: &ArrayRef,
+        from: &Field,
+        to: &Field,
+        options: &CastOptions,
+    ) -> Result<ArrayRef>;
+}
+
+/// A cheaply cloneable pointer to a [CastRegistry].
+pub type CastRegistryRef = Arc<dyn CastRegistry>;
+
+/// Manages [`UserDefinedCast`]s.
+///
+/// When planning a `CAST` the registry is consulted before the casts supported
+/// by Arrow, so that a registered cast takes precedence. The type coercion
+/// analyzer consults it when the operands of an operator can not otherwise be
+/// coerced to a common type.
+pub trait CastRegistry: Debug + Send + Sync {
+    /// Returns the cast converting values of `from` to `to`, if any.
+    fn find_cast(&self, from: &Field, to: &Field) -> Option<UserDefinedCastRef>;
+
+    /// Returns all registered [`UserDefinedCast`]s.
+    fn casts(&self) -> Vec<UserDefinedCastRef>;
+
+    /// Registers a new [`UserDefinedCast`], returning any previously registered
+    /// cast with the same name.
+    ///
+    /// Returns an error if the cast cannot be registered, for example, if the
+    /// registry is read-only.
+    fn register_cast(
+        &self,
+        cast: UserDefinedCastRef,
+    ) -> Result<Option<UserDefinedCastRef>>;
+
+    /// Deregisters the cast with the name `name`, returning the cast that was
+    /// deregistered.
+    ///
+    /// Returns an error if the cast cannot be deregistered, for example, if the
+    /// registry is read-only.
+    fn deregister_cast(&self, name: &str) -> Result<Option<UserDefinedCastRef>>;
+}
+
+/// A [`CastRegistry`] that keeps the casts in memory.
+///
+/// If more than one cast converts between the same fields, the one registered
+/// last is used.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCastRegistry {
+    /// The registered casts, in the order of their registration
+    casts: Arc<RwLock<Vec<UserDefinedCastRef>>>,
+}
+
+impl MemoryCastRegistry {
+    /// Creates an empty [MemoryCastRegistry].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CastRegistry for MemoryCastRegistry {
+    fn find_cast(&self, from: &Field, to: &Field) -> Option<UserDefinedCastRef> {
+        self.casts
+            .read()
+            .expect("Cast registry lock poisoned")
+            .iter()
+            .rev()
+            .find(|cast| cast.can_cast(from, to))
+            .cloned()
+    }
+
+    fn casts(&self) -> Vec<UserDefinedCastRef> {
+        self.casts
+            .read()
+            .expect("Cast registry lock poisoned")
+            .clone()
+    }
+
+    fn register_cast(
+        &self,
+        cast: UserDefinedCastRef,
+    ) -> Result<Option<UserDefinedCastRef>> {
+        let mut casts = self.casts.write().expect("Cast registry lock poisoned");
+        let old_cast = casts
+            .iter()
+            .position(|c| c.name() == cast.name())
+            .map(|index| casts.remove(index));
+        casts.push(cast);
+        Ok(old_cast)
+    }
+
+    fn deregister_cast(&self, name: &str) -> Result<Option<UserDefinedCastRef>> {
+        let mut casts = self.casts.write().expect("Cast registry lock poisoned");
+        Ok(casts
+            .iter()
+            .position(|c| c.name() == name)
+            .map(|index| casts.remove(index)))
+    }
+}
@@ -24,6 +24,7 @@ use chrono::{DateTime, Utc};
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::{DFSchema, DFSchemaRef, Result};
 
+use crate::registry::CastRegistryRef;
 use crate::{Expr, ExprSchemable};
 
 /// Provides simplification information based on schema, query execution time,
@@ -38,6 +39,7 @@ pub struct SimplifyContext {
     schema: DFSchemaRef,
     query_execution_start_time: Option<DateTime<Utc>>,
     config_options: Arc<ConfigOptions>,
+    cast_registry: Option<CastRegistryRef>,
 }
 
 /// Builder for [`SimplifyContext`].
@@ -46,6 +48,7 @@ pub struct SimplifyContextBuilder {
     schema: Option<DFSchemaRef>,
     query_execution_start_time: Option<DateTime<Utc>>,
     config_options: Option<Arc<ConfigOptions>>,
+    cast_registry: Option<CastRegistryRef>,
 }
 
 impl Default for SimplifyContext {
@@ -54,6 +57,7 @@ impl Default for SimplifyContext {
             schema: Arc::new(DFSchema::empty()),
             query_execution_start_time: None,
             config_options: Arc::new(ConfigOptions::default()),
+            cast_registry: None,
         }
     }
 }
@@ -137,6 +141,12 @@ impl SimplifyContext {
     pub fn config_options(&self) -> &Arc<ConfigOptions> {
         &self.config_options
     }
+
+    /// Returns the registry of user defined casts used to evaluate constant
+    /// `CAST` expressions, if any.
+    pub fn cast_registry(&self) -> Option<&CastRegistryRef> {
+        self.cast_registry.as_ref()
+    }
 }
 
 impl SimplifyContextBuilder {
@@ -167,6 +177,12 @@ impl SimplifyContextBuilder {
         self
     }
 
+    /// Set the registry of user defined casts.
+    pub fn with_cast_registry(mut self, cast_registry: Option<CastRegistryRef>) -> Self {
+        self.cast_registry = cast_registry;
+        self
+    }
+
     /// Build a [`SimplifyContext`], filling in any unspecified fields with defaults.
     pub fn build(self) -> SimplifyContext {
         SimplifyContext {
@@ -175,6 +191,7 @@ impl SimplifyContextBuilder {
             config_options: self
                 .config_options
                 .unwrap_or_else(|| Arc::new(ConfigOptions::default())),
+            cast_registry: self.cast_registry,
         }
     }
 }
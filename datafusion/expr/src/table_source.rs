@@ -20,8 +20,9 @@
 use crate::{Expr, LogicalPlan};
 
 use arrow::datatypes::SchemaRef;
-use datafusion_common::{Constraints, Result};
+use datafusion_common::{Constraints, Result, Statistics};
 
+use std::sync::Arc;
 use std::{any::Any, borrow::Cow};
 
 /// Indicates how a filter expression is handled by
@@ -71,6 +72,25 @@ impl std::fmt::Display for TableType {
     }
 }
 
+/// An aggregation of the rows of a table that the optimizer offers to compute
+/// in the table scan, see [`TableSource::push_down_aggregate`].
+///
+/// It is equivalent to `SELECT <group_expr>, <aggr_expr> FROM t WHERE
+/// <filters> GROUP BY <group_expr>`.
+#[derive(Debug, Clone)]
+pub struct TableAggregate {
+    /// Grouping expressions, empty for an aggregation without `GROUP BY`
+    pub group_expr: Vec<Expr>,
+    /// Aggregate expressions, such as `count(t.a)` or `max(t.b)`
+    pub aggr_expr: Vec<Expr>,
+    /// Filters the rows must pass to be aggregated, previously pushed down
+    /// into the scan as [`TableProviderFilterPushDown::Exact`]
+    pub filters: Vec<Expr>,
+    /// Schema of the result: one field per grouping expression followed by
+    /// one field per aggregate expression
+    pub schema: SchemaRef,
+}
+
 /// Planning time information about a table.
 ///
 /// This trait is used during logical query planning and optimizations, and
@@ -95,6 +115,18 @@ pub trait TableSource: Any + Sync + Send {
     /// Get a reference to the schema for this table
     fn schema(&self) -> SchemaRef;
 
+    /// Get the hidden metadata columns of this table, if any.
+    ///
+    /// Metadata columns, such as the file each row was read from, are not
+    /// part of [`Self::schema`] and are only scanned when referenced
+    /// explicitly. In the projection of a [`TableScan`], index
+    /// `schema().fields().len() + i` refers to the `i`-th metadata column.
+    ///
+    /// [`TableScan`]: crate::logical_plan::TableScan
+    fn metadata_columns(&self) -> Option<SchemaRef> {
+        None
+    }
+
     /// Get primary key indices, if any
     fn constraints(&self) -> Option<&Constraints> {
         None
@@ -116,6 +148,18 @@ pub trait TableSource: Any + Sync + Send {
             .collect())
     }
 
+    /// Returns a table source whose rows are the result of `aggregate` over
+    /// this table, or `None` if the aggregation can not be computed by the
+    /// table itself.
+    ///
+    /// The schema of the returned source must be `aggregate.schema`.
+    fn push_down_aggregate(
+        &self,
+        _aggregate: &TableAggregate,
+    ) -> Result<Option<Arc<dyn TableSource>>> {
+        Ok(None)
+    }
+
     /// Get the Logical plan of this table provider, if available.
     ///
     /// For example, a view may have a logical plan, but a CSV file does not.
@@ -127,6 +171,13 @@ pub trait TableSource: Any + Sync + Send {
     fn get_column_default(&self, _column: &str) -> Option<&Expr> {
         None
     }
+
+    /// Get statistics for this table, if available.
+    ///
+    /// Used by cost-based logical optimizer rules such as join reordering.
+    fn statistics(&self) -> Option<Statistics> {
+        None
+    }
 }
 
 impl dyn TableSource {
@@ -457,7 +457,12 @@ fn try_coerce_types(
         for (i, (current, expected)) in
             current_types.iter().zip(expected_types.iter()).enumerate()
         {
-            if maybe_data_types(&[expected.clone()], &[current.clone()]).is_none() {
+            if maybe_data_types(
+                std::slice::from_ref(expected),
+                std::slice::from_ref(current),
+            )
+            .is_none()
+            {
                 arg_index = Some(i);
                 expected_types_str = expected.to_string();
                 actual_type_str = current.to_string();
@@ -466,19 +471,21 @@ fn try_coerce_types(
         }
     }
 
-    Err(datafusion_common::DataFusionError::TypeCoercion {
-        message: format!(
-            "Failed to coerce arguments to satisfy a call to '{function_name}' function: coercion from {} to the signature {type_signature} failed",
-            current_types
-                .iter()
-                .map(|t| t.to_string())
-                .collect::<Vec<_>>()
-                .join(", ")
-        ),
-        arg_index,
-        expected_types: expected_types_str,
-        actual_type: actual_type_str,
-    })
+    Err(datafusion_common::DataFusionError::TypeCoercion(Box::new(
+        datafusion_common::error::TypeCoercionError {
+            message: format!(
+                "Failed to coerce arguments to satisfy a call to '{function_name}' function: coercion from {} to the signature {type_signature} failed",
+                current_types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            arg_index,
+            expected_types: expected_types_str,
+            actual_type: actual_type_str,
+        },
+    )))
 }
 
 fn get_valid_types_with_udf<F: UDFCoercionExt>(
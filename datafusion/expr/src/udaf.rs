@@ -24,8 +24,12 @@ use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::vec;
 
-use arrow::datatypes::{DataType, Field, FieldRef};
+use arrow::array::{RecordBatch, RecordBatchOptions};
+use arrow::datatypes::{DataType, Field, FieldRef, Schema};
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::StreamWriter;
 
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::{Result, ScalarValue, Statistics, exec_err, not_impl_err};
 use datafusion_expr_common::dyn_eq::{DynEq, DynHash};
 use datafusion_expr_common::operator::Operator;
@@ -272,6 +276,14 @@ impl AggregateUDF {
         self.inner.coerce_types(arg_types)
     }
 
+    /// Returns this function configured for `config`, if it depends on the
+    /// configuration.
+    ///
+    /// See [`AggregateUDFImpl::with_updated_config`] for more details.
+    pub fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        self.inner.with_updated_config(config)
+    }
+
     /// See [`AggregateUDFImpl::with_beneficial_ordering`] for more details.
     pub fn with_beneficial_ordering(
         self,
@@ -350,6 +362,16 @@ impl AggregateUDF {
         self.inner.supports_within_group_clause()
     }
 
+    /// See [`AggregateUDFImpl::serialize_state`] for more details.
+    pub fn serialize_state(&self, state: &[ScalarValue]) -> Result<Vec<u8>> {
+        self.inner.serialize_state(state)
+    }
+
+    /// See [`AggregateUDFImpl::deserialize_state`] for more details.
+    pub fn deserialize_state(&self, bytes: &[u8]) -> Result<Vec<ScalarValue>> {
+        self.inner.deserialize_state(bytes)
+    }
+
     /// Returns the documentation for this Aggregate UDF.
     ///
     /// Documentation can be accessed programmatically as well as
@@ -630,6 +652,19 @@ pub trait AggregateUDFImpl: Debug + DynEq + DynHash + Send + Sync + Any {
         self.accumulator(args)
     }
 
+    /// Returns a new instance of this function configured for the given
+    /// [`ConfigOptions`], or `None` if it does not depend on the configuration
+    /// (the default).
+    ///
+    /// The physical planner calls this with the options of the query before
+    /// it creates the accumulators, so functions can pick their accumulators,
+    /// and the state those accumulators exchange, based on the configuration.
+    /// For example, `sum` uses compensated summation of floating point values
+    /// if `datafusion.execution.float_summation` is set to `compensated`.
+    fn with_updated_config(&self, _config: &ConfigOptions) -> Option<AggregateUDF> {
+        None
+    }
+
     /// Sets the indicator whether ordering requirements of the AggregateUDFImpl is
     /// satisfied by its input. If this is not the case, UDFs with order
     /// sensitivity `AggregateOrderSensitivity::Beneficial` can still produce
@@ -905,6 +940,33 @@ pub trait AggregateUDFImpl: Debug + DynEq + DynHash + Send + Sync + Any {
     fn set_monotonicity(&self, _data_type: &DataType) -> SetMonotonicity {
         SetMonotonicity::NotMonotonic
     }
+
+    /// Serializes a partial aggregate state, as returned by
+    /// [`Accumulator::state`], to bytes.
+    ///
+    /// This allows distributed engines to ship partial states between
+    /// processes and merge them with [`Accumulator::merge_batch`] on the
+    /// receiving side, which deserializes them with
+    /// [`Self::deserialize_state`].
+    ///
+    /// The default implementation uses the versioned Arrow IPC based encoding
+    /// of [`udaf_default_serialize_state`], which round trips any state made
+    /// of [`ScalarValue`]s. Functions with a more compact representation may
+    /// override this together with [`Self::deserialize_state`], and are then
+    /// responsible for versioning their own format.
+    fn serialize_state(&self, state: &[ScalarValue]) -> Result<Vec<u8>> {
+        udaf_default_serialize_state(state)
+    }
+
+    /// Deserializes a partial aggregate state produced by
+    /// [`Self::serialize_state`].
+    ///
+    /// The default implementation decodes the encoding of
+    /// [`udaf_default_serialize_state`], see
+    /// [`udaf_default_deserialize_state`].
+    fn deserialize_state(&self, bytes: &[u8]) -> Result<Vec<ScalarValue>> {
+        udaf_default_deserialize_state(bytes)
+    }
 }
 
 impl dyn AggregateUDFImpl {
@@ -1212,6 +1274,71 @@ pub fn udaf_default_return_field<F: AggregateUDFImpl + ?Sized>(
     )))
 }
 
+/// Version of the encoding produced by [`udaf_default_serialize_state`].
+pub const AGGREGATE_STATE_FORMAT_VERSION: u8 = 1;
+
+/// Encapsulates default implementation of [`AggregateUDFImpl::serialize_state`].
+///
+/// The state is encoded as a single [`AGGREGATE_STATE_FORMAT_VERSION`] byte
+/// followed by an Arrow IPC stream holding one row, with one column per state
+/// value.
+pub fn udaf_default_serialize_state(state: &[ScalarValue]) -> Result<Vec<u8>> {
+    let fields: Vec<_> = state
+        .iter()
+        .enumerate()
+        .map(|(i, value)| Field::new(format!("state_{i}"), value.data_type(), true))
+        .collect();
+    let arrays = state
+        .iter()
+        .map(|value| value.to_array())
+        .collect::<Result<Vec<_>>>()?;
+    let batch = RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(fields)),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(1)),
+    )?;
+
+    let mut bytes = vec![AGGREGATE_STATE_FORMAT_VERSION];
+    let mut writer = StreamWriter::try_new(&mut bytes, &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    drop(writer);
+
+    Ok(bytes)
+}
+
+/// Encapsulates default implementation of [`AggregateUDFImpl::deserialize_state`].
+///
+/// Decodes bytes produced by [`udaf_default_serialize_state`], returning an
+/// error if they were written with an unsupported format version.
+pub fn udaf_default_deserialize_state(bytes: &[u8]) -> Result<Vec<ScalarValue>> {
+    let Some((version, ipc)) = bytes.split_first() else {
+        return exec_err!("Cannot deserialize aggregate state from empty bytes");
+    };
+    if *version != AGGREGATE_STATE_FORMAT_VERSION {
+        return exec_err!(
+            "Unsupported aggregate state format version {version}, expected {AGGREGATE_STATE_FORMAT_VERSION}"
+        );
+    }
+
+    let mut reader = StreamReader::try_new(ipc, None)?;
+    let Some(batch) = reader.next().transpose()? else {
+        return exec_err!("Serialized aggregate state does not contain a batch");
+    };
+    if batch.num_rows() != 1 {
+        return exec_err!(
+            "Serialized aggregate state must contain exactly one row, got {}",
+            batch.num_rows()
+        );
+    }
+
+    batch
+        .columns()
+        .iter()
+        .map(|array| ScalarValue::try_from_array(array, 0))
+        .collect()
+}
+
 pub enum ReversedUDAF {
     /// The expression is the same as the original expression, like SUM, COUNT
     Identical,
@@ -1314,6 +1441,15 @@ impl AggregateUDFImpl for AliasedAggregateUDFImpl {
         self.inner.accumulator(args)
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        self.inner.with_updated_config(config).map(|udf| {
+            AggregateUDF::new_from_impl(AliasedAggregateUDFImpl {
+                inner: Arc::clone(udf.inner()).into(),
+                aliases: self.aliases.clone(),
+            })
+        })
+    }
+
     fn with_beneficial_ordering(
         self: Arc<Self>,
         beneficial_ordering: bool,
@@ -1393,6 +1529,14 @@ impl AggregateUDFImpl for AliasedAggregateUDFImpl {
     fn documentation(&self) -> Option<&Documentation> {
         self.inner.documentation()
     }
+
+    fn serialize_state(&self, state: &[ScalarValue]) -> Result<Vec<u8>> {
+        self.inner.serialize_state(state)
+    }
+
+    fn deserialize_state(&self, bytes: &[u8]) -> Result<Vec<ScalarValue>> {
+        self.inner.deserialize_state(bytes)
+    }
 }
 
 /// Indicates whether an aggregation function is monotonic as a set
@@ -1418,9 +1562,10 @@ pub enum SetMonotonicity {
 
 #[cfg(test)]
 mod test {
+    use crate::udaf::AGGREGATE_STATE_FORMAT_VERSION;
     use crate::{AggregateUDF, AggregateUDFImpl};
     use arrow::datatypes::{DataType, FieldRef};
-    use datafusion_common::Result;
+    use datafusion_common::{Result, ScalarValue, assert_contains};
     use datafusion_expr_common::accumulator::Accumulator;
     use datafusion_expr_common::signature::{Signature, Volatility};
     use datafusion_functions_aggregate_common::accumulator::{
@@ -1527,6 +1672,47 @@ mod test {
         assert!(!(a1 == b1));
     }
 
+    #[test]
+    fn test_state_serialization_roundtrip() -> Result<()> {
+        let udaf = AggregateUDF::from(AMeanUdf::new());
+        let state = vec![
+            ScalarValue::Float64(Some(4.5)),
+            ScalarValue::UInt64(None),
+            ScalarValue::Utf8(Some("partial".to_string())),
+            ScalarValue::List(ScalarValue::new_list_nullable(
+                &[ScalarValue::Int32(Some(1)), ScalarValue::Int32(None)],
+                &DataType::Int32,
+            )),
+        ];
+
+        let bytes = udaf.serialize_state(&state)?;
+        assert_eq!(bytes[0], AGGREGATE_STATE_FORMAT_VERSION);
+        assert_eq!(udaf.deserialize_state(&bytes)?, state);
+
+        let empty = udaf.serialize_state(&[])?;
+        assert!(udaf.deserialize_state(&empty)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state_deserialization_rejects_unknown_version() -> Result<()> {
+        let udaf = AggregateUDF::from(AMeanUdf::new());
+        let mut bytes = udaf.serialize_state(&[ScalarValue::Int64(Some(1))])?;
+        bytes[0] = AGGREGATE_STATE_FORMAT_VERSION + 1;
+
+        let err = udaf.deserialize_state(&bytes).unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Unsupported aggregate state format version"
+        );
+
+        let err = udaf.deserialize_state(&[]).unwrap_err();
+        assert_contains!(err.to_string(), "empty bytes");
+
+        Ok(())
+    }
+
     fn hash<T: Hash>(value: T) -> u64 {
         let hasher = &mut DefaultHasher::new();
         value.hash(hasher);
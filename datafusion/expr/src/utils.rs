@@ -29,6 +29,7 @@ use crate::{
 use datafusion_expr_common::signature::{Signature, TypeSignature};
 
 use arrow::datatypes::{DataType, Field, Schema};
+use datafusion_common::metadata_column::is_metadata_column;
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
 };
@@ -364,19 +365,22 @@ fn get_excluded_columns(
 }
 
 /// Returns all `Expr`s in the schema, except the `Column`s in the `columns_to_skip`
+/// and hidden metadata columns
 fn get_exprs_except_skipped(
     schema: &DFSchema,
     columns_to_skip: &HashSet<Column>,
 ) -> Vec<Expr> {
+    let visible_fields = schema
+        .iter()
+        .filter(|(_, field)| !is_metadata_column(field));
     if columns_to_skip.is_empty() {
-        schema.iter().map(Expr::from).collect::<Vec<Expr>>()
+        visible_fields.map(Expr::from).collect::<Vec<Expr>>()
     } else {
-        schema
-            .columns()
-            .iter()
+        visible_fields
+            .map(Column::from)
             .filter_map(|c| {
-                if !columns_to_skip.contains(c) {
-                    Some(Expr::Column(c.clone()))
+                if !columns_to_skip.contains(&c) {
+                    Some(Expr::Column(c))
                 } else {
                     None
                 }
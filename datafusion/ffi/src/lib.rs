@@ -37,6 +37,7 @@ pub mod insert_op;
 pub mod physical_expr;
 pub mod physical_optimizer;
 pub mod plan_properties;
+pub mod plugin;
 pub mod proto;
 pub mod record_batch_stream;
 pub mod schema_provider;
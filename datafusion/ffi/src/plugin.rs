@@ -0,0 +1,488 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Plugins: shared libraries that package UDFs, table functions, catalogs
+//! and table provider factories (file formats) for a host to load at runtime.
+//!
+//! A plugin library builds a [`Plugin`] and exports it with
+//! [`export_plugin!`](crate::export_plugin):
+//!
+//! ```ignore
+//! fn create_plugin() -> Plugin {
+//!     Plugin::new("my_plugin")
+//!         .with_scalar_udf(my_udf())
+//!         .with_table_factory("MYFORMAT", Arc::new(MyFormatFactory {}))
+//! }
+//!
+//! datafusion_ffi::export_plugin!(create_plugin);
+//! ```
+//!
+//! The host loads the plugin with [`load_plugin`], or every plugin of a
+//! directory with [`load_plugins`], and registers the returned
+//! [`LoadedPlugin`] with its session. Before any other symbol is used, the
+//! host checks that the plugin was built against the same
+//! [`PLUGIN_ABI_VERSION`] and the same major version of DataFusion.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use datafusion_catalog::{CatalogProvider, TableFunctionImpl, TableProviderFactory};
+use datafusion_common::{DataFusionError, Result, ffi_datafusion_err, ffi_err};
+use datafusion_execution::TaskContextProvider;
+use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
+use datafusion_proto::logical_plan::DefaultLogicalExtensionCodec;
+use stabby::string::String as SString;
+use stabby::vec::Vec as SVec;
+
+use crate::catalog_provider::FFI_CatalogProvider;
+use crate::execution::FFI_TaskContextProvider;
+use crate::proto::logical_extension_codec::FFI_LogicalExtensionCodec;
+use crate::table_provider_factory::FFI_TableProviderFactory;
+use crate::udaf::FFI_AggregateUDF;
+use crate::udf::FFI_ScalarUDF;
+use crate::udtf::FFI_TableFunction;
+use crate::udwf::FFI_WindowUDF;
+
+/// Version of the layout of [`FFI_Plugin`]. It is incremented whenever the
+/// layout changes, so that hosts refuse plugins they can not read.
+pub const PLUGIN_ABI_VERSION: u64 = 1;
+
+/// Name of the symbol returning the [`FFI_PluginVersion`] of a plugin
+pub const PLUGIN_VERSION_SYMBOL: &[u8] = b"datafusion_ffi_plugin_version";
+
+/// Name of the symbol returning the [`FFI_Plugin`] of a plugin
+pub const PLUGIN_SYMBOL: &[u8] = b"datafusion_ffi_plugin";
+
+/// The versions a plugin was built with. Its layout never changes, so a host
+/// can always read it before reading the [`FFI_Plugin`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FFI_PluginVersion {
+    /// The [`PLUGIN_ABI_VERSION`] of the plugin
+    pub abi_version: u64,
+
+    /// The major DataFusion version of the plugin
+    pub datafusion_version: u64,
+}
+
+impl FFI_PluginVersion {
+    /// The versions of this build
+    pub fn current() -> Self {
+        Self {
+            abi_version: PLUGIN_ABI_VERSION,
+            datafusion_version: crate::version(),
+        }
+    }
+}
+
+/// A named value exported by a plugin
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFI_PluginEntry<T> {
+    pub name: SString,
+    pub value: T,
+}
+
+/// A stable struct for sharing the contents of a [`Plugin`] across FFI
+/// boundaries.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFI_Plugin {
+    /// Name of the plugin, used in error messages
+    pub name: SString,
+
+    pub scalar_udfs: SVec<FFI_ScalarUDF>,
+
+    pub aggregate_udfs: SVec<FFI_AggregateUDF>,
+
+    pub window_udfs: SVec<FFI_WindowUDF>,
+
+    /// Table functions, by the name they are called with
+    pub table_functions: SVec<FFI_PluginEntry<FFI_TableFunction>>,
+
+    /// Catalogs, by the name they are registered with
+    pub catalogs: SVec<FFI_PluginEntry<FFI_CatalogProvider>>,
+
+    /// Table provider factories, by the file type they create tables for,
+    /// as in `CREATE EXTERNAL TABLE ... STORED AS <file type>`
+    pub table_factories: SVec<FFI_PluginEntry<FFI_TableProviderFactory>>,
+}
+
+/// The extensions packaged by a plugin library, see the [module
+/// documentation](self).
+#[derive(Debug, Default)]
+pub struct Plugin {
+    name: String,
+    scalar_udfs: Vec<Arc<ScalarUDF>>,
+    aggregate_udfs: Vec<Arc<AggregateUDF>>,
+    window_udfs: Vec<Arc<WindowUDF>>,
+    table_functions: Vec<(String, Arc<dyn TableFunctionImpl>)>,
+    catalogs: Vec<(String, Arc<dyn CatalogProvider>)>,
+    table_factories: Vec<(String, Arc<dyn TableProviderFactory + Send>)>,
+}
+
+impl Plugin {
+    /// Creates an empty plugin named `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a scalar function
+    pub fn with_scalar_udf(mut self, udf: Arc<ScalarUDF>) -> Self {
+        self.scalar_udfs.push(udf);
+        self
+    }
+
+    /// Adds an aggregate function
+    pub fn with_aggregate_udf(mut self, udaf: Arc<AggregateUDF>) -> Self {
+        self.aggregate_udfs.push(udaf);
+        self
+    }
+
+    /// Adds a window function
+    pub fn with_window_udf(mut self, udwf: Arc<WindowUDF>) -> Self {
+        self.window_udfs.push(udwf);
+        self
+    }
+
+    /// Adds a table function called `name`
+    pub fn with_table_function(
+        mut self,
+        name: impl Into<String>,
+        udtf: Arc<dyn TableFunctionImpl>,
+    ) -> Self {
+        self.table_functions.push((name.into(), udtf));
+        self
+    }
+
+    /// Adds a catalog registered as `name`
+    pub fn with_catalog(
+        mut self,
+        name: impl Into<String>,
+        catalog: Arc<dyn CatalogProvider>,
+    ) -> Self {
+        self.catalogs.push((name.into(), catalog));
+        self
+    }
+
+    /// Adds a table provider factory for `file_type`, for example to support
+    /// a new file format in `CREATE EXTERNAL TABLE ... STORED AS <file_type>`
+    pub fn with_table_factory(
+        mut self,
+        file_type: impl Into<String>,
+        factory: Arc<dyn TableProviderFactory + Send>,
+    ) -> Self {
+        self.table_factories.push((file_type.into(), factory));
+        self
+    }
+
+    /// Converts the plugin to its FFI representation, using the host's
+    /// `logical_codec` to exchange expressions and plans with it
+    pub fn into_ffi(self, logical_codec: &FFI_LogicalExtensionCodec) -> FFI_Plugin {
+        fn entry<T>(name: String, value: T) -> FFI_PluginEntry<T> {
+            FFI_PluginEntry {
+                name: name.into(),
+                value,
+            }
+        }
+
+        FFI_Plugin {
+            name: self.name.into(),
+            scalar_udfs: self.scalar_udfs.into_iter().map(Into::into).collect(),
+            aggregate_udfs: self.aggregate_udfs.into_iter().map(Into::into).collect(),
+            window_udfs: self.window_udfs.into_iter().map(Into::into).collect(),
+            table_functions: self
+                .table_functions
+                .into_iter()
+                .map(|(name, udtf)| {
+                    let udtf = FFI_TableFunction::new_with_ffi_codec(
+                        udtf,
+                        None,
+                        logical_codec.clone(),
+                    );
+                    entry(name, udtf)
+                })
+                .collect(),
+            catalogs: self
+                .catalogs
+                .into_iter()
+                .map(|(name, catalog)| {
+                    let catalog = FFI_CatalogProvider::new_with_ffi_codec(
+                        catalog,
+                        None,
+                        logical_codec.clone(),
+                    );
+                    entry(name, catalog)
+                })
+                .collect(),
+            table_factories: self
+                .table_factories
+                .into_iter()
+                .map(|(file_type, factory)| {
+                    let factory = FFI_TableProviderFactory::new_with_ffi_codec(
+                        factory,
+                        None,
+                        logical_codec.clone(),
+                    );
+                    entry(file_type, factory)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Exports the [`Plugin`] returned by `$create`, a `fn() -> Plugin`, from a
+/// `cdylib` crate so that hosts can load it with [`load_plugin`].
+///
+/// [`load_plugin`]: crate::plugin::load_plugin
+#[macro_export]
+macro_rules! export_plugin {
+    ($create:expr) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn datafusion_ffi_plugin_version()
+        -> $crate::plugin::FFI_PluginVersion {
+            $crate::plugin::FFI_PluginVersion::current()
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn datafusion_ffi_plugin(
+            logical_codec: $crate::proto::logical_extension_codec::FFI_LogicalExtensionCodec,
+        ) -> $crate::plugin::FFI_Plugin {
+            let create: fn() -> $crate::plugin::Plugin = $create;
+            create().into_ffi(&logical_codec)
+        }
+    };
+}
+
+/// The extensions of a plugin loaded by the host, ready to be registered
+/// with a session.
+#[derive(Debug)]
+pub struct LoadedPlugin {
+    pub name: String,
+    pub path: PathBuf,
+    pub scalar_udfs: Vec<ScalarUDF>,
+    pub aggregate_udfs: Vec<AggregateUDF>,
+    pub window_udfs: Vec<WindowUDF>,
+    pub table_functions: Vec<(String, Arc<dyn TableFunctionImpl>)>,
+    pub catalogs: Vec<(String, Arc<dyn CatalogProvider>)>,
+    pub table_factories: Vec<(String, Arc<dyn TableProviderFactory>)>,
+}
+
+impl From<&FFI_Plugin> for LoadedPlugin {
+    fn from(plugin: &FFI_Plugin) -> Self {
+        Self {
+            name: plugin.name.as_str().to_owned(),
+            path: PathBuf::new(),
+            scalar_udfs: plugin
+                .scalar_udfs
+                .iter()
+                .map(|udf| ScalarUDF::new_from_shared_impl(udf.into()))
+                .collect(),
+            aggregate_udfs: plugin
+                .aggregate_udfs
+                .iter()
+                .map(|udaf| AggregateUDF::new_from_shared_impl(udaf.into()))
+                .collect(),
+            window_udfs: plugin
+                .window_udfs
+                .iter()
+                .map(|udwf| WindowUDF::new_from_shared_impl(udwf.into()))
+                .collect(),
+            table_functions: plugin
+                .table_functions
+                .iter()
+                .map(|entry| (entry.name.as_str().to_owned(), entry.value.clone().into()))
+                .collect(),
+            catalogs: plugin
+                .catalogs
+                .iter()
+                .map(|entry| (entry.name.as_str().to_owned(), (&entry.value).into()))
+                .collect(),
+            table_factories: plugin
+                .table_factories
+                .iter()
+                .map(|entry| (entry.name.as_str().to_owned(), (&entry.value).into()))
+                .collect(),
+        }
+    }
+}
+
+/// Loads the plugin at `path`.
+///
+/// `task_ctx_provider` is used by the plugin to decode the expressions and
+/// plans it receives from the host, and must outlive the plugin's
+/// extensions. Returns an error if the library does not export a plugin, or
+/// if it was built against another [`PLUGIN_ABI_VERSION`] or major version of
+/// DataFusion.
+///
+/// The library is never unloaded, as the returned extensions call into it.
+pub fn load_plugin(
+    path: impl AsRef<Path>,
+    task_ctx_provider: &Arc<dyn TaskContextProvider>,
+) -> Result<LoadedPlugin> {
+    let path = path.as_ref();
+    let lib =
+        unsafe { libloading::Library::new(path) }.map_err(|e| load_error(path, e))?;
+
+    let plugin_version = {
+        let get_version: libloading::Symbol<extern "C" fn() -> FFI_PluginVersion> =
+            unsafe { lib.get(PLUGIN_VERSION_SYMBOL) }.map_err(|e| load_error(path, e))?;
+        get_version()
+    };
+    let expected_version = FFI_PluginVersion::current();
+    if plugin_version != expected_version {
+        return ffi_err!(
+            "Plugin {} was built for plugin ABI version {} and DataFusion {}, \
+             but the host uses plugin ABI version {} and DataFusion {}",
+            path.display(),
+            plugin_version.abi_version,
+            plugin_version.datafusion_version,
+            expected_version.abi_version,
+            expected_version.datafusion_version
+        );
+    }
+
+    let create_plugin: libloading::Symbol<
+        extern "C" fn(FFI_LogicalExtensionCodec) -> FFI_Plugin,
+    > = unsafe { lib.get(PLUGIN_SYMBOL) }.map_err(|e| load_error(path, e))?;
+    let logical_codec = FFI_LogicalExtensionCodec::new(
+        Arc::new(DefaultLogicalExtensionCodec {}),
+        None,
+        FFI_TaskContextProvider::from(task_ctx_provider),
+    );
+    let plugin = create_plugin(logical_codec);
+    let loaded = LoadedPlugin {
+        path: path.to_path_buf(),
+        ..LoadedPlugin::from(&plugin)
+    };
+
+    // The extensions of the plugin keep calling into the library
+    std::mem::forget(lib);
+
+    Ok(loaded)
+}
+
+/// Loads every plugin found in `dirs` with [`load_plugin`], in the order of
+/// `dirs` and then of their file names. See [`plugin_paths`] for the files
+/// that are considered plugins.
+pub fn load_plugins(
+    dirs: impl IntoIterator<Item = impl AsRef<Path>>,
+    task_ctx_provider: &Arc<dyn TaskContextProvider>,
+) -> Result<Vec<LoadedPlugin>> {
+    let mut plugins = vec![];
+    for dir in dirs {
+        for path in plugin_paths(dir)? {
+            plugins.push(load_plugin(path, task_ctx_provider)?);
+        }
+    }
+    Ok(plugins)
+}
+
+/// Returns the paths of the shared libraries (with the platform's extension,
+/// such as `.so`) directly in `dir`, sorted by file name
+pub fn plugin_paths(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir).map_err(|e| load_error(dir, e))?;
+
+    let mut paths = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| load_error(dir, e))?.path();
+        let is_library = path
+            .extension()
+            .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION);
+        if is_library && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn load_error(path: &Path, e: impl std::fmt::Display) -> DataFusionError {
+    ffi_datafusion_err!("Failed to load plugin from {}: {e}", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn plugin_paths_only_lists_libraries() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "datafusion_ffi_plugin_paths_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(dir.join("nested"))?;
+        let ext = std::env::consts::DLL_EXTENSION;
+        for name in [
+            format!("b.{ext}"),
+            format!("a.{ext}"),
+            "README.md".to_owned(),
+        ] {
+            fs::write(dir.join(name), b"")?;
+        }
+
+        let paths = plugin_paths(&dir)?;
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(
+            paths,
+            vec![dir.join(format!("a.{ext}")), dir.join(format!("b.{ext}"))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn load_plugin_rejects_non_libraries() {
+        let path = std::env::temp_dir().join(format!(
+            "datafusion_ffi_not_a_plugin_{}.{}",
+            std::process::id(),
+            std::env::consts::DLL_EXTENSION
+        ));
+        fs::write(&path, b"not a library").unwrap();
+
+        let ctx: Arc<dyn TaskContextProvider> =
+            Arc::new(datafusion::prelude::SessionContext::new());
+        let err = load_plugin(&path, &ctx).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(err, DataFusionError::Ffi(_)), "{err}");
+        assert!(err.to_string().contains("Failed to load plugin"), "{err}");
+    }
+
+    #[test]
+    fn plugin_round_trip() {
+        let plugin = Plugin::new("test")
+            .with_scalar_udf(datafusion_functions::math::abs())
+            .with_aggregate_udf(datafusion_functions_aggregate::sum::sum_udaf());
+
+        let ctx: Arc<dyn TaskContextProvider> =
+            Arc::new(datafusion::prelude::SessionContext::new());
+        let logical_codec = FFI_LogicalExtensionCodec::new_default(&ctx);
+        let loaded = LoadedPlugin::from(&plugin.into_ffi(&logical_codec));
+
+        assert_eq!(loaded.name, "test");
+        assert_eq!(loaded.scalar_udfs[0].name(), "abs");
+        assert_eq!(loaded.aggregate_udfs[0].name(), "sum");
+        assert!(loaded.window_udfs.is_empty());
+    }
+}
@@ -0,0 +1,452 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Sums of floating point values that are more accurate than adding the
+//! values one after the other, see [`FloatSummation`]
+
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, Float64Array};
+use arrow::datatypes::{DataType, Field, Float64Type};
+use datafusion_common::config::FloatSummation;
+use datafusion_common::{Result, ScalarValue};
+
+/// A running sum of `Float64` values, added up as configured by a
+/// [`FloatSummation`]
+#[derive(Debug, Clone)]
+pub enum FloatSum {
+    /// [`FloatSummation::Standard`]
+    Standard(f64),
+    /// [`FloatSummation::Compensated`]
+    Compensated(CompensatedSum),
+    /// [`FloatSummation::Deterministic`]
+    Exact(ExactSum),
+}
+
+impl FloatSum {
+    /// Creates an empty sum
+    pub fn new(summation: FloatSummation) -> Self {
+        match summation {
+            FloatSummation::Standard => Self::Standard(0.0),
+            FloatSummation::Compensated => Self::Compensated(CompensatedSum::default()),
+            FloatSummation::Deterministic => Self::Exact(ExactSum::default()),
+        }
+    }
+
+    /// Returns the names and types of the state fields of a sum
+    pub fn state_types(summation: FloatSummation) -> Vec<(&'static str, DataType)> {
+        match summation {
+            FloatSummation::Standard => vec![("sum", DataType::Float64)],
+            FloatSummation::Compensated => vec![
+                ("sum", DataType::Float64),
+                ("sum_compensation", DataType::Float64),
+            ],
+            FloatSummation::Deterministic => vec![(
+                "sum_partials",
+                DataType::List(Arc::new(Field::new_list_field(DataType::Float64, true))),
+            )],
+        }
+    }
+
+    /// Adds `value` to the sum
+    pub fn add(&mut self, value: f64) {
+        match self {
+            Self::Standard(sum) => *sum += value,
+            Self::Compensated(sum) => sum.add(value),
+            Self::Exact(sum) => sum.add(value),
+        }
+    }
+
+    /// Adds the non null `values` to the sum
+    pub fn update(&mut self, values: &Float64Array) {
+        values.iter().flatten().for_each(|value| self.add(value))
+    }
+
+    /// Subtracts the non null `values` from the sum
+    pub fn retract(&mut self, values: &Float64Array) {
+        values.iter().flatten().for_each(|value| self.add(-value))
+    }
+
+    /// Returns the sum, rounded to a `f64`
+    pub fn sum(&self) -> f64 {
+        match self {
+            Self::Standard(sum) => *sum,
+            Self::Compensated(sum) => sum.sum(),
+            Self::Exact(sum) => sum.sum(),
+        }
+    }
+
+    /// Returns the state of the sum, with one value for each of the
+    /// [`Self::state_types`], which are null if `is_empty` is true
+    pub fn state(&self, is_empty: bool) -> Vec<ScalarValue> {
+        match self {
+            _ if is_empty => Self::state_types(self.summation())
+                .iter()
+                .map(|(_, data_type)| ScalarValue::try_from(data_type))
+                .collect::<Result<_>>()
+                .expect("state types have null values"),
+            Self::Standard(sum) => vec![ScalarValue::from(*sum)],
+            Self::Compensated(sum) => {
+                vec![
+                    ScalarValue::from(sum.sum),
+                    ScalarValue::from(sum.compensation),
+                ]
+            }
+            Self::Exact(sum) => vec![sum.to_list()],
+        }
+    }
+
+    /// Merges the state at `row` of `states`, one array for each of the
+    /// [`Self::state_types`], into the sum. Returns false if the state is
+    /// null, which means that it is the state of an empty sum
+    pub fn merge(&mut self, states: &[ArrayRef], row: usize) -> bool {
+        if states[0].is_null(row) {
+            return false;
+        }
+        match self {
+            Self::Standard(sum) => {
+                *sum += states[0].as_primitive::<Float64Type>().value(row)
+            }
+            Self::Compensated(sum) => sum.merge(CompensatedSum {
+                sum: states[0].as_primitive::<Float64Type>().value(row),
+                compensation: states[1].as_primitive::<Float64Type>().value(row),
+            }),
+            Self::Exact(sum) => {
+                let values = states[0].as_list::<i32>().value(row);
+                sum.update(values.as_primitive::<Float64Type>())
+            }
+        }
+        true
+    }
+
+    /// Returns the [`FloatSummation`] of this sum
+    pub fn summation(&self) -> FloatSummation {
+        match self {
+            Self::Standard(_) => FloatSummation::Standard,
+            Self::Compensated(_) => FloatSummation::Compensated,
+            Self::Exact(_) => FloatSummation::Deterministic,
+        }
+    }
+
+    /// Returns the memory used by the sum, in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Exact(sum) => size_of_val(self) - size_of_val(sum) + sum.size(),
+            _ => size_of_val(self),
+        }
+    }
+}
+
+/// A sum that tracks the rounding error of each addition, known as Kahan
+/// summation in the improved variant by Neumaier.
+///
+/// The error of the sum is bounded independently of the number of values,
+/// unlike the error of adding the values one after the other, which grows
+/// with their number. The result may still depend on the order of the values.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    /// Adds `value` to the sum
+    pub fn add(&mut self, value: f64) {
+        let sum = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - sum) + value;
+        } else {
+            self.compensation += (value - sum) + self.sum;
+        }
+        self.sum = sum;
+    }
+
+    /// Adds the sum `other` to this sum
+    pub fn merge(&mut self, other: CompensatedSum) {
+        self.add(other.sum);
+        self.compensation += other.compensation;
+    }
+
+    /// Returns the sum
+    pub fn sum(&self) -> f64 {
+        // the compensation of an infinite or NaN sum is NaN
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+/// A sum that represents the exact sum of its values, as a list of non
+/// overlapping partial sums ordered by increasing magnitude, following
+/// Shewchuk's "Adaptive Precision Floating-Point Arithmetic and Fast Robust
+/// Geometric Predicates" (1997), like Python's `math.fsum`.
+///
+/// [`Self::sum`] returns the exact sum correctly rounded to a `f64`, which
+/// does not depend on the order in which the values were added or on how
+/// partial sums were merged. Values can be subtracted again exactly, which
+/// makes the sum suitable for sliding windows.
+///
+/// Infinite and NaN values, as well as sums that overflow, make the sum
+/// infinite or NaN like adding the values one after the other.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExactSum {
+    partials: Vec<f64>,
+    /// The sum of the infinite and NaN values, and of overflowed partial sums
+    non_finite: f64,
+}
+
+impl ExactSum {
+    /// Adds `value` to the sum
+    pub fn add(&mut self, value: f64) {
+        if !value.is_finite() {
+            self.non_finite += value;
+            return;
+        }
+        let mut x = value;
+        let mut len = 0;
+        for i in 0..self.partials.len() {
+            let mut y = self.partials[i];
+            if x.abs() < y.abs() {
+                std::mem::swap(&mut x, &mut y);
+            }
+            let hi = x + y;
+            let lo = y - (hi - x);
+            if lo != 0.0 {
+                self.partials[len] = lo;
+                len += 1;
+            }
+            x = hi;
+        }
+        if !x.is_finite() {
+            self.partials.clear();
+            self.non_finite += x;
+            return;
+        }
+        self.partials.truncate(len);
+        self.partials.push(x);
+    }
+
+    /// Adds the exact square of `value` to the sum
+    pub fn add_square(&mut self, value: f64) {
+        let (hi, lo) = two_product(value, value);
+        self.add(hi);
+        if hi.is_finite() {
+            self.add(lo);
+        }
+    }
+
+    /// Subtracts the exact square of `value` from the sum
+    pub fn sub_square(&mut self, value: f64) {
+        let (hi, lo) = two_product(value, value);
+        self.add(-hi);
+        if hi.is_finite() {
+            self.add(-lo);
+        }
+    }
+
+    /// Adds the non null `values` to the sum
+    pub fn update(&mut self, values: &Float64Array) {
+        values.iter().flatten().for_each(|value| self.add(value))
+    }
+
+    /// Adds the sum `other` to this sum
+    pub fn merge(&mut self, other: &ExactSum) {
+        other.values().for_each(|value| self.add(value))
+    }
+
+    /// Returns values whose exact sum is this sum, to exchange it between
+    /// accumulators
+    pub fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.partials
+            .iter()
+            .copied()
+            .chain((self.non_finite != 0.0).then_some(self.non_finite))
+    }
+
+    /// Returns the [`Self::values`] as a list, which [`Self::update`] adds
+    /// to another sum
+    pub fn to_list(&self) -> ScalarValue {
+        let values = self.values().map(ScalarValue::from).collect::<Vec<_>>();
+        ScalarValue::List(ScalarValue::new_list_nullable(&values, &DataType::Float64))
+    }
+
+    /// Returns the memory used by the sum, in bytes
+    pub fn size(&self) -> usize {
+        size_of_val(self) + self.partials.capacity() * size_of::<f64>()
+    }
+
+    /// Returns the exact sum, correctly rounded to a `f64`
+    pub fn sum(&self) -> f64 {
+        // also true for NaN
+        if self.non_finite != 0.0 {
+            return self.non_finite;
+        }
+        let partials = &self.partials;
+        let Some(mut n) = partials.len().checked_sub(1) else {
+            return 0.0;
+        };
+        // add the partials from the largest one until the sum is inexact
+        let mut hi = partials[n];
+        let mut lo = 0.0;
+        while n > 0 {
+            let x = hi;
+            n -= 1;
+            let y = partials[n];
+            hi = x + y;
+            lo = y - (hi - x);
+            if lo != 0.0 {
+                break;
+            }
+        }
+        // round half to even ties, unless the remaining partials break them
+        if n > 0
+            && ((lo < 0.0 && partials[n - 1] < 0.0)
+                || (lo > 0.0 && partials[n - 1] > 0.0))
+        {
+            let y = lo * 2.0;
+            let x = hi + y;
+            if y == x - hi {
+                hi = x;
+            }
+        }
+        hi
+    }
+}
+
+/// Returns the sum of the squared differences from the mean of `count`
+/// values whose exact sum is `sum` and whose exact sum of squares is
+/// `sum_squares`, correctly rounded to a `f64` except for the rounding of the
+/// mean.
+///
+/// Unlike the textbook formula `sum_squares - sum * sum / count`, this does
+/// not lose precision to cancellation, because the difference is computed
+/// exactly.
+pub fn exact_m2(count: u64, sum: &ExactSum, sum_squares: &ExactSum) -> f64 {
+    let n = count as f64;
+    let (s1, s2) = (sum.sum(), sum_squares.sum());
+    if !s1.is_finite() || !s2.is_finite() {
+        return s2 - s1 * s1 / n;
+    }
+    let mean = s1 / n;
+    // sum((x - mean)^2) = sum(x^2) - 2 * mean * sum(x) + n * mean^2
+    let mut m2 = sum_squares.clone();
+    for partial in sum.values() {
+        let (hi, lo) = two_product(-2.0 * mean, partial);
+        m2.add(hi);
+        m2.add(lo);
+    }
+    let (square_hi, square_lo) = two_product(mean, mean);
+    for part in [square_hi, square_lo] {
+        let (hi, lo) = two_product(n, part);
+        m2.add(hi);
+        m2.add(lo);
+    }
+    m2.sum().max(0.0)
+}
+
+/// Returns `a * b` and the rounding error of the product, whose sum is
+/// exactly `a * b` unless the product overflows or underflows
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact_sum(values: &[f64]) -> ExactSum {
+        let mut sum = ExactSum::default();
+        values.iter().for_each(|value| sum.add(*value));
+        sum
+    }
+
+    #[test]
+    fn compensated_sum_is_accurate() {
+        let mut compensated = CompensatedSum::default();
+        let mut standard = 0.0;
+        for value in [1.0, 1e100, 1.0, -1e100] {
+            compensated.add(value);
+            standard += value;
+        }
+        assert_eq!(compensated.sum(), 2.0);
+        assert_eq!(standard, 0.0);
+
+        let mut sum = CompensatedSum::default();
+        (0..10).for_each(|_| sum.add(0.1));
+        assert_eq!(sum.sum(), 1.0);
+    }
+
+    #[test]
+    fn exact_sum_does_not_depend_on_order() {
+        let values = [1e16, 1.0, -1e16, 0.1, 3.0e-20, 7.0, -0.3, 1e-3];
+        let expected = exact_sum(&values).sum();
+
+        let mut reversed = values;
+        reversed.reverse();
+        assert_eq!(exact_sum(&reversed).sum(), expected);
+
+        let mut merged = exact_sum(&values[5..]);
+        merged.merge(&exact_sum(&values[..2]));
+        merged.merge(&exact_sum(&values[2..5]));
+        assert_eq!(merged.sum(), expected);
+    }
+
+    #[test]
+    fn exact_sum_rounds_correctly() {
+        // the exact sum is halfway between 1 and the next f64, plus a bit
+        let sum = exact_sum(&[1.0, f64::EPSILON / 2.0, f64::EPSILON * 1e-10]);
+        assert_eq!(sum.sum(), 1.0 + f64::EPSILON);
+        // halfway cases round to even
+        let sum = exact_sum(&[1.0, f64::EPSILON / 2.0]);
+        assert_eq!(sum.sum(), 1.0);
+    }
+
+    #[test]
+    fn exact_sum_retract() {
+        let mut sum = exact_sum(&[1e100, 1.0, 2.5]);
+        sum.add(-1e100);
+        assert_eq!(sum.sum(), 3.5);
+    }
+
+    #[test]
+    fn exact_sum_non_finite() {
+        assert_eq!(exact_sum(&[1.0, f64::INFINITY]).sum(), f64::INFINITY);
+        assert!(
+            exact_sum(&[f64::INFINITY, f64::NEG_INFINITY])
+                .sum()
+                .is_nan()
+        );
+        assert_eq!(exact_sum(&[f64::MAX, f64::MAX]).sum(), f64::INFINITY);
+        assert_eq!(exact_sum(&[]).sum(), 0.0);
+    }
+
+    #[test]
+    fn exact_m2_without_cancellation() {
+        let values = [1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0];
+        let mut sum = ExactSum::default();
+        let mut sum_squares = ExactSum::default();
+        for value in values {
+            sum.add(value);
+            sum_squares.add_square(value);
+        }
+        assert_eq!(exact_m2(4, &sum, &sum_squares), 90.0);
+    }
+}
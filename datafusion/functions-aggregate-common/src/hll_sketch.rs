@@ -0,0 +1,304 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`HllSketch`]: a [`HyperLogLog`] with a stable serialized form, that can
+//! be stored and merged later
+
+use arrow::array::{Array, ArrayRef, AsArray, make_array};
+use arrow::datatypes::{
+    DataType, Decimal128Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type,
+    Int64Type, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use datafusion_common::{Result, exec_err, not_impl_err};
+
+use crate::hyperloglog::{HLL_P, HyperLogLog, NUM_REGISTERS};
+
+/// The first bytes of a serialized [`HllSketch`]
+const SKETCH_MAGIC: &[u8; 4] = b"DFHL";
+/// The version of the serialized form of [`HllSketch`]
+const SKETCH_VERSION: u8 = 1;
+/// The length of the header of a serialized [`HllSketch`]: the magic bytes,
+/// the version and the precision
+const SKETCH_HEADER_LEN: usize = SKETCH_MAGIC.len() + 2;
+
+/// A [`HyperLogLog`] sketch of distinct values that can be serialized, stored
+/// (for example in a Parquet file) and merged with other sketches later.
+///
+/// Unlike the sketches of `approx_distinct`, whose hash function may change
+/// between releases, values are hashed with a fixed hash function (64 bit
+/// FNV-1a followed by the MurmurHash3 finalizer) of a canonical little
+/// endian encoding of the value:
+///
+/// * integers of any width are hashed as 8 bytes, so that sketches of the
+///   same values stored with different integer types can be merged
+/// * floats are hashed as the bits of the equivalent `f64`
+/// * strings and binaries are hashed as their bytes
+/// * dates, times, timestamps and durations are hashed as their underlying
+///   integer value
+///
+/// The serialized form (version 1) is the 4 bytes `DFHL`, a version byte
+/// (`1`), a precision byte (`14`) and the `2**14` registers of the sketch,
+/// one byte each.
+#[derive(Debug, Default)]
+pub struct HllSketch {
+    hll: HyperLogLog<[u8]>,
+}
+
+impl HllSketch {
+    /// Create a new, empty [`HllSketch`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the non null values of `array` to the sketch
+    pub fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        macro_rules! add_integers {
+            ($t:ty, $as:ty) => {
+                for v in array.as_primitive::<$t>().iter().flatten() {
+                    self.add_bytes(&(v as $as).to_le_bytes());
+                }
+            };
+        }
+
+        match array.data_type() {
+            DataType::Null => {}
+            DataType::Boolean => {
+                for v in array.as_boolean().iter().flatten() {
+                    self.add_bytes(&[v as u8]);
+                }
+            }
+            DataType::Int8 => add_integers!(Int8Type, i64),
+            DataType::Int16 => add_integers!(Int16Type, i64),
+            DataType::Int32 => add_integers!(Int32Type, i64),
+            DataType::Int64 => add_integers!(Int64Type, i64),
+            DataType::UInt8 => add_integers!(UInt8Type, u64),
+            DataType::UInt16 => add_integers!(UInt16Type, u64),
+            DataType::UInt32 => add_integers!(UInt32Type, u64),
+            DataType::UInt64 => add_integers!(UInt64Type, u64),
+            DataType::Float32 => {
+                for v in array.as_primitive::<Float32Type>().iter().flatten() {
+                    self.add_bytes(&f64::from(v).to_bits().to_le_bytes());
+                }
+            }
+            DataType::Float64 => {
+                for v in array.as_primitive::<Float64Type>().iter().flatten() {
+                    self.add_bytes(&v.to_bits().to_le_bytes());
+                }
+            }
+            DataType::Decimal128(_, _) => {
+                for v in array.as_primitive::<Decimal128Type>().iter().flatten() {
+                    self.add_bytes(&v.to_le_bytes());
+                }
+            }
+            DataType::Utf8 => {
+                array.as_string::<i32>().iter().flatten().for_each(|v| {
+                    self.add_bytes(v.as_bytes());
+                });
+            }
+            DataType::LargeUtf8 => {
+                array.as_string::<i64>().iter().flatten().for_each(|v| {
+                    self.add_bytes(v.as_bytes());
+                });
+            }
+            DataType::Utf8View => {
+                array.as_string_view().iter().flatten().for_each(|v| {
+                    self.add_bytes(v.as_bytes());
+                });
+            }
+            DataType::Binary => {
+                array.as_binary::<i32>().iter().flatten().for_each(|v| {
+                    self.add_bytes(v);
+                });
+            }
+            DataType::LargeBinary => {
+                array.as_binary::<i64>().iter().flatten().for_each(|v| {
+                    self.add_bytes(v);
+                });
+            }
+            DataType::BinaryView => {
+                array.as_binary_view().iter().flatten().for_each(|v| {
+                    self.add_bytes(v);
+                });
+            }
+            DataType::Date32 | DataType::Time32(_) => {
+                self.update(&reinterpret(array, DataType::Int32)?)?;
+            }
+            DataType::Date64
+            | DataType::Time64(_)
+            | DataType::Timestamp(_, _)
+            | DataType::Duration(_) => {
+                self.update(&reinterpret(array, DataType::Int64)?)?;
+            }
+            other => {
+                return not_impl_err!(
+                    "Support for HyperLogLog sketches of data type {other} is not implemented"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a value, in its canonical encoding, to the sketch
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        self.hll.add_hashed(sketch_hash(bytes));
+    }
+
+    /// Merges the `other` sketch into this one
+    pub fn merge(&mut self, other: &HllSketch) {
+        self.hll.merge(&other.hll);
+    }
+
+    /// The estimated number of distinct values added to the sketch
+    pub fn estimate(&self) -> u64 {
+        self.hll.count() as u64
+    }
+
+    /// Serializes the sketch
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SKETCH_HEADER_LEN + NUM_REGISTERS);
+        bytes.extend_from_slice(SKETCH_MAGIC);
+        bytes.push(SKETCH_VERSION);
+        bytes.push(HLL_P as u8);
+        bytes.extend_from_slice(self.hll.as_ref());
+        bytes
+    }
+
+    /// Deserializes a sketch serialized by [`Self::to_bytes`]
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        let Some((header, registers)) = bytes.split_at_checked(SKETCH_HEADER_LEN) else {
+            return exec_err!("Invalid HyperLogLog sketch: too short");
+        };
+        if header[..SKETCH_MAGIC.len()] != SKETCH_MAGIC[..] {
+            return exec_err!("Invalid HyperLogLog sketch: unexpected header");
+        }
+        let (version, precision) =
+            (header[SKETCH_MAGIC.len()], header[SKETCH_HEADER_LEN - 1]);
+        if version != SKETCH_VERSION {
+            return exec_err!("Unsupported HyperLogLog sketch version {version}");
+        }
+        if precision as usize != HLL_P {
+            return exec_err!(
+                "Unsupported HyperLogLog sketch precision {precision}, expected {HLL_P}"
+            );
+        }
+        let Ok(registers) = registers.try_into() else {
+            return exec_err!(
+                "Invalid HyperLogLog sketch: expected {NUM_REGISTERS} registers, got {}",
+                registers.len()
+            );
+        };
+        Ok(Self {
+            hll: HyperLogLog::new_with_registers(registers),
+        })
+    }
+}
+
+/// Calls `f` with each serialized sketch of `array`, a binary array of
+/// sketches serialized by [`HllSketch::to_bytes`], or `None` for nulls
+pub fn try_for_each_serialized(
+    array: &ArrayRef,
+    mut f: impl FnMut(Option<&[u8]>) -> Result<()>,
+) -> Result<()> {
+    match array.data_type() {
+        DataType::Binary => array.as_binary::<i32>().iter().try_for_each(f),
+        DataType::LargeBinary => array.as_binary::<i64>().iter().try_for_each(f),
+        DataType::BinaryView => array.as_binary_view().iter().try_for_each(f),
+        DataType::Null => (0..array.len()).try_for_each(|_| f(None)),
+        other => exec_err!("Expected a binary HyperLogLog sketch, got {other}"),
+    }
+}
+
+/// Reinterprets the values of a primitive `array` as values of the integer
+/// type `data_type` of the same width
+fn reinterpret(array: &ArrayRef, data_type: DataType) -> Result<ArrayRef> {
+    let data = array
+        .to_data()
+        .into_builder()
+        .data_type(data_type)
+        .build()?;
+    Ok(make_array(data))
+}
+
+/// The hash of a value of a [`HllSketch`]: 64 bit FNV-1a, followed by the
+/// finalizer of MurmurHash3 to mix the bits of the hash
+fn sketch_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use arrow::array::{Int32Array, Int64Array, StringArray, StringViewArray};
+
+    #[test]
+    fn sketch_hash_is_stable() {
+        // the hash is part of the serialized form: it must never change
+        assert_eq!(sketch_hash(b""), 0xefd0_1f60_ba99_2926);
+        assert_eq!(sketch_hash(b"datafusion"), sketch_hash(b"datafusion"));
+        assert_ne!(sketch_hash(b"datafusion"), sketch_hash(b"DataFusion"));
+    }
+
+    #[test]
+    fn sketch_round_trip() -> Result<()> {
+        let mut sketch = HllSketch::new();
+        let values: ArrayRef = Arc::new(Int64Array::from_iter_values(0..1000));
+        sketch.update(&values)?;
+        let estimate = sketch.estimate();
+        assert!((990..=1010).contains(&estimate), "{estimate}");
+
+        let bytes = sketch.to_bytes();
+        assert_eq!(bytes.len(), SKETCH_HEADER_LEN + NUM_REGISTERS);
+        assert_eq!(&bytes[..6], b"DFHL\x01\x0e");
+        assert_eq!(HllSketch::try_from_bytes(&bytes)?.estimate(), estimate);
+
+        assert!(HllSketch::try_from_bytes(&bytes[..100]).is_err());
+        assert!(HllSketch::try_from_bytes(b"not a sketch").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sketches_of_equal_values_merge() -> Result<()> {
+        let mut ints = HllSketch::new();
+        ints.update(&(Arc::new(Int32Array::from_iter_values(0..500)) as ArrayRef))?;
+        let mut longs = HllSketch::new();
+        longs.update(&(Arc::new(Int64Array::from_iter_values(250..750)) as ArrayRef))?;
+        ints.merge(&longs);
+        let estimate = ints.estimate();
+        assert!((740..=760).contains(&estimate), "{estimate}");
+
+        let strings: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c"]));
+        let views: ArrayRef = Arc::new(StringViewArray::from(vec!["c", "d"]));
+        let mut sketch = HllSketch::new();
+        sketch.update(&strings)?;
+        sketch.update(&views)?;
+        assert_eq!(sketch.estimate(), 4);
+        Ok(())
+    }
+}
@@ -38,16 +38,22 @@ use std::hash::BuildHasher;
 use std::hash::Hash;
 use std::marker::PhantomData;
 
+use datafusion_common::{
+    DataFusionError, Result, ScalarValue, internal_datafusion_err, internal_err,
+};
+
 /// The greater is P, the smaller the error.
-const HLL_P: usize = 14_usize;
+pub(crate) const HLL_P: usize = 14_usize;
 /// The number of bits of the hash value used determining the number of leading zeros
 const HLL_Q: usize = 64_usize - HLL_P;
-const NUM_REGISTERS: usize = 1_usize << HLL_P;
+pub(crate) const NUM_REGISTERS: usize = 1_usize << HLL_P;
 /// Mask to obtain index into the registers
 const HLL_P_MASK: u64 = (NUM_REGISTERS as u64) - 1;
 
+/// A HyperLogLog sketch of `2**14` registers, estimating the number of
+/// distinct values of `T` added to it
 #[derive(Clone, Debug)]
-pub(crate) struct HyperLogLog<T>
+pub struct HyperLogLog<T>
 where
     T: Hash + ?Sized,
 {
@@ -61,7 +67,7 @@ where
 /// shared across cluster, this HLL_HASH_STATE will have to be consistent across all
 /// parties otherwise we might have corruption. So ideally for later this seed
 /// shall be part of the serialized form (or stay unchanged across versions).
-pub(crate) const HLL_HASH_STATE: foldhash::quality::FixedState =
+pub const HLL_HASH_STATE: foldhash::quality::FixedState =
     foldhash::quality::FixedState::with_seed(0);
 
 impl<T> Default for HyperLogLog<T>
@@ -86,7 +92,7 @@ where
     /// Creates a HyperLogLog from already populated registers
     /// note that this method should not be invoked in untrusted environment
     /// because the internal structure of registers are not examined.
-    pub(crate) fn new_with_registers(registers: [u8; NUM_REGISTERS]) -> Self {
+    pub fn new_with_registers(registers: [u8; NUM_REGISTERS]) -> Self {
         Self {
             registers,
             phantom: PhantomData,
@@ -112,12 +118,21 @@ where
     /// The hash should be computed using [`HLL_HASH_STATE`], the same hasher used
     /// by [`Self::add`].
     #[inline]
-    pub(crate) fn add_hashed(&mut self, hash: u64) {
+    pub fn add_hashed(&mut self, hash: u64) {
         let index = (hash & HLL_P_MASK) as usize;
         let p = ((hash >> HLL_P) | (1_u64 << HLL_Q)).trailing_zeros() + 1;
         self.registers[index] = self.registers[index].max(p as u8);
     }
 
+    /// Returns the registers as a [`ScalarValue::Binary`], the serialized
+    /// form read back by `TryFrom<&ScalarValue>`.
+    ///
+    /// This is not a `From` impl, as that would make `ScalarValue::from(x.as_ref())`
+    /// ambiguous in every crate depending on this one.
+    pub fn to_scalar_value(&self) -> ScalarValue {
+        ScalarValue::Binary(Some(self.registers.to_vec()))
+    }
+
     /// Get the register histogram (each value in register index into
     /// the histogram; u32 is enough because we only have 2**14=16384 registers
     #[inline]
@@ -214,6 +229,29 @@ where
     }
 }
 
+impl<T: Hash + ?Sized> TryFrom<&[u8]> for HyperLogLog<T> {
+    type Error = DataFusionError;
+    fn try_from(v: &[u8]) -> Result<HyperLogLog<T>> {
+        let arr: [u8; NUM_REGISTERS] = v.try_into().map_err(|_| {
+            internal_datafusion_err!("Impossibly got invalid binary array from states")
+        })?;
+        Ok(HyperLogLog::<T>::new_with_registers(arr))
+    }
+}
+
+impl<T: Hash + ?Sized> TryFrom<&ScalarValue> for HyperLogLog<T> {
+    type Error = DataFusionError;
+    fn try_from(v: &ScalarValue) -> Result<HyperLogLog<T>> {
+        if let ScalarValue::Binary(Some(slice)) = v {
+            slice.as_slice().try_into()
+        } else {
+            internal_err!(
+                "Impossibly got invalid scalar value while converting to HyperLogLog"
+            )
+        }
+    }
+}
+
 impl<T> Extend<T> for HyperLogLog<T>
 where
     T: Hash,
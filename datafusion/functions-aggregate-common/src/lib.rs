@@ -34,6 +34,9 @@
 
 pub mod accumulator;
 pub mod aggregate;
+pub mod float_sum;
+pub mod hll_sketch;
+pub mod hyperloglog;
 pub mod merge_arrays;
 pub mod min_max;
 pub mod noop_accumulator;
@@ -17,7 +17,6 @@
 
 //! Defines physical expressions that can evaluated at runtime during query execution
 
-use crate::hyperloglog::{HLL_HASH_STATE, HyperLogLog};
 use arrow::array::{Array, BinaryArray, StringViewArray};
 use arrow::array::{
     GenericBinaryArray, GenericStringArray, OffsetSizeTrait, PrimitiveArray,
@@ -31,8 +30,7 @@ use arrow::datatypes::{
 use arrow::{array::ArrayRef, datatypes::DataType, datatypes::Field};
 use datafusion_common::ScalarValue;
 use datafusion_common::{
-    DataFusionError, Result, downcast_value, internal_datafusion_err, internal_err,
-    not_impl_err,
+    Result, downcast_value, internal_datafusion_err, internal_err, not_impl_err,
 };
 use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
 use datafusion_expr::utils::format_state_name;
@@ -43,6 +41,7 @@ use datafusion_functions_aggregate_common::aggregate::count_distinct::{
     Bitmap65536DistinctCountAccumulator, Bitmap65536DistinctCountAccumulatorI16,
     BoolArray256DistinctCountAccumulator, BoolArray256DistinctCountAccumulatorI8,
 };
+use datafusion_functions_aggregate_common::hyperloglog::{HLL_HASH_STATE, HyperLogLog};
 use datafusion_functions_aggregate_common::noop_accumulator::NoopAccumulator;
 use datafusion_macros::user_doc;
 use std::fmt::{Debug, Formatter};
@@ -57,36 +56,6 @@ make_udaf_expr_and_func!(
     approx_distinct_udaf
 );
 
-impl<T: Hash + ?Sized> From<&HyperLogLog<T>> for ScalarValue {
-    fn from(v: &HyperLogLog<T>) -> ScalarValue {
-        let values = v.as_ref().to_vec();
-        ScalarValue::Binary(Some(values))
-    }
-}
-
-impl<T: Hash + ?Sized> TryFrom<&[u8]> for HyperLogLog<T> {
-    type Error = DataFusionError;
-    fn try_from(v: &[u8]) -> Result<HyperLogLog<T>> {
-        let arr: [u8; 16384] = v.try_into().map_err(|_| {
-            internal_datafusion_err!("Impossibly got invalid binary array from states")
-        })?;
-        Ok(HyperLogLog::<T>::new_with_registers(arr))
-    }
-}
-
-impl<T: Hash + ?Sized> TryFrom<&ScalarValue> for HyperLogLog<T> {
-    type Error = DataFusionError;
-    fn try_from(v: &ScalarValue) -> Result<HyperLogLog<T>> {
-        if let ScalarValue::Binary(Some(slice)) = v {
-            slice.as_slice().try_into()
-        } else {
-            internal_err!(
-                "Impossibly got invalid scalar value while converting to HyperLogLog"
-            )
-        }
-    }
-}
-
 #[derive(Debug)]
 struct ApproxDistinctBitmapWrapper<A: Accumulator> {
     inner: A,
@@ -211,7 +180,7 @@ macro_rules! default_accumulator_impl {
         }
 
         fn state(&mut self) -> Result<Vec<ScalarValue>> {
-            let value = ScalarValue::from(&self.hll);
+            let value = self.hll.to_scalar_value();
             Ok(vec![value])
         }
 
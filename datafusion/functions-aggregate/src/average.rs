@@ -31,12 +31,13 @@ use arrow::datatypes::{
     DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType,
     DurationSecondType, Field, FieldRef, Float64Type, TimeUnit, UInt64Type, i256,
 };
+use datafusion_common::config::{ConfigOptions, FloatSummation};
 use datafusion_common::types::{NativeType, logical_float64};
 use datafusion_common::{Result, ScalarValue, exec_err, not_impl_err};
 use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
 use datafusion_expr::utils::format_state_name;
 use datafusion_expr::{
-    Accumulator, AggregateUDFImpl, Coercion, Documentation, EmitTo, Expr,
+    Accumulator, AggregateUDF, AggregateUDFImpl, Coercion, Documentation, EmitTo, Expr,
     GroupsAccumulator, ReversedUDAF, Signature, TypeSignature, TypeSignatureClass,
     Volatility,
 };
@@ -47,6 +48,7 @@ use datafusion_functions_aggregate_common::aggregate::groups_accumulator::accumu
 use datafusion_functions_aggregate_common::aggregate::groups_accumulator::nulls::{
     filtered_null_mask, set_nulls,
 };
+use datafusion_functions_aggregate_common::float_sum::FloatSum;
 use datafusion_functions_aggregate_common::utils::DecimalAverager;
 use datafusion_macros::user_doc;
 use log::debug;
@@ -91,6 +93,8 @@ pub fn avg_distinct(expr: Expr) -> Expr {
 pub struct Avg {
     signature: Signature,
     aliases: Vec<String>,
+    /// How `Float64` values are added up
+    float_summation: FloatSummation,
 }
 
 impl Avg {
@@ -115,8 +119,17 @@ impl Avg {
                 Volatility::Immutable,
             ),
             aliases: vec![String::from("mean")],
+            float_summation: FloatSummation::Standard,
         }
     }
+
+    /// Returns how the values of `data_type` are added up, if they are
+    /// floating point values that are not added up one after the other
+    fn float_summation(&self, data_type: &DataType) -> Option<FloatSummation> {
+        (data_type == &DataType::Float64
+            && self.float_summation != FloatSummation::Standard)
+            .then_some(self.float_summation)
+    }
 }
 
 impl Default for Avg {
@@ -221,7 +234,12 @@ impl AggregateUDFImpl for Avg {
             }
         } else {
             match (&data_type, acc_args.return_type()) {
-                (Float64, Float64) => Ok(Box::<AvgAccumulator>::default()),
+                (Float64, Float64) => match self.float_summation(data_type) {
+                    Some(float_summation) => {
+                        Ok(Box::new(FloatAvgAccumulator::new(float_summation)))
+                    }
+                    None => Ok(Box::<AvgAccumulator>::default()),
+                },
                 (
                     Decimal32(sum_precision, sum_scale),
                     Decimal32(target_precision, target_scale),
@@ -313,6 +331,16 @@ impl AggregateUDFImpl for Avg {
                 )
                 .into(),
             ])
+        } else if let Some(float_summation) =
+            self.float_summation(args.input_fields[0].data_type())
+        {
+            let count = (format_state_name(args.name, "count"), DataType::UInt64);
+            Ok(std::iter::once(count)
+                .chain(FloatSum::state_types(float_summation).into_iter().map(
+                    |(name, data_type)| (format_state_name(args.name, name), data_type),
+                ))
+                .map(|(name, data_type)| Arc::new(Field::new(name, data_type, true)))
+                .collect())
         } else {
             Ok(vec![
                 Field::new(
@@ -342,6 +370,9 @@ impl AggregateUDFImpl for Avg {
                 | DataType::Decimal256(_, _)
                 | DataType::Duration(_)
         ) && !args.is_distinct
+            && self
+                .float_summation(args.return_field.data_type())
+                .is_none()
     }
 
     fn create_groups_accumulator(
@@ -491,6 +522,17 @@ impl AggregateUDFImpl for Avg {
         &self.aliases
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        let float_summation = config.execution.float_summation;
+        (float_summation != self.float_summation).then(|| {
+            AggregateUDF::from(Self {
+                signature: self.signature.clone(),
+                aliases: self.aliases.clone(),
+                float_summation,
+            })
+        })
+    }
+
     fn reverse_expr(&self) -> ReversedUDAF {
         ReversedUDAF::Identical
     }
@@ -560,6 +602,70 @@ impl Accumulator for AvgAccumulator {
     }
 }
 
+/// An accumulator to compute the average of `Float64` values, whose sum is
+/// computed with a [`FloatSummation`] other than the standard one
+#[derive(Debug)]
+struct FloatAvgAccumulator {
+    sum: FloatSum,
+    count: u64,
+}
+
+impl FloatAvgAccumulator {
+    fn new(float_summation: FloatSummation) -> Self {
+        Self {
+            sum: FloatSum::new(float_summation),
+            count: 0,
+        }
+    }
+}
+
+impl Accumulator for FloatAvgAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<Float64Type>();
+        self.count += (values.len() - values.null_count()) as u64;
+        self.sum.update(values);
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(
+            (self.count != 0).then(|| self.sum.sum() / self.count as f64),
+        ))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) - size_of_val(&self.sum) + self.sum.size()
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let mut state = vec![ScalarValue::from(self.count)];
+        state.extend(self.sum.state(self.count == 0));
+        Ok(state)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        // counts are summed
+        self.count += sum(states[0].as_primitive::<UInt64Type>()).unwrap_or_default();
+
+        // sums are merged, skipping the states of empty accumulators
+        for row in 0..states[0].len() {
+            self.sum.merge(&states[1..], row);
+        }
+        Ok(())
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<Float64Type>();
+        self.count -= (values.len() - values.null_count()) as u64;
+        self.sum.retract(values);
+        Ok(())
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+}
+
 /// An accumulator to compute the average for decimals
 #[derive(Debug)]
 struct DecimalAvgAccumulator<T: DecimalType + ArrowNumericType + Debug> {
@@ -0,0 +1,243 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `hll_sketch` and `hll_merge` aggregate functions, building HyperLogLog
+//! sketches that can be stored and merged later
+
+use std::mem::size_of_val;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::hll_sketch::{
+    HllSketch as Sketch, try_for_each_serialized,
+};
+use datafusion_macros::user_doc;
+
+make_udaf_expr_and_func!(
+    HllSketch,
+    hll_sketch,
+    expression,
+    "HyperLogLog sketch of the distinct input values",
+    hll_sketch_udaf
+);
+
+make_udaf_expr_and_func!(
+    HllMerge,
+    hll_merge,
+    sketch,
+    "merge of HyperLogLog sketches",
+    hll_merge_udaf
+);
+
+#[user_doc(
+    doc_section(label = "Approximate Functions"),
+    description = r#"Returns a HyperLogLog sketch of the distinct input values, as a binary value.
+
+Unlike `approx_distinct`, the sketch can be stored (for example in a Parquet file), merged with other sketches using `hll_merge`, and estimated later using `hll_estimate`. The serialized form of the sketch is stable across releases."#,
+    syntax_example = "hll_sketch(expression)",
+    sql_example = r#"```sql
+> SELECT hll_estimate(hll_sketch(column_name)) FROM table_name;
++--------------------------------------------------+
+| hll_estimate(hll_sketch(table_name.column_name)) |
++--------------------------------------------------+
+| 42                                               |
++--------------------------------------------------+
+```"#,
+    standard_argument(name = "expression",)
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct HllSketch {
+    signature: Signature,
+}
+
+impl HllSketch {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl Default for HllSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregateUDFImpl for HllSketch {
+    fn name(&self) -> &str {
+        "hll_sketch"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        Ok(vec![sketch_state_field(args.name)])
+    }
+
+    fn accumulator(&self, _acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(HllSketchAccumulator::new(false)))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+#[user_doc(
+    doc_section(label = "Approximate Functions"),
+    description = "Merges HyperLogLog sketches built by `hll_sketch` into a single sketch, as a binary value. Null sketches are ignored.",
+    syntax_example = "hll_merge(sketch)",
+    sql_example = r#"```sql
+> SELECT hll_estimate(hll_merge(daily_sketch)) FROM daily_users;
++---------------------------------------------------+
+| hll_estimate(hll_merge(daily_users.daily_sketch)) |
++---------------------------------------------------+
+| 1204                                              |
++---------------------------------------------------+
+```"#,
+    argument(
+        name = "sketch",
+        description = "Binary HyperLogLog sketch built by `hll_sketch` or `hll_merge`."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct HllMerge {
+    signature: Signature,
+}
+
+impl HllMerge {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::uniform(
+                1,
+                vec![
+                    DataType::Binary,
+                    DataType::LargeBinary,
+                    DataType::BinaryView,
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl Default for HllMerge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregateUDFImpl for HllMerge {
+    fn name(&self) -> &str {
+        "hll_merge"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        Ok(vec![sketch_state_field(args.name)])
+    }
+
+    fn accumulator(&self, _acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(HllSketchAccumulator::new(true)))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+fn sketch_state_field(name: &str) -> FieldRef {
+    Field::new(
+        format_state_name(name, "hll_sketch"),
+        DataType::Binary,
+        false,
+    )
+    .into()
+}
+
+/// Accumulator of `hll_sketch` and `hll_merge`
+#[derive(Debug)]
+struct HllSketchAccumulator {
+    sketch: Sketch,
+    /// True if the input values are serialized sketches to merge, rather
+    /// than values to add to the sketch
+    merge_input: bool,
+}
+
+impl HllSketchAccumulator {
+    fn new(merge_input: bool) -> Self {
+        Self {
+            sketch: Sketch::new(),
+            merge_input,
+        }
+    }
+
+    fn merge_sketches(&mut self, sketches: &ArrayRef) -> Result<()> {
+        try_for_each_serialized(sketches, |bytes| {
+            if let Some(bytes) = bytes {
+                self.sketch.merge(&Sketch::try_from_bytes(bytes)?);
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Accumulator for HllSketchAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        if self.merge_input {
+            self.merge_sketches(&values[0])
+        } else {
+            self.sketch.update(&values[0])
+        }
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.merge_sketches(&states[0])
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Binary(Some(self.sketch.to_bytes())))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+    }
+}
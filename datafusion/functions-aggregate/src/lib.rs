@@ -78,12 +78,13 @@ pub mod count;
 pub mod covariance;
 pub mod first_last;
 pub mod grouping;
-pub mod hyperloglog;
+pub mod hll_sketch;
 pub mod median;
 pub mod min_max;
 pub mod nth_value;
 pub mod percentile_cont;
 pub mod regr;
+pub mod reservoir_sample;
 pub mod stddev;
 pub mod string_agg;
 pub mod sum;
@@ -92,6 +93,8 @@ pub mod variance;
 pub mod planner;
 mod utils;
 
+pub use datafusion_functions_aggregate_common::hyperloglog;
+
 use crate::approx_percentile_cont::approx_percentile_cont_udaf;
 use crate::approx_percentile_cont_with_weight::approx_percentile_cont_with_weight_udaf;
 use datafusion_common::Result;
@@ -122,6 +125,8 @@ pub mod expr_fn {
     pub use super::first_last::first_value;
     pub use super::first_last::last_value;
     pub use super::grouping::grouping;
+    pub use super::hll_sketch::hll_merge;
+    pub use super::hll_sketch::hll_sketch;
     pub use super::median::median;
     pub use super::min_max::max;
     pub use super::min_max::min;
@@ -136,6 +141,7 @@ pub mod expr_fn {
     pub use super::regr::regr_sxx;
     pub use super::regr::regr_sxy;
     pub use super::regr::regr_syy;
+    pub use super::reservoir_sample::reservoir_sample;
     pub use super::stddev::stddev;
     pub use super::stddev::stddev_pop;
     pub use super::sum::sum;
@@ -173,6 +179,8 @@ pub fn all_default_aggregate_functions() -> Vec<Arc<AggregateUDF>> {
         stddev::stddev_pop_udaf(),
         approx_median::approx_median_udaf(),
         approx_distinct::approx_distinct_udaf(),
+        hll_sketch::hll_sketch_udaf(),
+        hll_sketch::hll_merge_udaf(),
         approx_percentile_cont_udaf(),
         approx_percentile_cont_with_weight_udaf(),
         percentile_cont::percentile_cont_udaf(),
@@ -185,6 +193,7 @@ pub fn all_default_aggregate_functions() -> Vec<Arc<AggregateUDF>> {
         average::avg_udaf(),
         grouping::grouping_udaf(),
         nth_value::nth_value_udaf(),
+        reservoir_sample::reservoir_sample_udaf(),
     ]
 }
 
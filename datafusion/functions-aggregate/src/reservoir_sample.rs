@@ -0,0 +1,359 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `reservoir_sample` aggregate function, returning a uniform random sample
+//! of the input values
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use datafusion_common::cast::{as_float64_array, as_list_array};
+use datafusion_common::{Result, ScalarValue, plan_err};
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
+};
+use datafusion_macros::user_doc;
+use datafusion_physical_expr::expressions::Literal;
+use rand::{Rng, rng};
+
+make_udaf_expr_and_func!(
+    ReservoirSample,
+    reservoir_sample,
+    expression k,
+    "uniform random sample of k input values",
+    reservoir_sample_udaf
+);
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = r#"Returns an array of `k` input values sampled uniformly at random, without replacement, or all the input values if there are at most `k` of them. Null values are ignored.
+
+The sample is computed in one pass using reservoir sampling: each value gets a random key, and the `k` values with the smallest keys are kept, so that the samples of the partitions of a group can be merged. The order of the sampled values is random, and the result differs between executions."#,
+    syntax_example = "reservoir_sample(expression, k)",
+    sql_example = r#"```sql
+> SELECT reservoir_sample(column_name, 3) FROM table_name;
++---------------------------------------------------+
+| reservoir_sample(table_name.column_name,Int64(3)) |
++---------------------------------------------------+
+| [42, 7, 19]                                       |
++---------------------------------------------------+
+```"#,
+    standard_argument(name = "expression",),
+    argument(
+        name = "k",
+        description = "Number of values to sample. Must be a positive integer literal."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ReservoirSample {
+    signature: Signature,
+}
+
+impl ReservoirSample {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Volatile),
+        }
+    }
+}
+
+impl Default for ReservoirSample {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregateUDFImpl for ReservoirSample {
+    fn name(&self) -> &str {
+        "reservoir_sample"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::List(Arc::new(Field::new_list_field(
+            arg_types[0].clone(),
+            true,
+        ))))
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        Ok(vec![
+            Field::new_list(
+                format_state_name(args.name, "reservoir_sample"),
+                // See COMMENTS.md to understand why nullable is set to true
+                Field::new_list_field(args.input_fields[0].data_type().clone(), true),
+                false,
+            )
+            .into(),
+            Field::new_list(
+                format_state_name(args.name, "reservoir_sample_keys"),
+                Field::new_list_field(DataType::Float64, true),
+                false,
+            )
+            .into(),
+        ])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let k = acc_args.exprs[1]
+            .downcast_ref::<Literal>()
+            .map(|lit| lit.value())
+            .filter(|value| value.data_type().is_integer())
+            .and_then(|value| value.cast_to(&DataType::Int64).ok());
+        let k = match k {
+            Some(ScalarValue::Int64(Some(k))) if k > 0 => k as usize,
+            _ => {
+                return plan_err!(
+                    "{} requires a positive integer literal for k, got {}",
+                    self.name(),
+                    acc_args.exprs[1]
+                );
+            }
+        };
+        Ok(Box::new(ReservoirSampleAccumulator::new(
+            k,
+            acc_args.expr_fields[0].data_type().clone(),
+        )))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// A sampled value and its random key
+#[derive(Debug)]
+struct SampledValue {
+    key: f64,
+    value: ScalarValue,
+}
+
+impl PartialEq for SampledValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SampledValue {}
+
+impl PartialOrd for SampledValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SampledValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.total_cmp(&other.key)
+    }
+}
+
+/// Accumulator of `reservoir_sample`, keeping the `k` values with the
+/// smallest random keys.
+///
+/// As every value is equally likely to get one of the `k` smallest keys, the
+/// kept values are a uniform sample. Samples are merged by keeping the `k`
+/// values with the smallest keys among them, which is the same sample as if
+/// all their values had been accumulated together.
+#[derive(Debug)]
+struct ReservoirSampleAccumulator {
+    k: usize,
+    data_type: DataType,
+    /// Max-heap on the keys, so that the value with the largest key is the
+    /// one replaced by a value with a smaller key
+    sample: BinaryHeap<SampledValue>,
+}
+
+impl ReservoirSampleAccumulator {
+    fn new(k: usize, data_type: DataType) -> Self {
+        Self {
+            k,
+            data_type,
+            sample: BinaryHeap::new(),
+        }
+    }
+
+    /// Adds the value with the given key to the sample if its key is among
+    /// the `k` smallest, only computing the value in that case
+    fn offer(
+        &mut self,
+        key: f64,
+        value: impl FnOnce() -> Result<ScalarValue>,
+    ) -> Result<()> {
+        if self.sample.len() < self.k {
+            self.sample.push(SampledValue {
+                key,
+                value: value()?,
+            });
+        } else if let Some(mut largest) = self.sample.peek_mut()
+            && key < largest.key
+        {
+            *largest = SampledValue {
+                key,
+                value: value()?,
+            };
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ReservoirSampleAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = &values[0];
+        let mut rng = rng();
+        for i in 0..values.len() {
+            if values.is_valid(i) {
+                self.offer(rng.random(), || ScalarValue::try_from_array(values, i))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let values = as_list_array(&states[0])?;
+        let keys = as_list_array(&states[1])?;
+        for (values, keys) in values.iter().zip(keys.iter()) {
+            let (Some(values), Some(keys)) = (values, keys) else {
+                continue;
+            };
+            for (i, key) in as_float64_array(&keys)?.values().iter().enumerate() {
+                self.offer(*key, || ScalarValue::try_from_array(&values, i))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let (keys, values): (Vec<_>, Vec<_>) = self
+            .sample
+            .iter()
+            .map(|sampled| {
+                (
+                    ScalarValue::Float64(Some(sampled.key)),
+                    sampled.value.clone(),
+                )
+            })
+            .unzip();
+        Ok(vec![
+            ScalarValue::List(ScalarValue::new_list_nullable(&values, &self.data_type)),
+            ScalarValue::List(ScalarValue::new_list_nullable(&keys, &DataType::Float64)),
+        ])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        if self.sample.is_empty() {
+            return Ok(ScalarValue::new_null_list(self.data_type.clone(), true, 1));
+        }
+        let values = self
+            .sample
+            .iter()
+            .map(|sampled| sampled.value.clone())
+            .collect::<Vec<_>>();
+        Ok(ScalarValue::List(ScalarValue::new_list_nullable(
+            &values,
+            &self.data_type,
+        )))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self.sample.capacity() * size_of::<SampledValue>()
+            + self
+                .sample
+                .iter()
+                .map(|sampled| sampled.value.size() - size_of_val(&sampled.value))
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int64Array;
+
+    fn sampled_values(acc: &mut ReservoirSampleAccumulator) -> Vec<i64> {
+        let ScalarValue::List(list) = acc.evaluate().unwrap() else {
+            unreachable!()
+        };
+        let mut values = ScalarValue::convert_array_to_scalar_vec(list.as_ref())
+            .unwrap()
+            .remove(0)
+            .unwrap()
+            .into_iter()
+            .map(|value| match value {
+                ScalarValue::Int64(Some(value)) => value,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        values
+    }
+
+    #[test]
+    fn sample_of_k_values() -> Result<()> {
+        let mut acc = ReservoirSampleAccumulator::new(3, DataType::Int64);
+        let values: ArrayRef = Arc::new(Int64Array::from_iter_values(0..100));
+        acc.update_batch(&[values])?;
+
+        let sample = sampled_values(&mut acc);
+        assert_eq!(sample.len(), 3);
+        assert!(sample.windows(2).all(|w| w[0] != w[1]));
+        assert!(sample.iter().all(|value| (0..100).contains(value)));
+        Ok(())
+    }
+
+    #[test]
+    fn sample_of_fewer_than_k_values() -> Result<()> {
+        let mut acc = ReservoirSampleAccumulator::new(5, DataType::Int64);
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(2)]));
+        acc.update_batch(&[values])?;
+        assert_eq!(sampled_values(&mut acc), vec![1, 2]);
+
+        let mut empty = ReservoirSampleAccumulator::new(5, DataType::Int64);
+        assert!(empty.evaluate()?.is_null());
+        Ok(())
+    }
+
+    #[test]
+    fn merge_keeps_smallest_keys() -> Result<()> {
+        let mut acc1 = ReservoirSampleAccumulator::new(2, DataType::Int64);
+        acc1.offer(0.1, || Ok(ScalarValue::Int64(Some(1))))?;
+        acc1.offer(0.7, || Ok(ScalarValue::Int64(Some(2))))?;
+        let mut acc2 = ReservoirSampleAccumulator::new(2, DataType::Int64);
+        acc2.offer(0.3, || Ok(ScalarValue::Int64(Some(3))))?;
+        acc2.offer(0.9, || Ok(ScalarValue::Int64(Some(4))))?;
+
+        let states = acc2
+            .state()?
+            .into_iter()
+            .map(|state| state.to_array())
+            .collect::<Result<Vec<_>>>()?;
+        acc1.merge_batch(&states)?;
+        assert_eq!(sampled_values(&mut acc1), vec![1, 3]);
+        Ok(())
+    }
+}
@@ -26,17 +26,21 @@ use arrow::array::Float64Array;
 use arrow::datatypes::FieldRef;
 use arrow::{array::ArrayRef, datatypes::DataType, datatypes::Field};
 use datafusion_common::ScalarValue;
+use datafusion_common::config::{ConfigOptions, FloatSummation};
 use datafusion_common::{Result, internal_err, not_impl_err};
 use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
 use datafusion_expr::utils::format_state_name;
 use datafusion_expr::{
-    Accumulator, AggregateUDFImpl, Documentation, GroupsAccumulator, Signature,
-    Volatility,
+    Accumulator, AggregateUDF, AggregateUDFImpl, Documentation, GroupsAccumulator,
+    Signature, Volatility,
 };
 use datafusion_functions_aggregate_common::stats::StatsType;
 use datafusion_macros::user_doc;
 
-use crate::variance::{VarianceAccumulator, VarianceGroupsAccumulator};
+use crate::variance::{
+    ExactVarianceAccumulator, VarianceAccumulator, VarianceGroupsAccumulator,
+    exact_variance_state_fields,
+};
 
 make_udaf_expr_and_func!(
     Stddev,
@@ -65,6 +69,8 @@ make_udaf_expr_and_func!(
 pub struct Stddev {
     signature: Signature,
     alias: Vec<String>,
+    /// How the values are added up
+    float_summation: FloatSummation,
 }
 
 impl Default for Stddev {
@@ -79,6 +85,7 @@ impl Stddev {
         Self {
             signature: Signature::exact(vec![DataType::Float64], Volatility::Immutable),
             alias: vec!["stddev_samp".to_string()],
+            float_summation: FloatSummation::Standard,
         }
     }
 }
@@ -97,6 +104,9 @@ impl AggregateUDFImpl for Stddev {
     }
 
     fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        if self.float_summation != FloatSummation::Standard {
+            return Ok(exact_variance_state_fields(args.name));
+        }
         Ok(vec![
             Field::new(
                 format_state_name(args.name, "count"),
@@ -119,6 +129,9 @@ impl AggregateUDFImpl for Stddev {
         if acc_args.is_distinct {
             return not_impl_err!("STDDEV_POP(DISTINCT) aggregations are not available");
         }
+        if self.float_summation != FloatSummation::Standard {
+            return Ok(Box::new(ExactStddevAccumulator::new(StatsType::Sample)));
+        }
         Ok(Box::new(StddevAccumulator::try_new(StatsType::Sample)?))
     }
 
@@ -126,8 +139,19 @@ impl AggregateUDFImpl for Stddev {
         &self.alias
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        let float_summation = config.execution.float_summation;
+        (float_summation != self.float_summation).then(|| {
+            AggregateUDF::from(Self {
+                signature: self.signature.clone(),
+                alias: self.alias.clone(),
+                float_summation,
+            })
+        })
+    }
+
     fn groups_accumulator_supported(&self, acc_args: AccumulatorArgs) -> bool {
-        !acc_args.is_distinct
+        !acc_args.is_distinct && self.float_summation == FloatSummation::Standard
     }
 
     fn create_groups_accumulator(
@@ -168,6 +192,8 @@ make_udaf_expr_and_func!(
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub struct StddevPop {
     signature: Signature,
+    /// How the values are added up
+    float_summation: FloatSummation,
 }
 
 impl Default for StddevPop {
@@ -181,6 +207,7 @@ impl StddevPop {
     pub fn new() -> Self {
         Self {
             signature: Signature::exact(vec![DataType::Float64], Volatility::Immutable),
+            float_summation: FloatSummation::Standard,
         }
     }
 }
@@ -195,6 +222,9 @@ impl AggregateUDFImpl for StddevPop {
     }
 
     fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
+        if self.float_summation != FloatSummation::Standard {
+            return Ok(exact_variance_state_fields(args.name));
+        }
         Ok(vec![
             Field::new(
                 format_state_name(args.name, "count"),
@@ -217,6 +247,9 @@ impl AggregateUDFImpl for StddevPop {
         if acc_args.is_distinct {
             return not_impl_err!("STDDEV_POP(DISTINCT) aggregations are not available");
         }
+        if self.float_summation != FloatSummation::Standard {
+            return Ok(Box::new(ExactStddevAccumulator::new(StatsType::Population)));
+        }
         Ok(Box::new(StddevAccumulator::try_new(StatsType::Population)?))
     }
 
@@ -225,7 +258,7 @@ impl AggregateUDFImpl for StddevPop {
     }
 
     fn groups_accumulator_supported(&self, acc_args: AccumulatorArgs) -> bool {
-        !acc_args.is_distinct
+        !acc_args.is_distinct && self.float_summation == FloatSummation::Standard
     }
 
     fn create_groups_accumulator(
@@ -237,6 +270,16 @@ impl AggregateUDFImpl for StddevPop {
         )))
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        let float_summation = config.execution.float_summation;
+        (float_summation != self.float_summation).then(|| {
+            AggregateUDF::from(Self {
+                signature: self.signature.clone(),
+                float_summation,
+            })
+        })
+    }
+
     fn documentation(&self) -> Option<&Documentation> {
         self.doc()
     }
@@ -300,6 +343,56 @@ impl Accumulator for StddevAccumulator {
     }
 }
 
+/// An accumulator to compute the standard deviation from an
+/// [`ExactVarianceAccumulator`]
+#[derive(Debug)]
+struct ExactStddevAccumulator {
+    variance: ExactVarianceAccumulator,
+}
+
+impl ExactStddevAccumulator {
+    fn new(s_type: StatsType) -> Self {
+        Self {
+            variance: ExactVarianceAccumulator::new(s_type),
+        }
+    }
+}
+
+impl Accumulator for ExactStddevAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        self.variance.state()
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.variance.update_batch(values)
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.variance.retract_batch(values)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.variance.merge_batch(states)
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        match self.variance.evaluate()? {
+            ScalarValue::Float64(variance) => {
+                Ok(ScalarValue::Float64(variance.map(f64::sqrt)))
+            }
+            _ => internal_err!("Variance should be f64"),
+        }
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) - size_of_val(&self.variance) + self.variance.size()
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Debug)]
 pub struct StddevGroupsAccumulator {
     variance: VarianceGroupsAccumulator,
@@ -26,6 +26,7 @@ use arrow::datatypes::{
     DurationMillisecondType, DurationNanosecondType, DurationSecondType, FieldRef,
     Float64Type, Int64Type, TimeUnit, UInt64Type,
 };
+use datafusion_common::config::{ConfigOptions, FloatSummation};
 use datafusion_common::hash_utils::RandomState;
 use datafusion_common::internal_err;
 use datafusion_common::types::{
@@ -38,12 +39,13 @@ use datafusion_expr::expr_fn::cast;
 use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
 use datafusion_expr::utils::{AggregateOrderSensitivity, format_state_name};
 use datafusion_expr::{
-    Accumulator, AggregateUDFImpl, Coercion, Documentation, Expr, GroupsAccumulator,
-    Operator, ReversedUDAF, SetMonotonicity, Signature, TypeSignature,
+    Accumulator, AggregateUDF, AggregateUDFImpl, Coercion, Documentation, Expr,
+    GroupsAccumulator, Operator, ReversedUDAF, SetMonotonicity, Signature, TypeSignature,
     TypeSignatureClass, Volatility,
 };
 use datafusion_functions_aggregate_common::aggregate::groups_accumulator::prim_op::PrimitiveGroupsAccumulator;
 use datafusion_functions_aggregate_common::aggregate::sum_distinct::DistinctSumAccumulator;
+use datafusion_functions_aggregate_common::float_sum::FloatSum;
 use datafusion_macros::user_doc;
 use std::mem::size_of_val;
 
@@ -145,6 +147,8 @@ macro_rules! downcast_sum {
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Sum {
     signature: Signature,
+    /// How `Float64` values are added up
+    float_summation: FloatSummation,
 }
 
 impl Sum {
@@ -189,8 +193,17 @@ impl Sum {
                 ],
                 Volatility::Immutable,
             ),
+            float_summation: FloatSummation::Standard,
         }
     }
+
+    /// Returns how the values of `data_type` are added up, if they are
+    /// floating point values that are not added up one after the other
+    fn float_summation(&self, data_type: &DataType) -> Option<FloatSummation> {
+        (data_type == &DataType::Float64
+            && self.float_summation != FloatSummation::Standard)
+            .then_some(self.float_summation)
+    }
 }
 
 impl Default for Sum {
@@ -239,6 +252,11 @@ impl AggregateUDFImpl for Sum {
     }
 
     fn accumulator(&self, args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if !args.is_distinct
+            && let Some(float_summation) = self.float_summation(args.return_type())
+        {
+            return Ok(Box::new(FloatSumAccumulator::new(float_summation)));
+        }
         if args.is_distinct {
             macro_rules! helper {
                 ($t:ty, $dt:expr) => {
@@ -267,6 +285,13 @@ impl AggregateUDFImpl for Sum {
                 )
                 .into(),
             ])
+        } else if let Some(float_summation) = self.float_summation(args.return_type()) {
+            Ok(FloatSum::state_types(float_summation)
+                .into_iter()
+                .map(|(name, data_type)| {
+                    Field::new(format_state_name(args.name, name), data_type, true).into()
+                })
+                .collect())
         } else {
             Ok(vec![
                 Field::new(
@@ -280,7 +305,7 @@ impl AggregateUDFImpl for Sum {
     }
 
     fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
-        !args.is_distinct
+        !args.is_distinct && self.float_summation(args.return_type()).is_none()
     }
 
     fn create_groups_accumulator(
@@ -310,6 +335,8 @@ impl AggregateUDFImpl for Sum {
                 };
             }
             downcast_sum!(args, helper_distinct)
+        } else if let Some(float_summation) = self.float_summation(args.return_type()) {
+            Ok(Box::new(FloatSumAccumulator::new(float_summation)))
         } else {
             // non‐distinct path: existing sliding sum
             macro_rules! helper {
@@ -321,6 +348,16 @@ impl AggregateUDFImpl for Sum {
         }
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        let float_summation = config.execution.float_summation;
+        (float_summation != self.float_summation).then(|| {
+            AggregateUDF::from(Self {
+                signature: self.signature.clone(),
+                float_summation,
+            })
+        })
+    }
+
     fn reverse_expr(&self) -> ReversedUDAF {
         ReversedUDAF::Identical
     }
@@ -435,6 +472,67 @@ impl<T: ArrowNumericType> Accumulator for SumAccumulator<T> {
     }
 }
 
+/// This accumulator computes SUM of `Float64` values with a [`FloatSummation`]
+/// other than the standard one, over groups or sliding windows
+#[derive(Debug)]
+struct FloatSumAccumulator {
+    sum: FloatSum,
+    /// The number of values added up, or of non empty states merged
+    count: u64,
+}
+
+impl FloatSumAccumulator {
+    fn new(float_summation: FloatSummation) -> Self {
+        Self {
+            sum: FloatSum::new(float_summation),
+            count: 0,
+        }
+    }
+}
+
+impl Accumulator for FloatSumAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(self.sum.state(self.count == 0))
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<Float64Type>();
+        self.count += (values.len() - values.null_count()) as u64;
+        self.sum.update(values);
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        for row in 0..states[0].len() {
+            if self.sum.merge(states, row) {
+                self.count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(
+            (self.count != 0).then(|| self.sum.sum()),
+        ))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) - size_of_val(&self.sum) + self.sum.size()
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<Float64Type>();
+        self.count -= (values.len() - values.null_count()) as u64;
+        self.sum.retract(values);
+        Ok(())
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+}
+
 /// This accumulator incrementally computes sums over a sliding window
 ///
 /// This is separate from [`SumAccumulator`] as requires additional state
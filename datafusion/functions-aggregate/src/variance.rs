@@ -20,18 +20,20 @@
 
 use arrow::datatypes::{FieldRef, Float64Type};
 use arrow::{
-    array::{Array, ArrayRef, BooleanArray, Float64Array, UInt64Array},
+    array::{Array, ArrayRef, AsArray, BooleanArray, Float64Array, UInt64Array},
     buffer::NullBuffer,
     datatypes::{DataType, Field},
 };
 use datafusion_common::cast::{as_float64_array, as_uint64_array};
+use datafusion_common::config::{ConfigOptions, FloatSummation};
 use datafusion_common::{Result, ScalarValue};
 use datafusion_expr::{
-    Accumulator, AggregateUDFImpl, Documentation, GroupsAccumulator, Signature,
-    Volatility,
+    Accumulator, AggregateUDF, AggregateUDFImpl, Documentation, GroupsAccumulator,
+    Signature, Volatility,
     function::{AccumulatorArgs, StateFieldsArgs},
     utils::format_state_name,
 };
+use datafusion_functions_aggregate_common::float_sum::{ExactSum, exact_m2};
 use datafusion_functions_aggregate_common::utils::GenericDistinctBuffer;
 use datafusion_functions_aggregate_common::{
     aggregate::groups_accumulator::accumulate::accumulate, stats::StatsType,
@@ -66,6 +68,8 @@ make_udaf_expr_and_func!(
 pub struct VarianceSample {
     signature: Signature,
     aliases: Vec<String>,
+    /// How the values are added up
+    float_summation: FloatSummation,
 }
 
 impl Default for VarianceSample {
@@ -79,6 +83,7 @@ impl VarianceSample {
         Self {
             aliases: vec![String::from("var_sample"), String::from("var_samp")],
             signature: Signature::exact(vec![DataType::Float64], Volatility::Immutable),
+            float_summation: FloatSummation::Standard,
         }
     }
 }
@@ -99,6 +104,9 @@ impl AggregateUDFImpl for VarianceSample {
     fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
         let name = args.name;
         match args.is_distinct {
+            false if self.float_summation != FloatSummation::Standard => {
+                Ok(exact_variance_state_fields(name))
+            }
             false => Ok(vec![
                 Field::new(format_state_name(name, "count"), DataType::UInt64, true),
                 Field::new(format_state_name(name, "mean"), DataType::Float64, true),
@@ -128,6 +136,9 @@ impl AggregateUDFImpl for VarianceSample {
                 StatsType::Sample,
             )));
         }
+        if self.float_summation != FloatSummation::Standard {
+            return Ok(Box::new(ExactVarianceAccumulator::new(StatsType::Sample)));
+        }
 
         Ok(Box::new(VarianceAccumulator::try_new(StatsType::Sample)?))
     }
@@ -136,8 +147,19 @@ impl AggregateUDFImpl for VarianceSample {
         &self.aliases
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        let float_summation = config.execution.float_summation;
+        (float_summation != self.float_summation).then(|| {
+            AggregateUDF::from(Self {
+                signature: self.signature.clone(),
+                aliases: self.aliases.clone(),
+                float_summation,
+            })
+        })
+    }
+
     fn groups_accumulator_supported(&self, acc_args: AccumulatorArgs) -> bool {
-        !acc_args.is_distinct
+        !acc_args.is_distinct && self.float_summation == FloatSummation::Standard
     }
 
     fn create_groups_accumulator(
@@ -162,6 +184,8 @@ impl AggregateUDFImpl for VarianceSample {
 pub struct VariancePopulation {
     signature: Signature,
     aliases: Vec<String>,
+    /// How the values are added up
+    float_summation: FloatSummation,
 }
 
 impl Default for VariancePopulation {
@@ -175,6 +199,7 @@ impl VariancePopulation {
         Self {
             aliases: vec![String::from("var_population")],
             signature: Signature::exact(vec![DataType::Float64], Volatility::Immutable),
+            float_summation: FloatSummation::Standard,
         }
     }
 }
@@ -194,6 +219,9 @@ impl AggregateUDFImpl for VariancePopulation {
 
     fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<FieldRef>> {
         match args.is_distinct {
+            false if self.float_summation != FloatSummation::Standard => {
+                Ok(exact_variance_state_fields(args.name))
+            }
             false => {
                 let name = args.name;
                 Ok(vec![
@@ -226,6 +254,11 @@ impl AggregateUDFImpl for VariancePopulation {
                 StatsType::Population,
             )));
         }
+        if self.float_summation != FloatSummation::Standard {
+            return Ok(Box::new(ExactVarianceAccumulator::new(
+                StatsType::Population,
+            )));
+        }
 
         Ok(Box::new(VarianceAccumulator::try_new(
             StatsType::Population,
@@ -236,8 +269,19 @@ impl AggregateUDFImpl for VariancePopulation {
         &self.aliases
     }
 
+    fn with_updated_config(&self, config: &ConfigOptions) -> Option<AggregateUDF> {
+        let float_summation = config.execution.float_summation;
+        (float_summation != self.float_summation).then(|| {
+            AggregateUDF::from(Self {
+                signature: self.signature.clone(),
+                aliases: self.aliases.clone(),
+                float_summation,
+            })
+        })
+    }
+
     fn groups_accumulator_supported(&self, acc_args: AccumulatorArgs) -> bool {
-        !acc_args.is_distinct
+        !acc_args.is_distinct && self.float_summation == FloatSummation::Standard
     }
 
     fn create_groups_accumulator(
@@ -418,6 +462,128 @@ impl Accumulator for VarianceAccumulator {
     }
 }
 
+/// Returns the state fields of an [`ExactVarianceAccumulator`]
+pub(crate) fn exact_variance_state_fields(name: &str) -> Vec<FieldRef> {
+    let partials =
+        DataType::List(Arc::new(Field::new_list_field(DataType::Float64, true)));
+    vec![
+        Field::new(format_state_name(name, "count"), DataType::UInt64, true),
+        Field::new(
+            format_state_name(name, "sum_partials"),
+            partials.clone(),
+            true,
+        ),
+        Field::new(
+            format_state_name(name, "sum_squares_partials"),
+            partials,
+            true,
+        ),
+    ]
+    .into_iter()
+    .map(Arc::new)
+    .collect()
+}
+
+/// An accumulator to compute variance from the exact sum and the exact sum of
+/// squares of the values, used for any [`FloatSummation`] other than the
+/// standard one.
+///
+/// Unlike [`VarianceAccumulator`], its result does not depend on the order in
+/// which the values are added or the states are merged.
+#[derive(Debug)]
+pub struct ExactVarianceAccumulator {
+    count: u64,
+    sum: ExactSum,
+    sum_squares: ExactSum,
+    stats_type: StatsType,
+}
+
+impl ExactVarianceAccumulator {
+    /// Creates a new `ExactVarianceAccumulator`
+    pub fn new(stats_type: StatsType) -> Self {
+        Self {
+            count: 0,
+            sum: ExactSum::default(),
+            sum_squares: ExactSum::default(),
+            stats_type,
+        }
+    }
+}
+
+impl Accumulator for ExactVarianceAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![
+            ScalarValue::from(self.count),
+            self.sum.to_list(),
+            self.sum_squares.to_list(),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let arr = as_float64_array(&values[0])?;
+        for value in arr.iter().flatten() {
+            self.count += 1;
+            self.sum.add(value);
+            self.sum_squares.add_square(value);
+        }
+        Ok(())
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let arr = as_float64_array(&values[0])?;
+        for value in arr.iter().flatten() {
+            self.count -= 1;
+            self.sum.add(-value);
+            self.sum_squares.sub_square(value);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let counts = as_uint64_array(&states[0])?;
+        let sums = states[1].as_list::<i32>();
+        let sums_squares = states[2].as_list::<i32>();
+
+        for i in 0..counts.len() {
+            let c = counts.value(i);
+            if counts.is_null(i) || c == 0 {
+                continue;
+            }
+            self.count += c;
+            self.sum.update(sums.value(i).as_primitive::<Float64Type>());
+            self.sum_squares
+                .update(sums_squares.value(i).as_primitive::<Float64Type>());
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let count = match self.stats_type {
+            StatsType::Population => self.count,
+            StatsType::Sample => self.count.saturating_sub(1),
+        };
+
+        Ok(ScalarValue::Float64(match self.count {
+            0 => None,
+            1 => match self.stats_type {
+                StatsType::Population => Some(0.0),
+                StatsType::Sample => None,
+            },
+            _ => Some(exact_m2(self.count, &self.sum, &self.sum_squares) / count as f64),
+        }))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) - size_of_val(&self.sum) - size_of_val(&self.sum_squares)
+            + self.sum.size()
+            + self.sum_squares.size()
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Debug)]
 pub struct VarianceGroupsAccumulator {
     m2s: Vec<f64>,
@@ -0,0 +1,207 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for from_json function.
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, new_empty_array};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use arrow::json::ReaderBuilder;
+use datafusion_common::types::logical_string;
+use datafusion_common::utils::take_function_args;
+use datafusion_common::{Result, ScalarValue, exec_datafusion_err, exec_err};
+use datafusion_expr::{
+    Coercion, ColumnarValue, Documentation, ReturnFieldArgs, ScalarFunctionArgs,
+    ScalarUDFImpl, Signature, TypeSignatureClass, Volatility,
+};
+use datafusion_macros::user_doc;
+
+use crate::path::parse_json;
+use crate::utils::StringValues;
+
+make_udf_expr_and_func!(
+    FromJson,
+    from_json,
+    json data_type,
+    "parses a JSON document into a value of the given type.",
+    from_json_udf
+);
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = "Parses a JSON document into a value of the given Arrow type, for example a struct from an object or an array from an array. Object members that are not fields of the struct are ignored, and missing fields are `NULL`. Casting a string to a struct or an array type calls `from_json`.",
+    syntax_example = "from_json(json, 'type')",
+    sql_example = r#"```sql
+> select from_json('{"a": 1, "b": ["x", "y"]}', 'Struct("a": Int64, "b": List(Utf8))');
++------------------------------------------------------------------------------------------+
+| from_json(Utf8("{"a": 1, "b": ["x", "y"]}"),Utf8("Struct("a": Int64, "b": List(Utf8))")) |
++------------------------------------------------------------------------------------------+
+| {a: 1, b: [x, y]}                                                                        |
++------------------------------------------------------------------------------------------+
+> select cast('[1, 2, null]' as int[]);
++----------------------+
+| Utf8("[1, 2, null]") |
++----------------------+
+| [1, 2, NULL]         |
++----------------------+
+```"#,
+    argument(
+        name = "json",
+        description = "String expression containing a JSON document."
+    ),
+    argument(
+        name = "type",
+        description = "[Arrow Type](https://docs.rs/arrow/latest/arrow/datatypes/enum.DataType.html) name to parse the document into, as accepted by `arrow_cast`."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct FromJson {
+    signature: Signature,
+}
+
+impl Default for FromJson {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromJson {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![
+                    Coercion::new_exact(TypeSignatureClass::Native(logical_string())),
+                    Coercion::new_exact(TypeSignatureClass::Native(logical_string())),
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for FromJson {
+    fn name(&self) -> &str {
+        "from_json"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        datafusion_common::internal_err!(
+            "return_field_from_args should be called instead"
+        )
+    }
+
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let [_, type_arg] = take_function_args(self.name(), args.scalar_arguments)?;
+        let Some(type_name) = type_arg.and_then(|arg| arg.try_as_str().flatten()) else {
+            return exec_err!(
+                "{} requires its second argument to be a constant type name",
+                self.name()
+            );
+        };
+        let data_type = type_name
+            .parse::<DataType>()
+            .map_err(|e| exec_datafusion_err!("{e}"))?;
+        Ok(Arc::new(Field::new(self.name(), data_type, true)))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let data_type = args.return_field.data_type().clone();
+        let [json, _] = take_function_args(self.name(), args.args)?;
+        match json {
+            ColumnarValue::Array(array) => {
+                Ok(ColumnarValue::Array(from_json_array(&array, data_type)?))
+            }
+            ColumnarValue::Scalar(scalar) => {
+                let array = from_json_array(&scalar.to_array()?, data_type)?;
+                Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                    &array, 0,
+                )?))
+            }
+        }
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// Parses each JSON document of `array` into a value of type `data_type`
+pub(crate) fn from_json_array(array: &ArrayRef, data_type: DataType) -> Result<ArrayRef> {
+    if array.is_empty() {
+        return Ok(new_empty_array(&data_type));
+    }
+    let jsons = StringValues::try_new("from_json", array.as_ref())?;
+    let field = Arc::new(Field::new("value", data_type, true));
+    let mut decoder = ReaderBuilder::new_with_field(field)
+        .with_batch_size(array.len())
+        .build_decoder()?;
+    for i in 0..array.len() {
+        // Check each document on its own, as the decoder reads a stream of
+        // documents and would otherwise merge an incomplete document with the
+        // next one
+        let json = match jsons.value(i) {
+            Some(json) => parse_json(json)?.get(),
+            None => "null",
+        };
+        decoder.decode(json.as_bytes())?;
+        decoder.decode(b"\n")?;
+    }
+    match decoder.flush()? {
+        Some(batch) => Ok(Arc::clone(batch.column(0))),
+        None => exec_err!("from_json decoded no rows"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{Array, AsArray, StringArray};
+    use arrow::datatypes::Int64Type;
+
+    #[test]
+    fn test_from_json_array() -> Result<()> {
+        let data_type: DataType = r#"Struct("a": Int64, "b": List(Utf8))"#.parse()?;
+        let array: ArrayRef = Arc::new(StringArray::from(vec![
+            Some(r#"{"a": 1, "b": ["x"], "c": 3}"#),
+            None,
+            Some(r#"{"b": null}"#),
+        ]));
+        let result = from_json_array(&array, data_type.clone())?;
+        assert_eq!(result.data_type(), &data_type);
+        let result = result.as_struct();
+        assert!(result.is_null(1));
+        let a = result.column(0).as_primitive::<Int64Type>();
+        assert_eq!(a.value(0), 1);
+        assert!(a.is_null(2));
+        assert_eq!(result.column(1).as_list::<i32>().value(0).len(), 1);
+
+        let array: ArrayRef = Arc::new(StringArray::from(vec!["[1,", "2]"]));
+        assert_eq!(
+            from_json_array(&array, data_type)
+                .unwrap_err()
+                .strip_backtrace(),
+            "Execution error: Invalid JSON: EOF while parsing a value at line 1 column 3"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`TableFunctionImpl`] definition for the json_array_elements table function.
+
+use std::sync::Arc;
+
+use arrow::array::{RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion_catalog::{MemTable, TableFunctionArgs, TableFunctionImpl, TableProvider};
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{Result, ScalarValue, exec_err, plan_err};
+use datafusion_expr::Expr;
+use serde_json::value::RawValue;
+
+use crate::path::{JsonKind, invalid_json, json_kind, parse_json};
+
+/// `json_array_elements(json)` returns a row for each element of a JSON
+/// array, with the JSON text of the element in the `value` column.
+///
+/// The function can be called with a constant, or laterally with a column,
+/// such as `SELECT value FROM logs, LATERAL json_array_elements(logs.tags)`.
+/// A `NULL` document has no elements.
+#[derive(Debug, Default)]
+pub struct JsonArrayElementsFunc {}
+
+impl JsonArrayElementsFunc {
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new(
+            "value",
+            DataType::Utf8,
+            false,
+        )]))
+    }
+
+    /// Returns the elements of the JSON array `json`
+    fn elements(json: &ScalarValue) -> Result<Vec<RecordBatch>> {
+        if json.is_null() {
+            return Ok(vec![]);
+        }
+        let Some(json) = json.try_as_str() else {
+            return plan_err!(
+                "json_array_elements expects a string argument, got {}",
+                json.data_type()
+            );
+        };
+        let Some(json) = json else {
+            return Ok(vec![]);
+        };
+        let value = parse_json(json)?;
+        if json_kind(value) != JsonKind::Array {
+            return exec_err!("json_array_elements expects a JSON array, got {json}");
+        }
+        let elements: Vec<&RawValue> =
+            serde_json::from_str(value.get()).map_err(|e| invalid_json(&e))?;
+        let elements = StringArray::from_iter_values(elements.iter().map(|e| e.get()));
+        Ok(vec![RecordBatch::try_new(
+            Self::schema(),
+            vec![Arc::new(elements)],
+        )?])
+    }
+}
+
+impl TableFunctionImpl for JsonArrayElementsFunc {
+    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
+        let [arg] = args.exprs() else {
+            return plan_err!("json_array_elements requires 1 argument");
+        };
+        let Expr::Literal(json, _) = arg else {
+            return plan_err!(
+                "json_array_elements requires a constant argument, use a LATERAL call for columns"
+            );
+        };
+        let batches = Self::elements(json)?;
+        Ok(Arc::new(MemTable::try_new(Self::schema(), vec![batches])?))
+    }
+
+    fn lateral_schema(&self, arg_types: &[DataType]) -> Result<Option<SchemaRef>> {
+        match arg_types {
+            [
+                DataType::Utf8
+                | DataType::LargeUtf8
+                | DataType::Utf8View
+                | DataType::Null,
+            ] => Ok(Some(Self::schema())),
+            [arg_type] => {
+                plan_err!("json_array_elements expects a string argument, got {arg_type}")
+            }
+            _ => plan_err!("json_array_elements requires 1 argument"),
+        }
+    }
+
+    fn call_lateral(
+        &self,
+        args: &[ScalarValue],
+        _config: &ConfigOptions,
+    ) -> Result<Vec<RecordBatch>> {
+        match args {
+            [ScalarValue::Null] => Ok(vec![]),
+            [json] => Self::elements(json),
+            _ => plan_err!("json_array_elements requires 1 argument"),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for json_extract function.
+
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_common::types::logical_string;
+use datafusion_expr::{
+    Coercion, ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    TypeSignatureClass, Volatility,
+};
+use datafusion_macros::user_doc;
+
+use crate::utils::invoke_json_path;
+
+make_udf_expr_and_func!(
+    JsonExtract,
+    json_extract,
+    json path,
+    "returns the JSON text of the value at a JSON path in a JSON document.",
+    json_extract_udf
+);
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = "Returns the JSON text of the value at a JSON path in a JSON document, or `NULL` if the document does not contain the path. Strings are returned quoted, use `json_value` to get their value. The `->` operator selects a member or element with `json_extract`.",
+    syntax_example = "json_extract(json, path)",
+    sql_example = r#"```sql
+> select json_extract('{"a": {"b": [1, "x"]}}', '$.a.b');
++------------------------------------------------------------+
+| json_extract(Utf8("{"a": {"b": [1, "x"]}}"),Utf8("$.a.b")) |
++------------------------------------------------------------+
+| [1, "x"]                                                   |
++------------------------------------------------------------+
+> select '{"a": {"b": [1, "x"]}}' -> 'a' -> 'b' -> 1;
++----------------------------------------------------------------------+
+| Utf8("{"a": {"b": [1, "x"]}}") -> Utf8("a") -> Utf8("b") -> Int64(1) |
++----------------------------------------------------------------------+
+| "x"                                                                  |
++----------------------------------------------------------------------+
+```"#,
+    argument(
+        name = "json",
+        description = "String expression containing a JSON document."
+    ),
+    argument(
+        name = "path",
+        description = "JSON path expression selecting the value, such as `$.a.b[0]`."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct JsonExtract {
+    signature: Signature,
+}
+
+impl Default for JsonExtract {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonExtract {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![
+                    Coercion::new_exact(TypeSignatureClass::Native(logical_string())),
+                    Coercion::new_exact(TypeSignatureClass::Native(logical_string())),
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonExtract {
+    fn name(&self) -> &str {
+        "json_extract"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        invoke_json_path(self.name(), &args.args, |value| {
+            Ok(Some(value.get().to_string()))
+        })
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
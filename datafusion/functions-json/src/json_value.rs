@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for json_value function.
+
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_common::types::logical_string;
+use datafusion_expr::{
+    Coercion, ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    TypeSignatureClass, Volatility,
+};
+use datafusion_macros::user_doc;
+
+use crate::path::scalar_text;
+use crate::utils::invoke_json_path;
+
+make_udf_expr_and_func!(
+    JsonValue,
+    json_value,
+    json path,
+    "returns the text of the scalar value at a JSON path in a JSON document.",
+    json_value_udf
+);
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = "Returns the text of the scalar value at a JSON path in a JSON document. Strings are returned unquoted, and numbers and booleans as they are written in the document. Returns `NULL` if the document does not contain the path or the value is `null`, an object or an array. The `->>` operator selects a member or element with `json_value`.",
+    syntax_example = "json_value(json, path)",
+    sql_example = r#"```sql
+> select json_value('{"a": {"b": [1, "x"]}}', '$.a.b[1]');
++-------------------------------------------------------------+
+| json_value(Utf8("{"a": {"b": [1, "x"]}}"),Utf8("$.a.b[1]")) |
++-------------------------------------------------------------+
+| x                                                           |
++-------------------------------------------------------------+
+> select '{"a": {"b": [1, "x"]}}' -> 'a' ->> 'b';
++-----------------------------------------------------------+
+| Utf8("{"a": {"b": [1, "x"]}}") -> Utf8("a") ->> Utf8("b") |
++-----------------------------------------------------------+
+| NULL                                                      |
++-----------------------------------------------------------+
+```"#,
+    argument(
+        name = "json",
+        description = "String expression containing a JSON document."
+    ),
+    argument(
+        name = "path",
+        description = "JSON path expression selecting the value, such as `$.a.b[0]`."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct JsonValue {
+    signature: Signature,
+}
+
+impl Default for JsonValue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonValue {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![
+                    Coercion::new_exact(TypeSignatureClass::Native(logical_string())),
+                    Coercion::new_exact(TypeSignatureClass::Native(logical_string())),
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonValue {
+    fn name(&self) -> &str {
+        "json_value"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        invoke_json_path(self.name(), &args.args, scalar_text)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
@@ -0,0 +1,151 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/apache/datafusion/19fe44cf2f30cbdd63d4a4f52c74055163c6cc38/docs/logos/standalone_logo/logo_original.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/apache/datafusion/19fe44cf2f30cbdd63d4a4f52c74055163c6cc38/docs/logos/standalone_logo/logo_original.svg"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+// Make sure fast / cheap clones on Arc are explicit:
+// https://github.com/apache/datafusion/issues/11143
+#![deny(clippy::clone_on_ref_ptr)]
+#![cfg_attr(test, allow(clippy::needless_pass_by_value))]
+
+//! JSON Functions for [DataFusion].
+//!
+//! This crate contains functions for working with JSON documents stored as
+//! strings, implemented using the extension API:
+//!
+//! * scalar functions such as `json_extract` and `json_value`, selecting
+//!   values with [JSON path expressions](path::JsonPath)
+//! * the `->` and `->>` operators and casts between JSON strings and
+//!   `Struct` and `List` types, rewritten to function calls by
+//!   [`rewrite::JsonFunctionRewrite`]
+//! * the `json_array_elements` table function
+//!
+//! [DataFusion]: https://crates.io/crates/datafusion
+//!
+//! You can register the functions in this crate using the [`register_all`] function.
+
+#[macro_use]
+pub mod macros;
+
+pub mod from_json;
+pub mod json_array_elements;
+pub mod json_extract;
+pub mod json_value;
+pub mod path;
+pub mod rewrite;
+pub mod to_json;
+mod utils;
+
+use datafusion_catalog::TableFunction;
+use datafusion_common::Result;
+use datafusion_execution::FunctionRegistry;
+use datafusion_expr::ScalarUDF;
+use datafusion_expr::expr_rewriter::FunctionRewrite;
+use log::debug;
+use std::sync::Arc;
+
+/// Fluent-style API for creating `Expr`s
+pub mod expr_fn {
+    pub use super::from_json::from_json;
+    pub use super::json_extract::json_extract;
+    pub use super::json_value::json_value;
+    pub use super::to_json::to_json;
+}
+
+/// Return all default JSON functions
+pub fn all_default_json_functions() -> Vec<Arc<ScalarUDF>> {
+    vec![
+        json_extract::json_extract_udf(),
+        json_value::json_value_udf(),
+        to_json::to_json_udf(),
+        from_json::from_json_udf(),
+    ]
+}
+
+/// Return all default JSON table functions
+pub fn all_default_json_table_functions() -> Vec<Arc<TableFunction>> {
+    vec![json_array_elements()]
+}
+
+/// Return the [`FunctionRewrite`]s for the JSON operators and casts
+pub fn all_default_json_function_rewrites() -> Vec<Arc<dyn FunctionRewrite + Send + Sync>>
+{
+    vec![Arc::new(rewrite::JsonFunctionRewrite::default())]
+}
+
+/// Returns the `json_array_elements` table function
+pub fn json_array_elements() -> Arc<TableFunction> {
+    static INSTANCE: std::sync::LazyLock<Arc<TableFunction>> =
+        std::sync::LazyLock::new(|| {
+            Arc::new(TableFunction::new(
+                "json_array_elements".to_string(),
+                Arc::new(json_array_elements::JsonArrayElementsFunc::default()),
+            ))
+        });
+    Arc::clone(&INSTANCE)
+}
+
+/// Registers all enabled packages with a [`FunctionRegistry`], including the
+/// [`FunctionRewrite`]s for the JSON operators and casts.
+///
+/// Table functions are not part of a [`FunctionRegistry`], see
+/// [`all_default_json_table_functions`].
+pub fn register_all(registry: &mut dyn FunctionRegistry) -> Result<()> {
+    let functions: Vec<Arc<ScalarUDF>> = all_default_json_functions();
+    functions.into_iter().try_for_each(|udf| {
+        let existing_udf = registry.register_udf(udf)?;
+        if let Some(existing_udf) = existing_udf {
+            debug!("Overwrite existing UDF: {}", existing_udf.name());
+        }
+        Ok(()) as Result<()>
+    })?;
+
+    all_default_json_function_rewrites()
+        .into_iter()
+        .try_for_each(|rewrite| registry.register_function_rewrite(rewrite))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::all_default_json_functions;
+    use datafusion_common::Result;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_no_duplicate_name() -> Result<()> {
+        let mut names = HashSet::new();
+        for func in all_default_json_functions() {
+            assert!(
+                names.insert(func.name().to_string().to_lowercase()),
+                "duplicate function name: {}",
+                func.name()
+            );
+            for alias in func.aliases() {
+                assert!(
+                    names.insert(alias.to_string().to_lowercase()),
+                    "duplicate function name: {alias}"
+                );
+            }
+        }
+        Ok(())
+    }
+}
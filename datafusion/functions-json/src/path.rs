@@ -0,0 +1,263 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! JSON path expressions, such as `$.store.book[0].title`
+
+use std::collections::HashMap;
+
+use datafusion_common::{DataFusionError, Result, exec_datafusion_err};
+use serde_json::value::RawValue;
+
+/// A step of a [`JsonPath`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonPathStep {
+    /// The member of an object with the given key, `.key` or `["key"]`
+    Key(String),
+    /// The element of an array at the given index, `[1]`. Negative indexes
+    /// count from the end of the array, `[-1]` is its last element.
+    Index(i64),
+}
+
+/// A parsed JSON path expression, selecting a value within a JSON document.
+///
+/// Paths start with `$`, the whole document, followed by any number of
+/// steps:
+///
+/// * `.key` selects the member `key` of an object. The key ends at the next
+///   `.` or `[`, keys containing them can be written `["key"]`
+/// * `["key"]` or `['key']` selects the member `key` of an object, double
+///   quoted keys can contain JSON escapes
+/// * `[n]` selects the `n`th element of an array, starting from `0`.
+///   Negative indexes count from the end of the array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    steps: Vec<JsonPathStep>,
+}
+
+impl JsonPath {
+    /// Parse a JSON path expression
+    pub fn try_new(path: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| exec_datafusion_err!("Invalid JSON path '{path}': {reason}");
+        let Some(mut rest) = path.trim().strip_prefix('$') else {
+            return Err(invalid("must start with '$'"));
+        };
+        let mut steps = vec![];
+        while let Some(c) = rest.chars().next() {
+            match c {
+                '.' => {
+                    let end = rest[1..].find(['.', '[']).map_or(rest.len(), |i| i + 1);
+                    let key = &rest[1..end];
+                    if key.is_empty() {
+                        return Err(invalid("expected a key after '.'"));
+                    }
+                    steps.push(JsonPathStep::Key(key.to_string()));
+                    rest = &rest[end..];
+                }
+                '[' => {
+                    let Some(end) = bracket_end(rest) else {
+                        return Err(invalid("unterminated '['"));
+                    };
+                    let inner = rest[1..end].trim();
+                    let step = if let Some(key) = inner
+                        .strip_prefix('\'')
+                        .and_then(|key| key.strip_suffix('\''))
+                    {
+                        JsonPathStep::Key(key.to_string())
+                    } else if inner.starts_with('"') {
+                        let key = serde_json::from_str(inner)
+                            .map_err(|_| invalid("invalid quoted key"))?;
+                        JsonPathStep::Key(key)
+                    } else {
+                        let index = inner
+                            .parse()
+                            .map_err(|_| invalid("expected an index or a quoted key"))?;
+                        JsonPathStep::Index(index)
+                    };
+                    steps.push(step);
+                    rest = &rest[end + 1..];
+                }
+                _ => return Err(invalid("expected '.' or '['")),
+            }
+        }
+        Ok(Self { steps })
+    }
+
+    /// Returns the steps of this path
+    pub fn steps(&self) -> &[JsonPathStep] {
+        &self.steps
+    }
+
+    /// Returns the value selected by this path in `json`, or `None` if `json`
+    /// does not contain it.
+    ///
+    /// The selected value is returned as it appears in `json`. Only the
+    /// objects and arrays along the path are parsed.
+    pub fn extract<'a>(&self, json: &'a str) -> Result<Option<&'a RawValue>> {
+        let mut value = parse_json(json)?;
+        for step in &self.steps {
+            let next = match (step, json_kind(value)) {
+                (JsonPathStep::Key(key), JsonKind::Object) => {
+                    let mut members: HashMap<String, &RawValue> =
+                        serde_json::from_str(value.get())
+                            .map_err(|e| invalid_json(&e))?;
+                    members.remove(key)
+                }
+                (JsonPathStep::Index(index), JsonKind::Array) => {
+                    let elements: Vec<&RawValue> = serde_json::from_str(value.get())
+                        .map_err(|e| invalid_json(&e))?;
+                    let index = if *index < 0 {
+                        elements.len().checked_sub(index.unsigned_abs() as usize)
+                    } else {
+                        Some(*index as usize)
+                    };
+                    index.and_then(|index| elements.get(index).copied())
+                }
+                _ => None,
+            };
+            match next {
+                Some(next) => value = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(value))
+    }
+}
+
+/// Returns the position of the `]` closing the `[` at the start of `path`,
+/// skipping over quoted keys
+fn bracket_end(path: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in path.char_indices().skip(1) {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ']' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+/// The kind of a JSON value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JsonKind {
+    Object,
+    Array,
+    String,
+    Null,
+    /// A number or a boolean
+    Other,
+}
+
+/// Returns the kind of a JSON `value`
+pub(crate) fn json_kind(value: &RawValue) -> JsonKind {
+    match value.get().as_bytes().first() {
+        Some(b'{') => JsonKind::Object,
+        Some(b'[') => JsonKind::Array,
+        Some(b'"') => JsonKind::String,
+        Some(b'n') => JsonKind::Null,
+        _ => JsonKind::Other,
+    }
+}
+
+/// Parses a JSON document, without parsing its nested values
+pub(crate) fn parse_json(json: &str) -> Result<&RawValue> {
+    serde_json::from_str(json).map_err(|e| invalid_json(&e))
+}
+
+/// Returns the error for invalid JSON
+pub(crate) fn invalid_json(e: &serde_json::Error) -> DataFusionError {
+    exec_datafusion_err!("Invalid JSON: {e}")
+}
+
+/// Returns the text of a scalar JSON `value`, with strings unquoted, or
+/// `None` for `null`, objects and arrays
+pub(crate) fn scalar_text(value: &RawValue) -> Result<Option<String>> {
+    match json_kind(value) {
+        JsonKind::String => serde_json::from_str(value.get())
+            .map(Some)
+            .map_err(|e| invalid_json(&e)),
+        JsonKind::Other => Ok(Some(value.get().to_string())),
+        JsonKind::Object | JsonKind::Array | JsonKind::Null => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(json: &str, path: &str) -> Option<String> {
+        JsonPath::try_new(path)
+            .unwrap()
+            .extract(json)
+            .unwrap()
+            .map(|value| value.get().to_string())
+    }
+
+    #[test]
+    fn parse_json_path() {
+        assert_eq!(JsonPath::try_new("$").unwrap().steps(), &[]);
+        assert_eq!(
+            JsonPath::try_new(r#"$.a[0]["b.c"]['d'][-1]"#)
+                .unwrap()
+                .steps(),
+            &[
+                JsonPathStep::Key("a".to_string()),
+                JsonPathStep::Index(0),
+                JsonPathStep::Key("b.c".to_string()),
+                JsonPathStep::Key("d".to_string()),
+                JsonPathStep::Index(-1),
+            ]
+        );
+        assert_eq!(
+            JsonPath::try_new(r#"$["a\"]"]"#).unwrap().steps(),
+            &[JsonPathStep::Key("a\"]".to_string())]
+        );
+
+        for (path, reason) in [
+            ("a", "must start with '$'"),
+            ("$.", "expected a key after '.'"),
+            ("$[0", "unterminated '['"),
+            ("$[x]", "expected an index or a quoted key"),
+            ("$a", "expected '.' or '['"),
+        ] {
+            assert_eq!(
+                JsonPath::try_new(path).unwrap_err().strip_backtrace(),
+                format!("Execution error: Invalid JSON path '{path}': {reason}")
+            );
+        }
+    }
+
+    #[test]
+    fn extract_json_path() {
+        let json = r#"{"a": {"b": [1, {"c": "x"}, null]}, "d": true}"#;
+        assert_eq!(extract(json, "$").as_deref(), Some(json));
+        assert_eq!(extract(json, "$.a.b[1].c").as_deref(), Some(r#""x""#));
+        assert_eq!(extract(json, "$.a.b[-1]").as_deref(), Some("null"));
+        assert_eq!(extract(json, "$.a.b[1]").as_deref(), Some(r#"{"c": "x"}"#));
+        assert_eq!(extract(json, "$.a.b[3]"), None);
+        assert_eq!(extract(json, "$.a.b[-4]"), None);
+        assert_eq!(extract(json, "$.d.e"), None);
+        assert_eq!(extract(json, "$[0]"), None);
+        assert!(JsonPath::try_new("$").unwrap().extract("{").is_err());
+    }
+}
@@ -0,0 +1,132 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FunctionRewrite`] for the JSON operators and casts
+
+use arrow::datatypes::DataType;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{DFSchema, Result, ScalarValue};
+use datafusion_expr::expr::{BinaryExpr, Cast};
+use datafusion_expr::expr_rewriter::FunctionRewrite;
+use datafusion_expr::{Expr, ExprSchemable, Operator, binary_expr, cast, lit};
+
+use crate::expr_fn::{from_json, json_extract, json_value, to_json};
+
+/// Rewrites the JSON operators and casts to calls of the JSON functions:
+///
+/// * `json -> key` to `json_extract(json, '$["key"]')`, and `json -> n` to
+///   `json_extract(json, '$[n]')`
+/// * `json ->> key` and `json ->> n` to `json_value` in the same way
+/// * casts of strings to struct, list and map types to `from_json`
+/// * casts of structs, lists and maps to string types to `to_json`
+#[derive(Debug, Default)]
+pub struct JsonFunctionRewrite {}
+
+impl FunctionRewrite for JsonFunctionRewrite {
+    fn name(&self) -> &str {
+        "json_function_rewrite"
+    }
+
+    fn rewrite(
+        &self,
+        expr: Expr,
+        schema: &DFSchema,
+        _config: &ConfigOptions,
+    ) -> Result<Transformed<Expr>> {
+        let transformed = match expr {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Arrow,
+                right,
+            }) => json_extract(*left, key_path(*right)),
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::LongArrow,
+                right,
+            }) => json_value(*left, key_path(*right)),
+            Expr::Cast(Cast { expr, field }) => {
+                let source_type = expr.get_type(schema)?;
+                let target_type = field.data_type();
+                if is_string(&source_type) && is_json_container(target_type) {
+                    from_json(*expr, lit(target_type.to_string()))
+                } else if is_json_formatted(&source_type) && is_string(target_type) {
+                    match target_type {
+                        DataType::Utf8 => to_json(*expr),
+                        _ => cast(to_json(*expr), target_type.clone()),
+                    }
+                } else {
+                    return Ok(Transformed::no(Expr::Cast(Cast { expr, field })));
+                }
+            }
+            _ => return Ok(Transformed::no(expr)),
+        };
+        Ok(Transformed::yes(transformed))
+    }
+}
+
+/// Returns the JSON path selecting the member or element `key`
+fn key_path(key: Expr) -> Expr {
+    match key {
+        Expr::Literal(scalar, _)
+            if scalar.data_type().is_integer() && !scalar.is_null() =>
+        {
+            lit(format!("$[{scalar}]"))
+        }
+        Expr::Literal(
+            ScalarValue::Utf8(Some(key))
+            | ScalarValue::LargeUtf8(Some(key))
+            | ScalarValue::Utf8View(Some(key)),
+            _,
+        ) => lit(format!("$[{}]", quote(&key))),
+        // JSON text of a string is the quoted string, and of an integer the
+        // integer itself
+        key => binary_expr(
+            binary_expr(lit("$["), Operator::StringConcat, to_json(key)),
+            Operator::StringConcat,
+            lit("]"),
+        ),
+    }
+}
+
+/// Returns `key` as a JSON string
+fn quote(key: &str) -> String {
+    serde_json::Value::from(key).to_string()
+}
+
+fn is_string(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    )
+}
+
+/// Returns true for the types parsed from JSON objects and arrays
+fn is_json_container(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Struct(_)
+            | DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::Map(_, _)
+    )
+}
+
+/// Returns true for the types formatted as JSON objects and arrays
+fn is_json_formatted(data_type: &DataType) -> bool {
+    is_json_container(data_type) || matches!(data_type, DataType::FixedSizeList(_, _))
+}
@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for to_json function.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, StringBuilder};
+use arrow::datatypes::{DataType, Field, FieldRef};
+use arrow::json::writer::{EncoderOptions, make_encoder};
+use datafusion_common::utils::take_function_args;
+use datafusion_common::{Result, ScalarValue, exec_datafusion_err};
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    Volatility,
+};
+use datafusion_macros::user_doc;
+
+make_udf_expr_and_func!(
+    ToJson,
+    to_json,
+    value,
+    "returns the JSON text of a value.",
+    to_json_udf
+);
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = "Returns the JSON text of a value. Structs are formatted as objects, arrays as arrays, and `NULL` members and elements as `null`. Casting a struct or an array to a string calls `to_json`.",
+    syntax_example = "to_json(value)",
+    sql_example = r#"```sql
+> select to_json({a: 1, b: [true, null]});
++------------------------------------------------------------------------------------+
+| to_json(named_struct(Utf8("a"),Int64(1),Utf8("b"),make_array(Boolean(true),NULL))) |
++------------------------------------------------------------------------------------+
+| {"a":1,"b":[true,null]}                                                            |
++------------------------------------------------------------------------------------+
+```"#,
+    argument(
+        name = "value",
+        description = "Expression to format as JSON. Can be a constant, column, or function, and any combination of operators."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ToJson {
+    signature: Signature,
+}
+
+impl Default for ToJson {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToJson {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ToJson {
+    fn name(&self) -> &str {
+        "to_json"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let [value] = take_function_args(self.name(), args.args)?;
+        match value {
+            ColumnarValue::Array(array) => {
+                Ok(ColumnarValue::Array(to_json_array(&array)?))
+            }
+            ColumnarValue::Scalar(scalar) => {
+                let array = to_json_array(&scalar.to_array()?)?;
+                Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                    &array, 0,
+                )?))
+            }
+        }
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// Formats each value of `array` as JSON text
+pub(crate) fn to_json_array(array: &ArrayRef) -> Result<ArrayRef> {
+    let field: FieldRef = Arc::new(Field::new("value", array.data_type().clone(), true));
+    let options = EncoderOptions::default().with_explicit_nulls(true);
+    let mut encoder = make_encoder(&field, array.as_ref(), &options)?;
+
+    // `NullArray`s have no null buffer, but all of their values are null
+    let nulls = array.logical_nulls();
+    let mut builder = StringBuilder::with_capacity(array.len(), 0);
+    let mut buffer = vec![];
+    for i in 0..array.len() {
+        if nulls.as_ref().is_some_and(|nulls| nulls.is_null(i)) {
+            builder.append_null();
+            continue;
+        }
+        buffer.clear();
+        encoder.encode(i, &mut buffer);
+        let json = std::str::from_utf8(&buffer)
+            .map_err(|e| exec_datafusion_err!("to_json produced invalid UTF-8: {e}"))?;
+        builder.append_value(json);
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{AsArray, Int32Array, ListArray, StructArray};
+    use arrow::datatypes::Int32Type;
+
+    #[test]
+    fn test_to_json_array() -> Result<()> {
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), None]),
+            None,
+            Some(vec![]),
+        ]);
+        let array: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Arc::new(Field::new("a", DataType::Int32, true)),
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new_list(
+                    "b",
+                    Field::new_list_field(DataType::Int32, true),
+                    true,
+                )),
+                Arc::new(list) as ArrayRef,
+            ),
+        ]));
+        let json = to_json_array(&array)?;
+        let json = json.as_string::<i32>();
+        assert_eq!(json.value(0), r#"{"a":1,"b":[1,null]}"#);
+        assert_eq!(json.value(1), r#"{"a":null,"b":null}"#);
+        assert_eq!(json.value(2), r#"{"a":3,"b":[]}"#);
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Utilities shared by the JSON functions
+
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, LargeStringArray, StringArray, StringBuilder,
+    StringViewArray,
+};
+use arrow::datatypes::DataType;
+use datafusion_common::{Result, ScalarValue, exec_err};
+use datafusion_expr::ColumnarValue;
+use serde_json::value::RawValue;
+
+use crate::path::JsonPath;
+
+/// The values of an array of any string type
+pub(crate) enum StringValues<'a> {
+    Utf8(&'a StringArray),
+    LargeUtf8(&'a LargeStringArray),
+    Utf8View(&'a StringViewArray),
+}
+
+impl<'a> StringValues<'a> {
+    pub(crate) fn try_new(name: &str, array: &'a dyn Array) -> Result<Self> {
+        match array.data_type() {
+            DataType::Utf8 => Ok(Self::Utf8(array.as_string())),
+            DataType::LargeUtf8 => Ok(Self::LargeUtf8(array.as_string())),
+            DataType::Utf8View => Ok(Self::Utf8View(array.as_string_view())),
+            other => exec_err!("{name} expects a string argument, got {other}"),
+        }
+    }
+
+    /// Returns the value at `index`, or `None` if it is null
+    pub(crate) fn value(&self, index: usize) -> Option<&'a str> {
+        match self {
+            Self::Utf8(array) => array.is_valid(index).then(|| array.value(index)),
+            Self::LargeUtf8(array) => array.is_valid(index).then(|| array.value(index)),
+            Self::Utf8View(array) => array.is_valid(index).then(|| array.value(index)),
+        }
+    }
+}
+
+/// Evaluates `f` on the value selected by the path `args[1]` in each JSON
+/// document of `args[0]`, returning `NULL` where either argument is `NULL` or
+/// the document does not contain the path.
+///
+/// Constant paths are only parsed once.
+pub(crate) fn invoke_json_path(
+    name: &str,
+    args: &[ColumnarValue],
+    f: impl Fn(&RawValue) -> Result<Option<String>>,
+) -> Result<ColumnarValue> {
+    let [json, path] = args else {
+        return exec_err!("{name} expects 2 arguments, got {}", args.len());
+    };
+    let scalar_path = match path {
+        ColumnarValue::Scalar(path) => match path.try_as_str() {
+            Some(path) => Some(path.map(JsonPath::try_new).transpose()?),
+            None => return exec_err!("{name} expects a string path, got {path}"),
+        },
+        ColumnarValue::Array(_) => None,
+    };
+    let arrays = ColumnarValue::values_to_arrays(args)?;
+    let jsons = StringValues::try_new(name, &arrays[0])?;
+    let paths = StringValues::try_new(name, &arrays[1])?;
+
+    let num_rows = arrays[0].len();
+    let mut builder = StringBuilder::with_capacity(num_rows, 0);
+    for i in 0..num_rows {
+        let Some(json) = jsons.value(i) else {
+            builder.append_null();
+            continue;
+        };
+        let value = match &scalar_path {
+            Some(Some(path)) => path.extract(json)?,
+            Some(None) => None,
+            None => match paths.value(i) {
+                Some(path) => JsonPath::try_new(path)?.extract(json)?,
+                None => None,
+            },
+        };
+        builder.append_option(value.map(&f).transpose()?.flatten());
+    }
+    let result: ArrayRef = Arc::new(builder.finish());
+
+    if matches!(json, ColumnarValue::Scalar(_))
+        && matches!(path, ColumnarValue::Scalar(_))
+    {
+        Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+            &result, 0,
+        )?))
+    } else {
+        Ok(ColumnarValue::Array(result))
+    }
+}
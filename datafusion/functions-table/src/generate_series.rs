@@ -26,6 +26,7 @@ use async_trait::async_trait;
 use datafusion_catalog::TableFunctionImpl;
 use datafusion_catalog::TableProvider;
 use datafusion_catalog::{Session, TableFunctionArgs};
+use datafusion_common::config::ConfigOptions;
 use datafusion_common::{Result, ScalarValue, plan_err};
 use datafusion_expr::{Expr, TableType};
 use datafusion_physical_plan::ExecutionPlan;
@@ -503,10 +504,64 @@ impl TableFunctionImpl for GenerateSeriesFuncImpl {
             _ => plan_err!("Arguments must be literals"),
         }
     }
+
+    fn lateral_schema(&self, arg_types: &[DataType]) -> Result<Option<SchemaRef>> {
+        if arg_types.is_empty() || arg_types.len() > 3 {
+            return plan_err!("{} function requires 1 to 3 arguments", self.name);
+        }
+        if let Some(arg_type) = arg_types
+            .iter()
+            .find(|arg_type| !arg_type.is_integer() && !arg_type.is_null())
+        {
+            return plan_err!(
+                "Lateral calls of {} only support INTEGER arguments, got {arg_type}",
+                self.name
+            );
+        }
+        Ok(Some(Self::int64_schema()))
+    }
+
+    fn call_lateral(
+        &self,
+        args: &[ScalarValue],
+        config: &ConfigOptions,
+    ) -> Result<Vec<RecordBatch>> {
+        let exprs = args
+            .iter()
+            .map(|arg| {
+                let value = if arg.is_null() {
+                    ScalarValue::Null
+                } else {
+                    arg.cast_to(&DataType::Int64)?
+                };
+                Ok(Expr::Literal(value, None))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let generator = self
+            .int64_table(&exprs)?
+            .as_generator(config.execution.batch_size)?;
+        let mut batches = vec![];
+        while let Some(batch) = generator.write().generate_next_batch()? {
+            batches.push(batch);
+        }
+        Ok(batches)
+    }
 }
 
 impl GenerateSeriesFuncImpl {
+    fn int64_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new(
+            "value",
+            DataType::Int64,
+            false,
+        )]))
+    }
+
     fn call_int64(&self, exprs: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        Ok(Arc::new(self.int64_table(exprs)?))
+    }
+
+    fn int64_table(&self, exprs: &[Expr]) -> Result<GenerateSeriesTable> {
         let mut normalize_args = Vec::new();
         for (expr_index, expr) in exprs.iter().enumerate() {
             match expr {
@@ -522,18 +577,14 @@ impl GenerateSeriesFuncImpl {
             };
         }
 
-        let schema = Arc::new(Schema::new(vec![Field::new(
-            "value",
-            DataType::Int64,
-            false,
-        )]));
+        let schema = Self::int64_schema();
 
         if normalize_args.len() != exprs.len() {
             // contain null
-            return Ok(Arc::new(GenerateSeriesTable {
+            return Ok(GenerateSeriesTable {
                 schema,
                 args: GenSeriesArgs::ContainsNull { name: self.name },
-            }));
+            });
         }
 
         let (start, end, step) = match &normalize_args[..] {
@@ -549,7 +600,7 @@ impl GenerateSeriesFuncImpl {
             return plan_err!("Step cannot be zero");
         }
 
-        Ok(Arc::new(GenerateSeriesTable {
+        Ok(GenerateSeriesTable {
             schema,
             args: GenSeriesArgs::Int64Args {
                 start,
@@ -558,7 +609,7 @@ impl GenerateSeriesFuncImpl {
                 include_end: self.include_end,
                 name: self.name,
             },
-        }))
+        })
     }
 
     fn call_timestamp(&self, exprs: &[Expr]) -> Result<Arc<dyn TableProvider>> {
@@ -733,26 +784,60 @@ impl GenerateSeriesFuncImpl {
 #[derive(Debug)]
 pub struct GenerateSeriesFunc {}
 
-impl TableFunctionImpl for GenerateSeriesFunc {
-    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
-        let impl_func = GenerateSeriesFuncImpl {
+impl GenerateSeriesFunc {
+    fn impl_func(&self) -> GenerateSeriesFuncImpl {
+        GenerateSeriesFuncImpl {
             name: "generate_series",
             include_end: true,
-        };
-        impl_func.call_with_args(args)
+        }
+    }
+}
+
+impl TableFunctionImpl for GenerateSeriesFunc {
+    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
+        self.impl_func().call_with_args(args)
+    }
+
+    fn lateral_schema(&self, arg_types: &[DataType]) -> Result<Option<SchemaRef>> {
+        self.impl_func().lateral_schema(arg_types)
+    }
+
+    fn call_lateral(
+        &self,
+        args: &[ScalarValue],
+        config: &ConfigOptions,
+    ) -> Result<Vec<RecordBatch>> {
+        self.impl_func().call_lateral(args, config)
     }
 }
 
 #[derive(Debug)]
 pub struct RangeFunc {}
 
-impl TableFunctionImpl for RangeFunc {
-    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
-        let impl_func = GenerateSeriesFuncImpl {
+impl RangeFunc {
+    fn impl_func(&self) -> GenerateSeriesFuncImpl {
+        GenerateSeriesFuncImpl {
             name: "range",
             include_end: false,
-        };
-        impl_func.call_with_args(args)
+        }
+    }
+}
+
+impl TableFunctionImpl for RangeFunc {
+    fn call_with_args(&self, args: TableFunctionArgs) -> Result<Arc<dyn TableProvider>> {
+        self.impl_func().call_with_args(args)
+    }
+
+    fn lateral_schema(&self, arg_types: &[DataType]) -> Result<Option<SchemaRef>> {
+        self.impl_func().lateral_schema(arg_types)
+    }
+
+    fn call_lateral(
+        &self,
+        args: &[ScalarValue],
+        config: &ConfigOptions,
+    ) -> Result<Vec<RecordBatch>> {
+        self.impl_func().call_lateral(args, config)
     }
 }
 
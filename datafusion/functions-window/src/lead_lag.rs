@@ -281,6 +281,7 @@ impl WindowUDFImpl for WindowShift {
             default_value,
             ignore_nulls: partition_evaluator_args.ignore_nulls(),
             non_null_offsets: VecDeque::new(),
+            trailing_null_count: 0,
         }))
     }
 
@@ -408,6 +409,9 @@ struct WindowShiftEvaluator {
     ignore_nulls: bool,
     // VecDeque contains offset values that between non-null entries
     non_null_offsets: VecDeque<usize>,
+    // Number of null entries scanned after the last non-null entry of
+    // `non_null_offsets` (or the current row, if empty) in LEAD mode
+    trailing_null_count: usize,
 }
 
 impl WindowShiftEvaluator {
@@ -424,42 +428,33 @@ fn evaluate_all_with_ignore_null(
     default_value: &ScalarValue,
     is_lag: bool,
 ) -> Result<ArrayRef, DataFusionError> {
-    let valid_indices: Vec<usize> =
-        array.nulls().unwrap().valid_indices().collect::<Vec<_>>();
-    let direction = !is_lag;
+    let Some(nulls) = array.nulls().filter(|nulls| nulls.null_count() > 0) else {
+        // Without nulls, ignoring nulls does not change the result
+        return shift_with_default_value(array, offset, default_value);
+    };
+    let valid_indices: Vec<usize> = nulls.valid_indices().collect::<Vec<_>>();
+    let offset = offset.unsigned_abs() as usize;
     let new_array_results: Result<Vec<_>, DataFusionError> = (0..array.len())
         .map(|id| {
-            let result_index = match valid_indices.binary_search(&id) {
-                Ok(pos) => if direction {
-                    pos.checked_add(offset as usize)
-                } else {
-                    pos.checked_sub(offset.unsigned_abs() as usize)
-                }
-                .and_then(|new_pos| {
-                    if new_pos < valid_indices.len() {
-                        Some(valid_indices[new_pos])
-                    } else {
-                        None
-                    }
-                }),
-                Err(pos) => if direction {
-                    pos.checked_add(offset as usize)
-                } else if pos > 0 {
-                    pos.checked_sub(offset.unsigned_abs() as usize)
-                } else {
-                    None
-                }
-                .and_then(|new_pos| {
-                    if new_pos < valid_indices.len() {
-                        Some(valid_indices[new_pos])
-                    } else {
-                        None
-                    }
-                }),
+            // `pos` is the number of non-null entries before the current row
+            let (pos, is_valid) = match valid_indices.binary_search(&id) {
+                Ok(pos) => (pos, true),
+                Err(pos) => (pos, false),
+            };
+            let new_pos = if is_lag {
+                pos.checked_sub(offset)
+            } else if is_valid {
+                pos.checked_add(offset)
+            } else if offset > 0 {
+                // The first non-null entry after a null current row is at `pos`
+                Some(pos + offset - 1)
+            } else {
+                // The current row is null
+                None
             };
 
-            match result_index {
-                Some(index) => ScalarValue::try_from_array(array, index),
+            match new_pos.and_then(|new_pos| valid_indices.get(new_pos)) {
+                Some(&index) => ScalarValue::try_from_array(array, index),
                 None => Ok(default_value.clone()),
             }
         })
@@ -584,50 +579,40 @@ impl PartitionEvaluator for WindowShiftEvaluator {
             // Stores the necessary non-null entry number further than the current row.
             let non_null_row_count = (-self.shift_offset) as usize;
 
-            if self.non_null_offsets.is_empty() {
-                // When empty, fill non_null offsets with the data further than the current row.
-                let mut offset_val = 1;
-                for idx in range.start + 1..range.end {
-                    if array.is_valid(idx) {
-                        self.non_null_offsets.push_back(offset_val);
-                        offset_val = 1;
-                    } else {
-                        offset_val += 1;
-                    }
-                    // It is enough to keep track of `non_null_row_count + 1` non-null offset.
-                    // further data is unnecessary for the result.
-                    if self.non_null_offsets.len() == non_null_row_count + 1 {
-                        break;
-                    }
-                }
-            } else if range.end < len && array.is_valid(range.end) {
-                // Update `non_null_offsets` with the new end data.
-                if array.is_valid(range.end) {
-                    // When non-null, append a new offset.
-                    self.non_null_offsets.push_back(1);
+            // Continue scanning after the rows scanned for the previous rows,
+            // until the necessary number of non-null entries are found.
+            let mut next = range.start
+                + self.non_null_offsets.iter().sum::<usize>()
+                + self.trailing_null_count
+                + 1;
+            while self.non_null_offsets.len() < non_null_row_count && next < range.end {
+                if array.is_valid(next) {
+                    self.non_null_offsets
+                        .push_back(self.trailing_null_count + 1);
+                    self.trailing_null_count = 0;
                 } else {
-                    // When null, increment offset count of the last entry
-                    let last_idx = self.non_null_offsets.len() - 1;
-                    self.non_null_offsets[last_idx] += 1;
+                    self.trailing_null_count += 1;
                 }
+                next += 1;
             }
 
             // Find the nonNULL row index that shifted by offset comparing to current row index
-            idx = if self.non_null_offsets.len() >= non_null_row_count {
-                let total_offset: usize =
-                    self.non_null_offsets.iter().take(non_null_row_count).sum();
+            idx = if self.non_null_offsets.len() == non_null_row_count {
+                let total_offset: usize = self.non_null_offsets.iter().sum();
                 Some(range.start + total_offset)
             } else {
                 None
             };
             // Prune `self.non_null_offsets` from the start. so that at next iteration
             // start of the `self.non_null_offsets` matches with current row.
-            if !self.non_null_offsets.is_empty() {
-                self.non_null_offsets[0] -= 1;
-                if self.non_null_offsets[0] == 0 {
+            if let Some(first_offset) = self.non_null_offsets.front_mut() {
+                *first_offset -= 1;
+                if *first_offset == 0 {
                     // When offset is 0. Remove it.
                     self.non_null_offsets.pop_front();
                 }
+            } else {
+                self.trailing_null_count = self.trailing_null_count.saturating_sub(1);
             }
         }
 
@@ -699,6 +684,7 @@ mod tests {
             default_value: ScalarValue::Null,
             ignore_nulls: false,
             non_null_offsets: Default::default(),
+            trailing_null_count: 0,
         };
         assert_eq!(lag_fn.get_range(6, 10)?, Range { start: 4, end: 7 });
         assert_eq!(lag_fn.get_range(0, 10)?, Range { start: 0, end: 1 });
@@ -710,6 +696,7 @@ mod tests {
             ignore_nulls: true,
             // models data received [<Some>, <Some>, <Some>, NULL, <Some>, NULL, <current row>, ...]
             non_null_offsets: vec![2, 2].into(), // [1, 1, 2, 2] actually, just last 2 is used
+            trailing_null_count: 0,
         };
         assert_eq!(lag_fn.get_range(6, 10)?, Range { start: 2, end: 7 });
 
@@ -719,6 +706,7 @@ mod tests {
             default_value: ScalarValue::Null,
             ignore_nulls: false,
             non_null_offsets: Default::default(),
+            trailing_null_count: 0,
         };
         assert_eq!(lead_fn.get_range(6, 10)?, Range { start: 6, end: 8 });
         assert_eq!(lead_fn.get_range(9, 10)?, Range { start: 9, end: 10 });
@@ -730,6 +718,7 @@ mod tests {
             ignore_nulls: true,
             // models data received [..., <current row>, NULL, <Some>, NULL, <Some>, ..]
             non_null_offsets: vec![2, 2].into(),
+            trailing_null_count: 0,
         };
         assert_eq!(lead_fn.get_range(4, 10)?, Range { start: 4, end: 9 });
 
@@ -822,4 +811,118 @@ mod tests {
             .collect::<Int32Array>(),
         )
     }
+
+    #[test]
+    fn test_lead_lag_ignore_nulls() -> Result<()> {
+        let arr: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(3),
+            Some(4),
+            None,
+            None,
+            Some(7),
+            None,
+            Some(9),
+        ]));
+        let column = Arc::new(Column::new("c3", 0)) as Arc<dyn PhysicalExpr>;
+        let two =
+            Arc::new(Literal::new(ScalarValue::Int32(Some(2)))) as Arc<dyn PhysicalExpr>;
+        let input_fields = [DataType::Int32, DataType::Int32]
+            .into_iter()
+            .map(|d| Field::new("f", d, true))
+            .map(Arc::new)
+            .collect::<Vec<_>>();
+
+        for (expr, input_exprs, expected) in [
+            (
+                WindowShift::lead(),
+                vec![Arc::clone(&column)],
+                vec![
+                    Some(3),
+                    Some(3),
+                    Some(4),
+                    Some(7),
+                    Some(7),
+                    Some(7),
+                    Some(9),
+                    Some(9),
+                    None,
+                ],
+            ),
+            (
+                WindowShift::lead(),
+                vec![Arc::clone(&column), Arc::clone(&two)],
+                vec![
+                    Some(4),
+                    Some(4),
+                    Some(7),
+                    Some(9),
+                    Some(9),
+                    Some(9),
+                    None,
+                    None,
+                    None,
+                ],
+            ),
+            (
+                WindowShift::lag(),
+                vec![Arc::clone(&column)],
+                vec![
+                    None,
+                    Some(1),
+                    Some(1),
+                    Some(3),
+                    Some(4),
+                    Some(4),
+                    Some(4),
+                    Some(7),
+                    Some(7),
+                ],
+            ),
+            (
+                WindowShift::lag(),
+                vec![Arc::clone(&column), Arc::clone(&two)],
+                vec![
+                    None,
+                    None,
+                    None,
+                    Some(1),
+                    Some(3),
+                    Some(3),
+                    Some(3),
+                    Some(4),
+                    Some(4),
+                ],
+            ),
+        ] {
+            let expected = Int32Array::from(expected);
+            let args = || {
+                PartitionEvaluatorArgs::new(
+                    &input_exprs,
+                    &input_fields[..input_exprs.len()],
+                    false,
+                    true,
+                )
+            };
+            let values = vec![Arc::clone(&arr)];
+
+            let result = expr
+                .partition_evaluator(args())?
+                .evaluate_all(&values, arr.len())?;
+            assert_eq!(as_int32_array(&result)?, &expected);
+
+            // Evaluate row by row, as bounded execution does
+            let mut evaluator = expr.partition_evaluator(args())?;
+            let result = (0..arr.len())
+                .map(|idx| {
+                    let range = evaluator.get_range(idx, arr.len())?;
+                    evaluator.evaluate(&values, &range)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let result = ScalarValue::iter_to_array(result)?;
+            assert_eq!(as_int32_array(&result)?, &expected);
+        }
+        Ok(())
+    }
 }
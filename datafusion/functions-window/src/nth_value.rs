@@ -272,6 +272,7 @@ impl WindowUDFImpl for NthValue {
                 state,
                 ignore_nulls: partition_evaluator_args.ignore_nulls(),
                 n: 0,
+                pruned_last_value: None,
             }));
         }
 
@@ -304,6 +305,7 @@ impl WindowUDFImpl for NthValue {
             state,
             ignore_nulls: partition_evaluator_args.ignore_nulls(),
             n,
+            pruned_last_value: None,
         }))
     }
 
@@ -358,6 +360,9 @@ pub(crate) struct NthValueEvaluator {
     state: NthValueState,
     ignore_nulls: bool,
     n: i64,
+    // The last non-null value of the rows pruned from the frame of
+    // LAST_VALUE when nulls are ignored
+    pruned_last_value: Option<ScalarValue>,
 }
 
 impl PartitionEvaluator for NthValueEvaluator {
@@ -392,8 +397,38 @@ impl PartitionEvaluator for NthValueEvaluator {
                         // Fall through to the main case because there are no nulls
                     }
                 }
-                // Do not memoize for other kinds when nulls are ignored
-                NthValueKind::Last | NthValueKind::Nth => return Ok(()),
+                // The N-th non-null value of a frame starting at the beginning
+                // of the partition is final once found
+                NthValueKind::Nth if self.n > 0 => {
+                    if self.state.finalized_result.is_none()
+                        && size > 0
+                        && out.is_valid(size - 1)
+                    {
+                        let result = ScalarValue::try_from_array(out, size - 1)?;
+                        self.state.finalized_result = Some(result);
+                    }
+                    if self.state.finalized_result.is_some()
+                        && state.window_frame_range.start < state.window_frame_range.end
+                    {
+                        state.window_frame_range.start = state.window_frame_range.end - 1;
+                    }
+                    return Ok(());
+                }
+                // The last non-null value of a frame is either in the rows
+                // kept, or the last non-null value of the pruned rows, which
+                // is the last output
+                NthValueKind::Last => {
+                    if size > 0 {
+                        self.pruned_last_value =
+                            Some(ScalarValue::try_from_array(out, size - 1)?);
+                    }
+                    if state.window_frame_range.start < state.window_frame_range.end {
+                        state.window_frame_range.start = state.window_frame_range.end - 1;
+                    }
+                    return Ok(());
+                }
+                // Do not memoize NTH_VALUE from the end when nulls are ignored
+                NthValueKind::Nth => return Ok(()),
             }
         }
         let mut buffer_size = 1;
@@ -449,9 +484,10 @@ impl PartitionEvaluator for NthValueEvaluator {
                 // We produce None if the window is empty.
                 return ScalarValue::try_from(arr.data_type());
             }
-            match self.valid_index(arr, range) {
-                Some(index) => ScalarValue::try_from_array(arr, index),
-                None => ScalarValue::try_from(arr.data_type()),
+            match (self.valid_index(arr, range), &self.pruned_last_value) {
+                (Some(index), _) => ScalarValue::try_from_array(arr, index),
+                (None, Some(value)) => Ok(value.clone()),
+                (None, None) => ScalarValue::try_from(arr.data_type()),
             }
         }
     }
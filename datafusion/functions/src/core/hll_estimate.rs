@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, UInt64Builder};
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_common::utils::take_function_args;
+use datafusion_doc::Documentation;
+use datafusion_expr::{
+    ColumnarValue, ScalarFunctionArgs, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::hll_sketch::{
+    HllSketch, try_for_each_serialized,
+};
+use datafusion_macros::user_doc;
+
+use crate::utils::make_scalar_function;
+
+#[user_doc(
+    doc_section(label = "Other Functions"),
+    description = "Returns the estimated number of distinct values of a HyperLogLog sketch built by the `hll_sketch` or `hll_merge` aggregate functions.",
+    syntax_example = "hll_estimate(sketch)",
+    sql_example = r#"```sql
+> SELECT day, hll_estimate(daily_sketch) FROM daily_users;
++------------+----------------------------------------+
+| day        | hll_estimate(daily_users.daily_sketch) |
++------------+----------------------------------------+
+| 2024-01-01 | 823                                    |
+| 2024-01-02 | 791                                    |
++------------+----------------------------------------+
+```"#,
+    argument(
+        name = "sketch",
+        description = "Binary HyperLogLog sketch built by `hll_sketch` or `hll_merge`."
+    )
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct HllEstimateFunc {
+    signature: Signature,
+}
+
+impl Default for HllEstimateFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HllEstimateFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::uniform(
+                1,
+                vec![
+                    DataType::Binary,
+                    DataType::LargeBinary,
+                    DataType::BinaryView,
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for HllEstimateFunc {
+    fn name(&self) -> &str {
+        "hll_estimate"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        make_scalar_function(hll_estimate, vec![])(&args.args)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+fn hll_estimate(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let [sketches] = take_function_args("hll_estimate", args)?;
+    let mut estimates = UInt64Builder::with_capacity(sketches.len());
+    try_for_each_serialized(sketches, |bytes| {
+        let estimate = bytes
+            .map(|bytes| HllSketch::try_from_bytes(bytes).map(|s| s.estimate()))
+            .transpose()?;
+        estimates.append_option(estimate);
+        Ok(())
+    })?;
+    Ok(Arc::new(estimates.finish()))
+}
@@ -31,6 +31,7 @@ pub mod expr_ext;
 pub mod getfield;
 pub mod greatest;
 mod greatest_least_utils;
+pub mod hll_estimate;
 pub mod least;
 pub mod named_struct;
 pub mod nullif;
@@ -61,6 +62,7 @@ make_udf_function!(getfield::GetFieldFunc, get_field);
 make_udf_function!(coalesce::CoalesceFunc, coalesce);
 make_udf_function!(greatest::GreatestFunc, greatest);
 make_udf_function!(least::LeastFunc, least);
+make_udf_function!(hll_estimate::HllEstimateFunc, hll_estimate);
 make_udf_function!(union_extract::UnionExtractFun, union_extract);
 make_udf_function!(union_tag::UnionTagFunc, union_tag);
 make_udf_function!(version::VersionFunc, version);
@@ -143,6 +145,10 @@ pub mod expr_fn {
         union_tag,
         "Returns the name of the currently selected field in the union",
         arg1
+    ),(
+        hll_estimate,
+        "Returns the estimated number of distinct values of a HyperLogLog sketch",
+        arg1
     ));
 
     #[doc = "Returns the value of the field with the given name from the struct"]
@@ -192,6 +198,7 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         coalesce(),
         greatest(),
         least(),
+        hll_estimate(),
         union_extract(),
         union_tag(),
         version(),
@@ -28,17 +28,18 @@ use arrow::datatypes::DataType::{Time32, Time64, Timestamp};
 use arrow::datatypes::IntervalUnit::{DayTime, MonthDayNano};
 use arrow::datatypes::TimeUnit::{Microsecond, Millisecond, Nanosecond, Second};
 use arrow::datatypes::{
-    DataType, Time32MillisecondType, Time32SecondType, Time64MicrosecondType,
-    Time64NanosecondType, TimeUnit,
+    DataType, Field, FieldRef, Time32MillisecondType, Time32SecondType,
+    Time64MicrosecondType, Time64NanosecondType, TimeUnit,
 };
 use arrow::temporal_conversions::NANOSECONDS_IN_DAY;
 use datafusion_common::cast::as_primitive_array;
+use datafusion_common::watermark::Watermark;
 use datafusion_common::{Result, ScalarValue, exec_err, not_impl_err, plan_err};
 use datafusion_expr::TypeSignature::Exact;
 use datafusion_expr::sort_properties::{ExprProperties, SortProperties};
 use datafusion_expr::{
-    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
-    TIMEZONE_WILDCARD, Volatility,
+    ColumnarValue, Documentation, ReturnFieldArgs, ScalarFunctionArgs, ScalarUDFImpl,
+    Signature, TIMEZONE_WILDCARD, Volatility,
 };
 use datafusion_macros::user_doc;
 
@@ -232,6 +233,40 @@ impl ScalarUDFImpl for DateBinFunc {
         }
     }
 
+    fn return_field_from_args(&self, args: ReturnFieldArgs) -> Result<FieldRef> {
+        let arg_types = args
+            .arg_fields
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect::<Vec<_>>();
+        let field = Field::new(self.name(), self.return_type(&arg_types)?, true);
+
+        // Binning is monotonic, so the bins of an event time with a watermark
+        // have a watermark as well, allowing one more stride of lateness
+        let watermark = Watermark::try_from_field(&args.arg_fields[1])?;
+        let stride_nanos = match args.scalar_arguments.first() {
+            Some(Some(ScalarValue::IntervalMonthDayNano(Some(v)))) if v.months == 0 => {
+                (v.days as i64)
+                    .checked_mul(NANOSECONDS_IN_DAY)
+                    .and_then(|days| days.checked_add(v.nanoseconds))
+            }
+            Some(Some(ScalarValue::IntervalDayTime(Some(v)))) => (v.days as i64)
+                .checked_mul(NANOSECONDS_IN_DAY)
+                .and_then(|days| {
+                    days.checked_add(v.milliseconds as i64 * NANOS_PER_MILLI)
+                }),
+            _ => None,
+        };
+        match (watermark, stride_nanos) {
+            (Some(watermark), Some(stride_nanos)) if stride_nanos > 0 => Ok(Arc::new(
+                watermark
+                    .with_additional_delay(stride_nanos)?
+                    .add_to_field(field),
+            )),
+            _ => Ok(Arc::new(field)),
+        }
+    }
+
     fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
         let args = &args.args;
         if args.len() == 2 {
@@ -1337,4 +1372,43 @@ mod tests {
             assert!(val.is_none(), "Expected None for out of range operation");
         }
     }
+
+    #[test]
+    fn test_date_bin_watermark() -> datafusion_common::Result<()> {
+        use datafusion_common::watermark::Watermark;
+        use datafusion_expr::ReturnFieldArgs;
+
+        let stride = ScalarValue::new_interval_mdn(0, 0, 60_000_000_000);
+        let source: FieldRef =
+            Arc::new(Watermark::try_new(10_000_000_000)?.add_to_field(Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Second, None),
+                false,
+            )));
+        let stride_field: FieldRef =
+            Arc::new(Field::new("stride", stride.data_type(), false));
+        let return_field =
+            DateBinFunc::new().return_field_from_args(ReturnFieldArgs {
+                arg_fields: &[Arc::clone(&stride_field), Arc::clone(&source)],
+                scalar_arguments: &[Some(&stride), None],
+            })?;
+        assert_eq!(
+            Watermark::try_from_field(&return_field)?,
+            Some(Watermark::try_new(70_000_000_000)?)
+        );
+
+        // bins of a column without a watermark have no watermark either
+        let source: FieldRef = Arc::new(Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Second, None),
+            false,
+        ));
+        let return_field =
+            DateBinFunc::new().return_field_from_args(ReturnFieldArgs {
+                arg_fields: &[stride_field, source],
+                scalar_arguments: &[Some(&stride), None],
+            })?;
+        assert_eq!(Watermark::try_from_field(&return_field)?, None);
+        Ok(())
+    }
 }
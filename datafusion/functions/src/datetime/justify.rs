@@ -0,0 +1,375 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `justify_days`, `justify_hours` and `justify_interval`, which normalize the
+//! fields of an interval the way PostgreSQL does.
+
+use std::sync::Arc;
+
+use arrow::array::{AsArray, IntervalMonthDayNanoArray};
+use arrow::datatypes::IntervalUnit::MonthDayNano;
+use arrow::datatypes::{DataType, IntervalMonthDayNano, IntervalMonthDayNanoType};
+
+use datafusion_common::types::{NativeType, logical_interval_mdn};
+use datafusion_common::{
+    Result, ScalarValue, exec_datafusion_err, exec_err, utils::take_function_args,
+};
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    Volatility,
+};
+use datafusion_expr_common::signature::{Coercion, TypeSignatureClass};
+use datafusion_macros::user_doc;
+
+const DAYS_PER_MONTH: i32 = 30;
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+#[user_doc(
+    doc_section(label = "Time and Date Functions"),
+    description = "Adjusts an interval so that 30-day periods are represented as months.",
+    syntax_example = "justify_days(interval)",
+    sql_example = r#"```sql
+> select justify_days(interval '35 days');
++----------------------------------------------------------------------------------------------------+
+| justify_days(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 35, nanoseconds: 0 }")) |
++----------------------------------------------------------------------------------------------------+
+| 1 mons 5 days                                                                                      |
++----------------------------------------------------------------------------------------------------+
+```
+"#,
+    argument(name = "interval", description = "Interval expression to justify.")
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct JustifyDaysFunc {
+    signature: Signature,
+}
+
+impl Default for JustifyDaysFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JustifyDaysFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: justify_signature(),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JustifyDaysFunc {
+    fn name(&self) -> &str {
+        "justify_days"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Interval(MonthDayNano))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        justify(self.name(), args, justify_days)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+#[user_doc(
+    doc_section(label = "Time and Date Functions"),
+    description = "Adjusts an interval so that 24-hour time periods are represented as days.",
+    syntax_example = "justify_hours(interval)",
+    sql_example = r#"```sql
+> select justify_hours(interval '27 hours');
++-----------------------------------------------------------------------------------------------------------------+
+| justify_hours(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 97200000000000 }")) |
++-----------------------------------------------------------------------------------------------------------------+
+| 1 days 3 hours                                                                                                  |
++-----------------------------------------------------------------------------------------------------------------+
+```
+"#,
+    argument(name = "interval", description = "Interval expression to justify.")
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct JustifyHoursFunc {
+    signature: Signature,
+}
+
+impl Default for JustifyHoursFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JustifyHoursFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: justify_signature(),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JustifyHoursFunc {
+    fn name(&self) -> &str {
+        "justify_hours"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Interval(MonthDayNano))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        justify(self.name(), args, justify_hours)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+#[user_doc(
+    doc_section(label = "Time and Date Functions"),
+    description = "Adjusts an interval using both `justify_days` and `justify_hours`, with additional sign adjustments so that all fields have the same sign.",
+    syntax_example = "justify_interval(interval)",
+    sql_example = r#"```sql
+> select justify_interval(interval '1 mon -1 hour');
++--------------------------------------------------------------------------------------------------------------------+
+| justify_interval(IntervalMonthDayNano("IntervalMonthDayNano { months: 1, days: 0, nanoseconds: -3600000000000 }")) |
++--------------------------------------------------------------------------------------------------------------------+
+| 29 days 23 hours                                                                                                   |
++--------------------------------------------------------------------------------------------------------------------+
+```
+"#,
+    argument(name = "interval", description = "Interval expression to justify.")
+)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct JustifyIntervalFunc {
+    signature: Signature,
+}
+
+impl Default for JustifyIntervalFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JustifyIntervalFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: justify_signature(),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JustifyIntervalFunc {
+    fn name(&self) -> &str {
+        "justify_interval"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Interval(MonthDayNano))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        justify(self.name(), args, justify_interval)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// All the justify functions take a single interval, which is cast to
+/// `Interval(MonthDayNano)`
+fn justify_signature() -> Signature {
+    let interval = Coercion::new_implicit(
+        TypeSignatureClass::Native(logical_interval_mdn()),
+        vec![TypeSignatureClass::Interval],
+        NativeType::Interval(MonthDayNano),
+    );
+    Signature::coercible(vec![interval], Volatility::Immutable)
+}
+
+/// Applies `f` to every interval of the single argument of `name`
+fn justify(
+    name: &str,
+    args: ScalarFunctionArgs,
+    f: fn(IntervalMonthDayNano) -> Option<IntervalMonthDayNano>,
+) -> Result<ColumnarValue> {
+    let [interval] = take_function_args(name, args.args)?;
+    let justify = |value| {
+        f(value).ok_or_else(|| exec_datafusion_err!("{name}: interval out of range"))
+    };
+
+    match interval {
+        ColumnarValue::Scalar(ScalarValue::IntervalMonthDayNano(value)) => {
+            Ok(ColumnarValue::Scalar(ScalarValue::IntervalMonthDayNano(
+                value.map(justify).transpose()?,
+            )))
+        }
+        ColumnarValue::Array(array) => {
+            let result = array
+                .as_primitive::<IntervalMonthDayNanoType>()
+                .iter()
+                .map(|value| value.map(justify).transpose())
+                .collect::<Result<IntervalMonthDayNanoArray>>()?;
+            Ok(ColumnarValue::Array(Arc::new(result)))
+        }
+        other => exec_err!(
+            "Unsupported data type {} for function {name}",
+            other.data_type()
+        ),
+    }
+}
+
+/// Moves whole 24-hour periods of the time part into the days, so that the
+/// days and the time part have the same sign, e.g. `27 hours` is `1 day 3 hours`
+fn justify_hours(interval: IntervalMonthDayNano) -> Option<IntervalMonthDayNano> {
+    let IntervalMonthDayNano {
+        months,
+        days,
+        nanoseconds,
+    } = interval;
+    let mut days = days.checked_add((nanoseconds / NANOS_PER_DAY).try_into().ok()?)?;
+    let mut nanoseconds = nanoseconds % NANOS_PER_DAY;
+    if days > 0 && nanoseconds < 0 {
+        nanoseconds += NANOS_PER_DAY;
+        days -= 1;
+    } else if days < 0 && nanoseconds > 0 {
+        nanoseconds -= NANOS_PER_DAY;
+        days += 1;
+    }
+    Some(IntervalMonthDayNano::new(months, days, nanoseconds))
+}
+
+/// Moves whole 30-day periods of the days into the months, so that the months
+/// and the days have the same sign, e.g. `35 days` is `1 month 5 days`
+fn justify_days(interval: IntervalMonthDayNano) -> Option<IntervalMonthDayNano> {
+    let IntervalMonthDayNano {
+        months,
+        days,
+        nanoseconds,
+    } = interval;
+    let mut months = months.checked_add(days / DAYS_PER_MONTH)?;
+    let mut days = days % DAYS_PER_MONTH;
+    if months > 0 && days < 0 {
+        days += DAYS_PER_MONTH;
+        months -= 1;
+    } else if months < 0 && days > 0 {
+        days -= DAYS_PER_MONTH;
+        months += 1;
+    }
+    Some(IntervalMonthDayNano::new(months, days, nanoseconds))
+}
+
+/// Combines [`justify_days`] and [`justify_hours`], so that all the fields of
+/// the interval have the same sign, e.g. `1 month -1 hour` is `29 days 23 hours`
+fn justify_interval(interval: IntervalMonthDayNano) -> Option<IntervalMonthDayNano> {
+    let IntervalMonthDayNano {
+        mut months,
+        mut days,
+        nanoseconds,
+    } = interval;
+    // Move the days into the months first when that cannot change the sign of
+    // the days, which avoids overflowing the days below
+    if (days > 0 && nanoseconds > 0) || (days < 0 && nanoseconds < 0) {
+        months = months.checked_add(days / DAYS_PER_MONTH)?;
+        days %= DAYS_PER_MONTH;
+    }
+    days = days.checked_add((nanoseconds / NANOS_PER_DAY).try_into().ok()?)?;
+    let mut nanoseconds = nanoseconds % NANOS_PER_DAY;
+    months = months.checked_add(days / DAYS_PER_MONTH)?;
+    days %= DAYS_PER_MONTH;
+
+    if months > 0 && (days < 0 || (days == 0 && nanoseconds < 0)) {
+        days += DAYS_PER_MONTH;
+        months -= 1;
+    } else if months < 0 && (days > 0 || (days == 0 && nanoseconds > 0)) {
+        days -= DAYS_PER_MONTH;
+        months += 1;
+    }
+    if days > 0 && nanoseconds < 0 {
+        nanoseconds += NANOS_PER_DAY;
+        days -= 1;
+    } else if days < 0 && nanoseconds > 0 {
+        nanoseconds -= NANOS_PER_DAY;
+        days += 1;
+    }
+    Some(IntervalMonthDayNano::new(months, days, nanoseconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600_000_000_000;
+
+    fn interval(months: i32, days: i32, nanoseconds: i64) -> IntervalMonthDayNano {
+        IntervalMonthDayNano::new(months, days, nanoseconds)
+    }
+
+    #[test]
+    fn test_justify() {
+        assert_eq!(
+            justify_hours(interval(0, 0, 27 * HOUR)),
+            Some(interval(0, 1, 3 * HOUR))
+        );
+        assert_eq!(
+            justify_hours(interval(0, 1, -HOUR)),
+            Some(interval(0, 0, 23 * HOUR))
+        );
+        assert_eq!(
+            justify_hours(interval(0, -1, HOUR)),
+            Some(interval(0, 0, -23 * HOUR))
+        );
+        assert_eq!(justify_hours(interval(0, i32::MAX, 24 * HOUR)), None);
+
+        assert_eq!(justify_days(interval(0, 35, 0)), Some(interval(1, 5, 0)));
+        assert_eq!(
+            justify_days(interval(1, -1, HOUR)),
+            Some(interval(0, 29, HOUR))
+        );
+        assert_eq!(justify_days(interval(-1, 35, 0)), Some(interval(0, 5, 0)));
+
+        assert_eq!(
+            justify_interval(interval(1, 0, -HOUR)),
+            Some(interval(0, 29, 23 * HOUR))
+        );
+        assert_eq!(
+            justify_interval(interval(0, 29, 25 * HOUR)),
+            Some(interval(1, 0, HOUR))
+        );
+        assert_eq!(
+            justify_interval(interval(-1, 0, HOUR)),
+            Some(interval(0, -29, -23 * HOUR))
+        );
+        assert_eq!(justify_interval(interval(i32::MAX, 30, 0)), None);
+    }
+}
@@ -28,6 +28,7 @@ pub mod date_bin;
 pub mod date_part;
 pub mod date_trunc;
 pub mod from_unixtime;
+pub mod justify;
 pub mod make_date;
 pub mod make_time;
 pub mod now;
@@ -45,6 +46,9 @@ make_udf_function!(current_time::CurrentTimeFunc, current_time);
 make_udf_function!(date_bin::DateBinFunc, date_bin);
 make_udf_function!(date_part::DatePartFunc, date_part);
 make_udf_function!(date_trunc::DateTruncFunc, date_trunc);
+make_udf_function!(justify::JustifyDaysFunc, justify_days);
+make_udf_function!(justify::JustifyHoursFunc, justify_hours);
+make_udf_function!(justify::JustifyIntervalFunc, justify_interval);
 make_udf_function!(make_date::MakeDateFunc, make_date);
 make_udf_function!(make_time::MakeTimeFunc, make_time);
 make_udf_function!(from_unixtime::FromUnixtimeFunc, from_unixtime);
@@ -93,6 +97,18 @@ pub mod expr_fn {
         date_trunc,
         "truncates the date to a specified level of precision",
         part date
+    ),(
+        justify_days,
+        "adjusts an interval so that 30-day periods are represented as months",
+        interval
+    ),(
+        justify_hours,
+        "adjusts an interval so that 24-hour periods are represented as days",
+        interval
+    ),(
+        justify_interval,
+        "adjusts an interval with justify_days and justify_hours, so that all its fields have the same sign",
+        interval
     ),(
         make_date,
         "make a date from year, month and day component parts",
@@ -282,6 +298,9 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         date_part(),
         date_trunc(),
         from_unixtime(),
+        justify_days(),
+        justify_hours(),
+        justify_interval(),
         make_date(),
         make_time(),
         now(&config),
@@ -141,6 +141,9 @@ make_stub_package!(unicode, "unicode_expressions");
 #[cfg(any(feature = "datetime_expressions", feature = "unicode_expressions"))]
 pub mod planner;
 
+// The string builders are used by the `string_expressions` functions, some of
+// them by the `unicode_expressions` functions as well
+#[cfg_attr(not(feature = "string_expressions"), expect(dead_code))]
 pub mod strings;
 
 pub mod utils;
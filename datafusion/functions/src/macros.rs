@@ -208,6 +208,7 @@ macro_rules! downcast_arg {
 /// $UNARY_FUNC: the unary function to apply to the argument
 /// $OUTPUT_ORDERING: the output ordering calculation method of the function
 /// $GET_DOC: the function to get the documentation of the UDF
+#[cfg(feature = "math_expressions")]
 macro_rules! make_math_unary_udf {
     ($UDF:ident, $NAME:ident, $UNARY_FUNC:ident, $OUTPUT_ORDERING:expr, $EVALUATE_BOUNDS:expr, $GET_DOC:expr) => {
         $crate::make_udf_function!($NAME::$UDF, $NAME);
@@ -319,6 +320,7 @@ macro_rules! make_math_unary_udf {
 /// $BINARY_FUNC: the binary function to apply to the argument
 /// $OUTPUT_ORDERING: the output ordering calculation method of the function
 /// $GET_DOC: the function to get the documentation of the UDF
+#[cfg(feature = "math_expressions")]
 macro_rules! make_math_binary_udf {
     ($UDF:ident, $NAME:ident, $BINARY_FUNC:ident, $OUTPUT_ORDERING:expr, $GET_DOC:expr) => {
         $crate::make_udf_function!($NAME::$UDF, $NAME);
@@ -72,6 +72,7 @@ macro_rules! get_optimal_return_type {
 get_optimal_return_type!(utf8_to_str_type, DataType::LargeUtf8, DataType::Utf8);
 
 // `utf8_to_int_type`: returns either a Int32 or Int64 based on the input type size.
+#[cfg(any(feature = "string_expressions", feature = "unicode_expressions"))]
 get_optimal_return_type!(utf8_to_int_type, DataType::Int64, DataType::Int32);
 
 /// Creates a scalar function implementation for the given function.
@@ -388,6 +389,7 @@ pub mod test {
     }
 
     #[test]
+    #[cfg(any(feature = "string_expressions", feature = "unicode_expressions"))]
     fn string_to_int_type() {
         let v = utf8_to_int_type(&DataType::Utf8, "test").unwrap();
         assert_eq!(v, DataType::Int32);
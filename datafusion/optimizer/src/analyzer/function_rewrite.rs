@@ -25,6 +25,7 @@ use datafusion_common::{DFSchema, Result};
 use crate::utils::NamePreserver;
 use datafusion_expr::LogicalPlan;
 use datafusion_expr::expr_rewriter::FunctionRewrite;
+use datafusion_expr::registry::{FunctionRegistry, MemoryFunctionRegistry};
 use datafusion_expr::utils::merge_schema;
 use std::sync::Arc;
 
@@ -45,6 +46,7 @@ impl ApplyFunctionRewrites {
         &self,
         plan: LogicalPlan,
         options: &ConfigOptions,
+        registry: &dyn FunctionRegistry,
     ) -> Result<Transformed<LogicalPlan>> {
         // get schema representing all available input fields. This is used for data type
         // resolution only, so order does not matter here
@@ -68,7 +70,7 @@ impl ApplyFunctionRewrites {
                 let mut result = Transformed::no(expr);
                 for rewriter in self.function_rewrites.iter() {
                     result = result.transform_data(|expr| {
-                        rewriter.rewrite(expr, &schema, options)
+                        rewriter.rewrite_with_registry(expr, &schema, options, registry)
                     })?;
                 }
                 Ok(result)
@@ -85,7 +87,23 @@ impl AnalyzerRule for ApplyFunctionRewrites {
     }
 
     fn analyze(&self, plan: LogicalPlan, options: &ConfigOptions) -> Result<LogicalPlan> {
-        plan.transform_up_with_subqueries(|plan| self.rewrite_plan(plan, options))
-            .map(|res| res.data)
+        self.analyze_with_registry(plan, options, &MemoryFunctionRegistry::new())
+    }
+
+    /// Apply the rewrites to `plan`, passing `registry` to
+    /// [`FunctionRewrite::rewrite_with_registry`] so that the rewrites can look
+    /// up the functions they call.
+    ///
+    /// [`AnalyzerRule::analyze`] passes an empty registry.
+    fn analyze_with_registry(
+        &self,
+        plan: LogicalPlan,
+        options: &ConfigOptions,
+        registry: &dyn FunctionRegistry,
+    ) -> Result<LogicalPlan> {
+        plan.transform_up_with_subqueries(|plan| {
+            self.rewrite_plan(plan, options, registry)
+        })
+        .map(|res| res.data)
     }
 }
@@ -26,15 +26,20 @@ use datafusion_common::Result;
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::instant::Instant;
 use datafusion_expr::expr_rewriter::FunctionRewrite;
-use datafusion_expr::{InvariantLevel, LogicalPlan};
+use datafusion_expr::registry::{FunctionRegistry, MemoryFunctionRegistry};
+use datafusion_expr::{InvariantLevel, LogicalPlan, PlanRewrite, PolicyProvider};
 
 use crate::analyzer::resolve_grouping_function::ResolveGroupingFunction;
 use crate::analyzer::type_coercion::TypeCoercion;
 use crate::utils::log_plan;
 
 use self::function_rewrite::ApplyFunctionRewrites;
+use self::plan_rewrite::ApplyPlanRewrites;
+use self::policy::ApplyPolicies;
 
 pub mod function_rewrite;
+pub mod plan_rewrite;
+pub mod policy;
 pub mod resolve_grouping_function;
 pub mod type_coercion;
 
@@ -57,14 +62,27 @@ pub trait AnalyzerRule: Debug {
     /// Rewrite `plan`
     fn analyze(&self, plan: LogicalPlan, config: &ConfigOptions) -> Result<LogicalPlan>;
 
+    /// Rewrite `plan`, with access to the functions and other extensions
+    /// registered in the session through `registry`
+    ///
+    /// The [`Analyzer`] calls this method, which defaults to [`Self::analyze`].
+    fn analyze_with_registry(
+        &self,
+        plan: LogicalPlan,
+        config: &ConfigOptions,
+        _registry: &dyn FunctionRegistry,
+    ) -> Result<LogicalPlan> {
+        self.analyze(plan, config)
+    }
+
     /// A human readable name for this analyzer rule
     fn name(&self) -> &str;
 }
 
 /// Rule-based Analyzer.
 ///
-/// Applies [`FunctionRewrite`]s and [`AnalyzerRule`]s to transform a
-/// [`LogicalPlan`] in preparation for execution.
+/// Applies [`PolicyProvider`]s, [`FunctionRewrite`]s, [`AnalyzerRule`]s and
+/// [`PlanRewrite`]s to transform a [`LogicalPlan`] in preparation for execution.
 ///
 /// For example, the `Analyzer` applies type coercion to ensure the types of
 /// operands match the types required by functions.
@@ -74,6 +92,10 @@ pub struct Analyzer {
     pub function_rewrites: Vec<Arc<dyn FunctionRewrite + Send + Sync>>,
     /// All rules to apply
     pub rules: Vec<Arc<dyn AnalyzerRule + Send + Sync>>,
+    /// Plan rewrites to apply after the analysis passes
+    pub plan_rewrites: Vec<Arc<dyn PlanRewrite + Send + Sync>>,
+    /// Policies to apply to the table scans before the analysis passes
+    pub policy_providers: Vec<Arc<dyn PolicyProvider>>,
 }
 
 impl Default for Analyzer {
@@ -97,6 +119,8 @@ impl Analyzer {
         Self {
             function_rewrites: vec![],
             rules,
+            plan_rewrites: vec![],
+            policy_providers: vec![],
         }
     }
 
@@ -113,12 +137,56 @@ impl Analyzer {
         &self.function_rewrites
     }
 
+    /// Add a plan rewrite rule
+    pub fn add_plan_rewrite(&mut self, rewrite: Arc<dyn PlanRewrite + Send + Sync>) {
+        self.plan_rewrites.push(rewrite);
+    }
+
+    /// return the list of plan rewrites in this analyzer
+    pub fn plan_rewrites(&self) -> &[Arc<dyn PlanRewrite + Send + Sync>] {
+        &self.plan_rewrites
+    }
+
+    /// Add a policy provider
+    pub fn add_policy_provider(&mut self, provider: Arc<dyn PolicyProvider>) {
+        self.policy_providers.push(provider);
+    }
+
+    /// return the list of policy providers in this analyzer
+    pub fn policy_providers(&self) -> &[Arc<dyn PolicyProvider>] {
+        &self.policy_providers
+    }
+
     /// Analyze the logical plan by applying analyzer rules, and
     /// do necessary check and fail the invalid plans
+    ///
+    /// The [`FunctionRewrite`]s and [`AnalyzerRule`]s are passed an empty
+    /// [`FunctionRegistry`], use [`Self::execute_and_check_with_registry`] to
+    /// let them look up the registered functions.
     pub fn execute_and_check<F>(
         &self,
         plan: LogicalPlan,
         config: &ConfigOptions,
+        observer: F,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn AnalyzerRule),
+    {
+        self.execute_and_check_with_registry(
+            plan,
+            config,
+            &MemoryFunctionRegistry::new(),
+            observer,
+        )
+    }
+
+    /// Analyze the logical plan like [`Self::execute_and_check`], passing
+    /// `registry` to the [`FunctionRewrite`]s and [`AnalyzerRule`]s
+    pub fn execute_and_check_with_registry<F>(
+        &self,
+        plan: LogicalPlan,
+        config: &ConfigOptions,
+        registry: &dyn FunctionRegistry,
         mut observer: F,
     ) -> Result<LogicalPlan>
     where
@@ -131,31 +199,52 @@ impl Analyzer {
         let start_time = Instant::now();
         let mut new_plan = plan;
 
-        // Create an analyzer pass that rewrites `Expr`s to function_calls, as
+        // Apply the policies first, so that the filters and masks they add
+        // are analyzed like the rest of the plan
+        if !self.policy_providers.is_empty() {
+            let rule = ApplyPolicies::new(self.policy_providers.clone());
+            new_plan = rule
+                .analyze(new_plan, config)
+                .map_err(|e| e.context(rule.name()))?;
+            log_plan(rule.name(), &new_plan);
+            observer(&new_plan, &rule);
+        }
+
+        // Run an analyzer pass that rewrites `Expr`s to function_calls, as
         // appropriate.
         //
         // Note this is run before all other rules since it rewrites based on
         // the argument types (List or Scalar), and TypeCoercion may cast the
         // argument types from Scalar to List.
-        let expr_to_function: Option<Arc<dyn AnalyzerRule + Send + Sync>> =
-            if self.function_rewrites.is_empty() {
-                None
-            } else {
-                Some(Arc::new(ApplyFunctionRewrites::new(
-                    self.function_rewrites.clone(),
-                )))
-            };
-        let rules = expr_to_function.iter().chain(self.rules.iter());
+        if !self.function_rewrites.is_empty() {
+            let rule = ApplyFunctionRewrites::new(self.function_rewrites.clone());
+            new_plan = rule
+                .analyze_with_registry(new_plan, config, registry)
+                .map_err(|e| e.context(rule.name()))?;
+            log_plan(rule.name(), &new_plan);
+            observer(&new_plan, &rule);
+        }
 
         // TODO add common rule executor for Analyzer and Optimizer
-        for rule in rules {
+        for rule in self.rules.iter() {
             new_plan = rule
-                .analyze(new_plan, config)
+                .analyze_with_registry(new_plan, config, registry)
                 .map_err(|e| e.context(rule.name()))?;
             log_plan(rule.name(), &new_plan);
             observer(&new_plan, rule.as_ref());
         }
 
+        // Plan rewrites run last, so that they see the analyzed plan and
+        // their output is passed to the optimizer as is
+        if !self.plan_rewrites.is_empty() {
+            let rule = ApplyPlanRewrites::new(self.plan_rewrites.clone());
+            new_plan = rule
+                .analyze(new_plan, config)
+                .map_err(|e| e.context(rule.name()))?;
+            log_plan(rule.name(), &new_plan);
+            observer(&new_plan, &rule);
+        }
+
         // verify at the end, after the last LP analyzer pass, that the plan is executable.
         new_plan
             .check_invariants(InvariantLevel::Executable)
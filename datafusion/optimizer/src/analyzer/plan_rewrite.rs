@@ -0,0 +1,71 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ApplyPlanRewrites`] to replace fragments of a plan using [`PlanRewrite`]s
+
+use super::AnalyzerRule;
+use datafusion_common::Result;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::Transformed;
+use datafusion_expr::{LogicalPlan, PlanRewrite, assert_expected_schema};
+use std::sync::Arc;
+
+/// Analyzer rule that invokes [`PlanRewrite`]s on every node of a plan,
+/// bottom-up
+#[derive(Default, Debug)]
+pub struct ApplyPlanRewrites {
+    /// Plan rewrites to apply
+    plan_rewrites: Vec<Arc<dyn PlanRewrite + Send + Sync>>,
+}
+
+impl ApplyPlanRewrites {
+    pub fn new(plan_rewrites: Vec<Arc<dyn PlanRewrite + Send + Sync>>) -> Self {
+        Self { plan_rewrites }
+    }
+
+    /// Rewrite a single plan node using the provided rewriters
+    fn rewrite_plan(
+        &self,
+        plan: LogicalPlan,
+        options: &ConfigOptions,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let mut result = Transformed::no(plan);
+        for rewriter in self.plan_rewrites.iter() {
+            result = result.transform_data(|plan| {
+                let schema = Arc::clone(plan.schema());
+                let rewritten = rewriter.rewrite(plan, options)?;
+                if rewritten.transformed {
+                    assert_expected_schema(&schema, &rewritten.data)
+                        .map_err(|e| e.context(rewriter.name()))?;
+                }
+                Ok(rewritten)
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+impl AnalyzerRule for ApplyPlanRewrites {
+    fn name(&self) -> &str {
+        "apply_plan_rewrites"
+    }
+
+    fn analyze(&self, plan: LogicalPlan, options: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform_up_with_subqueries(|plan| self.rewrite_plan(plan, options))
+            .map(|res| res.data)
+    }
+}
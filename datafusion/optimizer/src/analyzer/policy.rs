@@ -0,0 +1,146 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ApplyPolicies`] to enforce the policies of [`PolicyProvider`]s
+
+use super::AnalyzerRule;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{Column, DFSchema, Result};
+use datafusion_expr::expr_rewriter::NamePreserver;
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{
+    Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder, PolicyProvider, TableScan,
+};
+use std::sync::Arc;
+
+/// Analyzer rule that applies the row filters and column masks of
+/// [`PolicyProvider`]s to every [`TableScan`] of a plan
+#[derive(Default, Debug)]
+pub struct ApplyPolicies {
+    /// Policy providers to consult
+    policy_providers: Vec<Arc<dyn PolicyProvider>>,
+}
+
+impl ApplyPolicies {
+    pub fn new(policy_providers: Vec<Arc<dyn PolicyProvider>>) -> Self {
+        Self { policy_providers }
+    }
+
+    /// Apply the policies of the table scanned by `scan`, if any
+    fn apply_policies(
+        &self,
+        scan: TableScan,
+        options: &ConfigOptions,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let table_schema = DFSchema::try_from_qualified_schema(
+            scan.table_name.clone(),
+            &scan.source.schema(),
+        )?;
+        let mut row_filters = vec![];
+        let mut column_masks = vec![];
+        for provider in &self.policy_providers {
+            if let Some(row_filter) =
+                provider.row_filter(&scan.table_name, &table_schema, options)?
+            {
+                row_filters.push(row_filter);
+            }
+            let masks =
+                provider.column_masks(&scan.table_name, &table_schema, options)?;
+            if !masks.is_empty() {
+                column_masks.push(masks);
+            }
+        }
+        if row_filters.is_empty() && column_masks.is_empty() {
+            return Ok(Transformed::no(LogicalPlan::TableScan(scan)));
+        }
+
+        let output_schema = Arc::clone(&scan.projected_schema);
+        let mut builder = match conjunction(row_filters) {
+            None => LogicalPlanBuilder::from(LogicalPlan::TableScan(scan)),
+            Some(row_filter) => {
+                // The rows are filtered before the projection and fetch of
+                // the scan, as the filter may refer to any column
+                let TableScan {
+                    table_name,
+                    source,
+                    projection,
+                    filters,
+                    fetch,
+                    ..
+                } = scan;
+                let num_columns = table_schema.fields().len();
+                let projection = projection.map(|projection| {
+                    // keep the metadata columns the scan reads
+                    (0..num_columns)
+                        .chain(projection.into_iter().filter(|i| *i >= num_columns))
+                        .collect()
+                });
+                let builder = LogicalPlanBuilder::scan_with_filters(
+                    table_name, source, projection, filters,
+                )?
+                .filter(row_filter)?;
+                match fetch {
+                    Some(fetch) => builder.limit(0, Some(fetch))?,
+                    None => builder,
+                }
+            }
+        };
+
+        // Project the output columns of the scan, replacing the masked ones,
+        // with one projection per provider so that masks compose
+        if column_masks.is_empty() {
+            column_masks.push(Default::default());
+        }
+        let name_preserver = NamePreserver::new_for_projection();
+        for masks in column_masks {
+            let input_schema = Arc::clone(builder.schema());
+            let exprs = output_schema
+                .iter()
+                .map(|(qualifier, field)| {
+                    let column = Expr::Column(Column::from((qualifier, field)));
+                    match masks.get(field.name()) {
+                        Some(mask) => {
+                            let name = name_preserver.save(&column);
+                            let mask = mask
+                                .clone()
+                                .cast_to(field.data_type(), input_schema.as_ref())?;
+                            Ok(name.restore(mask))
+                        }
+                        None => Ok(column),
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            builder = builder.project(exprs)?;
+        }
+        builder.build().map(Transformed::yes)
+    }
+}
+
+impl AnalyzerRule for ApplyPolicies {
+    fn name(&self) -> &str {
+        "apply_policies"
+    }
+
+    fn analyze(&self, plan: LogicalPlan, options: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform_up_with_subqueries(|plan| match plan {
+            LogicalPlan::TableScan(scan) => self.apply_policies(scan, options),
+            plan => Ok(Transformed::no(plan)),
+        })
+        .map(|res| res.data)
+    }
+}
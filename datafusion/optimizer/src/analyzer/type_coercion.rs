@@ -42,6 +42,7 @@ use datafusion_expr::expr::{
 use datafusion_expr::expr_rewriter::coerce_plan_expr_for_schema;
 use datafusion_expr::expr_schema::cast_subquery;
 use datafusion_expr::logical_plan::Subquery;
+use datafusion_expr::registry::{CastRegistry, FunctionRegistry};
 use datafusion_expr::type_coercion::binary::{
     comparison_coercion, like_coercion, type_union_coercion,
 };
@@ -97,24 +98,48 @@ impl AnalyzerRule for TypeCoercion {
     }
 
     fn analyze(&self, plan: LogicalPlan, config: &ConfigOptions) -> Result<LogicalPlan> {
-        static EMPTY_SCHEMA: LazyLock<DFSchema> = LazyLock::new(DFSchema::empty);
-
-        // recurse
-        let transformed_plan = plan
-            .transform_up_with_subqueries(|plan| analyze_internal(&EMPTY_SCHEMA, plan))?
-            .data;
+        analyze_with_cast_registry(plan, config, None)
+    }
 
-        // finish
-        coerce_output(transformed_plan, config)
+    /// Coerce the types in `plan`, falling back to the casts of the
+    /// [`CastRegistry`] of `registry` for operands that can not otherwise be
+    /// coerced
+    fn analyze_with_registry(
+        &self,
+        plan: LogicalPlan,
+        config: &ConfigOptions,
+        registry: &dyn FunctionRegistry,
+    ) -> Result<LogicalPlan> {
+        let cast_registry = registry.cast_registry();
+        analyze_with_cast_registry(plan, config, cast_registry.as_deref())
     }
 }
 
+fn analyze_with_cast_registry(
+    plan: LogicalPlan,
+    config: &ConfigOptions,
+    cast_registry: Option<&dyn CastRegistry>,
+) -> Result<LogicalPlan> {
+    static EMPTY_SCHEMA: LazyLock<DFSchema> = LazyLock::new(DFSchema::empty);
+
+    // recurse
+    let transformed_plan = plan
+        .transform_up_with_subqueries(|plan| {
+            analyze_internal(&EMPTY_SCHEMA, plan, cast_registry)
+        })?
+        .data;
+
+    // finish
+    coerce_output(transformed_plan, config)
+}
+
 /// use the external schema to handle the correlated subqueries case
 ///
 /// Assumes that children have already been optimized
 fn analyze_internal(
     external_schema: &DFSchema,
     plan: LogicalPlan,
+    cast_registry: Option<&dyn CastRegistry>,
 ) -> Result<Transformed<LogicalPlan>> {
     // get schema representing all available input fields. This is used for data type
     // resolution only, so order does not matter here
@@ -133,15 +158,21 @@ fn analyze_internal(
     // select t2.c2 from t1 where t1.c1 in (select t2.c1 from t2 where t2.c2=t1.c3)
     schema.merge(external_schema);
 
-    // Coerce filter predicates to boolean (handles `WHERE NULL`)
-    let plan = if let LogicalPlan::Filter(mut filter) = plan {
-        filter.predicate = filter.predicate.cast_to(&DataType::Boolean, &schema)?;
-        LogicalPlan::Filter(filter)
-    } else {
-        plan
+    // Coerce filter predicates to boolean (handles `WHERE NULL`). With user
+    // defined casts, the type of the predicate may only be known once its
+    // operands are coerced below.
+    let plan = match plan {
+        LogicalPlan::Filter(mut filter)
+            if cast_registry.is_none() || filter.predicate.get_type(&schema).is_ok() =>
+        {
+            filter.predicate = filter.predicate.cast_to(&DataType::Boolean, &schema)?;
+            LogicalPlan::Filter(filter)
+        }
+        plan => plan,
     };
 
-    let mut expr_rewrite = TypeCoercionRewriter::new(&schema);
+    let mut expr_rewrite =
+        TypeCoercionRewriter::new(&schema).with_cast_registry(cast_registry);
 
     let name_preserver = NamePreserver::new(&plan);
     // apply coercion rewrite all expressions in the plan individually
@@ -159,13 +190,28 @@ fn analyze_internal(
 /// Rewrite expressions to apply type coercion.
 pub struct TypeCoercionRewriter<'a> {
     pub(crate) schema: &'a DFSchema,
+    /// User defined casts to fall back to
+    cast_registry: Option<&'a dyn CastRegistry>,
 }
 
 impl<'a> TypeCoercionRewriter<'a> {
     /// Create a new [`TypeCoercionRewriter`] with a provided schema
     /// representing both the inputs and output of the [`LogicalPlan`] node.
     pub fn new(schema: &'a DFSchema) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            cast_registry: None,
+        }
+    }
+
+    /// Use the casts of `cast_registry` to coerce the operands of binary
+    /// expressions that can not otherwise be coerced to a common type
+    pub fn with_cast_registry(
+        mut self,
+        cast_registry: Option<&'a dyn CastRegistry>,
+    ) -> Self {
+        self.cast_registry = cast_registry;
+        self
     }
 
     /// Coerce the [`LogicalPlan`].
@@ -209,6 +255,23 @@ impl<'a> TypeCoercionRewriter<'a> {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        // ASOF match expressions are coerced as a single comparison, and the
+        // tolerance must be boolean like the join filter
+        if let Some(mut asof_match) = join.asof_match.take() {
+            (asof_match.left, asof_match.right) = self.coerce_binary_op(
+                asof_match.left,
+                join.left.schema(),
+                asof_match.op,
+                asof_match.right,
+                join.right.schema(),
+            )?;
+            asof_match.tolerance = asof_match
+                .tolerance
+                .map(|expr| self.coerce_join_filter(expr))
+                .transpose()?;
+            join.asof_match = Some(asof_match);
+        }
+
         // Join filter must be boolean
         join.filter = join
             .filter
@@ -300,8 +363,21 @@ impl<'a> TypeCoercionRewriter<'a> {
         let left_data_type = left.get_type(left_schema)?;
         let right_data_type = right.get_type(right_schema)?;
         let (left_type, right_type) =
-            BinaryTypeCoercer::new(&left_data_type, &op, &right_data_type)
-                .get_input_types()?;
+            match BinaryTypeCoercer::new(&left_data_type, &op, &right_data_type)
+                .get_input_types()
+            {
+                Ok(types) => types,
+                Err(e) => {
+                    return self
+                        .coerce_binary_op_with_user_defined_cast(
+                            left,
+                            left_schema,
+                            right,
+                            right_schema,
+                        )?
+                        .ok_or(e);
+                }
+            };
         let left_cast_ok = can_cast_types(&left_data_type, &left_type);
         let right_cast_ok = can_cast_types(&right_data_type, &right_type);
 
@@ -335,6 +411,35 @@ impl<'a> TypeCoercionRewriter<'a> {
         Ok((left_expr, right_expr))
     }
 
+    /// Coerce the operands of a binary expression by casting one of them to
+    /// the type of the other with a user defined cast, if one is registered.
+    ///
+    /// Casting the right operand is preferred, as it is often a literal.
+    fn coerce_binary_op_with_user_defined_cast(
+        &self,
+        left: Expr,
+        left_schema: &DFSchema,
+        right: Expr,
+        right_schema: &DFSchema,
+    ) -> Result<Option<(Expr, Expr)>> {
+        let Some(cast_registry) = self.cast_registry else {
+            return Ok(None);
+        };
+        let (_, left_field) = left.to_field(left_schema)?;
+        let (_, right_field) = right.to_field(right_schema)?;
+        let cast = |expr: Expr, field: &Field| {
+            let field = field.clone().with_name("").with_nullable(true);
+            Expr::Cast(Cast::new_from_field(Box::new(expr), Arc::new(field)))
+        };
+        if cast_registry.find_cast(&right_field, &left_field).is_some() {
+            Ok(Some((left, cast(right, &left_field))))
+        } else if cast_registry.find_cast(&left_field, &right_field).is_some() {
+            Ok(Some((cast(left, &right_field), right)))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn coerce_date_time_math_op(
         expr: Expr,
         op: &Operator,
@@ -457,8 +562,12 @@ impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
                 outer_ref_columns,
                 spans,
             }) => {
-                let new_plan =
-                    analyze_internal(self.schema, Arc::unwrap_or_clone(subquery))?.data;
+                let new_plan = analyze_internal(
+                    self.schema,
+                    Arc::unwrap_or_clone(subquery),
+                    self.cast_registry,
+                )?
+                .data;
                 Ok(Transformed::yes(Expr::ScalarSubquery(Subquery {
                     subquery: Arc::new(new_plan),
                     outer_ref_columns,
@@ -469,6 +578,7 @@ impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
                 let new_plan = analyze_internal(
                     self.schema,
                     Arc::unwrap_or_clone(subquery.subquery),
+                    self.cast_registry,
                 )?
                 .data;
                 Ok(Transformed::yes(Expr::Exists(Exists {
@@ -488,6 +598,7 @@ impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
                 let new_plan = analyze_internal(
                     self.schema,
                     Arc::unwrap_or_clone(subquery.subquery),
+                    self.cast_registry,
                 )?
                 .data;
                 let expr_type = expr.get_type(self.schema)?;
@@ -517,6 +628,7 @@ impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
                 let new_plan = analyze_internal(
                     self.schema,
                     Arc::unwrap_or_clone(subquery.subquery),
+                    self.cast_registry,
                 )?
                 .data;
                 let expr_type = expr.get_type(self.schema)?;
@@ -956,7 +1068,7 @@ fn extract_window_frame_target_type(col_type: &DataType) -> Result<DataType> {
     } else if let DataType::Dictionary(_, value_type) = col_type {
         extract_window_frame_target_type(value_type)
     } else {
-        internal_err!("Cannot run range queries on datatype: {col_type}")
+        plan_err!("Cannot run range queries on datatype: {col_type}")
     }
 }
 
@@ -974,10 +1086,18 @@ fn coerce_window_frame(
                 .first()
                 .map(|s| s.expr.get_type(schema))
                 .transpose()?;
-            if let Some(col_type) = current_types {
-                extract_window_frame_target_type(&col_type)?
-            } else {
+            let Some(col_type) = current_types else {
                 return internal_err!("ORDER BY column cannot be empty");
+            };
+            match extract_window_frame_target_type(&col_type) {
+                Ok(target_type) => target_type,
+                // Frames whose bounds are UNBOUNDED or CURRENT ROW (which are
+                // the frames accepting multiple ORDER BY columns) only compare
+                // the ORDER BY values, so they support any orderable type
+                Err(_) if window_frame.can_accept_multi_orderby() => {
+                    return Ok(window_frame);
+                }
+                Err(e) => return Err(e),
             }
         }
         WindowFrameUnits::Rows | WindowFrameUnits::Groups => DataType::UInt64,
@@ -2268,7 +2388,7 @@ mod test {
             vec![Field::new("a", DataType::Int64, true)].into(),
             std::collections::HashMap::new(),
         )?);
-        let mut rewriter = TypeCoercionRewriter { schema: &schema };
+        let mut rewriter = TypeCoercionRewriter::new(&schema);
         let expr = is_true(lit(12i32).gt(lit(13i64)));
         let expected = is_true(cast(lit(12i32), DataType::Int64).gt(lit(13i64)));
         let result = expr.rewrite(&mut rewriter).data()?;
@@ -2279,7 +2399,7 @@ mod test {
             vec![Field::new("a", DataType::Int64, true)].into(),
             std::collections::HashMap::new(),
         )?);
-        let mut rewriter = TypeCoercionRewriter { schema: &schema };
+        let mut rewriter = TypeCoercionRewriter::new(&schema);
         let expr = is_true(lit(12i32).eq(lit(13i64)));
         let expected = is_true(cast(lit(12i32), DataType::Int64).eq(lit(13i64)));
         let result = expr.rewrite(&mut rewriter).data()?;
@@ -2290,7 +2410,7 @@ mod test {
             vec![Field::new("a", DataType::Int64, true)].into(),
             std::collections::HashMap::new(),
         )?);
-        let mut rewriter = TypeCoercionRewriter { schema: &schema };
+        let mut rewriter = TypeCoercionRewriter::new(&schema);
         let expr = is_true(lit(12i32).lt(lit(13i64)));
         let expected = is_true(cast(lit(12i32), DataType::Int64).lt(lit(13i64)));
         let result = expr.rewrite(&mut rewriter).data()?;
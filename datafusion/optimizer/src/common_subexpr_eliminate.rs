@@ -25,20 +25,16 @@ use crate::{OptimizerConfig, OptimizerRule};
 
 use crate::optimizer::ApplyOrder;
 use crate::utils::NamePreserver;
-use datafusion_common::alias::AliasGenerator;
 
-use datafusion_common::cse::{CSE, CSEController, FoundCommonNodes};
+use datafusion_common::cse::{CSE, FoundCommonNodes};
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{Column, DFSchema, DFSchemaRef, Result, qualified_name};
-use datafusion_expr::expr::{Alias, HigherOrderFunction, ScalarFunction};
+use datafusion_expr::expr::Alias;
+use datafusion_expr::expr_rewriter::{CSE_PREFIX, ExprCSEController, ExprMask};
 use datafusion_expr::logical_plan::{
     Aggregate, Filter, LogicalPlan, Projection, Sort, Window,
 };
-use datafusion_expr::{
-    BinaryExpr, Case, Expr, ExpressionPlacement, Operator, SortExpr, col,
-};
-
-const CSE_PREFIX: &str = "__common_expr";
+use datafusion_expr::{Expr, SortExpr};
 
 /// Performs Common Sub-expression Elimination optimization.
 ///
@@ -584,6 +580,7 @@ impl OptimizerRule for CommonSubexprEliminate {
             | LogicalPlan::Dml(_)
             | LogicalPlan::Copy(_)
             | LogicalPlan::Unnest(_)
+            | LogicalPlan::LateralTableFunction(_)
             | LogicalPlan::RecursiveQuery(_) => {
                 // This rule handles recursion itself in a `ApplyOrder::TopDown` like
                 // manner. Process uncorrelated subqueries in expressions
@@ -611,163 +608,6 @@ impl OptimizerRule for CommonSubexprEliminate {
     }
 }
 
-/// Which type of [expressions](Expr) should be considered for rewriting?
-#[derive(Debug, Clone, Copy)]
-enum ExprMask {
-    /// Ignores:
-    ///
-    /// - [`Literal`](Expr::Literal)
-    /// - [`Columns`](Expr::Column)
-    /// - [`ScalarVariable`](Expr::ScalarVariable)
-    /// - [`Alias`](Expr::Alias)
-    /// - [`Wildcard`](Expr::Wildcard)
-    /// - [`AggregateFunction`](Expr::AggregateFunction)
-    Normal,
-
-    /// Like [`Normal`](Self::Normal), but includes [`AggregateFunction`](Expr::AggregateFunction).
-    NormalAndAggregates,
-}
-
-struct ExprCSEController<'a> {
-    alias_generator: &'a AliasGenerator,
-    mask: ExprMask,
-
-    // how many aliases have we seen so far
-    alias_counter: usize,
-}
-
-impl<'a> ExprCSEController<'a> {
-    fn new(alias_generator: &'a AliasGenerator, mask: ExprMask) -> Self {
-        Self {
-            alias_generator,
-            mask,
-            alias_counter: 0,
-        }
-    }
-}
-
-impl CSEController for ExprCSEController<'_> {
-    type Node = Expr;
-
-    fn conditional_children(node: &Expr) -> Option<(Vec<&Expr>, Vec<&Expr>)> {
-        match node {
-            // In case of `ScalarFunction`s and `HigherOrderFunction`s we don't know which children are surely
-            // executed so start visiting all children conditionally and stop the
-            // recursion with `TreeNodeRecursion::Jump`.
-            Expr::ScalarFunction(ScalarFunction { func, args }) => {
-                func.conditional_arguments(args)
-            }
-            Expr::HigherOrderFunction(HigherOrderFunction { func, args }) => {
-                func.conditional_arguments(args)
-            }
-
-            // In case of `And` and `Or` the first child is surely executed, but we
-            // account subexpressions as conditional in the second.
-            Expr::BinaryExpr(BinaryExpr {
-                left,
-                op: Operator::And | Operator::Or,
-                right,
-            }) => Some((vec![left.as_ref()], vec![right.as_ref()])),
-
-            // In case of `Case` the optional base expression and the first when
-            // expressions are surely executed, but we account subexpressions as
-            // conditional in the others.
-            Expr::Case(Case {
-                expr,
-                when_then_expr,
-                else_expr,
-            }) => Some((
-                expr.iter()
-                    .map(|e| e.as_ref())
-                    .chain(when_then_expr.iter().take(1).map(|(when, _)| when.as_ref()))
-                    .collect(),
-                when_then_expr
-                    .iter()
-                    .take(1)
-                    .map(|(_, then)| then.as_ref())
-                    .chain(
-                        when_then_expr
-                            .iter()
-                            .skip(1)
-                            .flat_map(|(when, then)| [when.as_ref(), then.as_ref()]),
-                    )
-                    .chain(else_expr.iter().map(|e| e.as_ref()))
-                    .collect(),
-            )),
-            _ => None,
-        }
-    }
-
-    fn is_valid(node: &Expr) -> bool {
-        !node.is_volatile_node()
-            && !matches!(node, Expr::Lambda(_) | Expr::LambdaVariable(_))
-    }
-
-    fn is_ignored(&self, node: &Expr) -> bool {
-        // MoveTowardsLeafNodes expressions (e.g. get_field) are cheap struct
-        // field accesses that the ExtractLeafExpressions / PushDownLeafProjections
-        // rules deliberately duplicate when needed (one copy for a filter
-        // predicate, another for an output column). CSE deduplicating them
-        // creates intermediate projections that fight with those rules,
-        // causing optimizer instability — ExtractLeafExpressions will undo
-        // the dedup, creating an infinite loop that runs until the iteration
-        // limit is hit. Skip them.
-        if node.placement() == ExpressionPlacement::MoveTowardsLeafNodes {
-            return true;
-        }
-
-        // TODO: remove the next line after `Expr::Wildcard` is removed
-        #[expect(deprecated)]
-        let is_normal_minus_aggregates = matches!(
-            node,
-            // TODO: there's an argument for removing `Literal` from here,
-            // maybe using `Expr::placemement().should_push_to_leaves()` instead
-            // so that we extract common literals and don't broadcast them to num_batch_rows multiple times.
-            // However that currently breaks things like `percentile_cont()` which expect literal arguments
-            // (and would instead be getting `col(__common_expr_n)`).
-            Expr::Literal(..)
-                | Expr::Column(..)
-                | Expr::ScalarVariable(..)
-                | Expr::Alias(..)
-                | Expr::Wildcard { .. }
-                | Expr::Lambda(_)
-                | Expr::LambdaVariable(_)
-        );
-
-        let is_aggr = matches!(node, Expr::AggregateFunction(..));
-
-        match self.mask {
-            ExprMask::Normal => is_normal_minus_aggregates || is_aggr,
-            ExprMask::NormalAndAggregates => is_normal_minus_aggregates,
-        }
-    }
-
-    fn generate_alias(&self) -> String {
-        self.alias_generator.next(CSE_PREFIX)
-    }
-
-    fn rewrite(&mut self, node: &Self::Node, alias: &str) -> Self::Node {
-        // alias the expressions without an `Alias` ancestor node
-        if self.alias_counter > 0 {
-            col(alias)
-        } else {
-            self.alias_counter += 1;
-            col(alias).alias(node.schema_name().to_string())
-        }
-    }
-
-    fn rewrite_f_down(&mut self, node: &Expr) {
-        if matches!(node, Expr::Alias(_)) {
-            self.alias_counter += 1;
-        }
-    }
-    fn rewrite_f_up(&mut self, node: &Expr) {
-        if matches!(node, Expr::Alias(_)) {
-            self.alias_counter -= 1
-        }
-    }
-}
-
 impl Default for CommonSubexprEliminate {
     fn default() -> Self {
         Self::new()
@@ -845,7 +685,7 @@ mod test {
         ScalarUDF, ScalarUDFImpl, Signature, SimpleAggregateUDF, Volatility,
         grouping_set, is_null, not,
     };
-    use datafusion_expr::{lit, logical_plan::builder::LogicalPlanBuilder};
+    use datafusion_expr::{col, lit, logical_plan::builder::LogicalPlanBuilder};
 
     use super::*;
     use crate::assert_optimized_plan_eq_snapshot;
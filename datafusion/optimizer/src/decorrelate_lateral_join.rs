@@ -17,18 +17,23 @@
 
 //! [`DecorrelateLateralJoin`] decorrelates logical plans produced by lateral joins.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::decorrelate::{PullUpCorrelatedExpr, UN_MATCHED_ROW_INDICATOR};
 use crate::optimizer::ApplyOrder;
+use crate::push_down_filter::replace_cols_by_name;
 use crate::utils::evaluates_to_null;
 use crate::{OptimizerConfig, OptimizerRule};
-use datafusion_expr::{Expr, Join, expr};
+use datafusion_expr::expr_rewriter::strip_outer_reference;
+use datafusion_expr::{EmptyRelation, Expr, Join, expr};
 
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
 };
-use datafusion_common::{Column, DFSchema, Result, ScalarValue, TableReference};
+use datafusion_common::{
+    Column, DFSchema, NullEquality, Result, ScalarValue, TableReference,
+};
 use datafusion_expr::logical_plan::{JoinType, Subquery};
 use datafusion_expr::utils::conjunction;
 use datafusion_expr::{LogicalPlan, LogicalPlanBuilder, SubqueryAlias};
@@ -79,6 +84,10 @@ fn rewrite_internal(join: Join) -> Result<Transformed<LogicalPlan>> {
     }
     let original_join_type = join.join_type;
 
+    if let Some(plan) = rewrite_lateral_unnest(&join)? {
+        return Ok(Transformed::new(plan, true, TreeNodeRecursion::Jump));
+    }
+
     // The right side is wrapped in a Subquery node when it contains outer
     // references. Quickly skip joins that don't have this structure.
     let Some((subquery, alias)) = extract_lateral_subquery(join.right.as_ref()) else {
@@ -324,6 +333,137 @@ fn rewrite_internal(join: Join) -> Result<Transformed<LogicalPlan>> {
     Ok(Transformed::new(new_plan, true, TreeNodeRecursion::Jump))
 }
 
+/// Rewrites a lateral join with an `UNNEST` of columns of the left input,
+/// such as `t CROSS JOIN UNNEST(t.arr) AS u(x)`, into an unnest of those
+/// columns in place, repeating each left row for every element of its lists:
+///
+/// ```text
+/// Join: Left                         Projection: t.a, t.arr, <unnested> AS u.x
+///   t                                  Unnest: lists[placeholder] outer
+///   SubqueryAlias: u            =>       Projection: t.a, t.arr, t.arr AS placeholder
+///     Subquery:                            t
+///       Projection: ...
+///         Unnest: lists[placeholder]
+///           Projection: outer_ref(t.arr) AS placeholder
+///             EmptyRelation: rows=1
+/// ```
+///
+/// LEFT joins, which must have no condition other than `ON true`, use an
+/// outer unnest that keeps rows whose lists are empty or null. The condition
+/// of INNER joins becomes a filter on the output.
+///
+/// Returns `None` if `join` does not have this shape.
+fn rewrite_lateral_unnest(join: &Join) -> Result<Option<LogicalPlan>> {
+    let Some((subquery, _)) = extract_lateral_subquery(join.right.as_ref()) else {
+        return Ok(None);
+    };
+
+    // Collect the projections above the unnest, top-most first
+    let mut projections = vec![];
+    let mut plan = subquery.subquery.as_ref();
+    while let LogicalPlan::Projection(projection) = plan {
+        projections.push(projection);
+        plan = projection.input.as_ref();
+    }
+    let LogicalPlan::Unnest(unnest) = plan else {
+        return Ok(None);
+    };
+    let (Some(top), LogicalPlan::Projection(input_projection)) =
+        (projections.first(), unnest.input.as_ref())
+    else {
+        return Ok(None);
+    };
+    let LogicalPlan::EmptyRelation(EmptyRelation {
+        produce_one_row: true,
+        ..
+    }) = input_projection.input.as_ref()
+    else {
+        return Ok(None);
+    };
+
+    let filter = match join.join_type {
+        JoinType::Inner => {
+            if !join.on.is_empty()
+                && join.null_equality != NullEquality::NullEqualsNothing
+            {
+                return Ok(None);
+            }
+            conjunction(
+                join.on
+                    .iter()
+                    .map(|(l, r)| l.clone().eq(r.clone()))
+                    .chain(join.filter.clone()),
+            )
+        }
+        JoinType::Left => {
+            // Rows without a match must keep nulls rather than unnested
+            // values, which an outer unnest only does for empty lists
+            let on_true = matches!(
+                &join.filter,
+                None | Some(Expr::Literal(ScalarValue::Boolean(Some(true)), _))
+            );
+            if !join.on.is_empty() || !on_true || unnest.options.ordinality {
+                return Ok(None);
+            }
+            None
+        }
+        _ => return Ok(None),
+    };
+
+    // Express each output column of the subquery in terms of the unnest
+    let mut exprs = top
+        .expr
+        .iter()
+        .cloned()
+        .map(Expr::unalias)
+        .collect::<Vec<_>>();
+    for projection in &projections[1..] {
+        let replace_map = projection
+            .schema
+            .columns()
+            .iter()
+            .map(|column| column.flat_name())
+            .zip(projection.expr.iter().cloned().map(Expr::unalias))
+            .collect::<HashMap<_, _>>();
+        exprs = exprs
+            .into_iter()
+            .map(|expr| replace_cols_by_name(expr, &replace_map))
+            .collect::<Result<_>>()?;
+    }
+
+    let left_columns = join
+        .left
+        .schema()
+        .columns()
+        .into_iter()
+        .map(Expr::Column)
+        .collect::<Vec<_>>();
+    let input_exprs = left_columns.iter().cloned().chain(
+        input_projection
+            .expr
+            .iter()
+            .cloned()
+            .map(strip_outer_reference),
+    );
+    let options = unnest
+        .options
+        .clone()
+        .with_outer(join.join_type == JoinType::Left);
+    let output_exprs = exprs.into_iter().zip(join.right.schema().iter()).map(
+        |(expr, (qualifier, field))| {
+            strip_outer_reference(expr).alias_qualified(qualifier.cloned(), field.name())
+        },
+    );
+    let builder = LogicalPlanBuilder::from(Arc::clone(&join.left))
+        .project(input_exprs)?
+        .unnest_columns_with_options(unnest.exec_columns.clone(), options)?
+        .project(left_columns.into_iter().chain(output_exprs))?;
+    match filter {
+        Some(filter) => builder.filter(filter)?.build().map(Some),
+        None => builder.build().map(Some),
+    }
+}
+
 /// Extract the Subquery and optional alias from a lateral join's right side.
 fn extract_lateral_subquery(
     plan: &LogicalPlan,
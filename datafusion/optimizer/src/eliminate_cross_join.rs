@@ -240,7 +240,9 @@ fn flatten_join_inputs(
     all_filters: &mut Vec<Expr>,
 ) -> Result<()> {
     match plan {
-        LogicalPlan::Join(join) if join.join_type == JoinType::Inner => {
+        LogicalPlan::Join(join)
+            if join.join_type == JoinType::Inner && join.asof_match.is_none() =>
+        {
             if let Some(filter) = join.filter {
                 all_filters.push(filter);
             }
@@ -270,15 +272,17 @@ fn flatten_join_inputs(
 ///
 /// Must stay in sync with `flatten_join_inputs`
 fn can_flatten_join_inputs(plan: &LogicalPlan) -> bool {
-    // can only flatten inner / cross joins
+    // can only flatten inner / cross joins, ASOF joins are kept as inputs
     match plan {
-        LogicalPlan::Join(join) if join.join_type == JoinType::Inner => {}
+        LogicalPlan::Join(join)
+            if join.join_type == JoinType::Inner && join.asof_match.is_none() => {}
         _ => return false,
     };
 
     for child in plan.inputs() {
         if let LogicalPlan::Join(Join {
             join_type: JoinType::Inner,
+            asof_match: None,
             ..
         }) = child
             && !can_flatten_join_inputs(child)
@@ -347,6 +351,7 @@ fn find_inner_join(
                 schema: join_schema,
                 null_equality,
                 null_aware: false,
+                asof_match: None,
             }));
         }
     }
@@ -370,6 +375,7 @@ fn find_inner_join(
         join_constraint: JoinConstraint::On,
         null_equality,
         null_aware: false,
+        asof_match: None,
     }))
 }
 
@@ -1375,6 +1381,7 @@ mod tests {
             schema: join_schema,
             null_equality: NullEquality::NullEqualsNull, // Test preservation
             null_aware: false,
+            asof_match: None,
         });
 
         // Apply filter that can create join conditions
@@ -60,14 +60,18 @@ impl OptimizerRule for EliminateGroupByConstant {
                     })
                     .collect();
 
-                let (redundant, required): (Vec<_>, Vec<_>) = aggregate
+                let (mut redundant, mut required): (Vec<_>, Vec<_>) = aggregate
                     .group_expr
                     .iter()
                     .partition(|expr| is_redundant_group_expr(expr, &group_by_columns));
 
-                if redundant.is_empty()
-                    || (required.is_empty() && aggregate.aggr_expr.is_empty())
-                {
+                // Grouping only by constants makes no group of empty input,
+                // unlike an aggregate without GROUP BY, so keep one of them
+                if required.is_empty() && !redundant.is_empty() {
+                    required.push(redundant.remove(0));
+                }
+
+                if redundant.is_empty() {
                     return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
                 }
 
@@ -229,7 +233,7 @@ mod tests {
 
         assert_optimized_plan_equal!(plan, @r#"
         Projection: Utf8("test"), UInt32(123), count(test.c)
-          Aggregate: groupBy=[[]], aggr=[[count(test.c)]]
+          Aggregate: groupBy=[[Utf8("test")]], aggr=[[count(test.c)]]
             TableScan: test
         "#)
     }
@@ -120,6 +120,7 @@ impl OptimizerRule for EliminateOuterJoin {
                         schema: Arc::clone(&join.schema),
                         null_equality: join.null_equality,
                         null_aware: join.null_aware,
+                        asof_match: join.asof_match.clone(),
                     }));
                     Filter::try_new(filter.predicate, new_join)
                         .map(|f| Transformed::yes(LogicalPlan::Filter(f)))
@@ -77,6 +77,7 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                 schema,
                 null_equality,
                 null_aware,
+                asof_match: None,
             }) => {
                 let left_schema = left.schema();
                 let right_schema = right.schema();
@@ -119,6 +120,7 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                             // safe to override it
                             null_equality: NullEquality::NullEqualsNull,
                             null_aware,
+                            asof_match: None,
                         })));
                     }
                 }
@@ -135,6 +137,7 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                         schema,
                         null_equality,
                         null_aware,
+                        asof_match: None,
                     })))
                 } else {
                     Ok(Transformed::no(LogicalPlan::Join(Join {
@@ -147,6 +150,7 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                         schema,
                         null_equality,
                         null_aware,
+                        asof_match: None,
                     })))
                 }
             }
@@ -0,0 +1,243 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`InListToJoin`] rewrites large `IN` lists to `IN` subqueries over a `VALUES` table
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{Result, Spans};
+use datafusion_expr::Expr;
+use datafusion_expr::expr::{InList, InSubquery};
+use datafusion_expr::logical_plan::{Filter, LogicalPlan, LogicalPlanBuilder, Subquery};
+use datafusion_expr::utils::{conjunction, split_conjunction, split_conjunction_owned};
+
+/// Rewrites filter predicates `expr [NOT] IN (<literals>)` with at least
+/// `datafusion.optimizer.in_list_to_join_threshold` literals to
+/// `expr [NOT] IN (SELECT column1 FROM (VALUES ...))`.
+///
+/// The subquery is then converted to a semi (or anti) join by
+/// [`DecorrelatePredicateSubquery`], which builds a hash table of the values
+/// once instead of comparing every row to a (possibly very large) list.
+///
+/// Only `IN` lists at the top level of a filter predicate are rewritten.
+///
+/// [`DecorrelatePredicateSubquery`]: crate::decorrelate_predicate_subquery::DecorrelatePredicateSubquery
+#[derive(Default, Debug)]
+pub struct InListToJoin;
+
+impl InListToJoin {
+    #[expect(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for InListToJoin {
+    fn name(&self) -> &str {
+        "in_list_to_join"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let threshold = config.options().optimizer.in_list_to_join_threshold;
+        if threshold == 0 {
+            return Ok(Transformed::no(plan));
+        }
+
+        let LogicalPlan::Filter(filter) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+        if !split_conjunction(&filter.predicate)
+            .into_iter()
+            .any(|expr| is_large_in_list(expr, threshold))
+        {
+            return Ok(Transformed::no(LogicalPlan::Filter(filter)));
+        }
+
+        let predicates = split_conjunction_owned(filter.predicate)
+            .into_iter()
+            .map(|expr| match expr {
+                Expr::InList(in_list) if in_list.list.len() >= threshold => {
+                    in_list_to_subquery(in_list)
+                }
+                expr => Ok(expr),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let predicate = conjunction(predicates).expect("at least one predicate");
+
+        Ok(Transformed::yes(LogicalPlan::Filter(Filter::try_new(
+            predicate,
+            filter.input,
+        )?)))
+    }
+}
+
+/// Returns true if `expr` is an `IN` list of at least `threshold` literals
+fn is_large_in_list(expr: &Expr, threshold: usize) -> bool {
+    match expr {
+        Expr::InList(InList { list, .. }) => {
+            list.len() >= threshold
+                && list.iter().all(|item| matches!(item, Expr::Literal(_, _)))
+        }
+        _ => false,
+    }
+}
+
+/// Rewrites `expr [NOT] IN (v1, v2, ...)` to
+/// `expr [NOT] IN (SELECT column1 FROM (VALUES (v1), (v2), ...))`
+fn in_list_to_subquery(in_list: InList) -> Result<Expr> {
+    if !in_list
+        .list
+        .iter()
+        .all(|item| matches!(item, Expr::Literal(_, _)))
+    {
+        return Ok(Expr::InList(in_list));
+    }
+
+    let InList {
+        expr,
+        list,
+        negated,
+    } = in_list;
+    let values =
+        LogicalPlanBuilder::values(list.into_iter().map(|item| vec![item]).collect())?;
+    // `DecorrelatePredicateSubquery` expects the subquery to project its output
+    let column = Expr::Column(values.schema().columns().remove(0));
+    let subquery = values.project(vec![column])?.build()?;
+    let subquery = Subquery {
+        subquery: Arc::new(subquery),
+        outer_ref_columns: vec![],
+        spans: Spans::new(),
+    };
+    Ok(Expr::InSubquery(InSubquery::new(expr, subquery, negated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptimizerContext;
+    use crate::assert_optimized_plan_eq_snapshot;
+    use crate::test::test_table_scan;
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_expr::{col, lit};
+
+    macro_rules! assert_optimized_plan_equal {
+        (
+            $threshold:expr,
+            $plan:expr,
+            @ $expected:literal $(,)?
+        ) => {{
+            let mut options = ConfigOptions::default();
+            options.optimizer.in_list_to_join_threshold = $threshold;
+            let optimizer_ctx =
+                OptimizerContext::new_with_config_options(Arc::new(options))
+                    .with_max_passes(1);
+            let rules: Vec<Arc<dyn crate::OptimizerRule + Send + Sync>> =
+                vec![Arc::new(InListToJoin::new())];
+            assert_optimized_plan_eq_snapshot!(
+                optimizer_ctx,
+                rules,
+                $plan,
+                @ $expected,
+            )
+        }};
+    }
+
+    #[test]
+    fn in_list_to_subquery() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(
+                col("a")
+                    .in_list(vec![lit(1u32), lit(2u32), lit(3u32)], false)
+                    .and(col("b").in_list(vec![lit(1u32)], true)),
+            )?
+            .build()?;
+
+        assert_optimized_plan_equal!(
+            3,
+            plan,
+            @r"
+        Filter: test.a IN (<subquery>) AND test.b NOT IN ([UInt32(1)])
+          Subquery:
+            Projection: column1
+              Values: (UInt32(1)), (UInt32(2)), (UInt32(3))
+          TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn in_list_below_threshold() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").in_list(vec![lit(1u32), lit(2u32)], true))?
+            .build()?;
+
+        assert_optimized_plan_equal!(
+            3,
+            plan,
+            @r"
+        Filter: test.a NOT IN ([UInt32(1), UInt32(2)])
+          TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn in_list_with_non_literals() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").in_list(vec![lit(1u32), col("b"), lit(3u32)], false))?
+            .build()?;
+
+        assert_optimized_plan_equal!(
+            2,
+            plan,
+            @r"
+        Filter: test.a IN ([UInt32(1), test.b, UInt32(3)])
+          TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn in_list_to_join_disabled() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").in_list(vec![lit(1u32), lit(2u32)], false))?
+            .build()?;
+
+        assert_optimized_plan_equal!(
+            0,
+            plan,
+            @r"
+        Filter: test.a IN ([UInt32(1), UInt32(2)])
+          TableScan: test
+        "
+        )
+    }
+}
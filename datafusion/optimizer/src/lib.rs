@@ -59,12 +59,15 @@ pub mod eliminate_outer_join;
 pub mod extract_equijoin_predicate;
 pub mod extract_leaf_expressions;
 pub mod filter_null_join_keys;
+pub mod in_list_to_join;
 pub mod optimize_projections;
 pub mod optimize_unions;
 pub mod optimizer;
 pub mod propagate_empty_relation;
+pub mod push_down_aggregate;
 pub mod push_down_filter;
 pub mod push_down_limit;
+pub mod reorder_joins;
 pub mod replace_distinct_aggregate;
 pub mod rewrite_set_comparison;
 pub mod scalar_subquery_to_join;
@@ -25,12 +25,13 @@ use std::sync::Arc;
 
 use datafusion_common::{
     Column, DFSchema, HashMap, JoinType, Result, assert_eq_or_internal_err,
-    get_required_group_by_exprs_indices, internal_datafusion_err, internal_err,
+    assert_or_internal_err, get_required_group_by_exprs_indices, internal_datafusion_err,
+    internal_err,
 };
 use datafusion_expr::expr::Alias;
 use datafusion_expr::{
-    Aggregate, Distinct, EmptyRelation, Expr, Projection, TableScan, Unnest, Window,
-    logical_plan::LogicalPlan,
+    Aggregate, Distinct, EmptyRelation, Expr, Extension, Projection, TableScan, Unnest,
+    Window, logical_plan::LogicalPlan,
 };
 
 use crate::optimize_projections::required_indices::RequiredIndices;
@@ -131,6 +132,16 @@ fn optimize_projections(
     config: &dyn OptimizerConfig,
     indices: RequiredIndices,
 ) -> Result<Transformed<LogicalPlan>> {
+    // User defined nodes may drop the output columns their parents do not need,
+    // after which all of their output columns are required.
+    if let LogicalPlan::Extension(extension) = &plan
+        && let Some(pruned) = prune_extension_output(extension, &indices)?
+    {
+        let indices = RequiredIndices::new_for_all_exprs(&pruned);
+        let optimized = optimize_projections(pruned, config, indices)?;
+        return Ok(Transformed::yes(optimized.data));
+    }
+
     // Recursively rewrite any nodes that may be able to avoid computation given
     // their parents' required indices.
     match plan {
@@ -327,6 +338,24 @@ fn optimize_projections(
                 .map(|input| indices.clone().with_plan_exprs(&plan, input.schema()))
                 .collect::<Result<_>>()?
         }
+        LogicalPlan::LateralTableFunction(lateral) => {
+            // Pass the requirements of the parent on the columns of the input,
+            // which come first in the output, as well as the columns used by
+            // the arguments of the call
+            let input_len = lateral.input.schema().fields().len();
+            let required_input_indices = indices
+                .indices()
+                .iter()
+                .copied()
+                .filter(|idx| *idx < input_len)
+                .collect::<Vec<_>>();
+            vec![
+                RequiredIndices::new()
+                    .with_projection_beneficial()
+                    .with_plan_exprs(&plan, lateral.input.schema())?
+                    .append(&required_input_indices),
+            ]
+        }
         LogicalPlan::Copy(_)
         | LogicalPlan::Ddl(_)
         | LogicalPlan::Dml(_)
@@ -395,15 +424,25 @@ fn optimize_projections(
                 return Ok(Transformed::no(plan));
             }
 
-            plan.inputs()
-                .into_iter()
-                .map(|input| {
-                    indices
-                        .clone()
-                        .with_projection_beneficial()
-                        .with_plan_exprs(&plan, input.schema())
-                })
-                .collect::<Result<Vec<_>>>()?
+            // The work table is fed the full output of both terms, so when the
+            // recursive term reads it every column may be needed by a later
+            // iteration and none of them can be pruned.
+            if plan_scans_table(recursive.recursive_term.as_ref(), &recursive.name) {
+                plan.inputs()
+                    .into_iter()
+                    .map(RequiredIndices::new_for_all_exprs)
+                    .collect()
+            } else {
+                plan.inputs()
+                    .into_iter()
+                    .map(|input| {
+                        indices
+                            .clone()
+                            .with_projection_beneficial()
+                            .with_plan_exprs(&plan, input.schema())
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
         }
         LogicalPlan::Join(join) => {
             let left_len = join.left.schema().fields().len();
@@ -492,6 +531,40 @@ fn optimize_projections(
     }
 }
 
+/// Asks the user defined node of `extension` to only produce the output columns
+/// at `indices`, see [`UserDefinedLogicalNode::prune_output_columns`].
+///
+/// Returns `None` if all output columns are required, or if the node can not
+/// drop any of them.
+///
+/// [`UserDefinedLogicalNode::prune_output_columns`]: datafusion_expr::UserDefinedLogicalNode::prune_output_columns
+fn prune_extension_output(
+    extension: &Extension,
+    indices: &RequiredIndices,
+) -> Result<Option<LogicalPlan>> {
+    let schema = extension.node.schema();
+    let output_columns = indices.indices();
+    if output_columns.is_empty() || output_columns.len() == schema.fields().len() {
+        return Ok(None);
+    }
+    let Some(node) = extension.node.prune_output_columns(output_columns)? else {
+        return Ok(None);
+    };
+
+    let expected = output_columns
+        .iter()
+        .map(|&idx| Column::from(schema.qualified_field(idx)))
+        .collect::<Vec<_>>();
+    assert_or_internal_err!(
+        node.schema().columns() == expected,
+        "Output columns of the pruned {} node differ from the required columns. \
+        Make sure `.prune_output_columns` implementation of the \
+        `UserDefinedLogicalNode` only keeps the columns at the given indices.",
+        node.name()
+    );
+    Ok(Some(LogicalPlan::Extension(Extension { node })))
+}
+
 /// Optimizes uncorrelated subquery plans embedded in expressions of the given
 /// plan node (e.g., `Expr::ScalarSubquery`). `map_children` only visits direct
 /// plan inputs, so subqueries must be handled separately.
@@ -914,6 +987,14 @@ fn expr_contains_subquery(expr: &Expr) -> bool {
     .unwrap()
 }
 
+fn plan_scans_table(plan: &LogicalPlan, table_name: &str) -> bool {
+    plan.exists(|node| {
+        Ok(matches!(node, LogicalPlan::TableScan(scan) if scan.table_name.table() == table_name))
+    })
+    // Safe unwrap since we are doing a simple boolean check
+    .unwrap()
+}
+
 fn subquery_alias_targets_recursive_cte(plan: &LogicalPlan, cte_name: &str) -> bool {
     match plan {
         LogicalPlan::TableScan(scan) => scan.table_name.table() == cte_name,
@@ -1219,6 +1300,101 @@ mod tests {
         }
     }
 
+    /// A user-defined node that produces some columns of its input, and drops
+    /// those that are not needed by its parents
+    #[derive(Debug, Hash, PartialEq, Eq)]
+    struct PrunableUserDefined {
+        input: Arc<LogicalPlan>,
+        columns: Vec<Column>,
+        schema: DFSchemaRef,
+    }
+
+    impl PrunableUserDefined {
+        fn try_new(input: Arc<LogicalPlan>, columns: Vec<Column>) -> Result<Self> {
+            let fields = columns
+                .iter()
+                .map(|column| {
+                    let (qualifier, field) =
+                        input.schema().qualified_field_from_column(column)?;
+                    Ok((qualifier.cloned(), Arc::clone(field)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let schema =
+                Arc::new(DFSchema::new_with_metadata(fields, Default::default())?);
+            Ok(Self {
+                input,
+                columns,
+                schema,
+            })
+        }
+    }
+
+    // Manual implementation needed because of `schema` field. Comparison excludes this field.
+    impl PartialOrd for PrunableUserDefined {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            match self.input.partial_cmp(&other.input) {
+                Some(Ordering::Equal) => self.columns.partial_cmp(&other.columns),
+                cmp => cmp,
+            }
+            .filter(|cmp| *cmp != Ordering::Equal || self == other)
+        }
+    }
+
+    impl UserDefinedLogicalNodeCore for PrunableUserDefined {
+        fn name(&self) -> &str {
+            "PrunableUserDefined"
+        }
+
+        fn inputs(&self) -> Vec<&LogicalPlan> {
+            vec![&self.input]
+        }
+
+        fn schema(&self) -> &DFSchemaRef {
+            &self.schema
+        }
+
+        fn expressions(&self) -> Vec<Expr> {
+            vec![]
+        }
+
+        fn fmt_for_explain(&self, f: &mut Formatter) -> std::fmt::Result {
+            let columns = self
+                .columns
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            write!(f, "PrunableUserDefined: columns=[{}]", columns.join(", "))
+        }
+
+        fn with_exprs_and_inputs(
+            &self,
+            _exprs: Vec<Expr>,
+            mut inputs: Vec<LogicalPlan>,
+        ) -> Result<Self> {
+            Self::try_new(Arc::new(inputs.swap_remove(0)), self.columns.clone())
+        }
+
+        fn necessary_children_exprs(
+            &self,
+            output_columns: &[usize],
+        ) -> Option<Vec<Vec<usize>>> {
+            let input_schema = self.input.schema();
+            output_columns
+                .iter()
+                .map(|&idx| input_schema.index_of_column(&self.columns[idx]).ok())
+                .collect::<Option<Vec<_>>>()
+                .map(|indices| vec![indices])
+        }
+
+        fn prune_output_columns(&self, output_columns: &[usize]) -> Result<Option<Self>> {
+            let columns = output_columns
+                .iter()
+                .map(|&idx| self.columns[idx].clone())
+                .collect();
+            Self::try_new(Arc::clone(&self.input), columns).map(Some)
+        }
+    }
+
     #[test]
     fn merge_two_projection() -> Result<()> {
         let table_scan = test_table_scan()?;
@@ -2251,6 +2427,32 @@ mod tests {
         Ok(())
     }
 
+    // Only column `a` is referred at the output. The user defined node drops its
+    // other output columns, so that it no longer requires `b` and `c` from its input.
+    #[test]
+    fn test_user_defined_logical_plan_node_prune_output() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let columns = ["test.a", "test.b", "test.c"]
+            .into_iter()
+            .map(Column::from_qualified_name)
+            .collect();
+        let custom_plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(PrunableUserDefined::try_new(Arc::new(table_scan), columns)?),
+        });
+        let plan = LogicalPlanBuilder::from(custom_plan)
+            .project(vec![col("a"), lit(0).alias("d")])?
+            .build()?;
+
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Projection: test.a, Int32(0) AS d
+          PrunableUserDefined: columns=[test.a]
+            TableScan: test projection=[a]
+        "
+        )
+    }
+
     #[test]
     fn test_continue_processing_through_extension() -> Result<()> {
         let table_scan = test_table_scan()?;
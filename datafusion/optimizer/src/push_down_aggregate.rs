@@ -0,0 +1,238 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PushDownAggregate`] pushes aggregations into table scans
+
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::Result;
+use datafusion_common::tree_node::Transformed;
+use datafusion_expr::logical_plan::{LogicalPlan, Projection, TableScan};
+use datafusion_expr::{Expr, TableAggregate};
+
+/// Optimization rule that pushes an `Aggregate` into the `TableScan` it
+/// aggregates, if the table can compute it, see
+/// [`TableSource::push_down_aggregate`].
+///
+/// ```text
+/// Aggregate: groupBy=[[t.b]], aggr=[[max(t.a)]]      Projection: t.b, t.max(t.a) AS max(t.a)
+///   TableScan: t, full_filters=[t.c = 1]        =>     TableScan: t
+/// ```
+///
+/// The aggregate is only pushed down if every filter on the rows of the
+/// table was pushed into the scan, and the scan has no limit.
+///
+/// [`TableSource::push_down_aggregate`]: datafusion_expr::TableSource::push_down_aggregate
+#[derive(Default, Debug)]
+pub struct PushDownAggregate {}
+
+impl PushDownAggregate {
+    #[expect(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for PushDownAggregate {
+    fn name(&self) -> &str {
+        "push_down_aggregate"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Aggregate(aggregate) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+        let LogicalPlan::TableScan(scan) = aggregate.input.as_ref() else {
+            return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+        };
+        if scan.fetch.is_some()
+            || aggregate
+                .group_expr
+                .iter()
+                .any(|expr| matches!(expr, Expr::GroupingSet(_)))
+        {
+            return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+        }
+
+        let table_aggregate = TableAggregate {
+            group_expr: aggregate.group_expr.clone(),
+            aggr_expr: aggregate.aggr_expr.clone(),
+            filters: scan.filters.clone(),
+            schema: Arc::clone(aggregate.schema.inner()),
+        };
+        let Some(source) = scan.source.push_down_aggregate(&table_aggregate)? else {
+            return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+        };
+
+        let new_scan =
+            TableScan::try_new(scan.table_name.clone(), source, None, vec![], None)?;
+        // Restore the qualified names of the aggregate's output columns
+        let exprs = new_scan
+            .projected_schema
+            .columns()
+            .into_iter()
+            .zip(aggregate.schema.iter())
+            .map(|(column, (qualifier, field))| {
+                Expr::Column(column).alias_qualified(qualifier.cloned(), field.name())
+            })
+            .collect();
+        let projection = Projection::try_new_with_schema(
+            exprs,
+            Arc::new(LogicalPlan::TableScan(new_scan)),
+            Arc::clone(&aggregate.schema),
+        )?;
+        Ok(Transformed::yes(LogicalPlan::Projection(projection)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::OptimizerContext;
+    use crate::assert_optimized_plan_eq_snapshot;
+    use crate::test::test_table_scan_fields;
+
+    use arrow::datatypes::{Schema, SchemaRef};
+    use datafusion_expr::test::function_stub::{count, max};
+    use datafusion_expr::{LogicalPlanBuilder, TableSource, col, grouping_set, lit};
+
+    macro_rules! assert_optimized_plan_equal {
+        (
+            $plan:expr,
+            @ $expected:literal $(,)?
+        ) => {{
+            let optimizer_ctx = OptimizerContext::new().with_max_passes(1);
+            let rules: Vec<Arc<dyn crate::OptimizerRule + Send + Sync>> =
+                vec![Arc::new(PushDownAggregate::new())];
+            assert_optimized_plan_eq_snapshot!(
+                optimizer_ctx,
+                rules,
+                $plan,
+                @ $expected,
+            )
+        }};
+    }
+
+    /// A table that computes aggregations without grouping
+    struct AggregatingSource {
+        schema: SchemaRef,
+    }
+
+    impl TableSource for AggregatingSource {
+        fn schema(&self) -> SchemaRef {
+            Arc::clone(&self.schema)
+        }
+
+        fn push_down_aggregate(
+            &self,
+            aggregate: &TableAggregate,
+        ) -> Result<Option<Arc<dyn TableSource>>> {
+            if !aggregate.group_expr.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Arc::new(AggregatingSource {
+                schema: Arc::clone(&aggregate.schema),
+            })))
+        }
+    }
+
+    fn scan(filters: Vec<Expr>, fetch: Option<usize>) -> Result<LogicalPlanBuilder> {
+        let source = AggregatingSource {
+            schema: Arc::new(Schema::new(test_table_scan_fields())),
+        };
+        LogicalPlanBuilder::scan_with_filters_fetch(
+            "test",
+            Arc::new(source),
+            None,
+            filters,
+            fetch,
+        )
+    }
+
+    #[test]
+    fn push_down_aggregate() -> Result<()> {
+        let plan = scan(vec![col("c").eq(lit(1u32))], None)?
+            .aggregate(Vec::<Expr>::new(), vec![count(col("a")), max(col("b"))])?
+            .build()?;
+
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Projection: test.COUNT(test.a) AS COUNT(test.a), test.max(test.b) AS max(test.b)
+          TableScan: test
+        "
+        )
+    }
+
+    #[test]
+    fn unsupported_aggregates_are_not_pushed_down() -> Result<()> {
+        // The source does not support grouping
+        let plan = scan(vec![], None)?
+            .aggregate(vec![col("a")], vec![max(col("b"))])?
+            .build()?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Aggregate: groupBy=[[test.a]], aggr=[[max(test.b)]]
+          TableScan: test
+        "
+        )?;
+
+        // Grouping sets are never pushed down
+        let plan = scan(vec![], None)?
+            .aggregate(
+                vec![grouping_set(vec![vec![col("a")], vec![]])],
+                vec![max(col("b"))],
+            )?
+            .build()?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Aggregate: groupBy=[[GROUPING SETS ((test.a), ())]], aggr=[[max(test.b)]]
+          TableScan: test
+        "
+        )?;
+
+        // Nor are aggregates over a limited scan
+        let plan = scan(vec![], Some(10))?
+            .aggregate(Vec::<Expr>::new(), vec![max(col("b"))])?
+            .build()?;
+        assert_optimized_plan_equal!(
+            plan,
+            @r"
+        Aggregate: groupBy=[[]], aggr=[[max(test.b)]]
+          TableScan: test, fetch=10
+        "
+        )
+    }
+}
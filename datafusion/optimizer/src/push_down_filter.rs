@@ -30,8 +30,8 @@ use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
 };
 use datafusion_common::{
-    Column, DFSchema, Result, assert_eq_or_internal_err, assert_or_internal_err,
-    internal_err, plan_err, qualified_name,
+    Column, DFSchema, Result, UNNEST_ORDINALITY_COLUMN, assert_eq_or_internal_err,
+    assert_or_internal_err, internal_err, plan_err, qualified_name,
 };
 use datafusion_expr::expr::WindowFunction;
 use datafusion_expr::expr_rewriter::replace_col;
@@ -524,6 +524,10 @@ fn push_down_join(
     join: Join,
     parent_predicate: Option<&Expr>,
 ) -> Result<Transformed<LogicalPlan>> {
+    if join.asof_match.is_some() {
+        return push_down_asof_join(join, parent_predicate);
+    }
+
     // Split the parent predicate into individual conjunctive parts.
     let predicates = parent_predicate
         .map_or_else(Vec::new, |pred| split_conjunction_owned(pred.clone()));
@@ -559,6 +563,41 @@ fn push_down_join(
     push_down_all_join(predicates, inferred_join_predicates, join, on_filters)
 }
 
+/// Pushes the predicates that only reference the left input through an ASOF
+/// join.
+///
+/// Nothing else can be pushed down or inferred: filtering the right input
+/// would change which row is the nearest match of a left row.
+fn push_down_asof_join(
+    mut join: Join,
+    parent_predicate: Option<&Expr>,
+) -> Result<Transformed<LogicalPlan>> {
+    let Some(parent_predicate) = parent_predicate else {
+        return Ok(Transformed::no(LogicalPlan::Join(join)));
+    };
+
+    let mut checker = ColumnChecker::new(join.left.schema(), join.right.schema());
+    let (left_push, keep_predicates): (Vec<_>, Vec<_>) =
+        split_conjunction_owned(parent_predicate.clone())
+            .into_iter()
+            .partition(|predicate| checker.is_left_only(predicate));
+
+    let Some(left_predicate) = conjunction(left_push) else {
+        return Ok(Transformed::no(make_filter(
+            parent_predicate.clone(),
+            Arc::new(LogicalPlan::Join(join)),
+        )?));
+    };
+    join.left = Arc::new(make_filter(left_predicate, join.left)?);
+
+    let plan = LogicalPlan::Join(join);
+    let plan = match conjunction(keep_predicates) {
+        Some(predicate) => make_filter(predicate, Arc::new(plan))?,
+        None => plan,
+    };
+    Ok(Transformed::yes(plan))
+}
+
 /// Extracts any equi-join join predicates from the given filter expressions.
 ///
 /// Parameters
@@ -900,7 +939,15 @@ impl OptimizerRule for PushDownFilter {
                         unnest.input.schema().qualified_field(*idx);
                     let field_name = field.name().clone();
 
-                    if let DataType::Struct(children) = field.data_type() {
+                    let children = match field.data_type() {
+                        DataType::Struct(children) => Some(children),
+                        DataType::Map(entries, _) => match entries.data_type() {
+                            DataType::Struct(children) => Some(children),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    if let Some(children) = children {
                         for child in children {
                             let child_name = child.name().clone();
                             unnest_struct_columns.push(Column::new(
@@ -922,8 +969,13 @@ impl OptimizerRule for PushDownFilter {
                         });
                     let contains_struct_columns =
                         unnest_struct_columns.iter().any(|c| accum.contains(c));
+                    let contains_ordinality_column = unnest.options.ordinality
+                        && accum.iter().any(|c| c.name == UNNEST_ORDINALITY_COLUMN);
 
-                    if contains_list_columns || contains_struct_columns {
+                    if contains_list_columns
+                        || contains_struct_columns
+                        || contains_ordinality_column
+                    {
                         unnest_predicates.push(predicate);
                     } else {
                         non_unnest_predicates.push(predicate);
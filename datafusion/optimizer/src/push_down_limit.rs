@@ -243,7 +243,10 @@ fn push_down_join(mut join: Join, limit: usize) -> Transformed<Join> {
 
     // Cross join is the special case of inner join where there is no join condition. see [LogicalPlanBuilder::cross_join]
     fn is_cross_join(join: &Join) -> bool {
-        join.join_type == Inner && join.on.is_empty() && join.filter.is_none()
+        join.join_type == Inner
+            && join.on.is_empty()
+            && join.filter.is_none()
+            && join.asof_match.is_none()
     }
 
     let (left_limit, right_limit) = if is_cross_join(&join) {
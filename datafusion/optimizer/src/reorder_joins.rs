@@ -0,0 +1,740 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ReorderJoins`] reorders trees of inner joins based on table statistics
+
+use std::sync::Arc;
+
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::config::OptimizerOptions;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{Column, NullEquality, Result};
+use datafusion_expr::Expr;
+use datafusion_expr::logical_plan::{
+    Filter, Join, JoinConstraint, JoinType, LogicalPlan, Projection, SubqueryAlias,
+};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+
+/// Selectivity assumed for predicates without better estimates, matching the
+/// default filter selectivity of the physical planner
+const DEFAULT_SELECTIVITY: f64 = 0.2;
+
+/// Relative cost improvement required to change the join order, so that
+/// orders of equal cost are left as written
+const MIN_IMPROVEMENT: f64 = 1e-6;
+
+/// Reorders trees of inner joins to minimize the estimated size of the
+/// intermediate results.
+///
+/// The row counts and distinct value counts of the joined relations come from
+/// [`TableSource::statistics`], and the size of a join is estimated as the
+/// product of the sizes of its inputs, divided by the larger distinct count of
+/// the two columns of every equijoin predicate. All bushy join trees that do
+/// not introduce cross joins are enumerated by dynamic programming, for trees
+/// of up to `datafusion.optimizer.join_reordering_max_relations` relations.
+///
+/// For example, given a large fact table joined to a medium and a small
+/// dimension table:
+///
+/// ```text
+/// Inner Join: medium.b = small.b
+///   Inner Join: fact.a = medium.a
+///     TableScan: fact
+///     TableScan: medium
+///   TableScan: small
+/// ```
+///
+/// The two dimension tables are joined first when that produces fewer rows:
+///
+/// ```text
+/// Projection: fact.a, medium.a, medium.b, small.b
+///   Inner Join: medium.a = fact.a
+///     Inner Join: small.b = medium.b
+///       TableScan: small
+///       TableScan: medium
+///     TableScan: fact
+/// ```
+///
+/// Trees are left unchanged if any relation has no row count estimate.
+///
+/// [`TableSource::statistics`]: datafusion_expr::TableSource::statistics
+#[derive(Default, Debug)]
+pub struct ReorderJoins;
+
+impl ReorderJoins {
+    #[expect(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for ReorderJoins {
+    fn name(&self) -> &str {
+        "reorder_joins"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let options = &config.options().optimizer;
+        // the option may have been assigned directly, without being limited
+        let max_relations = options
+            .join_reordering_max_relations
+            .min(OptimizerOptions::MAX_JOIN_REORDERING_RELATIONS);
+        if !options.join_reordering || max_relations < 3 {
+            return Ok(Transformed::no(plan));
+        }
+
+        plan.transform_down_with_subqueries(|plan| match &plan {
+            LogicalPlan::Join(join) if is_reorderable(join) => {
+                match JoinGraph::try_new(join, max_relations)? {
+                    Some(graph) => graph.reorder(plan),
+                    None => Ok(Transformed::no(plan)),
+                }
+            }
+            _ => Ok(Transformed::no(plan)),
+        })
+    }
+}
+
+/// Returns true if `join` can be freely reordered with its inputs
+fn is_reorderable(join: &Join) -> bool {
+    join.join_type == JoinType::Inner
+        && join.join_constraint == JoinConstraint::On
+        && join.null_equality == NullEquality::NullEqualsNothing
+        && !join.null_aware
+        && join.asof_match.is_none()
+}
+
+/// The shape of a tree of joins over the relations of a [`JoinGraph`]
+enum JoinTree {
+    Relation(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+/// A join input that is not itself a reorderable join
+struct Relation {
+    plan: Arc<LogicalPlan>,
+    /// Estimated number of rows
+    rows: f64,
+    /// Predicates referencing only this relation
+    filters: Vec<Expr>,
+}
+
+enum PredicateKind {
+    /// An equijoin predicate, with the relations referenced by either side
+    Equi {
+        left: Expr,
+        left_relations: u64,
+        right: Expr,
+        right_relations: u64,
+    },
+    Filter(Expr),
+}
+
+/// A join predicate referencing more than one relation
+struct Predicate {
+    kind: PredicateKind,
+    relations: u64,
+    selectivity: f64,
+}
+
+/// A tree of inner joins flattened into its relations and predicates
+struct JoinGraph {
+    relations: Vec<Relation>,
+    predicates: Vec<Predicate>,
+    /// Predicates referencing no relation at all
+    constant_predicates: Vec<Expr>,
+    tree: JoinTree,
+}
+
+impl JoinGraph {
+    /// Flatten the tree of inner joins rooted at `join`, returning `None` if
+    /// it can not be reordered
+    fn try_new(join: &Join, max_relations: usize) -> Result<Option<Self>> {
+        let mut plans = vec![];
+        let mut equi_predicates = vec![];
+        let mut filter_predicates = vec![];
+        let tree = flatten(
+            join,
+            &mut plans,
+            &mut equi_predicates,
+            &mut filter_predicates,
+        );
+        if plans.len() < 3 || plans.len() > max_relations {
+            return Ok(None);
+        }
+
+        let mut relations = Vec::with_capacity(plans.len());
+        for plan in plans {
+            let Some(rows) = estimate_rows(&plan) else {
+                return Ok(None);
+            };
+            relations.push(Relation {
+                plan,
+                rows,
+                filters: vec![],
+            });
+        }
+
+        let mut graph = Self {
+            relations,
+            predicates: vec![],
+            constant_predicates: vec![],
+            tree,
+        };
+        for (left, right) in equi_predicates {
+            let (Some(left_relations), Some(right_relations)) = (
+                graph.referenced_relations(&left),
+                graph.referenced_relations(&right),
+            ) else {
+                return Ok(None);
+            };
+            let relations = left_relations | right_relations;
+            if relations.count_ones() < 2 {
+                graph.add_predicate(left.eq(right), relations);
+                continue;
+            }
+            let selectivity = graph.equijoin_selectivity(
+                &left,
+                left_relations,
+                &right,
+                right_relations,
+            );
+            graph.predicates.push(Predicate {
+                kind: PredicateKind::Equi {
+                    left,
+                    left_relations,
+                    right,
+                    right_relations,
+                },
+                relations,
+                selectivity,
+            });
+        }
+        for predicate in filter_predicates {
+            let Some(relations) = graph.referenced_relations(&predicate) else {
+                return Ok(None);
+            };
+            graph.add_predicate(predicate, relations);
+        }
+        Ok(Some(graph))
+    }
+
+    /// Add a non-equijoin predicate referencing `relations`
+    fn add_predicate(&mut self, predicate: Expr, relations: u64) {
+        match relations.count_ones() {
+            0 => self.constant_predicates.push(predicate),
+            1 => {
+                let relation = &mut self.relations[relations.trailing_zeros() as usize];
+                relation.rows *= DEFAULT_SELECTIVITY;
+                relation.filters.push(predicate);
+            }
+            _ => self.predicates.push(Predicate {
+                kind: PredicateKind::Filter(predicate),
+                relations,
+                selectivity: DEFAULT_SELECTIVITY,
+            }),
+        }
+    }
+
+    /// The set of relations whose columns `expr` references, or `None` if a
+    /// column does not belong to exactly one relation
+    fn referenced_relations(&self, expr: &Expr) -> Option<u64> {
+        let mut relations = 0;
+        for column in expr.column_refs() {
+            let mut matches = self
+                .relations
+                .iter()
+                .enumerate()
+                .filter(|(_, relation)| relation.plan.schema().has_column(column));
+            let (index, _) = matches.next()?;
+            if matches.next().is_some() {
+                return None;
+            }
+            relations |= 1 << index;
+        }
+        Some(relations)
+    }
+
+    /// The fraction of row pairs matching `left = right`
+    fn equijoin_selectivity(
+        &self,
+        left: &Expr,
+        left_relations: u64,
+        right: &Expr,
+        right_relations: u64,
+    ) -> f64 {
+        let distinct = |expr: &Expr, relations: u64| {
+            let relation = &self.relations[relations.trailing_zeros() as usize];
+            let distinct = match expr {
+                Expr::Column(column) if relations.count_ones() == 1 => {
+                    distinct_count(&relation.plan, column)
+                }
+                _ => None,
+            };
+            // Without a distinct count assume the values are unique
+            distinct.map_or(relation.rows, |distinct| distinct.min(relation.rows))
+        };
+        let distinct =
+            distinct(left, left_relations).max(distinct(right, right_relations));
+        1.0 / distinct.max(1.0)
+    }
+
+    /// Estimated number of rows of joining `relations`
+    fn cardinality(&self, relations: u64) -> f64 {
+        let rows: f64 = self
+            .relations
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| relations & (1 << index) != 0)
+            .map(|(_, relation)| relation.rows)
+            .product();
+        self.predicates
+            .iter()
+            .filter(|predicate| predicate.relations & !relations == 0)
+            .fold(rows, |rows, predicate| rows * predicate.selectivity)
+    }
+
+    /// Returns true if a predicate connects `left` and `right`
+    fn connected(&self, left: u64, right: u64) -> bool {
+        self.predicates.iter().any(|predicate| {
+            predicate.relations & !(left | right) == 0
+                && predicate.relations & left != 0
+                && predicate.relations & right != 0
+        })
+    }
+
+    /// Sum of the estimated sizes of all joins in `tree`, and the relations
+    /// it joins
+    fn cost(&self, tree: &JoinTree) -> (f64, u64) {
+        match tree {
+            JoinTree::Relation(index) => (0.0, 1 << index),
+            JoinTree::Join(left, right) => {
+                let (left_cost, left) = self.cost(left);
+                let (right_cost, right) = self.cost(right);
+                let relations = left | right;
+                (
+                    left_cost + right_cost + self.cardinality(relations),
+                    relations,
+                )
+            }
+        }
+    }
+
+    /// Find the cheapest join tree, returning its cost and, for every set of
+    /// relations, the relations of the left input of its cheapest join
+    fn cheapest_tree(&self) -> (f64, Vec<u64>) {
+        let all = (1u64 << self.relations.len()) - 1;
+        let mut costs = vec![0.0; all as usize + 1];
+        let mut splits = vec![0; all as usize + 1];
+        for relations in 1..=all {
+            if relations.count_ones() < 2 {
+                continue;
+            }
+            // Prefer splits connected by a predicate, falling back to a cross
+            // join if there are none
+            let mut best: Option<(bool, f64, u64)> = None;
+            let lowest = relations & relations.wrapping_neg();
+            let mut left = (relations - 1) & relations;
+            while left != 0 {
+                let right = relations ^ left;
+                if left & lowest != 0 {
+                    let connected = self.connected(left, right);
+                    let cost = costs[left as usize] + costs[right as usize];
+                    let better = best.is_none_or(|(best_connected, best_cost, _)| {
+                        (connected && !best_connected)
+                            || (connected == best_connected && cost < best_cost)
+                    });
+                    if better {
+                        best = Some((connected, cost, left));
+                    }
+                }
+                left = (left - 1) & relations;
+            }
+            let (_, cost, left) = best.expect("at least two relations");
+            costs[relations as usize] = cost + self.cardinality(relations);
+            // Put the smaller input on the left, which is the build side of
+            // hash joins
+            let right = relations ^ left;
+            splits[relations as usize] =
+                if self.cardinality(right) < self.cardinality(left) {
+                    right
+                } else {
+                    left
+                };
+        }
+        (costs[all as usize], splits)
+    }
+
+    /// Replace `plan`, the root of this graph, with the cheapest join order
+    fn reorder(self, plan: LogicalPlan) -> Result<Transformed<LogicalPlan>> {
+        let (current_cost, _) = self.cost(&self.tree);
+        let (cost, splits) = self.cheapest_tree();
+        if cost >= current_cost * (1.0 - MIN_IMPROVEMENT) {
+            return Ok(Transformed::no(plan));
+        }
+
+        let all = (1u64 << self.relations.len()) - 1;
+        let mut reordered = self.build(all, &splits)?;
+        if let Some(predicate) = conjunction(self.constant_predicates) {
+            reordered =
+                LogicalPlan::Filter(Filter::try_new(predicate, Arc::new(reordered))?);
+        }
+
+        // Restore the column order of the original join
+        let exprs = plan.schema().columns().into_iter().map(Expr::Column);
+        let projection = Projection::try_new(exprs.collect(), Arc::new(reordered))?;
+        Ok(Transformed::yes(LogicalPlan::Projection(projection)))
+    }
+
+    /// Build the cheapest join tree of `relations`
+    fn build(&self, relations: u64, splits: &[u64]) -> Result<LogicalPlan> {
+        if relations.count_ones() == 1 {
+            let relation = &self.relations[relations.trailing_zeros() as usize];
+            let plan = Arc::clone(&relation.plan);
+            return Ok(match conjunction(relation.filters.iter().cloned()) {
+                Some(predicate) => LogicalPlan::Filter(Filter::try_new(predicate, plan)?),
+                None => Arc::unwrap_or_clone(plan),
+            });
+        }
+
+        let left_relations = splits[relations as usize];
+        let right_relations = relations ^ left_relations;
+        let left = self.build(left_relations, splits)?;
+        let right = self.build(right_relations, splits)?;
+
+        let mut on = vec![];
+        let mut filters = vec![];
+        for predicate in &self.predicates {
+            if predicate.relations & !relations != 0
+                || predicate.relations & left_relations == 0
+                || predicate.relations & right_relations == 0
+            {
+                continue;
+            }
+            match &predicate.kind {
+                PredicateKind::Equi {
+                    left,
+                    left_relations: left_side,
+                    right,
+                    right_relations: right_side,
+                } => {
+                    let within =
+                        |side: u64, relations: u64| side != 0 && side & !relations == 0;
+                    if within(*left_side, left_relations)
+                        && within(*right_side, right_relations)
+                    {
+                        on.push((left.clone(), right.clone()))
+                    } else if within(*left_side, right_relations)
+                        && within(*right_side, left_relations)
+                    {
+                        on.push((right.clone(), left.clone()))
+                    } else {
+                        filters.push(left.clone().eq(right.clone()))
+                    }
+                }
+                PredicateKind::Filter(predicate) => filters.push(predicate.clone()),
+            }
+        }
+
+        Ok(LogicalPlan::Join(Join::try_new(
+            Arc::new(left),
+            Arc::new(right),
+            on,
+            conjunction(filters),
+            JoinType::Inner,
+            JoinConstraint::On,
+            NullEquality::NullEqualsNothing,
+            false,
+        )?))
+    }
+}
+
+/// Collect the relations and predicates of the tree of reorderable joins
+/// rooted at `join`
+fn flatten(
+    join: &Join,
+    relations: &mut Vec<Arc<LogicalPlan>>,
+    equi_predicates: &mut Vec<(Expr, Expr)>,
+    filter_predicates: &mut Vec<Expr>,
+) -> JoinTree {
+    equi_predicates.extend(join.on.iter().cloned());
+    if let Some(filter) = &join.filter {
+        filter_predicates.extend(split_conjunction(filter).into_iter().cloned());
+    }
+    let mut flatten_input = |input: &Arc<LogicalPlan>| match input.as_ref() {
+        LogicalPlan::Join(join) if is_reorderable(join) => {
+            flatten(join, relations, equi_predicates, filter_predicates)
+        }
+        _ => {
+            relations.push(Arc::clone(input));
+            JoinTree::Relation(relations.len() - 1)
+        }
+    };
+    let left = flatten_input(&join.left);
+    let right = flatten_input(&join.right);
+    JoinTree::Join(Box::new(left), Box::new(right))
+}
+
+/// Estimate the number of rows produced by `plan`
+fn estimate_rows(plan: &LogicalPlan) -> Option<f64> {
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            let statistics = scan.source.statistics()?;
+            let rows = *statistics.num_rows.get_value()? as f64
+                * DEFAULT_SELECTIVITY.powi(scan.filters.len() as i32);
+            Some(scan.fetch.map_or(rows, |fetch| rows.min(fetch as f64)))
+        }
+        LogicalPlan::Filter(filter) => {
+            let predicates = split_conjunction(&filter.predicate).len();
+            Some(
+                estimate_rows(&filter.input)?
+                    * DEFAULT_SELECTIVITY.powi(predicates as i32),
+            )
+        }
+        LogicalPlan::Projection(Projection { input, .. })
+        | LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => estimate_rows(input),
+        _ => None,
+    }
+}
+
+/// The number of distinct values of `column` of `plan`, if known
+fn distinct_count(plan: &LogicalPlan, column: &Column) -> Option<f64> {
+    let index = plan.schema().index_of_column(column).ok()?;
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            let index = scan
+                .projection
+                .as_ref()
+                .map_or(Some(index), |projection| projection.get(index).copied())?;
+            let statistics = scan.source.statistics()?;
+            let distinct = statistics
+                .column_statistics
+                .get(index)?
+                .distinct_count
+                .get_value()?;
+            Some(*distinct as f64)
+        }
+        LogicalPlan::Filter(Filter { input, .. })
+        | LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            let (qualifier, field) = input.schema().qualified_field(index);
+            distinct_count(input, &Column::from((qualifier, field)))
+        }
+        LogicalPlan::Projection(projection) => match projection.expr.get(index)? {
+            Expr::Column(column) => distinct_count(&projection.input, column),
+            Expr::Alias(alias) => match alias.expr.as_ref() {
+                Expr::Column(column) => distinct_count(&projection.input, column),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptimizerContext;
+
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{ColumnStatistics, Statistics};
+    use datafusion_expr::{LogicalPlanBuilder, TableSource};
+    use insta::assert_snapshot;
+
+    /// A table source with statistics
+    struct StatisticsSource {
+        schema: SchemaRef,
+        statistics: Statistics,
+    }
+
+    impl TableSource for StatisticsSource {
+        fn schema(&self) -> SchemaRef {
+            Arc::clone(&self.schema)
+        }
+
+        fn statistics(&self) -> Option<Statistics> {
+            Some(self.statistics.clone())
+        }
+    }
+
+    /// A scan of a table with `rows` rows and the given distinct counts per
+    /// column
+    fn scan(name: &str, rows: usize, columns: &[(&str, usize)]) -> Result<LogicalPlan> {
+        let schema = Schema::new(
+            columns
+                .iter()
+                .map(|(name, _)| Field::new(*name, DataType::Int32, false))
+                .collect::<Vec<_>>(),
+        );
+        let column_statistics = columns
+            .iter()
+            .map(|(_, distinct)| {
+                ColumnStatistics::new_unknown()
+                    .with_distinct_count(Precision::Inexact(*distinct))
+            })
+            .collect();
+        let source = StatisticsSource {
+            schema: Arc::new(schema),
+            statistics: Statistics {
+                num_rows: Precision::Exact(rows),
+                total_byte_size: Precision::Absent,
+                column_statistics,
+            },
+        };
+        LogicalPlanBuilder::scan(name, Arc::new(source), None)?.build()
+    }
+
+    fn optimize(plan: LogicalPlan) -> Result<Transformed<LogicalPlan>> {
+        let starting_schema = Arc::clone(plan.schema());
+        let result = ReorderJoins::new().rewrite(plan, &OptimizerContext::new())?;
+        assert_eq!(&starting_schema, result.data.schema());
+        Ok(result)
+    }
+
+    /// `fact` joined with `medium` joined with `small`, in that order
+    fn star_join() -> Result<LogicalPlan> {
+        LogicalPlanBuilder::from(scan("fact", 1_000_000, &[("a", 1000)])?)
+            .join(
+                scan("medium", 1000, &[("a", 1000), ("b", 1000)])?,
+                JoinType::Inner,
+                (vec!["fact.a"], vec!["medium.a"]),
+                None,
+            )?
+            .join(
+                scan("small", 10, &[("b", 10)])?,
+                JoinType::Inner,
+                (vec!["medium.b"], vec!["small.b"]),
+                None,
+            )?
+            .build()
+    }
+
+    #[test]
+    fn join_small_relations_first() -> Result<()> {
+        let result = optimize(star_join()?)?;
+        assert!(result.transformed);
+        assert_snapshot!(result.data, @r"
+        Projection: fact.a, medium.a, medium.b, small.b
+          Inner Join: medium.a = fact.a
+            Inner Join: small.b = medium.b
+              TableScan: small
+              TableScan: medium
+            TableScan: fact
+        ");
+
+        // The reordered plan is already the cheapest
+        assert!(!optimize(result.data)?.transformed);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_order_when_disabled() -> Result<()> {
+        let mut options = ConfigOptions::default();
+        options.optimizer.join_reordering_max_relations = 2;
+        let config = OptimizerContext::new_with_config_options(Arc::new(options));
+
+        let result = ReorderJoins::new().rewrite(star_join()?, &config)?;
+        assert!(!result.transformed);
+        Ok(())
+    }
+
+    /// A chain of `relations` joins of tables with decreasing row counts
+    fn chain_join(relations: usize) -> Result<LogicalPlan> {
+        let name = |index: usize| format!("t{index}");
+        let mut builder =
+            LogicalPlanBuilder::from(scan(&name(0), 1_000_000, &[("a", 1000)])?);
+        for index in 1..relations {
+            builder = builder.join(
+                scan(&name(index), 1_000_000 / (index + 1), &[("a", 1000)])?,
+                JoinType::Inner,
+                (
+                    vec![format!("{}.a", name(index - 1))],
+                    vec![format!("{}.a", name(index))],
+                ),
+                None,
+            )?;
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn reorder_up_to_the_max_relations() -> Result<()> {
+        let mut options = ConfigOptions::default();
+        options.optimizer.join_reordering_max_relations =
+            OptimizerOptions::MAX_JOIN_REORDERING_RELATIONS;
+        let config = OptimizerContext::new_with_config_options(Arc::new(options));
+
+        let plan = chain_join(OptimizerOptions::MAX_JOIN_REORDERING_RELATIONS)?;
+        let starting_schema = Arc::clone(plan.schema());
+        let result = ReorderJoins::new().rewrite(plan, &config)?;
+        assert!(result.transformed);
+        assert_eq!(&starting_schema, result.data.schema());
+        Ok(())
+    }
+
+    #[test]
+    fn keep_order_beyond_the_max_relations() -> Result<()> {
+        // larger values than supported are limited, even if set directly
+        let mut options = ConfigOptions::default();
+        options.optimizer.join_reordering_max_relations = usize::MAX;
+        let config = OptimizerContext::new_with_config_options(Arc::new(options));
+
+        // only the innermost joins, over at most the max relations, move
+        for relations in [17, 70] {
+            let result = ReorderJoins::new().rewrite(chain_join(relations)?, &config)?;
+            let LogicalPlan::Join(join) = &result.data else {
+                panic!("expected the outermost join to be kept");
+            };
+            let LogicalPlan::TableScan(scan) = join.right.as_ref() else {
+                panic!("expected the outermost join to keep its right input");
+            };
+            assert_eq!(scan.table_name.table(), format!("t{}", relations - 1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn keep_order_without_statistics() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(scan("fact", 1_000_000, &[("a", 1000)])?)
+            .join(
+                crate::test::test_table_scan_with_name("t")?,
+                JoinType::Inner,
+                (vec!["fact.a"], vec!["t.a"]),
+                None,
+            )?
+            .join(
+                scan("small", 10, &[("b", 10)])?,
+                JoinType::Inner,
+                (vec!["t.b"], vec!["small.b"]),
+                None,
+            )?
+            .build()?;
+
+        assert!(!optimize(plan)?.transformed);
+        Ok(())
+    }
+}
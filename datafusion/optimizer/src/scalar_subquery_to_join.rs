@@ -408,9 +408,7 @@ fn build_join(
                     else_expr: Some(Box::new(Expr::Column(value_col.clone()))),
                 })
             };
-            let mut expr_rewrite = TypeCoercionRewriter {
-                schema: new_plan.schema(),
-            };
+            let mut expr_rewrite = TypeCoercionRewriter::new(new_plan.schema());
             computation_project_expr
                 .insert(value_col, computer_expr.rewrite(&mut expr_rewrite).data()?);
         }
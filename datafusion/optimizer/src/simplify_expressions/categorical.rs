@@ -0,0 +1,377 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Simplify expressions on categorical columns
+//!
+//! A column declared with the [`DFCategorical`] extension type can only hold
+//! one of its allowed values (or `NULL`). The functions in this module use
+//! the allowed values to simplify the following expressions, where `A` is a
+//! categorical column:
+//!
+//! 1. `A = x` and `A != x`, where `x` is not an allowed value
+//! 2. `A [NOT] IN (x, y, ...)`, where some items are not allowed values or
+//!    the items cover all allowed values
+//! 3. `CASE A WHEN x THEN ... END`, where some `WHEN` values are not allowed
+//!    values, or the `WHEN` values cover all allowed values and `A` is not
+//!    nullable, so that the `ELSE` branch is unreachable
+
+use std::collections::HashSet;
+
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::types::DFCategorical;
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::expr::InList;
+use datafusion_expr::simplify::SimplifyContext;
+use datafusion_expr::{Case, Expr, Operator, lit};
+
+use super::utils::lit_bool_null;
+
+/// Returns the [`DFCategorical`] type of `expr`, if it is a column declared
+/// as categorical
+fn categorical_type(
+    info: &SimplifyContext,
+    expr: &Expr,
+) -> Result<Option<DFCategorical>> {
+    let Expr::Column(column) = expr else {
+        return Ok(None);
+    };
+    match info.schema().qualified_field_from_column(column) {
+        Ok((_, field)) => DFCategorical::try_from_field(field),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Returns `Some(false)` if `expr` is a string literal that is not an allowed
+/// value, `Some(true)` if it is an allowed value, and `None` otherwise
+fn contains(categorical: &DFCategorical, expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Literal(value, _) => categorical.contains_scalar(value),
+        _ => None,
+    }
+}
+
+/// Returns true if `values` contain every allowed value of `categorical`
+fn covers_all<'a>(
+    categorical: &DFCategorical,
+    values: impl IntoIterator<Item = &'a Expr>,
+) -> bool {
+    let covered = values
+        .into_iter()
+        .filter_map(|expr| match expr {
+            Expr::Literal(ScalarValue::Dictionary(_, value), _) => value.try_as_str(),
+            Expr::Literal(value, _) => value.try_as_str(),
+            _ => None,
+        })
+        .flatten()
+        .filter(|value| categorical.contains(value))
+        .collect::<HashSet<_>>();
+    covered.len() == categorical.values().len()
+}
+
+/// Returns an expression that evaluates to `value` if `column` is not null,
+/// and to `NULL` otherwise
+///
+/// * `true`: `A IS NOT NULL OR NULL`
+/// * `false`: `A IS NULL AND NULL`
+fn null_or(info: &SimplifyContext, column: Expr, value: bool) -> Result<Expr> {
+    if !info.nullable(&column)? {
+        return Ok(lit(value));
+    }
+    Ok(if value {
+        column.is_not_null().or(lit_bool_null())
+    } else {
+        column.is_null().and(lit_bool_null())
+    })
+}
+
+/// Returns true if `column = literal` compares a categorical column to a
+/// value that is not allowed
+pub(super) fn is_categorical_comparison_simplifiable(
+    info: &SimplifyContext,
+    column: &Expr,
+    literal: &Expr,
+) -> Result<bool> {
+    Ok(categorical_type(info, column)?
+        .is_some_and(|categorical| contains(&categorical, literal) == Some(false)))
+}
+
+/// Simplifies `column = x` to false, or `column != x` to true, where `x` is not
+/// an allowed value of the categorical `column`
+pub(super) fn simplify_categorical_comparison(
+    info: &SimplifyContext,
+    column: Expr,
+    op: Operator,
+) -> Result<Transformed<Expr>> {
+    Ok(Transformed::yes(null_or(
+        info,
+        column,
+        op == Operator::NotEq,
+    )?))
+}
+
+/// Returns true if `expr [NOT] IN (list)` has items that are not allowed
+/// values of the categorical `expr`, or items covering all allowed values
+pub(super) fn is_categorical_in_list_simplifiable(
+    info: &SimplifyContext,
+    expr: &Expr,
+    list: &[Expr],
+) -> Result<bool> {
+    let Some(categorical) = categorical_type(info, expr)? else {
+        return Ok(false);
+    };
+    Ok(list
+        .iter()
+        .any(|item| contains(&categorical, item) == Some(false))
+        || covers_all(&categorical, list))
+}
+
+/// Removes the items that are not allowed values from `expr [NOT] IN (list)`,
+/// or simplifies it to a constant if all allowed values are listed
+pub(super) fn simplify_categorical_in_list(
+    info: &SimplifyContext,
+    in_list: InList,
+) -> Result<Transformed<Expr>> {
+    let InList {
+        expr,
+        list,
+        negated,
+    } = in_list;
+    let Some(categorical) = categorical_type(info, &expr)? else {
+        return Ok(Transformed::no(Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        })));
+    };
+
+    // A IN (<all allowed values>) --> true
+    // A NOT IN (<all allowed values>) --> false
+    if covers_all(&categorical, &list) {
+        return Ok(Transformed::yes(null_or(info, *expr, !negated)?));
+    }
+
+    let list = list
+        .into_iter()
+        .filter(|item| contains(&categorical, item) != Some(false))
+        .collect::<Vec<_>>();
+    if list.is_empty() {
+        return Ok(Transformed::yes(null_or(info, *expr, negated)?));
+    }
+    Ok(Transformed::yes(Expr::InList(InList {
+        expr,
+        list,
+        negated,
+    })))
+}
+
+/// Returns true if `CASE A WHEN ... END` has `WHEN` values that are not allowed
+/// values of the categorical `A`, or an unreachable `ELSE` branch
+pub(super) fn is_categorical_case_simplifiable(
+    info: &SimplifyContext,
+    case: &Case,
+) -> Result<bool> {
+    let Some(expr) = case.expr.as_deref() else {
+        return Ok(false);
+    };
+    let Some(categorical) = categorical_type(info, expr)? else {
+        return Ok(false);
+    };
+    if case
+        .when_then_expr
+        .iter()
+        .any(|(when, _)| contains(&categorical, when) == Some(false))
+    {
+        return Ok(true);
+    }
+    Ok(case.else_expr.is_some()
+        && !info.nullable(expr)?
+        && covers_all(
+            &categorical,
+            case.when_then_expr.iter().map(|(when, _)| when.as_ref()),
+        ))
+}
+
+/// Removes the `WHEN` branches that can never match, and the `ELSE` branch if
+/// the `WHEN` values cover all allowed values of the non-nullable `A`
+pub(super) fn simplify_categorical_case(
+    info: &SimplifyContext,
+    case: Case,
+) -> Result<Transformed<Expr>> {
+    let (categorical, nullable) = match case.expr.as_deref() {
+        Some(expr) => (categorical_type(info, expr)?, info.nullable(expr)?),
+        None => (None, true),
+    };
+    let Some(categorical) = categorical else {
+        return Ok(Transformed::no(Expr::Case(case)));
+    };
+    let Case {
+        expr,
+        when_then_expr,
+        mut else_expr,
+    } = case;
+
+    let out_type = info.get_data_type(&when_then_expr[0].1)?;
+    if !nullable
+        && covers_all(
+            &categorical,
+            when_then_expr.iter().map(|(when, _)| when.as_ref()),
+        )
+    {
+        else_expr = None;
+    }
+    let when_then_expr = when_then_expr
+        .into_iter()
+        .filter(|(when, _)| contains(&categorical, when) != Some(false))
+        .collect::<Vec<_>>();
+
+    // CASE A WHEN x THEN B ELSE C END --> C
+    if when_then_expr.is_empty() {
+        return Ok(Transformed::yes(match else_expr {
+            Some(else_expr) => *else_expr,
+            None => Expr::Literal(ScalarValue::try_new_null(&out_type)?, None),
+        }));
+    }
+    Ok(Transformed::yes(Expr::Case(Case {
+        expr,
+        when_then_expr,
+        else_expr,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use arrow::datatypes::{DataType, Schema};
+    use datafusion_common::DFSchema;
+    use datafusion_expr::col;
+
+    fn context(nullable: bool) -> SimplifyContext {
+        let categorical = DFCategorical::try_new(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            vec!["a".to_string(), "b".to_string()],
+        )
+        .unwrap();
+        let schema = Schema::new(vec![categorical.to_field("c").with_nullable(nullable)]);
+        SimplifyContext::builder()
+            .with_schema(Arc::new(DFSchema::try_from(schema).unwrap()))
+            .build()
+    }
+
+    #[test]
+    fn test_categorical_comparison() -> Result<()> {
+        let info = context(false);
+        assert!(is_categorical_comparison_simplifiable(
+            &info,
+            &col("c"),
+            &lit("x")
+        )?);
+        assert!(!is_categorical_comparison_simplifiable(
+            &info,
+            &col("c"),
+            &lit("a")
+        )?);
+        assert!(!is_categorical_comparison_simplifiable(
+            &info,
+            &col("d"),
+            &lit("x")
+        )?);
+
+        let simplified = simplify_categorical_comparison(&info, col("c"), Operator::Eq)?;
+        assert_eq!(simplified.data, lit(false));
+
+        let info = context(true);
+        let simplified =
+            simplify_categorical_comparison(&info, col("c"), Operator::NotEq)?;
+        assert_eq!(simplified.data, col("c").is_not_null().or(lit_bool_null()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_categorical_in_list() -> Result<()> {
+        let info = context(true);
+        let in_list = |list: Vec<Expr>, negated| InList {
+            expr: Box::new(col("c")),
+            list,
+            negated,
+        };
+
+        assert!(!is_categorical_in_list_simplifiable(
+            &info,
+            &col("c"),
+            &[lit("a")]
+        )?);
+        let simplified = simplify_categorical_in_list(
+            &info,
+            in_list(vec![lit("a"), lit("x")], false),
+        )?;
+        assert_eq!(simplified.data, col("c").in_list(vec![lit("a")], false));
+
+        let simplified =
+            simplify_categorical_in_list(&info, in_list(vec![lit("x")], false))?;
+        assert_eq!(simplified.data, col("c").is_null().and(lit_bool_null()));
+
+        let simplified =
+            simplify_categorical_in_list(&info, in_list(vec![lit("b"), lit("a")], true))?;
+        assert_eq!(simplified.data, col("c").is_null().and(lit_bool_null()));
+
+        let info = context(false);
+        let simplified = simplify_categorical_in_list(
+            &info,
+            in_list(vec![lit("b"), lit("a")], false),
+        )?;
+        assert_eq!(simplified.data, lit(true));
+        Ok(())
+    }
+
+    fn case(whens: &[&str]) -> Case {
+        Case::new(
+            Some(Box::new(col("c"))),
+            whens
+                .iter()
+                .map(|value| (Box::new(lit(*value)), Box::new(lit(*value))))
+                .collect(),
+            Some(Box::new(lit("other"))),
+        )
+    }
+
+    #[test]
+    fn test_categorical_case() -> Result<()> {
+        // the ELSE branch is reachable for NULL values
+        let info = context(true);
+        assert!(!is_categorical_case_simplifiable(
+            &info,
+            &case(&["a", "b"])
+        )?);
+        assert!(is_categorical_case_simplifiable(&info, &case(&["a", "x"]))?);
+        let simplified = simplify_categorical_case(&info, case(&["a", "x"]))?;
+        assert_eq!(simplified.data, Expr::Case(case(&["a"])));
+
+        let simplified = simplify_categorical_case(&info, case(&["x"]))?;
+        assert_eq!(simplified.data, lit("other"));
+
+        // the ELSE branch is unreachable if all values are covered
+        let info = context(false);
+        assert!(is_categorical_case_simplifiable(&info, &case(&["a", "b"]))?);
+        let simplified = simplify_categorical_case(&info, case(&["b", "a", "x"]))?;
+        let mut expected = case(&["b", "a"]);
+        expected.else_expr = None;
+        assert_eq!(simplified.data, Expr::Case(expected));
+        Ok(())
+    }
+}
@@ -52,6 +52,11 @@ use datafusion_expr::{
 };
 use datafusion_physical_expr::{create_physical_expr, execution_props::ExecutionProps};
 
+use super::categorical::{
+    is_categorical_case_simplifiable, is_categorical_comparison_simplifiable,
+    is_categorical_in_list_simplifiable, simplify_categorical_case,
+    simplify_categorical_comparison, simplify_categorical_in_list,
+};
 use super::inlist_simplifier::ShortenInListSimplifier;
 use super::utils::*;
 use crate::simplify_expressions::SimplifyContext;
@@ -66,6 +71,7 @@ use crate::{
     simplify_expressions::udf_preimage::rewrite_with_preimage,
 };
 use datafusion_expr::expr_rewriter::rewrite_with_guarantees_map;
+use datafusion_expr::registry::CastRegistryRef;
 use datafusion_expr_common::casts::try_cast_literal_to_type;
 use indexmap::IndexSet;
 use regex::Regex;
@@ -211,7 +217,8 @@ impl ExprSimplifier {
     ) -> Result<(Transformed<Expr>, u32)> {
         let mut simplifier = Simplifier::new(&self.info);
         let config_options = Some(Arc::clone(self.info.config_options()));
-        let mut const_evaluator = ConstEvaluator::try_new(config_options)?;
+        let mut const_evaluator = ConstEvaluator::try_new(config_options)?
+            .with_cast_registry(self.info.cast_registry().cloned());
         let mut shorten_in_list_simplifier = ShortenInListSimplifier::new();
         let guarantees_map: HashMap<&Expr, &NullableInterval> =
             self.guarantees.iter().map(|(k, v)| (k, v)).collect();
@@ -256,7 +263,7 @@ impl ExprSimplifier {
     /// See the [type coercion module](datafusion_expr::type_coercion)
     /// documentation for more details on type coercion
     pub fn coerce(&self, expr: Expr, schema: &DFSchema) -> Result<Expr> {
-        let mut expr_rewrite = TypeCoercionRewriter { schema };
+        let mut expr_rewrite = TypeCoercionRewriter::new(schema);
         expr.rewrite(&mut expr_rewrite).data()
     }
 
@@ -610,6 +617,13 @@ impl ConstEvaluator {
         })
     }
 
+    /// Evaluate `CAST` expressions with the user defined casts of
+    /// `cast_registry`, as they are when executed
+    fn with_cast_registry(mut self, cast_registry: Option<CastRegistryRef>) -> Self {
+        self.execution_props.cast_registry = cast_registry;
+        self
+    }
+
     /// Can a function of the specified volatility be evaluated?
     fn volatility_ok(volatility: Volatility) -> bool {
         match volatility {
@@ -1428,6 +1442,46 @@ impl TreeNodeRewriter for Simplifier<'_> {
             //
             Expr::Negative(inner) => Transformed::yes(distribute_negation(*inner)),
 
+            //
+            // Rules for categorical columns
+            //
+
+            // A = x  --> false
+            // A != x --> true
+            // if x is not an allowed value of the categorical column A
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: op @ (Eq | NotEq),
+                right,
+            }) if is_categorical_comparison_simplifiable(info, &left, &right)? => {
+                simplify_categorical_comparison(info, *left, op)?
+            }
+            // x = A  --> false
+            // x != A --> true
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: op @ (Eq | NotEq),
+                right,
+            }) if is_categorical_comparison_simplifiable(info, &right, &left)? => {
+                simplify_categorical_comparison(info, *right, op)?
+            }
+            // A IN (x, y, z) --> A IN (x, y) if z is not an allowed value of A
+            // A IN (x, y, z) --> true if x, y and z are all the allowed values of A
+            Expr::InList(in_list)
+                if is_categorical_in_list_simplifiable(
+                    info,
+                    &in_list.expr,
+                    &in_list.list,
+                )? =>
+            {
+                simplify_categorical_in_list(info, in_list)?
+            }
+            // CASE A WHEN x THEN B WHEN z THEN C END --> CASE A WHEN x THEN B END
+            // if z is not an allowed value of A
+            Expr::Case(case) if is_categorical_case_simplifiable(info, &case)? => {
+                simplify_categorical_case(info, case)?
+            }
+
             //
             // Rules for Case
             //
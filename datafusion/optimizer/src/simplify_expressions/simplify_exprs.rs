@@ -102,6 +102,11 @@ impl SimplifyExpressions {
             .with_schema(schema)
             .with_config_options(config.options())
             .with_query_execution_start_time(config.query_execution_start_time())
+            .with_cast_registry(
+                config
+                    .function_registry()
+                    .and_then(|registry| registry.cast_registry()),
+            )
             .build();
 
         // Inputs have already been rewritten (due to bottom-up traversal handled by Optimizer)
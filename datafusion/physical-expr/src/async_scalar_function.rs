@@ -26,6 +26,7 @@ use datafusion_expr::ScalarFunctionArgs;
 use datafusion_expr::async_udf::AsyncScalarUDF;
 use datafusion_expr_common::columnar_value::ColumnarValue;
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+use futures::{StreamExt, TryFutureExt, TryStreamExt, stream};
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -139,60 +140,58 @@ impl AsyncFuncExpr {
             .map(|e| e.return_field(batch.schema_ref()))
             .collect::<Result<Vec<_>>>()?;
 
-        let mut result_batches = vec![];
-        if let Some(ideal_batch_size) = self.ideal_batch_size()? {
-            let mut remainder = batch.clone();
-            while remainder.num_rows() > 0 {
-                let size = if ideal_batch_size > remainder.num_rows() {
-                    remainder.num_rows()
-                } else {
-                    ideal_batch_size
-                };
-
-                let current_batch = remainder.slice(0, size); // get next 10 rows
-                remainder = remainder.slice(size, remainder.num_rows() - size);
+        // split the batch into chunks of the ideal batch size (if any)
+        let chunk_size = self
+            .ideal_batch_size()?
+            .filter(|size| *size > 0)
+            .unwrap_or_else(|| batch.num_rows())
+            .max(1);
+        let chunks = (0..batch.num_rows())
+            .step_by(chunk_size)
+            .map(|offset| batch.slice(offset, chunk_size.min(batch.num_rows() - offset)))
+            .collect::<Vec<_>>();
+        let chunks = if chunks.is_empty() {
+            vec![batch.clone()]
+        } else {
+            chunks
+        };
+
+        let invocations = chunks
+            .iter()
+            .map(|chunk| {
                 let args = scalar_function_expr
                     .args()
                     .iter()
-                    .map(|e| e.evaluate(&current_batch))
+                    .map(|e| e.evaluate(chunk))
                     .collect::<Result<Vec<_>>>()?;
-                result_batches.push(
-                    async_udf
-                        .invoke_async_with_args(ScalarFunctionArgs {
-                            args,
-                            arg_fields: arg_fields.clone(),
-                            number_rows: current_batch.num_rows(),
-                            return_field: Arc::clone(&self.return_field),
-                            config_options: Arc::clone(&config_options),
-                        })
-                        .await?,
-                );
-            }
-        } else {
-            let args = scalar_function_expr
-                .args()
-                .iter()
-                .map(|e| e.evaluate(batch))
-                .collect::<Result<Vec<_>>>()?;
+                Ok(ScalarFunctionArgs {
+                    args,
+                    arg_fields: arg_fields.clone(),
+                    number_rows: chunk.num_rows(),
+                    return_field: Arc::clone(&self.return_field),
+                    config_options: Arc::clone(&config_options),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-            result_batches.push(
+        // Invoke the function for up to `max_concurrency` chunks at a time.
+        // `buffered` yields the results in the order of the chunks.
+        let results = stream::iter(invocations)
+            .map(|args| {
+                let number_rows = args.number_rows;
                 async_udf
-                    .invoke_async_with_args(ScalarFunctionArgs {
-                        args: args.to_vec(),
-                        arg_fields,
-                        number_rows: batch.num_rows(),
-                        return_field: Arc::clone(&self.return_field),
-                        config_options: Arc::clone(&config_options),
-                    })
-                    .await?,
-            );
-        }
+                    .invoke_async_with_args(args)
+                    .map_ok(move |cv| (cv, number_rows))
+            })
+            .buffered(async_udf.max_concurrency().max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
 
-        let datas = result_batches
+        let datas = results
             .into_iter()
-            .map(|cv| match cv {
+            .map(|(cv, number_rows)| match cv {
                 ColumnarValue::Array(arr) => Ok(arr),
-                ColumnarValue::Scalar(scalar) => Ok(scalar.to_array_of_size(1)?),
+                ColumnarValue::Scalar(scalar) => scalar.to_array_of_size(number_rows),
             })
             .collect::<Result<Vec<_>>>()?;
 
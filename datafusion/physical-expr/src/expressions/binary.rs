@@ -33,7 +33,7 @@ use arrow::error::ArrowError;
 use datafusion_common::cast::as_boolean_array;
 use datafusion_common::{Result, ScalarValue, internal_err, not_impl_err};
 
-use datafusion_expr::binary::BinaryTypeCoercer;
+use datafusion_expr::binary::{BinaryTypeCoercer, is_interval_scaling};
 use datafusion_expr::interval_arithmetic::{Interval, apply_operator};
 use datafusion_expr::sort_properties::ExprProperties;
 use datafusion_expr::statistics::Distribution::{Bernoulli, Gaussian};
@@ -254,6 +254,113 @@ fn duration_to_days(array: &ArrayRef) -> Result<ArrayRef> {
     }
 }
 
+/// Multiplies or divides intervals by numbers, returning `Interval(MonthDayNano)`
+///
+/// Both operands are expected to be coerced to `Interval(MonthDayNano)` and
+/// `Float64` by [`BinaryTypeCoercer`]; the interval may be on either side of
+/// a multiplication.
+fn apply_interval_scaling(
+    op: Operator,
+    lhs: &ColumnarValue,
+    rhs: &ColumnarValue,
+    num_rows: usize,
+) -> Result<ColumnarValue> {
+    use arrow::compute::kernels::arity::try_binary;
+
+    let (interval, factor) = match lhs.data_type() {
+        DataType::Interval(_) => (lhs, rhs),
+        _ => (rhs, lhs),
+    };
+    let num_rows = match (interval, factor) {
+        (ColumnarValue::Scalar(_), ColumnarValue::Scalar(_)) => 1,
+        _ => num_rows,
+    };
+    let intervals = interval.to_array(num_rows)?;
+    let factors = factor.to_array(num_rows)?;
+    let result: IntervalMonthDayNanoArray = try_binary(
+        intervals.as_primitive::<IntervalMonthDayNanoType>(),
+        factors.as_primitive::<Float64Type>(),
+        |interval, factor| scale_interval(interval, factor, op),
+    )?;
+
+    match (interval, factor) {
+        (ColumnarValue::Scalar(_), ColumnarValue::Scalar(_)) => Ok(
+            ColumnarValue::Scalar(ScalarValue::try_from_array(&result, 0)?),
+        ),
+        _ => Ok(ColumnarValue::Array(Arc::new(result))),
+    }
+}
+
+/// Multiplies or divides a single interval by `factor`, following PostgreSQL:
+/// the fractional part of the months is carried into days, assuming 30 days
+/// per month, and the fractional part of the days into nanoseconds, assuming
+/// 24 hours per day. The nanoseconds are rounded to the nearest integer.
+fn scale_interval(
+    interval: IntervalMonthDayNano,
+    factor: f64,
+    op: Operator,
+) -> Result<IntervalMonthDayNano, ArrowError> {
+    const DAYS_PER_MONTH: f64 = 30.0;
+    const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+    if op == Operator::Divide && factor == 0.0 {
+        return Err(ArrowError::DivideByZero);
+    }
+    let scale = |value: f64| match op {
+        Operator::Divide => value / factor,
+        _ => value * factor,
+    };
+    let overflow = || {
+        ArrowError::ArithmeticOverflow(format!(
+            "Overflow happened on: {interval:?} {op} {factor}"
+        ))
+    };
+    let to_i32 = |value: f64| {
+        (value.is_finite() && value >= i32::MIN as f64 && value <= i32::MAX as f64)
+            .then_some(value as i32)
+            .ok_or_else(overflow)
+    };
+    let to_i64 = |value: f64| {
+        // `i64::MAX as f64` rounds up to 2^63, which is out of range
+        (value.is_finite() && value >= i64::MIN as f64 && value < i64::MAX as f64)
+            .then_some(value as i64)
+            .ok_or_else(overflow)
+    };
+
+    // Multiplying by a whole number is exact, without going through `f64`
+    if op == Operator::Multiply && factor.fract() == 0.0 {
+        let factor = to_i32(factor)?;
+        return match (
+            interval.months.checked_mul(factor),
+            interval.days.checked_mul(factor),
+            interval.nanoseconds.checked_mul(factor as i64),
+        ) {
+            (Some(months), Some(days), Some(nanos)) => {
+                Ok(IntervalMonthDayNano::new(months, days, nanos))
+            }
+            _ => Err(overflow()),
+        };
+    }
+
+    let months = scale(interval.months as f64);
+    let whole_months = to_i32(months.trunc())?;
+    let days = scale(interval.days as f64) + months.fract() * DAYS_PER_MONTH;
+    let mut whole_days = to_i32(days.trunc())?;
+    // Rounding the fractional day may yield a full day, e.g. for `1 month / 3`
+    let mut day_nanos = (days.fract() * NANOS_PER_DAY as f64).round() as i64;
+    if day_nanos.abs() >= NANOS_PER_DAY {
+        whole_days = whole_days
+            .checked_add(day_nanos.signum() as i32)
+            .ok_or_else(overflow)?;
+        day_nanos -= day_nanos.signum() * NANOS_PER_DAY;
+    }
+    let nanos = to_i64(scale(interval.nanoseconds as f64).round())?
+        .checked_add(day_nanos)
+        .ok_or_else(overflow)?;
+
+    Ok(IntervalMonthDayNano::new(whole_months, whole_days, nanos))
+}
+
 impl PhysicalExpr for BinaryExpr {
     fn data_type(&self, input_schema: &Schema) -> Result<DataType> {
         BinaryTypeCoercer::new(
@@ -345,6 +452,13 @@ impl PhysicalExpr for BinaryExpr {
             }
             Operator::Minus if self.fail_on_overflow => return apply(&lhs, &rhs, sub),
             Operator::Minus => return apply(&lhs, &rhs, sub_wrapping),
+            // Special case: interval * number and interval / number carry
+            // fractional months and days into the smaller units, as in PostgreSQL
+            Operator::Multiply | Operator::Divide
+                if is_interval_scaling(self.op, &left_data_type, &right_data_type) =>
+            {
+                return apply_interval_scaling(self.op, &lhs, &rhs, batch.num_rows());
+            }
             Operator::Multiply if self.fail_on_overflow => return apply(&lhs, &rhs, mul),
             Operator::Multiply => return apply(&lhs, &rhs, mul_wrapping),
             Operator::Divide => return apply(&lhs, &rhs, div),
@@ -5403,4 +5517,39 @@ mod tests {
             BooleanArray::from_iter(vec![Some(true), Some(true), Some(true), Some(true)]);
         assert_eq!(eq_result.into_array(4).unwrap().as_boolean(), &expected);
     }
+
+    #[test]
+    fn scale_interval_carries_fractions() {
+        let interval =
+            |months, days, nanos| IntervalMonthDayNano::new(months, days, nanos);
+        const HOUR: i64 = 3_600_000_000_000;
+
+        // 1 month 1 day * 1.5 = 1 month 16 days 12 hours
+        assert_eq!(
+            scale_interval(interval(1, 1, 0), 1.5, Operator::Multiply).unwrap(),
+            interval(1, 16, 12 * HOUR)
+        );
+        // 1 month / 3 = 10 days, without rounding errors
+        assert_eq!(
+            scale_interval(interval(1, 0, 0), 3.0, Operator::Divide).unwrap(),
+            interval(0, 10, 0)
+        );
+        // 1 day 1 hour * -2 = -2 days -2 hours
+        assert_eq!(
+            scale_interval(interval(0, 1, HOUR), -2.0, Operator::Multiply).unwrap(),
+            interval(0, -2, -2 * HOUR)
+        );
+        assert!(matches!(
+            scale_interval(interval(1, 0, 0), 0.0, Operator::Divide),
+            Err(ArrowError::DivideByZero)
+        ));
+        assert!(matches!(
+            scale_interval(interval(i32::MAX, 0, 0), 2.0, Operator::Multiply),
+            Err(ArrowError::ArithmeticOverflow(_))
+        ));
+        assert!(matches!(
+            scale_interval(interval(1, 0, 0), f64::NAN, Operator::Multiply),
+            Err(ArrowError::ArithmeticOverflow(_))
+        ));
+    }
 }
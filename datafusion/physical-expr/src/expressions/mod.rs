@@ -35,6 +35,7 @@ mod no_op;
 mod not;
 mod try_cast;
 mod unknown_column;
+mod user_defined_cast;
 
 pub use crate::PhysicalSortExpr;
 /// Module with some convenient methods used in expression building
@@ -58,5 +59,6 @@ pub use no_op::NoOp;
 pub use not::{NotExpr, not};
 pub use try_cast::{TryCastExpr, try_cast};
 pub use unknown_column::UnKnownColumn;
+pub use user_defined_cast::{UserDefinedCastExpr, user_defined_cast};
 
 pub(crate) use cast::cast_with_target_field;
@@ -0,0 +1,275 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::PhysicalExpr;
+use crate::expressions::cast::DEFAULT_CAST_OPTIONS;
+
+use arrow::compute::CastOptions;
+use arrow::datatypes::{DataType, FieldRef, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use datafusion_expr::ptr_eq::{arc_ptr_eq, arc_ptr_hash};
+use datafusion_expr::registry::{CastRegistry, UserDefinedCastRef};
+
+/// CAST expression that converts its input with a [`UserDefinedCast`]
+/// registered in a [`CastRegistry`], rather than with the Arrow cast kernels
+///
+/// [`UserDefinedCast`]: datafusion_expr::registry::UserDefinedCast
+#[derive(Debug, Clone)]
+pub struct UserDefinedCastExpr {
+    /// The expression to cast
+    expr: Arc<dyn PhysicalExpr>,
+    /// The field of the expression to cast
+    input_field: FieldRef,
+    /// Field describing the output after casting
+    target_field: FieldRef,
+    /// The cast converting the values
+    cast: UserDefinedCastRef,
+    /// Cast options
+    cast_options: CastOptions<'static>,
+}
+
+impl PartialEq for UserDefinedCastExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.expr.eq(&other.expr)
+            && self.input_field.eq(&other.input_field)
+            && self.target_field.eq(&other.target_field)
+            && arc_ptr_eq(&self.cast, &other.cast)
+            && self.cast_options.eq(&other.cast_options)
+    }
+}
+
+impl Eq for UserDefinedCastExpr {}
+
+impl Hash for UserDefinedCastExpr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.expr.hash(state);
+        self.input_field.hash(state);
+        self.target_field.hash(state);
+        arc_ptr_hash(&self.cast, state);
+        self.cast_options.hash(state);
+    }
+}
+
+impl UserDefinedCastExpr {
+    /// Create a new `UserDefinedCastExpr` converting `expr`, whose output is
+    /// described by `input_field`, to `target_field` with `cast`
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        input_field: FieldRef,
+        target_field: FieldRef,
+        cast: UserDefinedCastRef,
+        cast_options: Option<CastOptions<'static>>,
+    ) -> Self {
+        Self {
+            expr,
+            input_field,
+            target_field,
+            cast,
+            cast_options: cast_options.unwrap_or(DEFAULT_CAST_OPTIONS),
+        }
+    }
+
+    /// The expression to cast
+    pub fn expr(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.expr
+    }
+
+    /// The data type to cast to
+    pub fn cast_type(&self) -> &DataType {
+        self.target_field.data_type()
+    }
+
+    /// Field describing the output after casting
+    pub fn target_field(&self) -> &FieldRef {
+        &self.target_field
+    }
+
+    /// The cast converting the values
+    pub fn cast(&self) -> &UserDefinedCastRef {
+        &self.cast
+    }
+
+    /// The cast options
+    pub fn cast_options(&self) -> &CastOptions<'static> {
+        &self.cast_options
+    }
+}
+
+impl fmt::Display for UserDefinedCastExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CAST({} AS {})", self.expr, self.cast_type())
+    }
+}
+
+impl PhysicalExpr for UserDefinedCastExpr {
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(self.cast_type().clone())
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> Result<bool> {
+        Ok(self.expr.nullable(input_schema)? || self.target_field.is_nullable())
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        match self.expr.evaluate(batch)? {
+            ColumnarValue::Array(array) => Ok(ColumnarValue::Array(self.cast.cast(
+                &array,
+                &self.input_field,
+                &self.target_field,
+                &self.cast_options,
+            )?)),
+            ColumnarValue::Scalar(scalar) => {
+                let array = self.cast.cast(
+                    &scalar.to_array()?,
+                    &self.input_field,
+                    &self.target_field,
+                    &self.cast_options,
+                )?;
+                Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                    &array, 0,
+                )?))
+            }
+        }
+    }
+
+    fn return_field(&self, _input_schema: &Schema) -> Result<FieldRef> {
+        Ok(Arc::clone(&self.target_field))
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn PhysicalExpr>> {
+        vec![&self.expr]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(UserDefinedCastExpr::new(
+            Arc::clone(&children[0]),
+            Arc::clone(&self.input_field),
+            Arc::clone(&self.target_field),
+            Arc::clone(&self.cast),
+            Some(self.cast_options.clone()),
+        )))
+    }
+
+    fn fmt_sql(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CAST(")?;
+        self.expr.fmt_sql(f)?;
+        write!(f, " AS {:?}", self.cast_type())?;
+
+        write!(f, ")")
+    }
+}
+
+/// Return a PhysicalExpression converting `expr` to `target_field` with the
+/// cast registered in `registry`, or `None` if there is no such cast
+pub fn user_defined_cast(
+    expr: Arc<dyn PhysicalExpr>,
+    input_schema: &Schema,
+    target_field: FieldRef,
+    registry: &dyn CastRegistry,
+) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+    let input_field = expr.return_field(input_schema)?;
+    let Some(cast) = registry.find_cast(&input_field, &target_field) else {
+        return Ok(None);
+    };
+    Ok(Some(Arc::new(UserDefinedCastExpr::new(
+        expr,
+        input_field,
+        target_field,
+        cast,
+        None,
+    ))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::expressions::column::col;
+
+    use arrow::array::{Array, ArrayRef, Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field};
+    use datafusion_common::cast::as_string_array;
+    use datafusion_expr::registry::{MemoryCastRegistry, UserDefinedCast};
+
+    /// Converts integers to strings, with a prefix
+    #[derive(Debug)]
+    struct PrefixedCast;
+
+    impl UserDefinedCast for PrefixedCast {
+        fn name(&self) -> &str {
+            "prefixed"
+        }
+
+        fn can_cast(&self, from: &Field, to: &Field) -> bool {
+            from.data_type() == &DataType::Int32 && to.data_type() == &DataType::Utf8
+        }
+
+        fn cast(
+            &self,
+            array: &ArrayRef,
+            _from: &Field,
+            _to: &Field,
+            _options: &CastOptions,
+        ) -> Result<ArrayRef> {
+            let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+            Ok(Arc::new(
+                array
+                    .iter()
+                    .map(|v| v.map(|v| format!("#{v}")))
+                    .collect::<StringArray>(),
+            ))
+        }
+    }
+
+    #[test]
+    fn user_defined_cast_expr() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let registry = MemoryCastRegistry::new();
+        registry.register_cast(Arc::new(PrefixedCast))?;
+
+        let to_utf8 = Arc::new(Field::new("", DataType::Utf8, true));
+        let to_int64 = Arc::new(Field::new("", DataType::Int64, true));
+        assert!(
+            user_defined_cast(col("a", &schema)?, &schema, to_int64, &registry)?
+                .is_none()
+        );
+        let expr =
+            user_defined_cast(col("a", &schema)?, &schema, to_utf8, &registry)?.unwrap();
+        assert_eq!(expr.to_string(), "CAST(a@0 AS Utf8)");
+        assert_eq!(expr.data_type(&schema)?, DataType::Utf8);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))],
+        )?;
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows())?;
+        let result = as_string_array(&result)?;
+        assert_eq!(result.value(0), "#1");
+        assert!(result.is_null(1));
+        assert_eq!(result.value(2), "#3");
+        Ok(())
+    }
+}
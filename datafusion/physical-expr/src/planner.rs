@@ -214,7 +214,17 @@ pub fn create_physical_expr(
             //
             // There should be no coercion during physical
             // planning.
-            binary(lhs, *op, rhs, input_schema)
+            //
+            // In ANSI mode integer arithmetic raises an error on overflow
+            // rather than wrapping around. As constant folding evaluates the
+            // same physical expressions, folded and runtime results agree.
+            let fail_on_overflow = execution_props
+                .config_options()
+                .is_some_and(|options| options.execution.enable_ansi_mode);
+            Ok(Arc::new(
+                expressions::BinaryExpr::new(lhs, *op, rhs)
+                    .with_fail_on_overflow(fail_on_overflow),
+            ))
         }
         Expr::Like(Like {
             negated,
@@ -294,12 +304,26 @@ pub fn create_physical_expr(
                 };
             Ok(expressions::case(expr, when_then_expr, else_expr)?)
         }
-        Expr::Cast(Cast { expr, field }) => expressions::cast_with_target_field(
-            create_physical_expr(expr, input_dfschema, execution_props)?,
-            input_schema,
-            Arc::clone(field),
-            None,
-        ),
+        Expr::Cast(Cast { expr, field }) => {
+            let expr = create_physical_expr(expr, input_dfschema, execution_props)?;
+            // casts registered by the user take precedence over the Arrow casts
+            if let Some(registry) = execution_props.cast_registry()
+                && let Some(cast) = expressions::user_defined_cast(
+                    Arc::clone(&expr),
+                    input_schema,
+                    Arc::clone(field),
+                    registry.as_ref(),
+                )?
+            {
+                return Ok(cast);
+            }
+            expressions::cast_with_target_field(
+                expr,
+                input_schema,
+                Arc::clone(field),
+                None,
+            )
+        }
         Expr::TryCast(TryCast { expr, field }) => {
             if !field.metadata().is_empty() {
                 let (_, src_field) = expr.to_field(input_dfschema)?;
@@ -29,7 +29,7 @@ use crate::optimizer::PhysicalOptimizerRule;
 use crate::output_requirements::OutputRequirementExec;
 use crate::utils::{
     add_sort_above_with_check, is_coalesce_partitions, is_repartition,
-    is_sort_preserving_merge,
+    is_sort_preserving_merge, prefer_order_preserving_variants,
 };
 
 use arrow::compute::SortOptions;
@@ -1216,8 +1216,11 @@ pub fn ensure_distribution(
     // - it is desired according to config
     // - when plan is unbounded
     // - when it is pipeline friendly (can incrementally produce results)
-    let order_preserving_variants_desirable =
-        unbounded_and_pipeline_friendly || config.optimizer.prefer_existing_sort;
+    let mut order_preserving_variants_desirable = unbounded_and_pipeline_friendly;
+    for child in dist_context.plan.children() {
+        order_preserving_variants_desirable = order_preserving_variants_desirable
+            || prefer_order_preserving_variants(child, config)?;
+    }
 
     // Remove unnecessary repartition from the physical plan if any
     let DistributionContext {
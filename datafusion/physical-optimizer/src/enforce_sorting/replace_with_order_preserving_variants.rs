@@ -23,6 +23,7 @@ use std::sync::Arc;
 
 use crate::utils::{
     is_coalesce_partitions, is_repartition, is_sort, is_sort_preserving_merge,
+    prefer_order_preserving_variants,
 };
 
 use datafusion_common::config::ConfigOptions;
@@ -269,9 +270,9 @@ pub fn replace_with_order_preserving_variants(
 
     // For unbounded cases, we replace with the order-preserving variant in any
     // case, as doing so helps fix the pipeline. Also replace if config allows.
-    let use_order_preserving_variant = config.optimizer.prefer_existing_sort
-        || (requirements.plan.boundedness().is_unbounded()
-            && requirements.plan.pipeline_behavior() == EmissionType::Final);
+    let use_order_preserving_variant = (requirements.plan.boundedness().is_unbounded()
+        && requirements.plan.pipeline_behavior() == EmissionType::Final)
+        || prefer_order_preserving_variants(&requirements.children[0].plan, config)?;
 
     // Create an alternate plan with order-preserving variants:
     let mut alternate_plan = plan_with_order_preserving_variants(
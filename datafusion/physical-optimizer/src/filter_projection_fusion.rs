@@ -0,0 +1,132 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FilterProjectionFusion`] fuses a projection on top of a filter into a
+//! single [`FilterProjectExec`].
+
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+use datafusion_common::Result;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_plan::ExecutionPlan;
+use datafusion_physical_plan::filter::FilterExec;
+use datafusion_physical_plan::filter_project::FilterProjectExec;
+use datafusion_physical_plan::projection::{ProjectionExec, ProjectionExpr};
+
+/// Physical optimizer rule that replaces
+///
+/// ```text
+/// ProjectionExec
+///   FilterExec
+/// ```
+///
+/// with a [`FilterProjectExec`], which filters and projects each input batch
+/// in a single pass and evaluates sub-expressions shared by the predicate
+/// and the projection only once.
+///
+/// The rule only runs when `enable_filter_projection_fusion` is set, and
+/// skips filters with a fetch limit.
+#[derive(Default, Clone, Debug)]
+pub struct FilterProjectionFusion;
+
+impl FilterProjectionFusion {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn try_fuse(plan: &Arc<dyn ExecutionPlan>) -> Result<Option<FilterProjectExec>> {
+        let Some(projection) = plan.downcast_ref::<ProjectionExec>() else {
+            return Ok(None);
+        };
+        let Some(filter) = projection.input().downcast_ref::<FilterExec>() else {
+            return Ok(None);
+        };
+        if filter.fetch().is_some() {
+            return Ok(None);
+        }
+
+        // The projection references the output of the filter, which may be
+        // narrowed by the filter's own projection. Remap it to the filter input.
+        let exprs = match filter.projection() {
+            Some(indices) => {
+                let input_schema = filter.input().schema();
+                projection
+                    .expr()
+                    .iter()
+                    .map(|proj_expr| {
+                        let expr = Arc::clone(&proj_expr.expr)
+                            .transform_up(|expr| {
+                                let Some(column) = expr.downcast_ref::<Column>() else {
+                                    return Ok(Transformed::no(expr));
+                                };
+                                let index = indices[column.index()];
+                                Ok(Transformed::yes(Arc::new(Column::new(
+                                    input_schema.field(index).name(),
+                                    index,
+                                ))
+                                    as Arc<dyn PhysicalExpr>))
+                            })
+                            .data()?;
+                        Ok(ProjectionExpr::new(expr, proj_expr.alias.clone()))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
+            None => projection.expr().to_vec(),
+        };
+
+        let fused = FilterProjectExec::try_new(
+            Arc::clone(filter.predicate()),
+            exprs,
+            Arc::clone(filter.input()),
+        )?
+        .with_default_selectivity(filter.default_selectivity())?
+        .with_batch_size(filter.batch_size());
+        Ok(Some(fused))
+    }
+}
+
+impl PhysicalOptimizerRule for FilterProjectionFusion {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_filter_projection_fusion {
+            return Ok(plan);
+        }
+
+        plan.transform_up(|plan| {
+            Ok(match Self::try_fuse(&plan)? {
+                Some(fused) => Transformed::yes(Arc::new(fused) as _),
+                None => Transformed::no(plan),
+            })
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "FilterProjectionFusion"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,249 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FuseStarJoins`] replaces chains of hash joins of a fact input with
+//! several dimensions by a single [`StarJoinExec`]
+
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+
+use crate::PhysicalOptimizerRule;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{JoinType, NullEquality, Result, plan_err};
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
+use datafusion_physical_plan::ExecutionPlan;
+use datafusion_physical_plan::joins::{
+    HashJoinExec, PartitionMode, StarJoinDimension, StarJoinExec,
+};
+use datafusion_physical_plan::projection::{ProjectionExec, ProjectionExpr};
+
+/// Replaces a chain of inner [`HashJoinExec`]s in `CollectLeft` mode, each of
+/// which joins a small dimension input with the output of the next one, by a
+/// [`StarJoinExec`]:
+///
+/// ```text
+///   HashJoinExec                   ProjectionExec
+///    /        \                          |
+/// stores   HashJoinExec      =>     StarJoinExec
+///           /        \               /    |    \
+///       products    sales        sales stores products
+/// ```
+///
+/// The star join looks up each batch of the fact input in the hash tables of
+/// all dimensions before materializing the output, rather than materializing
+/// the output of every join of the chain.
+///
+/// Only joins without a filter, fetch or null-equal keys are fused, and only
+/// when the join keys of every dimension reference the fact input alone.
+/// Column only projections between the joins, such as those added when
+/// [`JoinSelection`] swaps the inputs of a join, are folded into a projection
+/// on top of the star join, which restores the original column order.
+///
+/// [`JoinSelection`]: crate::join_selection::JoinSelection
+#[derive(Debug, Default)]
+pub struct FuseStarJoins {}
+
+impl FuseStarJoins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicalOptimizerRule for FuseStarJoins {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_star_join {
+            return Ok(plan);
+        }
+
+        plan.transform_down(|plan| {
+            let Some(star) = StarJoin::try_new(&plan) else {
+                return Ok(Transformed::no(plan));
+            };
+            if star.dimensions.len() < 2 {
+                return Ok(Transformed::no(plan));
+            }
+            let fused = star.build(&plan.schema())?;
+            // keep the plan if the fused plan would differ in field metadata or
+            // nullability
+            if fused.schema() != plan.schema() {
+                return Ok(Transformed::no(plan));
+            }
+            Ok(Transformed::yes(fused))
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "FuseStarJoins"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Where an output column of a chain of joins comes from
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    /// The column with the index of the fact input
+    Fact(usize),
+    /// The column with the second index of the dimension with the first index
+    Dimension(usize, usize),
+}
+
+/// A chain of joins that can be replaced by a [`StarJoinExec`]
+struct StarJoin {
+    fact: Arc<dyn ExecutionPlan>,
+    /// The dimensions, in the order in which the chain joins them
+    dimensions: Vec<StarJoinDimension>,
+    /// The source of each output column of the chain
+    columns: Vec<Source>,
+}
+
+impl StarJoin {
+    /// A chain without joins, which only outputs the fact input
+    fn fact(fact: &Arc<dyn ExecutionPlan>) -> Self {
+        Self {
+            fact: Arc::clone(fact),
+            dimensions: vec![],
+            columns: (0..fact.schema().fields().len())
+                .map(Source::Fact)
+                .collect(),
+        }
+    }
+
+    /// Returns the longest chain of joins that ends in `plan`, if `plan` is
+    /// a fusable join or a column only projection of one
+    fn try_new(plan: &Arc<dyn ExecutionPlan>) -> Option<Self> {
+        if let Some(projection) = plan.downcast_ref::<ProjectionExec>() {
+            let mut star = Self::try_new(projection.input())?;
+            star.columns = projection
+                .expr()
+                .iter()
+                .map(|expr| {
+                    let column = expr.expr.downcast_ref::<Column>()?;
+                    star.columns.get(column.index()).copied()
+                })
+                .collect::<Option<_>>()?;
+            return Some(star);
+        }
+
+        let join = plan.downcast_ref::<HashJoinExec>()?;
+        if join.mode != PartitionMode::CollectLeft
+            || join.join_type != JoinType::Inner
+            || join.filter.is_some()
+            || join.fetch().is_some()
+            || join.null_equality != NullEquality::NullEqualsNothing
+            || join.null_aware
+        {
+            return None;
+        }
+
+        // continue the chain of the probe side if the join keys only
+        // reference its fact input, or start a new chain otherwise
+        let right_on = join.on.iter().map(|(_, r)| r).collect::<Vec<_>>();
+        let (mut star, fact_on) = Self::try_new(&join.right)
+            .and_then(|star| {
+                let fact_on = star.fact_exprs(&right_on)?;
+                Some((star, fact_on))
+            })
+            .or_else(|| {
+                let star = Self::fact(&join.right);
+                let fact_on = star.fact_exprs(&right_on)?;
+                Some((star, fact_on))
+            })?;
+
+        let dimension = star.dimensions.len();
+        let on = fact_on
+            .into_iter()
+            .zip(join.on.iter().map(|(l, _)| Arc::clone(l)))
+            .collect();
+        star.dimensions
+            .push(StarJoinDimension::new(Arc::clone(&join.left), on));
+        let mut columns = (0..join.left.schema().fields().len())
+            .map(|i| Source::Dimension(dimension, i))
+            .collect::<Vec<_>>();
+        columns.append(&mut star.columns);
+        star.columns = match &join.projection {
+            Some(projection) => projection.iter().map(|i| columns[*i]).collect(),
+            None => columns,
+        };
+        Some(star)
+    }
+
+    /// Rewrites expressions over the output of the chain into expressions
+    /// over the fact input, if they only reference fact columns
+    fn fact_exprs(&self, exprs: &[&PhysicalExprRef]) -> Option<Vec<PhysicalExprRef>> {
+        let fact_schema = self.fact.schema();
+        exprs
+            .iter()
+            .map(|expr| {
+                Arc::clone(expr)
+                    .transform(|expr| {
+                        let Some(column) = expr.downcast_ref::<Column>() else {
+                            return Ok(Transformed::no(expr));
+                        };
+                        match self.columns.get(column.index()) {
+                            Some(Source::Fact(index)) => Ok(Transformed::yes(Arc::new(
+                                Column::new(fact_schema.field(*index).name(), *index),
+                            )
+                                as Arc<dyn PhysicalExpr>)),
+                            _ => plan_err!(
+                                "Join key {column} does not reference the fact input"
+                            ),
+                        }
+                    })
+                    .data()
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Creates the [`StarJoinExec`], and a projection on top of it that
+    /// outputs the columns of the chain with the names in `schema`
+    fn build(self, schema: &Schema) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut offsets = vec![self.fact.schema().fields().len()];
+        for dimension in &self.dimensions {
+            let offset = offsets[offsets.len() - 1];
+            offsets.push(offset + dimension.input.schema().fields().len());
+        }
+
+        let star_join = Arc::new(StarJoinExec::try_new(self.fact, self.dimensions)?);
+        let star_schema = star_join.schema();
+        let exprs = self
+            .columns
+            .iter()
+            .zip(schema.fields())
+            .map(|(source, field)| {
+                let index = match source {
+                    Source::Fact(index) => *index,
+                    Source::Dimension(dimension, index) => offsets[*dimension] + index,
+                };
+                let column = Column::new(star_schema.field(index).name(), index);
+                ProjectionExpr::new(Arc::new(column), field.name().clone())
+            })
+            .collect::<Vec<_>>();
+        Ok(Arc::new(ProjectionExec::try_new(exprs, star_join)?))
+    }
+}
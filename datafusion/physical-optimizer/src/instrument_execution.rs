@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`InstrumentExecution`] optimizer rule wraps every operator of the
+//! physical plan in an [`InstrumentedExec`], to emit a `tracing` span for the
+//! execution of each of its partitions.
+//!
+//! [`InstrumentedExec`]: datafusion_physical_plan::instrument::InstrumentedExec
+
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+
+use datafusion_common::Result;
+use datafusion_common::config::ConfigOptions;
+use datafusion_physical_plan::ExecutionPlan;
+use datafusion_physical_plan::instrument::instrument_plan;
+
+/// `InstrumentExecution` is a [`PhysicalOptimizerRule`] that wraps every
+/// operator of the plan in an [`InstrumentedExec`].
+///
+/// It is not one of the default rules: register it last, after all the
+/// rules that rewrite the plan, for example with
+/// `SessionStateBuilder::with_physical_optimizer_rule`.
+///
+/// [`InstrumentedExec`]: datafusion_physical_plan::instrument::InstrumentedExec
+#[derive(Default, Debug)]
+pub struct InstrumentExecution {}
+
+impl InstrumentExecution {
+    #[expect(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for InstrumentExecution {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        _config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        instrument_plan(plan)
+    }
+
+    fn name(&self) -> &str {
+        "InstrumentExecution"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
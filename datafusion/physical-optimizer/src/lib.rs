@@ -30,7 +30,9 @@ pub mod combine_partial_final_agg;
 pub mod enforce_distribution;
 pub mod enforce_sorting;
 pub mod ensure_coop;
+pub mod filter_projection_fusion;
 pub mod filter_pushdown;
+pub mod fuse_star_joins;
 pub mod join_selection;
 pub mod limit_pushdown;
 pub mod limit_pushdown_past_window;
@@ -40,12 +42,18 @@ pub mod output_requirements;
 pub mod projection_pushdown;
 pub use datafusion_pruning as pruning;
 pub mod hash_join_buffering;
+#[cfg(feature = "tracing")]
+pub mod instrument_execution;
 pub mod pushdown_sort;
 pub mod sanity_checker;
+pub mod share_hash_join_build_side;
 pub mod topk_aggregation;
 pub mod topk_repartition;
 pub mod update_aggr_exprs;
 pub mod utils;
 pub mod window_topn;
 
-pub use optimizer::{ConfigOnlyContext, PhysicalOptimizerContext, PhysicalOptimizerRule};
+pub use optimizer::{
+    ConfigOnlyContext, PhysicalOptimizerContext, PhysicalOptimizerRule,
+    PhysicalOptimizerRulePosition,
+};
@@ -25,13 +25,16 @@ use crate::combine_partial_final_agg::CombinePartialFinalAggregate;
 use crate::enforce_distribution::EnforceDistribution;
 use crate::enforce_sorting::EnforceSorting;
 use crate::ensure_coop::EnsureCooperative;
+use crate::filter_projection_fusion::FilterProjectionFusion;
 use crate::filter_pushdown::FilterPushdown;
+use crate::fuse_star_joins::FuseStarJoins;
 use crate::join_selection::JoinSelection;
 use crate::limit_pushdown::LimitPushdown;
 use crate::limited_distinct_aggregation::LimitedDistinctAggregation;
 use crate::output_requirements::OutputRequirements;
 use crate::projection_pushdown::ProjectionPushdown;
 use crate::sanity_checker::SanityCheckPlan;
+use crate::share_hash_join_build_side::ShareHashJoinBuildSide;
 use crate::topk_aggregation::TopKAggregation;
 use crate::topk_repartition::TopKRepartition;
 use crate::update_aggr_exprs::OptimizeAggregateOrder;
@@ -40,8 +43,8 @@ use crate::hash_join_buffering::HashJoinBuffering;
 use crate::limit_pushdown_past_window::LimitPushPastWindows;
 use crate::pushdown_sort::PushdownSort;
 use crate::window_topn::WindowTopN;
-use datafusion_common::Result;
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::{DataFusionError, Result, plan_datafusion_err, plan_err};
 use datafusion_physical_plan::ExecutionPlan;
 use datafusion_physical_plan::operator_statistics::StatisticsRegistry;
 
@@ -125,6 +128,39 @@ pub trait PhysicalOptimizerRule: Debug + std::any::Any {
     /// Some of the optimization rules might change the nullable properties of the schema
     /// and should disable the schema check.
     fn schema_check(&self) -> bool;
+
+    /// Whether this rule bases its rewrites on the statistics of the plan.
+    ///
+    /// Rules that return `true` are skipped when
+    /// `datafusion.execution.collect_statistics` is disabled, as the plan
+    /// then carries no statistics to base their decisions on.
+    fn requires_statistics(&self) -> bool {
+        false
+    }
+
+    /// Whether this rule keeps the ordering of every operator's output, so
+    /// that the ordering requirements satisfied by [`EnforceSorting`] still
+    /// hold after it runs.
+    ///
+    /// Rules that return `false` can only be inserted before
+    /// [`EnforceSorting`], see [`PhysicalOptimizer::insert_rule`].
+    fn preserves_ordering(&self) -> bool {
+        true
+    }
+}
+
+/// Where to insert a [`PhysicalOptimizerRule`] relative to the existing
+/// rules of a [`PhysicalOptimizer`], see [`PhysicalOptimizer::insert_rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhysicalOptimizerRulePosition {
+    /// Before all other rules
+    First,
+    /// After all other rules
+    Last,
+    /// Immediately before the first rule with the given name
+    Before(String),
+    /// Immediately after the last rule with the given name
+    After(String),
 }
 
 /// A rule-based physical optimizer.
@@ -160,6 +196,10 @@ impl PhysicalOptimizer {
             // repartitioning and local sorting steps to meet distribution and ordering requirements.
             // Therefore, it should run before EnforceDistribution and EnforceSorting.
             Arc::new(JoinSelection::new()),
+            // The FuseStarJoins rule replaces chains of hash joins with dimension tables
+            // by a single star join. It runs right after JoinSelection, which decides on the
+            // join modes it looks for, so that the following rules see the fused plan.
+            Arc::new(FuseStarJoins::new()),
             // The LimitedDistinctAggregation rule should be applied before the EnforceDistribution rule,
             // as that rule may inject other operations in between the different AggregateExecs.
             // Applying the rule early means only directly-connected AggregateExecs must be examined.
@@ -230,6 +270,15 @@ impl PhysicalOptimizer {
             // Therefore, it should be run at the end of the optimization process since any changes to the plan may break the dynamic filter's references.
             // See `FilterPushdownPhase` for more details.
             Arc::new(FilterPushdown::new_post_optimization()),
+            // The FilterProjectionFusion rule fuses projections on top of filters
+            // into a single operator. It runs after the filter and projection
+            // pushdown rules, which only know about the unfused operators.
+            Arc::new(FilterProjectionFusion::new()),
+            // The ShareHashJoinBuildSide rule lets hash joins over the same build
+            // side share one hash table. It must run after all rules that change
+            // hash joins or their inputs, as changing the build side of a join
+            // stops it from sharing the table.
+            Arc::new(ShareHashJoinBuildSide::new()),
             // The SanityCheckPlan rule checks whether the order and
             // distribution requirements of each node in the plan
             // is satisfied. It will also reject non-runnable query
@@ -248,4 +297,56 @@ impl PhysicalOptimizer {
     pub fn with_rules(rules: Vec<Arc<dyn PhysicalOptimizerRule + Send + Sync>>) -> Self {
         Self { rules }
     }
+
+    /// Insert `rule` at `position` relative to the existing rules.
+    ///
+    /// Returns an error if the rule named by `position` does not exist, or
+    /// if `rule` does not [preserve ordering] and would run after
+    /// [`EnforceSorting`].
+    ///
+    /// [preserve ordering]: PhysicalOptimizerRule::preserves_ordering
+    pub fn insert_rule(
+        &mut self,
+        rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+        position: PhysicalOptimizerRulePosition,
+    ) -> Result<()> {
+        let index = match position {
+            PhysicalOptimizerRulePosition::First => 0,
+            PhysicalOptimizerRulePosition::Last => self.rules.len(),
+            PhysicalOptimizerRulePosition::Before(name) => self
+                .rules
+                .iter()
+                .position(|r| r.name() == name)
+                .ok_or_else(|| rule_not_found(&name))?,
+            PhysicalOptimizerRulePosition::After(name) => {
+                self.rules
+                    .iter()
+                    .rposition(|r| r.name() == name)
+                    .ok_or_else(|| rule_not_found(&name))?
+                    + 1
+            }
+        };
+
+        let enforce_sorting = EnforceSorting::new();
+        if !rule.preserves_ordering()
+            && let Some(sorting_index) = self
+                .rules
+                .iter()
+                .position(|r| r.name() == enforce_sorting.name())
+            && index > sorting_index
+        {
+            return plan_err!(
+                "Physical optimizer rule {} does not preserve ordering and must run before {}",
+                rule.name(),
+                enforce_sorting.name()
+            );
+        }
+
+        self.rules.insert(index, rule);
+        Ok(())
+    }
+}
+
+fn rule_not_found(name: &str) -> DataFusionError {
+    plan_datafusion_err!("Physical optimizer rule {name} not found")
 }
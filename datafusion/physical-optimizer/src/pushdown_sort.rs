@@ -45,6 +45,10 @@
 //!   order (e.g., alphabetical order ≠ sort key order), this fixes the ordering
 //!   and enables sort elimination. Works for both single-partition and
 //!   multi-partition plans with multi-file groups.
+//! - **Merging sorted partitions**: when every partition of the input of a
+//!   sort is sorted, for instance because the files of each file group do
+//!   not overlap, the partitions are combined with a `SortPreservingMergeExec`
+//!   instead of being concatenated and sorted again.
 //! - **Reverse scan optimization**: when required sort is the reverse of the data source's
 //!   natural ordering, enable reverse scanning (reading row groups in reverse order)
 //! - **Prefix matching**: if data has ordering [A DESC, B ASC] and query needs
@@ -58,12 +62,12 @@ use crate::PhysicalOptimizerRule;
 use datafusion_common::Result;
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
-use datafusion_physical_plan::ExecutionPlan;
 use datafusion_physical_plan::SortOrderPushdownResult;
 use datafusion_physical_plan::buffer::BufferExec;
 use datafusion_physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion_physical_plan::sorts::sort::SortExec;
 use datafusion_physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use datafusion_physical_plan::{ExecutionPlan, ExecutionPlanProperties};
 use std::sync::Arc;
 
 /// A PhysicalOptimizerRule that attempts to push down sort requirements to data sources.
@@ -155,6 +159,20 @@ impl PhysicalOptimizerRule for PushdownSort {
                 SortOrderPushdownResult::Exact { inner } => {
                     // Data source guarantees perfect ordering - remove the Sort operator.
                     //
+                    // A sort that does not preserve partitioning produces a single
+                    // partition: when each of several input partitions is already
+                    // sorted (e.g. their files do not overlap), merge them instead.
+                    if !sort_exec.preserve_partitioning()
+                        && inner.output_partitioning().partition_count() > 1
+                    {
+                        let spm = SortPreservingMergeExec::new(
+                            required_ordering.clone(),
+                            inner,
+                        )
+                        .with_fetch(sort_exec.fetch());
+                        return Ok(Transformed::yes(Arc::new(spm)));
+                    }
+
                     // If the SortExec carried a fetch (LIMIT), we must preserve it.
                     // First try pushing the limit into the source via `with_fetch()`.
                     // If the source doesn't support `with_fetch`, fall back to
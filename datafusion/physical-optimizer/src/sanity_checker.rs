@@ -30,7 +30,9 @@ use datafusion_common::config::{ConfigOptions, OptimizerOptions};
 use datafusion_common::plan_err;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
 use datafusion_physical_expr::intervals::utils::{check_support, is_datatype_supported};
-use datafusion_physical_plan::execution_plan::{Boundedness, EmissionType};
+use datafusion_physical_plan::execution_plan::{
+    Boundedness, EmissionType, InvariantLevel,
+};
 use datafusion_physical_plan::joins::SymmetricHashJoinExec;
 use datafusion_physical_plan::{ExecutionPlanProperties, get_plan_string};
 
@@ -83,6 +85,36 @@ fn check_plan_sanity_recursive(
     Ok(TreeNodeRecursion::Continue)
 }
 
+/// Validates `plan`, returning an error describing the first broken invariant
+/// found.
+///
+/// This is intended to be called after building or rewriting a plan by hand
+/// (for example in a custom [`PhysicalOptimizerRule`]), to catch bugs before the
+/// plan is executed. Bottom-up, it checks for every node that:
+///
+/// * it satisfies its own [`InvariantLevel::Executable`] invariants, see
+///   [`ExecutionPlan::check_invariants`]
+/// * the ordering and distribution requirements it has on its children are
+///   satisfied, and it does not break the pipeline on infinite inputs, see
+///   [`check_plan_sanity`]
+pub fn validate_physical_plan(
+    plan: &Arc<dyn ExecutionPlan>,
+    config: &ConfigOptions,
+) -> Result<()> {
+    plan.apply_children(|child| {
+        validate_physical_plan(child, config)?;
+        Ok(TreeNodeRecursion::Continue)
+    })?;
+    plan.check_invariants(InvariantLevel::Executable)
+        .and_then(|_| check_plan_sanity(plan, &config.optimizer))
+        .map_err(|e| {
+            e.context(format!(
+                "Invariant for ExecutionPlan node '{}' failed",
+                plan.name()
+            ))
+        })
+}
+
 /// This function propagates finiteness information and rejects any plan with
 /// pipeline-breaking operators acting on infinite inputs.
 pub fn check_finiteness_requirements(
@@ -0,0 +1,292 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ShareHashJoinBuildSide`] lets hash joins over the same build side share
+//! a single hash table
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::PhysicalOptimizerRule;
+use datafusion_common::Result;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_datasource::PartitionedFile;
+use datafusion_datasource::file_scan_config::FileScanConfig;
+use datafusion_datasource::memory::MemorySourceConfig;
+use datafusion_datasource::source::DataSourceExec;
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr::expressions::DynamicFilterPhysicalExpr;
+use datafusion_physical_plan::empty::EmptyExec;
+use datafusion_physical_plan::joins::HashJoinExec;
+use datafusion_physical_plan::placeholder_row::PlaceholderRowExec;
+use datafusion_physical_plan::{ExecutionPlan, displayable};
+use itertools::Itertools;
+
+/// Finds [`HashJoinExec`]s in `CollectLeft` mode whose build sides are the
+/// same subplan, and lets them share a single hash table:
+///
+/// ```text
+///            ┌──────────────────┐
+///            │   HashJoinExec   │ <─┐
+///            └────▲────────▲────┘   │
+///        ┌────────┘        └──────┐ │ shared
+///        │                        │ │ hash table
+///  ┌───────────┐     ┌──────────────────┐
+///  │ dimension │     │   HashJoinExec   │ <─┘
+///  └───────────┘     └────▲────────▲────┘
+///                 ┌───────┘        └────────┐
+///                 │                         │
+///           ┌───────────┐              ┌────────┐
+///           │ dimension │              │  fact  │
+///           └───────────┘              └────────┘
+/// ```
+///
+/// The build side is only executed and hashed once, which saves memory and
+/// CPU in queries that join the same dimension table several times.
+///
+/// Two build sides are considered the same if their plans display the same
+/// and their leaves read the same data: the same files, compared file by file
+/// with their byte ranges and object store, or the same in-memory batches. Build sides that contain
+/// volatile expressions or dynamic filters, which may produce different rows
+/// each time they are evaluated, are never shared.
+///
+/// This rule must run after all rules that change hash joins or their inputs.
+#[derive(Debug, Default)]
+pub struct ShareHashJoinBuildSide {}
+
+impl ShareHashJoinBuildSide {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicalOptimizerRule for ShareHashJoinBuildSide {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.share_hash_join_build_side {
+            return Ok(plan);
+        }
+
+        // only mark hash tables as shared if another join uses them
+        let mut build_sides = HashMap::<String, Vec<BuildSide>>::new();
+        plan.apply(|node| {
+            if let Some((join, key)) = shareable_join(node) {
+                let build_sides = build_sides.entry(key).or_default();
+                match build_sides
+                    .iter_mut()
+                    .find(|build_side| same_sources(&build_side.input, &join.left))
+                {
+                    Some(build_side) => build_side.joins += 1,
+                    None => build_sides.push(BuildSide {
+                        input: Arc::clone(&join.left),
+                        joins: 1,
+                        owner: None,
+                    }),
+                }
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        if build_sides
+            .values()
+            .flatten()
+            .all(|build_side| build_side.joins < 2)
+        {
+            return Ok(plan);
+        }
+
+        plan.transform_up(|plan| {
+            let Some((join, key)) = shareable_join(&plan) else {
+                return Ok(Transformed::no(plan));
+            };
+            let Some(build_side) = build_sides.get_mut(&key).and_then(|build_sides| {
+                build_sides
+                    .iter_mut()
+                    .find(|build_side| same_sources(&build_side.input, &join.left))
+            }) else {
+                return Ok(Transformed::no(plan));
+            };
+            if build_side.joins < 2 {
+                return Ok(Transformed::no(plan));
+            }
+            let owner = build_side
+                .owner
+                .as_ref()
+                .map_or(join, |owner| owner.downcast_ref::<HashJoinExec>().unwrap());
+            let shared = join.builder().with_shared_build_side(owner).build_exec()?;
+            build_side.owner.get_or_insert_with(|| Arc::clone(&shared));
+            Ok(Transformed::yes(shared))
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "ShareHashJoinBuildSide"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// The joins of a plan that may share one hash table
+struct BuildSide {
+    /// The left input of the first of the joins
+    input: Arc<dyn ExecutionPlan>,
+    /// The number of joins
+    joins: usize,
+    /// The join whose hash table the others share, once it has been rewritten
+    owner: Option<Arc<dyn ExecutionPlan>>,
+}
+
+/// Returns `plan` if it is a [`HashJoinExec`] whose hash table may be shared,
+/// together with a key that it has in common with the joins it may share the
+/// table with.
+fn shareable_join(plan: &Arc<dyn ExecutionPlan>) -> Option<(&HashJoinExec, String)> {
+    let join = plan.downcast_ref::<HashJoinExec>()?;
+    if !join.can_share_build_side() || !is_deterministic(&join.left) {
+        return None;
+    }
+    let on = join.on.iter().map(|(left, _)| left).join(", ");
+    let key = format!(
+        "on=[{on}], null_equality={:?}\n{}",
+        join.null_equality,
+        displayable(join.left.as_ref()).indent(true)
+    );
+    Some((join, key))
+}
+
+/// Returns true if `plan` produces the same rows each time it is executed
+fn is_deterministic(plan: &Arc<dyn ExecutionPlan>) -> bool {
+    fn check(expr: &dyn PhysicalExpr) -> bool {
+        !expr.is_volatile_node()
+            && expr.downcast_ref::<DynamicFilterPhysicalExpr>().is_none()
+            && expr
+                .children()
+                .into_iter()
+                .all(|child| check(child.as_ref()))
+    }
+
+    let mut deterministic = true;
+    plan.apply(|node| {
+        node.apply_expressions(&mut |expr| {
+            deterministic &= check(expr);
+            Ok(if deterministic {
+                TreeNodeRecursion::Continue
+            } else {
+                TreeNodeRecursion::Stop
+            })
+        })
+    })
+    .expect("infallible closure should not fail");
+    deterministic
+}
+
+/// Returns true if `left` and `right`, whose plans display the same, have the
+/// same shape and their leaves read the same data
+fn same_sources(left: &Arc<dyn ExecutionPlan>, right: &Arc<dyn ExecutionPlan>) -> bool {
+    let (left_children, right_children) = (left.children(), right.children());
+    if left_children.len() != right_children.len() {
+        return false;
+    }
+    if !left_children.is_empty() {
+        return left_children
+            .into_iter()
+            .zip(right_children)
+            .all(|(left, right)| same_sources(left, right));
+    }
+    if Arc::ptr_eq(left, right) {
+        return true;
+    }
+    if left.schema() != right.schema() {
+        return false;
+    }
+    if left.is::<EmptyExec>() || left.is::<PlaceholderRowExec>() {
+        return true;
+    }
+    let (Some(left), Some(right)) = (
+        left.downcast_ref::<DataSourceExec>(),
+        right.downcast_ref::<DataSourceExec>(),
+    ) else {
+        return false;
+    };
+    let (left, right) = (left.data_source(), right.data_source());
+    if let Some(left) = left.downcast_ref::<FileScanConfig>() {
+        return right
+            .downcast_ref::<FileScanConfig>()
+            .is_some_and(|right| same_files(left, right));
+    }
+    // in-memory sources display only the number of batches
+    let (Some(left), Some(right)) = (
+        left.downcast_ref::<MemorySourceConfig>(),
+        right.downcast_ref::<MemorySourceConfig>(),
+    ) else {
+        return false;
+    };
+    let (left, right) = (left.partitions(), right.partitions());
+    left.len() == right.len()
+        && left.iter().zip(right).all(|(left, right)| {
+            left.len() == right.len()
+                && left.iter().zip(right).all(|(left, right)| {
+                    left.num_rows() == right.num_rows()
+                        && left
+                            .columns()
+                            .iter()
+                            .zip(right.columns())
+                            .all(|(left, right)| Arc::ptr_eq(left, right))
+                })
+        })
+}
+
+/// Returns true if the file scans `left` and `right` read the same files, in
+/// the same groups and byte ranges, from the same object store and with the
+/// same options. Their file sources are compared by their display.
+///
+/// The display of file scans can not be relied on for the files, as it leaves
+/// out the object store and cuts long lists of files short.
+fn same_files(left: &FileScanConfig, right: &FileScanConfig) -> bool {
+    let same_file = |left: &PartitionedFile, right: &PartitionedFile| {
+        left.object_meta == right.object_meta
+            && left.partition_values == right.partition_values
+            && left.range == right.range
+            && left.extensions.ptr_eq(&right.extensions)
+    };
+    let same_expr_adapter =
+        match (&left.expr_adapter_factory, &right.expr_adapter_factory) {
+            (None, None) => true,
+            (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+            _ => false,
+        };
+    left.object_store_url == right.object_store_url
+        && left.limit == right.limit
+        && left.file_compression_type == right.file_compression_type
+        && same_expr_adapter
+        && left.file_groups.len() == right.file_groups.len()
+        && left
+            .file_groups
+            .iter()
+            .zip(&right.file_groups)
+            .all(|(left, right)| {
+                left.len() == right.len()
+                    && left.iter().zip(right.iter()).all(|(l, r)| same_file(l, r))
+            })
+}
@@ -18,6 +18,7 @@
 use std::sync::Arc;
 
 use datafusion_common::Result;
+use datafusion_common::config::ConfigOptions;
 use datafusion_physical_expr::{LexOrdering, LexRequirement};
 use datafusion_physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use datafusion_physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
@@ -113,3 +114,30 @@ pub fn is_repartition(plan: &Arc<dyn ExecutionPlan>) -> bool {
 pub fn is_limit(plan: &Arc<dyn ExecutionPlan>) -> bool {
     plan.is::<GlobalLimitExec>() || plan.is::<LocalLimitExec>()
 }
+
+/// Checks whether ordering-preserving variants of the operators that change
+/// the partitioning of `plan` are preferred over sorting its output again.
+///
+/// This is the case if `prefer_existing_sort` is set or, if
+/// `order_preserving_repartition_cost_model` is enabled, when the estimated
+/// number of rows of `plan` exceeds what an order-preserving repartition
+/// buffers, so that sorting again costs more than merging.
+pub fn prefer_order_preserving_variants(
+    plan: &Arc<dyn ExecutionPlan>,
+    config: &ConfigOptions,
+) -> Result<bool> {
+    if !config.optimizer.order_preserving_repartition_cost_model {
+        return Ok(config.optimizer.prefer_existing_sort);
+    }
+    let num_rows = plan.partition_statistics(None)?.num_rows;
+    Ok(match num_rows.get_value() {
+        Some(&num_rows) => {
+            let target_partitions = config.execution.target_partitions;
+            let buffered_rows = target_partitions
+                .saturating_mul(target_partitions)
+                .saturating_mul(config.execution.batch_size);
+            num_rows > buffered_rows
+        }
+        None => config.optimizer.prefer_existing_sort,
+    })
+}
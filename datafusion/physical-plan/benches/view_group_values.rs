@@ -0,0 +1,171 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks for grouping and hashing `Utf8View` keys natively, compared
+//! with `Utf8` keys and with `Utf8View` keys cast to `Utf8` first (the copy
+//! the native implementations avoid).
+//!
+//! `view_group_values` measures `intern` followed by `emit(EmitTo::All)`, as
+//! the aggregation does; `view_hash` measures `create_hashes`, as the hash join
+//! does when building and probing its hash table.
+
+use arrow::array::{ArrayRef, StringArray, StringViewArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema};
+use criterion::{
+    BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main,
+};
+use datafusion_common::hash_utils::{RandomState, create_hashes};
+use datafusion_expr::EmitTo;
+use datafusion_physical_plan::aggregates::group_values::new_group_values;
+use datafusion_physical_plan::aggregates::order::GroupOrdering;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use std::sync::Arc;
+
+const SIZE: usize = 64 * 1024;
+const CARDINALITIES: [usize; 3] = [100, 10_000, SIZE];
+/// Short values are inlined in the views, long values are not
+const VALUE_PREFIXES: [(&str, &str); 2] =
+    [("short", "v_"), ("long", "a_long_key_prefix_")];
+// Fixed for reproducibility.
+const SEED: u64 = 0x5EED;
+
+/// The ways the keys are presented to the operators
+#[derive(Clone, Copy)]
+enum Input {
+    Utf8,
+    Utf8View,
+    /// `Utf8View` keys cast to `Utf8` before grouping or hashing
+    Utf8ViewCast,
+}
+
+impl Input {
+    const ALL: [Input; 3] = [Input::Utf8, Input::Utf8View, Input::Utf8ViewCast];
+
+    fn label(self) -> &'static str {
+        match self {
+            Input::Utf8 => "utf8",
+            Input::Utf8View => "utf8view",
+            Input::Utf8ViewCast => "utf8view_cast_to_utf8",
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Input::Utf8 | Input::Utf8ViewCast => DataType::Utf8,
+            Input::Utf8View => DataType::Utf8View,
+        }
+    }
+
+    /// Returns the keys to group or hash, given the keys as produced by the
+    /// input of the operator
+    fn prepare(self, array: &ArrayRef) -> ArrayRef {
+        match self {
+            Input::Utf8 | Input::Utf8View => Arc::clone(array),
+            Input::Utf8ViewCast => cast(array, &DataType::Utf8).unwrap(),
+        }
+    }
+}
+
+fn make_values(cardinality: usize, prefix: &str) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    (0..SIZE)
+        .map(|_| format!("{prefix}{:08}", rng.random_range(0..cardinality)))
+        .collect()
+}
+
+/// Returns the keys as produced by the input of the operator
+fn make_input(input: Input, values: &[String]) -> ArrayRef {
+    match input {
+        Input::Utf8 => Arc::new(StringArray::from_iter_values(values)),
+        Input::Utf8View | Input::Utf8ViewCast => {
+            Arc::new(StringViewArray::from_iter_values(values))
+        }
+    }
+}
+
+fn bench_id(input: Input, prefix_label: &str, cardinality: usize) -> BenchmarkId {
+    BenchmarkId::new(input.label(), format!("{prefix_label}_card_{cardinality}"))
+}
+
+fn bench_group_values(c: &mut Criterion) {
+    let mut group = c.benchmark_group("view_group_values");
+    group.throughput(Throughput::Elements(SIZE as u64));
+
+    for (prefix_label, prefix) in VALUE_PREFIXES {
+        for cardinality in CARDINALITIES {
+            let values = make_values(cardinality, prefix);
+            for input in Input::ALL {
+                let array = make_input(input, &values);
+                let schema =
+                    Arc::new(Schema::new(vec![Field::new("g", input.data_type(), true)]));
+                group.bench_function(bench_id(input, prefix_label, cardinality), |b| {
+                    b.iter_batched_ref(
+                        || {
+                            (
+                                new_group_values(
+                                    Arc::clone(&schema),
+                                    &GroupOrdering::None,
+                                )
+                                .unwrap(),
+                                Vec::<usize>::with_capacity(SIZE),
+                            )
+                        },
+                        |(gv, groups)| {
+                            let keys = input.prepare(&array);
+                            gv.intern(std::slice::from_ref(&keys), groups).unwrap();
+                            black_box(&*groups);
+                            black_box(gv.emit(EmitTo::All).unwrap());
+                        },
+                        BatchSize::SmallInput,
+                    );
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+fn bench_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("view_hash");
+    group.throughput(Throughput::Elements(SIZE as u64));
+    let random_state = RandomState::with_seed(0);
+
+    for (prefix_label, prefix) in VALUE_PREFIXES {
+        for cardinality in CARDINALITIES {
+            let values = make_values(cardinality, prefix);
+            for input in Input::ALL {
+                let array = make_input(input, &values);
+                let mut hashes = vec![0; SIZE];
+                group.bench_function(bench_id(input, prefix_label, cardinality), |b| {
+                    b.iter(|| {
+                        let keys = input.prepare(&array);
+                        hashes.fill(0);
+                        create_hashes([&keys], &random_state, &mut hashes).unwrap();
+                        black_box(&hashes);
+                    });
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_group_values, bench_hash);
+criterion_main!(benches);
@@ -45,6 +45,7 @@ use arrow::record_batch::RecordBatch;
 use arrow_schema::FieldRef;
 use datafusion_common::stats::Precision;
 use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::watermark::Watermark;
 use datafusion_common::{
     Constraint, Constraints, Result, ScalarValue, assert_eq_or_internal_err, not_impl_err,
 };
@@ -662,6 +663,9 @@ pub struct AggregateExec {
     required_input_ordering: Option<OrderingRequirements>,
     /// Describes how the input is ordered relative to the group by columns
     input_order_mode: InputOrderMode,
+    /// Index and watermark of the group key that is the event time of an
+    /// unbounded input, see [`watermark_group_key`]
+    watermark: Option<(usize, Watermark)>,
     cache: Arc<PlanProperties>,
     /// During initialization, if the plan supports dynamic filtering (see [`AggrDynFilter`]),
     /// it is set to `Some(..)` regardless of whether it can be pushed down to a child node.
@@ -686,6 +690,7 @@ impl AggregateExec {
             required_input_ordering: self.required_input_ordering.clone(),
             metrics: ExecutionPlanMetricsSet::new(),
             input_order_mode: self.input_order_mode.clone(),
+            watermark: self.watermark,
             cache: Arc::clone(&self.cache),
             mode: self.mode,
             group_by: Arc::clone(&self.group_by),
@@ -706,6 +711,7 @@ impl AggregateExec {
             required_input_ordering: self.required_input_ordering.clone(),
             metrics: ExecutionPlanMetricsSet::new(),
             input_order_mode: self.input_order_mode.clone(),
+            watermark: self.watermark,
             cache: Arc::clone(&self.cache),
             mode: self.mode,
             group_by: Arc::clone(&self.group_by),
@@ -826,7 +832,7 @@ impl AggregateExec {
         let group_expr_mapping =
             ProjectionMapping::try_new(group_by.expr.clone(), &input.schema())?;
 
-        let cache = Self::compute_properties(
+        let mut cache = Self::compute_properties(
             &input,
             Arc::clone(&schema),
             &group_expr_mapping,
@@ -835,6 +841,12 @@ impl AggregateExec {
             aggr_expr.as_ref(),
         )?;
 
+        // Groups are emitted as the watermark of their event time advances
+        let watermark = watermark_group_key(&group_by, &input, &input_order_mode)?;
+        if watermark.is_some() {
+            cache = cache.with_emission_type(EmissionType::Incremental);
+        }
+
         let mut exec = AggregateExec {
             mode,
             group_by,
@@ -847,6 +859,7 @@ impl AggregateExec {
             required_input_ordering,
             limit_options: None,
             input_order_mode,
+            watermark,
             cache: Arc::new(cache),
             dynamic_filter: None,
         };
@@ -1047,6 +1060,13 @@ impl AggregateExec {
         &self.input_order_mode
     }
 
+    /// Returns the index of the group key that is the event time of an
+    /// unbounded input and its [`Watermark`], if groups are emitted as the
+    /// watermark advances
+    pub fn watermark(&self) -> Option<(usize, Watermark)> {
+        self.watermark
+    }
+
     /// Estimates output statistics for this aggregate node.
     ///
     /// For grouped aggregations with known input row count > 1, the output row
@@ -1384,6 +1404,13 @@ impl DisplayAs for AggregateExec {
                 if self.input_order_mode != InputOrderMode::Linear {
                     write!(f, ", ordering_mode={:?}", self.input_order_mode)?;
                 }
+                if let Some((index, watermark)) = self.watermark {
+                    write!(
+                        f,
+                        ", watermark=[{} - {watermark}]",
+                        self.group_by.expr[index].1
+                    )?;
+                }
             }
             DisplayFormatType::TreeRender => {
                 let format_expr_with_alias =
@@ -1741,6 +1768,34 @@ fn create_schema(
     ))
 }
 
+/// Returns the index and [`Watermark`] of the first group key that is the
+/// event time of an unbounded input, as declared by its field metadata.
+///
+/// Groups keyed by such a column are emitted once the watermark passes their
+/// event time (see [`order::GroupOrderingWatermark`]), so the aggregation does
+/// not have to buffer the whole input. This is only used when the input is not
+/// already ordered by the group keys, and not for grouping sets.
+fn watermark_group_key(
+    group_by: &PhysicalGroupBy,
+    input: &Arc<dyn ExecutionPlan>,
+    input_order_mode: &InputOrderMode,
+) -> Result<Option<(usize, Watermark)>> {
+    if !group_by.is_single()
+        || *input_order_mode != InputOrderMode::Linear
+        || !input.boundedness().is_unbounded()
+    {
+        return Ok(None);
+    }
+    let input_schema = input.schema();
+    for (index, (expr, _)) in group_by.expr.iter().enumerate() {
+        let field = expr.return_field(&input_schema)?;
+        if let Some(watermark) = Watermark::try_from_field(&field)? {
+            return Ok(Some((index, watermark)));
+        }
+    }
+    Ok(None)
+}
+
 /// Determines the lexical ordering requirement for an aggregate expression.
 ///
 /// # Parameters
@@ -2935,6 +2990,29 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_without_groups_and_aggregates() -> Result<()> {
+        // a global aggregation produces a single row, even over empty input
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Float64, true)]));
+        let input = TestMemoryExec::try_new_exec(&[vec![]], Arc::clone(&schema), None)?;
+        let aggregate_exec = Arc::new(AggregateExec::try_new(
+            AggregateMode::Single,
+            PhysicalGroupBy::default(),
+            vec![],
+            vec![],
+            input,
+            schema,
+        )?);
+
+        let task_ctx = Arc::new(TaskContext::default());
+        let result = crate::collect(aggregate_exec, task_ctx).await?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].num_rows(), 1);
+        assert_eq!(result[0].num_columns(), 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_drop_cancel_with_groups() -> Result<()> {
         let task_ctx = Arc::new(TaskContext::default());
@@ -25,7 +25,7 @@ use crate::aggregates::{
 use crate::metrics::{BaselineMetrics, RecordOutput};
 use crate::{RecordBatchStream, SendableRecordBatchStream};
 use arrow::datatypes::SchemaRef;
-use arrow::record_batch::RecordBatch;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion_common::{Result, ScalarValue, internal_datafusion_err, internal_err};
 use datafusion_execution::TaskContext;
 use datafusion_expr::Operator;
@@ -377,9 +377,13 @@ impl AggregateStream {
                                     prepend_grouping_id_column(columns, None)
                                 })
                                 .and_then(|columns| {
-                                    RecordBatch::try_new(
+                                    // a single row, even without aggregates
+                                    let options =
+                                        RecordBatchOptions::new().with_row_count(Some(1));
+                                    RecordBatch::try_new_with_options(
                                         Arc::clone(&this.schema),
                                         columns,
+                                        &options,
                                     )
                                     .map_err(Into::into)
                                 })
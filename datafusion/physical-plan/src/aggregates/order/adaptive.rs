@@ -0,0 +1,226 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::Ordering;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::DataType;
+use arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion_common::Result;
+use datafusion_expr::EmitTo;
+
+use super::GroupOrderingPartial;
+
+/// Tracks grouping state when the input is not known to be ordered, but
+/// may turn out to be sorted by the first group key at runtime.
+///
+/// As long as the first group column is sorted (ascending or descending)
+/// within and across all batches seen so far, this behaves like a
+/// [`GroupOrderingPartial`] ordered by that column: once a new value of the
+/// first group key is seen, all groups with earlier values can be emitted.
+/// As soon as the input is found to be unsorted, no more groups are emitted
+/// before the end of the input.
+///
+/// Groups emitted early may receive more rows if the input later turns out
+/// to be unsorted. This is only correct for partial aggregation, where the
+/// final aggregation merges groups that were emitted more than once.
+///
+/// For example, given `SUM(amt) GROUP BY day, id` over an input that happens
+/// to be clustered by `day`, the groups for each `day` are emitted as soon as
+/// the next `day` is seen, instead of being kept until the end of the input.
+#[derive(Debug)]
+pub struct GroupOrderingAdaptive {
+    /// Tracks the groups that can be emitted while the input is sorted,
+    /// `None` once the input was found to be unsorted
+    partial: Option<GroupOrderingPartial>,
+
+    /// Converts the first group column into comparable rows
+    row_converter: RowConverter,
+
+    /// Whether the first group key is increasing or decreasing, once two
+    /// distinct values have been seen
+    direction: Option<Ordering>,
+
+    /// The last value of the first group key seen
+    last_key: Option<OwnedRow>,
+}
+
+impl GroupOrderingAdaptive {
+    /// Create a new `GroupOrderingAdaptive` for a first group column of type
+    /// `key_type`
+    pub fn try_new(key_type: &DataType) -> Result<Self> {
+        Ok(Self {
+            partial: Some(GroupOrderingPartial::try_new(vec![0])?),
+            row_converter: RowConverter::new(vec![SortField::new(key_type.clone())])?,
+            direction: None,
+            last_key: None,
+        })
+    }
+
+    /// Returns true while the input has been sorted by the first group key
+    pub fn is_sorted(&self) -> bool {
+        self.partial.is_some()
+    }
+
+    /// How many groups be emitted, or None if no data can be emitted
+    pub fn emit_to(&self) -> Option<EmitTo> {
+        self.partial
+            .as_ref()
+            .and_then(GroupOrderingPartial::emit_to)
+    }
+
+    /// remove the first n groups from the internal state, shifting
+    /// all existing indexes down by `n`
+    pub fn remove_groups(&mut self, n: usize) {
+        if let Some(partial) = self.partial.as_mut() {
+            partial.remove_groups(n);
+        }
+    }
+
+    /// Note that the input is complete so any outstanding groups are done as well
+    pub fn input_done(&mut self) {
+        if let Some(partial) = self.partial.as_mut() {
+            partial.input_done();
+        }
+    }
+
+    /// Called when new groups are added in a batch. See documentation
+    /// on [`super::GroupOrdering::new_groups`]
+    ///
+    /// Only batches that add new groups need to be checked: while the input
+    /// is sorted, the groups kept in the hash table all share the last value
+    /// of the first group key, so a batch that adds no groups only contains
+    /// that value.
+    pub fn new_groups(
+        &mut self,
+        batch_group_values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        if self.partial.is_none() {
+            return Ok(());
+        }
+
+        if self.update_sorted(&batch_group_values[0])? {
+            if let Some(partial) = self.partial.as_mut() {
+                partial.new_groups(
+                    batch_group_values,
+                    group_indices,
+                    total_num_groups,
+                )?;
+            }
+        } else {
+            self.partial = None;
+            self.last_key = None;
+        }
+        Ok(())
+    }
+
+    /// Checks that `keys` continue the sorted sequence of first group keys
+    /// seen so far, remembering the direction and the last key
+    fn update_sorted(&mut self, keys: &ArrayRef) -> Result<bool> {
+        let rows = self.row_converter.convert_columns(&[Arc::clone(keys)])?;
+
+        let mut previous = self.last_key.as_ref().map(|row| row.row());
+        for row in rows.iter() {
+            if let Some(previous) = previous {
+                let ordering = previous.cmp(&row);
+                if ordering != Ordering::Equal {
+                    match self.direction {
+                        None => self.direction = Some(ordering),
+                        Some(direction) if direction != ordering => return Ok(false),
+                        Some(_) => {}
+                    }
+                }
+            }
+            previous = Some(row);
+        }
+
+        self.last_key = previous.map(|row| row.owned());
+        Ok(true)
+    }
+
+    /// Return the size of memory allocated by this structure
+    pub(crate) fn size(&self) -> usize {
+        size_of::<Self>()
+            + self.partial.as_ref().map_or(0, GroupOrderingPartial::size)
+            + self.row_converter.size()
+            + self.last_key.as_ref().map_or(0, |row| row.as_ref().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int32Array;
+
+    fn push(
+        group_ordering: &mut GroupOrderingAdaptive,
+        keys: Vec<i32>,
+        group_indices: Vec<usize>,
+    ) -> Result<()> {
+        let total_num_groups = group_indices.iter().max().unwrap() + 1;
+        let batch_group_values: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(keys))];
+        group_ordering.new_groups(&batch_group_values, &group_indices, total_num_groups)
+    }
+
+    #[test]
+    fn test_group_ordering_adaptive_sorted() -> Result<()> {
+        let mut group_ordering = GroupOrderingAdaptive::try_new(&DataType::Int32)?;
+
+        push(&mut group_ordering, vec![1, 1, 2], vec![0, 0, 1])?;
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::First(1)));
+
+        // descending input is detected as well
+        let mut group_ordering = GroupOrderingAdaptive::try_new(&DataType::Int32)?;
+        push(&mut group_ordering, vec![5, 4], vec![0, 1])?;
+        push(&mut group_ordering, vec![4, 3], vec![1, 2])?;
+        assert!(group_ordering.is_sorted());
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::First(2)));
+
+        group_ordering.input_done();
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::All));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_ordering_adaptive_unsorted() -> Result<()> {
+        let mut group_ordering = GroupOrderingAdaptive::try_new(&DataType::Int32)?;
+
+        push(&mut group_ordering, vec![1, 2, 3], vec![0, 1, 2])?;
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::First(2)));
+        group_ordering.remove_groups(2);
+
+        // the input goes back to a smaller key across batches
+        push(&mut group_ordering, vec![3, 1], vec![0, 1])?;
+        assert!(!group_ordering.is_sorted());
+        assert_eq!(group_ordering.emit_to(), None);
+
+        // nothing is emitted early once the input is unsorted
+        push(&mut group_ordering, vec![4, 5], vec![2, 3])?;
+        assert_eq!(group_ordering.emit_to(), None);
+
+        group_ordering.input_done();
+        assert_eq!(group_ordering.emit_to(), None);
+
+        Ok(())
+    }
+}
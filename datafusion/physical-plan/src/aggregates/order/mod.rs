@@ -21,12 +21,16 @@ use arrow::array::ArrayRef;
 use datafusion_common::Result;
 use datafusion_expr::EmitTo;
 
+mod adaptive;
 mod full;
 mod partial;
+mod watermark;
 
 use crate::InputOrderMode;
+pub use adaptive::GroupOrderingAdaptive;
 pub use full::GroupOrderingFull;
 pub use partial::GroupOrderingPartial;
+pub use watermark::GroupOrderingWatermark;
 
 /// Ordering information for each group in the hash table
 #[derive(Debug)]
@@ -37,6 +41,12 @@ pub enum GroupOrdering {
     Partial(GroupOrderingPartial),
     /// Groups are entirely contiguous,
     Full(GroupOrderingFull),
+    /// Groups are not known to be ordered, but are emitted by the first group
+    /// key for as long as the input turns out to be sorted by it
+    Adaptive(GroupOrderingAdaptive),
+    /// Groups are partially ordered by an event time key with a watermark, and
+    /// are emitted once the watermark passes them
+    Watermark(GroupOrderingWatermark),
 }
 
 impl GroupOrdering {
@@ -59,6 +69,8 @@ impl GroupOrdering {
             GroupOrdering::None => None,
             GroupOrdering::Partial(partial) => partial.emit_to(),
             GroupOrdering::Full(full) => full.emit_to(),
+            GroupOrdering::Adaptive(adaptive) => adaptive.emit_to(),
+            GroupOrdering::Watermark(watermark) => watermark.emit_to(),
         }
     }
 
@@ -75,7 +87,13 @@ impl GroupOrdering {
 
         match self {
             GroupOrdering::None => Some(EmitTo::First(n)),
-            GroupOrdering::Partial(_) | GroupOrdering::Full(_) => {
+            GroupOrdering::Adaptive(adaptive) if !adaptive.is_sorted() => {
+                Some(EmitTo::First(n))
+            }
+            GroupOrdering::Partial(_)
+            | GroupOrdering::Full(_)
+            | GroupOrdering::Adaptive(_)
+            | GroupOrdering::Watermark(_) => {
                 self.emit_to().map(|emit_to| match emit_to {
                     EmitTo::First(max) => EmitTo::First(n.min(max)),
                     EmitTo::All => EmitTo::First(n),
@@ -90,6 +108,8 @@ impl GroupOrdering {
             GroupOrdering::None => {}
             GroupOrdering::Partial(partial) => partial.input_done(),
             GroupOrdering::Full(full) => full.input_done(),
+            GroupOrdering::Adaptive(adaptive) => adaptive.input_done(),
+            GroupOrdering::Watermark(watermark) => watermark.input_done(),
         }
     }
 
@@ -100,6 +120,8 @@ impl GroupOrdering {
             GroupOrdering::None => {}
             GroupOrdering::Partial(partial) => partial.remove_groups(n),
             GroupOrdering::Full(full) => full.remove_groups(n),
+            GroupOrdering::Adaptive(adaptive) => adaptive.remove_groups(n),
+            GroupOrdering::Watermark(watermark) => watermark.remove_groups(n),
         }
     }
 
@@ -129,6 +151,20 @@ impl GroupOrdering {
             GroupOrdering::Full(full) => {
                 full.new_groups(total_num_groups);
             }
+            GroupOrdering::Adaptive(adaptive) => {
+                adaptive.new_groups(
+                    batch_group_values,
+                    group_indices,
+                    total_num_groups,
+                )?;
+            }
+            GroupOrdering::Watermark(watermark) => {
+                watermark.new_groups(
+                    batch_group_values,
+                    group_indices,
+                    total_num_groups,
+                )?;
+            }
         };
         Ok(())
     }
@@ -140,6 +176,8 @@ impl GroupOrdering {
                 GroupOrdering::None => 0,
                 GroupOrdering::Partial(partial) => partial.size(),
                 GroupOrdering::Full(full) => full.size(),
+                GroupOrdering::Adaptive(adaptive) => adaptive.size(),
+                GroupOrdering::Watermark(watermark) => watermark.size(),
             }
     }
 }
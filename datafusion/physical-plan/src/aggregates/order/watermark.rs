@@ -0,0 +1,259 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::mem::size_of;
+
+use arrow::array::{ArrayRef, AsArray, BooleanArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Int64Type};
+use datafusion_common::watermark::Watermark;
+use datafusion_common::{Result, internal_err};
+use datafusion_execution::memory_pool::proxy::VecAllocExt;
+use datafusion_expr::EmitTo;
+
+/// Tracks grouping state when the input is partially ordered by an event
+/// time group key with a [`Watermark`].
+///
+/// The watermark is the largest event time seen so far minus the allowed
+/// lateness. Rows arriving later never have an event time below it, so the
+/// groups whose event time is below the watermark are complete and can be
+/// emitted, in the order they were first seen.
+///
+/// Rows that arrive after the watermark passed their event time are late:
+/// their group may already have been emitted, so they are dropped (see
+/// [`Self::on_time_rows`]).
+///
+/// For example, given `SUM(amt) GROUP BY window_start` over a stream whose
+/// `window_start` values are at most 10 minutes late, once a row for
+/// `10:30` is seen, all groups before `10:20` are emitted.
+#[derive(Debug)]
+pub struct GroupOrderingWatermark {
+    /// Index of the event time column in the group keys
+    key_index: usize,
+
+    /// Allowed lateness, in the unit of the event time column
+    delay: i64,
+
+    /// Event time of each group in the hash table, `None` for a `NULL` event
+    /// time. Groups with a `NULL` event time are only emitted at the end of
+    /// the input.
+    group_times: Vec<Option<i64>>,
+
+    /// Largest event time seen so far
+    max_time: Option<i64>,
+
+    /// Whether the input is complete
+    input_done: bool,
+}
+
+impl GroupOrderingWatermark {
+    /// Create a new `GroupOrderingWatermark` for the group key `key_index`
+    /// of type `key_type`
+    pub fn try_new(
+        key_index: usize,
+        key_type: &DataType,
+        watermark: Watermark,
+    ) -> Result<Self> {
+        let DataType::Timestamp(unit, _) = key_type else {
+            return internal_err!(
+                "Watermark group key must be a timestamp, got {key_type}"
+            );
+        };
+        Ok(Self {
+            key_index,
+            delay: watermark.delay(*unit),
+            group_times: vec![],
+            max_time: None,
+            input_done: false,
+        })
+    }
+
+    /// Returns the current watermark, or `None` if no event time was seen yet
+    fn watermark(&self) -> Option<i64> {
+        self.max_time
+            .map(|max_time| max_time.saturating_sub(self.delay))
+    }
+
+    /// How many groups be emitted, or None if no data can be emitted
+    pub fn emit_to(&self) -> Option<EmitTo> {
+        if self.input_done {
+            return Some(EmitTo::All);
+        }
+        let watermark = self.watermark()?;
+        let n = self
+            .group_times
+            .iter()
+            .take_while(|time| time.is_some_and(|time| time < watermark))
+            .count();
+        (n > 0).then_some(EmitTo::First(n))
+    }
+
+    /// remove the first n groups from the internal state, shifting
+    /// all existing indexes down by `n`
+    pub fn remove_groups(&mut self, n: usize) {
+        self.group_times.drain(0..n);
+    }
+
+    /// Note that the input is complete so any outstanding groups are done as well
+    pub fn input_done(&mut self) {
+        self.input_done = true;
+    }
+
+    /// Returns a filter selecting the rows of a batch with group keys
+    /// `batch_group_values` whose event time is not below the watermark, or
+    /// `None` if no row is late
+    pub fn on_time_rows(
+        &self,
+        batch_group_values: &[ArrayRef],
+    ) -> Result<Option<BooleanArray>> {
+        let Some(watermark) = self.watermark() else {
+            return Ok(None);
+        };
+        let times = self.event_times(batch_group_values)?;
+        let times = times.as_primitive::<Int64Type>();
+        if times
+            .iter()
+            .all(|time| time.is_none_or(|time| time >= watermark))
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            times
+                .iter()
+                .map(|time| Some(time.is_none_or(|time| time >= watermark)))
+                .collect(),
+        ))
+    }
+
+    /// Called when new groups are added in a batch. See documentation
+    /// on [`super::GroupOrdering::new_groups`]
+    pub fn new_groups(
+        &mut self,
+        batch_group_values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let times = self.event_times(batch_group_values)?;
+        let times = times.as_primitive::<Int64Type>();
+
+        self.group_times.resize(total_num_groups, None);
+        for (time, &group_index) in times.iter().zip(group_indices) {
+            self.group_times[group_index] = time;
+        }
+        if let Some(time) = arrow::compute::max(times) {
+            self.max_time = Some(self.max_time.map_or(time, |max| max.max(time)));
+        }
+        Ok(())
+    }
+
+    /// Returns the event times of a batch as an `Int64` array
+    fn event_times(&self, batch_group_values: &[ArrayRef]) -> Result<ArrayRef> {
+        Ok(cast(&batch_group_values[self.key_index], &DataType::Int64)?)
+    }
+
+    /// Return the size of memory allocated by this structure
+    pub(crate) fn size(&self) -> usize {
+        size_of::<Self>() + self.group_times.allocated_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use arrow::array::{Int32Array, TimestampSecondArray};
+    use arrow::datatypes::TimeUnit;
+
+    fn push(
+        group_ordering: &mut GroupOrderingWatermark,
+        times: Vec<Option<i64>>,
+        group_indices: Vec<usize>,
+    ) -> Result<()> {
+        let total_num_groups = group_indices.iter().max().unwrap() + 1;
+        let batch_group_values = batch(times);
+        group_ordering.new_groups(&batch_group_values, &group_indices, total_num_groups)
+    }
+
+    fn batch(times: Vec<Option<i64>>) -> Vec<ArrayRef> {
+        let ids = Int32Array::from(vec![0; times.len()]);
+        vec![Arc::new(ids), Arc::new(TimestampSecondArray::from(times))]
+    }
+
+    #[test]
+    fn test_group_ordering_watermark() -> Result<()> {
+        let mut group_ordering = GroupOrderingWatermark::try_new(
+            1,
+            &DataType::Timestamp(TimeUnit::Second, None),
+            Watermark::try_new(10_000_000_000)?,
+        )?;
+        assert_eq!(group_ordering.emit_to(), None);
+
+        // watermark is 5, the group at 3 is complete but follows an open group
+        push(
+            &mut group_ordering,
+            vec![Some(1), Some(15), Some(3)],
+            vec![0, 1, 2],
+        )?;
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::First(1)));
+
+        // a group at the watermark is not complete
+        push(&mut group_ordering, vec![Some(5)], vec![3])?;
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::First(1)));
+        group_ordering.remove_groups(1);
+
+        // watermark is 20
+        push(&mut group_ordering, vec![Some(30), Some(5)], vec![3, 2])?;
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::First(3)));
+        group_ordering.remove_groups(3);
+        assert_eq!(group_ordering.emit_to(), None);
+
+        group_ordering.input_done();
+        assert_eq!(group_ordering.emit_to(), Some(EmitTo::All));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_ordering_watermark_late_rows() -> Result<()> {
+        let mut group_ordering = GroupOrderingWatermark::try_new(
+            1,
+            &DataType::Timestamp(TimeUnit::Second, None),
+            Watermark::try_new(10_000_000_000)?,
+        )?;
+        assert_eq!(group_ordering.on_time_rows(&batch(vec![Some(1)]))?, None);
+
+        // watermark is 10
+        push(&mut group_ordering, vec![Some(20)], vec![0])?;
+        assert_eq!(
+            group_ordering.on_time_rows(&batch(vec![Some(10), Some(25)]))?,
+            None
+        );
+        assert_eq!(
+            group_ordering.on_time_rows(&batch(vec![Some(9), None, Some(12)]))?,
+            Some(BooleanArray::from(vec![false, true, true]))
+        );
+
+        // groups with a NULL event time are kept until the end of the input
+        push(&mut group_ordering, vec![None, Some(30)], vec![1, 2])?;
+        group_ordering.remove_groups(1);
+        assert_eq!(group_ordering.emit_to(), None);
+
+        Ok(())
+    }
+}
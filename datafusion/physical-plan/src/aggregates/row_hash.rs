@@ -24,7 +24,9 @@ use std::vec;
 use super::AggregateExec;
 use super::order::GroupOrdering;
 use crate::aggregates::group_values::{GroupByMetrics, GroupValues, new_group_values};
-use crate::aggregates::order::GroupOrderingFull;
+use crate::aggregates::order::{
+    GroupOrderingAdaptive, GroupOrderingFull, GroupOrderingWatermark,
+};
 use crate::aggregates::{
     AggregateInputMode, AggregateMode, AggregateOutputMode, PhysicalGroupBy,
     create_schema, evaluate_group_by, evaluate_many, evaluate_optional, group_id_array,
@@ -37,7 +39,9 @@ use crate::{PhysicalExpr, aggregates, metrics};
 use crate::{RecordBatchStream, SendableRecordBatchStream};
 
 use arrow::array::*;
+use arrow::compute::filter_record_batch;
 use arrow::datatypes::SchemaRef;
+use datafusion_common::config::AggregateEmissionPolicy;
 use datafusion_common::{
     DataFusionError, Result, assert_eq_or_internal_err, assert_or_internal_err,
     internal_err, resources_datafusion_err,
@@ -133,6 +137,11 @@ struct SkipAggregationProbe {
     /// (from `SessionConfig`). If the ratio exceeds this value, aggregation
     /// is skipped and input rows are directly converted to output
     probe_ratio_threshold: f64,
+    /// Number of consecutive early emissions (under memory pressure) whose
+    /// ratio of `num_groups` to input rows exceeds `probe_ratio_threshold`,
+    /// after which aggregation is skipped (from `SessionConfig`). 0 disables
+    /// the check.
+    early_emit_threshold: usize,
 
     // ========================================================================
     // STATES:
@@ -143,6 +152,10 @@ struct SkipAggregationProbe {
     input_rows: usize,
     /// Number of total group values for `input_rows` (updated during probing)
     num_groups: usize,
+    /// Number of processed input rows since the last early emission
+    rows_since_early_emit: usize,
+    /// Number of consecutive early emissions exceeding `probe_ratio_threshold`
+    low_reduction_early_emits: usize,
 
     /// Flag indicating further data aggregation may be skipped (decision made
     /// when probing complete)
@@ -167,13 +180,17 @@ impl SkipAggregationProbe {
     fn new(
         probe_rows_threshold: usize,
         probe_ratio_threshold: f64,
+        early_emit_threshold: usize,
         skipped_aggregation_rows: metrics::Count,
     ) -> Self {
         Self {
             input_rows: 0,
             num_groups: 0,
+            rows_since_early_emit: 0,
+            low_reduction_early_emits: 0,
             probe_rows_threshold,
             probe_ratio_threshold,
+            early_emit_threshold,
             should_skip: false,
             is_locked: false,
             skipped_aggregation_rows,
@@ -191,6 +208,7 @@ impl SkipAggregationProbe {
             return;
         }
         self.input_rows += input_rows;
+        self.rows_since_early_emit += input_rows;
         self.num_groups = num_groups;
         if self.input_rows >= self.probe_rows_threshold {
             self.should_skip = self.num_groups as f64 / self.input_rows as f64
@@ -201,6 +219,26 @@ impl SkipAggregationProbe {
         }
     }
 
+    /// Updates `SkipAggregationProbe` state on an early emission, because of
+    /// memory pressure, with `num_groups` group values:
+    /// - calculates the aggregation ratio of the input rows processed since
+    ///   the previous early emission
+    /// - on `early_emit_threshold` consecutive ratios exceeding
+    ///   `probe_ratio_threshold`, sets and locks the `should_skip` flag
+    fn update_state_on_early_emit(&mut self, num_groups: usize) {
+        if self.is_locked || self.early_emit_threshold == 0 {
+            return;
+        }
+        let input_rows = std::mem::take(&mut self.rows_since_early_emit);
+        if num_groups as f64 / input_rows.max(1) as f64 >= self.probe_ratio_threshold {
+            self.low_reduction_early_emits += 1;
+        } else {
+            self.low_reduction_early_emits = 0;
+        }
+        self.should_skip = self.low_reduction_early_emits >= self.early_emit_threshold;
+        self.is_locked = self.should_skip;
+    }
+
     fn should_skip(&self) -> bool {
         self.should_skip
     }
@@ -303,6 +341,13 @@ enum OutOfMemoryMode {
 /// supported, this operator will stop applying Partial aggregation and directly
 /// pass the input rows to the next aggregation phase.
 ///
+/// The ratio is checked once a number of input rows have been read, and,
+/// optionally, whenever the memory pressure forces this operator to emit its
+/// groups early: after a number of consecutive early emissions that reduced
+/// their input rows too little, the partial aggregation is skipped as well,
+/// rather than repeatedly filling the memory pool with groups that the next
+/// aggregation phase has to merge anyway.
+///
 /// [`Accumulator::state`]: datafusion_expr::Accumulator::state
 ///
 /// # Spilling (to disk)
@@ -567,7 +612,34 @@ impl GroupedHashAggregateStream {
             .collect::<Vec<_>>()
             .join(", ");
         let name = format!("GroupedHashAggregateStream[{partition}] ({agg_fn_names})");
-        let group_ordering = GroupOrdering::try_new(&agg.input_order_mode)?;
+        let mut group_ordering = GroupOrdering::try_new(&agg.input_order_mode)?;
+        // Groups of an unbounded input are emitted once the watermark of
+        // their event time passes them
+        if let Some((key_index, watermark)) = agg.watermark() {
+            group_ordering = GroupOrdering::Watermark(GroupOrderingWatermark::try_new(
+                key_index,
+                group_schema.field(key_index).data_type(),
+                watermark,
+            )?);
+        }
+        // Partial aggregation over unordered input may still emit groups early
+        // if the input turns out to be sorted by the first group key, as the
+        // final aggregation merges any group emitted more than once.
+        if agg.mode == AggregateMode::Partial
+            && matches!(group_ordering, GroupOrdering::None)
+            && agg_group_by.is_single()
+            && context
+                .session_config()
+                .options()
+                .execution
+                .aggregate_emission_policy
+                == AggregateEmissionPolicy::Adaptive
+            && let Some(first_group_field) = group_schema.fields().first()
+        {
+            group_ordering = GroupOrdering::Adaptive(GroupOrderingAdaptive::try_new(
+                first_group_field.data_type(),
+            )?);
+        }
         let oom_mode = match (agg.mode, &group_ordering) {
             // In partial aggregation mode, always prefer to emit incomplete results early.
             (AggregateMode::Partial, _) => OutOfMemoryMode::EmitEarly,
@@ -605,7 +677,8 @@ impl GroupedHashAggregateStream {
             metrics::SpillMetrics::new(&agg.metrics, partition),
             Arc::clone(&spill_schema),
         )
-        .with_compression_type(context.session_config().spill_compression());
+        .with_compression_type(context.session_config().spill_compression())
+        .with_resource_tracker(context.resource_tracker().cloned());
 
         let spill_state = SpillState {
             spills: vec![],
@@ -622,13 +695,16 @@ impl GroupedHashAggregateStream {
 
         // Skip aggregation is supported if:
         // - aggregation mode is Partial
-        // - input is not ordered by GROUP BY expressions,
+        // - input is not known to be ordered by GROUP BY expressions,
         //   since Final mode expects unique group values as its input
         // - all accumulators support input batch to intermediate
         //   aggregate state conversion
         // - there is only one GROUP BY expressions set
         let skip_aggregation_probe = if agg.mode == AggregateMode::Partial
-            && matches!(group_ordering, GroupOrdering::None)
+            && matches!(
+                group_ordering,
+                GroupOrdering::None | GroupOrdering::Adaptive(_)
+            )
             && accumulators
                 .iter()
                 .all(|acc| acc.supports_convert_to_state())
@@ -639,12 +715,15 @@ impl GroupedHashAggregateStream {
                 options.skip_partial_aggregation_probe_rows_threshold;
             let probe_ratio_threshold =
                 options.skip_partial_aggregation_probe_ratio_threshold;
+            let early_emit_threshold =
+                options.skip_partial_aggregation_early_emit_threshold;
             let skipped_aggregation_rows = MetricBuilder::new(&agg.metrics)
                 .with_category(MetricCategory::Rows)
                 .counter("skipped_aggregation_rows", partition);
             Some(SkipAggregationProbe::new(
                 probe_rows_threshold,
                 probe_ratio_threshold,
+                early_emit_threshold,
                 skipped_aggregation_rows,
             ))
         } else {
@@ -919,6 +998,15 @@ impl GroupedHashAggregateStream {
             evaluate_group_by(&self.group_by, batch)?
         };
 
+        // Drop the rows that arrive after the watermark passed their group,
+        // which may already have been emitted
+        if let GroupOrdering::Watermark(watermark) = &self.group_ordering
+            && let Some(on_time) = watermark.on_time_rows(&group_by_values[0])?
+        {
+            let batch = filter_record_batch(batch, &on_time)?;
+            return self.group_aggregate_batch(&batch);
+        }
+
         // Only create the timer if there are actual aggregate arguments to evaluate
         let timer = match (
             self.spill_state.is_stream_merging,
@@ -1034,6 +1122,16 @@ impl GroupedHashAggregateStream {
                 Ok(None)
             }
             OutOfMemoryMode::EmitEarly if self.group_values.len() > 1 => {
+                // Rather than emitting early again and again, emit all the
+                // groups and skip aggregation if the aggregation reduces the
+                // input too little
+                if let Some(probe) = self.skip_aggregation_probe.as_mut() {
+                    probe.update_state_on_early_emit(self.group_values.len());
+                }
+                if let Some(new_state) = self.switch_to_skip_aggregation()? {
+                    return Ok(Some(new_state));
+                }
+
                 let n = if self.group_values.len() >= self.batch_size {
                     // Try to emit an integer multiple of batch size if possible
                     self.group_values.len() / self.batch_size * self.batch_size
@@ -1803,4 +1901,163 @@ mod tests {
 
         Ok(())
     }
+
+    /// Runs a partial `COUNT(v) GROUP BY k` over `keys`, one batch per entry,
+    /// returning the number of rows of each output batch
+    async fn partial_count_output_rows(
+        keys: Vec<Vec<i32>>,
+        policy: AggregateEmissionPolicy,
+    ) -> Result<Vec<usize>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Int32, false),
+            Field::new("v", DataType::Int64, false),
+        ]));
+        let batches = keys
+            .into_iter()
+            .map(|keys| {
+                let values = Int64Array::from(vec![1; keys.len()]);
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![Arc::new(Int32Array::from(keys)), Arc::new(values)],
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut task_ctx = TaskContext::default();
+        let mut cfg = task_ctx.session_config().clone();
+        cfg.options_mut().execution.aggregate_emission_policy = policy;
+        task_ctx = task_ctx.with_session_config(cfg);
+        let task_ctx = Arc::new(task_ctx);
+
+        let exec = TestMemoryExec::try_new_exec(&[batches], Arc::clone(&schema), None)?;
+        let aggregate_exec = AggregateExec::try_new(
+            AggregateMode::Partial,
+            PhysicalGroupBy::new_single(vec![(col("k", &schema)?, "k".to_string())]),
+            vec![Arc::new(
+                AggregateExprBuilder::new(count_udaf(), vec![col("v", &schema)?])
+                    .schema(Arc::clone(&schema))
+                    .alias("count_v")
+                    .build()?,
+            )],
+            vec![None],
+            exec,
+            Arc::clone(&schema),
+        )?;
+        assert_eq!(aggregate_exec.input_order_mode(), &InputOrderMode::Linear);
+
+        let mut stream = GroupedHashAggregateStream::new(&aggregate_exec, &task_ctx, 0)?;
+        let mut output_rows = vec![];
+        while let Some(batch) = stream.next().await {
+            output_rows.push(batch?.num_rows());
+        }
+        Ok(output_rows)
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_emission_with_sorted_input() -> Result<()> {
+        let keys = vec![vec![0, 0, 1], vec![1, 2, 2], vec![3]];
+
+        // Each key is emitted as soon as the next one is seen
+        let output_rows =
+            partial_count_output_rows(keys.clone(), AggregateEmissionPolicy::Adaptive)
+                .await?;
+        assert_eq!(output_rows, vec![1, 1, 1, 1]);
+
+        let output_rows =
+            partial_count_output_rows(keys, AggregateEmissionPolicy::Hash).await?;
+        assert_eq!(output_rows, vec![4]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_emission_with_unsorted_input() -> Result<()> {
+        // Key 0 is emitted before the input turns out to be unsorted, and
+        // is emitted again at the end for the final aggregation to merge
+        let keys = vec![vec![0, 1], vec![2, 0], vec![3, 1]];
+        let output_rows =
+            partial_count_output_rows(keys, AggregateEmissionPolicy::Adaptive).await?;
+        assert_eq!(output_rows, vec![1, 4]);
+
+        Ok(())
+    }
+
+    /// Runs a partial `COUNT(v) GROUP BY k` over batches of distinct keys
+    /// under memory pressure, returning the number of output rows and the
+    /// number of rows that skipped aggregation
+    async fn partial_count_under_memory_pressure(
+        early_emit_threshold: usize,
+    ) -> Result<(usize, usize)> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Int32, false),
+            Field::new("v", DataType::Int64, false),
+        ]));
+        let batches = (0..20)
+            .map(|i| {
+                let keys = Int32Array::from_iter_values(i * 64..(i + 1) * 64);
+                let values = Int64Array::from(vec![1; 64]);
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![Arc::new(keys), Arc::new(values)],
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let runtime = RuntimeEnvBuilder::default()
+            .with_memory_limit(1024, 1.0)
+            .build_arc()?;
+        let mut task_ctx = TaskContext::default().with_runtime(runtime);
+        let mut cfg = task_ctx.session_config().clone();
+        let options = &mut cfg.options_mut().execution;
+        // only the early emissions may trigger skipping aggregation
+        options.skip_partial_aggregation_probe_rows_threshold = usize::MAX;
+        options.skip_partial_aggregation_early_emit_threshold = early_emit_threshold;
+        task_ctx = task_ctx.with_session_config(cfg);
+        let task_ctx = Arc::new(task_ctx);
+
+        let exec = TestMemoryExec::try_new_exec(&[batches], Arc::clone(&schema), None)?;
+        let aggregate_exec = AggregateExec::try_new(
+            AggregateMode::Partial,
+            PhysicalGroupBy::new_single(vec![(col("k", &schema)?, "k".to_string())]),
+            vec![Arc::new(
+                AggregateExprBuilder::new(count_udaf(), vec![col("v", &schema)?])
+                    .schema(Arc::clone(&schema))
+                    .alias("count_v")
+                    .build()?,
+            )],
+            vec![None],
+            exec,
+            Arc::clone(&schema),
+        )?;
+
+        let mut stream = GroupedHashAggregateStream::new(&aggregate_exec, &task_ctx, 0)?;
+        let mut output_rows = 0;
+        while let Some(batch) = stream.next().await {
+            output_rows += batch?.num_rows();
+        }
+        let skipped_rows = aggregate_exec
+            .metrics()
+            .unwrap()
+            .sum_by_name("skipped_aggregation_rows")
+            .map(|m| m.as_usize())
+            .unwrap_or(0);
+        Ok((output_rows, skipped_rows))
+    }
+
+    #[tokio::test]
+    async fn test_skip_aggregation_after_early_emits() -> Result<()> {
+        // Every batch is emitted early without any reduction, so aggregation
+        // is skipped after the second early emission
+        let (output_rows, skipped_rows) = partial_count_under_memory_pressure(2).await?;
+        assert_eq!(output_rows, 20 * 64);
+        assert!(skipped_rows > 0);
+        assert!(skipped_rows <= 18 * 64, "{skipped_rows}");
+
+        // Disabled by default
+        let (output_rows, skipped_rows) = partial_count_under_memory_pressure(0).await?;
+        assert_eq!(output_rows, 20 * 64);
+        assert_eq!(skipped_rows, 0);
+
+        Ok(())
+    }
 }
@@ -17,6 +17,7 @@
 
 //! Defines the ANALYZE operator
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use super::stream::{RecordBatchReceiverStream, RecordBatchStreamAdapter};
@@ -25,18 +26,23 @@ use super::{
     SendableRecordBatchStream,
 };
 use crate::display::DisplayableExecutionPlan;
-use crate::metrics::{MetricCategory, MetricType};
+use crate::metrics::{MetricCategory, MetricType, MetricValue, MetricsSet};
 use crate::{DisplayFormatType, ExecutionPlan, Partitioning};
 
 use arrow::{array::StringBuilder, datatypes::SchemaRef, record_batch::RecordBatch};
+use datafusion_common::format::ExplainAnalyzeFormat;
 use datafusion_common::instant::Instant;
 use datafusion_common::tree_node::TreeNodeRecursion;
-use datafusion_common::{DataFusionError, Result, assert_eq_or_internal_err};
+use datafusion_common::{
+    DataFusionError, Result, assert_eq_or_internal_err, human_readable_size,
+};
 use datafusion_execution::TaskContext;
+use datafusion_execution::memory_pool::MemoryProfileEntry;
 use datafusion_physical_expr::EquivalenceProperties;
 use datafusion_physical_expr::PhysicalExpr;
 
 use futures::StreamExt;
+use serde_json::{Map, Value, json};
 
 /// `EXPLAIN ANALYZE` execution plan operator. This operator runs its input,
 /// discards the results, and then prints out an annotated plan with metrics
@@ -50,6 +56,8 @@ pub struct AnalyzeExec {
     metric_types: Vec<MetricType>,
     /// Optional filter by semantic category (rows / bytes / timing).
     metric_categories: Option<Vec<MetricCategory>>,
+    /// Output format of the plan with its metrics
+    format: ExplainAnalyzeFormat,
     /// The input plan (the plan being analyzed)
     pub(crate) input: Arc<dyn ExecutionPlan>,
     /// The output schema for RecordBatches of this exec node
@@ -73,12 +81,19 @@ impl AnalyzeExec {
             show_statistics,
             metric_types,
             metric_categories,
+            format: ExplainAnalyzeFormat::Indent,
             input,
             schema,
             cache: Arc::new(cache),
         }
     }
 
+    /// Set the output format of the plan with its metrics
+    pub fn with_format(mut self, format: ExplainAnalyzeFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Access to verbose
     pub fn verbose(&self) -> bool {
         self.verbose
@@ -94,6 +109,11 @@ impl AnalyzeExec {
         self.metric_categories.as_deref()
     }
 
+    /// Access to format
+    pub fn format(&self) -> ExplainAnalyzeFormat {
+        self.format
+    }
+
     /// The input plan
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
@@ -121,7 +141,11 @@ impl DisplayAs for AnalyzeExec {
     ) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(f, "AnalyzeExec verbose={}", self.verbose)
+                write!(f, "AnalyzeExec verbose={}", self.verbose)?;
+                if self.format != ExplainAnalyzeFormat::Indent {
+                    write!(f, ", format={}", self.format)?;
+                }
+                Ok(())
             }
             DisplayFormatType::TreeRender => {
                 // TODO: collect info
@@ -160,14 +184,17 @@ impl ExecutionPlan for AnalyzeExec {
         self: Arc<Self>,
         mut children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(Self::new(
-            self.verbose,
-            self.show_statistics,
-            self.metric_types.clone(),
-            self.metric_categories.clone(),
-            children.pop().unwrap(),
-            Arc::clone(&self.schema),
-        )))
+        Ok(Arc::new(
+            Self::new(
+                self.verbose,
+                self.show_statistics,
+                self.metric_types.clone(),
+                self.metric_categories.clone(),
+                children.pop().unwrap(),
+                Arc::clone(&self.schema),
+            )
+            .with_format(self.format),
+        ))
     }
 
     fn execute(
@@ -181,6 +208,13 @@ impl ExecutionPlan for AnalyzeExec {
             "AnalyzeExec invalid partition. Expected 0, got {partition}"
         );
 
+        // If the memory pool records a profile, record the memory used by
+        // this query only, as the pool may be shared with other queries
+        let context = match context.memory_pool().memory_profile() {
+            Some(_) => Arc::new(TaskContext::clone(&context).with_memory_profile()),
+            None => context,
+        };
+
         // Gather futures that will run each input partition in
         // parallel (on a separate tokio task) using a JoinSet to
         // cancel outstanding futures on drop
@@ -204,6 +238,8 @@ impl ExecutionPlan for AnalyzeExec {
         let show_statistics = self.show_statistics;
         let metric_types = self.metric_types.clone();
         let metric_categories = self.metric_categories.clone();
+        let format = self.format;
+        let memory_pool = Arc::clone(context.memory_pool());
 
         // future that gathers the results from all the tasks in the
         // JoinSet that computes the overall row count and final
@@ -216,6 +252,17 @@ impl ExecutionPlan for AnalyzeExec {
             }
 
             let duration = Instant::now() - start;
+            if format == ExplainAnalyzeFormat::Json {
+                return create_json_output_batch(
+                    total_rows,
+                    duration,
+                    &captured_input,
+                    &captured_schema,
+                    &metric_types,
+                    metric_categories.as_deref(),
+                    memory_pool.memory_profile(),
+                );
+            }
             create_output_batch(
                 verbose,
                 show_statistics,
@@ -225,6 +272,7 @@ impl ExecutionPlan for AnalyzeExec {
                 &captured_schema,
                 &metric_types,
                 metric_categories.as_deref(),
+                memory_pool.memory_profile(),
             )
         };
 
@@ -246,6 +294,7 @@ fn create_output_batch(
     schema: &SchemaRef,
     metric_types: &[MetricType],
     metric_categories: Option<&[MetricCategory]>,
+    memory_profile: Option<Vec<MemoryProfileEntry>>,
 ) -> Result<RecordBatch> {
     let mut type_builder = StringBuilder::with_capacity(1, 1024);
     let mut plan_builder = StringBuilder::with_capacity(1, 1024);
@@ -281,6 +330,25 @@ fn create_output_batch(
         plan_builder.append_value(format!("{duration:?}"));
     }
 
+    // Peak memory per consumer, if the memory pool records it
+    if let Some(memory_profile) = memory_profile.filter(|p| !p.is_empty()) {
+        type_builder.append_value("Memory Profile");
+        let profile = memory_profile
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}: peak={}, cumulative_reserved={}, consumers={}",
+                    entry.name,
+                    human_readable_size(entry.peak),
+                    human_readable_size(entry.cumulative_reserved),
+                    entry.num_consumers
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        plan_builder.append_value(profile);
+    }
+
     RecordBatch::try_new(
         Arc::clone(schema),
         vec![
@@ -291,6 +359,159 @@ fn create_output_batch(
     .map_err(DataFusionError::from)
 }
 
+/// Creates the output of AnalyzeExec in the JSON format as a RecordBatch
+fn create_json_output_batch(
+    total_rows: usize,
+    duration: std::time::Duration,
+    input: &Arc<dyn ExecutionPlan>,
+    schema: &SchemaRef,
+    metric_types: &[MetricType],
+    metric_categories: Option<&[MetricCategory]>,
+    memory_profile: Option<Vec<MemoryProfileEntry>>,
+) -> Result<RecordBatch> {
+    let filter = |metrics: MetricsSet| {
+        let metrics = metrics.filter_by_metric_types(metric_types);
+        match metric_categories {
+            Some(categories) => metrics.filter_by_categories(categories),
+            None => metrics,
+        }
+    };
+
+    let mut output = Map::new();
+    output.insert(
+        "plan".to_string(),
+        plan_metrics_json(input.as_ref(), &filter),
+    );
+    output.insert("output_rows".to_string(), json!(total_rows));
+    output.insert("duration_ns".to_string(), json!(duration.as_nanos()));
+    if let Some(memory_profile) = memory_profile.filter(|p| !p.is_empty()) {
+        let memory_profile = memory_profile
+            .iter()
+            .map(|entry| {
+                json!({
+                    "name": entry.name,
+                    "peak": entry.peak,
+                    "cumulative_reserved": entry.cumulative_reserved,
+                    "consumers": entry.num_consumers,
+                })
+            })
+            .collect();
+        output.insert("memory_profile".to_string(), Value::Array(memory_profile));
+    }
+
+    let mut type_builder = StringBuilder::with_capacity(1, 1024);
+    let mut plan_builder = StringBuilder::with_capacity(1, 1024);
+    type_builder.append_value("Plan with Metrics");
+    plan_builder.append_value(
+        serde_json::to_string_pretty(&Value::Object(output))
+            .map_err(|e| DataFusionError::External(Box::new(e)))?,
+    );
+
+    RecordBatch::try_new(
+        Arc::clone(schema),
+        vec![
+            Arc::new(type_builder.finish()),
+            Arc::new(plan_builder.finish()),
+        ],
+    )
+    .map_err(DataFusionError::from)
+}
+
+/// Returns the metrics of an executed plan as a JSON document
+///
+/// The document is a tree of the operators of the plan, in which each operator
+/// has the following fields:
+///
+/// * `operator`: the name of the operator, e.g. `SortExec`
+/// * `description`: the one line description of the operator, as printed by
+///   `EXPLAIN`
+/// * `metrics`: the metrics of the operator, aggregated across all partitions,
+///   such as `output_rows`, `output_batches`, `elapsed_compute` (in
+///   nanoseconds), `spill_count`, `spilled_bytes` and, for operators that
+///   track it, `peak_mem_used`
+/// * `partitions`: the metrics of each partition of the operator
+/// * `children`: the child operators
+///
+/// This is the plan printed by `EXPLAIN (ANALYZE, FORMAT JSON)`.
+pub fn collect_plan_metrics_json(plan: &dyn ExecutionPlan) -> Result<String> {
+    serde_json::to_string(&plan_metrics_json(plan, &|metrics| metrics))
+        .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+fn plan_metrics_json(
+    plan: &dyn ExecutionPlan,
+    filter: &dyn Fn(MetricsSet) -> MetricsSet,
+) -> Value {
+    let description = DisplayableExecutionPlan::new(plan).one_line().to_string();
+
+    let mut node = Map::new();
+    node.insert("operator".to_string(), json!(plan.name()));
+    node.insert("description".to_string(), json!(description.trim_end()));
+
+    if let Some(metrics) = plan.metrics().map(filter) {
+        node.insert(
+            "metrics".to_string(),
+            metrics_json(&metrics.aggregate_by_name()),
+        );
+
+        let mut partitions = BTreeMap::<usize, MetricsSet>::new();
+        for metric in metrics.iter() {
+            if let Some(partition) = metric.partition() {
+                partitions
+                    .entry(partition)
+                    .or_default()
+                    .push(Arc::clone(metric));
+            }
+        }
+        let partitions = partitions
+            .into_iter()
+            .map(|(partition, metrics)| {
+                json!({
+                    "partition": partition,
+                    "metrics": metrics_json(&metrics.aggregate_by_name()),
+                })
+            })
+            .collect();
+        node.insert("partitions".to_string(), Value::Array(partitions));
+    }
+
+    let children = plan
+        .children()
+        .into_iter()
+        .map(|child| plan_metrics_json(child.as_ref(), filter))
+        .collect();
+    node.insert("children".to_string(), Value::Array(children));
+    Value::Object(node)
+}
+
+/// The metrics of `metrics` by name. Timestamps are omitted, and metrics that
+/// are not a single number are rendered as objects or strings.
+fn metrics_json(metrics: &MetricsSet) -> Value {
+    let metrics = metrics
+        .iter()
+        .filter(|metric| !metric.value().is_timestamp())
+        .map(|metric| {
+            let value = match metric.value() {
+                MetricValue::PruningMetrics {
+                    pruning_metrics, ..
+                } => json!({
+                    "pruned": pruning_metrics.pruned(),
+                    "matched": pruning_metrics.matched(),
+                    "fully_matched": pruning_metrics.fully_matched(),
+                }),
+                MetricValue::Ratio { ratio_metrics, .. } => json!({
+                    "part": ratio_metrics.part(),
+                    "total": ratio_metrics.total(),
+                }),
+                MetricValue::Custom { value, .. } => json!(value.to_string()),
+                value => json!(value.as_usize()),
+            };
+            (metric.value().name().to_string(), value)
+        })
+        .collect();
+    Value::Object(metrics)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,11 +519,20 @@ mod tests {
         collect,
         test::{
             assert_is_pending,
-            exec::{BlockingExec, assert_strong_count_converges_to_zero},
+            exec::{BlockingExec, MockExec, assert_strong_count_converges_to_zero},
         },
     };
 
+    use crate::sorts::sort::SortExec;
+    use crate::test;
+
+    use arrow::array::AsArray;
     use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::assert_contains;
+    use datafusion_execution::memory_pool::{ProfilingMemoryPool, UnboundedMemoryPool};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_physical_expr::PhysicalSortExpr;
+    use datafusion_physical_expr::expressions::col;
     use futures::FutureExt;
 
     #[tokio::test]
@@ -331,4 +561,99 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_memory_profile() -> Result<()> {
+        let pool = Arc::new(ProfilingMemoryPool::new(UnboundedMemoryPool::default()));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_pool(Arc::clone(&pool) as _)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        let analyze = || -> Result<Arc<AnalyzeExec>> {
+            let batch = test::make_partition(100);
+            let input = Arc::new(MockExec::new(vec![Ok(batch.clone())], batch.schema()));
+            let sort_exec = Arc::new(SortExec::new(
+                [PhysicalSortExpr::new_default(col("i", &input.schema())?)].into(),
+                input,
+            ));
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("plan_type", DataType::Utf8, false),
+                Field::new("plan", DataType::Utf8, false),
+            ]));
+            Ok(Arc::new(AnalyzeExec::new(
+                false,
+                false,
+                vec![MetricType::Summary],
+                None,
+                sort_exec,
+                schema,
+            )))
+        };
+
+        // each query only reports the consumers it registered, while the
+        // shared pool records the consumers of both queries
+        for _ in 0..2 {
+            let batches = collect(analyze()?, Arc::clone(&task_ctx)).await?;
+            let plan_types = batches[0].column(0).as_string::<i32>();
+            let plans = batches[0].column(1).as_string::<i32>();
+            assert_eq!(plan_types.value(1), "Memory Profile");
+            assert_contains!(plans.value(1), "ExternalSorter[0]: peak=");
+            assert_contains!(plans.value(1), "consumers=1");
+        }
+        let sorter = pool
+            .profile()
+            .into_iter()
+            .find(|entry| entry.name == "ExternalSorter[0]")
+            .unwrap();
+        assert_eq!(sorter.num_consumers, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_json_format() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let batch = test::make_partition(100);
+        let input = Arc::new(MockExec::new(vec![Ok(batch.clone())], batch.schema()));
+        let sort_exec: Arc<dyn ExecutionPlan> = Arc::new(SortExec::new(
+            [PhysicalSortExpr::new_default(col("i", &input.schema())?)].into(),
+            input,
+        ));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("plan_type", DataType::Utf8, false),
+            Field::new("plan", DataType::Utf8, false),
+        ]));
+        let analyze_exec = Arc::new(
+            AnalyzeExec::new(
+                false,
+                false,
+                vec![MetricType::Summary, MetricType::Dev],
+                None,
+                Arc::clone(&sort_exec),
+                schema,
+            )
+            .with_format(ExplainAnalyzeFormat::Json),
+        );
+
+        let batches = collect(analyze_exec, task_ctx).await?;
+        let plans = batches[0].column(1).as_string::<i32>();
+        let output: Value = serde_json::from_str(plans.value(0)).unwrap();
+        assert_eq!(output["output_rows"], 100);
+
+        let sort = &output["plan"];
+        assert_eq!(sort["operator"], "SortExec");
+        assert_eq!(sort["metrics"]["output_rows"], 100);
+        assert_eq!(sort["partitions"][0]["partition"], 0);
+        assert_eq!(sort["partitions"][0]["metrics"]["output_rows"], 100);
+        assert_eq!(sort["children"][0]["operator"], "MockExec");
+        assert!(sort["children"][0]["metrics"].is_null());
+
+        // The metrics of the executed plan are also available directly
+        let sort: Value =
+            serde_json::from_str(&collect_plan_metrics_json(sort_exec.as_ref())?)
+                .unwrap();
+        assert_eq!(sort["metrics"]["output_rows"], 100);
+        assert!(sort["metrics"]["elapsed_compute"].as_u64().is_some());
+        Ok(())
+    }
 }
@@ -30,8 +30,9 @@ use crate::execution_plan::{CardinalityEffect, EvaluationType, SchedulingType};
 use crate::filter_pushdown::{FilterDescription, FilterPushdownPhase};
 use crate::projection::{ProjectionExec, make_with_child};
 use crate::sort_pushdown::SortOrderPushdownResult;
+use crate::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use crate::{DisplayFormatType, ExecutionPlan, Partitioning, check_if_same_properties};
-use datafusion_physical_expr_common::sort_expr::PhysicalSortExpr;
+use datafusion_physical_expr_common::sort_expr::{LexOrdering, PhysicalSortExpr};
 
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::TreeNodeRecursion;
@@ -318,32 +319,47 @@ impl ExecutionPlan for CoalescePartitionsExec {
         // global ordering. However, we can still push the sort requirement down
         // to optimize individual partitions - the Sort operator above will handle
         // the global ordering.
-        //
-        // Note: The result will always be at most Inexact (never Exact) when there
-        // are multiple partitions, because merging destroys global ordering.
         let result = self.input.try_pushdown_sort(order)?;
 
-        // If we have multiple partitions, we can't return Exact even if the
-        // underlying source claims Exact - merging destroys global ordering
         let has_multiple_partitions =
             self.input.output_partitioning().partition_count() > 1;
-
-        result
-            .try_map(|new_input| {
+        if !has_multiple_partitions {
+            return result.try_map(|new_input| {
                 Ok(
                     Arc::new(
                         CoalescePartitionsExec::new(new_input).with_fetch(self.fetch),
                     ) as Arc<dyn ExecutionPlan>,
                 )
-            })
-            .map(|r| {
-                if has_multiple_partitions {
-                    // Downgrade Exact to Inexact when merging multiple partitions
-                    r.into_inexact()
-                } else {
-                    r
-                }
-            })
+            });
+        }
+
+        match result {
+            // Every partition is sorted, e.g. because the files of each file
+            // group are sorted and do not overlap: merging the partitions
+            // rather than concatenating them keeps the ordering, so the sort
+            // above is not needed.
+            SortOrderPushdownResult::Exact { inner } => {
+                let Some(ordering) = LexOrdering::new(order.iter().cloned()) else {
+                    return Ok(SortOrderPushdownResult::Unsupported);
+                };
+                Ok(SortOrderPushdownResult::Exact {
+                    inner: Arc::new(
+                        SortPreservingMergeExec::new(ordering, inner)
+                            .with_fetch(self.fetch),
+                    ),
+                })
+            }
+            SortOrderPushdownResult::Inexact { inner } => {
+                Ok(SortOrderPushdownResult::Inexact {
+                    inner: Arc::new(
+                        CoalescePartitionsExec::new(inner).with_fetch(self.fetch),
+                    ),
+                })
+            }
+            SortOrderPushdownResult::Unsupported => {
+                Ok(SortOrderPushdownResult::Unsupported)
+            }
+        }
     }
 }
 
@@ -69,6 +69,14 @@
 //!
 //! The optimizer rule currently checks the plan for exchange-like operators and leave operators
 //! that report [`SchedulingType::NonCooperative`] in their [plan properties](ExecutionPlan::properties).
+//!
+//! ## Expensive expressions
+//!
+//! The budget above counts batches, not time: a single batch may take long to produce when an
+//! operator evaluates an expensive expression over it, such as a regular expression over huge
+//! strings or a large `IN` list. Operators that evaluate expressions (`FilterExec` and
+//! `ProjectionExec`) therefore also track the time spent evaluating them with a
+//! [`PollTimeBudget`], and yield once it exceeds `datafusion.execution.max_poll_duration_ms`.
 
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::TreeNodeRecursion;
@@ -91,10 +99,13 @@ use crate::{
 };
 use arrow::record_batch::RecordBatch;
 use arrow_schema::Schema;
+use datafusion_common::instant::Instant;
 use datafusion_common::{Result, Statistics, assert_eq_or_internal_err};
 use datafusion_execution::TaskContext;
+use std::time::Duration;
 
 use crate::execution_plan::SchedulingType;
+use crate::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder};
 use crate::stream::RecordBatchStreamAdapter;
 use datafusion_physical_expr_common::sort_expr::PhysicalSortExpr;
 use futures::{Stream, StreamExt};
@@ -393,6 +404,97 @@ pub fn make_cooperative(stream: SendableRecordBatchStream) -> SendableRecordBatc
     )))
 }
 
+/// Bounds the time an operator spends evaluating expressions without yielding
+/// to the Tokio executor.
+///
+/// The operator [records](Self::record) the evaluation of its expressions, and
+/// calls [`Self::checkpoint`] before it starts working on the next batch: once
+/// the recorded time exceeds `datafusion.execution.max_poll_duration_ms` since
+/// the operator last returned `Poll::Pending`, the checkpoint wakes the task and
+/// returns `Poll::Pending`, so that other tasks of the runtime can run. The
+/// number of such yields is reported as the `poll_time_yields` metric.
+///
+/// The budget is disabled when `max_poll_duration_ms` is 0 (the default).
+///
+/// See the [module level documentation](crate::coop) for more details.
+#[derive(Debug)]
+pub struct PollTimeBudget {
+    /// The maximum time to evaluate expressions for without yielding, `None`
+    /// when disabled
+    max_poll_duration: Option<Duration>,
+    /// The time spent evaluating expressions since the operator last yielded
+    elapsed: Duration,
+    /// The number of times the operator yielded because of the budget
+    yields: Option<Count>,
+}
+
+impl PollTimeBudget {
+    /// Create a new [`PollTimeBudget`] for `partition` of an operator, from
+    /// the configuration of `context`
+    pub fn new(
+        context: &TaskContext,
+        metrics: &ExecutionPlanMetricsSet,
+        partition: usize,
+    ) -> Self {
+        let max_poll_duration_ms = context
+            .session_config()
+            .options()
+            .execution
+            .max_poll_duration_ms;
+        let max_poll_duration = (max_poll_duration_ms > 0)
+            .then(|| Duration::from_millis(max_poll_duration_ms as u64));
+        let yields = max_poll_duration
+            .map(|_| MetricBuilder::new(metrics).counter("poll_time_yields", partition));
+        Self {
+            max_poll_duration,
+            elapsed: Duration::ZERO,
+            yields,
+        }
+    }
+
+    /// A [`PollTimeBudget`] that never yields
+    pub fn disabled() -> Self {
+        Self {
+            max_poll_duration: None,
+            elapsed: Duration::ZERO,
+            yields: None,
+        }
+    }
+
+    /// Records the time spent in `f`, evaluating expressions
+    pub fn record<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        if self.max_poll_duration.is_none() {
+            return f();
+        }
+        let start = Instant::now();
+        let result = f();
+        self.elapsed += start.elapsed();
+        result
+    }
+
+    /// Returns `Poll::Pending`, after waking the task, if the time recorded
+    /// since the operator last yielded exceeds the budget
+    pub fn checkpoint(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        match self.max_poll_duration {
+            Some(max_poll_duration) if self.elapsed >= max_poll_duration => {
+                self.elapsed = Duration::ZERO;
+                if let Some(yields) = &self.yields {
+                    yields.add(1);
+                }
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            _ => Poll::Ready(()),
+        }
+    }
+
+    /// Restarts the budget, once the operator returned `Poll::Pending` for
+    /// another reason, for example because its input is not ready
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,4 +545,37 @@ mod tests {
         assert_eq!(out.len(), count);
         Ok(())
     }
+
+    #[test]
+    fn poll_time_budget_yields_once_exceeded() {
+        let config = datafusion_execution::config::SessionConfig::new()
+            .set_usize("datafusion.execution.max_poll_duration_ms", 1);
+        let context = TaskContext::default().with_session_config(config);
+        let metrics = ExecutionPlanMetricsSet::new();
+        let mut budget = PollTimeBudget::new(&context, &metrics, 0);
+
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(budget.checkpoint(&mut cx).is_ready());
+        budget.record(|| std::thread::sleep(Duration::from_millis(2)));
+        assert!(budget.checkpoint(&mut cx).is_pending());
+        // yielding restarts the budget
+        assert!(budget.checkpoint(&mut cx).is_ready());
+
+        budget.record(|| std::thread::sleep(Duration::from_millis(2)));
+        budget.reset();
+        assert!(budget.checkpoint(&mut cx).is_ready());
+        assert_eq!(
+            metrics
+                .clone_inner()
+                .sum_by_name("poll_time_yields")
+                .unwrap()
+                .as_usize(),
+            1
+        );
+
+        let mut disabled = PollTimeBudget::disabled();
+        disabled.record(|| std::thread::sleep(Duration::from_millis(2)));
+        assert!(disabled.checkpoint(&mut cx).is_ready());
+    }
 }
@@ -46,12 +46,15 @@ use std::sync::{Arc, LazyLock};
 use crate::coalesce_partitions::CoalescePartitionsExec;
 use crate::display::DisplayableExecutionPlan;
 use crate::metrics::MetricsSet;
+use crate::plan_span::PlanSpanExportStream;
 use crate::projection::ProjectionExec;
+use crate::resource_limits::ResourceLimitedStream;
 use crate::stream::RecordBatchStreamAdapter;
 
 use arrow::array::{Array, RecordBatch};
 use arrow::datatypes::SchemaRef;
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::types::DFCategorical;
 use datafusion_common::{
     Constraints, DataFusionError, Result, assert_eq_or_internal_err,
     assert_or_internal_err, exec_err,
@@ -1310,6 +1313,19 @@ pub async fn collect(
 ///
 /// Dropping the stream will abort the execution of the query, and free up
 /// any allocated resources
+///
+/// # Exporting the Profile
+///
+/// If the runtime has a [`PlanSpanExporter`], the profile of the executed
+/// plan is exported once the stream is exhausted or fails.
+///
+/// # Resource Limits
+///
+/// If the `context` has [`QueryResourceLimits`], the stream fails once the
+/// query executes for too long or produces too many rows.
+///
+/// [`PlanSpanExporter`]: datafusion_execution::plan_span::PlanSpanExporter
+/// [`QueryResourceLimits`]: datafusion_execution::resource_limits::QueryResourceLimits
 #[expect(
     clippy::needless_pass_by_value,
     reason = "Public API that historically takes owned Arcs"
@@ -1318,17 +1334,31 @@ pub fn execute_stream(
     plan: Arc<dyn ExecutionPlan>,
     context: Arc<TaskContext>,
 ) -> Result<SendableRecordBatchStream> {
-    match plan.output_partitioning().partition_count() {
-        0 => Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
-        1 => plan.execute(0, context),
+    let plan = match plan.output_partitioning().partition_count() {
+        0 => return Ok(Box::pin(EmptyRecordBatchStream::new(plan.schema()))),
+        1 => plan,
         2.. => {
             // merge into a single partition
             let plan = CoalescePartitionsExec::new(Arc::clone(&plan));
             // CoalescePartitionsExec must produce a single partition
             assert_eq!(1, plan.properties().output_partitioning().partition_count());
-            plan.execute(0, context)
+            Arc::new(plan) as _
         }
-    }
+    };
+
+    if let Some(tracker) = context.resource_tracker() {
+        tracker.start();
+    }
+    let stream = limit_resources(plan.execute(0, Arc::clone(&context))?, &context);
+    Ok(match &context.runtime_env().plan_span_exporter {
+        // export the profile of the plan once it is executed
+        Some(exporter) => Box::pin(PlanSpanExportStream::new(
+            stream,
+            plan,
+            Arc::clone(exporter),
+        )),
+        None => stream,
+    })
 }
 
 /// Execute the [ExecutionPlan] and collect the results in memory
@@ -1387,13 +1417,35 @@ pub fn execute_stream_partitioned(
     context: Arc<TaskContext>,
 ) -> Result<Vec<SendableRecordBatchStream>> {
     let num_partitions = plan.output_partitioning().partition_count();
+    if let Some(tracker) = context.resource_tracker() {
+        tracker.start();
+    }
     let mut streams = Vec::with_capacity(num_partitions);
     for i in 0..num_partitions {
-        streams.push(plan.execute(i, Arc::clone(&context))?);
+        streams.push(limit_resources(
+            plan.execute(i, Arc::clone(&context))?,
+            &context,
+        ));
     }
     Ok(streams)
 }
 
+/// Enforce the [`QueryResourceLimits`] of `context`, if any, on an output
+/// stream of the query
+///
+/// [`QueryResourceLimits`]: datafusion_execution::resource_limits::QueryResourceLimits
+fn limit_resources(
+    stream: SendableRecordBatchStream,
+    context: &TaskContext,
+) -> SendableRecordBatchStream {
+    match context.resource_tracker() {
+        Some(tracker) => {
+            Box::pin(ResourceLimitedStream::new(stream, Arc::clone(tracker)))
+        }
+        None => stream,
+    }
+}
+
 /// Executes an input stream and ensures that the resulting stream adheres to
 /// the `not null` constraints specified in the `sink_schema`.
 ///
@@ -1413,6 +1465,8 @@ pub fn execute_stream_partitioned(
 /// violate the `not null` constraints specified in the `sink_schema`. If there are
 /// such columns, it wraps the resulting stream to enforce the `not null` constraints
 /// by invoking the [`check_not_null_constraints`] function on each batch of the stream.
+/// Likewise, the values of columns declared as [`DFCategorical`] in the `sink_schema`
+/// are checked with [`check_categorical_constraints`].
 #[expect(
     clippy::needless_pass_by_value,
     reason = "Public API that historically takes owned Arcs"
@@ -1438,14 +1492,35 @@ pub fn execute_input_stream(
         })
         .collect();
 
-    if risky_columns.is_empty() {
+    // Find columns whose values must be one of the allowed categorical values.
+    let categorical_columns = sink_schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, sink_field)| {
+            DFCategorical::try_from_field(sink_field)
+                .transpose()
+                .map(|categorical| categorical.map(|categorical| (idx, categorical)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if risky_columns.is_empty() && categorical_columns.is_empty() {
         Ok(input_stream)
     } else {
-        // Check not null constraint on the input stream
+        // Check not null and categorical constraints on the input stream
+        let mut first_row = 0;
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             sink_schema,
-            input_stream
-                .map(move |batch| check_not_null_constraints(batch?, &risky_columns)),
+            input_stream.map(move |batch| {
+                let batch = batch?;
+                let num_rows = batch.num_rows();
+                let batch =
+                    check_not_null_constraints_impl(batch, &risky_columns, |row| {
+                        format!("row {} of the input", first_row + row)
+                    })?;
+                first_row += num_rows;
+                check_categorical_constraints(batch, &categorical_columns)
+            }),
         )))
     }
 }
@@ -1464,10 +1539,22 @@ pub fn execute_input_stream(
 ///
 /// This function iterates over the specified column indices and ensures that none
 /// of the columns contain null values. If any column contains null values, an error
-/// is returned.
+/// naming the column and its first null row is returned.
 pub fn check_not_null_constraints(
     batch: RecordBatch,
-    column_indices: &Vec<usize>,
+    column_indices: &[usize],
+) -> Result<RecordBatch> {
+    check_not_null_constraints_impl(batch, column_indices, |row| {
+        format!("row {row} of the batch")
+    })
+}
+
+/// Implementation of [`check_not_null_constraints`], describing the (one
+/// based) row of the first null value with `describe_row`
+fn check_not_null_constraints_impl(
+    batch: RecordBatch,
+    column_indices: &[usize],
+    describe_row: impl Fn(usize) -> String,
 ) -> Result<RecordBatch> {
     for &index in column_indices {
         if batch.num_columns() <= index {
@@ -1478,18 +1565,50 @@ pub fn check_not_null_constraints(
             );
         }
 
-        if batch
+        let first_null = batch
             .column(index)
             .logical_nulls()
-            .map(|nulls| nulls.null_count())
-            .unwrap_or_default()
-            > 0
-        {
+            .filter(|nulls| nulls.null_count() > 0)
+            .and_then(|nulls| nulls.iter().position(|valid| !valid));
+        if let Some(row) = first_null {
+            return exec_err!(
+                "Invalid batch column at '{}' has null but schema specifies non-nullable: \
+                column '{}' is null in {}",
+                index,
+                batch.schema_ref().field(index).name(),
+                describe_row(row + 1)
+            );
+        }
+    }
+
+    Ok(batch)
+}
+
+/// Checks a `RecordBatch` for categorical constraints on specified columns.
+///
+/// # Arguments
+///
+/// * `batch` - The `RecordBatch` to be checked
+/// * `columns` - The indices of the columns to be checked, together with the
+///   [`DFCategorical`] type declaring their allowed values.
+///
+/// # Returns
+///
+/// * `Result<RecordBatch>` - The original `RecordBatch` if all constraints are met
+pub fn check_categorical_constraints(
+    batch: RecordBatch,
+    columns: &[(usize, DFCategorical)],
+) -> Result<RecordBatch> {
+    for (index, categorical) in columns {
+        if batch.num_columns() <= *index {
             return exec_err!(
-                "Invalid batch column at '{}' has null but schema specifies non-nullable",
+                "Invalid batch column count {} expected > {}",
+                batch.num_columns(),
                 index
             );
         }
+
+        categorical.validate(batch.column(*index))?;
     }
 
     Ok(batch)
@@ -1599,7 +1718,7 @@ mod tests {
     use crate::{DisplayAs, DisplayFormatType, ExecutionPlan};
 
     use arrow::array::{DictionaryArray, Int32Array, NullArray, RunArray};
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::datatypes::{DataType, Field, Int32Type, Schema};
 
     #[derive(Debug)]
     pub struct EmptyExec;
@@ -1862,7 +1981,7 @@ mod tests {
                 Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)])),
                 vec![Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(3)]))],
             )?,
-            &vec![0],
+            &[0],
         )?;
         Ok(())
     }
@@ -1874,12 +1993,12 @@ mod tests {
                 Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)])),
                 vec![Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))],
             )?,
-            &vec![0],
+            &[0],
         );
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().strip_backtrace(),
-            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable",
+            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable: column 'a' is null in row 2 of the batch",
         );
         Ok(())
     }
@@ -1899,12 +2018,12 @@ mod tests {
                 )])),
                 vec![Arc::new(run_end_array)],
             )?,
-            &vec![0],
+            &[0],
         );
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().strip_backtrace(),
-            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable",
+            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable: column 'a' is null in row 2 of the batch",
         );
         Ok(())
     }
@@ -1923,12 +2042,12 @@ mod tests {
                 )])),
                 vec![Arc::new(dictionary)],
             )?,
-            &vec![0],
+            &[0],
         );
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().strip_backtrace(),
-            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable",
+            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable: column 'a' is null in row 2 of the batch",
         );
         Ok(())
     }
@@ -1953,7 +2072,7 @@ mod tests {
                 )])),
                 vec![Arc::new(dictionary)],
             )?,
-            &vec![0],
+            &[0],
         )?;
         Ok(())
     }
@@ -1966,12 +2085,40 @@ mod tests {
                 Arc::new(Schema::new(vec![Field::new("a", DataType::Null, true)])),
                 vec![Arc::new(NullArray::new(3))],
             )?,
-            &vec![0],
+            &[0],
         );
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().strip_backtrace(),
-            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable",
+            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable: column 'a' is null in row 1 of the batch",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_categorical_constraints() -> Result<()> {
+        let categorical = DFCategorical::try_new(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            vec!["a".to_string(), "b".to_string()],
+        )?;
+        let schema = Arc::new(Schema::new(vec![categorical.to_field("c")]));
+
+        let valid: DictionaryArray<Int32Type> =
+            vec![Some("a"), None, Some("b")].into_iter().collect();
+        check_categorical_constraints(
+            RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(valid)])?,
+            &[(0, categorical.clone())],
+        )?;
+
+        let invalid: DictionaryArray<Int32Type> =
+            vec![Some("a"), Some("c")].into_iter().collect();
+        let result = check_categorical_constraints(
+            RecordBatch::try_new(schema, vec![Arc::new(invalid)])?,
+            &[(0, categorical)],
+        );
+        assert_eq!(
+            result.unwrap_err().strip_backtrace(),
+            "Execution error: Value 'c' is not allowed for categorical type, expected one of: a, b",
         );
         Ok(())
     }
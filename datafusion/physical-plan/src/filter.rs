@@ -31,6 +31,7 @@ use super::{
 use crate::check_if_same_properties;
 use crate::coalesce::{LimitedBatchCoalescer, PushBatchStatus};
 use crate::common::can_project;
+use crate::coop::PollTimeBudget;
 use crate::execution_plan::CardinalityEffect;
 use crate::filter_pushdown::{
     ChildFilterDescription, ChildPushdownResult, FilterDescription, FilterPushdownPhase,
@@ -556,6 +557,7 @@ impl ExecutionPlan for FilterExec {
             context.task_id()
         );
         let metrics = FilterExecMetrics::new(&self.metrics, partition);
+        let time_budget = PollTimeBudget::new(&context, &self.metrics, partition);
         Ok(Box::pin(FilterExecStream {
             schema: self.schema(),
             predicate: Arc::clone(&self.predicate),
@@ -567,6 +569,7 @@ impl ExecutionPlan for FilterExec {
                 self.batch_size,
                 self.fetch,
             ),
+            time_budget,
         }))
     }
 
@@ -950,6 +953,8 @@ struct FilterExecStream {
     projection: Option<ProjectionRef>,
     /// Batch coalescer to combine small batches
     batch_coalescer: LimitedBatchCoalescer,
+    /// Bounds the time spent evaluating the predicate without yielding
+    time_budget: PollTimeBudget,
 }
 
 /// The metrics for `FilterExec`
@@ -1026,16 +1031,30 @@ impl Stream for FilterExecStream {
                 return Poll::Ready(None);
             }
 
+            // Yield if evaluating the predicate took too long since the last yield
+            ready!(self.time_budget.checkpoint(cx));
+
             // Attempt to pull the next batch from the input stream.
-            match ready!(self.input.poll_next_unpin(cx)) {
+            let poll = self.input.poll_next_unpin(cx);
+            if poll.is_pending() {
+                self.time_budget.reset();
+            }
+            match ready!(poll) {
                 None => {
                     self.batch_coalescer.finish()?;
                     // continue draining the coalescer
                 }
                 Some(Ok(batch)) => {
                     let timer = elapsed_compute.timer();
-                    let status = self.predicate.as_ref()
-                        .evaluate(&batch)
+                    let evaluated = {
+                        let Self {
+                            predicate,
+                            time_budget,
+                            ..
+                        } = &mut *self;
+                        time_budget.record(|| predicate.evaluate(&batch))
+                    };
+                    let status = evaluated
                         .and_then(|v| v.into_array(batch.num_rows()))
                         .and_then(|array| {
                             Ok(match self.projection.as_ref()  {
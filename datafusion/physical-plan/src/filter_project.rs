@@ -0,0 +1,797 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FilterProjectExec`] evaluates a filter and a projection in a single
+//! pipeline operator.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use super::{
+    DisplayAs, PlanProperties, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+use crate::check_if_same_properties;
+use crate::coalesce::{LimitedBatchCoalescer, PushBatchStatus};
+use crate::execution_plan::CardinalityEffect;
+use crate::filter::FilterExecBuilder;
+use crate::metrics::{MetricBuilder, MetricType};
+use crate::projection::{ProjectionExec, ProjectionExpr};
+use crate::{
+    DisplayFormatType, ExecutionPlan,
+    metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet, RatioMetrics},
+};
+
+use arrow::array::RecordBatchOptions;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::{Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::cast::as_boolean_array;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Result, internal_err};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{BinaryExpr, CaseExpr, Column, Literal};
+use datafusion_physical_expr::projection::ProjectionExprs;
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr::{PhysicalExpr, ScalarFunctionExpr};
+use datafusion_physical_expr_common::physical_expr::{fmt_sql, is_volatile};
+use datafusion_physical_expr_common::utils::evaluate_expressions_to_arrays;
+
+use futures::stream::{Stream, StreamExt};
+use itertools::Itertools;
+use log::trace;
+
+const FILTER_PROJECT_EXEC_DEFAULT_SELECTIVITY: u8 = 20;
+const FILTER_PROJECT_EXEC_DEFAULT_BATCH_SIZE: usize = 8192;
+
+/// FilterProjectExec filters its input batches with a boolean predicate and
+/// evaluates a set of projection expressions on the remaining rows, coalescing
+/// the results into batches of `batch_size` rows.
+///
+/// It produces the same output as a [`ProjectionExec`] on top of a
+/// [`FilterExec`](crate::filter::FilterExec), but avoids materializing the
+/// filtered batch with all input columns: only the columns referenced by the
+/// projection are filtered. Sub-expressions that appear in both the predicate
+/// and the projection (e.g. `a + b` in `SELECT a + b ... WHERE a + b > 10`)
+/// are evaluated once per input batch and reused.
+///
+/// Only sub-expressions the predicate evaluates for every input row are
+/// reused, so rows that the predicate would skip (e.g. the right side of an
+/// `AND`, or the branches of a `CASE`) are never evaluated eagerly.
+#[derive(Debug, Clone)]
+pub struct FilterProjectExec {
+    /// The expression to filter on. This expression must evaluate to a boolean value.
+    predicate: Arc<dyn PhysicalExpr>,
+    /// The projection expressions, evaluated against the input schema
+    projection: ProjectionExprs,
+    /// The input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Selectivity for statistics. 0 = no rows, 100 = all rows
+    default_selectivity: u8,
+    /// Target batch size for output batches
+    batch_size: usize,
+    /// The predicate and projection rewritten to share common sub-expressions
+    fused: Arc<FusedExprs>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Properties equivalence properties, partitioning, etc.
+    cache: Arc<PlanProperties>,
+}
+
+impl FilterProjectExec {
+    /// Create a FilterProjectExec that filters `input` with `predicate` and
+    /// then evaluates the `projection` expressions.
+    pub fn try_new<I, E>(
+        predicate: Arc<dyn PhysicalExpr>,
+        projection: I,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<ProjectionExpr>,
+    {
+        let projection =
+            ProjectionExprs::new(projection.into_iter().map(Into::into).collect_vec());
+        Self::try_new_with_selectivity(
+            predicate,
+            projection,
+            input,
+            FILTER_PROJECT_EXEC_DEFAULT_SELECTIVITY,
+            FILTER_PROJECT_EXEC_DEFAULT_BATCH_SIZE,
+        )
+    }
+
+    fn try_new_with_selectivity(
+        predicate: Arc<dyn PhysicalExpr>,
+        projection: ProjectionExprs,
+        input: Arc<dyn ExecutionPlan>,
+        default_selectivity: u8,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let unfused =
+            Self::unfused_plan(&predicate, &projection, &input, default_selectivity)?;
+        let fused = FusedExprs::try_new(&predicate, &projection, &input.schema())?;
+        Ok(Self {
+            predicate,
+            projection,
+            input,
+            default_selectivity,
+            batch_size,
+            fused: Arc::new(fused),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: Arc::clone(unfused.properties()),
+        })
+    }
+
+    /// Return new instance of [`FilterProjectExec`] with the given selectivity.
+    pub fn with_default_selectivity(self, default_selectivity: u8) -> Result<Self> {
+        Self::try_new_with_selectivity(
+            self.predicate,
+            self.projection,
+            self.input,
+            default_selectivity,
+            self.batch_size,
+        )
+    }
+
+    /// Set the target batch size for output batches
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// The expression to filter on
+    pub fn predicate(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.predicate
+    }
+
+    /// The projection expressions
+    pub fn expr(&self) -> &[ProjectionExpr] {
+        self.projection.as_ref()
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The default selectivity
+    pub fn default_selectivity(&self) -> u8 {
+        self.default_selectivity
+    }
+
+    /// Target batch size for output batches
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// The sub-expressions shared by the predicate and the projection, which
+    /// are evaluated once per input batch
+    pub fn common_exprs(&self) -> &[Arc<dyn PhysicalExpr>] {
+        &self.fused.common
+    }
+
+    /// Builds the equivalent `ProjectionExec` on top of a `FilterExec`, which
+    /// defines the plan properties and statistics of this operator.
+    fn unfused_plan(
+        predicate: &Arc<dyn PhysicalExpr>,
+        projection: &ProjectionExprs,
+        input: &Arc<dyn ExecutionPlan>,
+        default_selectivity: u8,
+    ) -> Result<ProjectionExec> {
+        let filter = FilterExecBuilder::new(Arc::clone(predicate), Arc::clone(input))
+            .with_default_selectivity(default_selectivity)
+            .build()?;
+        ProjectionExec::try_new(projection.iter().cloned(), Arc::new(filter))
+    }
+
+    fn with_new_children_and_same_properties(
+        &self,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Self {
+        Self {
+            input: children.swap_remove(0),
+            metrics: ExecutionPlanMetricsSet::new(),
+            ..Self::clone(self)
+        }
+    }
+}
+
+impl DisplayAs for FilterProjectExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let expr: Vec<String> = self
+                    .expr()
+                    .iter()
+                    .map(|proj_expr| {
+                        let e = proj_expr.expr.to_string();
+                        if e != proj_expr.alias {
+                            format!("{e} as {}", proj_expr.alias)
+                        } else {
+                            e
+                        }
+                    })
+                    .collect();
+
+                write!(
+                    f,
+                    "FilterProjectExec: predicate={}, expr=[{}]",
+                    self.predicate,
+                    expr.join(", ")
+                )
+            }
+            DisplayFormatType::TreeRender => {
+                writeln!(f, "predicate={}", fmt_sql(self.predicate.as_ref()))?;
+                for (i, proj_expr) in self.expr().iter().enumerate() {
+                    let expr_sql = fmt_sql(proj_expr.expr.as_ref());
+                    if proj_expr.expr.to_string() == proj_expr.alias {
+                        writeln!(f, "expr{i}={expr_sql}")?;
+                    } else {
+                        writeln!(f, "{}={expr_sql}", proj_expr.alias)?;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for FilterProjectExec {
+    fn name(&self) -> &'static str {
+        "FilterProjectExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn properties(&self) -> &Arc<PlanProperties> {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn apply_expressions(
+        &self,
+        f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        let mut tnr = f(self.predicate.as_ref())?;
+        for proj_expr in self.expr() {
+            tnr = tnr.visit_sibling(|| f(proj_expr.expr.as_ref()))?;
+        }
+        Ok(tnr)
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        // Tell optimizer this operator doesn't reorder its input
+        vec![true]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        check_if_same_properties!(self, children);
+        Self::try_new_with_selectivity(
+            Arc::clone(&self.predicate),
+            self.projection.clone(),
+            children.swap_remove(0),
+            self.default_selectivity,
+            self.batch_size,
+        )
+        .map(|e| Arc::new(e) as _)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        trace!(
+            "Start FilterProjectExec::execute for partition {} of context session_id {} and task_id {:?}",
+            partition,
+            context.session_id(),
+            context.task_id()
+        );
+        Ok(Box::pin(FilterProjectExecStream {
+            schema: self.schema(),
+            fused: Arc::clone(&self.fused),
+            input: self.input.execute(partition, context)?,
+            metrics: FilterProjectExecMetrics::new(&self.metrics, partition),
+            batch_coalescer: LimitedBatchCoalescer::new(
+                self.schema(),
+                self.batch_size,
+                None,
+            ),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Arc<Statistics>> {
+        Self::unfused_plan(
+            &self.predicate,
+            &self.projection,
+            &self.input,
+            self.default_selectivity,
+        )?
+        .partition_statistics(partition)
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::LowerEqual
+    }
+}
+
+/// The predicate and projection of a [`FilterProjectExec`], rewritten so that
+/// common sub-expressions are evaluated only once per input batch.
+#[derive(Debug)]
+struct FusedExprs {
+    /// Sub-expressions shared by the predicate and the projection. Their
+    /// results are appended to the input batch as extra columns.
+    common: Vec<Arc<dyn PhysicalExpr>>,
+    /// Output fields of the `common` expressions
+    common_fields: Vec<Arc<Field>>,
+    /// The predicate, referencing the input batch extended with the common
+    /// sub-expression columns
+    predicate: Arc<dyn PhysicalExpr>,
+    /// Columns of the extended input batch needed by the projection. Only
+    /// these columns are filtered.
+    required_columns: Vec<usize>,
+    /// The projection, referencing the filtered `required_columns`
+    projection: Vec<Arc<dyn PhysicalExpr>>,
+}
+
+impl FusedExprs {
+    fn try_new(
+        predicate: &Arc<dyn PhysicalExpr>,
+        projection: &ProjectionExprs,
+        input_schema: &SchemaRef,
+    ) -> Result<Self> {
+        let num_input_columns = input_schema.fields().len();
+        let mut candidates = vec![];
+        collect_shareable_exprs(predicate, &mut candidates);
+
+        // Replace the largest sub-expressions of the projection that the
+        // predicate also evaluates with references to their shared column
+        let mut common: Vec<Arc<dyn PhysicalExpr>> = vec![];
+        let projection = projection
+            .expr_iter()
+            .map(|expr| {
+                expr.transform_down(|expr| {
+                    if !candidates.iter().any(|c| c.as_ref() == expr.as_ref()) {
+                        return Ok(Transformed::no(expr));
+                    }
+                    let index = match common.iter().position(|c| c == &expr) {
+                        Some(index) => index,
+                        None => {
+                            common.push(expr);
+                            common.len() - 1
+                        }
+                    };
+                    Ok(Transformed::new(
+                        common_column(num_input_columns, index),
+                        true,
+                        TreeNodeRecursion::Jump,
+                    ))
+                })
+                .map(|t| t.data)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let predicate = Arc::clone(predicate)
+            .transform_down(|expr| {
+                Ok(match common.iter().position(|c| c == &expr) {
+                    Some(index) => Transformed::new(
+                        common_column(num_input_columns, index),
+                        true,
+                        TreeNodeRecursion::Jump,
+                    ),
+                    None => Transformed::no(expr),
+                })
+            })?
+            .data;
+
+        let common_fields = common
+            .iter()
+            .enumerate()
+            .map(|(index, expr)| {
+                let field = expr.return_field(input_schema)?;
+                Ok(Arc::new(
+                    field.as_ref().clone().with_name(common_column_name(index)),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Only filter the columns the projection needs, and remap the
+        // projection to the filtered batch
+        let required_columns = projection
+            .iter()
+            .flat_map(|expr| collect_columns(expr).into_iter().map(|c| c.index()))
+            .sorted_unstable()
+            .dedup()
+            .collect_vec();
+        let projection = projection
+            .into_iter()
+            .map(|expr| {
+                expr.transform_up(|expr| {
+                    let Some(column) = expr.downcast_ref::<Column>() else {
+                        return Ok(Transformed::no(expr));
+                    };
+                    let Ok(index) = required_columns.binary_search(&column.index())
+                    else {
+                        return internal_err!(
+                            "Column {column} not found in the required columns"
+                        );
+                    };
+                    Ok(Transformed::yes(
+                        Arc::new(Column::new(column.name(), index)) as _,
+                    ))
+                })
+                .map(|t| t.data)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            common,
+            common_fields,
+            predicate,
+            required_columns,
+            projection,
+        })
+    }
+
+    /// Filters `batch` and evaluates the projection on the remaining rows
+    fn evaluate(&self, batch: RecordBatch, schema: &SchemaRef) -> Result<RecordBatch> {
+        let num_rows = batch.num_rows();
+        let batch = if self.common.is_empty() {
+            batch
+        } else {
+            let fields = batch
+                .schema()
+                .fields()
+                .iter()
+                .cloned()
+                .chain(self.common_fields.iter().cloned())
+                .collect_vec();
+            let mut columns = batch.columns().to_vec();
+            columns.extend(evaluate_expressions_to_arrays(&self.common, &batch)?);
+            RecordBatch::try_new_with_options(
+                Arc::new(Schema::new_with_metadata(
+                    fields,
+                    batch.schema().metadata().clone(),
+                )),
+                columns,
+                &RecordBatchOptions::new().with_row_count(Some(num_rows)),
+            )?
+        };
+
+        let mask = self.predicate.evaluate(&batch)?.into_array(num_rows)?;
+        let Ok(mask) = as_boolean_array(&mask) else {
+            return internal_err!(
+                "Cannot create filter_array from non-boolean predicates"
+            );
+        };
+        let filtered =
+            filter_record_batch(&batch.project(&self.required_columns)?, mask)?;
+
+        let arrays = evaluate_expressions_to_arrays(&self.projection, &filtered)?;
+        Ok(RecordBatch::try_new_with_options(
+            Arc::clone(schema),
+            arrays,
+            &RecordBatchOptions::new().with_row_count(Some(filtered.num_rows())),
+        )?)
+    }
+}
+
+fn common_column_name(index: usize) -> String {
+    format!("__common_expr_{index}")
+}
+
+fn common_column(num_input_columns: usize, index: usize) -> Arc<dyn PhysicalExpr> {
+    Arc::new(Column::new(
+        &common_column_name(index),
+        num_input_columns + index,
+    ))
+}
+
+/// Collects the sub-expressions of `expr` that are evaluated for every input
+/// row and are worth sharing, i.e. all but columns, literals and volatile
+/// expressions.
+///
+/// Does not descend into sub-expressions that may only be evaluated for some
+/// rows, like the right side of `AND` / `OR`, the branches of `CASE` and the
+/// arguments of short-circuiting functions, since evaluating those eagerly
+/// could fail on rows the predicate would skip.
+fn collect_shareable_exprs(
+    expr: &Arc<dyn PhysicalExpr>,
+    shareable: &mut Vec<Arc<dyn PhysicalExpr>>,
+) {
+    if expr.downcast_ref::<Column>().is_some()
+        || expr.downcast_ref::<Literal>().is_some()
+        || is_volatile(expr)
+    {
+        return;
+    }
+    shareable.push(Arc::clone(expr));
+
+    if let Some(binary) = expr.downcast_ref::<BinaryExpr>()
+        && matches!(binary.op(), Operator::And | Operator::Or)
+    {
+        collect_shareable_exprs(binary.left(), shareable);
+        return;
+    }
+    if expr.downcast_ref::<CaseExpr>().is_some() {
+        return;
+    }
+    if let Some(func) = expr.downcast_ref::<ScalarFunctionExpr>()
+        && func.fun().short_circuits()
+    {
+        return;
+    }
+    for child in expr.children() {
+        collect_shareable_exprs(child, shareable);
+    }
+}
+
+/// The FilterProjectExec stream filters and projects the input batches and
+/// coalesces the results
+struct FilterProjectExecStream {
+    /// Output schema after the projection
+    schema: SchemaRef,
+    /// The rewritten predicate and projection
+    fused: Arc<FusedExprs>,
+    /// The input partition to filter.
+    input: SendableRecordBatchStream,
+    /// Runtime metrics recording
+    metrics: FilterProjectExecMetrics,
+    /// Batch coalescer to combine small batches
+    batch_coalescer: LimitedBatchCoalescer,
+}
+
+/// The metrics for `FilterProjectExec`
+struct FilterProjectExecMetrics {
+    /// Common metrics for most operators
+    baseline_metrics: BaselineMetrics,
+    /// Selectivity of the filter, calculated as output_rows / input_rows
+    selectivity: RatioMetrics,
+}
+
+impl FilterProjectExecMetrics {
+    fn new(metrics: &ExecutionPlanMetricsSet, partition: usize) -> Self {
+        Self {
+            baseline_metrics: BaselineMetrics::new(metrics, partition),
+            selectivity: MetricBuilder::new(metrics)
+                .with_type(MetricType::Summary)
+                .ratio_metrics("selectivity", partition),
+        }
+    }
+}
+
+impl Stream for FilterProjectExecStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let elapsed_compute = self.metrics.baseline_metrics.elapsed_compute().clone();
+        loop {
+            // If there is a completed batch ready, return it
+            if let Some(batch) = self.batch_coalescer.next_completed_batch() {
+                self.metrics.selectivity.add_part(batch.num_rows());
+                let poll = Poll::Ready(Some(Ok(batch)));
+                return self.metrics.baseline_metrics.record_poll(poll);
+            }
+
+            if self.batch_coalescer.is_finished() {
+                // If input is done and no batches are ready, return None to signal end of stream.
+                return Poll::Ready(None);
+            }
+
+            // Attempt to pull the next batch from the input stream.
+            match ready!(self.input.poll_next_unpin(cx)) {
+                None => {
+                    self.batch_coalescer.finish()?;
+                    // continue draining the coalescer
+                }
+                Some(Ok(batch)) => {
+                    let timer = elapsed_compute.timer();
+                    self.metrics.selectivity.add_total(batch.num_rows());
+                    let batch = self.fused.evaluate(batch, &self.schema)?;
+                    let status = self.batch_coalescer.push_batch(batch)?;
+                    timer.done();
+
+                    match status {
+                        PushBatchStatus::Continue => {
+                            // Keep pushing more batches
+                        }
+                        PushBatchStatus::LimitReached => {
+                            // limit was reached, so stop early
+                            self.batch_coalescer.finish()?;
+                            // continue draining the coalescer
+                        }
+                    }
+                }
+
+                // Error case
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Same number of record batches
+        self.input.size_hint()
+    }
+}
+
+impl RecordBatchStream for FilterProjectExecStream {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, case, col, lit};
+    use crate::filter::FilterExec;
+    use crate::test::TestMemoryExec;
+    use crate::{collect, displayable};
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::test_util::batches_to_string;
+    use insta::assert_snapshot;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+            Field::new("c", DataType::Int32, false),
+        ]))
+    }
+
+    /// Two input batches with rows `(i, i % 3, 10 * i)` for `i` in `0..10`
+    fn input() -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = schema();
+        let batch = |range: std::ops::Range<i32>| {
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![
+                    Arc::new(Int32Array::from_iter_values(range.clone())),
+                    Arc::new(Int32Array::from_iter_values(range.clone().map(|i| i % 3))),
+                    Arc::new(Int32Array::from_iter_values(range.map(|i| 10 * i))),
+                ],
+            )
+        };
+        Ok(TestMemoryExec::try_new_exec(
+            &[vec![batch(0..5)?, batch(5..10)?]],
+            Arc::clone(&schema),
+            None,
+        )?)
+    }
+
+    #[tokio::test]
+    async fn shares_common_sub_expressions() -> Result<()> {
+        let input = input()?;
+        let schema = input.schema();
+        let a_plus_b = binary(
+            col("a", &schema)?,
+            Operator::Plus,
+            col("b", &schema)?,
+            &schema,
+        )?;
+        let predicate = binary(Arc::clone(&a_plus_b), Operator::Gt, lit(5), &schema)?;
+        let projection = vec![
+            ProjectionExpr::new(col("c", &schema)?, "c"),
+            ProjectionExpr::new(
+                binary(a_plus_b, Operator::Multiply, lit(2), &schema)?,
+                "doubled",
+            ),
+        ];
+
+        let exec = FilterProjectExec::try_new(
+            Arc::clone(&predicate),
+            projection.clone(),
+            Arc::clone(&input),
+        )?;
+        assert_eq!(exec.common_exprs().len(), 1);
+        assert_eq!(exec.common_exprs()[0].to_string(), "a@0 + b@1");
+        assert_snapshot!(
+            displayable(&exec).one_line().to_string(),
+            @"FilterProjectExec: predicate=a@0 + b@1 > 5, expr=[c@2 as c, (a@0 + b@1) * 2 as doubled]"
+        );
+
+        // the fused operator coalesces the two filtered input batches
+        let batches = collect(Arc::new(exec), Arc::new(TaskContext::default())).await?;
+        assert_eq!(batches.len(), 1);
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+---------+
+        | c  | doubled |
+        +----+---------+
+        | 50 | 14      |
+        | 60 | 12      |
+        | 70 | 16      |
+        | 80 | 20      |
+        | 90 | 18      |
+        +----+---------+
+        ");
+
+        // and produces the same output as the unfused operators
+        let unfused = ProjectionExec::try_new(
+            projection,
+            Arc::new(FilterExec::try_new(predicate, input)?),
+        )?;
+        let expected =
+            collect(Arc::new(unfused), Arc::new(TaskContext::default())).await?;
+        assert_eq!(batches_to_string(&batches), batches_to_string(&expected));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn does_not_share_conditionally_evaluated_exprs() -> Result<()> {
+        let input = input()?;
+        let schema = input.schema();
+        // `a / b` is only evaluated by the predicate for rows where `b != 0`,
+        // so evaluating it for all rows would fail with a division by zero
+        let a_div_b = binary(
+            col("a", &schema)?,
+            Operator::Divide,
+            col("b", &schema)?,
+            &schema,
+        )?;
+        let predicate = case(
+            None,
+            vec![(
+                binary(col("b", &schema)?, Operator::NotEq, lit(0), &schema)?,
+                binary(Arc::clone(&a_div_b), Operator::Gt, lit(3), &schema)?,
+            )],
+            Some(lit(false)),
+        )?;
+
+        let exec = FilterProjectExec::try_new(
+            predicate,
+            vec![ProjectionExpr::new(a_div_b, "ratio")],
+            input,
+        )?;
+        assert!(exec.common_exprs().is_empty());
+
+        let batches = collect(Arc::new(exec), Arc::new(TaskContext::default())).await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +-------+
+        | ratio |
+        +-------+
+        | 4     |
+        | 7     |
+        | 4     |
+        +-------+
+        ");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`InstrumentedExec`] emits a `tracing` span for the execution of each
+//! partition of an operator
+//!
+//! Requires the `tracing` feature. The spans are ordinary `tracing` spans, so
+//! a subscriber such as `tracing-opentelemetry` exports them to OpenTelemetry
+//! along with the other spans of the embedding service.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::execution_plan::CardinalityEffect;
+use crate::metrics::MetricsSet;
+use crate::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties, RecordBatchStream,
+    SendableRecordBatchStream,
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Result, Statistics};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::{Distribution, PhysicalExpr};
+use datafusion_physical_expr_common::sort_expr::OrderingRequirements;
+use futures::{Stream, StreamExt};
+use tracing::Span;
+
+/// Wraps an [`ExecutionPlan`] so that the stream of each of its partitions
+/// runs within a `tracing` span named `datafusion.execute`.
+///
+/// The span is entered whenever the stream is polled, so the time spent in
+/// the operator (and in the inputs it polls) is attributed to it, and has
+/// the following attributes:
+///
+/// * `operator`: the name of the operator, e.g. `ProjectionExec`
+/// * `partition`: the executed partition
+/// * `session_id`: the id of the session executing the plan
+/// * `query_id`: the [task id] of the [`TaskContext`], if any. Services
+///   that execute plans on behalf of a query set it to the id of the query.
+/// * `output_rows`: the number of rows produced, once the stream completes
+///
+/// [`InstrumentedExec`] is transparent: it reports the name, properties and
+/// display of the wrapped operator, and its children are the (instrumented)
+/// children of the wrapped operator. Use [`instrument_plan`] to instrument
+/// every operator of a plan.
+///
+/// [task id]: TaskContext::task_id
+#[derive(Debug, Clone)]
+pub struct InstrumentedExec {
+    inner: Arc<dyn ExecutionPlan>,
+}
+
+impl InstrumentedExec {
+    /// Create a new [`InstrumentedExec`] tracing the execution of `inner`
+    pub fn new(inner: Arc<dyn ExecutionPlan>) -> Self {
+        Self { inner }
+    }
+
+    /// The instrumented operator
+    pub fn inner(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.inner
+    }
+}
+
+/// Wraps every operator of `plan` in an [`InstrumentedExec`], unless it is
+/// already instrumented
+pub fn instrument_plan(plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_up(|plan| {
+        if plan.is::<InstrumentedExec>() {
+            return Ok(Transformed::no(plan));
+        }
+        Ok(Transformed::yes(Arc::new(InstrumentedExec::new(plan))))
+    })
+    .map(|t| t.data)
+}
+
+impl DisplayAs for InstrumentedExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        self.inner.fmt_as(t, f)
+    }
+}
+
+impl ExecutionPlan for InstrumentedExec {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.inner.schema()
+    }
+
+    fn properties(&self) -> &Arc<PlanProperties> {
+        self.inner.properties()
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        self.inner.required_input_distribution()
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<OrderingRequirements>> {
+        self.inner.required_input_ordering()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        self.inner.maintains_input_order()
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        self.inner.benefits_from_input_partitioning()
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        self.inner.children()
+    }
+
+    fn apply_expressions(
+        &self,
+        f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        self.inner.apply_expressions(f)
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let inner = Arc::clone(&self.inner).with_new_children(children)?;
+        Ok(Arc::new(Self::new(inner)))
+    }
+
+    fn reset_state(self: Arc<Self>) -> Result<Arc<dyn ExecutionPlan>> {
+        let inner = Arc::clone(&self.inner).reset_state()?;
+        Ok(Arc::new(Self::new(inner)))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let span = tracing::info_span!(
+            "datafusion.execute",
+            operator = self.inner.name(),
+            partition,
+            session_id = %context.session_id(),
+            query_id = context.task_id().as_deref(),
+            output_rows = tracing::field::Empty,
+        );
+        let input = span.in_scope(|| self.inner.execute(partition, context))?;
+        Ok(Box::pin(InstrumentedStream {
+            input,
+            span,
+            output_rows: 0,
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.inner.metrics()
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Arc<Statistics>> {
+        self.inner.partition_statistics(partition)
+    }
+
+    fn supports_limit_pushdown(&self) -> bool {
+        self.inner.supports_limit_pushdown()
+    }
+
+    fn fetch(&self) -> Option<usize> {
+        self.inner.fetch()
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        self.inner.cardinality_effect()
+    }
+}
+
+/// The stream of a partition of an [`InstrumentedExec`], polled within its
+/// span
+struct InstrumentedStream {
+    input: SendableRecordBatchStream,
+    span: Span,
+    output_rows: usize,
+}
+
+impl Stream for InstrumentedStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let _entered = this.span.enter();
+        let poll = this.input.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(batch))) => this.output_rows += batch.num_rows(),
+            Poll::Ready(None) => {
+                this.span.record("output_rows", this.output_rows);
+            }
+            _ => {}
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl RecordBatchStream for InstrumentedStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common::collect;
+    use crate::test::scan_partitioned;
+    use crate::{displayable, filter::FilterExec};
+
+    use datafusion_physical_expr::expressions::lit;
+
+    #[tokio::test]
+    async fn instrument_plan_is_transparent() -> Result<()> {
+        let scan = scan_partitioned(1);
+        let plan: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(lit(true), scan)?);
+        let expected = displayable(plan.as_ref()).indent(true).to_string();
+
+        let instrumented = instrument_plan(Arc::clone(&plan))?;
+        assert!(instrumented.is::<InstrumentedExec>());
+        assert!(instrumented.children()[0].is::<InstrumentedExec>());
+        assert_eq!(instrumented.name(), "FilterExec");
+        assert_eq!(
+            displayable(instrumented.as_ref()).indent(true).to_string(),
+            expected
+        );
+
+        // instrumenting twice does not wrap operators again
+        let twice = instrument_plan(Arc::clone(&instrumented))?;
+        let inner = twice.downcast_ref::<InstrumentedExec>().unwrap().inner();
+        assert!(!inner.is::<InstrumentedExec>());
+
+        let task_ctx = Arc::new(TaskContext::default());
+        let expected = collect(plan.execute(0, Arc::clone(&task_ctx))?).await?;
+        let batches = collect(instrumented.execute(0, task_ctx)?).await?;
+        assert_eq!(batches, expected);
+        Ok(())
+    }
+}
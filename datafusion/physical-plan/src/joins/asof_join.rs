@@ -0,0 +1,1053 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ASOF join plan, which matches every left row with the nearest
+//! right row on an ordered column by merging both sorted inputs
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::execution_plan::{CardinalityEffect, EmissionType, boundedness_from_children};
+use crate::joins::JoinOn;
+use crate::joins::utils::{ColumnIndex, JoinFilter, build_join_schema};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::spill::get_record_batch_memory_size;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
+    PlanProperties, SendableRecordBatchStream, check_if_same_properties,
+};
+
+use arrow::array::{Array, ArrayRef, AsArray, RecordBatch, UInt32Array, new_null_array};
+use arrow::buffer::NullBuffer;
+use arrow::compute::{interleave, take};
+use arrow::datatypes::SchemaRef;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::{JoinSide, JoinType, Result, internal_err, plan_err};
+use datafusion_execution::TaskContext;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_expr::Operator;
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::{
+    LexOrdering, OrderingRequirements, PhysicalExpr, PhysicalExprRef, PhysicalSortExpr,
+};
+use datafusion_physical_expr_common::physical_expr::fmt_sql;
+
+use futures::StreamExt;
+
+/// Join execution plan that matches every row of the left input with at most
+/// one row of the right input: among the right rows with equal `on` keys, the
+/// nearest one according to the match condition `left_match <op> right_match`.
+///
+/// For example with `op` being `>=`, each left row is matched with the right
+/// row with the greatest `right_match` that is not greater than its
+/// `left_match`, i.e. the most recent preceding right row when matching on
+/// timestamps. `>` excludes right rows with an equal value, while `<=` and `<`
+/// look for the nearest following right row instead.
+///
+/// An optional tolerance is a predicate on the joined row that the nearest
+/// match must satisfy, such as `left.ts - right.ts <= INTERVAL '5' SECOND`.
+/// Left rows without a satisfying match are emitted with nulls for [`JoinType::Left`]
+/// and dropped for [`JoinType::Inner`]. Rows with a null key or match value
+/// never match.
+///
+/// # Execution
+///
+/// Both inputs must be sorted on the `on` keys followed by the match column,
+/// ascending with nulls first, and partitioned on the `on` keys. Each left
+/// batch is joined by advancing a cursor over the right rows, which are only
+/// buffered until no further left row can match them. The output therefore
+/// preserves the order of the left input and is produced incrementally.
+#[derive(Debug)]
+pub struct AsOfJoinExec {
+    /// Left input, whose rows are all matched
+    left: Arc<dyn ExecutionPlan>,
+    /// Right input, searched for the nearest match
+    right: Arc<dyn ExecutionPlan>,
+    /// Equijoin keys of the match
+    on: JoinOn,
+    /// The (left, right) expressions compared by the match condition
+    match_on: (PhysicalExprRef, PhysicalExprRef),
+    /// The comparison of the match condition: one of `>=`, `>`, `<=` or `<`
+    match_op: Operator,
+    /// Predicate the nearest match must satisfy
+    tolerance: Option<JoinFilter>,
+    /// `Inner` or `Left`
+    join_type: JoinType,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// The ordering required from the left input
+    left_sort_exprs: LexOrdering,
+    /// The ordering required from the right input
+    right_sort_exprs: LexOrdering,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: Arc<PlanProperties>,
+}
+
+impl AsOfJoinExec {
+    /// Tries to create a new [`AsOfJoinExec`].
+    ///
+    /// # Error
+    /// This function errors when the join type is neither `Inner` nor `Left`,
+    /// when `match_op` is not an inequality, or when the compared expressions
+    /// of the two inputs have different types.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        match_on: (PhysicalExprRef, PhysicalExprRef),
+        match_op: Operator,
+        tolerance: Option<JoinFilter>,
+        join_type: JoinType,
+    ) -> Result<Self> {
+        if !matches!(join_type, JoinType::Inner | JoinType::Left) {
+            return plan_err!(
+                "AsOfJoinExec only supports Inner and Left joins, got {join_type}"
+            );
+        }
+        if !matches!(
+            match_op,
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq
+        ) {
+            return plan_err!(
+                "AsOfJoinExec match condition must be one of >, >=, < or <=, got {match_op}"
+            );
+        }
+
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        for (l, r) in on.iter().chain(std::iter::once(&match_on)) {
+            let left_type = l.data_type(&left_schema)?;
+            let right_type = r.data_type(&right_schema)?;
+            if left_type != right_type {
+                return plan_err!(
+                    "AsOfJoinExec requires compared expressions of the same type, got {l}: {left_type} and {r}: {right_type}"
+                );
+            }
+        }
+
+        let sort_exprs = |exprs: Vec<PhysicalExprRef>| {
+            LexOrdering::new(exprs.into_iter().map(PhysicalSortExpr::new_default))
+        };
+        let (left_sort_exprs, right_sort_exprs) = on
+            .iter()
+            .chain(std::iter::once(&match_on))
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .unzip();
+        let (Some(left_sort_exprs), Some(right_sort_exprs)) =
+            (sort_exprs(left_sort_exprs), sort_exprs(right_sort_exprs))
+        else {
+            return internal_err!("AsOfJoinExec requires valid sort expressions");
+        };
+
+        let schema =
+            Arc::new(build_join_schema(&left_schema, &right_schema, &join_type).0);
+        let cache =
+            Self::compute_properties(&left, &right, Arc::clone(&schema), join_type, &on)?;
+
+        Ok(Self {
+            left,
+            right,
+            on,
+            match_on,
+            match_op,
+            tolerance,
+            join_type,
+            schema,
+            left_sort_exprs,
+            right_sort_exprs,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: Arc::new(cache),
+        })
+    }
+
+    /// Left input
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Right input
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Equijoin keys
+    pub fn on(&self) -> &[(PhysicalExprRef, PhysicalExprRef)] {
+        &self.on
+    }
+
+    /// The (left, right) expressions compared by the match condition
+    pub fn match_on(&self) -> &(PhysicalExprRef, PhysicalExprRef) {
+        &self.match_on
+    }
+
+    /// The comparison of the match condition
+    pub fn match_op(&self) -> Operator {
+        self.match_op
+    }
+
+    /// Predicate the nearest match must satisfy
+    pub fn tolerance(&self) -> Option<&JoinFilter> {
+        self.tolerance.as_ref()
+    }
+
+    /// Join type
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        join_type: JoinType,
+        join_on: &JoinOn,
+    ) -> Result<PlanProperties> {
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &join_type,
+            schema,
+            &[true, false],
+            Some(JoinSide::Left),
+            join_on,
+        )?;
+
+        // Every output row is a left row, extended with the columns of its match
+        Ok(PlanProperties::new(
+            eq_properties,
+            left.output_partitioning().clone(),
+            EmissionType::Incremental,
+            boundedness_from_children([left, right]),
+        ))
+    }
+
+    fn with_new_children_and_same_properties(
+        &self,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Self {
+        let left = children.swap_remove(0);
+        let right = children.swap_remove(0);
+        Self {
+            left,
+            right,
+            on: self.on.clone(),
+            match_on: self.match_on.clone(),
+            match_op: self.match_op,
+            tolerance: self.tolerance.clone(),
+            join_type: self.join_type,
+            schema: Arc::clone(&self.schema),
+            left_sort_exprs: self.left_sort_exprs.clone(),
+            right_sort_exprs: self.right_sort_exprs.clone(),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: Arc::clone(&self.cache),
+        }
+    }
+}
+
+impl DisplayAs for AsOfJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| format!("({c1}, {c2})"))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AsOfJoinExec: join_type={:?}, on=[{}], match={} {} {}{}",
+                    self.join_type,
+                    on,
+                    self.match_on.0,
+                    self.match_op,
+                    self.match_on.1,
+                    self.tolerance.as_ref().map_or_else(
+                        || "".to_string(),
+                        |f| format!(", tolerance={}", f.expression())
+                    ),
+                )
+            }
+            DisplayFormatType::TreeRender => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| {
+                        format!("({} = {})", fmt_sql(c1.as_ref()), fmt_sql(c2.as_ref()))
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                if self.join_type != JoinType::Inner {
+                    writeln!(f, "join_type={:?}", self.join_type)?;
+                }
+                if !on.is_empty() {
+                    writeln!(f, "on={on}")?;
+                }
+                writeln!(
+                    f,
+                    "match={} {} {}",
+                    fmt_sql(self.match_on.0.as_ref()),
+                    self.match_op,
+                    fmt_sql(self.match_on.1.as_ref())
+                )?;
+                if let Some(tolerance) = &self.tolerance {
+                    writeln!(
+                        f,
+                        "tolerance={}",
+                        fmt_sql(tolerance.expression().as_ref())
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsOfJoinExec {
+    fn name(&self) -> &'static str {
+        "AsOfJoinExec"
+    }
+
+    fn properties(&self) -> &Arc<PlanProperties> {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        if self.on.is_empty() {
+            return vec![Distribution::SinglePartition, Distribution::SinglePartition];
+        }
+        let (left_expr, right_expr) = self
+            .on
+            .iter()
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .unzip();
+        vec![
+            Distribution::HashPartitioned(left_expr),
+            Distribution::HashPartitioned(right_expr),
+        ]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<OrderingRequirements>> {
+        vec![
+            Some(OrderingRequirements::from(self.left_sort_exprs.clone())),
+            Some(OrderingRequirements::from(self.right_sort_exprs.clone())),
+        ]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn apply_expressions(
+        &self,
+        f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        let mut tnr = TreeNodeRecursion::Continue;
+        for (left, right) in self.on.iter().chain(std::iter::once(&self.match_on)) {
+            tnr = tnr.visit_sibling(|| f(left.as_ref()))?;
+            tnr = tnr.visit_sibling(|| f(right.as_ref()))?;
+        }
+        if let Some(tolerance) = &self.tolerance {
+            tnr = tnr.visit_sibling(|| f(tolerance.expression().as_ref()))?;
+        }
+        Ok(tnr)
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        check_if_same_properties!(self, children);
+        match &children[..] {
+            [left, right] => Ok(Arc::new(AsOfJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                self.on.clone(),
+                self.match_on.clone(),
+                self.match_op,
+                self.tolerance.clone(),
+                self.join_type,
+            )?)),
+            _ => {
+                internal_err!("AsOfJoinExec wrong number of children {}", children.len())
+            }
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left = self.left.execute(partition, Arc::clone(&context))?;
+        let right = self.right.execute(partition, Arc::clone(&context))?;
+
+        let left_schema = self.left.schema();
+        let key_converter = if self.on.is_empty() {
+            None
+        } else {
+            let fields = self
+                .on
+                .iter()
+                .map(|(l, _)| Ok(SortField::new(l.data_type(&left_schema)?)))
+                .collect::<Result<Vec<_>>>()?;
+            Some(RowConverter::new(fields)?)
+        };
+        let match_converter = RowConverter::new(vec![SortField::new(
+            self.match_on.0.data_type(&left_schema)?,
+        )])?;
+        let reservation = MemoryConsumer::new(format!("AsOfJoinStream[{partition}]"))
+            .register(context.memory_pool());
+
+        let (left_on, right_on) = self
+            .on
+            .iter()
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .unzip();
+        let state = AsOfJoinState {
+            left,
+            right,
+            right_exhausted: false,
+            left_exprs: SortExprs {
+                on: left_on,
+                match_on: Arc::clone(&self.match_on.0),
+            },
+            right_exprs: SortExprs {
+                on: right_on,
+                match_on: Arc::clone(&self.match_on.1),
+            },
+            key_converter,
+            match_converter,
+            // `>=` and `<` advance over right rows equal to the left row
+            advance_over_equal: matches!(self.match_op, Operator::GtEq | Operator::Lt),
+            match_preceding: matches!(self.match_op, Operator::GtEq | Operator::Gt),
+            tolerance: self.tolerance.clone(),
+            join_type: self.join_type,
+            schema: Arc::clone(&self.schema),
+            buffered: VecDeque::new(),
+            front_id: 0,
+            cursor: (0, 0),
+            last: None,
+            reservation,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        };
+
+        let stream = futures::stream::try_unfold(state, |mut state| async move {
+            let elapsed_compute = state.baseline_metrics.elapsed_compute().clone();
+            while let Some(left_batch) = state.left.next().await.transpose()? {
+                if left_batch.num_rows() == 0 {
+                    continue;
+                }
+                let timer = elapsed_compute.timer();
+                let left_rows = state.left_exprs.encode(
+                    &left_batch,
+                    state.key_converter.as_ref(),
+                    &state.match_converter,
+                )?;
+                timer.done();
+
+                state.buffer_right(&left_rows).await?;
+
+                let timer = elapsed_compute.timer();
+                let output = state.join_batch(&left_batch, &left_rows)?;
+                state.release_right();
+                timer.done();
+
+                if output.num_rows() > 0 {
+                    state.baseline_metrics.record_output(output.num_rows());
+                    return Ok(Some((output, state)));
+                }
+            }
+            state.baseline_metrics.done();
+            Ok(None)
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        match self.join_type {
+            JoinType::Left => CardinalityEffect::Equal,
+            _ => CardinalityEffect::LowerEqual,
+        }
+    }
+}
+
+/// The expressions one input is sorted on: the join keys followed by the
+/// match expression
+struct SortExprs {
+    on: Vec<PhysicalExprRef>,
+    match_on: PhysicalExprRef,
+}
+
+impl SortExprs {
+    /// Evaluates the expressions on `batch` and converts them to row format
+    fn encode(
+        &self,
+        batch: &RecordBatch,
+        key_converter: Option<&RowConverter>,
+        match_converter: &RowConverter,
+    ) -> Result<SortedRows> {
+        let num_rows = batch.num_rows();
+        let keys = self
+            .on
+            .iter()
+            .map(|expr| expr.evaluate(batch)?.into_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+        let match_values = self.match_on.evaluate(batch)?.into_array(num_rows)?;
+
+        // A row with any null key or match value never matches
+        let nulls = keys.iter().chain(std::iter::once(&match_values)).fold(
+            None,
+            |nulls, array| {
+                NullBuffer::union(nulls.as_ref(), array.logical_nulls().as_ref())
+            },
+        );
+
+        let keys = match key_converter {
+            Some(converter) => Some(converter.convert_columns(&keys)?),
+            None => None,
+        };
+        let values = match_converter.convert_columns(&[match_values])?;
+        Ok(SortedRows {
+            keys,
+            values,
+            nulls,
+        })
+    }
+}
+
+/// The sort columns of a batch in row format
+struct SortedRows {
+    /// The join keys, if any
+    keys: Option<Rows>,
+    /// The match values
+    values: Rows,
+    /// Rows that can not match, having a null key or match value
+    nulls: Option<NullBuffer>,
+}
+
+impl SortedRows {
+    fn num_rows(&self) -> usize {
+        self.values.num_rows()
+    }
+
+    /// Compares row `i` with row `j` of `other` in the sort order of the inputs
+    fn compare(&self, i: usize, other: &SortedRows, j: usize) -> Ordering {
+        let keys = match (&self.keys, &other.keys) {
+            (Some(keys), Some(other_keys)) => keys.row(i).cmp(&other_keys.row(j)),
+            _ => Ordering::Equal,
+        };
+        keys.then_with(|| self.values.row(i).cmp(&other.values.row(j)))
+    }
+
+    /// Returns true if row `i` has the same keys as row `j` of `other`
+    fn keys_eq(&self, i: usize, other: &SortedRows, j: usize) -> bool {
+        match (&self.keys, &other.keys) {
+            (Some(keys), Some(other_keys)) => keys.row(i) == other_keys.row(j),
+            _ => true,
+        }
+    }
+
+    fn is_valid(&self, i: usize) -> bool {
+        self.nulls.as_ref().is_none_or(|nulls| nulls.is_valid(i))
+    }
+
+    fn size(&self) -> usize {
+        self.keys.as_ref().map_or(0, Rows::size) + self.values.size()
+    }
+}
+
+/// A buffered batch of the right input
+struct RightBatch {
+    batch: RecordBatch,
+    rows: SortedRows,
+}
+
+/// Position of a right row: the id of its batch and its index in the batch
+type RightRow = (usize, usize);
+
+struct AsOfJoinState {
+    left: SendableRecordBatchStream,
+    right: SendableRecordBatchStream,
+    right_exhausted: bool,
+    left_exprs: SortExprs,
+    right_exprs: SortExprs,
+    key_converter: Option<RowConverter>,
+    match_converter: RowConverter,
+    /// Whether the cursor advances over right rows equal to the left row
+    advance_over_equal: bool,
+    /// Whether the match is the last right row the cursor advanced over
+    /// (`>=` and `>`), rather than the row at the cursor (`<=` and `<`)
+    match_preceding: bool,
+    tolerance: Option<JoinFilter>,
+    join_type: JoinType,
+    schema: SchemaRef,
+    /// Right batches that may still be matched, in input order
+    buffered: VecDeque<RightBatch>,
+    /// Id of the first batch of `buffered`
+    front_id: usize,
+    /// The next right row to compare with the left rows
+    cursor: RightRow,
+    /// The last right row the cursor advanced over
+    last: Option<RightRow>,
+    reservation: MemoryReservation,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl AsOfJoinState {
+    /// Returns true if the cursor must advance over a right row that
+    /// compares as `ordering` with a left row
+    fn advances(&self, ordering: Ordering) -> bool {
+        match ordering {
+            Ordering::Less => true,
+            Ordering::Equal => self.advance_over_equal,
+            Ordering::Greater => false,
+        }
+    }
+
+    fn right_batch(&self, id: usize) -> Option<&RightBatch> {
+        id.checked_sub(self.front_id)
+            .and_then(|index| self.buffered.get(index))
+    }
+
+    /// Buffers right batches until the cursor can not advance past them for
+    /// any row of the sorted `left_rows`
+    async fn buffer_right(&mut self, left_rows: &SortedRows) -> Result<()> {
+        let last_left = left_rows.num_rows() - 1;
+        while !self.right_exhausted {
+            if let Some(last_right) = self.buffered.back() {
+                let ordering = last_right.rows.compare(
+                    last_right.rows.num_rows() - 1,
+                    left_rows,
+                    last_left,
+                );
+                if !self.advances(ordering) {
+                    break;
+                }
+            }
+            match self.right.next().await.transpose()? {
+                Some(batch) if batch.num_rows() == 0 => {}
+                Some(batch) => {
+                    let timer = self.baseline_metrics.elapsed_compute().timer();
+                    let rows = self.right_exprs.encode(
+                        &batch,
+                        self.key_converter.as_ref(),
+                        &self.match_converter,
+                    )?;
+                    timer.done();
+                    self.reservation
+                        .try_grow(get_record_batch_memory_size(&batch) + rows.size())?;
+                    self.buffered.push_back(RightBatch { batch, rows });
+                }
+                None => self.right_exhausted = true,
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the buffered right batches that can no longer be matched
+    fn release_right(&mut self) {
+        let keep_from = match (self.match_preceding, self.last) {
+            (true, Some((last_id, _))) => last_id.min(self.cursor.0),
+            _ => self.cursor.0,
+        };
+        while self.front_id < keep_from {
+            let Some(right) = self.buffered.pop_front() else {
+                break;
+            };
+            self.reservation
+                .shrink(get_record_batch_memory_size(&right.batch) + right.rows.size());
+            self.front_id += 1;
+        }
+    }
+
+    /// Finds the match of every row of `left_rows`
+    fn find_matches(&mut self, left_rows: &SortedRows) -> Vec<Option<RightRow>> {
+        (0..left_rows.num_rows())
+            .map(|i| {
+                while let Some(right) = self.right_batch(self.cursor.0) {
+                    if self.cursor.1 == right.rows.num_rows() {
+                        self.cursor = (self.cursor.0 + 1, 0);
+                    } else if self.advances(right.rows.compare(
+                        self.cursor.1,
+                        left_rows,
+                        i,
+                    )) {
+                        self.last = Some(self.cursor);
+                        self.cursor.1 += 1;
+                    } else {
+                        break;
+                    }
+                }
+
+                if !left_rows.is_valid(i) {
+                    return None;
+                }
+                let candidate = if self.match_preceding {
+                    self.last
+                } else {
+                    Some(self.cursor)
+                }?;
+                let right = self.right_batch(candidate.0)?;
+                (candidate.1 < right.rows.num_rows()
+                    && right.rows.is_valid(candidate.1)
+                    && right.rows.keys_eq(candidate.1, left_rows, i))
+                .then_some(candidate)
+            })
+            .collect()
+    }
+
+    /// Gathers the right rows `matches` from the buffered batches, and a null
+    /// row for `None`
+    fn take_right(
+        &self,
+        column: usize,
+        matches: &[Option<RightRow>],
+    ) -> Result<ArrayRef> {
+        let null_row = new_null_array(
+            self.buffered
+                .front()
+                .map_or(&arrow::datatypes::DataType::Null, |right| {
+                    right.batch.column(column).data_type()
+                }),
+            1,
+        );
+        let arrays = self
+            .buffered
+            .iter()
+            .map(|right| right.batch.column(column).as_ref())
+            .chain(std::iter::once(null_row.as_ref()))
+            .collect::<Vec<_>>();
+        let indices = matches
+            .iter()
+            .map(|candidate| match candidate {
+                Some((id, row)) => (id - self.front_id, *row),
+                None => (self.buffered.len(), 0),
+            })
+            .collect::<Vec<_>>();
+        Ok(interleave(&arrays, &indices)?)
+    }
+
+    /// Drops the matches that do not satisfy the tolerance
+    fn apply_tolerance(
+        &self,
+        tolerance: &JoinFilter,
+        left_batch: &RecordBatch,
+        matches: &mut [Option<RightRow>],
+    ) -> Result<()> {
+        let (left_indices, right_matches): (Vec<u32>, Vec<_>) = matches
+            .iter()
+            .enumerate()
+            .filter_map(|(i, candidate)| candidate.map(|c| (i as u32, Some(c))))
+            .unzip();
+        if left_indices.is_empty() {
+            return Ok(());
+        }
+        let left_indices = UInt32Array::from(left_indices);
+
+        let columns = tolerance
+            .column_indices()
+            .iter()
+            .map(|ColumnIndex { index, side }| match side {
+                JoinSide::Left => {
+                    Ok(take(left_batch.column(*index), &left_indices, None)?)
+                }
+                JoinSide::Right => self.take_right(*index, &right_matches),
+                JoinSide::None => {
+                    internal_err!("AsOfJoinExec tolerance references an unknown side")
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let intermediate = RecordBatch::try_new(Arc::clone(tolerance.schema()), columns)?;
+        let mask = tolerance
+            .expression()
+            .evaluate(&intermediate)?
+            .into_array(intermediate.num_rows())?;
+        let mask = mask.as_boolean();
+
+        for (i, left_index) in left_indices.values().iter().enumerate() {
+            if !mask.is_valid(i) || !mask.value(i) {
+                matches[*left_index as usize] = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Joins a batch of the left input with the buffered right batches
+    fn join_batch(
+        &mut self,
+        left_batch: &RecordBatch,
+        left_rows: &SortedRows,
+    ) -> Result<RecordBatch> {
+        let mut matches = self.find_matches(left_rows);
+        if let Some(tolerance) = &self.tolerance {
+            self.apply_tolerance(tolerance, left_batch, &mut matches)?;
+        }
+
+        let (left_columns, matches) = match self.join_type {
+            JoinType::Inner => {
+                let (left_indices, matches): (Vec<u32>, Vec<_>) = matches
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, candidate)| candidate.is_some())
+                    .map(|(i, candidate)| (i as u32, candidate))
+                    .unzip();
+                let left_indices = UInt32Array::from(left_indices);
+                let left_columns = left_batch
+                    .columns()
+                    .iter()
+                    .map(|column| Ok(take(column, &left_indices, None)?))
+                    .collect::<Result<Vec<_>>>()?;
+                (left_columns, matches)
+            }
+            _ => (left_batch.columns().to_vec(), matches),
+        };
+
+        let right_columns = (0..self.right.schema().fields().len())
+            .map(|column| self.take_right(column, &matches))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RecordBatch::try_new(
+            Arc::clone(&self.schema),
+            left_columns.into_iter().chain(right_columns).collect(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::test::TestMemoryExec;
+    use crate::test::build_table_i32;
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::test_util::batches_to_string;
+    use datafusion_physical_expr::expressions::{BinaryExpr, Column, col, lit};
+    use insta::{allow_duplicates, assert_snapshot};
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+        batch_size: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        let batches = (0..batch.num_rows())
+            .step_by(batch_size)
+            .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+            .collect::<Vec<_>>();
+        TestMemoryExec::try_new_exec(&[batches], schema, None).unwrap()
+    }
+
+    /// Trades of symbol `a1` at time `b1`
+    fn trades(batch_size: usize) -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("a1", &vec![1, 1, 1, 2, 2, 3]),
+            ("b1", &vec![1, 5, 10, 2, 7, 4]),
+            ("c1", &vec![100, 101, 102, 103, 104, 105]),
+            batch_size,
+        )
+    }
+
+    /// Quotes of symbol `a2` at time `b2`
+    fn quotes(batch_size: usize) -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("a2", &vec![1, 1, 1, 2, 2, 4]),
+            ("b2", &vec![0, 5, 8, 3, 7, 1]),
+            ("c2", &vec![10, 11, 12, 13, 14, 15]),
+            batch_size,
+        )
+    }
+
+    fn asof_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        op: Operator,
+        tolerance: Option<JoinFilter>,
+        join_type: JoinType,
+    ) -> Result<AsOfJoinExec> {
+        let on = vec![(col("a1", &left.schema())?, col("a2", &right.schema())?)];
+        let match_on = (col("b1", &left.schema())?, col("b2", &right.schema())?);
+        AsOfJoinExec::try_new(left, right, on, match_on, op, tolerance, join_type)
+    }
+
+    async fn join_collect(join: AsOfJoinExec) -> Result<Vec<RecordBatch>> {
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        common::collect(stream).await
+    }
+
+    #[tokio::test]
+    async fn asof_join_preceding() -> Result<()> {
+        for batch_size in [1, 2, 6] {
+            let join = asof_join(
+                trades(batch_size),
+                quotes(batch_size),
+                Operator::GtEq,
+                None,
+                JoinType::Left,
+            )?;
+            let batches = join_collect(join).await?;
+            allow_duplicates! {
+                assert_snapshot!(batches_to_string(&batches), @r"
+                +----+----+-----+----+----+----+
+                | a1 | b1 | c1  | a2 | b2 | c2 |
+                +----+----+-----+----+----+----+
+                | 1  | 1  | 100 | 1  | 0  | 10 |
+                | 1  | 5  | 101 | 1  | 5  | 11 |
+                | 1  | 10 | 102 | 1  | 8  | 12 |
+                | 2  | 2  | 103 |    |    |    |
+                | 2  | 7  | 104 | 2  | 7  | 14 |
+                | 3  | 4  | 105 |    |    |    |
+                +----+----+-----+----+----+----+
+                ");
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_strictly_preceding() -> Result<()> {
+        let join = asof_join(trades(2), quotes(2), Operator::Gt, None, JoinType::Inner)?;
+        let batches = join_collect(join).await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+----+-----+----+----+----+
+        | a1 | b1 | c1  | a2 | b2 | c2 |
+        +----+----+-----+----+----+----+
+        | 1  | 1  | 100 | 1  | 0  | 10 |
+        | 1  | 5  | 101 | 1  | 0  | 10 |
+        | 1  | 10 | 102 | 1  | 8  | 12 |
+        | 2  | 7  | 104 | 2  | 3  | 13 |
+        +----+----+-----+----+----+----+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_following() -> Result<()> {
+        for (op, batch_size) in [(Operator::LtEq, 1), (Operator::LtEq, 4)] {
+            let join = asof_join(
+                trades(batch_size),
+                quotes(batch_size),
+                op,
+                None,
+                JoinType::Left,
+            )?;
+            let batches = join_collect(join).await?;
+            allow_duplicates! {
+                assert_snapshot!(batches_to_string(&batches), @r"
+                +----+----+-----+----+----+----+
+                | a1 | b1 | c1  | a2 | b2 | c2 |
+                +----+----+-----+----+----+----+
+                | 1  | 1  | 100 | 1  | 5  | 11 |
+                | 1  | 5  | 101 | 1  | 5  | 11 |
+                | 1  | 10 | 102 |    |    |    |
+                | 2  | 2  | 103 | 2  | 3  | 13 |
+                | 2  | 7  | 104 | 2  | 7  | 14 |
+                | 3  | 4  | 105 |    |    |    |
+                +----+----+-----+----+----+----+
+                ");
+            }
+        }
+
+        let join = asof_join(trades(3), quotes(3), Operator::Lt, None, JoinType::Inner)?;
+        let batches = join_collect(join).await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+----+-----+----+----+----+
+        | a1 | b1 | c1  | a2 | b2 | c2 |
+        +----+----+-----+----+----+----+
+        | 1  | 1  | 100 | 1  | 5  | 11 |
+        | 1  | 5  | 101 | 1  | 8  | 12 |
+        | 2  | 2  | 103 | 2  | 3  | 13 |
+        +----+----+-----+----+----+----+
+        ");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_tolerance() -> Result<()> {
+        let left = trades(2);
+        let right = quotes(2);
+
+        // b1 - b2 <= 1
+        let filter_schema = Arc::new(Schema::new(vec![
+            Field::new("b1", DataType::Int32, false),
+            Field::new("b2", DataType::Int32, true),
+        ]));
+        let expression = Arc::new(BinaryExpr::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("b1", 0)),
+                Operator::Minus,
+                Arc::new(Column::new("b2", 1)),
+            )),
+            Operator::LtEq,
+            lit(1),
+        ));
+        let tolerance = JoinFilter::new(
+            expression,
+            JoinFilter::build_column_indices(vec![1], vec![1]),
+            filter_schema,
+        );
+
+        let join =
+            asof_join(left, right, Operator::GtEq, Some(tolerance), JoinType::Left)?;
+        let batches = join_collect(join).await?;
+        assert_snapshot!(batches_to_string(&batches), @r"
+        +----+----+-----+----+----+----+
+        | a1 | b1 | c1  | a2 | b2 | c2 |
+        +----+----+-----+----+----+----+
+        | 1  | 1  | 100 | 1  | 0  | 10 |
+        | 1  | 5  | 101 | 1  | 5  | 11 |
+        | 1  | 10 | 102 |    |    |    |
+        | 2  | 2  | 103 |    |    |    |
+        | 2  | 7  | 104 | 2  | 7  | 14 |
+        | 3  | 4  | 105 |    |    |    |
+        +----+----+-----+----+----+----+
+        ");
+        Ok(())
+    }
+
+    #[test]
+    fn asof_join_rejects_unsupported_join_types() -> Result<()> {
+        let err = asof_join(trades(1), quotes(1), Operator::GtEq, None, JoinType::Full)
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: AsOfJoinExec only supports Inner and Left joins, got Full"
+        );
+        Ok(())
+    }
+}
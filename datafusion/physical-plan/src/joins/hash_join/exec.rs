@@ -37,6 +37,10 @@ use crate::joins::hash_join::inlist_builder::build_struct_inlist_values;
 use crate::joins::hash_join::shared_bounds::{
     ColumnBounds, PartitionBounds, PushdownStrategy, SharedBuildAccumulator,
 };
+use crate::joins::hash_join::spill::{
+    CancelBuildReportGuard, SPILLED_PARTITIONS_METRIC_NAME, SpillPartitioner,
+    SpilledHashJoin,
+};
 use crate::joins::hash_join::stream::{
     BuildSide, BuildSideInitialState, HashJoinStream, HashJoinStreamState,
 };
@@ -46,13 +50,15 @@ use crate::joins::utils::{
     swap_join_projection, update_hash,
 };
 use crate::joins::{JoinOn, JoinOnRef, PartitionMode, SharedBitmapBuilder};
-use crate::metrics::{Count, MetricBuilder, MetricCategory};
+use crate::metrics::{Count, MetricBuilder, MetricCategory, SpillMetrics};
 use crate::projection::{
     EmbeddedProjection, JoinData, ProjectionExec, try_embed_projection,
     try_pushdown_through_join,
 };
 use crate::repartition::REPARTITION_RANDOM_STATE;
 use crate::spill::get_record_batch_memory_size;
+use crate::spill::spill_manager::SpillManager;
+use crate::stream::{EmptyRecordBatchStream, RecordBatchStreamAdapter};
 use crate::{
     DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, Partitioning,
     PlanProperties, SendableRecordBatchStream, Statistics,
@@ -75,8 +81,8 @@ use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::TreeNodeRecursion;
 use datafusion_common::utils::memory::estimate_memory_size;
 use datafusion_common::{
-    JoinSide, JoinType, NullEquality, Result, assert_or_internal_err, internal_err,
-    plan_err, project_schema,
+    DataFusionError, JoinSide, JoinType, NullEquality, Result, assert_or_internal_err,
+    internal_err, plan_err, project_schema,
 };
 use datafusion_execution::TaskContext;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
@@ -89,13 +95,13 @@ use datafusion_physical_expr::expressions::{Column, DynamicFilterPhysicalExpr, l
 use datafusion_physical_expr::projection::{ProjectionRef, combine_projections};
 use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
 
-use datafusion_common::hash_utils::RandomState;
+use datafusion_common::hash_utils::{RandomState, create_hashes};
 use datafusion_physical_expr_common::physical_expr::fmt_sql;
 use datafusion_physical_expr_common::utils::evaluate_expressions_to_arrays;
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 use parking_lot::Mutex;
 
-use super::partitioned_hash_eval::SeededRandomState;
+use super::partitioned_hash_eval::{JoinKeyBloomFilter, SeededRandomState};
 
 /// Hard-coded seed to ensure hash values from the hash join differ from `RepartitionExec`, avoiding collisions.
 pub(crate) const HASH_JOIN_SEED: SeededRandomState =
@@ -103,6 +109,8 @@ pub(crate) const HASH_JOIN_SEED: SeededRandomState =
 
 const ARRAY_MAP_CREATED_COUNT_METRIC_NAME: &str = "array_map_created_count";
 
+const BUILD_SIDE_SWITCHES_METRIC_NAME: &str = "build_side_switches";
+
 #[expect(clippy::too_many_arguments)]
 fn try_create_array_map(
     bounds: &Option<PartitionBounds>,
@@ -285,6 +293,7 @@ impl HashJoinExecBuilder {
                 filter: None,
                 join_type,
                 left_fut: Default::default(),
+                shared_build_side: false,
                 random_state: HASH_JOIN_SEED,
                 mode: PartitionMode::Auto,
                 fetch: None,
@@ -361,6 +370,18 @@ impl HashJoinExecBuilder {
         self
     }
 
+    /// Share the hash table built from the left input with `other`, so that
+    /// it is built only once, by whichever join is executed first.
+    ///
+    /// Both joins must have the same left input and join keys and must
+    /// support sharing, see [`HashJoinExec::can_share_build_side`]. Passing
+    /// the join the builder was created from marks its table as shared.
+    pub fn with_shared_build_side(mut self, other: &HashJoinExec) -> Self {
+        self.exec.left_fut = Arc::clone(&other.left_fut);
+        self.exec.shared_build_side = true;
+        self
+    }
+
     /// Require to recompute plan properties.
     pub fn recompute_properties(mut self) -> Self {
         self.preserve_properties = false;
@@ -377,6 +398,11 @@ impl HashJoinExecBuilder {
             "wrong number of children passed into `HashJoinExecBuilder`"
         );
         self.preserve_properties &= has_same_children_properties(&self.exec, &children)?;
+        // a hash table built from another input can no longer be shared
+        if self.exec.shared_build_side && !Arc::ptr_eq(&self.exec.left, &children[0]) {
+            self.exec.left_fut = Default::default();
+            self.exec.shared_build_side = false;
+        }
         self.exec.right = children.swap_remove(1);
         self.exec.left = children.swap_remove(0);
         Ok(self)
@@ -385,6 +411,7 @@ impl HashJoinExecBuilder {
     /// Reset runtime state.
     pub fn reset_state(mut self) -> Self {
         self.exec.left_fut = Default::default();
+        self.exec.shared_build_side = false;
         self.exec.dynamic_filter = None;
         self.exec.metrics = ExecutionPlanMetricsSet::new();
         self
@@ -430,6 +457,7 @@ impl HashJoinExecBuilder {
             filter,
             join_type,
             left_fut,
+            shared_build_side,
             random_state,
             mode,
             metrics,
@@ -477,6 +505,7 @@ impl HashJoinExecBuilder {
             join_type,
             join_schema,
             left_fut,
+            shared_build_side,
             random_state,
             mode,
             metrics,
@@ -507,6 +536,7 @@ impl From<&HashJoinExec> for HashJoinExecBuilder {
                 join_type: exec.join_type,
                 join_schema: Arc::clone(&exec.join_schema),
                 left_fut: Arc::clone(&exec.left_fut),
+                shared_build_side: exec.shared_build_side,
                 random_state: exec.random_state.clone(),
                 mode: exec.mode,
                 metrics: exec.metrics.clone(),
@@ -710,6 +740,46 @@ impl From<&HashJoinExec> for HashJoinExecBuilder {
 ///                       └───────────────┘     └───────────────┘
 /// ```
 ///
+/// # Adaptive Build Side
+///
+/// The build side is chosen by the optimizer based on statistics, which are
+/// often missing or wrong (e.g. for ad-hoc Parquet directories). When
+/// `datafusion.execution.enable_hash_join_adaptive_build_side` is set, each
+/// partition of a [`PartitionMode::Partitioned`] join first buffers its build
+/// side. If it has more rows than estimated (by more than
+/// `datafusion.execution.hash_join_adaptive_build_side_ratio`), up to as many
+/// rows of the probe side are buffered as well. If the probe side of the
+/// partition ends before that, the hash table is built on the probe side
+/// instead, and the buffered build side is used to probe it.
+///
+/// The output of the partition is the same, except for its order, so this is
+/// only done when the output ordering of the join is not required.
+///
+/// # Spilling
+///
+/// If the build side of a partition of a [`PartitionMode::Partitioned`] join
+/// does not fit in memory, and temporary files can be created, the rows of
+/// both inputs of the partition are split into spill partitions by the hash
+/// of their join keys and written to disk. The spill partitions are then
+/// joined one after the other, each with a hash table of its part of the
+/// build side (a "Grace" hash join). A spill partition whose build side still
+/// does not fit in memory is split again, up to a fixed number of times.
+///
+/// The build side of a spilled partition does not contribute to the dynamic
+/// filter pushed down to the probe side. [`PartitionMode::CollectLeft`] joins,
+/// whose build side is shared by all partitions, and joins whose output must
+/// keep the order of the probe side are never spilled. The `spill_count`,
+/// `spilled_bytes`, `spilled_rows` and `spilled_partitions` metrics are
+/// reported once a partition spills.
+///
+/// # Sharing the build side
+///
+/// Several [`PartitionMode::CollectLeft`] joins over the same build side, such
+/// as a dimension table joined twice, can share a single hash table, see
+/// [`HashJoinExecBuilder::with_shared_build_side`]. The left input of the
+/// join that is executed first is then used to build the table, and the
+/// left inputs of the other joins are not executed at all.
+///
 /// # Clone / Shared State
 ///
 /// Note this structure includes a [`OnceAsync`] that is used to coordinate the
@@ -736,6 +806,9 @@ pub struct HashJoinExec {
     /// Each output stream waits on the `OnceAsync` to signal the completion of
     /// the hash table creation.
     left_fut: Arc<OnceAsync<JoinLeftData>>,
+    /// Whether `left_fut` is shared with other joins over the same left input,
+    /// see [`HashJoinExecBuilder::with_shared_build_side`]
+    shared_build_side: bool,
     /// Shared the `SeededRandomState` for the hashing algorithm (seeds preserved for serialization)
     random_state: SeededRandomState,
     /// Partitioning mode to use
@@ -779,6 +852,7 @@ impl fmt::Debug for HashJoinExec {
             .field("join_type", &self.join_type)
             .field("join_schema", &self.join_schema)
             .field("left_fut", &self.left_fut)
+            .field("shared_build_side", &self.shared_build_side)
             .field("random_state", &self.random_state)
             .field("mode", &self.mode)
             .field("metrics", &self.metrics)
@@ -861,6 +935,388 @@ impl HashJoinExec {
         true
     }
 
+    /// Whether the build side of a partition may be switched at runtime, see
+    /// "Adaptive Build Side" above
+    fn allow_adaptive_build_side(&self, config: &ConfigOptions) -> bool {
+        config.execution.enable_hash_join_adaptive_build_side
+            && self.mode == PartitionMode::Partitioned
+            && !self.null_aware
+            && !matches!(self.join_type, JoinType::LeftMark | JoinType::RightMark)
+            // switching the build side changes the order of the output
+            && self.properties().output_ordering().is_none()
+    }
+
+    /// Whether the build side of a partition may be spilled to disk, see
+    /// "Spilling" above
+    fn allow_spilling(&self, context: &TaskContext) -> bool {
+        context.runtime_env().disk_manager.tmp_files_enabled()
+            && self.mode == PartitionMode::Partitioned
+            && !self.null_aware
+            // the spill partitions are joined one after the other
+            && self.properties().output_ordering().is_none()
+    }
+
+    /// Returns the output columns, after the projection
+    fn column_indices_after_projection(&self) -> Vec<ColumnIndex> {
+        match self.projection.as_ref() {
+            Some(projection) => projection
+                .iter()
+                .map(|i| self.column_indices[*i].clone())
+                .collect(),
+            None => self.column_indices.clone(),
+        }
+    }
+
+    /// Executes `partition`, building the hash table on the smaller of the
+    /// two inputs if the left input is larger than estimated.
+    ///
+    /// See "Adaptive Build Side" above.
+    fn execute_adaptive_build_side(
+        &self,
+        partition: usize,
+        context: &Arc<TaskContext>,
+        join_metrics: BuildProbeJoinMetrics,
+        array_map_created_count: Count,
+    ) -> Result<SendableRecordBatchStream> {
+        let config = Arc::clone(context.session_config().options());
+        // Without an estimate, the build side is switched whenever the right
+        // input is smaller
+        let max_expected_rows = self
+            .left
+            .partition_statistics(Some(partition))?
+            .num_rows
+            .get_value()
+            .map_or(0, |rows| {
+                (*rows as f64 * config.execution.hash_join_adaptive_build_side_ratio)
+                    as usize
+            });
+
+        let mut left_stream = self.left.execute(partition, Arc::clone(context))?;
+        let mut right_stream = self.right.execute(partition, Arc::clone(context))?;
+        let left_schema = self.left.schema();
+        let right_schema = self.right.schema();
+
+        // Tracks the batches buffered before the build side is chosen
+        let buffer_reservation =
+            MemoryConsumer::new(format!("HashJoinAdaptiveInput[{partition}]"))
+                .register(context.memory_pool());
+        let build_reservation =
+            MemoryConsumer::new(format!("HashJoinInput[{partition}]"))
+                .register(context.memory_pool());
+        let build_side_switches = MetricBuilder::new(&self.metrics)
+            .counter(BUILD_SIDE_SWITCHES_METRIC_NAME, partition);
+
+        let schema = self.schema();
+        let on = self.on.clone();
+        let filter = self.filter.clone();
+        let join_type = self.join_type;
+        let random_state = self.random_state.random_state().clone();
+        let column_indices = self.column_indices_after_projection();
+        let null_equality = self.null_equality;
+        let batch_size = context.session_config().batch_size();
+        let fetch = self.fetch;
+
+        let stream = futures::stream::once(async move {
+            let mut left_batches: Vec<RecordBatch> = vec![];
+            let mut left_rows = 0;
+            while let Some(batch) = left_stream.next().await.transpose()? {
+                buffer_reservation.try_grow(get_record_batch_memory_size(&batch))?;
+                left_rows += batch.num_rows();
+                left_batches.push(batch);
+            }
+
+            // Only read ahead on the right input if the left input is larger
+            // than expected, and no more than the size of the left input
+            let mut right_batches = vec![];
+            let mut right_rows = 0;
+            let mut right_exhausted = false;
+            if left_rows > max_expected_rows {
+                while right_rows < left_rows {
+                    let Some(batch) = right_stream.next().await.transpose()? else {
+                        right_exhausted = true;
+                        break;
+                    };
+                    buffer_reservation.try_grow(get_record_batch_memory_size(&batch))?;
+                    right_rows += batch.num_rows();
+                    right_batches.push(batch);
+                }
+            }
+
+            let switch_build_side = right_exhausted && right_rows < left_rows;
+            let (build_schema, build_batches, probe_schema, probe_batches, probe_stream) =
+                if switch_build_side {
+                    build_side_switches.add(1);
+                    // the right input was read to the end
+                    let probe_stream: SendableRecordBatchStream =
+                        Box::pin(EmptyRecordBatchStream::new(Arc::clone(&left_schema)));
+                    (
+                        right_schema,
+                        right_batches,
+                        left_schema,
+                        left_batches,
+                        probe_stream,
+                    )
+                } else {
+                    (
+                        left_schema,
+                        left_batches,
+                        right_schema,
+                        right_batches,
+                        right_stream,
+                    )
+                };
+            let (on_build, on_probe) = if switch_build_side {
+                on.into_iter().map(|(l, r)| (r, l)).unzip()
+            } else {
+                on.into_iter().unzip()
+            };
+            let (join_type, filter, column_indices) = if switch_build_side {
+                let column_indices = column_indices
+                    .into_iter()
+                    .map(|ColumnIndex { index, side }| ColumnIndex {
+                        index,
+                        side: side.negate(),
+                    })
+                    .collect();
+                (join_type.swap(), filter.map(|f| f.swap()), column_indices)
+            } else {
+                (join_type, filter, column_indices)
+            };
+
+            // The buffered build batches are tracked by the build side from
+            // now on, and the buffered probe batches until they are probed
+            buffer_reservation.shrink(
+                build_batches
+                    .iter()
+                    .map(get_record_batch_memory_size)
+                    .sum::<usize>(),
+            );
+            let build_stream = Box::pin(RecordBatchStreamAdapter::new(
+                build_schema,
+                futures::stream::iter(build_batches.into_iter().map(Ok)),
+            ));
+            let probe_stream = Box::pin(RecordBatchStreamAdapter::new(
+                probe_schema,
+                futures::stream::iter(probe_batches)
+                    .map(move |batch| {
+                        buffer_reservation.shrink(get_record_batch_memory_size(&batch));
+                        Ok(batch)
+                    })
+                    .chain(probe_stream),
+            ));
+
+            let left_fut = OnceFut::new(collect_left_input(
+                random_state.clone(),
+                build_stream,
+                on_build,
+                join_metrics.clone(),
+                build_reservation,
+                need_produce_result_in_final(join_type),
+                1,
+                false,
+                config,
+                null_equality,
+                array_map_created_count,
+            ));
+
+            Ok::<_, DataFusionError>(HashJoinStream::new(
+                partition,
+                Arc::clone(&schema),
+                on_probe,
+                filter,
+                join_type,
+                probe_stream,
+                random_state,
+                join_metrics,
+                column_indices,
+                null_equality,
+                HashJoinStreamState::WaitBuildSide,
+                BuildSide::Initial(BuildSideInitialState { left_fut }),
+                batch_size,
+                vec![],
+                false,
+                None,
+                PartitionMode::Partitioned,
+                false,
+                fetch,
+            ))
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    /// Executes `partition`, spilling both inputs to disk if its build side
+    /// does not fit in memory.
+    ///
+    /// See "Spilling" above.
+    fn execute_spillable(
+        &self,
+        partition: usize,
+        context: &Arc<TaskContext>,
+        join_metrics: BuildProbeJoinMetrics,
+        array_map_created_count: Count,
+        build_accumulator: Option<Arc<SharedBuildAccumulator>>,
+        enable_dynamic_filter_pushdown: bool,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut left_stream = self.left.execute(partition, Arc::clone(context))?;
+        let right_stream = self.right.execute(partition, Arc::clone(context))?;
+        let left_schema = self.left.schema();
+        let right_schema = self.right.schema();
+
+        let reservation = MemoryConsumer::new(format!("HashJoinInput[{partition}]"))
+            .register(context.memory_pool());
+        let report_guard = CancelBuildReportGuard::new(build_accumulator, partition);
+
+        let (on_left, on_right): (Vec<_>, Vec<_>) = self.on.iter().cloned().unzip();
+        let schema = self.schema();
+        let filter = self.filter.clone();
+        let join_type = self.join_type;
+        let random_state = self.random_state.random_state().clone();
+        let column_indices = self.column_indices_after_projection();
+        let null_equality = self.null_equality;
+        let batch_size = context.session_config().batch_size();
+        let config = Arc::clone(context.session_config().options());
+        let spill_compression = context.session_config().spill_compression();
+        let runtime_env = context.runtime_env();
+        let resource_tracker = context.resource_tracker().cloned();
+        let memory_pool = Arc::clone(context.memory_pool());
+        let right_side_ordered = self.right.output_ordering().is_some();
+        let fetch = self.fetch;
+        let metrics = self.metrics.clone();
+
+        let stream = futures::stream::once(async move {
+            // Buffer the build side until it turns out not to fit in memory
+            let build_timer = join_metrics.build_time.timer();
+            let mut batches = vec![];
+            let mut fits = true;
+            while let Some(batch) = left_stream.next().await.transpose()? {
+                fits = reservation
+                    .try_grow(get_record_batch_memory_size(&batch))
+                    .is_ok();
+                batches.push(batch);
+                if !fits {
+                    break;
+                }
+            }
+            if fits {
+                let num_rows = batches.iter().map(|batch| batch.num_rows()).sum();
+                fits = reservation
+                    .try_grow(estimate_hash_table_size(num_rows)?)
+                    .is_ok();
+            }
+            build_timer.done();
+            // The memory is reserved again while building the hash table
+            reservation.free();
+
+            if fits {
+                let build_stream = Box::pin(RecordBatchStreamAdapter::new(
+                    Arc::clone(&left_schema),
+                    futures::stream::iter(batches.into_iter().map(Ok)),
+                ));
+                let left_fut = OnceFut::new(collect_left_input(
+                    random_state.clone(),
+                    build_stream,
+                    on_left,
+                    join_metrics.clone(),
+                    reservation,
+                    need_produce_result_in_final(join_type),
+                    1,
+                    enable_dynamic_filter_pushdown,
+                    config,
+                    null_equality,
+                    array_map_created_count,
+                ));
+                let stream: SendableRecordBatchStream = Box::pin(HashJoinStream::new(
+                    partition,
+                    schema,
+                    on_right,
+                    filter,
+                    join_type,
+                    right_stream,
+                    random_state,
+                    join_metrics,
+                    column_indices,
+                    null_equality,
+                    HashJoinStreamState::WaitBuildSide,
+                    BuildSide::Initial(BuildSideInitialState { left_fut }),
+                    batch_size,
+                    vec![],
+                    right_side_ordered,
+                    report_guard.disarm(),
+                    PartitionMode::Partitioned,
+                    false,
+                    fetch,
+                ));
+                return Ok(stream);
+            }
+
+            drop(report_guard);
+            let spilled_partitions = MetricBuilder::new(&metrics)
+                .counter(SPILLED_PARTITIONS_METRIC_NAME, partition);
+            spilled_partitions.add(1);
+            let spill_metrics = SpillMetrics::new(&metrics, partition);
+            let left_spill_manager = Arc::new(
+                SpillManager::new(
+                    Arc::clone(&runtime_env),
+                    spill_metrics.clone(),
+                    left_schema,
+                )
+                .with_compression_type(spill_compression)
+                .with_resource_tracker(resource_tracker.clone()),
+            );
+            let right_spill_manager = Arc::new(
+                SpillManager::new(runtime_env, spill_metrics, right_schema)
+                    .with_compression_type(spill_compression)
+                    .with_resource_tracker(resource_tracker),
+            );
+
+            let mut partitioner = SpillPartitioner::new(
+                on_left.clone(),
+                0,
+                Arc::clone(&left_spill_manager),
+            );
+            for batch in batches {
+                partitioner.append(&batch)?;
+            }
+            while let Some(batch) = left_stream.next().await.transpose()? {
+                partitioner.append(&batch)?;
+            }
+            let left_files = partitioner.finish()?;
+
+            let join = SpilledHashJoin {
+                partition,
+                schema,
+                on_left,
+                on_right,
+                filter,
+                join_type,
+                random_state,
+                column_indices,
+                null_equality,
+                batch_size,
+                fetch,
+                config,
+                memory_pool,
+                join_metrics,
+                array_map_created_count,
+                spilled_partitions,
+                left_spill_manager,
+                right_spill_manager,
+            };
+            Ok::<_, DataFusionError>(join.execute(left_files, right_stream))
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
     /// left (build) side which gets hashed
     pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
         &self.left
@@ -902,6 +1358,24 @@ impl HashJoinExec {
         self.null_equality
     }
 
+    /// Returns true if the hash table of this join may be shared with other
+    /// joins over the same left input, see
+    /// [`HashJoinExecBuilder::with_shared_build_side`].
+    ///
+    /// Only [`PartitionMode::CollectLeft`] joins build a single table, and
+    /// only joins that do not track which build rows were matched leave the
+    /// table unchanged while probing it.
+    pub fn can_share_build_side(&self) -> bool {
+        self.mode == PartitionMode::CollectLeft
+            && !self.null_aware
+            && !need_produce_result_in_final(self.join_type)
+    }
+
+    /// Returns true if the hash table of this join is shared with other joins
+    pub fn shares_build_side(&self) -> bool {
+        self.shared_build_side
+    }
+
     /// Get the dynamic filter expression for testing purposes.
     /// Returns `None` if no dynamic filter has been set.
     ///
@@ -1343,6 +1817,28 @@ impl ExecutionPlan for HashJoinExec {
             .flatten()
             .flatten();
 
+        if build_accumulator.is_none()
+            && self.allow_adaptive_build_side(context.session_config().options())
+        {
+            return self.execute_adaptive_build_side(
+                partition,
+                &context,
+                join_metrics,
+                array_map_created_count,
+            );
+        }
+
+        if self.allow_spilling(&context) {
+            return self.execute_spillable(
+                partition,
+                &context,
+                join_metrics,
+                array_map_created_count,
+                build_accumulator,
+                enable_dynamic_filter_pushdown,
+            );
+        }
+
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.try_once(|| {
                 let left_stream = self.left.execute(0, Arc::clone(&context))?;
@@ -1358,7 +1854,8 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     self.right().output_partitioning().partition_count(),
-                    enable_dynamic_filter_pushdown,
+                    // other joins sharing the table may push down dynamic filters
+                    enable_dynamic_filter_pushdown || self.shared_build_side,
                     Arc::clone(context.session_config().options()),
                     self.null_equality,
                     array_map_created_count,
@@ -1399,13 +1896,7 @@ impl ExecutionPlan for HashJoinExec {
         let right_stream = self.right.execute(partition, context)?;
 
         // update column indices to reflect the projection
-        let column_indices_after_projection = match self.projection.as_ref() {
-            Some(projection) => projection
-                .iter()
-                .map(|i| self.column_indices[*i].clone())
-                .collect(),
-            None => self.column_indices.clone(),
-        };
+        let column_indices_after_projection = self.column_indices_after_projection();
 
         let on_right = self
             .on
@@ -1854,6 +2345,17 @@ fn should_collect_min_max_for_perfect_hash(
     Ok(ArrayMap::is_supported_type(&data_type))
 }
 
+/// Estimation of memory size, required for the hash table of `num_rows` rows,
+/// prior to allocation. Final result can be verified using
+/// `RawTable.allocation_info()`
+fn estimate_hash_table_size(num_rows: usize) -> Result<usize> {
+    if num_rows > u32::MAX as usize {
+        estimate_memory_size::<(u64, u64)>(num_rows, size_of::<JoinHashMapU64>())
+    } else {
+        estimate_memory_size::<(u32, u64)>(num_rows, size_of::<JoinHashMapU32>())
+    }
+}
+
 /// Collects all batches from the left (build) side stream and creates a hash map for joining.
 ///
 /// This function is responsible for:
@@ -1883,7 +2385,7 @@ fn should_collect_min_max_for_perfect_hash(
 /// `JoinLeftData` containing the hash map, consolidated batch, join key values,
 /// visited indices bitmap, and computed bounds (if requested).
 #[expect(clippy::too_many_arguments)]
-async fn collect_left_input(
+pub(super) async fn collect_left_input(
     random_state: RandomState,
     left_stream: SendableRecordBatchStream,
     on_left: Vec<PhysicalExprRef>,
@@ -1971,25 +2473,16 @@ async fn collect_left_input(
 
             (Map::ArrayMap(array_map), batch, left_value)
         } else {
-            // Estimation of memory size, required for hashtable, prior to allocation.
-            // Final result can be verified using `RawTable.allocation_info()`
-            let fixed_size_u32 = size_of::<JoinHashMapU32>();
-            let fixed_size_u64 = size_of::<JoinHashMapU64>();
+            let estimated_hashtable_size = estimate_hash_table_size(num_rows)?;
+            reservation.try_grow(estimated_hashtable_size)?;
+            metrics.build_mem_used.add(estimated_hashtable_size);
 
             // Use `u32` indices for the JoinHashMap when num_rows ≤ u32::MAX, otherwise use the
             // `u64` indice variant
             // Arc is used instead of Box to allow sharing with SharedBuildAccumulator for hash map pushdown
             let mut hashmap: Box<dyn JoinHashMapType> = if num_rows > u32::MAX as usize {
-                let estimated_hashtable_size =
-                    estimate_memory_size::<(u64, u64)>(num_rows, fixed_size_u64)?;
-                reservation.try_grow(estimated_hashtable_size)?;
-                metrics.build_mem_used.add(estimated_hashtable_size);
                 Box::new(JoinHashMapU64::with_capacity(num_rows))
             } else {
-                let estimated_hashtable_size =
-                    estimate_memory_size::<(u32, u64)>(num_rows, fixed_size_u32)?;
-                reservation.try_grow(estimated_hashtable_size)?;
-                metrics.build_mem_used.add(estimated_hashtable_size);
                 Box::new(JoinHashMapU32::with_capacity(num_rows))
             };
 
@@ -2048,17 +2541,29 @@ async fn collect_left_input(
             .iter()
             .map(|arr| arr.get_array_memory_size())
             .sum::<usize>();
-        if left_values.is_empty()
+        let use_inlist = !(left_values.is_empty()
             || left_values[0].is_empty()
             || estimated_size > config.optimizer.hash_join_inlist_pushdown_max_size
             || map.num_of_distinct_key()
                 > config
                     .optimizer
-                    .hash_join_inlist_pushdown_max_distinct_values
+                    .hash_join_inlist_pushdown_max_distinct_values);
+        if use_inlist
+            && let Some(in_list_values) = build_struct_inlist_values(&left_values)?
         {
-            PushdownStrategy::Map(Arc::clone(&map))
-        } else if let Some(in_list_values) = build_struct_inlist_values(&left_values)? {
             PushdownStrategy::InList(in_list_values)
+        } else if should_compute_dynamic_filters
+            && let Some(fpp) = config.optimizer.hash_join_bloom_filter_pushdown_fpp
+        {
+            // The filter is probed with `HASH_JOIN_SEED`, see `SharedBuildAccumulator`
+            let mut bloom_filter =
+                JoinKeyBloomFilter::try_new(map.num_of_distinct_key(), fpp)?;
+            let mut hashes = vec![0; batch.num_rows()];
+            create_hashes(&left_values, HASH_JOIN_SEED.random_state(), &mut hashes)?;
+            hashes.iter().for_each(|hash| bloom_filter.insert(*hash));
+            reservation.try_grow(bloom_filter.size())?;
+            metrics.build_mem_used.add(bloom_filter.size());
+            PushdownStrategy::BloomFilter(Arc::new(bloom_filter))
         } else {
             PushdownStrategy::Map(Arc::clone(&map))
         }
@@ -2125,6 +2630,7 @@ mod tests {
     }
 
     use crate::coalesce_partitions::CoalescePartitionsExec;
+    use crate::joins::hash_join::spill::MAX_SPILL_LEVEL;
     use crate::joins::hash_join::stream::lookup_join_hashmap;
     use crate::test::{TestMemoryExec, assert_join_metrics};
     use crate::{
@@ -2137,7 +2643,6 @@ mod tests {
     };
     use arrow::buffer::NullBuffer;
     use arrow::datatypes::{DataType, Field};
-    use datafusion_common::hash_utils::create_hashes;
     use datafusion_common::test_util::{batches_to_sort_string, batches_to_string};
     use datafusion_common::{
         ScalarValue, assert_batches_eq, assert_batches_sorted_eq, assert_contains,
@@ -2605,6 +3110,122 @@ mod tests {
         Ok(())
     }
 
+    /// Executes a single partition `Partitioned` join of `left` and `right`,
+    /// returning the sorted output and the number of build side switches
+    async fn adaptive_build_side_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+        adaptive_build_side: bool,
+    ) -> Result<(String, usize)> {
+        let mut session_config = SessionConfig::default();
+        let options = session_config.options_mut();
+        options.execution.enable_hash_join_adaptive_build_side = adaptive_build_side;
+        options.execution.hash_join_adaptive_build_side_ratio = 0.5;
+        let task_ctx =
+            Arc::new(TaskContext::default().with_session_config(session_config));
+
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        )];
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::Partitioned,
+            NullEquality::NullEqualsNothing,
+            false,
+        )?;
+        let batches = common::collect(join.execute(0, task_ctx)?).await?;
+        let switches = join
+            .metrics()
+            .unwrap()
+            .sum_by_name(BUILD_SIDE_SWITCHES_METRIC_NAME)
+            .map(|v| v.as_usize())
+            .unwrap_or_default();
+        Ok((batches_to_sort_string(&batches), switches))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn partitioned_join_adaptive_build_side(
+        #[values(
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::RightSemi,
+            JoinType::LeftAnti,
+            JoinType::RightAnti
+        )]
+        join_type: JoinType,
+    ) -> Result<()> {
+        // the left input is more than twice as large as its statistics
+        // (estimate * hash_join_adaptive_build_side_ratio), and larger than
+        // the right input
+        let left = build_table(
+            ("a1", &vec![1, 2, 3, 4, 5, 6]),
+            ("b1", &vec![4, 5, 5, 7, 8, 9]),
+            ("c1", &vec![7, 8, 9, 10, 11, 12]),
+        );
+        let right = build_table(
+            ("a2", &vec![10, 20, 30]),
+            ("b1", &vec![4, 5, 6]),
+            ("c2", &vec![70, 80, 90]),
+        );
+
+        let (expected, switches) = adaptive_build_side_join(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            join_type,
+            false,
+        )
+        .await?;
+        assert_eq!(switches, 0);
+
+        let (actual, switches) =
+            adaptive_build_side_join(left, right, join_type, true).await?;
+        assert_eq!(switches, 1);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partitioned_join_adaptive_build_side_larger_probe_side() -> Result<()> {
+        let left = build_table(
+            ("a1", &vec![1, 2, 3]),
+            ("b1", &vec![4, 5, 5]),
+            ("c1", &vec![7, 8, 9]),
+        );
+        let right = build_table(
+            ("a2", &vec![10, 20, 30, 40]),
+            ("b1", &vec![4, 5, 6, 7]),
+            ("c2", &vec![70, 80, 90, 100]),
+        );
+
+        // the right input is larger, so the build side is not switched
+        let (actual, switches) =
+            adaptive_build_side_join(left, right, JoinType::Inner, true).await?;
+        assert_eq!(switches, 0);
+        assert_snapshot!(actual, @r"
+        +----+----+----+----+----+----+
+        | a1 | b1 | c1 | a2 | b1 | c2 |
+        +----+----+----+----+----+----+
+        | 1  | 4  | 7  | 10 | 4  | 70 |
+        | 2  | 5  | 8  | 20 | 5  | 80 |
+        | 3  | 5  | 9  | 20 | 5  | 80 |
+        +----+----+----+----+----+----+
+        ");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn join_inner_one_no_shared_column_names() -> Result<()> {
         let task_ctx = Arc::new(TaskContext::default());
@@ -5369,6 +5990,9 @@ mod tests {
     async fn partitioned_join_overallocation() -> Result<()> {
         // Prepare partitioned inputs for HashJoinExec
         // No need to adjust partitioning, as execution should fail with `Resources exhausted` error
+        // when spilling is not possible
+        use datafusion_execution::disk_manager::{DiskManagerBuilder, DiskManagerMode};
+
         let left_batch = build_table_i32(
             ("a1", &vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0]),
             ("b1", &vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0]),
@@ -5410,6 +6034,9 @@ mod tests {
         for join_type in join_types {
             let runtime = RuntimeEnvBuilder::new()
                 .with_memory_limit(100, 1.0)
+                .with_disk_manager_builder(
+                    DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+                )
                 .build_arc()?;
             let session_config = SessionConfig::default().with_batch_size(50);
             let task_ctx = TaskContext::default()
@@ -5447,6 +6074,121 @@ mod tests {
         Ok(())
     }
 
+    /// Joins `left` and `right` on `b1 = b2` with a memory limit of
+    /// `memory_limit` bytes, returning the sorted output and the number of
+    /// spilled partitions
+    async fn spilling_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+        memory_limit: Option<usize>,
+    ) -> (Result<String>, MetricsSet) {
+        let mut runtime = RuntimeEnvBuilder::new();
+        if let Some(memory_limit) = memory_limit {
+            runtime = runtime.with_memory_limit(memory_limit, 1.0);
+        }
+        let task_ctx = TaskContext::default()
+            .with_session_config(SessionConfig::default().with_batch_size(50))
+            .with_runtime(runtime.build_arc().unwrap());
+
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema()).unwrap()) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema()).unwrap()) as _,
+        )];
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::Partitioned,
+            NullEquality::NullEqualsNothing,
+            false,
+        )
+        .unwrap();
+        let batches = match join.execute(0, Arc::new(task_ctx)) {
+            Ok(stream) => common::collect(stream).await,
+            Err(e) => Err(e),
+        };
+        (
+            batches.map(|batches| batches_to_sort_string(&batches)),
+            join.metrics().unwrap(),
+        )
+    }
+
+    fn spilled_partitions(metrics: &MetricsSet) -> usize {
+        metrics
+            .sum_by_name(SPILLED_PARTITIONS_METRIC_NAME)
+            .map(|v| v.as_usize())
+            .unwrap_or_default()
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn partitioned_join_spill(
+        #[values(
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::RightSemi,
+            JoinType::LeftAnti,
+            JoinType::RightAnti,
+            JoinType::LeftMark,
+            JoinType::RightMark
+        )]
+        join_type: JoinType,
+    ) -> Result<()> {
+        // 100 distinct keys on the left, half of which are on the right
+        let left = build_table(
+            ("a1", &(0..1000).collect()),
+            ("b1", &(0..1000).map(|i| i % 100).collect()),
+            ("c1", &(0..1000).collect()),
+        );
+        let right = build_table(
+            ("a2", &(0..100).collect()),
+            ("b2", &(50..150).collect()),
+            ("c2", &(0..100).collect()),
+        );
+
+        let (expected, metrics) =
+            spilling_join(Arc::clone(&left), Arc::clone(&right), join_type, None).await;
+        assert_eq!(spilled_partitions(&metrics), 0);
+
+        // the build side does not fit in memory, but its spill partitions do
+        let (actual, metrics) =
+            spilling_join(left, right, join_type, Some(8 * 1024)).await;
+        assert_eq!(spilled_partitions(&metrics), 1);
+        assert!(metrics.spilled_bytes().unwrap() > 0);
+        assert_eq!(actual?, expected?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partitioned_join_spill_skewed_build_side() -> Result<()> {
+        // all rows of the build side have the same key, so they never fit in
+        // memory no matter how often they are split
+        let left = build_table(
+            ("a1", &(0..1000).collect()),
+            ("b1", &vec![1; 1000]),
+            ("c1", &(0..1000).collect()),
+        );
+        let right = build_table(("a2", &vec![1]), ("b2", &vec![1]), ("c2", &vec![1]));
+
+        let (result, metrics) =
+            spilling_join(left, right, JoinType::Inner, Some(8 * 1024)).await;
+        assert_contains!(
+            result.unwrap_err().to_string(),
+            "Resources exhausted: Additional allocation failed for HashJoinInput[0]"
+        );
+        assert_eq!(spilled_partitions(&metrics), MAX_SPILL_LEVEL);
+
+        Ok(())
+    }
+
     fn build_table_struct(
         struct_name: &str,
         field_name_and_values: (&str, &Vec<Option<i32>>),
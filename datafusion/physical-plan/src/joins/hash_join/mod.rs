@@ -18,10 +18,14 @@
 //! [`HashJoinExec`] Partitioned Hash Join Operator
 
 pub use exec::{HashJoinExec, HashJoinExecBuilder};
-pub use partitioned_hash_eval::{HashExpr, HashTableLookupExpr, SeededRandomState};
+pub use partitioned_hash_eval::{
+    BloomFilterLookupExpr, HashExpr, HashTableLookupExpr, JoinKeyBloomFilter,
+    SeededRandomState,
+};
 
 mod exec;
 mod inlist_builder;
 mod partitioned_hash_eval;
 mod shared_bounds;
+mod spill;
 mod stream;
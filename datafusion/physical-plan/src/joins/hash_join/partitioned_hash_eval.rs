@@ -20,13 +20,14 @@
 use std::{fmt::Display, hash::Hash, sync::Arc};
 
 use arrow::{
-    array::{ArrayRef, UInt64Array},
+    array::{ArrayRef, BooleanArray, UInt64Array},
+    buffer::BooleanBuffer,
     datatypes::{DataType, Schema},
     record_batch::RecordBatch,
 };
-use datafusion_common::Result;
 use datafusion_common::hash_utils::RandomState;
 use datafusion_common::hash_utils::{create_hashes, with_hashes};
+use datafusion_common::{Result, config_err};
 use datafusion_expr::ColumnarValue;
 use datafusion_physical_expr_common::physical_expr::{
     DynHash, PhysicalExpr, PhysicalExprRef,
@@ -343,6 +344,192 @@ impl PhysicalExpr for HashTableLookupExpr {
     }
 }
 
+/// Bloom filter over the hashes of the build side join keys of a hash join.
+///
+/// Probe side rows whose join key hash is not in the filter can not match any
+/// build side row. Unlike a [`HashTableLookupExpr`], checking the filter does
+/// not need the hash table, and the filter is much smaller than the hash table
+/// for large build sides, at the cost of some false positives.
+#[derive(Debug)]
+pub struct JoinKeyBloomFilter {
+    /// Bit set, its length in bits is a power of two
+    bits: Vec<u64>,
+    /// Number of bits set for each hash
+    num_probes: u64,
+}
+
+impl JoinKeyBloomFilter {
+    /// Create an empty filter for `num_keys` distinct keys with a false
+    /// positive probability of `fpp`, which must be in `(0, 1)`.
+    pub fn try_new(num_keys: usize, fpp: f64) -> Result<Self> {
+        if !(fpp > 0.0 && fpp < 1.0) {
+            return config_err!(
+                "Bloom filter false positive probability must be between 0 and 1, got {fpp}"
+            );
+        }
+        let ln_2 = std::f64::consts::LN_2;
+        let num_keys = num_keys.max(1) as f64;
+        let num_bits = (-num_keys * fpp.ln() / (ln_2 * ln_2)).ceil() as usize;
+        let num_words = num_bits.div_ceil(64).next_power_of_two();
+        let num_probes = (-fpp.log2()).round().clamp(1.0, 16.0) as u64;
+        Ok(Self {
+            bits: vec![0; num_words],
+            num_probes,
+        })
+    }
+
+    /// Bit positions of `hash`, derived from its two halves by double hashing
+    fn bit_positions(&self, hash: u64) -> impl Iterator<Item = usize> + use<> {
+        let mask = self.bits.len() as u64 * 64 - 1;
+        let h1 = hash & u64::from(u32::MAX);
+        let h2 = (hash >> 32) | 1;
+        (0..self.num_probes)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) & mask) as usize)
+    }
+
+    /// Add a hash to the filter
+    pub fn insert(&mut self, hash: u64) {
+        for bit in self.bit_positions(hash) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns `false` if `hash` was definitely not added to the filter
+    pub fn contains(&self, hash: u64) -> bool {
+        self.bit_positions(hash)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Checks each of `hash_values`, see [`Self::contains`]
+    pub fn contain_hashes(&self, hash_values: &[u64]) -> BooleanArray {
+        let buffer = BooleanBuffer::collect_bool(hash_values.len(), |i| {
+            self.contains(hash_values[i])
+        });
+        BooleanArray::new(buffer, None)
+    }
+
+    /// Memory used by the filter, in bytes
+    pub fn size(&self) -> usize {
+        self.bits.capacity() * size_of::<u64>()
+    }
+}
+
+/// Physical expression that checks join keys against a [`JoinKeyBloomFilter`].
+///
+/// Returns a [`BooleanArray`] that is `false` for rows whose join keys (from
+/// `on_columns`) are definitely not on the build side.
+pub struct BloomFilterLookupExpr {
+    /// Columns in the ON clause used to compute the join key for lookups
+    on_columns: Vec<PhysicalExprRef>,
+    /// Random state for hashing, the one used to build the filter
+    random_state: SeededRandomState,
+    /// Filter to check against
+    bloom_filter: Arc<JoinKeyBloomFilter>,
+    /// Description for display
+    description: String,
+}
+
+impl BloomFilterLookupExpr {
+    /// Create a new BloomFilterLookupExpr
+    ///
+    /// # Arguments
+    /// * `on_columns` - Columns in the ON clause used to compute the join key
+    /// * `random_state` - SeededRandomState the filter was built with
+    /// * `bloom_filter` - Filter over the build side join key hashes
+    /// * `description` - Description for debugging
+    pub fn new(
+        on_columns: Vec<PhysicalExprRef>,
+        random_state: SeededRandomState,
+        bloom_filter: Arc<JoinKeyBloomFilter>,
+        description: String,
+    ) -> Self {
+        Self {
+            on_columns,
+            random_state,
+            bloom_filter,
+            description,
+        }
+    }
+}
+
+impl std::fmt::Debug for BloomFilterLookupExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cols = self
+            .on_columns
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let seed = self.random_state.seed();
+        write!(f, "{}({cols}, [{seed}])", self.description)
+    }
+}
+
+impl Hash for BloomFilterLookupExpr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.on_columns.dyn_hash(state);
+        self.description.hash(state);
+        self.random_state.seed().hash(state);
+        // Like `HashTableLookupExpr`, the filter is compared by pointer
+        Arc::as_ptr(&self.bloom_filter).hash(state);
+    }
+}
+
+impl PartialEq for BloomFilterLookupExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.on_columns == other.on_columns
+            && self.description == other.description
+            && self.random_state.seed() == other.random_state.seed()
+            && Arc::ptr_eq(&self.bloom_filter, &other.bloom_filter)
+    }
+}
+
+impl Eq for BloomFilterLookupExpr {}
+
+impl Display for BloomFilterLookupExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+impl PhysicalExpr for BloomFilterLookupExpr {
+    fn children(&self) -> Vec<&Arc<dyn PhysicalExpr>> {
+        self.on_columns.iter().collect()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(BloomFilterLookupExpr::new(
+            children,
+            self.random_state.clone(),
+            Arc::clone(&self.bloom_filter),
+            self.description.clone(),
+        )))
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        let join_keys = evaluate_columns(&self.on_columns, batch)?;
+        with_hashes(&join_keys, self.random_state.random_state(), |hashes| {
+            let array = self.bloom_filter.contain_hashes(hashes);
+            Ok(ColumnarValue::Array(Arc::new(array)))
+        })
+    }
+
+    fn fmt_sql(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
 fn evaluate_columns(
     columns: &[PhysicalExprRef],
     batch: &RecordBatch,
@@ -360,7 +547,7 @@ mod tests {
     use crate::joins::join_hash_map::JoinHashMapU32;
     use datafusion_physical_expr::expressions::Column;
     use std::collections::hash_map::DefaultHasher;
-    use std::hash::Hasher;
+    use std::hash::{BuildHasher, Hasher};
 
     fn compute_hash<T: Hash>(value: &T) -> u64 {
         let mut hasher = DefaultHasher::new();
@@ -591,4 +778,58 @@ mod tests {
         assert_eq!(expr1, expr2);
         assert_eq!(compute_hash(&expr1), compute_hash(&expr2));
     }
+
+    #[test]
+    fn test_join_key_bloom_filter() -> Result<()> {
+        let mut bloom_filter = JoinKeyBloomFilter::try_new(1000, 0.01)?;
+        let random_state = RandomState::with_seed(0);
+        for i in 0..1000u64 {
+            bloom_filter.insert(random_state.hash_one(i));
+        }
+
+        // No false negatives
+        assert!((0..1000u64).all(|i| bloom_filter.contains(random_state.hash_one(i))));
+
+        // About 1% false positives
+        let false_positives = (1000..101_000u64)
+            .filter(|i| bloom_filter.contains(random_state.hash_one(i)))
+            .count();
+        assert!(false_positives < 2_000, "{false_positives} false positives");
+
+        assert!(JoinKeyBloomFilter::try_new(10, 0.0).is_err());
+        assert!(JoinKeyBloomFilter::try_new(10, 1.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bloom_filter_lookup_expr_evaluate() -> Result<()> {
+        let random_state = SeededRandomState::with_seed(1);
+        let build_keys: ArrayRef = Arc::new(UInt64Array::from(vec![1, 3, 5]));
+        let mut hashes = vec![0; build_keys.len()];
+        create_hashes([&build_keys], random_state.random_state(), &mut hashes)?;
+        let mut bloom_filter = JoinKeyBloomFilter::try_new(3, 0.001)?;
+        hashes.iter().for_each(|hash| bloom_filter.insert(*hash));
+
+        let expr = BloomFilterLookupExpr::new(
+            vec![Arc::new(Column::new("a", 0))],
+            random_state,
+            Arc::new(bloom_filter),
+            "bloom_filter_lookup".to_string(),
+        );
+        let schema = Arc::new(Schema::new(vec![arrow::datatypes::Field::new(
+            "a",
+            DataType::UInt64,
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(UInt64Array::from(vec![1, 3, 5]))],
+        )?;
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows())?;
+        assert_eq!(
+            result.as_ref(),
+            &BooleanArray::from(vec![true, true, true]) as &dyn arrow::array::Array
+        );
+        Ok(())
+    }
 }
@@ -28,7 +28,8 @@ use crate::joins::PartitionMode;
 use crate::joins::hash_join::exec::HASH_JOIN_SEED;
 use crate::joins::hash_join::inlist_builder::build_struct_fields;
 use crate::joins::hash_join::partitioned_hash_eval::{
-    HashExpr, HashTableLookupExpr, SeededRandomState,
+    BloomFilterLookupExpr, HashExpr, HashTableLookupExpr, JoinKeyBloomFilter,
+    SeededRandomState,
 };
 use arrow::array::ArrayRef;
 use arrow::datatypes::{DataType, Field, Schema};
@@ -82,7 +83,7 @@ impl PartitionBounds {
 /// Creates a membership predicate for filter pushdown.
 ///
 /// If `inlist_values` is provided (for small build sides), creates an InList expression.
-/// Otherwise, creates a HashTableLookup expression (for large build sides).
+/// Otherwise, creates a HashTableLookup or BloomFilterLookup expression (for large build sides).
 ///
 /// Supports both single-column and multi-column joins using struct expressions.
 fn create_membership_predicate(
@@ -135,6 +136,15 @@ fn create_membership_predicate(
             hash_map,
             "hash_lookup".to_string(),
         )) as Arc<dyn PhysicalExpr>)),
+        // Use a bloom filter lookup for large build sides, if enabled
+        PushdownStrategy::BloomFilter(bloom_filter) => {
+            Ok(Some(Arc::new(BloomFilterLookupExpr::new(
+                on_right.to_vec(),
+                random_state.clone(),
+                bloom_filter,
+                "bloom_filter_lookup".to_string(),
+            )) as Arc<dyn PhysicalExpr>))
+        }
         // Empty partition - should not create a filter for this
         PushdownStrategy::Empty => Ok(None),
     }
@@ -264,6 +274,9 @@ pub(crate) enum PushdownStrategy {
     InList(ArrayRef),
     /// Use map lookup for large build sides
     Map(Arc<Map>),
+    /// Use a bloom filter for large build sides, see
+    /// `hash_join_bloom_filter_pushdown_fpp`
+    BloomFilter(Arc<JoinKeyBloomFilter>),
     /// There was no data in this partition, do not build a dynamic filter for it
     Empty,
 }
@@ -0,0 +1,411 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Spilling for [`super::HashJoinExec`]
+//!
+//! When the build side of a partition does not fit in memory, both inputs
+//! are split into [`SPILL_PARTITION_COUNT`] spill partitions by the hash of
+//! their join keys and written to disk. Matching rows always end up in the
+//! same spill partition, so the spill partitions are then joined one after
+//! the other (a "Grace" hash join). A spill partition whose build side still
+//! does not fit in memory is split again, up to [`MAX_SPILL_LEVEL`] times.
+
+use std::sync::Arc;
+
+use crate::joins::PartitionMode;
+use crate::joins::hash_join::exec::collect_left_input;
+use crate::joins::hash_join::shared_bounds::SharedBuildAccumulator;
+use crate::joins::hash_join::stream::{
+    BuildSide, BuildSideInitialState, HashJoinStream, HashJoinStreamState,
+};
+use crate::joins::utils::{
+    BuildProbeJoinMetrics, ColumnIndex, JoinFilter, OnceFut, need_produce_result_in_final,
+};
+use crate::metrics::Count;
+use crate::spill::in_progress_spill_file::InProgressSpillFile;
+use crate::spill::spill_manager::SpillManager;
+use crate::stream::{EmptyRecordBatchStream, RecordBatchStreamAdapter};
+use crate::{SendableRecordBatchStream, hash_utils::create_hashes};
+
+use arrow::array::UInt32Array;
+use arrow::compute::take_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::hash_utils::RandomState;
+use datafusion_common::{DataFusionError, JoinType, NullEquality, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool};
+use datafusion_physical_expr::PhysicalExprRef;
+use datafusion_physical_expr_common::utils::evaluate_expressions_to_arrays;
+use futures::{StreamExt, TryStreamExt};
+
+/// Number of spill partitions the rows of a partition are split into
+pub(super) const SPILL_PARTITION_COUNT: usize = 16;
+
+/// Maximum number of times the rows of a partition are split, after which
+/// running out of memory is an error
+pub(super) const MAX_SPILL_LEVEL: usize = 3;
+
+/// Name of the metric counting the partitions whose build side was spilled
+pub(super) const SPILLED_PARTITIONS_METRIC_NAME: &str = "spilled_partitions";
+
+/// Hard-coded seed of the hashes the rows are split by, so that they differ
+/// from the hashes of `RepartitionExec` and of the hash table. The level is
+/// added to it so that each split distributes the rows differently.
+const SPILL_SEED: u64 = 9113602466305612581;
+
+/// Returns whether `e` is caused by the memory pool running out of memory
+pub(super) fn is_resources_exhausted(e: &DataFusionError) -> bool {
+    matches!(e.find_root(), DataFusionError::ResourcesExhausted(_))
+}
+
+/// Splits batches into [`SPILL_PARTITION_COUNT`] spill files by the hash of
+/// their join keys
+pub(super) struct SpillPartitioner {
+    on: Vec<PhysicalExprRef>,
+    random_state: RandomState,
+    spill_manager: Arc<SpillManager>,
+    /// The spill file of each spill partition, created on its first row
+    files: Vec<Option<InProgressSpillFile>>,
+    hashes_buffer: Vec<u64>,
+}
+
+impl SpillPartitioner {
+    /// Creates a partitioner for rows that were already split `level` times,
+    /// which determines how the rows are distributed
+    pub(super) fn new(
+        on: Vec<PhysicalExprRef>,
+        level: usize,
+        spill_manager: Arc<SpillManager>,
+    ) -> Self {
+        Self {
+            on,
+            random_state: RandomState::with_seed(SPILL_SEED.wrapping_add(level as u64)),
+            spill_manager,
+            files: (0..SPILL_PARTITION_COUNT).map(|_| None).collect(),
+            hashes_buffer: vec![],
+        }
+    }
+
+    /// Appends the rows of `batch` to the spill files of their spill partitions
+    pub(super) fn append(&mut self, batch: &RecordBatch) -> Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let keys = evaluate_expressions_to_arrays(&self.on, batch)?;
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys, &self.random_state, &mut self.hashes_buffer)?;
+
+        let mut indices = vec![vec![]; SPILL_PARTITION_COUNT];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % SPILL_PARTITION_COUNT as u64) as usize].push(row as u32);
+        }
+
+        for (file, indices) in self.files.iter_mut().zip(indices) {
+            if indices.is_empty() {
+                continue;
+            }
+            let batch = take_record_batch(batch, &UInt32Array::from(indices))?;
+            let file = match file {
+                Some(file) => file,
+                None => file.insert(
+                    self.spill_manager
+                        .create_in_progress_file("HashJoinExec spill partition")?,
+                ),
+            };
+            file.append_batch(&batch)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the spill files, returning `None` for empty spill partitions
+    pub(super) fn finish(self) -> Result<Vec<Option<RefCountedTempFile>>> {
+        self.files
+            .into_iter()
+            .map(|file| match file {
+                Some(mut file) => file.finish(),
+                None => Ok(None),
+            })
+            .collect()
+    }
+
+    /// Splits the rows of `file`, which were already split `level` times
+    async fn split(
+        file: Option<RefCountedTempFile>,
+        on: &[PhysicalExprRef],
+        level: usize,
+        spill_manager: &Arc<SpillManager>,
+    ) -> Result<Vec<Option<RefCountedTempFile>>> {
+        let mut partitioner = Self::new(on.to_vec(), level, Arc::clone(spill_manager));
+        if let Some(file) = file {
+            let mut stream = spill_manager.read_spill_as_stream(file, None)?;
+            while let Some(batch) = stream.next().await.transpose()? {
+                partitioner.append(&batch)?;
+            }
+        }
+        partitioner.finish()
+    }
+}
+
+/// The spilled rows of both inputs that hash to the same spill partition
+struct SpillPartition {
+    left: Option<RefCountedTempFile>,
+    right: Option<RefCountedTempFile>,
+    /// Number of times the rows were split
+    level: usize,
+}
+
+/// Joins a partition of a [`super::HashJoinExec`] whose build side was
+/// spilled, one spill partition at a time
+pub(super) struct SpilledHashJoin {
+    pub(super) partition: usize,
+    pub(super) schema: SchemaRef,
+    pub(super) on_left: Vec<PhysicalExprRef>,
+    pub(super) on_right: Vec<PhysicalExprRef>,
+    pub(super) filter: Option<JoinFilter>,
+    pub(super) join_type: JoinType,
+    pub(super) random_state: RandomState,
+    pub(super) column_indices: Vec<ColumnIndex>,
+    pub(super) null_equality: NullEquality,
+    pub(super) batch_size: usize,
+    pub(super) fetch: Option<usize>,
+    pub(super) config: Arc<ConfigOptions>,
+    pub(super) memory_pool: Arc<dyn MemoryPool>,
+    pub(super) join_metrics: BuildProbeJoinMetrics,
+    pub(super) array_map_created_count: Count,
+    pub(super) spilled_partitions: Count,
+    pub(super) left_spill_manager: Arc<SpillManager>,
+    pub(super) right_spill_manager: Arc<SpillManager>,
+}
+
+impl SpilledHashJoin {
+    /// Spills the `right` input the same way as the build side, whose spill
+    /// files are `left_files`, and joins the spill partitions
+    pub(super) fn execute(
+        self,
+        left_files: Vec<Option<RefCountedTempFile>>,
+        mut right: SendableRecordBatchStream,
+    ) -> SendableRecordBatchStream {
+        let schema = Arc::clone(&self.schema);
+        let fetch = self.fetch;
+        let join = Arc::new(self);
+
+        let stream = futures::stream::once(async move {
+            let mut partitioner = SpillPartitioner::new(
+                join.on_right.clone(),
+                0,
+                Arc::clone(&join.right_spill_manager),
+            );
+            while let Some(batch) = right.next().await.transpose()? {
+                partitioner.append(&batch)?;
+            }
+            let right_files = partitioner.finish()?;
+
+            // Joined in the order of the spill partitions
+            let pending = left_files
+                .into_iter()
+                .zip(right_files)
+                .map(|(left, right)| SpillPartition {
+                    left,
+                    right,
+                    level: 1,
+                })
+                .rev()
+                .collect::<Vec<_>>();
+
+            Ok::<_, DataFusionError>(
+                futures::stream::try_unfold(
+                    (join, pending),
+                    |(join, mut pending)| async move {
+                        while let Some(spill_partition) = pending.pop() {
+                            if let Some(stream) = join
+                                .join_spill_partition(spill_partition, &mut pending)
+                                .await?
+                            {
+                                return Ok(Some((stream, (join, pending))));
+                            }
+                        }
+                        Ok::<_, DataFusionError>(None)
+                    },
+                )
+                .try_flatten(),
+            )
+        })
+        .try_flatten();
+
+        // Each spill partition is limited to `fetch` rows, but not all of
+        // them together
+        let stream = match fetch {
+            Some(fetch) => stream
+                .scan(fetch, |remaining, batch| {
+                    if *remaining == 0 {
+                        return futures::future::ready(None);
+                    }
+                    let batch = batch.map(|batch| {
+                        let batch = batch.slice(0, batch.num_rows().min(*remaining));
+                        *remaining -= batch.num_rows();
+                        batch
+                    });
+                    futures::future::ready(Some(batch))
+                })
+                .boxed(),
+            None => stream.boxed(),
+        };
+
+        Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+    }
+
+    /// Returns a stream joining `spill_partition`, or splits it into
+    /// `pending` if its build side does not fit in memory
+    async fn join_spill_partition(
+        &self,
+        spill_partition: SpillPartition,
+        pending: &mut Vec<SpillPartition>,
+    ) -> Result<Option<SendableRecordBatchStream>> {
+        let SpillPartition { left, right, level } = spill_partition;
+        if left.is_none() && right.is_none() {
+            return Ok(None);
+        }
+
+        let left_stream = self.read_spill(left.clone(), &self.left_spill_manager)?;
+        let reservation =
+            MemoryConsumer::new(format!("HashJoinInput[{}]", self.partition))
+                .register(&self.memory_pool);
+        let left_data = collect_left_input(
+            self.random_state.clone(),
+            left_stream,
+            self.on_left.clone(),
+            self.join_metrics.clone(),
+            reservation,
+            need_produce_result_in_final(self.join_type),
+            1,
+            false,
+            Arc::clone(&self.config),
+            self.null_equality,
+            self.array_map_created_count.clone(),
+        )
+        .await;
+
+        match left_data {
+            Ok(left_data) => {
+                let right_stream = self.read_spill(right, &self.right_spill_manager)?;
+                let left_fut = OnceFut::new(async move { Ok(left_data) });
+                Ok(Some(Box::pin(HashJoinStream::new(
+                    self.partition,
+                    Arc::clone(&self.schema),
+                    self.on_right.clone(),
+                    self.filter.clone(),
+                    self.join_type,
+                    right_stream,
+                    self.random_state.clone(),
+                    self.join_metrics.clone(),
+                    self.column_indices.clone(),
+                    self.null_equality,
+                    HashJoinStreamState::WaitBuildSide,
+                    BuildSide::Initial(BuildSideInitialState { left_fut }),
+                    self.batch_size,
+                    vec![],
+                    false,
+                    None,
+                    PartitionMode::Partitioned,
+                    false,
+                    self.fetch,
+                ))))
+            }
+            Err(e) if is_resources_exhausted(&e) && level < MAX_SPILL_LEVEL => {
+                self.spilled_partitions.add(1);
+                let left_files = SpillPartitioner::split(
+                    left,
+                    &self.on_left,
+                    level,
+                    &self.left_spill_manager,
+                )
+                .await?;
+                let right_files = SpillPartitioner::split(
+                    right,
+                    &self.on_right,
+                    level,
+                    &self.right_spill_manager,
+                )
+                .await?;
+                pending.extend(
+                    left_files
+                        .into_iter()
+                        .zip(right_files)
+                        .map(|(left, right)| SpillPartition {
+                            left,
+                            right,
+                            level: level + 1,
+                        })
+                        .rev(),
+                );
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn read_spill(
+        &self,
+        file: Option<RefCountedTempFile>,
+        spill_manager: &SpillManager,
+    ) -> Result<SendableRecordBatchStream> {
+        match file {
+            Some(file) => spill_manager.read_spill_as_stream(file, None),
+            None => Ok(Box::pin(EmptyRecordBatchStream::new(Arc::clone(
+                spill_manager.schema(),
+            )))),
+        }
+    }
+}
+
+/// Reports a partition as canceled to the [`SharedBuildAccumulator`] of the
+/// join when dropped, unless it was disarmed by [`Self::disarm`]. This
+/// unblocks the other partitions waiting on the dynamic filter when a
+/// partition never builds its hash table from its whole build side.
+pub(super) struct CancelBuildReportGuard {
+    build_accumulator: Option<Arc<SharedBuildAccumulator>>,
+    partition: usize,
+}
+
+impl CancelBuildReportGuard {
+    pub(super) fn new(
+        build_accumulator: Option<Arc<SharedBuildAccumulator>>,
+        partition: usize,
+    ) -> Self {
+        Self {
+            build_accumulator,
+            partition,
+        }
+    }
+
+    /// Returns the accumulator, which the partition now reports to
+    pub(super) fn disarm(mut self) -> Option<Arc<SharedBuildAccumulator>> {
+        self.build_accumulator.take()
+    }
+}
+
+impl Drop for CancelBuildReportGuard {
+    fn drop(&mut self) {
+        if let Some(build_accumulator) = self.build_accumulator.take() {
+            build_accumulator.report_canceled_partition(self.partition);
+        }
+    }
+}
@@ -18,23 +18,28 @@
 //! DataFusion Join implementations
 
 use arrow::array::BooleanBufferBuilder;
+pub use asof_join::AsOfJoinExec;
 pub use cross_join::CrossJoinExec;
 use datafusion_physical_expr::PhysicalExprRef;
 pub use hash_join::{
-    HashExpr, HashJoinExec, HashJoinExecBuilder, HashTableLookupExpr, SeededRandomState,
+    BloomFilterLookupExpr, HashExpr, HashJoinExec, HashJoinExecBuilder,
+    HashTableLookupExpr, JoinKeyBloomFilter, SeededRandomState,
 };
 pub use nested_loop_join::{NestedLoopJoinExec, NestedLoopJoinExecBuilder};
 use parking_lot::Mutex;
 // Note: SortMergeJoin is not used in plans yet
 pub use piecewise_merge_join::PiecewiseMergeJoinExec;
 pub use sort_merge_join::SortMergeJoinExec;
+pub use star_join::{StarJoinDimension, StarJoinExec};
 pub use symmetric_hash_join::SymmetricHashJoinExec;
+mod asof_join;
 pub mod chain;
 mod cross_join;
 mod hash_join;
 mod nested_loop_join;
 mod piecewise_merge_join;
 mod sort_merge_join;
+mod star_join;
 mod stream_join_utils;
 mod symmetric_hash_join;
 pub mod utils;
@@ -126,9 +126,8 @@ use crate::spill::spill_manager::SpillManager;
 /// ## 1. Buffering Left Input
 /// - The operator eagerly buffers all left-side input batches into memory,
 ///   util a memory limit is reached.
-///   Currently, an out-of-memory error will be thrown if all the left-side input batches
-///   cannot fit into memory at once.
-///   In the future, it's possible to make this case finish execution. (see
+///   If all the left-side input batches cannot fit into memory at once, the
+///   left side is buffered and joined in several chunks instead. (see
 ///   'Memory-limited Execution' section)
 /// - The rationale for buffering the left side is that scanning the right side
 ///   can be expensive (e.g., decoding Parquet files), so buffering more left
@@ -143,6 +142,13 @@ use crate::spill::spill_manager::SpillManager;
 ///     loop iteration.
 ///   - Matched results are accumulated into an output buffer. (see more in
 ///     `Output Buffering Strategy` section)
+///   - If the right batch is small, a block of left rows is joined with it at
+///     once, so that the join filter is still evaluated on about `batch_size`
+///     rows at a time.
+///   - Semi, anti and mark joins only need to know whether a row has a match.
+///     Left rows that already have one are skipped, the join filter is only
+///     evaluated on right rows without one once most have one, and a right
+///     batch is done as soon as all of its rows have one.
 /// - This process continues until all right-side input is consumed.
 ///
 /// # Producing unmatched build-side data
@@ -1400,7 +1406,8 @@ impl NestedLoopJoinStream {
                     spill_metrics,
                     Arc::clone(&schema),
                 )
-                .with_compression_type(ctx.session_config().spill_compression());
+                .with_compression_type(ctx.session_config().spill_compression())
+                .with_resource_tracker(ctx.resource_tracker().cloned());
 
                 let result = left_spill_manager
                     .spill_record_batch_stream_and_return_max_batch_memory(
@@ -1449,7 +1456,8 @@ impl NestedLoopJoinStream {
             self.metrics.spill_metrics.clone(),
             right_schema,
         )
-        .with_compression_type(context.session_config().spill_compression());
+        .with_compression_type(context.session_config().spill_compression())
+        .with_resource_tracker(context.resource_tracker().cloned());
 
         self.spill_state = SpillState::Active(Box::new(SpillStateActive {
             left_spill_fut,
@@ -2030,6 +2038,17 @@ impl NestedLoopJoinStream {
             return Ok(false);
         }
 
+        // A right semi, anti or mark join is done with the right batch once
+        // all of its rows have a match
+        if self.skips_matched_right_rows()
+            && self
+                .current_right_batch_matched
+                .as_ref()
+                .is_some_and(|matched| matched.true_count() == matched.len())
+        {
+            return Ok(false);
+        }
+
         // ========
         // Join (l_row x right_batch)
         // and push the result into output_buffer
@@ -2053,49 +2072,97 @@ impl NestedLoopJoinStream {
             // Calculate max left rows to handle at once. This operator tries to handle
             // up to `datafusion.execution.batch_size` rows at once in the intermediate
             // batch.
-            let l_row_count = std::cmp::min(
-                l_row_cnt_ratio,
-                left_data.batch().num_rows() - self.left_probe_idx,
-            );
+            let left_rows = self.next_left_rows(&left_data, l_row_cnt_ratio);
+            if left_rows.is_empty() {
+                return Ok(false);
+            }
 
-            debug_assert!(
-                l_row_count != 0,
-                "This function should only be entered when there are remaining left rows to process"
-            );
-            let joined_batch = self.process_left_range_join(
-                &left_data,
-                &right_batch,
-                self.left_probe_idx,
-                l_row_count,
-            )?;
+            let joined_batch =
+                self.process_left_range_join(&left_data, &right_batch, &left_rows)?;
 
             if let Some(batch) = joined_batch {
                 self.output_buffer.push_batch(batch)?;
             }
 
-            self.left_probe_idx += l_row_count;
-
             return Ok(true);
         }
 
-        let l_idx = self.left_probe_idx;
+        // Advances the left cursor
+        let Some(&l_idx) = self.next_left_rows(&left_data, 1).first() else {
+            return Ok(false);
+        };
         let joined_batch =
-            self.process_single_left_row_join(&left_data, &right_batch, l_idx)?;
+            self.process_single_left_row_join(&left_data, &right_batch, l_idx as usize)?;
 
         if let Some(batch) = joined_batch {
             self.output_buffer.push_batch(batch)?;
         }
 
-        // ==== Prepare for the next iteration ====
-
-        // Advance left cursor
-        self.left_probe_idx += 1;
-
         // Return true to continue probing
         Ok(true)
     }
 
-    /// Process [l_start_index, l_start_index + l_count) JOIN right_batch
+    /// Returns true for left semi, anti and mark joins, which only need to
+    /// know whether a left row has a match
+    fn skips_matched_left_rows(&self) -> bool {
+        matches!(
+            self.join_type,
+            JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark
+        )
+    }
+
+    /// Returns true for right semi, anti and mark joins, which only need to
+    /// know whether a right row has a match
+    fn skips_matched_right_rows(&self) -> bool {
+        matches!(
+            self.join_type,
+            JoinType::RightSemi | JoinType::RightAnti | JoinType::RightMark
+        )
+    }
+
+    /// Returns the indices of up to `limit` left rows to join with the current
+    /// right batch next, and advances the left cursor past them.
+    ///
+    /// Left rows that already have a match are skipped if only whether they
+    /// have one matters, see [`Self::skips_matched_left_rows`].
+    fn next_left_rows(&mut self, left_data: &JoinLeftData, limit: usize) -> Vec<u32> {
+        let num_rows = left_data.batch().num_rows();
+        if !self.skips_matched_left_rows() {
+            let end = std::cmp::min(self.left_probe_idx + limit, num_rows);
+            let rows = (self.left_probe_idx as u32..end as u32).collect();
+            self.left_probe_idx = end;
+            return rows;
+        }
+
+        let bitmap = left_data.bitmap().lock();
+        let mut rows = Vec::with_capacity(limit);
+        while self.left_probe_idx < num_rows && rows.len() < limit {
+            if !bitmap.get_bit(self.left_probe_idx) {
+                rows.push(self.left_probe_idx as u32);
+            }
+            self.left_probe_idx += 1;
+        }
+        rows
+    }
+
+    /// Returns the rows of the current right batch that do not have a match
+    /// yet, if only whether they have one matters and at least half of them
+    /// already do. Evaluating the join filter on these rows only is then
+    /// cheaper than evaluating it on the whole batch.
+    fn unmatched_right_rows(&self) -> Result<Option<BooleanArray>> {
+        if !self.skips_matched_right_rows() {
+            return Ok(None);
+        }
+        let Some(matched) = self.current_right_batch_matched.as_ref() else {
+            return Ok(None);
+        };
+        if matched.true_count() * 2 < matched.len() {
+            return Ok(None);
+        }
+        Ok(Some(not(matched)?))
+    }
+
+    /// Process the left rows with indices `left_rows` JOIN right_batch
     /// Returns a RecordBatch containing the join results (None if empty)
     ///
     /// Side Effect: If the join type requires, left or right side matched bitmap
@@ -2104,22 +2171,23 @@ impl NestedLoopJoinStream {
         &mut self,
         left_data: &JoinLeftData,
         right_batch: &RecordBatch,
-        l_start_index: usize,
-        l_row_count: usize,
+        left_rows: &[u32],
     ) -> Result<Option<RecordBatch>> {
         // Construct the Cartesian product between the specified range of left rows
         // and the entire right_batch. First, it calculates the index vectors, then
         // materializes the intermediate batch, and finally applies the join filter
         // to it.
         // -----------------------------------------------------------
+        let l_row_count = left_rows.len();
         let right_rows = right_batch.num_rows();
         let total_rows = l_row_count * right_rows;
 
-        // Build index arrays for cartesian product: left_range X right_batch
-        let left_indices: UInt32Array =
-            UInt32Array::from_iter_values((0..l_row_count).flat_map(|i| {
-                std::iter::repeat_n((l_start_index + i) as u32, right_rows)
-            }));
+        // Build index arrays for cartesian product: left_rows X right_batch
+        let left_indices: UInt32Array = UInt32Array::from_iter_values(
+            left_rows
+                .iter()
+                .flat_map(|l_index| std::iter::repeat_n(*l_index, right_rows)),
+        );
         let right_indices: UInt32Array = UInt32Array::from_iter_values(
             (0..l_row_count).flat_map(|_| 0..right_rows as u32),
         );
@@ -2198,7 +2266,7 @@ impl NestedLoopJoinStream {
                 internal_datafusion_err!("Must be Some after the previous combining step")
             })?;
 
-            let l_index = l_start_index + i / right_rows;
+            let l_index = left_rows[i / right_rows] as usize;
             let r_index = i % right_rows;
 
             if let Some(bitmap) = left_bitmap.as_mut()
@@ -2297,12 +2365,34 @@ impl NestedLoopJoinStream {
         }
 
         let cur_right_bitmap = if let Some(filter) = &self.join_filter {
-            apply_filter_to_row_join_batch(
-                left_data.batch(),
-                l_index,
-                right_batch,
-                filter,
-            )?
+            match self.unmatched_right_rows()? {
+                Some(unmatched) => {
+                    let candidates = filter_record_batch(right_batch, &unmatched)?;
+                    let candidates_matched = apply_filter_to_row_join_batch(
+                        left_data.batch(),
+                        l_index,
+                        &candidates,
+                        filter,
+                    )?;
+                    // map the matches back to the rows of the right batch
+                    let mut matched = BooleanBufferBuilder::new(right_row_count);
+                    matched.append_n(right_row_count, false);
+                    for (candidate, r_index) in
+                        unmatched.values().set_indices().enumerate()
+                    {
+                        if candidates_matched.value(candidate) {
+                            matched.set_bit(r_index, true);
+                        }
+                    }
+                    BooleanArray::new(matched.finish(), None)
+                }
+                None => apply_filter_to_row_join_batch(
+                    left_data.batch(),
+                    l_index,
+                    right_batch,
+                    filter,
+                )?,
+            }
         } else {
             BooleanArray::from(vec![true; right_row_count])
         };
@@ -2985,8 +3075,9 @@ pub(crate) mod tests {
         common, expressions::Column, repartition::RepartitionExec, test::build_table_i32,
     };
 
+    use arrow::array::AsArray;
     use arrow::compute::SortOptions;
-    use arrow::datatypes::{DataType, Field};
+    use arrow::datatypes::{DataType, Field, Int32Type};
     use datafusion_common::assert_contains;
     use datafusion_common::test_util::batches_to_sort_string;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
@@ -3433,6 +3524,86 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn join_semi_anti_with_many_matches(
+        #[values(1, 2, 16)] batch_size: usize,
+    ) -> Result<()> {
+        // most rows have several matches, so matched rows are skipped
+        let build_left = || {
+            build_table(
+                ("a1", &vec![1, 2, 3, 7, 5]),
+                ("b1", &vec![1, 2, 3, 7, 5]),
+                ("c1", &vec![10, 20, 30, 70, 50]),
+                None,
+                Vec::new(),
+            )
+        };
+        let build_right = || {
+            build_table(
+                ("a2", &vec![3, 3, 1, 6]),
+                ("b2", &vec![3, 3, 1, 6]),
+                ("c2", &vec![30, 31, 10, 60]),
+                None,
+                Vec::new(),
+            )
+        };
+        // left.b1 < right.b2
+        let build_filter = || {
+            let column_indices = vec![
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Right,
+                },
+            ];
+            let intermediate_schema = Schema::new(vec![
+                Field::new("x", DataType::Int32, true),
+                Field::new("y", DataType::Int32, true),
+            ]);
+            let expression = Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("x", 0)),
+                Operator::Lt,
+                Arc::new(Column::new("y", 1)),
+            )) as Arc<dyn PhysicalExpr>;
+            JoinFilter::new(expression, column_indices, Arc::new(intermediate_schema))
+        };
+
+        for (join_type, expected) in [
+            (JoinType::LeftSemi, vec![10, 20, 30, 50]),
+            (JoinType::LeftAnti, vec![70]),
+            (JoinType::RightSemi, vec![30, 31, 60]),
+            (JoinType::RightAnti, vec![10]),
+        ] {
+            let (_, batches, metrics) = multi_partitioned_join_collect(
+                build_left(),
+                build_right(),
+                &join_type,
+                Some(build_filter()),
+                new_task_ctx(batch_size),
+            )
+            .await?;
+            let mut values = batches
+                .iter()
+                .flat_map(|batch| {
+                    batch
+                        .column(2)
+                        .as_primitive::<Int32Type>()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>();
+            values.sort();
+            assert_eq!(values, expected, "{join_type}");
+            assert_join_metrics!(metrics, expected.len());
+        }
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn join_left_mark_with_filter(
@@ -482,77 +482,31 @@ fn resolve_classic_join(
     // Our buffer_idx variable allows us to start probing on the buffered side where we last matched
     // in the previous stream row.
     for row_idx in stream_idx..stream_batch.batch.num_rows() {
-        while buffer_idx < buffered_len {
-            let compare = cmp.compare(row_idx, buffer_idx);
-
-            // If we find a match we append all indices and move to the next stream row index
-            match operator {
-                Operator::Gt | Operator::Lt => {
-                    if compare == Ordering::Less {
-                        batch_process_state.found = true;
-                        let count = buffered_len - buffer_idx;
-
-                        let batch = build_matched_indices_and_set_buffered_bitmap(
-                            (buffer_idx, count),
-                            (row_idx, count),
-                            buffered_side,
-                            stream_batch,
-                            join_type,
-                            join_schema,
-                        )?;
-
-                        batch_process_state.output_batches.push_batch(batch)?;
-
-                        // Flush batch and update pointers if we have a completed batch
-                        if let Some(batch) =
-                            batch_process_state.output_batches.next_completed_batch()
-                        {
-                            batch_process_state.found = false;
-                            batch_process_state.start_buffer_idx = buffer_idx;
-                            batch_process_state.start_stream_idx = row_idx + 1;
-                            return Ok(batch);
-                        }
-
-                        break;
-                    }
-                }
-                Operator::GtEq | Operator::LtEq => {
-                    if matches!(compare, Ordering::Equal | Ordering::Less) {
-                        batch_process_state.found = true;
-                        let count = buffered_len - buffer_idx;
-                        let batch = build_matched_indices_and_set_buffered_bitmap(
-                            (buffer_idx, count),
-                            (row_idx, count),
-                            buffered_side,
-                            stream_batch,
-                            join_type,
-                            join_schema,
-                        )?;
-
-                        // Flush batch and update pointers if we have a completed batch
-                        batch_process_state.output_batches.push_batch(batch)?;
-                        if let Some(batch) =
-                            batch_process_state.output_batches.next_completed_batch()
-                        {
-                            batch_process_state.found = false;
-                            batch_process_state.start_buffer_idx = buffer_idx;
-                            batch_process_state.start_stream_idx = row_idx + 1;
-                            return Ok(batch);
-                        }
-
-                        break;
-                    }
-                }
-                _ => {
-                    return internal_err!(
-                        "PiecewiseMergeJoin should not contain operator, {}",
-                        operator
-                    );
-                }
-            };
-
-            // Increment buffer_idx after every row
-            buffer_idx += 1;
+        buffer_idx = first_match(&cmp, operator, row_idx, buffer_idx, buffered_len)?;
+
+        // If we find a match we append all indices and move to the next stream row index
+        if buffer_idx < buffered_len {
+            batch_process_state.found = true;
+            let count = buffered_len - buffer_idx;
+            let batch = build_matched_indices_and_set_buffered_bitmap(
+                (buffer_idx, count),
+                (row_idx, count),
+                buffered_side,
+                stream_batch,
+                join_type,
+                join_schema,
+            )?;
+
+            batch_process_state.output_batches.push_batch(batch)?;
+
+            // Flush batch and update pointers if we have a completed batch
+            if let Some(batch) = batch_process_state.output_batches.next_completed_batch()
+            {
+                batch_process_state.found = false;
+                batch_process_state.start_buffer_idx = buffer_idx;
+                batch_process_state.start_stream_idx = row_idx + 1;
+                return Ok(batch);
+            }
         }
 
         // If a match was not found for the current stream row index the stream indice is appended
@@ -583,6 +537,44 @@ fn resolve_classic_join(
     Ok(RecordBatch::new_empty(Arc::clone(join_schema)))
 }
 
+// Returns the index of the first buffered row in `start..buffered_len` that matches the
+// stream row `row_idx`, or `buffered_len` if there is none.
+//
+// Both sides are sorted so that once a buffered row matches, all rows after it match too,
+// which allows binary searching for the first match instead of scanning the buffered side.
+fn first_match(
+    cmp: &JoinKeyComparator,
+    operator: Operator,
+    row_idx: usize,
+    start: usize,
+    buffered_len: usize,
+) -> Result<usize> {
+    let matches = |buffer_idx: usize| match operator {
+        Operator::Gt | Operator::Lt => {
+            Ok(cmp.compare(row_idx, buffer_idx) == Ordering::Less)
+        }
+        Operator::GtEq | Operator::LtEq => Ok(matches!(
+            cmp.compare(row_idx, buffer_idx),
+            Ordering::Equal | Ordering::Less
+        )),
+        _ => internal_err!(
+            "PiecewiseMergeJoin should not contain operator, {}",
+            operator
+        ),
+    };
+
+    let (mut low, mut high) = (start, buffered_len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if matches(mid)? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(low)
+}
+
 // Builds a record batch from indices ranges on the buffered and streamed side.
 //
 // The two ranges are: buffered_range: (start index, count) and streamed_range: (start index, count) due
@@ -656,12 +648,14 @@ mod tests {
         joins::PiecewiseMergeJoinExec,
         test::{TestMemoryExec, build_table_i32},
     };
-    use arrow::array::{Date32Array, Date64Array};
+    use arrow::array::{ArrayRef, AsArray, Date32Array, Date64Array, Int32Array};
+    use arrow::datatypes::Int32Type;
     use arrow_schema::{DataType, Field};
-    use datafusion_common::test_util::batches_to_string;
+    use datafusion_common::test_util::{batches_to_sort_string, batches_to_string};
     use datafusion_execution::TaskContext;
     use datafusion_physical_expr::{PhysicalExpr, expressions::Column};
     use insta::assert_snapshot;
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     fn columns(schema: &Schema) -> Vec<String> {
@@ -1207,6 +1201,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_inner_greater_than_equal_many_buffered_rows() -> Result<()> {
+        let values = (0..100).collect::<Vec<i32>>();
+        let left = build_table(("a1", &values), ("b1", &values), ("c1", &values));
+        let right = build_table(
+            ("a2", &vec![1, 2, 3, 4, 5, 6]),
+            ("b1", &vec![50, 0, 99, 100, 25, 25]),
+            ("c2", &vec![10, 20, 30, 40, 50, 60]),
+        );
+
+        let on = (
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        );
+
+        let (_, batches) =
+            join_collect(left, right, on, Operator::GtEq, JoinType::Inner).await?;
+
+        // each right row matches the left rows with b1 >= right.b1
+        let mut matches = HashMap::<i32, usize>::new();
+        for batch in &batches {
+            let right_b1 = batch.column(4).as_primitive::<Int32Type>();
+            for value in right_b1.values() {
+                *matches.entry(*value).or_default() += 1;
+            }
+        }
+        let expected = HashMap::from([(50, 50), (0, 100), (99, 1), (25, 150)]);
+        assert_eq!(matches, expected);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn join_inner_greater_than_unsorted_right() -> Result<()> {
         // +----+----+----+
@@ -1539,4 +1564,109 @@ mod tests {
         ");
         Ok(())
     }
+
+    #[test]
+    fn first_match_skips_null_keys_and_finds_first_duplicate() -> Result<()> {
+        // `Gt` and `GtEq` sort both sides ascending with nulls first
+        let buffered: ArrayRef = Arc::new(Int32Array::from(vec![
+            None,
+            Some(1),
+            Some(2),
+            Some(2),
+            Some(2),
+            Some(3),
+        ]));
+        let stream: ArrayRef =
+            Arc::new(Int32Array::from(vec![None, Some(0), Some(2), Some(3)]));
+        let cmp = JoinKeyComparator::new(
+            &[stream],
+            &[Arc::clone(&buffered)],
+            &[SortOptions::new(false, true)],
+            NullEquality::NullEqualsNothing,
+        )?;
+        // the search starts after the null keys of the buffered side
+        let start = buffered.null_count();
+
+        assert_eq!(first_match(&cmp, Operator::Gt, 1, start, 6)?, 1);
+        assert_eq!(first_match(&cmp, Operator::GtEq, 2, start, 6)?, 2);
+        assert_eq!(first_match(&cmp, Operator::Gt, 2, start, 6)?, 5);
+        assert_eq!(first_match(&cmp, Operator::Gt, 3, start, 6)?, 6);
+        // the search resumes where the previous stream row matched
+        assert_eq!(first_match(&cmp, Operator::GtEq, 3, 2, 6)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn first_match_descending() -> Result<()> {
+        // `Lt` and `LtEq` sort both sides descending with nulls first
+        let buffered: ArrayRef = Arc::new(Int32Array::from(vec![
+            None,
+            None,
+            Some(5),
+            Some(4),
+            Some(4),
+            Some(4),
+            Some(1),
+        ]));
+        let stream: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(6), Some(4), Some(0)]));
+        let cmp = JoinKeyComparator::new(
+            &[stream],
+            &[Arc::clone(&buffered)],
+            &[SortOptions::new(true, true)],
+            NullEquality::NullEqualsNothing,
+        )?;
+        let start = buffered.null_count();
+
+        assert_eq!(first_match(&cmp, Operator::Lt, 0, start, 7)?, 2);
+        assert_eq!(first_match(&cmp, Operator::LtEq, 1, start, 7)?, 3);
+        assert_eq!(first_match(&cmp, Operator::Lt, 1, start, 7)?, 6);
+        assert_eq!(first_match(&cmp, Operator::LtEq, 2, start, 7)?, 7);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_inner_less_than_equal_with_null_keys() -> Result<()> {
+        let build_nullable_table = |prefix: &str, a: Vec<i32>, b: Vec<Option<i32>>| {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new(format!("a{prefix}"), DataType::Int32, false),
+                Field::new("b1", DataType::Int32, true),
+            ]));
+            let batch = RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![Arc::new(Int32Array::from(a)), Arc::new(Int32Array::from(b))],
+            )
+            .unwrap();
+            TestMemoryExec::try_new_exec(&[vec![batch]], schema, None).unwrap()
+        };
+        // the buffered side is sorted descending with nulls first for `LtEq`
+        let left = build_nullable_table(
+            "1",
+            vec![2, 5, 1, 4, 3],
+            vec![None, None, Some(4), Some(4), Some(2)],
+        );
+        let right =
+            build_nullable_table("2", vec![10, 20, 30], vec![Some(4), None, Some(3)]);
+
+        let on = (
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        );
+
+        let (_, batches) =
+            join_collect(left, right, on, Operator::LtEq, JoinType::Inner).await?;
+
+        // null keys match nothing, and both left rows with the boundary key 4 match
+        assert_snapshot!(batches_to_sort_string(&batches), @r"
+        +----+----+----+----+
+        | a1 | b1 | a2 | b1 |
+        +----+----+----+----+
+        | 1  | 4  | 10 | 4  |
+        | 3  | 2  | 10 | 4  |
+        | 3  | 2  | 30 | 3  |
+        | 4  | 4  | 10 | 4  |
+        +----+----+----+----+
+        ");
+        Ok(())
+    }
 }
@@ -103,11 +103,8 @@ use crate::{
 ///
 /// ```text
 /// for stream_row in stream_batch:
-///     for buffer_row in buffer_batch:
-///         if compare(stream_row, probe_row):
-///             output stream_row X buffer_batch[buffer_row:]
-///         else:
-///             continue
+///     buffer_row = binary_search(buffer_batch[buffer_row:], compare(stream_row, _))
+///     output stream_row X buffer_batch[buffer_row:]
 /// ```
 ///
 /// The algorithm uses the streamed side (larger) to drive the loop. This is due to every row on the stream side iterating
@@ -233,9 +230,9 @@ use crate::{
 /// ## Piecewise Merge Join (PWMJ)
 ///
 /// # Classic Join:
-/// Requires sorting the probe side and, for each probe row, scanning the buffered side until the first match
-/// is found.
-///     Complexity: `O(sort(S) + num_of_batches(|S|) * scan(R))`.
+/// Requires sorting the probe side and, for each probe row, binary searching the buffered side for the first
+/// match. The search starts at the first match of the previous probe row.
+///     Complexity: `O(sort(S) + |S| * log(|R|))`.
 ///
 /// # Mark Join:
 /// Sorts the probe side, then computes the min/max range of the probe keys and scans the buffered side only
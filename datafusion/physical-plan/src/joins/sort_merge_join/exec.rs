@@ -529,7 +529,8 @@ impl ExecutionPlan for SortMergeJoinExec {
             SpillMetrics::new(&self.metrics, partition),
             buffered.schema(),
         )
-        .with_compression_type(context.session_config().spill_compression());
+        .with_compression_type(context.session_config().spill_compression())
+        .with_resource_tracker(context.resource_tracker().cloned());
 
         if matches!(
             self.join_type,
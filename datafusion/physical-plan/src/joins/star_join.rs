@@ -0,0 +1,693 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the star join plan, which joins a fact input with several small
+//! dimension inputs in a single pass over the fact input
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::execution_plan::{CardinalityEffect, EmissionType, boundedness_from_children};
+use crate::joins::JoinOn;
+use crate::joins::utils::{OnceAsync, OnceFut};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::spill::get_record_batch_memory_size;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
+    PlanProperties, SendableRecordBatchStream, check_if_same_properties, collect,
+};
+
+use arrow::array::{ArrayRef, RecordBatch, RecordBatchOptions, UInt32Array};
+use arrow::buffer::NullBuffer;
+use arrow::compute::{concat_batches, take};
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::row::{RowConverter, SortField};
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::{JoinSide, JoinType, Result, internal_err, plan_err};
+use datafusion_execution::TaskContext;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
+use datafusion_physical_expr_common::physical_expr::fmt_sql;
+
+use futures::StreamExt;
+
+/// A dimension of a [`StarJoinExec`]: an input that is collected into a hash
+/// table, and the keys it is joined with the fact input on
+#[derive(Debug, Clone)]
+pub struct StarJoinDimension {
+    /// The dimension input
+    pub input: Arc<dyn ExecutionPlan>,
+    /// The (fact, dimension) pairs of equijoin keys
+    pub on: JoinOn,
+}
+
+impl StarJoinDimension {
+    pub fn new(input: Arc<dyn ExecutionPlan>, on: JoinOn) -> Self {
+        Self { input, on }
+    }
+}
+
+/// Join execution plan that inner joins a fact input with several dimension
+/// inputs at once, as in the star schema query
+///
+/// ```sql
+/// SELECT * FROM sales
+/// JOIN stores ON sales.store_id = stores.id
+/// JOIN products ON sales.product_id = products.id
+/// ```
+///
+/// The output contains the columns of the fact input followed by the columns
+/// of each dimension, in order. Its rows are the fact rows that have a match in
+/// every dimension, extended with the columns of the matches. Rows with a null
+/// key never match.
+///
+/// # Execution
+///
+/// Like the build side of a [`HashJoinExec`] in `CollectLeft` mode, every
+/// dimension is collected into a single hash table that is shared by all
+/// output partitions. Each batch of the fact input is then looked up in the
+/// hash tables of all dimensions in turn, and the output is only materialized
+/// once all lookups are done, rather than after each of them as in a chain of
+/// binary joins. The output preserves the order of the fact input.
+///
+/// The join keys of every dimension must only reference the fact input, which
+/// rules out snowflake schemas.
+///
+/// [`HashJoinExec`]: crate::joins::HashJoinExec
+#[derive(Debug)]
+pub struct StarJoinExec {
+    /// Fact input, which is streamed
+    fact: Arc<dyn ExecutionPlan>,
+    /// Dimension inputs, which are collected into hash tables
+    dimensions: Vec<StarJoinDimension>,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// The hash tables of the dimensions, built once for all partitions
+    tables: Arc<OnceAsync<Vec<DimensionTable>>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: Arc<PlanProperties>,
+}
+
+impl StarJoinExec {
+    /// Tries to create a new [`StarJoinExec`].
+    ///
+    /// # Error
+    /// This function errors when there are no dimensions, when a dimension has
+    /// no join keys, or when the keys of the fact input and a dimension have
+    /// different types.
+    pub fn try_new(
+        fact: Arc<dyn ExecutionPlan>,
+        dimensions: Vec<StarJoinDimension>,
+    ) -> Result<Self> {
+        if dimensions.is_empty() {
+            return plan_err!("StarJoinExec requires at least one dimension");
+        }
+
+        let fact_schema = fact.schema();
+        for dimension in &dimensions {
+            if dimension.on.is_empty() {
+                return plan_err!("StarJoinExec requires join keys for every dimension");
+            }
+            let dimension_schema = dimension.input.schema();
+            for (f, d) in &dimension.on {
+                let fact_type = f.data_type(&fact_schema)?;
+                let dimension_type = d.data_type(&dimension_schema)?;
+                if fact_type != dimension_type {
+                    return plan_err!(
+                        "StarJoinExec requires join keys of the same type, got {f}: {fact_type} and {d}: {dimension_type}"
+                    );
+                }
+            }
+        }
+
+        let mut fields = fact_schema.fields().to_vec();
+        let mut metadata = fact_schema.metadata().clone();
+        for dimension in &dimensions {
+            let dimension_schema = dimension.input.schema();
+            fields.extend(dimension_schema.fields().iter().cloned());
+            metadata.extend(dimension_schema.metadata().clone());
+        }
+        let schema = Arc::new(Schema::new_with_metadata(fields, metadata));
+        let cache = Self::compute_properties(&fact, &dimensions)?;
+
+        Ok(Self {
+            fact,
+            dimensions,
+            schema,
+            tables: Default::default(),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: Arc::new(cache),
+        })
+    }
+
+    /// Fact input
+    pub fn fact(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.fact
+    }
+
+    /// Dimension inputs and their join keys
+    pub fn dimensions(&self) -> &[StarJoinDimension] {
+        &self.dimensions
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        fact: &Arc<dyn ExecutionPlan>,
+        dimensions: &[StarJoinDimension],
+    ) -> Result<PlanProperties> {
+        // The output is equivalent to a chain of inner joins, each of which
+        // keeps the order of the fact input
+        let mut fields = fact.schema().fields().to_vec();
+        let mut eq_properties = fact.equivalence_properties().clone();
+        for dimension in dimensions {
+            fields.extend(dimension.input.schema().fields().iter().cloned());
+            eq_properties = join_equivalence_properties(
+                eq_properties,
+                dimension.input.equivalence_properties().clone(),
+                &JoinType::Inner,
+                Arc::new(Schema::new(fields.clone())),
+                &[true, false],
+                Some(JoinSide::Left),
+                &dimension.on,
+            )?;
+        }
+
+        let children = std::iter::once(fact)
+            .chain(dimensions.iter().map(|dimension| &dimension.input))
+            .collect::<Vec<_>>();
+        Ok(PlanProperties::new(
+            eq_properties,
+            fact.output_partitioning().clone(),
+            EmissionType::Incremental,
+            boundedness_from_children(children),
+        ))
+    }
+
+    fn with_new_children_and_same_properties(
+        &self,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Self {
+        let fact = children.remove(0);
+        let dimensions = self
+            .dimensions
+            .iter()
+            .zip(children)
+            .map(|(dimension, input)| StarJoinDimension::new(input, dimension.on.clone()))
+            .collect();
+        Self {
+            fact,
+            dimensions,
+            schema: Arc::clone(&self.schema),
+            tables: Default::default(),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache: Arc::clone(&self.cache),
+        }
+    }
+}
+
+impl DisplayAs for StarJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let dimensions = self
+                    .dimensions
+                    .iter()
+                    .map(|dimension| {
+                        let on = dimension
+                            .on
+                            .iter()
+                            .map(|(c1, c2)| format!("({c1}, {c2})"))
+                            .collect::<Vec<String>>()
+                            .join(", ");
+                        format!("[{on}]")
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "StarJoinExec: on=[{dimensions}]")
+            }
+            DisplayFormatType::TreeRender => {
+                for (i, dimension) in self.dimensions.iter().enumerate() {
+                    let on = dimension
+                        .on
+                        .iter()
+                        .map(|(c1, c2)| {
+                            format!(
+                                "({} = {})",
+                                fmt_sql(c1.as_ref()),
+                                fmt_sql(c2.as_ref())
+                            )
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    writeln!(f, "on_{i}={on}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for StarJoinExec {
+    fn name(&self) -> &'static str {
+        "StarJoinExec"
+    }
+
+    fn properties(&self) -> &Arc<PlanProperties> {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        std::iter::once(Distribution::UnspecifiedDistribution)
+            .chain(
+                self.dimensions
+                    .iter()
+                    .map(|_| Distribution::SinglePartition),
+            )
+            .collect()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        std::iter::once(true)
+            .chain(self.dimensions.iter().map(|_| false))
+            .collect()
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        std::iter::once(&self.fact)
+            .chain(self.dimensions.iter().map(|dimension| &dimension.input))
+            .collect()
+    }
+
+    fn apply_expressions(
+        &self,
+        f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        let mut tnr = TreeNodeRecursion::Continue;
+        for (fact, dimension) in self.dimensions.iter().flat_map(|d| &d.on) {
+            tnr = tnr.visit_sibling(|| f(fact.as_ref()))?;
+            tnr = tnr.visit_sibling(|| f(dimension.as_ref()))?;
+        }
+        Ok(tnr)
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        check_if_same_properties!(self, children);
+        if children.len() != self.dimensions.len() + 1 {
+            return internal_err!(
+                "StarJoinExec wrong number of children {}",
+                children.len()
+            );
+        }
+        let fact = children.remove(0);
+        let dimensions = self
+            .dimensions
+            .iter()
+            .zip(children)
+            .map(|(dimension, input)| StarJoinDimension::new(input, dimension.on.clone()))
+            .collect();
+        Ok(Arc::new(StarJoinExec::try_new(fact, dimensions)?))
+    }
+
+    fn reset_state(self: Arc<Self>) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(self.with_new_children_and_same_properties(
+            self.children().into_iter().cloned().collect(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let tables = self.tables.try_once(|| {
+            let dimensions = self.dimensions.clone();
+            let context = Arc::clone(&context);
+            Ok(async move {
+                futures::future::try_join_all(dimensions.into_iter().enumerate().map(
+                    |(i, dimension)| {
+                        collect_dimension(i, dimension, Arc::clone(&context))
+                    },
+                ))
+                .await
+            })
+        })?;
+        let fact = self.fact.execute(partition, Arc::clone(&context))?;
+
+        let state = StarJoinState {
+            fact,
+            tables,
+            fact_on: self
+                .dimensions
+                .iter()
+                .map(|dimension| {
+                    dimension.on.iter().map(|(f, _)| Arc::clone(f)).collect()
+                })
+                .collect(),
+            schema: Arc::clone(&self.schema),
+            batch_size: context.session_config().batch_size(),
+            pending: VecDeque::new(),
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        };
+
+        let stream = futures::stream::try_unfold(state, |mut state| async move {
+            let tables =
+                futures::future::poll_fn(|cx| state.tables.get_shared(cx)).await?;
+            let elapsed_compute = state.baseline_metrics.elapsed_compute().clone();
+            loop {
+                if let Some(output) = state.pending.pop_front() {
+                    state.baseline_metrics.record_output(output.num_rows());
+                    return Ok(Some((output, state)));
+                }
+                let Some(fact_batch) = state.fact.next().await.transpose()? else {
+                    state.baseline_metrics.done();
+                    return Ok(None);
+                };
+                if fact_batch.num_rows() == 0 {
+                    continue;
+                }
+
+                let timer = elapsed_compute.timer();
+                let output = state.join_batch(&tables, &fact_batch)?;
+                for offset in (0..output.num_rows()).step_by(state.batch_size) {
+                    let length = state.batch_size.min(output.num_rows() - offset);
+                    state.pending.push_back(output.slice(offset, length));
+                }
+                timer.done();
+            }
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::Unknown
+    }
+}
+
+/// The hash table of a dimension
+struct DimensionTable {
+    /// All rows of the dimension
+    batch: RecordBatch,
+    /// Converts the join keys to row format
+    converter: RowConverter,
+    /// The indices of the rows of `batch` with each join key in row format
+    map: HashMap<Box<[u8]>, Vec<u32>>,
+    /// Accounts for the memory used by the hash table
+    _reservation: MemoryReservation,
+}
+
+/// Collects the `i`th dimension of a [`StarJoinExec`] into a hash table
+async fn collect_dimension(
+    i: usize,
+    dimension: StarJoinDimension,
+    context: Arc<TaskContext>,
+) -> Result<DimensionTable> {
+    let schema = dimension.input.schema();
+    let reservation = MemoryConsumer::new(format!("StarJoinExec[dimension {i}]"))
+        .register(context.memory_pool());
+    let batches = collect(dimension.input, context).await?;
+    let batch = concat_batches(&schema, &batches)?;
+    reservation.try_grow(get_record_batch_memory_size(&batch))?;
+
+    let num_rows = batch.num_rows();
+    let keys = dimension
+        .on
+        .iter()
+        .map(|(_, d)| d.evaluate(&batch)?.into_array(num_rows))
+        .collect::<Result<Vec<_>>>()?;
+    let converter = RowConverter::new(
+        keys.iter()
+            .map(|key| SortField::new(key.data_type().clone()))
+            .collect(),
+    )?;
+    let rows = converter.convert_columns(&keys)?;
+    let nulls = null_keys(&keys);
+
+    let mut map = HashMap::<Box<[u8]>, Vec<u32>>::new();
+    for row in 0..num_rows {
+        if nulls.as_ref().is_some_and(|nulls| nulls.is_null(row)) {
+            continue;
+        }
+        let key = rows.row(row);
+        match map.get_mut(key.as_ref()) {
+            Some(indices) => indices.push(row as u32),
+            None => {
+                map.insert(key.as_ref().into(), vec![row as u32]);
+            }
+        }
+    }
+    reservation.try_grow(
+        rows.size()
+            + num_rows * size_of::<u32>()
+            + map.capacity() * size_of::<(Box<[u8]>, Vec<u32>)>(),
+    )?;
+
+    Ok(DimensionTable {
+        batch,
+        converter,
+        map,
+        _reservation: reservation,
+    })
+}
+
+/// Returns the rows with a null in any of `keys`, which never match
+fn null_keys(keys: &[ArrayRef]) -> Option<NullBuffer> {
+    keys.iter().fold(None, |nulls, key| {
+        NullBuffer::union(nulls.as_ref(), key.logical_nulls().as_ref())
+    })
+}
+
+struct StarJoinState {
+    fact: SendableRecordBatchStream,
+    tables: OnceFut<Vec<DimensionTable>>,
+    /// The join keys of every dimension on the fact side
+    fact_on: Vec<Vec<PhysicalExprRef>>,
+    schema: SchemaRef,
+    batch_size: usize,
+    /// Output batches that have not been returned yet
+    pending: VecDeque<RecordBatch>,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl StarJoinState {
+    /// Joins a batch of the fact input with all dimensions
+    fn join_batch(
+        &self,
+        tables: &[DimensionTable],
+        fact_batch: &RecordBatch,
+    ) -> Result<RecordBatch> {
+        let num_rows = fact_batch.num_rows();
+        // The fact rows matched so far, and the rows of each dimension they
+        // were matched with
+        let mut fact_indices = (0..num_rows as u32).collect::<Vec<_>>();
+        let mut dimension_indices: Vec<Vec<u32>> = Vec::with_capacity(tables.len());
+        for (table, on) in tables.iter().zip(&self.fact_on) {
+            let keys = on
+                .iter()
+                .map(|f| f.evaluate(fact_batch)?.into_array(num_rows))
+                .collect::<Result<Vec<_>>>()?;
+            let nulls = null_keys(&keys);
+            let rows = table.converter.convert_columns(&keys)?;
+
+            let mut next_fact_indices = vec![];
+            let mut next_dimension_indices = vec![vec![]; dimension_indices.len() + 1];
+            for (i, fact_row) in fact_indices.iter().enumerate() {
+                let row = *fact_row as usize;
+                if nulls.as_ref().is_some_and(|nulls| nulls.is_null(row)) {
+                    continue;
+                }
+                let Some(matches) = table.map.get(rows.row(row).as_ref()) else {
+                    continue;
+                };
+                for dimension_row in matches {
+                    next_fact_indices.push(*fact_row);
+                    for (next, previous) in
+                        next_dimension_indices.iter_mut().zip(&dimension_indices)
+                    {
+                        next.push(previous[i]);
+                    }
+                    next_dimension_indices[dimension_indices.len()].push(*dimension_row);
+                }
+            }
+            fact_indices = next_fact_indices;
+            dimension_indices = next_dimension_indices;
+
+            if fact_indices.is_empty() {
+                return Ok(RecordBatch::new_empty(Arc::clone(&self.schema)));
+            }
+        }
+
+        let fact_indices = UInt32Array::from(fact_indices);
+        let mut columns = fact_batch
+            .columns()
+            .iter()
+            .map(|column| Ok(take(column, &fact_indices, None)?))
+            .collect::<Result<Vec<_>>>()?;
+        for (table, indices) in tables.iter().zip(dimension_indices) {
+            let indices = UInt32Array::from(indices);
+            for column in table.batch.columns() {
+                columns.push(take(column, &indices, None)?);
+            }
+        }
+        Ok(RecordBatch::try_new_with_options(
+            Arc::clone(&self.schema),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(fact_indices.len())),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::test::TestMemoryExec;
+    use crate::test::build_table_i32;
+
+    use arrow::datatypes::{DataType, Field};
+    use datafusion_common::test_util::batches_to_string;
+    use datafusion_execution::config::SessionConfig;
+    use datafusion_physical_expr::expressions::col;
+    use insta::{allow_duplicates, assert_snapshot};
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+        batch_size: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        let batches = (0..batch.num_rows())
+            .step_by(batch_size)
+            .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+            .collect::<Vec<_>>();
+        TestMemoryExec::try_new_exec(&[batches], schema, None).unwrap()
+    }
+
+    /// Sales of product `product` in store `store`
+    fn sales(batch_size: usize) -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("id", &vec![1, 2, 3, 4, 5]),
+            ("store", &vec![10, 20, 10, 30, 20]),
+            ("product", &vec![100, 100, 200, 100, 300]),
+            batch_size,
+        )
+    }
+
+    fn stores() -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("store_id", &vec![10, 20, 20]),
+            ("city", &vec![1, 2, 3]),
+            ("size", &vec![7, 8, 9]),
+            2,
+        )
+    }
+
+    fn products() -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("product_id", &vec![100, 200]),
+            ("price", &vec![5, 6]),
+            ("weight", &vec![1, 2]),
+            1,
+        )
+    }
+
+    fn star_join(fact: Arc<dyn ExecutionPlan>) -> Result<StarJoinExec> {
+        let stores = stores();
+        let products = products();
+        let dimensions = vec![
+            StarJoinDimension::new(
+                Arc::clone(&stores),
+                vec![(
+                    col("store", &fact.schema())?,
+                    col("store_id", &stores.schema())?,
+                )],
+            ),
+            StarJoinDimension::new(
+                Arc::clone(&products),
+                vec![(
+                    col("product", &fact.schema())?,
+                    col("product_id", &products.schema())?,
+                )],
+            ),
+        ];
+        StarJoinExec::try_new(fact, dimensions)
+    }
+
+    #[tokio::test]
+    async fn star_join_matches_all_dimensions() -> Result<()> {
+        for (batch_size, output_batch_size) in [(1, 8192), (2, 1), (5, 2)] {
+            let join = star_join(sales(batch_size))?;
+            let context = TaskContext::default().with_session_config(
+                SessionConfig::new().with_batch_size(output_batch_size),
+            );
+            let stream = join.execute(0, Arc::new(context))?;
+            let batches = common::collect(stream).await?;
+            assert!(batches.iter().all(|b| b.num_rows() <= output_batch_size));
+            allow_duplicates! {
+                assert_snapshot!(batches_to_string(&batches), @r"
+                +----+-------+---------+----------+------+------+------------+-------+--------+
+                | id | store | product | store_id | city | size | product_id | price | weight |
+                +----+-------+---------+----------+------+------+------------+-------+--------+
+                | 1  | 10    | 100     | 10       | 1    | 7    | 100        | 5     | 1      |
+                | 2  | 20    | 100     | 20       | 2    | 8    | 100        | 5     | 1      |
+                | 2  | 20    | 100     | 20       | 3    | 9    | 100        | 5     | 1      |
+                | 3  | 10    | 200     | 10       | 1    | 7    | 200        | 6     | 2      |
+                +----+-------+---------+----------+------+------+------------+-------+--------+
+                ");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn star_join_rejects_mismatched_key_types() -> Result<()> {
+        let fact = sales(1);
+        let dimension = TestMemoryExec::try_new_exec(
+            &[vec![]],
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)])),
+            None,
+        )?;
+        let on = vec![(
+            col("store", &fact.schema())?,
+            col("id", &dimension.schema())?,
+        )];
+        let err =
+            StarJoinExec::try_new(fact, vec![StarJoinDimension::new(dimension, on)])
+                .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: StarJoinExec requires join keys of the same type, got store@1: Int32 and id@0: Int64"
+        );
+        Ok(())
+    }
+}
@@ -40,6 +40,7 @@ pub use datafusion_physical_expr::{
     Distribution, Partitioning, PhysicalExpr, expressions,
 };
 
+pub use crate::analyze::collect_plan_metrics_json;
 pub use crate::display::{DefaultDisplay, DisplayAs, DisplayFormatType, VerboseDisplay};
 pub use crate::execution_plan::{
     ExecutionPlan, ExecutionPlanProperties, PlanProperties, collect, collect_partitioned,
@@ -57,6 +58,7 @@ pub use spill::spill_manager::SpillManager;
 
 mod ordering;
 mod render_tree;
+mod resource_limits;
 mod topk;
 mod visitor;
 
@@ -75,25 +77,34 @@ pub mod empty;
 pub mod execution_plan;
 pub mod explain;
 pub mod filter;
+pub mod filter_project;
 pub mod filter_pushdown;
+#[cfg(feature = "tracing")]
+pub mod instrument;
 pub mod joins;
 pub mod limit;
 pub mod memory;
 pub mod metrics;
 pub mod operator_statistics;
 pub mod placeholder_row;
+pub mod plan_span;
 pub mod projection;
 pub mod recursive_query;
 pub mod repartition;
+pub mod result_limit;
+pub mod rewrite;
 pub mod scalar_subquery;
 pub mod sort_pushdown;
 pub mod sorts;
 pub mod spill;
+pub mod spool;
+pub mod statistics_collector;
 pub mod stream;
 pub mod streaming;
 pub mod tree_node;
 pub mod union;
 pub mod unnest;
+pub mod values;
 pub mod windows;
 pub mod work_table;
 pub mod udaf {
@@ -32,8 +32,11 @@ use crate::{
 
 use arrow::array::RecordBatch;
 use arrow::datatypes::SchemaRef;
+use datafusion_common::stats::Precision;
 use datafusion_common::tree_node::TreeNodeRecursion;
-use datafusion_common::{Result, assert_eq_or_internal_err, assert_or_internal_err};
+use datafusion_common::{
+    Result, Statistics, assert_eq_or_internal_err, assert_or_internal_err,
+};
 use datafusion_execution::TaskContext;
 use datafusion_execution::memory_pool::MemoryReservation;
 use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
@@ -143,6 +146,11 @@ pub trait LazyBatchGenerator: Send + Sync + fmt::Debug + fmt::Display {
         Boundedness::Bounded
     }
 
+    /// Returns the total number of rows generated from the start, if known
+    fn num_rows(&self) -> Option<usize> {
+        None
+    }
+
     /// Generate the next batch, return `None` when no more batches are available
     fn generate_next_batch(&mut self) -> Result<Option<RecordBatch>>;
 
@@ -361,6 +369,30 @@ impl ExecutionPlan for LazyMemoryExec {
         Some(self.metrics.clone_inner())
     }
 
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Arc<Statistics>> {
+        let generators = match partition {
+            Some(partition) => {
+                assert_or_internal_err!(
+                    partition < self.batch_generators.len(),
+                    "Invalid partition {} for LazyMemoryExec with {} partitions",
+                    partition,
+                    self.batch_generators.len()
+                );
+                &self.batch_generators[partition..=partition]
+            }
+            None => self.batch_generators.as_slice(),
+        };
+        let num_rows = generators
+            .iter()
+            .map(|g| g.read().num_rows())
+            .sum::<Option<usize>>()
+            .map(Precision::Exact)
+            .unwrap_or(Precision::Absent);
+        Ok(Arc::new(
+            Statistics::new_unknown(&self.schema).with_num_rows(num_rows),
+        ))
+    }
+
     fn reset_state(self: Arc<Self>) -> Result<Arc<dyn ExecutionPlan>> {
         let generators = self
             .generators()
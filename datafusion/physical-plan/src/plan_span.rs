@@ -0,0 +1,171 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Build the [`PlanSpan`] tree of an executed plan, and export it with the
+//! [`PlanSpanExporter`] of the runtime once the execution completes
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::SystemTime;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::plan_span::{PlanSpan, PlanSpanExporter};
+use datafusion_execution::{RecordBatchStream, SendableRecordBatchStream};
+use futures::{Stream, StreamExt};
+
+use crate::ExecutionPlan;
+use crate::display::DisplayableExecutionPlan;
+use crate::metrics::MetricValue;
+
+/// Build the [`PlanSpan`] tree of `plan`, from the metrics collected while
+/// executing it.
+///
+/// Each operator is described by a span named after the operator, with the
+/// earliest start time and the latest end time of its partitions, and with
+/// its one line description (as the `operator` attribute) and its metrics,
+/// aggregated over all partitions, as attributes.
+pub fn plan_span(plan: &dyn ExecutionPlan) -> PlanSpan {
+    let mut span = PlanSpan::new(plan.name());
+    span.attributes.push((
+        "operator".to_string(),
+        DisplayableExecutionPlan::new(plan).one_line().to_string(),
+    ));
+
+    if let Some(metrics) = plan.metrics() {
+        let metrics = metrics.aggregate_by_name().sorted_for_display();
+        for metric in metrics.iter() {
+            match metric.value() {
+                MetricValue::StartTimestamp(timestamp) => {
+                    span.start_time = timestamp.value().map(SystemTime::from);
+                }
+                MetricValue::EndTimestamp(timestamp) => {
+                    span.end_time = timestamp.value().map(SystemTime::from);
+                }
+                value => span
+                    .attributes
+                    .push((value.name().to_string(), value.to_string())),
+            }
+        }
+    }
+
+    span.children = plan
+        .children()
+        .into_iter()
+        .map(|child| plan_span(child.as_ref()))
+        .collect();
+    span
+}
+
+/// Stream that exports the [`PlanSpan`] tree of the plan producing it once
+/// it is exhausted or fails.
+pub(crate) struct PlanSpanExportStream {
+    input: SendableRecordBatchStream,
+    /// The executed plan, until its profile is exported
+    plan: Option<Arc<dyn ExecutionPlan>>,
+    exporter: Arc<dyn PlanSpanExporter>,
+}
+
+impl PlanSpanExportStream {
+    pub(crate) fn new(
+        input: SendableRecordBatchStream,
+        plan: Arc<dyn ExecutionPlan>,
+        exporter: Arc<dyn PlanSpanExporter>,
+    ) -> Self {
+        Self {
+            input,
+            plan: Some(plan),
+            exporter,
+        }
+    }
+}
+
+impl Stream for PlanSpanExportStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.input.poll_next_unpin(cx);
+        if matches!(poll, Poll::Ready(None | Some(Err(_))))
+            && let Some(plan) = self.plan.take()
+        {
+            self.exporter.export(&plan_span(plan.as_ref()));
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl RecordBatchStream for PlanSpanExportStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use datafusion_execution::TaskContext;
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+
+    use super::*;
+    use crate::execution_plan::collect;
+    use crate::test::scan_partitioned;
+
+    #[derive(Debug, Default)]
+    struct CollectingExporter {
+        spans: Mutex<Vec<PlanSpan>>,
+    }
+
+    impl PlanSpanExporter for CollectingExporter {
+        fn export(&self, span: &PlanSpan) {
+            self.spans.lock().unwrap().push(span.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn export_plan_spans() -> Result<()> {
+        let exporter = Arc::new(CollectingExporter::default());
+        let runtime = RuntimeEnvBuilder::new()
+            .with_plan_span_exporter(Arc::clone(&exporter) as _)
+            .build_arc()?;
+        let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        let batches = collect(scan_partitioned(2), task_ctx).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 200);
+
+        let spans = exporter.spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        // the partitions are merged with a `CoalescePartitionsExec`
+        let root = &spans[0];
+        assert_eq!(root.name, "CoalescePartitionsExec");
+        assert_eq!(root.attribute("output_rows"), Some("200"));
+        assert!(root.start_time.is_some());
+        assert!(root.start_time <= root.end_time);
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].children, vec![]);
+        Ok(())
+    }
+}
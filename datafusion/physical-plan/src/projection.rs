@@ -27,6 +27,7 @@ use super::{
     SendableRecordBatchStream, SortOrderPushdownResult, Statistics,
 };
 use crate::column_rewriter::PhysicalColumnRewriter;
+use crate::coop::PollTimeBudget;
 use crate::execution_plan::CardinalityEffect;
 use crate::filter_pushdown::{
     ChildFilterDescription, ChildPushdownResult, FilterDescription, FilterPushdownPhase,
@@ -37,7 +38,7 @@ use crate::{DisplayFormatType, ExecutionPlan, PhysicalExpr, check_if_same_proper
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::task::{Context, Poll};
+use std::task::{Context, Poll, ready};
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
@@ -348,10 +349,12 @@ impl ExecutionPlan for ProjectionExec {
         );
 
         let projector = self.projector.with_metrics(&self.metrics, partition);
+        let time_budget = PollTimeBudget::new(&context, &self.metrics, partition);
         Ok(Box::pin(ProjectionStream::new(
             projector,
             self.input.execute(partition, context)?,
             BaselineMetrics::new(&self.metrics, partition),
+            time_budget,
         )?))
     }
 
@@ -514,18 +517,21 @@ impl ProjectionStream {
         projector: Projector,
         input: SendableRecordBatchStream,
         baseline_metrics: BaselineMetrics,
+        time_budget: PollTimeBudget,
     ) -> Result<Self> {
         Ok(Self {
             projector,
             input,
             baseline_metrics,
+            time_budget,
         })
     }
 
-    fn batch_project(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+    fn batch_project(&mut self, batch: &RecordBatch) -> Result<RecordBatch> {
         // Records time on drop
         let _timer = self.baseline_metrics.elapsed_compute().timer();
-        self.projector.project_batch(batch)
+        let projector = &self.projector;
+        self.time_budget.record(|| projector.project_batch(batch))
     }
 }
 
@@ -534,6 +540,7 @@ struct ProjectionStream {
     projector: Projector,
     input: SendableRecordBatchStream,
     baseline_metrics: BaselineMetrics,
+    time_budget: PollTimeBudget,
 }
 
 impl Stream for ProjectionStream {
@@ -543,7 +550,12 @@ impl Stream for ProjectionStream {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let poll = self.input.poll_next_unpin(cx).map(|x| match x {
+        ready!(self.time_budget.checkpoint(cx));
+        let poll = self.input.poll_next_unpin(cx);
+        if poll.is_pending() {
+            self.time_budget.reset();
+        }
+        let poll = poll.map(|x| match x {
             Some(Ok(batch)) => Some(self.batch_project(&batch)),
             other => other,
         });
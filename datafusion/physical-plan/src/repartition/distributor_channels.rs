@@ -135,6 +135,17 @@ impl<T> DistributionSender<T> {
             element: Box::new(Some(element)),
         }
     }
+
+    /// Number of elements buffered in the channel, not yet received.
+    pub fn len(&self) -> usize {
+        self.channel
+            .state
+            .lock()
+            .data
+            .as_ref()
+            .map(VecDeque::len)
+            .unwrap_or_default()
+    }
 }
 
 impl<T> Clone for DistributionSender<T> {
@@ -359,6 +359,18 @@ impl RepartitionExecState {
             );
         }
 
+        // Bound the batches buffered for an output partition that lags behind,
+        // as long as they can be spilled instead
+        let max_buffered_batches = context
+            .session_config()
+            .options()
+            .execution
+            .preserve_order_repartition_max_buffered_batches;
+        let max_buffered_batches = (preserve_order
+            && max_buffered_batches > 0
+            && context.runtime_env().disk_manager.tmp_files_enabled())
+        .then_some(max_buffered_batches);
+
         // launch one async task per *input* partition
         let mut spawned_tasks = Vec::with_capacity(num_input_partitions);
         for (i, (stream, metrics)) in
@@ -396,6 +408,7 @@ impl RepartitionExecState {
                 // preserve_order depends on partition index to start from 0
                 if preserve_order { 0 } else { i },
                 num_input_partitions,
+                max_buffered_batches,
             ));
 
             // In a separate task, wait for each input to be done
@@ -1060,7 +1073,8 @@ impl ExecutionPlan for RepartitionExec {
             Arc::clone(&context.runtime_env()),
             spill_metrics,
             input.schema(),
-        );
+        )
+        .with_resource_tracker(context.resource_tracker().cloned());
 
         // Get existing ordering to use for merging
         let sort_exprs = self.sort_exprs().cloned();
@@ -1430,6 +1444,7 @@ impl RepartitionExec {
         metrics: RepartitionMetrics,
         input_partition: usize,
         num_input_partitions: usize,
+        max_buffered_batches: Option<usize>,
     ) -> Result<()> {
         let mut partitioner = match &partitioning {
             Partitioning::Hash(exprs, num_partitions) => {
@@ -1478,19 +1493,20 @@ impl RepartitionExec {
                 let timer = metrics.send_time[partition].timer();
                 // if there is still a receiver, send to it
                 if let Some(channel) = output_channels.get_mut(&partition) {
+                    // The output partition lags behind this input
+                    let is_lagging = max_buffered_batches
+                        .is_some_and(|max| channel.sender.len() >= max);
                     let (batch_to_send, is_memory_batch) =
-                        match channel.reservation.try_grow(size) {
-                            Ok(_) => {
-                                // Memory available - send in-memory batch
-                                (RepartitionBatch::Memory(batch), true)
-                            }
-                            Err(_) => {
-                                // We're memory limited - spill to SpillPool
-                                // SpillPool handles file handle reuse and rotation
-                                channel.spill_writer.push_batch(&batch)?;
-                                // Send marker indicating batch was spilled
-                                (RepartitionBatch::Spilled, false)
-                            }
+                        if !is_lagging && channel.reservation.try_grow(size).is_ok() {
+                            // Memory available - send in-memory batch
+                            (RepartitionBatch::Memory(batch), true)
+                        } else {
+                            // We're memory limited or the output partition lags
+                            // behind - spill to SpillPool
+                            // SpillPool handles file handle reuse and rotation
+                            channel.spill_writer.push_batch(&batch)?;
+                            // Send marker indicating batch was spilled
+                            (RepartitionBatch::Spilled, false)
                         };
 
                     if channel.sender.send(Some(Ok(batch_to_send))).await.is_err() {
@@ -2794,7 +2810,7 @@ mod tests {
 
 #[cfg(test)]
 mod test {
-    use arrow::array::record_batch;
+    use arrow::array::{AsArray, record_batch};
     use arrow::compute::SortOptions;
     use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::assert_batches_eq;
@@ -3005,6 +3021,80 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_preserve_order_spills_lagging_partitions() -> Result<()> {
+        use datafusion_execution::config::SessionConfig;
+
+        // Both inputs are sorted, each output partition receives 20 batches
+        let batches = |start: u32| {
+            (0..40)
+                .map(|i| {
+                    let value = start + 2 * i;
+                    record_batch!(("c0", UInt32, [value])).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        let input_partitions = vec![batches(0), batches(1)];
+        let schema = input_partitions[0][0].schema();
+        let sort_exprs = sort_exprs(&schema);
+
+        let run = |max_buffered_batches: usize| {
+            let input_partitions = input_partitions.clone();
+            let schema = Arc::clone(&schema);
+            let sort_exprs = sort_exprs.clone();
+            async move {
+                let mut session_config = SessionConfig::new();
+                session_config
+                    .options_mut()
+                    .execution
+                    .preserve_order_repartition_max_buffered_batches =
+                    max_buffered_batches;
+                let task_ctx =
+                    Arc::new(TaskContext::default().with_session_config(session_config));
+
+                let exec = TestMemoryExec::try_new(&input_partitions, schema, None)?
+                    .try_with_sort_information(vec![sort_exprs.clone(), sort_exprs])?;
+                let exec = Arc::new(TestMemoryExec::update_cache(&Arc::new(exec)));
+                let exec =
+                    RepartitionExec::try_new(exec, Partitioning::RoundRobinBatch(2))?
+                        .with_preserve_order();
+
+                // Drain the output partitions one after the other, so that
+                // the batches of the second one pile up meanwhile
+                let mut outputs = vec![];
+                for i in 0..2 {
+                    let stream = exec.execute(i, Arc::clone(&task_ctx))?;
+                    outputs.push(crate::common::collect(stream).await?);
+                }
+                let spill_count = exec.metrics().unwrap().spill_count().unwrap_or(0);
+                Ok::<_, DataFusionError>((outputs, spill_count))
+            }
+        };
+
+        let (outputs, spill_count) = run(0).await?;
+        assert_eq!(spill_count, 0);
+
+        let (spilled_outputs, spill_count) = run(2).await?;
+        assert!(
+            spill_count > 0,
+            "Expected the lagging output partition to spill, but spill_count = 0"
+        );
+
+        // Spilling does not change the output
+        assert_eq!(outputs, spilled_outputs);
+        for output in &outputs {
+            let values = output
+                .iter()
+                .flat_map(|batch| batch.column(0).as_primitive::<UInt32Type>().values())
+                .copied()
+                .collect::<Vec<_>>();
+            assert_eq!(values.len(), 40);
+            assert!(values.is_sorted());
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_hash_partitioning_with_spilling() -> Result<()> {
         use datafusion_execution::runtime_env::RuntimeEnvBuilder;
@@ -0,0 +1,146 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Enforce the [`QueryResourceLimits`] of a query on its output streams
+//!
+//! [`QueryResourceLimits`]: datafusion_execution::resource_limits::QueryResourceLimits
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::resource_limits::QueryResourceTracker;
+use datafusion_execution::{RecordBatchStream, SendableRecordBatchStream};
+use futures::{Stream, StreamExt};
+
+/// Stream that checks the execution time of the query every time it is
+/// polled, and counts the rows it produces towards the output rows of the
+/// query. The stream ends after the first error.
+pub(crate) struct ResourceLimitedStream {
+    input: SendableRecordBatchStream,
+    tracker: Arc<QueryResourceTracker>,
+    done: bool,
+}
+
+impl ResourceLimitedStream {
+    pub(crate) fn new(
+        input: SendableRecordBatchStream,
+        tracker: Arc<QueryResourceTracker>,
+    ) -> Self {
+        Self {
+            input,
+            tracker,
+            done: false,
+        }
+    }
+}
+
+impl Stream for ResourceLimitedStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        let poll = match self.tracker.check_execution_time() {
+            Ok(()) => match self.input.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(batch))) => Poll::Ready(Some(
+                    self.tracker
+                        .add_output_rows(batch.num_rows())
+                        .map(|_| batch),
+                )),
+                poll => poll,
+            },
+            Err(e) => Poll::Ready(Some(Err(e))),
+        };
+        if matches!(poll, Poll::Ready(None | Some(Err(_)))) {
+            self.done = true;
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl RecordBatchStream for ResourceLimitedStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use datafusion_execution::TaskContext;
+    use datafusion_execution::resource_limits::QueryResourceLimits;
+
+    use super::*;
+    use crate::execution_plan::{collect, collect_partitioned};
+    use crate::test::scan_partitioned;
+
+    #[tokio::test]
+    async fn output_rows_limit() {
+        // 3 partitions of 100 rows
+        let plan = scan_partitioned(3);
+
+        let task_ctx = TaskContext::default()
+            .with_resource_limits(QueryResourceLimits::new().with_max_output_rows(300));
+        let batches = collect(Arc::clone(&plan), Arc::new(task_ctx))
+            .await
+            .unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 300);
+
+        // the limit applies across the partitions of the query
+        let task_ctx = TaskContext::default()
+            .with_resource_limits(QueryResourceLimits::new().with_max_output_rows(250));
+        let err = collect_partitioned(plan, Arc::new(task_ctx))
+            .await
+            .unwrap_err()
+            .strip_backtrace();
+        assert!(
+            err.starts_with(
+                "Resources exhausted: Query exceeded its max_output_rows limit of 250"
+            ),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn execution_time_limit() {
+        let task_ctx = TaskContext::default().with_resource_limits(
+            QueryResourceLimits::new().with_max_execution_time(Duration::ZERO),
+        );
+        let err = collect(scan_partitioned(1), Arc::new(task_ctx))
+            .await
+            .unwrap_err()
+            .strip_backtrace();
+        assert!(
+            err.contains("Query exceeded its max_execution_time limit of 0ns"),
+            "{err}"
+        );
+    }
+}
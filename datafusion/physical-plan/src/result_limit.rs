@@ -0,0 +1,283 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ResultLimiter`]: enforces `datafusion.execution.max_result_rows` and
+//! `datafusion.execution.max_result_bytes` on the results of a query
+
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::config::ResultLimitAction;
+use datafusion_common::diagnostic::WarningCollector;
+use datafusion_common::{Diagnostic, Result, resources_datafusion_err};
+use datafusion_execution::TaskContext;
+use futures::{StreamExt, stream};
+use parking_lot::Mutex;
+
+use crate::SendableRecordBatchStream;
+use crate::stream::RecordBatchStreamAdapter;
+
+/// Limits the number of rows and bytes returned by the streams of a query,
+/// as configured by `datafusion.execution.max_result_rows`,
+/// `datafusion.execution.max_result_bytes` and
+/// `datafusion.execution.result_limit_action`.
+///
+/// The limits apply to the total of all the streams limited by the same
+/// [`ResultLimiter`] (and its clones), e.g. to all the partitions of a query.
+/// Once the limits are exceeded, the streams either return an error, or
+/// return the rows up to the limits and end, after reporting a warning to
+/// the [`WarningCollector`] of the session, if any. Ending the streams
+/// drops their inputs, which stops the query.
+#[derive(Debug, Clone)]
+pub struct ResultLimiter {
+    max_rows: Option<usize>,
+    max_bytes: Option<usize>,
+    action: ResultLimitAction,
+    warnings: Option<Arc<WarningCollector>>,
+    state: Arc<Mutex<ResultLimitState>>,
+}
+
+/// The results returned so far by the streams of a [`ResultLimiter`]
+#[derive(Debug, Default)]
+struct ResultLimitState {
+    rows: usize,
+    bytes: usize,
+    exceeded: bool,
+}
+
+/// What a [`ResultLimiter`] lets through of a batch
+enum Admitted {
+    /// The whole batch
+    All(RecordBatch),
+    /// The first rows of the batch, after which the stream ends
+    Last(RecordBatch),
+    /// Nothing, the stream ends
+    None,
+}
+
+impl ResultLimiter {
+    /// Returns a [`ResultLimiter`] enforcing the limits configured in
+    /// `context`, or `None` if the results are not limited
+    pub fn try_new(context: &TaskContext) -> Option<Self> {
+        let config = context.session_config();
+        let options = &config.options().execution;
+        if options.max_result_rows.is_none() && options.max_result_bytes.is_none() {
+            return None;
+        }
+        Some(Self {
+            max_rows: options.max_result_rows,
+            max_bytes: options.max_result_bytes,
+            action: options.result_limit_action,
+            warnings: config.get_extension::<WarningCollector>(),
+            state: Default::default(),
+        })
+    }
+
+    /// Returns a stream returning the batches of `input` within the limits
+    pub fn limit_stream(
+        &self,
+        input: SendableRecordBatchStream,
+    ) -> SendableRecordBatchStream {
+        let schema = input.schema();
+        let limiter = self.clone();
+        let stream = stream::unfold(Some(input), move |input| {
+            let limiter = limiter.clone();
+            async move {
+                let mut input = input?;
+                let batch = match input.next().await? {
+                    Ok(batch) => batch,
+                    Err(e) => return Some((Err(e), None)),
+                };
+                match limiter.admit(batch) {
+                    Ok(Admitted::All(batch)) => Some((Ok(batch), Some(input))),
+                    Ok(Admitted::Last(batch)) => Some((Ok(batch), None)),
+                    Ok(Admitted::None) => None,
+                    Err(e) => Some((Err(e), None)),
+                }
+            }
+        });
+        Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+    }
+
+    /// Accounts for `batch`, and returns the part of it within the limits
+    fn admit(&self, batch: RecordBatch) -> Result<Admitted> {
+        let mut state = self.state.lock();
+        if state.exceeded {
+            return Ok(Admitted::None);
+        }
+
+        let bytes = batch.get_array_memory_size();
+        let mut rows = batch.num_rows();
+        let mut exceeded_limit = None;
+        if let Some(max_rows) = self.max_rows {
+            let remaining = max_rows.saturating_sub(state.rows);
+            if rows > remaining {
+                rows = remaining;
+                exceeded_limit = Some(format!("{max_rows} rows (max_result_rows)"));
+            }
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            let remaining = max_bytes.saturating_sub(state.bytes);
+            if bytes > remaining {
+                // keep the rows fitting in the remaining bytes, assuming
+                // they all have the same size
+                let row_bytes = bytes.div_ceil(batch.num_rows().max(1));
+                let fitting_rows = remaining / row_bytes.max(1);
+                if fitting_rows < rows {
+                    rows = fitting_rows;
+                    exceeded_limit =
+                        Some(format!("{max_bytes} bytes (max_result_bytes)"));
+                }
+            }
+        }
+
+        let Some(exceeded_limit) = exceeded_limit else {
+            state.rows += rows;
+            state.bytes += bytes;
+            return Ok(Admitted::All(batch));
+        };
+        state.exceeded = true;
+        match self.action {
+            ResultLimitAction::Error => Err(resources_datafusion_err!(
+                "The results of the query exceed the limit of {exceeded_limit}"
+            )),
+            ResultLimitAction::Truncate => {
+                if let Some(warnings) = &self.warnings {
+                    warnings.report(Diagnostic::new_warning(
+                        format!(
+                            "The results of the query were truncated to the limit of {exceeded_limit}"
+                        ),
+                        None,
+                    ));
+                }
+                state.rows += rows;
+                Ok(Admitted::Last(batch.slice(0, rows)))
+            }
+        }
+    }
+}
+
+/// Limits `input` as configured in `context`, see [`ResultLimiter`]
+pub fn limit_result_stream(
+    input: SendableRecordBatchStream,
+    context: &TaskContext,
+) -> SendableRecordBatchStream {
+    match ResultLimiter::try_new(context) {
+        Some(limiter) => limiter.limit_stream(input),
+        None => input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common::collect;
+    use crate::test::exec::{BlockingExec, assert_strong_count_converges_to_zero};
+    use crate::test::scan_partitioned;
+    use crate::{ExecutionPlan, execute_stream};
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::assert_contains;
+    use datafusion_execution::config::SessionConfig;
+
+    fn task_ctx(max_rows: Option<usize>, action: &str) -> Arc<TaskContext> {
+        let mut config = SessionConfig::new()
+            .set_str("datafusion.execution.result_limit_action", action)
+            .with_extension(Arc::new(WarningCollector::new()));
+        config.options_mut().execution.max_result_rows = max_rows;
+        Arc::new(TaskContext::default().with_session_config(config))
+    }
+
+    fn num_rows(batches: &[RecordBatch]) -> usize {
+        batches.iter().map(|batch| batch.num_rows()).sum()
+    }
+
+    #[tokio::test]
+    async fn results_within_limit_are_returned() -> Result<()> {
+        let plan = scan_partitioned(1);
+        let task_ctx = task_ctx(Some(1000), "error");
+        let expected = crate::collect(Arc::clone(&plan), Arc::clone(&task_ctx)).await?;
+
+        let stream = execute_stream(plan, Arc::clone(&task_ctx))?;
+        let batches = collect(limit_result_stream(stream, &task_ctx)).await?;
+        assert_eq!(batches, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn results_exceeding_limit_error() -> Result<()> {
+        let plan = scan_partitioned(1);
+        let task_ctx = task_ctx(Some(3), "error");
+
+        let stream = execute_stream(plan, Arc::clone(&task_ctx))?;
+        let err = collect(limit_result_stream(stream, &task_ctx))
+            .await
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "The results of the query exceed the limit of 3 rows (max_result_rows)"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn results_exceeding_limit_are_truncated() -> Result<()> {
+        let plan = scan_partitioned(2);
+        let task_ctx = task_ctx(Some(3), "truncate");
+
+        let limiter = ResultLimiter::try_new(&task_ctx).unwrap();
+        let mut batches = vec![];
+        for partition in 0..2 {
+            let stream = plan.execute(partition, Arc::clone(&task_ctx))?;
+            batches.extend(collect(limiter.limit_stream(stream)).await?);
+        }
+        assert_eq!(num_rows(&batches), 3);
+
+        let warnings = task_ctx
+            .session_config()
+            .get_extension::<WarningCollector>()
+            .unwrap()
+            .take();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "The results of the query were truncated to the limit of 3 rows (max_result_rows)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn results_are_not_limited_by_default() {
+        assert!(ResultLimiter::try_new(&TaskContext::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn dropping_limited_stream_stops_the_query() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let blocking = Arc::new(BlockingExec::new(schema, 1));
+        let refs = blocking.refs();
+
+        let task_ctx = task_ctx(Some(0), "truncate");
+        let stream =
+            limit_result_stream(blocking.execute(0, Arc::clone(&task_ctx))?, &task_ctx);
+        drop(stream);
+        drop(blocking);
+        assert_strong_count_converges_to_zero(refs).await;
+        Ok(())
+    }
+}
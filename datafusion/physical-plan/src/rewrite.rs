@@ -0,0 +1,370 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Rewriting of [`ExecutionPlan`]s without knowing how their operators are
+//! constructed.
+//!
+//! Physical plans implement [`TreeNode`] (see [`crate::tree_node`]), so they
+//! can be visited and rewritten like logical plans, for example with
+//! [`TreeNode::transform_up`]. Operators are rebuilt over rewritten children
+//! with [`ExecutionPlan::with_new_children`], which recomputes their
+//! [`PlanProperties`] from the new children, so rules only need the
+//! constructors of the operators they replace.
+//!
+//! This module adds:
+//!
+//! * [`transform_nodes`], to rewrite the operators of one type, downcast to
+//!   that type, and
+//! * [`check_with_new_children`], to check that an operator is rebuilt with
+//!   the same properties by `with_new_children`, which rewrites rely on.
+//!
+//! [`PlanProperties`]: crate::PlanProperties
+
+use std::sync::Arc;
+
+use crate::execution_plan::InvariantLevel;
+use crate::{ExecutionPlan, Partitioning, displayable};
+
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{Result, internal_err};
+
+/// Rewrites the operators of type `T` of `plan`, bottom up, with `f`.
+///
+/// `f` returns the operator replacing the given one, or `None` to keep it.
+/// The ancestors of replaced operators are rebuilt with
+/// [`ExecutionPlan::with_new_children`].
+///
+/// # Example: remove all `CoalescePartitionsExec`s
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion_common::Result;
+/// # use datafusion_physical_plan::coalesce_partitions::CoalescePartitionsExec;
+/// # use datafusion_physical_plan::rewrite::transform_nodes;
+/// # use datafusion_physical_plan::test::scan_partitioned;
+/// # use datafusion_physical_plan::{ExecutionPlan, ExecutionPlanProperties};
+/// # fn main() -> Result<()> {
+/// let plan: Arc<dyn ExecutionPlan> =
+///     Arc::new(CoalescePartitionsExec::new(scan_partitioned(2)));
+/// let rewritten = transform_nodes(plan, |coalesce: &CoalescePartitionsExec| {
+///     Ok(Some(Arc::clone(coalesce.input())))
+/// })?;
+/// assert!(rewritten.transformed);
+/// assert_eq!(rewritten.data.output_partitioning().partition_count(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn transform_nodes<T: ExecutionPlan>(
+    plan: Arc<dyn ExecutionPlan>,
+    mut f: impl FnMut(&T) -> Result<Option<Arc<dyn ExecutionPlan>>>,
+) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
+    plan.transform_up(|node| {
+        let replacement = match node.downcast_ref::<T>() {
+            Some(typed) => f(typed)?,
+            None => None,
+        };
+        Ok(match replacement {
+            Some(replacement) => Transformed::yes(replacement),
+            None => Transformed::no(node),
+        })
+    })
+}
+
+/// Checks that `plan` is rebuilt by [`ExecutionPlan::with_new_children`]
+/// with its own children into an equivalent operator.
+///
+/// The rebuilt operator must have the same name, schema, properties, fetch
+/// and display as `plan`. Operators that lose their configuration, such as a
+/// limit, when rebuilt break the rules that rewrite their children, so
+/// authors of operators are encouraged to check them with this function in
+/// their tests.
+pub fn check_with_new_children(plan: &Arc<dyn ExecutionPlan>) -> Result<()> {
+    let children = plan.children().into_iter().cloned().collect();
+    let rebuilt = Arc::clone(plan).with_new_children(children)?;
+    rebuilt.check_invariants(InvariantLevel::Always)?;
+
+    let name = plan.name();
+    if rebuilt.name() != name {
+        return internal_err!(
+            "{name} was rebuilt by with_new_children as {}",
+            rebuilt.name()
+        );
+    }
+    if rebuilt.schema() != plan.schema() {
+        return internal_err!("{name} changed its schema in with_new_children");
+    }
+
+    let (old, new) = (plan.properties(), rebuilt.properties());
+    // `Partitioning` never considers unknown partitionings equal
+    let same_partitioning = match (&old.partitioning, &new.partitioning) {
+        (
+            Partitioning::UnknownPartitioning(old_count),
+            Partitioning::UnknownPartitioning(new_count),
+        ) => old_count == new_count,
+        (old, new) => old == new,
+    };
+    if !same_partitioning {
+        return internal_err!(
+            "{name} changed its partitioning in with_new_children from {} to {}",
+            old.partitioning,
+            new.partitioning
+        );
+    }
+    if new.output_ordering() != old.output_ordering() {
+        return internal_err!("{name} changed its ordering in with_new_children");
+    }
+    if new.emission_type != old.emission_type
+        || new.boundedness != old.boundedness
+        || new.evaluation_type != old.evaluation_type
+        || new.scheduling_type != old.scheduling_type
+    {
+        return internal_err!(
+            "{name} changed its execution properties in with_new_children"
+        );
+    }
+    if rebuilt.fetch() != plan.fetch() {
+        return internal_err!(
+            "{name} changed its fetch in with_new_children from {:?} to {:?}",
+            plan.fetch(),
+            rebuilt.fetch()
+        );
+    }
+
+    let old_display = displayable(plan.as_ref()).one_line().to_string();
+    let new_display = displayable(rebuilt.as_ref()).one_line().to_string();
+    if new_display != old_display {
+        return internal_err!(
+            "{name} changed in with_new_children from {} to {}",
+            old_display.trim_end(),
+            new_display.trim_end()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::coalesce_partitions::CoalescePartitionsExec;
+    use crate::empty::EmptyExec;
+    use crate::expressions::{binary, col, lit};
+    use crate::filter::FilterExecBuilder;
+    use crate::joins::CrossJoinExec;
+    use crate::limit::{GlobalLimitExec, LocalLimitExec};
+    use crate::placeholder_row::PlaceholderRowExec;
+    use crate::projection::ProjectionExec;
+    use crate::repartition::RepartitionExec;
+    use crate::sorts::sort::SortExec;
+    use crate::sorts::sort_preserving_merge::SortPreservingMergeExec;
+    use crate::test::{scan_partitioned, scan_partitioned_utf8};
+    use crate::union::UnionExec;
+    use crate::{
+        DisplayAs, DisplayFormatType, ExecutionPlanProperties, PhysicalExpr,
+        PlanProperties, SendableRecordBatchStream, get_plan_string,
+    };
+
+    use std::fmt;
+
+    use datafusion_common::assert_contains;
+    use datafusion_common::tree_node::TreeNodeRecursion;
+    use datafusion_execution::TaskContext;
+    use datafusion_expr::Operator;
+    use datafusion_physical_expr::expressions::Column;
+    use datafusion_physical_expr_common::sort_expr::{LexOrdering, PhysicalSortExpr};
+
+    fn ordering(plan: &Arc<dyn ExecutionPlan>) -> LexOrdering {
+        let expr = col("i", &plan.schema()).unwrap();
+        LexOrdering::new([PhysicalSortExpr::new_default(expr)]).unwrap()
+    }
+
+    /// One of each of the operators of this crate that can be built in
+    /// isolation, configured with options that `with_new_children` must keep
+    fn operators() -> Result<Vec<Arc<dyn ExecutionPlan>>> {
+        let input = scan_partitioned(2);
+        let schema = input.schema();
+        let i = col("i", &schema)?;
+        Ok(vec![
+            Arc::new(EmptyExec::new(Arc::clone(&schema)).with_partitions(3))
+                as Arc<dyn ExecutionPlan>,
+            Arc::new(PlaceholderRowExec::new(Arc::clone(&schema)).with_partitions(3)),
+            Arc::new(
+                FilterExecBuilder::new(
+                    binary(Arc::clone(&i), Operator::Gt, lit(10), &schema)?,
+                    Arc::clone(&input),
+                )
+                .with_fetch(Some(5))
+                .build()?,
+            ),
+            Arc::new(ProjectionExec::try_new(
+                [(Arc::clone(&i), "j".to_string())],
+                Arc::clone(&input),
+            )?),
+            Arc::new(
+                SortExec::new(ordering(&input), Arc::clone(&input))
+                    .with_preserve_partitioning(true)
+                    .with_fetch(Some(7)),
+            ),
+            Arc::new(
+                SortPreservingMergeExec::new(
+                    ordering(&input),
+                    Arc::new(
+                        SortExec::new(ordering(&input), Arc::clone(&input))
+                            .with_preserve_partitioning(true),
+                    ),
+                )
+                .with_fetch(Some(3)),
+            ),
+            Arc::new(CoalescePartitionsExec::new(Arc::clone(&input)).with_fetch(Some(4))),
+            Arc::new(GlobalLimitExec::new(
+                Arc::new(CoalescePartitionsExec::new(Arc::clone(&input))),
+                2,
+                Some(8),
+            )),
+            Arc::new(LocalLimitExec::new(Arc::clone(&input), 6)),
+            Arc::new(RepartitionExec::try_new(
+                Arc::clone(&input),
+                Partitioning::RoundRobinBatch(4),
+            )?),
+            Arc::new(RepartitionExec::try_new(
+                Arc::clone(&input),
+                Partitioning::Hash(vec![Arc::new(Column::new("i", 0))], 4),
+            )?),
+            UnionExec::try_new(vec![Arc::clone(&input), scan_partitioned(3)])?,
+            Arc::new(CrossJoinExec::new(
+                Arc::clone(&input),
+                scan_partitioned_utf8(1),
+            )),
+        ])
+    }
+
+    #[test]
+    fn operators_keep_properties_in_with_new_children() -> Result<()> {
+        for plan in operators()? {
+            check_with_new_children(&plan)?;
+        }
+        Ok(())
+    }
+
+    /// An operator with a limit that it forgets when rebuilt
+    #[derive(Debug)]
+    struct ForgetfulLimitExec {
+        input: Arc<dyn ExecutionPlan>,
+        fetch: Option<usize>,
+    }
+
+    impl DisplayAs for ForgetfulLimitExec {
+        fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "ForgetfulLimitExec: fetch={:?}", self.fetch)
+        }
+    }
+
+    impl ExecutionPlan for ForgetfulLimitExec {
+        fn name(&self) -> &'static str {
+            "ForgetfulLimitExec"
+        }
+
+        fn properties(&self) -> &Arc<PlanProperties> {
+            self.input.properties()
+        }
+
+        fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+            vec![&self.input]
+        }
+
+        fn with_new_children(
+            self: Arc<Self>,
+            mut children: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(Self {
+                input: children.swap_remove(0),
+                fetch: None,
+            }))
+        }
+
+        fn apply_expressions(
+            &self,
+            _f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+        ) -> Result<TreeNodeRecursion> {
+            Ok(TreeNodeRecursion::Continue)
+        }
+
+        fn fetch(&self) -> Option<usize> {
+            self.fetch
+        }
+
+        fn execute(
+            &self,
+            _partition: usize,
+            _context: Arc<TaskContext>,
+        ) -> Result<SendableRecordBatchStream> {
+            internal_err!("ForgetfulLimitExec can not be executed")
+        }
+    }
+
+    #[test]
+    fn detects_lost_configuration() {
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(ForgetfulLimitExec {
+            input: scan_partitioned(1),
+            fetch: Some(6),
+        });
+        let err = check_with_new_children(&plan).unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "ForgetfulLimitExec changed its fetch in with_new_children from Some(6) to None"
+        );
+    }
+
+    #[test]
+    fn transform_typed_nodes() -> Result<()> {
+        let input = scan_partitioned(2);
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(GlobalLimitExec::new(
+            Arc::new(CoalescePartitionsExec::new(Arc::new(LocalLimitExec::new(
+                Arc::clone(&input),
+                10,
+            )))),
+            0,
+            Some(10),
+        ));
+
+        // raise the limit of the LocalLimitExec, whose parents are rebuilt
+        let rewritten = transform_nodes(Arc::clone(&plan), |limit: &LocalLimitExec| {
+            Ok(Some(Arc::new(LocalLimitExec::new(
+                Arc::clone(limit.input()),
+                limit.fetch() * 2,
+            ))))
+        })?;
+        assert!(rewritten.transformed);
+        assert_eq!(
+            get_plan_string(&rewritten.data),
+            vec![
+                "GlobalLimitExec: skip=0, fetch=10",
+                "  CoalescePartitionsExec",
+                "    LocalLimitExec: fetch=20",
+                "      DataSourceExec: partitions=2, partition_sizes=[1, 1]",
+            ]
+        );
+        assert_eq!(rewritten.data.output_partitioning().partition_count(), 1);
+
+        // operators of other types are not visited
+        let unchanged = transform_nodes(Arc::clone(&plan), |_: &SortExec| {
+            panic!("no SortExec in the plan")
+        })?;
+        assert!(!unchanged.transformed);
+        assert!(Arc::ptr_eq(&unchanged.data, &plan));
+        Ok(())
+    }
+}
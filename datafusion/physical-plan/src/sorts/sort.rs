@@ -278,23 +278,25 @@ impl ExternalSorter {
         // Configured via `datafusion.execution.spill_compression`.
         spill_compression: SpillCompression,
         metrics: &ExecutionPlanMetricsSet,
-        runtime: Arc<RuntimeEnv>,
+        context: &TaskContext,
     ) -> Result<Self> {
+        let runtime = context.runtime_env();
         let metrics = ExternalSorterMetrics::new(metrics, partition_id);
         let reservation = MemoryConsumer::new(format!("ExternalSorter[{partition_id}]"))
             .with_can_spill(true)
-            .register(&runtime.memory_pool);
+            .register(context.memory_pool());
 
         let merge_reservation =
             MemoryConsumer::new(format!("ExternalSorterMerge[{partition_id}]"))
-                .register(&runtime.memory_pool);
+                .register(context.memory_pool());
 
         let spill_manager = SpillManager::new(
             Arc::clone(&runtime),
             metrics.spill_metrics.clone(),
             Arc::clone(&schema),
         )
-        .with_compression_type(spill_compression);
+        .with_compression_type(spill_compression)
+        .with_resource_tracker(context.resource_tracker().cloned());
 
         Ok(Self {
             schema,
@@ -1255,7 +1257,7 @@ impl ExecutionPlan for SortExec {
                     execution_options.sort_in_place_threshold_bytes,
                     context.session_config().spill_compression(),
                     &self.metrics_set,
-                    context.runtime_env(),
+                    &context,
                 )?;
                 Ok(Box::pin(RecordBatchStreamAdapter::new(
                     self.schema(),
@@ -1433,15 +1435,16 @@ mod tests {
     use arrow::array::*;
     use arrow::compute::SortOptions;
     use arrow::datatypes::*;
-    use datafusion_common::ScalarValue;
     use datafusion_common::cast::as_primitive_array;
     use datafusion_common::config::ConfigOptions;
     use datafusion_common::test_util::batches_to_string;
+    use datafusion_common::{ScalarValue, assert_contains};
     use datafusion_execution::RecordBatchStream;
     use datafusion_execution::config::SessionConfig;
     use datafusion_execution::memory_pool::{
         GreedyMemoryPool, MemoryConsumer, MemoryPool,
     };
+    use datafusion_execution::resource_limits::QueryResourceLimits;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_physical_expr::EquivalenceProperties;
     use datafusion_physical_expr::expressions::{Column, Literal};
@@ -1667,6 +1670,49 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_spill_query_resource_limits() -> Result<()> {
+        let session_config = SessionConfig::new();
+        let sort_spill_reservation_bytes = session_config
+            .options()
+            .execution
+            .sort_spill_reservation_bytes;
+
+        let input = test::scan_partitioned(100);
+        let schema = input.schema();
+        let sort_exec: Arc<dyn ExecutionPlan> = Arc::new(SortExec::new(
+            [PhysicalSortExpr {
+                expr: col("i", &schema)?,
+                options: SortOptions::default(),
+            }]
+            .into(),
+            Arc::new(CoalescePartitionsExec::new(input)),
+        ));
+
+        // The memory limit of the query makes the sort spill, even though the
+        // memory pool of the runtime is unbounded
+        let limits = QueryResourceLimits::new()
+            .with_max_memory(sort_spill_reservation_bytes + 12288);
+        let task_ctx = TaskContext::default()
+            .with_session_config(session_config.clone())
+            .with_resource_limits(limits.clone());
+        let result = collect(Arc::clone(&sort_exec), Arc::new(task_ctx)).await?;
+        assert_eq!(result.iter().map(|b| b.num_rows()).sum::<usize>(), 10000);
+        assert!(sort_exec.metrics().unwrap().spill_count().unwrap() > 0);
+
+        // Spilling more than allowed fails the query
+        let task_ctx = TaskContext::default()
+            .with_session_config(session_config)
+            .with_resource_limits(limits.with_max_spilled_bytes(10000));
+        let err = collect(sort_exec, Arc::new(task_ctx)).await.unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "Resources exhausted: Query exceeded its max_spilled_bytes limit of 9.8 KB"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_batch_reservation_error() -> Result<()> {
         // Pick a memory limit and sort_spill_reservation that make the first batch reservation fail.
@@ -2765,7 +2811,7 @@ mod tests {
             usize::MAX, // sort_in_place_threshold_bytes (high to avoid concat path)
             SpillCompression::Uncompressed,
             &metrics_set,
-            Arc::clone(&runtime),
+            &TaskContext::default().with_runtime(Arc::clone(&runtime)),
         )?;
 
         // Insert enough data to force spilling.
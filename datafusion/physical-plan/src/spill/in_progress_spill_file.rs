@@ -64,6 +64,8 @@ impl InProgressSpillFile {
     /// - Returns an error if the file is not active (has been finalized)
     /// - Returns an error if appending would exceed the disk usage limit configured
     ///   by `max_temp_directory_size` in `DiskManager`
+    /// - Returns an error if the query has spilled more than its
+    ///   `max_spilled_bytes` resource limit
     pub fn append_batch(&mut self, batch: &RecordBatch) -> Result<usize> {
         if self.in_progress_file.is_none() {
             return Err(exec_datafusion_err!(
@@ -96,6 +98,7 @@ impl InProgressSpillFile {
                     .metrics
                     .spilled_bytes
                     .add(initial_size as usize);
+                self.track_spilled_bytes(initial_size as usize)?;
             }
         }
         if let Some(writer) = &mut self.writer {
@@ -110,6 +113,7 @@ impl InProgressSpillFile {
                     .metrics
                     .spilled_bytes
                     .add((post_size - pre_size) as usize);
+                self.track_spilled_bytes((post_size - pre_size) as usize)?;
             } else {
                 unreachable!() // Already checked inside current function
             }
@@ -117,6 +121,15 @@ impl InProgressSpillFile {
         gc_batch.get_sliced_size()
     }
 
+    /// Counts `bytes` towards the spilled bytes of the query, if it has
+    /// resource limits
+    fn track_spilled_bytes(&self, bytes: usize) -> Result<()> {
+        match &self.spill_writer.resource_tracker {
+            Some(tracker) => tracker.add_spilled_bytes(bytes),
+            None => Ok(()),
+        }
+    }
+
     pub fn flush(&mut self) -> Result<()> {
         if let Some(writer) = &mut self.writer {
             writer.flush()?;
@@ -26,6 +26,7 @@ use arrow::record_batch::RecordBatch;
 use datafusion_common::{DataFusionError, Result, config::SpillCompression};
 use datafusion_execution::SendableRecordBatchStream;
 use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::resource_limits::QueryResourceTracker;
 use datafusion_execution::runtime_env::RuntimeEnv;
 use std::borrow::Borrow;
 use std::sync::Arc;
@@ -45,6 +46,8 @@ pub struct SpillManager {
     batch_read_buffer_capacity: usize,
     /// general-purpose compression options
     pub(crate) compression: SpillCompression,
+    /// Tracks the bytes spilled by the query, if it has resource limits
+    pub(crate) resource_tracker: Option<Arc<QueryResourceTracker>>,
 }
 
 impl SpillManager {
@@ -55,6 +58,7 @@ impl SpillManager {
             schema,
             batch_read_buffer_capacity: 2,
             compression: SpillCompression::default(),
+            resource_tracker: None,
         }
     }
 
@@ -71,6 +75,18 @@ impl SpillManager {
         self
     }
 
+    /// Count the bytes spilled towards the spilled bytes of the query tracked
+    /// by `resource_tracker`, see [`TaskContext::resource_tracker`]
+    ///
+    /// [`TaskContext::resource_tracker`]: datafusion_execution::TaskContext::resource_tracker
+    pub fn with_resource_tracker(
+        mut self,
+        resource_tracker: Option<Arc<QueryResourceTracker>>,
+    ) -> Self {
+        self.resource_tracker = resource_tracker;
+        self
+    }
+
     /// Returns the schema for batches managed by this SpillManager
     pub fn schema(&self) -> &SchemaRef {
         &self.schema
@@ -0,0 +1,160 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`spool_stream`]: writes the results of a query to temporary files, so
+//! that the query completes (and releases its memory) independently of how
+//! fast the results are consumed
+
+use std::sync::Arc;
+
+use datafusion_common::{DataFusionError, Result};
+use datafusion_common_runtime::SpawnedTask;
+use datafusion_execution::TaskContext;
+use futures::{StreamExt, stream};
+
+use crate::metrics::{ExecutionPlanMetricsSet, SpillMetrics};
+use crate::spill::spill_manager::SpillManager;
+use crate::spill::spill_pool;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{ExecutionPlan, SendableRecordBatchStream, execute_stream};
+
+/// Spools the batches of `input` to temporary Arrow IPC files, and returns a
+/// stream reading them back.
+///
+/// A background task drains `input` as fast as it produces batches and
+/// writes them to the temporary files of the [`DiskManager`] of `context`,
+/// so the operators producing `input` complete, and release their memory,
+/// without waiting for the consumer of the returned stream. This suits
+/// servers whose clients fetch large results slowly, for example over
+/// minutes.
+///
+/// The files are rotated once they reach
+/// `datafusion.execution.max_spill_file_size_bytes`, and each file is
+/// deleted once it has been read, so the disk space used is bounded by the
+/// results not yet consumed. Errors of `input` are returned once the batches
+/// produced before them have been read.
+///
+/// Dropping the returned stream stops the background task, and deletes the
+/// remaining files.
+///
+/// [`DiskManager`]: datafusion_execution::disk_manager::DiskManager
+pub fn spool_stream(
+    mut input: SendableRecordBatchStream,
+    context: &TaskContext,
+) -> SendableRecordBatchStream {
+    let schema = input.schema();
+    let spill_manager = SpillManager::new(
+        context.runtime_env(),
+        SpillMetrics::new(&ExecutionPlanMetricsSet::new(), 0),
+        Arc::clone(&schema),
+    )
+    .with_compression_type(context.session_config().spill_compression())
+    .with_resource_tracker(context.resource_tracker().cloned());
+    let (writer, reader) = spill_pool::channel(
+        context
+            .session_config()
+            .options()
+            .execution
+            .max_spill_file_size_bytes,
+        Arc::new(spill_manager),
+    );
+
+    let task = SpawnedTask::spawn(async move {
+        while let Some(batch) = input.next().await {
+            writer.push_batch(&batch?)?;
+        }
+        // dropping the writer finishes the last file
+        Ok(())
+    });
+
+    // once all the spooled batches are read, return the error of the task,
+    // if any
+    let error = stream::once(task).filter_map(|result| async move {
+        match result {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(Err(e)),
+            Err(e) => Some(Err(DataFusionError::ExecutionJoin(Box::new(e)))),
+        }
+    });
+    Box::pin(RecordBatchStreamAdapter::new(schema, reader.chain(error)))
+}
+
+/// Executes `plan` as [`execute_stream`] does, and spools its results to
+/// temporary files with [`spool_stream`]
+pub fn execute_stream_spooled(
+    plan: Arc<dyn ExecutionPlan>,
+    context: &Arc<TaskContext>,
+) -> Result<SendableRecordBatchStream> {
+    let stream = execute_stream(plan, Arc::clone(context))?;
+    Ok(spool_stream(stream, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common::collect;
+    use crate::test::exec::{
+        BlockingExec, MockExec, assert_strong_count_converges_to_zero,
+    };
+    use crate::test::scan_partitioned;
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::{assert_contains, exec_err};
+
+    #[tokio::test]
+    async fn spooled_stream_returns_all_batches() -> Result<()> {
+        let plan = scan_partitioned(1);
+        let task_ctx = Arc::new(TaskContext::default());
+        let expected = crate::collect(Arc::clone(&plan), Arc::clone(&task_ctx)).await?;
+
+        let stream = execute_stream_spooled(plan, &task_ctx)?;
+        let batches = collect(stream).await?;
+        assert_eq!(batches, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spooled_stream_returns_errors_last() -> Result<()> {
+        let batches =
+            collect(scan_partitioned(1).execute(0, Default::default())?).await?;
+        let schema = batches[0].schema();
+        let plan = Arc::new(MockExec::new(
+            vec![Ok(batches[0].clone()), exec_err!("bad data")],
+            schema,
+        ));
+
+        let stream = execute_stream_spooled(plan, &Arc::new(TaskContext::default()))?;
+        let results = stream.collect::<Vec<_>>().await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), &batches[0]);
+        assert_contains!(results[1].as_ref().unwrap_err().to_string(), "bad data");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dropping_spooled_stream_stops_the_query() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let blocking = Arc::new(BlockingExec::new(schema, 1));
+        let refs = blocking.refs();
+
+        let stream = execute_stream_spooled(blocking, &Arc::new(TaskContext::default()))?;
+        drop(stream);
+        assert_strong_count_converges_to_zero(refs).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,560 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`StatisticsCollectorExec`]: collects approximate statistics of the rows
+//! passing through it, such as the number of distinct values of each column
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    SendableRecordBatchStream,
+};
+
+use arrow::array::{Array, ArrayRef, RecordBatch};
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_common::hash_utils::{RandomState, create_hashes};
+use datafusion_common::stats::Precision;
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::{
+    ColumnStatistics, Result, ScalarValue, Statistics, assert_eq_or_internal_err,
+};
+use datafusion_execution::TaskContext;
+use datafusion_functions_aggregate_common::hyperloglog::HyperLogLog;
+use datafusion_physical_expr::PhysicalExpr;
+use futures::{StreamExt, TryStreamExt, future, stream};
+use parking_lot::Mutex;
+
+/// The number of most frequent values collected per column by default
+pub const DEFAULT_TOP_K: usize = 10;
+
+/// A value of a column and the number of rows with that value, see
+/// [`CollectedStatistics::heavy_hitters`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeavyHitter {
+    /// The value
+    pub value: ScalarValue,
+    /// The estimated number of rows with the value, which may overestimate
+    /// the actual number
+    pub count: usize,
+}
+
+/// The statistics collected by a [`StatisticsCollectorExec`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectedStatistics {
+    /// The exact number of rows and null counts, and the estimated number of
+    /// distinct values of each column
+    pub statistics: Statistics,
+    /// The most frequent non null values of each column, most frequent first
+    pub heavy_hitters: Vec<Vec<HeavyHitter>>,
+}
+
+/// Receives the statistics collected by a [`StatisticsCollectorExec`], for
+/// example to store them for planning later queries
+#[async_trait]
+pub trait CollectedStatisticsSink: Debug + Send + Sync {
+    /// Called with the statistics of all rows of the input, once all its
+    /// partitions were read to completion
+    async fn collected(&self, statistics: CollectedStatistics) -> Result<()>;
+}
+
+/// An [`ExecutionPlan`] that passes through the rows of its input, and
+/// collects their statistics while the query runs: the number of rows, and
+/// for each column the number of nulls, the approximate number of distinct
+/// values and the approximately most frequent values.
+///
+/// The number of distinct values is estimated with a [`HyperLogLog`] sketch,
+/// and the most frequent values with the Space-Saving algorithm, so the
+/// overhead is a hash of each value and a small fixed amount of memory per
+/// column.
+///
+/// Once every partition of the input has been read to completion, the
+/// statistics are passed to a [`CollectedStatisticsSink`]. Nothing is
+/// reported if the query stops early, for example because of a `LIMIT`.
+#[derive(Debug)]
+pub struct StatisticsCollectorExec {
+    input: Arc<dyn ExecutionPlan>,
+    sink: Arc<dyn CollectedStatisticsSink>,
+    top_k: usize,
+    /// The statistics of the partitions finished so far
+    state: Arc<Mutex<CollectorState>>,
+}
+
+#[derive(Debug)]
+struct CollectorState {
+    /// Partitions of the input that have not been read to completion
+    remaining: usize,
+    collected: Option<StatisticsCollector>,
+}
+
+impl StatisticsCollectorExec {
+    /// Create a new `StatisticsCollectorExec` passing the statistics of
+    /// `input` to `sink`
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        sink: Arc<dyn CollectedStatisticsSink>,
+    ) -> Self {
+        let remaining = input.output_partitioning().partition_count();
+        Self {
+            input,
+            sink,
+            top_k: DEFAULT_TOP_K,
+            state: Arc::new(Mutex::new(CollectorState {
+                remaining,
+                collected: None,
+            })),
+        }
+    }
+
+    /// Set the number of most frequent values collected per column, which
+    /// defaults to [`DEFAULT_TOP_K`]
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Returns the input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Returns the sink the statistics are passed to
+    pub fn sink(&self) -> &Arc<dyn CollectedStatisticsSink> {
+        &self.sink
+    }
+
+    /// Returns the number of most frequent values collected per column
+    pub fn top_k(&self) -> usize {
+        self.top_k
+    }
+}
+
+impl DisplayAs for StatisticsCollectorExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "StatisticsCollectorExec: top_k={}", self.top_k)
+            }
+            DisplayFormatType::TreeRender => writeln!(f, "top_k={}", self.top_k),
+        }
+    }
+}
+
+impl ExecutionPlan for StatisticsCollectorExec {
+    fn name(&self) -> &str {
+        "StatisticsCollectorExec"
+    }
+
+    fn properties(&self) -> &Arc<PlanProperties> {
+        self.input.properties()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn apply_expressions(
+        &self,
+        _f: &mut dyn FnMut(&dyn PhysicalExpr) -> Result<TreeNodeRecursion>,
+    ) -> Result<TreeNodeRecursion> {
+        Ok(TreeNodeRecursion::Continue)
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert_eq_or_internal_err!(
+            children.len(),
+            1,
+            "StatisticsCollectorExec requires exactly one child"
+        );
+        let exec = Self::new(children.swap_remove(0), Arc::clone(&self.sink));
+        Ok(Arc::new(exec.with_top_k(self.top_k)))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, context)?;
+        let schema = input.schema();
+        let collector = Arc::new(Mutex::new(Some(StatisticsCollector::new(
+            &schema, self.top_k,
+        ))));
+
+        let observed = {
+            let collector = Arc::clone(&collector);
+            input.and_then(move |batch| {
+                let updated = match collector.lock().as_mut() {
+                    Some(collector) => collector.update(&batch),
+                    None => Ok(()),
+                };
+                future::ready(updated.map(|_| batch))
+            })
+        };
+        let (state, sink) = (Arc::clone(&self.state), Arc::clone(&self.sink));
+        let finish = stream::once(async move {
+            let collector = collector.lock().take()?;
+            let collected = {
+                let mut state = state.lock();
+                match state.collected.as_mut() {
+                    Some(collected) => collected.merge(collector),
+                    None => state.collected = Some(collector),
+                }
+                state.remaining = state.remaining.saturating_sub(1);
+                if state.remaining > 0 {
+                    return None;
+                }
+                state.collected.take()
+            };
+            let collected = collected?.finish();
+            sink.collected(collected).await.err().map(Err)
+        })
+        .filter_map(future::ready);
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            observed.chain(finish),
+        )))
+    }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Arc<Statistics>> {
+        self.input.partition_statistics(partition)
+    }
+}
+
+/// Collects the statistics of the batches of one or more partitions
+#[derive(Debug)]
+struct StatisticsCollector {
+    num_rows: usize,
+    columns: Vec<ColumnCollector>,
+    top_k: usize,
+    random_state: RandomState,
+}
+
+impl StatisticsCollector {
+    fn new(schema: &SchemaRef, top_k: usize) -> Self {
+        Self {
+            num_rows: 0,
+            columns: schema
+                .fields()
+                .iter()
+                .map(|_| ColumnCollector::new(top_k))
+                .collect(),
+            top_k,
+            random_state: RandomState::default(),
+        }
+    }
+
+    fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.num_rows += batch.num_rows();
+        let mut hashes = vec![0; batch.num_rows()];
+        for (column, array) in self.columns.iter_mut().zip(batch.columns()) {
+            column.update(array, &self.random_state, &mut hashes)?;
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.num_rows += other.num_rows;
+        for (column, other) in self.columns.iter_mut().zip(other.columns) {
+            column.merge(other);
+        }
+    }
+
+    fn finish(self) -> CollectedStatistics {
+        let (column_statistics, heavy_hitters) = self
+            .columns
+            .into_iter()
+            .map(|column| column.finish(self.top_k))
+            .unzip();
+        CollectedStatistics {
+            statistics: Statistics {
+                num_rows: Precision::Exact(self.num_rows),
+                total_byte_size: Precision::Absent,
+                column_statistics,
+            },
+            heavy_hitters,
+        }
+    }
+}
+
+/// Collects the statistics of a column
+#[derive(Debug)]
+struct ColumnCollector {
+    null_count: usize,
+    /// `None` if the values of the column can not be hashed
+    distinct: Option<HyperLogLog<ScalarValue>>,
+    /// The Space-Saving counters of the most frequent values, by hash
+    counters: HashMap<u64, HeavyHitter>,
+    /// The maximum number of counters, a multiple of the number of most
+    /// frequent values reported to make them more accurate
+    capacity: usize,
+}
+
+impl ColumnCollector {
+    fn new(top_k: usize) -> Self {
+        Self {
+            null_count: 0,
+            distinct: Some(HyperLogLog::new()),
+            counters: HashMap::new(),
+            capacity: top_k * 4,
+        }
+    }
+
+    fn update(
+        &mut self,
+        array: &ArrayRef,
+        random_state: &RandomState,
+        hashes: &mut [u64],
+    ) -> Result<()> {
+        self.null_count += array.null_count();
+        let Some(distinct) = self.distinct.as_mut() else {
+            return Ok(());
+        };
+        hashes.fill(0);
+        if create_hashes([array], random_state, hashes).is_err() {
+            // the column only reports its null count
+            self.distinct = None;
+            self.counters.clear();
+            return Ok(());
+        }
+        for (row, &hash) in hashes.iter().enumerate() {
+            if array.is_null(row) {
+                continue;
+            }
+            distinct.add_hashed(hash);
+            if self.capacity == 0 {
+                continue;
+            }
+            if let Some(counter) = self.counters.get_mut(&hash) {
+                counter.count += 1;
+                continue;
+            }
+            // replace the least frequent value, which is counted for the
+            // new value as it may have been one of its occurrences
+            let mut count = 1;
+            if self.counters.len() >= self.capacity {
+                let (&min_hash, min) = self
+                    .counters
+                    .iter()
+                    .min_by_key(|(_, counter)| counter.count)
+                    .expect("counters are not empty");
+                count += min.count;
+                self.counters.remove(&min_hash);
+            }
+            let value = ScalarValue::try_from_array(array, row)?;
+            self.counters.insert(hash, HeavyHitter { value, count });
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.null_count += other.null_count;
+        let (Some(distinct), Some(other_distinct)) =
+            (self.distinct.as_mut(), other.distinct.as_ref())
+        else {
+            self.distinct = None;
+            self.counters.clear();
+            return;
+        };
+        distinct.merge(other_distinct);
+        for (hash, other) in other.counters {
+            self.counters
+                .entry(hash)
+                .and_modify(|counter| counter.count += other.count)
+                .or_insert(other);
+        }
+        if self.counters.len() > self.capacity {
+            let mut counters: Vec<_> = self.counters.drain().collect();
+            counters.sort_unstable_by(|(_, a), (_, b)| b.count.cmp(&a.count));
+            counters.truncate(self.capacity);
+            self.counters = counters.into_iter().collect();
+        }
+    }
+
+    fn finish(self, top_k: usize) -> (ColumnStatistics, Vec<HeavyHitter>) {
+        let mut statistics = ColumnStatistics::new_unknown()
+            .with_null_count(Precision::Exact(self.null_count));
+        if let Some(distinct) = &self.distinct {
+            statistics =
+                statistics.with_distinct_count(Precision::Inexact(distinct.count()));
+        }
+        let mut heavy_hitters: Vec<_> = self.counters.into_values().collect();
+        heavy_hitters.sort_unstable_by(|a, b| b.count.cmp(&a.count));
+        heavy_hitters.truncate(top_k);
+        (statistics, heavy_hitters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::collect;
+    use crate::test::TestMemoryExec;
+
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::assert_batches_eq;
+
+    #[derive(Debug, Default)]
+    struct MemorySink(Mutex<Vec<CollectedStatistics>>);
+
+    #[async_trait]
+    impl CollectedStatisticsSink for MemorySink {
+        async fn collected(&self, statistics: CollectedStatistics) -> Result<()> {
+            self.0.lock().push(statistics);
+            Ok(())
+        }
+    }
+
+    fn batch(a: Vec<Option<i32>>, b: Vec<&str>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(a)),
+                Arc::new(StringArray::from(b)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn collect_statistics_of_all_partitions() -> Result<()> {
+        let partitions = vec![
+            vec![batch(vec![Some(1), Some(2), None], vec!["x", "y", "x"])],
+            vec![
+                batch(vec![Some(1), Some(1)], vec!["x", "z"]),
+                batch(vec![Some(3)], vec!["x"]),
+            ],
+        ];
+        let schema = partitions[0][0].schema();
+        let input = TestMemoryExec::try_new_exec(&partitions, schema, None)?;
+        let sink = Arc::new(MemorySink::default());
+        let exec = Arc::new(
+            StatisticsCollectorExec::new(input, Arc::clone(&sink) as _).with_top_k(2),
+        );
+
+        let batches = collect(exec, Arc::new(TaskContext::default())).await?;
+        assert_batches_eq!(
+            [
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| 1 | x |",
+                "| 2 | y |",
+                "|   | x |",
+                "| 1 | x |",
+                "| 1 | z |",
+                "| 3 | x |",
+                "+---+---+",
+            ],
+            &batches
+        );
+
+        let collected = sink.0.lock();
+        assert_eq!(collected.len(), 1);
+        let statistics = &collected[0].statistics;
+        assert_eq!(statistics.num_rows, Precision::Exact(6));
+        let a = &statistics.column_statistics[0];
+        assert_eq!(a.null_count, Precision::Exact(1));
+        assert_eq!(a.distinct_count, Precision::Inexact(3));
+        assert_eq!(
+            statistics.column_statistics[1].distinct_count,
+            Precision::Inexact(3)
+        );
+
+        let heavy_hitters = &collected[0].heavy_hitters;
+        assert_eq!(
+            heavy_hitters[0][0],
+            HeavyHitter {
+                value: ScalarValue::Int32(Some(1)),
+                count: 3
+            }
+        );
+        assert_eq!(
+            heavy_hitters[1][0],
+            HeavyHitter {
+                value: ScalarValue::from("x"),
+                count: 4
+            }
+        );
+        assert_eq!(heavy_hitters[1].len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn heavy_hitters_with_few_counters() -> Result<()> {
+        // one frequent value among many distinct values
+        let values: Vec<_> = (0..1000)
+            .map(|i| Some(if i % 2 == 0 { 7 } else { i }))
+            .collect();
+        let names = vec!["n"; values.len()];
+        let partitions = vec![vec![batch(values, names)]];
+        let schema = partitions[0][0].schema();
+        let input = TestMemoryExec::try_new_exec(&partitions, schema, None)?;
+        let sink = Arc::new(MemorySink::default());
+        let exec = Arc::new(
+            StatisticsCollectorExec::new(input, Arc::clone(&sink) as _).with_top_k(1),
+        );
+        collect(exec, Arc::new(TaskContext::default())).await?;
+
+        let collected = sink.0.lock();
+        let top = &collected[0].heavy_hitters[0][0];
+        assert_eq!(top.value, ScalarValue::Int32(Some(7)));
+        // Space-Saving never underestimates
+        assert!(top.count >= 500, "{}", top.count);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_statistics_for_partial_reads() -> Result<()> {
+        let partitions = vec![
+            vec![batch(vec![Some(1)], vec!["x"])],
+            vec![batch(vec![Some(2)], vec!["y"])],
+        ];
+        let schema = partitions[0][0].schema();
+        let input = TestMemoryExec::try_new_exec(&partitions, schema, None)?;
+        let sink = Arc::new(MemorySink::default());
+        let exec = StatisticsCollectorExec::new(input, Arc::clone(&sink) as _);
+
+        let context = Arc::new(TaskContext::default());
+        let batches: Vec<_> = exec.execute(0, context)?.try_collect().await?;
+        assert_eq!(batches.len(), 1);
+        assert!(sink.0.lock().is_empty());
+        Ok(())
+    }
+}
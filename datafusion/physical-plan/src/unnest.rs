@@ -55,10 +55,12 @@ use datafusion_physical_expr::expressions::Column;
 use futures::{Stream, StreamExt};
 use log::trace;
 
-/// Unnest the given columns (either with type struct or list)
+/// Unnest the given columns (either with type struct, list or map)
 /// For list unnesting, each row is vertically transformed into multiple rows
 /// For struct unnesting, each column is horizontally transformed into multiple columns,
 /// Thus the original RecordBatch with dimension (n x m) may have new dimension (n' x m')
+/// Map columns are unnested as a list of their entries, whose key and value
+/// are then flattened like a struct
 ///
 /// See [`UnnestOptions`] for more details and an example.
 #[derive(Debug, Clone)]
@@ -411,9 +413,8 @@ impl UnnestStream {
 /// {a.item: 3, a.name: "b", b: 4}
 fn flatten_struct_cols(
     input_batch: &[Arc<dyn Array>],
-    schema: &SchemaRef,
     struct_column_indices: &HashSet<usize>,
-) -> Result<RecordBatch> {
+) -> Result<Vec<ArrayRef>> {
     // horizontal expansion because of struct unnest
     let columns_expanded = input_batch
         .iter()
@@ -435,7 +436,22 @@ fn flatten_struct_cols(
         .into_iter()
         .flatten()
         .collect();
-    Ok(RecordBatch::try_new(Arc::clone(schema), columns_expanded)?)
+    Ok(columns_expanded)
+}
+
+/// Returns a map array as a list array of its key/value entries, so that it
+/// can be unnested like any other list
+fn map_as_list(array: &ArrayRef) -> Result<ArrayRef> {
+    let DataType::Map(entries_field, _) = array.data_type() else {
+        return internal_err!("expecting a map array, got {}", array.data_type());
+    };
+    let map = array.as_map();
+    Ok(Arc::new(ListArray::try_new(
+        Arc::clone(entries_field),
+        map.offsets().clone(),
+        Arc::new(map.entries().clone()),
+        map.nulls().cloned(),
+    )?))
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -481,14 +497,15 @@ pub struct ListUnnest {
 /// - colB(1-dimension) unnest into P3 (unnesting of P3 starts from this level)
 ///
 /// The returned array will has the same size as the input batch
-/// and only contains original columns that are not being unnested.
+/// and only contains original columns that are not being unnested. It is
+/// returned along with the number of rows each input row is unnested into.
 fn list_unnest_at_level(
     batch: &[ArrayRef],
     list_type_unnests: &[ListUnnest],
     temp_unnested_arrs: &mut HashMap<ListUnnest, ArrayRef>,
     level_to_unnest: usize,
     options: &UnnestOptions,
-) -> Result<Option<Vec<ArrayRef>>> {
+) -> Result<Option<(Vec<ArrayRef>, ArrayRef)>> {
     // Extract unnestable columns at this level
     let (arrs_to_unnest, list_unnest_specs): (Vec<Arc<dyn Array>>, Vec<_>) =
         list_type_unnests
@@ -563,7 +580,7 @@ fn list_unnest_at_level(
     // as the side effect of unnesting
     let ret = repeat_arrs_from_indices(batch, &take_indices, &repeat_mask)?;
 
-    Ok(Some(ret))
+    Ok(Some((ret, longest_length)))
 }
 struct UnnestingResult {
     arr: ArrayRef,
@@ -633,9 +650,27 @@ fn build_batch(
     struct_column_indices: &HashSet<usize>,
     options: &UnnestOptions,
 ) -> Result<Option<RecordBatch>> {
-    let transformed = match list_type_columns.len() {
-        0 => flatten_struct_cols(batch.columns(), schema, struct_column_indices),
+    let (mut transformed, unnested_length) = match list_type_columns.len() {
+        0 => (
+            flatten_struct_cols(batch.columns(), struct_column_indices)?,
+            Arc::new(Int64Array::from_value(1, batch.num_rows())) as ArrayRef,
+        ),
         _ => {
+            // Map columns are unnested as lists of their entries
+            let columns = batch
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(idx, column)| {
+                    let is_unnested = list_type_columns
+                        .iter()
+                        .any(|unnest| unnest.index_in_input_schema == idx);
+                    match column.data_type() {
+                        DataType::Map(_, _) if is_unnested => map_as_list(column),
+                        _ => Ok(Arc::clone(column)),
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
             let mut temp_unnested_result = HashMap::new();
             let max_recursion = list_type_columns
                 .iter()
@@ -645,15 +680,17 @@ fn build_batch(
 
             // This arr always has the same column count with the input batch
             let mut flatten_arrs = vec![];
+            // The number of rows each row is unnested into at the last level
+            let mut unnested_length = None;
 
             // Original batch has the same columns
             // All unnesting results are written to temp_batch
             for depth in (1..=max_recursion).rev() {
                 let input = match depth == max_recursion {
-                    true => batch.columns(),
+                    true => &columns,
                     false => &flatten_arrs,
                 };
-                let Some(temp_result) = list_unnest_at_level(
+                let Some((temp_result, length)) = list_unnest_at_level(
                     input,
                     list_type_columns,
                     &mut temp_unnested_result,
@@ -664,6 +701,7 @@ fn build_batch(
                     return Ok(None);
                 };
                 flatten_arrs = temp_result;
+                unnested_length = Some(length);
             }
             let unnested_array_map: HashMap<usize, Vec<UnnestingResult>> =
                 temp_unnested_result.into_iter().fold(
@@ -744,10 +782,16 @@ fn build_batch(
                 })
                 .collect::<Vec<_>>();
 
-            flatten_struct_cols(&ret, schema, struct_column_indices)
+            (
+                flatten_struct_cols(&ret, struct_column_indices)?,
+                unnested_length.unwrap(),
+            )
         }
-    }?;
-    Ok(Some(transformed))
+    };
+    if options.ordinality {
+        transformed.push(create_ordinality(unnested_length.as_primitive()));
+    }
+    Ok(Some(RecordBatch::try_new(Arc::clone(schema), transformed)?))
 }
 
 /// Find the longest list length among the given list arrays for each row.
@@ -771,6 +815,9 @@ fn build_batch(
 /// ```ignore
 /// longest_length: [3, 1, 1, 2]
 /// ```
+///
+/// If `outer` is true, rows whose longest length is 0 are given a length of
+/// 1 so that they produce a single row of nulls.
 fn find_longest_length(
     list_arrays: &[ArrayRef],
     options: &UnnestOptions,
@@ -800,6 +847,11 @@ fn find_longest_length(
             zip(&is_lt, &current, &longest)
         },
     )?;
+    if options.outer {
+        let one = Scalar::new(Int64Array::from_value(1, 1));
+        let is_empty = lt(&longest_length, &one)?;
+        return Ok(zip(&is_empty, &one, &longest_length)?);
+    }
     Ok(longest_length)
 }
 
@@ -986,6 +1038,26 @@ fn create_take_indices(
     builder.finish()
 }
 
+/// Creates the ordinality column for [`UnnestOptions::ordinality`], holding
+/// the 1-based position of every unnested row among the rows unnested from
+/// the same input row.
+///
+/// If the length array looks like this:
+///
+/// ```ignore
+/// [2, 3, 1]
+/// ```
+/// Then [`create_ordinality`] will return an array like this
+///
+/// ```ignore
+/// [1, 2, 1, 2, 3, 1]
+/// ```
+fn create_ordinality(length_array: &PrimitiveArray<Int64Type>) -> ArrayRef {
+    Arc::new(Int64Array::from_iter_values(
+        length_array.values().iter().flat_map(|repeat| 1..=*repeat),
+    ))
+}
+
 /// Create a batch of arrays based on an input `batch` and a `indices` array.
 /// The `indices` array is used by the take kernel to repeat values in the arrays
 /// that are marked with `true` in the `repeat_mask`. Arrays marked with `false`
@@ -1054,7 +1126,8 @@ fn repeat_arrs_from_indices(
 mod tests {
     use super::*;
     use arrow::array::{
-        GenericListArray, NullBufferBuilder, OffsetSizeTrait, StringArray,
+        GenericListArray, Int32Array, Int32Builder, MapBuilder, NullBufferBuilder,
+        OffsetSizeTrait, StringArray, StringBuilder,
     };
     use arrow::buffer::{NullBuffer, OffsetBuffer};
     use arrow::datatypes::{Field, Int32Type};
@@ -1242,6 +1315,8 @@ mod tests {
             &UnnestOptions {
                 preserve_nulls: true,
                 recursions: vec![],
+                ordinality: false,
+                outer: false,
             },
         )?
         .unwrap();
@@ -1276,6 +1351,68 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_batch_map_with_ordinality() -> Result<()> {
+        // col1             | col2
+        // {a: 1, b: 2}     | 10
+        // null             | 20
+        // {c: 3}           | 30
+        let mut builder =
+            MapBuilder::new(None, StringBuilder::new(), Int32Builder::new());
+        builder.keys().append_value("a");
+        builder.values().append_value(1);
+        builder.keys().append_value("b");
+        builder.values().append_value(2);
+        builder.append(true)?;
+        builder.append(false)?;
+        builder.keys().append_value("c");
+        builder.values().append_value(3);
+        builder.append(true)?;
+        let col1 = builder.finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("col1", col1.data_type().clone(), true),
+            Field::new("col2", DataType::Int32, false),
+        ]));
+        let out_schema = Arc::new(Schema::new(vec![
+            Field::new("col1.keys", DataType::Utf8, true),
+            Field::new("col1.values", DataType::Int32, true),
+            Field::new("col2", DataType::Int32, false),
+            Field::new("ordinality", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(col1) as ArrayRef,
+                Arc::new(Int32Array::from(vec![10, 20, 30])) as ArrayRef,
+            ],
+        )?;
+        let ret = build_batch(
+            &batch,
+            &out_schema,
+            &[ListUnnest {
+                index_in_input_schema: 0,
+                depth: 1,
+            }],
+            &HashSet::from_iter([0]),
+            &UnnestOptions::new().with_ordinality(true),
+        )?
+        .unwrap();
+
+        assert_snapshot!(batches_to_string(&[ret]),
+        @r"
+        +-----------+-------------+------+------------+
+        | col1.keys | col1.values | col2 | ordinality |
+        +-----------+-------------+------+------------+
+        | a         | 1           | 10   | 1          |
+        | b         | 2           | 10   | 2          |
+        |           |             | 20   | 1          |
+        | c         | 3           | 30   | 1          |
+        +-----------+-------------+------+------------+
+        ");
+        Ok(())
+    }
+
     #[test]
     fn test_unnest_list_array() -> Result<()> {
         // [A, B, C], [], NULL, [D], NULL, [NULL, F]
@@ -1329,6 +1466,8 @@ mod tests {
         let options = UnnestOptions {
             preserve_nulls,
             recursions: vec![],
+            ordinality: false,
+            outer: false,
         };
         let longest_length = find_longest_length(list_arrays, &options)?;
         let expected_array = Int64Array::from(expected);
@@ -1374,6 +1513,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_longest_list_length_outer() -> Result<()> {
+        //  [A, B, C], [], NULL, [D], NULL, [NULL, F]
+        let list_array = Arc::new(make_generic_array::<i32>()) as ArrayRef;
+        for preserve_nulls in [false, true] {
+            let options = UnnestOptions::new()
+                .with_preserve_nulls(preserve_nulls)
+                .with_outer(true);
+            let longest_length =
+                find_longest_length(&[Arc::clone(&list_array)], &options)?;
+            assert_eq!(
+                longest_length.as_primitive::<Int64Type>(),
+                &Int64Array::from(vec![3, 1, 1, 1, 1, 2])
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_create_take_indices() -> Result<()> {
         let length_array = Int64Array::from(vec![2, 3, 1]);
@@ -0,0 +1,206 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Evaluation of `VALUES` lists into record batches
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::memory::{LazyBatchGenerator, LazyMemoryExec};
+
+use arrow::array::{RecordBatch, RecordBatchOptions};
+use arrow::datatypes::{Schema, SchemaRef};
+use datafusion_common::{Result, ScalarValue, plan_err};
+use datafusion_expr::ColumnarValue;
+use datafusion_physical_expr::PhysicalExpr;
+use parking_lot::RwLock;
+
+/// Evaluates rows of constant expressions into a single [`RecordBatch`]
+/// with the given schema.
+pub fn evaluate_values(
+    schema: &SchemaRef,
+    rows: &[Vec<Arc<dyn PhysicalExpr>>],
+) -> Result<RecordBatch> {
+    // We have this single row batch as a placeholder to satisfy evaluation argument
+    // and generate a single output row
+    let placeholder_batch = RecordBatch::try_new_with_options(
+        Arc::new(Schema::empty()),
+        vec![],
+        &RecordBatchOptions::new().with_row_count(Some(1)),
+    )?;
+
+    // Evaluate each column
+    let arrays = (0..schema.fields().len())
+        .map(|j| {
+            rows.iter()
+                .map(|row| match row[j].evaluate(&placeholder_batch)? {
+                    ColumnarValue::Scalar(scalar) => Ok(scalar),
+                    ColumnarValue::Array(array) if array.len() == 1 => {
+                        ScalarValue::try_from_array(&array, 0)
+                    }
+                    ColumnarValue::Array(_) => {
+                        plan_err!("Cannot have array values in a values list")
+                    }
+                })
+                .collect::<Result<Vec<_>>>()
+                .and_then(ScalarValue::iter_to_array)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(RecordBatch::try_new_with_options(
+        Arc::clone(schema),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(rows.len())),
+    )?)
+}
+
+/// [`LazyBatchGenerator`] that evaluates a `VALUES` list `batch_size` rows
+/// at a time, so that large lists are never materialized as a single batch.
+#[derive(Debug, Clone)]
+pub struct ValuesBatchGenerator {
+    schema: SchemaRef,
+    rows: Arc<[Vec<Arc<dyn PhysicalExpr>>]>,
+    batch_size: usize,
+    offset: usize,
+}
+
+impl ValuesBatchGenerator {
+    /// Create a generator for `rows`, which must all have one expression per
+    /// field of `schema`
+    pub fn try_new(
+        schema: SchemaRef,
+        rows: Vec<Vec<Arc<dyn PhysicalExpr>>>,
+        batch_size: usize,
+    ) -> Result<Self> {
+        if rows.is_empty() {
+            return plan_err!("Values list cannot be empty");
+        }
+        if batch_size == 0 {
+            return plan_err!("Values batch size must be greater than zero");
+        }
+        Ok(Self {
+            schema,
+            rows: rows.into(),
+            batch_size,
+            offset: 0,
+        })
+    }
+
+    /// Create a [`LazyMemoryExec`] producing the rows of this generator
+    pub fn into_exec(self) -> Result<LazyMemoryExec> {
+        let schema = Arc::clone(&self.schema);
+        LazyMemoryExec::try_new(schema, vec![Arc::new(RwLock::new(self))])
+    }
+}
+
+impl fmt::Display for ValuesBatchGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "values: rows={}, batch_size={}",
+            self.rows.len(),
+            self.batch_size
+        )
+    }
+}
+
+impl LazyBatchGenerator for ValuesBatchGenerator {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn num_rows(&self) -> Option<usize> {
+        Some(self.rows.len())
+    }
+
+    fn generate_next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        if self.offset >= self.rows.len() {
+            return Ok(None);
+        }
+        let end = self.rows.len().min(self.offset + self.batch_size);
+        let batch = evaluate_values(&self.schema, &self.rows[self.offset..end])?;
+        self.offset = end;
+        Ok(Some(batch))
+    }
+
+    fn reset_state(&self) -> Arc<RwLock<dyn LazyBatchGenerator>> {
+        Arc::new(RwLock::new(Self {
+            offset: 0,
+            ..self.clone()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{ExecutionPlan, collect};
+
+    use arrow::array::AsArray;
+    use arrow::datatypes::{DataType, Field, Int64Type};
+    use datafusion_common::stats::Precision;
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_expr::expressions::lit;
+
+    #[tokio::test]
+    async fn values_batch_generator() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let rows = (0..5)
+            .map(|i| vec![lit(i as i64), lit(ScalarValue::Utf8(None))])
+            .collect();
+        let exec: Arc<dyn ExecutionPlan> = Arc::new(
+            ValuesBatchGenerator::try_new(Arc::clone(&schema), rows, 2)?.into_exec()?,
+        );
+
+        let statistics = exec.partition_statistics(None)?;
+        assert_eq!(statistics.num_rows, Precision::Exact(5));
+
+        // every execution evaluates the rows again from the start
+        for _ in 0..2 {
+            let batches =
+                collect(Arc::clone(&exec), Arc::new(TaskContext::default())).await?;
+            let sizes: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+            assert_eq!(sizes, vec![2, 2, 1]);
+            let a: Vec<_> = batches
+                .iter()
+                .flat_map(|b| b.column(0).as_primitive::<Int64Type>().values().to_vec())
+                .collect();
+            assert_eq!(a, vec![0, 1, 2, 3, 4]);
+            assert!(
+                batches
+                    .iter()
+                    .all(|b| b.column(1).null_count() == b.num_rows())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn values_batch_generator_empty() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let err = ValuesBatchGenerator::try_new(schema, vec![], 2).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Values list cannot be empty"
+        );
+    }
+}
@@ -43,7 +43,7 @@ use datafusion_common::{
         TableParquetOptions,
     },
     file_options::{csv_writer::CsvWriterOptions, json_writer::JsonWriterOptions},
-    parsers::CompressionTypeVariant,
+    parsers::{CompressionTypeVariant, CsvLineTerminator},
     plan_datafusion_err,
     stats::Precision,
 };
@@ -983,7 +983,9 @@ impl TryFrom<&protobuf::CsvWriterOptions> for CsvWriterOptions {
     ) -> datafusion_common::Result<Self, Self::Error> {
         let write_options = csv_writer_options_from_proto(opts)?;
         let compression: CompressionTypeVariant = opts.compression().into();
-        Ok(CsvWriterOptions::new(write_options, compression))
+        let line_terminator = csv_line_terminator_from_proto(&opts.line_terminator)?;
+        Ok(CsvWriterOptions::new(write_options, compression)
+            .with_line_terminator(line_terminator))
     }
 }
 
@@ -994,7 +996,7 @@ impl TryFrom<&protobuf::JsonWriterOptions> for JsonWriterOptions {
         opts: &protobuf::JsonWriterOptions,
     ) -> datafusion_common::Result<Self, Self::Error> {
         let compression: CompressionTypeVariant = opts.compression().into();
-        Ok(JsonWriterOptions::new(compression))
+        Ok(JsonWriterOptions::new(compression).with_explicit_nulls(opts.explicit_nulls))
     }
 }
 
@@ -1040,6 +1042,7 @@ impl TryFrom<&protobuf::CsvOptions> for CsvOptions {
                 .ignore_trailing_whitespace
                 .first()
                 .map(|h| *h != 0),
+            line_terminator: csv_line_terminator_from_proto(&proto_opts.line_terminator)?,
         })
     }
 }
@@ -1226,6 +1229,7 @@ impl TryFrom<&protobuf::JsonOptions> for JsonOptions {
             compression_level: proto_opts.compression_level,
             schema_infer_max_rec: proto_opts.schema_infer_max_rec.map(|h| h as usize),
             newline_delimited: proto_opts.newline_delimited.unwrap_or(true),
+            explicit_nulls: proto_opts.explicit_nulls,
         })
     }
 }
@@ -1264,6 +1268,17 @@ where
         .collect::<datafusion_common::Result<_, _>>()
 }
 
+/// Parses the bytes that terminate CSV records, which default to `\n`
+fn csv_line_terminator_from_proto(
+    line_terminator: &[u8],
+) -> datafusion_common::Result<CsvLineTerminator> {
+    match line_terminator {
+        b"" | b"\n" => Ok(CsvLineTerminator::Lf),
+        b"\r\n" => Ok(CsvLineTerminator::Crlf),
+        _ => Err(proto_error("Unsupported CSV line terminator")),
+    }
+}
+
 pub(crate) fn csv_writer_options_from_proto(
     writer_options: &protobuf::CsvWriterOptions,
 ) -> datafusion_common::Result<WriterBuilder> {
@@ -1876,6 +1876,9 @@ impl serde::Serialize for CsvOptions {
         if !self.ignore_trailing_whitespace.is_empty() {
             len += 1;
         }
+        if !self.line_terminator.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.CsvOptions", len)?;
         if !self.has_header.is_empty() {
             #[allow(clippy::needless_borrow)]
@@ -1971,6 +1974,11 @@ impl serde::Serialize for CsvOptions {
             #[allow(clippy::needless_borrows_for_generic_args)]
             struct_ser.serialize_field("ignoreTrailingWhitespace", pbjson::private::base64::encode(&self.ignore_trailing_whitespace).as_str())?;
         }
+        if !self.line_terminator.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("lineTerminator", pbjson::private::base64::encode(&self.line_terminator).as_str())?;
+        }
         struct_ser.end()
     }
 }
@@ -2019,6 +2027,8 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
             "ignoreLeadingWhitespace",
             "ignore_trailing_whitespace",
             "ignoreTrailingWhitespace",
+            "line_terminator",
+            "lineTerminator",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -2045,6 +2055,7 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
             QuoteStyle,
             IgnoreLeadingWhitespace,
             IgnoreTrailingWhitespace,
+            LineTerminator,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -2088,6 +2099,7 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                             "quoteStyle" | "quote_style" => Ok(GeneratedField::QuoteStyle),
                             "ignoreLeadingWhitespace" | "ignore_leading_whitespace" => Ok(GeneratedField::IgnoreLeadingWhitespace),
                             "ignoreTrailingWhitespace" | "ignore_trailing_whitespace" => Ok(GeneratedField::IgnoreTrailingWhitespace),
+                            "lineTerminator" | "line_terminator" => Ok(GeneratedField::LineTerminator),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -2129,6 +2141,7 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                 let mut quote_style__ = None;
                 let mut ignore_leading_whitespace__ = None;
                 let mut ignore_trailing_whitespace__ = None;
+                let mut line_terminator__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::HasHeader => {
@@ -2289,6 +2302,14 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                                 Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::LineTerminator => {
+                            if line_terminator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("lineTerminator"));
+                            }
+                            line_terminator__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
                     }
                 }
                 Ok(CsvOptions {
@@ -2314,6 +2335,7 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                     quote_style: quote_style__.unwrap_or_default(),
                     ignore_leading_whitespace: ignore_leading_whitespace__.unwrap_or_default(),
                     ignore_trailing_whitespace: ignore_trailing_whitespace__.unwrap_or_default(),
+                    line_terminator: line_terminator__.unwrap_or_default(),
                 })
             }
         }
@@ -2447,6 +2469,9 @@ impl serde::Serialize for CsvWriterOptions {
         if self.ignore_trailing_whitespace {
             len += 1;
         }
+        if !self.line_terminator.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.CsvWriterOptions", len)?;
         if self.compression != 0 {
             let v = CompressionTypeVariant::try_from(self.compression)
@@ -2494,6 +2519,11 @@ impl serde::Serialize for CsvWriterOptions {
         if self.ignore_trailing_whitespace {
             struct_ser.serialize_field("ignoreTrailingWhitespace", &self.ignore_trailing_whitespace)?;
         }
+        if !self.line_terminator.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("lineTerminator", pbjson::private::base64::encode(&self.line_terminator).as_str())?;
+        }
         struct_ser.end()
     }
 }
@@ -2528,6 +2558,8 @@ impl<'de> serde::Deserialize<'de> for CsvWriterOptions {
             "ignoreLeadingWhitespace",
             "ignore_trailing_whitespace",
             "ignoreTrailingWhitespace",
+            "line_terminator",
+            "lineTerminator",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -2546,6 +2578,7 @@ impl<'de> serde::Deserialize<'de> for CsvWriterOptions {
             QuoteStyle,
             IgnoreLeadingWhitespace,
             IgnoreTrailingWhitespace,
+            LineTerminator,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -2581,6 +2614,7 @@ impl<'de> serde::Deserialize<'de> for CsvWriterOptions {
                             "quoteStyle" | "quote_style" => Ok(GeneratedField::QuoteStyle),
                             "ignoreLeadingWhitespace" | "ignore_leading_whitespace" => Ok(GeneratedField::IgnoreLeadingWhitespace),
                             "ignoreTrailingWhitespace" | "ignore_trailing_whitespace" => Ok(GeneratedField::IgnoreTrailingWhitespace),
+                            "lineTerminator" | "line_terminator" => Ok(GeneratedField::LineTerminator),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -2614,6 +2648,7 @@ impl<'de> serde::Deserialize<'de> for CsvWriterOptions {
                 let mut quote_style__ = None;
                 let mut ignore_leading_whitespace__ = None;
                 let mut ignore_trailing_whitespace__ = None;
+                let mut line_terminator__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Compression => {
@@ -2700,6 +2735,14 @@ impl<'de> serde::Deserialize<'de> for CsvWriterOptions {
                             }
                             ignore_trailing_whitespace__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::LineTerminator => {
+                            if line_terminator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("lineTerminator"));
+                            }
+                            line_terminator__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
                     }
                 }
                 Ok(CsvWriterOptions {
@@ -2717,6 +2760,7 @@ impl<'de> serde::Deserialize<'de> for CsvWriterOptions {
                     quote_style: quote_style__.unwrap_or_default(),
                     ignore_leading_whitespace: ignore_leading_whitespace__.unwrap_or_default(),
                     ignore_trailing_whitespace: ignore_trailing_whitespace__.unwrap_or_default(),
+                    line_terminator: line_terminator__.unwrap_or_default(),
                 })
             }
         }
@@ -5046,6 +5090,9 @@ impl serde::Serialize for JsonOptions {
         if self.newline_delimited.is_some() {
             len += 1;
         }
+        if self.explicit_nulls.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.JsonOptions", len)?;
         if self.compression != 0 {
             let v = CompressionTypeVariant::try_from(self.compression)
@@ -5063,6 +5110,9 @@ impl serde::Serialize for JsonOptions {
         if let Some(v) = self.newline_delimited.as_ref() {
             struct_ser.serialize_field("newlineDelimited", v)?;
         }
+        if let Some(v) = self.explicit_nulls.as_ref() {
+            struct_ser.serialize_field("explicitNulls", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -5080,6 +5130,8 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
             "compressionLevel",
             "newline_delimited",
             "newlineDelimited",
+            "explicit_nulls",
+            "explicitNulls",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -5088,6 +5140,7 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
             SchemaInferMaxRec,
             CompressionLevel,
             NewlineDelimited,
+            ExplicitNulls,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -5113,6 +5166,7 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
                             "schemaInferMaxRec" | "schema_infer_max_rec" => Ok(GeneratedField::SchemaInferMaxRec),
                             "compressionLevel" | "compression_level" => Ok(GeneratedField::CompressionLevel),
                             "newlineDelimited" | "newline_delimited" => Ok(GeneratedField::NewlineDelimited),
+                            "explicitNulls" | "explicit_nulls" => Ok(GeneratedField::ExplicitNulls),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -5136,6 +5190,7 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
                 let mut schema_infer_max_rec__ = None;
                 let mut compression_level__ = None;
                 let mut newline_delimited__ = None;
+                let mut explicit_nulls__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Compression => {
@@ -5166,6 +5221,12 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
                             }
                             newline_delimited__ = map_.next_value()?;
                         }
+                        GeneratedField::ExplicitNulls => {
+                            if explicit_nulls__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("explicitNulls"));
+                            }
+                            explicit_nulls__ = map_.next_value()?;
+                        }
                     }
                 }
                 Ok(JsonOptions {
@@ -5173,6 +5234,7 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
                     schema_infer_max_rec: schema_infer_max_rec__,
                     compression_level: compression_level__,
                     newline_delimited: newline_delimited__,
+                    explicit_nulls: explicit_nulls__,
                 })
             }
         }
@@ -5190,12 +5252,18 @@ impl serde::Serialize for JsonWriterOptions {
         if self.compression != 0 {
             len += 1;
         }
+        if self.explicit_nulls {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.JsonWriterOptions", len)?;
         if self.compression != 0 {
             let v = CompressionTypeVariant::try_from(self.compression)
                 .map_err(|_| serde::ser::Error::custom(format!("Invalid variant {}", self.compression)))?;
             struct_ser.serialize_field("compression", &v)?;
         }
+        if self.explicit_nulls {
+            struct_ser.serialize_field("explicitNulls", &self.explicit_nulls)?;
+        }
         struct_ser.end()
     }
 }
@@ -5207,11 +5275,14 @@ impl<'de> serde::Deserialize<'de> for JsonWriterOptions {
     {
         const FIELDS: &[&str] = &[
             "compression",
+            "explicit_nulls",
+            "explicitNulls",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Compression,
+            ExplicitNulls,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -5234,6 +5305,7 @@ impl<'de> serde::Deserialize<'de> for JsonWriterOptions {
                     {
                         match value {
                             "compression" => Ok(GeneratedField::Compression),
+                            "explicitNulls" | "explicit_nulls" => Ok(GeneratedField::ExplicitNulls),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -5254,6 +5326,7 @@ impl<'de> serde::Deserialize<'de> for JsonWriterOptions {
                     V: serde::de::MapAccess<'de>,
             {
                 let mut compression__ = None;
+                let mut explicit_nulls__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Compression => {
@@ -5262,10 +5335,17 @@ impl<'de> serde::Deserialize<'de> for JsonWriterOptions {
                             }
                             compression__ = Some(map_.next_value::<CompressionTypeVariant>()? as i32);
                         }
+                        GeneratedField::ExplicitNulls => {
+                            if explicit_nulls__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("explicitNulls"));
+                            }
+                            explicit_nulls__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(JsonWriterOptions {
                     compression: compression__.unwrap_or_default(),
+                    explicit_nulls: explicit_nulls__.unwrap_or_default(),
                 })
             }
         }
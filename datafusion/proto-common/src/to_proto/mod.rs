@@ -31,7 +31,7 @@ use arrow::datatypes::{
 use arrow::ipc::writer::{
     CompressionContext, DictionaryTracker, IpcDataGenerator, IpcWriteOptions,
 };
-use datafusion_common::parsers::CsvQuoteStyle;
+use datafusion_common::parsers::{CsvLineTerminator, CsvQuoteStyle};
 use datafusion_common::{
     Column, ColumnStatistics, Constraint, Constraints, DFSchema, DFSchemaRef,
     DataFusionError, JoinSide, ScalarValue, Statistics,
@@ -883,6 +883,7 @@ impl TryFrom<&CsvWriterOptions> for protobuf::CsvWriterOptions {
         Ok(csv_writer_options_to_proto(
             &opts.writer_options,
             &opts.compression,
+            opts.line_terminator,
         ))
     }
 }
@@ -896,6 +897,7 @@ impl TryFrom<&JsonWriterOptions> for protobuf::JsonWriterOptions {
         let compression: protobuf::CompressionTypeVariant = opts.compression.into();
         Ok(protobuf::JsonWriterOptions {
             compression: compression.into(),
+            explicit_nulls: opts.explicit_nulls,
         })
     }
 }
@@ -1049,6 +1051,7 @@ impl TryFrom<&CsvOptions> for protobuf::CsvOptions {
             ignore_trailing_whitespace: opts
                 .ignore_trailing_whitespace
                 .map_or_else(Vec::new, |h| vec![h as u8]),
+            line_terminator: opts.line_terminator.as_bytes().to_vec(),
         })
     }
 }
@@ -1063,6 +1066,7 @@ impl TryFrom<&JsonOptions> for protobuf::JsonOptions {
             schema_infer_max_rec: opts.schema_infer_max_rec.map(|h| h as u64),
             compression_level: opts.compression_level,
             newline_delimited: Some(opts.newline_delimited),
+            explicit_nulls: opts.explicit_nulls,
         })
     }
 }
@@ -1184,6 +1188,7 @@ where
 pub(crate) fn csv_writer_options_to_proto(
     csv_options: &WriterBuilder,
     compression: &CompressionTypeVariant,
+    line_terminator: CsvLineTerminator,
 ) -> protobuf::CsvWriterOptions {
     let compression: protobuf::CompressionTypeVariant = compression.into();
     let quote_style: protobuf::CsvQuoteStyle = csv_options.quote_style().into();
@@ -1202,5 +1207,6 @@ pub(crate) fn csv_writer_options_to_proto(
         quote_style: quote_style.into(),
         ignore_leading_whitespace: csv_options.ignore_leading_whitespace(),
         ignore_trailing_whitespace: csv_options.ignore_trailing_whitespace(),
+        line_terminator: line_terminator.as_bytes().to_vec(),
     }
 }
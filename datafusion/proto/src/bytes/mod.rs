@@ -25,9 +25,10 @@ use crate::physical_plan::{
     PhysicalPlanDecodeContext, PhysicalProtoConverterExtension,
 };
 use crate::protobuf;
-use datafusion_common::{Result, plan_datafusion_err};
+use datafusion_common::{Result, ScalarValue, plan_datafusion_err};
 use datafusion_execution::TaskContext;
-use datafusion_expr::{Expr, LogicalPlan};
+use datafusion_expr::registry::FunctionRegistry;
+use datafusion_expr::{AggregateUDF, Expr, LogicalPlan};
 use prost::{
     Message,
     bytes::{Bytes, BytesMut},
@@ -107,6 +108,76 @@ impl Serializeable for Expr {
     }
 }
 
+/// Serialize the partial state of an aggregate function as bytes, using
+/// [`AggregateUDF::serialize_state`]
+///
+/// The output can be shipped to another process and decoded with
+/// [`aggregate_state_from_bytes`] there, for example to merge partial
+/// aggregates in a distributed engine.
+pub fn aggregate_state_to_bytes(
+    udaf: &AggregateUDF,
+    state: &[ScalarValue],
+) -> Result<Bytes> {
+    let extension_codec = DefaultLogicalExtensionCodec {};
+    aggregate_state_to_bytes_with_extension_codec(udaf, state, &extension_codec)
+}
+
+/// Serialize the partial state of an aggregate function as bytes, using the
+/// provided extension codec to encode the function definition
+pub fn aggregate_state_to_bytes_with_extension_codec(
+    udaf: &AggregateUDF,
+    state: &[ScalarValue],
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<Bytes> {
+    let mut fun_definition = Vec::new();
+    extension_codec.try_encode_udaf(udaf, &mut fun_definition)?;
+    let protobuf = protobuf::AggregateStateNode {
+        fun_name: udaf.name().to_string(),
+        fun_definition: (!fun_definition.is_empty()).then_some(fun_definition),
+        state: udaf.serialize_state(state)?,
+    };
+
+    let mut buffer = BytesMut::new();
+    protobuf
+        .encode(&mut buffer)
+        .map_err(|e| plan_datafusion_err!("Error encoding protobuf as bytes: {e}"))?;
+    Ok(buffer.into())
+}
+
+/// Deserialize the partial state of an aggregate function from bytes,
+/// resolving the function with the specified `ctx`
+///
+/// Returns the aggregate function together with the state values, which
+/// are decoded with [`AggregateUDF::deserialize_state`].
+pub fn aggregate_state_from_bytes(
+    bytes: &[u8],
+    ctx: &TaskContext,
+) -> Result<(Arc<AggregateUDF>, Vec<ScalarValue>)> {
+    let extension_codec = DefaultLogicalExtensionCodec {};
+    aggregate_state_from_bytes_with_extension_codec(bytes, ctx, &extension_codec)
+}
+
+/// Deserialize the partial state of an aggregate function from bytes, using
+/// the provided extension codec to decode the function definition
+pub fn aggregate_state_from_bytes_with_extension_codec(
+    bytes: &[u8],
+    ctx: &TaskContext,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<(Arc<AggregateUDF>, Vec<ScalarValue>)> {
+    let protobuf = protobuf::AggregateStateNode::decode(bytes).map_err(|e| {
+        plan_datafusion_err!("Error decoding aggregate state as protobuf: {e}")
+    })?;
+
+    let udaf = match &protobuf.fun_definition {
+        Some(buf) => extension_codec.try_decode_udaf(&protobuf.fun_name, buf)?,
+        None => ctx
+            .udaf(&protobuf.fun_name)
+            .or_else(|_| extension_codec.try_decode_udaf(&protobuf.fun_name, &[]))?,
+    };
+    let state = udaf.deserialize_state(&protobuf.state)?;
+    Ok((udaf, state))
+}
+
 /// Serialize a LogicalPlan as bytes
 pub fn logical_plan_to_bytes(plan: &LogicalPlan) -> Result<Bytes> {
     let extension_codec = DefaultLogicalExtensionCodec {};
@@ -583,6 +583,9 @@ pub struct EmptyMessage {}
 pub struct JsonWriterOptions {
     #[prost(enumeration = "CompressionTypeVariant", tag = "1")]
     pub compression: i32,
+    /// Whether to write null values as explicit nulls
+    #[prost(bool, tag = "2")]
+    pub explicit_nulls: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CsvWriterOptions {
@@ -628,6 +631,9 @@ pub struct CsvWriterOptions {
     /// Whether to ignore trailing whitespace in string values
     #[prost(bool, tag = "14")]
     pub ignore_trailing_whitespace: bool,
+    /// The bytes that terminate each record. Defaults to `\n`
+    #[prost(bytes = "vec", tag = "15")]
+    pub line_terminator: ::prost::alloc::vec::Vec<u8>,
 }
 /// Options controlling CSV format
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
@@ -698,6 +704,9 @@ pub struct CsvOptions {
     /// Whether to ignore trailing whitespace in string values
     #[prost(bytes = "vec", tag = "22")]
     pub ignore_trailing_whitespace: ::prost::alloc::vec::Vec<u8>,
+    /// Optional bytes that terminate each record when writing
+    #[prost(bytes = "vec", tag = "23")]
+    pub line_terminator: ::prost::alloc::vec::Vec<u8>,
 }
 /// Options controlling CSV format
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
@@ -714,6 +723,9 @@ pub struct JsonOptions {
     /// Whether to read as newline-delimited JSON (default true). When false, expects JSON array format \[{},...\]
     #[prost(bool, optional, tag = "4")]
     pub newline_delimited: ::core::option::Option<bool>,
+    /// Whether to write null values as explicit nulls
+    #[prost(bool, optional, tag = "5")]
+    pub explicit_nulls: ::core::option::Option<bool>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TableParquetOptions {
@@ -608,6 +608,141 @@ impl<'de> serde::Deserialize<'de> for AggregateNode {
         deserializer.deserialize_struct("datafusion.AggregateNode", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for AggregateStateNode {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.fun_name.is_empty() {
+            len += 1;
+        }
+        if self.fun_definition.is_some() {
+            len += 1;
+        }
+        if !self.state.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("datafusion.AggregateStateNode", len)?;
+        if !self.fun_name.is_empty() {
+            struct_ser.serialize_field("funName", &self.fun_name)?;
+        }
+        if let Some(v) = self.fun_definition.as_ref() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("funDefinition", pbjson::private::base64::encode(&v).as_str())?;
+        }
+        if !self.state.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("state", pbjson::private::base64::encode(&self.state).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for AggregateStateNode {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "fun_name",
+            "funName",
+            "fun_definition",
+            "funDefinition",
+            "state",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FunName,
+            FunDefinition,
+            State,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl serde::de::Visitor<'_> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "funName" | "fun_name" => Ok(GeneratedField::FunName),
+                            "funDefinition" | "fun_definition" => Ok(GeneratedField::FunDefinition),
+                            "state" => Ok(GeneratedField::State),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = AggregateStateNode;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct datafusion.AggregateStateNode")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<AggregateStateNode, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut fun_name__ = None;
+                let mut fun_definition__ = None;
+                let mut state__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::FunName => {
+                            if fun_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("funName"));
+                            }
+                            fun_name__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::FunDefinition => {
+                            if fun_definition__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("funDefinition"));
+                            }
+                            fun_definition__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::BytesDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::State => {
+                            if state__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("state"));
+                            }
+                            state__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(AggregateStateNode {
+                    fun_name: fun_name__.unwrap_or_default(),
+                    fun_definition: fun_definition__,
+                    state: state__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("datafusion.AggregateStateNode", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for AggregateUdfExprNode {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -981,6 +1116,9 @@ impl serde::Serialize for AnalyzeExecNode {
         if !self.metric_categories.is_empty() {
             len += 1;
         }
+        if !self.format.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.AnalyzeExecNode", len)?;
         if self.verbose {
             struct_ser.serialize_field("verbose", &self.verbose)?;
@@ -1000,6 +1138,9 @@ impl serde::Serialize for AnalyzeExecNode {
         if !self.metric_categories.is_empty() {
             struct_ser.serialize_field("metricCategories", &self.metric_categories)?;
         }
+        if !self.format.is_empty() {
+            struct_ser.serialize_field("format", &self.format)?;
+        }
         struct_ser.end()
     }
 }
@@ -1019,6 +1160,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeExecNode {
             "hasMetricCategories",
             "metric_categories",
             "metricCategories",
+            "format",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1029,6 +1171,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeExecNode {
             Schema,
             HasMetricCategories,
             MetricCategories,
+            Format,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1056,6 +1199,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeExecNode {
                             "schema" => Ok(GeneratedField::Schema),
                             "hasMetricCategories" | "has_metric_categories" => Ok(GeneratedField::HasMetricCategories),
                             "metricCategories" | "metric_categories" => Ok(GeneratedField::MetricCategories),
+                            "format" => Ok(GeneratedField::Format),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1081,6 +1225,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeExecNode {
                 let mut schema__ = None;
                 let mut has_metric_categories__ = None;
                 let mut metric_categories__ = None;
+                let mut format__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Verbose => {
@@ -1119,6 +1264,12 @@ impl<'de> serde::Deserialize<'de> for AnalyzeExecNode {
                             }
                             metric_categories__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::Format => {
+                            if format__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("format"));
+                            }
+                            format__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(AnalyzeExecNode {
@@ -1128,6 +1279,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeExecNode {
                     schema: schema__,
                     has_metric_categories: has_metric_categories__.unwrap_or_default(),
                     metric_categories: metric_categories__.unwrap_or_default(),
+                    format: format__.unwrap_or_default(),
                 })
             }
         }
@@ -1148,6 +1300,9 @@ impl serde::Serialize for AnalyzeNode {
         if self.verbose {
             len += 1;
         }
+        if !self.format.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.AnalyzeNode", len)?;
         if let Some(v) = self.input.as_ref() {
             struct_ser.serialize_field("input", v)?;
@@ -1155,6 +1310,9 @@ impl serde::Serialize for AnalyzeNode {
         if self.verbose {
             struct_ser.serialize_field("verbose", &self.verbose)?;
         }
+        if !self.format.is_empty() {
+            struct_ser.serialize_field("format", &self.format)?;
+        }
         struct_ser.end()
     }
 }
@@ -1167,12 +1325,14 @@ impl<'de> serde::Deserialize<'de> for AnalyzeNode {
         const FIELDS: &[&str] = &[
             "input",
             "verbose",
+            "format",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Input,
             Verbose,
+            Format,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1196,6 +1356,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeNode {
                         match value {
                             "input" => Ok(GeneratedField::Input),
                             "verbose" => Ok(GeneratedField::Verbose),
+                            "format" => Ok(GeneratedField::Format),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1217,6 +1378,7 @@ impl<'de> serde::Deserialize<'de> for AnalyzeNode {
             {
                 let mut input__ = None;
                 let mut verbose__ = None;
+                let mut format__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Input => {
@@ -1231,11 +1393,18 @@ impl<'de> serde::Deserialize<'de> for AnalyzeNode {
                             }
                             verbose__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::Format => {
+                            if format__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("format"));
+                            }
+                            format__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(AnalyzeNode {
                     input: input__,
                     verbose: verbose__.unwrap_or_default(),
+                    format: format__.unwrap_or_default(),
                 })
             }
         }
@@ -5533,6 +5702,8 @@ impl serde::Serialize for dml_node::Type {
             Self::InsertOverwrite => "INSERT_OVERWRITE",
             Self::InsertReplace => "INSERT_REPLACE",
             Self::Truncate => "TRUNCATE",
+            Self::Merge => "MERGE",
+            Self::Analyze => "ANALYZE",
         };
         serializer.serialize_str(variant)
     }
@@ -5551,6 +5722,8 @@ impl<'de> serde::Deserialize<'de> for dml_node::Type {
             "INSERT_OVERWRITE",
             "INSERT_REPLACE",
             "TRUNCATE",
+            "MERGE",
+            "ANALYZE",
         ];
 
         struct GeneratedVisitor;
@@ -5598,6 +5771,8 @@ impl<'de> serde::Deserialize<'de> for dml_node::Type {
                     "INSERT_OVERWRITE" => Ok(dml_node::Type::InsertOverwrite),
                     "INSERT_REPLACE" => Ok(dml_node::Type::InsertReplace),
                     "TRUNCATE" => Ok(dml_node::Type::Truncate),
+                    "MERGE" => Ok(dml_node::Type::Merge),
+                    "ANALYZE" => Ok(dml_node::Type::Analyze),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -25011,6 +25186,12 @@ impl serde::Serialize for UnnestOptions {
         if !self.recursions.is_empty() {
             len += 1;
         }
+        if self.ordinality {
+            len += 1;
+        }
+        if self.outer {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.UnnestOptions", len)?;
         if self.preserve_nulls {
             struct_ser.serialize_field("preserveNulls", &self.preserve_nulls)?;
@@ -25018,6 +25199,12 @@ impl serde::Serialize for UnnestOptions {
         if !self.recursions.is_empty() {
             struct_ser.serialize_field("recursions", &self.recursions)?;
         }
+        if self.ordinality {
+            struct_ser.serialize_field("ordinality", &self.ordinality)?;
+        }
+        if self.outer {
+            struct_ser.serialize_field("outer", &self.outer)?;
+        }
         struct_ser.end()
     }
 }
@@ -25031,12 +25218,16 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
             "preserve_nulls",
             "preserveNulls",
             "recursions",
+            "ordinality",
+            "outer",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             PreserveNulls,
             Recursions,
+            Ordinality,
+            Outer,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -25060,6 +25251,8 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
                         match value {
                             "preserveNulls" | "preserve_nulls" => Ok(GeneratedField::PreserveNulls),
                             "recursions" => Ok(GeneratedField::Recursions),
+                            "ordinality" => Ok(GeneratedField::Ordinality),
+                            "outer" => Ok(GeneratedField::Outer),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -25081,6 +25274,8 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
             {
                 let mut preserve_nulls__ = None;
                 let mut recursions__ = None;
+                let mut ordinality__ = None;
+                let mut outer__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::PreserveNulls => {
@@ -25095,11 +25290,25 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
                             }
                             recursions__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::Ordinality => {
+                            if ordinality__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ordinality"));
+                            }
+                            ordinality__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Outer => {
+                            if outer__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("outer"));
+                            }
+                            outer__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(UnnestOptions {
                     preserve_nulls: preserve_nulls__.unwrap_or_default(),
                     recursions: recursions__.unwrap_or_default(),
+                    ordinality: ordinality__.unwrap_or_default(),
+                    outer: outer__.unwrap_or_default(),
                 })
             }
         }
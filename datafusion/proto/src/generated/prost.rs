@@ -344,6 +344,9 @@ pub struct AnalyzeNode {
     pub input: ::core::option::Option<::prost::alloc::boxed::Box<LogicalPlanNode>>,
     #[prost(bool, tag = "2")]
     pub verbose: bool,
+    /// Output format, e.g. "json". Empty means the default "indent" format.
+    #[prost(string, tag = "3")]
+    pub format: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ExplainNode {
@@ -458,6 +461,8 @@ pub mod dml_node {
         InsertOverwrite = 4,
         InsertReplace = 5,
         Truncate = 6,
+        Merge = 7,
+        Analyze = 8,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -473,6 +478,8 @@ pub mod dml_node {
                 Self::InsertOverwrite => "INSERT_OVERWRITE",
                 Self::InsertReplace => "INSERT_REPLACE",
                 Self::Truncate => "TRUNCATE",
+                Self::Merge => "MERGE",
+                Self::Analyze => "ANALYZE",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -485,6 +492,8 @@ pub mod dml_node {
                 "INSERT_OVERWRITE" => Some(Self::InsertOverwrite),
                 "INSERT_REPLACE" => Some(Self::InsertReplace),
                 "TRUNCATE" => Some(Self::Truncate),
+                "MERGE" => Some(Self::Merge),
+                "ANALYZE" => Some(Self::Analyze),
                 _ => None,
             }
         }
@@ -532,6 +541,10 @@ pub struct UnnestOptions {
     pub preserve_nulls: bool,
     #[prost(message, repeated, tag = "2")]
     pub recursions: ::prost::alloc::vec::Vec<RecursionUnnestOption>,
+    #[prost(bool, tag = "3")]
+    pub ordinality: bool,
+    #[prost(bool, tag = "4")]
+    pub outer: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct RecursionUnnestOption {
@@ -842,6 +855,18 @@ pub struct AggregateUdfExprNode {
     #[prost(enumeration = "NullTreatment", optional, tag = "7")]
     pub null_treatment: ::core::option::Option<i32>,
 }
+/// Partial state of an aggregate function, serialized with
+/// `AggregateUDFImpl::serialize_state`, for exchanging partial aggregates
+/// between processes
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct AggregateStateNode {
+    #[prost(string, tag = "1")]
+    pub fun_name: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub fun_definition: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub state: ::prost::alloc::vec::Vec<u8>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScalarUdfExprNode {
     #[prost(string, tag = "1")]
@@ -1831,6 +1856,9 @@ pub struct AnalyzeExecNode {
     pub has_metric_categories: bool,
     #[prost(string, repeated, tag = "6")]
     pub metric_categories: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Output format, e.g. "json". Empty means the default "indent" format.
+    #[prost(string, tag = "7")]
+    pub format: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CrossJoinExecNode {
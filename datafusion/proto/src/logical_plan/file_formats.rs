@@ -25,7 +25,7 @@ use crate::protobuf::{
 use datafusion_common::config::{CsvOptions, JsonOptions};
 use datafusion_common::{
     TableReference, exec_datafusion_err, exec_err, not_impl_err,
-    parsers::{CompressionTypeVariant, CsvQuoteStyle},
+    parsers::{CompressionTypeVariant, CsvLineTerminator, CsvQuoteStyle},
 };
 use datafusion_datasource::file_format::FileFormatFactory;
 use datafusion_datasource_arrow::file_format::ArrowFormatFactory;
@@ -72,6 +72,7 @@ impl CsvOptionsProto {
                 ignore_trailing_whitespace: options
                     .ignore_trailing_whitespace
                     .map_or(vec![], |v| vec![v as u8]),
+                line_terminator: options.line_terminator.as_bytes().to_vec(),
             }
         } else {
             CsvOptionsProto::default()
@@ -180,6 +181,10 @@ impl From<&CsvOptionsProto> for CsvOptions {
             } else {
                 Some(proto.ignore_trailing_whitespace[0] != 0)
             },
+            line_terminator: match proto.line_terminator.as_slice() {
+                b"\r\n" => CsvLineTerminator::Crlf,
+                _ => CsvLineTerminator::Lf,
+            },
         }
     }
 }
@@ -267,6 +272,7 @@ impl JsonOptionsProto {
                 schema_infer_max_rec: options.schema_infer_max_rec.map(|v| v as u64),
                 compression_level: options.compression_level,
                 newline_delimited: Some(options.newline_delimited),
+                explicit_nulls: options.explicit_nulls,
             }
         } else {
             JsonOptionsProto::default()
@@ -287,6 +293,7 @@ impl From<&JsonOptionsProto> for JsonOptions {
             schema_infer_max_rec: proto.schema_infer_max_rec.map(|v| v as usize),
             compression_level: proto.compression_level,
             newline_delimited: proto.newline_delimited.unwrap_or(true),
+            explicit_nulls: proto.explicit_nulls,
         }
     }
 }
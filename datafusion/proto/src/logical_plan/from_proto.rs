@@ -69,6 +69,8 @@ impl From<&protobuf::UnnestOptions> for UnnestOptions {
                     depth: r.depth as usize,
                 })
                 .collect::<Vec<_>>(),
+            ordinality: opts.ordinality,
+            outer: opts.outer,
         }
     }
 }
@@ -243,6 +245,8 @@ impl From<protobuf::dml_node::Type> for WriteOp {
             protobuf::dml_node::Type::InsertReplace => WriteOp::Insert(InsertOp::Replace),
             protobuf::dml_node::Type::Ctas => WriteOp::Ctas,
             protobuf::dml_node::Type::Truncate => WriteOp::Truncate,
+            protobuf::dml_node::Type::Merge => WriteOp::Merge,
+            protobuf::dml_node::Type::Analyze => WriteOp::Analyze,
         }
     }
 }
@@ -37,7 +37,7 @@ use arrow::datatypes::{DataType, Field, Schema, SchemaBuilder, SchemaRef};
 use datafusion_catalog::cte_worktable::CteWorkTable;
 use datafusion_catalog::empty::EmptyTable;
 use datafusion_common::file_options::file_type::FileType;
-use datafusion_common::format::ExplainFormat;
+use datafusion_common::format::{ExplainAnalyzeFormat, ExplainFormat};
 use datafusion_common::{
     Result, TableReference, ToDFSchema, assert_or_internal_err, context,
     internal_datafusion_err, internal_err, not_impl_err, plan_err,
@@ -795,8 +795,18 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlanType::Analyze(analyze) => {
                 let input: LogicalPlan =
                     into_logical_plan!(analyze.input, ctx, extension_codec)?;
+                let format = if analyze.format.is_empty() {
+                    ExplainAnalyzeFormat::Indent
+                } else {
+                    analyze.format.parse()?
+                };
                 LogicalPlanBuilder::from(input)
-                    .explain(analyze.verbose, true)?
+                    .explain_option_format(
+                        datafusion_expr::logical_plan::ExplainOption::default()
+                            .with_verbose(analyze.verbose)
+                            .with_analyze(true)
+                            .with_analyze_format(format),
+                    )?
                     .build()
             }
             LogicalPlanType::Explain(explain) => {
@@ -1492,8 +1502,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                 join_type,
                 join_constraint,
                 null_equality,
+                asof_match,
                 ..
             }) => {
+                if asof_match.is_some() {
+                    return not_impl_err!("Serializing ASOF joins is not supported");
+                }
                 let left: LogicalPlanNode = LogicalPlanNode::try_from_logical_plan(
                     left.as_ref(),
                     extension_codec,
@@ -1762,6 +1776,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                         protobuf::AnalyzeNode {
                             input: Some(Box::new(input)),
                             verbose: a.verbose,
+                            format: a.format.to_string(),
                         },
                     ))),
                 })
@@ -1921,6 +1936,12 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Ddl(DdlStatement::DropFunction(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropFunction",
             )),
+            LogicalPlan::Ddl(DdlStatement::RefreshTableSchema(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for RefreshTableSchema",
+            )),
+            LogicalPlan::Ddl(DdlStatement::AlterTable(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for AlterTable",
+            )),
             LogicalPlan::Statement(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Statement",
             )),
@@ -1974,6 +1995,9 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::DescribeTable(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DescribeTable",
             )),
+            LogicalPlan::LateralTableFunction(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for LateralTableFunction",
+            )),
             LogicalPlan::RecursiveQuery(recursive) => {
                 let static_term = LogicalPlanNode::try_from_logical_plan(
                     recursive.static_term.as_ref(),
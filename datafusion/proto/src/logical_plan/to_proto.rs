@@ -65,6 +65,8 @@ impl From<&UnnestOptions> for protobuf::UnnestOptions {
                     depth: r.depth as u32,
                 })
                 .collect(),
+            ordinality: opts.ordinality,
+            outer: opts.outer,
         }
     }
 }
@@ -760,6 +762,8 @@ impl From<&WriteOp> for protobuf::dml_node::Type {
             WriteOp::Update => protobuf::dml_node::Type::Update,
             WriteOp::Ctas => protobuf::dml_node::Type::Ctas,
             WriteOp::Truncate => protobuf::dml_node::Type::Truncate,
+            WriteOp::Merge => protobuf::dml_node::Type::Merge,
+            WriteOp::Analyze => protobuf::dml_node::Type::Analyze,
         }
     }
 }
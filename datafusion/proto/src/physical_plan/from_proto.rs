@@ -900,6 +900,7 @@ impl TryFrom<&protobuf::FileSinkConfig> for FileSinkConfig {
             keep_partition_by_columns: conf.keep_partition_by_columns,
             file_extension: conf.file_extension.clone(),
             file_output_mode,
+            statistics_cache: None,
         })
     }
 }
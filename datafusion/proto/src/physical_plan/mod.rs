@@ -25,6 +25,7 @@ use arrow::compute::SortOptions;
 use arrow::datatypes::{IntervalMonthDayNanoType, Schema, SchemaRef};
 use datafusion_catalog::memory::MemorySourceConfig;
 use datafusion_common::config::CsvOptions;
+use datafusion_common::format::ExplainAnalyzeFormat;
 use datafusion_common::{
     DataFusionError, Result, internal_datafusion_err, internal_err, not_impl_err,
 };
@@ -890,13 +891,14 @@ impl protobuf::PhysicalPlanNode {
                 .task_ctx()
                 .runtime_env()
                 .object_store(object_store_url)?;
-            let metadata_cache = ctx
-                .task_ctx()
-                .runtime_env()
-                .cache_manager
-                .get_file_metadata_cache();
-            let reader_factory =
-                Arc::new(CachedParquetFileReaderFactory::new(store, metadata_cache));
+            let runtime_env = ctx.task_ctx().runtime_env();
+            let reader_factory = Arc::new(
+                CachedParquetFileReaderFactory::new(
+                    store,
+                    runtime_env.cache_manager.get_file_metadata_cache(),
+                )
+                .with_file_range_cache(runtime_env.cache_manager.get_file_range_cache()),
+            );
 
             let mut source = ParquetSource::new(table_schema)
                 .with_parquet_file_reader_factory(reader_factory)
@@ -1821,14 +1823,22 @@ impl protobuf::PhysicalPlanNode {
         } else {
             None
         };
-        Ok(Arc::new(AnalyzeExec::new(
-            analyze.verbose,
-            analyze.show_statistics,
-            vec![MetricType::Summary, MetricType::Dev],
-            metric_categories,
-            input,
-            Arc::new(convert_required!(analyze.schema)?),
-        )))
+        let format = if analyze.format.is_empty() {
+            ExplainAnalyzeFormat::Indent
+        } else {
+            analyze.format.parse()?
+        };
+        Ok(Arc::new(
+            AnalyzeExec::new(
+                analyze.verbose,
+                analyze.show_statistics,
+                vec![MetricType::Summary, MetricType::Dev],
+                metric_categories,
+                input,
+                Arc::new(convert_required!(analyze.schema)?),
+            )
+            .with_format(format),
+        ))
     }
 
     fn try_into_json_sink_physical_plan(
@@ -2319,6 +2329,7 @@ impl protobuf::PhysicalPlanNode {
                     schema: Some(exec.schema().as_ref().try_into()?),
                     has_metric_categories,
                     metric_categories,
+                    format: exec.format().to_string(),
                 },
             ))),
         })
@@ -40,7 +40,9 @@ use datafusion_physical_plan::expressions::{
     IsNotNullExpr, IsNullExpr, LikeExpr, Literal, NegativeExpr, NotExpr, TryCastExpr,
     UnKnownColumn,
 };
-use datafusion_physical_plan::joins::{HashExpr, HashTableLookupExpr};
+use datafusion_physical_plan::joins::{
+    BloomFilterLookupExpr, HashExpr, HashTableLookupExpr,
+};
 use datafusion_physical_plan::udaf::AggregateFunctionExpr;
 use datafusion_physical_plan::windows::{PlainAggregateWindowExpr, WindowUDFExpr};
 use datafusion_physical_plan::{Partitioning, PhysicalExpr, WindowExpr};
@@ -273,7 +275,10 @@ pub fn serialize_physical_expr_with_converter(
     //
     // In distributed execution, the remote worker won't have access to the hash
     // table anyway, so the best we can do is skip this optimization.
-    if expr.downcast_ref::<HashTableLookupExpr>().is_some() {
+    // BloomFilterLookupExpr is replaced for the same reasons.
+    if expr.downcast_ref::<HashTableLookupExpr>().is_some()
+        || expr.downcast_ref::<BloomFilterLookupExpr>().is_some()
+    {
         let value = datafusion_proto_common::ScalarValue {
             value: Some(datafusion_proto_common::scalar_value::Value::BoolValue(
                 true,
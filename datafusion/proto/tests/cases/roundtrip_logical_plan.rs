@@ -68,7 +68,7 @@ use datafusion::physical_expr::PhysicalExpr;
 use datafusion::prelude::*;
 use datafusion::test_util::{TestTableFactory, TestTableProvider};
 use datafusion_common::config::TableOptions;
-use datafusion_common::format::ExplainFormat;
+use datafusion_common::format::{ExplainAnalyzeFormat, ExplainFormat};
 use datafusion_common::scalar::ScalarStructBuilder;
 use datafusion_common::{
     DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue, TableReference,
@@ -299,6 +299,27 @@ async fn roundtrip_explain_format_tree() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn roundtrip_explain_analyze_format_json() -> Result<()> {
+    let ctx = SessionContext::new();
+    let plan = ctx
+        .state()
+        .create_logical_plan("EXPLAIN (ANALYZE, FORMAT JSON) SELECT 1")
+        .await?;
+
+    let bytes = logical_plan_to_bytes(&plan)?;
+    let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx.task_ctx())?;
+
+    match logical_round_trip {
+        LogicalPlan::Analyze(analyze) => {
+            assert_eq!(analyze.format, ExplainAnalyzeFormat::Json);
+        }
+        plan => panic!("expected Analyze plan, got {plan:?}"),
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn roundtrip_custom_listing_tables() -> Result<()> {
     let ctx = SessionContext::new();
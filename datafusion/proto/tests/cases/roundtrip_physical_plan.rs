@@ -92,6 +92,7 @@ use datafusion::scalar::ScalarValue;
 use datafusion_common::config::{ConfigOptions, TableParquetOptions};
 use datafusion_common::file_options::csv_writer::CsvWriterOptions;
 use datafusion_common::file_options::json_writer::JsonWriterOptions;
+use datafusion_common::format::ExplainAnalyzeFormat;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::stats::Precision;
 use datafusion_common::{
@@ -1554,9 +1555,21 @@ fn roundtrip_analyze() -> Result<()> {
         false,
         vec![MetricType::Summary, MetricType::Dev],
         None,
-        input,
-        Arc::new(schema),
-    )))
+        Arc::clone(&input) as _,
+        Arc::new(schema.clone()),
+    )))?;
+
+    roundtrip_test(Arc::new(
+        AnalyzeExec::new(
+            false,
+            false,
+            vec![MetricType::Summary, MetricType::Dev],
+            None,
+            input,
+            Arc::new(schema),
+        )
+        .with_format(ExplainAnalyzeFormat::Json),
+    ))
 }
 
 #[tokio::test]
@@ -1586,6 +1599,7 @@ fn roundtrip_json_sink() -> Result<()> {
         keep_partition_by_columns: true,
         file_extension: "json".into(),
         file_output_mode: FileOutputMode::SingleFile,
+        statistics_cache: None,
     };
     let data_sink = Arc::new(JsonSink::new(
         file_sink_config,
@@ -1625,6 +1639,7 @@ fn roundtrip_csv_sink() -> Result<()> {
         keep_partition_by_columns: true,
         file_extension: "csv".into(),
         file_output_mode: FileOutputMode::Directory,
+        statistics_cache: None,
     };
     let data_sink = Arc::new(CsvSink::new(
         file_sink_config,
@@ -1678,6 +1693,7 @@ fn roundtrip_parquet_sink() -> Result<()> {
         keep_partition_by_columns: true,
         file_extension: "parquet".into(),
         file_output_mode: FileOutputMode::Automatic,
+        statistics_cache: None,
     };
     let data_sink = Arc::new(ParquetSink::new(
         file_sink_config,
@@ -100,6 +100,47 @@ fn udf_roundtrip_without_registry() {
     Expr::from_bytes(&bytes).unwrap();
 }
 
+#[test]
+fn aggregate_state_roundtrip_with_registry() {
+    use datafusion_common::ScalarValue;
+    use datafusion_proto::bytes::{aggregate_state_from_bytes, aggregate_state_to_bytes};
+
+    let ctx = SessionContext::new();
+    let avg = ctx.udaf("avg").expect("could not find udaf");
+    let state = vec![
+        ScalarValue::UInt64(Some(3)),
+        ScalarValue::Float64(Some(7.5)),
+    ];
+
+    let bytes = aggregate_state_to_bytes(&avg, &state).unwrap();
+    let (udaf, deserialized_state) =
+        aggregate_state_from_bytes(&bytes, ctx.task_ctx().as_ref()).unwrap();
+
+    assert_eq!(udaf.name(), "avg");
+    assert_eq!(deserialized_state, state);
+}
+
+#[test]
+#[should_panic(
+    expected = "LogicalExtensionCodec is not provided for aggregate function avg"
+)]
+fn aggregate_state_roundtrip_without_registry() {
+    use datafusion::execution::TaskContext;
+    use datafusion_common::ScalarValue;
+    use datafusion_proto::bytes::{aggregate_state_from_bytes, aggregate_state_to_bytes};
+
+    let ctx = SessionContext::new();
+    let avg = ctx.udaf("avg").expect("could not find udaf");
+    let state = vec![
+        ScalarValue::UInt64(Some(3)),
+        ScalarValue::Float64(Some(7.5)),
+    ];
+
+    let bytes = aggregate_state_to_bytes(&avg, &state).unwrap();
+    // should explode
+    aggregate_state_from_bytes(&bytes, &TaskContext::default()).unwrap();
+}
+
 fn roundtrip_expr(expr: &Expr) -> Expr {
     let bytes = expr.to_bytes().unwrap();
     Expr::from_bytes(&bytes).unwrap()
@@ -19,6 +19,7 @@
 //! based on statistics (e.g. Parquet Row Groups)
 //!
 //! [`Expr`]: https://docs.rs/datafusion/latest/datafusion/logical_expr/enum.Expr.html
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -35,15 +36,18 @@ use datafusion_physical_plan::metrics::Count;
 use log::{debug, trace};
 
 use datafusion_common::error::Result;
+use datafusion_common::pruning::PrunableStatistics;
 use datafusion_common::tree_node::{TransformedResult, TreeNodeRecursion};
-use datafusion_common::{Column, DFSchema, assert_eq_or_internal_err};
+use datafusion_common::{Column, DFSchema, Statistics, assert_eq_or_internal_err};
 use datafusion_common::{
     ScalarValue, internal_datafusion_err, plan_datafusion_err, plan_err,
     tree_node::{Transformed, TreeNode},
 };
 use datafusion_expr_common::operator::Operator;
 use datafusion_physical_expr::utils::{Guarantee, LiteralGuarantee};
-use datafusion_physical_expr::{PhysicalExprRef, expressions as phys_expr};
+use datafusion_physical_expr::{
+    PhysicalExprRef, ScalarFunctionExpr, expressions as phys_expr,
+};
 use datafusion_physical_expr_common::physical_expr::snapshot_physical_expr_opt;
 use datafusion_physical_plan::{ColumnarValue, PhysicalExpr};
 
@@ -568,6 +572,25 @@ impl PruningPredicate {
         Ok(builder.build())
     }
 
+    /// Evaluates the pruning predicate for containers described by
+    /// [`Statistics`], such as the files or partitions of a custom
+    /// [`TableProvider`], see [`Self::prune`] for the meaning of the result.
+    ///
+    /// Only exact statistics are used. Containers need to implement
+    /// [`PruningStatistics`] to provide other statistics, such as bloom
+    /// filters.
+    ///
+    /// [`TableProvider`]: https://docs.rs/datafusion/latest/datafusion/datasource/trait.TableProvider.html
+    pub fn prune_statistics(
+        &self,
+        statistics: Vec<Arc<Statistics>>,
+    ) -> Result<Vec<bool>> {
+        self.prune(&PrunableStatistics::new(
+            statistics,
+            Arc::clone(&self.schema),
+        ))
+    }
+
     /// Return a reference to the input schema
     pub fn schema(&self) -> &SchemaRef {
         &self.schema
@@ -1099,6 +1122,7 @@ impl<'a> PruningExpressionBuilder<'a> {
 /// 4. `abs(a - 10) > 0` not supported
 /// 5. `cast(can_prunable_expr) > 10`
 /// 6. `try_cast(can_prunable_expr) > 10`
+/// 7. `date_trunc('day', can_prunable_expr) > 10`
 ///
 /// More rewrite rules are still in progress.
 fn rewrite_expr_to_prunable(
@@ -1173,6 +1197,21 @@ fn rewrite_expr_to_prunable(
         } else {
             plan_err!("Not with complex expression {column_expr:?} is not supported")
         }
+    } else if let Some(func) = column_expr.downcast_ref::<ScalarFunctionExpr>()
+        && func.name() == "date_trunc"
+        && let [granularity, arg] = func.args()
+        && granularity.downcast_ref::<phys_expr::Literal>().is_some()
+    {
+        // `date_trunc(granularity, col) op lit()`: date_trunc is non-decreasing,
+        // so it can be applied to the min/max of the column like a cast
+        let (left, new_op, right) =
+            rewrite_expr_to_prunable(arg, op, scalar_expr, schema)?;
+        if new_op != op || !Arc::ptr_eq(&right, scalar_expr) {
+            return plan_err!("date_trunc of {arg:?} is not supported");
+        }
+        let left = Arc::clone(column_expr)
+            .with_new_children(vec![Arc::clone(granularity), left])?;
+        Ok((left, op, right))
     } else {
         plan_err!("column expression {column_expr:?} is not supported")
     }
@@ -1458,6 +1497,14 @@ fn build_predicate_expression(
         if let Some(col) = not.arg().downcast_ref::<phys_expr::Column>() {
             return build_single_column_expr(col, schema, required_columns, true)
                 .unwrap_or_else(|| unhandled_hook.handle(expr));
+        } else if let Some(negated) = negate_predicate(not.arg(), schema) {
+            // NOT over a supported predicate, e.g. `NOT (a > 5)` --> `a <= 5`
+            return build_predicate_expression(
+                &negated,
+                schema,
+                required_columns,
+                unhandled_hook,
+            );
         } else {
             return unhandled_hook.handle(expr);
         }
@@ -1494,10 +1541,31 @@ fn build_predicate_expression(
                 required_columns,
                 unhandled_hook,
             );
+        } else if let Some(range_expr) = in_list_range_expr(in_list) {
+            // too many values to check each of them, check their range instead
+            return build_predicate_expression(
+                &range_expr,
+                schema,
+                required_columns,
+                unhandled_hook,
+            );
         } else {
             return unhandled_hook.handle(expr);
         }
     }
+    if let Some(func) = expr.downcast_ref::<ScalarFunctionExpr>()
+        && func.name() == "starts_with"
+    {
+        return match starts_with_range_expr(func) {
+            Some(range_expr) => build_predicate_expression(
+                &range_expr,
+                schema,
+                required_columns,
+                unhandled_hook,
+            ),
+            None => unhandled_hook.handle(expr),
+        };
+    }
 
     let (left, op, right) = {
         if let Some(bin_expr) = expr.downcast_ref::<phys_expr::BinaryExpr>() {
@@ -1578,6 +1646,151 @@ fn build_predicate_expression(
         .unwrap_or_else(|_| unhandled_hook.handle(expr))
 }
 
+/// Return the negation of a predicate with `NOT` pushed into its children,
+/// or `None` if the predicate can not be negated that way.
+///
+/// For example `NOT (a > 5 AND b IS NULL)` is `a <= 5 OR b IS NOT NULL`.
+/// These rewrites are valid under SQL's three-valued logic.
+fn negate_predicate(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &SchemaRef,
+) -> Option<Arc<dyn PhysicalExpr>> {
+    if let Some(not) = expr.downcast_ref::<phys_expr::NotExpr>() {
+        return Some(Arc::clone(not.arg()));
+    }
+    if expr.downcast_ref::<phys_expr::Column>().is_some() {
+        return Some(Arc::new(phys_expr::NotExpr::new(Arc::clone(expr))));
+    }
+    if let Some(is_null) = expr.downcast_ref::<phys_expr::IsNullExpr>() {
+        return Some(Arc::new(phys_expr::IsNotNullExpr::new(Arc::clone(
+            is_null.arg(),
+        ))));
+    }
+    if let Some(is_not_null) = expr.downcast_ref::<phys_expr::IsNotNullExpr>() {
+        return Some(Arc::new(phys_expr::IsNullExpr::new(Arc::clone(
+            is_not_null.arg(),
+        ))));
+    }
+    if let Some(in_list) = expr.downcast_ref::<phys_expr::InListExpr>() {
+        return phys_expr::InListExpr::try_new(
+            Arc::clone(in_list.expr()),
+            in_list.list().to_vec(),
+            !in_list.negated(),
+            schema,
+        )
+        .ok()
+        .map(|in_list| Arc::new(in_list) as _);
+    }
+    if let Some(like) = expr.downcast_ref::<phys_expr::LikeExpr>() {
+        return Some(Arc::new(phys_expr::LikeExpr::new(
+            !like.negated(),
+            like.case_insensitive(),
+            Arc::clone(like.expr()),
+            Arc::clone(like.pattern()),
+        )));
+    }
+    let bin_expr = expr.downcast_ref::<phys_expr::BinaryExpr>()?;
+    let (left, right) = (bin_expr.left(), bin_expr.right());
+    match bin_expr.op() {
+        // De Morgan's laws
+        Operator::And | Operator::Or => {
+            let op = if *bin_expr.op() == Operator::And {
+                Operator::Or
+            } else {
+                Operator::And
+            };
+            Some(Arc::new(phys_expr::BinaryExpr::new(
+                negate_predicate(left, schema)?,
+                op,
+                negate_predicate(right, schema)?,
+            )))
+        }
+        op => Some(Arc::new(phys_expr::BinaryExpr::new(
+            Arc::clone(left),
+            op.negate()?,
+            Arc::clone(right),
+        ))),
+    }
+}
+
+/// Rewrite `col IN (v1, ..., vn)` with literal values to the range check
+/// `col >= min(v1, ..., vn) AND col <= max(v1, ..., vn)`.
+///
+/// Returns `None` for `NOT IN` and lists with non literal values.
+fn in_list_range_expr(in_list: &phys_expr::InListExpr) -> Option<Arc<dyn PhysicalExpr>> {
+    if in_list.negated() {
+        return None;
+    }
+    let mut min: Option<&ScalarValue> = None;
+    let mut max: Option<&ScalarValue> = None;
+    for value in in_list.list() {
+        let value = value.downcast_ref::<phys_expr::Literal>()?.value();
+        // null never matches
+        if value.is_null() {
+            continue;
+        }
+        if min.is_none_or(|min| value.partial_cmp(min) == Some(Ordering::Less)) {
+            min = Some(value);
+        }
+        if max.is_none_or(|max| value.partial_cmp(max) == Some(Ordering::Greater)) {
+            max = Some(value);
+        }
+    }
+    let (min, max) = (min?, max?);
+    let bound = |op, value: &ScalarValue| -> Arc<dyn PhysicalExpr> {
+        Arc::new(phys_expr::BinaryExpr::new(
+            Arc::clone(in_list.expr()),
+            op,
+            Arc::new(phys_expr::Literal::new(value.clone())),
+        ))
+    };
+    Some(Arc::new(phys_expr::BinaryExpr::new(
+        bound(Operator::GtEq, min),
+        Operator::And,
+        bound(Operator::LtEq, max),
+    )))
+}
+
+/// Rewrite `starts_with(col, 'prefix')` to the range check
+/// `col >= 'prefix' AND col < 'prefiy'`, see [`increment_utf8`].
+fn starts_with_range_expr(func: &ScalarFunctionExpr) -> Option<Arc<dyn PhysicalExpr>> {
+    let [arg, prefix] = func.args() else {
+        return None;
+    };
+    let prefix = prefix.downcast_ref::<phys_expr::Literal>()?.value();
+    let prefix_str = unpack_string(prefix)?;
+    if prefix_str.is_empty() {
+        return None;
+    }
+    let lower_bound: Arc<dyn PhysicalExpr> = Arc::new(phys_expr::BinaryExpr::new(
+        Arc::clone(arg),
+        Operator::GtEq,
+        Arc::new(phys_expr::Literal::new(prefix.clone())),
+    ));
+    let upper_bound = match increment_utf8(prefix_str) {
+        Some(upper) => {
+            let upper = match prefix {
+                ScalarValue::Utf8(_) => ScalarValue::Utf8(Some(upper)),
+                ScalarValue::LargeUtf8(_) => ScalarValue::LargeUtf8(Some(upper)),
+                ScalarValue::Utf8View(_) => ScalarValue::Utf8View(Some(upper)),
+                _ => return None,
+            };
+            Arc::new(phys_expr::BinaryExpr::new(
+                Arc::clone(arg),
+                Operator::Lt,
+                Arc::new(phys_expr::Literal::new(upper)),
+            ))
+        }
+        // no string is greater than all the strings starting with the prefix
+        None => return Some(lower_bound),
+    };
+    Some(Arc::new(phys_expr::BinaryExpr::new(
+        lower_bound,
+        Operator::And,
+        upper_bound,
+    )))
+}
+
 /// Count of distinct column references in an expression.
 /// This is the same as [`collect_columns`] but optimized to stop counting
 /// once more than one distinct column is found.
@@ -2040,6 +2253,8 @@ mod tests {
     use std::ops::{Not, Rem};
 
     use super::*;
+    use datafusion_common::ColumnStatistics;
+    use datafusion_common::stats::Precision;
     use datafusion_common::test_util::batches_to_string;
     use datafusion_expr::{and, col, lit, or};
     use datafusion_physical_expr::utils::collect_columns;
@@ -2047,11 +2262,15 @@ mod tests {
 
     use arrow::array::Decimal128Array;
     use arrow::{
-        array::{BinaryArray, Int32Array, Int64Array, StringArray, UInt64Array},
+        array::{
+            BinaryArray, Int32Array, Int64Array, StringArray, TimestampSecondArray,
+            UInt64Array,
+        },
         datatypes::TimeUnit,
     };
     use datafusion_expr::expr::InList;
     use datafusion_expr::{BinaryExpr, Expr, cast, is_null, try_cast};
+    use datafusion_functions::expr_fn::{date_trunc, starts_with};
     use datafusion_functions_nested::expr_fn::{array_has, make_array};
     use datafusion_physical_expr::expressions::{
         self as phys_expr, DynamicFilterPhysicalExpr,
@@ -3306,11 +3525,11 @@ mod tests {
     fn row_group_predicate_in_list_to_many_values() -> Result<()> {
         let schema = Schema::new(vec![Field::new("c1", DataType::Int32, false)]);
         // test c1 in(1..21)
-        // in pruning.rs has MAX_LIST_VALUE_SIZE_REWRITE = 20, more than this value will be rewrite
-        // always true
+        // in pruning.rs has MAX_LIST_VALUE_SIZE_REWRITE = 20, more values than this are
+        // checked as a range
         let expr = col("c1").in_list((1..=21).map(lit).collect(), false);
 
-        let expected_expr = "true";
+        let expected_expr = "c1_null_count@1 != row_count@2 AND c1_max@0 >= 1 AND c1_null_count@1 != row_count@2 AND c1_min@3 <= 21";
         let predicate_expr =
             test_build_predicate_expression(&expr, &schema, &mut RequiredColumns::new());
         assert_eq!(predicate_expr.to_string(), expected_expr);
@@ -3812,6 +4031,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prune_statistics_api() {
+        let schema = Arc::new(Schema::new(vec![Field::new("i", DataType::Int32, true)]));
+        let container = |min: i32, max: i32| {
+            Arc::new(Statistics {
+                num_rows: Precision::Exact(10),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![ColumnStatistics {
+                    min_value: Precision::Exact(ScalarValue::Int32(Some(min))),
+                    max_value: Precision::Exact(ScalarValue::Int32(Some(max))),
+                    ..ColumnStatistics::new_unknown()
+                }],
+            })
+        };
+        let unknown = Arc::new(Statistics::new_unknown(&schema));
+
+        let expr = logical2physical(&col("i").gt(lit(0)), &schema);
+        let p = PruningPredicate::try_new(expr, Arc::clone(&schema)).unwrap();
+        let result = p
+            .prune_statistics(vec![container(-5, -1), container(1, 5), unknown])
+            .unwrap();
+        assert_eq!(result, [false, true, true]);
+    }
+
     #[test]
     fn prune_not_eq_data() {
         let schema = Arc::new(Schema::new(vec![Field::new("s1", DataType::Utf8, true)]));
@@ -4246,6 +4489,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prune_int32_not() {
+        let (schema, statistics) = int32_setup();
+
+        // NOT (i > 0) is i <= 0
+        prune_with_expr(
+            col("i").gt(lit(0)).not(),
+            &schema,
+            &statistics,
+            &[true, false, true, true, false],
+        );
+
+        // NOT (NOT (i > 0)) is i > 0
+        prune_with_expr(
+            col("i").gt(lit(0)).not().not(),
+            &schema,
+            &statistics,
+            &[true, true, false, true, true],
+        );
+
+        // NOT (i <= 0 OR i >= 5) is i > 0 AND i < 5
+        // i [-5, 5] ==> some rows could pass (must keep)
+        // i [1, 11] ==> some rows could pass (must keep)
+        // i [-11, -1] ==>  no rows can pass (not keep)
+        // i [NULL, NULL]  ==> unknown (must keep)
+        // i [1, NULL]  ==> unknown (must keep)
+        prune_with_expr(
+            or(col("i").lt_eq(lit(0)), col("i").gt_eq(lit(5))).not(),
+            &schema,
+            &statistics,
+            &[true, true, false, true, true],
+        );
+
+        // NOT (i NOT IN (-3, 20)) is i IN (-3, 20)
+        // i [-5, 5] ==> some rows could pass (must keep)
+        // i [1, 11] ==> no rows can pass (not keep)
+        // i [-11, -1] ==>  some rows could pass (must keep)
+        // i [NULL, NULL]  ==> unknown (must keep)
+        // i [1, NULL]  ==> unknown (must keep)
+        prune_with_expr(
+            col("i").in_list(vec![lit(-3), lit(20)], true).not(),
+            &schema,
+            &statistics,
+            &[true, false, true, true, true],
+        );
+    }
+
+    #[test]
+    fn prune_int32_in_list_many_values() {
+        let (schema, statistics) = int32_setup();
+
+        // i IN (20, 22, ..., 60) is checked as i >= 20 AND i <= 60
+        // i [-5, 5] ==> no rows can pass (not keep)
+        // i [1, 11] ==> no rows can pass (not keep)
+        // i [-11, -1] ==>  no rows can pass (not keep)
+        // i [NULL, NULL]  ==> unknown (must keep)
+        // i [1, NULL]  ==> unknown (must keep)
+        let many_values = |start: i32| (start..start + 42).step_by(2).map(lit).collect();
+        prune_with_expr(
+            col("i").in_list(many_values(20), false),
+            &schema,
+            &statistics,
+            &[false, false, false, true, true],
+        );
+
+        // OR of IN lists checks each of the ranges
+        // i [-11, -1] ==>  some rows could pass (must keep)
+        prune_with_expr(
+            or(
+                col("i").in_list(many_values(20), false),
+                col("i").in_list(many_values(-50), false),
+            ),
+            &schema,
+            &statistics,
+            &[false, false, true, true, true],
+        );
+
+        // NOT IN with many values can not be pruned
+        prune_with_expr(
+            col("i").in_list(many_values(20), true),
+            &schema,
+            &statistics,
+            &[true, true, true, true, true],
+        );
+    }
+
+    #[test]
+    fn prune_timestamp_date_trunc() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Second, None),
+            true,
+        )]));
+        let hour = 3600;
+        let day = 24 * hour;
+        let statistics = TestStatistics::new().with(
+            "ts",
+            ContainerStats::new()
+                .with_min(Arc::new(TimestampSecondArray::from(vec![
+                    Some(10 * hour),
+                    Some(23 * hour),
+                    Some(day),
+                    None,
+                ])))
+                .with_max(Arc::new(TimestampSecondArray::from(vec![
+                    Some(20 * hour),
+                    Some(day + hour),
+                    Some(2 * day),
+                    None,
+                ]))),
+        );
+        let day_1 = lit(ScalarValue::TimestampSecond(Some(day), None));
+        let date_trunc_day = || date_trunc(lit("day"), col("ts"));
+
+        // ts [day 0 10:00, day 0 20:00] ==> no rows can pass (not keep)
+        // ts [day 0 23:00, day 1 01:00] ==> some rows could pass (must keep)
+        // ts [day 1 00:00, day 2 00:00] ==> some rows could pass (must keep)
+        // ts [NULL, NULL]  ==> unknown (must keep)
+        prune_with_expr(
+            date_trunc_day().eq(day_1.clone()),
+            &schema,
+            &statistics,
+            &[false, true, true, true],
+        );
+
+        // ts [day 1 00:00, day 2 00:00] ==> no rows can pass (not keep)
+        prune_with_expr(
+            date_trunc_day().lt(day_1),
+            &schema,
+            &statistics,
+            &[true, true, false, true],
+        );
+    }
+
     #[test]
     fn prune_int32_is_null() {
         let (schema, statistics) = int32_setup();
@@ -4942,6 +5319,41 @@ mod tests {
         prune_with_expr(expr, &schema, &statistics, expected_ret);
     }
 
+    #[test]
+    fn prune_utf8_starts_with() {
+        let (schema, statistics) = utf8_setup();
+
+        let expr = starts_with(col("s1"), lit("A"));
+        #[rustfmt::skip]
+        let expected_ret = &[
+            // s1 ["A", "Z"] ==> some rows could pass (must keep)
+            true,
+            // s1 ["A", "L"] ==> some rows could pass (must keep)
+            true,
+            // s1 ["N", "Z"] ==> no rows can pass (not keep)
+            false,
+            // s1 ["M", "M"] ==> no rows can pass (not keep)
+            false,
+            // s1 [NULL, NULL]  ==> unknown (must keep)
+            true,
+            // s1 ["A", NULL]  ==> unknown (must keep)
+            true,
+            // s1 ["", "A"]  ==> some rows could pass (must keep)
+            true,
+            // s1 ["", ""]  ==> no rows can pass (not keep)
+            false,
+            // s1 ["AB", "A\u{10ffff}\u{10ffff}\u{10ffff}"]  ==> some rows could pass (must keep)
+            true,
+            // s1 ["A\u{10ffff}\u{10ffff}", "A\u{10ffff}\u{10ffff}"]  ==> some rows could pass (must keep)
+            true,
+        ];
+        prune_with_expr(expr, &schema, &statistics, expected_ret);
+
+        // an empty prefix matches all strings
+        let expr = starts_with(col("s1"), lit(""));
+        prune_with_expr(expr, &schema, &statistics, &[true; 10]);
+    }
+
     #[test]
     fn test_rewrite_expr_to_prunable() {
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
@@ -0,0 +1,238 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reuse of [`PruningPredicate`]s across files that share a schema
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use arrow::datatypes::SchemaRef;
+use datafusion_physical_expr_common::physical_expr::{
+    PhysicalExpr, is_dynamic_physical_expr,
+};
+use datafusion_physical_plan::metrics::Count;
+use log::debug;
+
+use crate::PruningPredicate;
+
+/// Default maximum number of distinct predicate / schema pairs kept by a
+/// [`PruningPredicateCache`]
+const DEFAULT_MAX_ENTRIES: usize = 32;
+
+/// A predicate and the file schema it was built for
+type CacheKey = (Arc<dyn PhysicalExpr>, SchemaRef);
+
+/// Outcome of building a [`PruningPredicate`]
+#[derive(Debug, Clone)]
+enum CachedPruningPredicate {
+    /// The predicate can prune
+    Built(Arc<PruningPredicate>),
+    /// The predicate is always true and can not prune anything
+    AlwaysTrue,
+    /// The predicate could not be converted
+    Error,
+}
+
+/// Caches the [`PruningPredicate`]s built by [`build_pruning_predicate`] for
+/// the same predicate and file schema.
+///
+/// Building a [`PruningPredicate`] rewrites the predicate in terms of min /
+/// max statistics, casting its literals to the type of the columns they are
+/// compared to. A scan over a table with thousands of files usually rewrites
+/// the very same predicate for every file, as most files share one schema.
+/// Sharing a cache between the files of a scan builds it only once per
+/// distinct schema.
+///
+/// Predicates that contain dynamic filters are never cached, as their value
+/// changes while the scan runs.
+///
+/// [`build_pruning_predicate`]: crate::build_pruning_predicate
+#[derive(Debug)]
+pub struct PruningPredicateCache {
+    entries: Mutex<HashMap<CacheKey, CachedPruningPredicate>>,
+    max_entries: usize,
+}
+
+impl Default for PruningPredicateCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENTRIES)
+    }
+}
+
+impl PruningPredicateCache {
+    /// Create a cache holding at most `max_entries` predicate / schema pairs.
+    ///
+    /// Once full, predicates for new pairs are built without being cached.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            max_entries,
+        }
+    }
+
+    /// Number of cached predicate / schema pairs
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns true if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Same as [`build_pruning_predicate`], reusing the result of a previous
+    /// call with an equal predicate and file schema.
+    ///
+    /// Errors are recorded in `predicate_creation_errors` on every call, as
+    /// if the predicate was built again.
+    ///
+    /// [`build_pruning_predicate`]: crate::build_pruning_predicate
+    pub fn get_or_build(
+        &self,
+        predicate: Arc<dyn PhysicalExpr>,
+        file_schema: &SchemaRef,
+        predicate_creation_errors: &Count,
+    ) -> Option<Arc<PruningPredicate>> {
+        if is_dynamic_physical_expr(&predicate) {
+            return build(predicate, file_schema)
+                .into_pruning_predicate(predicate_creation_errors);
+        }
+
+        let key: CacheKey = (predicate, Arc::clone(file_schema));
+        if let Some(cached) = self.entries.lock().unwrap().get(&key) {
+            return cached
+                .clone()
+                .into_pruning_predicate(predicate_creation_errors);
+        }
+
+        // Build without holding the lock, a concurrent build of the same
+        // predicate is wasted work but yields an equal result
+        let built = build(Arc::clone(&key.0), file_schema);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() < self.max_entries {
+            entries.insert(key, built.clone());
+        }
+        built.into_pruning_predicate(predicate_creation_errors)
+    }
+}
+
+fn build(
+    predicate: Arc<dyn PhysicalExpr>,
+    file_schema: &SchemaRef,
+) -> CachedPruningPredicate {
+    match PruningPredicate::try_new(predicate, Arc::clone(file_schema)) {
+        Ok(pruning_predicate) if pruning_predicate.always_true() => {
+            CachedPruningPredicate::AlwaysTrue
+        }
+        Ok(pruning_predicate) => {
+            CachedPruningPredicate::Built(Arc::new(pruning_predicate))
+        }
+        Err(e) => {
+            debug!("Could not create pruning predicate for: {e}");
+            CachedPruningPredicate::Error
+        }
+    }
+}
+
+impl CachedPruningPredicate {
+    fn into_pruning_predicate(
+        self,
+        predicate_creation_errors: &Count,
+    ) -> Option<Arc<PruningPredicate>> {
+        match self {
+            Self::Built(pruning_predicate) => Some(pruning_predicate),
+            Self::AlwaysTrue => None,
+            Self::Error => {
+                predicate_creation_errors.add(1);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::ScalarValue;
+    use datafusion_expr_common::operator::Operator;
+    use datafusion_physical_expr::expressions::{
+        BinaryExpr, Column, DynamicFilterPhysicalExpr, lit,
+    };
+
+    fn schema(data_type: DataType) -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new("a", data_type, true)]))
+    }
+
+    fn a_gt_5() -> Arc<dyn PhysicalExpr> {
+        Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("a", 0)),
+            Operator::Gt,
+            lit(ScalarValue::Int32(Some(5))),
+        ))
+    }
+
+    #[test]
+    fn reuses_predicate_for_equal_schemas() {
+        let cache = PruningPredicateCache::default();
+        let errors = Count::new();
+
+        let first = cache
+            .get_or_build(a_gt_5(), &schema(DataType::Int32), &errors)
+            .unwrap();
+        // A structurally equal predicate and schema hit the cache
+        let second = cache
+            .get_or_build(a_gt_5(), &schema(DataType::Int32), &errors)
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        // A different schema builds a new predicate
+        let third = cache
+            .get_or_build(a_gt_5(), &schema(DataType::Int64), &errors)
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &third));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(errors.value(), 0);
+    }
+
+    #[test]
+    fn respects_capacity_and_skips_dynamic_predicates() {
+        let cache = PruningPredicateCache::new(1);
+        let errors = Count::new();
+        let int32 = schema(DataType::Int32);
+
+        assert!(cache.get_or_build(lit(true), &int32, &errors).is_none());
+        assert_eq!(cache.len(), 1);
+
+        // The cache is full
+        let first = cache.get_or_build(a_gt_5(), &int32, &errors).unwrap();
+        let second = cache.get_or_build(a_gt_5(), &int32, &errors).unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        let cache = PruningPredicateCache::default();
+        let dynamic = Arc::new(DynamicFilterPhysicalExpr::new(
+            vec![Arc::new(Column::new("a", 0))],
+            a_gt_5(),
+        ));
+        dynamic.update(a_gt_5()).unwrap();
+        assert!(cache.get_or_build(dynamic, &int32, &errors).is_some());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,456 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of Oracle style hierarchical queries (`START WITH ... CONNECT BY`)
+//! as recursive CTEs
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{
+    Column, DFSchema, DataFusionError, JoinType, Result, internal_datafusion_err,
+    not_impl_err, plan_datafusion_err, plan_err,
+};
+use datafusion_expr::{
+    Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder, ScalarUDF, cast, lit,
+};
+use sqlparser::ast::{
+    BinaryOperator, ConnectByKind, Expr as SQLExpr, FunctionArg, FunctionArgExpr,
+    FunctionArguments, Ident, OrderBy, Select, Visit, visit_expressions,
+    visit_expressions_mut,
+};
+
+/// Name of the work table of the recursive CTE
+const WORK_TABLE: &str = "connect_by";
+/// Name of the `LEVEL` pseudo column
+const LEVEL: &str = "level";
+/// Name of the column with the `PRIOR` keys of the rows on the path to each
+/// row, which is used to stop at cycles
+const KEYS: &str = "__connect_by_keys";
+/// Prefix of the names of the `PRIOR` expressions of the `CONNECT BY`
+/// condition while it is planned
+const PRIOR: &str = "__connect_by_prior_";
+/// Alias of an input of a hierarchical query with unqualified columns
+const INPUT: &str = "__connect_by_input";
+
+/// The hierarchical clauses of a `SELECT`, see [`SqlToRel::plan_connect_by`]
+pub(crate) struct ConnectBy {
+    /// The `START WITH` condition, which selects the root rows
+    start_with: Option<SQLExpr>,
+    /// The `CONNECT BY` condition between a parent and its children
+    condition: SQLExpr,
+    /// Whether the query references the `LEVEL` pseudo column
+    level: bool,
+    /// The column names, arguments and separators of the
+    /// `SYS_CONNECT_BY_PATH` calls of the query
+    paths: Vec<(String, SQLExpr, SQLExpr)>,
+}
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Takes the hierarchical clauses out of `select`, if any, and replaces
+    /// the `SYS_CONNECT_BY_PATH` calls of its projection, `WHERE`, `HAVING`,
+    /// `QUALIFY` and `ORDER BY` by the columns that
+    /// [`Self::plan_connect_by`] computes for them
+    pub(super) fn prepare_connect_by(
+        &self,
+        select: &mut Select,
+        order_by: &mut Option<OrderBy>,
+    ) -> Result<Option<ConnectBy>> {
+        if select.connect_by.is_empty() {
+            return Ok(None);
+        }
+        let mut start_with = None;
+        let mut condition = None;
+        for kind in std::mem::take(&mut select.connect_by) {
+            match kind {
+                ConnectByKind::StartWith {
+                    condition: start, ..
+                } if start_with.is_none() => start_with = Some(*start),
+                ConnectByKind::ConnectBy { relationships, .. } if condition.is_none() => {
+                    condition = relationships.into_iter().reduce(|left, right| {
+                        SQLExpr::BinaryOp {
+                            left: Box::new(left),
+                            op: BinaryOperator::And,
+                            right: Box::new(right),
+                        }
+                    })
+                }
+                kind => return plan_err!("Unexpected hierarchical clause {kind}"),
+            }
+        }
+        let Some(condition) = condition else {
+            return plan_err!("START WITH requires a CONNECT BY clause");
+        };
+
+        let level = references_level(&*select)
+            || references_level(&condition)
+            || references_level(order_by);
+
+        let mut paths = vec![];
+        let mut rewrite_paths = |expr: &mut SQLExpr| -> ControlFlow<DataFusionError> {
+            let SQLExpr::Function(function) = expr else {
+                return ControlFlow::Continue(());
+            };
+            if !function
+                .name
+                .to_string()
+                .eq_ignore_ascii_case("sys_connect_by_path")
+            {
+                return ControlFlow::Continue(());
+            }
+            let args = match &function.args {
+                FunctionArguments::List(list) => list
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => {
+                            Some(arg.clone())
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>(),
+                _ => None,
+            };
+            let Some([arg, separator]) =
+                args.and_then(|args| <[SQLExpr; 2]>::try_from(args).ok())
+            else {
+                return ControlFlow::Break(plan_datafusion_err!(
+                    "Expected sys_connect_by_path(expr, separator)"
+                ));
+            };
+            let name = format!("sys_connect_by_path({arg}, {separator})");
+            if !paths.iter().any(|(path, _, _)| path == &name) {
+                paths.push((name.clone(), arg, separator));
+            }
+            *expr = SQLExpr::Identifier(Ident::with_quote('"', name));
+            ControlFlow::Continue(())
+        };
+        let rewritten = visit_expressions_mut(&mut select.projection, &mut rewrite_paths)
+            .break_value()
+            .or_else(|| {
+                visit_expressions_mut(&mut select.selection, &mut rewrite_paths)
+                    .break_value()
+            })
+            .or_else(|| {
+                visit_expressions_mut(&mut select.having, &mut rewrite_paths)
+                    .break_value()
+            })
+            .or_else(|| {
+                visit_expressions_mut(&mut select.qualify, &mut rewrite_paths)
+                    .break_value()
+            })
+            .or_else(|| {
+                visit_expressions_mut(order_by, &mut rewrite_paths).break_value()
+            });
+        if let Some(err) = rewritten {
+            return Err(err);
+        }
+
+        Ok(Some(ConnectBy {
+            start_with,
+            condition,
+            level,
+            paths,
+        }))
+    }
+
+    /// Plans the hierarchical query over the rows of `input` described by
+    /// `connect_by`:
+    ///
+    /// ```sql
+    /// SELECT name, LEVEL, SYS_CONNECT_BY_PATH(name, '/')
+    /// FROM employees
+    /// START WITH manager_id IS NULL
+    /// CONNECT BY PRIOR employee_id = manager_id
+    /// ```
+    ///
+    /// The rows that satisfy the `START WITH` condition, or all rows if
+    /// there is none, are the roots of the hierarchy. The children of each
+    /// row are the rows that satisfy the `CONNECT BY` condition, in which
+    /// `PRIOR` expressions are evaluated on the parent row.
+    ///
+    /// This is planned as a recursive CTE whose static term returns the
+    /// roots, and whose recursive term joins the rows returned by the
+    /// previous iteration with their children. The CTE computes the `LEVEL`
+    /// of each row, starting at 1 for the roots, and the `SYS_CONNECT_BY_PATH`
+    /// of each row, which concatenates the separator and the value of the
+    /// argument for every row from the root to the row. The `WHERE` clause of
+    /// the query filters the rows of the hierarchy.
+    ///
+    /// Like with `CONNECT BY NOCYCLE`, a row is not returned again as a
+    /// descendant of itself: the recursion stops at a child whose `PRIOR`
+    /// expressions have the same values as one of its ancestors.
+    pub(super) fn plan_connect_by(
+        &self,
+        input: LogicalPlan,
+        connect_by: ConnectBy,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        if !self
+            .context_provider
+            .options()
+            .execution
+            .enable_recursive_ctes
+        {
+            return not_impl_err!("CONNECT BY requires recursive CTEs to be enabled");
+        }
+        let ConnectBy {
+            start_with,
+            mut condition,
+            level,
+            paths,
+        } = connect_by;
+
+        // the recursive term joins the input with the work table, which
+        // requires qualified input columns
+        let input = if input
+            .schema()
+            .iter()
+            .any(|(qualifier, _)| qualifier.is_none())
+        {
+            LogicalPlanBuilder::from(input).alias(INPUT)?.build()?
+        } else {
+            input
+        };
+        let schema = Arc::clone(input.schema());
+        let level = level && !schema.has_column_with_unqualified_name(LEVEL);
+
+        let start_with = start_with
+            .map(|start_with| self.sql_to_expr(start_with, &schema, planner_context))
+            .transpose()?;
+        let paths = paths
+            .into_iter()
+            .map(|(name, arg, separator)| {
+                let arg = self.sql_to_expr(arg, &schema, planner_context)?;
+                let separator =
+                    self.sql_to_expr(separator, &DFSchema::empty(), planner_context)?;
+                Ok((name, arg, separator))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let concat = if paths.is_empty() {
+            None
+        } else {
+            Some(self.connect_by_function("concat")?)
+        };
+
+        // plan the condition with a placeholder column for each PRIOR
+        // expression, which is replaced by the expression over the parent
+        let mut priors = vec![];
+        let _ = visit_expressions_mut(&mut condition, |expr| {
+            if matches!(expr, SQLExpr::Prior(_)) {
+                let placeholder =
+                    SQLExpr::Identifier(Ident::new(format!("{PRIOR}{}", priors.len())));
+                if let SQLExpr::Prior(prior) = std::mem::replace(expr, placeholder) {
+                    priors.push(*prior);
+                }
+            }
+            ControlFlow::<()>::Continue(())
+        });
+        let priors = priors
+            .into_iter()
+            .map(|prior| self.sql_to_expr(prior, &schema, planner_context))
+            .collect::<Result<Vec<_>>>()?;
+        let mut placeholders = priors
+            .iter()
+            .enumerate()
+            .map(|(i, prior)| {
+                let (_, field) = prior.to_field(&schema)?;
+                let field = Field::new(
+                    format!("{PRIOR}{i}"),
+                    field.data_type().clone(),
+                    field.is_nullable(),
+                );
+                Ok((None, Arc::new(field)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if level {
+            placeholders
+                .push((None, Arc::new(Field::new(LEVEL, DataType::Int64, false))));
+        }
+        let condition_schema =
+            schema.join(&DFSchema::new_with_metadata(placeholders, HashMap::new())?)?;
+        let condition =
+            self.sql_to_expr(condition, &condition_schema, planner_context)?;
+
+        // the PRIOR expressions identify the rows on the path to each row
+        let key = match priors.as_slice() {
+            [] => None,
+            [prior] => Some(prior.clone()),
+            _ => Some(self.connect_by_function("struct")?.call(priors.clone())),
+        };
+
+        // the static term returns the roots
+        let mut root = schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        if level {
+            root.push(lit(1_i64).alias(LEVEL));
+        }
+        if let Some(concat) = &concat {
+            for (name, arg, separator) in &paths {
+                let path = concat
+                    .call(vec![separator.clone(), cast(arg.clone(), DataType::Utf8)]);
+                root.push(cast(path, DataType::Utf8).alias(name));
+            }
+        }
+        if let Some(key) = &key {
+            root.push(
+                self.connect_by_function("make_array")?
+                    .call(vec![key.clone()])
+                    .alias(KEYS),
+            );
+        }
+        let mut static_plan = LogicalPlanBuilder::from(input.clone());
+        if let Some(start_with) = start_with {
+            static_plan = static_plan.filter(start_with)?;
+        }
+        let static_plan = static_plan.project(root)?.build()?;
+
+        // the recursive term joins the rows returned by the previous
+        // iteration, the parents, with their children
+        let work_table_source = self.context_provider.create_cte_work_table(
+            WORK_TABLE,
+            Arc::clone(static_plan.schema().inner()),
+        )?;
+        let work_table = LogicalPlanBuilder::scan(WORK_TABLE, work_table_source, None)?;
+        let mut work_columns = work_table
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        let work_keys = key.as_ref().and_then(|_| work_columns.pop());
+        let mut work_columns = work_columns.into_iter().skip(schema.fields().len());
+        let parent_level = if level { work_columns.next() } else { None };
+        let parent_paths = work_columns.collect::<Vec<_>>();
+
+        // rewrites an expression over a row of the input into an expression
+        // over its parent in the work table
+        let work_table_schema = Arc::clone(work_table.schema());
+        let parent = |expr: Expr| {
+            expr.transform(|expr| match expr {
+                Expr::Column(column) => {
+                    let index = schema.index_of_column(&column)?;
+                    let (qualifier, field) = work_table_schema.qualified_field(index);
+                    Ok(Transformed::yes(Expr::Column(Column::from((
+                        qualifier, field,
+                    )))))
+                }
+                _ => Ok(Transformed::no(expr)),
+            })
+            .data()
+        };
+        let parent_priors = priors
+            .into_iter()
+            .map(&parent)
+            .collect::<Result<Vec<_>>>()?;
+        let mut condition = condition
+            .transform(|expr| match expr {
+                Expr::Column(column) if column.relation.is_none() => {
+                    if let Some(i) = column.name.strip_prefix(PRIOR) {
+                        let i = i.parse::<usize>().map_err(|e| {
+                            internal_datafusion_err!(
+                                "Invalid PRIOR placeholder {column}: {e}"
+                            )
+                        })?;
+                        return Ok(Transformed::yes(parent_priors[i].clone()));
+                    }
+                    match &parent_level {
+                        Some(parent_level) if column.name == LEVEL => {
+                            Ok(Transformed::yes(parent_level.clone() + lit(1_i64)))
+                        }
+                        _ => Ok(Transformed::no(Expr::Column(column))),
+                    }
+                }
+                _ => Ok(Transformed::no(expr)),
+            })
+            .data()?;
+
+        let mut child = schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        if let Some(parent_level) = parent_level {
+            child.push((parent_level + lit(1_i64)).alias(LEVEL));
+        }
+        if let Some(concat) = &concat {
+            for ((name, arg, separator), parent_path) in
+                paths.into_iter().zip(parent_paths)
+            {
+                let path =
+                    concat.call(vec![parent_path, separator, cast(arg, DataType::Utf8)]);
+                child.push(cast(path, DataType::Utf8).alias(name));
+            }
+        }
+        if let (Some(key), Some(work_keys)) = (key, work_keys) {
+            let is_cycle = self
+                .connect_by_function("array_has")?
+                .call(vec![work_keys.clone(), key.clone()]);
+            condition = condition.and(is_cycle.is_not_true());
+            child.push(
+                self.connect_by_function("array_append")?
+                    .call(vec![work_keys, key])
+                    .alias(KEYS),
+            );
+        }
+        let recursive_plan = work_table
+            .join_on(input, JoinType::Inner, [condition])?
+            .project(child)?
+            .build()?;
+
+        let hierarchy = LogicalPlanBuilder::from(static_plan).to_recursive_query(
+            WORK_TABLE.to_string(),
+            recursive_plan,
+            false,
+        )?;
+        let columns = hierarchy
+            .schema()
+            .columns()
+            .into_iter()
+            .filter(|column| column.name != KEYS)
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        hierarchy.project(columns)?.build()
+    }
+
+    /// Returns the scalar function `name` used to plan hierarchical queries
+    fn connect_by_function(&self, name: &str) -> Result<Arc<ScalarUDF>> {
+        match self.context_provider.get_function_meta(name) {
+            Some(function) => Ok(function),
+            None => plan_err!("CONNECT BY requires the {name} function to be registered"),
+        }
+    }
+}
+
+/// Returns true if `node` references the `LEVEL` pseudo column
+fn references_level<V: Visit>(node: &V) -> bool {
+    visit_expressions(node, |expr| match expr {
+        SQLExpr::Identifier(ident) if ident.value.eq_ignore_ascii_case(LEVEL) => {
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .is_break()
+}
@@ -227,6 +227,35 @@ impl FunctionArgs {
 type WithinGroupExtraction = (Vec<SortExpr>, Vec<Expr>, Vec<Option<ArgumentName>>);
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Resolves `name`, of a function called without a namespace, to the
+    /// qualified name of the function in the first namespace of
+    /// `datafusion.catalog.function_search_path` that has one, unless a
+    /// function is registered as `name` itself
+    fn resolve_function_name(&self, name: String) -> String {
+        let Some(search_path) =
+            &self.context_provider.options().catalog.function_search_path
+        else {
+            return name;
+        };
+        if self.function_exists(&name) {
+            return name;
+        }
+        search_path
+            .split(',')
+            .map(str::trim)
+            .filter(|namespace| !namespace.is_empty())
+            .map(|namespace| format!("{namespace}.{name}"))
+            .find(|qualified_name| self.function_exists(qualified_name))
+            .unwrap_or(name)
+    }
+
+    fn function_exists(&self, name: &str) -> bool {
+        self.context_provider.get_function_meta(name).is_some()
+            || self.context_provider.get_higher_order_meta(name).is_some()
+            || self.context_provider.get_aggregate_meta(name).is_some()
+            || self.context_provider.get_window_meta(name).is_some()
+    }
+
     pub(super) fn sql_function_to_expr(
         &self,
         function: SQLFunction,
@@ -264,20 +293,23 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // required ordering should be defined in OVER clause.
         let is_function_window = over.is_some();
         let sql_parser_span = object_name.0[0].span();
+        // Functions registered in a namespace are called by their qualified
+        // name, e.g. `my_ext.geo.st_area`
+        let name = object_name
+            .0
+            .iter()
+            .map(|part| match part.as_ident() {
+                Some(ident) => Ok(crate::utils::normalize_ident(ident.clone())),
+                None => plan_err!(
+                    "Expected an identifier in function name, but found {part:?}"
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(".");
         let name = if object_name.0.len() > 1 {
-            // DF doesn't handle compound identifiers
-            // (e.g. "foo.bar") for function names yet
-            object_name.to_string()
+            name
         } else {
-            match object_name.0[0].as_ident() {
-                Some(ident) => crate::utils::normalize_ident(ident.clone()),
-                None => {
-                    return plan_err!(
-                        "Expected an identifier in function name, but found {:?}",
-                        object_name.0[0]
-                    );
-                }
-            }
+            self.resolve_function_name(name)
         };
 
         // handle make_map and map functions
@@ -356,46 +388,41 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 .iter()
                 .map(|e| e.to_field(schema).map(|(_, f)| f))
                 .collect::<Result<Vec<_>>>();
-            if let Ok(fields) = current_fields {
-                if let Err(e) = datafusion_expr::type_coercion::functions::fields_with_udf(
+            if let Ok(fields) = current_fields
+                && let Err(e) = datafusion_expr::type_coercion::functions::fields_with_udf(
                     &fields,
                     inner.func.as_ref(),
-                ) {
-                    let extracted =
-                        if let datafusion_common::DataFusionError::TypeCoercion {
-                            message: msg,
-                            arg_index: Some(idx),
-                            expected_types,
-                            actual_type,
-                        } = &e
-                        {
-                            Some((
-                                msg.clone(),
-                                *idx,
-                                expected_types.clone(),
-                                actual_type.clone(),
-                            ))
-                        } else {
-                            None
-                        };
-
-                    if let Some((msg, idx, expected_types, actual_type)) = extracted {
-                        if let Some(arg) = original_args.get(idx) {
-                            let span = Span::try_from_sqlparser_span(match arg {
-                                FunctionArg::Named { arg, .. } => arg.span(),
-                                FunctionArg::Unnamed(arg) => arg.span(),
-                            });
-                            let diagnostic = Diagnostic::new_error(msg, span).with_note(
-                                format!(
-                                    "Expected {expected_types}, but found {actual_type}"
-                                ),
-                                span,
-                            );
-                            return Err(e.with_diagnostic(diagnostic));
-                        }
-                    }
-                    return Err(e);
+                )
+            {
+                let extracted =
+                    if let datafusion_common::DataFusionError::TypeCoercion(err) = &e
+                        && let Some(idx) = err.arg_index
+                    {
+                        Some((
+                            err.message.clone(),
+                            idx,
+                            err.expected_types.clone(),
+                            err.actual_type.clone(),
+                        ))
+                    } else {
+                        None
+                    };
+
+                if let Some((msg, idx, expected_types, actual_type)) = extracted
+                    && let Some(arg) = original_args.get(idx)
+                {
+                    let span = Span::try_from_sqlparser_span(match arg {
+                        FunctionArg::Named { arg, .. } => arg.span(),
+                        FunctionArg::Unnamed(arg) => arg.span(),
+                        FunctionArg::ExprNamed { arg, .. } => arg.span(),
+                    });
+                    let diagnostic = Diagnostic::new_error(msg, span).with_note(
+                        format!("Expected {expected_types}, but found {actual_type}"),
+                        span,
+                    );
+                    return Err(e.with_diagnostic(diagnostic));
                 }
+                return Err(e);
             }
 
             if name.eq_ignore_ascii_case(inner.name()) {
@@ -1208,19 +1235,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 
     pub(crate) fn check_unnest_arg(arg: &Expr, schema: &DFSchema) -> Result<()> {
-        // Check argument type, array types are supported
+        // Check argument type, array, map and struct types are supported
         match arg.get_type(schema)? {
             DataType::List(_)
             | DataType::LargeList(_)
             | DataType::FixedSizeList(_, _)
             | DataType::ListView(_)
             | DataType::LargeListView(_)
+            | DataType::Map(_, _)
             | DataType::Struct(_) => Ok(()),
             DataType::Null => {
                 not_impl_err!("unnest() does not support null yet")
             }
             _ => {
-                plan_err!("unnest() can only be applied to array, struct and null")
+                plan_err!("unnest() can only be applied to array, map, struct and null")
             }
         }
     }
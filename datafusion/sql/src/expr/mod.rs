@@ -22,8 +22,9 @@ use datafusion_expr::planner::{
 use sqlparser::ast::{
     AccessExpr, BinaryOperator, CastFormat, CastKind, CeilFloorKind,
     DataType as SQLDataType, DateTimeField, DictionaryField, Expr as SQLExpr,
-    ExprWithAlias as SQLExprWithAlias, JsonPath, MapEntry, StructField, Subscript,
-    TrimWhereField, TypedString, Value, ValueWithSpan,
+    ExprWithAlias as SQLExprWithAlias, Ident, JsonPath, LambdaFunction, LambdaSyntax,
+    MapEntry, OneOrManyWithParens, StructField, Subscript, TrimWhereField, TypedString,
+    Value, ValueWithSpan,
 };
 
 use datafusion_common::{
@@ -97,6 +98,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                             stack.push(StackEntry::SQLExpr(right));
                             stack.push(StackEntry::SQLExpr(left));
                         }
+                        SQLExpr::Lambda(LambdaFunction {
+                            params: OneOrManyWithParens::One(param),
+                            body,
+                            syntax: LambdaSyntax::Arrow,
+                        }) => {
+                            // Lambdas are only valid as arguments of higher-order
+                            // functions, so this is a `->` operator
+                            let expr = lambda_to_arrow_op(param, *body);
+                            stack.push(StackEntry::SQLExpr(Box::new(expr)));
+                        }
                         _ => {
                             let expr = self.sql_expr_to_logical_expr_internal(
                                 *sql_expr,
@@ -1268,6 +1279,60 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 ///   WHEN decisive_condition → decisive_result (ANY:true match found, ALL:false violation found)
 ///   WHEN has_nulls          → NULL
 ///   ELSE                    → vacuous_result
+/// Rebuilds `param -> body`, which dialects supporting lambda functions parse
+/// as a lambda, as the `->` operator applied to the column `param`.
+///
+/// The body of the lambda extends to the end of the expression, while the
+/// right operand of `->` ends at the first operator that does not bind
+/// tighter than `->`, so for example `payload -> 'a' ->> 'b'` is rebuilt as
+/// `(payload -> 'a') ->> 'b'`.
+fn lambda_to_arrow_op(param: Ident, body: SQLExpr) -> SQLExpr {
+    let body = match body {
+        SQLExpr::Lambda(LambdaFunction {
+            params: OneOrManyWithParens::One(inner_param),
+            body,
+            syntax: LambdaSyntax::Arrow,
+        }) => lambda_to_arrow_op(inner_param, *body),
+        body => body,
+    };
+    match body {
+        SQLExpr::BinaryOp { left, op, right } if !binds_tighter_than_arrow(&op) => {
+            SQLExpr::BinaryOp {
+                left: Box::new(lambda_to_arrow_op(param, *left)),
+                op,
+                right,
+            }
+        }
+        right => SQLExpr::BinaryOp {
+            left: Box::new(SQLExpr::Identifier(param)),
+            op: BinaryOperator::Arrow,
+            right: Box::new(right),
+        },
+    }
+}
+
+/// Returns true if `op` binds tighter than `->`, which has the same
+/// precedence as the other PostgreSQL style operators
+fn binds_tighter_than_arrow(op: &BinaryOperator) -> bool {
+    !matches!(
+        op,
+        BinaryOperator::Arrow
+            | BinaryOperator::LongArrow
+            | BinaryOperator::HashArrow
+            | BinaryOperator::HashLongArrow
+            | BinaryOperator::AtArrow
+            | BinaryOperator::ArrowAt
+            | BinaryOperator::HashMinus
+            | BinaryOperator::AtQuestion
+            | BinaryOperator::AtAt
+            | BinaryOperator::Question
+            | BinaryOperator::QuestionAnd
+            | BinaryOperator::Custom(_)
+            | BinaryOperator::And
+            | BinaryOperator::Or
+    )
+}
+
 fn plan_quantified_op(
     needle: &Expr,
     haystack: &Expr,
@@ -1497,4 +1562,39 @@ mod tests {
 
         assert!(matches!(expr, Expr::Alias(_)));
     }
+
+    #[test]
+    fn test_lambda_to_arrow_op() {
+        fn parenthesize(expr: &SQLExpr) -> String {
+            match expr {
+                SQLExpr::BinaryOp { left, op, right } => {
+                    format!("({} {op} {})", parenthesize(left), parenthesize(right))
+                }
+                expr => expr.to_string(),
+            }
+        }
+
+        let dialect = GenericDialect {};
+        for (sql, expected) in [
+            ("payload -> 'a'", "(payload -> 'a')"),
+            ("payload -> 'a' ->> 'b'", "((payload -> 'a') ->> 'b')"),
+            ("payload -> 'a' || 'b'", "(payload -> ('a' || 'b'))"),
+            (
+                "payload -> 'a' = 'b' AND x",
+                "((payload -> ('a' = 'b')) AND x)",
+            ),
+        ] {
+            let mut parser = Parser::new(&dialect).try_with_sql(sql).unwrap();
+            let SQLExpr::Lambda(LambdaFunction {
+                params: OneOrManyWithParens::One(param),
+                body,
+                ..
+            }) = parser.parse_expr().unwrap()
+            else {
+                panic!("expected {sql} to be parsed as a lambda");
+            };
+            let expr = lambda_to_arrow_op(param, *body);
+            assert_eq!(parenthesize(&expr), expected, "{sql}");
+        }
+    }
 }
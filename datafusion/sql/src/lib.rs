@@ -42,6 +42,7 @@
 //! [`LogicalPlan`]: datafusion_expr::logical_plan::LogicalPlan
 //! [`Expr`]: datafusion_expr::expr::Expr
 
+mod connect_by;
 mod cte;
 mod expr;
 pub mod parser;
@@ -23,7 +23,7 @@
 use datafusion_common::DataFusionError;
 use datafusion_common::config::SqlParserOptions;
 use datafusion_common::{Diagnostic, Span, sql_err};
-use sqlparser::ast::{ExprWithAlias, Ident, OrderByOptions};
+use sqlparser::ast::{Expr, ExprWithAlias, Ident, OrderByOptions};
 use sqlparser::tokenizer::TokenWithSpan;
 use sqlparser::{
     ast::{
@@ -58,6 +58,13 @@ fn parse_file_type(s: &str) -> Result<String, DataFusionError> {
 /// Syntax:
 /// ```sql
 /// EXPLAIN <ANALYZE> <VERBOSE> [FORMAT format] statement
+/// EXPLAIN ESTIMATE statement
+/// EXPLAIN ( option [, ...] ) statement
+///
+/// where option is one of
+///   ANALYZE [ TRUE | FALSE ]
+///   VERBOSE [ TRUE | FALSE ]
+///   FORMAT format
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExplainStatement {
@@ -67,6 +74,8 @@ pub struct ExplainStatement {
     pub verbose: bool,
     /// `EXPLAIN .. FORMAT `
     pub format: Option<String>,
+    /// `EXPLAIN ESTIMATE ..`
+    pub estimate: bool,
     /// The statement to analyze. Note this is a DataFusion [`Statement`] (not a
     /// [`sqlparser::ast::Statement`] so that we can use `EXPLAIN`, `COPY`, and other
     /// DataFusion specific statements
@@ -79,10 +88,14 @@ impl fmt::Display for ExplainStatement {
             analyze,
             verbose,
             format,
+            estimate,
             statement,
         } = self;
 
         write!(f, "EXPLAIN ")?;
+        if *estimate {
+            write!(f, "ESTIMATE ")?;
+        }
         if *analyze {
             write!(f, "ANALYZE ")?;
         }
@@ -126,8 +139,9 @@ pub struct CopyToStatement {
     pub source: CopyToSource,
     /// The URL to where the data is heading
     pub target: String,
-    /// Partition keys
-    pub partitioned_by: Vec<String>,
+    /// Partition keys: columns of the source, or expressions named by their
+    /// aliases, e.g. `date_trunc('day', ts) AS day`
+    pub partitioned_by: Vec<ExprWithAlias>,
     /// File type (Parquet, NDJSON, CSV etc.)
     pub stored_as: Option<String>,
     /// Target specific options
@@ -150,7 +164,14 @@ impl fmt::Display for CopyToStatement {
             write!(f, " STORED AS {file_type}")?;
         }
         if !partitioned_by.is_empty() {
-            write!(f, " PARTITIONED BY ({})", partitioned_by.join(", "))?;
+            let partitions: Vec<_> = partitioned_by
+                .iter()
+                .map(|ExprWithAlias { expr, alias }| match alias {
+                    Some(alias) => format!("{expr} AS {alias}"),
+                    None => expr.to_string(),
+                })
+                .collect();
+            write!(f, " PARTITIONED BY ({})", partitions.join(", "))?;
         }
 
         if !options.is_empty() {
@@ -274,6 +295,49 @@ impl fmt::Display for ResetStatement {
     }
 }
 
+/// DataFusion extension for `ALTER TABLE <name> REFRESH SCHEMA`, which
+/// infers the schema of an external table again from its current files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshSchemaStatement {
+    /// Table name
+    pub table_name: ObjectName,
+}
+
+impl fmt::Display for RefreshSchemaStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ALTER TABLE {} REFRESH SCHEMA", self.table_name)
+    }
+}
+
+/// DataFusion extension for `EXECUTE IMMEDIATE`, which plans and executes
+/// a dynamically constructed SQL string
+///
+/// Syntax:
+/// ```sql
+/// EXECUTE IMMEDIATE <sql expression> [USING <expression> [, ...]]
+/// ```
+///
+/// The expressions after `USING` are bound to the placeholders `$1`, `$2`,
+/// ... of the SQL string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecuteImmediateStatement {
+    /// Expression that evaluates to the SQL string
+    pub sql: Expr,
+    /// Values of the placeholders of the SQL string
+    pub parameters: Vec<Expr>,
+}
+
+impl fmt::Display for ExecuteImmediateStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EXECUTE IMMEDIATE {}", self.sql)?;
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            let prefix = if i == 0 { " USING " } else { ", " };
+            write!(f, "{prefix}{parameter}")?;
+        }
+        Ok(())
+    }
+}
+
 /// DataFusion SQL Statement.
 ///
 /// This can either be a [`Statement`] from [`sqlparser`] from a
@@ -293,6 +357,10 @@ pub enum Statement {
     Explain(ExplainStatement),
     /// Extension: `RESET`
     Reset(ResetStatement),
+    /// Extension: `ALTER TABLE ... REFRESH SCHEMA`
+    RefreshSchema(RefreshSchemaStatement),
+    /// Extension: `EXECUTE IMMEDIATE`
+    ExecuteImmediate(ExecuteImmediateStatement),
 }
 
 impl fmt::Display for Statement {
@@ -303,6 +371,8 @@ impl fmt::Display for Statement {
             Statement::CopyTo(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
             Statement::Reset(stmt) => write!(f, "{stmt}"),
+            Statement::RefreshSchema(stmt) => write!(f, "{stmt}"),
+            Statement::ExecuteImmediate(stmt) => write!(f, "{stmt}"),
         }
     }
 }
@@ -570,6 +640,20 @@ impl<'a> DFParser<'a> {
                         self.parser.next_token(); // RESET
                         self.parse_reset()
                     }
+                    Keyword::EXECUTE => {
+                        // use sqlparser-rs parser for EXECUTE of prepared statements
+                        match self.parse_execute_immediate()? {
+                            Some(statement) => Ok(statement),
+                            None => self.parse_and_handle_statement(),
+                        }
+                    }
+                    Keyword::ALTER => {
+                        // use sqlparser-rs parser for other ALTER statements
+                        match self.parse_refresh_schema()? {
+                            Some(statement) => Ok(statement),
+                            None => self.parse_and_handle_statement(),
+                        }
+                    }
                     _ => {
                         // use sqlparser-rs parser
                         self.parse_and_handle_statement()
@@ -640,7 +724,7 @@ impl<'a> DFParser<'a> {
         struct Builder {
             stored_as: Option<String>,
             target: Option<String>,
-            partitioned_by: Option<Vec<String>>,
+            partitioned_by: Option<Vec<ExprWithAlias>>,
             options: Option<Vec<(String, Value)>>,
         }
 
@@ -674,7 +758,7 @@ impl<'a> DFParser<'a> {
                     Keyword::PARTITIONED => {
                         self.parser.expect_keyword(Keyword::BY)?;
                         ensure_not_set(&builder.partitioned_by, "PARTITIONED BY")?;
-                        builder.partitioned_by = Some(self.parse_partitions()?);
+                        builder.partitioned_by = Some(self.parse_partition_exprs()?);
                     }
                     Keyword::OPTIONS => {
                         ensure_not_set(&builder.options, "OPTIONS")?;
@@ -759,9 +843,16 @@ impl<'a> DFParser<'a> {
 
     /// Parse a SQL `EXPLAIN`
     pub fn parse_explain(&mut self) -> Result<Statement, DataFusionError> {
-        let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
-        let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
-        let format = self.parse_explain_format()?;
+        let estimate = self.parse_explain_estimate();
+        let (analyze, verbose, format) = if estimate {
+            (false, false, None)
+        } else if self.peek_explain_options() {
+            self.parse_explain_options()?
+        } else {
+            let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
+            let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+            (analyze, verbose, self.parse_explain_format()?)
+        };
 
         let statement = self.parse_statement()?;
 
@@ -770,9 +861,59 @@ impl<'a> DFParser<'a> {
             analyze,
             verbose,
             format,
+            estimate,
         }))
     }
 
+    /// Parse the `ESTIMATE` of `EXPLAIN ESTIMATE`, which is not a keyword
+    fn parse_explain_estimate(&mut self) -> bool {
+        let is_estimate = matches!(
+            &self.parser.peek_token().token,
+            Token::Word(w) if w.quote_style.is_none()
+                && w.value.eq_ignore_ascii_case("ESTIMATE")
+        );
+        if is_estimate {
+            self.parser.next_token();
+        }
+        is_estimate
+    }
+
+    /// Parse `ALTER TABLE <name> REFRESH SCHEMA`, returning `None` without
+    /// consuming any tokens for other statements
+    pub fn parse_refresh_schema(&mut self) -> Result<Option<Statement>, DataFusionError> {
+        let table_name = self.parser.maybe_parse(|parser| {
+            parser.expect_keywords(&[Keyword::ALTER, Keyword::TABLE])?;
+            let table_name = parser.parse_object_name(false)?;
+            parser.expect_keywords(&[Keyword::REFRESH, Keyword::SCHEMA])?;
+            Ok(table_name)
+        })?;
+        Ok(table_name.map(|table_name| {
+            Statement::RefreshSchema(RefreshSchemaStatement { table_name })
+        }))
+    }
+
+    /// Parse a SQL `EXECUTE IMMEDIATE` statement, or return `None` if the
+    /// statement executes a prepared statement
+    pub fn parse_execute_immediate(
+        &mut self,
+    ) -> Result<Option<Statement>, DataFusionError> {
+        if !self
+            .parser
+            .parse_keywords(&[Keyword::EXECUTE, Keyword::IMMEDIATE])
+        {
+            return Ok(None);
+        }
+        let sql = self.parser.parse_expr()?;
+        let parameters = if self.parser.parse_keyword(Keyword::USING) {
+            self.parser.parse_comma_separated(Parser::parse_expr)?
+        } else {
+            vec![]
+        };
+        Ok(Some(Statement::ExecuteImmediate(
+            ExecuteImmediateStatement { sql, parameters },
+        )))
+    }
+
     /// Parse a SQL `RESET`
     pub fn parse_reset(&mut self) -> Result<Statement, DataFusionError> {
         let mut parts: Vec<String> = Vec::new();
@@ -814,11 +955,71 @@ impl<'a> DFParser<'a> {
         Ok(Statement::Reset(ResetStatement::Variable(variable)))
     }
 
+    /// Returns true if the next tokens start a parenthesized list of `EXPLAIN`
+    /// options, such as `(ANALYZE, FORMAT JSON)`, rather than a parenthesized
+    /// query
+    fn peek_explain_options(&self) -> bool {
+        self.parser.peek_token().token == Token::LParen
+            && matches!(
+                self.parser.peek_nth_token(1).token,
+                Token::Word(w) if matches!(
+                    w.keyword,
+                    Keyword::ANALYZE | Keyword::VERBOSE | Keyword::FORMAT
+                )
+            )
+    }
+
+    /// Parse a parenthesized list of `EXPLAIN` options, returning whether
+    /// `ANALYZE` and `VERBOSE` are enabled, and the `FORMAT`
+    fn parse_explain_options(
+        &mut self,
+    ) -> Result<(bool, bool, Option<String>), DataFusionError> {
+        self.parser.next_token(); // (
+
+        let mut analyze = false;
+        let mut verbose = false;
+        let mut format = None;
+        loop {
+            let next_token = self.parser.next_token();
+            match &next_token.token {
+                Token::Word(w) if w.keyword == Keyword::ANALYZE => {
+                    analyze = self.parse_explain_option_bool();
+                }
+                Token::Word(w) if w.keyword == Keyword::VERBOSE => {
+                    verbose = self.parse_explain_option_bool();
+                }
+                Token::Word(w) if w.keyword == Keyword::FORMAT => {
+                    format = Some(self.parse_explain_format_name()?);
+                }
+                _ => return self.expected("ANALYZE, VERBOSE or FORMAT", &next_token),
+            }
+
+            let next_token = self.parser.next_token();
+            match next_token.token {
+                Token::Comma => continue,
+                Token::RParen => break,
+                _ => return self.expected(", or )", &next_token),
+            }
+        }
+        Ok((analyze, verbose, format))
+    }
+
+    /// Parse the optional boolean value of an `EXPLAIN` option, which is
+    /// enabled if no value is specified
+    fn parse_explain_option_bool(&mut self) -> bool {
+        self.parser
+            .parse_one_of_keywords(&[Keyword::TRUE, Keyword::FALSE])
+            != Some(Keyword::FALSE)
+    }
+
     pub fn parse_explain_format(&mut self) -> Result<Option<String>, DataFusionError> {
         if !self.parser.parse_keyword(Keyword::FORMAT) {
             return Ok(None);
         }
+        self.parse_explain_format_name().map(Some)
+    }
 
+    fn parse_explain_format_name(&mut self) -> Result<String, DataFusionError> {
         let next_token = self.parser.next_token();
         let format = match next_token.token {
             Token::Word(w) => Ok(w.value),
@@ -826,7 +1027,7 @@ impl<'a> DFParser<'a> {
             Token::DoubleQuotedString(w) => Ok(w),
             _ => self.expected("an explain format such as TREE", &next_token),
         }?;
-        Ok(Some(format))
+        Ok(format)
     }
 
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
@@ -885,6 +1086,32 @@ impl<'a> DFParser<'a> {
         Ok(partitions)
     }
 
+    /// Parse the partitioning clause of a `COPY TO` statement: columns or
+    /// expressions with optional aliases, e.g. `(a, date_trunc('day', ts) AS day)`
+    fn parse_partition_exprs(&mut self) -> Result<Vec<ExprWithAlias>, DataFusionError> {
+        let mut partitions = vec![];
+        if !self.parser.consume_token(&Token::LParen)
+            || self.parser.consume_token(&Token::RParen)
+        {
+            return Ok(partitions);
+        }
+
+        loop {
+            partitions.push(self.parser.parse_expr_with_alias()?);
+            let comma = self.parser.consume_token(&Token::Comma);
+            if self.parser.consume_token(&Token::RParen) {
+                // allow a trailing comma, even though it's not in standard
+                break;
+            } else if !comma {
+                return self.expected(
+                    "',' or ')' after partition definition",
+                    &self.parser.peek_token(),
+                );
+            }
+        }
+        Ok(partitions)
+    }
+
     /// Parse the ordering clause of a `CREATE EXTERNAL TABLE` SQL statement
     pub fn parse_order_by_exprs(&mut self) -> Result<Vec<OrderByExpr>, DataFusionError> {
         let mut values = vec![];
@@ -1876,6 +2103,7 @@ mod tests {
                 analyze,
                 verbose,
                 format: None,
+                estimate: false,
                 statement: Box::new(expected_copy),
             });
             assert_eq!(verified_stmt(sql), expected);
@@ -1883,6 +2111,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn explain_options() -> Result<(), DataFusionError> {
+        let cases = vec![
+            (
+                "EXPLAIN (ANALYZE, FORMAT JSON) SELECT 1",
+                "EXPLAIN ANALYZE FORMAT JSON SELECT 1",
+            ),
+            (
+                "EXPLAIN (FORMAT tree, ANALYZE false) SELECT 1",
+                "EXPLAIN FORMAT tree SELECT 1",
+            ),
+            (
+                "EXPLAIN (ANALYZE TRUE, VERBOSE) SELECT 1",
+                "EXPLAIN ANALYZE VERBOSE SELECT 1",
+            ),
+            // a parenthesized query is not a list of options
+            ("EXPLAIN (SELECT 1)", "EXPLAIN (SELECT 1)"),
+            ("EXPLAIN estimate SELECT 1", "EXPLAIN ESTIMATE SELECT 1"),
+        ];
+        for (sql, canonical) in cases {
+            one_statement_parses_to(sql, canonical);
+        }
+
+        let err = DFParser::parse_sql("EXPLAIN (ANALYZE, COSTS) SELECT 1")
+            .unwrap_err()
+            .strip_backtrace();
+        assert_contains!(err, "Expected: ANALYZE, VERBOSE or FORMAT, found: COSTS");
+        Ok(())
+    }
+
     #[test]
     fn copy_to_query_to_table() -> Result<(), DataFusionError> {
         let statement = verified_stmt("SELECT 1");
@@ -1939,7 +2197,10 @@ mod tests {
         let expected = Statement::CopyTo(CopyToStatement {
             source: object_name("foo"),
             target: "bar".to_string(),
-            partitioned_by: vec!["a".to_string()],
+            partitioned_by: vec![ExprWithAlias {
+                expr: Identifier(Ident::new("a")),
+                alias: None,
+            }],
             stored_as: Some("CSV".to_owned()),
             options: vec![(
                 "row_group_size".to_string(),
@@ -1950,6 +2211,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn copy_to_partitioned_by_exprs() -> Result<(), DataFusionError> {
+        let sql = "COPY foo TO bar STORED AS CSV PARTITIONED BY (a, b - c AS d)";
+        let expected = Statement::CopyTo(CopyToStatement {
+            source: object_name("foo"),
+            target: "bar".to_string(),
+            partitioned_by: vec![
+                ExprWithAlias {
+                    expr: Identifier(Ident::new("a")),
+                    alias: None,
+                },
+                ExprWithAlias {
+                    expr: Expr::BinaryOp {
+                        left: Box::new(Identifier(Ident::new("b"))),
+                        op: BinaryOperator::Minus,
+                        right: Box::new(Identifier(Ident::new("c"))),
+                    },
+                    alias: Some(Ident::new("d")),
+                },
+            ],
+            stored_as: Some("CSV".to_owned()),
+            options: vec![],
+        });
+        assert_eq!(verified_stmt(sql), expected);
+        Ok(())
+    }
+
     #[test]
     fn copy_to_multi_options() -> Result<(), DataFusionError> {
         // order of options is preserved
@@ -2095,6 +2383,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_refresh_schema() -> Result<(), DataFusionError> {
+        let sql = "ALTER TABLE s.t REFRESH SCHEMA";
+        let expected = Statement::RefreshSchema(RefreshSchemaStatement {
+            table_name: ObjectName::from(vec![Ident::from("s"), Ident::from("t")]),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // other ALTER statements are parsed by sqlparser
+        let statements = DFParser::parse_sql("ALTER TABLE t RENAME TO u")?;
+        assert!(matches!(statements[0], Statement::Statement(_)));
+        expect_parse_error("ALTER TABLE t REFRESH", "Expected: ");
+        Ok(())
+    }
+
+    #[test]
+    fn parse_execute_immediate() -> Result<(), DataFusionError> {
+        let statements =
+            DFParser::parse_sql("EXECUTE IMMEDIATE 'SELECT ' || '$1, $2' USING 1, 'a'")?;
+        let Statement::ExecuteImmediate(statement) = &statements[0] else {
+            panic!("expected EXECUTE IMMEDIATE, got {}", statements[0]);
+        };
+        assert_eq!(statement.sql.to_string(), "'SELECT ' || '$1, $2'");
+        assert_eq!(statement.parameters.len(), 2);
+        assert_eq!(
+            statement.to_string(),
+            "EXECUTE IMMEDIATE 'SELECT ' || '$1, $2' USING 1, 'a'"
+        );
+
+        // EXECUTE of prepared statements is parsed by sqlparser
+        let statements = DFParser::parse_sql("EXECUTE my_plan(1)")?;
+        assert!(matches!(statements[0], Statement::Statement(_)));
+        expect_parse_error("EXECUTE IMMEDIATE 'SELECT 1' USING", "Expected: ");
+        Ok(())
+    }
+
     #[test]
     fn test_custom_tokens() {
         // Span mock.
@@ -27,6 +27,7 @@ use datafusion_common::TableReference;
 use datafusion_common::config::SqlParserOptions;
 use datafusion_common::datatype::{DataTypeExt, FieldExt};
 use datafusion_common::error::add_possible_columns_to_diag;
+use datafusion_common::types::DFCategorical;
 use datafusion_common::{DFSchema, DataFusionError, Result, not_impl_err, plan_err};
 use datafusion_common::{
     DFSchemaRef, Diagnostic, SchemaError, field_not_found, internal_err,
@@ -36,7 +37,7 @@ use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
 pub use datafusion_expr::planner::ContextProvider;
 use datafusion_expr::utils::find_column_exprs;
 use datafusion_expr::{Expr, col};
-use sqlparser::ast::{ArrayElemTypeDef, ExactNumberInfo, TimezoneInfo};
+use sqlparser::ast::{ArrayElemTypeDef, EnumMember, ExactNumberInfo, TimezoneInfo};
 use sqlparser::ast::{ColumnDef as SQLColumnDef, ColumnOption};
 use sqlparser::ast::{DataType as SQLDataType, Ident, ObjectName, TableAlias};
 
@@ -681,6 +682,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             SQLDataType::Array(ArrayElemTypeDef::None) => {
                 not_impl_err!("Arrays with unspecified type is not supported")
             }
+            SQLDataType::Enum(members, _) => {
+                let values = members
+                    .iter()
+                    .map(|member| match member {
+                        EnumMember::Name(name) => Ok(name.clone()),
+                        EnumMember::NamedValue(name, _) => not_impl_err!(
+                            "ENUM with explicit values is not supported: {name}"
+                        ),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let storage_type = DataType::Dictionary(
+                    Box::new(DataType::Int32),
+                    Box::new(DataType::Utf8),
+                );
+                Ok(Arc::new(
+                    DFCategorical::try_new(&storage_type, values)?.to_field(""),
+                ))
+            }
             other => Ok(self
                 .convert_simple_data_type(other)?
                 .into_nullable_field_ref()),
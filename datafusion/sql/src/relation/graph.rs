@@ -0,0 +1,210 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of the `graph_reachable` and `graph_shortest_paths` table
+//! functions
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{
+    DFSchema, JoinType, Result, ScalarValue, not_impl_err, plan_err,
+};
+use datafusion_expr::{Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder, col, lit};
+use sqlparser::ast::{Expr as SQLExpr, FunctionArg, FunctionArgExpr};
+
+/// Returns true if `name` is a graph table function planned by
+/// [`SqlToRel::plan_graph_function`]
+pub(crate) fn is_graph_function(name: &str) -> bool {
+    name.eq_ignore_ascii_case("graph_reachable")
+        || name.eq_ignore_ascii_case("graph_shortest_paths")
+}
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans the graph table functions, which traverse a table of edges
+    /// whose first two columns are the source and destination of each edge
+    ///
+    /// * `graph_reachable(edges, start)`: returns the `node`s reachable from
+    ///   `start`, including `start` itself
+    /// * `graph_shortest_paths(edges, start, max_depth)`: returns the `node`s
+    ///   reachable from `start` over at most `max_depth` edges, and the
+    ///   `depth` of the shortest path to each of them
+    ///
+    /// Both are planned as recursive CTEs whose recursive term follows the
+    /// edges of the nodes reached by the previous iteration. The recursion
+    /// removes duplicate rows like a `UNION`, so it stops at cycles:
+    /// `graph_reachable` visits each node once, and `graph_shortest_paths`
+    /// visits each node at most once per depth before keeping the smallest
+    /// depth. The memory used by the recursion is therefore bounded by the
+    /// number of nodes, times `max_depth` for `graph_shortest_paths`.
+    pub(super) fn plan_graph_function(
+        &self,
+        name: &str,
+        args: Vec<FunctionArg>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let name = name.to_lowercase();
+        let name = name.as_str();
+        if !self
+            .context_provider
+            .options()
+            .execution
+            .enable_recursive_ctes
+        {
+            return not_impl_err!("{name} requires recursive CTEs to be enabled");
+        }
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                _ => plan_err!("Unsupported argument for {name}: {arg}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (edges, start, max_depth) = match <[SQLExpr; 2]>::try_from(args) {
+            Ok([edges, start]) if name == "graph_reachable" => (edges, start, None),
+            Err(args) if name == "graph_shortest_paths" => {
+                match <[SQLExpr; 3]>::try_from(args) {
+                    Ok([edges, start, max_depth]) => (edges, start, Some(max_depth)),
+                    Err(_) => {
+                        return plan_err!(
+                            "Expected graph_shortest_paths(edges, start, max_depth)"
+                        );
+                    }
+                }
+            }
+            _ if name == "graph_reachable" => {
+                return plan_err!("Expected graph_reachable(edges, start)");
+            }
+            _ => {
+                return plan_err!(
+                    "Expected graph_shortest_paths(edges, start, max_depth)"
+                );
+            }
+        };
+
+        let edges = self.plan_table_argument(name, edges, planner_context)?;
+        let edge_columns = edges.schema().columns();
+        let [source, destination, ..] = edge_columns.as_slice() else {
+            return plan_err!(
+                "{name} expects a table of edges with a source and a destination column"
+            );
+        };
+        let node_type = Expr::Column(destination.clone()).get_type(edges.schema())?;
+
+        let start = self.sql_to_expr(start, &DFSchema::empty(), planner_context)?;
+        let max_depth = max_depth
+            .map(|max_depth| self.graph_max_depth(name, max_depth, planner_context))
+            .transpose()?;
+
+        // the static term returns the start node. Its node is declared nullable
+        // like the destinations returned by the recursive term, as the
+        // recursive query takes the schema of its static term
+        let mut start_fields = vec![Field::new("node", node_type, true)];
+        let mut start_row = vec![start];
+        if max_depth.is_some() {
+            start_fields.push(Field::new("depth", DataType::Int64, false));
+            start_row.push(lit(0_i64));
+        }
+        let start_schema =
+            DFSchema::from_unqualified_fields(start_fields.into(), HashMap::new())?;
+        let static_plan = LogicalPlanBuilder::values_with_schema(
+            vec![start_row],
+            &Arc::new(start_schema),
+        )?;
+        let start_columns = static_plan
+            .schema()
+            .columns()
+            .into_iter()
+            .zip(["node", "depth"])
+            .map(|(column, name)| Expr::Column(column).alias(name))
+            .collect::<Vec<_>>();
+        let static_plan = static_plan.project(start_columns)?.build()?;
+
+        // the recursive term returns the destinations of the edges of the nodes
+        // reached by the previous iteration, ignoring edges without destination
+        let work_table_source = self
+            .context_provider
+            .create_cte_work_table(name, Arc::clone(static_plan.schema().inner()))?;
+        let mut work_table = LogicalPlanBuilder::scan(name, work_table_source, None)?;
+        let work_columns = work_table.schema().columns();
+        if let Some(max_depth) = max_depth {
+            work_table = work_table
+                .filter(Expr::Column(work_columns[1].clone()).lt(lit(max_depth)))?;
+        }
+        let mut next_row = vec![Expr::Column(destination.clone()).alias("node")];
+        if max_depth.is_some() {
+            next_row.push(
+                (Expr::Column(work_columns[1].clone()) + lit(1_i64)).alias("depth"),
+            );
+        }
+        let recursive_plan = work_table
+            .join(
+                edges,
+                JoinType::Inner,
+                (vec![work_columns[0].clone()], vec![source.clone()]),
+                None,
+            )?
+            .filter(Expr::Column(destination.clone()).is_not_null())?
+            .project(next_row)?
+            .build()?;
+
+        let reachable = LogicalPlanBuilder::from(static_plan).to_recursive_query(
+            name.to_string(),
+            recursive_plan,
+            true,
+        )?;
+        if max_depth.is_none() {
+            return reachable.build();
+        }
+
+        let Some(min) = self.context_provider.get_aggregate_meta("min") else {
+            return plan_err!("{name} requires the min function to be registered");
+        };
+        reachable
+            .aggregate([col("node")], [min.call(vec![col("depth")]).alias("depth")])?
+            .build()
+    }
+
+    /// Plans the `max_depth` argument of `graph_shortest_paths`
+    fn graph_max_depth(
+        &self,
+        name: &str,
+        max_depth: SQLExpr,
+        planner_context: &mut PlannerContext,
+    ) -> Result<i64> {
+        let max_depth =
+            self.sql_to_expr(max_depth, &DFSchema::empty(), planner_context)?;
+        let depth = match &max_depth {
+            Expr::Literal(value, _) if value.data_type().is_integer() => {
+                match value.cast_to(&DataType::Int64)? {
+                    ScalarValue::Int64(Some(depth)) => Some(depth),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match depth {
+            Some(depth) if depth > 0 => Ok(depth),
+            _ => {
+                plan_err!("{name} expects a positive integer max_depth, got {max_depth}")
+            }
+        }
+    }
+}
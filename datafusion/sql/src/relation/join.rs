@@ -16,12 +16,28 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{Column, Result, not_impl_err, plan_datafusion_err};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
+use arrow::datatypes::Schema;
+use datafusion_common::metadata_column::is_metadata_column;
+use datafusion_common::{
+    Column, DFSchema, NullEquality, Result, not_impl_err, plan_datafusion_err, plan_err,
+};
+use datafusion_expr::expr::BinaryExpr;
+use datafusion_expr::logical_plan::{
+    Join as LogicalJoin, JoinConstraint as LogicalJoinConstraint,
+};
+use datafusion_expr::utils::{
+    conjunction, find_valid_equijoin_key_pair, split_conjunction_owned,
+};
+use datafusion_expr::{
+    AsOfMatch, Expr, ExprSchemable, JoinType, LogicalPlan, LogicalPlanBuilder, Operator,
+    lit,
+};
 use sqlparser::ast::{
-    Join, JoinConstraint, JoinOperator, ObjectName, TableFactor, TableWithJoins,
+    Expr as SQLExpr, Join, JoinConstraint, JoinOperator, ObjectName, TableFactor,
+    TableWithJoins,
 };
 use std::collections::HashSet;
+use std::sync::Arc;
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
     pub(crate) fn plan_table_with_joins(
@@ -49,6 +65,45 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         join: Join,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
+        let lateral_join_type = match &join.join_operator {
+            JoinOperator::Inner(_)
+            | JoinOperator::Join(_)
+            | JoinOperator::CrossJoin(JoinConstraint::None)
+            | JoinOperator::CrossApply => Some(JoinType::Inner),
+            JoinOperator::Left(_)
+            | JoinOperator::LeftOuter(_)
+            | JoinOperator::OuterApply => Some(JoinType::Left),
+            _ => None,
+        };
+        if let Some(join_type) = lateral_join_type
+            && let Some(plan) = self.plan_lateral_table_function(
+                &left,
+                &join.relation,
+                join_type,
+                planner_context,
+            )?
+        {
+            return match join.join_operator {
+                JoinOperator::Inner(constraint) | JoinOperator::Join(constraint) => {
+                    self.apply_lateral_join_constraint(plan, constraint, planner_context)
+                }
+                JoinOperator::Left(constraint) | JoinOperator::LeftOuter(constraint) => {
+                    // The condition must hold for the rows the function returns
+                    // or is not applied at all, so only `ON true` is supported
+                    if let JoinConstraint::On(sql_expr) = constraint
+                        && self.sql_to_expr(sql_expr, plan.schema(), planner_context)?
+                            != lit(true)
+                    {
+                        return not_impl_err!(
+                            "LEFT JOIN LATERAL with a table function only supports ON true"
+                        );
+                    }
+                    Ok(plan)
+                }
+                _ => Ok(plan),
+            };
+        }
+
         let right = if is_lateral_join(&join)? {
             self.create_relation_subquery(join.relation, planner_context)?
         } else {
@@ -98,10 +153,129 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             JoinOperator::CrossJoin(JoinConstraint::None) => {
                 self.parse_cross_join(left, right)
             }
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => self.parse_asof_join(
+                left,
+                right,
+                match_condition,
+                constraint,
+                planner_context,
+            ),
             other => not_impl_err!("Unsupported JOIN operator {other:?}"),
         }
     }
 
+    /// Plans a lateral call of a table function with arguments that
+    /// reference columns of `left`, such as `FROM t, LATERAL f(t.x)`, as a
+    /// [`LateralTableFunction`] that calls the function for every row of
+    /// `left`.
+    ///
+    /// Returns `None` if `relation` is not such a call, in which case it is
+    /// planned as a regular table function with constant arguments.
+    ///
+    /// [`LateralTableFunction`]: datafusion_expr::logical_plan::LateralTableFunction
+    pub(crate) fn plan_lateral_table_function(
+        &self,
+        left: &LogicalPlan,
+        relation: &TableFactor,
+        join_type: JoinType,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Option<LogicalPlan>> {
+        let TableFactor::Function {
+            lateral: true,
+            name,
+            args,
+            alias,
+        } = relation
+        else {
+            return Ok(None);
+        };
+        let args =
+            self.table_function_args(args.clone(), left.schema(), planner_context)?;
+        if args.iter().all(|arg| arg.column_refs().is_empty()) {
+            return Ok(None);
+        }
+
+        let function_name = self.object_name_to_table_reference(name.clone())?;
+        let function_name = function_name.table();
+        let arg_types = args
+            .iter()
+            .map(|arg| arg.get_type(left.schema()))
+            .collect::<Result<Vec<_>>>()?;
+        let schema = self
+            .context_provider
+            .get_lateral_table_function_schema(function_name, &arg_types)?;
+
+        // The columns of the function are qualified by the alias, if any,
+        // and otherwise by the name of the function
+        let (qualifier, column_names) = match alias {
+            Some(alias) => (
+                self.ident_normalizer.normalize(alias.name.clone()),
+                alias
+                    .columns
+                    .iter()
+                    .map(|column| self.ident_normalizer.normalize(column.name.clone()))
+                    .collect(),
+            ),
+            None => (function_name.to_string(), vec![]),
+        };
+        let fields = if column_names.is_empty() {
+            schema.fields().to_vec()
+        } else if column_names.len() != schema.fields().len() {
+            return plan_err!(
+                "Table function {function_name} returns {} columns but {} names given as column alias",
+                schema.fields().len(),
+                column_names.len()
+            );
+        } else {
+            schema
+                .fields()
+                .iter()
+                .zip(column_names)
+                .map(|(field, name)| Arc::new(field.as_ref().clone().with_name(name)))
+                .collect()
+        };
+        let function_schema = DFSchema::try_from_qualified_schema(
+            qualifier,
+            &Schema::new_with_metadata(fields, schema.metadata().clone()),
+        )?;
+
+        LogicalPlanBuilder::from(left.clone())
+            .lateral_table_function(
+                function_name,
+                args,
+                Arc::new(function_schema),
+                join_type,
+            )?
+            .build()
+            .map(Some)
+    }
+
+    /// Applies the constraint of an inner join with a lateral table function
+    /// call as a filter on the output of the call
+    fn apply_lateral_join_constraint(
+        &self,
+        plan: LogicalPlan,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        match constraint {
+            JoinConstraint::On(sql_expr) => {
+                let expr = self.sql_to_expr(sql_expr, plan.schema(), planner_context)?;
+                if expr == lit(true) {
+                    return Ok(plan);
+                }
+                LogicalPlanBuilder::from(plan).filter(expr)?.build()
+            }
+            JoinConstraint::None => Ok(plan),
+            other => not_impl_err!(
+                "Unsupported join constraint for a lateral table function: {other:?}"
+            ),
+        }
+    }
+
     fn parse_cross_join(
         &self,
         left: LogicalPlan,
@@ -110,6 +284,104 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         LogicalPlanBuilder::from(left).cross_join(right)?.build()
     }
 
+    /// Plans `left ASOF JOIN right MATCH_CONDITION (...) [ON ...]`, which
+    /// keeps every left row, like a left outer join, and matches it with the
+    /// nearest right row according to the first conjunct of the match
+    /// condition. The remaining conjuncts form the tolerance of the match.
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        match_condition: SQLExpr,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let join_schema = left.schema().join(right.schema())?;
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+
+        let match_condition =
+            self.sql_to_expr(match_condition, &join_schema, planner_context)?;
+        let mut conjuncts = split_conjunction_owned(match_condition).into_iter();
+        let asof_match = match conjuncts.next() {
+            Some(Expr::BinaryExpr(BinaryExpr {
+                left: l,
+                op: op @ (Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq),
+                right: r,
+            })) => match find_valid_equijoin_key_pair(&l, &r, left_schema, right_schema)?
+            {
+                Some((left_expr, right_expr)) => {
+                    // `right.ts <= left.ts` is planned as `left.ts >= right.ts`
+                    let op = if left_expr == *l {
+                        op
+                    } else {
+                        op.swap().unwrap_or(op)
+                    };
+                    AsOfMatch::try_new(left_expr, op, right_expr, conjunction(conjuncts))?
+                }
+                None => {
+                    return plan_err!(
+                        "ASOF join match condition must compare an expression of each input, got {l} {op} {r}"
+                    );
+                }
+            },
+            other => {
+                return plan_err!(
+                    "ASOF join match condition must start with a >, >=, < or <= comparison, got {}",
+                    other.map(|e| e.to_string()).unwrap_or_default()
+                );
+            }
+        };
+
+        let on = match constraint {
+            JoinConstraint::On(sql_expr) => {
+                let expr = self.sql_to_expr(sql_expr, &join_schema, planner_context)?;
+                split_conjunction_owned(expr)
+                    .into_iter()
+                    .map(|expr| {
+                        let key_pair = match &expr {
+                            Expr::BinaryExpr(BinaryExpr {
+                                left: l,
+                                op: Operator::Eq,
+                                right: r,
+                            }) => find_valid_equijoin_key_pair(
+                                l,
+                                r,
+                                left_schema,
+                                right_schema,
+                            )?,
+                            _ => None,
+                        };
+                        key_pair.ok_or_else(|| {
+                            plan_datafusion_err!(
+                                "ASOF join ON clause only supports equalities between the two inputs, got {expr}"
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
+            JoinConstraint::None => vec![],
+            other => {
+                return not_impl_err!(
+                    "Unsupported ASOF join constraint {other:?}, only ON is supported"
+                );
+            }
+        };
+
+        let join = LogicalJoin::try_new(
+            Arc::new(left),
+            Arc::new(right),
+            on,
+            None,
+            JoinType::Left,
+            LogicalJoinConstraint::On,
+            NullEquality::NullEqualsNothing,
+            false,
+        )?
+        .with_asof_match(asof_match)?;
+        Ok(LogicalPlan::Join(join))
+    }
+
     fn parse_join(
         &self,
         left: LogicalPlan,
@@ -154,12 +426,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     .build()
             }
             JoinConstraint::Natural => {
-                let left_cols: HashSet<&String> =
-                    left.schema().fields().iter().map(|f| f.name()).collect();
+                // hidden metadata columns, such as the file of each side,
+                // are not joined on
+                let left_cols: HashSet<&String> = left
+                    .schema()
+                    .fields()
+                    .iter()
+                    .filter(|f| !is_metadata_column(f))
+                    .map(|f| f.name())
+                    .collect();
                 let keys: Vec<Column> = right
                     .schema()
                     .fields()
                     .iter()
+                    .filter(|f| !is_metadata_column(f))
                     .map(|f| f.name())
                     .filter(|f| left_cols.contains(f))
                     .map(Column::from_name)
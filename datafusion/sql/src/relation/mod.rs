@@ -29,9 +29,18 @@ use datafusion_expr::planner::{
 };
 use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, expr::Unnest};
 use datafusion_expr::{Subquery, SubqueryAlias};
-use sqlparser::ast::{FunctionArg, FunctionArgExpr, Spanned, TableFactor};
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, FunctionArg, FunctionArgExpr, NullInclusion,
+    ObjectName, Spanned, TableFactor, Value,
+};
 
+mod graph;
 mod join;
+mod pivot;
+mod time_window;
+
+pub(crate) use graph::is_graph_function;
+pub(crate) use time_window::is_time_window_function;
 
 struct SqlToRelRelationContext<'a, 'b, S: ContextProvider> {
     planner: &'a SqlToRel<'b, S>,
@@ -71,10 +80,7 @@ impl<'a, 'b, S: ContextProvider> RelationPlannerContext
         self.planner.ident_normalizer.normalize(ident)
     }
 
-    fn object_name_to_table_reference(
-        &self,
-        name: sqlparser::ast::ObjectName,
-    ) -> Result<TableReference> {
+    fn object_name_to_table_reference(&self, name: ObjectName) -> Result<TableReference> {
         self.planner.object_name_to_table_reference(name)
     }
 }
@@ -150,11 +156,44 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let relation_span = relation.span();
         let (plan, alias) = match relation {
             TableFactor::Table {
-                name, alias, args, ..
+                name,
+                alias,
+                args,
+                with_hints,
+                index_hints,
+                ..
             } => {
+                let (options, with_hints) = table_hint_options(with_hints);
+                if !with_hints.is_empty() || !index_hints.is_empty() {
+                    self.context_provider.report_warning(Diagnostic::new_warning(
+                        format!("table hints for '{name}' are not supported and were ignored"),
+                        Span::try_from_sqlparser_span(relation_span),
+                    ));
+                }
                 if let Some(func_args) = args {
+                    if !options.is_empty() {
+                        return not_impl_err!(
+                            "Table options are not supported for table function '{name}'"
+                        );
+                    }
                     let tbl_func_name =
                         name.0.first().unwrap().as_ident().unwrap().to_string();
+                    if is_time_window_function(&tbl_func_name) {
+                        let plan = self.plan_time_window(
+                            &tbl_func_name,
+                            func_args.args,
+                            planner_context,
+                        )?;
+                        return Ok(PlannedRelation::new(plan, alias));
+                    }
+                    if is_graph_function(&tbl_func_name) {
+                        let plan = self.plan_graph_function(
+                            &tbl_func_name,
+                            func_args.args,
+                            planner_context,
+                        )?;
+                        return Ok(PlannedRelation::new(plan, alias));
+                    }
                     let args = func_args
                         .args
                         .into_iter()
@@ -174,12 +213,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     let provider = self
                         .context_provider
                         .get_table_function_source(&tbl_func_name, args)?;
-                    let plan = LogicalPlanBuilder::scan(
+                    let plan = LogicalPlanBuilder::scan_with_metadata_columns(
                         TableReference::Bare {
                             table: format!("{tbl_func_name}()").into(),
                         },
                         provider,
-                        None,
                     )?
                     .build()?;
                     (plan, alias)
@@ -188,18 +226,27 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     let table_ref = self.object_name_to_table_reference(name)?;
                     let table_name = table_ref.to_string();
                     let cte = planner_context.get_cte(&table_name);
+                    if cte.is_some() && !options.is_empty() {
+                        return not_impl_err!(
+                            "Table options are not supported for CTE '{table_name}'"
+                        );
+                    }
+                    let source = if options.is_empty() {
+                        self.context_provider.get_table_source(table_ref.clone())
+                    } else {
+                        self.context_provider
+                            .get_table_source_with_options(table_ref.clone(), options)
+                    };
                     (
-                        match (
-                            cte,
-                            self.context_provider.get_table_source(table_ref.clone()),
-                        ) {
+                        match (cte, source) {
                             (Some(cte_plan), _) => Ok(cte_plan.clone()),
-                            (_, Ok(provider)) => LogicalPlanBuilder::scan(
-                                table_ref.clone(),
-                                provider,
-                                None,
-                            )?
-                            .build(),
+                            (_, Ok(provider)) => {
+                                LogicalPlanBuilder::scan_with_metadata_columns(
+                                    table_ref.clone(),
+                                    provider,
+                                )?
+                                .build()
+                            }
                             (None, Err(e)) => {
                                 let e = e.with_diagnostic(Diagnostic::new_error(
                                     format!("table '{table_ref}' not found"),
@@ -232,10 +279,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 with_offset_alias: None,
                 with_ordinality,
             } => {
-                if with_ordinality {
-                    return not_impl_err!("UNNEST with ordinality is not supported yet");
-                }
-
                 // Unnest table factor has empty input
                 let schema = DFSchema::empty();
                 let input = LogicalPlanBuilder::empty(true).build()?;
@@ -256,7 +299,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if unnest_exprs.is_empty() {
                     return plan_err!("UNNEST must have at least one argument");
                 }
-                let logical_plan = self.try_process_unnest(input, unnest_exprs)?;
+                let logical_plan =
+                    self.try_process_unnest(input, unnest_exprs, with_ordinality)?;
                 (logical_plan, alias)
             }
             TableFactor::UNNEST { .. } => {
@@ -273,19 +317,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     .last()
                     .cloned()
                     .unwrap_or_else(|| Arc::new(DFSchema::empty()));
-                let func_args = args
-                    .into_iter()
-                    .map(|arg| match arg {
-                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
-                        | FunctionArg::Named {
-                            arg: FunctionArgExpr::Expr(expr),
-                            ..
-                        } => {
-                            self.sql_expr_to_logical_expr(expr, &schema, planner_context)
-                        }
-                        _ => plan_err!("Unsupported function argument: {arg:?}"),
-                    })
-                    .collect::<Result<Vec<Expr>>>()?;
+                let func_args =
+                    self.table_function_args(args, &schema, planner_context)?;
                 let provider = self
                     .context_provider
                     .get_table_function_source(tbl_func_ref.table(), func_args)?;
@@ -294,6 +327,46 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         .build()?;
                 (plan, alias)
             }
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan = self.plan_pivot(
+                    input,
+                    aggregate_functions,
+                    value_column,
+                    value_source,
+                    default_on_null,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
+            TableFactor::Unpivot {
+                table,
+                value,
+                name,
+                columns,
+                null_inclusion,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let include_nulls =
+                    matches!(null_inclusion, Some(NullInclusion::IncludeNulls));
+                let plan = self.plan_unpivot(
+                    &input,
+                    value,
+                    name,
+                    columns,
+                    include_nulls,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
             // @todo Support TableFactory::TableFunction?
             _ => {
                 return not_impl_err!(
@@ -304,6 +377,46 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(PlannedRelation::new(plan, alias))
     }
 
+    /// Plans the arguments of a table function call against `schema`
+    pub(crate) fn table_function_args(
+        &self,
+        args: Vec<FunctionArg>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Vec<Expr>> {
+        args.into_iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                | FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(expr),
+                    ..
+                } => self.sql_expr_to_logical_expr(expr, schema, planner_context),
+                _ => plan_err!("Unsupported function argument: {arg:?}"),
+            })
+            .collect()
+    }
+
+    /// Plans an argument of a table function that names a table or CTE,
+    /// such as the first argument of `tumble`
+    pub(super) fn plan_table_argument(
+        &self,
+        name: &str,
+        table: SQLExpr,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let table = match table {
+            SQLExpr::Identifier(ident) => ObjectName::from(vec![ident]),
+            SQLExpr::CompoundIdentifier(idents) => ObjectName::from(idents),
+            _ => return plan_err!("{name} expects a table name, got {table}"),
+        };
+        let table_ref = self.object_name_to_table_reference(table)?;
+        if let Some(cte) = planner_context.get_cte(&table_ref.to_string()) {
+            return Ok(cte.clone());
+        }
+        let source = self.context_provider.get_table_source(table_ref.clone())?;
+        LogicalPlanBuilder::scan(table_ref, source, None)?.build()
+    }
+
     pub(crate) fn create_relation_subquery(
         &self,
         subquery: TableFactor,
@@ -364,6 +477,52 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 }
 
+/// Split table hints of the form `key = value`, such as
+/// `WITH (datafusion.execution.batch_size = 1024)`, from other hints such as
+/// `WITH (NOLOCK)`, returning the options and the remaining hints
+fn table_hint_options(hints: Vec<SQLExpr>) -> (Vec<(String, String)>, Vec<SQLExpr>) {
+    let mut options = vec![];
+    let mut other = vec![];
+    for hint in hints {
+        match table_hint_option(&hint) {
+            Some(option) => options.push(option),
+            None => other.push(hint),
+        }
+    }
+    (options, other)
+}
+
+fn table_hint_option(hint: &SQLExpr) -> Option<(String, String)> {
+    let SQLExpr::BinaryOp {
+        left,
+        op: BinaryOperator::Eq,
+        right,
+    } = hint
+    else {
+        return None;
+    };
+    let key = match left.as_ref() {
+        SQLExpr::Identifier(ident) => ident.value.clone(),
+        SQLExpr::CompoundIdentifier(idents) => idents
+            .iter()
+            .map(|ident| ident.value.as_str())
+            .collect::<Vec<_>>()
+            .join("."),
+        SQLExpr::Value(value) => value.value.clone().into_string()?,
+        _ => return None,
+    };
+    let value = match right.as_ref() {
+        SQLExpr::Identifier(ident) => ident.value.clone(),
+        SQLExpr::Value(value) => match &value.value {
+            Value::Number(n, _) => n.to_string(),
+            Value::Boolean(b) => b.to_string(),
+            v => v.clone().into_string()?,
+        },
+        _ => return None,
+    };
+    Some((key, value))
+}
+
 fn optimize_subquery_sort(
     plan: LogicalPlan,
     enable_subquery_sort_elimination: bool,
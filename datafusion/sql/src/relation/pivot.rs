@@ -0,0 +1,238 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of the `PIVOT` and `UNPIVOT` table operators
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+
+use datafusion_common::{Column, DFSchema, Result, not_impl_err, plan_err};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, lit, when};
+use sqlparser::ast::{Expr as SQLExpr, ExprWithAlias, Ident, PivotValueSource};
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans `input PIVOT (agg [AS alias], ... FOR value_column IN (value [AS alias], ...))`
+    ///
+    /// The pivot is rewritten to an aggregation grouped by every input column
+    /// not referenced by the aggregates or the value column, with one
+    /// `agg FILTER (WHERE value_column = value)` output column per pivot value
+    /// and aggregate. The output column is named after the value, suffixed with
+    /// `_<aggregate name>` when there is more than one aggregate.
+    pub(super) fn plan_pivot(
+        &self,
+        input: LogicalPlan,
+        aggregate_functions: Vec<ExprWithAlias>,
+        value_column: Vec<SQLExpr>,
+        value_source: PivotValueSource,
+        default_on_null: Option<SQLExpr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let schema = Arc::clone(input.schema());
+
+        let [value_column] = <[SQLExpr; 1]>::try_from(value_column).or_else(|_| {
+            not_impl_err!("PIVOT with multiple value columns is not supported yet")
+        })?;
+        let value_column = self.sql_to_expr(value_column, &schema, planner_context)?;
+
+        let values = match value_source {
+            PivotValueSource::List(values) => values,
+            PivotValueSource::Any(_) => {
+                return not_impl_err!("PIVOT with ANY values is not supported yet");
+            }
+            PivotValueSource::Subquery(_) => {
+                return not_impl_err!("PIVOT with a subquery is not supported yet");
+            }
+        };
+        let values = values
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let value =
+                    self.sql_to_expr(expr, &DFSchema::empty(), planner_context)?;
+                let name = match (alias, &value) {
+                    (Some(alias), _) => self.ident_normalizer.normalize(alias),
+                    (None, Expr::Literal(scalar, _)) => scalar.to_string(),
+                    (None, value) => value.schema_name().to_string(),
+                };
+                Ok((value, name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let aggregates = aggregate_functions
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let expr = self.sql_to_expr(expr, &schema, planner_context)?;
+                let name = match alias {
+                    Some(alias) => self.ident_normalizer.normalize(alias),
+                    None => expr.schema_name().to_string(),
+                };
+                // `COUNT(*)` is planned as an aliased `count(1)`
+                let Expr::AggregateFunction(aggregate) = expr.unalias() else {
+                    return plan_err!("PIVOT expects an aggregate function, got {name}");
+                };
+                Ok((aggregate, name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Every input column that is neither aggregated nor pivoted on is
+        // implicitly grouped by
+        let mut pivoted_columns = value_column.column_refs();
+        for (aggregate, _) in &aggregates {
+            pivoted_columns.extend(
+                aggregate
+                    .params
+                    .args
+                    .iter()
+                    .chain(aggregate.params.filter.as_deref())
+                    .chain(aggregate.params.order_by.iter().map(|sort| &sort.expr))
+                    .flat_map(|expr| expr.column_refs()),
+            );
+        }
+        let pivoted_columns = pivoted_columns
+            .into_iter()
+            .map(|column| schema.index_of_column(column))
+            .collect::<Result<HashSet<_>>>()?;
+        let group_exprs = schema
+            .columns()
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !pivoted_columns.contains(index))
+            .map(|(_, column)| Expr::Column(column))
+            .collect::<Vec<_>>();
+
+        let mut output_names = Vec::with_capacity(values.len() * aggregates.len());
+        let mut aggr_exprs = Vec::with_capacity(values.len() * aggregates.len());
+        for (value, value_name) in &values {
+            for (aggregate, aggregate_name) in &aggregates {
+                let output_name = if aggregates.len() == 1 {
+                    value_name.clone()
+                } else {
+                    format!("{value_name}_{aggregate_name}")
+                };
+                let predicate = value_column.clone().eq(value.clone());
+                let mut aggregate = aggregate.clone();
+                aggregate.params.filter =
+                    Some(Box::new(match aggregate.params.filter.take() {
+                        Some(filter) => filter.and(predicate),
+                        None => predicate,
+                    }));
+                aggr_exprs.push(Expr::AggregateFunction(aggregate).alias(&output_name));
+                output_names.push(output_name);
+            }
+        }
+
+        let builder =
+            LogicalPlanBuilder::from(input).aggregate(group_exprs.clone(), aggr_exprs)?;
+        let Some(default_on_null) = default_on_null else {
+            return builder.build();
+        };
+
+        let default_on_null =
+            self.sql_to_expr(default_on_null, &DFSchema::empty(), planner_context)?;
+        let projection =
+            group_exprs
+                .into_iter()
+                .map(Ok)
+                .chain(output_names.into_iter().map(|name| {
+                    let column = Expr::Column(Column::new_unqualified(&name));
+                    when(column.clone().is_null(), default_on_null.clone())
+                        .otherwise(column)
+                        .map(|expr| expr.alias(name))
+                }));
+        builder
+            .project(projection.collect::<Result<Vec<_>>>()?)?
+            .build()
+    }
+
+    /// Plans `input UNPIVOT [INCLUDE | EXCLUDE NULLS] (value FOR name IN (column [AS alias], ...))`
+    ///
+    /// The unpivot is rewritten to a `UNION ALL` with one branch per unpivoted
+    /// column, each projecting the remaining input columns, the column label
+    /// as `name` and the column itself as `value`. Rows with a `NULL` value are
+    /// removed unless `INCLUDE NULLS` is given.
+    pub(super) fn plan_unpivot(
+        &self,
+        input: &LogicalPlan,
+        value: SQLExpr,
+        name: Ident,
+        columns: Vec<ExprWithAlias>,
+        include_nulls: bool,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let SQLExpr::Identifier(value) = value else {
+            return not_impl_err!(
+                "UNPIVOT with multiple value columns is not supported yet"
+            );
+        };
+        let value_name = self.ident_normalizer.normalize(value);
+        let label_name = self.ident_normalizer.normalize(name);
+        let schema = input.schema();
+
+        let columns = columns
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let expr = self.sql_to_expr(expr, schema, planner_context)?;
+                let Expr::Column(column) = expr else {
+                    return plan_err!("UNPIVOT expects a column, got {expr}");
+                };
+                let index = schema.index_of_column(&column)?;
+                let label = match alias {
+                    Some(alias) => self.ident_normalizer.normalize(alias),
+                    None => column.name.clone(),
+                };
+                Ok((index, column, label))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let unpivoted = columns
+            .iter()
+            .map(|(index, _, _)| *index)
+            .collect::<HashSet<_>>();
+        let kept_columns = schema
+            .columns()
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !unpivoted.contains(index))
+            .map(|(_, column)| Expr::Column(column))
+            .collect::<Vec<_>>();
+
+        let mut builder: Option<LogicalPlanBuilder> = None;
+        for (_, column, label) in columns {
+            let projection = kept_columns.iter().cloned().chain([
+                lit(label).alias(&label_name),
+                Expr::Column(column).alias(&value_name),
+            ]);
+            let branch = LogicalPlanBuilder::from(input.clone())
+                .project(projection)?
+                .build()?;
+            builder = Some(match builder {
+                Some(builder) => builder.union(branch)?,
+                None => LogicalPlanBuilder::from(branch),
+            });
+        }
+        let Some(mut builder) = builder else {
+            return plan_err!("UNPIVOT requires at least one column");
+        };
+
+        if !include_nulls {
+            builder = builder.filter(
+                Expr::Column(Column::new_unqualified(value_name)).is_not_null(),
+            )?;
+        }
+        builder.build()
+    }
+}
@@ -0,0 +1,181 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of the `tumble` and `hop` windowing table functions
+
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+
+use arrow::datatypes::DataType;
+use arrow::temporal_conversions::NANOSECONDS_IN_DAY;
+use datafusion_common::{DFSchema, Result, ScalarValue, plan_err};
+use datafusion_expr::expr::ScalarFunction;
+use datafusion_expr::{Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder, lit};
+use sqlparser::ast::{Expr as SQLExpr, FunctionArg, FunctionArgExpr};
+
+/// Returns true if `name` is a windowing table function planned by
+/// [`SqlToRel::plan_time_window`]
+pub(crate) fn is_time_window_function(name: &str) -> bool {
+    name.eq_ignore_ascii_case("tumble") || name.eq_ignore_ascii_case("hop")
+}
+
+impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Plans the windowing table functions
+    ///
+    /// * `tumble(table, time_column, size)`: assigns each row of `table` to
+    ///   the window of length `size` that contains `time_column`
+    /// * `hop(table, time_column, slide, size)`: assigns each row of `table`
+    ///   to every window of length `size` starting at a multiple of `slide`
+    ///   that contains `time_column`
+    ///
+    /// Both return the columns of `table` followed by the `window_start` and
+    /// `window_end` of the window. Windows are aligned to the Unix epoch.
+    ///
+    /// Windows are computed with `date_bin`, so `window_start` keeps the
+    /// watermark of `time_column` if it has one, allowing aggregations
+    /// grouped by `window_start` to emit each window of an unbounded input
+    /// once it is complete. `hop` is planned as a `UNION ALL` with one branch
+    /// per window containing each row.
+    pub(super) fn plan_time_window(
+        &self,
+        name: &str,
+        args: Vec<FunctionArg>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let name = name.to_lowercase();
+        let name = name.as_str();
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                _ => plan_err!("Unsupported argument for {name}: {arg}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let usage = if name == "hop" {
+            "hop(table, time_column, slide, size)"
+        } else {
+            "tumble(table, time_column, size)"
+        };
+        let (table, time_column, slide, size) = match <[SQLExpr; 3]>::try_from(args) {
+            Ok([table, time_column, size]) if name == "tumble" => {
+                (table, time_column, None, size)
+            }
+            Err(args) if name == "hop" => match <[SQLExpr; 4]>::try_from(args) {
+                Ok([table, time_column, slide, size]) => {
+                    (table, time_column, Some(slide), size)
+                }
+                Err(_) => return plan_err!("Expected {usage}"),
+            },
+            _ => return plan_err!("Expected {usage}"),
+        };
+
+        let input = self.plan_table_argument(name, table, planner_context)?;
+        let schema = input.schema();
+        let time_column = self.sql_to_expr(time_column, schema, planner_context)?;
+        let Expr::Column(_) = &time_column else {
+            return plan_err!(
+                "{name} expects a column as time column, got {time_column}"
+            );
+        };
+        let data_type = time_column.get_type(schema)?;
+        let DataType::Timestamp(_, tz) = data_type else {
+            return plan_err!(
+                "{name} expects a timestamp time column, got {time_column} of type {data_type}"
+            );
+        };
+
+        let size = self.time_window_interval(name, size, planner_context)?;
+        let slide = match slide {
+            Some(slide) => self.time_window_interval(name, slide, planner_context)?,
+            None => size,
+        };
+        if size % slide != 0 {
+            return plan_err!("{name} size must be a multiple of its slide");
+        }
+
+        let Some(date_bin) = self.context_provider.get_function_meta("date_bin") else {
+            return plan_err!("{name} requires the date_bin function to be registered");
+        };
+        let size_interval = lit(ScalarValue::new_interval_mdn(0, 0, size));
+
+        // Each window containing a row starts at a different multiple of the
+        // slide modulo the size, so the `i`th branch bins the rows by the size
+        // with an origin of `i * slide`
+        let columns = schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        let mut builder: Option<LogicalPlanBuilder> = None;
+        for i in 0..size / slide {
+            let mut args = vec![size_interval.clone(), time_column.clone()];
+            if i > 0 {
+                args.push(lit(ScalarValue::TimestampNanosecond(
+                    Some(i * slide),
+                    tz.clone(),
+                )));
+            }
+            let window_start = Expr::ScalarFunction(ScalarFunction::new_udf(
+                Arc::clone(&date_bin),
+                args,
+            ));
+            let projection = columns.iter().cloned().chain([
+                window_start.clone().alias("window_start"),
+                (window_start + size_interval.clone()).alias("window_end"),
+            ]);
+            let branch = LogicalPlanBuilder::from(input.clone())
+                .project(projection)?
+                .build()?;
+            builder = Some(match builder {
+                Some(builder) => builder.union(branch)?,
+                None => LogicalPlanBuilder::from(branch),
+            });
+        }
+        match builder {
+            Some(builder) => builder.build(),
+            None => plan_err!("{name} requires a positive window size"),
+        }
+    }
+
+    /// Plans an interval argument of a windowing table function, returning
+    /// its length in nanoseconds
+    fn time_window_interval(
+        &self,
+        name: &str,
+        interval: SQLExpr,
+        planner_context: &mut PlannerContext,
+    ) -> Result<i64> {
+        let interval = self.sql_to_expr(interval, &DFSchema::empty(), planner_context)?;
+        let nanos = match &interval {
+            Expr::Literal(ScalarValue::IntervalMonthDayNano(Some(v)), _)
+                if v.months == 0 =>
+            {
+                (v.days as i64)
+                    .checked_mul(NANOSECONDS_IN_DAY)
+                    .and_then(|days| days.checked_add(v.nanoseconds))
+            }
+            _ => None,
+        };
+        match nanos {
+            Some(nanos) if nanos > 0 => Ok(nanos),
+            _ => plan_err!(
+                "{name} expects a positive interval without months, got {interval}"
+            ),
+        }
+    }
+}
@@ -23,6 +23,7 @@ use datafusion_common::{DataFusionError, Result};
 use crate::TableReference;
 use crate::parser::{CopyToSource, CopyToStatement, Statement as DFStatement};
 use crate::planner::object_name_to_table_reference;
+use crate::relation::{is_graph_function, is_time_window_function};
 use sqlparser::ast::*;
 
 // following constants are used in `resolve_table_references`
@@ -35,6 +36,7 @@ const DF_SETTINGS: &str = "df_settings";
 const SCHEMATA: &str = "schemata";
 const ROUTINES: &str = "routines";
 const PARAMETERS: &str = "parameters";
+const TABLE_PROPERTIES: &str = "table_properties";
 
 /// All information schema tables
 const INFORMATION_SCHEMA_TABLES: &[&str] = &[
@@ -45,6 +47,7 @@ const INFORMATION_SCHEMA_TABLES: &[&str] = &[
     SCHEMATA,
     ROUTINES,
     PARAMETERS,
+    TABLE_PROPERTIES,
 ];
 
 // Collect table/CTE references as `TableReference`s and normalize them during traversal.
@@ -83,6 +86,36 @@ impl Visitor for RelationVisitor {
         self.insert_relation(relation)
     }
 
+    fn pre_visit_table_factor(
+        &mut self,
+        table_factor: &TableFactor,
+    ) -> ControlFlow<Self::Break> {
+        // The first argument of the windowing and graph table functions is a
+        // table
+        if let TableFactor::Table {
+            name,
+            args: Some(args),
+            ..
+        } = table_factor
+            && let [ObjectNamePart::Identifier(function)] = name.0.as_slice()
+            && (is_time_window_function(&function.value)
+                || is_graph_function(&function.value))
+            && let Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(table))) =
+                args.args.first()
+        {
+            match table {
+                Expr::Identifier(ident) => {
+                    self.insert_relation(&ObjectName::from(vec![ident.clone()]))?
+                }
+                Expr::CompoundIdentifier(idents) => {
+                    self.insert_relation(&ObjectName::from(idents.clone()))?
+                }
+                _ => {}
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
     fn pre_visit_query(&mut self, q: &Query) -> ControlFlow<Self::Break> {
         if let Some(with) = &q.with {
             for cte in &with.cte_tables {
@@ -185,6 +218,18 @@ fn visit_statement(statement: &DFStatement, visitor: &mut RelationVisitor) -> Re
         DFStatement::Explain(explain) => {
             visit_statement(&explain.statement, visitor)?;
         }
+        DFStatement::RefreshSchema(refresh) => {
+            control_flow_to_result(visitor.insert_relation(&refresh.table_name))?;
+        }
+        // the relations of the SQL string are resolved when it is executed,
+        // but the expressions that build it and its parameters may contain
+        // subqueries
+        DFStatement::ExecuteImmediate(execute) => {
+            control_flow_to_result(execute.sql.visit(visitor))?;
+            for parameter in &execute.parameters {
+                control_flow_to_result(parameter.visit(visitor))?;
+            }
+        }
         DFStatement::Reset(_) => {}
     }
     Ok(())
@@ -356,4 +401,18 @@ mod tests {
         assert_eq!(ctes[0].to_string(), "FOObar");
         assert_eq!(table_refs.len(), 0);
     }
+
+    #[test]
+    fn resolve_table_references_time_window() {
+        use crate::parser::DFParser;
+
+        let query = "select * from tumble(events, ts, interval '1 minute') \
+            union all select * from hop(s.events2, ts, interval '1 minute', interval '2 minutes')";
+        let statement = DFParser::parse_sql(query).unwrap().pop_back().unwrap();
+        let (table_refs, ctes) = resolve_table_references(&statement, true).unwrap();
+        assert_eq!(ctes.len(), 0);
+        let table_refs = table_refs.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+        assert!(table_refs.contains(&"events".to_string()));
+        assert!(table_refs.contains(&"s.events2".to_string()));
+    }
 }
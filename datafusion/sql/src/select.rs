@@ -30,9 +30,11 @@ use crate::utils::{
 
 use arrow::datatypes::DataType;
 use datafusion_common::error::DataFusionErrorBuilder;
-use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
 use datafusion_common::{Column, DFSchema, DFSchemaRef, Result, not_impl_err, plan_err};
-use datafusion_common::{RecursionUnnestOption, UnnestOptions};
+use datafusion_common::{RecursionUnnestOption, UNNEST_ORDINALITY_COLUMN, UnnestOptions};
 use datafusion_expr::ExprSchemable;
 use datafusion_expr::builder::get_struct_unnested_columns;
 use datafusion_expr::expr::{PlannedReplaceSelectItem, WildcardOptions};
@@ -44,8 +46,8 @@ use datafusion_expr::utils::{
     expr_as_column_expr, expr_to_columns, find_aggregate_exprs, find_window_exprs,
 };
 use datafusion_expr::{
-    Aggregate, Expr, Filter, GroupingSet, LogicalPlan, LogicalPlanBuilder,
-    LogicalPlanBuilderOptions, Partitioning, SortExpr,
+    Aggregate, Expr, Filter, GroupingSet, JoinType, LogicalPlan, LogicalPlanBuilder,
+    LogicalPlanBuilderOptions, Partitioning, SortExpr, Subquery,
 };
 
 use indexmap::IndexMap;
@@ -76,7 +78,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     pub(super) fn select_to_plan(
         &self,
         mut select: Select,
-        query_order_by: Option<OrderBy>,
+        mut query_order_by: Option<OrderBy>,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         // Check for unsupported syntax first
@@ -98,8 +100,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // into subqueries planned during FROM clause handling.
         let set_expr_left_schema = planner_context.set_set_expr_left_schema(None);
 
+        let connect_by = self.prepare_connect_by(&mut select, &mut query_order_by)?;
+
         // Process `from` clause
         let plan = self.plan_from_tables(select.from, planner_context)?;
+        // Process `START WITH` and `CONNECT BY` clauses
+        let plan = match connect_by {
+            Some(connect_by) => {
+                self.plan_connect_by(plan, connect_by, planner_context)?
+            }
+            None => plan,
+        };
         let empty_from = matches!(plan, LogicalPlan::EmptyRelation(_));
 
         // Process `where` clause
@@ -201,12 +212,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 })
                 .collect::<Result<Vec<Expr>>>()?
         } else {
-            // 'group by all' groups wrt. all select expressions except 'AggregateFunction's.
-            // Filter and collect non-aggregate select expressions.
+            // 'group by all' groups wrt. all select expressions except 'AggregateFunction's
+            // and 'WindowFunction's, which are evaluated after the aggregation.
+            // Filter and collect the other select expressions.
             select_exprs
                 .iter()
                 .filter(|select_expr| {
                     find_aggregate_exprs(std::iter::once(*select_expr)).is_empty()
+                        && find_window_exprs(std::iter::once(*select_expr)).is_empty()
                 })
                 .cloned()
                 .collect()
@@ -267,7 +280,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             having_expr: having_expr_post_aggr,
             qualify_expr: qualify_expr_post_aggr,
             order_by_exprs: mut order_by_rex,
-        } = if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
+        } = if !group_by_exprs.is_empty()
+            || !aggr_exprs.is_empty()
+            // A HAVING clause without GROUP BY makes the query an aggregation
+            // with a single group, even over empty input, e.g.
+            // `SELECT 1 FROM t HAVING true` returns one row
+            || having_expr_opt.is_some()
+        {
+            if group_by_exprs.is_empty()
+                && aggr_exprs.is_empty()
+                && let Some(having_expr) = &having_expr_opt
+                && !having_expr.column_refs().is_empty()
+            {
+                // the columns of the input are not available in the single
+                // group, report this before any ungrouped SELECT expressions
+                return plan_err!(
+                    "HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"
+                );
+            }
             self.aggregate(
                 &base_plan,
                 &select_exprs,
@@ -278,19 +308,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 &aggr_exprs,
             )?
         } else {
-            match having_expr_opt {
-                Some(having_expr) => {
-                    return plan_err!(
-                        "HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"
-                    );
-                }
-                None => AggregatePlanResult {
-                    plan: base_plan.clone(),
-                    select_exprs: select_exprs.clone(),
-                    having_expr: having_expr_opt,
-                    qualify_expr: qualify_expr_opt,
-                    order_by_exprs: order_by_rex,
-                },
+            AggregatePlanResult {
+                plan: base_plan.clone(),
+                select_exprs: select_exprs.clone(),
+                having_expr: having_expr_opt,
+                qualify_expr: qualify_expr_opt,
+                order_by_exprs: order_by_rex,
             }
         };
 
@@ -378,7 +401,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         };
 
         // Try processing unnest expression or do the final projection
-        let plan = self.try_process_unnest(plan, select_exprs_post_aggr)?;
+        let plan = self.try_process_unnest(plan, select_exprs_post_aggr, false)?;
 
         // Process distinct clause
         let plan = match select.distinct {
@@ -436,10 +459,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 
     /// Try converting Expr(Unnest(Expr)) to Projection/Unnest/Projection
+    ///
+    /// If `with_ordinality` is true, the position of each unnested row is
+    /// appended to the output as an [`UNNEST_ORDINALITY_COLUMN`] column
     pub(super) fn try_process_unnest(
         &self,
         input: LogicalPlan,
         select_exprs: Vec<Expr>,
+        with_ordinality: bool,
     ) -> Result<LogicalPlan> {
         // Try process group by unnest
         let input = self.try_process_aggregate_unnest(input)?;
@@ -491,6 +518,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
                 outer_projection_exprs.extend(rewritten_exprs);
             }
+            if with_ordinality && i == 0 {
+                outer_projection_exprs
+                    .push(Expr::Column(Column::from_name(UNNEST_ORDINALITY_COLUMN)));
+            }
 
             // No more unnest is possible
             if unnest_columns.is_empty() {
@@ -503,7 +534,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 break;
             } else {
                 // Set preserve_nulls to false to ensure compatibility with DuckDB and PostgreSQL
-                let mut unnest_options = UnnestOptions::new().with_preserve_nulls(false);
+                let mut unnest_options = UnnestOptions::new()
+                    .with_preserve_nulls(false)
+                    .with_ordinality(with_ordinality && i == 0);
                 let mut unnest_col_vec = vec![];
 
                 for (col, maybe_list_unnest) in unnest_columns.into_iter() {
@@ -554,8 +587,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         };
 
         let field = unnest_expr.expr.to_field(input.schema())?.1;
-        let DataType::Struct(inner_fields) = field.data_type() else {
-            return Ok(None);
+        let inner_fields = match field.data_type() {
+            DataType::Struct(inner_fields) => inner_fields,
+            DataType::Map(entries, _) => match entries.data_type() {
+                DataType::Struct(inner_fields) => inner_fields,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
         };
 
         Ok(Some(get_struct_unnested_columns(
@@ -770,9 +808,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     planner_context.set_outer_from_schema(left_schema)
                 };
                 for input in from {
-                    // Join `input` with the current result (`left`).
-                    let right = self.plan_table_with_joins(input, planner_context)?;
-                    left = left.cross_join(right)?;
+                    if input.joins.is_empty()
+                        && let Some(plan) = self.plan_lateral_table_function(
+                            left.plan(),
+                            &input.relation,
+                            JoinType::Inner,
+                            planner_context,
+                        )?
+                    {
+                        left = LogicalPlanBuilder::from(plan);
+                    } else {
+                        // Join `input` with the current result (`left`).
+                        let right = self.plan_table_with_joins(input, planner_context)?;
+                        left = left.cross_join(right)?;
+                    }
                     // Update the outer FROM schema.
                     let left_schema = Some(Arc::clone(left.schema()));
                     planner_context.set_outer_from_schema(left_schema);
@@ -1015,16 +1064,21 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         aggr_exprs: &[Expr],
     ) -> Result<AggregatePlanResult> {
         // create the aggregate plan
-        let options =
-            LogicalPlanBuilderOptions::new().with_add_implicit_group_by_exprs(true);
-        let plan = LogicalPlanBuilder::from(input.clone())
-            .with_options(options)
-            .aggregate(group_by_exprs.to_vec(), aggr_exprs.to_vec())?
-            .build()?;
-        let group_by_exprs = if let LogicalPlan::Aggregate(agg) = &plan {
-            &agg.group_expr
+        let plan = if group_by_exprs.is_empty() && aggr_exprs.is_empty() {
+            // a single group without any aggregates, as made by a HAVING
+            // clause alone, has no columns and is a single row
+            LogicalPlanBuilder::empty(true).build()?
         } else {
-            unreachable!();
+            let options =
+                LogicalPlanBuilderOptions::new().with_add_implicit_group_by_exprs(true);
+            LogicalPlanBuilder::from(input.clone())
+                .with_options(options)
+                .aggregate(group_by_exprs.to_vec(), aggr_exprs.to_vec())?
+                .build()?
+        };
+        let group_by_exprs = match &plan {
+            LogicalPlan::Aggregate(agg) => agg.group_expr.as_slice(),
+            _ => group_by_exprs,
         };
 
         // in this next section of code we are re-writing the projection to refer to columns
@@ -1087,6 +1141,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let having_expr_post_aggr = if let Some(having_expr) = having_expr_opt {
             let having_expr_post_aggr =
                 rebase_expr(having_expr, &aggr_projection_exprs, input)?;
+            let having_expr_post_aggr = correlate_subqueries_to_aggregate(
+                having_expr_post_aggr,
+                select_exprs,
+                &aggr_projection_exprs,
+                input,
+                &plan,
+            )?;
 
             check_columns_satisfy_exprs(
                 &column_exprs_post_aggr,
@@ -1269,3 +1330,102 @@ fn has_unnest_expr_recursively(expr: &Expr) -> bool {
     });
     has_unnest
 }
+
+/// Rewrites the outer references of the subqueries of the HAVING expression
+/// `having_expr` to the output of the aggregation `aggregate`, so that the
+/// subqueries are correlated to its groups:
+///
+/// * references to grouped columns are kept, as the aggregation outputs them
+/// * references to aliases of the select list are replaced by references to
+///   the aliased aggregate or grouping expressions, e.g. `cnt` in
+///   `SELECT count(*) AS cnt ... HAVING EXISTS (SELECT ... WHERE u.x = cnt)`
+/// * references to other columns of `input` are an error, as they are not
+///   defined for a group
+fn correlate_subqueries_to_aggregate(
+    having_expr: Expr,
+    select_exprs: &[Expr],
+    aggr_projection_exprs: &[Expr],
+    input: &LogicalPlan,
+    aggregate: &LogicalPlan,
+) -> Result<Expr> {
+    let correlate = |expr: Expr| -> Result<Transformed<Expr>> {
+        let Expr::OuterReferenceColumn(_, column) = &expr else {
+            return Ok(Transformed::no(expr));
+        };
+        if aggregate.schema().has_column(column) {
+            return Ok(Transformed::no(expr));
+        }
+        let aliased = select_exprs
+            .iter()
+            .find_map(|select_expr| match select_expr {
+                Expr::Alias(alias)
+                    if column.relation.is_none() && alias.name == column.name =>
+                {
+                    // the aliased expression may carry the alias of its
+                    // display name, e.g. `count(Int64(1)) AS count(*)`
+                    Some(alias.expr.as_ref().clone().unalias_nested().data)
+                }
+                _ => None,
+            });
+        if let Some(aliased) = aliased
+            && let Expr::Column(output_column) =
+                rebase_expr(&aliased, aggr_projection_exprs, input)?
+        {
+            let (_, field) = aggregate
+                .schema()
+                .qualified_field_from_column(&output_column)?;
+            return Ok(Transformed::yes(Expr::OuterReferenceColumn(
+                Arc::clone(field),
+                output_column,
+            )));
+        }
+        if input.schema().has_column(column) {
+            return plan_err!(
+                "Correlated column {column} in a HAVING subquery must appear in the GROUP BY clause or be used in an aggregate function"
+            );
+        }
+        // a reference to a query further out
+        Ok(Transformed::no(expr))
+    };
+    let correlate_subquery = |subquery: Subquery| -> Result<Subquery> {
+        let plan = Arc::unwrap_or_clone(subquery.subquery)
+            .transform_up_with_subqueries(|plan| {
+                plan.map_expressions(|expr| expr.transform(correlate))
+            })?
+            .data;
+        let outer_ref_columns = subquery
+            .outer_ref_columns
+            .into_iter()
+            .map(|expr| expr.transform(correlate).data())
+            .collect::<Result<_>>()?;
+        Ok(Subquery {
+            subquery: Arc::new(plan),
+            outer_ref_columns,
+            spans: subquery.spans,
+        })
+    };
+
+    having_expr
+        .transform_down(|expr| {
+            Ok(match expr {
+                Expr::Exists(mut exists) => {
+                    exists.subquery = correlate_subquery(exists.subquery)?;
+                    Transformed::yes(Expr::Exists(exists))
+                }
+                Expr::InSubquery(mut in_subquery) => {
+                    in_subquery.subquery = correlate_subquery(in_subquery.subquery)?;
+                    Transformed::yes(Expr::InSubquery(in_subquery))
+                }
+                Expr::SetComparison(mut set_comparison) => {
+                    set_comparison.subquery =
+                        correlate_subquery(set_comparison.subquery)?;
+                    Transformed::yes(Expr::SetComparison(set_comparison))
+                }
+                Expr::ScalarSubquery(subquery) => {
+                    Transformed::yes(Expr::ScalarSubquery(correlate_subquery(subquery)?))
+                }
+                expr => Transformed::no(expr),
+            })
+        })
+        .data()
+}
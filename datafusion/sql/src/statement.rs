@@ -21,38 +21,48 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::parser::{
-    CopyToSource, CopyToStatement, CreateExternalTable, DFParser, ExplainStatement,
-    LexOrdering, ResetStatement, Statement as DFStatement,
+    CopyToSource, CopyToStatement, CreateExternalTable, DFParser,
+    ExecuteImmediateStatement, ExplainStatement, LexOrdering, RefreshSchemaStatement,
+    ResetStatement, Statement as DFStatement,
 };
 use crate::planner::{
     ContextProvider, PlannerContext, SqlToRel, object_name_to_qualifier,
 };
 use crate::utils::normalize_ident;
 
-use arrow::datatypes::{Field, FieldRef, Fields};
+use arrow::compute::kernels::cast_utils::parse_interval_month_day_nano;
+use arrow::datatypes::{DataType, Field, FieldRef, Fields, Schema};
+use arrow::temporal_conversions::NANOSECONDS_IN_DAY;
 use datafusion_common::error::_plan_err;
+use datafusion_common::format::ExplainAnalyzeFormat;
 use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::watermark::Watermark;
 use datafusion_common::{
     Column, Constraint, Constraints, DFSchema, DFSchemaRef, DataFusionError, Result,
     ScalarValue, SchemaError, SchemaReference, TableReference, ToDFSchema, exec_err,
     internal_err, not_impl_err, plan_datafusion_err, plan_err, schema_err,
     unqualified_field_not_found,
 };
-use datafusion_expr::dml::{CopyTo, InsertOp};
+use datafusion_expr::dml::{
+    ANALYZE_ROW_COUNT_COLUMN, CopyTo, InsertOp, MERGE_ACTION_COLUMN,
+    MERGE_OLD_VALUE_PREFIX, analyze_statistic_column,
+};
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::logical_plan::builder::project;
-use datafusion_expr::utils::expr_to_columns;
+use datafusion_expr::utils::{COUNT_STAR_EXPANSION, expr_to_columns};
 use datafusion_expr::{
-    Analyze, CreateCatalog, CreateCatalogSchema,
-    CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
-    CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, Deallocate,
-    DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView,
-    EmptyRelation, Execute, Explain, ExplainFormat, Expr, ExprSchemable, Filter,
-    LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare,
+    AlterTable as PlanAlterTable, AlterTableOperation, Analyze, CreateCatalog,
+    CreateCatalogSchema, CreateExternalTable as PlanCreateExternalTable, CreateFunction,
+    CreateFunctionBody, CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView,
+    Deallocate, DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable,
+    DropView, EmptyRelation, Estimate, Execute, ExecuteImmediate, Explain, ExplainFormat,
+    Expr, ExprFunctionExt, ExprSchemable, Filter, JoinType, LogicalPlan,
+    LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare, RefreshTableSchema,
     ResetVariable, SetVariable, SortExpr, Statement as PlanStatement, ToStringifiedPlan,
     TransactionAccessMode, TransactionConclusion, TransactionEnd,
     TransactionIsolationLevel, TransactionStart, Volatility, WriteOp, cast, col,
+    expr::Case, lit,
 };
 use sqlparser::ast::{
     self, BeginTransactionKind, CheckConstraint, ForeignKeyConstraint, IndexColumn,
@@ -227,13 +237,34 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             DFStatement::CreateExternalTable(s) => self.external_table_to_plan(s),
             DFStatement::Statement(s) => self.sql_statement_to_plan(*s),
             DFStatement::CopyTo(s) => self.copy_to_plan(s),
+            DFStatement::Explain(ExplainStatement {
+                estimate: true,
+                verbose,
+                analyze,
+                format,
+                statement,
+            }) => {
+                if analyze || verbose || format.is_some() {
+                    return plan_err!(
+                        "EXPLAIN ESTIMATE does not support ANALYZE, VERBOSE or FORMAT"
+                    );
+                }
+                self.estimate_to_plan(*statement)
+            }
             DFStatement::Explain(ExplainStatement {
                 verbose,
                 analyze,
                 format,
                 statement,
+                ..
             }) => self.explain_to_plan(verbose, analyze, format, *statement),
             DFStatement::Reset(statement) => self.reset_statement_to_plan(statement),
+            DFStatement::RefreshSchema(statement) => {
+                self.refresh_schema_to_plan(statement)
+            }
+            DFStatement::ExecuteImmediate(statement) => {
+                self.execute_immediate_to_plan(statement)
+            }
         }
     }
 
@@ -803,16 +834,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
                 if fields.is_empty() {
                     let map_types = plan.get_parameter_fields()?;
-                    let param_types: Vec<_> = (1..=map_types.len())
-                        .filter_map(|i| {
+                    // The types are only recorded if all of them could be inferred,
+                    // otherwise they are taken from the values passed to EXECUTE
+                    let param_types: Option<Vec<_>> = (1..=map_types.len())
+                        .map(|i| {
                             let key = format!("${i}");
                             map_types.get(&key).and_then(|opt| opt.clone())
                         })
                         .collect();
-                    fields.extend(param_types.iter().cloned());
-                    planner_context.with_prepare_param_data_types(
-                        param_types.into_iter().map(Some).collect(),
-                    );
+                    if let Some(param_types) = param_types {
+                        fields.extend(param_types.iter().cloned());
+                        planner_context.with_prepare_param_data_types(
+                            param_types.into_iter().map(Some).collect(),
+                        );
+                    }
                 }
 
                 Ok(LogicalPlan::Statement(PlanStatement::Prepare(Prepare {
@@ -839,6 +874,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         "Execute statement with USING is not supported"
                     );
                 }
+                // `EXECUTE IMMEDIATE` is parsed by `DFParser`
                 if immediate {
                     return not_impl_err!(
                         "Execute statement with IMMEDIATE is not supported"
@@ -1144,6 +1180,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 self.delete_to_plan(&table_name, selection, limit)
             }
 
+            Statement::Merge(merge) => self.merge_to_plan(merge),
+
+            Statement::Analyze(analyze) => self.analyze_to_plan(analyze),
+
             Statement::StartTransaction {
                 modes,
                 begin: false,
@@ -1520,12 +1560,90 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     },
                 )))
             }
+            Statement::AlterTable(ast::AlterTable {
+                name,
+                if_exists,
+                only: _,
+                operations,
+                location,
+                on_cluster,
+                table_type,
+                end_token: _,
+            }) => {
+                if location.is_some() || on_cluster.is_some() || table_type.is_some() {
+                    return not_impl_err!(
+                        "Only ALTER TABLE ADD, DROP and RENAME COLUMN are supported"
+                    );
+                }
+                let operations = operations
+                    .into_iter()
+                    .map(|operation| self.alter_table_operation_to_plan(operation))
+                    .collect::<Result<_>>()?;
+                Ok(LogicalPlan::Ddl(DdlStatement::AlterTable(PlanAlterTable {
+                    name: self.object_name_to_table_reference(name)?,
+                    if_exists,
+                    operations,
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                })))
+            }
             stmt => {
                 not_impl_err!("Unsupported SQL statement: {stmt}")
             }
         }
     }
 
+    fn alter_table_operation_to_plan(
+        &self,
+        operation: ast::AlterTableOperation,
+    ) -> Result<AlterTableOperation> {
+        match operation {
+            ast::AlterTableOperation::AddColumn {
+                column_keyword: _,
+                if_not_exists,
+                column_def,
+                column_position: None,
+            } => {
+                if let Some(option) = column_def.options.iter().find(|option| {
+                    !matches!(
+                        option.option,
+                        ast::ColumnOption::Null | ast::ColumnOption::NotNull
+                    )
+                }) {
+                    return not_impl_err!(
+                        "Unsupported option for a column added by ALTER TABLE: {option}"
+                    );
+                }
+                let schema = self.build_schema(vec![column_def])?;
+                Ok(AlterTableOperation::AddColumn {
+                    field: Arc::clone(&schema.fields()[0]),
+                    if_not_exists,
+                })
+            }
+            ast::AlterTableOperation::DropColumn {
+                has_column_keyword: _,
+                column_names,
+                if_exists,
+                drop_behavior: None,
+            } => Ok(AlterTableOperation::DropColumn {
+                names: column_names
+                    .into_iter()
+                    .map(|name| self.ident_normalizer.normalize(name))
+                    .collect(),
+                if_exists,
+            }),
+            ast::AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => Ok(AlterTableOperation::RenameColumn {
+                old_name: self.ident_normalizer.normalize(old_column_name),
+                new_name: self.ident_normalizer.normalize(new_column_name),
+            }),
+            operation => {
+                not_impl_err!("Unsupported ALTER TABLE operation: {operation}")
+            }
+        }
+    }
+
     fn get_delete_target(&self, from: FromTable) -> Result<ObjectName> {
         let mut from = match from {
             FromTable::WithFromKeyword(v) => v,
@@ -1641,14 +1759,43 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         };
 
-        let partition_by = statement
-            .partitioned_by
-            .iter()
-            .map(|col| input_schema.field_with_name(table_ref.as_ref(), col))
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .map(|f| f.name().to_owned())
-            .collect();
+        // Partition columns are written as they are, while partition
+        // expressions are computed by a projection of the input, named by their
+        // aliases
+        let mut partition_by = vec![];
+        let mut partition_exprs = vec![];
+        for ast::ExprWithAlias { expr, alias } in statement.partitioned_by {
+            match (expr, alias) {
+                (SQLExpr::Identifier(ident), None) => {
+                    let field = input_schema
+                        .field_with_name(table_ref.as_ref(), &ident.to_string())?;
+                    partition_by.push(field.name().to_owned());
+                }
+                (expr, alias) => {
+                    let name = match alias {
+                        Some(alias) => self.ident_normalizer.normalize(alias),
+                        None => expr.to_string(),
+                    };
+                    let expr = self.sql_to_expr(
+                        expr,
+                        &input_schema,
+                        &mut PlannerContext::new(),
+                    )?;
+                    partition_exprs.push(expr.alias(&name));
+                    partition_by.push(name);
+                }
+            }
+        }
+        let input = if partition_exprs.is_empty() {
+            input
+        } else {
+            let exprs = input_schema
+                .columns()
+                .into_iter()
+                .map(Expr::Column)
+                .chain(partition_exprs);
+            LogicalPlanBuilder::from(input).project(exprs)?.build()?
+        };
 
         Ok(LogicalPlan::Copy(CopyTo::new(
             Arc::new(input),
@@ -1741,7 +1888,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let inline_constraints = calc_inline_constraints_from_columns(&columns);
         all_constraints.extend(inline_constraints);
 
-        let options_map = self.parse_options_map(options, false)?;
+        let mut options_map = self.parse_options_map(options, false)?;
 
         let compression = options_map
             .get("format.compression")
@@ -1765,6 +1912,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             .collect();
 
         let schema = self.build_schema(columns)?;
+        let schema = with_watermark_options(schema, &mut options_map)?;
         let df_schema = schema.to_dfschema_ref()?;
         df_schema.check_names()?;
 
@@ -1941,11 +2089,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
 
         if analyze {
-            if format.is_some() {
-                return plan_err!("EXPLAIN ANALYZE with FORMAT is not supported");
-            }
+            let format = match format {
+                Some(format) => ExplainAnalyzeFormat::from_str(&format)?,
+                None => ExplainAnalyzeFormat::Indent,
+            };
             Ok(LogicalPlan::Analyze(Analyze {
                 verbose,
+                format,
                 input: plan,
                 schema,
             }))
@@ -1975,6 +2125,23 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    /// Plans `EXPLAIN ESTIMATE`, which returns the estimated size and cost of
+    /// the statement from its statistics instead of executing it
+    fn estimate_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
+        let plan = self.statement_to_plan(statement)?;
+        if matches!(
+            plan,
+            LogicalPlan::Explain(_)
+                | LogicalPlan::Analyze(_)
+                | LogicalPlan::Statement(PlanStatement::Estimate(_))
+        ) {
+            return plan_err!("Nested EXPLAINs are not supported");
+        }
+        Ok(LogicalPlan::Statement(PlanStatement::Estimate(Estimate {
+            input: Arc::new(plan),
+        })))
+    }
+
     fn show_variable_to_plan(&self, variable: &[Ident]) -> Result<LogicalPlan> {
         if !self.has_table("information_schema", "df_settings") {
             return plan_err!(
@@ -2106,6 +2273,36 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    fn refresh_schema_to_plan(
+        &self,
+        statement: RefreshSchemaStatement,
+    ) -> Result<LogicalPlan> {
+        let name = self.object_name_to_table_reference(statement.table_name)?;
+        Ok(LogicalPlan::Ddl(DdlStatement::RefreshTableSchema(
+            RefreshTableSchema {
+                name,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            },
+        )))
+    }
+
+    fn execute_immediate_to_plan(
+        &self,
+        statement: ExecuteImmediateStatement,
+    ) -> Result<LogicalPlan> {
+        let ExecuteImmediateStatement { sql, parameters } = statement;
+        let empty_schema = DFSchema::empty();
+        let mut planner_context = PlannerContext::new();
+        let sql = self.sql_to_expr(sql, &empty_schema, &mut planner_context)?;
+        let parameters = parameters
+            .into_iter()
+            .map(|expr| self.sql_to_expr(expr, &empty_schema, &mut planner_context))
+            .collect::<Result<Vec<Expr>>>()?;
+        Ok(LogicalPlan::Statement(PlanStatement::ExecuteImmediate(
+            ExecuteImmediate { sql, parameters },
+        )))
+    }
+
     fn delete_to_plan(
         &self,
         table_name: &ObjectName,
@@ -2273,6 +2470,349 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(plan)
     }
 
+    /// Plan `MERGE INTO` as the stream of changes to the target table, see
+    /// [`WriteOp::Merge`].
+    ///
+    /// The source is joined with the target and, for each joined row, the
+    /// first clause whose condition holds determines the action and the new
+    /// values of the target columns.
+    fn merge_to_plan(&self, merge: ast::Merge) -> Result<LogicalPlan> {
+        let ast::Merge {
+            table,
+            source,
+            on,
+            clauses,
+            output,
+            optimizer_hint,
+            merge_token: _,
+            into: _,
+        } = merge;
+        if output.is_some() {
+            plan_err!("MERGE with OUTPUT clause not supported")?;
+        }
+        if optimizer_hint.is_some() {
+            plan_err!("Optimizer hints not supported")?;
+        }
+
+        let table_name = match &table {
+            TableFactor::Table { name, .. } => name.clone(),
+            _ => plan_err!("Cannot merge into non-table relation!")?,
+        };
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+
+        let mut planner_context = PlannerContext::new();
+        let target = self.plan_from_tables(
+            vec![TableWithJoins {
+                relation: table,
+                joins: vec![],
+            }],
+            &mut planner_context,
+        )?;
+        let source = self.plan_from_tables(
+            vec![TableWithJoins {
+                relation: source,
+                joins: vec![],
+            }],
+            &mut planner_context,
+        )?;
+        let target_columns = target.schema().columns();
+
+        // Mark the rows of both sides to tell whether a joined row matched
+        let source_row = "__merge_source_row";
+        let target_row = "__merge_target_row";
+        let mark_rows = |plan: LogicalPlan, marker: &str| {
+            let exprs = plan.schema().columns().into_iter().map(Expr::Column);
+            LogicalPlanBuilder::from(plan)
+                .project(exprs.chain([lit(true).alias(marker)]))?
+                .build()
+        };
+        let source = mark_rows(source, source_row)?;
+        let target = mark_rows(target, target_row)?;
+
+        let join_schema = source.schema().join(target.schema())?;
+        let on = self.sql_to_expr(*on, &join_schema, &mut planner_context)?;
+        let join_type = if clauses
+            .iter()
+            .any(|clause| clause.clause_kind == ast::MergeClauseKind::NotMatchedBySource)
+        {
+            JoinType::Full
+        } else {
+            JoinType::Left
+        };
+        let joined = LogicalPlanBuilder::from(source)
+            .join_on(target, join_type, [on])?
+            .build()?;
+        let schema = Arc::clone(joined.schema());
+
+        let source_row = col(source_row);
+        let target_row = col(target_row);
+        let table_schema = DFSchema::try_from_qualified_schema(
+            table_name.clone(),
+            &table_source.schema(),
+        )?;
+        let mut actions = vec![];
+        let mut values = vec![vec![]; table_schema.fields().len()];
+        for clause in clauses {
+            let mut condition = match clause.clause_kind {
+                ast::MergeClauseKind::Matched => source_row
+                    .clone()
+                    .is_not_null()
+                    .and(target_row.clone().is_not_null()),
+                ast::MergeClauseKind::NotMatched
+                | ast::MergeClauseKind::NotMatchedByTarget => {
+                    target_row.clone().is_null()
+                }
+                ast::MergeClauseKind::NotMatchedBySource => source_row.clone().is_null(),
+            };
+            if let Some(predicate) = clause.predicate {
+                condition = condition.and(self.sql_to_expr(
+                    predicate,
+                    &schema,
+                    &mut planner_context,
+                )?);
+            }
+
+            let (action, clause_values) = match clause.action {
+                ast::MergeAction::Insert(insert) => {
+                    if insert.insert_predicate.is_some() {
+                        not_impl_err!("MERGE INSERT with WHERE clause not supported")?;
+                    }
+                    let ast::MergeInsertKind::Values(ast::Values { mut rows, .. }) =
+                        insert.kind
+                    else {
+                        return not_impl_err!("MERGE INSERT ROW not supported");
+                    };
+                    let (Some(row), true) = (rows.pop(), rows.is_empty()) else {
+                        return plan_err!("MERGE INSERT must have exactly one row");
+                    };
+                    let columns = if insert.columns.is_empty() {
+                        table_schema
+                            .fields()
+                            .iter()
+                            .map(|field| field.name().clone())
+                            .collect()
+                    } else {
+                        insert
+                            .columns
+                            .iter()
+                            .map(|column| {
+                                let column = object_name_to_string(column);
+                                table_schema.field_with_unqualified_name(&column)?;
+                                Ok(column)
+                            })
+                            .collect::<Result<Vec<_>>>()?
+                    };
+                    if columns.len() != row.len() {
+                        plan_err!("Column count doesn't match MERGE INSERT values!")?;
+                    }
+                    let mut inserted: HashMap<String, SQLExpr> =
+                        columns.into_iter().zip(row).collect();
+                    let clause_values = table_schema
+                        .fields()
+                        .iter()
+                        .map(|field| match inserted.remove(field.name()) {
+                            Some(value) => self
+                                .sql_to_expr(value, &schema, &mut planner_context)?
+                                .cast_to(field.data_type(), &schema),
+                            None => table_source
+                                .get_column_default(field.name())
+                                .cloned()
+                                .unwrap_or_else(|| Expr::Literal(ScalarValue::Null, None))
+                                .cast_to(field.data_type(), &DFSchema::empty()),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    ("insert", clause_values)
+                }
+                ast::MergeAction::Update(update) => {
+                    if update.update_predicate.is_some()
+                        || update.delete_predicate.is_some()
+                    {
+                        not_impl_err!("MERGE UPDATE with WHERE clause not supported")?;
+                    }
+                    let mut assignments = HashMap::new();
+                    for assignment in update.assignments {
+                        let AssignmentTarget::ColumnName(names) = &assignment.target
+                        else {
+                            return plan_err!("Tuples are not supported");
+                        };
+                        let name = names
+                            .0
+                            .last()
+                            .and_then(|name| name.as_ident())
+                            .ok_or_else(|| plan_datafusion_err!("Empty column id"))?;
+                        let name = self.ident_normalizer.normalize(name.clone());
+                        table_schema.field_with_unqualified_name(&name)?;
+                        assignments.insert(name, assignment.value);
+                    }
+                    let clause_values = table_schema
+                        .fields()
+                        .iter()
+                        .zip(&target_columns)
+                        .map(|(field, column)| match assignments.remove(field.name()) {
+                            Some(value) => self
+                                .sql_to_expr(value, &schema, &mut planner_context)?
+                                .cast_to(field.data_type(), &schema),
+                            None => Ok(Expr::Column(column.clone())),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    ("update", clause_values)
+                }
+                ast::MergeAction::Delete { .. } => {
+                    let clause_values = target_columns
+                        .iter()
+                        .map(|column| Expr::Column(column.clone()))
+                        .collect();
+                    ("delete", clause_values)
+                }
+            };
+            actions.push((condition.clone(), lit(action)));
+            for (values, value) in values.iter_mut().zip(clause_values) {
+                values.push((condition.clone(), value));
+            }
+        }
+
+        let case = |when_then: Vec<(Expr, Expr)>| {
+            Expr::Case(Case::new(
+                None,
+                when_then
+                    .into_iter()
+                    .map(|(when, then)| (Box::new(when), Box::new(then)))
+                    .collect(),
+                None,
+            ))
+        };
+        let exprs = [case(actions).alias(MERGE_ACTION_COLUMN)]
+            .into_iter()
+            .chain(
+                table_schema
+                    .fields()
+                    .iter()
+                    .zip(values)
+                    .map(|(field, values)| case(values).alias(field.name())),
+            )
+            .chain(table_schema.fields().iter().zip(target_columns).map(
+                |(field, column)| {
+                    Expr::Column(column)
+                        .alias(format!("{MERGE_OLD_VALUE_PREFIX}{}", field.name()))
+                },
+            ));
+        let changes = LogicalPlanBuilder::from(joined)
+            .project(exprs)?
+            .filter(col(MERGE_ACTION_COLUMN).is_not_null())?
+            .build()?;
+
+        Ok(LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            table_source,
+            WriteOp::Merge,
+            Arc::new(changes),
+        )))
+    }
+
+    /// Plan `ANALYZE TABLE` as an aggregate collecting the statistics of the
+    /// table, see [`WriteOp::Analyze`].
+    ///
+    /// Distinct counts are only collected if the `approx_distinct` aggregate
+    /// function is registered.
+    fn analyze_to_plan(&self, analyze: ast::Analyze) -> Result<LogicalPlan> {
+        let ast::Analyze {
+            table_name,
+            partitions,
+            columns,
+            cache_metadata,
+            noscan,
+            for_columns: _,
+            compute_statistics: _,
+            has_table_keyword: _,
+        } = analyze;
+        let Some(table_name) = table_name else {
+            return not_impl_err!("ANALYZE without a table is not supported");
+        };
+        if partitions.is_some() {
+            return not_impl_err!("ANALYZE TABLE with PARTITION is not supported");
+        }
+        if cache_metadata {
+            return not_impl_err!("ANALYZE TABLE with CACHE METADATA is not supported");
+        }
+        if noscan {
+            return not_impl_err!("ANALYZE TABLE with NOSCAN is not supported");
+        }
+
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        let scan = LogicalPlanBuilder::scan(
+            table_name.clone(),
+            Arc::clone(&table_source),
+            None,
+        )?
+        .build()?;
+        let schema = Arc::clone(scan.schema());
+        let mut fields = vec![];
+        if columns.is_empty() {
+            fields.extend(schema.fields().iter().map(Arc::clone));
+        } else {
+            for ident in columns {
+                let name = self.ident_normalizer.normalize(ident);
+                let field = schema.field_with_unqualified_name(&name)?;
+                if !fields.iter().any(|f| f.name() == field.name()) {
+                    fields.push(Arc::clone(field));
+                }
+            }
+        }
+
+        let aggregate = |name: &str| {
+            self.context_provider
+                .get_aggregate_meta(name)
+                .ok_or_else(|| {
+                    plan_datafusion_err!(
+                        "ANALYZE TABLE requires the {name} aggregate function"
+                    )
+                })
+        };
+        let count = aggregate("count")?;
+        let min = aggregate("min")?;
+        let max = aggregate("max")?;
+        let approx_distinct = self.context_provider.get_aggregate_meta("approx_distinct");
+        let count_all = || count.call(vec![lit(COUNT_STAR_EXPANSION)]);
+
+        let mut aggr_exprs = vec![count_all().alias(ANALYZE_ROW_COUNT_COLUMN)];
+        for field in fields {
+            let column =
+                Expr::Column(Column::new(Some(table_name.clone()), field.name()));
+            let alias = |statistic| analyze_statistic_column(statistic, field.name());
+            if !field.data_type().is_nested() {
+                aggr_exprs.push(min.call(vec![column.clone()]).alias(alias("min")));
+                aggr_exprs.push(max.call(vec![column.clone()]).alias(alias("max")));
+            }
+            aggr_exprs.push(
+                count_all()
+                    .filter(column.clone().is_null())
+                    .build()?
+                    .alias(alias("null_count")),
+            );
+            if let Some(approx_distinct) = &approx_distinct
+                && supports_approx_distinct(field.data_type())
+            {
+                aggr_exprs.push(
+                    approx_distinct
+                        .call(vec![column])
+                        .alias(alias("distinct_count")),
+                );
+            }
+        }
+        let input = LogicalPlanBuilder::from(scan)
+            .aggregate(Vec::<Expr>::new(), aggr_exprs)?
+            .build()?;
+
+        Ok(LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            table_source,
+            WriteOp::Analyze,
+            Arc::new(input),
+        )))
+    }
+
     fn insert_to_plan(
         &self,
         table_name: ObjectName,
@@ -2587,3 +3127,75 @@ ON p.function_name = r.routine_name
         }
     }
 }
+
+/// Declares the event time columns of an external table from its
+/// `watermark.<column>` options, whose values are the allowed lateness of the
+/// column, e.g. `'watermark.ts' '10 seconds'`. The options are removed from
+/// `options`.
+fn with_watermark_options(
+    schema: Schema,
+    options: &mut HashMap<String, String>,
+) -> Result<Schema> {
+    let watermarks = options
+        .extract_if(|key, _| key.starts_with("watermark."))
+        .collect::<Vec<_>>();
+    if watermarks.is_empty() {
+        return Ok(schema);
+    }
+
+    let mut fields = schema
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone())
+        .collect::<Vec<_>>();
+    for (key, lateness) in watermarks {
+        let column = &key["watermark.".len()..];
+        let Some(field) = fields.iter_mut().find(|field| field.name() == column) else {
+            return plan_err!("Watermark column {column} not found in schema");
+        };
+        let lateness = parse_interval_month_day_nano(&lateness)?;
+        let delay_nanos = (lateness.months == 0)
+            .then(|| {
+                (lateness.days as i64)
+                    .checked_mul(NANOSECONDS_IN_DAY)?
+                    .checked_add(lateness.nanoseconds)
+            })
+            .flatten();
+        let Some(delay_nanos) = delay_nanos else {
+            return plan_err!(
+                "Invalid watermark lateness for column {column}: months are not supported"
+            );
+        };
+        *field = Watermark::try_new(delay_nanos)?.add_to_field(field.clone());
+        // validates that the column is a timestamp
+        Watermark::try_from_field(field)?;
+    }
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+/// Return true if the `approx_distinct` aggregate function supports
+/// `data_type`
+fn supports_approx_distinct(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Null
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Time32(_)
+            | DataType::Time64(_)
+            | DataType::Timestamp(_, _)
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Utf8View
+            | DataType::Binary
+            | DataType::LargeBinary
+    )
+}
@@ -22,7 +22,7 @@ use super::{
     utils::sqlite_date_trunc_to_sql, utils::sqlite_from_unixtime_to_sql,
 };
 use arrow::array::timezone::Tz;
-use arrow::datatypes::TimeUnit;
+use arrow::datatypes::{DataType, TimeUnit};
 use chrono::DateTime;
 use datafusion_common::{Result, internal_err};
 use datafusion_expr::Expr;
@@ -281,6 +281,34 @@ pub trait Dialect: Send + Sync {
         false
     }
 
+    /// Allows the dialect to override the SQL type used for an Arrow type,
+    /// e.g. for types the dialect does not support such as unsigned integers.
+    /// Returns None if the default type should be used.
+    ///
+    /// This takes precedence over the type specific methods such as
+    /// [`Self::int64_cast_dtype`].
+    fn data_type_override(&self, _data_type: &DataType) -> Option<ast::DataType> {
+        None
+    }
+
+    /// Allows the dialect to rename a scalar function that is called
+    /// differently in the dialect, but has the same arguments and semantics.
+    /// Returns None if the DataFusion name should be used.
+    ///
+    /// This is only used when [`Self::scalar_function_to_sql_overrides`]
+    /// does not unparse the function.
+    fn scalar_function_name_override(&self, _func_name: &str) -> Option<&str> {
+        None
+    }
+
+    /// The `LIMIT` to use for queries with an `OFFSET` but no `LIMIT`, for
+    /// dialects that do not support `OFFSET` without `LIMIT`.
+    ///
+    /// E.g. SQLite requires `LIMIT -1 OFFSET 10`
+    fn limit_for_offset_without_limit(&self) -> Option<ast::Expr> {
+        None
+    }
+
     /// Override the default string literal unparsing.
     ///
     /// Returns `Some(ast::Expr)` to replace the default single-quoted string,
@@ -385,6 +413,31 @@ impl Dialect for PostgreSqlDialect {
         ast::DataType::SmallInt(None)
     }
 
+    fn data_type_override(&self, data_type: &DataType) -> Option<ast::DataType> {
+        // Postgres has no unsigned integers, use the next larger signed type
+        match data_type {
+            DataType::UInt8 => Some(ast::DataType::SmallInt(None)),
+            DataType::UInt16 => Some(ast::DataType::Integer(None)),
+            DataType::UInt32 => Some(ast::DataType::BigInt(None)),
+            DataType::UInt64 => Some(ast::DataType::Numeric(
+                ast::ExactNumberInfo::PrecisionAndScale(20, 0),
+            )),
+            DataType::Float32 => Some(ast::DataType::Real),
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => Some(ast::DataType::Bytea),
+            _ => None,
+        }
+    }
+
+    fn scalar_function_name_override(&self, func_name: &str) -> Option<&str> {
+        match func_name {
+            "nvl" => Some("coalesce"),
+            _ => None,
+        }
+    }
+
     fn scalar_function_to_sql_overrides(
         &self,
         unparser: &Unparser,
@@ -498,6 +551,32 @@ impl Dialect for DuckDBDialect {
         BinaryOperator::DuckIntegerDivide
     }
 
+    fn data_type_override(&self, data_type: &DataType) -> Option<ast::DataType> {
+        let name = match data_type {
+            DataType::UInt8 => "UTINYINT",
+            DataType::UInt16 => "USMALLINT",
+            DataType::UInt32 => "UINTEGER",
+            DataType::UInt64 => "UBIGINT",
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => "BLOB",
+            _ => return None,
+        };
+        Some(ast::DataType::Custom(
+            ObjectName::from(vec![Ident::new(name)]),
+            vec![],
+        ))
+    }
+
+    fn scalar_function_name_override(&self, func_name: &str) -> Option<&str> {
+        match func_name {
+            "nvl" => Some("coalesce"),
+            "btrim" => Some("trim"),
+            _ => None,
+        }
+    }
+
     fn with_custom_scalar_overrides(
         mut self,
         handlers: Vec<(&str, ScalarFnToSqlHandler)>,
@@ -578,6 +657,42 @@ impl Dialect for MySqlDialect {
         ast::DataType::Datetime(None)
     }
 
+    fn data_type_override(&self, data_type: &DataType) -> Option<ast::DataType> {
+        // MySQL only supports a few types in CAST
+        let name = match data_type {
+            DataType::Boolean | DataType::Int8 | DataType::Int16 => "SIGNED",
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+                "UNSIGNED"
+            }
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => return Some(ast::DataType::Binary(None)),
+            _ => return None,
+        };
+        Some(ast::DataType::Custom(
+            ObjectName::from(vec![Ident::new(name)]),
+            vec![],
+        ))
+    }
+
+    fn scalar_function_name_override(&self, func_name: &str) -> Option<&str> {
+        match func_name {
+            "nvl" => Some("ifnull"),
+            "strpos" => Some("instr"),
+            "random" => Some("rand"),
+            _ => None,
+        }
+    }
+
+    fn limit_for_offset_without_limit(&self) -> Option<ast::Expr> {
+        // the maximum number of rows, as recommended by the MySQL documentation
+        Some(ast::Expr::value(ast::Value::Number(
+            u64::MAX.to_string(),
+            false,
+        )))
+    }
+
     fn requires_derived_table_alias(&self) -> bool {
         true
     }
@@ -631,6 +746,33 @@ impl Dialect for SqliteDialect {
         ast::DataType::Text
     }
 
+    fn data_type_override(&self, data_type: &DataType) -> Option<ast::DataType> {
+        match data_type {
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => Some(ast::DataType::Blob(None)),
+            _ => None,
+        }
+    }
+
+    fn scalar_function_name_override(&self, func_name: &str) -> Option<&str> {
+        match func_name {
+            "nvl" => Some("ifnull"),
+            "btrim" => Some("trim"),
+            "strpos" => Some("instr"),
+            _ => None,
+        }
+    }
+
+    fn limit_for_offset_without_limit(&self) -> Option<ast::Expr> {
+        // a negative limit means no limit
+        Some(ast::Expr::value(ast::Value::Number(
+            "-1".to_string(),
+            false,
+        )))
+    }
+
     fn scalar_function_to_sql_overrides(
         &self,
         unparser: &Unparser,
@@ -30,7 +30,7 @@ use std::sync::Arc;
 use std::vec;
 
 use super::Unparser;
-use super::dialect::IntervalStyle;
+use super::dialect::{Dialect, IntervalStyle};
 use arrow::array::{
     ArrayRef, Date32Array, Date64Array, PrimitiveArray,
     types::{
@@ -87,6 +87,29 @@ pub fn expr_to_sql(expr: &Expr) -> Result<ast::Expr> {
     unparser.expr_to_sql(expr)
 }
 
+/// Converts [`Expr`]s to SQL text that plans back to the same expressions,
+/// for example to store expressions as text.
+///
+/// Unlike [`expr_to_sql`], the types of literals are kept (see
+/// [`Unparser::with_typed_literals`]), so that the text is planned back to the
+/// same expression by `SessionContext::parse_expr`.
+pub trait ExprSqlExt {
+    /// Converts this expression to SQL text in `dialect`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression, or the type of one of its
+    /// literals, can not be represented in SQL
+    fn to_sql_string(&self, dialect: &dyn Dialect) -> Result<String>;
+}
+
+impl ExprSqlExt for Expr {
+    fn to_sql_string(&self, dialect: &dyn Dialect) -> Result<String> {
+        let unparser = Unparser::new(dialect).with_typed_literals(true);
+        Ok(unparser.expr_to_sql(self)?.to_string())
+    }
+}
+
 const LOWEST: &BinaryOperator = &BinaryOperator::Or;
 // Closest precedence we have to IS operator is BitwiseAnd (any other) in PG docs
 // (https://www.postgresql.org/docs/7.2/sql-precedence.html)
@@ -220,7 +243,14 @@ impl Unparser<'_> {
                 })
             }
             Expr::Cast(Cast { expr, field }) => Ok(self.cast_to_sql(expr, field)?),
-            Expr::Literal(value, _) => Ok(self.scalar_to_sql(value)?),
+            Expr::Literal(value, _) => {
+                let sql = self.scalar_to_sql(value)?;
+                if self.typed_literals {
+                    self.typed_literal_to_sql(value, sql)
+                } else {
+                    Ok(sql)
+                }
+            }
             Expr::Alias(Alias { expr, name: _, .. }) => self.expr_to_sql_inner(expr),
             Expr::WindowFunction(window_fun) => {
                 let WindowFunction {
@@ -623,7 +653,13 @@ impl Unparser<'_> {
             "get_field" => self.get_field_to_sql(args),
             "map" => self.map_to_sql(args),
             // TODO: support for the construct and access functions of the `map` type
-            _ => self.function_to_sql_internal(func_name, args),
+            _ => {
+                let func_name = self
+                    .dialect
+                    .scalar_function_name_override(func_name)
+                    .unwrap_or(func_name);
+                self.function_to_sql_internal(func_name, args)
+            }
         }
     }
 
@@ -1266,6 +1302,36 @@ impl Unparser<'_> {
 
     /// DataFusion ScalarValues sometimes require a ast::Expr to construct.
     /// For example ScalarValue::Date32(d) corresponds to the ast::Expr CAST('datestr' as DATE)
+    /// Casts `sql`, the SQL of the literal `value`, to the type of `value`,
+    /// unless SQL planning infers that type for it
+    fn typed_literal_to_sql(
+        &self,
+        value: &ScalarValue,
+        sql: ast::Expr,
+    ) -> Result<ast::Expr> {
+        let data_type = value.data_type();
+        let inferred = match &sql {
+            // already typed, e.g. `CAST('2024-01-01' AS DATE)`
+            ast::Expr::Cast { .. } => true,
+            _ if value.is_null() => data_type == DataType::Null,
+            _ => matches!(
+                data_type,
+                DataType::Boolean | DataType::Int64 | DataType::Float64 | DataType::Utf8
+            ),
+        };
+        if inferred {
+            return Ok(sql);
+        }
+        Ok(ast::Expr::Cast {
+            kind: ast::CastKind::Cast,
+            expr: Box::new(sql),
+            data_type: self
+                .arrow_dtype_to_ast_dtype(&data_type.into_nullable_field_ref())?,
+            array: false,
+            format: None,
+        })
+    }
+
     fn scalar_to_sql(&self, v: &ScalarValue) -> Result<ast::Expr> {
         match v {
             ScalarValue::Null => Ok(ast::Expr::value(ast::Value::Null)),
@@ -1790,6 +1856,9 @@ impl Unparser<'_> {
 
     fn arrow_dtype_to_ast_dtype(&self, field: &FieldRef) -> Result<ast::DataType> {
         let data_type = field.data_type();
+        if let Some(ast_data_type) = self.dialect.data_type_override(data_type) {
+            return Ok(ast_data_type);
+        }
         match data_type {
             DataType::Null => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type}")
@@ -3532,6 +3601,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_scalar_function_name_override() -> Result<()> {
+        let strpos = Arc::new(ScalarUDF::new_from_impl(
+            datafusion_functions::unicode::strpos::StrposFunc::new(),
+        ));
+        let expr = strpos.call(vec![col("a"), lit("b")]);
+
+        let default_dialect: Arc<dyn Dialect> = Arc::new(DefaultDialect {});
+        let postgres_dialect: Arc<dyn Dialect> = Arc::new(PostgreSqlDialect {});
+        let sqlite_dialect: Arc<dyn Dialect> = Arc::new(SqliteDialect {});
+        let mysql_dialect: Arc<dyn Dialect> =
+            Arc::new(crate::unparser::dialect::MySqlDialect {});
+
+        for (dialect, expected) in [
+            (default_dialect, "strpos(a, 'b')"),
+            (postgres_dialect, r#"strpos("a", 'b')"#),
+            (sqlite_dialect, "instr(`a`, 'b')"),
+            (mysql_dialect, "instr(`a`, 'b')"),
+        ] {
+            let unparser = Unparser::new(dialect.as_ref());
+            let actual = unparser.expr_to_sql(&expr)?.to_string();
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_cast_timestamp_sqlite() -> Result<()> {
         let dialect: Arc<dyn Dialect> = Arc::new(SqliteDialect {});
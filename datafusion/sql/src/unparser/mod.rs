@@ -25,7 +25,7 @@ mod utils;
 
 use self::dialect::{DefaultDialect, Dialect};
 use crate::unparser::extension_unparser::UserDefinedLogicalNodeUnparser;
-pub use expr::expr_to_sql;
+pub use expr::{ExprSqlExt, expr_to_sql};
 pub use plan::plan_to_sql;
 use std::sync::Arc;
 pub mod dialect;
@@ -57,6 +57,7 @@ pub mod extension_unparser;
 pub struct Unparser<'a> {
     dialect: &'a dyn Dialect,
     pretty: bool,
+    typed_literals: bool,
     extension_unparsers: Vec<Arc<dyn UserDefinedLogicalNodeUnparser>>,
 }
 
@@ -65,6 +66,7 @@ impl<'a> Unparser<'a> {
         Self {
             dialect,
             pretty: false,
+            typed_literals: false,
             extension_unparsers: vec![],
         }
     }
@@ -110,6 +112,29 @@ impl<'a> Unparser<'a> {
         self
     }
 
+    /// Cast literals to their type, unless SQL planning infers that type for
+    /// them
+    ///
+    /// By default, literals are unparsed without their type, so that for
+    /// example both an `Int8` and an `Int64` literal `1` are unparsed to `1`,
+    /// which is planned as an `Int64` literal. With typed literals, the `Int8`
+    /// literal is unparsed to `CAST(1 AS TINYINT)`, as is a typed `NULL`.
+    ///
+    /// # Example
+    /// ```
+    /// use datafusion_common::ScalarValue;
+    /// use datafusion_expr::{col, lit};
+    /// use datafusion_sql::unparser::Unparser;
+    /// let expr = col("a").eq(lit(ScalarValue::Int8(Some(1))));
+    /// let unparser = Unparser::default().with_typed_literals(true);
+    /// let sql = unparser.expr_to_sql(&expr).unwrap();
+    /// assert_eq!(sql.to_string(), "(a = CAST(1 AS TINYINT))");
+    /// ```
+    pub fn with_typed_literals(mut self, typed_literals: bool) -> Self {
+        self.typed_literals = typed_literals;
+        self
+    }
+
     /// Add a custom unparser for user defined logical nodes
     ///
     /// DataFusion allows user to define custom logical nodes. This method allows to add custom child unparsers for these nodes.
@@ -135,6 +160,7 @@ impl Default for Unparser<'_> {
         Self {
             dialect: &DefaultDialect {},
             pretty: false,
+            typed_literals: false,
             extension_unparsers: vec![],
         }
     }
@@ -50,7 +50,7 @@ use datafusion_expr::expr::{OUTER_REFERENCE_COLUMN_PREFIX, UNNEST_COLUMN_PREFIX}
 use datafusion_expr::{
     BinaryExpr, Distinct, Expr, JoinConstraint, JoinType, LogicalPlan,
     LogicalPlanBuilder, Operator, Projection, SortExpr, TableScan, Unnest,
-    UserDefinedLogicalNode, expr::Alias,
+    UserDefinedLogicalNode, binary_expr, expr::Alias,
 };
 use sqlparser::ast::{self, Ident, OrderByKind, SetExpr, TableAliasColumnDef};
 use std::{sync::Arc, vec};
@@ -134,7 +134,10 @@ impl Unparser<'_> {
             | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::RecursiveQuery(_)
-            | LogicalPlan::Unnest(_) => not_impl_err!("Unsupported plan: {plan:?}"),
+            | LogicalPlan::Unnest(_)
+            | LogicalPlan::LateralTableFunction(_) => {
+                not_impl_err!("Unsupported plan: {plan:?}")
+            }
         }
     }
 
@@ -671,6 +674,12 @@ impl Unparser<'_> {
                         rows: ast::OffsetRows::None,
                         value: self.expr_to_sql(skip)?,
                     }));
+                    if limit.fetch.is_none()
+                        && let Some(no_limit) =
+                            self.dialect.limit_for_offset_without_limit()
+                    {
+                        query.limit(Some(no_limit));
+                    }
                 }
 
                 self.select_to_sql_recursively(
@@ -810,6 +819,12 @@ impl Unparser<'_> {
                 self.select_to_sql_recursively(input, query, select, relation)
             }
             LogicalPlan::Join(join) => {
+                if join.asof_match.is_some() && join.join_type != JoinType::Left {
+                    return not_impl_err!(
+                        "Unsupported plan: {} ASOF join",
+                        join.join_type
+                    );
+                }
                 let mut table_scan_filters = vec![];
                 let (left_plan, right_plan) = match join.join_type {
                     JoinType::RightSemi | JoinType::RightAnti => {
@@ -959,8 +974,30 @@ impl Unparser<'_> {
                         let ast_join = ast::Join {
                             relation,
                             global: false,
-                            join_operator: self
-                                .join_operator_to_sql(join.join_type, join_constraint)?,
+                            join_operator: match &join.asof_match {
+                                Some(asof_match) => {
+                                    let match_condition = binary_expr(
+                                        asof_match.left.clone(),
+                                        asof_match.op,
+                                        asof_match.right.clone(),
+                                    );
+                                    let match_condition = match &asof_match.tolerance {
+                                        Some(tolerance) => {
+                                            match_condition.and(tolerance.clone())
+                                        }
+                                        None => match_condition,
+                                    };
+                                    ast::JoinOperator::AsOf {
+                                        match_condition: self
+                                            .expr_to_sql(&match_condition)?,
+                                        constraint: join_constraint,
+                                    }
+                                }
+                                None => self.join_operator_to_sql(
+                                    join.join_type,
+                                    join_constraint,
+                                )?,
+                            },
                         };
                         let mut from = select.pop_from().unwrap();
                         from.push_join(ast_join);
@@ -1176,6 +1213,14 @@ impl Unparser<'_> {
                 }
             }
             LogicalPlan::Unnest(unnest) => {
+                if unnest.options.ordinality {
+                    return not_impl_err!(
+                        "Unparsing UNNEST WITH ORDINALITY is not supported"
+                    );
+                }
+                if unnest.options.outer {
+                    return not_impl_err!("Unparsing outer UNNEST is not supported");
+                }
                 if !unnest.struct_type_columns.is_empty() {
                     if self.dialect.unnest_as_lateral_flatten() {
                         return not_impl_err!(
@@ -1305,6 +1350,12 @@ impl Unparser<'_> {
                         rows: ast::OffsetRows::None,
                         value: self.expr_to_sql(skip)?,
                     }));
+                    if limit.fetch.is_none()
+                        && let Some(no_limit) =
+                            self.dialect.limit_for_offset_without_limit()
+                    {
+                        q.limit(Some(no_limit));
+                    }
                 }
                 self.peel_to_unnest_with_modifiers(
                     limit.input.as_ref(),
@@ -448,6 +448,27 @@ impl RecursiveUnnestRewriter<'_> {
         let data_type = field.data_type();
 
         match data_type {
+            DataType::Map(entries, _) => {
+                let DataType::Struct(inner_fields) = entries.data_type() else {
+                    return internal_err!(
+                        "map entries must be a struct, got {data_type}"
+                    );
+                };
+                assert_or_internal_err!(
+                    struct_allowed,
+                    "unnest on map can only be applied at the root level of select expression"
+                );
+                push_projection_dedupl(
+                    self.inner_projection_exprs,
+                    expr_in_unnest.clone().alias(placeholder_name.clone()),
+                );
+                self.columns_unnestings
+                    .insert(Column::from_name(placeholder_name.clone()), None);
+                Ok(get_struct_unnested_columns(&placeholder_name, inner_fields)
+                    .into_iter()
+                    .map(Expr::Column)
+                    .collect())
+            }
             DataType::Struct(inner_fields) => {
                 assert_or_internal_err!(
                     struct_allowed,
@@ -490,7 +511,7 @@ impl RecursiveUnnestRewriter<'_> {
                 Ok(vec![post_unnest_expr])
             }
             _ => {
-                internal_err!("unnest on non-list or struct type is not supported")
+                internal_err!("unnest on non-list, map or struct type is not supported")
             }
         }
     }
@@ -517,7 +538,7 @@ impl TreeNodeRewriter for RecursiveUnnestRewriter<'_> {
             // - unnest(struct_col)
             //      unnest(struct_arr_col) as struct_col
 
-            if let DataType::Struct(_) = data_type {
+            if let DataType::Struct(_) | DataType::Map(_, _) = data_type {
                 self.consecutive_unnest.push(None);
             }
             if self.top_most_unnest.is_none() {
@@ -19,12 +19,14 @@ use datafusion_functions::string;
 use insta::assert_snapshot;
 use std::{collections::HashMap, sync::Arc};
 
+use datafusion_common::diagnostic::DiagnosticKind;
 use datafusion_common::{Diagnostic, Location, Result, Span};
 use datafusion_sql::{
     parser::{DFParser, DFParserBuilder},
     planner::{ParserOptions, SqlToRel},
 };
 use regex::Regex;
+use sqlparser::dialect::MsSqlDialect;
 
 use crate::{MockContextProvider, MockSessionState};
 
@@ -390,3 +392,28 @@ fn test_syntax_error() -> Result<()> {
         },
     }
 }
+
+#[test]
+fn test_ignored_table_hints_warning() -> Result<()> {
+    let query = "SELECT * FROM /*table*/person/*table*/ WITH (NOLOCK)";
+    let spans = get_spans(query);
+    let statement = DFParserBuilder::new(query)
+        .with_dialect(&MsSqlDialect {})
+        .build()?
+        .parse_statement()?;
+    let options = ParserOptions {
+        collect_spans: true,
+        ..ParserOptions::default()
+    };
+    let context = MockContextProvider {
+        state: MockSessionState::default(),
+    };
+    SqlToRel::new_with_options(&context, options).statement_to_plan(statement)?;
+
+    let warnings = context.state.warnings.take();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, DiagnosticKind::Warning);
+    assert_snapshot!(warnings[0].message, @"table hints for 'person' are not supported and were ignored");
+    assert_eq!(warnings[0].span, Some(spans["table"]));
+    Ok(())
+}
@@ -706,6 +706,40 @@ fn roundtrip_statement_with_dialect_26() -> Result<(), DataFusionError> {
     Ok(())
 }
 
+#[test]
+fn roundtrip_statement_offset_without_limit() -> Result<(), DataFusionError> {
+    roundtrip_statement_with_dialect_helper!(
+        sql: "SELECT j1_id FROM j1 OFFSET 5",
+        parser_dialect: GenericDialect {},
+        unparser_dialect: SqliteDialect {},
+        expected: @"SELECT `j1`.`j1_id` FROM `j1` LIMIT -1 OFFSET 5",
+    );
+    roundtrip_statement_with_dialect_helper!(
+        sql: "SELECT j1_id FROM j1 OFFSET 5",
+        parser_dialect: MySqlDialect {},
+        unparser_dialect: UnparserMySqlDialect {},
+        expected: @"SELECT `j1`.`j1_id` FROM `j1` LIMIT 18446744073709551615 OFFSET 5",
+    );
+    roundtrip_statement_with_dialect_helper!(
+        sql: "SELECT j1_id FROM j1 OFFSET 5",
+        parser_dialect: GenericDialect {},
+        unparser_dialect: UnparserPostgreSqlDialect {},
+        expected: @r#"SELECT "j1"."j1_id" FROM "j1" OFFSET 5"#,
+    );
+    Ok(())
+}
+
+#[test]
+fn roundtrip_statement_dialect_data_type_override() -> Result<(), DataFusionError> {
+    roundtrip_statement_with_dialect_helper!(
+        sql: "SELECT CAST(j1_id AS INT UNSIGNED) FROM j1",
+        parser_dialect: GenericDialect {},
+        unparser_dialect: UnparserPostgreSqlDialect {},
+        expected: @r#"SELECT CAST("j1"."j1_id" AS BIGINT) FROM "j1""#,
+    );
+    Ok(())
+}
+
 #[test]
 fn roundtrip_statement_with_dialect_27() -> Result<(), DataFusionError> {
     roundtrip_statement_with_dialect_helper!(
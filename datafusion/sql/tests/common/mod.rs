@@ -24,8 +24,9 @@ use std::{sync::Arc, vec};
 use arrow::datatypes::*;
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::datatype::DataTypeExt;
+use datafusion_common::diagnostic::WarningCollector;
 use datafusion_common::file_options::file_type::FileType;
-use datafusion_common::{DFSchema, GetExt, Result, TableReference, plan_err};
+use datafusion_common::{DFSchema, Diagnostic, GetExt, Result, TableReference, plan_err};
 use datafusion_expr::planner::{ExprPlanner, PlannerResult, TypePlanner};
 use datafusion_expr::{
     AggregateUDF, Expr, HigherOrderUDF, ScalarUDF, TableSource, WindowUDF,
@@ -62,6 +63,7 @@ pub(crate) struct MockSessionState {
     type_planner: Option<Arc<dyn TypePlanner>>,
     window_functions: HashMap<String, Arc<WindowUDF>>,
     pub config_options: ConfigOptions,
+    pub warnings: WarningCollector,
 }
 
 impl MockSessionState {
@@ -342,6 +344,10 @@ impl ContextProvider for MockContextProvider {
         Vec::new()
     }
 
+    fn report_warning(&self, warning: Diagnostic) {
+        self.state.warnings.report(warning)
+    }
+
     fn get_expr_planners(&self) -> &[Arc<dyn ExprPlanner>] {
         &self.state.expr_planners
     }
@@ -699,7 +699,7 @@ fn plan_insert() {
         @r#"
     Dml: op=[Insert Into] table=[person]
       Projection: column1 AS id, column2 AS first_name, column3 AS last_name, CAST(NULL AS Int32) AS age, CAST(NULL AS Utf8) AS state, CAST(NULL AS Float64) AS salary, CAST(NULL AS Timestamp(ns)) AS birth_date, CAST(NULL AS Int32) AS 😀
-        Values: (CAST(Int64(1) AS UInt32), Utf8("Alan"), Utf8("Turing"))
+        Values: (UInt32(1), Utf8("Alan"), Utf8("Turing"))
     "#
     );
 }
@@ -713,7 +713,7 @@ fn plan_insert_no_target_columns() {
         @r"
     Dml: op=[Insert Into] table=[test_decimal]
       Projection: column1 AS id, column2 AS price
-        Values: (CAST(Int64(1) AS Int32), CAST(Int64(2) AS Decimal128(10, 2))), (CAST(Int64(3) AS Int32), CAST(Int64(4) AS Decimal128(10, 2)))
+        Values: (Int32(1), Decimal128(Some(200),10,2)), (Int32(3), Decimal128(Some(400),10,2))
     "
     );
 }
@@ -791,6 +791,38 @@ fn plan_delete() {
     );
 }
 
+#[test]
+fn merge_column_does_not_exist() {
+    let sql = "MERGE INTO person USING person AS p ON person.id = p.id \
+               WHEN MATCHED THEN UPDATE SET doesnotexist = p.age";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_field_not_found(err, "doesnotexist");
+}
+
+#[test]
+fn plan_analyze_table() {
+    let sql = "ANALYZE TABLE person COMPUTE STATISTICS FOR COLUMNS id, first_name";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+    Dml: op=[Analyze] table=[person]
+      Aggregate: groupBy=[[]], aggr=[[count(Int64(1)) AS row_count, min(person.id) AS min(id), max(person.id) AS max(id), count(Int64(1)) FILTER (WHERE person.id IS NULL) AS null_count(id), min(person.first_name) AS min(first_name), max(person.first_name) AS max(first_name), count(Int64(1)) FILTER (WHERE person.first_name IS NULL) AS null_count(first_name)]]
+        TableScan: person
+    "
+    );
+}
+
+#[test]
+fn analyze_table_with_noscan() {
+    let sql = "ANALYZE TABLE person COMPUTE STATISTICS NOSCAN";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_snapshot!(
+        err.strip_backtrace(),
+        @"This feature is not implemented: ANALYZE TABLE with NOSCAN is not supported"
+    );
+}
+
 #[test]
 fn plan_delete_quoted_identifier_case_sensitive() {
     let sql =
@@ -1163,6 +1195,29 @@ fn select_with_having_referencing_column_nested_in_select_expression() {
     );
 }
 
+#[test]
+fn select_with_having_without_column_references() {
+    // HAVING without GROUP BY and aggregates makes a single group of no
+    // columns, which is a single row
+    let sql = "SELECT 1 FROM person HAVING true";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+    Projection: Int64(1)
+      Filter: Boolean(true)
+        EmptyRelation: rows=1
+    "
+    );
+
+    let sql = "SELECT id FROM person HAVING true";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_contains!(
+        err.strip_backtrace(),
+        "Column in SELECT must be in GROUP BY or an aggregate function"
+    );
+}
+
 #[test]
 fn select_with_having_with_aggregate_not_in_select() {
     let sql = "SELECT first_name
@@ -3832,6 +3887,36 @@ fn exists_subquery_schema_outer_schema_overlap() {
     );
 }
 
+#[test]
+fn exists_subquery_in_having() {
+    // the subquery is correlated to the groups of the aggregation
+    let sql = "SELECT age, count(*) AS cnt FROM person GROUP BY age \
+            HAVING EXISTS (SELECT 1 FROM orders \
+            WHERE qty = person.age AND order_id = cnt)";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r"
+    Projection: person.age, count(*) AS cnt
+      Filter: EXISTS (<subquery>)
+        Subquery:
+          Projection: Int64(1)
+            Filter: orders.qty = outer_ref(person.age) AND orders.order_id = outer_ref(count(*))
+              TableScan: orders
+        Aggregate: groupBy=[[person.age]], aggr=[[count(*)]]
+          TableScan: person
+    "
+    );
+
+    let sql = "SELECT age FROM person GROUP BY age \
+            HAVING age IN (SELECT qty FROM orders WHERE order_id = person.id)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_contains!(
+        err.strip_backtrace(),
+        "Correlated column person.id in a HAVING subquery must appear in the GROUP BY clause or be used in an aggregate function"
+    );
+}
+
 #[test]
 fn in_subquery_uncorrelated() {
     let sql = "SELECT id FROM person p WHERE id IN \
@@ -4605,6 +4690,61 @@ fn test_select_qualify_complex_condition() {
     );
 }
 
+#[test]
+fn test_select_pivot() {
+    let sql = "SELECT * FROM (SELECT state, age, salary FROM person) \
+        PIVOT (SUM(salary) FOR state IN ('CO', 'NY' AS new_york)) AS p";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: p.age, p.CO, p.new_york
+      SubqueryAlias: p
+        Aggregate: groupBy=[[person.age]], aggr=[[sum(person.salary) FILTER (WHERE person.state = Utf8("CO")) AS CO, sum(person.salary) FILTER (WHERE person.state = Utf8("NY")) AS new_york]]
+          Projection: person.state, person.age, person.salary
+            TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn test_select_pivot_multiple_aggregates_default_on_null() {
+    let sql = "SELECT * FROM (SELECT state, salary FROM person) \
+        PIVOT (SUM(salary) AS total, MAX(salary) FOR state IN ('CO') DEFAULT ON NULL (0))";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: CO_total, CO_max(person.salary)
+      Projection: CASE WHEN CO_total IS NULL THEN Int64(0) ELSE CO_total END AS CO_total, CASE WHEN CO_max(person.salary) IS NULL THEN Int64(0) ELSE CO_max(person.salary) END AS CO_max(person.salary)
+        Aggregate: groupBy=[[]], aggr=[[sum(person.salary) FILTER (WHERE person.state = Utf8("CO")) AS CO_total, max(person.salary) FILTER (WHERE person.state = Utf8("CO")) AS CO_max(person.salary)]]
+          Projection: person.state, person.salary
+            TableScan: person
+    "#
+    );
+}
+
+#[test]
+fn test_select_unpivot() {
+    let sql = "SELECT * FROM (SELECT id, first_name, last_name FROM person) \
+        UNPIVOT (name FOR kind IN (first_name, last_name AS surname))";
+    let plan = logical_plan(sql).unwrap();
+    assert_snapshot!(
+        plan,
+        @r#"
+    Projection: id, kind, name
+      Filter: name IS NOT NULL
+        Union
+          Projection: person.id, Utf8("first_name") AS kind, person.first_name AS name
+            Projection: person.id, person.first_name, person.last_name
+              TableScan: person
+          Projection: person.id, Utf8("surname") AS kind, person.last_name AS name
+            Projection: person.id, person.first_name, person.last_name
+              TableScan: person
+    "#
+    );
+}
+
 #[rstest]
 #[case::select_cluster_by_unsupported(
     "SELECT customer_name, sum(order_total) as total_order_amount FROM orders CLUSTER BY customer_name",
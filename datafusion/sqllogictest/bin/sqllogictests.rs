@@ -24,7 +24,7 @@ use datafusion_sqllogictest::DataFusionSubstraitRoundTrip;
 use datafusion_sqllogictest::TestFile;
 use datafusion_sqllogictest::{
     CurrentlyExecutingSqlTracker, DataFusion, Filter, TestContext, df_value_validator,
-    read_dir_recursive, setup_scratch_dir, should_skip_file, should_skip_record,
+    read_dir_recursive, run_file_in_runner, setup_scratch_dir, should_skip_file,
     value_normalizer,
 };
 use futures::stream::StreamExt;
@@ -35,7 +35,7 @@ use itertools::Itertools;
 use log::Level::Info;
 use log::{info, log_enabled};
 use sqllogictest::{
-    AsyncDB, Condition, MakeConnection, Normalizer, Record, Validator, parse_file,
+    AsyncDB, Condition, Normalizer, Record, Validator, parse_file,
     strict_column_validator,
 };
 
@@ -61,7 +61,6 @@ const DATAFUSION_TESTING_TEST_DIRECTORY: &str = "../../datafusion-testing/data/"
 const PG_COMPAT_FILE_PREFIX: &str = "pg_compat_";
 const TPCH_PREFIX: &str = "tpch";
 const SQLITE_PREFIX: &str = "sqlite";
-const ERRS_PER_FILE_LIMIT: usize = 10;
 const TIMING_DEBUG_SLOW_FILES_ENV: &str = "SLT_TIMING_DEBUG_SLOW_FILES";
 
 #[derive(Debug)]
@@ -537,51 +536,6 @@ async fn run_test_file(
     config_change_result(&config_change_errors)
 }
 
-async fn run_file_in_runner<D: AsyncDB, M: MakeConnection<Conn = D>>(
-    path: PathBuf,
-    runner: &mut sqllogictest::Runner<D, M>,
-    filters: &[Filter],
-    colored_output: bool,
-) -> Result<()> {
-    let path = path.canonicalize()?;
-    let records =
-        parse_file(&path).map_err(|e| DataFusionError::External(Box::new(e)))?;
-    let mut errs = vec![];
-    for record in records.into_iter() {
-        if let Record::Halt { .. } = record {
-            break;
-        }
-        if should_skip_record::<D>(&record, filters) {
-            continue;
-        }
-        if let Err(err) = runner.run_async(record).await {
-            if colored_output {
-                errs.push(format!("{}", err.display(true)));
-            } else {
-                errs.push(format!("{err}"));
-            }
-        }
-    }
-
-    if !errs.is_empty() {
-        let mut msg = format!("{} errors in file {}\n\n", errs.len(), path.display());
-        for (i, err) in errs.iter().enumerate() {
-            if i >= ERRS_PER_FILE_LIMIT {
-                msg.push_str(&format!(
-                    "... other {} errors in {} not shown ...\n\n",
-                    errs.len() - ERRS_PER_FILE_LIMIT,
-                    path.display()
-                ));
-                break;
-            }
-            msg.push_str(&format!("{}. {err}\n\n", i + 1));
-        }
-        return Err(DataFusionError::External(msg.into()));
-    }
-
-    Ok(())
-}
-
 #[expect(clippy::needless_pass_by_value)]
 fn get_record_count(path: &PathBuf, label: String) -> u64 {
     let records: Vec<Record<<DataFusion as AsyncDB>::ColumnType>> =
@@ -0,0 +1,271 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Library API for running sqllogictest files against a customized
+//! [`SessionContext`].
+
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use datafusion::common::{DataFusionError, Result};
+use datafusion::prelude::SessionContext;
+use indicatif::ProgressBar;
+use sqllogictest::{
+    AsyncDB, MakeConnection, Record, parse_file, strict_column_validator,
+};
+
+use crate::{
+    DataFusion, Filter, TestContext, TestFile, df_value_validator, read_dir_recursive,
+    setup_scratch_dir, should_skip_file, should_skip_record, value_normalizer,
+};
+
+/// Maximum number of errors reported per test file
+pub const ERRS_PER_FILE_LIMIT: usize = 10;
+
+/// Directory containing the `.slt` files shipped with this crate
+pub fn test_files_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_files")
+}
+
+/// Function called with the [`SessionContext`] of each test file, see
+/// [`SqlLogicTestHarness::with_setup`]
+pub type SetupHook = Arc<dyn Fn(&SessionContext, &Path) -> Result<()> + Send + Sync>;
+
+/// Runs sqllogictest files using the DataFusion engine, allowing downstream
+/// crates to customize the [`SessionContext`] used for each file.
+///
+/// This lets `TableProvider`, catalog or UDF implementors run the
+/// conformance suite against their own implementations, for example by
+/// registering a custom default schema so that `CREATE TABLE` statements
+/// are stored in their storage layer instead of in memory.
+///
+/// # Example
+/// ```no_run
+/// # use datafusion::common::Result;
+/// # use datafusion_sqllogictest::{SqlLogicTestHarness, test_files_dir};
+/// # async fn run() -> Result<()> {
+/// let harness = SqlLogicTestHarness::new().with_setup(|ctx, _path| {
+///     // register custom table providers, catalogs, UDFs, ...
+///     Ok(())
+/// });
+/// harness.run_dir(test_files_dir()).await
+/// # }
+/// ```
+///
+/// Note that some of the bundled files refer to data using paths relative
+/// to this crate's directory, so they should be run with that directory as
+/// the current working directory.
+#[derive(Clone, Default)]
+pub struct SqlLogicTestHarness {
+    setup: Vec<SetupHook>,
+    filters: Vec<Filter>,
+}
+
+impl Debug for SqlLogicTestHarness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlLogicTestHarness")
+            .field("setup", &self.setup.len())
+            .field("filters", &self.filters)
+            .finish()
+    }
+}
+
+impl SqlLogicTestHarness {
+    /// Create a harness that runs files with the default test setup
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a function that is called after the standard setup of each test
+    /// file and before any of its records run. It receives the context the
+    /// file runs against and the file's path relative to the test directory.
+    ///
+    /// Hooks are called in the order they were added.
+    pub fn with_setup(
+        mut self,
+        setup: impl Fn(&SessionContext, &Path) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.setup.push(Arc::new(setup));
+        self
+    }
+
+    /// Only run files and records matching the given [`Filter`]s
+    pub fn with_filters(mut self, filters: Vec<Filter>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Return the `.slt` files under `dir` that match the filters, in the
+    /// order they should be run
+    pub fn test_files(&self, dir: impl AsRef<Path>) -> Result<Vec<TestFile>> {
+        let dir = dir.as_ref();
+        let prefix = format!("{}/", dir.display());
+        let mut files = read_dir_recursive(dir)?
+            .into_iter()
+            .map(|path| TestFile::new(path, &[prefix.as_str()]))
+            .filter(|f| f.is_slt_file())
+            .filter(|f| !should_skip_file(&f.relative_path, &self.filters))
+            .collect::<Vec<_>>();
+        files.sort_unstable();
+        Ok(files)
+    }
+
+    /// Run all `.slt` files under `dir`, returning an error describing every
+    /// failed file
+    pub async fn run_dir(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let mut errors = vec![];
+        for test_file in self.test_files(dir)? {
+            let relative_path = test_file.relative_path.clone();
+            if let Err(e) = self.run_file(test_file).await {
+                errors.push(format!("{}: {e}", relative_path.display()));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DataFusionError::External(
+                format!("{} failures\n\n{}", errors.len(), errors.join("\n")).into(),
+            ))
+        }
+    }
+
+    /// Run a single test file. Files that can not run with the enabled
+    /// features are skipped.
+    pub async fn run_file(&self, test_file: TestFile) -> Result<()> {
+        let TestFile {
+            path,
+            relative_path,
+        } = test_file;
+        let Some(test_ctx) = TestContext::try_new_for_test_file(&relative_path).await
+        else {
+            return Ok(());
+        };
+        for setup in &self.setup {
+            setup(test_ctx.session_ctx(), &relative_path)?;
+        }
+        setup_scratch_dir(&relative_path)?;
+
+        let mut runner = sqllogictest::Runner::new(|| async {
+            Ok(DataFusion::new(
+                test_ctx.session_ctx().clone(),
+                relative_path.clone(),
+                ProgressBar::hidden(),
+            ))
+        });
+        runner.add_label("Datafusion");
+        runner.with_column_validator(strict_column_validator);
+        runner.with_normalizer(value_normalizer);
+        runner.with_validator(df_value_validator);
+        let result = run_file_in_runner(path, &mut runner, &self.filters, false).await;
+        runner.shutdown_async().await;
+        result
+    }
+}
+
+/// Run all records of the file at `path` that match `filters`, collecting
+/// the errors of failed records into a single error
+pub async fn run_file_in_runner<D: AsyncDB, M: MakeConnection<Conn = D>>(
+    path: PathBuf,
+    runner: &mut sqllogictest::Runner<D, M>,
+    filters: &[Filter],
+    colored_output: bool,
+) -> Result<()> {
+    let path = path.canonicalize()?;
+    let records =
+        parse_file(&path).map_err(|e| DataFusionError::External(Box::new(e)))?;
+    let mut errs = vec![];
+    for record in records.into_iter() {
+        if let Record::Halt { .. } = record {
+            break;
+        }
+        if should_skip_record::<D>(&record, filters) {
+            continue;
+        }
+        if let Err(err) = runner.run_async(record).await {
+            if colored_output {
+                errs.push(format!("{}", err.display(true)));
+            } else {
+                errs.push(format!("{err}"));
+            }
+        }
+    }
+
+    if !errs.is_empty() {
+        let mut msg = format!("{} errors in file {}\n\n", errs.len(), path.display());
+        for (i, err) in errs.iter().enumerate() {
+            if i >= ERRS_PER_FILE_LIMIT {
+                msg.push_str(&format!(
+                    "... other {} errors in {} not shown ...\n\n",
+                    errs.len() - ERRS_PER_FILE_LIMIT,
+                    path.display()
+                ));
+                break;
+            }
+            msg.push_str(&format!("{}. {err}\n\n", i + 1));
+        }
+        return Err(DataFusionError::External(msg.into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int32Array, RecordBatch};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn run_file_with_setup_hook() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("custom_table.slt");
+        std::fs::write(
+            &path,
+            "query I\nselect count(*) from custom_table\n----\n3\n",
+        )?;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hook_calls = Arc::clone(&calls);
+        let harness = SqlLogicTestHarness::new().with_setup(move |ctx, relative_path| {
+            assert_eq!(relative_path, Path::new("custom_table.slt"));
+            hook_calls.fetch_add(1, Ordering::SeqCst);
+            let batch = RecordBatch::try_from_iter([(
+                "a",
+                Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+            )])?;
+            ctx.register_batch("custom_table", batch)?;
+            Ok(())
+        });
+
+        let files = harness.test_files(dir.path())?;
+        assert_eq!(files.len(), 1);
+        for file in files {
+            harness.run_file(file).await?;
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // without the hook the table does not exist
+        let err = SqlLogicTestHarness::new()
+            .run_dir(dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("custom_table"), "{err}");
+        Ok(())
+    }
+}
@@ -43,10 +43,15 @@ pub use engines::convert_schema_to_types;
 pub use engines::Postgres;
 
 mod filters;
+mod harness;
 mod test_context;
 mod util;
 
 pub use filters::*;
+pub use harness::{
+    ERRS_PER_FILE_LIMIT, SetupHook, SqlLogicTestHarness, run_file_in_runner,
+    test_files_dir,
+};
 pub use test_context::TestContext;
 pub use test_file::TestFile;
 pub use util::*;
@@ -27,6 +27,7 @@ use datafusion::logical_expr::expr::WindowFunctionParams;
 use datafusion::logical_expr::{
     Expr, WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition, expr,
 };
+use substrait::proto::AggregationInvocation;
 use substrait::proto::expression::WindowFunction;
 use substrait::proto::expression::window_function::{Bound, BoundsType};
 use substrait::proto::expression::{
@@ -113,7 +114,7 @@ pub async fn from_window_function(
             window_frame,
             filter: None,
             null_treatment: None,
-            distinct: false,
+            distinct: window.invocation == AggregationInvocation::Distinct as i32,
         },
     }))
 }
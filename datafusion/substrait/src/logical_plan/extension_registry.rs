@@ -0,0 +1,117 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Registry of the encodings of user defined logical nodes in Substrait
+//! extension relations
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use datafusion::common::{Result, not_impl_err};
+use datafusion::execution::registry::SerializerRegistry;
+use datafusion::logical_expr::UserDefinedLogicalNode;
+
+/// Encodes and decodes one kind of [`UserDefinedLogicalNode`] as the `detail`
+/// of a Substrait extension relation.
+///
+/// The encoding only covers the node itself: its inputs are encoded as the
+/// inputs of the extension relation and passed to
+/// [`UserDefinedLogicalNode::with_exprs_and_inputs`] after decoding.
+pub trait LogicalNodeCodec: Debug + Send + Sync {
+    /// The name of the nodes this codec handles, as returned by
+    /// [`UserDefinedLogicalNode::name`]. It is used as the type url of the
+    /// extension relation's `detail`.
+    fn name(&self) -> &str;
+
+    /// Encodes `node` to bytes
+    fn encode(&self, node: &dyn UserDefinedLogicalNode) -> Result<Vec<u8>>;
+
+    /// Decodes a node from bytes produced by [`Self::encode`]
+    fn decode(&self, bytes: &[u8]) -> Result<Arc<dyn UserDefinedLogicalNode>>;
+}
+
+/// A [`SerializerRegistry`] that dispatches the encoding of user defined
+/// logical nodes to the [`LogicalNodeCodec`] registered for their name.
+///
+/// Install it on the session used to produce and consume Substrait plans
+/// with [`SessionStateBuilder::with_serializer_registry`].
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion::execution::session_state::SessionStateBuilder;
+/// # use datafusion_substrait::logical_plan::extension_registry::SubstraitExtensionRegistry;
+/// let registry = SubstraitExtensionRegistry::new();
+/// // register the codecs of the custom nodes with `registry.register(...)`
+/// let state = SessionStateBuilder::new()
+///     .with_default_features()
+///     .with_serializer_registry(Arc::new(registry))
+///     .build();
+/// ```
+///
+/// [`SessionStateBuilder::with_serializer_registry`]: datafusion::execution::session_state::SessionStateBuilder::with_serializer_registry
+#[derive(Debug, Default)]
+pub struct SubstraitExtensionRegistry {
+    codecs: HashMap<String, Arc<dyn LogicalNodeCodec>>,
+}
+
+impl SubstraitExtensionRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `codec` for the nodes named [`LogicalNodeCodec::name`],
+    /// returning the codec it replaces, if any
+    pub fn register(
+        &mut self,
+        codec: Arc<dyn LogicalNodeCodec>,
+    ) -> Option<Arc<dyn LogicalNodeCodec>> {
+        self.codecs.insert(codec.name().to_string(), codec)
+    }
+
+    /// Returns the codec registered for the nodes named `name`
+    pub fn codec(&self, name: &str) -> Option<&Arc<dyn LogicalNodeCodec>> {
+        self.codecs.get(name)
+    }
+
+    fn codec_or_err(&self, name: &str) -> Result<&Arc<dyn LogicalNodeCodec>> {
+        match self.codec(name) {
+            Some(codec) => Ok(codec),
+            None => not_impl_err!(
+                "No Substrait extension encoding registered for logical node {name}"
+            ),
+        }
+    }
+}
+
+impl SerializerRegistry for SubstraitExtensionRegistry {
+    fn serialize_logical_plan(
+        &self,
+        node: &dyn UserDefinedLogicalNode,
+    ) -> Result<Vec<u8>> {
+        self.codec_or_err(node.name())?.encode(node)
+    }
+
+    fn deserialize_logical_plan(
+        &self,
+        name: &str,
+        bytes: &[u8],
+    ) -> Result<Arc<dyn UserDefinedLogicalNode>> {
+        self.codec_or_err(name)?.decode(bytes)
+    }
+}
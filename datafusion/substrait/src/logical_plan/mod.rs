@@ -16,4 +16,5 @@
 // under the License.
 
 pub mod consumer;
+pub mod extension_registry;
 pub mod producer;
@@ -18,8 +18,11 @@
 use crate::logical_plan::producer::SubstraitProducer;
 use crate::logical_plan::producer::utils::substrait_sort_field;
 use datafusion::common::{DFSchemaRef, ScalarValue, not_impl_err};
-use datafusion::logical_expr::expr::{WindowFunction, WindowFunctionParams};
+use datafusion::logical_expr::expr::{
+    NullTreatment, WindowFunction, WindowFunctionParams,
+};
 use datafusion::logical_expr::{WindowFrame, WindowFrameBound, WindowFrameUnits};
+use substrait::proto::AggregationInvocation;
 use substrait::proto::expression::RexType;
 use substrait::proto::expression::WindowFunction as SubstraitWindowFunction;
 use substrait::proto::expression::window_function::bound as SubstraitBound;
@@ -41,11 +44,19 @@ pub fn from_window_function(
                 partition_by,
                 order_by,
                 window_frame,
-                null_treatment: _,
-                distinct: _,
-                filter: _,
+                null_treatment,
+                distinct,
+                filter,
             },
     } = window_fn;
+    if filter.is_some() {
+        return not_impl_err!("Window function with FILTER is not supported: {fun}");
+    }
+    if *null_treatment == Some(NullTreatment::IgnoreNulls) {
+        return not_impl_err!(
+            "Window function with IGNORE NULLS is not supported: {fun}"
+        );
+    }
     // function reference
     let function_anchor = producer.register_function(fun.to_string());
     // arguments
@@ -68,6 +79,10 @@ pub fn from_window_function(
     // window frame
     let bounds = to_substrait_bounds(window_frame)?;
     let bound_type = to_substrait_bound_type(window_frame)?;
+    let invocation = match distinct {
+        true => AggregationInvocation::Distinct,
+        false => AggregationInvocation::All,
+    };
     Ok(make_substrait_window_function(
         function_anchor,
         arguments,
@@ -75,6 +90,7 @@ pub fn from_window_function(
         order_by,
         bounds,
         bound_type,
+        invocation,
     ))
 }
 
@@ -85,6 +101,7 @@ fn make_substrait_window_function(
     sorts: Vec<SortField>,
     bounds: (Bound, Bound),
     bounds_type: BoundsType,
+    invocation: AggregationInvocation,
 ) -> Expression {
     #[expect(deprecated)]
     Expression {
@@ -95,8 +112,8 @@ fn make_substrait_window_function(
             sorts,
             options: vec![],
             output_type: None,
-            phase: 0,      // default to AGGREGATION_PHASE_UNSPECIFIED
-            invocation: 0, // TODO: fix
+            phase: 0, // default to AGGREGATION_PHASE_UNSPECIFIED
+            invocation: invocation as i32,
             lower_bound: Some(bounds.0),
             upper_bound: Some(bounds.1),
             args: vec![],
@@ -120,45 +137,47 @@ fn to_substrait_bounds(
     window_frame: &WindowFrame,
 ) -> datafusion::common::Result<(Bound, Bound)> {
     Ok((
-        to_substrait_bound(&window_frame.start_bound),
-        to_substrait_bound(&window_frame.end_bound),
+        to_substrait_bound(&window_frame.start_bound)?,
+        to_substrait_bound(&window_frame.end_bound)?,
     ))
 }
 
-fn to_substrait_bound(bound: &WindowFrameBound) -> Bound {
-    match bound {
-        WindowFrameBound::CurrentRow => Bound {
-            kind: Some(BoundKind::CurrentRow(SubstraitBound::CurrentRow {})),
-        },
-        WindowFrameBound::Preceding(s) => match to_substrait_bound_offset(s) {
-            Some(offset) => Bound {
-                kind: Some(BoundKind::Preceding(SubstraitBound::Preceding { offset })),
-            },
-            None => Bound {
-                kind: Some(BoundKind::Unbounded(SubstraitBound::Unbounded {})),
-            },
+fn to_substrait_bound(bound: &WindowFrameBound) -> datafusion::common::Result<Bound> {
+    let kind = match bound {
+        WindowFrameBound::CurrentRow => {
+            BoundKind::CurrentRow(SubstraitBound::CurrentRow {})
+        }
+        WindowFrameBound::Preceding(s) if s.is_null() => {
+            BoundKind::Unbounded(SubstraitBound::Unbounded {})
+        }
+        WindowFrameBound::Following(s) if s.is_null() => {
+            BoundKind::Unbounded(SubstraitBound::Unbounded {})
+        }
+        // Substrait offsets must be positive, an offset of zero is the current row
+        WindowFrameBound::Preceding(s) => match to_substrait_bound_offset(s)? {
+            0 => BoundKind::CurrentRow(SubstraitBound::CurrentRow {}),
+            offset => BoundKind::Preceding(SubstraitBound::Preceding { offset }),
         },
-        WindowFrameBound::Following(s) => match to_substrait_bound_offset(s) {
-            Some(offset) => Bound {
-                kind: Some(BoundKind::Following(SubstraitBound::Following { offset })),
-            },
-            None => Bound {
-                kind: Some(BoundKind::Unbounded(SubstraitBound::Unbounded {})),
-            },
+        WindowFrameBound::Following(s) => match to_substrait_bound_offset(s)? {
+            0 => BoundKind::CurrentRow(SubstraitBound::CurrentRow {}),
+            offset => BoundKind::Following(SubstraitBound::Following { offset }),
         },
-    }
+    };
+    Ok(Bound { kind: Some(kind) })
 }
 
-fn to_substrait_bound_offset(value: &ScalarValue) -> Option<i64> {
+fn to_substrait_bound_offset(value: &ScalarValue) -> datafusion::common::Result<i64> {
     match value {
-        ScalarValue::UInt8(Some(v)) => Some(*v as i64),
-        ScalarValue::UInt16(Some(v)) => Some(*v as i64),
-        ScalarValue::UInt32(Some(v)) => Some(*v as i64),
-        ScalarValue::UInt64(Some(v)) => Some(*v as i64),
-        ScalarValue::Int8(Some(v)) => Some(*v as i64),
-        ScalarValue::Int16(Some(v)) => Some(*v as i64),
-        ScalarValue::Int32(Some(v)) => Some(*v as i64),
-        ScalarValue::Int64(Some(v)) => Some(*v),
-        _ => None,
+        ScalarValue::UInt8(Some(v)) => Ok(*v as i64),
+        ScalarValue::UInt16(Some(v)) => Ok(*v as i64),
+        ScalarValue::UInt32(Some(v)) => Ok(*v as i64),
+        ScalarValue::UInt64(Some(v)) => i64::try_from(*v)
+            .or_else(|_| not_impl_err!("Unsupported window frame offset: {value:?}")),
+        ScalarValue::Int8(Some(v)) => Ok(*v as i64),
+        ScalarValue::Int16(Some(v)) => Ok(*v as i64),
+        ScalarValue::Int32(Some(v)) => Ok(*v as i64),
+        ScalarValue::Int64(Some(v)) => Ok(*v),
+        // Substrait only supports integer offsets
+        _ => not_impl_err!("Unsupported window frame offset: {value:?}"),
     }
 }
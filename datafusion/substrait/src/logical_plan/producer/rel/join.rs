@@ -36,6 +36,9 @@ pub fn from_join(
         JoinConstraint::On => {}
         JoinConstraint::Using => return not_impl_err!("join constraint: `using`"),
     }
+    if join.asof_match.is_some() {
+        return not_impl_err!("ASOF join");
+    }
     let in_join_schema = Arc::new(join.left.schema().join(join.right.schema())?);
 
     // convert filter if present
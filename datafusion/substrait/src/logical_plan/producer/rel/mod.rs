@@ -74,5 +74,8 @@ pub fn to_substrait_rel(
         LogicalPlan::RecursiveQuery(plan) => {
             not_impl_err!("Unsupported plan type: {plan:?}")?
         }
+        LogicalPlan::LateralTableFunction(plan) => {
+            not_impl_err!("Unsupported plan type: {plan:?}")?
+        }
     }
 }
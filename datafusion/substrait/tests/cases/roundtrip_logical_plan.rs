@@ -21,6 +21,9 @@ use datafusion::functions_nested::map::map;
 use datafusion::logical_expr::LogicalPlanBuilder;
 use datafusion::physical_plan::Accumulator;
 use datafusion::scalar::ScalarValue;
+use datafusion_substrait::logical_plan::extension_registry::{
+    LogicalNodeCodec, SubstraitExtensionRegistry,
+};
 use datafusion_substrait::logical_plan::{
     consumer::from_substrait_plan, producer::to_substrait_plan,
 };
@@ -1358,6 +1361,36 @@ async fn window_with_rows() -> Result<()> {
     roundtrip("SELECT sum(b) OVER (PARTITION BY a ROWS BETWEEN 4 PRECEDING AND 2 PRECEDING) FROM data;").await
 }
 
+#[tokio::test]
+async fn window_with_range() -> Result<()> {
+    roundtrip("SELECT sum(b) OVER (PARTITION BY d ORDER BY a RANGE BETWEEN 2 PRECEDING AND 2 FOLLOWING) FROM data;").await?;
+    roundtrip("SELECT sum(b) OVER (PARTITION BY d ORDER BY a RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) FROM data;").await
+}
+
+#[tokio::test]
+async fn window_with_distinct() -> Result<()> {
+    roundtrip("SELECT count(DISTINCT b) OVER (PARTITION BY a) FROM data;").await
+}
+
+#[tokio::test]
+async fn window_with_unsupported_frame() -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx
+        .sql("SELECT count(a) OVER (ORDER BY c RANGE BETWEEN INTERVAL '1 day' PRECEDING AND CURRENT ROW) FROM data")
+        .await?
+        .into_optimized_plan()?;
+    let err = to_substrait_plan(&plan, &ctx.state()).unwrap_err();
+    assert!(err.to_string().contains("Unsupported window frame offset"));
+
+    let plan = ctx
+        .sql("SELECT first_value(a) IGNORE NULLS OVER (ORDER BY b) FROM data")
+        .await?
+        .into_optimized_plan()?;
+    let err = to_substrait_plan(&plan, &ctx.state()).unwrap_err();
+    assert!(err.to_string().contains("IGNORE NULLS is not supported"));
+    Ok(())
+}
+
 #[tokio::test]
 async fn qualified_schema_table_reference() -> Result<()> {
     roundtrip("SELECT * FROM public.data;").await
@@ -1614,6 +1647,67 @@ async fn new_test_grammar() -> Result<()> {
     .await
 }
 
+#[derive(Debug)]
+struct MockLogicalNodeCodec;
+
+impl LogicalNodeCodec for MockLogicalNodeCodec {
+    fn name(&self) -> &str {
+        "MockUserDefinedLogicalPlan"
+    }
+
+    fn encode(&self, node: &dyn UserDefinedLogicalNode) -> Result<Vec<u8>> {
+        node.as_any()
+            .downcast_ref::<MockUserDefinedLogicalPlan>()
+            .unwrap()
+            .serialize()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Arc<dyn UserDefinedLogicalNode>> {
+        MockUserDefinedLogicalPlan::deserialize(bytes)
+    }
+}
+
+#[tokio::test]
+async fn extension_logical_plan_with_extension_registry() -> Result<()> {
+    let mut registry = SubstraitExtensionRegistry::new();
+    let state = SessionStateBuilder::new()
+        .with_default_features()
+        .with_serializer_registry(Arc::new(SubstraitExtensionRegistry::new()))
+        .build();
+    let input = LogicalPlanBuilder::empty(false).build()?;
+    let ext_plan = LogicalPlan::Extension(Extension {
+        node: Arc::new(MockUserDefinedLogicalPlan {
+            validation_bytes: b"validation".to_vec(),
+            inputs: vec![input],
+            empty_schema: Arc::new(DFSchema::empty()),
+        }),
+    });
+
+    // nodes without a registered codec can not be encoded
+    let err = to_substrait_plan(&ext_plan, &state).unwrap_err();
+    assert!(err.to_string().contains(
+        "No Substrait extension encoding registered for logical node MockUserDefinedLogicalPlan"
+    ));
+
+    assert!(registry.register(Arc::new(MockLogicalNodeCodec)).is_none());
+    let state = SessionStateBuilder::new_from_existing(state)
+        .with_serializer_registry(Arc::new(registry))
+        .build();
+    let proto = to_substrait_plan(&ext_plan, &state)?;
+    assert!(matches!(
+        proto.relations[0].rel_type.as_ref().unwrap(),
+        plan_rel::RelType::Root(root)
+            if matches!(
+                root.input.as_ref().unwrap().rel_type,
+                Some(RelType::ExtensionSingle(_))
+            )
+    ));
+    let plan2 = from_substrait_plan(&state, &proto).await?;
+    assert_eq!(format!("{ext_plan}"), format!("{plan2}"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn extension_logical_plan() -> Result<()> {
     let ctx = create_context().await?;
@@ -313,4 +313,53 @@ mod test {
              +----+-------+"
         );
     }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn test_in_memory_files_and_clock() {
+        use chrono::DateTime;
+        use datafusion::execution::SessionStateBuilder;
+        use datafusion_common::clock::FixedClock;
+        use datafusion_execution::object_store::ObjectStoreUrl;
+
+        let clock = FixedClock(DateTime::from_timestamp(0, 0).unwrap());
+        let state = SessionStateBuilder::new()
+            .with_default_features()
+            .with_clock(Arc::new(clock))
+            .build();
+        let ctx = SessionContext::new_with_state(state);
+
+        // `file://` paths are served from memory
+        let store = ctx
+            .runtime_env()
+            .object_store(ObjectStoreUrl::local_filesystem())
+            .unwrap();
+        store
+            .put(
+                &Path::from("data.csv"),
+                PutPayload::from_static(b"id,value\n1,a\n"),
+            )
+            .await
+            .unwrap();
+        ctx.register_csv("t", "file:///data.csv", CsvReadOptions::new())
+            .await
+            .unwrap();
+
+        let result = ctx
+            .sql("SELECT id, value, current_date() AS today FROM t")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            batches_to_string(&result),
+            "+----+-------+------------+\n\
+             | id | value | today      |\n\
+             +----+-------+------------+\n\
+             | 1  | a     | 1970-01-01 |\n\
+             +----+-------+------------+"
+        );
+    }
 }